@@ -8,15 +8,17 @@ use crate::{
     failpoint::fail_point_poem,
     page::determine_limit,
     response::{
-        account_not_found, resource_not_found, struct_field_not_found, BadRequestError,
-        BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404, InternalError,
+        account_not_found, check_field_selection, resource_not_found, struct_field_not_found,
+        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
+        InternalError,
     },
     ApiTags,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    AccountData, Address, AptosErrorCode, AsConverter, LedgerInfo, MoveModuleBytecode,
-    MoveModuleId, MoveResource, MoveStructTag, StateKeyWrapper, U64,
+    AccountData, Address, AptosErrorCode, AsConverter, FieldSelection, LedgerInfo,
+    LedgerVersionPin, MoveModuleBytecode, MoveModuleId, MoveResource, MoveStructTag, Projected,
+    StateKeyWrapper, U64,
 };
 use aptos_types::{
     access_path::AccessPath,
@@ -62,18 +64,29 @@ impl AccountsApi {
         ///
         /// If not provided, it will be the latest version
         ledger_version: Query<Option<U64>>,
-    ) -> BasicResultWith404<AccountData> {
+        /// Ledger version pin to get state at
+        ///
+        /// If provided, the state is read at the pinned ledger version, see
+        /// `get_ledger_version_pin`. It can't be combined with a different
+        /// `ledger_version`.
+        ledger_version_pin: Query<Option<LedgerVersionPin>>,
+        /// Comma separated list of fields to include in the response
+        ///
+        /// For example, `sequence_number` returns only the sequence number of the account.
+        /// If not provided, all fields are returned. Only supported for JSON output.
+        fields: Query<Option<FieldSelection>>,
+    ) -> BasicResultWith404<Projected<AccountData>> {
         fail_point_poem("endpoint_get_account")?;
         self.context
             .check_api_output_enabled("Get account", &accept_type)?;
-        let account = Account::new(
-            self.context.clone(),
-            address.0,
-            ledger_version.0,
-            None,
-            None,
-        )?;
-        account.account(&accept_type)
+        check_field_selection("Get account", &accept_type, &fields.0)?;
+        let ledger_version = self
+            .context
+            .resolve_ledger_version_pin(ledger_version.0, ledger_version_pin.0)?;
+        let account = Account::new(self.context.clone(), address.0, ledger_version, None, None)?;
+        account
+            .account(&accept_type)
+            .map(|response| response.map_json(|account| Projected::new(account, fields.0)))
     }
 
     /// Get account resources
@@ -98,6 +111,12 @@ impl AccountsApi {
         ///
         /// If not provided, it will be the latest version
         ledger_version: Query<Option<U64>>,
+        /// Ledger version pin to get state at
+        ///
+        /// If provided, the state is read at the pinned ledger version, see
+        /// `get_ledger_version_pin`. It can't be combined with a different
+        /// `ledger_version`.
+        ledger_version_pin: Query<Option<LedgerVersionPin>>,
         /// Cursor specifying where to start for pagination
         ///
         /// This cursor cannot be derived manually client-side. Instead, you must
@@ -109,18 +128,29 @@ impl AccountsApi {
         ///
         /// If not provided, defaults to default page size.
         limit: Query<Option<u16>>,
-    ) -> BasicResultWith404<Vec<MoveResource>> {
+        /// Comma separated list of fields to include in the response
+        ///
+        /// Nested fields are separated by dots, e.g. `type,data.coin.value`.
+        /// If not provided, all fields are returned. Only supported for JSON output.
+        fields: Query<Option<FieldSelection>>,
+    ) -> BasicResultWith404<Projected<Vec<MoveResource>>> {
         fail_point_poem("endpoint_get_account_resources")?;
         self.context
             .check_api_output_enabled("Get account resources", &accept_type)?;
+        check_field_selection("Get account resources", &accept_type, &fields.0)?;
+        let ledger_version = self
+            .context
+            .resolve_ledger_version_pin(ledger_version.0, ledger_version_pin.0)?;
         let account = Account::new(
             self.context.clone(),
             address.0,
-            ledger_version.0,
+            ledger_version,
             start.0.map(StateKey::from),
             limit.0,
         )?;
-        account.resources(&accept_type)
+        account
+            .resources(&accept_type)
+            .map(|response| response.map_json(|resources| Projected::new(resources, fields.0)))
     }
 
     /// Get account modules
@@ -145,6 +175,12 @@ impl AccountsApi {
         ///
         /// If not provided, it will be the latest version
         ledger_version: Query<Option<U64>>,
+        /// Ledger version pin to get state at
+        ///
+        /// If provided, the state is read at the pinned ledger version, see
+        /// `get_ledger_version_pin`. It can't be combined with a different
+        /// `ledger_version`.
+        ledger_version_pin: Query<Option<LedgerVersionPin>>,
         /// Cursor specifying where to start for pagination
         ///
         /// This cursor cannot be derived manually client-side. Instead, you must
@@ -156,18 +192,29 @@ impl AccountsApi {
         ///
         /// If not provided, defaults to default page size.
         limit: Query<Option<u16>>,
-    ) -> BasicResultWith404<Vec<MoveModuleBytecode>> {
+        /// Comma separated list of fields to include in the response
+        ///
+        /// Nested fields are separated by dots, e.g. `abi.name,abi.exposed_functions`.
+        /// If not provided, all fields are returned. Only supported for JSON output.
+        fields: Query<Option<FieldSelection>>,
+    ) -> BasicResultWith404<Projected<Vec<MoveModuleBytecode>>> {
         fail_point_poem("endpoint_get_account_modules")?;
         self.context
             .check_api_output_enabled("Get account modules", &accept_type)?;
+        check_field_selection("Get account modules", &accept_type, &fields.0)?;
+        let ledger_version = self
+            .context
+            .resolve_ledger_version_pin(ledger_version.0, ledger_version_pin.0)?;
         let account = Account::new(
             self.context.clone(),
             address.0,
-            ledger_version.0,
+            ledger_version,
             start.0.map(StateKey::from),
             limit.0,
         )?;
-        account.modules(&accept_type)
+        account
+            .modules(&accept_type)
+            .map(|response| response.map_json(|modules| Projected::new(modules, fields.0)))
     }
 }
 
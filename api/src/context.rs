@@ -4,24 +4,33 @@
 
 use crate::{
     accept_type::AcceptType,
+    gas_station::GasStation,
     response::{
         bcs_api_disabled, block_not_found_by_height, block_not_found_by_version,
-        block_pruned_by_height, json_api_disabled, version_not_found, version_pruned,
-        ForbiddenError, InternalError, NotFoundError, ServiceUnavailableError, StdApiError,
+        block_pruned_by_height, json_api_disabled, pinned_version_pruned, version_not_found,
+        version_pruned, BadRequestError, ForbiddenError, InternalError, NotFoundError,
+        ServiceUnavailableError, StdApiError,
     },
 };
 use anyhow::{bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_api_types::{
-    AptosErrorCode, AsConverter, BcsBlock, GasEstimation, LedgerInfo, ResourceGroup,
-    TransactionOnChainData,
+    AptosErrorCode, AsConverter, BcsBlock, GasEstimatePercentiles, GasEstimation,
+    IndexedTableItemsWithProof, LedgerInfo, LedgerVersionPin, ResourceGroup, StateValueWithProof,
+    TransactionOnChainData, TransactionWithLedgerInfo, U64,
 };
 use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::error;
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_mempool::{
+    AccountSequenceNumberDiagnostics, MempoolClientRequest, MempoolClientSender,
+    MempoolTransactionEvent, SubmissionOptions, SubmissionStatus, TransactionFilter,
+};
 use aptos_state_view::TStateView;
 use aptos_storage_interface::{
+    async_db_reader::{
+        AsyncDbReader, DEFAULT_ASYNC_READ_TIMEOUT, DEFAULT_NUM_ASYNC_READER_THREADS,
+    },
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
     DbReader, Order, MAX_REQUEST_LIMIT,
 };
@@ -36,10 +45,12 @@ use aptos_types::{
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     on_chain_config::{GasSchedule, GasScheduleV2, OnChainConfig, OnChainExecutionConfig},
+    proof::TransactionInfoWithProof,
     state_store::{
         state_key::{StateKey, StateKeyInner},
         state_key_prefix::StateKeyPrefix,
         state_value::StateValue,
+        table::TableHandle,
     },
     transaction::{SignedTransaction, TransactionWithProof, Version},
 };
@@ -52,20 +63,27 @@ use move_core_types::language_storage::{ModuleId, StructTag};
 use std::{
     collections::{BTreeMap, HashMap},
     ops::{Bound::Included, Deref},
-    sync::{Arc, RwLock, RwLockWriteGuard},
+    sync::{atomic::AtomicUsize, Arc, RwLock, RwLockWriteGuard},
     time::Instant,
 };
+use tokio::sync::{broadcast, watch, OwnedSemaphorePermit, Semaphore};
 
 // Context holds application scope context
 #[derive(Clone)]
 pub struct Context {
     chain_id: ChainId,
     pub db: Arc<dyn DbReader>,
+    async_db: AsyncDbReader,
     mp_sender: MempoolClientSender,
     pub node_config: NodeConfig,
     gas_schedule_cache: Arc<RwLock<GasScheduleCache>>,
     gas_estimation_cache: Arc<RwLock<GasEstimationCache>>,
     gas_limit_cache: Arc<RwLock<GasLimitCache>>,
+    commit_notifications: Option<watch::Receiver<u64>>,
+    stream_connections: Arc<AtomicUsize>,
+    submit_waiters: Arc<Semaphore>,
+    gas_station: Option<Arc<dyn GasStation>>,
+    transaction_filter: Option<TransactionFilter>,
 }
 
 impl std::fmt::Debug for Context {
@@ -81,9 +99,16 @@ impl Context {
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
     ) -> Self {
+        let submit_waiters = Arc::new(Semaphore::new(node_config.api.max_submit_waiters));
+        let async_db = AsyncDbReader::new(
+            db.clone(),
+            DEFAULT_NUM_ASYNC_READER_THREADS,
+            DEFAULT_ASYNC_READ_TIMEOUT,
+        );
         Self {
             chain_id,
             db,
+            async_db,
             mp_sender,
             node_config,
             gas_schedule_cache: Arc::new(RwLock::new(GasScheduleCache {
@@ -100,9 +125,65 @@ impl Context {
                 last_updated_epoch: None,
                 block_gas_limit: None,
             })),
+            commit_notifications: None,
+            stream_connections: Arc::new(AtomicUsize::new(0)),
+            submit_waiters,
+            gas_station: None,
+            transaction_filter: None,
         }
     }
 
+    /// Attaches the storage commit notifications (i.e., the highest synced
+    /// version) to the context. These are used to drive the streaming endpoint.
+    pub fn with_commit_notifications(mut self, commit_notifications: watch::Receiver<u64>) -> Self {
+        self.commit_notifications = Some(commit_notifications);
+        self
+    }
+
+    /// Attaches the gas station that approves the sponsorship of simulated transactions
+    pub fn with_gas_station(mut self, gas_station: Arc<dyn GasStation>) -> Self {
+        self.gas_station = Some(gas_station);
+        self
+    }
+
+    /// Returns the gas station that approves sponsorships (if any)
+    pub fn gas_station(&self) -> Option<Arc<dyn GasStation>> {
+        self.gas_station.clone()
+    }
+
+    /// Attaches the transaction filter that rejects submissions matching the filter policy
+    pub fn with_transaction_filter(mut self, transaction_filter: TransactionFilter) -> Self {
+        self.transaction_filter = Some(transaction_filter);
+        self
+    }
+
+    /// Returns the transaction filter (if any)
+    pub fn transaction_filter(&self) -> Option<&TransactionFilter> {
+        self.transaction_filter.as_ref()
+    }
+
+    /// Returns the reader to use for storage reads from async code (e.g., long running
+    /// handlers), so that they never block the API runtime
+    pub fn async_db(&self) -> &AsyncDbReader {
+        &self.async_db
+    }
+
+    /// Returns a new subscription to the storage commit notifications (if any)
+    pub fn subscribe_to_commits(&self) -> Option<watch::Receiver<u64>> {
+        self.commit_notifications.clone()
+    }
+
+    /// Returns the number of currently open stream connections
+    pub fn stream_connections(&self) -> &Arc<AtomicUsize> {
+        &self.stream_connections
+    }
+
+    /// Reserves one of the slots of the transaction submissions waiting for a commit, if any
+    /// is left. The slot is released when the returned permit is dropped.
+    pub fn try_acquire_submit_waiter(&self) -> Option<OwnedSemaphorePermit> {
+        self.submit_waiters.clone().try_acquire_owned().ok()
+    }
+
     pub fn max_transactions_page_size(&self) -> u16 {
         self.node_config.api.max_transactions_page_size
     }
@@ -119,6 +200,10 @@ impl Context {
         self.node_config.api.max_account_modules_page_size
     }
 
+    pub fn max_table_items_page_size(&self) -> u16 {
+        self.node_config.api.max_table_items_page_size
+    }
+
     pub fn latest_state_view(&self) -> Result<DbStateView> {
         self.db.latest_state_checkpoint_view()
     }
@@ -173,6 +258,14 @@ impl Context {
         self.node_config.api.max_submit_transaction_batch_size
     }
 
+    pub fn max_view_function_batch_size(&self) -> usize {
+        self.node_config.api.max_view_function_batch_size
+    }
+
+    pub fn max_simulation_state_overrides(&self) -> usize {
+        self.node_config.api.max_simulation_state_overrides
+    }
+
     pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<SubmissionStatus> {
         let (req_sender, callback) = oneshot::channel();
         self.mp_sender
@@ -183,6 +276,23 @@ impl Context {
         callback.await?
     }
 
+    /// Submits the transaction with the client selected TTL tier and expiration behavior
+    pub async fn submit_transaction_with_options(
+        &self,
+        txn: SignedTransaction,
+        options: SubmissionOptions,
+    ) -> Result<SubmissionStatus> {
+        let (req_sender, callback) = oneshot::channel();
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::SubmitTransactionWithOptions(
+                txn, options, req_sender,
+            ))
+            .await?;
+
+        callback.await?
+    }
+
     // For use from external crates where they don't want to handle
     // the API response error types.
     pub fn get_latest_ledger_info_wrapped(&self) -> anyhow::Result<LedgerInfo> {
@@ -253,6 +363,67 @@ impl Context {
         Ok((latest_ledger_info, requested_ledger_version))
     }
 
+    /// Returns a pin of the requested ledger version (or of the latest one, if no version
+    /// is requested), so that later reads can all be served at the same version.
+    pub fn pin_ledger_version<E: StdApiError>(
+        &self,
+        requested_ledger_version: Option<Version>,
+    ) -> Result<(LedgerInfo, LedgerVersionPin), E> {
+        let (latest_ledger_info, ledger_version) =
+            self.get_latest_ledger_info_and_verify_lookup_version(requested_ledger_version)?;
+        let pin = LedgerVersionPin::new(self.chain_id().id(), ledger_version);
+        Ok((latest_ledger_info, pin))
+    }
+
+    /// Resolves the ledger version to read at, given the requested ledger version and pin (if
+    /// any). A pinned version that has since been pruned is rejected with its own error code,
+    /// so that clients know to take a new pin (instead of retrying the same reads).
+    pub fn resolve_ledger_version_pin<E: BadRequestError + StdApiError>(
+        &self,
+        requested_ledger_version: Option<U64>,
+        pin: Option<LedgerVersionPin>,
+    ) -> Result<Option<U64>, E> {
+        let pin = match pin {
+            Some(pin) => pin,
+            None => return Ok(requested_ledger_version),
+        };
+
+        let latest_ledger_info = self.get_latest_ledger_info()?;
+        if pin.chain_id != self.chain_id().id() {
+            return Err(E::bad_request_with_code(
+                format!(
+                    "The ledger version pin is for chain {}, but this node is on chain {}",
+                    pin.chain_id,
+                    self.chain_id().id()
+                ),
+                AptosErrorCode::InvalidInput,
+                &latest_ledger_info,
+            ));
+        }
+        if let Some(requested_ledger_version) = requested_ledger_version {
+            if requested_ledger_version.0 != pin.ledger_version {
+                return Err(E::bad_request_with_code(
+                    format!(
+                        "The requested ledger version ({}) doesn't match the pinned ledger version ({})",
+                        requested_ledger_version, pin.ledger_version
+                    ),
+                    AptosErrorCode::InvalidInput,
+                    &latest_ledger_info,
+                ));
+            }
+        }
+        if pin.ledger_version > latest_ledger_info.version() {
+            return Err(version_not_found(pin.ledger_version, &latest_ledger_info));
+        } else if pin.ledger_version < latest_ledger_info.oldest_ledger_version.0 {
+            return Err(pinned_version_pruned(
+                pin.ledger_version,
+                &latest_ledger_info,
+            ));
+        }
+
+        Ok(Some(pin.ledger_version.into()))
+    }
+
     pub fn get_latest_ledger_info_with_signatures(&self) -> Result<LedgerInfoWithSignatures> {
         self.db.get_latest_ledger_info()
     }
@@ -301,11 +472,15 @@ impl Context {
         version: u64,
         limit: u64,
     ) -> Result<(Vec<(StructTag, Vec<u8>)>, Option<StateKey>)> {
-        let account_iter = self.db.get_prefixed_state_value_iterator(
-            &StateKeyPrefix::from(address),
-            prev_state_key,
-            version,
-        )?;
+        let account_iter =
+            match self.get_indexed_resource_iterator(address, prev_state_key, version)? {
+                Some(iter) => iter,
+                None => self.db.get_prefixed_state_value_iterator(
+                    &StateKeyPrefix::from(address),
+                    prev_state_key,
+                    version,
+                )?,
+            };
         // TODO: Consider rewriting this to consider resource groups:
         // * If a resource group is found, expand
         // * Return Option<Result<(PathType, StructTag, Vec<u8>)>>
@@ -376,6 +551,61 @@ impl Context {
         Ok((kvs, next_key))
     }
 
+    /// Returns an iterator over the resources (and resource groups) of the account, using the
+    /// resource types tracked by the internal indexer (so the account's state isn't scanned).
+    /// The resources are returned in the same order as by the prefixed state value iterator,
+    /// so the pagination cursors of both are interchangeable. Returns None if the indexer
+    /// doesn't track the resource types at `version`.
+    fn get_indexed_resource_iterator(
+        &self,
+        address: AccountAddress,
+        prev_state_key: Option<&StateKey>,
+        version: u64,
+    ) -> Result<Option<Box<dyn Iterator<Item = Result<(StateKey, StateValue)>> + '_>>> {
+        if !self.db.indexer_enabled() {
+            return Ok(None);
+        }
+        let struct_tags = match self.db.get_account_resource_tags(address, version)? {
+            Some(struct_tags) => struct_tags,
+            None => return Ok(None),
+        };
+
+        let state_view = self.state_view_at_version(version)?;
+        let resolver = state_view.as_move_resolver();
+        let mut state_keys = struct_tags
+            .into_iter()
+            .map(|struct_tag| {
+                let path = if resolver.is_resource_group(&struct_tag) {
+                    AccessPath::resource_group_path_vec(struct_tag)
+                } else {
+                    AccessPath::resource_path_vec(struct_tag)?
+                };
+                let state_key = StateKey::access_path(AccessPath::new(address, path));
+                Ok((state_key.encode()?, state_key))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // The prefixed state value iterator is ordered by the encoded state keys (and
+        // starts at the cursor)
+        state_keys
+            .sort_by(|(encoded_key, _), (other_encoded_key, _)| encoded_key.cmp(other_encoded_key));
+        if let Some(prev_state_key) = prev_state_key {
+            let encoded_prev_key = prev_state_key.encode()?;
+            state_keys.retain(|(encoded_key, _)| *encoded_key >= encoded_prev_key);
+        }
+
+        Ok(Some(Box::new(state_keys.into_iter().map(
+            move |(_, state_key)| match self.db.get_state_value_by_version(&state_key, version)? {
+                Some(state_value) => Ok((state_key, state_value)),
+                None => bail!(
+                    "The indexed resource {:?} doesn't exist at version {}",
+                    state_key,
+                    version
+                ),
+            },
+        ))))
+    }
+
     pub fn get_modules_by_pagination(
         &self,
         address: AccountAddress,
@@ -751,6 +981,53 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    /// Returns the mempool's view of the sequence numbers of the account
+    pub async fn get_mempool_sequence_number_diagnostics(
+        &self,
+        address: AccountAddress,
+    ) -> Result<AccountSequenceNumberDiagnostics> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetSequenceNumberDiagnostics(
+                address, req_sender,
+            ))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
+    /// Returns true iff the transaction expired in mempool, and was submitted with the notify
+    /// expiration behavior
+    pub async fn is_transaction_expired(&self, hash: HashValue) -> Result<bool> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::IsTransactionExpired(hash, req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
+    /// Subscribes to the transaction insert, commit and eviction events of mempool
+    pub async fn subscribe_mempool_transaction_events(
+        &self,
+    ) -> Result<broadcast::Receiver<MempoolTransactionEvent>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::SubscribeTransactionEvents(req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
@@ -763,6 +1040,111 @@ impl Context {
         )?)
     }
 
+    /// Returns the transaction at the given version, and the proof of it against
+    /// the latest signed ledger info (e.g., for light clients)
+    pub fn get_transaction_with_ledger_info(
+        &self,
+        version: u64,
+    ) -> Result<TransactionWithLedgerInfo> {
+        let ledger_info_with_signatures = self.get_latest_ledger_info_with_signatures()?;
+        let ledger_version = ledger_info_with_signatures.ledger_info().version();
+        ensure!(
+            version <= ledger_version,
+            "Version {} is ahead of the latest ledger version {}",
+            version,
+            ledger_version
+        );
+
+        let transaction_with_proof =
+            self.db
+                .get_transaction_by_version(version, ledger_version, false)?;
+        Ok(TransactionWithLedgerInfo {
+            transaction_with_proof,
+            ledger_info_with_signatures,
+        })
+    }
+
+    /// Returns the state value at the latest state checkpoint, and the proofs
+    /// of it against the latest signed ledger info (e.g., for light clients)
+    pub fn get_state_value_with_proof(&self, state_key: StateKey) -> Result<StateValueWithProof> {
+        let (version, transaction_info_with_proof, ledger_info_with_signatures) =
+            self.get_latest_state_checkpoint_with_proof()?;
+        let (state_value, state_proof) = self
+            .db
+            .get_state_value_with_proof_by_version(&state_key, version)?;
+        Ok(StateValueWithProof {
+            version,
+            state_key,
+            state_value,
+            state_proof,
+            transaction_info_with_proof,
+            ledger_info_with_signatures,
+        })
+    }
+
+    /// Reads (at most `limit`) items of a table keyed by contiguous indices, starting
+    /// at `start`, with a single proof of all of them. The items are read in one batch,
+    /// and the page stops after the first missing index.
+    pub fn get_indexed_table_items_with_proof(
+        &self,
+        table_handle: TableHandle,
+        start: u64,
+        limit: u16,
+    ) -> Result<IndexedTableItemsWithProof> {
+        let (version, transaction_info_with_proof, ledger_info_with_signatures) =
+            self.get_latest_state_checkpoint_with_proof()?;
+        let mut state_keys: Vec<_> = (start..start.saturating_add(limit as u64))
+            .map(|index| table_handle.indexed_item_state_key(index))
+            .collect();
+        let mut items = self.db.get_state_values_by_version(&state_keys, version)?;
+        if let Some(num_existing) = items.iter().position(Option::is_none) {
+            // Only the first missing item is proved
+            items.truncate(num_existing + 1);
+            state_keys.truncate(num_existing + 1);
+        }
+        let proof = self
+            .db
+            .get_state_batch_proof_by_version(&state_keys, version)?;
+        Ok(IndexedTableItemsWithProof {
+            version,
+            table_handle,
+            start,
+            items,
+            proof,
+            transaction_info_with_proof,
+            ledger_info_with_signatures,
+        })
+    }
+
+    /// Returns the latest state checkpoint version, the proof of its transaction
+    /// info and the latest ledger info (and signatures) the proof is against.
+    fn get_latest_state_checkpoint_with_proof(
+        &self,
+    ) -> Result<(Version, TransactionInfoWithProof, LedgerInfoWithSignatures)> {
+        let ledger_info_with_signatures = self.get_latest_ledger_info_with_signatures()?;
+        let ledger_version = ledger_info_with_signatures.ledger_info().version();
+        let version = self
+            .db
+            .get_latest_state_checkpoint_version()?
+            .ok_or_else(|| format_err!("No state checkpoint found in the DB"))?;
+        ensure!(
+            version <= ledger_version,
+            "The latest state checkpoint {} is ahead of the latest ledger version {}",
+            version,
+            ledger_version
+        );
+
+        let transaction_info_with_proof = self
+            .db
+            .get_transaction_by_version(version, ledger_version, false)?
+            .proof;
+        Ok((
+            version,
+            transaction_info_with_proof,
+            ledger_info_with_signatures,
+        ))
+    }
+
     pub fn get_accumulator_root_hash(&self, version: u64) -> Result<HashValue> {
         self.db.get_accumulator_root_hash(version)
     }
@@ -828,6 +1210,8 @@ impl Context {
         GasEstimation {
             deprioritized_gas_estimate: Some(min_gas_unit_price),
             gas_estimate: min_gas_unit_price,
+            gas_estimate_percentiles: None,
+            max_gas_amount_estimate: None,
             prioritized_gas_estimate: Some(self.next_bucket(min_gas_unit_price)),
         }
     }
@@ -906,6 +1290,8 @@ impl Context {
             return Ok(GasEstimation {
                 deprioritized_gas_estimate: Some(static_override.low),
                 gas_estimate: static_override.market,
+                gas_estimate_percentiles: None,
+                max_gas_amount_estimate: None,
                 prioritized_gas_estimate: Some(static_override.aggressive),
             });
         }
@@ -932,7 +1318,9 @@ impl Context {
             }
         }
 
-        let max_block_history = config.aggressive_block_history;
+        let max_block_history = config
+            .aggressive_block_history
+            .max(config.percentile_block_history);
         // 1. Get the block metadata txns
         let mut lookup_version = ledger_info.ledger_version.0;
         let mut blocks = vec![];
@@ -1056,7 +1444,30 @@ impl Context {
             Some(price) => low_price.max(*price),
         };
 
-        // (3) aggressive
+        // (3) percentiles
+        let mut percentile_prices: Vec<_> = min_inclusion_prices
+            .iter()
+            .take(config.percentile_block_history)
+            .cloned()
+            .collect();
+        percentile_prices.sort();
+        let percentile = |p: usize| {
+            percentile_prices
+                .get(percentile_prices.len() * p / 100)
+                .copied()
+                .unwrap_or(market_price)
+        };
+        let gas_estimate_percentiles = GasEstimatePercentiles {
+            p25: percentile(25),
+            p50: percentile(50),
+            p90: percentile(90),
+        };
+
+        // (4) aggressive
+        let mut min_inclusion_prices: Vec<_> = min_inclusion_prices
+            .into_iter()
+            .take(config.aggressive_block_history)
+            .collect();
         min_inclusion_prices.sort();
         let p90_price = match min_inclusion_prices.get(min_inclusion_prices.len() * 9 / 10) {
             None => {
@@ -1077,6 +1488,8 @@ impl Context {
         let estimation = GasEstimation {
             deprioritized_gas_estimate: Some(low_price),
             gas_estimate: market_price,
+            gas_estimate_percentiles: Some(gas_estimate_percentiles),
+            max_gas_amount_estimate: None,
             prioritized_gas_estimate: Some(aggressive_price),
         };
         // 4. Update cache
@@ -1233,6 +1646,35 @@ impl Context {
         Ok(())
     }
 
+    /// Invalidates the caches that were populated in an epoch other than the given one.
+    /// This is called on every epoch change, so that stale gas schedules, estimations and
+    /// block gas limits are dropped as soon as the epoch changes (and not on the next read).
+    pub fn invalidate_caches_for_epoch(&self, epoch: u64) {
+        {
+            let mut cache = self.gas_schedule_cache.write().unwrap();
+            if cache.last_updated_epoch != Some(epoch) {
+                cache.last_updated_epoch = None;
+                cache.gas_schedule_params = None;
+            }
+        }
+        {
+            let mut cache = self.gas_estimation_cache.write().unwrap();
+            if cache.last_updated_epoch != Some(epoch) {
+                cache.last_updated_epoch = None;
+                cache.last_updated_time = None;
+                cache.estimation = None;
+                cache.min_inclusion_prices.clear();
+            }
+        }
+        {
+            let mut cache = self.gas_limit_cache.write().unwrap();
+            if cache.last_updated_epoch != Some(epoch) {
+                cache.last_updated_epoch = None;
+                cache.block_gas_limit = None;
+            }
+        }
+    }
+
     pub fn last_updated_gas_schedule(&self) -> Option<u64> {
         self.gas_schedule_cache.read().unwrap().last_updated_epoch
     }
@@ -17,7 +17,7 @@ use crate::{
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     verify_field_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
-    MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
+    MoveStructTag, PageCursor, VerifyInputWithRecursion, VersionedEvent, U64,
 };
 use aptos_types::event::EventKey;
 use aptos_vm::data_cache::AsMoveResolver;
@@ -64,6 +64,12 @@ impl EventsApi {
         ///
         /// If unspecified, defaults to default page size
         limit: Query<Option<u16>>,
+        /// Cursor to the next page of events
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// use the cursor returned in the X-Aptos-Cursor header of a previous
+        /// response. It can't be combined with `start`.
+        cursor: Query<Option<PageCursor>>,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         fail_point_poem("endpoint_get_events_by_event_key")?;
         self.context
@@ -72,7 +78,8 @@ impl EventsApi {
             start.0.map(|v| v.0),
             limit.0,
             self.context.max_events_page_size(),
-        );
+        )
+        .with_cursor(cursor.0);
 
         // Ensure that account exists
         let account = Account::new(self.context.clone(), address.0, None, None, None)?;
@@ -116,6 +123,12 @@ impl EventsApi {
         ///
         /// If unspecified, defaults to default page size
         limit: Query<Option<u16>>,
+        /// Cursor to the next page of events
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// use the cursor returned in the X-Aptos-Cursor header of a previous
+        /// response. It can't be combined with `start`.
+        cursor: Query<Option<PageCursor>>,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         event_handle
             .0
@@ -136,7 +149,8 @@ impl EventsApi {
             start.0.map(|v| v.0),
             limit.0,
             self.context.max_events_page_size(),
-        );
+        )
+        .with_cursor(cursor.0);
         let account = Account::new(self.context.clone(), address.0, None, None, None)?;
         let key = account.find_event_key(event_handle.0, field_name.0.into())?;
         self.list(account.latest_ledger_info, accept_type, page, key)
@@ -153,6 +167,7 @@ impl EventsApi {
         event_key: EventKey,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         let ledger_version = latest_ledger_info.version();
+        page.verify_cursor(&latest_ledger_info)?;
         let events = self
             .context
             .get_events(
@@ -169,6 +184,12 @@ impl EventsApi {
                     &latest_ledger_info,
                 )
             })?;
+        let next_sequence_number = events
+            .last()
+            .map(|event| event.event.sequence_number() + 1)
+            .or_else(|| page.start_option())
+            .unwrap_or(0);
+        let cursor = PageCursor::new(ledger_version, next_sequence_number);
 
         match accept_type {
             AcceptType::Json => {
@@ -193,5 +214,6 @@ impl EventsApi {
                 BasicResponse::try_from_bcs((events, &latest_ledger_info, BasicResponseStatus::Ok))
             },
         }
+        .map(|response| response.with_page_cursor(Some(cursor)))
     }
 }
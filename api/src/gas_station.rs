@@ -0,0 +1,75 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{SponsorshipDecision, SponsorshipRequest};
+use aptos_config::config::GasStationConfig;
+use std::time::Duration;
+
+/// An external gas station, which decides whether to sponsor (i.e., pay the gas
+/// fees of) simulated transactions. This can be implemented to plug in other
+/// sponsorship policies.
+#[async_trait::async_trait]
+pub trait GasStation: Send + Sync {
+    /// Returns the decision of the gas station on sponsoring the given transaction
+    async fn approve_sponsorship(
+        &self,
+        request: &SponsorshipRequest,
+    ) -> anyhow::Result<SponsorshipDecision>;
+}
+
+/// A gas station that is reached via a webhook. The sponsorship request is POSTed
+/// to the webhook as JSON, and the webhook must respond with a JSON decision.
+pub struct WebhookGasStation {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl WebhookGasStation {
+    pub fn new(webhook_url: String, timeout: Duration) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to build the gas station client")?;
+        Ok(Self {
+            client,
+            webhook_url,
+        })
+    }
+
+    /// Creates the webhook gas station from the config (if a webhook is configured)
+    pub fn from_config(config: &GasStationConfig) -> anyhow::Result<Option<Self>> {
+        config
+            .webhook_url
+            .as_ref()
+            .map(|webhook_url| {
+                Self::new(
+                    webhook_url.clone(),
+                    Duration::from_millis(config.webhook_timeout_ms),
+                )
+            })
+            .transpose()
+    }
+}
+
+#[async_trait::async_trait]
+impl GasStation for WebhookGasStation {
+    async fn approve_sponsorship(
+        &self,
+        request: &SponsorshipRequest,
+    ) -> anyhow::Result<SponsorshipDecision> {
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .json(request)
+            .send()
+            .await
+            .context("Failed to send the sponsorship request to the gas station")?
+            .error_for_status()
+            .context("The gas station failed to handle the sponsorship request")?;
+        response
+            .json::<SponsorshipDecision>()
+            .await
+            .context("Failed to parse the sponsorship decision of the gas station")
+    }
+}
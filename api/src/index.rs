@@ -5,11 +5,11 @@
 use crate::{
     accept_type::AcceptType,
     context::Context,
-    response::{BasicResponse, BasicResponseStatus, BasicResult},
+    response::{BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404},
     ApiTags,
 };
-use aptos_api_types::{IndexResponse, IndexResponseBcs};
-use poem_openapi::OpenApi;
+use aptos_api_types::{IndexResponse, IndexResponseBcs, PinnedLedgerVersion, U64};
+use poem_openapi::{param::Query, OpenApi};
 use std::sync::Arc;
 
 /// API for the index, to retrieve the ledger information
@@ -55,4 +55,50 @@ impl IndexApi {
             },
         }
     }
+
+    /// Get ledger version pin
+    ///
+    /// Pin a ledger version, so that multiple reads (e.g., the pages of an account's
+    /// resources, and then the items of a table) are all served at the same version, even
+    /// as the node commits new transactions. Pass the returned pin as the `ledger_version_pin`
+    /// of the account and table endpoints.
+    ///
+    /// The Aptos nodes prune state history, via a configurable time window. A pin doesn't hold
+    /// back the pruner, so it gives no retention guarantee for the pinned version. If the pinned
+    /// version gets pruned, reads with the pin fail with a 410 and the `pinned_version_pruned`
+    /// error code, and a new pin must be taken.
+    #[oai(
+        path = "/ledger_version_pin",
+        method = "get",
+        operation_id = "get_ledger_version_pin",
+        tag = "ApiTags::General"
+    )]
+    async fn get_ledger_version_pin(
+        &self,
+        accept_type: AcceptType,
+        /// Ledger version to pin
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<PinnedLedgerVersion> {
+        self.context
+            .check_api_output_enabled("Get ledger version pin", &accept_type)?;
+        let (ledger_info, pin) = self
+            .context
+            .pin_ledger_version(ledger_version.0.map(|inner| inner.0))?;
+        let pinned_ledger_version = PinnedLedgerVersion::new(pin);
+
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                pinned_ledger_version,
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => BasicResponse::try_from_bcs((
+                pinned_ledger_version,
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+        }
+    }
 }
@@ -14,14 +14,18 @@ pub mod context;
 mod error_converter;
 mod events;
 mod failpoint;
+pub mod gas_station;
 mod index;
 mod log;
 pub mod metrics;
 mod page;
+pub mod quota;
 mod response;
 mod runtime;
 mod set_failpoints;
 mod state;
+mod state_override;
+mod stream;
 #[cfg(test)]
 pub mod tests;
 mod transactions;
@@ -62,3 +62,21 @@ pub static REQUEST_SOURCE_CLIENT: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static QUOTA_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_quota_requests",
+        "API requests checked against quotas, grouped by tier and result",
+        &["tier", "result"]
+    )
+    .unwrap()
+});
+
+pub static QUOTA_COMPUTE_UNITS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_quota_compute_units",
+        "Compute units charged against quotas, grouped by tier",
+        &["tier"]
+    )
+    .unwrap()
+});
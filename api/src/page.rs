@@ -2,8 +2,8 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::response::BadRequestError;
-use aptos_api_types::{AptosErrorCode, LedgerInfo};
+use crate::response::{version_not_found, BadRequestError, NotFoundError};
+use aptos_api_types::{AptosErrorCode, LedgerInfo, PageCursor};
 use serde::Deserialize;
 
 const DEFAULT_PAGE_SIZE: u16 = 25;
@@ -13,6 +13,8 @@ pub(crate) struct Page {
     start: Option<u64>,
     limit: Option<u16>,
     max_page_size: u16,
+    #[serde(skip)]
+    cursor: Option<PageCursor>,
 }
 
 impl Page {
@@ -21,9 +23,38 @@ impl Page {
             start,
             limit,
             max_page_size,
+            cursor: None,
         }
     }
 
+    /// Sets the cursor of the page. The index of the cursor takes the place of the start.
+    pub fn with_cursor(mut self, cursor: Option<PageCursor>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Verifies that the cursor (if any) can be served by this node. Cursors can't
+    /// be combined with an explicit start, and cursors from ledger versions ahead
+    /// of this node are rejected, to avoid serving incomplete pages.
+    pub fn verify_cursor<E: BadRequestError + NotFoundError>(
+        &self,
+        ledger_info: &LedgerInfo,
+    ) -> Result<(), E> {
+        if let Some(cursor) = &self.cursor {
+            if self.start.is_some() {
+                return Err(E::bad_request_with_code(
+                    "Only one of start or cursor can be given",
+                    AptosErrorCode::InvalidInput,
+                    ledger_info,
+                ));
+            }
+            if cursor.ledger_version > ledger_info.version() {
+                return Err(version_not_found(cursor.ledger_version, ledger_info));
+            }
+        }
+        Ok(())
+    }
+
     /// Compute the start of the page for transactions
     pub fn compute_start<E: BadRequestError>(
         &self,
@@ -42,7 +73,12 @@ impl Page {
         max: u64,
         ledger_info: &LedgerInfo,
     ) -> Result<u64, E> {
-        let start = self.start.unwrap_or(default);
+        let start = self.start_option().unwrap_or(default);
+        // A cursor may point right after the latest item (i.e., to an empty page)
+        let max = match self.cursor {
+            Some(_) => max.saturating_add(1),
+            None => max,
+        };
         if start > max {
             return Err(E::bad_request_with_code(
                 format!(
@@ -59,6 +95,7 @@ impl Page {
     /// Retrieve the start version
     pub fn start_option(&self) -> Option<u64> {
         self.start
+            .or_else(|| self.cursor.map(|cursor| cursor.index))
     }
 
     /// Get the page size for the request
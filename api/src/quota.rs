@@ -0,0 +1,441 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::{QUOTA_COMPUTE_UNITS, QUOTA_REQUESTS};
+use aptos_api_types::{AptosError, AptosErrorCode};
+use aptos_config::config::{QuotaConfig, QuotaTierConfig};
+use aptos_crypto::HashValue;
+use aptos_infallible::{Mutex, RwLock};
+use aptos_rate_limiter::rate_limit::TokenBucketRateLimiter;
+use poem::{
+    http::{header, StatusCode},
+    Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
+use poem_openapi::payload::Json;
+use std::{
+    collections::HashMap,
+    iter,
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// How often the budgets of idle keys are garbage collected
+const GARBAGE_COLLECTION_INTERVAL: Duration = Duration::from_secs(60);
+
+tokio::task_local! {
+    /// The view function gas used by the request currently being handled
+    static VIEW_GAS_USED: Arc<AtomicU64>;
+}
+
+/// Records the gas used by a view function, so that it's charged to the
+/// quota of the current request. This is a no-op if quotas are disabled.
+pub fn record_view_gas_used(gas_used: u64) {
+    let _ = VIEW_GAS_USED.try_with(|view_gas_used| {
+        view_gas_used.fetch_add(gas_used, Ordering::Relaxed);
+    });
+}
+
+/// Resolves API keys to the names of their quota tiers. This can be
+/// implemented to plug in other sources of API keys.
+pub trait ApiKeyResolver: Send + Sync {
+    /// Returns the tier of the given API key, or None if the key is unknown
+    fn resolve_tier(&self, api_key: &str) -> Option<String>;
+}
+
+/// Resolves API keys from a static map, e.g., from the node config
+pub struct StaticApiKeyResolver {
+    api_keys: HashMap<String, String>,
+}
+
+impl StaticApiKeyResolver {
+    pub fn new(api_keys: HashMap<String, String>) -> Self {
+        Self { api_keys }
+    }
+}
+
+impl ApiKeyResolver for StaticApiKeyResolver {
+    fn resolve_tier(&self, api_key: &str) -> Option<String> {
+        self.api_keys.get(api_key).cloned()
+    }
+}
+
+/// The request and compute unit budgets of every key in a quota tier
+struct QuotaTier {
+    name: String,
+    config: QuotaTierConfig,
+    requests: TokenBucketRateLimiter<String>,
+    compute_units: TokenBucketRateLimiter<String>,
+}
+
+impl QuotaTier {
+    fn new(name: &str, config: &QuotaTierConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config: config.clone(),
+            requests: TokenBucketRateLimiter::new(
+                "api_quota_requests",
+                name.to_string(),
+                100,
+                config.max_requests as usize,
+                config.requests_per_second as usize,
+                None,
+            ),
+            compute_units: TokenBucketRateLimiter::new(
+                "api_quota_compute_units",
+                name.to_string(),
+                100,
+                config.max_compute_units as usize,
+                config.compute_units_per_second as usize,
+                None,
+            ),
+        }
+    }
+
+    /// Drops the budgets that aren't in use and have refilled completely, as
+    /// they're equivalent to the budgets of new keys
+    fn garbage_collect(&self) {
+        self.requests.garbage_collect_full_buckets();
+        self.compute_units.garbage_collect_full_buckets();
+    }
+}
+
+/// The quota tiers, by name. Tiers are shared (with their budgets) between
+/// the versions of the config in which they are unchanged.
+struct QuotaTiers {
+    anonymous_tier: Arc<QuotaTier>,
+    tiers: HashMap<String, Arc<QuotaTier>>,
+}
+
+impl QuotaTiers {
+    fn new(config: &QuotaConfig, previous: Option<&QuotaTiers>) -> Self {
+        let tier = |name: &String, tier_config: &QuotaTierConfig| {
+            previous
+                .and_then(|previous| previous.tiers.get(name))
+                .filter(|tier| tier.config == *tier_config)
+                .cloned()
+                .unwrap_or_else(|| Arc::new(QuotaTier::new(name, tier_config)))
+        };
+        let anonymous_tier_config = config
+            .tiers
+            .get(&config.anonymous_tier)
+            .cloned()
+            .unwrap_or_default();
+        let anonymous_tier = match previous {
+            Some(previous)
+                if previous.anonymous_tier.name == config.anonymous_tier
+                    && previous.anonymous_tier.config == anonymous_tier_config =>
+            {
+                previous.anonymous_tier.clone()
+            },
+            _ => Arc::new(QuotaTier::new(
+                &config.anonymous_tier,
+                &anonymous_tier_config,
+            )),
+        };
+        Self {
+            anonymous_tier,
+            tiers: config
+                .tiers
+                .iter()
+                .map(|(name, tier_config)| (name.clone(), tier(name, tier_config)))
+                .collect(),
+        }
+    }
+}
+
+struct QuotaInner {
+    api_key_header: String,
+    api_key_resolver: Arc<dyn ApiKeyResolver>,
+    client_ip_header: Option<String>,
+    tiers: RwLock<Arc<QuotaTiers>>,
+    view_gas_per_compute_unit: u64,
+    last_garbage_collection: Mutex<Instant>,
+}
+
+impl QuotaInner {
+    /// Returns the API key of the request, if any
+    fn api_key(&self, req: &Request) -> Option<String> {
+        let api_key = req
+            .headers()
+            .get(self.api_key_header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .or_else(|| {
+                req.headers()
+                    .get(header::AUTHORIZATION)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.strip_prefix("Bearer "))
+            })?;
+        Some(api_key.trim().to_string())
+    }
+
+    /// Returns the IP address of the client. Behind a load balancer, this is the
+    /// last address of the client IP header, if configured.
+    fn client_ip(&self, req: &Request) -> String {
+        self.client_ip_header
+            .as_ref()
+            .and_then(|client_ip_header| req.headers().get(client_ip_header.as_str()))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|address| address.trim().parse::<IpAddr>().ok())
+            .or_else(|| {
+                req.remote_addr()
+                    .as_socket_addr()
+                    .map(|address| address.ip())
+            })
+            .map(|address| address.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Returns the tier and the budget key of the request. Requests with an
+    /// API key are budgeted by a hash of the key, so the key itself is never
+    /// logged by the rate limiter. Anonymous requests are budgeted by client
+    /// IP address.
+    fn identify(&self, req: &Request) -> Result<(Arc<QuotaTier>, String), Response> {
+        let tiers = self.tiers.read().clone();
+        match self.api_key(req) {
+            Some(api_key) => {
+                let tier = self
+                    .api_key_resolver
+                    .resolve_tier(&api_key)
+                    .and_then(|tier| tiers.tiers.get(&tier).cloned());
+                match tier {
+                    Some(tier) => Ok((tier, HashValue::sha3_256_of(api_key.as_bytes()).to_hex())),
+                    None => {
+                        QUOTA_REQUESTS
+                            .with_label_values(&["unknown", "invalid_api_key"])
+                            .inc();
+                        Err(error_response(
+                            StatusCode::UNAUTHORIZED,
+                            "Unknown API key",
+                            AptosErrorCode::ApiKeyInvalid,
+                        ))
+                    },
+                }
+            },
+            None => Ok((tiers.anonymous_tier.clone(), self.client_ip(req))),
+        }
+    }
+
+    /// Acquires one request and one compute unit from the budgets of the key
+    fn acquire(&self, tier: &QuotaTier, key: &str) -> Result<(), Response> {
+        let requests = tier.requests.bucket(key.to_string());
+        if let Err(ready_at) = requests.lock().acquire_all_tokens(1) {
+            QUOTA_REQUESTS
+                .with_label_values(&[&tier.name, "throttled_requests"])
+                .inc();
+            return Err(quota_exceeded_response("request", ready_at));
+        }
+
+        let compute_units = tier.compute_units.bucket(key.to_string());
+        if let Err(ready_at) = compute_units.lock().acquire_all_tokens(1) {
+            requests.lock().return_tokens(1);
+            QUOTA_REQUESTS
+                .with_label_values(&[&tier.name, "throttled_compute_units"])
+                .inc();
+            return Err(quota_exceeded_response("compute unit", ready_at));
+        }
+
+        QUOTA_REQUESTS
+            .with_label_values(&[&tier.name, "allowed"])
+            .inc();
+        QUOTA_COMPUTE_UNITS.with_label_values(&[&tier.name]).inc();
+        Ok(())
+    }
+
+    /// Garbage collects the budgets of idle keys, at most once per interval
+    fn garbage_collect(&self) {
+        {
+            let mut last_garbage_collection = self.last_garbage_collection.lock();
+            if last_garbage_collection.elapsed() < GARBAGE_COLLECTION_INTERVAL {
+                return;
+            }
+            *last_garbage_collection = Instant::now();
+        }
+
+        let tiers = self.tiers.read().clone();
+        for tier in iter::once(&tiers.anonymous_tier).chain(tiers.tiers.values()) {
+            tier.garbage_collect();
+        }
+    }
+
+    /// Charges the view function gas used by a request to the compute unit
+    /// budget of the key. The budget can be drained, but not overdrawn.
+    fn charge_view_gas(&self, tier: &QuotaTier, key: &str, gas_used: u64) {
+        let compute_units = gas_used / self.view_gas_per_compute_unit.max(1);
+        if compute_units == 0 {
+            return;
+        }
+        if let Ok(charged) = tier
+            .compute_units
+            .bucket(key.to_string())
+            .lock()
+            .acquire_tokens(compute_units as usize)
+        {
+            QUOTA_COMPUTE_UNITS
+                .with_label_values(&[&tier.name])
+                .inc_by(charged as u64);
+        }
+    }
+}
+
+fn error_response(status: StatusCode, message: &str, error_code: AptosErrorCode) -> Response {
+    Json(AptosError::new_with_error_code(message, error_code))
+        .with_status(status)
+        .into_response()
+}
+
+fn quota_exceeded_response(budget: &str, ready_at: Option<Instant>) -> Response {
+    let retry_after_secs = ready_at
+        .map(|ready_at| {
+            let wait = ready_at.saturating_duration_since(Instant::now());
+            wait.as_secs() + u64::from(wait.subsec_nanos() > 0)
+        })
+        .unwrap_or_default()
+        .max(1);
+    let mut response = error_response(
+        StatusCode::TOO_MANY_REQUESTS,
+        &format!(
+            "The {} quota was exceeded, retry after {} seconds",
+            budget, retry_after_secs
+        ),
+        AptosErrorCode::QuotaExceeded,
+    );
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, retry_after_secs.into());
+    response
+}
+
+/// This middleware enforces per-API-key request and compute unit budgets,
+/// based on the quota tier of the key. Requests without an API key are
+/// budgeted by client IP address, in the anonymous tier. Requests over
+/// budget are rejected with a 429 and a Retry-After header. The budgets of
+/// idle keys are garbage collected periodically.
+/// The tiers can be updated while the API is running (see `update_tiers`).
+#[derive(Clone)]
+pub struct Quota {
+    inner: Arc<QuotaInner>,
+}
+
+impl Quota {
+    pub fn new(config: &QuotaConfig) -> Self {
+        let api_keys = config.api_keys.clone().into_iter().collect();
+        Self::new_with_api_key_resolver(config, Arc::new(StaticApiKeyResolver::new(api_keys)))
+    }
+
+    pub fn new_with_api_key_resolver(
+        config: &QuotaConfig,
+        api_key_resolver: Arc<dyn ApiKeyResolver>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(QuotaInner {
+                api_key_header: config.api_key_header.clone(),
+                api_key_resolver,
+                client_ip_header: config.client_ip_header.clone(),
+                tiers: RwLock::new(Arc::new(QuotaTiers::new(config, None))),
+                view_gas_per_compute_unit: config.view_gas_per_compute_unit,
+                last_garbage_collection: Mutex::new(Instant::now()),
+            }),
+        }
+    }
+
+    /// Replaces the tiers with those of the given config. The budgets of the
+    /// keys in unchanged tiers are kept, while changed tiers start afresh.
+    pub fn update_tiers(&self, config: &QuotaConfig) {
+        let mut tiers = self.inner.tiers.write();
+        *tiers = Arc::new(QuotaTiers::new(config, Some(&tiers)));
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for Quota {
+    type Output = QuotaEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        QuotaEndpoint {
+            inner: ep,
+            quota: self.inner.clone(),
+        }
+    }
+}
+
+/// Endpoint for Quota middleware.
+pub struct QuotaEndpoint<E> {
+    inner: E,
+    quota: Arc<QuotaInner>,
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for QuotaEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        self.quota.garbage_collect();
+        let (tier, key) = match self.quota.identify(&req) {
+            Ok(identity) => identity,
+            Err(response) => return Ok(response),
+        };
+        if let Err(response) = self.quota.acquire(&tier, &key) {
+            return Ok(response);
+        }
+
+        let view_gas_used = Arc::new(AtomicU64::new(0));
+        let result = VIEW_GAS_USED
+            .scope(view_gas_used.clone(), self.inner.call(req))
+            .await;
+        self.quota
+            .charge_view_gas(&tier, &key, view_gas_used.load(Ordering::Relaxed));
+
+        result.map(IntoResponse::into_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Quota;
+    use aptos_config::config::{QuotaConfig, QuotaTierConfig};
+    use std::{collections::BTreeMap, sync::Arc};
+
+    #[test]
+    fn test_update_tiers() {
+        let mut config = QuotaConfig {
+            enabled: true,
+            tiers: BTreeMap::from([
+                ("anonymous".to_string(), QuotaTierConfig::default()),
+                ("premium".to_string(), QuotaTierConfig::default()),
+            ]),
+            ..Default::default()
+        };
+        let quota = Quota::new(&config);
+        let tiers = quota.inner.tiers.read().clone();
+
+        // Only the changed tiers are replaced
+        config.tiers.get_mut("premium").unwrap().max_requests += 1;
+        quota.update_tiers(&config);
+        let updated_tiers = quota.inner.tiers.read().clone();
+        assert!(Arc::ptr_eq(
+            &tiers.anonymous_tier,
+            &updated_tiers.anonymous_tier
+        ));
+        assert!(Arc::ptr_eq(
+            &tiers.tiers["anonymous"],
+            &updated_tiers.tiers["anonymous"]
+        ));
+        assert!(!Arc::ptr_eq(
+            &tiers.tiers["premium"],
+            &updated_tiers.tiers["premium"]
+        ));
+        assert_eq!(
+            updated_tiers.tiers["premium"].config,
+            config.tiers["premium"]
+        );
+
+        // Removed tiers are dropped
+        config.tiers.remove("premium");
+        quota.update_tiers(&config);
+        assert!(!quota.inner.tiers.read().tiers.contains_key("premium"));
+    }
+}
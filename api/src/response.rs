@@ -30,7 +30,7 @@
 // TODO: https://github.com/aptos-labs/aptos-core/issues/2279
 
 use super::{accept_type::AcceptType, bcs_payload::Bcs};
-use aptos_api_types::{Address, AptosError, AptosErrorCode, HashValue, LedgerInfo};
+use aptos_api_types::{Address, AptosError, AptosErrorCode, FieldSelection, HashValue, LedgerInfo};
 use move_core_types::{
     identifier::{IdentStr, Identifier},
     language_storage::StructTag,
@@ -298,8 +298,8 @@ macro_rules! generate_success_response {
                 /// Oldest non-pruned block height of the chain
                 #[oai(header = "X-Aptos-Oldest-Block-Height")] u64,
                 /// Cursor to be used for endpoints that support cursor-based
-                /// pagination. Pass this to the `start` field of the endpoint
-                /// on the next call to get the next page of results.
+                /// pagination. Pass this to the `start` (or `cursor`) field of
+                /// the endpoint on the next call to get the next page of results.
                 #[oai(header = "X-Aptos-Cursor")] Option<String>,
             ),
             )*
@@ -471,6 +471,64 @@ macro_rules! generate_success_response {
                 }
                 self
             }
+
+            pub fn with_page_cursor(mut self, new_cursor: Option<aptos_api_types::PageCursor>) -> Self {
+                match self {
+                    $(
+                    [<$enum_name>]::$name(_, _, _, _, _, _, _, _, ref mut cursor) => {
+                        *cursor = new_cursor.map(|c| c.to_string());
+                    }
+                    )*
+                }
+                self
+            }
+        }
+
+        impl<T: poem_openapi::types::ToJSON + Send + Sync> $enum_name<T> {
+            // Converts the JSON payload of the response, e.g. to project it onto
+            // a set of fields. BCS payloads are passed through unchanged.
+            pub fn map_json<U: poem_openapi::types::ToJSON + Send + Sync>(
+                self,
+                f: impl FnOnce(T) -> U,
+            ) -> $enum_name<U> {
+                match self {
+                    $(
+                    $enum_name::$name(
+                        content,
+                        chain_id,
+                        ledger_version,
+                        oldest_ledger_version,
+                        ledger_timestamp,
+                        epoch,
+                        block_height,
+                        oldest_block_height,
+                        cursor,
+                    ) => {
+                        let content = match content {
+                            $crate::response::AptosResponseContent::Json(value) => {
+                                $crate::response::AptosResponseContent::Json(
+                                    poem_openapi::payload::Json(f(value.0)),
+                                )
+                            },
+                            $crate::response::AptosResponseContent::Bcs(value) => {
+                                $crate::response::AptosResponseContent::Bcs(value)
+                            },
+                        };
+                        $enum_name::$name(
+                            content,
+                            chain_id,
+                            ledger_version,
+                            oldest_ledger_version,
+                            ledger_timestamp,
+                            epoch,
+                            block_height,
+                            oldest_block_height,
+                            cursor,
+                        )
+                    },
+                    )*
+                }
+            }
         }
         }
     };
@@ -489,7 +547,8 @@ generate_error_traits!(
     PayloadTooLarge,
     Internal,
     InsufficientStorage,
-    ServiceUnavailable
+    ServiceUnavailable,
+    TooManyRequests
 );
 
 // Group these common errors together
@@ -564,6 +623,24 @@ pub fn api_disabled<S: Display, E: ForbiddenError>(identifier: S) -> E {
     )
 }
 
+pub fn check_field_selection<S: Display, E: BadRequestError>(
+    identifier: S,
+    accept_type: &AcceptType,
+    fields: &Option<FieldSelection>,
+) -> Result<(), E> {
+    // The BCS layout of a response is fixed, so fields can only be selected for JSON
+    if fields.is_some() && accept_type == &AcceptType::Bcs {
+        return Err(E::bad_request_with_code_no_info(
+            format!(
+                "{} with BCS output does not support selecting fields",
+                identifier
+            ),
+            AptosErrorCode::InvalidInput,
+        ));
+    }
+    Ok(())
+}
+
 pub fn api_forbidden<S: Display, E: ForbiddenError>(identifier: S, extra_help: S) -> E {
     E::forbidden_with_code_no_info(
         format!("{} is not allowed. {}", identifier, extra_help),
@@ -604,6 +681,17 @@ pub fn transaction_not_found_by_hash<E: NotFoundError>(
     )
 }
 
+pub fn transaction_expired_by_hash<E: NotFoundError>(
+    hash: HashValue,
+    ledger_info: &LedgerInfo,
+) -> E {
+    E::not_found_with_code(
+        format!("Transaction hash({}) expired in mempool", hash),
+        AptosErrorCode::TransactionExpired,
+        ledger_info,
+    )
+}
+
 pub fn version_pruned<E: GoneError>(ledger_version: u64, ledger_info: &LedgerInfo) -> E {
     E::gone_with_code(
         format!("Ledger version({}) has been pruned", ledger_version),
@@ -612,6 +700,17 @@ pub fn version_pruned<E: GoneError>(ledger_version: u64, ledger_info: &LedgerInf
     )
 }
 
+pub fn pinned_version_pruned<E: GoneError>(ledger_version: u64, ledger_info: &LedgerInfo) -> E {
+    E::gone_with_code(
+        format!(
+            "Pinned ledger version({}) has been pruned, a new pin must be taken",
+            ledger_version
+        ),
+        AptosErrorCode::PinnedVersionPruned,
+        ledger_info,
+    )
+}
+
 pub fn account_not_found<E: NotFoundError>(
     address: Address,
     ledger_version: u64,
@@ -4,17 +4,20 @@
 
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
-    context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
+    context::Context, error_converter::convert_error, events::EventsApi,
+    gas_station::WebhookGasStation, index::IndexApi, log::middleware_log, quota::Quota,
+    set_failpoints, state::StateApi, stream, transactions::TransactionsApi,
     view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::X_APTOS_CLIENT;
 use aptos_config::config::{ApiConfig, NodeConfig};
+use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_logger::info;
-use aptos_mempool::MempoolClientSender;
+use aptos_mempool::{MempoolClientSender, TransactionFilter};
 use aptos_storage_interface::DbReader;
 use aptos_types::chain_id::ChainId;
+use futures::StreamExt;
 use poem::{
     http::{header, Method},
     listener::{Listener, RustlsCertificate, RustlsConfig, TcpListener},
@@ -23,26 +26,57 @@ use poem::{
 };
 use poem_openapi::{ContactObject, LicenseObject, OpenApiService};
 use std::{net::SocketAddr, sync::Arc};
-use tokio::runtime::{Handle, Runtime};
+use tokio::{
+    runtime::{Handle, Runtime},
+    sync::watch,
+};
 
 const VERSION: &str = include_str!("../doc/.version");
 
-/// Create a runtime and attach the Poem webserver to it.
+/// Create a runtime and attach the Poem webserver to it. Also returns the
+/// quota middleware (if quotas are enabled), so its tiers can be updated.
 pub fn bootstrap(
     config: &NodeConfig,
     chain_id: ChainId,
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
-) -> anyhow::Result<Runtime> {
+    commit_notifications: Option<watch::Receiver<u64>>,
+    transaction_filter: Option<TransactionFilter>,
+    epoch_changes: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+) -> anyhow::Result<(Runtime, Option<Quota>)> {
     let max_runtime_workers = get_max_runtime_workers(&config.api);
     let runtime = aptos_runtimes::spawn_named_runtime("api".into(), Some(max_runtime_workers));
 
-    let context = Context::new(chain_id, db, mp_sender, config.clone());
+    let mut context = Context::new(chain_id, db, mp_sender, config.clone());
+    if let Some(commit_notifications) = commit_notifications {
+        context = context.with_commit_notifications(commit_notifications);
+    }
+    if let Some(gas_station) = WebhookGasStation::from_config(&config.api.gas_station)
+        .context("Failed to create the gas station webhook")?
+    {
+        context = context.with_gas_station(Arc::new(gas_station));
+    }
+    if let Some(transaction_filter) = transaction_filter {
+        context = context.with_transaction_filter(transaction_filter);
+    }
 
-    attach_poem_to_runtime(runtime.handle(), context, config, false)
+    // Invalidate the epoch dependent caches on every epoch change
+    if let Some(mut epoch_changes) = epoch_changes {
+        let context = context.clone();
+        runtime.spawn(async move {
+            while let Some(notification) = epoch_changes.next().await {
+                let epoch = notification.on_chain_configs.epoch();
+                info!("Invalidating the API caches for the new epoch: {}", epoch);
+                context.invalidate_caches_for_epoch(epoch);
+            }
+        });
+    }
+
+    let quota = Quota::new(&config.api.quota);
+    attach_poem_to_runtime_with_quota(runtime.handle(), context, config, false, quota.clone())
         .context("Failed to attach poem to runtime")?;
 
-    Ok(runtime)
+    Ok((runtime, config.api.quota.enabled.then_some(quota)))
 }
 
 // TODOs regarding spec generation:
@@ -114,10 +148,22 @@ pub fn attach_poem_to_runtime(
     context: Context,
     config: &NodeConfig,
     random_port: bool,
+) -> anyhow::Result<SocketAddr> {
+    let quota = Quota::new(&config.api.quota);
+    attach_poem_to_runtime_with_quota(runtime_handle, context, config, random_port, quota)
+}
+
+fn attach_poem_to_runtime_with_quota(
+    runtime_handle: &Handle,
+    context: Context,
+    config: &NodeConfig,
+    random_port: bool,
+    quota: Quota,
 ) -> anyhow::Result<SocketAddr> {
     let context = Arc::new(context);
 
     let size_limit = context.content_length_limit();
+    let quota_config = config.api.quota.clone();
 
     let api_service = get_api_service(context.clone());
 
@@ -175,6 +221,13 @@ pub fn attach_poem_to_runtime(
                 header::CONTENT_TYPE,
                 header::ACCEPT,
             ]);
+        // Allow browsers to send API keys too
+        let cors = if quota_config.enabled {
+            let api_key_header = header::HeaderName::try_from(quota_config.api_key_header.as_str());
+            cors.allow_headers(std::iter::once(header::AUTHORIZATION).chain(api_key_header.ok()))
+        } else {
+            cors
+        };
 
         // Build routes for the API
         let route = Route::new()
@@ -189,8 +242,15 @@ pub fn attach_poem_to_runtime(
                     .at(
                         "/set_failpoint",
                         poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
+                    )
+                    .at(
+                        "/stream",
+                        poem::get(stream::stream_poem).data(context.clone()),
                     ),
             )
+            // The quota is enforced inside CORS, so that preflight requests
+            // aren't charged and throttled responses have CORS headers
+            .with_if(quota_config.enabled, quota)
             .with(cors)
             .with(PostSizeLimit::new(size_limit))
             // NOTE: Make sure to keep this after all the `with` middleware.
@@ -283,6 +343,9 @@ mod tests {
             ChainId::test(),
             context.db.clone(),
             context.mempool.ac_client.clone(),
+            None,
+            None,
+            None,
         );
         assert!(ret.is_ok());
 
@@ -4,6 +4,7 @@
 use crate::{
     accept_type::AcceptType,
     failpoint::fail_point_poem,
+    page::Page,
     response::{
         api_forbidden, build_not_found, module_not_found, resource_not_found, table_item_not_found,
         BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
@@ -14,15 +15,18 @@ use crate::{
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     verify_module_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper,
-    MoveModuleBytecode, MoveResource, MoveStructTag, MoveValue, RawStateValueRequest,
-    RawTableItemRequest, TableItemRequest, VerifyInput, VerifyInputWithRecursion, U64,
+    IndexedTableItem, IndexedTableItemsRequest, LedgerVersionPin, ModuleCompatibilityRequest,
+    ModuleCompatibilityResult, MoveModuleBytecode, MoveResource, MoveStructTag, MoveValue,
+    PageCursor, RawStateValueRequest, RawTableItemRequest, TableItemRequest, VerifyInput,
+    VerifyInputWithRecursion, U64,
 };
 use aptos_state_view::TStateView;
 use aptos_types::{
     access_path::AccessPath,
     state_store::{state_key::StateKey, table::TableHandle},
+    transaction::ModuleBundle,
 };
-use aptos_vm::data_cache::AsMoveResolver;
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use move_core_types::{
     language_storage::{ModuleId, StructTag},
     resolver::MoveResolver,
@@ -65,6 +69,12 @@ impl StateApi {
         ///
         /// If not provided, it will be the latest version
         ledger_version: Query<Option<U64>>,
+        /// Ledger version pin to get state at
+        ///
+        /// If provided, the state is read at the pinned ledger version, see
+        /// `get_ledger_version_pin`. It can't be combined with a different
+        /// `ledger_version`.
+        ledger_version_pin: Query<Option<LedgerVersionPin>>,
     ) -> BasicResultWith404<MoveResource> {
         resource_type
             .0
@@ -76,14 +86,58 @@ impl StateApi {
         fail_point_poem("endpoint_get_account_resource")?;
         self.context
             .check_api_output_enabled("Get account resource", &accept_type)?;
+        let ledger_version = self
+            .context
+            .resolve_ledger_version_pin(ledger_version.0, ledger_version_pin.0)?;
         self.resource(
             &accept_type,
             address.0,
             resource_type.0,
-            ledger_version.0.map(|inner| inner.0),
+            ledger_version.map(|inner| inner.0),
         )
     }
 
+    /// Get account resource proof
+    ///
+    /// Retrieves an individual resource from a given account at the latest state checkpoint,
+    /// together with the proofs authenticating it against the latest signed ledger info. The
+    /// response is a BCS encoded `StateValueWithProof`, allowing light clients to verify the
+    /// resource independently. If the resource doesn't exist, the proof is a non-inclusion proof.
+    ///
+    /// Only BCS is supported as an AcceptType.
+    #[oai(
+        path = "/accounts/:address/resource/:resource_type/proof",
+        method = "get",
+        operation_id = "get_account_resource_proof",
+        tag = "ApiTags::Accounts"
+    )]
+    async fn get_account_resource_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Name of struct to retrieve e.g. `0x1::account::Account`
+        resource_type: Path<MoveStructTag>,
+    ) -> BasicResultWith404<MoveValue> {
+        resource_type
+            .0
+            .verify(0)
+            .context("'resource_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_account_resource_proof")?;
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get account resource proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get account resource proof", &accept_type)?;
+        self.resource_proof(address.0, resource_type.0)
+    }
+
     /// Get account module
     ///
     /// Retrieves an individual module from a given account and at a specific ledger version. If the
@@ -108,6 +162,12 @@ impl StateApi {
         ///
         /// If not provided, it will be the latest version
         ledger_version: Query<Option<U64>>,
+        /// Ledger version pin to get state at
+        ///
+        /// If provided, the state is read at the pinned ledger version, see
+        /// `get_ledger_version_pin`. It can't be combined with a different
+        /// `ledger_version`.
+        ledger_version_pin: Query<Option<LedgerVersionPin>>,
     ) -> BasicResultWith404<MoveModuleBytecode> {
         verify_module_identifier(module_name.0.as_str())
             .context("'module_name' invalid")
@@ -117,7 +177,43 @@ impl StateApi {
         fail_point_poem("endpoint_get_account_module")?;
         self.context
             .check_api_output_enabled("Get account module", &accept_type)?;
-        self.module(&accept_type, address.0, module_name.0, ledger_version.0)
+        let ledger_version = self
+            .context
+            .resolve_ledger_version_pin(ledger_version.0, ledger_version_pin.0)?;
+        self.module(&accept_type, address.0, module_name.0, ledger_version)
+    }
+
+    /// Check module compatibility
+    ///
+    /// Checks whether the given module bundle can be published over the deployed modules at a
+    /// specific ledger version, without publishing it. A detailed report is returned for each
+    /// module in the bundle, e.g., removed structs or public functions, changed struct layouts
+    /// and removed friend declarations. This allows checking an upgrade before paying gas for
+    /// a publish that would fail. If the ledger version is not specified in the request, the
+    /// latest ledger version is used.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/modules/compatibility",
+        method = "post",
+        operation_id = "check_module_compatibility",
+        tag = "ApiTags::Accounts"
+    )]
+    async fn check_module_compatibility(
+        &self,
+        accept_type: AcceptType,
+        /// The module bundle to check
+        request: Json<ModuleCompatibilityRequest>,
+        /// Ledger version to check the modules against
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<ModuleCompatibilityResult>> {
+        fail_point_poem("endpoint_check_module_compatibility")?;
+        self.context
+            .check_api_output_enabled("Check module compatibility", &accept_type)?;
+        self.module_compatibility(&accept_type, request.0, ledger_version.0)
     }
 
     /// Get table item
@@ -149,6 +245,12 @@ impl StateApi {
         ///
         /// If not provided, it will be the latest version
         ledger_version: Query<Option<U64>>,
+        /// Ledger version pin to get state at
+        ///
+        /// If provided, the state is read at the pinned ledger version, see
+        /// `get_ledger_version_pin`. It can't be combined with a different
+        /// `ledger_version`.
+        ledger_version_pin: Query<Option<LedgerVersionPin>>,
     ) -> BasicResultWith404<MoveValue> {
         table_item_request
             .0
@@ -160,11 +262,14 @@ impl StateApi {
         fail_point_poem("endpoint_get_table_item")?;
         self.context
             .check_api_output_enabled("Get table item", &accept_type)?;
+        let ledger_version = self
+            .context
+            .resolve_ledger_version_pin(ledger_version.0, ledger_version_pin.0)?;
         self.table_item(
             &accept_type,
             table_handle.0,
             table_item_request.0,
-            ledger_version.0,
+            ledger_version,
         )
     }
 
@@ -195,6 +300,12 @@ impl StateApi {
         ///
         /// If not provided, it will be the latest version
         ledger_version: Query<Option<U64>>,
+        /// Ledger version pin to get state at
+        ///
+        /// If provided, the state is read at the pinned ledger version, see
+        /// `get_ledger_version_pin`. It can't be combined with a different
+        /// `ledger_version`.
+        ledger_version_pin: Query<Option<LedgerVersionPin>>,
     ) -> BasicResultWith404<MoveValue> {
         fail_point_poem("endpoint_get_table_item")?;
 
@@ -206,15 +317,128 @@ impl StateApi {
         }
         self.context
             .check_api_output_enabled("Get raw table item", &accept_type)?;
+        let ledger_version = self
+            .context
+            .resolve_ledger_version_pin(ledger_version.0, ledger_version_pin.0)?;
 
         self.raw_table_item(
             &accept_type,
             table_handle.0,
             table_item_request.0,
-            ledger_version.0,
+            ledger_version,
         )
     }
 
+    /// Get indexed table items
+    ///
+    /// Get a page of items from a table keyed by contiguous `u64` indices (starting at 0), i.e.,
+    /// the buckets of a `SmartTable` or `BigVector`, identified by {table_handle} in the path.
+    /// The layout of the table (IndexedTableItemsRequest) is provided in the request body, and
+    /// determines the type of the items. This avoids a separate request for each bucket.
+    ///
+    /// The page ends at the first missing index (i.e., the end of the table). If the page is
+    /// full, a cursor to the next page is returned in the X-Aptos-Cursor header.
+    #[oai(
+        path = "/tables/:table_handle/indexed_items",
+        method = "post",
+        operation_id = "get_indexed_table_items",
+        tag = "ApiTags::Tables"
+    )]
+    async fn get_indexed_table_items(
+        &self,
+        accept_type: AcceptType,
+        /// Table handle hex encoded 32-byte string
+        table_handle: Path<Address>,
+        /// Table request detailing the layout of the table
+        request: Json<IndexedTableItemsRequest>,
+        /// Index of the first item
+        ///
+        /// If unspecified, defaults to 0
+        start: Query<Option<U64>>,
+        /// Max number of items to retrieve
+        ///
+        /// If unspecified, defaults to default page size
+        limit: Query<Option<u16>>,
+        /// Cursor to the next page of items
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// use the cursor returned in the X-Aptos-Cursor header of a previous
+        /// response. It can't be combined with `start`.
+        cursor: Query<Option<PageCursor>>,
+    ) -> BasicResultWith404<Vec<IndexedTableItem>> {
+        request
+            .0
+            .verify()
+            .context("'request' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_indexed_table_items")?;
+        self.context
+            .check_api_output_enabled("Get indexed table items", &accept_type)?;
+        let page = Page::new(
+            start.0.map(|v| v.0),
+            limit.0,
+            self.context.max_table_items_page_size(),
+        )
+        .with_cursor(cursor.0);
+        self.indexed_table_items(&accept_type, table_handle.0, request.0, page)
+    }
+
+    /// Get indexed table items proof
+    ///
+    /// Get a page of items from a table keyed by contiguous `u64` indices (e.g., the buckets
+    /// of a `SmartTable` or `BigVector`) at the latest state checkpoint, together with the
+    /// proofs authenticating them against the latest signed ledger info. The response is a
+    /// BCS encoded `IndexedTableItemsWithProof`. If the table ends within the page, the last
+    /// item is missing, with a non-inclusion proof. If the page is full, a cursor to the next
+    /// page is returned in the X-Aptos-Cursor header.
+    ///
+    /// Only BCS is supported as an AcceptType.
+    #[oai(
+        path = "/tables/:table_handle/indexed_items/proof",
+        method = "get",
+        operation_id = "get_indexed_table_items_proof",
+        tag = "ApiTags::Tables"
+    )]
+    async fn get_indexed_table_items_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Table handle hex encoded 32-byte string
+        table_handle: Path<Address>,
+        /// Index of the first item
+        ///
+        /// If unspecified, defaults to 0
+        start: Query<Option<U64>>,
+        /// Max number of items to retrieve
+        ///
+        /// If unspecified, defaults to default page size
+        limit: Query<Option<u16>>,
+        /// Cursor to the next page of items
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// use the cursor returned in the X-Aptos-Cursor header of a previous
+        /// response. It can't be combined with `start`.
+        cursor: Query<Option<PageCursor>>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_indexed_table_items_proof")?;
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get indexed table items proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get indexed table items proof", &accept_type)?;
+        let page = Page::new(
+            start.0.map(|v| v.0),
+            limit.0,
+            self.context.max_table_items_page_size(),
+        )
+        .with_cursor(cursor.0);
+        self.indexed_table_items_proof(table_handle.0, page)
+    }
+
     /// Get raw state value.
     ///
     /// Get a state value at a specific ledger version, identified by the key provided
@@ -367,6 +591,41 @@ impl StateApi {
         }
     }
 
+    /// Check the compatibility of a module bundle with the modules at the ledger version
+    pub fn module_compatibility(
+        &self,
+        accept_type: &AcceptType,
+        request: ModuleCompatibilityRequest,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<Vec<ModuleCompatibilityResult>> {
+        let (ledger_info, _, state_view) = self
+            .context
+            .state_view(ledger_version.map(|inner| inner.0))?;
+        let modules = ModuleBundle::new(request.modules.into_iter().map(Into::into).collect());
+        let results: Vec<ModuleCompatibilityResult> =
+            AptosVM::check_module_bundle_compatibility(&state_view, &modules)
+                .context("Failed to check the compatibility of the module bundle")
+                .map_err(|err| {
+                    BasicErrorWith404::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        &ledger_info,
+                    )
+                })?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+
+        match accept_type {
+            AcceptType::Json => {
+                BasicResponse::try_from_json((results, &ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((results, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
     /// Retrieve table item for a specific ledger version
     pub fn table_item(
         &self,
@@ -511,6 +770,170 @@ impl StateApi {
         }
     }
 
+    /// Retrieve a page of indexed table items at the latest ledger version
+    ///
+    /// JSON: Convert the items to MoveValues, based on the table layout
+    /// BCS: Leave the items encoded, i.e., a list of (index, bytes) pairs
+    fn indexed_table_items(
+        &self,
+        accept_type: &AcceptType,
+        table_handle: Address,
+        request: IndexedTableItemsRequest,
+        page: Page,
+    ) -> BasicResultWith404<Vec<IndexedTableItem>> {
+        let item_type = request
+            .item_type()
+            .context("Failed to parse the table layout")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+
+        let (ledger_info, ledger_version, state_view) = self.context.state_view(None)?;
+        page.verify_cursor(&ledger_info)?;
+        let start = page.start_option().unwrap_or(0);
+        let limit = page.limit(&ledger_info)?;
+
+        let resolver = state_view.as_move_resolver();
+        let items = resolver
+            .get_indexed_table_items(&TableHandle(table_handle.into()), start, limit as u64)
+            .context(format!(
+                "Failed to retrieve the indexed items of table {} from the DB",
+                table_handle
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        // Only full pages may be followed by more items
+        let cursor = (items.len() == limit as usize)
+            .then(|| PageCursor::new(ledger_version, start + limit as u64));
+
+        match accept_type {
+            AcceptType::Json => {
+                let converter = resolver.as_converter(self.context.db.clone());
+                let items = items
+                    .into_iter()
+                    .map(|(index, bytes)| {
+                        Ok(IndexedTableItem {
+                            index: index.into(),
+                            value: converter.try_into_move_value(&item_type, &bytes)?,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .context("Failed to deserialize indexed table items retrieved from DB")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+
+                BasicResponse::try_from_json((items, &ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((items, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+        .map(|response| response.with_page_cursor(cursor))
+    }
+
+    /// Retrieve a page of indexed table items (with proofs) at the latest state checkpoint
+    fn indexed_table_items_proof(
+        &self,
+        table_handle: Address,
+        page: Page,
+    ) -> BasicResultWith404<MoveValue> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        page.verify_cursor(&ledger_info)?;
+        let start = page.start_option().unwrap_or(0);
+        let limit = page.limit(&ledger_info)?;
+
+        let items_with_proof = self
+            .context
+            .get_indexed_table_items_with_proof(TableHandle(table_handle.into()), start, limit)
+            .context(format!(
+                "Failed to get the indexed items proof of table {}",
+                table_handle
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let bytes = bcs::to_bytes(&items_with_proof)
+            .context("Failed serializing indexed table items proof")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        // Only full pages (i.e., without a missing item) may be followed by more items
+        let items = &items_with_proof.items;
+        let cursor = (items.len() == limit as usize && items.iter().all(Option::is_some))
+            .then(|| PageCursor::new(ledger_info.version(), start + limit as u64));
+        BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+            .map(|response| response.with_page_cursor(cursor))
+    }
+
+    /// Retrieve a resource (with a proof) at the latest state checkpoint
+    fn resource_proof(
+        &self,
+        address: Address,
+        resource_type: MoveStructTag,
+    ) -> BasicResultWith404<MoveValue> {
+        let resource_type: StructTag = resource_type
+            .try_into()
+            .context("Failed to parse given resource type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let access_path = AccessPath::resource_access_path(address.into(), resource_type)
+            .context("Failed to build the access path of the resource")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+
+        let state_value_with_proof = self
+            .context
+            .get_state_value_with_proof(StateKey::access_path(access_path))
+            .context(format!(
+                "Failed to get resource proof for account {}",
+                address
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let bytes = bcs::to_bytes(&state_value_with_proof)
+            .context("Failed serializing resource proof")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+    }
+
     /// Retrieve state value for a specific ledger version
     pub fn raw_value(
         &self,
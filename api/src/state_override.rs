@@ -0,0 +1,98 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_state_view::{StateView, StateViewId, TStateView};
+use aptos_types::state_store::{
+    state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+};
+use std::collections::HashMap;
+
+/// A state view that applies a set of overrides on top of another state view.
+///
+/// This is used to simulate transactions against hypothetical state, without
+/// modifying the underlying storage.
+pub struct OverriddenStateView<S> {
+    base: S,
+    overrides: HashMap<StateKey, Option<StateValue>>,
+}
+
+impl<S: StateView> OverriddenStateView<S> {
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Overrides the bytes of the value at `state_key`. The metadata of the
+    /// current value (if any) is kept.
+    pub fn set_bytes(&mut self, state_key: StateKey, bytes: Vec<u8>) -> Result<()> {
+        let state_value = match self
+            .get_state_value(&state_key)?
+            .and_then(StateValue::into_metadata)
+        {
+            Some(metadata) => StateValue::new_with_metadata(bytes, metadata),
+            None => StateValue::new_legacy(bytes),
+        };
+        self.overrides.insert(state_key, Some(state_value));
+        Ok(())
+    }
+
+    /// Removes the value at `state_key`
+    pub fn remove(&mut self, state_key: StateKey) {
+        self.overrides.insert(state_key, None);
+    }
+}
+
+impl<S: StateView> TStateView for OverriddenStateView<S> {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        match self.overrides.get(state_key) {
+            Some(state_value) => Ok(state_value.clone()),
+            None => self.base.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_state_view::in_memory_state_view::InMemoryStateView;
+
+    #[test]
+    fn test_overridden_state_view() {
+        let unchanged = StateKey::raw(b"unchanged".to_vec());
+        let updated = StateKey::raw(b"updated".to_vec());
+        let removed = StateKey::raw(b"removed".to_vec());
+        let base = InMemoryStateView::new(
+            [&unchanged, &updated, &removed]
+                .into_iter()
+                .map(|key| (key.clone(), StateValue::new_legacy(vec![0])))
+                .collect(),
+        );
+
+        let mut state_view = OverriddenStateView::new(base);
+        state_view.set_bytes(updated.clone(), vec![1]).unwrap();
+        state_view.remove(removed.clone());
+
+        assert_eq!(
+            state_view.get_state_value_bytes(&unchanged).unwrap(),
+            Some(vec![0])
+        );
+        assert_eq!(
+            state_view.get_state_value_bytes(&updated).unwrap(),
+            Some(vec![1])
+        );
+        assert_eq!(state_view.get_state_value_bytes(&removed).unwrap(), None);
+    }
+}
@@ -0,0 +1,529 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A push-based alternative to polling the REST API. Clients subscribe (via
+//! SSE or WebSocket) to new transactions sent by an account, events matching a
+//! type tag or an event handle, and/or new block headers. Messages are produced
+//! as new versions are committed to storage, and each connection is bounded by
+//! a fixed size buffer: clients that fall too far behind are disconnected.
+
+use crate::context::Context;
+use anyhow::{format_err, Context as AnyhowContext, Result};
+use aptos_api_types::{Address, AsConverter, Event, HashValue, MoveType, Transaction, U64};
+use aptos_logger::{debug, warn};
+use aptos_types::{
+    account_address::AccountAddress, event::EventKey, transaction::Transaction as CoreTransaction,
+};
+use aptos_vm::data_cache::AsMoveResolver;
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use move_core_types::language_storage::TypeTag;
+use poem::{
+    handler,
+    http::StatusCode,
+    web::{
+        sse::{Event as SseEvent, SSE},
+        websocket::{Message, WebSocket},
+        Data, Query,
+    },
+    IntoResponse, Response,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::watch;
+
+/// The query parameters accepted by the stream endpoint
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct StreamParams {
+    /// Stream the transactions sent by this account
+    account: Option<String>,
+    /// Stream the events with this type (e.g., `0x1::coin::DepositEvent`)
+    event_type: Option<String>,
+    /// Stream the events of the handle owned by this account (requires
+    /// `event_creation_number`)
+    event_account: Option<String>,
+    /// The creation number of the event handle (requires `event_account`)
+    event_creation_number: Option<u64>,
+    /// Stream the new block headers
+    #[serde(default)]
+    blocks: bool,
+    /// The version to start streaming from. Defaults to the next committed version.
+    /// Older versions can only be replayed up to the configured `max_replay_versions`.
+    start_version: Option<u64>,
+}
+
+/// The set of items a single connection is subscribed to
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamSubscription {
+    account: Option<AccountAddress>,
+    event_type: Option<TypeTag>,
+    event_key: Option<EventKey>,
+    blocks: bool,
+}
+
+impl StreamSubscription {
+    /// Parses and validates the subscription from the given query parameters
+    pub fn from_params(params: &StreamParams) -> Result<Self> {
+        let account = params
+            .account
+            .as_ref()
+            .map(|account| parse_address(account, "account"))
+            .transpose()?;
+        let event_type = params
+            .event_type
+            .as_ref()
+            .map(|event_type| {
+                let move_type = MoveType::from_str(event_type)
+                    .map_err(|error| format_err!("Invalid event_type: {}", error))?;
+                TypeTag::try_from(move_type).context("Invalid event_type")
+            })
+            .transpose()?;
+        let event_key = match (&params.event_account, params.event_creation_number) {
+            (Some(event_account), Some(creation_number)) => Some(EventKey::new(
+                creation_number,
+                parse_address(event_account, "event_account")?,
+            )),
+            (None, None) => None,
+            _ => {
+                return Err(format_err!(
+                    "event_account and event_creation_number must be specified together"
+                ))
+            },
+        };
+
+        let subscription = Self {
+            account,
+            event_type,
+            event_key,
+            blocks: params.blocks,
+        };
+        if subscription.is_empty() {
+            return Err(format_err!(
+                "At least one of account, event_type, event_account or blocks must be specified"
+            ));
+        }
+        Ok(subscription)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.account.is_none()
+            && self.event_type.is_none()
+            && self.event_key.is_none()
+            && !self.blocks
+    }
+
+    /// Returns true iff the transaction is a user transaction sent by the subscribed account
+    fn matches_transaction(&self, transaction: &CoreTransaction) -> bool {
+        match (&self.account, transaction) {
+            (Some(account), CoreTransaction::UserTransaction(txn)) => txn.sender() == *account,
+            _ => false,
+        }
+    }
+
+    /// Returns true iff the event matches the subscribed event type or handle
+    fn matches_event(&self, event_key: &EventKey, type_tag: &TypeTag) -> bool {
+        self.event_key.as_ref() == Some(event_key) || self.event_type.as_ref() == Some(type_tag)
+    }
+}
+
+/// The header of a newly committed block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StreamBlockHeader {
+    pub block_id: HashValue,
+    pub epoch: U64,
+    pub round: U64,
+    pub timestamp: U64,
+    pub proposer: Address,
+}
+
+/// A single message pushed to the stream subscribers
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamMessage {
+    Transaction {
+        version: U64,
+        transaction: Box<Transaction>,
+    },
+    Event {
+        version: U64,
+        event: Event,
+    },
+    Block {
+        version: U64,
+        block: StreamBlockHeader,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl StreamMessage {
+    fn label(&self) -> &'static str {
+        match self {
+            StreamMessage::Transaction { .. } => "transaction",
+            StreamMessage::Event { .. } => "event",
+            StreamMessage::Block { .. } => "block",
+            StreamMessage::Error { .. } => "error",
+        }
+    }
+}
+
+/// Tracks an open stream connection and releases it on drop
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl ConnectionGuard {
+    fn acquire(connections: &Arc<AtomicUsize>, max_connections: usize) -> Option<Self> {
+        connections
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < max_connections).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(connections.clone()))
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Streams new transactions, events and block headers over SSE (or
+/// WebSocket, if the request asks for an upgrade). This endpoint is not part
+/// of the OpenAPI spec.
+#[handler]
+pub async fn stream_poem(
+    context: Data<&Arc<Context>>,
+    Query(params): Query<StreamParams>,
+    websocket: Option<WebSocket>,
+) -> poem::Result<Response> {
+    let config = &context.node_config.api.stream;
+    if !config.enabled {
+        return Err(poem::Error::from_string(
+            "The stream endpoint is disabled",
+            StatusCode::FORBIDDEN,
+        ));
+    }
+    let subscription = StreamSubscription::from_params(&params)
+        .map_err(|error| poem::Error::from_string(error.to_string(), StatusCode::BAD_REQUEST))?;
+    if let Some(start_version) = params.start_version {
+        check_start_version(&context, start_version, config.max_replay_versions)?;
+    }
+    let guard = ConnectionGuard::acquire(context.stream_connections(), config.max_connections)
+        .ok_or_else(|| {
+            poem::Error::from_string(
+                "The maximum number of stream connections has been reached",
+                StatusCode::SERVICE_UNAVAILABLE,
+            )
+        })?;
+
+    // Start the producer. The bounded channel applies backpressure per connection.
+    let (message_sender, mut message_receiver) = mpsc::channel(config.max_buffered_messages);
+    tokio::spawn(produce_messages(
+        context.0.clone(),
+        subscription,
+        params.start_version,
+        message_sender,
+    ));
+
+    if let Some(websocket) = websocket {
+        let response = websocket.on_upgrade(move |socket| async move {
+            let _guard = guard;
+            let (mut sink, mut stream) = socket.split();
+            loop {
+                tokio::select! {
+                    message = message_receiver.next() => {
+                        let message = match message {
+                            Some(message) => message,
+                            None => break,
+                        };
+                        let text = match serde_json::to_string(&message) {
+                            Ok(text) => text,
+                            Err(error) => {
+                                warn!("Failed to serialize stream message: {}", error);
+                                break;
+                            },
+                        };
+                        if sink.send(Message::Text(text)).await.is_err() {
+                            break;
+                        }
+                    },
+                    frame = stream.next() => match frame {
+                        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                        Some(Ok(_)) => {}, // Client messages are ignored
+                    },
+                }
+            }
+        });
+        return Ok(response.into_response());
+    }
+
+    let keep_alive = Duration::from_millis(config.keep_alive_interval_ms);
+    let events = message_receiver.map(move |message| {
+        let _guard = &guard;
+        match serde_json::to_string(&message) {
+            Ok(data) => SseEvent::message(data).event_type(message.label()),
+            Err(error) => SseEvent::message(error.to_string()).event_type("error"),
+        }
+    });
+    Ok(SSE::new(events).keep_alive(keep_alive).into_response())
+}
+
+/// Checks that a new connection doesn't replay more than `max_replay_versions`
+fn check_start_version(
+    context: &Context,
+    start_version: u64,
+    max_replay_versions: u64,
+) -> poem::Result<()> {
+    let latest_version = latest_version(context).map_err(|error| {
+        poem::Error::from_string(error.to_string(), StatusCode::INTERNAL_SERVER_ERROR)
+    })?;
+    if latest_version.saturating_sub(start_version) > max_replay_versions {
+        return Err(poem::Error::from_string(
+            format!(
+                "start_version {} is too old, only the latest {} versions can be replayed",
+                start_version, max_replay_versions
+            ),
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+    Ok(())
+}
+
+/// Reads newly committed versions from storage and pushes the matching
+/// messages to the connection, until the connection is closed or lags behind.
+async fn produce_messages(
+    context: Arc<Context>,
+    subscription: StreamSubscription,
+    start_version: Option<u64>,
+    mut message_sender: mpsc::Sender<StreamMessage>,
+) {
+    let config = context.node_config.api.stream.clone();
+    let mut commit_notifications = context.subscribe_to_commits();
+    let mut next_version = match start_version {
+        Some(start_version) => start_version,
+        None => match latest_version(&context).await {
+            Ok(latest_version) => latest_version + 1,
+            Err(error) => {
+                let _ = message_sender
+                    .send(StreamMessage::Error {
+                        message: error.to_string(),
+                    })
+                    .await;
+                return;
+            },
+        },
+    };
+
+    loop {
+        // Drain all versions that have been committed so far
+        let result = async {
+            let latest_version = latest_version(&context).await?;
+            while next_version <= latest_version {
+                let lag = latest_version - next_version;
+                if lag > config.max_lag_versions {
+                    return Err(format_err!(
+                        "The stream fell too far behind (lag: {} versions)",
+                        lag
+                    ));
+                }
+
+                let limit = std::cmp::min(
+                    context.max_transactions_page_size() as u64,
+                    latest_version - next_version + 1,
+                ) as u16;
+                let page_context = context.clone();
+                let page_subscription = subscription.clone();
+                let (messages, num_transactions) = tokio::task::spawn_blocking(move || {
+                    read_messages(
+                        &page_context,
+                        &page_subscription,
+                        next_version,
+                        limit,
+                        latest_version,
+                    )
+                })
+                .await??;
+                if num_transactions == 0 {
+                    break;
+                }
+                next_version += num_transactions;
+
+                for message in messages {
+                    if message_sender.send(message).await.is_err() {
+                        return Ok(false); // The connection was closed
+                    }
+                }
+            }
+            Ok(true)
+        }
+        .await;
+
+        match result {
+            Ok(true) => {},
+            Ok(false) => return,
+            Err(error) => {
+                debug!("Closing stream connection: {}", error);
+                let _ = message_sender
+                    .send(StreamMessage::Error {
+                        message: error.to_string(),
+                    })
+                    .await;
+                return;
+            },
+        }
+
+        // Wait for the next commit (or poll if commit notifications are unavailable)
+        if !wait_for_commit(&mut commit_notifications, config.poll_interval_ms).await
+            || message_sender.is_closed()
+        {
+            return;
+        }
+    }
+}
+
+/// Waits for the next commit notification. Returns false iff the
+/// notification channel has been closed.
+async fn wait_for_commit(
+    commit_notifications: &mut Option<watch::Receiver<u64>>,
+    poll_interval_ms: u64,
+) -> bool {
+    match commit_notifications {
+        Some(commit_notifications) => commit_notifications.changed().await.is_ok(),
+        None => {
+            tokio::time::sleep(Duration::from_millis(poll_interval_ms)).await;
+            true
+        },
+    }
+}
+
+async fn latest_version(context: &Context) -> Result<u64> {
+    Ok(context
+        .async_db()
+        .get_latest_ledger_info()
+        .await?
+        .ledger_info()
+        .version())
+}
+
+/// Reads a page of transactions from storage and returns the messages matching
+/// the subscription (and the number of transactions read).
+fn read_messages(
+    context: &Context,
+    subscription: &StreamSubscription,
+    start_version: u64,
+    limit: u16,
+    ledger_version: u64,
+) -> Result<(Vec<StreamMessage>, u64)> {
+    let transactions = context.get_transactions(start_version, limit, ledger_version)?;
+    let num_transactions = transactions.len() as u64;
+
+    // The transactions are converted with the state as of the page, not the latest state
+    let state_view = match transactions.last() {
+        Some(transaction) => context.state_view_at_version(transaction.version)?,
+        None => return Ok((vec![], 0)),
+    };
+    let resolver = state_view.as_move_resolver();
+    let converter = resolver.as_converter(context.db.clone());
+
+    let mut timestamp = context.db.get_block_timestamp(start_version)?;
+    let mut messages = vec![];
+    for transaction in transactions {
+        let version = transaction.version;
+        if let Some(block_metadata) = transaction.transaction.try_as_block_metadata() {
+            timestamp = block_metadata.timestamp_usecs();
+            if subscription.blocks {
+                messages.push(StreamMessage::Block {
+                    version: version.into(),
+                    block: StreamBlockHeader {
+                        block_id: block_metadata.id().into(),
+                        epoch: block_metadata.epoch().into(),
+                        round: block_metadata.round().into(),
+                        timestamp: timestamp.into(),
+                        proposer: block_metadata.proposer().into(),
+                    },
+                });
+            }
+        }
+
+        let matching_events: Vec<_> = transaction
+            .events
+            .iter()
+            .filter(|event| subscription.matches_event(event.key(), event.type_tag()))
+            .cloned()
+            .collect();
+        for event in converter.try_into_events(&matching_events)? {
+            messages.push(StreamMessage::Event {
+                version: version.into(),
+                event,
+            });
+        }
+
+        if subscription.matches_transaction(&transaction.transaction) {
+            messages.push(StreamMessage::Transaction {
+                version: version.into(),
+                transaction: Box::new(
+                    converter.try_into_onchain_transaction(timestamp, transaction)?,
+                ),
+            });
+        }
+    }
+
+    Ok((messages, num_transactions))
+}
+
+fn parse_address(address: &str, name: &str) -> Result<AccountAddress> {
+    Address::from_str(address)
+        .map(|address| address.into())
+        .map_err(|error| format_err!("Invalid {}: {}", name, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StreamParams, StreamSubscription};
+    use aptos_types::{account_address::AccountAddress, event::EventKey};
+
+    #[test]
+    fn test_subscription_from_params() {
+        // An empty subscription is rejected
+        assert!(StreamSubscription::from_params(&StreamParams::default()).is_err());
+
+        // A partial event handle is rejected
+        let params = StreamParams {
+            event_account: Some("0x1".into()),
+            ..Default::default()
+        };
+        assert!(StreamSubscription::from_params(&params).is_err());
+
+        // An invalid event type is rejected
+        let params = StreamParams {
+            event_type: Some("not a type".into()),
+            ..Default::default()
+        };
+        assert!(StreamSubscription::from_params(&params).is_err());
+
+        // A valid subscription is parsed
+        let params = StreamParams {
+            account: Some("0x1".into()),
+            event_type: Some("0x1::coin::DepositEvent".into()),
+            event_account: Some("0x2".into()),
+            event_creation_number: Some(3),
+            blocks: true,
+            start_version: None,
+        };
+        let subscription = StreamSubscription::from_params(&params).unwrap();
+        assert_eq!(subscription.account, Some(AccountAddress::ONE));
+        assert_eq!(
+            subscription.event_key,
+            Some(EventKey::new(3, AccountAddress::TWO))
+        );
+        assert!(subscription.event_type.is_some());
+        assert!(subscription.blocks);
+    }
+}
@@ -2,9 +2,12 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_indexer};
 use aptos_api_test_context::{current_function_name, find_value};
-use aptos_api_types::{MoveModuleBytecode, MoveResource, StateKeyWrapper};
+use aptos_api_types::{
+    mime_types, LedgerVersionPin, MoveModuleBytecode, MoveResource, PinnedLedgerVersion,
+    StateKeyWrapper,
+};
 use serde_json::json;
 use std::str::FromStr;
 
@@ -277,6 +280,62 @@ async fn test_get_account_resources_with_pagination() {
     assert_eq!(resources, all_resources[10..].to_vec());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resources_with_indexer() {
+    let context = new_test_context(current_function_name!());
+    let indexer_context = new_test_context_with_indexer(current_function_name!());
+    let address = "0x1";
+
+    // Get all resources from both the state (without the indexer) and the indexer,
+    // and verify the same resource types are returned in the same order.
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("/v1{}", account_resources(address)));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let all_resources: Vec<MoveResource> = serde_json::from_slice(resp.body()).unwrap();
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("/v1{}", account_resources(address)));
+    let resp = indexer_context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let indexed_resources: Vec<MoveResource> = serde_json::from_slice(resp.body()).unwrap();
+    let resource_types = |resources: &[MoveResource]| {
+        resources
+            .iter()
+            .map(|resource| resource.typ.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        resource_types(&indexed_resources),
+        resource_types(&all_resources)
+    );
+
+    // Page through the indexed resources using the cursors
+    let mut paged_resources = vec![];
+    let mut cursor: Option<String> = None;
+    loop {
+        let path = match &cursor {
+            Some(cursor) => format!("/v1{}?limit=5&start={}", account_resources(address), cursor),
+            None => format!("/v1{}?limit=5", account_resources(address)),
+        };
+        let resp = indexer_context
+            .reply(warp::test::request().method("GET").path(&path))
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resources: Vec<MoveResource> = serde_json::from_slice(resp.body()).unwrap();
+        paged_resources.extend(resources);
+        cursor = resp
+            .headers()
+            .get("X-Aptos-Cursor")
+            .map(|cursor| cursor.to_str().unwrap().to_string());
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(paged_resources, indexed_resources);
+}
+
 // Same as the above test but for modules.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_modules_with_pagination() {
@@ -376,6 +435,148 @@ async fn test_get_account_items_limit_params() {
     assert_eq!(resp.status(), 400);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resources_with_field_selection() {
+    let context = new_test_context(current_function_name!());
+    let address = "0x1";
+
+    let resp = context
+        .get(&format!("{}?fields=type", account_resources(address)))
+        .await;
+    let resources = resp.as_array().unwrap();
+    assert!(!resources.is_empty());
+    for resource in resources {
+        let resource = resource.as_object().unwrap();
+        assert_eq!(resource.len(), 1);
+        assert!(resource.contains_key("type"));
+    }
+
+    // Fields can't be selected for BCS output
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", mime_types::BCS)
+        .path(&format!("/v1{}?fields=type", account_resources(address)));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_with_ledger_version_pin() {
+    let mut context = new_test_context(current_function_name!());
+    let resp = context.get("/ledger_version_pin").await;
+    let pinned: PinnedLedgerVersion = serde_json::from_value(resp).unwrap();
+    assert_eq!(pinned.pin.ledger_version, pinned.ledger_version.0);
+
+    // The account is created after the pinned version, so it doesn't exist at the pin
+    let account = context.create_account().await;
+    let address = account.address().to_hex_literal();
+    context.get(&format!("/accounts/{}", address)).await;
+    context
+        .expect_status_code(404)
+        .get(&format!(
+            "/accounts/{}?ledger_version_pin={}",
+            address, pinned.pin
+        ))
+        .await;
+
+    // Reads with the pin are served at the pinned version
+    let pinned_resources = context
+        .get(&format!(
+            "{}?ledger_version_pin={}",
+            account_resources("0x1"),
+            pinned.pin
+        ))
+        .await;
+    let resources = context
+        .get(&account_resources_with_ledger_version(
+            "0x1",
+            pinned.ledger_version.0 as i128,
+        ))
+        .await;
+    assert_eq!(pinned_resources, resources);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_with_invalid_ledger_version_pin() {
+    let context = new_test_context(current_function_name!());
+    let resp = context.get("/ledger_version_pin").await;
+    let pinned: PinnedLedgerVersion = serde_json::from_value(resp).unwrap();
+    let pin = pinned.pin;
+
+    // The pin can't be combined with a different ledger version
+    let resp = context
+        .expect_status_code(400)
+        .get(&format!(
+            "{}&ledger_version_pin={}",
+            account_resources_with_ledger_version("0x1", pin.ledger_version as i128 + 1),
+            pin
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "invalid_input");
+
+    // The pin must be for this chain
+    let other_chain_pin = LedgerVersionPin::new(pin.chain_id.wrapping_add(1), pin.ledger_version);
+    let resp = context
+        .expect_status_code(400)
+        .get(&format!(
+            "{}?ledger_version_pin={}",
+            account_resources("0x1"),
+            other_chain_pin
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "invalid_input");
+
+    // The pinned version must not be ahead of the node
+    let future_pin = LedgerVersionPin::new(pin.chain_id, pin.ledger_version + 100);
+    let resp = context
+        .expect_status_code(404)
+        .get(&format!(
+            "{}?ledger_version_pin={}",
+            account_resources("0x1"),
+            future_pin
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "version_not_found");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_with_pruned_ledger_version_pin() {
+    let mut context = new_test_context(current_function_name!());
+    let resp = context.get("/ledger_version_pin").await;
+    let pinned: PinnedLedgerVersion = serde_json::from_value(resp).unwrap();
+    let pin = pinned.pin;
+
+    // Prune the ledger past the pinned version
+    context.create_account().await;
+    context
+        .db
+        .prune_ledger_for_test(pin.ledger_version + 1)
+        .unwrap();
+    let latest_ledger_info = context.get_latest_ledger_info();
+    assert!(pin.ledger_version < latest_ledger_info.oldest_ledger_version.0);
+
+    // The pin no longer resolves, and a new one has to be taken
+    let resp = context
+        .expect_status_code(410)
+        .get(&format!(
+            "{}?ledger_version_pin={}",
+            account_resources("0x1"),
+            pin
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "pinned_version_pruned");
+
+    let resp = context.get("/ledger_version_pin").await;
+    let new_pinned: PinnedLedgerVersion = serde_json::from_value(resp).unwrap();
+    context
+        .get(&format!(
+            "{}?ledger_version_pin={}",
+            account_resources("0x1"),
+            new_pinned.pin
+        ))
+        .await;
+}
+
 fn account_resources(address: &str) -> String {
     format!("/accounts/{}/resources", address)
 }
@@ -11,8 +11,10 @@ mod invalid_post_request_test;
 mod modules;
 mod multisig_transactions_test;
 mod objects;
+mod quota_test;
 mod resource_groups;
 mod state_test;
+mod stream_test;
 mod string_resource_test;
 mod transaction_vector_test;
 mod transactions_test;
@@ -28,3 +30,7 @@ fn new_test_context(test_name: String) -> TestContext {
 fn new_test_context_with_config(test_name: String, node_config: NodeConfig) -> TestContext {
     super_new_test_context(test_name, node_config, false)
 }
+
+fn new_test_context_with_indexer(test_name: String) -> TestContext {
+    super_new_test_context(test_name, NodeConfig::default(), true)
+}
@@ -0,0 +1,18 @@
+module smart_table_holder::holder {
+    use aptos_std::smart_table::{Self, SmartTable};
+
+    struct Holder has key {
+        table: SmartTable<u64, u64>
+    }
+
+    /// Creates a table with 3 buckets, mapping each i in [0, num_items) to i * i.
+    entry fun create(account: &signer, num_items: u64) {
+        let table = smart_table::new_with_config(3, 0, 0);
+        let i = 0;
+        while (i < num_items) {
+            smart_table::add(&mut table, i, i * i);
+            i = i + 1;
+        };
+        move_to(account, Holder { table });
+    }
+}
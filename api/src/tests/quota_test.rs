@@ -0,0 +1,197 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context_with_config;
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::{NodeConfig, QuotaConfig, QuotaTierConfig};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+const PREMIUM_API_KEY: &str = "premium-key";
+const COMPUTE_LIMITED_API_KEY: &str = "compute-limited-key";
+
+fn new_test_context_with_quota(test_name: String) -> TestContext {
+    let tier = |max_requests, max_compute_units| QuotaTierConfig {
+        requests_per_second: 1,
+        max_requests,
+        compute_units_per_second: 1,
+        max_compute_units,
+    };
+
+    let mut node_config = NodeConfig::default();
+    node_config.api.quota = QuotaConfig {
+        enabled: true,
+        tiers: BTreeMap::from([
+            ("anonymous".to_string(), tier(2, 100)),
+            ("premium".to_string(), tier(100, 100)),
+            ("compute_limited".to_string(), tier(100, 2)),
+        ]),
+        api_keys: BTreeMap::from([
+            (PREMIUM_API_KEY.to_string(), "premium".to_string()),
+            (
+                COMPUTE_LIMITED_API_KEY.to_string(),
+                "compute_limited".to_string(),
+            ),
+        ]),
+        client_ip_header: Some("x-forwarded-for".to_string()),
+        view_gas_per_compute_unit: 1,
+        ..Default::default()
+    };
+    new_test_context_with_config(test_name, node_config)
+}
+
+async fn get_index(context: &TestContext, header: Option<(&str, &str)>) -> u16 {
+    let mut req = warp::test::request().method("GET").path("/v1/");
+    if let Some((name, value)) = header {
+        req = req.header(name, value);
+    }
+    context.reply(req).await.status().as_u16()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_quota_anonymous_requests() {
+    let context = new_test_context_with_quota(current_function_name!());
+
+    // The anonymous tier allows a burst of two requests
+    assert_eq!(get_index(&context, None).await, 200);
+    assert_eq!(get_index(&context, None).await, 200);
+
+    let resp = context
+        .reply(warp::test::request().method("GET").path("/v1/"))
+        .await;
+    assert_eq!(resp.status(), 429);
+    let retry_after: u64 = resp.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after >= 1);
+    let body: Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(body["error_code"], "quota_exceeded");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_quota_cors() {
+    let context = new_test_context_with_quota(current_function_name!());
+
+    // Preflight requests are answered by CORS, so they aren't charged
+    for _ in 0..3 {
+        let resp = context
+            .reply(
+                warp::test::request()
+                    .method("OPTIONS")
+                    .path("/v1/")
+                    .header("origin", "http://example.com")
+                    .header("access-control-request-method", "GET"),
+            )
+            .await;
+        assert!(resp.status().is_success());
+    }
+    assert_eq!(get_index(&context, None).await, 200);
+    assert_eq!(get_index(&context, None).await, 200);
+
+    // Throttled responses have CORS headers, so browsers can read them
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path("/v1/")
+                .header("origin", "http://example.com"),
+        )
+        .await;
+    assert_eq!(resp.status(), 429);
+    assert!(resp.headers().contains_key("access-control-allow-origin"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_quota_client_ip_header() {
+    let context = new_test_context_with_quota(current_function_name!());
+
+    // Anonymous requests are budgeted by the last address of the client IP
+    // header, as appended by the load balancer
+    let first_client = Some(("x-forwarded-for", "10.0.0.1"));
+    let second_client = Some(("x-forwarded-for", "1.2.3.4, 10.0.0.2"));
+    assert_eq!(get_index(&context, first_client).await, 200);
+    assert_eq!(get_index(&context, first_client).await, 200);
+    assert_eq!(get_index(&context, first_client).await, 429);
+    assert_eq!(get_index(&context, second_client).await, 200);
+    assert_eq!(
+        get_index(&context, Some(("x-forwarded-for", "10.0.0.2"))).await,
+        200
+    );
+    assert_eq!(get_index(&context, second_client).await, 429);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_quota_api_keys() {
+    let context = new_test_context_with_quota(current_function_name!());
+
+    // Requests with a known API key use the budgets of its tier, whichever
+    // way the key is sent
+    let bearer = format!("Bearer {}", PREMIUM_API_KEY);
+    for _ in 0..5 {
+        assert_eq!(
+            get_index(&context, Some(("x-api-key", PREMIUM_API_KEY))).await,
+            200
+        );
+        assert_eq!(
+            get_index(&context, Some(("authorization", &bearer))).await,
+            200
+        );
+    }
+
+    // Requests with an unknown API key are rejected
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path("/v1/")
+                .header("x-api-key", "unknown-key"),
+        )
+        .await;
+    assert_eq!(resp.status(), 401);
+    let body: Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(body["error_code"], "api_key_invalid");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_quota_view_compute_units() {
+    let context = new_test_context_with_quota(current_function_name!());
+    let view = || {
+        warp::test::request()
+            .method("POST")
+            .path("/v1/view")
+            .header("x-api-key", COMPUTE_LIMITED_API_KEY)
+            .json(&json!({
+                "function": "0x1::coin::is_coin_initialized",
+                "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+                "arguments": [],
+            }))
+    };
+
+    // The first view function is allowed, but its gas drains the compute
+    // budget of the key, so the next request is rejected
+    assert_eq!(context.reply(view()).await.status(), 200);
+    assert_eq!(context.reply(view()).await.status(), 429);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_quota_aborted_view_compute_units() {
+    let context = new_test_context_with_quota(current_function_name!());
+    let view = || {
+        warp::test::request()
+            .method("POST")
+            .path("/v1/view")
+            .header("x-api-key", COMPUTE_LIMITED_API_KEY)
+            .json(&json!({
+                "function": "0x1::coin::balance",
+                "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+                "arguments": ["0xdead"],
+            }))
+    };
+
+    // The view function aborts (as the account has no coin store), but its
+    // gas still drains the compute budget of the key
+    assert_eq!(context.reply(view()).await.status(), 400);
+    assert_eq!(context.reply(view()).await.status(), 429);
+}
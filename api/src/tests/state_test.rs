@@ -3,6 +3,7 @@
 
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::{mime_types, IndexedTableItemsWithProof, StateValueWithProof};
 use aptos_sdk::{transaction_builder::aptos_stdlib::aptos_token_stdlib, types::LocalAccount};
 use aptos_storage_interface::DbReader;
 use move_core_types::account_address::AccountAddress;
@@ -36,6 +37,184 @@ async fn test_get_account_resource_by_invalid_address() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_proof() {
+    let mut context = new_test_context(current_function_name!());
+
+    // The proof verifies for both existing and missing resources
+    for (address, exists) in [("0xA550C18", true), ("0xA550C19", false)] {
+        let req = warp::test::request()
+            .method("GET")
+            .header("Accept", mime_types::BCS)
+            .path(&format!(
+                "/v1{}/proof",
+                get_account_resource(address, "0x1::account::Account")
+            ));
+        let resp = context.reply(req).await;
+        assert_eq!(resp.status(), 200);
+
+        let proof: StateValueWithProof = bcs::from_bytes(resp.body()).unwrap();
+        assert_eq!(proof.state_value.is_some(), exists);
+        proof.verify().unwrap();
+    }
+
+    // Only BCS is supported
+    context
+        .expect_status_code(403)
+        .get(&format!(
+            "{}/proof",
+            get_account_resource("0xA550C18", "0x1::account::Account")
+        ))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_indexed_table_items_of_missing_table() {
+    let mut context = new_test_context(current_function_name!());
+    let table_handle = "0x1234";
+
+    // A missing table has no items
+    let items = context
+        .post(
+            &format!("/tables/{}/indexed_items?limit=10", table_handle),
+            json!({
+                "layout": "smart_table",
+                "key_type": "u64",
+                "value_type": "address",
+            }),
+        )
+        .await;
+    assert_eq!(items, json!([]));
+
+    // A big vector doesn't have a key type
+    context
+        .expect_status_code(400)
+        .post(
+            &format!("/tables/{}/indexed_items", table_handle),
+            json!({
+                "layout": "big_vector",
+                "key_type": "u64",
+                "value_type": "address",
+            }),
+        )
+        .await;
+
+    // The proof of a missing table is a single non-inclusion proof
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", mime_types::BCS)
+        .path(&format!(
+            "/v1/tables/{}/indexed_items/proof?start=0&limit=10",
+            table_handle
+        ));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+
+    let proof: IndexedTableItemsWithProof = bcs::from_bytes(resp.body()).unwrap();
+    assert_eq!(proof.items.len(), 1);
+    assert!(proof.items[0].is_none());
+    proof.verify().unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_indexed_table_items_of_smart_table() {
+    let mut context = new_test_context(current_function_name!());
+    let mut account = context.create_account().await;
+    let account_addr = account.address();
+
+    // Publish a module holding a smart table with 3 buckets and 5 entries
+    let named_addresses = vec![("smart_table_holder".to_string(), account_addr)];
+    let txn = futures::executor::block_on(async move {
+        let path =
+            PathBuf::from(std::env!("CARGO_MANIFEST_DIR")).join("src/tests/move/pack_smart_table");
+        TestContext::build_package(path, named_addresses)
+    });
+    context.publish_package(&mut account, txn).await;
+    context
+        .api_execute_entry_function(
+            &mut account,
+            &format!("0x{}::holder::create", account_addr),
+            json!([]),
+            json!(["5"]),
+        )
+        .await;
+
+    let holder_type = format!("0x{}::holder::Holder", account_addr);
+    let holder = context
+        .gen_resource(&account_addr, &holder_type)
+        .await
+        .unwrap();
+    let table = &holder["data"]["table"];
+    let table_handle = table["buckets"]["inner"]["handle"].as_str().unwrap();
+    assert_eq!(table["num_buckets"], json!("3"));
+
+    // Page through the buckets 2 at a time, following the cursors
+    let mut buckets = vec![];
+    let mut cursor = None;
+    loop {
+        let req = warp::test::request()
+            .method("POST")
+            .path(&indexed_items_page(table_handle, "", cursor.take()))
+            .json(&json!({
+                "layout": "smart_table",
+                "key_type": "u64",
+                "value_type": "u64",
+            }));
+        let resp = context.reply(req).await;
+        assert_eq!(resp.status(), 200);
+        let items: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+        for item in items {
+            assert_eq!(item["index"], json!(buckets.len().to_string()));
+            buckets.push(item["value"].clone());
+        }
+        match resp.headers().get("X-Aptos-Cursor") {
+            Some(header) => cursor = Some(header.to_str().unwrap().to_string()),
+            None => break,
+        }
+    }
+    assert_eq!(buckets.len(), 3);
+    let mut entries: Vec<_> = buckets
+        .iter()
+        .flat_map(|bucket| bucket.as_array().unwrap())
+        .map(|entry| (entry["key"].clone(), entry["value"].clone()))
+        .collect();
+    entries.sort_by_key(|(key, _)| key.as_str().unwrap().parse::<u64>().unwrap());
+    let expected_entries: Vec<_> = (0..5u64)
+        .map(|i| (json!(i.to_string()), json!((i * i).to_string())))
+        .collect();
+    assert_eq!(entries, expected_entries);
+
+    // The proofs of the same pages verify, and only the last page ends with a missing item
+    let mut num_items = 0;
+    let mut cursor = None;
+    loop {
+        let req = warp::test::request()
+            .method("GET")
+            .header("Accept", mime_types::BCS)
+            .path(&indexed_items_page(table_handle, "/proof", cursor.take()));
+        let resp = context.reply(req).await;
+        assert_eq!(resp.status(), 200);
+        let proof: IndexedTableItemsWithProof = bcs::from_bytes(resp.body()).unwrap();
+        proof.verify().unwrap();
+        assert_eq!(proof.start, num_items);
+        assert_eq!(proof.items.len(), 2);
+        match resp.headers().get("X-Aptos-Cursor") {
+            Some(header) => {
+                assert!(proof.items.iter().all(Option::is_some));
+                num_items += 2;
+                cursor = Some(header.to_str().unwrap().to_string());
+            },
+            None => {
+                assert!(proof.items[0].is_some());
+                assert!(proof.items[1].is_none());
+                num_items += 1;
+                break;
+            },
+        }
+    }
+    assert_eq!(num_items, 3);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_resource_by_invalid_struct_tag() {
     let mut context = new_test_context(current_function_name!());
@@ -313,6 +492,14 @@ fn get_account_module(address: &str, name: &str) -> String {
     format!("/accounts/{}/module/{}", address, name)
 }
 
+fn indexed_items_page(table_handle: &str, suffix: &str, cursor: Option<String>) -> String {
+    let cursor = cursor.map_or_else(String::new, |cursor| format!("&cursor={}", cursor));
+    format!(
+        "/v1/tables/{}/indexed_items{}?limit=2{}",
+        table_handle, suffix, cursor
+    )
+}
+
 fn get_table_item(handle: AccountAddress) -> String {
     format!("/tables/{}/item", handle)
 }
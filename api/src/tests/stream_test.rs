@@ -0,0 +1,114 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use aptos_api_test_context::{current_function_name, ApiSpecificConfig, TestContext};
+use aptos_config::config::NodeConfig;
+use serde_json::Value;
+use std::time::Duration;
+
+fn new_test_context_with_stream(test_name: String, max_replay_versions: u64) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.stream.enabled = true;
+    node_config.api.stream.max_replay_versions = max_replay_versions;
+    node_config.api.stream.poll_interval_ms = 10;
+    new_test_context_with_config(test_name, node_config)
+}
+
+/// Opens a stream with the given query parameters
+async fn open_stream(context: &TestContext, query: &str) -> reqwest::Response {
+    let ApiSpecificConfig::V1(address) = context.api_specific_config;
+    reqwest::get(format!("http://{}/v1/stream?{}", address, query))
+        .await
+        .unwrap()
+}
+
+async fn get_stream_status(context: &TestContext, query: &str) -> u16 {
+    open_stream(context, query).await.status().as_u16()
+}
+
+/// Reads the next SSE message of the stream, and returns its event type and data
+async fn next_message(response: &mut reqwest::Response, buffer: &mut String) -> (String, Value) {
+    while !buffer.contains("\n\n") {
+        let chunk = tokio::time::timeout(Duration::from_secs(10), response.chunk())
+            .await
+            .expect("Timed out waiting for a stream message")
+            .unwrap()
+            .expect("The stream was closed");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    let end = buffer.find("\n\n").unwrap();
+    let message: String = buffer.drain(..end + 2).collect();
+
+    let (mut event_type, mut data) = (String::new(), Value::Null);
+    for line in message.lines() {
+        if let Some(value) = line.strip_prefix("event: ") {
+            event_type = value.to_string();
+        } else if let Some(value) = line.strip_prefix("data: ") {
+            data = serde_json::from_str(value).unwrap();
+        }
+    }
+    (event_type, data)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_account_transactions() {
+    let mut context = new_test_context_with_stream(current_function_name!(), 1_000);
+    let root = context.root_account().await.address();
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let query = format!("account={}&start_version=0", root.to_hex_literal());
+    let mut response = open_stream(&context, &query).await;
+    assert_eq!(response.status(), 200);
+    let mut buffer = String::new();
+
+    // The committed transaction is replayed from the start version
+    let (event_type, data) = next_message(&mut response, &mut buffer).await;
+    assert_eq!(event_type, "transaction");
+    assert_eq!(data["transaction"]["sender"], root.to_hex_literal());
+    let replayed_version: u64 = data["version"].as_str().unwrap().parse().unwrap();
+
+    // And newly committed transactions are streamed as well
+    let other_account = context.gen_account();
+    let txn = context.create_user_account(&other_account).await;
+    context.commit_block(&vec![txn]).await;
+    let (event_type, data) = next_message(&mut response, &mut buffer).await;
+    assert_eq!(event_type, "transaction");
+    assert_eq!(data["transaction"]["sender"], root.to_hex_literal());
+    let version: u64 = data["version"].as_str().unwrap().parse().unwrap();
+    assert!(version > replayed_version);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_invalid_requests() {
+    let mut context = new_test_context_with_stream(current_function_name!(), 1);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    // A stream must be subscribed to something
+    assert_eq!(get_stream_status(&context, "start_version=0").await, 400);
+
+    // Streams can't replay more than the max replay versions
+    let latest_version = context.get_latest_ledger_info().version();
+    assert_eq!(
+        get_stream_status(&context, "blocks=true&start_version=0").await,
+        400
+    );
+    assert_eq!(
+        get_stream_status(
+            &context,
+            &format!("blocks=true&start_version={}", latest_version - 1)
+        )
+        .await,
+        200
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_disabled_by_default() {
+    let context = new_test_context(current_function_name!());
+    assert_eq!(get_stream_status(&context, "blocks=true").await, 403);
+}
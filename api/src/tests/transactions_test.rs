@@ -5,18 +5,23 @@
 use super::new_test_context;
 use crate::tests::new_test_context_with_config;
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
+use aptos_api_types::{mime_types, PageCursor, TransactionWithLedgerInfo};
 use aptos_config::config::{GasEstimationStaticOverride, NodeConfig};
 use aptos_crypto::{
     ed25519::Ed25519PrivateKey,
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
     PrivateKey, SigningKey, Uniform,
 };
+use aptos_mempool::{
+    SignedTransactionFilterPolicy, TransactionFilterPolicy, TransactionFilterRule,
+};
 use aptos_sdk::types::LocalAccount;
+use aptos_temppath::TempPath;
 use aptos_types::{
     account_address::AccountAddress,
     transaction::{
         authenticator::{AuthenticationKey, TransactionAuthenticator},
-        EntryFunction, Script, SignedTransaction,
+        EntryFunction, Script, SignedTransaction, Transaction,
     },
     utility_coin::APTOS_COIN_TYPE,
 };
@@ -83,6 +88,41 @@ async fn test_get_transactions_with_invalid_start_version_param() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_with_field_selection() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .get("/transactions?start=0&limit=1&fields=version,hash")
+        .await;
+    let expected = context.get("/transactions?start=0&limit=1").await;
+    assert_eq!(
+        resp,
+        json!([{
+            "version": expected[0]["version"],
+            "hash": expected[0]["hash"],
+        }])
+    );
+
+    let resp = context
+        .get("/transactions/by_version/0?fields=changes.type")
+        .await;
+    let changes = resp["changes"].as_array().unwrap();
+    assert!(!changes.is_empty());
+    assert!(changes
+        .iter()
+        .all(|change| change.as_object().unwrap().keys().eq(["type"].iter())));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_with_invalid_field_selection() {
+    let mut context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .get("/transactions?fields=hash;version")
+        .await;
+    context.check_golden_output(resp);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_transactions_with_invalid_limit_param() {
     let mut context = new_test_context(current_function_name!());
@@ -143,6 +183,224 @@ async fn test_post_bcs_format_transaction() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_transaction_rejected_by_transaction_filter() {
+    let policy_dir = TempPath::new();
+    policy_dir.create_as_dir().unwrap();
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let policy = TransactionFilterPolicy {
+        version: 1,
+        rules: vec![TransactionFilterRule {
+            entry_function: Some("0x1::aptos_account::transfer".to_string()),
+            ..Default::default()
+        }],
+    };
+    let signed_policy = SignedTransactionFilterPolicy::sign(policy, &private_key).unwrap();
+    let mut node_config = NodeConfig::default();
+    let filter_config = &mut node_config.mempool.transaction_filter;
+    filter_config.enabled = true;
+    filter_config.policy_path = policy_dir.path().join("transaction_filter_policy.json");
+    filter_config.policy_public_key = Some(private_key.public_key());
+    filter_config.applied_version_path =
+        policy_dir.path().join("transaction_filter_policy_version");
+    std::fs::write(
+        &filter_config.policy_path,
+        serde_json::to_vec(&signed_policy).unwrap(),
+    )
+    .unwrap();
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    // The transfers match the rule, so they are rejected before reaching mempool
+    let mut root_account = context.root_account().await;
+    let account = context.gen_account();
+    let txn = context.account_transfer_to(&mut root_account, account.address(), 1);
+    let resp = context
+        .expect_status_code(403)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn).unwrap())
+        .await;
+    assert_eq!(resp["error_code"], "transaction_filtered");
+
+    // The other transactions are accepted
+    let txn = context.create_user_account(&account).await;
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn).unwrap())
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_wait_for_committed_timeout() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+
+    // The transaction is never committed, so the wait times out and it is still pending
+    let resp = context
+        .expect_status_code(202)
+        .post_bcs_txn(
+            "/transactions?wait_for=committed&wait_timeout_secs=1",
+            bcs::to_bytes(&txn).unwrap(),
+        )
+        .await;
+    assert_eq!(
+        resp["hash"].as_str().unwrap(),
+        txn.committed_hash().to_hex_literal()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_wait_for_committed() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+
+    // The request is held until the transaction is committed
+    let waiting_context = context.expect_status_code(200);
+    let (resp, ()) = tokio::join!(
+        waiting_context.post_bcs_txn(
+            "/transactions?wait_for=committed&wait_timeout_secs=30",
+            bcs::to_bytes(&txn).unwrap(),
+        ),
+        async {
+            while context.mempool.get_txns(1).is_empty() {
+                sleep(Duration::from_millis(10)).await;
+            }
+            context.commit_mempool_txns(1).await;
+        },
+    );
+    assert_eq!(
+        resp["hash"].as_str().unwrap(),
+        txn.committed_hash().to_hex_literal()
+    );
+    context
+        .get(&format!(
+            "/transactions/by_hash/{}",
+            txn.committed_hash().to_hex_literal()
+        ))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_wait_for_executed() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+
+    // The request is held until mempool reports the transaction as executed, even though it
+    // can't be read from storage yet
+    let waiting_context = context.expect_status_code(200);
+    let (resp, ()) = tokio::join!(
+        waiting_context.post_bcs_txn(
+            "/transactions?wait_for=executed&wait_timeout_secs=30",
+            bcs::to_bytes(&txn).unwrap(),
+        ),
+        async {
+            while context.mempool.get_txns(1).is_empty() {
+                sleep(Duration::from_millis(10)).await;
+            }
+            context.mempool.remove_txn(&txn);
+        },
+    );
+    assert_eq!(
+        resp["hash"].as_str().unwrap(),
+        txn.committed_hash().to_hex_literal()
+    );
+    context
+        .expect_status_code(404)
+        .get(&format!(
+            "/transactions/by_hash/{}",
+            txn.committed_hash().to_hex_literal()
+        ))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_wait_for_committed_after_executed() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+
+    // Waiting for the commit doesn't return once the transaction is only executed
+    let waiting_context = context.expect_status_code(202);
+    let (resp, ()) = tokio::join!(
+        waiting_context.post_bcs_txn(
+            "/transactions?wait_for=committed&wait_timeout_secs=1",
+            bcs::to_bytes(&txn).unwrap(),
+        ),
+        async {
+            while context.mempool.get_txns(1).is_empty() {
+                sleep(Duration::from_millis(10)).await;
+            }
+            context.mempool.remove_txn(&txn);
+        },
+    );
+    assert_eq!(
+        resp["hash"].as_str().unwrap(),
+        txn.committed_hash().to_hex_literal()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_wait_for_committed_replaced() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let factory = context.transaction_factory();
+    let sign = |root: &mut LocalAccount, gas_unit_price| {
+        root.sign_with_transaction_builder(
+            factory
+                .create_user_account(account.public_key())
+                .gas_unit_price(gas_unit_price)
+                .expiration_timestamp_secs(u64::MAX),
+        )
+    };
+    let txn = sign(&mut context.root_account().await, 100);
+    let replacement = sign(&mut context.root_account().await, 1000);
+
+    // The waiting transaction is replaced in mempool, so the wait fails
+    let waiting_context = context.expect_status_code(400);
+    let (resp, _) = tokio::join!(
+        waiting_context.post_bcs_txn(
+            "/transactions?wait_for=committed&wait_timeout_secs=30",
+            bcs::to_bytes(&txn).unwrap(),
+        ),
+        async {
+            while context.mempool.get_txns(1).is_empty() {
+                sleep(Duration::from_millis(10)).await;
+            }
+            context
+                .expect_status_code(202)
+                .post_bcs_txn("/transactions", bcs::to_bytes(&replacement).unwrap())
+                .await
+        },
+    );
+    assert_eq!(resp["error_code"], "transaction_evicted");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_wait_for_committed_too_many_waiters() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_submit_waiters = 0;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+
+    // No submission can wait, so the transaction is only submitted
+    let resp = tokio::time::timeout(
+        Duration::from_secs(10),
+        context.expect_status_code(202).post_bcs_txn(
+            "/transactions?wait_for=committed&wait_timeout_secs=30",
+            bcs::to_bytes(&txn).unwrap(),
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        resp["hash"].as_str().unwrap(),
+        txn.committed_hash().to_hex_literal()
+    );
+    assert_eq!(context.mempool.get_txns(1), vec![txn]);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_post_invalid_bcs_format_transaction() {
     let mut context = new_test_context(current_function_name!());
@@ -453,6 +711,106 @@ async fn test_get_transaction_by_version() {
     assert_json(resp, txns[0].clone())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transaction_write_set_changes() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    // The full write set is returned, with resources decoded from the stored
+    // transaction output
+    let resp = context.get("/transactions/by_version/2").await;
+    let changes = resp["changes"].as_array().unwrap();
+    let account_resource = changes
+        .iter()
+        .find(|change| {
+            change["type"] == "write_resource"
+                && change["address"] == account.address().to_hex_literal()
+                && change["data"]["type"] == "0x1::account::Account"
+        })
+        .unwrap_or_else(|| panic!("account resource not in changes: {}", pretty(&resp)));
+    assert_eq!(account_resource["data"]["data"]["sequence_number"], "0");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_with_cursor() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    // Page through all transactions, starting from genesis
+    let mut path = "/v1/transactions?start=0&limit=1".to_string();
+    let mut versions = vec![];
+    let (cursor, ledger_version) = loop {
+        let req = warp::test::request().method("GET").path(&path);
+        let resp = context.reply(req).await;
+        assert_eq!(resp.status(), 200);
+
+        let cursor = resp.headers()["X-Aptos-Cursor"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let txns: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        let txns = txns.as_array().unwrap();
+        if txns.is_empty() {
+            let ledger_version = resp.headers()["X-Aptos-Ledger-Version"]
+                .to_str()
+                .unwrap()
+                .parse::<u64>()
+                .unwrap();
+            break (cursor, ledger_version);
+        }
+        versions.push(txns[0]["version"].as_str().unwrap().parse::<u64>().unwrap());
+        path = format!("/v1/transactions?cursor={}&limit=1", cursor);
+    };
+    assert_eq!(versions, (0..=ledger_version).collect::<Vec<_>>());
+
+    // The start and cursor can't be combined
+    context
+        .expect_status_code(400)
+        .get(&format!("/transactions?start=0&cursor={}", cursor))
+        .await;
+
+    // Cursors from ledger versions ahead of the node are rejected
+    let cursor = PageCursor::new(ledger_version + 100, 0);
+    context
+        .expect_status_code(404)
+        .get(&format!("/transactions?cursor={}", cursor))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transaction_proof_by_version() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn.clone()]).await;
+
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", mime_types::BCS)
+        .path("/v1/transactions/by_version/2/proof");
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+
+    let proof: TransactionWithLedgerInfo = bcs::from_bytes(resp.body()).unwrap();
+    assert_eq!(proof.transaction_with_proof.version, 2);
+    assert_eq!(
+        proof.transaction_with_proof.transaction,
+        Transaction::UserTransaction(txn)
+    );
+    proof.verify().unwrap();
+
+    // Versions ahead of the ledger are not found
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", mime_types::BCS)
+        .path("/v1/transactions/by_version/10000/proof");
+    assert_eq!(context.reply(req).await.status(), 404);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_pending_transaction_by_hash() {
     let mut context = new_test_context(current_function_name!());
@@ -488,6 +846,49 @@ async fn test_get_pending_transaction_by_hash() {
     context.check_golden_output(not_found);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_mempool_account_diagnostics() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.mempool_diagnostics_enabled = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+    let pending_txn = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+
+    let diagnostics = context
+        .get(&format!(
+            "/experimental/mempool/accounts/{}",
+            txn.sender().to_hex_literal()
+        ))
+        .await;
+    let pending_txns = diagnostics["pending_transactions"].as_array().unwrap();
+    assert_eq!(pending_txns.len(), 1);
+    assert_eq!(pending_txns[0]["hash"], pending_txn["hash"]);
+    assert_eq!(
+        pending_txns[0]["sequence_number"],
+        txn.sequence_number().to_string()
+    );
+    assert!(diagnostics["gaps"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_mempool_account_diagnostics_disabled() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+
+    context
+        .expect_status_code(403)
+        .get(&format!(
+            "/experimental/mempool/accounts/{}",
+            account.address().to_hex_literal()
+        ))
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_signing_message_with_entry_function_payload() {
     let mut context = new_test_context(current_function_name!());
@@ -1105,6 +1506,84 @@ async fn test_create_signing_message_rejects_no_content_length_request() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_state_overrides() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let receiver = context.root_account().await.address();
+    let payload = json!({
+        "type": "entry_function_payload",
+        "function": "0x1::aptos_account::transfer",
+        "type_arguments": [],
+        "arguments": [receiver.to_hex_literal(), "1000"]
+    });
+
+    // The new account has no funds, so the transfer fails
+    let resp = context
+        .simulate_transaction(&account, payload.clone(), 200)
+        .await;
+    assert!(!resp[0]["success"].as_bool().unwrap());
+
+    // With an overridden balance, the transfer succeeds
+    let resp = context
+        .simulate_transaction_with_state_overrides(
+            &account,
+            payload.clone(),
+            Some(json!([{
+                "type": "balance_override",
+                "address": account.address().to_hex_literal(),
+                "amount": "1000",
+            }])),
+            200,
+        )
+        .await;
+    assert!(resp[0]["success"].as_bool().unwrap(), "{}", pretty(&resp));
+
+    // Overrides are applied in order, so deleting the coin store afterwards
+    // makes the transfer fail again
+    let resp = context
+        .simulate_transaction_with_state_overrides(
+            &account,
+            payload.clone(),
+            Some(json!([
+                {
+                    "type": "balance_override",
+                    "address": account.address().to_hex_literal(),
+                    "amount": "1000",
+                },
+                {
+                    "type": "resource_override",
+                    "address": account.address().to_hex_literal(),
+                    "resource_type": "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                },
+            ])),
+            200,
+        )
+        .await;
+    assert!(!resp[0]["success"].as_bool().unwrap());
+
+    // Invalid resource data is rejected
+    context
+        .simulate_transaction_with_state_overrides(
+            &account,
+            payload,
+            Some(json!([{
+                "type": "resource_override",
+                "address": account.address().to_hex_literal(),
+                "resource_type": "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                "data": {"coin": "invalid"},
+            }])),
+            400,
+        )
+        .await;
+
+    // The overrides are never persisted
+    assert_eq!(context.get_apt_balance(account.address()).await, 0);
+}
+
 // Note: in tests, the min gas unit price is 0
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_gas_estimation_empty() {
@@ -1187,6 +1666,7 @@ async fn test_gas_estimation_cache() {
     node_config.api.gas_estimation.low_block_history = max_block_history;
     node_config.api.gas_estimation.market_block_history = max_block_history;
     node_config.api.gas_estimation.aggressive_block_history = max_block_history;
+    node_config.api.gas_estimation.percentile_block_history = max_block_history;
     let mut context = new_test_context_with_config(current_function_name!(), node_config);
 
     let ctx = &mut context;
@@ -1232,6 +1712,32 @@ async fn test_gas_estimation_cache() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_gas_caches_invalidated_on_epoch_change() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.gas_estimation.enabled = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    // Populate the caches
+    let ctx = &mut context;
+    for _i in 0..5 {
+        ctx.commit_block(&[]).await;
+    }
+    ctx.get("/estimate_gas_price").await;
+    let epoch = ctx.last_updated_gas_schedule().unwrap();
+    assert_eq!(ctx.last_updated_gas_estimation_cache_size(), 4);
+
+    // Verify the caches are kept for the same epoch
+    ctx.context.invalidate_caches_for_epoch(epoch);
+    assert_eq!(ctx.last_updated_gas_schedule(), Some(epoch));
+    assert_eq!(ctx.last_updated_gas_estimation_cache_size(), 4);
+
+    // Verify the caches are dropped for a new epoch
+    ctx.context.invalidate_caches_for_epoch(epoch + 1);
+    assert_eq!(ctx.last_updated_gas_schedule(), None);
+    assert_eq!(ctx.last_updated_gas_estimation_cache_size(), 0);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_gas_estimation_disabled() {
     let mut node_config = NodeConfig::default();
@@ -1260,6 +1766,141 @@ async fn test_gas_estimation_disabled() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_estimate_gas_price_for_payload() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let root = context.root_account().await.address();
+    let transfer = |amount: &str| {
+        json!({
+            "type": "entry_function_payload",
+            "function": "0x1::aptos_account::transfer",
+            "type_arguments": [],
+            "arguments": [account.address().to_hex_literal(), amount]
+        })
+    };
+
+    // The payload is simulated without the sender's key
+    let resp = context
+        .post(
+            "/estimate_gas_price",
+            json!({"sender": root.to_hex_literal(), "payload": transfer("1000")}),
+        )
+        .await;
+    assert!(resp["gas_estimate"].is_u64());
+    assert!(resp["max_gas_amount_estimate"].as_u64().unwrap() > 0);
+
+    // Payloads that fail simulation have no estimate
+    let resp = context
+        .expect_status_code(400)
+        .post(
+            "/estimate_gas_price",
+            json!({"sender": account.address().to_hex_literal(), "payload": transfer("1000")}),
+        )
+        .await;
+    assert_eq!(resp["error_code"], "vm_error");
+
+    // Nor do senders that don't exist
+    context
+        .expect_status_code(400)
+        .post(
+            "/estimate_gas_price",
+            json!({"sender": "0x1234", "payload": transfer("1000")}),
+        )
+        .await;
+
+    // The account balances are unchanged
+    assert_eq!(context.get_apt_balance(account.address()).await, 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_sponsored_transaction() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.gas_station.sponsored_simulation_enabled = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let root = context.root_account().await.address();
+    let request = |sender: &str, fee_payer: &str| {
+        json!({
+            "sender": sender,
+            "fee_payer_address": fee_payer,
+            "payload": {
+                "type": "entry_function_payload",
+                "function": "0x1::aptos_account::transfer",
+                "type_arguments": [],
+                "arguments": [root.to_hex_literal(), "0"]
+            },
+            "gas_unit_price": "100",
+        })
+    };
+
+    // The new account has no funds, but its transaction is paid for by the fee payer,
+    // and neither of them has signed it
+    let resp = context
+        .post(
+            "/transactions/simulate_sponsored",
+            request(&account.address().to_hex_literal(), &root.to_hex_literal()),
+        )
+        .await;
+    let txn = &resp["transaction"];
+    assert!(txn["success"].as_bool().unwrap(), "{}", pretty(&resp));
+    assert_eq!(txn["sender"], account.address().to_hex_literal());
+    assert_eq!(txn["sequence_number"], "0");
+    assert_ne!(txn["gas_used"], "0");
+    assert!(resp["sponsorship"].is_null());
+
+    // A fee payer without funds can't pay for the transaction
+    let resp = context
+        .post(
+            "/transactions/simulate_sponsored",
+            request(&root.to_hex_literal(), &account.address().to_hex_literal()),
+        )
+        .await;
+    assert!(!resp["transaction"]["success"].as_bool().unwrap());
+
+    // Senders that don't exist are rejected
+    context
+        .expect_status_code(400)
+        .post(
+            "/transactions/simulate_sponsored",
+            request("0x1234", &root.to_hex_literal()),
+        )
+        .await;
+
+    // Nothing is committed
+    assert_eq!(context.get_apt_balance(account.address()).await, 0);
+    assert_eq!(context.get_sequence_number(account.address()).await, 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_sponsored_transaction_disabled_by_default() {
+    let mut context = new_test_context(current_function_name!());
+    let root = context.root_account().await.address();
+    let resp = context
+        .expect_status_code(403)
+        .post(
+            "/transactions/simulate_sponsored",
+            json!({
+                "sender": root.to_hex_literal(),
+                "fee_payer_address": root.to_hex_literal(),
+                "payload": {
+                    "type": "entry_function_payload",
+                    "function": "0x1::aptos_account::transfer",
+                    "type_arguments": [],
+                    "arguments": [root.to_hex_literal(), "0"]
+                },
+            }),
+        )
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_gas_estimation_static_override() {
     let mut node_config = NodeConfig::default();
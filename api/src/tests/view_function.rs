@@ -3,8 +3,21 @@
 
 use super::new_test_context;
 use aptos_api_test_context::current_function_name;
+use aptos_types::{
+    account_address::AccountAddress, transaction::EntryFunction, utility_coin::APTOS_COIN_TYPE,
+};
+use move_core_types::{ident_str, language_storage::ModuleId};
 use serde_json::json;
 
+fn balance_view(address: AccountAddress) -> EntryFunction {
+    EntryFunction::new(
+        ModuleId::new(AccountAddress::ONE, ident_str!("coin").to_owned()),
+        ident_str!("balance").to_owned(),
+        vec![APTOS_COIN_TYPE.clone()],
+        vec![bcs::to_bytes(&address).unwrap()],
+    )
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_simple_view() {
     let mut context = new_test_context(current_function_name!());
@@ -79,6 +92,100 @@ async fn test_versioned_simple_view() {
     context.check_golden_output_no_prune(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch() {
+    let mut context = new_test_context(current_function_name!());
+    let creator = &mut context.gen_account();
+    let owner = &mut context.gen_account();
+    let txn1 = context.mint_user_account(creator).await;
+    let txn2 = context.account_transfer(creator, owner, 100_000);
+
+    context.commit_block(&vec![txn1, txn2]).await;
+
+    let resp = context
+        .post(
+            "/view/batch",
+            json!([
+                {
+                    "function":"0x1::coin::balance",
+                    "arguments": vec![owner.address().to_string()],
+                    "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+                },
+                {
+                    "function":"0x1::aptos_account::assert_account_exists",
+                    "arguments": vec![owner.address().to_string()],
+                    "type_arguments": [],
+                },
+            ]),
+        )
+        .await;
+
+    // The failing view function does not fail the batch
+    let results = resp.as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["result"], json!(["100000"]));
+    assert!(results[0]["error"].is_null());
+    assert!(results[1]["result"].is_null());
+    assert_eq!(results[1]["error"]["error_code"], json!("invalid_input"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_bcs() {
+    let mut context = new_test_context(current_function_name!());
+    let creator = &mut context.gen_account();
+    let owner = &mut context.gen_account();
+    let txn1 = context.mint_user_account(creator).await;
+    let txn2 = context.account_transfer(creator, owner, 100_000);
+
+    context.commit_block(&vec![txn1, txn2]).await;
+
+    let request = balance_view(owner.address());
+    let resp = context
+        .post_bcs_view("/view", bcs::to_bytes(&request).unwrap())
+        .await;
+    assert_eq!(resp, json!(["100000"]));
+
+    let resp = context
+        .post_bcs_view(
+            "/view/batch",
+            bcs::to_bytes(&vec![request.clone(), balance_view(creator.address())]).unwrap(),
+        )
+        .await;
+    let results = resp.as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["result"], json!(["100000"]));
+
+    // Invalid BCS is rejected
+    context
+        .expect_status_code(400)
+        .post_bcs_view("/view", vec![0xFF])
+        .await;
+    context
+        .expect_status_code(400)
+        .post_bcs_view("/view/batch", bcs::to_bytes(&request).unwrap())
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch_too_large() {
+    let mut context = new_test_context(current_function_name!());
+    let max_batch_size = context.context.max_view_function_batch_size();
+    let requests: Vec<_> = (0..=max_batch_size)
+        .map(|_| {
+            json!({
+                "function":"0x1::coin::balance",
+                "arguments": vec!["0x1"],
+                "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+            })
+        })
+        .collect();
+
+    context
+        .expect_status_code(400)
+        .post("/view/batch", json!(requests))
+        .await;
+}
+
 #[ignore] // TODO: reactivate with real source
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_view_tuple() {
@@ -11,48 +11,90 @@ use crate::{
     generate_error_response, generate_success_response,
     page::Page,
     response::{
-        api_disabled, api_forbidden, transaction_not_found_by_hash,
-        transaction_not_found_by_version, version_pruned, BadRequestError, BasicError,
-        BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
-        InsufficientStorageError, InternalError,
+        api_disabled, api_forbidden, check_field_selection, transaction_expired_by_hash,
+        transaction_not_found_by_hash, transaction_not_found_by_version, version_pruned,
+        BadRequestError, BasicError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+        BasicResult, BasicResultWith404, InsufficientStorageError, InternalError,
+        ServiceUnavailableError,
     },
+    state_override::OverriddenStateView,
     ApiTags,
 };
 use anyhow::{anyhow, Context as AnyhowContext};
 use aptos_api_types::{
     verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
-    AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
-    HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
+    AsConverter, EncodeSubmissionRequest, EstimateGasPriceRequest, FieldSelection, GasEstimation,
+    GasEstimationBcs, HashValue, HexEncodedBytes, LedgerInfo, MempoolAccountDiagnostics,
+    MempoolPendingTransaction, MempoolSequenceNumberGap, MoveType, MoveValue, PageCursor,
+    PendingTransaction, Projected, SimulateSponsoredTransactionRequest, SimulateTransactionRequest,
+    SponsoredTransactionSimulation, SponsorshipRequest, StateOverride, SubmitTransactionRequest,
+    Transaction, TransactionData, TransactionExpirationBehavior, TransactionOnChainData,
+    TransactionTtlTier, TransactionWaitStage, TransactionsBatchSingleSubmissionFailure,
     TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
     MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
-use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_config::network_id::PeerNetworkId;
+use aptos_crypto::{
+    ed25519::{
+        Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature, ED25519_PRIVATE_KEY_LENGTH,
+        ED25519_SIGNATURE_LENGTH,
+    },
+    hash::CryptoHash,
+    signing_message,
+};
+use aptos_mempool::{
+    AccountSequenceNumberDiagnostics, EvictionReason, ExpirationBehavior, MempoolTransactionEvent,
+    MempoolTransactionEventKind, SubmissionOptions, TtlTier,
+};
+use aptos_state_view::account_with_state_view::AsAccountWithStateView;
+use aptos_storage_interface::state_view::DbStateView;
 use aptos_types::{
-    account_config::CoinStoreResource,
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::{AccountResource, CoinStoreResource},
     account_view::AccountView,
+    chain_id::ChainId,
     mempool_status::MempoolStatusCode,
+    state_store::state_key::StateKey,
     transaction::{
+        authenticator::{AccountAuthenticator, AuthenticationKey},
         EntryFunction, ExecutionStatus, MultisigTransactionPayload, RawTransaction,
-        RawTransactionWithData, SignedTransaction, TransactionPayload, TransactionStatus,
+        RawTransactionWithData, SignedTransaction, TransactionOutput, TransactionPayload,
+        TransactionStatus,
     },
     vm_status::StatusCode,
 };
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use move_core_types::{
+    language_storage::{StructTag, TypeTag},
+    move_resource::MoveStructType,
+};
 use poem_openapi::{
     param::{Path, Query},
     payload::Json,
     ApiRequest, OpenApi,
 };
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+use tokio::sync::{broadcast, broadcast::error::RecvError, watch};
+
+/// How long transactions simulated for gas estimation are valid, from the ledger timestamp
+const SIMULATION_EXPIRATION_SECS: u64 = 60;
 
-generate_success_response!(SubmitTransactionResponse, (202, Accepted));
+/// Interval to check for new commits while waiting for a submitted transaction, when
+/// storage commit notifications are unavailable (e.g., in tests)
+const SUBMIT_WAIT_POLL_INTERVAL_MS: u64 = 100;
+
+generate_success_response!(SubmitTransactionResponse, (200, Ok), (202, Accepted));
 
 generate_error_response!(
     SubmitTransactionError,
     (400, BadRequest),
     (403, Forbidden),
     (413, PayloadTooLarge),
+    (429, TooManyRequests),
     (500, Internal),
     (503, ServiceUnavailable),
     (507, InsufficientStorage)
@@ -99,6 +141,39 @@ impl VerifyInput for SubmitTransactionPost {
     }
 }
 
+// We need a custom type here because we use different types for each of the
+// content types possible for the POST data.
+#[derive(ApiRequest, Debug)]
+pub enum SimulateTransactionPost {
+    #[oai(content_type = "application/json")]
+    Json(Json<SimulateTransactionRequest>),
+
+    #[oai(content_type = "application/x.aptos.signed_transaction+bcs")]
+    Bcs(Bcs),
+}
+
+impl SimulateTransactionPost {
+    /// Splits the request into the transaction and the state overrides
+    fn into_parts(self) -> (SubmitTransactionPost, Vec<StateOverride>) {
+        match self {
+            SimulateTransactionPost::Json(inner) => (
+                SubmitTransactionPost::Json(Json(inner.0.transaction)),
+                inner.0.state_overrides.unwrap_or_default(),
+            ),
+            SimulateTransactionPost::Bcs(inner) => (SubmitTransactionPost::Bcs(inner), vec![]),
+        }
+    }
+}
+
+impl VerifyInput for SimulateTransactionPost {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            SimulateTransactionPost::Json(inner) => inner.0.verify(),
+            SimulateTransactionPost::Bcs(_) => Ok(()),
+        }
+    }
+}
+
 // We need a custom type here because we use different types for each of the
 // content types possible for the POST data.
 #[derive(ApiRequest, Debug)]
@@ -159,16 +234,30 @@ impl TransactionsApi {
         ///
         /// If not provided, defaults to default page size
         limit: Query<Option<u16>>,
-    ) -> BasicResultWith404<Vec<Transaction>> {
+        /// Cursor to the next page of transactions
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// use the cursor returned in the X-Aptos-Cursor header of a previous
+        /// response. It can't be combined with `start`.
+        cursor: Query<Option<PageCursor>>,
+        /// Comma separated list of fields to include in the response
+        ///
+        /// Nested fields are separated by dots, e.g. `hash,success,payload.function`.
+        /// If not provided, all fields are returned. Only supported for JSON output.
+        fields: Query<Option<FieldSelection>>,
+    ) -> BasicResultWith404<Projected<Vec<Transaction>>> {
         fail_point_poem("endpoint_get_transactions")?;
         self.context
             .check_api_output_enabled("Get transactions", &accept_type)?;
+        check_field_selection("Get transactions", &accept_type, &fields.0)?;
         let page = Page::new(
             start.0.map(|v| v.0),
             limit.0,
             self.context.max_transactions_page_size(),
-        );
+        )
+        .with_cursor(cursor.0);
         self.list(&accept_type, page)
+            .map(|response| response.map_json(|txns| Projected::new(txns, fields.0)))
     }
 
     /// Get transaction by hash
@@ -196,13 +285,20 @@ impl TransactionsApi {
         accept_type: AcceptType,
         /// Hash of transaction to retrieve
         txn_hash: Path<HashValue>,
+        /// Comma separated list of fields to include in the response
+        ///
+        /// Nested fields are separated by dots, e.g. `hash,success,payload.function`.
+        /// If not provided, all fields are returned. Only supported for JSON output.
+        fields: Query<Option<FieldSelection>>,
         // TODO: Use a new request type that can't return 507.
-    ) -> BasicResultWith404<Transaction> {
+    ) -> BasicResultWith404<Projected<Transaction>> {
         fail_point_poem("endpoint_transaction_by_hash")?;
         self.context
             .check_api_output_enabled("Get transactions by hash", &accept_type)?;
+        check_field_selection("Get transactions by hash", &accept_type, &fields.0)?;
         self.get_transaction_by_hash_inner(&accept_type, txn_hash.0)
             .await
+            .map(|response| response.map_json(|txn| Projected::new(txn, fields.0)))
     }
 
     /// Get transaction by version
@@ -220,12 +316,51 @@ impl TransactionsApi {
         accept_type: AcceptType,
         /// Version of transaction to retrieve
         txn_version: Path<U64>,
-    ) -> BasicResultWith404<Transaction> {
+        /// Comma separated list of fields to include in the response
+        ///
+        /// Nested fields are separated by dots, e.g. `hash,success,payload.function`.
+        /// If not provided, all fields are returned. Only supported for JSON output.
+        fields: Query<Option<FieldSelection>>,
+    ) -> BasicResultWith404<Projected<Transaction>> {
         fail_point_poem("endpoint_transaction_by_version")?;
         self.context
             .check_api_output_enabled("Get transactions by version", &accept_type)?;
+        check_field_selection("Get transactions by version", &accept_type, &fields.0)?;
         self.get_transaction_by_version_inner(&accept_type, txn_version.0)
             .await
+            .map(|response| response.map_json(|txn| Projected::new(txn, fields.0)))
+    }
+
+    /// Get transaction proof by version
+    ///
+    /// Retrieves a transaction by a given version, together with the proof authenticating
+    /// it against the latest signed ledger info. The response is a BCS encoded
+    /// `TransactionWithLedgerInfo`, allowing light clients to verify the transaction
+    /// independently. If the version has been pruned, a 410 will be returned.
+    ///
+    /// Only BCS is supported as an AcceptType.
+    #[oai(
+        path = "/transactions/by_version/:txn_version/proof",
+        method = "get",
+        operation_id = "get_transaction_proof_by_version",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_transaction_proof_by_version(
+        &self,
+        accept_type: AcceptType,
+        /// Version of transaction to retrieve
+        txn_version: Path<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_transaction_proof_by_version")?;
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get transaction proof by version",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get transaction proof by version", &accept_type)?;
+        self.get_transaction_proof_by_version_inner(txn_version.0)
     }
 
     /// Get account transactions
@@ -255,16 +390,30 @@ impl TransactionsApi {
         ///
         /// If not provided, defaults to default page size
         limit: Query<Option<u16>>,
-    ) -> BasicResultWith404<Vec<Transaction>> {
+        /// Cursor to the next page of transactions
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// use the cursor returned in the X-Aptos-Cursor header of a previous
+        /// response. It can't be combined with `start`.
+        cursor: Query<Option<PageCursor>>,
+        /// Comma separated list of fields to include in the response
+        ///
+        /// Nested fields are separated by dots, e.g. `hash,success,payload.function`.
+        /// If not provided, all fields are returned. Only supported for JSON output.
+        fields: Query<Option<FieldSelection>>,
+    ) -> BasicResultWith404<Projected<Vec<Transaction>>> {
         fail_point_poem("endpoint_get_accounts_transactions")?;
         self.context
             .check_api_output_enabled("Get account transactions", &accept_type)?;
+        check_field_selection("Get account transactions", &accept_type, &fields.0)?;
         let page = Page::new(
             start.0.map(|v| v.0),
             limit.0,
             self.context.max_transactions_page_size(),
-        );
+        )
+        .with_cursor(cursor.0);
         self.list_by_account(&accept_type, page, address.0)
+            .map(|response| response.map_json(|txns| Projected::new(txns, fields.0)))
     }
 
     /// Submit transaction
@@ -285,6 +434,11 @@ impl TransactionsApi {
     /// To submit a transaction as BCS, you must submit a SignedTransaction
     /// encoded as BCS. See SignedTransaction in types/src/transaction/mod.rs.
     /// Make sure to use the `application/x.aptos.signed_transaction+bcs` Content-Type.
+    ///
+    /// To avoid polling for the transaction afterwards, set `wait_for` to hold the request
+    /// until the transaction is executed or committed. The response is a 200 once the stage
+    /// is reached, or a 202 if the wait timed out and the transaction is still pending. If too
+    /// many submissions are already waiting, the request returns without waiting (with a 202).
     // TODO: Point to examples of both of these flows, in multiple languages.
     #[oai(
         path = "/transactions",
@@ -295,6 +449,18 @@ impl TransactionsApi {
     async fn submit_transaction(
         &self,
         accept_type: AcceptType,
+        /// How long the transaction can stay pending in mempool, defaults to `standard`
+        ttl_tier: Query<Option<TransactionTtlTier>>,
+        /// What happens when the transaction expires in mempool, defaults to `drop`.
+        /// With `notify`, looking up the expired transaction by hash fails with the
+        /// `transaction_expired` error code instead of the transaction not being found.
+        expiration_behavior: Query<Option<TransactionExpirationBehavior>>,
+        /// The stage the transaction must reach before the request returns. If not
+        /// set, the request returns as soon as the transaction is accepted by mempool.
+        wait_for: Query<Option<TransactionWaitStage>>,
+        /// How long to wait for the transaction to reach the `wait_for` stage, in
+        /// seconds. Defaults to, and is capped by, the maximum wait of the node.
+        wait_timeout_secs: Query<Option<u64>>,
         data: SubmitTransactionPost,
     ) -> SubmitTransactionResult<PendingTransaction> {
         data.verify()
@@ -313,8 +479,37 @@ impl TransactionsApi {
             .check_api_output_enabled("Submit transaction", &accept_type)?;
         let ledger_info = self.context.get_latest_ledger_info()?;
         let signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
-        self.create(&accept_type, &ledger_info, signed_transaction)
-            .await
+        let options = submission_options(ttl_tier.0, expiration_behavior.0);
+        match wait_for.0 {
+            None => {
+                self.create(&accept_type, &ledger_info, signed_transaction, options)
+                    .await
+            },
+            Some(stage) => {
+                // The number of waiting submissions is capped, as each one reads storage on
+                // every commit
+                let _waiter = match self.context.try_acquire_submit_waiter() {
+                    Some(waiter) => waiter,
+                    None => {
+                        return self
+                            .create(&accept_type, &ledger_info, signed_transaction, options)
+                            .await
+                    },
+                };
+                let max_wait_secs = self.context.node_config.api.max_submit_wait_secs;
+                let wait_secs = wait_timeout_secs.0.unwrap_or(max_wait_secs);
+                let timeout = Duration::from_secs(wait_secs.min(max_wait_secs));
+                self.create_and_wait(
+                    &accept_type,
+                    &ledger_info,
+                    signed_transaction,
+                    options,
+                    stage,
+                    timeout,
+                )
+                .await
+            },
+        }
     }
 
     /// Submit batch transactions
@@ -394,6 +589,11 @@ impl TransactionsApi {
     ///
     /// To use this endpoint with BCS, you must submit a SignedTransaction
     /// encoded as BCS. See SignedTransaction in types/src/transaction/mod.rs.
+    ///
+    /// With JSON, you may also provide `state_overrides` (e.g., resource values or
+    /// APT balances), which are applied on top of the latest ledger state before
+    /// the transaction is simulated. This allows testing "what-if" scenarios,
+    /// e.g., without funding the sender. State overrides are not supported with BCS.
     #[oai(
         path = "/transactions/simulate",
         method = "post",
@@ -412,7 +612,7 @@ impl TransactionsApi {
         /// If set to true, the transaction will use a higher price than the original
         /// estimate.
         estimate_prioritized_gas_unit_price: Query<Option<bool>>,
-        data: SubmitTransactionPost,
+        data: SimulateTransactionPost,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         data.verify()
             .context("Simulated transaction invalid")
@@ -429,7 +629,20 @@ impl TransactionsApi {
         self.context
             .check_api_output_enabled("Simulate transaction", &accept_type)?;
         let ledger_info = self.context.get_latest_ledger_info()?;
+        let (data, state_overrides) = data.into_parts();
+        if state_overrides.len() > self.context.max_simulation_state_overrides() {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                format!(
+                    "Submitted too many state overrides: {}, while limit is {}",
+                    state_overrides.len(),
+                    self.context.max_simulation_state_overrides(),
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
         let mut signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
+        let state_view = self.overridden_state_view(&ledger_info, state_overrides)?;
 
         let estimated_gas_unit_price = match (
             estimate_gas_unit_price.0.unwrap_or_default(),
@@ -450,64 +663,14 @@ impl TransactionsApi {
 
         // If estimate max gas amount is provided, we will just make it the maximum value
         let estimated_max_gas_amount = if estimate_max_gas_amount.0.unwrap_or_default() {
-            // Retrieve max possible gas units
-            let (_, gas_params) = self.context.get_gas_schedule(&ledger_info)?;
-            let min_number_of_gas_units = u64::from(gas_params.vm.txn.min_transaction_gas_units)
-                / u64::from(gas_params.vm.txn.gas_unit_scaling_factor);
-            let max_number_of_gas_units = u64::from(gas_params.vm.txn.maximum_number_of_gas_units);
-
-            // Retrieve account balance to determine max gas available
-            let account_state = self
-                .context
-                .get_account_state(
-                    signed_transaction.sender(),
-                    ledger_info.version(),
-                    &ledger_info,
-                )?
-                .ok_or_else(|| {
-                    SubmitTransactionError::bad_request_with_code(
-                        "Account not found",
-                        AptosErrorCode::InvalidInput,
-                        &ledger_info,
-                    )
-                })?;
-            let coin_store: CoinStoreResource = account_state
-                .get_coin_store_resource()
-                .and_then(|inner| {
-                    inner.ok_or_else(|| {
-                        anyhow!(
-                            "No coin store found for account {}",
-                            signed_transaction.sender()
-                        )
-                    })
-                })
-                .map_err(|err| {
-                    SubmitTransactionError::internal_with_code(
-                        format!("Failed to get coin store resource {}", err),
-                        AptosErrorCode::InternalError,
-                        &ledger_info,
-                    )
-                })?;
-
             let gas_unit_price =
                 estimated_gas_unit_price.unwrap_or_else(|| signed_transaction.gas_unit_price());
-
-            // With 0 gas price, we set it to max gas units, since we can't divide by 0
-            let max_account_gas_units = if gas_unit_price == 0 {
-                coin_store.coin()
-            } else {
-                coin_store.coin() / gas_unit_price
-            };
-
-            // To give better error messaging, we should not go below the minimum number of gas units
-            let max_account_gas_units =
-                std::cmp::max(min_number_of_gas_units, max_account_gas_units);
-
-            // Minimum of the max account and the max total needs to be used for estimation
-            Some(std::cmp::min(
-                max_account_gas_units,
-                max_number_of_gas_units,
-            ))
+            Some(self.max_gas_amount(
+                &ledger_info,
+                &state_view,
+                signed_transaction.sender(),
+                gas_unit_price,
+            )?)
         } else {
             None
         };
@@ -521,7 +684,7 @@ impl TransactionsApi {
             );
         }
 
-        self.simulate(&accept_type, ledger_info, signed_transaction)
+        self.simulate(&accept_type, ledger_info, &state_view, signed_transaction)
             .await
     }
 
@@ -587,6 +750,9 @@ impl TransactionsApi {
     /// (aggressive). Using a more aggressive value increases the likelihood that the transaction
     /// will make it into the next block; more aggressive values are computed with a larger history
     /// and higher percentile statistics. More details are in AIP-34.
+    ///
+    /// The p25, p50 and p90 percentiles of the minimum gas unit price required for inclusion in
+    /// recent blocks are also given, when there is enough history to compute them.
     #[oai(
         path = "/estimate_gas_price",
         method = "get",
@@ -599,24 +765,144 @@ impl TransactionsApi {
             .check_api_output_enabled("Estimate gas price", &accept_type)?;
         let latest_ledger_info = self.context.get_latest_ledger_info()?;
         let gas_estimation = self.context.estimate_gas_price(&latest_ledger_info)?;
+        Self::gas_estimation_response(&accept_type, gas_estimation, &latest_ledger_info)
+    }
 
-        match accept_type {
-            AcceptType::Json => BasicResponse::try_from_json((
-                gas_estimation,
-                &latest_ledger_info,
-                BasicResponseStatus::Ok,
-            )),
-            AcceptType::Bcs => {
-                let gas_estimation_bcs = GasEstimationBcs {
-                    gas_estimate: gas_estimation.gas_estimate,
-                };
-                BasicResponse::try_from_bcs((
-                    gas_estimation_bcs,
-                    &latest_ledger_info,
-                    BasicResponseStatus::Ok,
-                ))
-            },
+    /// Estimate gas price and max gas amount for a payload
+    ///
+    /// Gives the same estimates of the gas unit price as the GET variant of this endpoint, along
+    /// with a suggested max gas amount for the given transaction payload. The payload is
+    /// simulated from the given sender at the estimated gas unit price, and the suggested max gas
+    /// amount is the gas used by the simulation plus a safety margin.
+    ///
+    /// The sender's authentication key is not checked by the simulation, so no signature or
+    /// public key is needed. The simulation must succeed for an estimate to be returned.
+    #[oai(
+        path = "/estimate_gas_price",
+        method = "post",
+        operation_id = "estimate_gas_price_for_payload",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn estimate_gas_price_for_payload(
+        &self,
+        accept_type: AcceptType,
+        data: Json<EstimateGasPriceRequest>,
+    ) -> BasicResult<GasEstimation> {
+        data.0
+            .verify()
+            .context("'EstimateGasPriceRequest' invalid")
+            .map_err(|err| {
+                BasicError::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_estimate_gas_price_for_payload")?;
+        if !self.context.node_config.api.transaction_simulation_enabled {
+            return Err(api_disabled("Estimate gas price for payload"));
+        }
+        self.context
+            .check_api_output_enabled("Estimate gas price", &accept_type)?;
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let mut gas_estimation = self.context.estimate_gas_price(&latest_ledger_info)?;
+        gas_estimation.max_gas_amount_estimate = Some(self.estimate_max_gas_amount(
+            &latest_ledger_info,
+            data.0,
+            gas_estimation.gas_estimate,
+        )?);
+        Self::gas_estimation_response(&accept_type, gas_estimation, &latest_ledger_info)
+    }
+
+    /// Simulate sponsored transaction
+    ///
+    /// Simulates a transaction whose gas fees are paid by the given fee payer, without any
+    /// signatures. The authentication keys of the sender, the secondary signers and the fee
+    /// payer are not checked by the simulation, so a fee payer (e.g., a gas station) can use
+    /// this to check what it would pay for an arbitrary sender's transaction.
+    ///
+    /// If the node has a gas station configured, each successfully simulated transaction is
+    /// also sent to the gas station, and the response includes its sponsorship decision.
+    #[oai(
+        path = "/transactions/simulate_sponsored",
+        method = "post",
+        operation_id = "simulate_sponsored_transaction",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn simulate_sponsored_transaction(
+        &self,
+        accept_type: AcceptType,
+        data: Json<SimulateSponsoredTransactionRequest>,
+    ) -> BasicResult<SponsoredTransactionSimulation> {
+        data.0
+            .verify()
+            .context("'SimulateSponsoredTransactionRequest' invalid")
+            .map_err(|err| {
+                BasicError::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_simulate_sponsored_transaction")?;
+        let api_config = &self.context.node_config.api;
+        if !api_config.transaction_simulation_enabled
+            || !api_config.gas_station.sponsored_simulation_enabled
+        {
+            return Err(api_disabled("Simulate sponsored transaction"));
+        }
+        self.context
+            .check_api_output_enabled("Simulate sponsored transaction", &accept_type)?;
+        if accept_type == AcceptType::Bcs {
+            return Err(BasicError::bad_request_with_code_no_info(
+                "BCS is not supported for sponsored transaction simulation",
+                AptosErrorCode::BcsNotSupported,
+            ));
         }
+
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let simulation = self.simulate_sponsored(&ledger_info, data.0).await?;
+        BasicResponse::try_from_json((simulation, &ledger_info, BasicResponseStatus::Ok))
+    }
+
+    /// Get mempool account diagnostics
+    ///
+    /// Retrieves the mempool's view of the sequence numbers of an account: the sequence
+    /// number last known by mempool, the transactions of the account pending in mempool,
+    /// and the missing sequence numbers (gaps) that prevent them from being committed.
+    ///
+    /// This is a debug endpoint, and is disabled unless `mempool_diagnostics_enabled` is
+    /// set in the node's API config.
+    #[oai(
+        path = "/experimental/mempool/accounts/:address",
+        method = "get",
+        operation_id = "get_mempool_account_diagnostics",
+        tag = "ApiTags::Experimental",
+        hidden
+    )]
+    async fn get_mempool_account_diagnostics(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+    ) -> BasicResult<MempoolAccountDiagnostics> {
+        fail_point_poem("endpoint_get_mempool_account_diagnostics")?;
+        if !self.context.node_config.api.mempool_diagnostics_enabled {
+            return Err(api_disabled("Mempool diagnostics"));
+        }
+        self.context
+            .check_api_output_enabled("Get mempool account diagnostics", &accept_type)?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let diagnostics = self
+            .context
+            .get_mempool_sequence_number_diagnostics(address.0.into())
+            .await
+            .context(format!(
+                "Failed to get mempool diagnostics for account {}",
+                address.0
+            ))
+            .map_err(|err| {
+                BasicError::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+            })?;
+
+        BasicResponse::try_from_rust_value((
+            mempool_account_diagnostics(diagnostics),
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
     }
 }
 
@@ -625,26 +911,35 @@ impl TransactionsApi {
     fn list(&self, accept_type: &AcceptType, page: Page) -> BasicResultWith404<Vec<Transaction>> {
         let latest_ledger_info = self.context.get_latest_ledger_info()?;
         let ledger_version = latest_ledger_info.version();
+        page.verify_cursor(&latest_ledger_info)?;
 
         let limit = page.limit(&latest_ledger_info)?;
         let start_version = page.compute_start(limit, ledger_version, &latest_ledger_info)?;
-        let data = self
-            .context
-            .get_transactions(start_version, limit, ledger_version)
-            .context("Failed to read raw transactions from storage")
-            .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
-                    err,
-                    AptosErrorCode::InternalError,
-                    &latest_ledger_info,
-                )
-            })?;
+        let data = if start_version > ledger_version {
+            // The cursor points right after the latest transaction
+            vec![]
+        } else {
+            self.context
+                .get_transactions(start_version, limit, ledger_version)
+                .context("Failed to read raw transactions from storage")
+                .map_err(|err| {
+                    BasicErrorWith404::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &latest_ledger_info,
+                    )
+                })?
+        };
+        let cursor = PageCursor::new(ledger_version, start_version + data.len() as u64);
 
         match accept_type {
             AcceptType::Json => {
-                let timestamp = self
-                    .context
-                    .get_block_timestamp(&latest_ledger_info, start_version)?;
+                let timestamp = if data.is_empty() {
+                    0
+                } else {
+                    self.context
+                        .get_block_timestamp(&latest_ledger_info, start_version)?
+                };
                 BasicResponse::try_from_json((
                     self.context.render_transactions_sequential(
                         &latest_ledger_info,
@@ -659,6 +954,7 @@ impl TransactionsApi {
                 BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
             },
         }
+        .map(|response| response.with_page_cursor(Some(cursor)))
     }
 
     async fn get_transaction_by_hash_inner(
@@ -677,9 +973,31 @@ impl TransactionsApi {
                     AptosErrorCode::InternalError,
                     &ledger_info,
                 )
-            })?
-            .context(format!("Failed to find transaction with hash: {}", hash))
-            .map_err(|_| transaction_not_found_by_hash(hash, &ledger_info))?;
+            })?;
+        let txn_data = match txn_data {
+            Some(txn_data) => txn_data,
+            None => {
+                // Distinguish the transactions whose submitters asked to be notified of the
+                // expiration from the ones that were never seen
+                let is_expired = self
+                    .context
+                    .is_transaction_expired(hash.into())
+                    .await
+                    .context(format!("Failed to check if transaction {} expired", hash))
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+                return Err(if is_expired {
+                    transaction_expired_by_hash(hash, &ledger_info)
+                } else {
+                    transaction_not_found_by_hash(hash, &ledger_info)
+                });
+            },
+        };
 
         self.get_transaction_inner(accept_type, txn_data, &ledger_info)
             .await
@@ -714,6 +1032,44 @@ impl TransactionsApi {
         }
     }
 
+    fn get_transaction_proof_by_version_inner(
+        &self,
+        version: U64,
+    ) -> BasicResultWith404<MoveValue> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        if version.0 > ledger_info.version() {
+            return Err(transaction_not_found_by_version(version.0, &ledger_info));
+        }
+        if version.0 < ledger_info.oldest_version() {
+            return Err(version_pruned(version.0, &ledger_info));
+        }
+
+        let transaction_with_ledger_info = self
+            .context
+            .get_transaction_with_ledger_info(version.0)
+            .context(format!(
+                "Failed to get transaction proof by version {}",
+                version
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let bytes = bcs::to_bytes(&transaction_with_ledger_info)
+            .context("Failed serializing transaction proof")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+    }
+
     /// Converts a transaction into the outgoing type
     async fn get_transaction_inner(
         &self,
@@ -817,6 +1173,7 @@ impl TransactionsApi {
         account.get_account_resource()?;
 
         let latest_ledger_info = account.latest_ledger_info;
+        page.verify_cursor(&latest_ledger_info)?;
         // TODO: Return more specific errors from within this function.
         let data = self.context.get_account_transactions(
             address.into(),
@@ -825,6 +1182,13 @@ impl TransactionsApi {
             latest_ledger_info.version(),
             &latest_ledger_info,
         )?;
+        let next_sequence_number = data
+            .last()
+            .and_then(|txn| txn.transaction.try_as_signed_user_txn())
+            .map(|txn| txn.sequence_number() + 1)
+            .or_else(|| page.start_option())
+            .unwrap_or(0);
+        let cursor = PageCursor::new(latest_ledger_info.version(), next_sequence_number);
         match accept_type {
             AcceptType::Json => BasicResponse::try_from_json((
                 self.context
@@ -836,6 +1200,7 @@ impl TransactionsApi {
                 BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
             },
         }
+        .map(|response| response.with_page_cursor(Some(cursor)))
     }
 
     /// Parses a single signed transaction
@@ -1005,10 +1370,26 @@ impl TransactionsApi {
     }
 
     /// Submits a single transaction, and converts mempool codes to errors
-    async fn create_internal(&self, txn: SignedTransaction) -> Result<(), AptosError> {
+    async fn create_internal(
+        &self,
+        txn: SignedTransaction,
+        options: SubmissionOptions,
+    ) -> Result<(), AptosError> {
+        // Reject the transactions matching the filter policy before reaching mempool
+        let filter_result = self
+            .context
+            .transaction_filter()
+            .map_or(Ok(()), |transaction_filter| transaction_filter.check(&txn));
+        if let Err(mempool_status) = filter_result {
+            return Err(AptosError::new_with_error_code(
+                mempool_status.message,
+                AptosErrorCode::TransactionFiltered,
+            ));
+        }
+
         let (mempool_status, vm_status_opt) = self
             .context
-            .submit_transaction(txn)
+            .submit_transaction_with_options(txn, options)
             .await
             .context("Mempool failed to initially evaluate submitted transaction")
             .map_err(|err| {
@@ -1049,6 +1430,14 @@ impl TransactionsApi {
                 mempool_status.message,
                 AptosErrorCode::InvalidTransactionUpdate,
             )),
+            MempoolStatusCode::RateLimited => Err(AptosError::new_with_error_code(
+                mempool_status.message,
+                AptosErrorCode::MempoolRateLimited,
+            )),
+            MempoolStatusCode::Filtered => Err(AptosError::new_with_error_code(
+                mempool_status.message,
+                AptosErrorCode::TransactionFiltered,
+            )),
             MempoolStatusCode::UnknownStatus => Err(AptosError::new_with_error_code(
                 format!("Transaction was rejected with status {}", mempool_status,),
                 AptosErrorCode::InternalError,
@@ -1062,68 +1451,187 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         ledger_info: &LedgerInfo,
         txn: SignedTransaction,
+        options: SubmissionOptions,
     ) -> SubmitTransactionResult<PendingTransaction> {
-        match self.create_internal(txn.clone()).await {
-            Ok(()) => match accept_type {
-                AcceptType::Json => {
-                    let state_view = self
-                        .context
-                        .latest_state_view()
-                        .context("Failed to read latest state checkpoint from DB")
-                        .map_err(|e| {
-                            SubmitTransactionError::internal_with_code(
-                                e,
-                                AptosErrorCode::InternalError,
-                                ledger_info,
-                            )
-                        })?;
-                    let resolver = state_view.as_move_resolver();
+        match self.create_internal(txn.clone(), options).await {
+            Ok(()) => self.pending_transaction_response(
+                accept_type,
+                ledger_info,
+                txn,
+                SubmitTransactionResponseStatus::Accepted,
+            ),
+            Err(error) => Err(submission_error(error, ledger_info)),
+        }
+    }
 
-                    // We provide the pending transaction so that users have the hash associated
-                    let pending_txn = resolver
-                            .as_converter(self.context.db.clone())
-                            .try_into_pending_transaction_poem(txn)
-                            .context("Failed to build PendingTransaction from mempool response, even though it said the request was accepted")
-                            .map_err(|err| SubmitTransactionError::internal_with_code(
-                                err,
-                                AptosErrorCode::InternalError,
-                                ledger_info,
-                            ))?;
-                    SubmitTransactionResponse::try_from_json((
-                        pending_txn,
-                        ledger_info,
-                        SubmitTransactionResponseStatus::Accepted,
-                    ))
-                },
-                // With BCS, we don't return the pending transaction for efficiency, because there
-                // is no new information.  The hash can be retrieved by hashing the original
-                // transaction.
-                AcceptType::Bcs => SubmitTransactionResponse::try_from_bcs((
-                    (),
+    /// Submits a single transaction, and holds the request until the transaction
+    /// reaches the given stage. If the wait times out, the transaction is still pending.
+    async fn create_and_wait(
+        &self,
+        accept_type: &AcceptType,
+        ledger_info: &LedgerInfo,
+        txn: SignedTransaction,
+        options: SubmissionOptions,
+        stage: TransactionWaitStage,
+        timeout: Duration,
+    ) -> SubmitTransactionResult<PendingTransaction> {
+        // Subscribe before submitting, so that no notifications for the transaction are missed
+        let mempool_events = self
+            .context
+            .subscribe_mempool_transaction_events()
+            .await
+            .context("Failed to subscribe to the mempool transaction events")
+            .map_err(|err| {
+                SubmitTransactionError::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
                     ledger_info,
+                )
+            })?;
+        let commit_notifications = self.context.subscribe_to_commits();
+
+        let hash = txn.clone().committed_hash();
+        if let Err(error) = self.create_internal(txn.clone(), options).await {
+            return Err(submission_error(error, ledger_info));
+        }
+
+        let wait = self.wait_for_stage(
+            hash,
+            stage,
+            ledger_info,
+            mempool_events,
+            commit_notifications,
+        );
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(Ok(ledger_info)) => self.pending_transaction_response(
+                accept_type,
+                &ledger_info,
+                txn,
+                SubmitTransactionResponseStatus::Ok,
+            ),
+            Ok(Err(error)) => Err(error),
+            Err(_) => {
+                let ledger_info = self.context.get_latest_ledger_info()?;
+                self.pending_transaction_response(
+                    accept_type,
+                    &ledger_info,
+                    txn,
                     SubmitTransactionResponseStatus::Accepted,
-                )),
+                )
             },
-            Err(error) => match error.error_code {
-                AptosErrorCode::InternalError => Err(
-                    SubmitTransactionError::internal_from_aptos_error(error, ledger_info),
-                ),
-                AptosErrorCode::VmError
-                | AptosErrorCode::SequenceNumberTooOld
-                | AptosErrorCode::InvalidTransactionUpdate => Err(
-                    SubmitTransactionError::bad_request_from_aptos_error(error, ledger_info),
-                ),
-                AptosErrorCode::MempoolIsFull => Err(
-                    SubmitTransactionError::insufficient_storage_from_aptos_error(
-                        error,
+        }
+    }
+
+    /// Waits until the transaction reaches the given stage, and returns the latest ledger
+    /// info once it does. Fails if the transaction is evicted from mempool before.
+    async fn wait_for_stage(
+        &self,
+        hash: aptos_crypto::HashValue,
+        stage: TransactionWaitStage,
+        ledger_info: &LedgerInfo,
+        mut mempool_events: broadcast::Receiver<MempoolTransactionEvent>,
+        mut commit_notifications: Option<watch::Receiver<u64>>,
+    ) -> Result<LedgerInfo, SubmitTransactionError> {
+        loop {
+            // Both stages are reached once the transaction is committed to storage. Storage
+            // is read off the runtime, as many submissions may be waiting at once.
+            let is_committed = self
+                .context
+                .async_db()
+                .call("api_wait_for_transaction", move |db| {
+                    let ledger_version = db.get_latest_ledger_info()?.ledger_info().version();
+                    Ok(db
+                        .get_transaction_by_hash(hash, ledger_version, false)?
+                        .is_some())
+                })
+                .await
+                .context("Failed to read the submitted transaction from storage")
+                .map_err(|err| {
+                    SubmitTransactionError::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
                         ledger_info,
-                    ),
-                ),
-                _ => Err(SubmitTransactionError::internal_from_aptos_error(
-                    error,
-                    ledger_info,
-                )),
+                    )
+                })?;
+            if is_committed {
+                return self.context.get_latest_ledger_info();
+            }
+
+            // Storage is only read again after a commit. The events of other transactions
+            // are skipped without reading it.
+            loop {
+                tokio::select! {
+                    event = mempool_events.recv() => match event {
+                        Ok(event) if event.hash == hash => match event.kind {
+                            MempoolTransactionEventKind::Inserted => {},
+                            // Mempool removes the transaction once it's executed in a
+                            // committed block, which may be before storage is readable
+                            MempoolTransactionEventKind::Committed => {
+                                if stage == TransactionWaitStage::Executed {
+                                    return self.context.get_latest_ledger_info();
+                                }
+                                break;
+                            },
+                            MempoolTransactionEventKind::Evicted(reason) => {
+                                return Err(transaction_evicted(hash, reason, ledger_info));
+                            },
+                        },
+                        Ok(_) => {},
+                        // If events were missed, storage is checked again
+                        Err(RecvError::Lagged(_)) => break,
+                        Err(RecvError::Closed) => {
+                            return Err(SubmitTransactionError::internal_with_code(
+                                "The mempool transaction events were closed",
+                                AptosErrorCode::InternalError,
+                                ledger_info,
+                            ));
+                        },
+                    },
+                    _ = wait_for_commit(&mut commit_notifications) => break,
+                }
+            }
+        }
+    }
+
+    /// Builds the response for a transaction accepted by mempool
+    fn pending_transaction_response(
+        &self,
+        accept_type: &AcceptType,
+        ledger_info: &LedgerInfo,
+        txn: SignedTransaction,
+        status: SubmitTransactionResponseStatus,
+    ) -> SubmitTransactionResult<PendingTransaction> {
+        match accept_type {
+            AcceptType::Json => {
+                let state_view = self
+                    .context
+                    .latest_state_view()
+                    .context("Failed to read latest state checkpoint from DB")
+                    .map_err(|e| {
+                        SubmitTransactionError::internal_with_code(
+                            e,
+                            AptosErrorCode::InternalError,
+                            ledger_info,
+                        )
+                    })?;
+                let resolver = state_view.as_move_resolver();
+
+                // We provide the pending transaction so that users have the hash associated
+                let pending_txn = resolver
+                        .as_converter(self.context.db.clone())
+                        .try_into_pending_transaction_poem(txn)
+                        .context("Failed to build PendingTransaction from mempool response, even though it said the request was accepted")
+                        .map_err(|err| SubmitTransactionError::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            ledger_info,
+                        ))?;
+                SubmitTransactionResponse::try_from_json((pending_txn, ledger_info, status))
             },
+            // With BCS, we don't return the pending transaction for efficiency, because there
+            // is no new information.  The hash can be retrieved by hashing the original
+            // transaction.
+            AcceptType::Bcs => SubmitTransactionResponse::try_from_bcs(((), ledger_info, status)),
         }
     }
 
@@ -1137,7 +1645,10 @@ impl TransactionsApi {
         // Iterate through transactions keeping track of failures
         let mut txn_failures = Vec::new();
         for (idx, txn) in txns.iter().enumerate() {
-            if let Err(error) = self.create_internal(txn.clone()).await {
+            if let Err(error) = self
+                .create_internal(txn.clone(), SubmissionOptions::default())
+                .await
+            {
                 txn_failures.push(TransactionsBatchSingleSubmissionFailure {
                     error,
                     transaction_index: idx,
@@ -1174,6 +1685,7 @@ impl TransactionsApi {
         &self,
         accept_type: &AcceptType,
         ledger_info: LedgerInfo,
+        state_view: &OverriddenStateView<DbStateView>,
         txn: SignedTransaction,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         // Transactions shouldn't have a valid signature or this could be used to attack
@@ -1186,38 +1698,9 @@ impl TransactionsApi {
         }
 
         // Simulate transaction
-        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
         let move_resolver = state_view.as_move_resolver();
         let (_, output) = AptosVM::simulate_signed_transaction(&txn, &move_resolver);
-        let version = ledger_info.version();
-
-        // Ensure that all known statuses return their values in the output (even if they aren't supposed to)
-        let exe_status = match output.status().clone() {
-            TransactionStatus::Keep(exec_status) => exec_status,
-            TransactionStatus::Discard(status) => ExecutionStatus::MiscellaneousError(Some(status)),
-            _ => ExecutionStatus::MiscellaneousError(None),
-        };
-
-        // Build up a transaction from the outputs
-        // All state hashes are invalid, and will be filled with 0s
-        let txn = aptos_types::transaction::Transaction::UserTransaction(txn);
-        let zero_hash = aptos_crypto::HashValue::zero();
-        let info = aptos_types::transaction::TransactionInfo::new(
-            txn.hash(),
-            zero_hash,
-            zero_hash,
-            None,
-            output.gas_used(),
-            exe_status,
-        );
-        let simulated_txn = TransactionOnChainData {
-            version,
-            transaction: txn,
-            info,
-            events: output.events().to_vec(),
-            accumulator_root_hash: zero_hash,
-            changes: output.write_set().clone(),
-        };
+        let simulated_txn = simulated_transaction_data(&ledger_info, txn, &output);
 
         match accept_type {
             AcceptType::Json => {
@@ -1252,6 +1735,379 @@ impl TransactionsApi {
         }
     }
 
+    fn gas_estimation_response(
+        accept_type: &AcceptType,
+        gas_estimation: GasEstimation,
+        ledger_info: &LedgerInfo,
+    ) -> BasicResult<GasEstimation> {
+        match accept_type {
+            AcceptType::Json => {
+                BasicResponse::try_from_json((gas_estimation, ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Bcs => {
+                let gas_estimation_bcs = GasEstimationBcs {
+                    gas_estimate: gas_estimation.gas_estimate,
+                };
+                BasicResponse::try_from_bcs((
+                    gas_estimation_bcs,
+                    ledger_info,
+                    BasicResponseStatus::Ok,
+                ))
+            },
+        }
+    }
+
+    /// Returns the maximum gas amount the sender can pay for at the given gas unit price,
+    /// bounded by the gas schedule
+    fn max_gas_amount<E: BadRequestError + InternalError>(
+        &self,
+        ledger_info: &LedgerInfo,
+        state_view: &OverriddenStateView<DbStateView>,
+        sender: AccountAddress,
+        gas_unit_price: u64,
+    ) -> Result<u64, E> {
+        // Retrieve max possible gas units
+        let (_, gas_params) = self.context.get_gas_schedule(ledger_info)?;
+        let min_number_of_gas_units = u64::from(gas_params.vm.txn.min_transaction_gas_units)
+            / u64::from(gas_params.vm.txn.gas_unit_scaling_factor);
+        let max_number_of_gas_units = u64::from(gas_params.vm.txn.maximum_number_of_gas_units);
+
+        // Retrieve account balance to determine max gas available, taking
+        // any state overrides into account
+        let account_view = state_view.as_account_with_state_view(&sender);
+        let account_exists = account_view
+            .get_account_resource()
+            .map_err(|err| {
+                E::internal_with_code(
+                    format!("Failed to get account resource {}", err),
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )
+            })?
+            .is_some();
+        if !account_exists {
+            return Err(E::bad_request_with_code(
+                "Account not found",
+                AptosErrorCode::InvalidInput,
+                ledger_info,
+            ));
+        }
+        let coin_store: CoinStoreResource = account_view
+            .get_coin_store_resource()
+            .and_then(|inner| {
+                inner.ok_or_else(|| anyhow!("No coin store found for account {}", sender))
+            })
+            .map_err(|err| {
+                E::internal_with_code(
+                    format!("Failed to get coin store resource {}", err),
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )
+            })?;
+
+        // With 0 gas price, we set it to max gas units, since we can't divide by 0
+        let max_account_gas_units = if gas_unit_price == 0 {
+            coin_store.coin()
+        } else {
+            coin_store.coin() / gas_unit_price
+        };
+
+        // To give better error messaging, we should not go below the minimum number of gas units
+        let max_account_gas_units = std::cmp::max(min_number_of_gas_units, max_account_gas_units);
+
+        // Minimum of the max account and the max total needs to be used for estimation
+        Ok(std::cmp::min(
+            max_account_gas_units,
+            max_number_of_gas_units,
+        ))
+    }
+
+    /// Simulates the payload of the request at the given gas unit price, and suggests a max
+    /// gas amount from the gas used, with a safety margin
+    fn estimate_max_gas_amount(
+        &self,
+        ledger_info: &LedgerInfo,
+        request: EstimateGasPriceRequest,
+        gas_unit_price: u64,
+    ) -> Result<u64, BasicError> {
+        let sender: AccountAddress = request.sender.into();
+        let mut state_view =
+            OverriddenStateView::new(self.context.latest_state_view_poem(ledger_info)?);
+        let max_gas_amount =
+            self.max_gas_amount(ledger_info, &state_view, sender, gas_unit_price)?;
+
+        let payload = state_view
+            .as_move_resolver()
+            .as_converter(self.context.db.clone())
+            .try_into_aptos_core_transaction_payload(request.payload)
+            .context("Failed to parse transaction payload")
+            .map_err(|err| {
+                BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, ledger_info)
+            })?;
+
+        // The sender's key is unknown, so the simulation is signed by a throwaway key, which
+        // replaces the authentication key of the sender's account
+        let (public_key, signature) = simulation_key_and_signature();
+        let sequence_number = replace_authentication_key(&mut state_view, sender, &public_key)
+            .map_err(|err| {
+                BasicError::internal_with_code(
+                    format!("Failed to prepare account for simulation {}", err),
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )
+            })?;
+
+        let raw_transaction = RawTransaction::new(
+            sender,
+            sequence_number,
+            payload,
+            max_gas_amount,
+            gas_unit_price,
+            ledger_info.timestamp() / 1_000_000 + SIMULATION_EXPIRATION_SECS,
+            ChainId::new(ledger_info.chain_id),
+        );
+        let txn = SignedTransaction::new(raw_transaction, public_key, signature);
+        let (vm_status, output) =
+            AptosVM::simulate_signed_transaction(&txn, &state_view.as_move_resolver());
+        match output.status() {
+            TransactionStatus::Keep(ExecutionStatus::Success) => {},
+            _ => {
+                return Err(BasicError::bad_request_with_code(
+                    format!(
+                        "Simulation of the transaction payload failed: {:?}",
+                        vm_status
+                    ),
+                    AptosErrorCode::VmError,
+                    ledger_info,
+                ))
+            },
+        }
+
+        let safety_margin_percent = self
+            .context
+            .node_config
+            .api
+            .gas_estimation
+            .max_gas_amount_safety_margin_percent;
+        let gas_used = output.gas_used();
+        // Round the safety margin up, so that it's never zero for a non-zero percentage
+        let safety_margin = gas_used
+            .saturating_mul(safety_margin_percent)
+            .saturating_add(99)
+            / 100;
+        let max_gas_amount_estimate = gas_used.saturating_add(safety_margin);
+        Ok(std::cmp::min(max_gas_amount_estimate, max_gas_amount))
+    }
+
+    /// Simulates the sponsored transaction of the request, and asks the gas station (if any)
+    /// to approve the sponsorship if the simulation succeeds
+    async fn simulate_sponsored(
+        &self,
+        ledger_info: &LedgerInfo,
+        request: SimulateSponsoredTransactionRequest,
+    ) -> Result<SponsoredTransactionSimulation, BasicError> {
+        let sender: AccountAddress = request.sender.into();
+        let fee_payer_address: AccountAddress = request.fee_payer_address.into();
+        let secondary_signers: Vec<AccountAddress> = request
+            .secondary_signers
+            .iter()
+            .flatten()
+            .map(|address| address.into())
+            .collect();
+        let state_view =
+            OverriddenStateView::new(self.context.latest_state_view_poem(ledger_info)?);
+
+        let payload = state_view
+            .as_move_resolver()
+            .as_converter(self.context.db.clone())
+            .try_into_aptos_core_transaction_payload(request.payload.clone())
+            .context("Failed to parse transaction payload")
+            .map_err(|err| {
+                BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, ledger_info)
+            })?;
+        let gas_unit_price = match request.gas_unit_price {
+            Some(gas_unit_price) => gas_unit_price.0,
+            None => self.context.estimate_gas_price(ledger_info)?.gas_estimate,
+        };
+        let max_gas_amount = match request.max_gas_amount {
+            Some(max_gas_amount) => max_gas_amount.0,
+            None => {
+                self.max_gas_amount(ledger_info, &state_view, fee_payer_address, gas_unit_price)?
+            },
+        };
+
+        let sequence_number = state_view
+            .as_account_with_state_view(&sender)
+            .get_account_resource()
+            .map_err(|err| {
+                BasicError::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
+            })?
+            .ok_or_else(|| {
+                BasicError::bad_request_with_code(
+                    format!("Account {} not found", sender),
+                    AptosErrorCode::AccountNotFound,
+                    ledger_info,
+                )
+            })?
+            .sequence_number();
+
+        let raw_transaction = RawTransaction::new(
+            sender,
+            sequence_number,
+            payload,
+            max_gas_amount,
+            gas_unit_price,
+            ledger_info.timestamp() / 1_000_000 + SIMULATION_EXPIRATION_SECS,
+            ChainId::new(ledger_info.chain_id),
+        );
+        // The signers' keys are unknown, so the simulation is signed by a throwaway key, and the
+        // VM checks the signers' on-chain authentication keys instead
+        let (public_key, signature) = simulation_key_and_signature();
+        let authenticator = AccountAuthenticator::ed25519(public_key, signature);
+        let txn = SignedTransaction::new_fee_payer(
+            raw_transaction,
+            authenticator.clone(),
+            secondary_signers.clone(),
+            vec![authenticator.clone(); secondary_signers.len()],
+            fee_payer_address,
+            authenticator,
+        );
+        let (_, output) =
+            AptosVM::simulate_sponsored_transaction(&txn, &state_view.as_move_resolver());
+        let succeeded = matches!(
+            output.status(),
+            TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+
+        let simulated_txn = simulated_transaction_data(ledger_info, txn, &output);
+        let transaction = match self
+            .context
+            .render_transactions_non_sequential(ledger_info, vec![simulated_txn])?
+            .pop()
+        {
+            Some(Transaction::UserTransaction(user_txn)) => *user_txn,
+            _ => {
+                return Err(BasicError::internal_with_code(
+                    "Simulation transaction resulted in a non-UserTransaction",
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                ))
+            },
+        };
+
+        // Only transactions that can be executed are sent to the gas station
+        let sponsorship = match self.context.gas_station() {
+            Some(gas_station) if succeeded => {
+                let sponsorship_request = SponsorshipRequest {
+                    sender: request.sender,
+                    fee_payer_address: request.fee_payer_address,
+                    secondary_signers: request.secondary_signers.unwrap_or_default(),
+                    payload: request.payload,
+                    max_gas_amount: max_gas_amount.into(),
+                    gas_unit_price: gas_unit_price.into(),
+                    gas_used: output.gas_used().into(),
+                };
+                let decision = gas_station
+                    .approve_sponsorship(&sponsorship_request)
+                    .await
+                    .map_err(|err| {
+                        BasicError::service_unavailable_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            ledger_info,
+                        )
+                    })?;
+                Some(decision)
+            },
+            _ => None,
+        };
+
+        Ok(SponsoredTransactionSimulation {
+            transaction,
+            sponsorship,
+        })
+    }
+
+    /// Builds the state view used for simulation, with the given state overrides
+    /// applied on top of the latest state checkpoint
+    fn overridden_state_view(
+        &self,
+        ledger_info: &LedgerInfo,
+        state_overrides: Vec<StateOverride>,
+    ) -> Result<OverriddenStateView<DbStateView>, SubmitTransactionError> {
+        let mut state_view =
+            OverriddenStateView::new(self.context.latest_state_view_poem(ledger_info)?);
+        for (index, state_override) in state_overrides.into_iter().enumerate() {
+            self.apply_state_override(&mut state_view, state_override)
+                .context(format!(
+                    "Failed to apply state override at position {}",
+                    index
+                ))
+                .map_err(|err| {
+                    SubmitTransactionError::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        ledger_info,
+                    )
+                })?;
+        }
+        Ok(state_view)
+    }
+
+    fn apply_state_override(
+        &self,
+        state_view: &mut OverriddenStateView<DbStateView>,
+        state_override: StateOverride,
+    ) -> anyhow::Result<()> {
+        match state_override {
+            StateOverride::ResourceOverride(resource_override) => {
+                let resource_type: StructTag = resource_override
+                    .resource_type
+                    .try_into()
+                    .context("Failed to parse given resource type")?;
+                let access_path = AccessPath::resource_access_path(
+                    resource_override.address.into(),
+                    resource_type.clone(),
+                )?;
+                let state_key = StateKey::access_path(access_path);
+                match resource_override.data {
+                    Some(data) => {
+                        let value = state_view
+                            .as_move_resolver()
+                            .as_converter(self.context.db.clone())
+                            .try_into_vm_value(&TypeTag::Struct(Box::new(resource_type)), data)
+                            .context("Failed to parse given resource data")?;
+                        let bytes = value
+                            .simple_serialize()
+                            .ok_or_else(|| anyhow!("Failed to serialize given resource data"))?;
+                        state_view.set_bytes(state_key, bytes)?;
+                    },
+                    None => state_view.remove(state_key),
+                }
+            },
+            StateOverride::BalanceOverride(balance_override) => {
+                let address = balance_override.address.into();
+                let coin_store = state_view
+                    .as_account_with_state_view(&address)
+                    .get_coin_store_resource()?
+                    .ok_or_else(|| anyhow!("No coin store found for account {}", address))?;
+                let coin_store = CoinStoreResource::new(
+                    balance_override.amount.into(),
+                    coin_store.frozen(),
+                    coin_store.deposit_events().clone(),
+                    coin_store.withdraw_events().clone(),
+                );
+                let access_path =
+                    AccessPath::resource_access_path(address, CoinStoreResource::struct_tag())?;
+                state_view.set_bytes(
+                    StateKey::access_path(access_path),
+                    bcs::to_bytes(&coin_store)?,
+                )?;
+            },
+        }
+        Ok(())
+    }
+
     /// Encode message as BCS
     pub fn get_signing_message(
         &self,
@@ -1311,6 +2167,68 @@ impl TransactionsApi {
     }
 }
 
+/// Builds the on-chain data of a simulated transaction from its output. All state
+/// hashes are invalid, and are filled with 0s.
+fn simulated_transaction_data(
+    ledger_info: &LedgerInfo,
+    txn: SignedTransaction,
+    output: &TransactionOutput,
+) -> TransactionOnChainData {
+    // Ensure that all known statuses return their values in the output (even if they aren't supposed to)
+    let exe_status = match output.status().clone() {
+        TransactionStatus::Keep(exec_status) => exec_status,
+        TransactionStatus::Discard(status) => ExecutionStatus::MiscellaneousError(Some(status)),
+        _ => ExecutionStatus::MiscellaneousError(None),
+    };
+
+    let txn = aptos_types::transaction::Transaction::UserTransaction(txn);
+    let zero_hash = aptos_crypto::HashValue::zero();
+    let info = aptos_types::transaction::TransactionInfo::new(
+        txn.hash(),
+        zero_hash,
+        zero_hash,
+        None,
+        output.gas_used(),
+        exe_status,
+    );
+    TransactionOnChainData {
+        version: ledger_info.version(),
+        transaction: txn,
+        info,
+        events: output.events().to_vec(),
+        accumulator_root_hash: zero_hash,
+        changes: output.write_set().clone(),
+    }
+}
+
+/// A throwaway key for simulating transactions whose sender's key is unknown, along with an
+/// invalid signature, so that the simulated transactions can never be submitted
+fn simulation_key_and_signature() -> (Ed25519PublicKey, Ed25519Signature) {
+    let private_key = Ed25519PrivateKey::try_from([1u8; ED25519_PRIVATE_KEY_LENGTH].as_slice())
+        .expect("Simulation private key must be valid");
+    let signature = Ed25519Signature::try_from([0u8; ED25519_SIGNATURE_LENGTH].as_slice())
+        .expect("Simulation signature must be valid");
+    (Ed25519PublicKey::from(&private_key), signature)
+}
+
+/// Replaces the authentication key of an account with the one of the given key, and returns
+/// the sequence number of the account
+fn replace_authentication_key(
+    state_view: &mut OverriddenStateView<DbStateView>,
+    address: AccountAddress,
+    public_key: &Ed25519PublicKey,
+) -> anyhow::Result<u64> {
+    let account = state_view
+        .as_account_with_state_view(&address)
+        .get_account_resource()?
+        .ok_or_else(|| anyhow!("Account {} not found", address))?;
+    let sequence_number = account.sequence_number();
+    let account = account.with_authentication_key(AuthenticationKey::ed25519(public_key).to_vec());
+    let access_path = AccessPath::resource_access_path(address, AccountResource::struct_tag())?;
+    state_view.set_bytes(StateKey::access_path(access_path), bcs::to_bytes(&account)?)?;
+    Ok(sequence_number)
+}
+
 fn override_gas_parameters(
     signed_txn: &SignedTransaction,
     max_gas_amount: Option<u64>,
@@ -1332,6 +2250,118 @@ fn override_gas_parameters(
     SignedTransaction::new_with_authenticator(raw_txn, signed_txn.authenticator())
 }
 
+/// Converts the mempool's view of the sequence numbers of an account into the API type
+fn mempool_account_diagnostics(
+    diagnostics: AccountSequenceNumberDiagnostics,
+) -> MempoolAccountDiagnostics {
+    let format_peer = |peer: Option<PeerNetworkId>| {
+        peer.map(|peer| format!("{}:{}", peer.network_id(), peer.peer_id()))
+    };
+    MempoolAccountDiagnostics {
+        committed_sequence_number: diagnostics.committed_sequence_number.map(U64::from),
+        pending_transactions: diagnostics
+            .pending_transactions
+            .into_iter()
+            .map(|txn| MempoolPendingTransaction {
+                sequence_number: txn.sequence_number.into(),
+                hash: txn.hash.into(),
+                gas_unit_price: txn.gas_unit_price.into(),
+                insertion_timestamp_usecs: (txn
+                    .insertion_time
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_micros() as u64)
+                    .into(),
+                is_parked: txn.is_parked,
+                in_consensus: txn.in_consensus,
+                received_from: format_peer(txn.received_from),
+            })
+            .collect(),
+        gaps: diagnostics
+            .gaps
+            .into_iter()
+            .map(|gap| MempoolSequenceNumberGap {
+                start_sequence_number: gap.start_sequence_number.into(),
+                end_sequence_number: gap.end_sequence_number.into(),
+                first_seen_by: format_peer(gap.first_seen_by),
+            })
+            .collect(),
+    }
+}
+
+/// Converts a failed submission into the error response
+fn submission_error(error: AptosError, ledger_info: &LedgerInfo) -> SubmitTransactionError {
+    match error.error_code {
+        AptosErrorCode::InternalError => {
+            SubmitTransactionError::internal_from_aptos_error(error, ledger_info)
+        },
+        AptosErrorCode::VmError
+        | AptosErrorCode::SequenceNumberTooOld
+        | AptosErrorCode::InvalidTransactionUpdate => {
+            SubmitTransactionError::bad_request_from_aptos_error(error, ledger_info)
+        },
+        AptosErrorCode::MempoolIsFull => {
+            SubmitTransactionError::insufficient_storage_from_aptos_error(error, ledger_info)
+        },
+        AptosErrorCode::MempoolRateLimited => {
+            SubmitTransactionError::too_many_requests_from_aptos_error(error, ledger_info)
+        },
+        AptosErrorCode::TransactionFiltered => {
+            SubmitTransactionError::forbidden_from_aptos_error(error, ledger_info)
+        },
+        _ => SubmitTransactionError::internal_from_aptos_error(error, ledger_info),
+    }
+}
+
+/// Returns the error for a submitted transaction that was evicted from mempool while waiting
+fn transaction_evicted(
+    hash: aptos_crypto::HashValue,
+    reason: EvictionReason,
+    ledger_info: &LedgerInfo,
+) -> SubmitTransactionError {
+    let error_code = match reason {
+        EvictionReason::Expired => AptosErrorCode::TransactionExpired,
+        _ => AptosErrorCode::TransactionEvicted,
+    };
+    SubmitTransactionError::bad_request_with_code(
+        format!(
+            "Transaction hash({}) was evicted from mempool: {}",
+            hash,
+            reason.as_str()
+        ),
+        error_code,
+        ledger_info,
+    )
+}
+
+/// Waits for the next storage commit (or the poll interval, if commit notifications
+/// are unavailable)
+async fn wait_for_commit(commit_notifications: &mut Option<watch::Receiver<u64>>) {
+    if let Some(commit_notifications) = commit_notifications {
+        if commit_notifications.changed().await.is_ok() {
+            return;
+        }
+    }
+    tokio::time::sleep(Duration::from_millis(SUBMIT_WAIT_POLL_INTERVAL_MS)).await;
+}
+
+/// Converts the submission query parameters into the mempool submission options
+fn submission_options(
+    ttl_tier: Option<TransactionTtlTier>,
+    expiration_behavior: Option<TransactionExpirationBehavior>,
+) -> SubmissionOptions {
+    SubmissionOptions {
+        ttl_tier: match ttl_tier {
+            None | Some(TransactionTtlTier::Standard) => TtlTier::Standard,
+            Some(TransactionTtlTier::Short) => TtlTier::Short,
+        },
+        expiration_behavior: match expiration_behavior {
+            None | Some(TransactionExpirationBehavior::Drop) => ExpirationBehavior::Drop,
+            Some(TransactionExpirationBehavior::Notify) => ExpirationBehavior::Notify,
+        },
+    }
+}
+
 enum GetByVersionResponse {
     VersionTooNew,
     VersionTooOld,
@@ -3,18 +3,48 @@
 
 use crate::{
     accept_type::AcceptType,
+    bcs_payload::Bcs,
     failpoint::fail_point_poem,
+    quota::record_view_gas_used,
     response::{
         BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
     },
     ApiTags, Context,
 };
-use aptos_api_types::{AptosErrorCode, AsConverter, MoveValue, ViewRequest, U64};
-use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    AptosError, AptosErrorCode, AsConverter, LedgerInfo, MoveConverter, MoveValue,
+    ViewFunctionResult, ViewRequest, MAX_RECURSIVE_TYPES_ALLOWED, U64,
+};
+use aptos_state_view::StateView;
+use aptos_types::transaction::EntryFunction;
+use aptos_vm::{data_cache::AsMoveResolver, move_vm_ext::MoveResolverExt, AptosVM};
 use move_core_types::language_storage::TypeTag;
-use poem_openapi::{param::Query, payload::Json, OpenApi};
+use poem_openapi::{param::Query, payload::Json, ApiRequest, OpenApi};
 use std::sync::Arc;
 
+/// A view function request, either as JSON or BCS
+#[derive(ApiRequest, Debug)]
+pub enum ViewFunctionRequest {
+    #[oai(content_type = "application/json")]
+    Json(Json<ViewRequest>),
+
+    // A BCS encoded EntryFunction
+    #[oai(content_type = "application/x.aptos.view_function+bcs")]
+    Bcs(Bcs),
+}
+
+/// A batch of view function requests, either as JSON or BCS
+#[derive(ApiRequest, Debug)]
+pub enum ViewFunctionBatchRequest {
+    #[oai(content_type = "application/json")]
+    Json(Json<Vec<ViewRequest>>),
+
+    // A BCS encoded Vec<EntryFunction>
+    #[oai(content_type = "application/x.aptos.view_function+bcs")]
+    Bcs(Bcs),
+}
+
 /// API for executing Move view function.
 pub struct ViewFunctionApi {
     pub context: Arc<Context>,
@@ -26,6 +56,9 @@ impl ViewFunctionApi {
     ///
     /// Execute the Move function with the given parameters and return its execution result.
     ///
+    /// The request can also be sent as a BCS encoded EntryFunction, with the
+    /// `application/x.aptos.view_function+bcs` Content-Type.
+    ///
     /// The Aptos nodes prune account state history, via a configurable time window.
     /// If the requested ledger version has been pruned, the server responds with a 410.
     #[oai(
@@ -38,7 +71,7 @@ impl ViewFunctionApi {
         &self,
         accept_type: AcceptType,
         /// View function request with type and position arguments
-        request: Json<ViewRequest>,
+        request: ViewFunctionRequest,
         /// Ledger version to get state of account
         ///
         /// If not provided, it will be the latest version
@@ -57,16 +90,102 @@ impl ViewFunctionApi {
         let state_view = self.context.latest_state_view_poem(&ledger_info)?;
         let resolver = state_view.as_move_resolver();
 
-        let entry_func = resolver
-            .as_converter(self.context.db.clone())
-            .convert_view_function(request.0)
+        let entry_func = match request {
+            ViewFunctionRequest::Json(data) => resolver
+                .as_converter(self.context.db.clone())
+                .convert_view_function(data.0),
+            ViewFunctionRequest::Bcs(data) => {
+                bcs::from_bytes_with_limit(&data.0, MAX_RECURSIVE_TYPES_ALLOWED as usize)
+                    .context("Failed to deserialize input into EntryFunction")
+            },
+        }
+        .map_err(|err| {
+            BasicErrorWith404::bad_request_with_code(
+                err,
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            )
+        })?;
+        let state_view = self
+            .context
+            .state_view_at_version(requested_version)
             .map_err(|err| {
                 BasicErrorWith404::bad_request_with_code(
                     err,
-                    AptosErrorCode::InvalidInput,
+                    AptosErrorCode::InternalError,
                     &ledger_info,
                 )
             })?;
+
+        let return_vals = self
+            .execute_view_function(&state_view, &entry_func)
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        match accept_type {
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((return_vals, &ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Json => {
+                let move_vals = Self::convert_return_values(
+                    &resolver.as_converter(self.context.db.clone()),
+                    &entry_func,
+                    return_vals,
+                )
+                .map_err(|err| {
+                    BasicErrorWith404::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &ledger_info,
+                    )
+                })?;
+
+                BasicResponse::try_from_json((move_vals, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
+    /// Execute a batch of view functions
+    ///
+    /// Execute the given Move view functions against the same ledger version and
+    /// return their execution results (or errors), in the order of the requests.
+    /// A failing view function does not fail the batch.
+    ///
+    /// The requests can also be sent as a BCS encoded Vec<EntryFunction>, with the
+    /// `application/x.aptos.view_function+bcs` Content-Type.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/view/batch",
+        method = "post",
+        operation_id = "view_batch",
+        tag = "ApiTags::View"
+    )]
+    async fn view_function_batch(
+        &self,
+        accept_type: AcceptType,
+        /// View function requests with type and position arguments
+        requests: ViewFunctionBatchRequest,
+        /// Ledger version to get state of account
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<ViewFunctionResult>> {
+        fail_point_poem("endpoint_view_function_batch")?;
+        self.context
+            .check_api_output_enabled("View function batch", &accept_type)?;
+
+        let (ledger_info, requested_version) = self
+            .context
+            .get_latest_ledger_info_and_verify_lookup_version(
+                ledger_version.map(|inner| inner.0),
+            )?;
+
+        // All view functions share a single snapshot of the requested version
+        let latest_state_view = self.context.latest_state_view_poem(&ledger_info)?;
+        let resolver = latest_state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.context.db.clone());
         let state_view = self
             .context
             .state_view_at_version(requested_version)
@@ -78,57 +197,129 @@ impl ViewFunctionApi {
                 )
             })?;
 
-        let return_vals = AptosVM::execute_view_function(
-            &state_view,
-            entry_func.module().clone(),
-            entry_func.function().to_owned(),
-            entry_func.ty_args().to_owned(),
-            entry_func.args().to_owned(),
-            self.context.node_config.api.max_gas_view_function,
-        )
-        .map_err(|err| {
-            BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
-        })?;
+        // Invalid JSON requests only fail themselves, but BCS requests are
+        // decoded all at once, so an invalid BCS batch fails as a whole
+        let entry_funcs: Vec<Result<EntryFunction, AptosError>> = match requests {
+            ViewFunctionBatchRequest::Json(data) => {
+                self.check_batch_size(data.0.len(), &ledger_info)?;
+                data.0
+                    .into_iter()
+                    .map(|request| {
+                        converter.convert_view_function(request).map_err(|err| {
+                            AptosError::new_with_error_code(err, AptosErrorCode::InvalidInput)
+                        })
+                    })
+                    .collect()
+            },
+            ViewFunctionBatchRequest::Bcs(data) => {
+                let entry_funcs: Vec<EntryFunction> =
+                    bcs::from_bytes_with_limit(&data.0, MAX_RECURSIVE_TYPES_ALLOWED as usize)
+                        .context("Failed to deserialize input into Vec<EntryFunction>")
+                        .map_err(|err| {
+                            BasicErrorWith404::bad_request_with_code(
+                                err,
+                                AptosErrorCode::InvalidInput,
+                                &ledger_info,
+                            )
+                        })?;
+                self.check_batch_size(entry_funcs.len(), &ledger_info)?;
+                entry_funcs.into_iter().map(Ok).collect()
+            },
+        };
+
+        let results = entry_funcs.into_iter().map(|entry_func| {
+            let entry_func = entry_func?;
+            let return_vals = self
+                .execute_view_function(&state_view, &entry_func)
+                .map_err(|err| {
+                    AptosError::new_with_error_code(err, AptosErrorCode::InvalidInput)
+                })?;
+            Ok::<_, AptosError>((entry_func, return_vals))
+        });
         match accept_type {
             AcceptType::Bcs => {
-                BasicResponse::try_from_bcs((return_vals, &ledger_info, BasicResponseStatus::Ok))
+                let results: Vec<Result<Vec<Vec<u8>>, AptosError>> = results
+                    .map(|result| result.map(|(_, return_vals)| return_vals))
+                    .collect();
+                BasicResponse::try_from_bcs((results, &ledger_info, BasicResponseStatus::Ok))
             },
             AcceptType::Json => {
-                let return_types = resolver
-                    .as_converter(self.context.db.clone())
-                    .function_return_types(&entry_func)
-                    .and_then(|tys| {
-                        tys.into_iter()
-                            .map(TypeTag::try_from)
-                            .collect::<anyhow::Result<Vec<_>>>()
-                    })
-                    .map_err(|err| {
-                        BasicErrorWith404::bad_request_with_code(
-                            err,
-                            AptosErrorCode::InternalError,
-                            &ledger_info,
-                        )
-                    })?;
-
-                let move_vals = return_vals
-                    .into_iter()
-                    .zip(return_types.into_iter())
-                    .map(|(v, ty)| {
-                        resolver
-                            .as_converter(self.context.db.clone())
-                            .try_into_move_value(&ty, &v)
+                let results: Vec<ViewFunctionResult> = results
+                    .map(|result| {
+                        result
+                            .and_then(|(entry_func, return_vals)| {
+                                Self::convert_return_values(&converter, &entry_func, return_vals)
+                                    .map_err(|err| {
+                                        AptosError::new_with_error_code(
+                                            err,
+                                            AptosErrorCode::InternalError,
+                                        )
+                                    })
+                            })
+                            .into()
                     })
-                    .collect::<anyhow::Result<Vec<_>>>()
-                    .map_err(|err| {
-                        BasicErrorWith404::bad_request_with_code(
-                            err,
-                            AptosErrorCode::InternalError,
-                            &ledger_info,
-                        )
-                    })?;
-
-                BasicResponse::try_from_json((move_vals, &ledger_info, BasicResponseStatus::Ok))
+                    .collect();
+                BasicResponse::try_from_json((results, &ledger_info, BasicResponseStatus::Ok))
             },
         }
     }
 }
+
+impl ViewFunctionApi {
+    /// Checks that a batch of view functions isn't too large
+    fn check_batch_size(
+        &self,
+        batch_size: usize,
+        ledger_info: &LedgerInfo,
+    ) -> Result<(), BasicErrorWith404> {
+        if self.context.max_view_function_batch_size() < batch_size {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "Submitted too many view functions: {}, while limit is {}",
+                    batch_size,
+                    self.context.max_view_function_batch_size(),
+                ),
+                AptosErrorCode::InvalidInput,
+                ledger_info,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Executes the view function against the given state view
+    fn execute_view_function(
+        &self,
+        state_view: &impl StateView,
+        entry_func: &EntryFunction,
+    ) -> anyhow::Result<Vec<Vec<u8>>> {
+        let (result, gas_used) = AptosVM::execute_view_function_with_gas_used(
+            state_view,
+            entry_func.module().clone(),
+            entry_func.function().to_owned(),
+            entry_func.ty_args().to_owned(),
+            entry_func.args().to_owned(),
+            self.context.node_config.api.max_gas_view_function,
+        );
+        // Failed view functions (e.g., aborted or out of gas) are charged as well
+        record_view_gas_used(gas_used);
+        result
+    }
+
+    /// Converts the BCS encoded return values of the view function to Move values
+    fn convert_return_values<R: MoveResolverExt>(
+        converter: &MoveConverter<R>,
+        entry_func: &EntryFunction,
+        return_vals: Vec<Vec<u8>>,
+    ) -> anyhow::Result<Vec<MoveValue>> {
+        let return_types = converter
+            .function_return_types(entry_func)?
+            .into_iter()
+            .map(TypeTag::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        return_vals
+            .into_iter()
+            .zip(return_types.into_iter())
+            .map(|(v, ty)| converter.try_into_move_value(&ty, &v))
+            .collect()
+    }
+}
@@ -20,7 +20,7 @@ use aptos_db::AptosDB;
 use aptos_executor::{block_executor::BlockExecutor, db_bootstrapper};
 use aptos_executor_types::BlockExecutorTrait;
 use aptos_framework::BuiltPackage;
-use aptos_mempool::mocks::MockSharedMempool;
+use aptos_mempool::{mocks::MockSharedMempool, TransactionFilter};
 use aptos_mempool_notifications::MempoolNotificationSender;
 use aptos_sdk::{
     bcs,
@@ -136,12 +136,17 @@ pub fn new_test_context(
 
     let mempool = MockSharedMempool::new_in_runtime(&db_rw, VMValidator::new(db.clone()));
 
-    let context = Context::new(
+    let mut context = Context::new(
         ChainId::test(),
         db.clone(),
         mempool.ac_client.clone(),
         node_config.clone(),
     );
+    if let Some(transaction_filter) =
+        TransactionFilter::new(&node_config.mempool.transaction_filter, tmp_dir.path()).unwrap()
+    {
+        context = context.with_transaction_filter(transaction_filter);
+    }
 
     // Configure the testing depending on which API version we're testing.
     let runtime_handle = tokio::runtime::Handle::current();
@@ -832,6 +837,17 @@ impl TestContext {
         sender: &LocalAccount,
         payload: Value,
         status_code: u16,
+    ) -> Value {
+        self.simulate_transaction_with_state_overrides(sender, payload, None, status_code)
+            .await
+    }
+
+    pub async fn simulate_transaction_with_state_overrides(
+        &mut self,
+        sender: &LocalAccount,
+        payload: Value,
+        state_overrides: Option<Value>,
+        status_code: u16,
     ) -> Value {
         let mut request = json!({
             "sender": sender.address(),
@@ -863,6 +879,9 @@ impl TestContext {
             "public_key": HexEncodedBytes::from(sender.public_key().to_bytes().to_vec()),
             "signature": HexEncodedBytes::from(sig.to_bytes().to_vec()),
         });
+        if let Some(state_overrides) = state_overrides {
+            request["state_overrides"] = state_overrides;
+        }
 
         self.expect_status_code(status_code)
             .post("/transactions/simulate", request)
@@ -903,6 +922,17 @@ impl TestContext {
         .await
     }
 
+    pub async fn post_bcs_view(&self, path: &str, body: impl AsRef<[u8]>) -> Value {
+        self.execute(
+            warp::test::request()
+                .method("POST")
+                .path(&self.prepend_path(path))
+                .header(CONTENT_TYPE, mime_types::BCS_VIEW_FUNCTION)
+                .body(body),
+        )
+        .await
+    }
+
     pub async fn reply(&self, req: warp::test::RequestBuilder) -> Response<Bytes> {
         match self.api_specific_config {
             ApiSpecificConfig::V1(address) => req.reply(&self.get_routes_with_poem(address)).await,
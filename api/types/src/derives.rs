@@ -20,8 +20,9 @@
 
 use crate::{
     move_types::{MoveAbility, MoveStructValue},
-    Address, EntryFunctionId, HashValue, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
-    MoveStructTag, MoveType, StateKeyWrapper, U128, U256, U64,
+    Address, EntryFunctionId, FieldSelection, HashValue, HexEncodedBytes, IdentifierWrapper,
+    LedgerVersionPin, MoveModuleId, MoveStructTag, MoveType, PageCursor, StateKeyWrapper, U128,
+    U256, U64,
 };
 use aptos_openapi::{impl_poem_parameter, impl_poem_type};
 use indoc::indoc;
@@ -242,6 +243,33 @@ impl_poem_type!(
     )
 );
 
+impl_poem_type!(
+    PageCursor,
+    "string",
+    (
+        example = Some(serde_json::Value::String(
+            "64000000000000000a00000000000000".to_string()
+        )),
+        description = Some(indoc! {"
+          An opaque cursor for cursor based pagination, encoding the ledger version and
+          the index of the next page. Use the cursor returned in the X-Aptos-Cursor header.
+        "})
+    )
+);
+
+impl_poem_type!(
+    LedgerVersionPin,
+    "string",
+    (
+        example = Some(serde_json::Value::String("046400000000000000".to_string())),
+        description = Some(indoc! {"
+          An opaque pin of a ledger version, encoding the chain ID and the pinned version.
+          Use the pin returned by the ledger version pin endpoint. A pin doesn't hold back
+          the pruner, so it gives no retention guarantee for the pinned version.
+        "})
+    )
+);
+
 impl_poem_type!(
     U64,
     "string",
@@ -257,6 +285,20 @@ impl_poem_type!(
     )
 );
 
+impl_poem_type!(
+    FieldSelection,
+    "string",
+    (
+        example = Some(serde_json::Value::String(
+            "hash,success,gas_used,payload.function".to_string()
+        )),
+        description = Some(indoc! {"
+          A comma separated list of fields to include in the response. Nested fields
+          are separated by dots, and apply to every element of an array.
+    "})
+    )
+);
+
 impl_poem_type!(
     U128,
     "string",
@@ -293,10 +335,13 @@ impl_poem_type!(
 
 impl_poem_parameter!(
     Address,
+    FieldSelection,
     HashValue,
     IdentifierWrapper,
     HexEncodedBytes,
+    LedgerVersionPin,
     MoveStructTag,
+    PageCursor,
     StateKeyWrapper,
     U64,
     U128
@@ -81,11 +81,20 @@ pub enum AptosErrorCode {
     BlockNotFound = 108,
     ///  StateValue not found at the requested version
     StateValueNotFound = 109,
+    /// Transaction expired in mempool before being committed
+    ///
+    /// Only reported for transactions submitted with the `notify` expiration behavior
+    TransactionExpired = 110,
+    /// Transaction was removed from mempool before being committed (e.g., it was
+    /// rejected during execution, or replaced by another transaction)
+    TransactionEvicted = 111,
 
     /// Ledger version is pruned
     VersionPruned = 200,
     /// Block is fully or partially pruned
     BlockPruned = 201,
+    /// The ledger version of a pin has been pruned, so a new pin must be taken
+    PinnedVersionPruned = 202,
 
     /// The API's inputs were invalid
     InvalidInput = 300,
@@ -96,11 +105,15 @@ pub enum AptosErrorCode {
     SequenceNumberTooOld = 402,
     /// The submitted transaction failed VM checks.
     VmError = 403,
+    /// The transaction was rejected by the node's transaction filter policy.
+    TransactionFiltered = 404,
 
     /// Health check failed.
     HealthCheckFailed = 500,
     /// The mempool is full, no new transactions can be submitted.
     MempoolIsFull = 501,
+    /// The sender exceeded the mempool submission rate limit, the transaction can be retried later.
+    MempoolRateLimited = 502,
 
     /// Internal server error
     InternalError = 600,
@@ -110,6 +123,10 @@ pub enum AptosErrorCode {
     BcsNotSupported = 602,
     /// API Disabled
     ApiDisabled = 603,
+    /// The API key of the request is unknown
+    ApiKeyInvalid = 604,
+    /// The request exceeded its quota (e.g., of its API key), the request can be retried later.
+    QuotaExceeded = 605,
 }
 
 impl AptosErrorCode {
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{LedgerInfo, U64};
+use crate::{LedgerInfo, LedgerVersionPin, U64};
 use aptos_config::config::RoleType;
 use poem_openapi::Object as PoemObject;
 use serde::{Deserialize, Serialize};
@@ -78,3 +78,20 @@ impl IndexResponseBcs {
         }
     }
 }
+
+/// A pinned ledger version, returned by the ledger version pin endpoint. Pass the
+/// pin as the `ledger_version_pin` of later reads to serve them all at this version.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PoemObject, Serialize)]
+pub struct PinnedLedgerVersion {
+    pub pin: LedgerVersionPin,
+    pub ledger_version: U64,
+}
+
+impl PinnedLedgerVersion {
+    pub fn new(pin: LedgerVersionPin) -> Self {
+        Self {
+            pin,
+            ledger_version: pin.ledger_version.into(),
+        }
+    }
+}
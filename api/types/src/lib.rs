@@ -15,8 +15,12 @@ mod hash;
 mod headers;
 mod index;
 mod ledger_info;
+mod mempool;
 pub mod mime_types;
+mod module_compatibility;
 mod move_types;
+mod projection;
+mod simulation;
 mod state;
 mod table;
 pub mod transaction;
@@ -25,14 +29,22 @@ mod wrappers;
 
 pub use account::AccountData;
 pub use address::Address;
+pub use aptos_light_client::{
+    IndexedTableItemsWithProof, StateValueWithProof, TransactionWithLedgerInfo,
+};
 pub use block::{BcsBlock, Block};
 pub use bytecode::Bytecode;
 pub use convert::{new_vm_utf8_string, AsConverter, ExplainVMStatus, MoveConverter};
 pub use error::{AptosError, AptosErrorCode};
 pub use hash::HashValue;
 pub use headers::*;
-pub use index::{IndexResponse, IndexResponseBcs};
+pub use index::{IndexResponse, IndexResponseBcs, PinnedLedgerVersion};
 pub use ledger_info::LedgerInfo;
+pub use mempool::{MempoolAccountDiagnostics, MempoolPendingTransaction, MempoolSequenceNumberGap};
+pub use module_compatibility::{
+    ModuleCompatibilityIssue, ModuleCompatibilityIssueKind, ModuleCompatibilityRequest,
+    ModuleCompatibilityResult,
+};
 pub use move_types::{
     verify_field_identifier, verify_function_identifier, verify_module_identifier, EntryFunctionId,
     HexEncodedBytes, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
@@ -40,24 +52,35 @@ pub use move_types::{
     MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType, MoveValue,
     ResourceGroup, MAX_RECURSIVE_TYPES_ALLOWED, U128, U256, U64,
 };
+pub use projection::{FieldSelection, Projected, MAX_PROJECTION_DEPTH, MAX_PROJECTION_FIELDS};
 use serde::{Deserialize, Deserializer};
+pub use simulation::{
+    BalanceOverride, ResourceOverride, SimulateSponsoredTransactionRequest,
+    SimulateTransactionRequest, SponsoredTransactionSimulation, SponsorshipDecision,
+    SponsorshipRequest, StateOverride,
+};
 pub use state::RawStateValueRequest;
 use std::str::FromStr;
-pub use table::{RawTableItemRequest, TableItemRequest};
+pub use table::{
+    IndexedTableItem, IndexedTableItemsRequest, IndexedTableLayout, RawTableItemRequest,
+    TableItemRequest,
+};
 pub use transaction::{
     AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
-    DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload, Event,
-    FeePayerSignature, GasEstimation, GasEstimationBcs, GenesisPayload, GenesisTransaction,
-    ModuleBundlePayload, MultiAgentSignature, MultiEd25519Signature, MultisigPayload,
-    MultisigTransactionPayload, PendingTransaction, ScriptPayload, ScriptWriteSet,
-    SubmitTransactionRequest, Transaction, TransactionData, TransactionId, TransactionInfo,
-    TransactionOnChainData, TransactionPayload, TransactionSignature, TransactionSigningMessage,
-    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult,
-    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, VersionedEvent,
-    WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem,
+    DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload,
+    EstimateGasPriceRequest, Event, FeePayerSignature, GasEstimatePercentiles, GasEstimation,
+    GasEstimationBcs, GenesisPayload, GenesisTransaction, ModuleBundlePayload, MultiAgentSignature,
+    MultiEd25519Signature, MultisigPayload, MultisigTransactionPayload, PendingTransaction,
+    ScriptPayload, ScriptWriteSet, SubmitTransactionRequest, Transaction, TransactionData,
+    TransactionExpirationBehavior, TransactionId, TransactionInfo, TransactionOnChainData,
+    TransactionPayload, TransactionSignature, TransactionSigningMessage, TransactionTtlTier,
+    TransactionWaitStage, TransactionsBatchSingleSubmissionFailure,
+    TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
+    UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
+    WriteSetPayload, WriteTableItem,
 };
-pub use view::ViewRequest;
-pub use wrappers::{EventGuid, IdentifierWrapper, StateKeyWrapper};
+pub use view::{ViewFunctionResult, ViewRequest};
+pub use wrappers::{EventGuid, IdentifierWrapper, LedgerVersionPin, PageCursor, StateKeyWrapper};
 
 pub fn deserialize_from_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
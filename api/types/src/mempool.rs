@@ -0,0 +1,49 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{HashValue, U64};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// The mempool's view of the sequence numbers of an account
+///
+/// Used to debug transactions that are stuck in mempool, e.g., behind a
+/// missing sequence number.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolAccountDiagnostics {
+    /// The sequence number of the account, as last known by mempool
+    pub committed_sequence_number: Option<U64>,
+    /// The transactions of the account pending in mempool, by sequence number
+    pub pending_transactions: Vec<MempoolPendingTransaction>,
+    /// The missing sequence numbers that prevent pending transactions from
+    /// being included in a block
+    pub gaps: Vec<MempoolSequenceNumberGap>,
+}
+
+/// A transaction pending in mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolPendingTransaction {
+    pub sequence_number: U64,
+    pub hash: HashValue,
+    pub gas_unit_price: U64,
+    /// The time the transaction was inserted into mempool, in microseconds
+    pub insertion_timestamp_usecs: U64,
+    /// Whether the transaction is parked, i.e., can't be included in the next block
+    pub is_parked: bool,
+    /// Whether the transaction was pulled into a batch of a block proposal. This doesn't
+    /// mean it's ordered yet. Only known on validators, so it's always false on fullnodes.
+    pub in_consensus: bool,
+    /// The peer the transaction was first received from, formatted as
+    /// `network:peer_id`. Missing if the transaction was submitted to this node.
+    pub received_from: Option<String>,
+}
+
+/// A range of missing sequence numbers (both ends inclusive)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolSequenceNumberGap {
+    pub start_sequence_number: U64,
+    pub end_sequence_number: U64,
+    /// The peer that first sent the transaction right after the gap, formatted
+    /// as `network:peer_id`. Missing if that transaction was submitted to this node.
+    pub first_seen_by: Option<String>,
+}
@@ -0,0 +1,92 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{HexEncodedBytes, MoveModuleId};
+use aptos_vm::{CompatibilityIssue, CompatibilityIssueKind, ModuleCompatibilityReport};
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+
+/// A request to check the compatibility of a module bundle with the deployed code
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ModuleCompatibilityRequest {
+    /// The BCS encoded bytecode of each module in the bundle
+    pub modules: Vec<HexEncodedBytes>,
+}
+
+/// The kind of a backward incompatible module change
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum ModuleCompatibilityIssueKind {
+    /// A struct was removed
+    StructRemoved,
+    /// A struct lost abilities or its type parameters changed incompatibly
+    StructSignatureChanged,
+    /// The fields of a struct changed
+    StructLayoutChanged,
+    /// A public, friend or entry function was removed
+    FunctionRemoved,
+    /// The visibility, entry modifier or signature of a function changed
+    FunctionSignatureChanged,
+    /// A friend declaration was removed
+    FriendRemoved,
+}
+
+impl From<CompatibilityIssueKind> for ModuleCompatibilityIssueKind {
+    fn from(kind: CompatibilityIssueKind) -> Self {
+        match kind {
+            CompatibilityIssueKind::StructRemoved => Self::StructRemoved,
+            CompatibilityIssueKind::StructSignatureChanged => Self::StructSignatureChanged,
+            CompatibilityIssueKind::StructLayoutChanged => Self::StructLayoutChanged,
+            CompatibilityIssueKind::FunctionRemoved => Self::FunctionRemoved,
+            CompatibilityIssueKind::FunctionSignatureChanged => Self::FunctionSignatureChanged,
+            CompatibilityIssueKind::FriendRemoved => Self::FriendRemoved,
+        }
+    }
+}
+
+/// A single backward incompatible change of a module
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ModuleCompatibilityIssue {
+    pub kind: ModuleCompatibilityIssueKind,
+    /// The name of the affected struct, function or friend module
+    pub name: String,
+    /// A human readable description of the change
+    pub message: String,
+    /// Whether the change would cause the publish to fail
+    pub blocks_publish: bool,
+}
+
+impl From<CompatibilityIssue> for ModuleCompatibilityIssue {
+    fn from(issue: CompatibilityIssue) -> Self {
+        Self {
+            kind: issue.kind.into(),
+            name: issue.name,
+            message: issue.message,
+            blocks_publish: issue.blocks_publish,
+        }
+    }
+}
+
+/// A report of the compatibility of a module with the deployed version of the module
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ModuleCompatibilityResult {
+    pub module: MoveModuleId,
+    /// Whether the module is not yet deployed
+    pub new_module: bool,
+    /// Whether the module can be published (i.e., no issue blocks the publish)
+    pub compatible: bool,
+    /// All backward incompatible changes versus the deployed module
+    pub issues: Vec<ModuleCompatibilityIssue>,
+}
+
+impl From<ModuleCompatibilityReport> for ModuleCompatibilityResult {
+    fn from(report: ModuleCompatibilityReport) -> Self {
+        Self {
+            compatible: report.is_compatible(),
+            module: report.module_id.into(),
+            new_module: report.is_new_module,
+            issues: report.issues.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for partial responses. Clients can pass a list of fields via the
+//! `fields` query parameter (e.g. `?fields=hash,success,gas_used,payload.function`)
+//! and only those fields are returned in the JSON response. This is applied
+//! after the full response has been rendered, so it saves bandwidth rather
+//! than work on the server.
+
+use anyhow::{bail, ensure};
+use poem_openapi::{
+    registry::{MetaSchemaRef, Registry},
+    types::{ToJSON, Type},
+};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::{borrow::Cow, collections::BTreeMap, fmt, str::FromStr};
+
+/// Max number of field paths that can be requested at once.
+pub const MAX_PROJECTION_FIELDS: usize = 64;
+/// Max depth of a single field path, e.g. `payload.function` has a depth of 2.
+pub const MAX_PROJECTION_DEPTH: usize = 8;
+
+/// A set of (possibly nested) fields to keep in a JSON response.
+///
+/// Fields are separated by commas and nested fields by dots. A field without
+/// nested fields selects the whole value. Arrays are transparent: selecting
+/// `events.type` on a transaction keeps only the `type` of every event.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FieldSelection(BTreeMap<String, FieldSelection>);
+
+impl FieldSelection {
+    /// Prunes the value down to the selected fields
+    pub fn project(&self, value: Value) -> Value {
+        match value {
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .filter_map(|(key, value)| {
+                        self.0.get(&key).map(|nested| {
+                            let value = if nested.0.is_empty() {
+                                value
+                            } else {
+                                nested.project(value)
+                            };
+                            (key, value)
+                        })
+                    })
+                    .collect(),
+            ),
+            Value::Array(values) => Value::Array(
+                values
+                    .into_iter()
+                    .map(|value| self.project(value))
+                    .collect(),
+            ),
+            // Selecting nested fields of a scalar doesn't remove anything
+            value => value,
+        }
+    }
+
+    fn paths(&self, prefix: &str, paths: &mut Vec<String>) {
+        for (field, nested) in &self.0 {
+            let path = if prefix.is_empty() {
+                field.clone()
+            } else {
+                format!("{}.{}", prefix, field)
+            };
+            if nested.0.is_empty() {
+                paths.push(path);
+            } else {
+                nested.paths(&path, paths);
+            }
+        }
+    }
+}
+
+impl FromStr for FieldSelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self, anyhow::Error> {
+        let paths: Vec<_> = s.split(',').map(str::trim).collect();
+        ensure!(
+            paths.len() <= MAX_PROJECTION_FIELDS,
+            "At most {} fields can be selected, got {}",
+            MAX_PROJECTION_FIELDS,
+            paths.len()
+        );
+
+        let mut selection = FieldSelection::default();
+        for path in paths {
+            let fields: Vec<_> = path.split('.').collect();
+            ensure!(
+                fields.len() <= MAX_PROJECTION_DEPTH,
+                "Field {} is nested deeper than {} levels",
+                path,
+                MAX_PROJECTION_DEPTH
+            );
+
+            let mut current = &mut selection;
+            for (index, field) in fields.iter().enumerate() {
+                if field.is_empty() || !field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    bail!("Invalid field {:?} in field selection {:?}", path, s);
+                }
+                let is_leaf = index == fields.len() - 1;
+                // A field that was already selected in full (e.g. `payload` in
+                // `payload,payload.function`) stays selected in full.
+                let selected_in_full = current.0.get(*field).map(|nested| nested.0.is_empty());
+                let nested = current.0.entry(field.to_string()).or_default();
+                if is_leaf {
+                    nested.0.clear();
+                    break;
+                }
+                if selected_in_full == Some(true) {
+                    break;
+                }
+                current = nested;
+            }
+        }
+        Ok(selection)
+    }
+}
+
+impl fmt::Display for FieldSelection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut paths = vec![];
+        self.paths("", &mut paths);
+        write!(f, "{}", paths.join(","))
+    }
+}
+
+impl Serialize for FieldSelection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldSelection {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = <String>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// A response value that is rendered with only the selected fields. In the
+/// OpenAPI spec it is indistinguishable from the wrapped type, as every field
+/// selection is a valid (partial) instance of it.
+pub struct Projected<T> {
+    inner: T,
+    fields: Option<FieldSelection>,
+}
+
+impl<T> Projected<T> {
+    pub fn new(inner: T, fields: Option<FieldSelection>) -> Self {
+        Self { inner, fields }
+    }
+}
+
+impl<T: Type> Type for Projected<T> {
+    type RawElementValueType = Self;
+    type RawValueType = Self;
+
+    const IS_REQUIRED: bool = T::IS_REQUIRED;
+
+    fn name() -> Cow<'static, str> {
+        T::name()
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry)
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        T::schema_ref()
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(self)
+    }
+
+    fn raw_element_iter<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+        Box::new(self.as_raw_value().into_iter())
+    }
+}
+
+impl<T: ToJSON> ToJSON for Projected<T> {
+    fn to_json(&self) -> Option<Value> {
+        let value = self.inner.to_json()?;
+        Some(match &self.fields {
+            Some(fields) => fields.project(value),
+            None => value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_and_display() {
+        let selection: FieldSelection = "hash, payload.function,events.type,payload.arguments"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            selection.to_string(),
+            "events.type,hash,payload.arguments,payload.function"
+        );
+
+        // Selecting the whole field wins over selecting nested fields, in any order
+        let selection: FieldSelection = "payload.function,payload".parse().unwrap();
+        assert_eq!(selection.to_string(), "payload");
+        let selection: FieldSelection = "payload,payload.function".parse().unwrap();
+        assert_eq!(selection.to_string(), "payload");
+
+        assert!("".parse::<FieldSelection>().is_err());
+        assert!("hash,".parse::<FieldSelection>().is_err());
+        assert!("payload..function".parse::<FieldSelection>().is_err());
+        assert!("hash;".parse::<FieldSelection>().is_err());
+        assert!(vec!["a"; MAX_PROJECTION_FIELDS + 1]
+            .join(",")
+            .parse::<FieldSelection>()
+            .is_err());
+        assert!(vec!["a"; MAX_PROJECTION_DEPTH + 1]
+            .join(".")
+            .parse::<FieldSelection>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_project() {
+        let transactions = json!([
+            {
+                "type": "user_transaction",
+                "hash": "0x1",
+                "success": true,
+                "payload": {"function": "0x1::coin::transfer", "arguments": ["0x2", "10"]},
+                "events": [{"type": "0x1::coin::WithdrawEvent", "data": {"amount": "10"}}],
+            },
+            {
+                "type": "block_metadata_transaction",
+                "hash": "0x2",
+                "success": true,
+            },
+        ]);
+
+        let selection: FieldSelection = "hash,payload.function,events.type".parse().unwrap();
+        assert_eq!(
+            selection.project(transactions),
+            json!([
+                {
+                    "hash": "0x1",
+                    "payload": {"function": "0x1::coin::transfer"},
+                    "events": [{"type": "0x1::coin::WithdrawEvent"}],
+                },
+                {
+                    "hash": "0x2",
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    Address, MoveStructTag, SubmitTransactionRequest, TransactionPayload, UserTransaction,
+    VerifyInput, VerifyInputWithRecursion, U64,
+};
+use poem_openapi::{Object, Union};
+use serde::{Deserialize, Serialize};
+
+/// A request to simulate a transaction
+///
+/// This is a SubmitTransactionRequest with an optional set of state overrides,
+/// which are applied on top of the latest ledger state before the transaction
+/// is simulated.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SimulateTransactionRequest {
+    #[serde(flatten)]
+    #[oai(flatten)]
+    pub transaction: SubmitTransactionRequest,
+    /// State to override before simulating the transaction
+    ///
+    /// Overrides are applied in order, so later overrides of the same state
+    /// take precedence over earlier ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<Vec<StateOverride>>,
+}
+
+impl VerifyInput for SimulateTransactionRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.transaction.verify()?;
+        for state_override in self.state_overrides.iter().flatten() {
+            state_override.verify()?;
+        }
+        Ok(())
+    }
+}
+
+/// An override of on-chain state, used for simulating transactions
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
+pub enum StateOverride {
+    ResourceOverride(ResourceOverride),
+    BalanceOverride(BalanceOverride),
+}
+
+impl VerifyInput for StateOverride {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            StateOverride::ResourceOverride(inner) => inner.resource_type.verify(0),
+            StateOverride::BalanceOverride(_) => Ok(()),
+        }
+    }
+}
+
+/// Overrides (or deletes) a resource under an account
+///
+/// Resources stored in resource groups can't be overridden.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ResourceOverride {
+    pub address: Address,
+    pub resource_type: MoveStructTag,
+    /// The new value of the resource, in the same JSON format as the resource
+    /// APIs. If not set, the resource is deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// Overrides the APT balance of an account
+///
+/// The account must already have a `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct BalanceOverride {
+    pub address: Address,
+    /// The new balance of the account, in octas
+    pub amount: U64,
+}
+
+/// A request to simulate a sponsored (i.e., fee payer) transaction
+///
+/// The transaction is simulated as if it were signed by the sender, the secondary
+/// signers and the fee payer, so no signatures (or public keys) are needed. This
+/// allows a fee payer (e.g., a gas station) to check what it would pay for an
+/// arbitrary sender's transaction, before the sender signs it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SimulateSponsoredTransactionRequest {
+    pub sender: Address,
+    pub fee_payer_address: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_signers: Option<Vec<Address>>,
+    pub payload: TransactionPayload,
+    /// The max gas amount of the transaction. If not set, the max amount
+    /// the fee payer can afford is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_gas_amount: Option<U64>,
+    /// The gas unit price of the transaction. If not set, the estimated
+    /// gas unit price is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_unit_price: Option<U64>,
+}
+
+impl VerifyInput for SimulateSponsoredTransactionRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.payload.verify()
+    }
+}
+
+/// The result of simulating a sponsored transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SponsoredTransactionSimulation {
+    pub transaction: UserTransaction,
+    /// The gas station's decision on sponsoring the transaction. This is only
+    /// set if the node has a gas station configured, and the simulation succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsorship: Option<SponsorshipDecision>,
+}
+
+/// A request to a gas station to sponsor (i.e., pay the gas fees of) a transaction,
+/// along with the results of simulating the transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SponsorshipRequest {
+    pub sender: Address,
+    pub fee_payer_address: Address,
+    pub secondary_signers: Vec<Address>,
+    pub payload: TransactionPayload,
+    pub max_gas_amount: U64,
+    pub gas_unit_price: U64,
+    /// The gas used by the simulated transaction
+    pub gas_used: U64,
+}
+
+/// A gas station's decision on whether to sponsor a transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SponsorshipDecision {
+    pub approved: bool,
+    /// The reason for the decision (e.g., why it was rejected)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_state_overrides() {
+        let overrides: Vec<StateOverride> = serde_json::from_value(json!([
+            {
+                "type": "resource_override",
+                "address": "0x1",
+                "resource_type": "0x1::account::Account",
+            },
+            {
+                "type": "balance_override",
+                "address": "0x1",
+                "amount": "100",
+            },
+        ]))
+        .unwrap();
+
+        assert_eq!(overrides, vec![
+            StateOverride::ResourceOverride(ResourceOverride {
+                address: "0x1".parse().unwrap(),
+                resource_type: "0x1::account::Account".parse().unwrap(),
+                data: None,
+            }),
+            StateOverride::BalanceOverride(BalanceOverride {
+                address: "0x1".parse().unwrap(),
+                amount: 100.into(),
+            }),
+        ]);
+    }
+}
@@ -1,8 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{HexEncodedBytes, MoveType, VerifyInput, VerifyInputWithRecursion};
-use poem_openapi::Object;
+use crate::{HexEncodedBytes, MoveType, MoveValue, VerifyInput, VerifyInputWithRecursion, U64};
+use anyhow::{bail, format_err};
+use move_core_types::{
+    ident_str,
+    language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS},
+};
+use poem_openapi::{Enum, Object};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -27,3 +32,74 @@ impl VerifyInput for TableItemRequest {
 pub struct RawTableItemRequest {
     pub key: HexEncodedBytes,
 }
+
+/// The layout of a table keyed by contiguous `u64` indices (starting at 0)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum IndexedTableLayout {
+    /// The buckets table of a `0x1::smart_table::SmartTable<K, V>`
+    SmartTable,
+    /// The buckets table of a `0x1::big_vector::BigVector<T>`
+    BigVector,
+}
+
+/// Indexed Table Items request for the GetIndexedTableItems API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct IndexedTableItemsRequest {
+    pub layout: IndexedTableLayout,
+    /// The key type of the smart table (not used by big vectors)
+    pub key_type: Option<MoveType>,
+    /// The value type of the smart table, or the element type of the big vector
+    pub value_type: MoveType,
+}
+
+impl IndexedTableItemsRequest {
+    /// Returns the type of the table items (i.e., the buckets) for the layout
+    pub fn item_type(&self) -> anyhow::Result<TypeTag> {
+        let value_type: TypeTag = self.value_type.clone().try_into()?;
+        let element_type = match self.layout {
+            IndexedTableLayout::SmartTable => {
+                let key_type = self
+                    .key_type
+                    .as_ref()
+                    .ok_or_else(|| format_err!("The key type of a smart table must be given"))?;
+                TypeTag::Struct(Box::new(StructTag {
+                    address: CORE_CODE_ADDRESS,
+                    module: ident_str!("smart_table").to_owned(),
+                    name: ident_str!("Entry").to_owned(),
+                    type_params: vec![key_type.clone().try_into()?, value_type],
+                }))
+            },
+            IndexedTableLayout::BigVector => value_type,
+        };
+        Ok(TypeTag::Vector(Box::new(element_type)))
+    }
+}
+
+impl VerifyInput for IndexedTableItemsRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        match (self.layout, &self.key_type) {
+            (IndexedTableLayout::SmartTable, None) => {
+                bail!("The key type of a smart table must be given")
+            },
+            (IndexedTableLayout::BigVector, Some(_)) => {
+                bail!("A big vector doesn't have a key type")
+            },
+            _ => {},
+        }
+        if let Some(key_type) = &self.key_type {
+            key_type.verify(0)?;
+        }
+        self.value_type.verify(0)
+    }
+}
+
+/// An item of a table keyed by contiguous indices (e.g., a bucket of a smart table)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct IndexedTableItem {
+    /// The index of the item in the table
+    pub index: U64,
+    /// The value of the item
+    pub value: MoveValue,
+}
@@ -21,7 +21,7 @@ use aptos_types::{
         Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
 };
-use poem_openapi::{Object, Union};
+use poem_openapi::{Enum, Object, Union};
 use serde::{Deserialize, Serialize};
 use std::{
     boxed::Box,
@@ -1400,6 +1400,41 @@ impl TransactionSigningMessage {
     }
 }
 
+/// How long a submitted transaction can stay pending in mempool
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum TransactionTtlTier {
+    /// The default mempool TTL of the node
+    Standard,
+    /// A shorter TTL, for transactions that are useless if not committed quickly
+    Short,
+}
+
+/// What happens when a submitted transaction expires in mempool
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum TransactionExpirationBehavior {
+    /// The transaction is silently dropped, and is no longer found by hash
+    Drop,
+    /// The expiration is recorded, and looking up the transaction by hash fails
+    /// with the `transaction_expired` error code
+    Notify,
+}
+
+/// The stage a submitted transaction must reach before the submission returns
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum TransactionWaitStage {
+    /// The transaction has been executed and removed from mempool. It may not
+    /// yet be readable from the node.
+    Executed,
+    /// The transaction has been committed to storage, and can be read from the node
+    Committed,
+}
+
 /// Struct holding the outputs of the estimate gas API
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct GasEstimationBcs {
@@ -1414,6 +1449,39 @@ pub struct GasEstimation {
     pub deprioritized_gas_estimate: Option<u64>,
     /// The current estimate for the gas unit price
     pub gas_estimate: u64,
+    /// Percentiles of the gas unit price required for inclusion in recent blocks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub gas_estimate_percentiles: Option<GasEstimatePercentiles>,
+    /// The suggested max gas amount for the given transaction payload, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub max_gas_amount_estimate: Option<u64>,
     /// The prioritized estimate for the gas unit price
     pub prioritized_gas_estimate: Option<u64>,
 }
+
+/// Percentiles of the minimum gas unit price that was required for inclusion
+/// in recent blocks
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct GasEstimatePercentiles {
+    pub p25: u64,
+    pub p50: u64,
+    pub p90: u64,
+}
+
+/// Request to estimate the gas of a transaction payload
+///
+/// The payload is simulated from the given sender, so the sender must exist
+/// on chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct EstimateGasPriceRequest {
+    pub sender: Address,
+    pub payload: TransactionPayload,
+}
+
+impl VerifyInput for EstimateGasPriceRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.payload.verify()
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{EntryFunctionId, MoveType};
+use crate::{AptosError, EntryFunctionId, MoveType, MoveValue};
 use poem_openapi::Object;
 use serde::{Deserialize, Serialize};
 
@@ -14,3 +14,29 @@ pub struct ViewRequest {
     /// Arguments of the function
     pub arguments: Vec<serde_json::Value>,
 }
+
+/// The result of a single view function executed as part of a batch
+///
+/// Exactly one of `result` or `error` is set.
+#[derive(Clone, Debug, Serialize, Object)]
+pub struct ViewFunctionResult {
+    /// The values returned by the view function
+    pub result: Option<Vec<MoveValue>>,
+    /// The error encountered while executing the view function
+    pub error: Option<AptosError>,
+}
+
+impl From<Result<Vec<MoveValue>, AptosError>> for ViewFunctionResult {
+    fn from(result: Result<Vec<MoveValue>, AptosError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => Self {
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+}
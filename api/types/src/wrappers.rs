@@ -151,3 +151,133 @@ impl From<StateKeyWrapper> for StateKey {
         value.0
     }
 }
+
+/// An opaque cursor used for cursor based pagination. It encodes the ledger
+/// version the page was served at, and the index (e.g., the transaction
+/// version or the sequence number) of the first item of the next page.
+///
+/// Cursors are serialized as hex encoded BCS bytes, and must not be built
+/// client-side. Instead, use the cursor returned in the X-Aptos-Cursor header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PageCursor {
+    pub ledger_version: u64,
+    pub index: u64,
+}
+
+impl PageCursor {
+    pub fn new(ledger_version: u64, index: u64) -> Self {
+        Self {
+            ledger_version,
+            index,
+        }
+    }
+}
+
+impl fmt::Display for PageCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = bcs::to_bytes(&(self.ledger_version, self.index)).map_err(|_| fmt::Error)?;
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+impl FromStr for PageCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self, anyhow::Error> {
+        let bytes = hex::decode(s).context("Failed to decode cursor as hex string")?;
+        let (ledger_version, index) =
+            bcs::from_bytes(&bytes).context("Failed to decode cursor from hex string")?;
+        Ok(Self::new(ledger_version, index))
+    }
+}
+
+impl Serialize for PageCursor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PageCursor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::deserialize_from_string(deserializer)
+    }
+}
+
+/// An opaque pin of a ledger version, used to read the state of a chain at the same
+/// version across multiple calls (e.g., a paginated list of resources and then the
+/// items of a table), even as the node commits new transactions. It encodes the chain
+/// ID and the pinned ledger version.
+///
+/// A pin doesn't hold back the pruner, so it gives no retention guarantee beyond
+/// `ledger_version` itself: once that version is pruned, the pin can't be resolved.
+///
+/// Pins are serialized as hex encoded BCS bytes, and must not be built client-side.
+/// Instead, use the pin returned by the ledger version pin endpoint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LedgerVersionPin {
+    pub chain_id: u8,
+    pub ledger_version: u64,
+}
+
+impl LedgerVersionPin {
+    pub fn new(chain_id: u8, ledger_version: u64) -> Self {
+        Self {
+            chain_id,
+            ledger_version,
+        }
+    }
+}
+
+impl fmt::Display for LedgerVersionPin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = bcs::to_bytes(&(self.chain_id, self.ledger_version)).map_err(|_| fmt::Error)?;
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+impl FromStr for LedgerVersionPin {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self, anyhow::Error> {
+        let bytes = hex::decode(s).context("Failed to decode ledger version pin as hex string")?;
+        let (chain_id, ledger_version) = bcs::from_bytes(&bytes)
+            .context("Failed to decode ledger version pin from hex string")?;
+        Ok(Self::new(chain_id, ledger_version))
+    }
+}
+
+impl Serialize for LedgerVersionPin {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LedgerVersionPin {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::deserialize_from_string(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LedgerVersionPin, PageCursor};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_page_cursor_round_trip() {
+        let cursor = PageCursor::new(100, 10);
+        assert_eq!(cursor.to_string(), "64000000000000000a00000000000000");
+        assert_eq!(PageCursor::from_str(&cursor.to_string()).unwrap(), cursor);
+        assert!(PageCursor::from_str("not a cursor").is_err());
+        assert!(PageCursor::from_str("6400").is_err());
+    }
+
+    #[test]
+    fn test_ledger_version_pin_round_trip() {
+        let pin = LedgerVersionPin::new(4, 100);
+        assert_eq!(pin.to_string(), "046400000000000000");
+        assert_eq!(LedgerVersionPin::from_str(&pin.to_string()).unwrap(), pin);
+        assert!(LedgerVersionPin::from_str("not a pin").is_err());
+        assert!(LedgerVersionPin::from_str("0464").is_err());
+    }
+}
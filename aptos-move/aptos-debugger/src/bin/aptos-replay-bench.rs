@@ -0,0 +1,600 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use aptos_backup_cli::{
+    backup_types::transaction::{
+        manifest::{TransactionBackup, TransactionChunk},
+        restore::LoadedChunk,
+    },
+    metadata::{self, cache::MetadataCacheOpt},
+    storage::{
+        command_adapter::{CommandAdapter, CommandAdapterOpt},
+        local_fs::{LocalFs, LocalFsOpt},
+        BackupStorage,
+    },
+    utils::{storage_ext::BackupStorageExt, ConcurrentDownloadsOpt},
+};
+use aptos_debugger::{is_reconfiguration, AptosDebugger};
+use aptos_executor_benchmark::native_executor::NativeExecutor;
+use aptos_rest_client::Client;
+use aptos_types::transaction::{
+    ExecutionStatus, Transaction, TransactionInfo, TransactionOutput, TransactionStatus, Version,
+};
+use aptos_validator_interface::DebuggerStateView;
+use aptos_vm::{AptosVM, VMExecutor};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::{
+    collections::VecDeque,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use url::Url;
+
+#[derive(Subcommand)]
+pub enum Target {
+    /// Use full node's rest api as query endpoint.
+    Rest { endpoint: String },
+    /// Use a local db instance to serve as query endpoint.
+    DB { path: PathBuf },
+}
+
+/// The executor used to replay the transactions
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExecutorKind {
+    /// Execute each block sequentially (i.e., on a single thread)
+    Sequential,
+    /// Execute each block in parallel using Block-STM
+    BlockStm,
+    /// Execute each block natively (i.e., without the VM), in parallel. Only the coin transfers
+    /// (and account creations) are supported, and their outputs only approximate the ones of the
+    /// VM, so the state equivalence isn't checked.
+    Native,
+}
+
+#[derive(Parser)]
+pub struct Argument {
+    /// The query endpoint the state (and, unless a backup is set, the transactions) is read from.
+    #[clap(subcommand)]
+    target: Target,
+
+    /// Read the transactions from a LocalFs backup storage in this dir.
+    #[clap(long)]
+    backup_local_fs_dir: Option<LocalFsOpt>,
+
+    /// Read the transactions from a CommandAdapter backup storage with this config file.
+    #[clap(long, conflicts_with = "backup_local_fs_dir")]
+    backup_command_adapter_config: Option<CommandAdapterOpt>,
+
+    #[clap(flatten)]
+    metadata_cache_opt: MetadataCacheOpt,
+
+    #[clap(flatten)]
+    concurrent_downloads: ConcurrentDownloadsOpt,
+
+    #[clap(long)]
+    begin_version: u64,
+
+    #[clap(long)]
+    limit: u64,
+
+    /// The executor used to replay the transactions.
+    #[clap(long, value_enum, default_value_t = ExecutorKind::BlockStm)]
+    executor: ExecutorKind,
+
+    /// The number of Block-STM (or native executor) threads (ignored by the sequential executor).
+    #[clap(long, default_value_t = 8)]
+    concurrency_level: usize,
+
+    /// The max number of transactions fetched and executed as a single block.
+    /// Blocks are always cut at epoch boundaries.
+    #[clap(long, default_value_t = 1000)]
+    block_size: u64,
+
+    /// If set, each block is executed once before it is timed, so that the
+    /// measurement excludes fetching the state from the query endpoint.
+    #[clap(long)]
+    warm_up: bool,
+}
+
+/// The statistics of a replayed version range
+#[derive(Debug, Default)]
+struct ReplayStats {
+    num_blocks: u64,
+    num_txns: u64,
+    num_success: u64,
+    num_aborted: u64,   // Kept transactions that failed (e.g., Move aborts)
+    num_discarded: u64, // Discarded (or retried) transactions
+    num_mismatches: u64,
+    first_mismatch: Option<Version>,
+    execution_time: Duration,
+}
+
+impl ReplayStats {
+    /// Updates the stats with the outputs of an executed block, and compares the outputs
+    /// with the transaction infos committed on-chain (if any are given).
+    fn record_block(
+        &mut self,
+        first_version: Version,
+        txn_outputs: &[TransactionOutput],
+        expected_txn_infos: Option<&[TransactionInfo]>,
+        execution_time: Duration,
+    ) {
+        self.num_blocks += 1;
+        self.num_txns += txn_outputs.len() as u64;
+        self.execution_time += execution_time;
+
+        for txn_output in txn_outputs {
+            match txn_output.status() {
+                TransactionStatus::Keep(ExecutionStatus::Success) => self.num_success += 1,
+                TransactionStatus::Keep(_) => self.num_aborted += 1,
+                TransactionStatus::Discard(_) | TransactionStatus::Retry => self.num_discarded += 1,
+            }
+        }
+
+        // Matching the write set hash (i.e., the state change hash) of each
+        // transaction implies the replay produces the same state root.
+        let expected_txn_infos = expected_txn_infos.unwrap_or_default();
+        for (idx, (txn_output, txn_info)) in txn_outputs.iter().zip(expected_txn_infos).enumerate()
+        {
+            let version = first_version + idx as Version;
+            if let Err(error) =
+                txn_output.ensure_match_transaction_info(version, txn_info, None, None)
+            {
+                println!("{}", error);
+                self.num_mismatches += 1;
+                self.first_mismatch.get_or_insert(version);
+            }
+        }
+    }
+
+    fn print_report(&self, executor: ExecutorKind, concurrency_level: usize) {
+        let seconds = self.execution_time.as_secs_f64();
+        let tps = if seconds > 0.0 {
+            self.num_txns as f64 / seconds
+        } else {
+            0.0
+        };
+        let rate = |count: u64| {
+            if self.num_txns > 0 {
+                100.0 * count as f64 / self.num_txns as f64
+            } else {
+                0.0
+            }
+        };
+
+        println!("===================");
+        println!("Replay Summary");
+        println!("===================");
+        println!(
+            "Executor: {:?} (concurrency level: {})",
+            executor, concurrency_level
+        );
+        println!("Blocks: {}", self.num_blocks);
+        println!("Transactions: {}", self.num_txns);
+        println!("Execution time: {:.3}s", seconds);
+        println!("TPS: {:.1}", tps);
+        println!(
+            "Succeeded: {} ({:.2}%)",
+            self.num_success,
+            rate(self.num_success)
+        );
+        println!(
+            "Aborted: {} ({:.2}%)",
+            self.num_aborted,
+            rate(self.num_aborted)
+        );
+        println!(
+            "Discarded: {} ({:.2}%)",
+            self.num_discarded,
+            rate(self.num_discarded)
+        );
+        match (executor, self.first_mismatch) {
+            (ExecutorKind::Native, _) => println!(
+                "State equivalence: not checked (the native executor only approximates the outputs)"
+            ),
+            (_, Some(version)) => println!(
+                "State equivalence: FAILED ({} mismatched transactions, first at version {})",
+                self.num_mismatches, version
+            ),
+            (_, None) => println!(
+                "State equivalence: OK (all outputs match the committed transaction infos)"
+            ),
+        }
+    }
+}
+
+/// Reads the committed transactions (and their infos) of a version range from a backup storage
+struct BackupReader {
+    storage: Arc<dyn BackupStorage>,
+    /// The chunks (overlapping the range) that haven't been loaded yet
+    chunks: VecDeque<TransactionChunk>,
+    /// The version of the first buffered transaction (i.e., the next one to be read)
+    next_version: Version,
+    txns: VecDeque<(Transaction, TransactionInfo)>,
+}
+
+impl BackupReader {
+    async fn new(
+        storage: Arc<dyn BackupStorage>,
+        metadata_cache_opt: &MetadataCacheOpt,
+        concurrent_downloads: usize,
+        begin_version: Version,
+        end_version: Version,
+    ) -> Result<Self> {
+        ensure!(
+            begin_version < end_version,
+            "The version range [{}, {}) is empty!",
+            begin_version,
+            end_version
+        );
+        let metadata_view = metadata::cache::sync_and_load(
+            metadata_cache_opt,
+            storage.clone(),
+            concurrent_downloads,
+        )
+        .await?;
+
+        let mut chunks = VecDeque::new();
+        for backup in metadata_view.select_transaction_backups(begin_version, end_version - 1)? {
+            let manifest: TransactionBackup = storage.load_json_file(&backup.manifest).await?;
+            manifest.verify()?;
+            chunks.extend(manifest.chunks.into_iter().filter(|chunk| {
+                chunk.last_version >= begin_version && chunk.first_version < end_version
+            }));
+        }
+
+        Ok(Self {
+            storage,
+            chunks,
+            next_version: begin_version,
+            txns: VecDeque::new(),
+        })
+    }
+
+    /// Returns (at most) `limit` transactions starting at version `begin`. The
+    /// transactions must be read in order (i.e., `begin` must follow the last
+    /// transaction returned).
+    async fn get_committed_transactions(
+        &mut self,
+        begin: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        ensure!(
+            begin == self.next_version,
+            "The backup must be read in order, expecting version {}, got {}.",
+            self.next_version,
+            begin
+        );
+
+        while (self.txns.len() as u64) < limit {
+            let chunk = match self.chunks.pop_front() {
+                Some(chunk) => chunk,
+                None => break,
+            };
+            let buffered_end = self.next_version + self.txns.len() as Version;
+            ensure!(
+                chunk.first_version <= buffered_end,
+                "Transaction backups not continuous, expecting version {}, got {}.",
+                buffered_end,
+                chunk.first_version
+            );
+            // The first chunk can start before the replayed range
+            let num_skipped = (buffered_end - chunk.first_version) as usize;
+
+            // Without the epoch history, the ledger infos aren't verified, but the
+            // transactions are verified against the ledger info of their chunk.
+            let (txns, txn_infos, _, _) = LoadedChunk::load(chunk, &self.storage, None)
+                .await?
+                .unpack();
+            self.txns
+                .extend(txns.into_iter().zip(txn_infos).skip(num_skipped));
+        }
+
+        let num_txns = std::cmp::min(limit as usize, self.txns.len());
+        self.next_version += num_txns as Version;
+        Ok(self.txns.drain(..num_txns).unzip())
+    }
+}
+
+/// Executes the block with the configured executor
+fn execute_block(
+    executor: ExecutorKind,
+    txns: Vec<Transaction>,
+    state_view: &DebuggerStateView,
+) -> Result<Vec<TransactionOutput>> {
+    match executor {
+        ExecutorKind::Sequential | ExecutorKind::BlockStm => {
+            AptosVM::execute_block(txns, state_view, None)
+                .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
+        },
+        ExecutorKind::Native => NativeExecutor::execute_transactions(&txns, state_view),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    aptos_logger::Logger::new().init();
+    let args = Argument::parse();
+    ensure!(args.block_size > 0, "The block size must be positive!");
+
+    // The concurrency level can only be set once, so it determines the executor
+    let concurrency_level = match args.executor {
+        ExecutorKind::Sequential => 1,
+        ExecutorKind::BlockStm => args.concurrency_level,
+        ExecutorKind::Native => {
+            NativeExecutor::set_concurrency_level_once(args.concurrency_level);
+            args.concurrency_level
+        },
+    };
+    AptosVM::set_concurrency_level_once(concurrency_level);
+
+    let debugger = match args.target {
+        Target::Rest { endpoint } => {
+            AptosDebugger::rest_client(Client::new(Url::parse(&endpoint)?))?
+        },
+        Target::DB { path } => AptosDebugger::db(path)?,
+    };
+
+    let mut stats = ReplayStats::default();
+    let mut begin = args.begin_version;
+    let end = args.begin_version + args.limit;
+
+    let backup_storage: Option<Arc<dyn BackupStorage>> =
+        match (args.backup_local_fs_dir, args.backup_command_adapter_config) {
+            (Some(opt), _) => Some(Arc::new(LocalFs::new_with_opt(opt))),
+            (None, Some(opt)) => Some(Arc::new(CommandAdapter::new_with_opt(opt).await?)),
+            (None, None) => None,
+        };
+    let mut backup_reader = match backup_storage {
+        Some(storage) => Some(
+            BackupReader::new(
+                storage,
+                &args.metadata_cache_opt,
+                args.concurrent_downloads.get(),
+                begin,
+                end,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+    let (mut txns, mut txn_infos): (Vec<Transaction>, Vec<TransactionInfo>) = (vec![], vec![]);
+    while begin < end {
+        // Stream the next block of transactions from the backup (or the query endpoint)
+        if txns.is_empty() {
+            let limit = std::cmp::min(args.block_size, end - begin);
+            (txns, txn_infos) = match backup_reader.as_mut() {
+                Some(reader) => reader.get_committed_transactions(begin, limit).await?,
+                None => debugger.get_committed_transactions(begin, limit).await?,
+            };
+            ensure!(
+                !txns.is_empty(),
+                "No transactions found at version {}",
+                begin
+            );
+        }
+
+        // The warm up shares the state view (and its cache) with the timed execution
+        let state_view = debugger.state_view_at_version(begin);
+        if args.warm_up {
+            execute_block(args.executor, txns.clone(), &state_view)?;
+        }
+        let timer = Instant::now();
+        let txn_outputs = execute_block(args.executor, txns.clone(), &state_view)?;
+        let execution_time = timer.elapsed();
+
+        // The block is cut at the first reconfiguration, so the
+        // remaining transactions are executed in the next block.
+        let num_executed = txn_outputs
+            .iter()
+            .position(is_reconfiguration)
+            .map_or(txn_outputs.len(), |idx| idx + 1);
+        // The native outputs only approximate the ones of the VM
+        let expected_txn_infos = match args.executor {
+            ExecutorKind::Native => None,
+            ExecutorKind::Sequential | ExecutorKind::BlockStm => Some(&txn_infos[..num_executed]),
+        };
+        stats.record_block(
+            begin,
+            &txn_outputs[..num_executed],
+            expected_txn_infos,
+            execution_time,
+        );
+        txns.drain(..num_executed);
+        txn_infos.drain(..num_executed);
+        begin += num_executed as Version;
+    }
+
+    stats.print_report(args.executor, AptosVM::get_concurrency_level());
+    Ok(())
+}
+
+#[test]
+fn verify_tool() {
+    use clap::CommandFactory;
+    Argument::command().debug_assert()
+}
+
+#[test]
+fn test_record_block() {
+    use aptos_crypto::{
+        hash::{CryptoHash, ACCUMULATOR_PLACEHOLDER_HASH},
+        HashValue,
+    };
+    use aptos_types::write_set::WriteSet;
+    use move_core_types::vm_status::StatusCode;
+
+    let output =
+        |gas_used, status| TransactionOutput::new(WriteSet::default(), vec![], gas_used, status);
+    let txn_outputs = vec![
+        output(1, TransactionStatus::Keep(ExecutionStatus::Success)),
+        output(2, TransactionStatus::Keep(ExecutionStatus::OutOfGas)),
+        output(
+            0,
+            TransactionStatus::Discard(StatusCode::SEQUENCE_NUMBER_TOO_OLD),
+        ),
+    ];
+    let txn_info = |gas_used, status| {
+        TransactionInfo::new(
+            HashValue::zero(),
+            CryptoHash::hash(&WriteSet::default()),
+            *ACCUMULATOR_PLACEHOLDER_HASH,
+            None,
+            gas_used,
+            status,
+        )
+    };
+    // The second transaction used less gas on-chain
+    let txn_infos = vec![
+        txn_info(1, ExecutionStatus::Success),
+        txn_info(1, ExecutionStatus::OutOfGas),
+    ];
+
+    let mut stats = ReplayStats::default();
+    stats.record_block(10, &txn_outputs, Some(&txn_infos), Duration::from_secs(1));
+    assert_eq!(stats.num_blocks, 1);
+    assert_eq!(stats.num_txns, 3);
+    assert_eq!(
+        (stats.num_success, stats.num_aborted, stats.num_discarded),
+        (1, 1, 1)
+    );
+    assert_eq!(stats.num_mismatches, 1);
+    assert_eq!(stats.first_mismatch, Some(11));
+
+    // Without the expected infos (i.e., with the native executor), nothing is compared
+    stats.record_block(13, &txn_outputs, None, Duration::from_secs(1));
+    assert_eq!(stats.num_blocks, 2);
+    assert_eq!(stats.num_txns, 6);
+    assert_eq!(stats.num_mismatches, 1);
+    assert_eq!(stats.execution_time, Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_backup_reader() {
+    use aptos_backup_cli::{
+        metadata::Metadata,
+        storage::{BackupHandleRef, FileHandle},
+    };
+    use aptos_crypto::{
+        hash::{CryptoHash, TransactionAccumulatorHasher, ACCUMULATOR_PLACEHOLDER_HASH},
+        HashValue,
+    };
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        aggregate_signature::AggregateSignature,
+        block_info::BlockInfo,
+        contract_event::ContractEvent,
+        ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+        proof::{accumulator::InMemoryAccumulator, TransactionAccumulatorRangeProof},
+        write_set::WriteSet,
+    };
+    use tokio::io::AsyncWriteExt;
+
+    async fn write_file(
+        storage: &Arc<dyn BackupStorage>,
+        backup_handle: &BackupHandleRef,
+        name: &str,
+        bytes: &[u8],
+    ) -> FileHandle {
+        let (file_handle, mut file) = storage
+            .create_for_write(backup_handle, &name.try_into().unwrap())
+            .await
+            .unwrap();
+        file.write_all(bytes).await.unwrap();
+        file.shutdown().await.unwrap();
+        file_handle
+    }
+
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let cache_dir = TempPath::new();
+    let storage: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(backup_dir.path().to_path_buf()));
+
+    // A single chunk of 8 transactions (i.e., versions [0, 7]). The chunk
+    // covers the whole accumulator, so its range proof is empty.
+    let txns: Vec<_> = (0..8u8)
+        .map(|idx| Transaction::StateCheckpoint(HashValue::sha3_256_of(&[idx])))
+        .collect();
+    let txn_infos: Vec<_> = txns
+        .iter()
+        .zip(0..)
+        .map(|(txn, gas_used)| {
+            TransactionInfo::new(
+                CryptoHash::hash(txn),
+                HashValue::zero(),
+                *ACCUMULATOR_PLACEHOLDER_HASH,
+                None,
+                gas_used,
+                ExecutionStatus::Success,
+            )
+        })
+        .collect();
+    let txn_info_hashes: Vec<_> = txn_infos.iter().map(CryptoHash::hash).collect();
+    let accumulator =
+        InMemoryAccumulator::<TransactionAccumulatorHasher>::from_leaves(&txn_info_hashes);
+    let block_info = BlockInfo::new(0, 0, HashValue::zero(), accumulator.root_hash(), 7, 0, None);
+    let ledger_info = LedgerInfoWithSignatures::new(
+        LedgerInfo::new(block_info, HashValue::zero()),
+        AggregateSignature::empty(),
+    );
+
+    let backup_handle = storage
+        .create_backup(&"transaction_0-7".try_into().unwrap())
+        .await
+        .unwrap();
+    let mut chunk_bytes = vec![];
+    for (txn, txn_info) in txns.iter().zip(&txn_infos) {
+        let events: Vec<ContractEvent> = vec![];
+        let record_bytes = bcs::to_bytes(&(txn, txn_info, events, WriteSet::default())).unwrap();
+        chunk_bytes.extend((record_bytes.len() as u32).to_be_bytes());
+        chunk_bytes.extend(record_bytes);
+    }
+    let transactions = write_file(&storage, &backup_handle, "0-.chunk", &chunk_bytes).await;
+    let proof_bytes =
+        bcs::to_bytes(&(TransactionAccumulatorRangeProof::new_empty(), ledger_info)).unwrap();
+    let proof = write_file(&storage, &backup_handle, "0-.proof", &proof_bytes).await;
+    let manifest = TransactionBackup {
+        first_version: 0,
+        last_version: 7,
+        chunks: vec![TransactionChunk {
+            first_version: 0,
+            last_version: 7,
+            transactions,
+            write_op_dictionary: None,
+            proof,
+        }],
+    };
+    let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+    let manifest = write_file(
+        &storage,
+        &backup_handle,
+        "transaction.manifest",
+        &manifest_bytes,
+    )
+    .await;
+    let metadata = Metadata::new_transaction_backup(0, 7, manifest);
+    storage
+        .save_metadata_line(&metadata.name(), &metadata.to_text_line().unwrap())
+        .await
+        .unwrap();
+
+    // Replay [3, 8) in blocks of (at most) 2 transactions
+    let metadata_cache_opt = MetadataCacheOpt::new(Some(cache_dir.path()));
+    let mut reader = BackupReader::new(storage, &metadata_cache_opt, 1, 3, 8)
+        .await
+        .unwrap();
+    for begin in [3, 5, 7] {
+        let (block_txns, block_txn_infos) =
+            reader.get_committed_transactions(begin, 2).await.unwrap();
+        let end = std::cmp::min(begin + 2, 8) as usize;
+        assert_eq!(block_txns, txns[begin as usize..end]);
+        assert_eq!(block_txn_infos, txn_infos[begin as usize..end]);
+    }
+    let (block_txns, _) = reader.get_committed_transactions(8, 2).await.unwrap();
+    assert!(block_txns.is_empty());
+
+    // The backup can only be read in order
+    assert!(reader.get_committed_transactions(3, 2).await.is_err());
+}
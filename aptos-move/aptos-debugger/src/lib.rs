@@ -1,18 +1,20 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{format_err, Result};
+use anyhow::{bail, format_err, Result};
 use aptos_gas_meter::{StandardGasAlgebra, StandardGasMeter};
-use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
+use aptos_gas_profiling::{ExecutionTrace, GasProfiler, TransactionGasLog};
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION};
 use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
 use aptos_resource_viewer::{AnnotatedAccountStateBlob, AptosValueAnnotator};
 use aptos_rest_client::Client;
 use aptos_state_view::TStateView;
 use aptos_types::{
+    access_path::Path,
     account_address::AccountAddress,
     chain_id::ChainId,
     on_chain_config::{Features, OnChainConfig, TimedFeatures},
+    state_store::state_key::StateKeyInner,
     transaction::{
         SignedTransaction, Transaction, TransactionInfo, TransactionOutput, TransactionPayload,
         Version,
@@ -30,8 +32,39 @@ use aptos_vm::{
 use aptos_vm_logging::log_schema::AdapterLogSchema;
 use aptos_vm_types::{change_set::VMChangeSet, output::VMOutput, storage::ChangeSetConfigs};
 use move_binary_format::errors::VMResult;
+use move_core_types::language_storage::StructTag;
+use serde::Serialize;
 use std::{path::Path, sync::Arc};
 
+/// An event emitted by a traced transaction
+#[derive(Clone, Debug, Serialize)]
+pub struct TracedEvent {
+    pub key: String,
+    pub sequence_number: u64,
+    pub event_type: String,
+    pub data: String, // The hex encoded (BCS) event data
+}
+
+/// A (serializable) execution trace of a historical transaction, including
+/// the Move call trace, the gas used per instruction class and the events.
+#[derive(Clone, Debug, Serialize)]
+pub struct TransactionTrace {
+    pub version: Version,
+    pub status: String,
+    pub gas_used: u64,
+    pub events: Vec<TracedEvent>,
+    pub execution_trace: ExecutionTrace,
+}
+
+/// A resource changed by a session, with its (annotated) values before and after the change
+#[derive(Clone, Debug, Serialize)]
+pub struct ResourceChange {
+    pub address: AccountAddress,
+    pub resource_type: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
 pub struct AptosDebugger {
     debugger: Arc<dyn AptosValidatorInterface + Send>,
 }
@@ -51,6 +84,11 @@ impl AptosDebugger {
         )?)))
     }
 
+    /// Returns a view of the state (lazily fetched and cached) at the given version
+    pub fn state_view_at_version(&self, version: Version) -> DebuggerStateView {
+        DebuggerStateView::new(self.debugger.clone(), version)
+    }
+
     pub fn execute_transactions_at_version(
         &self,
         version: Version,
@@ -103,6 +141,45 @@ impl AptosDebugger {
         Ok((status, output, gas_profiler.finish()))
     }
 
+    pub async fn trace_past_transaction(&self, version: Version) -> Result<TransactionTrace> {
+        let (mut txns, _) = self.debugger.get_committed_transactions(version, 1).await?;
+        let txn = match txns.pop() {
+            Some(Transaction::UserTransaction(txn)) => txn,
+            Some(_) => bail!("Only user transactions can be traced! Version: {}", version),
+            None => bail!("No transaction found at version {}", version),
+        };
+
+        let (status, output, gas_log) =
+            self.execute_transaction_at_version_with_gas_profiler(version, txn)?;
+        let events = output
+            .change_set()
+            .events()
+            .iter()
+            .map(|event| TracedEvent {
+                key: event.key().to_string(),
+                sequence_number: event.sequence_number(),
+                event_type: event.type_tag().to_string(),
+                data: hex::encode(event.event_data()),
+            })
+            .collect();
+
+        Ok(TransactionTrace {
+            version,
+            status: format!("{:?}", status),
+            gas_used: output.gas_used(),
+            events,
+            execution_trace: gas_log.exec_io.to_execution_trace(),
+        })
+    }
+
+    pub async fn get_committed_transactions(
+        &self,
+        begin: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        self.debugger.get_committed_transactions(begin, limit).await
+    }
+
     pub async fn execute_past_transactions(
         &self,
         mut begin: Version,
@@ -220,6 +297,20 @@ impl AptosDebugger {
     }
 
     pub fn run_session_at_version<F>(&self, version: Version, f: F) -> Result<VMChangeSet>
+    where
+        F: FnOnce(&mut SessionExt) -> VMResult<()>,
+    {
+        self.run_session_with_id_at_version(version, SessionId::Void, f)
+    }
+
+    /// Runs a session with the given id (which, e.g., sets the script hash seen by the
+    /// transaction context) at the given version
+    pub fn run_session_with_id_at_version<F>(
+        &self,
+        version: Version,
+        session_id: SessionId,
+        f: F,
+    ) -> Result<VMChangeSet>
     where
         F: FnOnce(&mut SessionExt) -> VMResult<()>,
     {
@@ -235,7 +326,7 @@ impl AptosDebugger {
             TimedFeatures::enable_all(),
         )
         .unwrap();
-        let mut session = move_vm.new_session(&state_view_storage, SessionId::Void);
+        let mut session = move_vm.new_session(&state_view_storage, session_id);
         f(&mut session).map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
         let change_set = session
             .finish(
@@ -245,9 +336,50 @@ impl AptosDebugger {
             .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
         Ok(change_set)
     }
+
+    /// Returns the resources changed by the change set of a session run at the given version.
+    /// Values that can't be annotated (e.g., as their module was upgraded) are hex encoded.
+    pub fn annotate_resource_changes_at_version(
+        &self,
+        version: Version,
+        change_set: &VMChangeSet,
+    ) -> Result<Vec<ResourceChange>> {
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let remote_storage = StorageAdapter::new(&state_view);
+        let annotator = AptosValueAnnotator::new(&remote_storage);
+        let annotate = |tag: &StructTag, bytes: &[u8]| {
+            annotator
+                .view_resource(tag, bytes)
+                .map_or_else(|_| hex::encode(bytes), |value| value.to_string())
+        };
+
+        let mut changes = vec![];
+        for (state_key, write_op) in change_set.resource_write_set() {
+            // Resource groups are skipped
+            let (address, tag) = match state_key.inner() {
+                StateKeyInner::AccessPath(access_path) => match access_path.get_path() {
+                    Path::Resource(tag) => (access_path.address, tag),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let old_value = state_view.get_state_value_bytes(state_key)?;
+            let new_value = write_op.bytes();
+            if old_value.as_deref() == new_value {
+                continue;
+            }
+            changes.push(ResourceChange {
+                address,
+                resource_type: tag.to_string(),
+                old_value: old_value.map(|bytes| annotate(&tag, &bytes)),
+                new_value: new_value.map(|bytes| annotate(&tag, bytes)),
+            });
+        }
+        Ok(changes)
+    }
 }
 
-fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
+pub fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
     let new_epoch_event_key = aptos_types::on_chain_config::new_epoch_event_key();
     vm_output
         .events()
@@ -29,6 +29,11 @@ pub struct Argument {
 
     #[clap(long, default_value_t = 1)]
     concurrency_level: usize,
+
+    /// If set, the transactions are not replayed. Instead, a JSON execution
+    /// trace of each user transaction is written to the given directory.
+    #[clap(long)]
+    trace_output_dir: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -44,6 +49,21 @@ async fn main() -> Result<()> {
         Target::DB { path } => AptosDebugger::db(path)?,
     };
 
+    if let Some(trace_output_dir) = args.trace_output_dir {
+        std::fs::create_dir_all(&trace_output_dir)?;
+        for version in args.begin_version..args.begin_version + args.limit {
+            match debugger.trace_past_transaction(version).await {
+                Ok(trace) => {
+                    let trace_path = trace_output_dir.join(format!("{}.json", version));
+                    std::fs::write(&trace_path, serde_json::to_string_pretty(&trace)?)?;
+                    println!("Wrote the execution trace to {}", trace_path.display());
+                },
+                Err(error) => println!("Skipping version {}: {}", version, error),
+            }
+        }
+        return Ok(());
+    }
+
     println!(
         "{:#?}",
         debugger
@@ -40,6 +40,11 @@ pub type NumSlots = GasQuantity<Slot>;
 
 pub type FeePerSlot = GasQuantity<UnitDiv<Octa, Slot>>;
 
+/// Unit of emitted events
+pub enum EventUnit {}
+
+pub type NumEvents = GasQuantity<EventUnit>;
+
 pub type FeePerByte = GasQuantity<UnitDiv<Octa, Byte>>;
 
 /***************************************************************************************************
@@ -0,0 +1,323 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    log::{CallFrame, ExecutionAndIOCosts, ExecutionGasEvent},
+    render::Render,
+};
+use move_binary_format::file_format_common::Opcodes;
+use move_core_types::gas_algebra::InternalGas;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A single entry in the (ordered) execution trace of a transaction.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TraceEntry {
+    /// A Move function (or the script) was entered
+    FunctionEntry { function: String, depth: usize },
+    /// A Move function (or the script) returned. The gas includes all nested calls.
+    FunctionExit {
+        function: String,
+        depth: usize,
+        gas_used: u64,
+    },
+    /// A native function was called
+    NativeCall {
+        function: String,
+        depth: usize,
+        gas_used: u64,
+    },
+    /// A resource was loaded from storage
+    ResourceLoad {
+        address: String,
+        resource_type: String,
+        depth: usize,
+        gas_used: u64,
+    },
+}
+
+/// The number of executed instructions of a class and their total gas cost.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct InstructionClassGas {
+    pub count: u64,
+    pub gas_used: u64,
+}
+
+/// The coverage of a single function, i.e., the number of times it was
+/// called and the code offsets that were jumped to by branch instructions.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct FunctionCoverage {
+    pub num_calls: u64,
+    pub branch_offsets: BTreeSet<u16>,
+}
+
+/// A serializable execution trace of a transaction (e.g., for tooling), built
+/// from the gas log. All gas values are in internal gas units.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ExecutionTrace {
+    pub gas_scaling_factor: u64,
+    pub total_execution_and_io_gas: u64,
+    pub entries: Vec<TraceEntry>,
+    pub instruction_gas: BTreeMap<String, InstructionClassGas>,
+    pub coverage: BTreeMap<String, FunctionCoverage>,
+}
+
+impl ExecutionAndIOCosts {
+    /// Converts the execution log into a (serializable) execution trace.
+    pub fn to_execution_trace(&self) -> ExecutionTrace {
+        let mut trace = ExecutionTrace {
+            gas_scaling_factor: self.gas_scaling_factor.into(),
+            total_execution_and_io_gas: self.total.into(),
+            entries: vec![],
+            instruction_gas: BTreeMap::new(),
+            coverage: BTreeMap::new(),
+        };
+        trace_call_frame(&mut trace, &self.call_graph, 0);
+        trace
+    }
+}
+
+/// Appends the trace of the given call frame (and all nested
+/// calls) to the execution trace. Returns the gas used by the frame.
+fn trace_call_frame(trace: &mut ExecutionTrace, frame: &CallFrame, depth: usize) -> InternalGas {
+    use ExecutionGasEvent::*;
+
+    let function = format!("{}", frame.name);
+    trace.entries.push(TraceEntry::FunctionEntry {
+        function: function.clone(),
+        depth,
+    });
+    trace
+        .coverage
+        .entry(function.clone())
+        .or_default()
+        .num_calls += 1;
+
+    let mut frame_cost = InternalGas::zero();
+    for event in &frame.events {
+        match event {
+            Loc(offset) => {
+                trace
+                    .coverage
+                    .entry(function.clone())
+                    .or_default()
+                    .branch_offsets
+                    .insert(*offset);
+            },
+            Bytecode { op, cost } => {
+                let instruction_gas = trace
+                    .instruction_gas
+                    .entry(get_instruction_class(op).to_string())
+                    .or_default();
+                instruction_gas.count += 1;
+                instruction_gas.gas_used += u64::from(*cost);
+                frame_cost += *cost;
+            },
+            Call(inner_frame) => {
+                frame_cost += trace_call_frame(trace, inner_frame, depth + 1);
+            },
+            CallNative {
+                module_id,
+                fn_name,
+                ty_args,
+                cost,
+            } => {
+                trace.entries.push(TraceEntry::NativeCall {
+                    function: format!(
+                        "{}",
+                        Render(&(module_id, fn_name.as_ident_str(), ty_args.as_slice()))
+                    ),
+                    depth: depth + 1,
+                    gas_used: (*cost).into(),
+                });
+                frame_cost += *cost;
+            },
+            LoadResource { addr, ty, cost } => {
+                trace.entries.push(TraceEntry::ResourceLoad {
+                    address: addr.to_hex_literal(),
+                    resource_type: format!("{}", ty),
+                    depth,
+                    gas_used: (*cost).into(),
+                });
+                frame_cost += *cost;
+            },
+        }
+    }
+
+    trace.entries.push(TraceEntry::FunctionExit {
+        function,
+        depth,
+        gas_used: frame_cost.into(),
+    });
+    frame_cost
+}
+
+/// Returns the class of the given instruction (used to aggregate instruction gas)
+fn get_instruction_class(op: &Opcodes) -> &'static str {
+    use Opcodes::*;
+
+    match op {
+        NOP | POP | RET | BR_TRUE | BR_FALSE | BRANCH | ABORT => "control_flow",
+        LD_U8 | LD_U16 | LD_U32 | LD_U64 | LD_U128 | LD_U256 | LD_CONST | LD_TRUE | LD_FALSE => {
+            "constant"
+        },
+        COPY_LOC | MOVE_LOC | ST_LOC => "local",
+        MUT_BORROW_LOC
+        | IMM_BORROW_LOC
+        | MUT_BORROW_FIELD
+        | IMM_BORROW_FIELD
+        | MUT_BORROW_FIELD_GENERIC
+        | IMM_BORROW_FIELD_GENERIC
+        | READ_REF
+        | WRITE_REF
+        | FREEZE_REF => "reference",
+        CALL | CALL_GENERIC => "call",
+        PACK | UNPACK | PACK_GENERIC | UNPACK_GENERIC => "struct",
+        ADD | SUB | MUL | MOD | DIV | BIT_OR | BIT_AND | XOR | SHL | SHR | CAST_U8 | CAST_U16
+        | CAST_U32 | CAST_U64 | CAST_U128 | CAST_U256 => "arithmetic",
+        OR | AND | NOT | EQ | NEQ | LT | GT | LE | GE => "comparison",
+        EXISTS
+        | EXISTS_GENERIC
+        | MUT_BORROW_GLOBAL
+        | MUT_BORROW_GLOBAL_GENERIC
+        | IMM_BORROW_GLOBAL
+        | IMM_BORROW_GLOBAL_GENERIC
+        | MOVE_FROM
+        | MOVE_FROM_GENERIC
+        | MOVE_TO
+        | MOVE_TO_GENERIC => "global_storage",
+        VEC_PACK | VEC_LEN | VEC_IMM_BORROW | VEC_MUT_BORROW | VEC_PUSH_BACK | VEC_POP_BACK
+        | VEC_UNPACK | VEC_SWAP => "vector",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_gas_algebra::GasScalingFactor;
+    use move_core_types::{
+        account_address::AccountAddress,
+        identifier::Identifier,
+        language_storage::{ModuleId, StructTag, TypeTag},
+    };
+
+    fn module_id(name: &str) -> ModuleId {
+        ModuleId::new(AccountAddress::ONE, Identifier::new(name).unwrap())
+    }
+
+    fn bytecode(op: Opcodes, cost: u64) -> ExecutionGasEvent {
+        ExecutionGasEvent::Bytecode {
+            op,
+            cost: InternalGas::new(cost),
+        }
+    }
+
+    #[test]
+    fn test_execution_trace() {
+        // Create a script that calls a function, which calls a native and loads a resource
+        let resource_type = TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::ONE,
+            module: Identifier::new("coin").unwrap(),
+            name: Identifier::new("CoinStore").unwrap(),
+            type_params: vec![],
+        }));
+        let mut function_frame = CallFrame::new_function(
+            module_id("coin"),
+            Identifier::new("transfer").unwrap(),
+            vec![],
+        );
+        function_frame.events = vec![
+            ExecutionGasEvent::Loc(3),
+            bytecode(Opcodes::ADD, 3),
+            ExecutionGasEvent::CallNative {
+                module_id: module_id("vector"),
+                fn_name: Identifier::new("length").unwrap(),
+                ty_args: vec![TypeTag::U64],
+                cost: InternalGas::new(5),
+            },
+            ExecutionGasEvent::LoadResource {
+                addr: AccountAddress::ONE,
+                ty: resource_type.clone(),
+                cost: InternalGas::new(7),
+            },
+            bytecode(Opcodes::BR_TRUE, 1),
+            ExecutionGasEvent::Loc(7),
+        ];
+        let mut script_frame = CallFrame::new_script();
+        script_frame.events = vec![
+            bytecode(Opcodes::LD_U64, 2),
+            ExecutionGasEvent::Call(function_frame),
+            bytecode(Opcodes::RET, 1),
+        ];
+        let exec_io = ExecutionAndIOCosts {
+            gas_scaling_factor: GasScalingFactor::new(100),
+            total: InternalGas::new(19),
+            intrinsic_cost: InternalGas::zero(),
+            call_graph: script_frame,
+            write_set_transient: vec![],
+        };
+
+        // Verify the trace entries are ordered and the gas includes nested calls
+        let trace = exec_io.to_execution_trace();
+        assert_eq!(trace.gas_scaling_factor, 100);
+        assert_eq!(trace.total_execution_and_io_gas, 19);
+        assert_eq!(trace.entries, vec![
+            TraceEntry::FunctionEntry {
+                function: "<script>".into(),
+                depth: 0,
+            },
+            TraceEntry::FunctionEntry {
+                function: "0x1::coin::transfer".into(),
+                depth: 1,
+            },
+            TraceEntry::NativeCall {
+                function: "0x1::vector::length<u64>".into(),
+                depth: 2,
+                gas_used: 5,
+            },
+            TraceEntry::ResourceLoad {
+                address: "0x1".into(),
+                resource_type: resource_type.to_string(),
+                depth: 1,
+                gas_used: 7,
+            },
+            TraceEntry::FunctionExit {
+                function: "0x1::coin::transfer".into(),
+                depth: 1,
+                gas_used: 16,
+            },
+            TraceEntry::FunctionExit {
+                function: "<script>".into(),
+                depth: 0,
+                gas_used: 19,
+            },
+        ]);
+
+        // Verify the instruction gas is aggregated by instruction class
+        let instruction_gas = |count, gas_used| InstructionClassGas { count, gas_used };
+        assert_eq!(
+            trace.instruction_gas,
+            BTreeMap::from([
+                ("arithmetic".to_string(), instruction_gas(1, 3)),
+                ("constant".to_string(), instruction_gas(1, 2)),
+                ("control_flow".to_string(), instruction_gas(2, 2)),
+            ])
+        );
+
+        // Verify the coverage of each function
+        assert_eq!(
+            trace.coverage,
+            BTreeMap::from([
+                ("<script>".to_string(), FunctionCoverage {
+                    num_calls: 1,
+                    branch_offsets: BTreeSet::new(),
+                }),
+                ("0x1::coin::transfer".to_string(), FunctionCoverage {
+                    num_calls: 1,
+                    branch_offsets: BTreeSet::from([3, 7]),
+                }),
+            ])
+        );
+    }
+}
@@ -195,6 +195,9 @@ crate::gas_schedule::macros::define_gas_parameters!(
 
         [state_storage_get_usage_base_cost: InternalGas, "state_storage.get_usage.base", 10000],
 
+        // Based on SHA3-256's cost
+        [randomness_fetch_random_bytes_base: InternalGas, { 13.. => "randomness.fetch_random_bytes.base" }, 80000],
+
         [aggregator_add_base: InternalGas, "aggregator.add.base", 6000],
         [aggregator_read_base: InternalGas, "aggregator.read.base", 6000],
         [aggregator_sub_base: InternalGas, "aggregator.sub.base", 6000],
@@ -4,7 +4,7 @@
 //! This module defines the gas parameters for the table extension.
 
 use crate::gas_schedule::NativeGasParameters;
-use aptos_gas_algebra::{InternalGas, InternalGasPerByte};
+use aptos_gas_algebra::{InternalGas, InternalGasPerArg, InternalGasPerByte, NumBytes};
 
 crate::gas_schedule::macros::define_gas_parameters!(
     TableGasParameters,
@@ -16,6 +16,8 @@ crate::gas_schedule::macros::define_gas_parameters!(
         [common_load_base_new: InternalGas, { 7.. => "common.load.base_new" }, 8000],
         [common_load_per_byte: InternalGasPerByte, "common.load.per_byte", 1000],
         [common_load_failure: InternalGas, "common.load.failure", 0],
+        [common_load_per_extra_page: InternalGasPerArg, { 13.. => "common.load.per_extra_page" }, 0],
+        [common_load_page_size: NumBytes, { 13.. => "common.load.page_size" }, 4096],
 
         [new_table_handle_base: InternalGas, "new_table_handle.base", 20000],
 
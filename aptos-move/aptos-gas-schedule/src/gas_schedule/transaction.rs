@@ -7,7 +7,7 @@
 use crate::gas_schedule::VMGasParameters;
 use aptos_gas_algebra::{
     AbstractValueSize, Fee, FeePerByte, FeePerGasUnit, FeePerSlot, Gas, GasExpression,
-    GasScalingFactor, GasUnit, NumSlots,
+    GasScalingFactor, GasUnit, NumEvents, NumSlots,
 };
 use aptos_types::{
     contract_event::ContractEvent, state_store::state_key::StateKey, write_set::WriteOp,
@@ -87,6 +87,23 @@ crate::gas_schedule::macros::define_gas_parameters!(
             300,
         ],
         [load_data_failure: InternalGas, "load_data.failure", 0],
+        // Calibrated read IO model: reads are additionally charged per level of the (estimated)
+        // depth of the state Merkle tree, and per extra page spanned by the item read.
+        [
+            storage_io_per_state_slot_read_per_depth: InternalGasPerArg,
+            { 13.. => "storage_io_per_state_slot_read_per_depth" },
+            15_000,
+        ],
+        [
+            storage_io_per_state_page_read: InternalGasPerArg,
+            { 13.. => "storage_io_per_state_page_read" },
+            300_000,
+        ],
+        [
+            storage_io_state_page_size: NumBytes,
+            { 13.. => "storage_io_state_page_size" },
+            4096,
+        ],
         // Gas parameters for writing data to storage.
         [
             storage_io_per_state_slot_write: InternalGasPerArg,
@@ -139,6 +156,11 @@ crate::gas_schedule::macros::define_gas_parameters!(
             { 5.. => "max_bytes_all_events_per_transaction"},
             10 << 20, // all events from a single transaction are 10MB max
         ],
+        [
+            max_num_events_per_transaction: NumEvents,
+            { 12.. => "max_num_events_per_transaction" },
+            10_000, // a single transaction emits 10K events max
+        ],
         [
             storage_fee_per_state_slot_create: FeePerSlot,
             { 7.. => "storage_fee_per_state_slot_create" },
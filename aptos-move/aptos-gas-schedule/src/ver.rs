@@ -8,6 +8,14 @@
 ///   - Changing how gas is calculated in any way
 ///
 /// Change log:
+/// - V13
+///   - Storage read IO gas depends on the estimated depth of the state Merkle tree and the
+///     number of pages spanned by the item read
+///   - Added the randomness native function fetch_random_bytes
+/// - V12
+///   - Limit on the number of events per transaction
+///   - abort with EVENT_COUNT_LIMIT_REACHED or EVENT_BYTES_LIMIT_REACHED if Events are too many
+///     or too large
 /// - V11
 //    - Ristretto255 natives (point cloning & double-scalar multiplication) and Bulletproofs natives
 /// - V10
@@ -41,4 +49,4 @@
 ///       global operations.
 /// - V1
 ///   - TBA
-pub const LATEST_GAS_FEATURE_VERSION: u64 = 11;
+pub const LATEST_GAS_FEATURE_VERSION: u64 = 13;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::utils::*;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use aptos_types::on_chain_config::{FeatureFlag as AptosFeatureFlag, Features as AptosFeatures};
 use move_model::{code_writer::CodeWriter, emit, emitln, model::Loc};
 use serde::{Deserialize, Serialize};
@@ -15,6 +15,10 @@ pub struct Features {
     pub enabled: Vec<FeatureFlag>,
     #[serde(default)]
     pub disabled: Vec<FeatureFlag>,
+    /// If set, the flags are changed at the given time within the epoch (instead of on the
+    /// reconfiguration triggered by the proposal). Only hot activatable flags can be scheduled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activation_time_microseconds: Option<u64>,
 }
 
 #[derive(Clone, Debug, Deserialize, EnumIter, PartialEq, Eq, Serialize, Hash)]
@@ -45,6 +49,7 @@ pub enum FeatureFlag {
     GasPayerEnabled,
     AptosUniqueIdentifiers,
     BulletproofsNatives,
+    Randomness,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -85,12 +90,32 @@ pub fn generate_feature_upgrade_proposal(
 
     assert!(enabled.len() < u16::MAX as usize);
     assert!(disabled.len() < u16::MAX as usize);
+    if features.activation_time_microseconds.is_some() {
+        if let Some(feature) = features
+            .enabled
+            .iter()
+            .chain(features.disabled.iter())
+            .find(|f| !AptosFeatureFlag::from((*f).clone()).is_hot_activatable())
+        {
+            bail!(
+                "Feature {:?} can't be activated within an epoch, so can't be scheduled",
+                feature
+            );
+        }
+    }
 
     let writer = CodeWriter::new(Loc::default());
 
     emitln!(writer, "// Modifying on-chain feature flags: ");
     emitln!(writer, "// Enabled Features: {:?}", features.enabled);
     emitln!(writer, "// Disabled Features: {:?}", features.disabled);
+    if let Some(activation_time) = features.activation_time_microseconds {
+        emitln!(
+            writer,
+            "// Activation Time (microseconds): {:?}",
+            activation_time
+        );
+    }
     emitln!(writer, "//");
 
     let proposal = generate_governance_proposal(
@@ -107,7 +132,21 @@ pub fn generate_feature_upgrade_proposal(
             generate_features_blob(writer, &disabled);
             emitln!(writer, ";\n");
 
-            if is_testnet && next_execution_hash.is_empty() {
+            let framework_signer = if is_testnet && next_execution_hash.is_empty() {
+                "framework_signer"
+            } else {
+                "&framework_signer"
+            };
+            if let Some(activation_time) = features.activation_time_microseconds {
+                // The flags are activated by the framework at the start of the first block at or
+                // after the activation time, so no reconfiguration is needed.
+                emitln!(
+                    writer,
+                    "features::schedule_feature_activations({}, enabled_blob, disabled_blob, {});",
+                    framework_signer,
+                    activation_time
+                );
+            } else if is_testnet && next_execution_hash.is_empty() {
                 emitln!(
                     writer,
                     "features::change_feature_flags(framework_signer, enabled_blob, disabled_blob);"
@@ -168,6 +207,7 @@ impl From<FeatureFlag> for AptosFeatureFlag {
             FeatureFlag::GasPayerEnabled => AptosFeatureFlag::GAS_PAYER_ENABLED,
             FeatureFlag::AptosUniqueIdentifiers => AptosFeatureFlag::APTOS_UNIQUE_IDENTIFIERS,
             FeatureFlag::BulletproofsNatives => AptosFeatureFlag::BULLETPROOFS_NATIVES,
+            FeatureFlag::Randomness => AptosFeatureFlag::RANDOMNESS,
         }
     }
 }
@@ -214,6 +254,7 @@ impl From<AptosFeatureFlag> for FeatureFlag {
             AptosFeatureFlag::GAS_PAYER_ENABLED => FeatureFlag::GasPayerEnabled,
             AptosFeatureFlag::APTOS_UNIQUE_IDENTIFIERS => FeatureFlag::AptosUniqueIdentifiers,
             AptosFeatureFlag::BULLETPROOFS_NATIVES => FeatureFlag::BulletproofsNatives,
+            AptosFeatureFlag::RANDOMNESS => FeatureFlag::Randomness,
         }
     }
 }
@@ -242,6 +283,10 @@ impl From<&AptosFeatures> for Features {
                 disabled.push(feature);
             }
         }
-        Features { enabled, disabled }
+        Features {
+            enabled,
+            disabled,
+            activation_time_microseconds: None,
+        }
     }
 }
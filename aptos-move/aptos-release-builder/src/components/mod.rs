@@ -281,10 +281,26 @@ impl ReleaseEntry {
                         )
                         .await
                 })?;
-                if features.has_modified(on_chain_features.inner()) {
+                let mut on_chain_features = on_chain_features.into_inner();
+                if features.activation_time_microseconds.is_some() {
+                    // Scheduled changes may not have been activated yet, so validate the features
+                    // they will result in.
+                    let scheduled_activations = block_on(async {
+                        client
+                            .get_account_resource_bcs::<aptos_types::on_chain_config::ScheduledFeatureActivations>(
+                                CORE_CODE_ADDRESS,
+                                "0x1::features::ScheduledFeatureActivations",
+                            )
+                            .await
+                    })?;
+                    on_chain_features = scheduled_activations
+                        .inner()
+                        .apply_due(&on_chain_features, u64::MAX);
+                }
+                if features.has_modified(&on_chain_features) {
                     bail!(
                         "Feature mismatch: Got {:?}, expected {:?}",
-                        on_chain_features.inner(),
+                        on_chain_features,
                         features
                     );
                 }
@@ -524,6 +540,7 @@ impl Default for ReleaseConfig {
                                 .map(crate::components::feature_flags::FeatureFlag::from)
                                 .collect(),
                             disabled: vec![],
+                            activation_time_microseconds: None,
                         }),
                         ReleaseEntry::Consensus(OnChainConsensusConfig::default()),
                         ReleaseEntry::Execution(OnChainExecutionConfig::V1(ExecutionConfigV1 {
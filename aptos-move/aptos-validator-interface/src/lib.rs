@@ -6,7 +6,7 @@ mod rest_interface;
 mod storage_interface;
 
 pub use crate::{rest_interface::RestDebuggerInterface, storage_interface::DBDebuggerInterface};
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use aptos_state_view::TStateView;
 use aptos_types::{
     account_address::AccountAddress,
@@ -193,6 +193,6 @@ impl TStateView for DebuggerStateView {
     }
 
     fn get_usage(&self) -> Result<StateStorageUsage> {
-        unimplemented!()
+        bail!("The state storage usage is not available to the debugger")
     }
 }
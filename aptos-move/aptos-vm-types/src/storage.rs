@@ -208,11 +208,68 @@ impl StoragePricingV3 {
     }
 }
 
+// Calibrated read IO model: reads are charged per level of the (estimated) depth of the state
+// Merkle tree and per page spanned by the item read. Writes are charged the same as in V3.
+#[derive(Debug, Clone)]
+pub struct StoragePricingV4 {
+    pub v3: StoragePricingV3,
+    pub estimated_jmt_depth: NumArgs,
+    pub page_size: NumBytes,
+}
+
+impl StoragePricingV4 {
+    pub fn new(
+        feature_version: u64,
+        gas_params: &AptosGasParameters,
+        num_state_items: usize,
+    ) -> Self {
+        Self {
+            v3: StoragePricingV3 {
+                feature_version,
+                free_write_bytes_quota: gas_params.vm.txn.free_write_bytes_quota,
+            },
+            estimated_jmt_depth: estimate_jmt_depth(num_state_items),
+            page_size: gas_params.vm.txn.storage_io_state_page_size,
+        }
+    }
+
+    /// Returns the number of pages spanned by the loaded bytes, beyond the first one.
+    fn num_extra_pages(&self, loaded: NumBytes) -> NumArgs {
+        let page_size = u64::from(self.page_size).max(1);
+        NumArgs::new(u64::from(loaded).saturating_sub(1) / page_size)
+    }
+
+    fn calculate_read_gas(
+        &self,
+        loaded: NumBytes,
+    ) -> impl GasExpression<VMGasParameters, Unit = InternalGasUnit> {
+        STORAGE_IO_PER_STATE_SLOT_READ * NumArgs::from(1)
+            + STORAGE_IO_PER_STATE_SLOT_READ_PER_DEPTH * self.estimated_jmt_depth
+            + STORAGE_IO_PER_STATE_BYTE_READ * loaded
+            + STORAGE_IO_PER_STATE_PAGE_READ * self.num_extra_pages(loaded)
+    }
+}
+
+/// Estimates the depth of the state Merkle tree (a 16-ary tree) holding the given number
+/// of items, i.e., the number of nibbles of the key hashes needed to tell the items apart.
+pub fn estimate_jmt_depth(num_state_items: usize) -> NumArgs {
+    const MAX_DEPTH: u64 = 64; // The key hashes are 32 bytes (64 nibbles) long
+
+    let mut depth = 1;
+    let mut capacity: u128 = 16;
+    while capacity < num_state_items as u128 && depth < MAX_DEPTH {
+        capacity *= 16;
+        depth += 1;
+    }
+    NumArgs::new(depth)
+}
+
 #[derive(Clone, Debug)]
 pub enum StoragePricing {
     V1(StoragePricingV1),
     V2(StoragePricingV2),
     V3(StoragePricingV3),
+    V4(StoragePricingV4),
 }
 
 impl StoragePricing {
@@ -220,6 +277,7 @@ impl StoragePricing {
         feature_version: u64,
         gas_params: &AptosGasParameters,
         config_storage: &impl ConfigStorage,
+        num_state_items: usize,
     ) -> StoragePricing {
         use StoragePricing::*;
 
@@ -233,10 +291,15 @@ impl StoragePricing {
                     gas_params,
                 )),
             },
-            10.. => V3(StoragePricingV3 {
+            10..=12 => V3(StoragePricingV3 {
                 feature_version,
                 free_write_bytes_quota: gas_params.vm.txn.free_write_bytes_quota,
             }),
+            13.. => V4(StoragePricingV4::new(
+                feature_version,
+                gas_params,
+                num_state_items,
+            )),
         }
     }
 
@@ -256,7 +319,8 @@ impl StoragePricing {
                 },
             )),
             V2(v2) => Either::Left(v2.calculate_read_gas(bytes_loaded)),
-            V3(v3) => Either::Right(v3.calculate_read_gas(bytes_loaded)),
+            V3(v3) => Either::Right(Either::Left(v3.calculate_read_gas(bytes_loaded))),
+            V4(v4) => Either::Right(Either::Right(v4.calculate_read_gas(bytes_loaded))),
         }
     }
 
@@ -271,6 +335,7 @@ impl StoragePricing {
             V1(v1) => Either::Left(v1.io_gas_per_write(key, op)),
             V2(v2) => Either::Left(v2.io_gas_per_write(key, op)),
             V3(v3) => Either::Right(v3.io_gas_per_write(key, op)),
+            V4(v4) => Either::Right(v4.v3.io_gas_per_write(key, op)),
         }
     }
 }
@@ -282,11 +347,19 @@ pub struct ChangeSetConfigs {
     max_bytes_all_write_ops_per_transaction: u64,
     max_bytes_per_event: u64,
     max_bytes_all_events_per_transaction: u64,
+    max_num_events_per_transaction: u64,
 }
 
 impl ChangeSetConfigs {
     pub fn unlimited_at_gas_feature_version(gas_feature_version: u64) -> Self {
-        Self::new_impl(gas_feature_version, u64::MAX, u64::MAX, u64::MAX, u64::MAX)
+        Self::new_impl(
+            gas_feature_version,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+        )
     }
 
     pub fn new(feature_version: u64, gas_params: &AptosGasParameters) -> Self {
@@ -299,12 +372,13 @@ impl ChangeSetConfigs {
         }
     }
 
-    fn new_impl(
+    pub(crate) fn new_impl(
         gas_feature_version: u64,
         max_bytes_per_write_op: u64,
         max_bytes_all_write_ops_per_transaction: u64,
         max_bytes_per_event: u64,
         max_bytes_all_events_per_transaction: u64,
+        max_num_events_per_transaction: u64,
     ) -> Self {
         Self {
             gas_feature_version,
@@ -312,6 +386,7 @@ impl ChangeSetConfigs {
             max_bytes_all_write_ops_per_transaction,
             max_bytes_per_event,
             max_bytes_all_events_per_transaction,
+            max_num_events_per_transaction,
         }
     }
 
@@ -327,10 +402,15 @@ impl ChangeSetConfigs {
     fn for_feature_version_3() -> Self {
         const MB: u64 = 1 << 20;
 
-        Self::new_impl(3, MB, u64::MAX, MB, 10 * MB)
+        Self::new_impl(3, MB, u64::MAX, MB, 10 * MB, u64::MAX)
     }
 
     fn from_gas_params(gas_feature_version: u64, gas_params: &AptosGasParameters) -> Self {
+        let max_num_events_per_transaction = if gas_feature_version >= 12 {
+            gas_params.vm.txn.max_num_events_per_transaction.into()
+        } else {
+            u64::MAX
+        };
         Self::new_impl(
             gas_feature_version,
             gas_params.vm.txn.max_bytes_per_write_op.into(),
@@ -345,8 +425,19 @@ impl ChangeSetConfigs {
                 .txn
                 .max_bytes_all_events_per_transaction
                 .into(),
+            max_num_events_per_transaction,
         )
     }
+
+    /// Before gas feature version 12, events that are too large abort with the generic
+    /// STORAGE_WRITE_LIMIT_REACHED, like write ops that are too large.
+    fn event_bytes_limit_status_code(&self) -> StatusCode {
+        if self.gas_feature_version >= 12 {
+            StatusCode::EVENT_BYTES_LIMIT_REACHED
+        } else {
+            StatusCode::STORAGE_WRITE_LIMIT_REACHED
+        }
+    }
 }
 
 impl CheckChangeSet for ChangeSetConfigs {
@@ -367,15 +458,20 @@ impl CheckChangeSet for ChangeSetConfigs {
             }
         }
 
+        if change_set.events().len() as u64 > self.max_num_events_per_transaction {
+            return Err(VMStatus::error(StatusCode::EVENT_COUNT_LIMIT_REACHED, None));
+        }
+
+        let event_err = self.event_bytes_limit_status_code();
         let mut total_event_size = 0;
         for event in change_set.events() {
             let size = event.event_data().len() as u64;
             if size > self.max_bytes_per_event {
-                return Err(VMStatus::error(ERR, None));
+                return Err(VMStatus::error(event_err, None));
             }
             total_event_size += size;
             if total_event_size > self.max_bytes_all_events_per_transaction {
-                return Err(VMStatus::error(ERR, None));
+                return Err(VMStatus::error(event_err, None));
             }
         }
 
@@ -394,8 +490,10 @@ impl StorageGasParameters {
         feature_version: u64,
         gas_params: &AptosGasParameters,
         config_storage: &impl ConfigStorage,
+        num_state_items: usize,
     ) -> Self {
-        let pricing = StoragePricing::new(feature_version, gas_params, config_storage);
+        let pricing =
+            StoragePricing::new(feature_version, gas_params, config_storage, num_state_items);
         let change_set_configs = ChangeSetConfigs::new(feature_version, gas_params);
 
         Self {
@@ -4,6 +4,10 @@
 #[cfg(test)]
 mod test_change_set;
 #[cfg(test)]
+mod test_change_set_configs;
+#[cfg(test)]
 mod test_output;
 #[cfg(test)]
+mod test_storage_pricing;
+#[cfg(test)]
 mod utils;
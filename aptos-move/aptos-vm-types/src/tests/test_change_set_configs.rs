@@ -0,0 +1,76 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{change_set::VMChangeSet, storage::ChangeSetConfigs};
+use aptos_types::{contract_event::ContractEvent, event::EventKey};
+use claims::{assert_err, assert_ok};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::TypeTag,
+    vm_status::{StatusCode, VMStatus},
+};
+use std::collections::BTreeMap;
+
+fn mock_events(num_events: usize, event_size: usize) -> Vec<ContractEvent> {
+    (0..num_events)
+        .map(|i| {
+            ContractEvent::new(
+                EventKey::new(0, AccountAddress::ONE),
+                i as u64,
+                TypeTag::Bool,
+                vec![0; event_size],
+            )
+        })
+        .collect()
+}
+
+fn build_change_set_with_events(
+    events: Vec<ContractEvent>,
+    configs: &ChangeSetConfigs,
+) -> anyhow::Result<VMChangeSet, VMStatus> {
+    VMChangeSet::new(
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        BTreeMap::new(),
+        events,
+        configs,
+    )
+}
+
+fn assert_status(result: anyhow::Result<VMChangeSet, VMStatus>, status_code: StatusCode) {
+    assert_eq!(assert_err!(result).status_code(), status_code);
+}
+
+#[test]
+fn test_event_count_limit() {
+    // At most 10 events per transaction
+    let configs = ChangeSetConfigs::new_impl(12, 100, 1000, 100, 1000, 10);
+    assert_ok!(build_change_set_with_events(mock_events(10, 1), &configs));
+    assert_status(
+        build_change_set_with_events(mock_events(11, 1), &configs),
+        StatusCode::EVENT_COUNT_LIMIT_REACHED,
+    );
+}
+
+#[test]
+fn test_event_bytes_limit() {
+    // At most 100 bytes per event, 1000 bytes per transaction
+    let configs = ChangeSetConfigs::new_impl(12, 100, 1000, 100, 1000, 100);
+    assert_ok!(build_change_set_with_events(mock_events(10, 100), &configs));
+    assert_status(
+        build_change_set_with_events(mock_events(1, 101), &configs),
+        StatusCode::EVENT_BYTES_LIMIT_REACHED,
+    );
+    assert_status(
+        build_change_set_with_events(mock_events(11, 100), &configs),
+        StatusCode::EVENT_BYTES_LIMIT_REACHED,
+    );
+
+    // Before gas feature version 12, the generic status code is used
+    let configs = ChangeSetConfigs::new_impl(11, 100, 1000, 100, 1000, u64::MAX);
+    assert_status(
+        build_change_set_with_events(mock_events(1, 101), &configs),
+        StatusCode::STORAGE_WRITE_LIMIT_REACHED,
+    );
+}
@@ -0,0 +1,67 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::storage::{estimate_jmt_depth, StoragePricing, StoragePricingV4};
+use aptos_gas_algebra::GasExpression;
+use aptos_gas_schedule::{AptosGasParameters, InitialGasSchedule, LATEST_GAS_FEATURE_VERSION};
+use move_core_types::gas_algebra::{InternalGas, NumArgs, NumBytes};
+
+fn read_gas(
+    gas_params: &AptosGasParameters,
+    num_state_items: usize,
+    bytes_loaded: u64,
+) -> InternalGas {
+    let pricing = StoragePricing::V4(StoragePricingV4::new(
+        LATEST_GAS_FEATURE_VERSION,
+        gas_params,
+        num_state_items,
+    ));
+    pricing
+        .calculate_read_gas(true, NumBytes::new(bytes_loaded))
+        .evaluate(LATEST_GAS_FEATURE_VERSION, &gas_params.vm)
+}
+
+#[test]
+fn test_estimate_jmt_depth() {
+    assert_eq!(estimate_jmt_depth(0), NumArgs::new(1));
+    assert_eq!(estimate_jmt_depth(16), NumArgs::new(1));
+    assert_eq!(estimate_jmt_depth(17), NumArgs::new(2));
+    assert_eq!(estimate_jmt_depth(256), NumArgs::new(2));
+    assert_eq!(estimate_jmt_depth(1 << 32), NumArgs::new(8));
+    assert_eq!(estimate_jmt_depth(usize::MAX), NumArgs::new(16));
+}
+
+#[test]
+fn test_read_gas_grows_with_depth() {
+    let gas_params = AptosGasParameters::initial();
+    let txn = &gas_params.vm.txn;
+
+    // Reading from a deeper tree costs an extra charge per level
+    let shallow = read_gas(&gas_params, 16, 100);
+    let deep = read_gas(&gas_params, 1 << 32, 100);
+    assert_eq!(
+        deep,
+        shallow + txn.storage_io_per_state_slot_read_per_depth * NumArgs::new(7)
+    );
+}
+
+#[test]
+fn test_read_gas_charges_extra_pages() {
+    let gas_params = AptosGasParameters::initial();
+    let txn = &gas_params.vm.txn;
+    let page_size = u64::from(txn.storage_io_state_page_size);
+    let per_byte = |bytes: u64| txn.storage_io_per_state_byte_read * NumBytes::new(bytes);
+
+    // Items that fit in a single page only pay for the bytes read
+    let empty = read_gas(&gas_params, 1000, 0);
+    assert_eq!(
+        read_gas(&gas_params, 1000, page_size),
+        empty + per_byte(page_size)
+    );
+
+    // Items spanning multiple pages pay for each extra page
+    assert_eq!(
+        read_gas(&gas_params, 1000, 2 * page_size + 1),
+        empty + per_byte(2 * page_size + 1) + txn.storage_io_per_state_page_read * NumArgs::new(2)
+    );
+}
@@ -15,7 +15,9 @@ use crate::{
     sharded_block_executor::{executor_client::ExecutorClient, ShardedBlockExecutor},
     system_module_names::*,
     transaction_metadata::TransactionMetadata,
-    verifier, VMExecutor, VMValidator,
+    verified_signature_cache,
+    verifier::{self, module_compatibility},
+    ModuleCompatibilityReport, VMExecutor, VMValidator,
 };
 use anyhow::{anyhow, Result};
 use aptos_block_executor::txn_commit_hook::NoOpTransactionCommitHook;
@@ -29,17 +31,17 @@ use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
 use aptos_state_view::StateView;
 use aptos_types::{
     account_config,
-    account_config::new_block_event_key,
+    account_config::{new_block_event_key, AccountResource},
     block_executor::partitioner::PartitionedTransactions,
     block_metadata::BlockMetadata,
     fee_statement::FeeStatement,
     on_chain_config::{new_epoch_event_key, FeatureFlag, TimedFeatureOverride},
     state_store::state_key::StateKey,
     transaction::{
-        EntryFunction, ExecutionError, ExecutionStatus, ModuleBundle, Multisig,
-        MultisigTransactionPayload, SignatureCheckedTransaction, SignedTransaction, Transaction,
-        TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
-        WriteSetPayload,
+        authenticator::SignerSignatureStatus, EntryFunction, ExecutionError, ExecutionStatus,
+        ModuleBundle, Multisig, MultisigTransactionPayload, SignatureCheckedTransaction,
+        SignedTransaction, Transaction, TransactionOutput, TransactionPayload, TransactionStatus,
+        VMValidatorResult, WriteSetPayload,
     },
     vm_status::{AbortLocation, StatusCode, VMStatus},
     write_set::WriteOp,
@@ -63,6 +65,8 @@ use move_core_types::{
     ident_str,
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
+    move_resource::MoveStructType,
+    resolver::ModuleResolver,
     transaction_argument::convert_txn_args,
     value::{serialize_values, MoveValue},
     vm_status::StatusType,
@@ -1354,13 +1358,34 @@ impl AptosVM {
         txn: &SignedTransaction,
         state_view: &impl StateView,
     ) -> (VMStatus, TransactionOutput) {
-        let vm = AptosVM::new(state_view);
+        Self::simulate_transaction(txn, state_view, false)
+    }
+
+    /// Executes a fee payer (i.e., sponsored) SignedTransaction on behalf of its fee payer,
+    /// without the signatures or the keys of its signers. The prologue checks the on-chain
+    /// authentication keys of the sender, the secondary signers and the fee payer instead of
+    /// the keys of the transaction's authenticator.
+    pub fn simulate_sponsored_transaction(
+        txn: &SignedTransaction,
+        state_view: &impl StateView,
+    ) -> (VMStatus, TransactionOutput) {
+        Self::simulate_transaction(txn, state_view, true)
+    }
+
+    fn simulate_transaction(
+        txn: &SignedTransaction,
+        state_view: &impl StateView,
+        sponsored: bool,
+    ) -> (VMStatus, TransactionOutput) {
+        let mut vm = AptosVM::new(state_view);
+        vm.0.enable_simulation_mode();
         let simulation_vm = AptosSimulationVM(vm);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
 
         let (vm_status, vm_output) = simulation_vm.simulate_signed_transaction(
             &simulation_vm.0.as_move_resolver(state_view),
             txn,
+            sponsored,
             &log_context,
         );
         (
@@ -1371,6 +1396,48 @@ impl AptosVM {
         )
     }
 
+    /// Executes a partially signed SignedTransaction (e.g., a multi-agent transaction or a k-of-n
+    /// multisignature transaction that not all signers have signed yet) without performing
+    /// signature verification. Along with the simulation results, this returns the signature
+    /// status of each signer, i.e., which signatures are still required before submission.
+    pub fn simulate_partially_signed_transaction(
+        txn: &SignedTransaction,
+        state_view: &impl StateView,
+    ) -> (VMStatus, TransactionOutput, Vec<SignerSignatureStatus>) {
+        let (vm_status, output) = Self::simulate_signed_transaction(txn, state_view);
+        (vm_status, output, txn.signature_statuses())
+    }
+
+    /// Checks the compatibility of the given module bundle with the deployed code (without
+    /// publishing the bundle), and returns a detailed report for each module in the bundle.
+    /// This allows users to detect incompatible upgrades before paying gas for a failed publish.
+    pub fn check_module_bundle_compatibility(
+        state_view: &impl StateView,
+        modules: &ModuleBundle,
+    ) -> Result<Vec<ModuleCompatibilityReport>> {
+        let vm = AptosVM::new(state_view);
+        let resolver = vm.as_move_resolver(state_view);
+        let check_friend_linking = !vm
+            .0
+            .get_features()
+            .is_enabled(FeatureFlag::TREAT_FRIEND_AS_PRIVATE);
+
+        let mut reports = vec![];
+        for new_module in vm.deserialize_module_bundle(modules)? {
+            let old_module = resolver
+                .get_module(&new_module.self_id())?
+                .map(|old_module_bytes| CompiledModule::deserialize(&old_module_bytes))
+                .transpose()
+                .map_err(|err| anyhow!("Failed to deserialize the deployed module: {:?}", err))?;
+            reports.push(module_compatibility::check_module_compatibility(
+                old_module.as_ref(),
+                &new_module,
+                check_friend_linking,
+            ));
+        }
+        Ok(reports)
+    }
+
     pub fn execute_view_function(
         state_view: &impl StateView,
         module_id: ModuleId,
@@ -1379,27 +1446,78 @@ impl AptosVM {
         arguments: Vec<Vec<u8>>,
         gas_budget: u64,
     ) -> Result<Vec<Vec<u8>>> {
+        Self::execute_view_function_with_gas_used(
+            state_view, module_id, func_name, type_args, arguments, gas_budget,
+        )
+        .0
+    }
+
+    /// Same as `execute_view_function`, but also returns the gas used by the view function.
+    /// The gas is used whether the view function succeeds or fails (e.g., if it aborts or
+    /// runs out of gas).
+    pub fn execute_view_function_with_gas_used(
+        state_view: &impl StateView,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_budget: u64,
+    ) -> (Result<Vec<Vec<u8>>>, u64) {
         let vm = AptosVM::new(state_view);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
-        let mut gas_meter =
-            MemoryTrackedGasMeter::new(StandardGasMeter::new(StandardGasAlgebra::new(
-                vm.0.get_gas_feature_version(),
-                vm.0.get_gas_parameters(&log_context)?.vm.clone(),
-                vm.0.get_storage_gas_parameters(&log_context)?.clone(),
+        let mut gas_meter = match vm.new_view_function_gas_meter(&log_context, gas_budget) {
+            Ok(gas_meter) => gas_meter,
+            Err(err) => return (Err(err), 0),
+        };
+        let result = vm.execute_view_function_with_gas_meter(
+            state_view,
+            module_id,
+            func_name,
+            type_args,
+            arguments,
+            &mut gas_meter,
+        );
+        let gas_used = gas_budget.saturating_sub(u64::from(gas_meter.balance()));
+        (result, gas_used)
+    }
+
+    fn new_view_function_gas_meter(
+        &self,
+        log_context: &AdapterLogSchema,
+        gas_budget: u64,
+    ) -> Result<MemoryTrackedGasMeter<StandardGasMeter<StandardGasAlgebra>>> {
+        Ok(MemoryTrackedGasMeter::new(StandardGasMeter::new(
+            StandardGasAlgebra::new(
+                self.0.get_gas_feature_version(),
+                self.0.get_gas_parameters(log_context)?.vm.clone(),
+                self.0.get_storage_gas_parameters(log_context)?.clone(),
                 gas_budget,
-            )));
-        let resolver = vm.as_move_resolver(state_view);
-        let mut session = vm.new_session(&resolver, SessionId::Void);
+            ),
+        )))
+    }
+
+    fn execute_view_function_with_gas_meter(
+        &self,
+        state_view: &impl StateView,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_meter: &mut impl AptosGasMeter,
+    ) -> Result<Vec<Vec<u8>>> {
+        let resolver = self.as_move_resolver(state_view);
+        let mut session = self.new_session(&resolver, SessionId::Void);
 
         let func_inst = session.load_function(&module_id, &func_name, &type_args)?;
-        let metadata = vm.0.extract_module_metadata(&module_id);
+        let metadata = self.0.extract_module_metadata(&module_id);
         let arguments = verifier::view_function::validate_view_function(
             &mut session,
             arguments,
             func_name.as_ident_str(),
             &func_inst,
             metadata.as_ref(),
-            vm.0.get_features()
+            self.0
+                .get_features()
                 .is_enabled(FeatureFlag::STRUCT_CONSTRUCTORS),
         )?;
 
@@ -1409,7 +1527,7 @@ impl AptosVM {
                 func_name.as_ident_str(),
                 type_args,
                 arguments,
-                &mut gas_meter,
+                gas_meter,
             )
             .map_err(|err| anyhow!("Failed to execute function: {:?}", err))?
             .return_values
@@ -1479,6 +1597,15 @@ impl VMExecutor for AptosVM {
         state_view: &(impl StateView + Sync),
         maybe_block_gas_limit: Option<u64>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        Self::execute_block_with_aborts(transactions, state_view, maybe_block_gas_limit)
+            .map(|(outputs, _)| outputs)
+    }
+
+    fn execute_block_with_aborts(
+        transactions: Vec<Transaction>,
+        state_view: &(impl StateView + Sync),
+        maybe_block_gas_limit: Option<u64>,
+    ) -> Result<(Vec<TransactionOutput>, u64), VMStatus> {
         fail_point!("move_adapter::execute_block", |_| {
             Err(VMStatus::error(
                 StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
@@ -1493,7 +1620,7 @@ impl VMExecutor for AptosVM {
         );
 
         let count = transactions.len();
-        let ret = BlockAptosVM::execute_block::<
+        let ret = BlockAptosVM::execute_block_with_aborts::<
             _,
             NoOpTransactionCommitHook<AptosTransactionOutput, VMStatus>,
         >(
@@ -1606,7 +1733,9 @@ impl VMAdapter for AptosVM {
     }
 
     fn check_signature(txn: SignedTransaction) -> Result<SignatureCheckedTransaction> {
-        txn.check_signature()
+        // Signatures verified by mempool are cached, so they are not verified again by
+        // the signature verification pre-pass of block execution.
+        verified_signature_cache::check_signature(txn)
     }
 
     fn check_transaction_format(&self, txn: &SignedTransaction) -> Result<(), VMStatus> {
@@ -1732,6 +1861,18 @@ impl VMAdapter for AptosVM {
                     }
                 }
 
+                // Increment the counter for transactions emitting pathological event volumes.
+                let event_limit_label = match vm_status.status_code() {
+                    StatusCode::EVENT_COUNT_LIMIT_REACHED => Some("count"),
+                    StatusCode::EVENT_BYTES_LIMIT_REACHED => Some("bytes"),
+                    _ => None,
+                };
+                if let Some(label) = event_limit_label {
+                    TRANSACTIONS_EVENT_LIMIT_REACHED
+                        .with_label_values(&[label])
+                        .inc();
+                }
+
                 // Increment the counter for user transactions executed.
                 let counter_label = match output.status() {
                     TransactionStatus::Keep(_) => Some("success"),
@@ -1793,6 +1934,7 @@ impl AptosSimulationVM {
         &self,
         resolver: &impl MoveResolverExt,
         txn: &SignedTransaction,
+        sponsored: bool,
         log_context: &AdapterLogSchema,
     ) -> (VMStatus, VMOutput) {
         // simulation transactions should not carry valid signatures, otherwise malicious fullnodes
@@ -1802,7 +1944,12 @@ impl AptosSimulationVM {
         }
 
         // Revalidate the transaction.
-        let txn_data = TransactionMetadata::new(txn);
+        let mut txn_data = TransactionMetadata::new(txn);
+        if sponsored {
+            if let Err(err) = use_onchain_authentication_keys(resolver, &mut txn_data) {
+                return discard_error_vm_status(err);
+            }
+        }
         let mut session = self.0.new_session(resolver, SessionId::txn_meta(&txn_data));
         if let Err(err) =
             self.validate_simulated_transaction(&mut session, resolver, txn, &txn_data, log_context)
@@ -1930,3 +2077,44 @@ impl AptosSimulationVM {
         }
     }
 }
+
+/// Replaces the authentication keys of the signers of a fee payer transaction with their
+/// on-chain authentication keys, so that the prologue accepts the transaction without the
+/// signers' keys. The keys of accounts that don't exist are kept, so the prologue rejects them.
+fn use_onchain_authentication_keys(
+    resolver: &impl MoveResolverExt,
+    txn_data: &mut TransactionMetadata,
+) -> Result<(), VMStatus> {
+    // Only sponsored transactions can be simulated without the signers' keys
+    if txn_data.fee_payer().is_none() {
+        return Err(VMStatus::error(StatusCode::INVALID_SIGNATURE, None));
+    }
+
+    let onchain_authentication_key = |address: AccountAddress| -> Result<_, VMStatus> {
+        let account = resolver
+            .get_resource(&address, &AccountResource::struct_tag())
+            .map_err(|_| VMStatus::error(StatusCode::STORAGE_ERROR, None))?
+            .map(|bytes| bcs::from_bytes::<AccountResource>(&bytes))
+            .transpose()
+            .map_err(|_| VMStatus::error(StatusCode::UNEXPECTED_DESERIALIZATION_ERROR, None))?;
+        Ok(account.map(|account| account.authentication_key().to_vec()))
+    };
+    if let Some(key) = onchain_authentication_key(txn_data.sender)? {
+        txn_data.authentication_key = key;
+    }
+    for (address, key) in txn_data
+        .secondary_signers
+        .iter()
+        .zip(txn_data.secondary_authentication_keys.iter_mut())
+    {
+        if let Some(onchain_key) = onchain_authentication_key(*address)? {
+            *key = onchain_key;
+        }
+    }
+    if let Some(fee_payer) = txn_data.fee_payer {
+        if let Some(key) = onchain_authentication_key(fee_payer)? {
+            txn_data.fee_payer_authentication_key = Some(key);
+        }
+    }
+    Ok(())
+}
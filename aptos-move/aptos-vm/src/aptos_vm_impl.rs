@@ -24,8 +24,9 @@ use aptos_types::{
     fee_statement::FeeStatement,
     on_chain_config::{
         ApprovedExecutionHashes, ConfigurationResource, FeatureFlag, Features, GasSchedule,
-        GasScheduleV2, OnChainConfig, TimedFeatures, Version,
+        GasScheduleV2, OnChainConfig, StateStorageUsageResource, TimedFeatures, Version,
     },
+    state_store::state_key::StateKey,
     transaction::{AbortInfo, ExecutionStatus, Multisig, TransactionStatus},
     vm_status::{StatusCode, VMStatus},
 };
@@ -77,6 +78,22 @@ pub fn gas_config(storage: &impl MoveResolverExt) -> (Result<AptosGasParameters,
     }
 }
 
+/// Returns the number of state items recorded on chain at the beginning of the epoch. Unlike the
+/// usage tracked by the state view, it is the same no matter how the block is executed (e.g.,
+/// sharded, or replayed from a remote state).
+fn on_chain_num_state_items(state: &impl StateView) -> anyhow::Result<usize> {
+    let state_key = StateKey::access_path(StateStorageUsageResource::access_path()?);
+    Ok(match state.get_state_value_bytes(&state_key)? {
+        Some(bytes) => {
+            StateStorageUsageResource::deserialize_into_config(&bytes)?
+                .usage
+                .items as usize
+        },
+        // The usage is only missing before it is initialized in genesis
+        None => 0,
+    })
+}
+
 impl AptosVMImpl {
     #[allow(clippy::new_without_default)]
     pub fn new(state: &impl StateView) -> Self {
@@ -85,10 +102,21 @@ impl AptosVMImpl {
         // Get the gas parameters
         let (mut gas_params, gas_feature_version) = gas_config(&storage);
 
-        let storage_gas_params = match &mut gas_params {
-            Ok(gas_params) => {
-                let storage_gas_params =
-                    StorageGasParameters::new(gas_feature_version, gas_params, &storage);
+        // The number of state items is used to estimate the depth of the state tree
+        let num_state_items = if gas_feature_version >= 13 {
+            on_chain_num_state_items(state)
+        } else {
+            Ok(0)
+        };
+
+        let storage_gas_params = match (&mut gas_params, num_state_items) {
+            (Ok(gas_params), Ok(num_state_items)) => {
+                let storage_gas_params = StorageGasParameters::new(
+                    gas_feature_version,
+                    gas_params,
+                    &storage,
+                    num_state_items,
+                );
 
                 // Overwrite table io gas parameters with global io pricing.
                 let g = &mut gas_params.natives.table;
@@ -110,16 +138,29 @@ impl AptosVMImpl {
                             g.common_load_failure = 0.into();
                         }
                     }
-                    10.. => {
+                    10..=12 => {
                         g.common_load_base_legacy = 0.into();
                         g.common_load_base_new = gas_params.vm.txn.storage_io_per_state_slot_read * NumArgs::new(1);
                         g.common_load_per_byte = gas_params.vm.txn.storage_io_per_state_byte_read;
                         g.common_load_failure = 0.into();
                     }
+                    13.. => {
+                        if let StoragePricing::V4(pricing) = &storage_gas_params.pricing {
+                            let txn = &gas_params.vm.txn;
+                            g.common_load_base_legacy = 0.into();
+                            g.common_load_base_new = txn.storage_io_per_state_slot_read * NumArgs::new(1)
+                                + txn.storage_io_per_state_slot_read_per_depth * pricing.estimated_jmt_depth;
+                            g.common_load_per_byte = txn.storage_io_per_state_byte_read;
+                            g.common_load_per_extra_page = txn.storage_io_per_state_page_read;
+                            g.common_load_page_size = txn.storage_io_state_page_size;
+                            g.common_load_failure = 0.into();
+                        }
+                    }
                 };
                 Ok(storage_gas_params)
             },
-            Err(err) => Err(format!("Failed to initialize storage gas params due to failure to load main gas parameters: {}", err)),
+            (Err(err), _) => Err(format!("Failed to initialize storage gas params due to failure to load main gas parameters: {}", err)),
+            (Ok(_), Err(err)) => Err(format!("Failed to initialize storage gas params due to failure to load the state storage usage: {}", err)),
         };
 
         // TODO(Gas): Right now, we have to use some dummy values for gas parameters if they are not found on-chain.
@@ -610,6 +651,11 @@ impl AptosVMImpl {
         }
     }
 
+    /// Marks the VM as being used for transaction simulation (see `MoveVmExt`)
+    pub(crate) fn enable_simulation_mode(&mut self) {
+        self.move_vm.enable_simulation_mode();
+    }
+
     pub fn new_session<'r>(
         &self,
         resolver: &'r impl MoveResolverExt,
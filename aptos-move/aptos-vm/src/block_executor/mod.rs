@@ -176,10 +176,35 @@ impl BlockAptosVM {
         maybe_block_gas_limit: Option<u64>,
         transaction_commit_listener: Option<L>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        Self::execute_block_with_aborts(
+            executor_thread_pool,
+            transactions,
+            state_view,
+            concurrency_level,
+            maybe_block_gas_limit,
+            transaction_commit_listener,
+        )
+        .map(|(outputs, _)| outputs)
+    }
+
+    /// Executes the block like `execute_block`, and also returns the number of speculative
+    /// executions aborted (and re-executed) by Block-STM while executing it.
+    pub fn execute_block_with_aborts<
+        S: StateView + Sync,
+        L: TransactionCommitHook<Output = AptosTransactionOutput>,
+    >(
+        executor_thread_pool: Arc<ThreadPool>,
+        transactions: Vec<Transaction>,
+        state_view: &S,
+        concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
+        transaction_commit_listener: Option<L>,
+    ) -> Result<(Vec<TransactionOutput>, u64), VMStatus> {
         let _timer = BLOCK_EXECUTOR_EXECUTE_BLOCK_SECONDS.start_timer();
         // Verify the signatures of all the transactions in parallel.
         // This is time consuming so don't wait and do the checking
-        // sequentially while executing the transactions.
+        // sequentially while executing the transactions. Transactions
+        // already verified by mempool are skipped (see VerifiedSignatureCache).
         // TODO: state sync runs this code but doesn't need to verify signatures
         let signature_verification_timer =
             BLOCK_EXECUTOR_SIGNATURE_VERIFICATION_SECONDS.start_timer();
@@ -209,6 +234,7 @@ impl BlockAptosVM {
         );
 
         let ret = executor.execute_block(state_view, signature_verified_block, state_view);
+        let num_speculative_aborts = executor.num_speculative_aborts();
         match ret {
             Ok(outputs) => {
                 let output_vec: Vec<TransactionOutput> = outputs
@@ -225,7 +251,7 @@ impl BlockAptosVM {
                     flush_speculative_logs(pos);
                 }
 
-                Ok(output_vec)
+                Ok((output_vec, num_speculative_aborts))
             },
             Err(Error::ModulePathReadWrite) => {
                 unreachable!("[Execution]: Must be handled by sequential fallback")
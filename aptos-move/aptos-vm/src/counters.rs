@@ -42,6 +42,17 @@ pub static BLOCK_EXECUTOR_SIGNATURE_VERIFICATION_SECONDS: Lazy<Histogram> = Lazy
     .unwrap()
 });
 
+/// Count the number of lookups in the verified signature cache, with a "result"
+/// label to distinguish hits (i.e., signature verification was skipped) and misses.
+pub static SIGNATURE_VERIFICATION_CACHE_LOOKUPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_vm_signature_verification_cache_lookups",
+        "Number of lookups in the verified signature cache",
+        &["result"]
+    )
+    .unwrap()
+});
+
 /// Count the number of transactions that brake invariants of VM.
 pub static TRANSACTIONS_INVARIANT_VIOLATION: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -73,6 +84,17 @@ pub static USER_TRANSACTIONS_EXECUTED: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count the number of user transactions that reached the limits on emitted events, with a
+/// "limit" label to distinguish the event count limit from the event bytes limit.
+pub static TRANSACTIONS_EVENT_LIMIT_REACHED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_vm_transactions_event_limit_reached",
+        "Number of user transactions that reached the limits on emitted events",
+        &["limit"]
+    )
+    .unwrap()
+});
+
 /// Count the number of system transactions executed.
 pub static SYSTEM_TRANSACTIONS_EXECUTED: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -9,13 +9,18 @@ use crate::{
 };
 #[allow(unused_imports)]
 use anyhow::Error;
-use aptos_framework::natives::state_storage::StateStorageUsageResolver;
+use aptos_framework::natives::{
+    randomness::RandomnessResolver, state_storage::StateStorageUsageResolver,
+};
 use aptos_state_view::StateView;
 use aptos_table_natives::{TableHandle, TableResolver};
 use aptos_types::{
     access_path::AccessPath,
-    on_chain_config::{ConfigStorage, Features, OnChainConfig},
-    state_store::{state_key::StateKey, state_storage_usage::StateStorageUsage},
+    on_chain_config::{ConfigStorage, Features, OnChainConfig, PerBlockRandomness},
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage,
+        table::TableHandle as StateTableHandle,
+    },
 };
 use move_binary_format::{errors::*, CompiledModule};
 use move_core_types::{
@@ -89,6 +94,27 @@ impl<'a, S: StateView> StorageAdapter<'a, S> {
             .map_err(|_| PartialVMError::new(StatusCode::STORAGE_ERROR))
     }
 
+    /// Reads the items of a table keyed by contiguous `u64` indices (e.g., the buckets
+    /// of a `SmartTable` or `BigVector`), starting at `start`. At most `limit` items
+    /// are returned, and the read stops at the first missing index (i.e., the end
+    /// of the table). The items of the page are read in one batch.
+    pub fn get_indexed_table_items(
+        &self,
+        handle: &StateTableHandle,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<(u64, Vec<u8>)>, Error> {
+        let state_keys: Vec<_> = (start..start.saturating_add(limit))
+            .map(|index| handle.indexed_item_state_key(index))
+            .collect();
+        Ok(self
+            .get_state_values(&state_keys)?
+            .into_iter()
+            .zip(start..)
+            .map_while(|(value, index)| value.map(|value| (index, value.into_bytes())))
+            .collect())
+    }
+
     fn get_any_resource(
         &self,
         address: &AccountAddress,
@@ -218,6 +244,17 @@ impl<'a, S: StateView> StateStorageUsageResolver for StorageAdapter<'a, S> {
     }
 }
 
+impl<'a, S: StateView> RandomnessResolver for StorageAdapter<'a, S> {
+    fn get_randomness_seed(&self) -> Result<Option<Vec<u8>>, Error> {
+        let state_key = StateKey::access_path(PerBlockRandomness::access_path()?);
+        let randomness = match self.get_state_value_bytes(&state_key)? {
+            Some(bytes) => PerBlockRandomness::deserialize_into_config(&bytes)?,
+            None => return Ok(None),
+        };
+        Ok(randomness.seed)
+    }
+}
+
 impl<'a, S> Deref for StorageAdapter<'a, S> {
     type Target = S;
 
@@ -117,6 +117,7 @@ pub mod sharded_block_executor;
 pub mod system_module_names;
 pub mod transaction_metadata;
 mod transaction_validation;
+mod verified_signature_cache;
 mod verifier;
 
 pub use crate::aptos_vm::AptosVM;
@@ -128,7 +129,10 @@ use aptos_types::{
     vm_status::VMStatus,
 };
 use std::{marker::Sync, sync::Arc};
-pub use verifier::view_function::determine_is_view;
+pub use verifier::{
+    module_compatibility::{CompatibilityIssue, CompatibilityIssueKind, ModuleCompatibilityReport},
+    view_function::determine_is_view,
+};
 
 /// This trait describes the VM's validation interfaces.
 pub trait VMValidator {
@@ -154,6 +158,18 @@ pub trait VMExecutor: Send + Sync {
         maybe_block_gas_limit: Option<u64>,
     ) -> Result<Vec<TransactionOutput>, VMStatus>;
 
+    /// Executes a block of transactions like `execute_block`, and also returns the number of
+    /// speculative executions aborted (and re-executed) by Block-STM while executing it (zero
+    /// if the executor doesn't speculate).
+    fn execute_block_with_aborts(
+        transactions: Vec<Transaction>,
+        state_view: &(impl StateView + Sync),
+        maybe_block_gas_limit: Option<u64>,
+    ) -> Result<(Vec<TransactionOutput>, u64), VMStatus> {
+        Self::execute_block(transactions, state_view, maybe_block_gas_limit)
+            .map(|outputs| (outputs, 0))
+    }
+
     /// Executes a block of transactions using a sharded block executor and returns the results.
     fn execute_block_sharded<S: StateView + Sync + Send + 'static, E: ExecutorClient<S>>(
         sharded_block_executor: &ShardedBlockExecutor<S, E>,
@@ -1,7 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_framework::natives::state_storage::StateStorageUsageResolver;
+use aptos_framework::natives::{
+    randomness::RandomnessResolver, state_storage::StateStorageUsageResolver,
+};
 use aptos_state_view::StateView;
 use aptos_table_natives::TableResolver;
 use aptos_types::on_chain_config::ConfigStorage;
@@ -13,7 +15,12 @@ use move_core_types::{
 use std::collections::BTreeMap;
 
 pub trait MoveResolverExt:
-    MoveResolver + TableResolver + StateStorageUsageResolver + ConfigStorage + StateView
+    MoveResolver
+    + TableResolver
+    + StateStorageUsageResolver
+    + RandomnessResolver
+    + ConfigStorage
+    + StateView
 {
     fn get_resource_group_data(
         &self,
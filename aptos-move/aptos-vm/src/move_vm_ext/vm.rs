@@ -9,6 +9,7 @@ use aptos_framework::natives::{
     aggregator_natives::NativeAggregatorContext,
     code::NativeCodeContext,
     cryptography::{algebra::AlgebraContext, ristretto255_point::NativeRistrettoPointContext},
+    randomness::NativeRandomnessContext,
     state_storage::NativeStateStorageContext,
     transaction_context::NativeTransactionContext,
 };
@@ -28,6 +29,7 @@ pub struct MoveVmExt {
     inner: MoveVM,
     chain_id: u8,
     features: Arc<Features>,
+    is_simulation: bool,
 }
 
 pub fn get_max_binary_format_version(features: &Features, gas_feature_version: u64) -> u32 {
@@ -99,6 +101,7 @@ impl MoveVmExt {
             })?,
             chain_id,
             features: Arc::new(features),
+            is_simulation: false,
         })
     }
 
@@ -144,6 +147,13 @@ impl MoveVmExt {
         )
     }
 
+    /// Marks the VM as being used for transaction simulation. Simulated transactions use fresh
+    /// local randomness instead of the on-chain randomness seed, so simulations cannot be used
+    /// to predict (or replay) the outcome of transactions that consume randomness.
+    pub fn enable_simulation_mode(&mut self) {
+        self.is_simulation = true;
+    }
+
     pub fn new_session<'r, S: MoveResolverExt>(
         &self,
         remote: &'r S,
@@ -188,6 +198,14 @@ impl MoveVmExt {
         ));
         extensions.add(NativeCodeContext::default());
         extensions.add(NativeStateStorageContext::new(remote));
+        if self.is_simulation {
+            extensions.add(NativeRandomnessContext::new_for_simulation(
+                txn_hash.to_vec(),
+                remote,
+            ));
+        } else {
+            extensions.add(NativeRandomnessContext::new(txn_hash.to_vec(), remote));
+        }
 
         // The VM code loader has bugs around module upgrade. After a module upgrade, the internal
         // cache needs to be flushed to work around those bugs.
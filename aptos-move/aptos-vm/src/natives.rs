@@ -18,7 +18,7 @@ use {
     aptos_framework::natives::{
         aggregator_natives::NativeAggregatorContext, code::NativeCodeContext,
         cryptography::ristretto255_point::NativeRistrettoPointContext,
-        transaction_context::NativeTransactionContext,
+        randomness::NativeRandomnessContext, transaction_context::NativeTransactionContext,
     },
     move_vm_runtime::native_extensions::NativeContextExtensions,
     move_vm_test_utils::BlankStorage,
@@ -113,4 +113,8 @@ fn unit_test_extensions_hook(exts: &mut NativeContextExtensions) {
     exts.add(NativeAggregatorContext::new([0; 32], &*DUMMY_RESOLVER));
     exts.add(NativeRistrettoPointContext::new());
     exts.add(AlgebraContext::new());
+    exts.add(NativeRandomnessContext::new_with_seed(
+        vec![1],
+        Some(vec![0; 32]),
+    )); // We use a fixed seed here, so unit tests are deterministic
 }
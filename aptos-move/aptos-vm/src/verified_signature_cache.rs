@@ -0,0 +1,37 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters::SIGNATURE_VERIFICATION_CACHE_LOOKUPS;
+use anyhow::Result;
+use aptos_types::transaction::{
+    SignatureCheckedTransaction, SignedTransaction, VerifiedSignatureCache,
+};
+use once_cell::sync::Lazy;
+
+/// The maximum number of verified transactions held by the cache (this should
+/// be large enough to hold all transactions in mempool that may end up in a block).
+const MAX_VERIFIED_TRANSACTIONS: usize = 200_000;
+
+// Useful labels for the cache lookup metrics
+const CACHE_HIT_LABEL: &str = "hit";
+const CACHE_MISS_LABEL: &str = "miss";
+
+/// The verified signature cache shared by mempool (i.e., transaction validation)
+/// and block execution, as both run in the same process on validators.
+static VERIFIED_SIGNATURE_CACHE: Lazy<VerifiedSignatureCache> =
+    Lazy::new(|| VerifiedSignatureCache::new(MAX_VERIFIED_TRANSACTIONS));
+
+/// Checks the signature of the given transaction, unless it was already verified
+/// (see `VerifiedSignatureCache`).
+pub(crate) fn check_signature(txn: SignedTransaction) -> Result<SignatureCheckedTransaction> {
+    let (result, cache_hit) = VERIFIED_SIGNATURE_CACHE.check_signature(txn);
+    let label = if cache_hit {
+        CACHE_HIT_LABEL
+    } else {
+        CACHE_MISS_LABEL
+    };
+    SIGNATURE_VERIFICATION_CACHE_LOOKUPS
+        .with_label_values(&[label])
+        .inc();
+    result
+}
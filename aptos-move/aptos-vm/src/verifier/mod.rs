@@ -1,5 +1,6 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
+pub(crate) mod module_compatibility;
 pub(crate) mod module_init;
 pub(crate) mod resource_groups;
 pub mod transaction_arg_validation;
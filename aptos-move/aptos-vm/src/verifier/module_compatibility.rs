@@ -0,0 +1,201 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::{
+    access::ModuleAccess,
+    compatibility::{
+        fun_signature_compatible, struct_abilities_compatibile, struct_type_parameters_compatibile,
+    },
+    file_format::Visibility,
+    normalized::{Field, Module},
+    CompiledModule,
+};
+use move_core_types::language_storage::ModuleId;
+use std::collections::BTreeSet;
+
+/// The kind of a backward incompatible change between two versions of a module
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompatibilityIssueKind {
+    /// A struct was removed
+    StructRemoved,
+    /// A struct lost abilities or its type parameters changed incompatibly
+    StructSignatureChanged,
+    /// The fields of a struct changed (i.e., its layout changed)
+    StructLayoutChanged,
+    /// A public, friend or entry function was removed
+    FunctionRemoved,
+    /// The visibility, entry modifier or signature of a function changed incompatibly
+    FunctionSignatureChanged,
+    /// A friend declaration was removed
+    FriendRemoved,
+}
+
+/// A single backward incompatible change between two versions of a module
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompatibilityIssue {
+    pub kind: CompatibilityIssueKind,
+    pub name: String,         // The name of the affected struct, function or friend
+    pub message: String,      // A human readable description of the issue
+    pub blocks_publish: bool, // Whether the issue would cause the publish to fail
+}
+
+impl CompatibilityIssue {
+    fn new(kind: CompatibilityIssueKind, name: String, message: String) -> Self {
+        Self {
+            kind,
+            name,
+            message,
+            blocks_publish: true,
+        }
+    }
+
+    fn new_friend_issue(
+        kind: CompatibilityIssueKind,
+        name: String,
+        message: String,
+        check_friend_linking: bool,
+    ) -> Self {
+        Self {
+            kind,
+            name,
+            message,
+            blocks_publish: check_friend_linking,
+        }
+    }
+}
+
+/// A report of the compatibility of a new module version with the deployed version
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ModuleCompatibilityReport {
+    pub module_id: ModuleId,
+    pub is_new_module: bool, // Whether the module is not yet deployed
+    pub issues: Vec<CompatibilityIssue>,
+}
+
+impl ModuleCompatibilityReport {
+    /// Returns true iff the module can be published (i.e., no issue blocks the publish)
+    pub fn is_compatible(&self) -> bool {
+        self.issues.iter().all(|issue| !issue.blocks_publish)
+    }
+}
+
+/// Computes a detailed compatibility report of the new module versus the deployed
+/// module (if any). Unlike `Compatibility::check`, this reports every incompatible
+/// change instead of failing on the first one. Changes to friend functions and
+/// declarations only block the publish if `check_friend_linking` is true.
+pub fn check_module_compatibility(
+    old_module: Option<&CompiledModule>,
+    new_module: &CompiledModule,
+    check_friend_linking: bool,
+) -> ModuleCompatibilityReport {
+    let module_id = new_module.self_id();
+    let old_module = match old_module {
+        Some(old_module) => Module::new(old_module),
+        None => {
+            return ModuleCompatibilityReport {
+                module_id,
+                is_new_module: true,
+                issues: vec![],
+            }
+        },
+    };
+    let new_module = Module::new(new_module);
+
+    let mut issues = vec![];
+    for (name, old_struct) in &old_module.structs {
+        let new_struct = match new_module.structs.get(name) {
+            Some(new_struct) => new_struct,
+            None => {
+                issues.push(CompatibilityIssue::new(
+                    CompatibilityIssueKind::StructRemoved,
+                    name.to_string(),
+                    format!("Struct {} was removed", name),
+                ));
+                continue;
+            },
+        };
+        if !struct_abilities_compatibile(old_struct.abilities, new_struct.abilities)
+            || !struct_type_parameters_compatibile(
+                &old_struct.type_parameters,
+                &new_struct.type_parameters,
+            )
+        {
+            issues.push(CompatibilityIssue::new(
+                CompatibilityIssueKind::StructSignatureChanged,
+                name.to_string(),
+                format!(
+                    "Struct {} removed abilities or changed its type parameters",
+                    name
+                ),
+            ));
+        }
+        if new_struct.fields != old_struct.fields {
+            issues.push(CompatibilityIssue::new(
+                CompatibilityIssueKind::StructLayoutChanged,
+                name.to_string(),
+                format!(
+                    "Struct {} changed its fields (from {:?} to {:?})",
+                    name,
+                    get_field_names(&old_struct.fields),
+                    get_field_names(&new_struct.fields)
+                ),
+            ));
+        }
+    }
+
+    for (name, old_func) in &old_module.exposed_functions {
+        let is_friend_function = matches!(old_func.visibility, Visibility::Friend);
+        let (kind, message) = match new_module.exposed_functions.get(name) {
+            Some(new_func) => {
+                if fun_signature_compatible(&old_module, &new_module, old_func, new_func) {
+                    continue;
+                }
+                (
+                    CompatibilityIssueKind::FunctionSignatureChanged,
+                    format!(
+                        "Function {} changed its visibility, entry modifier or signature",
+                        name
+                    ),
+                )
+            },
+            None => (
+                CompatibilityIssueKind::FunctionRemoved,
+                format!("Function {} was removed", name),
+            ),
+        };
+        let issue = if is_friend_function {
+            CompatibilityIssue::new_friend_issue(
+                kind,
+                name.to_string(),
+                message,
+                check_friend_linking,
+            )
+        } else {
+            CompatibilityIssue::new(kind, name.to_string(), message)
+        };
+        issues.push(issue);
+    }
+
+    let new_friends: BTreeSet<_> = new_module.friends.iter().collect();
+    for old_friend in &old_module.friends {
+        if !new_friends.contains(old_friend) {
+            issues.push(CompatibilityIssue::new_friend_issue(
+                CompatibilityIssueKind::FriendRemoved,
+                old_friend.to_string(),
+                format!("Friend declaration of {} was removed", old_friend),
+                check_friend_linking,
+            ));
+        }
+    }
+
+    ModuleCompatibilityReport {
+        module_id,
+        is_new_module: false,
+        issues,
+    }
+}
+
+/// Returns the names of the given struct fields
+fn get_field_names(fields: &[Field]) -> Vec<String> {
+    fields.iter().map(|field| field.name.to_string()).collect()
+}
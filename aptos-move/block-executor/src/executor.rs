@@ -30,6 +30,7 @@ use rayon::ThreadPool;
 use std::{
     marker::PhantomData,
     sync::{
+        atomic::{AtomicU64, Ordering},
         mpsc,
         mpsc::{Receiver, Sender},
         Arc,
@@ -75,6 +76,9 @@ pub struct BlockExecutor<T, E, S, L, X> {
     executor_thread_pool: Arc<ThreadPool>,
     maybe_block_gas_limit: Option<u64>,
     transaction_commit_hook: Option<L>,
+    // number of speculative executions aborted by this executor (unlike
+    // SPECULATIVE_ABORT_COUNT, it isn't shared with concurrent executors).
+    num_speculative_aborts: AtomicU64,
     phantom: PhantomData<(T, E, S, L, X)>,
 }
 
@@ -104,10 +108,17 @@ where
             executor_thread_pool,
             maybe_block_gas_limit,
             transaction_commit_hook,
+            num_speculative_aborts: AtomicU64::new(0),
             phantom: PhantomData,
         }
     }
 
+    /// Returns the number of speculative executions aborted (and re-executed) by the blocks
+    /// executed by this executor.
+    pub fn num_speculative_aborts(&self) -> u64 {
+        self.num_speculative_aborts.load(Ordering::Relaxed)
+    }
+
     fn execute(
         &self,
         version: Version,
@@ -232,6 +243,7 @@ where
 
         if aborted {
             counters::SPECULATIVE_ABORT_COUNT.inc();
+            self.num_speculative_aborts.fetch_add(1, Ordering::Relaxed);
 
             // Any logs from the aborted execution should be cleared and not reported.
             clear_speculative_txn_logs(idx_to_validate as usize);
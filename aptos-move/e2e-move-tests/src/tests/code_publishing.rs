@@ -2,12 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{assert_abort, assert_success, assert_vm_status, tests::common, MoveHarness};
-use aptos_framework::natives::code::{PackageRegistry, UpgradePolicy};
+use aptos_framework::{
+    natives::code::{PackageRegistry, UpgradePolicy},
+    BuildOptions, BuiltPackage,
+};
 use aptos_package_builder::PackageBuilder;
 use aptos_types::{
     account_address::{create_resource_address, AccountAddress},
     on_chain_config::FeatureFlag,
+    transaction::ModuleBundle,
 };
+use aptos_vm::{AptosVM, CompatibilityIssueKind, ModuleCompatibilityReport};
 use move_core_types::{parser::parse_struct_tag, vm_status::StatusCode};
 use rstest::rstest;
 use serde::{Deserialize, Serialize};
@@ -108,6 +113,41 @@ fn code_publishing_upgrade_fail_compat() {
     assert_vm_status!(status, StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
 }
 
+#[test]
+fn code_publishing_compatibility_report() {
+    let mut h = MoveHarness::new();
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    // Before the initial version is installed, the module is reported as new
+    let reports = check_package_compatibility(&h, "code_publishing.data/pack_initial");
+    assert_eq!(reports.len(), 1);
+    assert!(reports[0].is_new_module);
+    assert!(reports[0].is_compatible());
+
+    // Install the initial version with compat requirements
+    assert_success!(h.publish_package(
+        &acc,
+        &common::test_dir_path("code_publishing.data/pack_initial"),
+    ));
+
+    // The compatible version should not report any issues
+    let reports = check_package_compatibility(&h, "code_publishing.data/pack_upgrade_compat");
+    assert_eq!(reports.len(), 1);
+    assert!(!reports[0].is_new_module);
+    assert!(reports[0].issues.is_empty());
+
+    // The incompatible version should report the changed function signature
+    let reports = check_package_compatibility(&h, "code_publishing.data/pack_upgrade_incompat");
+    assert_eq!(reports.len(), 1);
+    assert!(!reports[0].is_compatible());
+    assert_eq!(reports[0].issues.len(), 1);
+    assert_eq!(
+        reports[0].issues[0].kind,
+        CompatibilityIssueKind::FunctionSignatureChanged
+    );
+    assert_eq!(reports[0].issues[0].name, "hello");
+}
+
 #[test]
 fn code_publishing_upgrade_fail_immutable() {
     let mut h = MoveHarness::new();
@@ -353,3 +393,14 @@ fn code_publishing_friend_as_private(enabled: Vec<FeatureFlag>, disabled: Vec<Fe
         assert_vm_status!(result, StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
     }
 }
+
+/// Builds the package at the given path and checks its compatibility with the deployed code
+fn check_package_compatibility(h: &MoveHarness, path: &str) -> Vec<ModuleCompatibilityReport> {
+    let package = BuiltPackage::build(common::test_dir_path(path), BuildOptions::default())
+        .expect("building package must succeed");
+    AptosVM::check_module_bundle_compatibility(
+        h.executor.get_state_view(),
+        &ModuleBundle::new(package.extract_code()),
+    )
+    .unwrap()
+}
@@ -6,6 +6,8 @@ module aptos_framework::block {
     use std::option;
 
     use aptos_framework::account;
+    use aptos_framework::create_signer;
+    use aptos_framework::randomness;
     use aptos_framework::event::{Self, EventHandle};
     use aptos_framework::reconfiguration;
     use aptos_framework::stake;
@@ -137,6 +139,10 @@ module aptos_framework::block {
         };
         emit_new_block_event(&vm, &mut block_metadata_ref.new_block_events, new_block_event);
 
+        // Activate the feature flag changes scheduled within the epoch before anything reads the flags, so that
+        // all transactions of the block see the same flags.
+        features::apply_scheduled_activations(&create_signer::create_signer(@aptos_framework), timestamp);
+
         if (features::collect_and_distribute_gas_fees()) {
             // Assign the fees collected from the previous block to the previous block proposer.
             // If for any reason the fees cannot be assigned, this function burns the collected coins.
@@ -150,6 +156,10 @@ module aptos_framework::block {
         // transition is the last block in the previous epoch.
         stake::update_performance_statistics(proposer_index, failed_proposer_indices);
         state_storage::on_new_block(reconfiguration::current_epoch());
+        if (features::randomness_enabled()) {
+            // The block metadata doesn't carry a randomness seed yet, so randomness is unavailable.
+            randomness::on_new_block(&vm, epoch, round, option::none());
+        };
 
         if (timestamp - reconfiguration::last_reconfiguration_time() >= block_metadata_ref.epoch_interval) {
             reconfiguration::reconfigure();
@@ -0,0 +1,166 @@
+/// Maintains the maintenance windows announced by the validator operators, e.g. to restart their
+/// nodes for a planned upgrade. Consensus doesn't elect a validator as the leader during its
+/// windows, so that their rounds don't time out.
+///
+/// Consensus reads the schedule at the start of each epoch, so windows can only be announced for
+/// the next epoch. This way, all the validators agree on the windows of the current epoch,
+/// including the ones restarting during it.
+module aptos_framework::maintenance_schedule {
+    use std::error;
+    use std::signer;
+    use std::vector;
+
+    use aptos_framework::create_signer;
+    use aptos_framework::reconfiguration;
+    use aptos_framework::stake;
+
+    #[test_only]
+    use aptos_framework::account;
+    #[test_only]
+    use aptos_framework::timestamp;
+
+    struct MaintenanceWindow has copy, drop, store {
+        /// Address of the stake pool of the validator
+        validator: address,
+        /// Epoch of the window
+        epoch: u64,
+        /// First round of the window
+        start_round: u64,
+        /// Number of rounds in the window
+        num_rounds: u64,
+    }
+
+    struct MaintenanceSchedule has key {
+        /// The windows of the current and next epochs, at most one per validator and epoch
+        windows: vector<MaintenanceWindow>,
+    }
+
+    /// The signer is not the operator of the stake pool
+    const ENOT_OPERATOR: u64 = 1;
+
+    /// The stake pool is not in the current validator set
+    const ENOT_VALIDATOR: u64 = 2;
+
+    /// Windows can only be announced for the next epoch
+    const ENOT_NEXT_EPOCH: u64 = 3;
+
+    /// The window is empty or longer than MAX_WINDOW_ROUNDS
+    const EINVALID_NUM_ROUNDS: u64 = 4;
+
+    /// Max number of rounds of a maintenance window
+    const MAX_WINDOW_ROUNDS: u64 = 1000;
+
+    /// Announces a maintenance window of the validator in the next epoch, replacing the window it
+    /// already announced for that epoch (if any). This can only be called by the operator.
+    public entry fun announce(
+        operator: &signer,
+        pool_address: address,
+        epoch: u64,
+        start_round: u64,
+        num_rounds: u64,
+    ) acquires MaintenanceSchedule {
+        assert!(
+            signer::address_of(operator) == stake::get_operator(pool_address),
+            error::unauthenticated(ENOT_OPERATOR),
+        );
+        assert!(stake::is_current_epoch_validator(pool_address), error::invalid_argument(ENOT_VALIDATOR));
+        let current_epoch = reconfiguration::current_epoch();
+        assert!(epoch == current_epoch + 1, error::invalid_argument(ENOT_NEXT_EPOCH));
+        assert!(
+            num_rounds > 0 && num_rounds <= MAX_WINDOW_ROUNDS,
+            error::invalid_argument(EINVALID_NUM_ROUNDS),
+        );
+
+        if (!exists<MaintenanceSchedule>(@aptos_framework)) {
+            let aptos_framework = create_signer::create_signer(@aptos_framework);
+            move_to(&aptos_framework, MaintenanceSchedule { windows: vector::empty() });
+        };
+        let windows = &mut borrow_global_mut<MaintenanceSchedule>(@aptos_framework).windows;
+        // The windows of the current epoch are kept, as the validators restarting during the
+        // epoch read them again.
+        let i = 0;
+        while (i < vector::length(windows)) {
+            let window = vector::borrow(windows, i);
+            if (window.epoch < current_epoch || (window.validator == pool_address && window.epoch == epoch)) {
+                vector::swap_remove(windows, i);
+            } else {
+                i = i + 1;
+            };
+        };
+        vector::push_back(windows, MaintenanceWindow { validator: pool_address, epoch, start_round, num_rounds });
+    }
+
+    #[test_only]
+    fun setup(aptos_framework: &signer, validator: &signer) {
+        account::create_account_for_test(@aptos_framework);
+        stake::initialize_for_test(aptos_framework);
+        reconfiguration::initialize_for_test(aptos_framework);
+        let (_sk, pk, pop) = stake::generate_identity();
+        stake::initialize_test_validator(&pk, &pop, validator, 100, true, true);
+    }
+
+    #[test_only]
+    fun end_epoch() {
+        timestamp::fast_forward_seconds(1);
+        stake::end_epoch();
+        reconfiguration::reconfigure_for_test_custom();
+    }
+
+    #[test(aptos_framework = @aptos_framework, validator = @0x123)]
+    public entry fun test_announce(aptos_framework: &signer, validator: &signer) acquires MaintenanceSchedule {
+        setup(aptos_framework, validator);
+        announce(validator, @0x123, 1, 10, 5);
+        // A new window replaces the one of the same validator and epoch
+        announce(validator, @0x123, 1, 20, 5);
+        assert!(
+            borrow_global<MaintenanceSchedule>(@aptos_framework).windows == vector[
+                MaintenanceWindow { validator: @0x123, epoch: 1, start_round: 20, num_rounds: 5 },
+            ],
+            0,
+        );
+
+        // The windows of the current epoch are kept, the ones of the past epochs are removed
+        end_epoch();
+        announce(validator, @0x123, 2, 30, 5);
+        end_epoch();
+        announce(validator, @0x123, 3, 40, 5);
+        assert!(
+            borrow_global<MaintenanceSchedule>(@aptos_framework).windows == vector[
+                MaintenanceWindow { validator: @0x123, epoch: 2, start_round: 30, num_rounds: 5 },
+                MaintenanceWindow { validator: @0x123, epoch: 3, start_round: 40, num_rounds: 5 },
+            ],
+            1,
+        );
+    }
+
+    #[test(aptos_framework = @aptos_framework, validator = @0x123)]
+    #[expected_failure(abort_code = 0x10003, location = Self)]
+    public entry fun test_announce_current_epoch(
+        aptos_framework: &signer,
+        validator: &signer,
+    ) acquires MaintenanceSchedule {
+        setup(aptos_framework, validator);
+        announce(validator, @0x123, 0, 10, 5);
+    }
+
+    #[test(aptos_framework = @aptos_framework, validator = @0x123)]
+    #[expected_failure(abort_code = 0x10004, location = Self)]
+    public entry fun test_announce_too_long(
+        aptos_framework: &signer,
+        validator: &signer,
+    ) acquires MaintenanceSchedule {
+        setup(aptos_framework, validator);
+        announce(validator, @0x123, 1, 10, MAX_WINDOW_ROUNDS + 1);
+    }
+
+    #[test(aptos_framework = @aptos_framework, validator = @0x123, other = @0x234)]
+    #[expected_failure(abort_code = 0x40001, location = Self)]
+    public entry fun test_announce_not_operator(
+        aptos_framework: &signer,
+        validator: &signer,
+        other: &signer,
+    ) acquires MaintenanceSchedule {
+        setup(aptos_framework, validator);
+        announce(other, @0x123, 1, 10, 5);
+    }
+}
@@ -0,0 +1,21 @@
+spec aptos_framework::maintenance_schedule {
+    spec module {
+        pragma verify = true;
+        pragma aborts_if_is_strict;
+    }
+
+    /// Ensure the caller is the operator of a current validator, and the window is in the next
+    /// epoch and not empty or too long.
+    spec announce(
+        operator: &signer,
+        pool_address: address,
+        epoch: u64,
+        start_round: u64,
+        num_rounds: u64,
+    ) {
+        pragma verify = false; // TODO: set to false because of the loop removing the windows
+
+        aborts_if epoch != reconfiguration::current_epoch() + 1;
+        aborts_if num_rounds == 0 || num_rounds > MAX_WINDOW_ROUNDS;
+    }
+}
@@ -0,0 +1,49 @@
+/// Maintains the schedule of the state snapshots taken by the nodes. The config is stored in a
+/// Reconfiguration, and may be updated by root. It overrides the schedule in the storage config
+/// of the nodes.
+module aptos_framework::state_snapshot_config {
+    use std::error;
+
+    use aptos_framework::reconfiguration;
+    use aptos_framework::system_addresses;
+
+    struct StateSnapshotConfig has key {
+        /// Max number of versions between two snapshots
+        interval_versions: u64,
+        /// Max number of blocks between two snapshots (0 to disable)
+        interval_blocks: u64,
+        /// Max number of seconds of block time between two snapshots (0 to disable)
+        interval_secs: u64,
+    }
+
+    /// The interval in versions is 0 or larger than the nodes support
+    const EINVALID_INTERVAL_VERSIONS: u64 = 1;
+
+    /// The nodes rely on the snapshots being at most this many versions apart
+    const MAX_INTERVAL_VERSIONS: u64 = 20000;
+
+    /// This can be called by on-chain governance to update the state snapshot schedule.
+    public fun set(
+        account: &signer,
+        interval_versions: u64,
+        interval_blocks: u64,
+        interval_secs: u64,
+    ) acquires StateSnapshotConfig {
+        system_addresses::assert_aptos_framework(account);
+        assert!(
+            interval_versions > 0 && interval_versions <= MAX_INTERVAL_VERSIONS,
+            error::invalid_argument(EINVALID_INTERVAL_VERSIONS),
+        );
+
+        if (exists<StateSnapshotConfig>(@aptos_framework)) {
+            let config = borrow_global_mut<StateSnapshotConfig>(@aptos_framework);
+            config.interval_versions = interval_versions;
+            config.interval_blocks = interval_blocks;
+            config.interval_secs = interval_secs;
+        } else {
+            move_to(account, StateSnapshotConfig { interval_versions, interval_blocks, interval_secs });
+        };
+        // Need to trigger reconfiguration so validator nodes can sync on the updated configs.
+        reconfiguration::reconfigure();
+    }
+}
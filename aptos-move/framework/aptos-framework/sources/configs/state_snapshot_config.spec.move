@@ -0,0 +1,21 @@
+spec aptos_framework::state_snapshot_config {
+    spec module {
+        pragma verify = true;
+        pragma aborts_if_is_strict;
+    }
+
+    /// Ensure the caller is admin and the interval in versions is supported.
+    /// When setting now time must be later than last_reconfiguration_time.
+    spec set(account: &signer, interval_versions: u64, interval_blocks: u64, interval_secs: u64) {
+        use aptos_framework::timestamp;
+        use std::signer;
+
+        pragma verify = false; // TODO: set to false because of timeout
+
+        let addr = signer::address_of(account);
+        aborts_if !system_addresses::is_aptos_framework_address(addr);
+        aborts_if interval_versions == 0 || interval_versions > MAX_INTERVAL_VERSIONS;
+
+        requires timestamp::spec_now_microseconds() >= reconfiguration::last_reconfiguration_time();
+    }
+}
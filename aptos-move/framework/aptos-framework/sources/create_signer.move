@@ -11,7 +11,9 @@
 module aptos_framework::create_signer {
     friend aptos_framework::account;
     friend aptos_framework::aptos_account;
+    friend aptos_framework::block;
     friend aptos_framework::genesis;
+    friend aptos_framework::maintenance_schedule;
     friend aptos_framework::multisig_account;
     friend aptos_framework::object;
 
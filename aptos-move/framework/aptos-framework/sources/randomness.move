@@ -0,0 +1,175 @@
+/// This module provides access to on-chain randomness. Random values are derived from the
+/// randomness seed of the current block (provided by consensus), the hash of the current
+/// transaction and a per-transaction counter, so each call returns fresh random values.
+///
+/// Randomness is only available when the `RANDOMNESS` feature is enabled.
+///
+/// A module that reads random values must not let its callers observe the outcome in the same
+/// transaction, e.g., by only reading them in private entry functions. Otherwise, a caller could
+/// abort its own transaction whenever it doesn't like the outcome (e.g., of a lottery), and retry
+/// until it does.
+module aptos_framework::randomness {
+    use std::error;
+    use std::features;
+    use std::option::{Self, Option};
+    use std::vector;
+
+    use aptos_framework::system_addresses;
+
+    friend aptos_framework::block;
+    friend aptos_framework::genesis;
+
+    /// Randomness is not available for the current block.
+    const ERANDOMNESS_UNAVAILABLE: u64 = 1;
+    /// The range is empty (i.e., the lower bound is not smaller than the upper bound).
+    const EEMPTY_RANGE: u64 = 2;
+    /// The randomness feature is not enabled.
+    const ERANDOMNESS_DISABLED: u64 = 3;
+
+    /// The randomness seed of the current block, as provided by consensus. The
+    /// seed is none if consensus did not provide randomness for the block.
+    struct PerBlockRandomness has drop, key {
+        epoch: u64,
+        round: u64,
+        seed: Option<vector<u8>>,
+    }
+
+    /// Called in genesis to initialize the randomness seed, which is then updated by each block.
+    public(friend) fun initialize(aptos_framework: &signer) {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        move_to(aptos_framework, PerBlockRandomness {
+            epoch: 0,
+            round: 0,
+            seed: option::none(),
+        });
+    }
+
+    /// Invoked in the block prologue (when the randomness feature is enabled) to set the randomness
+    /// seed of the new block. This clears the seed of the previous block when consensus provides
+    /// none, so it cannot be reused.
+    public(friend) fun on_new_block(
+        vm: &signer,
+        epoch: u64,
+        round: u64,
+        seed_for_new_block: Option<vector<u8>>,
+    ) acquires PerBlockRandomness {
+        system_addresses::assert_vm(vm);
+        if (exists<PerBlockRandomness>(@aptos_framework)) {
+            let randomness = borrow_global_mut<PerBlockRandomness>(@aptos_framework);
+            randomness.epoch = epoch;
+            randomness.round = round;
+            randomness.seed = seed_for_new_block;
+        }
+    }
+
+    /// Returns 32 random bytes.
+    public fun bytes(): vector<u8> {
+        next_random_bytes()
+    }
+
+    /// Returns a uniformly random u64.
+    public fun u64_integer(): u64 {
+        let random_bytes = next_random_bytes();
+        let value = 0;
+        let i = 0;
+        while (i < 8) {
+            value = (value << 8) | (*vector::borrow(&random_bytes, i) as u64);
+            i = i + 1;
+        };
+        value
+    }
+
+    /// Returns a uniformly random u64 in the range [min_incl, max_excl).
+    public fun u64_range(min_incl: u64, max_excl: u64): u64 {
+        assert!(min_incl < max_excl, error::invalid_argument(EEMPTY_RANGE));
+        // The range is at most 2^64, so reducing a 256-bit value modulo the range biases the
+        // result by at most 2^-192, which is negligible.
+        let range = ((max_excl - min_incl) as u256);
+        min_incl + ((u256_integer() % range) as u64)
+    }
+
+    /// Returns a uniformly random u256.
+    fun u256_integer(): u256 {
+        let random_bytes = next_random_bytes();
+        let value = 0;
+        let i = 0;
+        while (i < 32) {
+            value = (value << 8) | (*vector::borrow(&random_bytes, i) as u256);
+            i = i + 1;
+        };
+        value
+    }
+
+    /// Returns 32 random bytes, after checking that the randomness feature is enabled. The block
+    /// prologue stops updating the seed when the feature is disabled, so a stale seed must not be
+    /// read then.
+    fun next_random_bytes(): vector<u8> {
+        assert!(features::randomness_enabled(), error::invalid_state(ERANDOMNESS_DISABLED));
+        fetch_random_bytes()
+    }
+
+    /// Returns 32 random bytes, unique to this call. Aborts with
+    /// `error::invalid_state(ERANDOMNESS_UNAVAILABLE)` if there is no seed for the current block.
+    native fun fetch_random_bytes(): vector<u8>;
+
+    #[test(aptos_framework = @aptos_framework, vm = @vm_reserved)]
+    fun test_on_new_block(aptos_framework: &signer, vm: &signer) acquires PerBlockRandomness {
+        initialize(aptos_framework);
+        on_new_block(vm, 1, 2, option::some(x"0102"));
+        assert!(
+            borrow_global<PerBlockRandomness>(@aptos_framework).seed == option::some(x"0102"),
+            0,
+        );
+
+        // The seed of the previous block is cleared
+        on_new_block(vm, 1, 3, option::none());
+        let randomness = borrow_global<PerBlockRandomness>(@aptos_framework);
+        assert!(randomness.round == 3 && option::is_none(&randomness.seed), 1);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x50002, location = aptos_framework::system_addresses)]
+    fun test_on_new_block_not_vm(aptos_framework: &signer) acquires PerBlockRandomness {
+        initialize(aptos_framework);
+        on_new_block(aptos_framework, 1, 2, option::none());
+    }
+
+    #[test_only]
+    fun enable_randomness(fx: &signer) {
+        features::change_feature_flags(fx, vector[features::get_randomness_feature()], vector[]);
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_bytes(fx: &signer) {
+        enable_randomness(fx);
+        let random_bytes = bytes();
+        assert!(vector::length(&random_bytes) == 32, 0);
+        assert!(random_bytes != bytes(), 1);
+    }
+
+    #[test(fx = @aptos_framework)]
+    #[expected_failure(abort_code = 0x30003, location = Self)]
+    fun test_bytes_disabled(fx: &signer) {
+        features::change_feature_flags(fx, vector[], vector[features::get_randomness_feature()]);
+        bytes();
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_u64_range(fx: &signer) {
+        enable_randomness(fx);
+        let i = 0;
+        while (i < 10) {
+            let value = u64_range(10, 20);
+            assert!(value >= 10 && value < 20, i);
+            i = i + 1;
+        };
+        assert!(u64_range(0, 18446744073709551615) < 18446744073709551615, 10);
+    }
+
+    #[test(fx = @aptos_framework)]
+    #[expected_failure(abort_code = 0x10002, location = Self)]
+    fun test_u64_range_empty(fx: &signer) {
+        enable_randomness(fx);
+        u64_range(20, 20);
+    }
+}
@@ -0,0 +1,38 @@
+spec aptos_framework::randomness {
+    spec initialize(aptos_framework: &signer) {
+        use std::signer;
+        let addr = signer::address_of(aptos_framework);
+        aborts_if !system_addresses::is_aptos_framework_address(addr);
+        aborts_if exists<PerBlockRandomness>(addr);
+        ensures exists<PerBlockRandomness>(addr);
+    }
+
+    spec on_new_block(vm: &signer, epoch: u64, round: u64, seed_for_new_block: Option<vector<u8>>) {
+        aborts_if !system_addresses::is_vm(vm);
+        ensures exists<PerBlockRandomness>(@aptos_framework) ==>
+            global<PerBlockRandomness>(@aptos_framework).seed == seed_for_new_block;
+    }
+
+    /// Each call returns different bytes, as the per-transaction counter is part of the derivation,
+    /// so only the length of the result is specified.
+    spec fetch_random_bytes(): vector<u8> {
+        pragma opaque;
+        ensures [abstract] len(result) == 32;
+    }
+
+    spec u64_integer(): u64 {
+        pragma verify = false;
+    }
+
+    spec u64_range(min_incl: u64, max_excl: u64): u64 {
+        pragma verify = false;
+    }
+
+    spec u256_integer(): u256 {
+        pragma verify = false;
+    }
+
+    spec next_random_bytes(): vector<u8> {
+        pragma verify = false;
+    }
+}
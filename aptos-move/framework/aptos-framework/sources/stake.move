@@ -9,7 +9,9 @@
 /// automatically renewed at expiration.
 /// 5. At any point, if the validator operator wants to update the consensus key or network/fullnode addresses, they can
 /// call stake::rotate_consensus_key and stake::update_network_and_fullnode_addresses. Similar to changes to stake, the
-/// changes to consensus key/network/fullnode addresses are only effective in the next epoch.
+/// changes to consensus key/network/fullnode addresses are only effective in the next epoch. The operator can also
+/// pre-register a standby consensus key with stake::set_standby_consensus_key, and switch to it with a single call to
+/// stake::activate_standby_consensus_key (e.g., if the current consensus key is suspected to be compromised).
 /// 6. Validator can request to unlock their stake at any time. However, their stake will only become withdrawable when
 /// their current lockup expires. This can be at most as long as the fixed lockup duration.
 /// 7. After exiting, the validator can either explicitly leave the validator set by calling stake::leave_validator_set
@@ -78,6 +80,8 @@ module aptos_framework::stake {
     const EINVALID_LOCKUP: u64 = 18;
     /// Table to store collected transaction fees for each validator already exists.
     const EFEES_TABLE_ALREADY_EXISTS: u64 = 19;
+    /// No standby consensus key has been registered for the stake pool.
+    const ENO_STANDBY_CONSENSUS_KEY: u64 = 20;
 
     /// Validator status enum. We can switch to proper enum later once Move supports it.
     const VALIDATOR_STATUS_PENDING_ACTIVE: u64 = 1;
@@ -157,6 +161,12 @@ module aptos_framework::stake {
         validator_index: u64,
     }
 
+    /// Standby consensus keys pre-registered by an operator, keyed by stake pool address. Stored in the operator's
+    /// account, as the operator may not have access to the stake pool's signer.
+    struct StandbyConsensusKeys has key {
+        keys: Table<address, vector<u8>>,
+    }
+
     /// Consensus information per validator, stored in ValidatorSet.
     struct ValidatorInfo has copy, store, drop {
         addr: address,
@@ -323,6 +333,24 @@ module aptos_framework::stake {
         )
     }
 
+    #[view]
+    /// Return the standby consensus key registered by the current operator of the stake pool at `pool_address`, if any.
+    /// This will throw an error if there's no stake pool at `pool_address`.
+    public fun get_standby_consensus_key(pool_address: address): Option<vector<u8>>
+    acquires StakePool, StandbyConsensusKeys {
+        assert_stake_pool_exists(pool_address);
+        let operator_address = borrow_global<StakePool>(pool_address).operator_address;
+        if (!exists<StandbyConsensusKeys>(operator_address)) {
+            return option::none()
+        };
+        let standby_keys = &borrow_global<StandbyConsensusKeys>(operator_address).keys;
+        if (table::contains(standby_keys, pool_address)) {
+            option::some(*table::borrow(standby_keys, pool_address))
+        } else {
+            option::none()
+        }
+    }
+
     #[view]
     /// Returns the validator's state.
     public fun get_validator_state(pool_address: address): u64 acquires ValidatorSet {
@@ -720,6 +748,67 @@ module aptos_framework::stake {
         );
     }
 
+    /// Pre-register a standby consensus key for the validator, replacing any previously registered standby key.
+    /// The standby key only becomes the validator's consensus key once activate_standby_consensus_key is called.
+    public entry fun set_standby_consensus_key(
+        operator: &signer,
+        pool_address: address,
+        standby_consensus_pubkey: vector<u8>,
+        proof_of_possession: vector<u8>,
+    ) acquires StakePool, StandbyConsensusKeys {
+        assert_stake_pool_exists(pool_address);
+        let operator_address = signer::address_of(operator);
+        assert!(
+            operator_address == borrow_global<StakePool>(pool_address).operator_address,
+            error::unauthenticated(ENOT_OPERATOR),
+        );
+
+        // Checks the public key has a valid proof-of-possession to prevent rogue-key attacks.
+        let pubkey_from_pop = &mut bls12381::public_key_from_bytes_with_pop(
+            standby_consensus_pubkey,
+            &proof_of_possession_from_bytes(proof_of_possession)
+        );
+        assert!(option::is_some(pubkey_from_pop), error::invalid_argument(EINVALID_PUBLIC_KEY));
+
+        if (!exists<StandbyConsensusKeys>(operator_address)) {
+            move_to(operator, StandbyConsensusKeys { keys: table::new() });
+        };
+        let standby_keys = &mut borrow_global_mut<StandbyConsensusKeys>(operator_address).keys;
+        table::upsert(standby_keys, pool_address, standby_consensus_pubkey);
+    }
+
+    /// Switch the validator's consensus key to its pre-registered standby key. The proof-of-possession was already
+    /// verified when the standby key was registered. Like rotate_consensus_key, this only takes effect in the next
+    /// epoch, at which point nodes that hold the standby key switch to it.
+    public entry fun activate_standby_consensus_key(
+        operator: &signer,
+        pool_address: address,
+    ) acquires StakePool, StandbyConsensusKeys, ValidatorConfig {
+        assert_stake_pool_exists(pool_address);
+        let stake_pool = borrow_global_mut<StakePool>(pool_address);
+        let operator_address = signer::address_of(operator);
+        assert!(operator_address == stake_pool.operator_address, error::unauthenticated(ENOT_OPERATOR));
+
+        assert!(exists<ValidatorConfig>(pool_address), error::not_found(EVALIDATOR_CONFIG));
+        assert!(exists<StandbyConsensusKeys>(operator_address), error::not_found(ENO_STANDBY_CONSENSUS_KEY));
+        let standby_keys = &mut borrow_global_mut<StandbyConsensusKeys>(operator_address).keys;
+        assert!(table::contains(standby_keys, pool_address), error::not_found(ENO_STANDBY_CONSENSUS_KEY));
+        let new_consensus_pubkey = table::remove(standby_keys, pool_address);
+
+        let validator_info = borrow_global_mut<ValidatorConfig>(pool_address);
+        let old_consensus_pubkey = validator_info.consensus_pubkey;
+        validator_info.consensus_pubkey = new_consensus_pubkey;
+
+        event::emit_event(
+            &mut stake_pool.rotate_consensus_key_events,
+            RotateConsensusKeyEvent {
+                pool_address,
+                old_consensus_pubkey,
+                new_consensus_pubkey,
+            },
+        );
+    }
+
     /// Update the network and full node addresses of the validator. This only takes effect in the next epoch.
     public entry fun update_network_and_fullnode_addresses(
         operator: &signer,
@@ -2244,6 +2333,87 @@ module aptos_framework::stake {
         deposit_owner_cap(validator, owner_cap);
     }
 
+    #[test(aptos_framework = @aptos_framework, validator = @0x123)]
+    public entry fun test_activate_standby_consensus_key(
+        aptos_framework: &signer,
+        validator: &signer,
+    ) acquires AllowedValidators, AptosCoinCapabilities, OwnerCapability, StakePool, StandbyConsensusKeys, ValidatorConfig, ValidatorPerformance, ValidatorSet, ValidatorFees {
+        initialize_for_test(aptos_framework);
+        let (_sk, pk, pop) = generate_identity();
+        initialize_test_validator(&pk, &pop, validator, 100, true, true);
+        let pool_address = signer::address_of(validator);
+        assert!(option::is_none(&get_standby_consensus_key(pool_address)), 0);
+
+        // Operator pre-registers a standby consensus key.
+        let (_sk_standby, pk_standby, pop_standby) = generate_identity();
+        let pk_standby_bytes = bls12381::public_key_to_bytes(&pk_standby);
+        let pop_standby_bytes = bls12381::proof_of_possession_to_bytes(&pop_standby);
+        set_standby_consensus_key(validator, pool_address, pk_standby_bytes, pop_standby_bytes);
+        assert!(get_standby_consensus_key(pool_address) == option::some(pk_standby_bytes), 1);
+        assert!(borrow_global<ValidatorConfig>(pool_address).consensus_pubkey == bls12381::public_key_to_bytes(&pk), 2);
+
+        // Activating the standby key consumes it, and the new key is active in the next epoch.
+        activate_standby_consensus_key(validator, pool_address);
+        assert!(option::is_none(&get_standby_consensus_key(pool_address)), 3);
+        assert!(borrow_global<ValidatorConfig>(pool_address).consensus_pubkey == pk_standby_bytes, 4);
+        end_epoch();
+        let validator_set = borrow_global<ValidatorSet>(@aptos_framework);
+        let validator_info = vector::borrow(&validator_set.active_validators, 0);
+        assert!(validator_info.config.consensus_pubkey == pk_standby_bytes, 5);
+    }
+
+    #[test(aptos_framework = @aptos_framework, validator = @0x123)]
+    #[expected_failure(abort_code = 0x60014, location = Self)]
+    public entry fun test_activate_standby_consensus_key_without_standby_key(
+        aptos_framework: &signer,
+        validator: &signer,
+    ) acquires AllowedValidators, AptosCoinCapabilities, OwnerCapability, StakePool, StandbyConsensusKeys, ValidatorConfig, ValidatorPerformance, ValidatorSet, ValidatorFees {
+        initialize_for_test(aptos_framework);
+        let (_sk, pk, pop) = generate_identity();
+        initialize_test_validator(&pk, &pop, validator, 100, true, true);
+        activate_standby_consensus_key(validator, signer::address_of(validator));
+    }
+
+    #[test(aptos_framework = @aptos_framework, validator = @0x123)]
+    #[expected_failure(abort_code = 0x1000B, location = Self)]
+    public entry fun test_set_standby_consensus_key_with_invalid_pop(
+        aptos_framework: &signer,
+        validator: &signer,
+    ) acquires AllowedValidators, AptosCoinCapabilities, OwnerCapability, StakePool, StandbyConsensusKeys, ValidatorConfig, ValidatorPerformance, ValidatorSet, ValidatorFees {
+        initialize_for_test(aptos_framework);
+        let (_sk, pk, pop) = generate_identity();
+        initialize_test_validator(&pk, &pop, validator, 100, true, true);
+
+        // The proof-of-possession doesn't match the standby key.
+        let (_sk_standby, pk_standby, _pop_standby) = generate_identity();
+        set_standby_consensus_key(
+            validator,
+            signer::address_of(validator),
+            bls12381::public_key_to_bytes(&pk_standby),
+            bls12381::proof_of_possession_to_bytes(&pop),
+        );
+    }
+
+    #[test(aptos_framework = @aptos_framework, validator = @0x123, other = @0x234)]
+    #[expected_failure(abort_code = 0x50009, location = Self)]
+    public entry fun test_set_standby_consensus_key_not_operator(
+        aptos_framework: &signer,
+        validator: &signer,
+        other: &signer,
+    ) acquires AllowedValidators, AptosCoinCapabilities, OwnerCapability, StakePool, StandbyConsensusKeys, ValidatorConfig, ValidatorPerformance, ValidatorSet, ValidatorFees {
+        initialize_for_test(aptos_framework);
+        let (_sk, pk, pop) = generate_identity();
+        initialize_test_validator(&pk, &pop, validator, 100, true, true);
+
+        let (_sk_standby, pk_standby, pop_standby) = generate_identity();
+        set_standby_consensus_key(
+            other,
+            signer::address_of(validator),
+            bls12381::public_key_to_bytes(&pk_standby),
+            bls12381::proof_of_possession_to_bytes(&pop_standby),
+        );
+    }
+
     #[test(aptos_framework = @aptos_framework, validator = @0x123)]
     #[expected_failure(abort_code = 0x1000A, location = Self)]
     public entry fun test_validator_cannot_join_post_genesis(
@@ -337,6 +337,15 @@ pub enum EntryFunctionCall {
         amount: u64,
     },
 
+    /// Announces a maintenance window of the validator in the next epoch, replacing the window it
+    /// already announced for that epoch (if any). This can only be called by the operator.
+    MaintenanceScheduleAnnounce {
+        pool_address: AccountAddress,
+        epoch: u64,
+        start_round: u64,
+        num_rounds: u64,
+    },
+
     /// Withdraw an `amount` of coin `CoinType` from `account` and burn it.
     ManagedCoinBurn {
         coin_type: TypeTag,
@@ -582,6 +591,13 @@ pub enum EntryFunctionCall {
         code: Vec<Vec<u8>>,
     },
 
+    /// Switch the validator's consensus key to its pre-registered standby key. The proof-of-possession was already
+    /// verified when the standby key was registered. Like rotate_consensus_key, this only takes effect in the next
+    /// epoch, at which point nodes that hold the standby key switch to it.
+    StakeActivateStandbyConsensusKey {
+        pool_address: AccountAddress,
+    },
+
     /// Add `amount` of coins from the `account` owning the StakePool.
     StakeAddStake {
         amount: u64,
@@ -645,6 +661,14 @@ pub enum EntryFunctionCall {
         new_operator: AccountAddress,
     },
 
+    /// Pre-register a standby consensus key for the validator, replacing any previously registered standby key.
+    /// The standby key only becomes the validator's consensus key once activate_standby_consensus_key is called.
+    StakeSetStandbyConsensusKey {
+        pool_address: AccountAddress,
+        standby_consensus_pubkey: Vec<u8>,
+        proof_of_possession: Vec<u8>,
+    },
+
     /// Similar to unlock_with_cap but will use ownership capability from the signing account.
     StakeUnlock {
         amount: u64,
@@ -1055,6 +1079,12 @@ impl EntryFunctionCall {
                 pool_address,
                 amount,
             } => delegation_pool_withdraw(pool_address, amount),
+            MaintenanceScheduleAnnounce {
+                pool_address,
+                epoch,
+                start_round,
+                num_rounds,
+            } => maintenance_schedule_announce(pool_address, epoch, start_round, num_rounds),
             ManagedCoinBurn { coin_type, amount } => managed_coin_burn(coin_type, amount),
             ManagedCoinInitialize {
                 coin_type,
@@ -1219,6 +1249,9 @@ impl EntryFunctionCall {
                 metadata_serialized,
                 code,
             ),
+            StakeActivateStandbyConsensusKey { pool_address } => {
+                stake_activate_standby_consensus_key(pool_address)
+            },
             StakeAddStake { amount } => stake_add_stake(amount),
             StakeIncreaseLockup {} => stake_increase_lockup(),
             StakeInitializeStakeOwner {
@@ -1249,6 +1282,15 @@ impl EntryFunctionCall {
             },
             StakeSetDelegatedVoter { new_voter } => stake_set_delegated_voter(new_voter),
             StakeSetOperator { new_operator } => stake_set_operator(new_operator),
+            StakeSetStandbyConsensusKey {
+                pool_address,
+                standby_consensus_pubkey,
+                proof_of_possession,
+            } => stake_set_standby_consensus_key(
+                pool_address,
+                standby_consensus_pubkey,
+                proof_of_possession,
+            ),
             StakeUnlock { amount } => stake_unlock(amount),
             StakeUpdateNetworkAndFullnodeAddresses {
                 pool_address,
@@ -2238,6 +2280,33 @@ pub fn delegation_pool_withdraw(pool_address: AccountAddress, amount: u64) -> Tr
     ))
 }
 
+/// Announces a maintenance window of the validator in the next epoch, replacing the window it
+/// already announced for that epoch (if any). This can only be called by the operator.
+pub fn maintenance_schedule_announce(
+    pool_address: AccountAddress,
+    epoch: u64,
+    start_round: u64,
+    num_rounds: u64,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("maintenance_schedule").to_owned(),
+        ),
+        ident_str!("announce").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&pool_address).unwrap(),
+            bcs::to_bytes(&epoch).unwrap(),
+            bcs::to_bytes(&start_round).unwrap(),
+            bcs::to_bytes(&num_rounds).unwrap(),
+        ],
+    ))
+}
+
 /// Withdraw an `amount` of coin `CoinType` from `account` and burn it.
 pub fn managed_coin_burn(coin_type: TypeTag, amount: u64) -> TransactionPayload {
     TransactionPayload::EntryFunction(EntryFunction::new(
@@ -2927,6 +2996,24 @@ pub fn resource_account_create_resource_account_and_publish_package(
     ))
 }
 
+/// Switch the validator's consensus key to its pre-registered standby key. The proof-of-possession was already
+/// verified when the standby key was registered. Like rotate_consensus_key, this only takes effect in the next
+/// epoch, at which point nodes that hold the standby key switch to it.
+pub fn stake_activate_standby_consensus_key(pool_address: AccountAddress) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("stake").to_owned(),
+        ),
+        ident_str!("activate_standby_consensus_key").to_owned(),
+        vec![],
+        vec![bcs::to_bytes(&pool_address).unwrap()],
+    ))
+}
+
 /// Add `amount` of coins from the `account` owning the StakePool.
 pub fn stake_add_stake(amount: u64) -> TransactionPayload {
     TransactionPayload::EntryFunction(EntryFunction::new(
@@ -3121,6 +3208,31 @@ pub fn stake_set_operator(new_operator: AccountAddress) -> TransactionPayload {
     ))
 }
 
+/// Pre-register a standby consensus key for the validator, replacing any previously registered standby key.
+/// The standby key only becomes the validator's consensus key once activate_standby_consensus_key is called.
+pub fn stake_set_standby_consensus_key(
+    pool_address: AccountAddress,
+    standby_consensus_pubkey: Vec<u8>,
+    proof_of_possession: Vec<u8>,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("stake").to_owned(),
+        ),
+        ident_str!("set_standby_consensus_key").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&pool_address).unwrap(),
+            bcs::to_bytes(&standby_consensus_pubkey).unwrap(),
+            bcs::to_bytes(&proof_of_possession).unwrap(),
+        ],
+    ))
+}
+
 /// Similar to unlock_with_cap but will use ownership capability from the signing account.
 pub fn stake_unlock(amount: u64) -> TransactionPayload {
     TransactionPayload::EntryFunction(EntryFunction::new(
@@ -4353,6 +4465,21 @@ mod decoder {
         }
     }
 
+    pub fn maintenance_schedule_announce(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::MaintenanceScheduleAnnounce {
+                pool_address: bcs::from_bytes(script.args().get(0)?).ok()?,
+                epoch: bcs::from_bytes(script.args().get(1)?).ok()?,
+                start_round: bcs::from_bytes(script.args().get(2)?).ok()?,
+                num_rounds: bcs::from_bytes(script.args().get(3)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
     pub fn managed_coin_burn(payload: &TransactionPayload) -> Option<EntryFunctionCall> {
         if let TransactionPayload::EntryFunction(script) = payload {
             Some(EntryFunctionCall::ManagedCoinBurn {
@@ -4748,6 +4875,18 @@ mod decoder {
         }
     }
 
+    pub fn stake_activate_standby_consensus_key(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::StakeActivateStandbyConsensusKey {
+                pool_address: bcs::from_bytes(script.args().get(0)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
     pub fn stake_add_stake(payload: &TransactionPayload) -> Option<EntryFunctionCall> {
         if let TransactionPayload::EntryFunction(script) = payload {
             Some(EntryFunctionCall::StakeAddStake {
@@ -4853,6 +4992,20 @@ mod decoder {
         }
     }
 
+    pub fn stake_set_standby_consensus_key(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::StakeSetStandbyConsensusKey {
+                pool_address: bcs::from_bytes(script.args().get(0)?).ok()?,
+                standby_consensus_pubkey: bcs::from_bytes(script.args().get(1)?).ok()?,
+                proof_of_possession: bcs::from_bytes(script.args().get(2)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
     pub fn stake_unlock(payload: &TransactionPayload) -> Option<EntryFunctionCall> {
         if let TransactionPayload::EntryFunction(script) = payload {
             Some(EntryFunctionCall::StakeUnlock {
@@ -5475,6 +5628,10 @@ static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMa
             "delegation_pool_withdraw".to_string(),
             Box::new(decoder::delegation_pool_withdraw),
         );
+        map.insert(
+            "maintenance_schedule_announce".to_string(),
+            Box::new(decoder::maintenance_schedule_announce),
+        );
         map.insert(
             "managed_coin_burn".to_string(),
             Box::new(decoder::managed_coin_burn),
@@ -5591,6 +5748,10 @@ static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMa
             "resource_account_create_resource_account_and_publish_package".to_string(),
             Box::new(decoder::resource_account_create_resource_account_and_publish_package),
         );
+        map.insert(
+            "stake_activate_standby_consensus_key".to_string(),
+            Box::new(decoder::stake_activate_standby_consensus_key),
+        );
         map.insert(
             "stake_add_stake".to_string(),
             Box::new(decoder::stake_add_stake),
@@ -5631,6 +5792,10 @@ static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMa
             "stake_set_operator".to_string(),
             Box::new(decoder::stake_set_operator),
         );
+        map.insert(
+            "stake_set_standby_consensus_key".to_string(),
+            Box::new(decoder::stake_set_standby_consensus_key),
+        );
         map.insert("stake_unlock".to_string(), Box::new(decoder::stake_unlock));
         map.insert(
             "stake_update_network_and_fullnode_addresses".to_string(),
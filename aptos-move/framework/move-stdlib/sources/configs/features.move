@@ -220,17 +220,43 @@ module std::features {
         is_enabled(BULLETPROOFS_NATIVES)
     }
 
+    /// Whether on-chain randomness is available, i.e., the block prologue sets the randomness seed of each block,
+    /// and modules can read random values through the `randomness` module.
+    /// Lifetime: transient
+    const RANDOMNESS: u64 = 25;
+
+    public fun get_randomness_feature(): u64 { RANDOMNESS }
+
+    public fun randomness_enabled(): bool acquires Features {
+        is_enabled(RANDOMNESS)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
     /// The provided signer has not a framework address.
     const EFRAMEWORK_SIGNER_NEEDED: u64 = 1;
+    /// The feature is read by the VM when it is created, and so can only be changed at an epoch boundary.
+    const EFEATURE_NOT_HOT_ACTIVATABLE: u64 = 2;
 
     /// The enabled features, represented by a bitset stored on chain.
     struct Features has key {
         features: vector<u8>,
     }
 
+    /// A change to a feature flag that takes effect within the epoch, i.e., in the first block
+    /// whose timestamp is at or after the activation time.
+    struct ScheduledFeatureActivation has copy, drop, store {
+        feature: u64,
+        enable: bool,
+        activation_time_microseconds: u64,
+    }
+
+    /// The feature flag changes that are pending activation, in the order they were scheduled.
+    struct ScheduledFeatureActivations has key {
+        activations: vector<ScheduledFeatureActivation>,
+    }
+
     /// Function to enable and disable features. Can only be called by a signer of @std.
     public fun change_feature_flags(framework: &signer, enable: vector<u64>, disable: vector<u64>)
     acquires Features {
@@ -247,6 +273,91 @@ module std::features {
         });
     }
 
+    /// Function to schedule features to be enabled and disabled at the given time, without waiting for
+    /// an epoch boundary. Only features that are hot activatable (see `is_hot_activatable`) can be
+    /// scheduled. Can only be called by a signer of @std.
+    public fun schedule_feature_activations(
+        framework: &signer,
+        enable: vector<u64>,
+        disable: vector<u64>,
+        activation_time_microseconds: u64,
+    ) acquires ScheduledFeatureActivations {
+        assert!(signer::address_of(framework) == @std, error::permission_denied(EFRAMEWORK_SIGNER_NEEDED));
+        if (!exists<ScheduledFeatureActivations>(@std)) {
+            move_to<ScheduledFeatureActivations>(framework, ScheduledFeatureActivations{activations: vector[]})
+        };
+        let activations = &mut borrow_global_mut<ScheduledFeatureActivations>(@std).activations;
+        vector::for_each_ref(&enable, |feature| {
+            schedule(activations, *feature, true, activation_time_microseconds);
+        });
+        vector::for_each_ref(&disable, |feature| {
+            schedule(activations, *feature, false, activation_time_microseconds);
+        });
+    }
+
+    /// Function to cancel all feature flag changes that are pending activation. Can only be called by a
+    /// signer of @std.
+    public fun cancel_scheduled_activations(framework: &signer) acquires ScheduledFeatureActivations {
+        assert!(signer::address_of(framework) == @std, error::permission_denied(EFRAMEWORK_SIGNER_NEEDED));
+        if (exists<ScheduledFeatureActivations>(@std)) {
+            borrow_global_mut<ScheduledFeatureActivations>(@std).activations = vector[];
+        }
+    }
+
+    /// Applies the feature flag changes whose activation time has been reached. The framework calls this
+    /// at the start of every block (with the block timestamp), so that all validators activate the
+    /// changes at the same point of the chain. Can only be called by a signer of @std.
+    public fun apply_scheduled_activations(framework: &signer, now_microseconds: u64)
+    acquires Features, ScheduledFeatureActivations {
+        assert!(signer::address_of(framework) == @std, error::permission_denied(EFRAMEWORK_SIGNER_NEEDED));
+        if (!exists<ScheduledFeatureActivations>(@std) ||
+            vector::is_empty(&borrow_global<ScheduledFeatureActivations>(@std).activations)) {
+            return
+        };
+        if (!exists<Features>(@std)) {
+            move_to<Features>(framework, Features{features: vector[]})
+        };
+        let activations = &mut borrow_global_mut<ScheduledFeatureActivations>(@std).activations;
+        let features = &mut borrow_global_mut<Features>(@std).features;
+        let i = 0;
+        while (i < vector::length(activations)) {
+            let ScheduledFeatureActivation { feature, enable, activation_time_microseconds } =
+                *vector::borrow(activations, i);
+            if (activation_time_microseconds <= now_microseconds) {
+                set(features, feature, enable);
+                vector::remove(activations, i);
+            } else {
+                i = i + 1;
+            }
+        }
+    }
+
+    /// Whether the feature is only read by the framework (and not by the VM when it is created), and so can
+    /// be changed within an epoch. This must stay aligned with `FeatureFlag::is_hot_activatable` in Rust.
+    ///
+    /// COLLECT_AND_DISTRIBUTE_GAS_FEES is excluded: the fees collected in the epoch are only moved to the
+    /// stake pools while it is enabled, so disabling it mid-epoch would strand them.
+    fun is_hot_activatable(feature: u64): bool {
+        feature == MULTISIG_ACCOUNTS ||
+        feature == DELEGATION_POOLS ||
+        feature == PERIODICAL_REWARD_RATE_DECREASE ||
+        feature == PARTIAL_GOVERNANCE_VOTING ||
+        feature == DELEGATION_POOL_PARTIAL_GOVERNANCE_VOTING ||
+        feature == FEE_PAYER_ENABLED ||
+        feature == APTOS_UNIQUE_IDENTIFIERS
+    }
+
+    /// Helper to schedule a change to a hot activatable feature flag.
+    fun schedule(
+        activations: &mut vector<ScheduledFeatureActivation>,
+        feature: u64,
+        enable: bool,
+        activation_time_microseconds: u64,
+    ) {
+        assert!(is_hot_activatable(feature), error::invalid_argument(EFEATURE_NOT_HOT_ACTIVATABLE));
+        vector::push_back(activations, ScheduledFeatureActivation { feature, enable, activation_time_microseconds });
+    }
+
     /// Check whether the feature is enabled.
     fun is_enabled(feature: u64): bool acquires Features {
         exists<Features>(@std) &&
@@ -305,4 +416,48 @@ module std::features {
         assert!(is_enabled(17), 3);
         assert!(is_enabled(23), 4);
     }
+
+    #[test(fx = @std)]
+    fun test_scheduled_feature_activations(fx: signer) acquires Features, ScheduledFeatureActivations {
+        change_feature_flags(&fx, vector[MULTISIG_ACCOUNTS], vector[]);
+        schedule_feature_activations(&fx, vector[DELEGATION_POOLS], vector[MULTISIG_ACCOUNTS], 100);
+        schedule_feature_activations(&fx, vector[FEE_PAYER_ENABLED], vector[], 200);
+
+        // Nothing is activated before the activation time
+        apply_scheduled_activations(&fx, 99);
+        assert!(is_enabled(MULTISIG_ACCOUNTS), 1);
+        assert!(!is_enabled(DELEGATION_POOLS), 2);
+
+        // Only the due changes are activated
+        apply_scheduled_activations(&fx, 150);
+        assert!(!is_enabled(MULTISIG_ACCOUNTS), 3);
+        assert!(is_enabled(DELEGATION_POOLS), 4);
+        assert!(!is_enabled(FEE_PAYER_ENABLED), 5);
+        assert!(vector::length(&borrow_global<ScheduledFeatureActivations>(@std).activations) == 1, 6);
+
+        // Cancelled changes are never activated
+        cancel_scheduled_activations(&fx);
+        apply_scheduled_activations(&fx, 200);
+        assert!(!is_enabled(FEE_PAYER_ENABLED), 7);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10002, location = Self)]
+    fun test_schedule_non_hot_activatable_feature(fx: signer) acquires ScheduledFeatureActivations {
+        schedule_feature_activations(&fx, vector[RESOURCE_GROUPS], vector[], 100);
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 0x10002, location = Self)]
+    fun test_schedule_gas_fees_collection(fx: signer) acquires ScheduledFeatureActivations {
+        schedule_feature_activations(&fx, vector[], vector[COLLECT_AND_DISTRIBUTE_GAS_FEES], 100);
+    }
+
+    #[test(fx = @std, other = @0x123)]
+    #[expected_failure(abort_code = 0x50001, location = Self)]
+    fun test_apply_scheduled_activations_non_framework(fx: signer, other: signer)
+    acquires Features, ScheduledFeatureActivations {
+        schedule_feature_activations(&fx, vector[DELEGATION_POOLS], vector[], 100);
+        apply_scheduled_activations(&other, 100);
+    }
 }
@@ -29,6 +29,32 @@ spec std::features {
         aborts_if signer::address_of(framework) != @std;
     }
 
+    spec schedule_feature_activations(
+        framework: &signer,
+        enable: vector<u64>,
+        disable: vector<u64>,
+        activation_time_microseconds: u64,
+    ) {
+        pragma opaque;
+        pragma verify = false;
+        modifies global<ScheduledFeatureActivations>(@std);
+        aborts_if signer::address_of(framework) != @std;
+    }
+
+    spec cancel_scheduled_activations(framework: &signer) {
+        pragma opaque;
+        modifies global<ScheduledFeatureActivations>(@std);
+        aborts_if signer::address_of(framework) != @std;
+    }
+
+    spec apply_scheduled_activations(framework: &signer, now_microseconds: u64) {
+        pragma opaque;
+        pragma verify = false;
+        modifies global<Features>(@std);
+        modifies global<ScheduledFeatureActivations>(@std);
+        aborts_if signer::address_of(framework) != @std;
+    }
+
     spec is_enabled(feature: u64): bool {
         pragma opaque;
         aborts_if [abstract] false;
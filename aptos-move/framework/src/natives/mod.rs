@@ -13,6 +13,7 @@ pub mod event;
 pub mod hash;
 mod helpers;
 pub mod object;
+pub mod randomness;
 pub mod state_storage;
 pub mod string_utils;
 pub mod transaction_context;
@@ -74,6 +75,7 @@ pub fn all_natives(
     add_natives_from_module!("code", code::make_all(builder));
     add_natives_from_module!("event", event::make_all(builder));
     add_natives_from_module!("state_storage", state_storage::make_all(builder));
+    add_natives_from_module!("randomness", randomness::make_all(builder));
     add_natives_from_module!("aggregator", aggregator::make_all(builder));
     add_natives_from_module!("aggregator_factory", aggregator_factory::make_all(builder));
     add_natives_from_module!("object", object::make_all(builder));
@@ -0,0 +1,145 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::HashValue;
+use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
+use aptos_native_interface::{
+    RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeError, SafeNativeResult,
+};
+use aptos_types::vm_status::StatusCode;
+use better_any::{Tid, TidAble};
+use move_binary_format::errors::PartialVMError;
+use move_vm_runtime::native_functions::NativeFunction;
+use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
+use smallvec::{smallvec, SmallVec};
+use std::collections::VecDeque;
+
+/// Abort code when no randomness seed is available for the current block.
+/// The same as `error::invalid_state(ERANDOMNESS_UNAVAILABLE)` in Move.
+const ERANDOMNESS_UNAVAILABLE: u64 = 0x03_0001;
+
+/// The domain separator used when deriving random values from the seed
+const RANDOMNESS_DOMAIN_SEPARATOR: &[u8] = b"APTOS::Randomness";
+
+/// Ability to resolve the randomness seed of the current block (provided by consensus).
+pub trait RandomnessResolver {
+    fn get_randomness_seed(&self) -> anyhow::Result<Option<Vec<u8>>>;
+}
+
+/// The source of the randomness seed used by the natives
+enum RandomnessSeedSource<'a> {
+    /// The on-chain seed of the current block
+    Resolver(&'a dyn RandomnessResolver),
+    /// A fresh random seed, if the current block has a seed (i.e., for simulation)
+    Simulation(&'a dyn RandomnessResolver),
+    /// A fixed seed (e.g., for unit tests)
+    Fixed(Option<Vec<u8>>),
+}
+
+/// Exposes per-block randomness to native functions. Random values are derived from the seed,
+/// the hash of the transaction and a counter, so each call within a transaction (and each
+/// transaction within a block) gets different values.
+#[derive(Tid)]
+pub struct NativeRandomnessContext<'a> {
+    txn_hash: Vec<u8>,
+    seed_source: RandomnessSeedSource<'a>,
+    /// This is the number of random values generated during the execution of this transaction
+    randomness_counter: u64,
+}
+
+impl<'a> NativeRandomnessContext<'a> {
+    /// Creates a randomness context that uses the on-chain seed of the current block
+    pub fn new(txn_hash: Vec<u8>, resolver: &'a dyn RandomnessResolver) -> Self {
+        Self {
+            txn_hash,
+            seed_source: RandomnessSeedSource::Resolver(resolver),
+            randomness_counter: 0,
+        }
+    }
+
+    /// Creates a randomness context for simulation, which uses a fresh random seed instead of
+    /// the on-chain seed (so that simulations cannot predict the outcome of randomness). The seed
+    /// is only available if the current block has one, so simulations still fail when the
+    /// execution would fail due to missing randomness.
+    pub fn new_for_simulation(txn_hash: Vec<u8>, resolver: &'a dyn RandomnessResolver) -> Self {
+        Self {
+            txn_hash,
+            seed_source: RandomnessSeedSource::Simulation(resolver),
+            randomness_counter: 0,
+        }
+    }
+
+    /// Creates a randomness context that uses the given seed instead of the on-chain seed. This
+    /// is used for unit tests, so that tests are deterministic.
+    pub fn new_with_seed(txn_hash: Vec<u8>, seed: Option<Vec<u8>>) -> Self {
+        Self {
+            txn_hash,
+            seed_source: RandomnessSeedSource::Fixed(seed),
+            randomness_counter: 0,
+        }
+    }
+
+    fn get_seed(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        match &self.seed_source {
+            RandomnessSeedSource::Resolver(resolver) => resolver.get_randomness_seed(),
+            RandomnessSeedSource::Simulation(resolver) => Ok(resolver
+                .get_randomness_seed()?
+                .map(|_| HashValue::random().to_vec())),
+            RandomnessSeedSource::Fixed(seed) => Ok(seed.clone()),
+        }
+    }
+}
+
+/***************************************************************************************************
+ * native fun fetch_random_bytes
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_fetch_random_bytes(
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(RANDOMNESS_FETCH_RANDOM_BYTES_BASE)?;
+
+    let randomness_context = context
+        .extensions_mut()
+        .get_mut::<NativeRandomnessContext>();
+    let seed = randomness_context.get_seed().map_err(|err| {
+        PartialVMError::new(StatusCode::VM_EXTENSION_ERROR)
+            .with_message(format!("Failed to get the randomness seed: {}", err))
+    })?;
+    let seed = match seed {
+        Some(seed) => seed,
+        None => {
+            return Err(SafeNativeError::Abort {
+                abort_code: ERANDOMNESS_UNAVAILABLE,
+            })
+        },
+    };
+    randomness_context.randomness_counter += 1;
+
+    let mut input = RANDOMNESS_DOMAIN_SEPARATOR.to_vec();
+    input.extend(seed);
+    input.extend(&randomness_context.txn_hash);
+    input.extend(randomness_context.randomness_counter.to_le_bytes());
+    let random_bytes = HashValue::sha3_256_of(&input).to_vec();
+
+    Ok(smallvec![Value::vector_u8(random_bytes)])
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+pub fn make_all(
+    builder: &SafeNativeBuilder,
+) -> impl Iterator<Item = (String, NativeFunction)> + '_ {
+    let natives = [(
+        "fetch_random_bytes",
+        native_fetch_random_bytes as RawSafeNative,
+    )];
+
+    builder.make_named_natives(natives)
+}
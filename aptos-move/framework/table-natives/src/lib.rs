@@ -17,8 +17,12 @@ use aptos_native_interface::{
 use better_any::{Tid, TidAble};
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::{
-    account_address::AccountAddress, effects::Op, gas_algebra::NumBytes, identifier::Identifier,
-    value::MoveTypeLayout, vm_status::StatusCode,
+    account_address::AccountAddress,
+    effects::Op,
+    gas_algebra::{NumArgs, NumBytes},
+    identifier::Identifier,
+    value::MoveTypeLayout,
+    vm_status::StatusCode,
 };
 // ===========================================================================================
 // Public Data Structures and Constants
@@ -238,6 +242,16 @@ fn charge_load_cost(
     context.charge(COMMON_LOAD_BASE_LEGACY)?;
 
     match loaded {
+        Some(Some(num_bytes)) if context.gas_feature_version() >= 13 => {
+            // Items spanning multiple pages pay for each page beyond the first one
+            let page_size = u64::from(context.eval_gas(COMMON_LOAD_PAGE_SIZE)).max(1);
+            let num_extra_pages = NumArgs::new(u64::from(num_bytes).saturating_sub(1) / page_size);
+            context.charge(
+                COMMON_LOAD_BASE_NEW
+                    + COMMON_LOAD_PER_BYTE * num_bytes
+                    + COMMON_LOAD_PER_EXTRA_PAGE * num_extra_pages,
+            )
+        },
         Some(Some(num_bytes)) => {
             context.charge(COMMON_LOAD_BASE_NEW + COMMON_LOAD_PER_BYTE * num_bytes)
         },
@@ -0,0 +1,393 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reloads a whitelisted subset of the node config while the node is running, either
+//! on request (with the `config/reload` admin command) or whenever the config file
+//! changes. The reloadable fields are:
+//!   - the log level (`logger.level`),
+//!   - the state sync concurrency (`state_sync.data_streaming_service.max_concurrent_*`),
+//!   - the mempool capacity (`mempool.capacity*`),
+//!   - the API rate limits (`api.quota.tiers`).
+//!
+//! Changes to any other field are reported, but only take effect after a restart.
+//! Every applied change is logged and appended to an audit log.
+
+use anyhow::anyhow;
+use aptos_api::quota::Quota;
+use aptos_config::config::NodeConfig;
+use aptos_data_streaming_service::streaming_service::StreamConcurrencyLimits;
+use aptos_infallible::{duration_since_epoch, Mutex};
+use aptos_inspection_service::admin::AdminCommands;
+use aptos_logger::{info, warn, LoggerFilterUpdater};
+use aptos_mempool::MempoolCapacityUpdater;
+use futures::FutureExt;
+use serde::Serialize;
+use std::{
+    fmt::{self, Debug, Display},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// The name of the admin command that reloads the node config, i.e., POST /admin/config/reload
+const CONFIG_RELOAD_ADMIN_COMMAND: &str = "config/reload";
+
+// The triggers of the reloads, as recorded in the audit log
+const ADMIN_COMMAND_TRIGGER: &str = "admin_command";
+const FILE_WATCHER_TRIGGER: &str = "file_watcher";
+
+/// The handles through which the reloadable fields are applied to the running
+/// components. Fields of the components that aren't running can't be reloaded.
+#[derive(Clone, Default)]
+pub struct ReloadHandles {
+    pub logger_filter_updater: Option<LoggerFilterUpdater>,
+    pub stream_concurrency_limits: Option<StreamConcurrencyLimits>,
+    pub mempool_capacity_updater: Option<MempoolCapacityUpdater>,
+    pub api_quota: Option<Quota>,
+}
+
+/// A change of a reloadable field that was applied to the node
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct AppliedChange {
+    pub field: &'static str,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// The outcome of a config reload
+#[derive(Debug, Default)]
+pub struct ReloadOutcome {
+    /// The changes that were applied to the node
+    pub applied: Vec<AppliedChange>,
+    /// The sections of the config with changes that require a restart
+    pub restart_required: Vec<String>,
+}
+
+impl Display for ReloadOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.applied.is_empty() {
+            writeln!(f, "No reloadable changes were found")?;
+        }
+        for change in &self.applied {
+            writeln!(
+                f,
+                "Applied {}: {} -> {}",
+                change.field, change.old_value, change.new_value
+            )?;
+        }
+        if !self.restart_required.is_empty() {
+            writeln!(
+                f,
+                "Changes to these sections require a restart: {}",
+                self.restart_required.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// An entry of the audit log
+#[derive(Serialize)]
+struct AuditLogEntry<'a> {
+    timestamp_secs: u64,
+    trigger: &'a str,
+    #[serde(flatten)]
+    change: &'a AppliedChange,
+}
+
+/// Reloads the node config file, and applies the changes to the reloadable fields
+pub struct ConfigReloader {
+    config_path: PathBuf,
+    audit_log_path: PathBuf,
+    handles: ReloadHandles,
+    // The config that is currently applied (as loaded from the file)
+    current_config: Mutex<NodeConfig>,
+}
+
+impl ConfigReloader {
+    pub fn new(config_path: PathBuf, node_config: NodeConfig, handles: ReloadHandles) -> Self {
+        let audit_log_path = node_config
+            .config_reload
+            .audit_log_path(node_config.get_data_dir());
+        Self {
+            config_path,
+            audit_log_path,
+            handles,
+            current_config: Mutex::new(node_config),
+        }
+    }
+
+    /// Reloads the config file, applies the changes to the reloadable fields and
+    /// records them in the audit log
+    pub fn reload(&self, trigger: &str) -> anyhow::Result<ReloadOutcome> {
+        let new_config = NodeConfig::load_from_path(&self.config_path).map_err(|error| {
+            anyhow!(
+                "Failed to load the node config {:?}: {:?}",
+                self.config_path,
+                error
+            )
+        })?;
+        let outcome = self.apply(new_config);
+        self.audit(trigger, &outcome);
+        Ok(outcome)
+    }
+
+    /// Applies the changes to the reloadable fields, and returns the outcome
+    pub fn apply(&self, new_config: NodeConfig) -> ReloadOutcome {
+        let mut current_config = self.current_config.lock();
+        let mut applied = vec![];
+
+        if let Some(logger_filter_updater) = &self.handles.logger_filter_updater {
+            if update_field(
+                &mut applied,
+                "logger.level",
+                &mut current_config.logger.level,
+                new_config.logger.level,
+            ) {
+                logger_filter_updater.set_level(current_config.logger.level);
+            }
+        }
+
+        if let Some(stream_concurrency_limits) = &self.handles.stream_concurrency_limits {
+            let current = &mut current_config.state_sync.data_streaming_service;
+            let new = &new_config.state_sync.data_streaming_service;
+            let changed = update_field(
+                &mut applied,
+                "state_sync.data_streaming_service.max_concurrent_requests",
+                &mut current.max_concurrent_requests,
+                new.max_concurrent_requests,
+            ) | update_field(
+                &mut applied,
+                "state_sync.data_streaming_service.max_concurrent_state_requests",
+                &mut current.max_concurrent_state_requests,
+                new.max_concurrent_state_requests,
+            );
+            if changed {
+                stream_concurrency_limits.update(current);
+            }
+        }
+
+        if let Some(mempool_capacity_updater) = &self.handles.mempool_capacity_updater {
+            let current = &mut current_config.mempool;
+            let new = &new_config.mempool;
+            let changed = update_field(
+                &mut applied,
+                "mempool.capacity",
+                &mut current.capacity,
+                new.capacity,
+            ) | update_field(
+                &mut applied,
+                "mempool.capacity_bytes",
+                &mut current.capacity_bytes,
+                new.capacity_bytes,
+            ) | update_field(
+                &mut applied,
+                "mempool.capacity_per_user",
+                &mut current.capacity_per_user,
+                new.capacity_per_user,
+            );
+            if changed {
+                mempool_capacity_updater.set_capacity(current);
+            }
+        }
+
+        if let Some(api_quota) = &self.handles.api_quota {
+            if update_field(
+                &mut applied,
+                "api.quota.tiers",
+                &mut current_config.api.quota.tiers,
+                new_config.api.quota.tiers.clone(),
+            ) {
+                api_quota.update_tiers(&current_config.api.quota);
+            }
+        }
+
+        ReloadOutcome {
+            applied,
+            restart_required: changed_sections(&current_config, &new_config),
+        }
+    }
+
+    /// Logs the outcome of a reload, and appends the applied changes to the audit log
+    fn audit(&self, trigger: &str, outcome: &ReloadOutcome) {
+        for change in &outcome.applied {
+            info!(
+                "Reloaded {} ({}): {} -> {}",
+                change.field, trigger, change.old_value, change.new_value
+            );
+        }
+        if !outcome.restart_required.is_empty() {
+            warn!(
+                "The node config has changes that require a restart: {}",
+                outcome.restart_required.join(", ")
+            );
+        }
+        if let Err(error) = append_to_audit_log(&self.audit_log_path, trigger, &outcome.applied) {
+            warn!(
+                "Failed to write the config reload audit log {:?}: {:?}",
+                self.audit_log_path, error
+            );
+        }
+    }
+}
+
+/// Copies the new value of a field if it changed, and records the change.
+/// Returns true iff the field changed.
+fn update_field<T: Debug + PartialEq>(
+    applied: &mut Vec<AppliedChange>,
+    field: &'static str,
+    current: &mut T,
+    new: T,
+) -> bool {
+    if *current == new {
+        return false;
+    }
+    applied.push(AppliedChange {
+        field,
+        old_value: format!("{:?}", current),
+        new_value: format!("{:?}", new),
+    });
+    *current = new;
+    true
+}
+
+/// Returns the top-level sections (e.g., "consensus") that differ between the configs
+fn changed_sections(current_config: &NodeConfig, new_config: &NodeConfig) -> Vec<String> {
+    macro_rules! changed_sections {
+        ($($section:ident),*) => {
+            [$((
+                stringify!($section),
+                current_config.$section != new_config.$section,
+            )),*]
+        };
+    }
+    let mut sections: Vec<String> = changed_sections!(
+        api,
+        base,
+        config_reload,
+        consensus,
+        consensus_observer,
+        execution,
+        failpoints,
+        full_node_networks,
+        indexer,
+        indexer_grpc,
+        inspection_service,
+        logger,
+        mempool,
+        peer_monitoring_service,
+        resource_budget,
+        state_sync,
+        storage,
+        validator_network
+    )
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(section, _)| section.to_string())
+    .collect();
+
+    // Catch the changes to any sections missing above
+    if sections.is_empty() && current_config != new_config {
+        sections.push("unknown".into());
+    }
+    sections
+}
+
+/// Appends the applied changes to the audit log (one JSON entry per line)
+fn append_to_audit_log(
+    audit_log_path: &Path,
+    trigger: &str,
+    applied: &[AppliedChange],
+) -> anyhow::Result<()> {
+    if applied.is_empty() {
+        return Ok(());
+    }
+    let timestamp_secs = duration_since_epoch().as_secs();
+    let mut audit_log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(audit_log_path)?;
+    for change in applied {
+        let entry = AuditLogEntry {
+            timestamp_secs,
+            trigger,
+            change,
+        };
+        writeln!(audit_log, "{}", serde_json::to_string(&entry)?)?;
+    }
+    Ok(())
+}
+
+/// Registers the config reload admin command, and starts watching the config
+/// file (if enabled). The config can't be reloaded if the node wasn't started
+/// from a config file (e.g., in test mode).
+pub fn start_config_reloader(
+    node_config: &NodeConfig,
+    config_path: Option<PathBuf>,
+    handles: ReloadHandles,
+    admin_commands: &AdminCommands,
+) {
+    let config_path = match config_path {
+        Some(config_path) => config_path,
+        None => {
+            info!("The node wasn't started from a config file, so it can't be reloaded");
+            return;
+        },
+    };
+    let reloader = Arc::new(ConfigReloader::new(
+        config_path,
+        node_config.clone(),
+        handles,
+    ));
+
+    // Register the admin command, i.e., POST /admin/config/reload
+    admin_commands.register(CONFIG_RELOAD_ADMIN_COMMAND, {
+        let reloader = reloader.clone();
+        Arc::new(move |_params| {
+            let reloader = reloader.clone();
+            async move {
+                tokio::task::spawn_blocking(move || reloader.reload(ADMIN_COMMAND_TRIGGER))
+                    .await
+                    .map_err(|error| format!("Failed to reload the node config: {:?}", error))?
+                    .map(|outcome| outcome.to_string())
+                    .map_err(|error| error.to_string())
+            }
+            .boxed()
+        })
+    });
+
+    // Watch the config file for changes
+    let reload_config = &node_config.config_reload;
+    if reload_config.watch_config_file {
+        let watch_interval = Duration::from_millis(reload_config.watch_interval_ms);
+        thread::Builder::new()
+            .name("config-watcher".into())
+            .spawn(move || watch_config_file(reloader, watch_interval))
+            .expect("Failed to start the node config watcher");
+    }
+}
+
+/// Reloads the config whenever the modification time of the config file changes
+fn watch_config_file(reloader: Arc<ConfigReloader>, watch_interval: Duration) {
+    let mut last_modified = modified_time(&reloader.config_path);
+    loop {
+        thread::sleep(watch_interval);
+        let modified = modified_time(&reloader.config_path);
+        if modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+        if let Err(error) = reloader.reload(FILE_WATCHER_TRIGGER) {
+            warn!("Failed to reload the changed node config: {:?}", error);
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,249 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Registers the per-subsystem health checks that are aggregated by the readiness
+//! endpoint of the inspection service (i.e., `/health/ready`). The thresholds of
+//! the checks are configured in `inspection_service.health_checks`.
+//!
+//! The checks may be probed concurrently (e.g., by several load balancers), so
+//! their results don't depend on when (or how often) they were probed before.
+
+use aptos_config::config::{HealthChecksConfig, NodeConfig};
+use aptos_infallible::{duration_since_epoch, Mutex};
+use aptos_inspection_service::health::{HealthCheck, HealthCheckResult, HealthChecks};
+use aptos_storage_interface::DbReader;
+use futures::FutureExt;
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// The names of the health checks, as reported by the readiness endpoint
+const API_CHECK: &str = "api";
+const CONSENSUS_CHECK: &str = "consensus";
+const MEMPOOL_CHECK: &str = "mempool";
+const STATE_SYNC_CHECK: &str = "state_sync";
+const STORAGE_CHECK: &str = "storage";
+
+// The storage API whose latency is checked
+const SAVE_TRANSACTIONS_API: &str = "save_transactions";
+
+// The min duration over which the average latency of the storage writes is computed
+pub(crate) const STORAGE_LATENCY_WINDOW: Duration = Duration::from_secs(10);
+
+/// Registers the health checks of the subsystems running on the node
+pub fn register_health_checks(
+    node_config: &NodeConfig,
+    db_reader: Arc<dyn DbReader>,
+    health_checks: &HealthChecks,
+) {
+    let config = node_config.inspection_service.health_checks.clone();
+
+    // Consensus only runs on validators
+    if node_config.base.role.is_validator() {
+        health_checks.register(CONSENSUS_CHECK, consensus_check(db_reader.clone(), &config));
+    }
+    health_checks.register(STATE_SYNC_CHECK, state_sync_check(db_reader, &config));
+    health_checks.register(
+        MEMPOOL_CHECK,
+        mempool_check(node_config.mempool.capacity, &config),
+    );
+    health_checks.register(STORAGE_CHECK, storage_check(&config));
+    if node_config.api.enabled {
+        health_checks.register(API_CHECK, api_check(node_config.api.address, &config));
+    }
+}
+
+/// Checks that a block was committed recently (i.e., consensus is live). Every block
+/// commits a new ledger info, so the delay is measured from the latest one's timestamp.
+fn consensus_check(db_reader: Arc<dyn DbReader>, config: &HealthChecksConfig) -> HealthCheck {
+    let max_commit_delay = Duration::from_secs(config.max_consensus_commit_delay_secs);
+    Arc::new(move || {
+        let result = match db_reader.get_latest_ledger_info() {
+            Ok(ledger_info) => {
+                let ledger_info = ledger_info.ledger_info();
+                let commit_timestamp = Duration::from_micros(ledger_info.timestamp_usecs());
+                let commit_delay = duration_since_epoch().saturating_sub(commit_timestamp);
+                let message = format!(
+                    "The latest committed version is {} (committed {:?} ago)",
+                    ledger_info.version(),
+                    commit_delay
+                );
+                if commit_delay <= max_commit_delay {
+                    HealthCheckResult::healthy(message)
+                } else {
+                    HealthCheckResult::unhealthy(message)
+                }
+            },
+            Err(error) => HealthCheckResult::unhealthy(format!(
+                "Failed to read the latest ledger info: {:?}",
+                error
+            )),
+        };
+        async move { result }.boxed()
+    })
+}
+
+/// Checks that the latest synced ledger timestamp is close to the wall clock
+fn state_sync_check(db_reader: Arc<dyn DbReader>, config: &HealthChecksConfig) -> HealthCheck {
+    let max_lag = Duration::from_secs(config.max_state_sync_lag_secs);
+    Arc::new(move || {
+        let result = match db_reader.get_latest_ledger_info() {
+            Ok(ledger_info) => {
+                let ledger_timestamp =
+                    Duration::from_micros(ledger_info.ledger_info().timestamp_usecs());
+                let lag = duration_since_epoch().saturating_sub(ledger_timestamp);
+                let message = format!("The synced ledger is {:?} behind the wall clock", lag);
+                if lag <= max_lag {
+                    HealthCheckResult::healthy(message)
+                } else {
+                    HealthCheckResult::unhealthy(message)
+                }
+            },
+            Err(error) => HealthCheckResult::unhealthy(format!(
+                "Failed to read the latest ledger info: {:?}",
+                error
+            )),
+        };
+        async move { result }.boxed()
+    })
+}
+
+/// Checks that mempool isn't (close to) full
+fn mempool_check(capacity: usize, config: &HealthChecksConfig) -> HealthCheck {
+    let max_utilization_percent = config.max_mempool_utilization_percent;
+    Arc::new(move || {
+        let size = aptos_mempool::counters::CORE_MEMPOOL_INDEX_SIZE
+            .with_label_values(&[aptos_mempool::counters::SYSTEM_TTL_INDEX_LABEL])
+            .get()
+            .max(0) as u64;
+        let utilization_percent = size * 100 / (capacity.max(1) as u64);
+        let message = format!(
+            "Mempool holds {} transactions ({}% of its capacity)",
+            size, utilization_percent
+        );
+        let result = if utilization_percent <= max_utilization_percent {
+            HealthCheckResult::healthy(message)
+        } else {
+            HealthCheckResult::unhealthy(message)
+        };
+        async move { result }.boxed()
+    })
+}
+
+/// The latency samples of the storage writes (i.e., the sum and count of the latency
+/// histogram) at some point in time
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct LatencySnapshot {
+    pub time: Instant,
+    pub sample_sum: f64,
+    pub sample_count: u64,
+}
+
+impl LatencySnapshot {
+    fn now() -> Self {
+        let histogram = aptos_db::metrics::API_LATENCY_SECONDS
+            .with_label_values(&[SAVE_TRANSACTIONS_API, "Ok"]);
+        Self {
+            time: Instant::now(),
+            sample_sum: histogram.get_sample_sum(),
+            sample_count: histogram.get_sample_count(),
+        }
+    }
+}
+
+/// Computes the average latency of the storage writes since a base snapshot that is rotated
+/// once per window. Probes only read the base (unless it's rotated), so concurrent (or
+/// frequent) probes all compute the average over (at least) the same window, instead of
+/// over the time since the previous probe.
+pub(crate) struct StorageLatencyWindow {
+    // The snapshot the average is computed from
+    base: LatencySnapshot,
+    // The snapshot that becomes the base once it's a window old
+    next_base: LatencySnapshot,
+}
+
+impl StorageLatencyWindow {
+    pub fn new(snapshot: LatencySnapshot) -> Self {
+        Self {
+            base: snapshot,
+            next_base: snapshot,
+        }
+    }
+
+    /// Returns the number of storage writes since the base snapshot, their average latency,
+    /// and the duration they were written in
+    pub fn observe(&mut self, snapshot: LatencySnapshot) -> (u64, Duration, Duration) {
+        if snapshot.time.saturating_duration_since(self.next_base.time) >= STORAGE_LATENCY_WINDOW {
+            self.base = self.next_base;
+            self.next_base = snapshot;
+        }
+
+        let num_writes = snapshot.sample_count.saturating_sub(self.base.sample_count);
+        let average_latency = if num_writes == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(
+                (snapshot.sample_sum - self.base.sample_sum).max(0.0) / num_writes as f64,
+            )
+        };
+        let window = snapshot.time.saturating_duration_since(self.base.time);
+        (num_writes, average_latency, window)
+    }
+}
+
+/// Checks the average latency of the recent storage writes (see `StorageLatencyWindow`)
+fn storage_check(config: &HealthChecksConfig) -> HealthCheck {
+    let max_latency = Duration::from_millis(config.max_storage_commit_latency_ms);
+    let latency_window = StorageLatencyWindow::new(LatencySnapshot::now());
+    let latency_window = Arc::new(Mutex::new(latency_window));
+    Arc::new(move || {
+        let (num_writes, average_latency, window) =
+            latency_window.lock().observe(LatencySnapshot::now());
+        let result = if num_writes == 0 {
+            HealthCheckResult::healthy(format!("No storage writes in the last {:?}", window))
+        } else {
+            let message = format!(
+                "The average latency of {} storage writes in the last {:?} was {:?}",
+                num_writes, window, average_latency
+            );
+            if average_latency <= max_latency {
+                HealthCheckResult::healthy(message)
+            } else {
+                HealthCheckResult::unhealthy(message)
+            }
+        };
+        async move { result }.boxed()
+    })
+}
+
+/// Checks that the REST API responds to health checks in time
+fn api_check(api_address: SocketAddr, config: &HealthChecksConfig) -> HealthCheck {
+    // The API may listen on all interfaces, but is checked through localhost
+    let mut api_address = api_address;
+    if api_address.ip().is_unspecified() {
+        api_address.set_ip(Ipv4Addr::LOCALHOST.into());
+    }
+    let url = format!("http://{}/v1/-/healthy", api_address);
+    let timeout = Duration::from_millis(config.api_timeout_ms);
+    let client = reqwest::Client::new();
+    Arc::new(move || {
+        let request = client.get(&url).timeout(timeout).send();
+        async move {
+            match request.await {
+                Ok(response) if response.status().is_success() => {
+                    HealthCheckResult::healthy("The API is responsive")
+                },
+                Ok(response) => HealthCheckResult::unhealthy(format!(
+                    "The API responded with status {}",
+                    response.status()
+                )),
+                Err(error) => {
+                    HealthCheckResult::unhealthy(format!("The API is unresponsive: {}", error))
+                },
+            }
+        }
+        .boxed()
+    })
+}
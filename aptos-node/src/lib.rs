@@ -4,9 +4,12 @@
 
 #![forbid(unsafe_code)]
 
+mod config_reload;
+mod health_checks;
 mod indexer;
 mod logger;
 mod network;
+mod resource_budget;
 mod services;
 mod state_sync;
 mod storage;
@@ -19,10 +22,11 @@ use anyhow::anyhow;
 use aptos_api::bootstrap as bootstrap_api;
 use aptos_build_info::build_information;
 use aptos_config::config::{merge_node_config, NodeConfig, PersistableConfig};
+use aptos_crypto::ed25519::Ed25519PrivateKey;
 use aptos_framework::ReleaseBundle;
 use aptos_logger::{prelude::*, telemetry_log_writer::TelemetryLog, Level, LoggerFilterUpdater};
 use aptos_state_sync_driver::driver_factory::StateSyncRuntimes;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{chain_id::ChainId, waypoint::Waypoint};
 use clap::Parser;
 use futures::channel::mpsc;
 use hex::{FromHex, FromHexError};
@@ -30,7 +34,8 @@ use rand::{rngs::StdRng, SeedableRng};
 use std::{
     fs,
     io::{Read, Write},
-    path::PathBuf,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -150,7 +155,8 @@ impl AptosNodeArgs {
             });
 
             // Start the node
-            start(config, None, true).expect("Node should start correctly");
+            start_with_config_path(config, Some(config_path), None, true)
+                .expect("Node should start correctly");
         };
     }
 }
@@ -183,6 +189,17 @@ pub fn start(
     config: NodeConfig,
     log_file: Option<PathBuf>,
     create_global_rayon_pool: bool,
+) -> anyhow::Result<()> {
+    start_with_config_path(config, None, log_file, create_global_rayon_pool)
+}
+
+/// Start an Aptos node from the given config file. The whitelisted fields of
+/// the config can then be reloaded from the file while the node is running.
+pub fn start_with_config_path(
+    config: NodeConfig,
+    config_path: Option<PathBuf>,
+    log_file: Option<PathBuf>,
+    create_global_rayon_pool: bool,
 ) -> anyhow::Result<()> {
     // Setup panic handler
     aptos_crash_handler::setup_panic_handler();
@@ -216,8 +233,12 @@ pub fn start(
     }
 
     // Set up the node environment and start it
-    let _node_handle =
-        setup_environment_and_start_node(config, remote_log_receiver, Some(logger_filter_update))?;
+    let _node_handle = setup_environment_and_start_node(
+        config,
+        config_path,
+        remote_log_receiver,
+        Some(logger_filter_update),
+    )?;
     let term = Arc::new(AtomicBool::new(false));
     while !term.load(Ordering::Acquire) {
         thread::park();
@@ -275,18 +296,7 @@ where
             })))
             .with_randomize_first_validator_ports(random_ports);
         let (root_key, _genesis, genesis_waypoint, validators) = builder.build(rng)?;
-
-        // Write the mint key to disk
-        let serialized_keys = bcs::to_bytes(&root_key)?;
-        let mut key_file = fs::File::create(&aptos_root_key_path)?;
-        key_file.write_all(&serialized_keys)?;
-
-        // Build a waypoint file so that clients / docker can grab it easily
-        let waypoint_file_path = test_dir.join("waypoint.txt");
-        Write::write_all(
-            &mut fs::File::create(waypoint_file_path)?,
-            genesis_waypoint.to_string().as_bytes(),
-        )?;
+        write_mint_key_and_waypoint(&test_dir, &root_key, &genesis_waypoint)?;
 
         // Return the validator config
         validators[0].config.clone()
@@ -319,6 +329,12 @@ where
         "\tMetrics endpoint: http://{}:{}/metrics",
         &config.inspection_service.address, &config.inspection_service.port
     );
+    if config.consensus.enable_time_travel {
+        println!(
+            "\tTime travel endpoints: http://127.0.0.1:{}/admin/{{advance_time,force_epoch_change,mine_blocks}}",
+            &config.inspection_service.port
+        );
+    }
     println!(
         "\tAptosnet fullnode network endpoint: {}",
         &config.full_node_networks[0].listen_address
@@ -336,6 +352,92 @@ where
     start(config, Some(log_file), false)
 }
 
+/// Creates the genesis and configs of a local test network with the given number
+/// of validators, and returns the config paths of the validators. If the network
+/// already exists in the test directory, the existing configs are reused. The first
+/// validator serves on the default ports, and the other validators on random ports.
+pub fn setup_test_network<R>(
+    test_dir: &Path,
+    num_validators: NonZeroUsize,
+    indexer_grpc_port: Option<u16>,
+    framework: &ReleaseBundle,
+    rng: R,
+) -> anyhow::Result<Vec<PathBuf>>
+where
+    R: rand::RngCore + rand::CryptoRng,
+{
+    // The validator builder puts each node in a directory named after its index
+    let config_paths: Vec<PathBuf> = (0..num_validators.get())
+        .map(|index| test_dir.join(index.to_string()).join("node.yaml"))
+        .collect();
+
+    // If there's already a network, use it. Otherwise create a new one.
+    if config_paths[0].exists() {
+        let num_existing_validators = (0..)
+            .take_while(|index: &usize| test_dir.join(index.to_string()).join("node.yaml").exists())
+            .count();
+        if num_existing_validators != num_validators.get() {
+            return Err(anyhow!(
+                "The network in {:?} has {} validators, but {} were requested",
+                test_dir,
+                num_existing_validators,
+                num_validators
+            ));
+        }
+        return Ok(config_paths);
+    }
+
+    // Use the single node test config as the template, but keep the chain's time in
+    // sync across validators (i.e., time travel is only supported by single nodes).
+    let mut node_config = create_single_node_test_config(None, None, false)?;
+    node_config.consensus.enable_time_travel = false;
+    node_config.inspection_service.expose_admin_commands = false;
+
+    // Build genesis and the validator nodes
+    let builder = aptos_genesis::builder::Builder::new(test_dir, framework.clone())?
+        .with_num_validators(num_validators)
+        .with_init_config(Some(Arc::new(move |index, config, _| {
+            *config = node_config.clone();
+
+            // Only the first validator serves the indexer stream
+            if let (0, Some(indexer_grpc_port)) = (index, indexer_grpc_port) {
+                config.indexer_grpc.enabled = true;
+                config.indexer_grpc.address = Some(format!("0.0.0.0:{}", indexer_grpc_port));
+            }
+        })))
+        .with_init_genesis_config(Some(Arc::new(|genesis_config| {
+            genesis_config.allow_new_validators = true;
+            genesis_config.epoch_duration_secs = EPOCH_LENGTH_SECS;
+            genesis_config.recurring_lockup_duration_secs = 7200;
+        })))
+        .with_randomize_first_validator_ports(false);
+    let (root_key, _genesis, genesis_waypoint, _validators) = builder.build(rng)?;
+    write_mint_key_and_waypoint(test_dir, &root_key, &genesis_waypoint)?;
+
+    Ok(config_paths)
+}
+
+/// Writes the mint (i.e., root) key and the genesis waypoint of a test network
+/// to the test directory, so that clients / docker can grab them easily.
+fn write_mint_key_and_waypoint(
+    test_dir: &Path,
+    root_key: &Ed25519PrivateKey,
+    genesis_waypoint: &Waypoint,
+) -> anyhow::Result<()> {
+    // Write the mint key to disk
+    let serialized_keys = bcs::to_bytes(root_key)?;
+    let mut key_file = fs::File::create(test_dir.join("mint.key"))?;
+    key_file.write_all(&serialized_keys)?;
+
+    // Build a waypoint file
+    let waypoint_file_path = test_dir.join("waypoint.txt");
+    Write::write_all(
+        &mut fs::File::create(waypoint_file_path)?,
+        genesis_waypoint.to_string().as_bytes(),
+    )?;
+    Ok(())
+}
+
 /// Creates a single node test config, with a few config tweaks to reduce
 /// the overhead of running the node on a local machine.
 fn create_single_node_test_config(
@@ -376,6 +478,11 @@ fn create_single_node_test_config(
         .num_workers_for_remote_batches = 1;
     node_config.consensus.quorum_store_poll_time_ms = 1000;
 
+    // Let developers move the local chain through time (using the admin commands
+    // served by the inspection service), e.g., to test time-locked contracts.
+    node_config.consensus.enable_time_travel = true;
+    node_config.inspection_service.expose_admin_commands = true;
+
     node_config.execution.concurrency_level = 1;
     node_config.execution.num_proof_reading_threads = 1;
     node_config.execution.paranoid_hot_potato_verification = false;
@@ -458,19 +565,38 @@ fn create_single_node_test_config(
 /// Initializes the node environment and starts the node
 pub fn setup_environment_and_start_node(
     mut node_config: NodeConfig,
+    config_path: Option<PathBuf>,
     remote_log_rx: Option<mpsc::Receiver<TelemetryLog>>,
     logger_filter_update_job: Option<LoggerFilterUpdater>,
 ) -> anyhow::Result<AptosHandle> {
     // Log the node config at node startup
     info!("Using node config {:?}", &node_config);
 
+    // Keep the config as loaded (the services below may adjust it), to diff reloads against
+    let loaded_node_config = node_config.clone();
+    let logger_filter_updater = logger_filter_update_job.clone();
+
     // Start the node inspection service
     let peers_and_metadata = network::create_peers_and_metadata(&node_config);
-    services::start_node_inspection_service(&node_config, peers_and_metadata.clone());
+    let (admin_commands, health_checks) =
+        services::start_node_inspection_service(&node_config, peers_and_metadata.clone());
+
+    // Track the RocksDB block caches (if the resource budget is enforced)
+    if node_config.resource_budget.enabled {
+        aptos_db::block_cache::enable_tracking();
+    }
 
     // Set up the storage database and any RocksDB checkpoints
-    let (aptos_db, db_rw, backup_service, genesis_waypoint) =
-        storage::initialize_database_and_checkpoints(&mut node_config)?;
+    let (aptos_db, db_rw, backup_service, genesis_waypoint, state_snapshot_scheduler) =
+        storage::initialize_database_and_checkpoints(&mut node_config, &admin_commands)?;
+
+    // Allow the log levels of modules to be changed at runtime
+    if let Some(logger_filter_updater) = &logger_filter_updater {
+        logger::register_log_level_admin_commands(logger_filter_updater.clone(), &admin_commands);
+    }
+
+    // Register the health checks served by the readiness endpoint
+    health_checks::register_health_checks(&node_config, db_rw.reader.clone(), &health_checks);
 
     // Set the Aptos VM configurations
     utils::set_aptos_vm_configurations(&node_config);
@@ -496,10 +622,22 @@ pub fn setup_environment_and_start_node(
         consensus_reconfig_subscription,
     ) = state_sync::create_event_subscription_service(&node_config, &db_rw);
 
+    // Get the epoch change notifier (so that components can subscribe after the
+    // event subscription service is handed to state sync).
+    let epoch_change_notifier = event_subscription_service.epoch_change_notifier();
+
+    // Apply the on-chain state snapshot schedule (if any) on every reconfiguration
+    storage::start_state_snapshot_schedule_updater(
+        &node_config,
+        state_snapshot_scheduler,
+        &epoch_change_notifier,
+    );
+
     // Set up the networks and gather the application network handles
     let (
         network_runtimes,
         consensus_network_interfaces,
+        consensus_observer_network_interfaces,
         mempool_network_interfaces,
         peer_monitoring_service_network_interfaces,
         storage_service_network_interfaces,
@@ -508,6 +646,7 @@ pub fn setup_environment_and_start_node(
         chain_id,
         peers_and_metadata.clone(),
         &mut event_subscription_service,
+        &admin_commands,
     );
 
     // Start the peer monitoring service
@@ -515,24 +654,40 @@ pub fn setup_environment_and_start_node(
         &node_config,
         peer_monitoring_service_network_interfaces,
         db_rw.reader.clone(),
+        &admin_commands,
     );
 
-    // Start state sync and get the notification endpoints for mempool and consensus
-    let (state_sync_runtimes, mempool_listener, consensus_notifier) =
-        state_sync::start_state_sync_and_get_notification_handles(
-            &node_config,
-            storage_service_network_interfaces,
-            genesis_waypoint,
-            event_subscription_service,
-            db_rw.clone(),
-        )?;
+    // Start state sync and get the notification endpoints for mempool, consensus and the API
+    let (
+        state_sync_runtimes,
+        mempool_listener,
+        consensus_notifier,
+        commit_notifications,
+        stream_concurrency_limits,
+    ) = state_sync::start_state_sync_and_get_notification_handles(
+        &node_config,
+        storage_service_network_interfaces,
+        genesis_waypoint,
+        event_subscription_service,
+        db_rw.clone(),
+    )?;
+
+    // Load the transaction filter policy (if enabled)
+    let transaction_filter = services::start_transaction_filter(&node_config)?;
 
     // Bootstrap the API and indexer
-    let (mempool_client_receiver, api_runtime, indexer_runtime, indexer_grpc_runtime) =
-        services::bootstrap_api_and_indexer(&node_config, aptos_db, chain_id)?;
+    let (mempool_client_receiver, api_runtime, indexer_runtime, indexer_grpc_runtime, api_quota) =
+        services::bootstrap_api_and_indexer(
+            &node_config,
+            aptos_db,
+            chain_id,
+            commit_notifications,
+            transaction_filter.clone(),
+            &epoch_change_notifier,
+        )?;
 
     // Create mempool and get the consensus to mempool sender
-    let (mempool_runtime, consensus_to_mempool_sender) =
+    let (mempool_runtime, consensus_to_mempool_sender, mempool_capacity_updater) =
         services::start_mempool_runtime_and_get_consensus_sender(
             &mut node_config,
             &db_rw,
@@ -541,25 +696,79 @@ pub fn setup_environment_and_start_node(
             mempool_listener,
             mempool_client_receiver,
             peers_and_metadata,
+            transaction_filter,
         );
 
-    // Create the consensus runtime (this blocks on state sync first)
-    let consensus_runtime = consensus_network_interfaces.map(|consensus_network_interfaces| {
+    // Create the consensus runtime (this blocks on state sync first). Validators
+    // run consensus, and fullnodes may run the consensus observer instead.
+    let consensus_runtime = if let Some(consensus_network_interfaces) = consensus_network_interfaces
+    {
         // Wait until state sync has been initialized
         debug!("Waiting until state sync is initialized!");
         state_sync_runtimes.block_until_initialized();
         debug!("State sync initialization complete.");
 
         // Initialize and start consensus
-        services::start_consensus_runtime(
+        Some(services::start_consensus_runtime(
             &mut node_config,
             db_rw,
             consensus_reconfig_subscription,
             consensus_network_interfaces,
+            consensus_observer_network_interfaces,
             consensus_notifier,
             consensus_to_mempool_sender,
-        )
-    });
+            &admin_commands,
+        ))
+    } else if node_config.consensus_observer.observer_enabled {
+        consensus_observer_network_interfaces.map(|consensus_observer_network_interfaces| {
+            // Wait until state sync has been initialized
+            debug!("Waiting until state sync is initialized!");
+            state_sync_runtimes.block_until_initialized();
+            debug!("State sync initialization complete.");
+
+            // Initialize and start the consensus observer
+            services::start_consensus_observer_runtime(
+                &node_config,
+                db_rw,
+                consensus_reconfig_subscription,
+                consensus_observer_network_interfaces,
+                consensus_notifier,
+                consensus_to_mempool_sender,
+            )
+        })
+    } else {
+        None
+    };
+
+    // Enforce the node-wide resource budget (if enabled)
+    let mut budgeted_subsystems: Vec<Box<dyn resource_budget::BudgetedSubsystem>> = vec![
+        Box::new(resource_budget::MempoolBudget::new(mempool_capacity_updater.clone())),
+        Box::new(resource_budget::StateSyncBudget::new(
+            stream_concurrency_limits.clone(),
+            node_config
+                .state_sync
+                .storage_service
+                .max_network_chunk_bytes,
+        )),
+        Box::new(resource_budget::StorageBudget),
+    ];
+    if node_config.base.role.is_validator() {
+        budgeted_subsystems.push(Box::new(resource_budget::QuorumStoreBudget));
+    }
+    resource_budget::start_resource_budget(&node_config.resource_budget, budgeted_subsystems);
+
+    // Allow the whitelisted fields of the config to be reloaded while the node is running
+    config_reload::start_config_reloader(
+        &loaded_node_config,
+        config_path,
+        config_reload::ReloadHandles {
+            logger_filter_updater,
+            stream_concurrency_limits: Some(stream_concurrency_limits),
+            mempool_capacity_updater: Some(mempool_capacity_updater),
+            api_quota,
+        },
+        &admin_commands,
+    );
 
     Ok(AptosHandle {
         _api_runtime: api_runtime,
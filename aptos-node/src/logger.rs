@@ -1,17 +1,29 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::mpsc::Receiver;
+use crate::{mpsc::Receiver, services::parse_u64_param};
 use aptos_build_info::build_information;
 use aptos_config::config::NodeConfig;
+use aptos_inspection_service::admin::{AdminCommandResult, AdminCommands};
 use aptos_logger::{
-    aptos_logger::FileWriter, info, telemetry_log_writer::TelemetryLog, LoggerFilterUpdater,
+    aptos_logger::FileWriter, info, telemetry_log_writer::TelemetryLog, LevelFilter,
+    LoggerFilterUpdater,
+};
+use futures::{channel::mpsc, FutureExt};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use futures::channel::mpsc;
-use std::path::PathBuf;
 
 const TELEMETRY_LOG_INGEST_BUFFER_SIZE: usize = 128;
 
+// The names of the admin commands that control the log levels of modules
+const LOG_LEVELS_ADMIN_COMMAND: &str = "logger/levels";
+const RESET_LOG_LEVEL_ADMIN_COMMAND: &str = "logger/reset_level";
+const SET_LOG_LEVEL_ADMIN_COMMAND: &str = "logger/set_level";
+
 // Simple macro to help print out feature configurations
 macro_rules! log_feature_info {
     ($($feature:literal),*) => {
@@ -63,6 +75,111 @@ pub fn create_logger(
     (remote_log_receiver, logger_filter_updater)
 }
 
+/// Registers the admin commands that control the log levels of modules at runtime, e.g.:
+/// POST /admin/logger/set_level?module=aptos_state_sync::*&level=debug&duration_secs=600
+/// POST /admin/logger/reset_level?module=aptos_state_sync::*
+/// POST /admin/logger/levels
+pub fn register_log_level_admin_commands(
+    logger_filter_updater: LoggerFilterUpdater,
+    admin_commands: &AdminCommands,
+) {
+    admin_commands.register(SET_LOG_LEVEL_ADMIN_COMMAND, {
+        let logger_filter_updater = logger_filter_updater.clone();
+        Arc::new(move |params| {
+            let result = set_log_level(&logger_filter_updater, params);
+            async move { result }.boxed()
+        })
+    });
+    admin_commands.register(RESET_LOG_LEVEL_ADMIN_COMMAND, {
+        let logger_filter_updater = logger_filter_updater.clone();
+        Arc::new(move |params| {
+            let result = reset_log_level(&logger_filter_updater, params);
+            async move { result }.boxed()
+        })
+    });
+    admin_commands.register(
+        LOG_LEVELS_ADMIN_COMMAND,
+        Arc::new(move |_params| {
+            let result = get_log_levels(&logger_filter_updater);
+            async move { result }.boxed()
+        }),
+    );
+}
+
+/// Overrides the log level of the modules matching the pattern, given the admin
+/// command parameters. The override is reverted after the duration (if any).
+fn set_log_level(
+    logger_filter_updater: &LoggerFilterUpdater,
+    params: HashMap<String, String>,
+) -> AdminCommandResult {
+    let pattern = params
+        .get("module")
+        .filter(|pattern| !pattern.is_empty())
+        .ok_or("Missing parameter: module")?;
+    let level = params
+        .get("level")
+        .ok_or("Missing parameter: level")?
+        .parse::<LevelFilter>()
+        .map_err(|_| "Invalid level (expected off, error, warn, info, debug or trace)")?;
+    let duration = params
+        .contains_key("duration_secs")
+        .then(|| parse_u64_param(&params, "duration_secs", None))
+        .transpose()?
+        .map(Duration::from_secs);
+
+    logger_filter_updater.set_module_level(pattern, level, duration);
+    let message = match duration {
+        Some(duration) => format!(
+            "Set the log level of {} to {:?} for {:?}",
+            pattern, level, duration
+        ),
+        None => format!("Set the log level of {} to {:?}", pattern, level),
+    };
+    info!("{}", message);
+    Ok(message)
+}
+
+/// Resets the log level of the given module pattern (or of all patterns, if no
+/// module is given), given the admin command parameters
+fn reset_log_level(
+    logger_filter_updater: &LoggerFilterUpdater,
+    params: HashMap<String, String>,
+) -> AdminCommandResult {
+    let pattern = params.get("module").map(String::as_str);
+    let removed = logger_filter_updater.reset_module_levels(pattern);
+    if removed.is_empty() {
+        return Err(format!("No log level overrides found for {:?}", pattern));
+    }
+    let patterns: Vec<_> = removed
+        .into_iter()
+        .map(|module_level| module_level.pattern)
+        .collect();
+    let message = format!("Reset the log levels of {}", patterns.join(", "));
+    info!("{}", message);
+    Ok(message)
+}
+
+/// Returns the module log level overrides (and their remaining durations)
+fn get_log_levels(logger_filter_updater: &LoggerFilterUpdater) -> AdminCommandResult {
+    let module_levels = logger_filter_updater.module_levels();
+    if module_levels.is_empty() {
+        return Ok("No log level overrides are set".into());
+    }
+    let module_levels: Vec<_> = module_levels
+        .into_iter()
+        .map(|module_level| match module_level.expires_at {
+            Some(expires_at) => format!(
+                "{}={:?} (reverts in {:?})",
+                module_level.pattern,
+                module_level.level,
+                expires_at.saturating_duration_since(Instant::now())
+            ),
+            None => format!("{}={:?}", module_level.pattern, module_level.level),
+        })
+        .collect();
+    Ok(module_levels.join("\n"))
+}
+
 /// Logs the node config and build information
 fn log_config_and_build_information(node_config: &NodeConfig) {
     // Log the build information
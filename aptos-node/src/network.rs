@@ -1,13 +1,18 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::services::parse_u64_param;
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
-    config::{NetworkConfig, NodeConfig},
+    config::{NetworkConfig, NodeConfig, ProtocolBandwidthLimitConfig},
     network_id::NetworkId,
 };
-use aptos_consensus::network_interface::{ConsensusMsg, DIRECT_SEND, RPC};
+use aptos_consensus::{
+    consensus_observer::network_message::ConsensusObserverMessage,
+    network_interface::{ConsensusMsg, DIRECT_SEND, RPC},
+};
 use aptos_event_notifications::EventSubscriptionService;
+use aptos_inspection_service::admin::{AdminCommandResult, AdminCommands};
 use aptos_logger::debug;
 use aptos_mempool::network::MempoolSyncMsg;
 use aptos_network::{
@@ -15,6 +20,7 @@ use aptos_network::{
         interface::{NetworkClient, NetworkServiceEvents},
         storage::PeersAndMetadata,
     },
+    peer::bandwidth::ProtocolBandwidthLimits,
     protocols::network::{
         NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
         NetworkServiceConfig,
@@ -26,10 +32,20 @@ use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
 use aptos_storage_service_types::StorageServiceMessage;
 use aptos_time_service::TimeService;
 use aptos_types::chain_id::ChainId;
+use futures::FutureExt;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+    sync::Arc,
+};
 use tokio::runtime::Runtime;
 
+// The names of the admin commands that control the protocol bandwidth limits of the networks
+const BANDWIDTH_LIMITS_ADMIN_COMMAND: &str = "network/bandwidth_limits";
+const REMOVE_BANDWIDTH_LIMIT_ADMIN_COMMAND: &str = "network/remove_bandwidth_limit";
+const SET_BANDWIDTH_LIMIT_ADMIN_COMMAND: &str = "network/set_bandwidth_limit";
+
 /// A simple struct that holds both the network client
 /// and receiving interfaces for an application.
 pub struct ApplicationNetworkInterfaces<T> {
@@ -63,6 +79,26 @@ pub fn consensus_network_configuration(node_config: &NodeConfig) -> NetworkAppli
     NetworkApplicationConfig::new(network_client_config, network_service_config)
 }
 
+/// Returns the network application config for the consensus observer client and server
+pub fn consensus_observer_network_configuration(
+    node_config: &NodeConfig,
+) -> NetworkApplicationConfig {
+    let direct_send_protocols = vec![ProtocolId::ConsensusObserver];
+    let rpc_protocols = vec![ProtocolId::ConsensusObserverRpc];
+    let max_network_channel_size = node_config.consensus_observer.max_network_channel_size as usize;
+
+    let network_client_config =
+        NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
+    let network_service_config = NetworkServiceConfig::new(
+        direct_send_protocols,
+        rpc_protocols,
+        aptos_channel::Config::new(max_network_channel_size)
+            .queue_style(QueueStyle::FIFO)
+            .counters(&aptos_consensus::counters::PENDING_CONSENSUS_OBSERVER_NETWORK_EVENTS),
+    );
+    NetworkApplicationConfig::new(network_client_config, network_service_config)
+}
+
 /// Returns the network application config for the mempool client and service
 pub fn mempool_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
     let direct_send_protocols = vec![ProtocolId::MempoolDirectSend];
@@ -104,7 +140,10 @@ pub fn peer_monitoring_network_configuration(node_config: &NodeConfig) -> Networ
 /// Returns the network application config for the storage service client and server
 pub fn storage_service_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
     let direct_send_protocols = vec![]; // The storage service does not use direct send
-    let rpc_protocols = vec![ProtocolId::StorageServiceRpc];
+    let rpc_protocols = vec![
+        ProtocolId::StorageServiceRpcZstd, // Preferred, as responses are compressed by the network
+        ProtocolId::StorageServiceRpc,
+    ];
     let max_network_channel_size = node_config
         .state_sync
         .storage_service
@@ -151,15 +190,18 @@ pub fn create_peers_and_metadata(node_config: &NodeConfig) -> Arc<PeersAndMetada
     PeersAndMetadata::new(&network_ids)
 }
 
-/// Sets up all networks and returns the appropriate application network interfaces
+/// Sets up all networks and returns the appropriate application network interfaces.
+/// Also registers the admin commands that adjust the bandwidth limits of the networks.
 pub fn setup_networks_and_get_interfaces(
     node_config: &NodeConfig,
     chain_id: ChainId,
     peers_and_metadata: Arc<PeersAndMetadata>,
     event_subscription_service: &mut EventSubscriptionService,
+    admin_commands: &AdminCommands,
 ) -> (
     Vec<Runtime>,
     Option<ApplicationNetworkInterfaces<ConsensusMsg>>,
+    Option<ApplicationNetworkInterfaces<ConsensusObserverMessage>>,
     ApplicationNetworkInterfaces<MempoolSyncMsg>,
     ApplicationNetworkInterfaces<PeerMonitoringServiceMessage>,
     ApplicationNetworkInterfaces<StorageServiceMessage>,
@@ -170,9 +212,11 @@ pub fn setup_networks_and_get_interfaces(
     // Create each network and register the application handles
     let mut network_runtimes = vec![];
     let mut consensus_network_handle = None;
+    let mut consensus_observer_network_handles = vec![];
     let mut mempool_network_handles = vec![];
     let mut peer_monitoring_service_network_handles = vec![];
     let mut storage_service_network_handles = vec![];
+    let mut protocol_bandwidth_limits = BTreeMap::new();
     for network_config in network_configs.into_iter() {
         // Create a network runtime for the config
         let runtime = create_network_runtime(&network_config);
@@ -206,6 +250,22 @@ pub fn setup_networks_and_get_interfaces(
             }
         }
 
+        // Register the consensus observer (both client and server) with the VFN network.
+        // Validators publish the ordered blocks and VFNs observe them.
+        if network_id.is_vfn_network()
+            && node_config
+                .consensus_observer
+                .observer_or_publisher_enabled()
+        {
+            let consensus_observer_network_handle = register_client_and_service_with_network(
+                &mut network_builder,
+                network_id,
+                &network_config,
+                consensus_observer_network_configuration(node_config),
+            );
+            consensus_observer_network_handles.push(consensus_observer_network_handle);
+        }
+
         // Register mempool (both client and server) with the network
         let mempool_network_handle = register_client_and_service_with_network(
             &mut network_builder,
@@ -233,6 +293,9 @@ pub fn setup_networks_and_get_interfaces(
         );
         storage_service_network_handles.push(storage_service_network_handle);
 
+        // Keep the bandwidth limits of the network (to adjust them at runtime)
+        protocol_bandwidth_limits.insert(network_id, network_builder.protocol_bandwidth_limits());
+
         // Build and start the network on the runtime
         network_builder.build(runtime.handle().clone());
         network_builder.start();
@@ -243,15 +306,20 @@ pub fn setup_networks_and_get_interfaces(
         );
     }
 
+    // Register the admin commands for the bandwidth limits
+    register_bandwidth_limit_admin_commands(protocol_bandwidth_limits, admin_commands);
+
     // Transform all network handles into application interfaces
     let (
         consensus_interfaces,
+        consensus_observer_interfaces,
         mempool_interfaces,
         peer_monitoring_service_interfaces,
         storage_service_interfaces,
     ) = transform_network_handles_into_interfaces(
         node_config,
         consensus_network_handle,
+        consensus_observer_network_handles,
         mempool_network_handles,
         peer_monitoring_service_network_handles,
         storage_service_network_handles,
@@ -261,12 +329,117 @@ pub fn setup_networks_and_get_interfaces(
     (
         network_runtimes,
         consensus_interfaces,
+        consensus_observer_interfaces,
         mempool_interfaces,
         peer_monitoring_service_interfaces,
         storage_service_interfaces,
     )
 }
 
+/// Registers the admin commands that control the protocol bandwidth limits of the
+/// networks at runtime, e.g.:
+/// POST /admin/network/set_bandwidth_limit?network=public&protocol=StorageServiceRpc&rate=1048576&size=2097152
+/// POST /admin/network/remove_bandwidth_limit?network=public&protocol=StorageServiceRpc
+/// POST /admin/network/bandwidth_limits
+fn register_bandwidth_limit_admin_commands(
+    protocol_bandwidth_limits: BTreeMap<NetworkId, ProtocolBandwidthLimits>,
+    admin_commands: &AdminCommands,
+) {
+    let protocol_bandwidth_limits = Arc::new(protocol_bandwidth_limits);
+    admin_commands.register(SET_BANDWIDTH_LIMIT_ADMIN_COMMAND, {
+        let protocol_bandwidth_limits = protocol_bandwidth_limits.clone();
+        Arc::new(move |params| {
+            let result = set_bandwidth_limit(&protocol_bandwidth_limits, params);
+            async move { result }.boxed()
+        })
+    });
+    admin_commands.register(REMOVE_BANDWIDTH_LIMIT_ADMIN_COMMAND, {
+        let protocol_bandwidth_limits = protocol_bandwidth_limits.clone();
+        Arc::new(move |params| {
+            let result = remove_bandwidth_limit(&protocol_bandwidth_limits, params);
+            async move { result }.boxed()
+        })
+    });
+    admin_commands.register(
+        BANDWIDTH_LIMITS_ADMIN_COMMAND,
+        Arc::new(move |_params| {
+            let result = get_bandwidth_limits(&protocol_bandwidth_limits);
+            async move { result }.boxed()
+        }),
+    );
+}
+
+/// Sets the bandwidth limit of a protocol, given the admin command parameters
+fn set_bandwidth_limit(
+    protocol_bandwidth_limits: &BTreeMap<NetworkId, ProtocolBandwidthLimits>,
+    params: HashMap<String, String>,
+) -> AdminCommandResult {
+    let (network_id, limits) = get_network_bandwidth_limits(protocol_bandwidth_limits, &params)?;
+    let limit = ProtocolBandwidthLimitConfig {
+        protocol: params
+            .get("protocol")
+            .ok_or("Missing parameter: protocol")?
+            .clone(),
+        byte_bucket_rate: parse_u64_param(&params, "rate", None)? as usize,
+        byte_bucket_size: parse_u64_param(&params, "size", None)? as usize,
+    };
+    limits
+        .set_limit(limit.clone())
+        .map_err(|error| format!("Failed to set the bandwidth limit: {}", error))?;
+    Ok(format!(
+        "Set the bandwidth limit of network {}: {:?}",
+        network_id, limit
+    ))
+}
+
+/// Removes the bandwidth limit of a protocol, given the admin command parameters
+fn remove_bandwidth_limit(
+    protocol_bandwidth_limits: &BTreeMap<NetworkId, ProtocolBandwidthLimits>,
+    params: HashMap<String, String>,
+) -> AdminCommandResult {
+    let (network_id, limits) = get_network_bandwidth_limits(protocol_bandwidth_limits, &params)?;
+    let protocol = params
+        .get("protocol")
+        .ok_or("Missing parameter: protocol")?;
+    match limits.remove_limit(protocol) {
+        Ok(true) => Ok(format!(
+            "Removed the bandwidth limit of protocol {} from network {}",
+            protocol, network_id
+        )),
+        Ok(false) => Err(format!(
+            "Protocol {} has no bandwidth limit on network {}!",
+            protocol, network_id
+        )),
+        Err(error) => Err(format!("Failed to remove the bandwidth limit: {}", error)),
+    }
+}
+
+/// Returns the bandwidth limits of all networks (as JSON)
+fn get_bandwidth_limits(
+    protocol_bandwidth_limits: &BTreeMap<NetworkId, ProtocolBandwidthLimits>,
+) -> AdminCommandResult {
+    let limits: BTreeMap<_, _> = protocol_bandwidth_limits
+        .iter()
+        .map(|(network_id, limits)| (network_id.as_str(), limits.get_limits()))
+        .collect();
+    serde_json::to_string_pretty(&limits)
+        .map_err(|error| format!("Failed to serialize the bandwidth limits: {:?}", error))
+}
+
+/// Returns the bandwidth limits of the network in the admin command parameters
+fn get_network_bandwidth_limits<'a>(
+    protocol_bandwidth_limits: &'a BTreeMap<NetworkId, ProtocolBandwidthLimits>,
+    params: &HashMap<String, String>,
+) -> Result<(NetworkId, &'a ProtocolBandwidthLimits), String> {
+    let network = params.get("network").ok_or("Missing parameter: network")?;
+    let network_id = NetworkId::from_str(network)
+        .map_err(|error| format!("Invalid network {}: {}", network, error))?;
+    protocol_bandwidth_limits
+        .get(&network_id)
+        .map(|limits| (network_id, limits))
+        .ok_or_else(|| format!("Network {} is not configured!", network_id))
+}
+
 /// Creates a network runtime for the given network config
 fn create_network_runtime(network_config: &NetworkConfig) -> Runtime {
     let network_id = network_config.network_id;
@@ -305,6 +478,7 @@ fn register_client_and_service_with_network<
 fn transform_network_handles_into_interfaces(
     node_config: &NodeConfig,
     consensus_network_handle: Option<ApplicationNetworkHandle<ConsensusMsg>>,
+    consensus_observer_network_handles: Vec<ApplicationNetworkHandle<ConsensusObserverMessage>>,
     mempool_network_handles: Vec<ApplicationNetworkHandle<MempoolSyncMsg>>,
     peer_monitoring_service_network_handles: Vec<
         ApplicationNetworkHandle<PeerMonitoringServiceMessage>,
@@ -313,6 +487,7 @@ fn transform_network_handles_into_interfaces(
     peers_and_metadata: Arc<PeersAndMetadata>,
 ) -> (
     Option<ApplicationNetworkInterfaces<ConsensusMsg>>,
+    Option<ApplicationNetworkInterfaces<ConsensusObserverMessage>>,
     ApplicationNetworkInterfaces<MempoolSyncMsg>,
     ApplicationNetworkInterfaces<PeerMonitoringServiceMessage>,
     ApplicationNetworkInterfaces<StorageServiceMessage>,
@@ -324,6 +499,15 @@ fn transform_network_handles_into_interfaces(
             peers_and_metadata.clone(),
         )
     });
+    let consensus_observer_interfaces = if consensus_observer_network_handles.is_empty() {
+        None
+    } else {
+        Some(create_network_interfaces(
+            consensus_observer_network_handles,
+            consensus_observer_network_configuration(node_config),
+            peers_and_metadata.clone(),
+        ))
+    };
     let mempool_interfaces = create_network_interfaces(
         mempool_network_handles,
         mempool_network_configuration(node_config),
@@ -342,6 +526,7 @@ fn transform_network_handles_into_interfaces(
 
     (
         consensus_interfaces,
+        consensus_observer_interfaces,
         mempool_interfaces,
         peer_monitoring_service_interfaces,
         storage_service_interfaces,
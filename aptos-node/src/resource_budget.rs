@@ -0,0 +1,400 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The node-wide resource budget. It periodically samples the memory and IO of the
+//! node process and of its major subsystems (e.g., mempool and the RocksDB block
+//! cache), and asks the subsystems to shed load (e.g., shrink their caches or pause
+//! prefetching) while the node or the subsystem exceeds its budget. Shedding stops
+//! once the usage falls below the restore threshold (to avoid flapping).
+
+use aptos_config::config::{ResourceBudgetConfig, SubsystemQuota};
+use aptos_data_streaming_service::streaming_service::StreamConcurrencyLimits;
+use aptos_logger::{info, warn};
+use aptos_mempool::MempoolCapacityUpdater;
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+use sysinfo::{get_current_pid, Pid, ProcessExt, System, SystemExt};
+
+// The names of the subsystems (as used by the quotas in the config)
+pub const MEMPOOL_SUBSYSTEM: &str = "mempool";
+pub const QUORUM_STORE_SUBSYSTEM: &str = "quorum_store";
+pub const STATE_SYNC_SUBSYSTEM: &str = "state_sync";
+pub const STORAGE_SUBSYSTEM: &str = "storage";
+
+// The capacity of the mempool (bytes) and of the block cache while shedding
+// (as percentages of their configured capacity)
+const SHED_MEMPOOL_CAPACITY_PERCENT: u64 = 50;
+const SHED_BLOCK_CACHE_CAPACITY_PERCENT: u64 = 25;
+
+/// A subsystem whose memory and IO count against the resource budget, and
+/// which can shed load while a budget is exceeded
+pub trait BudgetedSubsystem: Send + Sync {
+    /// Returns the name of the subsystem
+    fn name(&self) -> &'static str;
+
+    /// Returns the memory (bytes) attributed to the subsystem
+    fn memory_bytes(&self) -> u64;
+
+    /// Returns the total IO (bytes) attributed to the subsystem so far (if tracked)
+    fn io_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    /// Starts shedding load (e.g., shrinks caches or pauses prefetching)
+    fn shed(&self);
+
+    /// Stops shedding load (i.e., restores the configured behaviour)
+    fn restore(&self);
+}
+
+/// The budget of the node or of a subsystem, and whether it's exceeded
+#[derive(Default)]
+struct BudgetState {
+    quota: SubsystemQuota,
+    over_budget: bool,
+    last_io_bytes: Option<u64>,
+}
+
+impl BudgetState {
+    fn new(quota: SubsystemQuota) -> Self {
+        Self {
+            quota,
+            ..Default::default()
+        }
+    }
+
+    /// Updates the state with the given usage, and returns true iff it changed.
+    /// The budget is exceeded as soon as any limit is exceeded, and stays
+    /// exceeded until all usages fall below the restore threshold.
+    fn update(
+        &mut self,
+        memory_bytes: u64,
+        io_bytes: Option<u64>,
+        elapsed: Duration,
+        restore_threshold_percent: u64,
+    ) -> bool {
+        // Calculate the IO rate since the previous update
+        let io_bytes_per_sec = match (self.last_io_bytes, io_bytes) {
+            (Some(last_io_bytes), Some(io_bytes)) if !elapsed.is_zero() => {
+                Some((io_bytes.saturating_sub(last_io_bytes) as f64 / elapsed.as_secs_f64()) as u64)
+            },
+            _ => None,
+        };
+        self.last_io_bytes = io_bytes;
+
+        let usages = [
+            (memory_bytes, self.quota.max_memory_bytes),
+            (
+                io_bytes_per_sec.unwrap_or_default(),
+                self.quota.max_io_bytes_per_sec,
+            ),
+        ];
+        let exceeded = usages
+            .iter()
+            .any(|(usage, limit)| matches!(limit, Some(limit) if usage > limit));
+        let below_threshold = usages.iter().all(|(usage, limit)| match limit {
+            Some(limit) => {
+                (*usage as u128) * 100 <= (*limit as u128) * (restore_threshold_percent as u128)
+            },
+            None => true,
+        });
+
+        let was_over_budget = self.over_budget;
+        if exceeded {
+            self.over_budget = true;
+        } else if below_threshold {
+            self.over_budget = false;
+        }
+        self.over_budget != was_over_budget
+    }
+}
+
+/// A subsystem, its budget and whether it's shedding load
+struct TrackedSubsystem {
+    subsystem: Box<dyn BudgetedSubsystem>,
+    state: BudgetState,
+    shedding: bool,
+}
+
+/// Tracks the resource usage of the node and its subsystems, and triggers the
+/// subsystems to shed load while a budget is exceeded
+pub struct ResourceBudget {
+    restore_threshold_percent: u64,
+    node: BudgetState,
+    subsystems: Vec<TrackedSubsystem>,
+}
+
+impl ResourceBudget {
+    pub fn new(config: &ResourceBudgetConfig, subsystems: Vec<Box<dyn BudgetedSubsystem>>) -> Self {
+        let node = BudgetState::new(SubsystemQuota {
+            max_memory_bytes: config.max_memory_bytes,
+            max_io_bytes_per_sec: config.max_io_bytes_per_sec,
+        });
+        let subsystems = subsystems
+            .into_iter()
+            .map(|subsystem| {
+                let quota = config
+                    .subsystem_quotas
+                    .get(subsystem.name())
+                    .cloned()
+                    .unwrap_or_default();
+                TrackedSubsystem {
+                    subsystem,
+                    state: BudgetState::new(quota),
+                    shedding: false,
+                }
+            })
+            .collect();
+        Self {
+            restore_threshold_percent: config.restore_threshold_percent,
+            node,
+            subsystems,
+        }
+    }
+
+    /// Checks the given usage of the node (and the usage of the subsystems) against
+    /// the budgets, and starts (or stops) the shedding of the subsystems accordingly.
+    /// All subsystems shed load while the node exceeds its budget.
+    pub fn check(&mut self, node_memory_bytes: u64, node_io_bytes: Option<u64>, elapsed: Duration) {
+        let restore_threshold_percent = self.restore_threshold_percent;
+        if self.node.update(
+            node_memory_bytes,
+            node_io_bytes,
+            elapsed,
+            restore_threshold_percent,
+        ) {
+            if self.node.over_budget {
+                warn!(
+                    "The node exceeds its resource budget (memory: {} bytes)! Shedding load.",
+                    node_memory_bytes
+                );
+            } else {
+                info!("The node is back within its resource budget");
+            }
+        }
+
+        for tracked in &mut self.subsystems {
+            let subsystem = &tracked.subsystem;
+            let memory_bytes = subsystem.memory_bytes();
+            tracked.state.update(
+                memory_bytes,
+                subsystem.io_bytes(),
+                elapsed,
+                restore_threshold_percent,
+            );
+
+            let should_shed = self.node.over_budget || tracked.state.over_budget;
+            if should_shed && !tracked.shedding {
+                warn!(
+                    "Shedding the load of {} (memory: {} bytes, over its budget: {})",
+                    subsystem.name(),
+                    memory_bytes,
+                    tracked.state.over_budget
+                );
+                subsystem.shed();
+            } else if !should_shed && tracked.shedding {
+                info!(
+                    "Restoring {} (memory: {} bytes)",
+                    subsystem.name(),
+                    memory_bytes
+                );
+                subsystem.restore();
+            }
+            tracked.shedding = should_shed;
+        }
+    }
+
+    /// Returns the names of the subsystems that are shedding load
+    pub fn shedding_subsystems(&self) -> Vec<&'static str> {
+        self.subsystems
+            .iter()
+            .filter(|tracked| tracked.shedding)
+            .map(|tracked| tracked.subsystem.name())
+            .collect()
+    }
+}
+
+/// Starts enforcing the resource budget (if enabled) on a dedicated thread
+pub fn start_resource_budget(
+    config: &ResourceBudgetConfig,
+    subsystems: Vec<Box<dyn BudgetedSubsystem>>,
+) {
+    if !config.enabled {
+        return;
+    }
+    let check_interval = Duration::from_millis(config.check_interval_ms);
+    let mut resource_budget = ResourceBudget::new(config, subsystems);
+    thread::Builder::new()
+        .name("resource-budget".into())
+        .spawn(move || {
+            let mut node_usage = NodeUsage::new();
+            let mut last_check = Instant::now();
+            loop {
+                thread::sleep(check_interval);
+                let (memory_bytes, io_bytes) = node_usage.sample();
+                resource_budget.check(memory_bytes, io_bytes, last_check.elapsed());
+                last_check = Instant::now();
+            }
+        })
+        .expect("Failed to start the resource budget");
+}
+
+/// Samples the memory and IO of the node process
+struct NodeUsage {
+    system: System,
+    pid: Option<Pid>,
+}
+
+impl NodeUsage {
+    fn new() -> Self {
+        let pid = get_current_pid()
+            .map_err(|error| warn!("Failed to get the pid of the node: {}", error))
+            .ok();
+        Self {
+            system: System::new(),
+            pid,
+        }
+    }
+
+    /// Returns the resident memory (bytes) and the total disk IO (bytes) of the node
+    fn sample(&mut self) -> (u64, Option<u64>) {
+        let pid = match self.pid {
+            Some(pid) => pid,
+            None => return (0, None),
+        };
+        if !self.system.refresh_process(pid) {
+            return (0, None);
+        }
+        match self.system.process(pid) {
+            Some(process) => {
+                let disk_usage = process.disk_usage();
+                let io_bytes = disk_usage.total_read_bytes + disk_usage.total_written_bytes;
+                (process.memory(), Some(io_bytes))
+            },
+            None => (0, None),
+        }
+    }
+}
+
+/// Mempool holds the pending transactions. It sheds load by shrinking its
+/// capacity (bytes), i.e., by rejecting new transactions sooner. The capacity
+/// updater scales the configured capacity, so config reloads aren't reverted.
+pub struct MempoolBudget {
+    capacity_updater: MempoolCapacityUpdater,
+}
+
+impl MempoolBudget {
+    pub fn new(capacity_updater: MempoolCapacityUpdater) -> Self {
+        Self { capacity_updater }
+    }
+}
+
+impl BudgetedSubsystem for MempoolBudget {
+    fn name(&self) -> &'static str {
+        MEMPOOL_SUBSYSTEM
+    }
+
+    fn memory_bytes(&self) -> u64 {
+        aptos_mempool::counters::CORE_MEMPOOL_INDEX_SIZE
+            .with_label_values(&[aptos_mempool::counters::SIZE_BYTES_LABEL])
+            .get()
+            .max(0) as u64
+    }
+
+    fn shed(&self) {
+        self.capacity_updater
+            .set_capacity_bytes_percent(SHED_MEMPOOL_CAPACITY_PERCENT);
+    }
+
+    fn restore(&self) {
+        self.capacity_updater.set_capacity_bytes_percent(100);
+    }
+}
+
+/// The quorum store caches the payloads of the batches in memory. It sheds load
+/// by pausing the caching (i.e., new payloads are only persisted).
+pub struct QuorumStoreBudget;
+
+impl BudgetedSubsystem for QuorumStoreBudget {
+    fn name(&self) -> &'static str {
+        QUORUM_STORE_SUBSYSTEM
+    }
+
+    fn memory_bytes(&self) -> u64 {
+        aptos_consensus::batch_memory_bytes() as u64
+    }
+
+    fn shed(&self) {
+        aptos_consensus::set_batch_memory_caching(false);
+    }
+
+    fn restore(&self) {
+        aptos_consensus::set_batch_memory_caching(true);
+    }
+}
+
+/// State sync buffers the responses of the in-flight data requests. It sheds
+/// load by pausing prefetching (i.e., only a single request is in flight).
+pub struct StateSyncBudget {
+    concurrency_limits: StreamConcurrencyLimits,
+    max_response_bytes: u64,
+}
+
+impl StateSyncBudget {
+    pub fn new(concurrency_limits: StreamConcurrencyLimits, max_response_bytes: u64) -> Self {
+        Self {
+            concurrency_limits,
+            max_response_bytes,
+        }
+    }
+}
+
+impl BudgetedSubsystem for StateSyncBudget {
+    fn name(&self) -> &'static str {
+        STATE_SYNC_SUBSYSTEM
+    }
+
+    fn memory_bytes(&self) -> u64 {
+        // The responses aren't measured, so this is an upper bound
+        let pending_responses = aptos_data_streaming_service::metrics::PENDING_DATA_RESPONSES
+            .get()
+            .max(0) as u64;
+        pending_responses.saturating_mul(self.max_response_bytes)
+    }
+
+    fn shed(&self) {
+        self.concurrency_limits.pause_prefetching(true);
+    }
+
+    fn restore(&self) {
+        self.concurrency_limits.pause_prefetching(false);
+    }
+}
+
+/// Storage caches the RocksDB blocks in memory. It sheds load by shrinking
+/// the block caches.
+pub struct StorageBudget;
+
+impl BudgetedSubsystem for StorageBudget {
+    fn name(&self) -> &'static str {
+        STORAGE_SUBSYSTEM
+    }
+
+    fn memory_bytes(&self) -> u64 {
+        aptos_db::block_cache::usage() as u64
+    }
+
+    fn io_bytes(&self) -> Option<u64> {
+        Some(aptos_schemadb::total_io_bytes())
+    }
+
+    fn shed(&self) {
+        aptos_db::block_cache::set_capacity_percent(SHED_BLOCK_CACHE_CAPACITY_PERCENT);
+    }
+
+    fn restore(&self) {
+        aptos_db::block_cache::set_capacity_percent(100);
+    }
+}
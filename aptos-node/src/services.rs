@@ -1,17 +1,36 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{bootstrap_api, indexer, mpsc::Receiver, network::ApplicationNetworkInterfaces};
+use crate::{bootstrap_api, indexer, mpsc::Receiver, network::ApplicationNetworkInterfaces, utils};
+use anyhow::Context as AnyhowContext;
+use aptos_api::quota::Quota;
 use aptos_build_info::build_information;
 use aptos_config::config::NodeConfig;
-use aptos_consensus::network_interface::ConsensusMsg;
+use aptos_consensus::{
+    admin::ConsensusAdminClient,
+    consensus_observer::{
+        network_events::ConsensusObserverNetworkEvents, network_message::ConsensusObserverMessage,
+        publisher::ConsensusPublisher,
+    },
+    network_interface::ConsensusMsg,
+};
 use aptos_consensus_notifications::ConsensusNotifier;
-use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_event_notifications::{
+    DbBackedOnChainConfig, EpochChangeNotifier, ReconfigNotificationListener,
+};
 use aptos_indexer_grpc_fullnode::runtime::bootstrap as bootstrap_indexer_grpc;
-use aptos_logger::{debug, telemetry_log_writer::TelemetryLog, LoggerFilterUpdater};
-use aptos_mempool::{network::MempoolSyncMsg, MempoolClientRequest, QuorumStoreRequest};
+use aptos_inspection_service::{
+    admin::{AdminCommandResult, AdminCommands},
+    health::HealthChecks,
+};
+use aptos_logger::{debug, info, telemetry_log_writer::TelemetryLog, warn, LoggerFilterUpdater};
+use aptos_mempool::{
+    network::MempoolSyncMsg, MempoolCapacityUpdater, MempoolClientRequest, QuorumStoreRequest,
+    TransactionFilter,
+};
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_network::application::{interface::NetworkClientInterface, storage::PeersAndMetadata};
+use aptos_peer_monitoring_service_client::soak_test::{SoakTestSchedule, SoakTests};
 use aptos_peer_monitoring_service_server::{
     network::PeerMonitoringServiceNetworkEvents, storage::StorageReader,
     PeerMonitoringServiceServer,
@@ -19,40 +38,66 @@ use aptos_peer_monitoring_service_server::{
 use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_time_service::TimeService;
-use aptos_types::chain_id::ChainId;
-use futures::channel::{mpsc, mpsc::Sender};
-use std::{sync::Arc, time::Instant};
-use tokio::runtime::Runtime;
+use aptos_types::{chain_id::ChainId, PeerId};
+use futures::{
+    channel::{mpsc, mpsc::Sender},
+    FutureExt,
+};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{runtime::Runtime, sync::watch};
 
 const AC_SMP_CHANNEL_BUFFER_SIZE: usize = 1_024;
 const INTRA_NODE_CHANNEL_BUFFER_SIZE: usize = 1;
 
+// The names of the admin commands that move local test chains through time
+const ADVANCE_TIME_ADMIN_COMMAND: &str = "advance_time";
+const FORCE_EPOCH_CHANGE_ADMIN_COMMAND: &str = "force_epoch_change";
+const MINE_BLOCKS_ADMIN_COMMAND: &str = "mine_blocks";
+
+// The names of the admin commands that manage the network soak tests (i.e., netbench)
+const NETBENCH_CANCEL_ADMIN_COMMAND: &str = "netbench_cancel";
+const NETBENCH_REPORT_ADMIN_COMMAND: &str = "netbench_report";
+const NETBENCH_SCHEDULE_ADMIN_COMMAND: &str = "netbench_schedule";
+
 /// Bootstraps the API and the indexer. Returns the Mempool client
-/// receiver, and both the api and indexer runtimes.
+/// receiver, the api and indexer runtimes, and the API quota (if enabled).
 pub fn bootstrap_api_and_indexer(
     node_config: &NodeConfig,
     aptos_db: Arc<dyn DbReader>,
     chain_id: ChainId,
+    commit_notifications: watch::Receiver<u64>,
+    transaction_filter: Option<TransactionFilter>,
+    epoch_change_notifier: &EpochChangeNotifier,
 ) -> anyhow::Result<(
     Receiver<MempoolClientRequest>,
     Option<Runtime>,
     Option<Runtime>,
     Option<Runtime>,
+    Option<Quota>,
 )> {
     // Create the mempool client and sender
     let (mempool_client_sender, mempool_client_receiver) =
         mpsc::channel(AC_SMP_CHANNEL_BUFFER_SIZE);
 
     // Create the API runtime
-    let api_runtime = if node_config.api.enabled {
-        Some(bootstrap_api(
+    let (api_runtime, api_quota) = if node_config.api.enabled {
+        let (api_runtime, api_quota) = bootstrap_api(
             node_config,
             chain_id,
             aptos_db.clone(),
             mempool_client_sender.clone(),
-        )?)
+            Some(commit_notifications),
+            transaction_filter,
+            Some(epoch_change_notifier.subscribe("api")),
+        )?;
+        (Some(api_runtime), api_quota)
     } else {
-        None
+        (None, None)
     };
 
     // Creates the indexer grpc runtime
@@ -72,34 +117,249 @@ pub fn bootstrap_api_and_indexer(
         api_runtime,
         indexer_runtime,
         indexer_grpc,
+        api_quota,
     ))
 }
 
-/// Starts consensus and returns the runtime
+/// Starts consensus, registers its admin commands and returns the runtime
 pub fn start_consensus_runtime(
     node_config: &mut NodeConfig,
     db_rw: DbReaderWriter,
     consensus_reconfig_subscription: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
     consensus_network_interfaces: ApplicationNetworkInterfaces<ConsensusMsg>,
+    consensus_observer_network_interfaces: Option<
+        ApplicationNetworkInterfaces<ConsensusObserverMessage>,
+    >,
+    consensus_notifier: ConsensusNotifier,
+    consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
+    admin_commands: &AdminCommands,
+) -> Runtime {
+    // Create the consensus publisher (if enabled)
+    let (consensus_publisher, consensus_publisher_network_events) =
+        match consensus_observer_network_interfaces {
+            Some(network_interfaces) if node_config.consensus_observer.publisher_enabled => {
+                let consensus_publisher = Arc::new(ConsensusPublisher::new(
+                    node_config.consensus_observer,
+                    network_interfaces.network_client,
+                ));
+                let network_events =
+                    ConsensusObserverNetworkEvents::new(network_interfaces.network_service_events);
+                (Some(consensus_publisher), Some(network_events))
+            },
+            _ => (None, None),
+        };
+
+    // If the chain travelled forward in time before the node restarted, the latest block
+    // may be ahead of the system time. Move the clock forward again to catch up with it.
+    let aptos_db = db_rw.reader.clone();
+    if node_config.consensus.enable_time_travel {
+        restore_time_travel_clock(&aptos_db);
+    }
+
+    let instant = Instant::now();
+    let (consensus_runtime, consensus_admin_client) =
+        aptos_consensus::consensus_provider::start_consensus(
+            node_config,
+            consensus_network_interfaces.network_client,
+            consensus_network_interfaces.network_service_events,
+            Arc::new(consensus_notifier),
+            consensus_to_mempool_sender,
+            db_rw,
+            consensus_reconfig_subscription
+                .expect("Consensus requires a reconfiguration subscription!"),
+            consensus_publisher.clone(),
+        );
+    debug!("Consensus started in {} ms", instant.elapsed().as_millis());
+
+    // Start the consensus publisher (to handle subscription requests)
+    if let (Some(consensus_publisher), Some(network_events)) =
+        (consensus_publisher, consensus_publisher_network_events)
+    {
+        consensus_runtime.spawn(consensus_publisher.start(network_events));
+    }
+
+    // Register the admin commands for moving local test chains through time, e.g.:
+    // POST /admin/advance_time?seconds=3600
+    // POST /admin/force_epoch_change
+    // POST /admin/mine_blocks?num_blocks=10
+    if node_config.consensus.enable_time_travel {
+        admin_commands.register(ADVANCE_TIME_ADMIN_COMMAND, {
+            let consensus_admin_client = consensus_admin_client.clone();
+            Arc::new(move |params| {
+                let consensus_admin_client = consensus_admin_client.clone();
+                async move { advance_time(consensus_admin_client, params).await }.boxed()
+            })
+        });
+        admin_commands.register(FORCE_EPOCH_CHANGE_ADMIN_COMMAND, {
+            let consensus_admin_client = consensus_admin_client.clone();
+            Arc::new(move |_params| {
+                let consensus_admin_client = consensus_admin_client.clone();
+                let aptos_db = aptos_db.clone();
+                async move { force_epoch_change(consensus_admin_client, aptos_db).await }.boxed()
+            })
+        });
+        admin_commands.register(
+            MINE_BLOCKS_ADMIN_COMMAND,
+            Arc::new(move |params| {
+                let consensus_admin_client = consensus_admin_client.clone();
+                async move { mine_blocks(consensus_admin_client, params).await }.boxed()
+            }),
+        );
+    }
+
+    consensus_runtime
+}
+
+/// Starts the consensus observer (on fullnodes) and returns the runtime
+pub fn start_consensus_observer_runtime(
+    node_config: &NodeConfig,
+    db_rw: DbReaderWriter,
+    consensus_reconfig_subscription: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    consensus_observer_network_interfaces: ApplicationNetworkInterfaces<ConsensusObserverMessage>,
     consensus_notifier: ConsensusNotifier,
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
 ) -> Runtime {
     let instant = Instant::now();
-    let consensus_runtime = aptos_consensus::consensus_provider::start_consensus(
+    let consensus_observer_runtime = aptos_consensus::consensus_provider::start_consensus_observer(
         node_config,
-        consensus_network_interfaces.network_client,
-        consensus_network_interfaces.network_service_events,
+        consensus_observer_network_interfaces.network_client,
+        consensus_observer_network_interfaces.network_service_events,
         Arc::new(consensus_notifier),
         consensus_to_mempool_sender,
         db_rw,
         consensus_reconfig_subscription
-            .expect("Consensus requires a reconfiguration subscription!"),
+            .expect("The consensus observer requires a reconfiguration subscription!"),
     );
-    debug!("Consensus started in {} ms", instant.elapsed().as_millis());
-    consensus_runtime
+    debug!(
+        "Consensus observer started in {} ms",
+        instant.elapsed().as_millis()
+    );
+
+    consensus_observer_runtime
+}
+
+/// Parses the given admin command parameter as a u64, or returns the default (if any)
+pub(crate) fn parse_u64_param(
+    params: &HashMap<String, String>,
+    name: &str,
+    default: Option<u64>,
+) -> Result<u64, String> {
+    match params.get(name) {
+        Some(value) => value
+            .parse::<u64>()
+            .map_err(|error| format!("Invalid {}: {:?}", name, error)),
+        None => default.ok_or_else(|| format!("Missing parameter: {}", name)),
+    }
+}
+
+/// Moves the chain forward in time, given the admin command parameters
+async fn advance_time(
+    consensus_admin_client: ConsensusAdminClient,
+    params: HashMap<String, String>,
+) -> AdminCommandResult {
+    let seconds = parse_u64_param(&params, "seconds", None)?;
+    consensus_admin_client
+        .advance_time(Duration::from_secs(seconds))
+        .await
+        .map(|mined_block| format!("Advanced the time by {}s, mined {}", seconds, mined_block))
+        .map_err(|error| format!("Failed to advance the time: {:?}", error))
+}
+
+/// Moves the chain forward in time to the end of the current epoch, so that the next
+/// block starts a new epoch
+async fn force_epoch_change(
+    consensus_admin_client: ConsensusAdminClient,
+    aptos_db: Arc<dyn DbReader>,
+) -> AdminCommandResult {
+    let epoch_end_time = utils::fetch_epoch_end_time(&aptos_db)
+        .map_err(|error| format!("Failed to fetch the end of the epoch: {:?}", error))?;
+    let duration = epoch_end_time.saturating_sub(aptos_infallible::duration_since_epoch());
+    consensus_admin_client
+        .advance_time(duration)
+        .await
+        .map(|mined_block| format!("Forced an epoch change, mined {}", mined_block))
+        .map_err(|error| format!("Failed to force an epoch change: {:?}", error))
+}
+
+/// Mines empty blocks on demand, given the admin command parameters
+async fn mine_blocks(
+    consensus_admin_client: ConsensusAdminClient,
+    params: HashMap<String, String>,
+) -> AdminCommandResult {
+    let num_blocks = parse_u64_param(&params, "num_blocks", Some(1))?;
+    consensus_admin_client
+        .mine_blocks(num_blocks)
+        .await
+        .map(|mined_block| {
+            format!(
+                "Mined {} blocks, the last one is {}",
+                num_blocks, mined_block
+            )
+        })
+        .map_err(|error| format!("Failed to mine the blocks: {:?}", error))
+}
+
+/// Schedules the network soak tests, given the admin command parameters
+fn schedule_soak_tests(
+    soak_tests: &SoakTests,
+    params: HashMap<String, String>,
+) -> AdminCommandResult {
+    let peers = params
+        .get("peers")
+        .ok_or_else(|| "Missing parameter: peers".to_string())?
+        .split(',')
+        .map(|peer| {
+            PeerId::from_str(peer).map_err(|error| format!("Invalid peer {}: {:?}", peer, error))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let schedule = SoakTestSchedule {
+        peers,
+        interval_ms: parse_u64_param(&params, "interval_secs", None)?.saturating_mul(1000),
+        num_probes_per_peer: parse_u64_param(&params, "num_probes", Some(10))?,
+        min_throughput_kb_per_sec: parse_u64_param(&params, "min_throughput_kb_per_sec", None)?,
+    };
+    soak_tests.schedule(schedule.clone())?;
+    Ok(format!("Scheduled the soak tests: {:?}", schedule))
 }
 
-/// Create the mempool runtime and start mempool
+/// Moves the clock forward to the timestamp of the latest block, if it is ahead of the
+/// system time (i.e., the chain travelled forward in time before the node restarted)
+fn restore_time_travel_clock(aptos_db: &Arc<dyn DbReader>) {
+    let latest_timestamp = match aptos_db.get_latest_ledger_info() {
+        Ok(ledger_info) => Duration::from_micros(ledger_info.ledger_info().timestamp_usecs()),
+        Err(error) => {
+            warn!("Failed to fetch the latest ledger info: {:?}", error);
+            return;
+        },
+    };
+    let clock_lag =
+        latest_timestamp.saturating_sub(aptos_consensus_types::clock::current_timestamp());
+    if !clock_lag.is_zero() {
+        let clock_offset = aptos_consensus_types::clock::advance_clock(clock_lag);
+        info!(
+            "Moved the clock forward to the latest block, the clock is now {:?} ahead of the system time",
+            clock_offset
+        );
+    }
+}
+
+/// Loads the transaction filter policy (if enabled), and starts reloading the policy
+/// whenever the policy file changes. The filter is shared by the API and mempool.
+pub fn start_transaction_filter(
+    node_config: &NodeConfig,
+) -> anyhow::Result<Option<TransactionFilter>> {
+    let filter_config = &node_config.mempool.transaction_filter;
+    let transaction_filter = TransactionFilter::new(filter_config, node_config.get_data_dir())
+        .context("Failed to load the transaction filter policy")?;
+    if let Some(transaction_filter) = &transaction_filter {
+        transaction_filter
+            .watch_policy_file(Duration::from_millis(filter_config.reload_interval_ms));
+    }
+    Ok(transaction_filter)
+}
+
+/// Create the mempool runtime and start mempool. Returns the runtime, the consensus
+/// to mempool sender and the updater of the mempool capacity.
 pub fn start_mempool_runtime_and_get_consensus_sender(
     node_config: &mut NodeConfig,
     db_rw: &DbReaderWriter,
@@ -108,14 +368,15 @@ pub fn start_mempool_runtime_and_get_consensus_sender(
     mempool_listener: MempoolNotificationListener,
     mempool_client_receiver: Receiver<MempoolClientRequest>,
     peers_and_metadata: Arc<PeersAndMetadata>,
-) -> (Runtime, Sender<QuorumStoreRequest>) {
+    transaction_filter: Option<TransactionFilter>,
+) -> (Runtime, Sender<QuorumStoreRequest>, MempoolCapacityUpdater) {
     // Create a communication channel between consensus and mempool
     let (consensus_to_mempool_sender, consensus_to_mempool_receiver) =
         mpsc::channel(INTRA_NODE_CHANNEL_BUFFER_SIZE);
 
     // Bootstrap and start mempool
     let instant = Instant::now();
-    let mempool = aptos_mempool::bootstrap(
+    let (mempool, mempool_capacity_updater) = aptos_mempool::bootstrap(
         node_config,
         Arc::clone(&db_rw.reader),
         network_interfaces.network_client,
@@ -125,25 +386,40 @@ pub fn start_mempool_runtime_and_get_consensus_sender(
         mempool_listener,
         mempool_reconfig_subscription,
         peers_and_metadata,
+        transaction_filter,
     );
     debug!("Mempool started in {} ms", instant.elapsed().as_millis());
 
-    (mempool, consensus_to_mempool_sender)
+    (
+        mempool,
+        consensus_to_mempool_sender,
+        mempool_capacity_updater,
+    )
 }
 
-/// Spawns a new thread for the node inspection service
+/// Spawns a new thread for the node inspection service, and returns the admin
+/// commands and health checks served by it (for the other services to register theirs)
 pub fn start_node_inspection_service(
     node_config: &NodeConfig,
     peers_and_metadata: Arc<PeersAndMetadata>,
-) {
-    aptos_inspection_service::start_inspection_service(node_config.clone(), peers_and_metadata)
+) -> (AdminCommands, HealthChecks) {
+    let admin_commands = AdminCommands::new();
+    let health_checks = HealthChecks::new();
+    aptos_inspection_service::start_inspection_service(
+        node_config.clone(),
+        peers_and_metadata,
+        admin_commands.clone(),
+        health_checks.clone(),
+    );
+    (admin_commands, health_checks)
 }
 
-/// Starts the peer monitoring service and returns the runtime
+/// Starts the peer monitoring service, registers its admin commands and returns the runtime
 pub fn start_peer_monitoring_service(
     node_config: &NodeConfig,
     network_interfaces: ApplicationNetworkInterfaces<PeerMonitoringServiceMessage>,
     db_reader: Arc<dyn DbReader>,
+    admin_commands: &AdminCommands,
 ) -> Runtime {
     // Get the network client and events
     let network_client = network_interfaces.network_client;
@@ -171,13 +447,46 @@ pub fn start_peer_monitoring_service(
         .peer_monitoring_service
         .enable_peer_monitoring_client
     {
+        let soak_tests = SoakTests::new(node_config.peer_monitoring_service.soak_testing);
         peer_monitoring_service_runtime.spawn(
             aptos_peer_monitoring_service_client::start_peer_monitor(
                 node_config.clone(),
                 network_client,
+                soak_tests.clone(),
                 Some(peer_monitoring_service_runtime.handle().clone()),
             ),
         );
+
+        // Register the admin commands for the network soak tests, e.g.:
+        // POST /admin/netbench_schedule?peers=<peer_id>,<peer_id>&interval_secs=3600&num_probes=10&min_throughput_kb_per_sec=1024
+        // POST /admin/netbench_report
+        // POST /admin/netbench_cancel
+        admin_commands.register(NETBENCH_SCHEDULE_ADMIN_COMMAND, {
+            let soak_tests = soak_tests.clone();
+            Arc::new(move |params| {
+                let result = schedule_soak_tests(&soak_tests, params);
+                async move { result }.boxed()
+            })
+        });
+        admin_commands.register(NETBENCH_REPORT_ADMIN_COMMAND, {
+            let soak_tests = soak_tests.clone();
+            Arc::new(move |_params| {
+                let result = serde_json::to_string_pretty(&soak_tests.get_report())
+                    .map_err(|error| format!("Failed to serialize the report: {:?}", error));
+                async move { result }.boxed()
+            })
+        });
+        admin_commands.register(
+            NETBENCH_CANCEL_ADMIN_COMMAND,
+            Arc::new(move |_params| {
+                let result = if soak_tests.cancel() {
+                    Ok("Cancelled the soak tests".into())
+                } else {
+                    Err("No soak tests are scheduled!".into())
+                };
+                async move { result }.boxed()
+            }),
+        );
     }
 
     // Return the runtime
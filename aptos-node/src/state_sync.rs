@@ -7,7 +7,7 @@ use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::client::AptosDataClient;
 use aptos_data_streaming_service::{
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
-    streaming_service::DataStreamingService,
+    streaming_service::{DataStreamingService, StreamConcurrencyLimits},
 };
 use aptos_event_notifications::{
     DbBackedOnChainConfig, EventSubscriptionService, ReconfigNotificationListener,
@@ -34,7 +34,7 @@ use aptos_time_service::TimeService;
 use aptos_types::waypoint::Waypoint;
 use aptos_vm::AptosVM;
 use std::sync::Arc;
-use tokio::runtime::Runtime;
+use tokio::{runtime::Runtime, sync::watch};
 
 /// Creates the event subscription service and two reconfiguration
 /// notification listeners (for mempool and consensus, respectively).
@@ -47,21 +47,19 @@ pub fn create_event_subscription_service(
     Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
 ) {
     // Create the event subscription service
-    let mut event_subscription_service =
+    let event_subscription_service =
         EventSubscriptionService::new(Arc::new(RwLock::new(db_rw.clone())));
+    let epoch_change_notifier = event_subscription_service.epoch_change_notifier();
 
     // Create a reconfiguration subscription for mempool
-    let mempool_reconfig_subscription = event_subscription_service
-        .subscribe_to_reconfigurations()
-        .expect("Mempool must subscribe to reconfigurations");
-
-    // Create a reconfiguration subscription for consensus (if this is a validator)
-    let consensus_reconfig_subscription = if node_config.base.role.is_validator() {
-        Some(
-            event_subscription_service
-                .subscribe_to_reconfigurations()
-                .expect("Consensus must subscribe to reconfigurations"),
-        )
+    let mempool_reconfig_subscription = epoch_change_notifier.subscribe("mempool");
+
+    // Create a reconfiguration subscription for consensus (if this is a validator,
+    // or if the node runs the consensus observer)
+    let consensus_reconfig_subscription = if node_config.base.role.is_validator()
+        || node_config.consensus_observer.observer_enabled
+    {
+        Some(epoch_change_notifier.subscribe("consensus"))
     } else {
         None
     };
@@ -84,6 +82,8 @@ pub fn start_state_sync_and_get_notification_handles(
     StateSyncRuntimes,
     MempoolNotificationListener,
     ConsensusNotifier,
+    watch::Receiver<u64>,
+    StreamConcurrencyLimits,
 )> {
     // Get the network client and events
     let network_client = storage_network_interfaces.network_client;
@@ -95,7 +95,7 @@ pub fn start_state_sync_and_get_notification_handles(
         setup_aptos_data_client(node_config, network_client, db_rw.reader.clone())?;
 
     // Start the data streaming service
-    let (streaming_service_client, streaming_service_runtime) =
+    let (streaming_service_client, streaming_service_runtime, stream_concurrency_limits) =
         setup_data_streaming_service(node_config.state_sync, aptos_data_client.clone())?;
 
     // Create the chunk executor and persistent storage
@@ -115,6 +115,9 @@ pub fn start_state_sync_and_get_notification_handles(
     let (storage_service_notifier, storage_service_listener) =
         aptos_storage_service_notifications::new_storage_service_notifier_listener_pair();
 
+    // Subscribe to the storage commit notifications (e.g., for API streaming)
+    let commit_notifications = storage_service_notifier.subscribe_to_commits();
+
     // Start the state sync storage service
     let storage_service_runtime = setup_state_sync_storage_service(
         node_config.state_sync,
@@ -149,14 +152,20 @@ pub fn start_state_sync_and_get_notification_handles(
         streaming_service_runtime,
     );
 
-    Ok((state_sync_runtimes, mempool_listener, consensus_notifier))
+    Ok((
+        state_sync_runtimes,
+        mempool_listener,
+        consensus_notifier,
+        commit_notifications,
+        stream_concurrency_limits,
+    ))
 }
 
 /// Sets up the data streaming service runtime
 fn setup_data_streaming_service(
     state_sync_config: StateSyncConfig,
     aptos_data_client: AptosDataClient,
-) -> anyhow::Result<(StreamingServiceClient, Runtime)> {
+) -> anyhow::Result<(StreamingServiceClient, Runtime, StreamConcurrencyLimits)> {
     // Create the data streaming service
     let (streaming_service_client, streaming_service_listener) =
         new_streaming_service_client_listener_pair();
@@ -166,12 +175,17 @@ fn setup_data_streaming_service(
         aptos_data_client,
         streaming_service_listener,
     );
+    let stream_concurrency_limits = data_streaming_service.concurrency_limits();
 
     // Start the data streaming service
     let streaming_service_runtime = aptos_runtimes::spawn_named_runtime("stream-serv".into(), None);
     streaming_service_runtime.spawn(data_streaming_service.start_service());
 
-    Ok((streaming_service_client, streaming_service_runtime))
+    Ok((
+        streaming_service_client,
+        streaming_service_runtime,
+        stream_concurrency_limits,
+    ))
 }
 
 /// Sets up the aptos data client runtime
@@ -1,17 +1,42 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::services::parse_u64_param;
 use anyhow::anyhow;
-use aptos_config::{config::NodeConfig, utils::get_genesis_txn};
-use aptos_db::AptosDB;
+use aptos_config::{
+    config::{NodeConfig, StateSnapshotScheduleConfig},
+    utils::get_genesis_txn,
+};
+use aptos_db::{
+    state_snapshot_scheduler::StateSnapshotScheduler, storage_forecaster::StorageForecaster,
+    AptosDB,
+};
+use aptos_event_notifications::EpochChangeNotifier;
 use aptos_executor::db_bootstrapper::maybe_bootstrap;
-use aptos_logger::{debug, info};
+use aptos_inspection_service::admin::{AdminCommandResult, AdminCommands};
+use aptos_logger::{debug, info, warn};
 use aptos_storage_interface::{DbReader, DbReaderWriter};
-use aptos_types::waypoint::Waypoint;
+use aptos_types::{
+    on_chain_config::{OnChainConfigPayload, OnChainConfigProvider, OnChainStateSnapshotConfig},
+    waypoint::Waypoint,
+};
 use aptos_vm::AptosVM;
-use std::{fs, net::SocketAddr, path::Path, sync::Arc, time::Instant};
+use futures::{FutureExt, StreamExt};
+use serde::Serialize;
+use std::{
+    collections::HashMap, fs, net::SocketAddr, path::Path, sync::Arc, thread, time::Instant,
+};
 use tokio::runtime::Runtime;
 
+// The name of the admin command that returns the storage usage forecast
+const STORAGE_FORECAST_ADMIN_COMMAND: &str = "storage_forecast";
+
+// The name of the admin command that returns the state snapshot versions and schedule
+const STATE_SNAPSHOTS_ADMIN_COMMAND: &str = "state_snapshots";
+
+// The number of state snapshot versions returned by default
+const DEFAULT_NUM_STATE_SNAPSHOTS: u64 = 10;
+
 #[cfg(not(feature = "consensus-only-perf-test"))]
 pub(crate) fn bootstrap_db(
     aptos_db: AptosDB,
@@ -87,7 +112,14 @@ fn create_rocksdb_checkpoint_and_change_working_dir(
 /// the various handles.
 pub fn initialize_database_and_checkpoints(
     node_config: &mut NodeConfig,
-) -> anyhow::Result<(Arc<dyn DbReader>, DbReaderWriter, Option<Runtime>, Waypoint)> {
+    admin_commands: &AdminCommands,
+) -> anyhow::Result<(
+    Arc<dyn DbReader>,
+    DbReaderWriter,
+    Option<Runtime>,
+    Waypoint,
+    StateSnapshotScheduler,
+)> {
     // If required, create RocksDB checkpoints and change the working directory.
     // This is test-only.
     if let Some(working_dir) = node_config.base.working_dir.clone() {
@@ -96,7 +128,7 @@ pub fn initialize_database_and_checkpoints(
 
     // Open the database
     let instant = Instant::now();
-    let aptos_db = AptosDB::open(
+    let mut aptos_db = AptosDB::open(
         &node_config.storage.dir(),
         false, /* readonly */
         node_config.storage.storage_pruner_config,
@@ -106,9 +138,35 @@ pub fn initialize_database_and_checkpoints(
         node_config.storage.max_num_nodes_per_lru_cache_shard,
     )
     .map_err(|err| anyhow!("DB failed to open {}", err))?;
+    if let Some(forecaster) =
+        aptos_db.start_storage_forecaster(node_config.storage.storage_forecast_config)
+    {
+        // Register the admin command for the storage usage forecast, i.e.:
+        // POST /admin/storage_forecast
+        admin_commands.register(
+            STORAGE_FORECAST_ADMIN_COMMAND,
+            Arc::new(move |_params| {
+                let forecaster = forecaster.clone();
+                async move { get_storage_forecast(&forecaster) }.boxed()
+            }),
+        );
+    }
+    let state_snapshot_scheduler = aptos_db.state_snapshot_scheduler();
+    state_snapshot_scheduler.set_schedule(node_config.storage.state_snapshot_schedule);
     let (aptos_db, db_rw, backup_service) =
         bootstrap_db(aptos_db, node_config.storage.backup_service_address);
 
+    // Register the admin command for the state snapshots, i.e.:
+    // POST /admin/state_snapshots?end_version=<version>&limit=<limit>
+    admin_commands.register(STATE_SNAPSHOTS_ADMIN_COMMAND, {
+        let db_reader = db_rw.reader.clone();
+        let state_snapshot_scheduler = state_snapshot_scheduler.clone();
+        Arc::new(move |params| {
+            let result = get_state_snapshots(db_reader.as_ref(), &state_snapshot_scheduler, params);
+            async move { result }.boxed()
+        })
+    });
+
     // TODO: handle non-genesis waypoints for state sync!
     // If there's a genesis txn and waypoint, commit it if the result matches.
     let genesis_waypoint = node_config.base.waypoint.genesis_waypoint();
@@ -125,5 +183,104 @@ pub fn initialize_database_and_checkpoints(
         instant.elapsed().as_millis()
     );
 
-    Ok((aptos_db, db_rw, backup_service, genesis_waypoint))
+    Ok((
+        aptos_db,
+        db_rw,
+        backup_service,
+        genesis_waypoint,
+        state_snapshot_scheduler,
+    ))
+}
+
+/// Returns the storage usage forecast as JSON
+fn get_storage_forecast(forecaster: &StorageForecaster) -> AdminCommandResult {
+    let forecast = forecaster
+        .forecast()
+        .ok_or_else(|| "The DB sizes have not been sampled yet".to_string())?;
+    serde_json::to_string_pretty(&forecast)
+        .map_err(|error| format!("Failed to serialize the forecast: {:?}", error))
+}
+
+/// The state snapshots, as returned by the admin command
+#[derive(Serialize)]
+struct StateSnapshots {
+    schedule: StateSnapshotScheduleConfig,
+    latest_checkpoint_version: Option<u64>,
+    snapshot_versions: Vec<u64>,
+}
+
+/// Returns the versions of the latest state snapshots (at or before the `end_version`
+/// parameter, if given) and the current schedule as JSON
+fn get_state_snapshots(
+    db_reader: &dyn DbReader,
+    state_snapshot_scheduler: &StateSnapshotScheduler,
+    params: HashMap<String, String>,
+) -> AdminCommandResult {
+    let limit = parse_u64_param(&params, "limit", Some(DEFAULT_NUM_STATE_SNAPSHOTS))?;
+    let end_version = match params.contains_key("end_version") {
+        true => parse_u64_param(&params, "end_version", None)?,
+        false => db_reader
+            .get_latest_version()
+            .map_err(|error| format!("Failed to read the latest version: {:?}", error))?,
+    };
+    let latest_checkpoint_version = db_reader
+        .get_latest_state_checkpoint_version()
+        .map_err(|error| format!("Failed to read the latest checkpoint version: {:?}", error))?;
+    let snapshot_versions = db_reader
+        .get_state_snapshot_versions(end_version, limit as usize)
+        .map_err(|error| format!("Failed to read the state snapshot versions: {:?}", error))?;
+    let state_snapshots = StateSnapshots {
+        schedule: state_snapshot_scheduler.schedule(),
+        latest_checkpoint_version,
+        snapshot_versions,
+    };
+    serde_json::to_string_pretty(&state_snapshots)
+        .map_err(|error| format!("Failed to serialize the state snapshots: {:?}", error))
+}
+
+/// Applies the on-chain state snapshot config (if set) to the schedule of the state
+/// snapshots on every reconfiguration. Otherwise, the schedule in the node config is used.
+/// Note: the schedule is applied asynchronously, so it may only take effect a few versions
+/// after the reconfiguration.
+pub fn start_state_snapshot_schedule_updater(
+    node_config: &NodeConfig,
+    state_snapshot_scheduler: StateSnapshotScheduler,
+    epoch_change_notifier: &EpochChangeNotifier,
+) {
+    let mut reconfig_subscription = epoch_change_notifier.subscribe("state_snapshot_schedule");
+    let local_schedule = node_config.storage.state_snapshot_schedule;
+    thread::Builder::new()
+        .name("snapshot-schedule".into())
+        .spawn(move || {
+            futures::executor::block_on(async move {
+                while let Some(notification) = reconfig_subscription.next().await {
+                    let schedule =
+                        state_snapshot_schedule(&notification.on_chain_configs, local_schedule);
+                    state_snapshot_scheduler.set_schedule(schedule);
+                }
+            })
+        })
+        .expect("Failed to start the state snapshot schedule updater");
+}
+
+/// Returns the state snapshot schedule set on-chain, or the local one if none is set
+fn state_snapshot_schedule<P: OnChainConfigProvider>(
+    on_chain_configs: &OnChainConfigPayload<P>,
+    local_schedule: StateSnapshotScheduleConfig,
+) -> StateSnapshotScheduleConfig {
+    match on_chain_configs.get::<OnChainStateSnapshotConfig>() {
+        Ok(config) => StateSnapshotScheduleConfig {
+            interval_versions: config.interval_versions,
+            interval_blocks: config.interval_blocks,
+            interval_secs: config.interval_secs,
+        },
+        Err(error) => {
+            // This is expected if the config was never set on-chain
+            warn!(
+                "Failed to read the on-chain state snapshot config, using the local schedule. Error: {:?}",
+                error
+            );
+            local_schedule
+        },
+    }
 }
@@ -1,14 +1,40 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{create_single_node_test_config, network};
-use aptos_config::config::{NodeConfig, WaypointConfig};
+use crate::{
+    config_reload::{ConfigReloader, ReloadHandles},
+    create_single_node_test_config,
+    health_checks::{
+        register_health_checks, LatencySnapshot, StorageLatencyWindow, STORAGE_LATENCY_WINDOW,
+    },
+    network,
+    resource_budget::{BudgetedSubsystem, ResourceBudget},
+};
+use aptos_api::quota::Quota;
+use aptos_config::config::{
+    NodeConfig, QuotaTierConfig, ResourceBudgetConfig, SubsystemQuota, WaypointConfig,
+};
+use aptos_crypto::HashValue;
 use aptos_event_notifications::EventSubscriptionService;
-use aptos_infallible::RwLock;
+use aptos_infallible::{duration_since_epoch, RwLock};
+use aptos_inspection_service::{admin::AdminCommands, health::HealthChecks};
 use aptos_storage_interface::{DbReader, DbReaderWriter, DbWriter};
 use aptos_temppath::TempPath;
-use aptos_types::{chain_id::ChainId, waypoint::Waypoint};
-use std::{fs, sync::Arc};
+use aptos_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    chain_id::ChainId,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    waypoint::Waypoint,
+};
+use std::{
+    fs,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 /// A mock database implementing DbReader and DbWriter
 pub struct MockDatabase;
@@ -39,9 +65,134 @@ fn test_mutual_authentication_validators() {
         ChainId::test(),
         peers_and_metadata,
         &mut event_subscription_service,
+        &AdminCommands::new(),
     );
 }
 
+#[test]
+fn test_config_reload() {
+    // Create a config reloader that can only update the API quota
+    let mut node_config = NodeConfig::default();
+    node_config.api.quota.enabled = true;
+    let reload_handles = ReloadHandles {
+        api_quota: Some(Quota::new(&node_config.api.quota)),
+        ..Default::default()
+    };
+    let reloader = ConfigReloader::new(
+        TempPath::new().path().to_path_buf(),
+        node_config.clone(),
+        reload_handles,
+    );
+
+    // Change the quota tiers, and fields that can't be reloaded
+    let mut new_config = node_config.clone();
+    new_config
+        .api
+        .quota
+        .tiers
+        .insert("premium".into(), QuotaTierConfig::default());
+    new_config.mempool.capacity += 1;
+    new_config.inspection_service.port += 1;
+
+    // Verify only the quota tiers are applied
+    let outcome = reloader.apply(new_config.clone());
+    assert_eq!(outcome.applied.len(), 1);
+    assert_eq!(outcome.applied[0].field, "api.quota.tiers");
+    assert_eq!(outcome.restart_required, vec![
+        "inspection_service",
+        "mempool"
+    ]);
+
+    // Verify reapplying the same config doesn't change anything
+    let outcome = reloader.apply(new_config);
+    assert!(outcome.applied.is_empty());
+    assert_eq!(outcome.restart_required, vec![
+        "inspection_service",
+        "mempool"
+    ]);
+}
+
+/// A mock subsystem with the given memory usage, which records whether it sheds load
+#[derive(Clone, Default)]
+struct MockSubsystem {
+    name: &'static str,
+    memory_bytes: Arc<AtomicU64>,
+    shedding: Arc<AtomicBool>,
+}
+
+impl BudgetedSubsystem for MockSubsystem {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn memory_bytes(&self) -> u64 {
+        self.memory_bytes.load(Ordering::Relaxed)
+    }
+
+    fn shed(&self) {
+        self.shedding.store(true, Ordering::Relaxed);
+    }
+
+    fn restore(&self) {
+        self.shedding.store(false, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_resource_budget() {
+    // Create a resource budget for two subsystems (one with a memory quota)
+    let config = ResourceBudgetConfig {
+        max_memory_bytes: Some(1000),
+        restore_threshold_percent: 80,
+        subsystem_quotas: [("cache".to_string(), SubsystemQuota {
+            max_memory_bytes: Some(100),
+            ..Default::default()
+        })]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    };
+    let cache = MockSubsystem {
+        name: "cache",
+        ..Default::default()
+    };
+    let buffers = MockSubsystem {
+        name: "buffers",
+        ..Default::default()
+    };
+    let mut resource_budget =
+        ResourceBudget::new(&config, vec![Box::new(cache.clone()), Box::new(buffers)]);
+    let elapsed = Duration::from_secs(1);
+
+    // Verify nothing sheds load while within the budgets
+    cache.memory_bytes.store(100, Ordering::Relaxed);
+    resource_budget.check(500, None, elapsed);
+    assert!(resource_budget.shedding_subsystems().is_empty());
+
+    // Verify a subsystem over its quota sheds load
+    cache.memory_bytes.store(101, Ordering::Relaxed);
+    resource_budget.check(500, None, elapsed);
+    assert_eq!(resource_budget.shedding_subsystems(), vec!["cache"]);
+    assert!(cache.shedding.load(Ordering::Relaxed));
+
+    // Verify the subsystem keeps shedding until it falls below the restore threshold
+    cache.memory_bytes.store(90, Ordering::Relaxed);
+    resource_budget.check(500, None, elapsed);
+    assert_eq!(resource_budget.shedding_subsystems(), vec!["cache"]);
+    cache.memory_bytes.store(80, Ordering::Relaxed);
+    resource_budget.check(500, None, elapsed);
+    assert!(resource_budget.shedding_subsystems().is_empty());
+    assert!(!cache.shedding.load(Ordering::Relaxed));
+
+    // Verify all subsystems shed load while the node is over its budget
+    resource_budget.check(1001, None, elapsed);
+    assert_eq!(resource_budget.shedding_subsystems(), vec![
+        "cache", "buffers"
+    ]);
+    resource_budget.check(700, None, elapsed);
+    assert!(resource_budget.shedding_subsystems().is_empty());
+}
+
 #[cfg(feature = "check-vm-features")]
 #[test]
 fn test_aptos_vm_does_not_have_test_natives() {
@@ -100,3 +251,115 @@ fn test_create_single_node_test_config() {
             .bootstrapping_mode
     );
 }
+
+/// A mock database whose latest ledger info was committed at the given time
+struct MockLedgerDatabase {
+    commit_timestamp: Duration,
+}
+
+impl DbReader for MockLedgerDatabase {
+    fn get_latest_ledger_info_option(&self) -> anyhow::Result<Option<LedgerInfoWithSignatures>> {
+        let timestamp_usecs = self.commit_timestamp.as_micros() as u64;
+        let block_info = BlockInfo::new(
+            1,
+            10,
+            HashValue::zero(),
+            HashValue::zero(),
+            100,
+            timestamp_usecs,
+            None,
+        );
+        Ok(Some(LedgerInfoWithSignatures::new(
+            LedgerInfo::new(block_info, HashValue::zero()),
+            AggregateSignature::empty(),
+        )))
+    }
+}
+
+#[tokio::test]
+async fn test_health_checks() {
+    // Register the health checks of a validator whose latest block was committed 45 seconds ago
+    let mut node_config = NodeConfig::get_default_validator_config();
+    node_config.api.enabled = false;
+    let commit_timestamp = duration_since_epoch() - Duration::from_secs(45);
+    let health_checks = HealthChecks::new();
+    register_health_checks(
+        &node_config,
+        Arc::new(MockLedgerDatabase { commit_timestamp }),
+        &health_checks,
+    );
+    assert_eq!(health_checks.names(), vec![
+        "consensus",
+        "mempool",
+        "state_sync",
+        "storage"
+    ]);
+
+    // Verify consensus is unhealthy (after 30 seconds), but state sync isn't (until 60 seconds)
+    let results = health_checks.run().await;
+    assert!(!results["consensus"].healthy);
+    assert!(results["state_sync"].healthy);
+    assert!(results["mempool"].healthy);
+
+    // Verify concurrent probes get the same results
+    let (results_1, results_2) = futures::join!(health_checks.run(), health_checks.run());
+    for (name, result) in results {
+        assert_eq!(results_1[&name].healthy, result.healthy);
+        assert_eq!(results_2[&name].healthy, result.healthy);
+    }
+
+    // Verify consensus isn't checked on fullnodes, and state sync is unhealthy after 60 seconds
+    let node_config = NodeConfig::get_default_pfn_config();
+    let commit_timestamp = duration_since_epoch() - Duration::from_secs(90);
+    let health_checks = HealthChecks::new();
+    register_health_checks(
+        &node_config,
+        Arc::new(MockLedgerDatabase { commit_timestamp }),
+        &health_checks,
+    );
+    assert!(!health_checks.names().contains(&"consensus".to_string()));
+    let results = health_checks.run().await;
+    assert!(!results["state_sync"].healthy);
+}
+
+#[test]
+fn test_storage_latency_window() {
+    let start_time = Instant::now();
+    let snapshot = |elapsed: Duration, sample_sum, sample_count| LatencySnapshot {
+        time: start_time + elapsed,
+        sample_sum,
+        sample_count,
+    };
+    let half_window = STORAGE_LATENCY_WINDOW / 2;
+    let mut latency_window = StorageLatencyWindow::new(snapshot(Duration::ZERO, 1.0, 10));
+
+    // Verify the average latency of the writes since the start (10 writes, 2.5 seconds)
+    let average = (10, Duration::from_millis(250), half_window);
+    assert_eq!(
+        latency_window.observe(snapshot(half_window, 3.5, 20)),
+        average
+    );
+
+    // Verify repeated probes (i.e., within the window) compute the average from the same base
+    assert_eq!(
+        latency_window.observe(snapshot(half_window, 3.5, 20)),
+        average
+    );
+    let (num_writes, _, _) = latency_window.observe(snapshot(half_window * 3 / 2, 3.5, 20));
+    assert_eq!(num_writes, 10);
+
+    // Verify the base is kept for a window, so the average is over at least a window
+    let average = (20, Duration::from_millis(250), STORAGE_LATENCY_WINDOW);
+    assert_eq!(
+        latency_window.observe(snapshot(STORAGE_LATENCY_WINDOW, 6.0, 30)),
+        average
+    );
+
+    // Verify the old writes are dropped once the base is rotated
+    let two_windows = STORAGE_LATENCY_WINDOW * 2;
+    let no_writes = (0, Duration::ZERO, STORAGE_LATENCY_WINDOW);
+    assert_eq!(
+        latency_window.observe(snapshot(two_windows, 6.0, 30)),
+        no_writes
+    );
+}
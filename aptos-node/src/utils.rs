@@ -4,11 +4,14 @@
 use anyhow::anyhow;
 use aptos_config::config::NodeConfig;
 use aptos_state_view::account_with_state_view::AsAccountWithStateView;
-use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReaderWriter};
+use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReader, DbReaderWriter};
 use aptos_types::{
-    account_config::CORE_CODE_ADDRESS, account_view::AccountView, chain_id::ChainId,
+    account_config::{BlockResource, CORE_CODE_ADDRESS},
+    account_view::AccountView,
+    chain_id::ChainId,
 };
 use aptos_vm::AptosVM;
+use std::{sync::Arc, time::Duration};
 
 /// Error message to display when non-production features are enabled
 pub const ERROR_MSG_BAD_FEATURE_FLAGS: &str = r#"
@@ -46,6 +49,28 @@ pub fn fetch_chain_id(db: &DbReaderWriter) -> anyhow::Result<ChainId> {
         .chain_id())
 }
 
+/// Fetches the time (since the Unix epoch) at which the current epoch ends, i.e., the
+/// first block with a timestamp at or after this time starts a new epoch
+pub fn fetch_epoch_end_time(db: &Arc<dyn DbReader>) -> anyhow::Result<Duration> {
+    let db_state_view = db
+        .latest_state_checkpoint_view()
+        .map_err(|err| anyhow!("[aptos-node] failed to create db state view {}", err))?;
+    let account_view = db_state_view.as_account_with_state_view(&CORE_CODE_ADDRESS);
+    let last_reconfiguration_time_usecs = account_view
+        .get_configuration_resource()
+        .map_err(|err| anyhow!("[aptos-node] failed to get configuration resource {}", err))?
+        .ok_or_else(|| anyhow!("[aptos-node] missing configuration resource"))?
+        .last_reconfiguration_time();
+    let epoch_interval_usecs = account_view
+        .get_move_resource::<BlockResource>()
+        .map_err(|err| anyhow!("[aptos-node] failed to get block resource {}", err))?
+        .ok_or_else(|| anyhow!("[aptos-node] missing block resource"))?
+        .epoch_interval();
+    Ok(Duration::from_micros(
+        last_reconfiguration_time_usecs.saturating_add(epoch_interval_usecs),
+    ))
+}
+
 /// Sets the Aptos VM configuration based on the node configurations
 pub fn set_aptos_vm_configurations(node_config: &NodeConfig) {
     AptosVM::set_paranoid_type_checks(node_config.execution.paranoid_type_verification);
@@ -11,6 +11,7 @@
 
 /// Definitions of global cryptographic keys (e.g., as held in secure storage)
 pub const CONSENSUS_KEY: &str = "consensus";
+pub const CONSENSUS_KEY_STANDBY: &str = "consensus_standby";
 pub const OWNER_ACCOUNT: &str = "owner_account";
 
 /// Definitions of global data items (e.g., as held in secure storage)
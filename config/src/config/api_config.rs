@@ -11,7 +11,7 @@ use crate::{
 };
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use std::{collections::BTreeMap, net::SocketAddr};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -48,8 +48,17 @@ pub struct ApiConfig {
     /// Enables transaction simulation
     #[serde(default = "default_enabled")]
     pub transaction_simulation_enabled: bool,
+    /// Enables the mempool diagnostics API (e.g., for debugging stuck transactions)
+    #[serde(default = "default_disabled")]
+    pub mempool_diagnostics_enabled: bool,
     /// Maximum number of transactions that can be sent with the Batch submit API
     pub max_submit_transaction_batch_size: usize,
+    /// Maximum number of seconds a transaction submission can wait for the
+    /// transaction to be committed (see the `wait_for` parameter)
+    pub max_submit_wait_secs: u64,
+    /// Maximum number of transaction submissions waiting at the same time. Once
+    /// reached, submissions return without waiting.
+    pub max_submit_waiters: usize,
     /// Maximum page size for transaction paginated APIs
     pub max_transactions_page_size: u16,
     /// Maximum page size for event paginated APIs
@@ -58,10 +67,16 @@ pub struct ApiConfig {
     pub max_account_resources_page_size: u16,
     /// Maximum page size for module paginated APIs
     pub max_account_modules_page_size: u16,
+    /// Maximum page size for indexed table item paginated APIs
+    pub max_table_items_page_size: u16,
     /// Maximum gas unit limit for view functions
     ///
     /// This limits the execution length of a view function to the given gas used.
     pub max_gas_view_function: u64,
+    /// Maximum number of view functions that can be executed with the Batch view API
+    pub max_view_function_batch_size: usize,
+    /// Maximum number of state overrides that can be sent with the simulate API
+    pub max_simulation_state_overrides: usize,
     /// Optional: Maximum number of worker threads for the API.
     ///
     /// If not set, `runtime_worker_multiplier` will multiply times the number of CPU cores on the machine
@@ -72,15 +87,25 @@ pub struct ApiConfig {
     pub runtime_worker_multiplier: usize,
     /// Configs for computing unit gas price estimation
     pub gas_estimation: GasEstimationConfig,
+    /// Configs for the streaming (SSE and WebSocket) endpoint
+    pub stream: StreamConfig,
+    /// Configs for per-API-key quotas and rate limiting
+    pub quota: QuotaConfig,
+    /// Configs for simulating sponsored (i.e., fee payer) transactions
+    pub gas_station: GasStationConfig,
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
 const DEFAULT_PORT: u16 = 8080;
 const DEFAULT_REQUEST_CONTENT_LENGTH_LIMIT: u64 = 8 * 1024 * 1024; // 8 MB
 pub const DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE: usize = 10;
+const DEFAULT_MAX_SUBMIT_WAIT_SECS: u64 = 30;
+const DEFAULT_MAX_SUBMIT_WAITERS: usize = 100;
 pub const DEFAULT_MAX_PAGE_SIZE: u16 = 100;
 const DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE: u16 = 9999;
+pub const DEFAULT_MAX_VIEW_FUNCTION_BATCH_SIZE: usize = 20;
+pub const DEFAULT_MAX_SIMULATION_STATE_OVERRIDES: usize = 100;
 const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000; // We keep this value the same as the max number of gas allowed for one single transaction defined in aptos-gas.
 
 fn default_enabled() -> bool {
@@ -107,15 +132,24 @@ impl Default for ApiConfig {
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
             transaction_simulation_enabled: default_enabled(),
+            mempool_diagnostics_enabled: default_disabled(),
             max_submit_transaction_batch_size: DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE,
+            max_submit_wait_secs: DEFAULT_MAX_SUBMIT_WAIT_SECS,
+            max_submit_waiters: DEFAULT_MAX_SUBMIT_WAITERS,
             max_transactions_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_events_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_account_resources_page_size: DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE,
             max_account_modules_page_size: DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE,
+            max_table_items_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_gas_view_function: DEFAULT_MAX_VIEW_GAS,
+            max_view_function_batch_size: DEFAULT_MAX_VIEW_FUNCTION_BATCH_SIZE,
+            max_simulation_state_overrides: DEFAULT_MAX_SIMULATION_STATE_OVERRIDES,
             max_runtime_workers: None,
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
+            stream: StreamConfig::default(),
+            quota: QuotaConfig::default(),
+            gas_station: GasStationConfig::default(),
         }
     }
 }
@@ -133,6 +167,138 @@ impl ApiConfig {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StreamConfig {
+    /// Enables the streaming endpoint
+    pub enabled: bool,
+    /// Maximum number of concurrent stream connections
+    pub max_connections: usize,
+    /// Maximum number of messages buffered for each connection. Once full,
+    /// the connection stops reading new data until the client catches up.
+    pub max_buffered_messages: usize,
+    /// Maximum number of versions a connection can fall behind the latest
+    /// ledger version before it is closed
+    pub max_lag_versions: u64,
+    /// Maximum number of versions a new connection can replay, i.e., how far
+    /// behind the latest ledger version its `start_version` can be
+    pub max_replay_versions: u64,
+    /// Interval to check for new commits, when storage commit notifications
+    /// are unavailable (e.g., in tests)
+    pub poll_interval_ms: u64,
+    /// Interval to send keep-alive messages on idle connections
+    pub keep_alive_interval_ms: u64,
+}
+
+impl Default for StreamConfig {
+    fn default() -> StreamConfig {
+        StreamConfig {
+            enabled: false,
+            max_connections: 100,
+            max_buffered_messages: 100,
+            max_lag_versions: 100_000,
+            max_replay_versions: 1_000,
+            poll_interval_ms: 500,
+            keep_alive_interval_ms: 15_000,
+        }
+    }
+}
+
+const DEFAULT_QUOTA_TIER: &str = "anonymous";
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuotaConfig {
+    /// Enables per-API-key quotas and rate limiting
+    pub enabled: bool,
+    /// The header to read API keys from. API keys can also be sent as
+    /// bearer tokens in the `Authorization` header.
+    pub api_key_header: String,
+    /// The tier of requests without an API key. These requests are limited
+    /// by client IP address.
+    pub anonymous_tier: String,
+    /// The quota tiers, by name
+    pub tiers: BTreeMap<String, QuotaTierConfig>,
+    /// The tier of each API key. Requests with unknown API keys are rejected.
+    pub api_keys: BTreeMap<String, String>,
+    /// The header holding the client IP address of anonymous requests, when
+    /// the node is behind a load balancer (e.g., `x-forwarded-for`). The last
+    /// address of the header is used, so the load balancer must append the
+    /// address it received the request from. If unset (or the header is
+    /// missing), the address of the connection is used.
+    pub client_ip_header: Option<String>,
+    /// The amount of view function gas that is charged as one compute unit
+    pub view_gas_per_compute_unit: u64,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> QuotaConfig {
+        QuotaConfig {
+            enabled: false,
+            api_key_header: "x-api-key".to_string(),
+            anonymous_tier: DEFAULT_QUOTA_TIER.to_string(),
+            tiers: BTreeMap::from([(DEFAULT_QUOTA_TIER.to_string(), QuotaTierConfig::default())]),
+            api_keys: BTreeMap::new(),
+            client_ip_header: None,
+            view_gas_per_compute_unit: 10_000,
+        }
+    }
+}
+
+/// The budgets of a quota tier. Each API key (or client IP address, for
+/// anonymous requests) in the tier has its own budgets.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuotaTierConfig {
+    /// Number of requests added to the request budget every second
+    pub requests_per_second: u64,
+    /// Maximum size of the request budget (i.e., the allowed burst)
+    pub max_requests: u64,
+    /// Number of compute units added to the compute budget every second.
+    /// Each request costs one compute unit, plus the gas used by any view
+    /// functions it executes.
+    pub compute_units_per_second: u64,
+    /// Maximum size of the compute budget (i.e., the allowed burst)
+    pub max_compute_units: u64,
+}
+
+impl Default for QuotaTierConfig {
+    fn default() -> QuotaTierConfig {
+        QuotaTierConfig {
+            requests_per_second: 10,
+            max_requests: 50,
+            compute_units_per_second: 200,
+            max_compute_units: 1_000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GasStationConfig {
+    /// Enables the sponsored transaction simulation endpoint. This allows a fee
+    /// payer to simulate paying for a transaction without the sender's signature.
+    /// Disabled by default, as the endpoint is unauthenticated and may call the
+    /// gas station webhook.
+    pub sponsored_simulation_enabled: bool,
+    /// Optional: The URL of an external gas station that is asked to approve the
+    /// sponsorship of each successfully simulated transaction. The simulation
+    /// results are POSTed to the URL as JSON.
+    pub webhook_url: Option<String>,
+    /// Timeout for the gas station to respond to a sponsorship request
+    pub webhook_timeout_ms: u64,
+}
+
+impl Default for GasStationConfig {
+    fn default() -> GasStationConfig {
+        GasStationConfig {
+            sponsored_simulation_enabled: false,
+            webhook_url: None,
+            webhook_timeout_ms: 2_000,
+        }
+    }
+}
+
 impl ConfigSanitizer for ApiConfig {
     fn sanitize(
         node_config: &mut NodeConfig,
@@ -163,6 +329,62 @@ impl ConfigSanitizer for ApiConfig {
             ));
         }
 
+        // Validate the stream config
+        let stream_config = &api_config.stream;
+        if stream_config.enabled
+            && (stream_config.max_buffered_messages == 0 || stream_config.poll_interval_ms == 0)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "stream max_buffered_messages and poll_interval_ms must be greater than 0!".into(),
+            ));
+        }
+
+        // Validate the quota config
+        let quota_config = &api_config.quota;
+        if quota_config.enabled {
+            if quota_config.view_gas_per_compute_unit == 0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "quota view_gas_per_compute_unit must be greater than 0!".into(),
+                ));
+            }
+            for (name, tier) in &quota_config.tiers {
+                if tier.requests_per_second == 0
+                    || tier.compute_units_per_second == 0
+                    || tier.max_requests < tier.requests_per_second
+                    || tier.max_compute_units < tier.compute_units_per_second
+                {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        format!(
+                            "quota tier {} must have non-zero rates, and budgets no smaller than its rates!",
+                            name
+                        ),
+                    ));
+                }
+            }
+            let tier_names =
+                std::iter::once(&quota_config.anonymous_tier).chain(quota_config.api_keys.values());
+            for tier_name in tier_names {
+                if !quota_config.tiers.contains_key(tier_name) {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        format!("quota tier {} is not defined!", tier_name),
+                    ));
+                }
+            }
+        }
+
+        // Validate the gas station config
+        let gas_station_config = &api_config.gas_station;
+        if gas_station_config.webhook_url.is_some() && gas_station_config.webhook_timeout_ms == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "gas station webhook_timeout_ms must be greater than 0!".into(),
+            ));
+        }
+
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
 
         Ok(())
@@ -227,4 +449,73 @@ mod tests {
             .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_stream_config() {
+        // Create a node config with an empty stream message buffer
+        let mut node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                stream: StreamConfig {
+                    enabled: true,
+                    max_buffered_messages: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because
+        // the stream message buffer is empty.
+        let error = ApiConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_undefined_quota_tier() {
+        // Create a node config with an API key in an undefined tier
+        let mut node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                quota: QuotaConfig {
+                    enabled: true,
+                    api_keys: BTreeMap::from([("key".to_string(), "undefined".to_string())]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because
+        // the tier of the API key is not defined.
+        let error = ApiConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_invalid_gas_station_config() {
+        // Create a node config with a gas station webhook that can never respond
+        let mut node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                gas_station: GasStationConfig {
+                    webhook_url: Some("http://localhost:8000/sponsor".to_string()),
+                    webhook_timeout_ms: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because
+        // the webhook timeout is zero.
+        let error = ApiConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
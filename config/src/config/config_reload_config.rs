@@ -0,0 +1,42 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The name of the audit log of the reloaded config changes (in the data directory)
+const DEFAULT_AUDIT_LOG_FILE: &str = "config_reload_audit.log";
+
+/// Configures how the reloadable fields of the node config (e.g., the log level and
+/// the mempool capacity) are applied while the node is running. Reloads can always
+/// be requested with the `config/reload` admin command.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigReloadConfig {
+    /// Whether to watch the node config file, and reload it whenever it changes
+    pub watch_config_file: bool,
+    /// The interval (ms) between checks of the node config file
+    pub watch_interval_ms: u64,
+    /// The file to which the applied changes are appended. Defaults to a file
+    /// in the data directory.
+    pub audit_log_path: Option<PathBuf>,
+}
+
+impl Default for ConfigReloadConfig {
+    fn default() -> ConfigReloadConfig {
+        ConfigReloadConfig {
+            watch_config_file: false,
+            watch_interval_ms: 10_000,
+            audit_log_path: None,
+        }
+    }
+}
+
+impl ConfigReloadConfig {
+    /// Returns the path of the audit log, given the data directory of the node
+    pub fn audit_log_path(&self, data_dir: &Path) -> PathBuf {
+        self.audit_log_path
+            .clone()
+            .unwrap_or_else(|| data_dir.join(DEFAULT_AUDIT_LOG_FILE))
+    }
+}
@@ -4,9 +4,9 @@
 use crate::config::{
     node_config_loader::NodeType,
     utils::{are_failpoints_enabled, get_config_name},
-    ApiConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig, IndexerConfig,
-    IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig, NodeConfig,
-    PeerMonitoringServiceConfig, StateSyncConfig, StorageConfig,
+    ApiConfig, BaseConfig, ConsensusConfig, ConsensusObserverConfig, Error, ExecutionConfig,
+    IndexerConfig, IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
+    NetworkConfig, NodeConfig, PeerMonitoringServiceConfig, StateSyncConfig, StorageConfig,
 };
 use aptos_types::chain_id::ChainId;
 use std::collections::HashSet;
@@ -45,6 +45,7 @@ impl ConfigSanitizer for NodeConfig {
         ApiConfig::sanitize(node_config, node_type, chain_id)?;
         BaseConfig::sanitize(node_config, node_type, chain_id)?;
         ConsensusConfig::sanitize(node_config, node_type, chain_id)?;
+        ConsensusObserverConfig::sanitize(node_config, node_type, chain_id)?;
         ExecutionConfig::sanitize(node_config, node_type, chain_id)?;
         sanitize_failpoints_config(node_config, node_type, chain_id)?;
         sanitize_fullnode_network_configs(node_config, node_type, chain_id)?;
@@ -139,6 +140,9 @@ fn sanitize_fullnode_network_configs(
             ));
         }
 
+        // Verify the protocol bandwidth limits
+        sanitize_protocol_bandwidth_limits(&sanitizer_name, fullnode_network_config)?;
+
         // Prepare the network id
         fullnode_network_config.set_listen_address_and_prepare_identity()?;
     }
@@ -189,6 +193,9 @@ fn sanitize_validator_network_config(
             ));
         }
 
+        // Verify the protocol bandwidth limits
+        sanitize_protocol_bandwidth_limits(&sanitizer_name, validator_network_config)?;
+
         // Prepare the network id
         validator_network_config.set_listen_address_and_prepare_identity()?;
     }
@@ -196,10 +203,43 @@ fn sanitize_validator_network_config(
     Ok(())
 }
 
+/// Sanitize the protocol bandwidth limits of the given network config
+fn sanitize_protocol_bandwidth_limits(
+    sanitizer_name: &str,
+    network_config: &NetworkConfig,
+) -> Result<(), Error> {
+    let mut limited_protocols = HashSet::new();
+    for limit in &network_config.outbound_protocol_bandwidth_limits {
+        // Verify that each protocol is only limited once
+        if !limited_protocols.insert(limit.protocol.as_str()) {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name.to_string(),
+                format!(
+                    "Each protocol bandwidth limit must be unique! Found duplicate: {}",
+                    limit.protocol
+                ),
+            ));
+        }
+
+        // Verify that the bucket can be filled and can hold at least one second of data
+        if limit.byte_bucket_rate == 0 || limit.byte_bucket_size < limit.byte_bucket_rate {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name.to_string(),
+                format!(
+                    "The bandwidth limit for protocol {} must have a non-zero rate and a bucket size >= rate! Rate: {}, size: {}",
+                    limit.protocol, limit.byte_bucket_rate, limit.byte_bucket_size
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{config::NetworkConfig, network_id::NetworkId};
+    use crate::{config::ProtocolBandwidthLimitConfig, network_id::NetworkId};
 
     #[test]
     fn test_sanitize_missing_pfn_network_configs() {
@@ -367,4 +407,54 @@ mod tests {
         .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_protocol_bandwidth_limits() {
+        // Create a fullnode config with a bandwidth limit smaller than its fill rate
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                outbound_protocol_bandwidth_limits: vec![ProtocolBandwidthLimitConfig {
+                    protocol: "StorageServiceRpc".into(),
+                    byte_bucket_rate: 1024,
+                    byte_bucket_size: 512,
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a fullnode config with duplicate bandwidth limits
+        let limit = ProtocolBandwidthLimitConfig {
+            protocol: "StorageServiceRpc".into(),
+            byte_bucket_rate: 1024,
+            byte_bucket_size: 1024,
+        };
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                outbound_protocol_bandwidth_limits: vec![limit.clone(), limit],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
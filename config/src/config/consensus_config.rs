@@ -32,9 +32,18 @@ pub struct ConsensusConfig {
     pub mempool_executed_txn_timeout_ms: u64,
     // Timeout for consensus to pull transactions from mempool and get a response (in milliseconds)
     pub mempool_txn_pull_timeout_ms: u64,
+    // Number of rounds (starting from the current one) whose proposers are sent to mempool, so
+    // that mempool prioritizes its broadcasts to them. Disabled if 0 (the default). Has no effect
+    // under quorum store, as validators don't broadcast transactions to each other then.
+    pub mempool_proposer_lookahead_rounds: u64,
     pub round_initial_timeout_ms: u64,
     pub round_timeout_backoff_exponent_base: f64,
     pub round_timeout_backoff_max_exponent: usize,
+    // Max random jitter added to the round timeouts, as a percentage of the timeout
+    pub round_timeout_jitter_percent: u64,
+    // Whether to use the round timeout parameters above, even if the on-chain consensus
+    // config sets them (e.g., to tune the liveness of a single node under WAN stress).
+    pub override_onchain_round_timeout: bool,
     pub safety_rules: SafetyRulesConfig,
     // Only sync committed transactions but not vote for any pending blocks. This is useful when
     // validators coordinate on the latest version to apply a manual transaction.
@@ -65,6 +74,29 @@ pub struct ConsensusConfig {
     // must match one of the CHAIN_HEALTH_WINDOW_SIZES values.
     pub window_for_chain_health: usize,
     pub chain_health_backoff: Vec<ChainHealthBackoffValues>,
+    // If set, the number of txns of a proposal is capped by the execution throughput of the
+    // pending blocks (as reported in their execution profiles).
+    pub execution_backpressure: Option<ExecutionBackpressureValues>,
+    // Whether to archive the commit and timeout certificates observed in each epoch, so they
+    // can be exported for external audits (see aptos-db-tool export-certificates).
+    pub enable_certificate_archive: bool,
+    // The number of most recent epochs whose certificates are kept in the archive. The older
+    // epochs are pruned whenever an epoch ends. 0 keeps every epoch.
+    pub certificate_archive_retention_epochs: u64,
+    // Whether to start executing proposed blocks as soon as they are received (before they are
+    // certified), and reuse the result once they are ordered. Only used with decoupled execution.
+    pub enable_optimistic_execution: bool,
+    // If set, the inbound consensus messages are recorded to this file, so they can be
+    // replayed into a test consensus instance (see consensus/src/message_recorder.rs).
+    pub message_recording_path: Option<PathBuf>,
+    // Our latest commit certificate is gossiped to the peers whose highest commit round is at
+    // least this many rounds behind ours, so that they fast-forward via state sync. 0 disables it.
+    pub commit_gossip_round_lag: u64,
+    // Min interval between two commit certificates gossiped to the same peer
+    pub commit_gossip_interval_ms: u64,
+    // Whether to accept the admin commands that move the chain forward in time, force epoch
+    // changes and mine empty blocks. Only allowed on single node local test chains.
+    pub enable_time_travel: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -80,6 +112,14 @@ pub struct PipelineBackpressureValues {
     pub backpressure_proposal_delay_ms: u64,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ExecutionBackpressureValues {
+    // The time it should take to execute a proposed block, at the recent execution throughput
+    pub target_block_execution_time_ms: u64,
+    // The proposals are never capped below this number of txns
+    pub min_sending_block_txns: u64,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct ChainHealthBackoffValues {
     pub backoff_if_below_participating_voting_power_percentage: usize,
@@ -109,11 +149,14 @@ impl Default for ConsensusConfig {
             max_pruned_blocks_in_mem: 100,
             mempool_executed_txn_timeout_ms: 1000,
             mempool_txn_pull_timeout_ms: 1000,
+            mempool_proposer_lookahead_rounds: 0,
             round_initial_timeout_ms: 1500,
             // 1.2^6 ~= 3
             // Timeout goes from initial_timeout to initial_timeout*3 in 6 steps
             round_timeout_backoff_exponent_base: 1.2,
             round_timeout_backoff_max_exponent: 6,
+            round_timeout_jitter_percent: 0,
+            override_onchain_round_timeout: false,
             safety_rules: SafetyRulesConfig::default(),
             sync_only: false,
             channel_size: 30, // hard-coded
@@ -205,6 +248,14 @@ impl Default for ConsensusConfig {
                     backoff_proposal_delay_ms: 300,
                 },
             ],
+            execution_backpressure: None,
+            enable_certificate_archive: false,
+            certificate_archive_retention_epochs: 100,
+            enable_optimistic_execution: false,
+            message_recording_path: None,
+            commit_gossip_round_lag: 20,
+            commit_gossip_interval_ms: 1000,
+            enable_time_travel: false,
         }
     }
 }
@@ -336,6 +387,13 @@ impl ConsensusConfig {
                 ),
             ));
         }
+        if let Some(execution_backpressure) = &config.execution_backpressure {
+            recv_batch_send_block_pairs.push((
+                config.quorum_store.receiver_max_batch_txns as u64,
+                execution_backpressure.min_sending_block_txns,
+                "execution backpressure: txns".to_string(),
+            ));
+        }
 
         for (batch, block, label) in &recv_batch_send_block_pairs {
             if *batch > *block {
@@ -369,6 +427,22 @@ impl ConfigSanitizer for ConsensusConfig {
             ));
         }
 
+        // Verify that time travel is only enabled on local test chains
+        if node_config.consensus.enable_time_travel && chain_id != ChainId::test() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Time travel can only be enabled on local test chains!".to_string(),
+            ));
+        }
+
+        // Verify that the round timeout jitter is a valid percentage
+        if node_config.consensus.round_timeout_jitter_percent > 100 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The round timeout jitter percent must be <= 100!".to_string(),
+            ));
+        }
+
         // Sender block limits must be <= receiver block limits
         Self::sanitize_send_recv_block_limits(&sanitizer_name, &node_config.consensus)?;
         // Quorum store batches must be <= consensus blocks
@@ -400,4 +474,42 @@ mod test {
 
         serde_yaml::from_str::<ConsensusConfig>(&s).unwrap();
     }
+    #[test]
+    fn test_sanitize_time_travel() {
+        // Create a node config with time travel enabled
+        let mut node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                enable_time_travel: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify the config is valid for local test chains
+        ConsensusConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::test()).unwrap();
+
+        // Verify the config fails sanitization for testnet
+        let error =
+            ConsensusConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_round_timeout_jitter() {
+        // Create a node config with an invalid round timeout jitter
+        let mut node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                round_timeout_jitter_percent: 101,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify the config fails sanitization
+        let error =
+            ConsensusConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
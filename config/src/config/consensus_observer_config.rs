@@ -0,0 +1,148 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsensusObserverConfig {
+    /// Whether the consensus observer is enabled. If so, the node subscribes to a
+    /// publisher (e.g., its validator) and executes the ordered blocks it receives,
+    /// instead of relying on state sync to catch up.
+    pub observer_enabled: bool,
+    /// Whether the consensus publisher is enabled. If so, the node publishes the
+    /// ordered blocks and commit decisions to its subscribed observers.
+    pub publisher_enabled: bool,
+
+    /// Maximum number of pending network messages
+    pub max_network_channel_size: u64,
+    /// Maximum number of ordered blocks the observer buffers before they're committed
+    pub max_num_pending_blocks: u64,
+    /// Maximum number of subscribers a publisher accepts
+    pub max_num_subscribers: u64,
+    /// Timeout (ms) for subscription requests
+    pub network_request_timeout_ms: u64,
+    /// Interval (ms) at which the observer checks the health of its subscription
+    pub observer_progress_check_interval_ms: u64,
+    /// Maximum time (ms) without messages from the publisher before the observer
+    /// terminates the subscription and falls back to state sync.
+    pub max_subscription_timeout_ms: u64,
+}
+
+impl Default for ConsensusObserverConfig {
+    fn default() -> Self {
+        Self {
+            observer_enabled: false,
+            publisher_enabled: false,
+            max_network_channel_size: 1000,
+            max_num_pending_blocks: 100,
+            max_num_subscribers: 10,
+            network_request_timeout_ms: 5_000,          // 5 seconds
+            observer_progress_check_interval_ms: 5_000, // 5 seconds
+            max_subscription_timeout_ms: 15_000,        // 15 seconds
+        }
+    }
+}
+
+impl ConsensusObserverConfig {
+    /// Returns true iff the observer or the publisher is enabled
+    pub fn observer_or_publisher_enabled(&self) -> bool {
+        self.observer_enabled || self.publisher_enabled
+    }
+}
+
+impl ConfigSanitizer for ConsensusObserverConfig {
+    fn sanitize(
+        node_config: &mut NodeConfig,
+        node_type: NodeType,
+        _chain_id: ChainId,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let consensus_observer_config = &node_config.consensus_observer;
+
+        // Validators participate in consensus, so they can't observe it
+        if node_type.is_validator() && consensus_observer_config.observer_enabled {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The consensus observer cannot be enabled on validators!".to_string(),
+            ));
+        }
+
+        // Only validators order blocks, so only they can publish them
+        if !node_type.is_validator() && consensus_observer_config.publisher_enabled {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The consensus publisher can only be enabled on validators!".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_observer_on_validator() {
+        // Create a node config with the observer enabled
+        let mut node_config = NodeConfig {
+            consensus_observer: ConsensusObserverConfig {
+                observer_enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify the config is valid for VFNs
+        ConsensusObserverConfig::sanitize(
+            &mut node_config,
+            NodeType::ValidatorFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap();
+
+        // Verify the config fails sanitization for validators
+        let error = ConsensusObserverConfig::sanitize(
+            &mut node_config,
+            NodeType::Validator,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_publisher_on_fullnode() {
+        // Create a node config with the publisher enabled
+        let mut node_config = NodeConfig {
+            consensus_observer: ConsensusObserverConfig {
+                publisher_enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify the config is valid for validators
+        ConsensusObserverConfig::sanitize(
+            &mut node_config,
+            NodeType::Validator,
+            ChainId::mainnet(),
+        )
+        .unwrap();
+
+        // Verify the config fails sanitization for fullnodes
+        let error = ConsensusObserverConfig::sanitize(
+            &mut node_config,
+            NodeType::ValidatorFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+}
@@ -34,6 +34,14 @@ pub struct ExecutionConfig {
     pub paranoid_hot_potato_verification: bool,
     /// Enables enhanced metrics around processed transactions
     pub processed_transactions_detailed_counters: bool,
+    /// Number of most recently committed blocks whose state (i.e., the keys they wrote and the
+    /// modules they called) is read at startup, before consensus executes any block, to warm up
+    /// the caches. Set to 0 to disable.
+    pub num_warmup_blocks: u64,
+    /// Maximum number of state keys read by the warm-up
+    pub warmup_max_num_keys: usize,
+    /// Maximum time spent collecting the state keys read by the warm-up (in milliseconds)
+    pub warmup_time_budget_ms: u64,
 }
 
 impl std::fmt::Debug for ExecutionConfig {
@@ -63,6 +71,9 @@ impl Default for ExecutionConfig {
             paranoid_type_verification: true,
             paranoid_hot_potato_verification: true,
             processed_transactions_detailed_counters: false,
+            num_warmup_blocks: 0,
+            warmup_max_num_keys: 100_000,
+            warmup_time_budget_ms: 10_000,
         }
     }
 }
@@ -30,6 +30,10 @@ pub struct GasEstimationConfig {
     pub market_block_history: usize,
     /// Maximum number of blocks read for aggressive gas estimation
     pub aggressive_block_history: usize,
+    /// Maximum number of blocks read for the gas price percentiles
+    pub percentile_block_history: usize,
+    /// Percentage added on top of the simulated gas used, when suggesting a max gas amount
+    pub max_gas_amount_safety_margin_percent: u64,
     /// Time after write when previous value is returned without recomputing
     pub cache_expiration_ms: u64,
 }
@@ -43,6 +47,8 @@ impl Default for GasEstimationConfig {
             low_block_history: 10,
             market_block_history: 30,
             aggressive_block_history: 120,
+            percentile_block_history: 120,
+            max_gas_amount_safety_margin_percent: 20,
             cache_expiration_ms: 500,
         }
     }
@@ -76,14 +82,16 @@ impl ConfigSanitizer for GasEstimationConfig {
         if gas_estimation_config.low_block_history == 0
             || gas_estimation_config.market_block_history == 0
             || gas_estimation_config.aggressive_block_history == 0
+            || gas_estimation_config.percentile_block_history == 0
         {
             return Err(Error::ConfigSanitizerFailed(
                 sanitizer_name,
                 format!(
-                    "low {}, market {}, aggressive {} block history must be > 0",
+                    "low {}, market {}, aggressive {}, percentile {} block history must be > 0",
                     gas_estimation_config.low_block_history,
                     gas_estimation_config.market_block_history,
-                    gas_estimation_config.aggressive_block_history
+                    gas_estimation_config.aggressive_block_history,
+                    gas_estimation_config.percentile_block_history
                 ),
             ));
         }
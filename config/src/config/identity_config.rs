@@ -24,6 +24,10 @@ pub struct IdentityBlob {
     /// Optional consensus key. Only used for validators
     #[serde(skip_serializing_if = "Option::is_none")]
     pub consensus_private_key: Option<bls12381::PrivateKey>,
+    /// Optional standby consensus key. Only used for validators that pre-register a
+    /// replacement key on-chain, so that it can be activated without rotating local keys.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standby_consensus_private_key: Option<bls12381::PrivateKey>,
     /// Network private key. Peer id is derived from this if account address is not present
     pub network_private_key: x25519::PrivateKey,
 }
@@ -17,9 +17,17 @@ use serde_yaml::Value;
 pub struct InspectionServiceConfig {
     pub address: String,
     pub port: u16,
+    // Admin commands change the state of the node (e.g., moving a local test chain
+    // through time), and are only accepted from localhost.
+    pub expose_admin_commands: bool,
     pub expose_configuration: bool,
     pub expose_peer_information: bool,
+    // Profiling endpoints capture CPU profiles and heap snapshots on demand. These
+    // are expensive, so they are only served to localhost.
+    pub expose_profiling: bool,
     pub expose_system_information: bool,
+    // The thresholds of the checks aggregated by the readiness endpoint
+    pub health_checks: HealthChecksConfig,
 }
 
 impl Default for InspectionServiceConfig {
@@ -27,9 +35,41 @@ impl Default for InspectionServiceConfig {
         InspectionServiceConfig {
             address: "0.0.0.0".to_string(),
             port: 9101,
+            expose_admin_commands: false,
             expose_configuration: false,
             expose_peer_information: true,
+            expose_profiling: false,
             expose_system_information: true,
+            health_checks: HealthChecksConfig::default(),
+        }
+    }
+}
+
+/// The thresholds of the per-subsystem health checks served by the
+/// readiness endpoint (e.g., for load balancers and k8s probes)
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthChecksConfig {
+    // The max time without consensus commits (only checked for validators)
+    pub max_consensus_commit_delay_secs: u64,
+    // The max lag between the latest synced ledger timestamp and the wall clock
+    pub max_state_sync_lag_secs: u64,
+    // The max number of transactions in mempool (as a percentage of its capacity)
+    pub max_mempool_utilization_percent: u64,
+    // The max average latency of the recent storage writes (over at least the last 10 seconds)
+    pub max_storage_commit_latency_ms: u64,
+    // The max time for the REST API to respond to a health check
+    pub api_timeout_ms: u64,
+}
+
+impl Default for HealthChecksConfig {
+    fn default() -> HealthChecksConfig {
+        HealthChecksConfig {
+            max_consensus_commit_delay_secs: 30,
+            max_state_sync_lag_secs: 60,
+            max_mempool_utilization_percent: 90,
+            max_storage_commit_latency_ms: 1000,
+            api_timeout_ms: 2000,
         }
     }
 }
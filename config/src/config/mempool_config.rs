@@ -6,10 +6,12 @@ use crate::config::{
     config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
     node_config_loader::NodeType, Error, NodeConfig, MAX_APPLICATION_MESSAGE_SIZE,
 };
+use aptos_crypto::ed25519::Ed25519PublicKey;
 use aptos_global_constants::DEFAULT_BUCKETS;
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -20,6 +22,9 @@ pub struct MempoolConfig {
     pub capacity_bytes: usize,
     /// Maximum number of transactions allowed in the Mempool per user
     pub capacity_per_user: usize,
+    /// The minimum percentage by which the gas unit price must increase to replace a pending
+    /// transaction with the same sequence number. Any increase is accepted if this is 0.
+    pub replace_by_fee_min_bump_percent: u64,
     /// Number of failover peers to broadcast to when the primary network is alive
     pub default_failovers: usize,
     /// The maximum number of broadcasts sent to a single peer that are pending a response ACK at any point.
@@ -40,6 +45,11 @@ pub struct MempoolConfig {
     pub shared_mempool_max_concurrent_inbound_syncs: usize,
     /// Interval to broadcast to upstream nodes.
     pub shared_mempool_tick_interval_ms: u64,
+    /// Interval to broadcast to the validators expected to propose the current and upcoming
+    /// rounds (validators only). Only used if consensus notifies mempool of the upcoming
+    /// proposers (see `mempool_proposer_lookahead_rounds`), and quorum store is disabled.
+    /// Other peers are still broadcast to every tick interval.
+    pub upcoming_proposer_tick_interval_ms: u64,
     pub shared_mempool_peer_update_interval_ms: u64,
     /// Number of seconds until the transaction will be removed from the Mempool ignoring if the transaction has expired.
     ///
@@ -53,12 +63,30 @@ pub struct MempoolConfig {
     pub broadcast_buckets: Vec<u64>,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    /// Maximum number of transaction events buffered for each subscriber (e.g., indexers).
+    /// Slow subscribers that fall further behind miss events.
+    pub transaction_events_channel_size: usize,
+    /// Rate limits for the transactions submitted directly by clients (e.g., via the REST API)
+    pub submission_rate_limit: SubmissionRateLimitConfig,
+    /// Persistence of the pending transactions across restarts
+    pub persistence: MempoolPersistenceConfig,
+    /// Filtering of the transactions submitted to the node (e.g., via the REST API)
+    pub transaction_filter: TransactionFilterConfig,
+    /// How non-ready transactions are chosen for eviction from the parking lot when mempool is full
+    pub parking_lot_eviction: ParkingLotEvictionConfig,
+    /// Number of seconds until a transaction submitted with the short TTL tier is removed from
+    /// the Mempool. Expired transactions are removed on the system transaction GC interval.
+    pub short_ttl_tier_secs: u64,
+    /// Maximum number of expired transactions remembered for clients that asked to be notified
+    /// of the expiration (instead of the transaction being silently dropped).
+    pub expiration_notifications_capacity: usize,
 }
 
 impl Default for MempoolConfig {
     fn default() -> MempoolConfig {
         MempoolConfig {
             shared_mempool_tick_interval_ms: 50,
+            upcoming_proposer_tick_interval_ms: 10,
             shared_mempool_backoff_interval_ms: 30_000,
             shared_mempool_batch_size: 300,
             shared_mempool_max_batch_bytes: MAX_APPLICATION_MESSAGE_SIZE as u64,
@@ -70,6 +98,7 @@ impl Default for MempoolConfig {
             capacity: 2_000_000,
             capacity_bytes: 2 * 1024 * 1024 * 1024,
             capacity_per_user: 100,
+            replace_by_fee_min_bump_percent: 10,
             default_failovers: 1,
             shared_mempool_peer_update_interval_ms: 1_000,
             system_transaction_timeout_secs: 600,
@@ -77,17 +106,254 @@ impl Default for MempoolConfig {
             broadcast_buckets: DEFAULT_BUCKETS.to_vec(),
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            transaction_events_channel_size: 10_000,
+            submission_rate_limit: SubmissionRateLimitConfig::default(),
+            persistence: MempoolPersistenceConfig::default(),
+            transaction_filter: TransactionFilterConfig::default(),
+            parking_lot_eviction: ParkingLotEvictionConfig::default(),
+            short_ttl_tier_secs: 30,
+            expiration_notifications_capacity: 10_000,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParkingLotEvictionPolicy {
+    /// Evicts the last transaction of a random sender in the parking lot
+    Random,
+    /// Samples candidate senders from the parking lot, and evicts the last transaction of the
+    /// candidate with the highest eviction score (see `ParkingLotEvictionConfig`)
+    Scored,
+}
+
+/// The parking lot holds the transactions that are not ready for broadcast (e.g., because of a
+/// sequence number gap). With the scored policy, each candidate is ranked against the others by
+/// its fee per byte (lower is worse), its sender's backlog in mempool (deeper is worse) and its
+/// age (older is worse). The eviction score is the weighted sum of the ranks.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ParkingLotEvictionConfig {
+    pub policy: ParkingLotEvictionPolicy,
+    /// Number of senders sampled from the parking lot for each eviction (scored policy only)
+    pub num_candidates: usize,
+    /// Weight of the fee per byte rank in the eviction score
+    pub fee_density_weight: u64,
+    /// Weight of the sender backlog rank in the eviction score
+    pub sender_backlog_weight: u64,
+    /// Weight of the age rank in the eviction score
+    pub age_weight: u64,
+}
+
+impl Default for ParkingLotEvictionConfig {
+    fn default() -> ParkingLotEvictionConfig {
+        ParkingLotEvictionConfig {
+            policy: ParkingLotEvictionPolicy::Random,
+            num_candidates: 16,
+            fee_density_weight: 2,
+            sender_backlog_weight: 1,
+            age_weight: 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MempoolPersistenceConfig {
+    /// Whether to periodically write the pending transactions to a journal, and reload
+    /// (and re-validate) them on startup
+    pub enabled: bool,
+    /// The journal file. Relative paths are relative to the node's data directory.
+    pub journal_path: PathBuf,
+    /// The interval at which the pending transactions are written to the journal
+    pub flush_interval_ms: u64,
+    /// Maximum number of transactions to write to the journal
+    pub max_journal_txns: usize,
+}
+
+impl Default for MempoolPersistenceConfig {
+    fn default() -> MempoolPersistenceConfig {
+        MempoolPersistenceConfig {
+            enabled: false,
+            journal_path: PathBuf::from("mempool_journal.bcs"),
+            flush_interval_ms: 5_000,
+            max_journal_txns: 100_000,
+        }
+    }
+}
+
+/// Rejects the transactions matching any rule of an operator provided policy (e.g., by sender,
+/// module address or entry function), both in the REST API and at mempool admission. The
+/// policy file must be signed with the configured key, and is reloaded when it changes.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionFilterConfig {
+    /// Whether to filter the transactions by the policy
+    pub enabled: bool,
+    /// The signed policy file. Relative paths are relative to the node's data directory.
+    pub policy_path: PathBuf,
+    /// The public key the policy must be signed with
+    pub policy_public_key: Option<Ed25519PublicKey>,
+    /// The file the version of the last applied policy is persisted to, so that an older policy
+    /// isn't applied after a restart. Relative paths are relative to the node's data directory.
+    pub applied_version_path: PathBuf,
+    /// The interval at which the policy file is checked for changes (and reloaded)
+    pub reload_interval_ms: u64,
+}
+
+impl Default for TransactionFilterConfig {
+    fn default() -> TransactionFilterConfig {
+        TransactionFilterConfig {
+            enabled: false,
+            policy_path: PathBuf::from("transaction_filter_policy.json"),
+            policy_public_key: None,
+            applied_version_path: PathBuf::from("transaction_filter_policy_version"),
+            reload_interval_ms: 10_000,
+        }
+    }
+}
+
+/// Admission control for client submitted transactions. Transactions are counted per sender
+/// over a sliding time window, and a transaction is rejected if its sender has exceeded
+/// either the overall limit or the limit of the transaction's gas unit price bucket.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubmissionRateLimitConfig {
+    /// Whether rate limiting of client submissions is enabled
+    pub enabled: bool,
+    /// The sliding time window over which submissions are counted
+    pub window_ms: u64,
+    /// Maximum number of transactions a single sender can submit per window
+    pub max_txns_per_sender: usize,
+    /// Per gas unit price bucket limits, sorted by `min_gas_unit_price`. A transaction falls in
+    /// the bucket with the highest `min_gas_unit_price` not above its gas unit price, and
+    /// transactions below the first bucket are only subject to `max_txns_per_sender`.
+    pub bucket_policies: Vec<BucketRateLimitPolicy>,
+    /// Maximum number of senders to track. When exceeded, the least recently seen senders are
+    /// evicted (and can submit again).
+    pub max_tracked_senders: usize,
+}
+
+impl Default for SubmissionRateLimitConfig {
+    fn default() -> SubmissionRateLimitConfig {
+        SubmissionRateLimitConfig {
+            enabled: false,
+            window_ms: 10_000,
+            max_txns_per_sender: 100,
+            bucket_policies: vec![],
+            max_tracked_senders: 100_000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BucketRateLimitPolicy {
+    /// The minimum gas unit price of the transactions in this bucket
+    pub min_gas_unit_price: u64,
+    /// Maximum number of transactions in this bucket a single sender can submit per window
+    pub max_txns_per_sender: usize,
+}
+
 impl ConfigSanitizer for MempoolConfig {
     fn sanitize(
-        _node_config: &mut NodeConfig,
+        node_config: &mut NodeConfig,
         _node_type: NodeType,
         _chain_id: ChainId,
     ) -> Result<(), Error> {
-        Ok(()) // TODO: add reasonable verifications
+        let sanitizer_name = Self::get_sanitizer_name();
+        let rate_limit_config = &node_config.mempool.submission_rate_limit;
+
+        // Verify that the rate limit window is non-zero
+        if rate_limit_config.enabled && rate_limit_config.window_ms == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The submission rate limit window must be non-zero!".to_string(),
+            ));
+        }
+
+        // Verify that the bucket policies are sorted by gas unit price
+        let bucket_prices: Vec<_> = rate_limit_config
+            .bucket_policies
+            .iter()
+            .map(|policy| policy.min_gas_unit_price)
+            .collect();
+        if bucket_prices
+            .windows(2)
+            .any(|prices| prices[0] >= prices[1])
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The submission rate limit bucket policies must be sorted by strictly increasing gas unit price: {:?}",
+                    bucket_prices
+                ),
+            ));
+        }
+
+        // Verify that the journal flush interval is non-zero
+        let persistence_config = &node_config.mempool.persistence;
+        if persistence_config.enabled && persistence_config.flush_interval_ms == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The mempool journal flush interval must be non-zero!".to_string(),
+            ));
+        }
+
+        // Verify that the transaction filter policy can be verified and reloaded
+        let filter_config = &node_config.mempool.transaction_filter;
+        if filter_config.enabled {
+            if filter_config.policy_public_key.is_none() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The transaction filter requires the public key of the policy signer!"
+                        .to_string(),
+                ));
+            }
+            if filter_config.reload_interval_ms == 0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The transaction filter reload interval must be non-zero!".to_string(),
+                ));
+            }
+        }
+
+        // Verify that the scored parking lot eviction policy has candidates and weights
+        let eviction_config = &node_config.mempool.parking_lot_eviction;
+        if eviction_config.policy == ParkingLotEvictionPolicy::Scored {
+            if eviction_config.num_candidates == 0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The number of parking lot eviction candidates must be non-zero!".to_string(),
+                ));
+            }
+            if eviction_config.fee_density_weight == 0
+                && eviction_config.sender_backlog_weight == 0
+                && eviction_config.age_weight == 0
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "At least one parking lot eviction weight must be non-zero!".to_string(),
+                ));
+            }
+        }
+
+        // Verify that the short TTL tier is non-zero and shorter than the system TTL
+        let mempool_config = &node_config.mempool;
+        if mempool_config.short_ttl_tier_secs == 0
+            || mempool_config.short_ttl_tier_secs > mempool_config.system_transaction_timeout_secs
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The short TTL tier must be non-zero and no longer than the system transaction timeout! Short TTL tier: {}, system transaction timeout: {}",
+                    mempool_config.short_ttl_tier_secs,
+                    mempool_config.system_transaction_timeout_secs
+                ),
+            ));
+        }
+
+        Ok(())
     }
 }
 
@@ -143,6 +409,143 @@ impl ConfigOptimizer for MempoolConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sanitize_submission_rate_limit() {
+        // Create a node config with an empty rate limit window
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                submission_rate_limit: SubmissionRateLimitConfig {
+                    enabled: true,
+                    window_ms: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            MempoolConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a node config with unsorted bucket policies
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                submission_rate_limit: SubmissionRateLimitConfig {
+                    enabled: true,
+                    bucket_policies: vec![
+                        BucketRateLimitPolicy {
+                            min_gas_unit_price: 150,
+                            max_txns_per_sender: 10,
+                        },
+                        BucketRateLimitPolicy {
+                            min_gas_unit_price: 100,
+                            max_txns_per_sender: 20,
+                        },
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            MempoolConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that the default config passes sanitization
+        let mut node_config = NodeConfig::default();
+        MempoolConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet()).unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_parking_lot_eviction() {
+        // Create a node config with no eviction candidates
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                parking_lot_eviction: ParkingLotEvictionConfig {
+                    policy: ParkingLotEvictionPolicy::Scored,
+                    num_candidates: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            MempoolConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a node config with all eviction weights set to zero
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                parking_lot_eviction: ParkingLotEvictionConfig {
+                    policy: ParkingLotEvictionPolicy::Scored,
+                    fee_density_weight: 0,
+                    sender_backlog_weight: 0,
+                    age_weight: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            MempoolConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_transaction_filter() {
+        // Create a node config with a transaction filter but no policy public key
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                transaction_filter: TransactionFilterConfig {
+                    enabled: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            MempoolConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_short_ttl_tier() {
+        // Create a node config with a short TTL tier longer than the system TTL
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                short_ttl_tier_secs: 1_000,
+                system_transaction_timeout_secs: 600,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            MempoolConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     #[test]
     fn test_optimize_vfn_configs() {
         // Create the default VFN config
@@ -6,8 +6,10 @@
 mod api_config;
 mod base_config;
 mod config_optimizer;
+mod config_reload_config;
 mod config_sanitizer;
 mod consensus_config;
+mod consensus_observer_config;
 mod error;
 mod execution_config;
 mod gas_estimation_config;
@@ -23,6 +25,7 @@ mod node_config_loader;
 mod peer_monitoring_config;
 mod persistable_config;
 mod quorum_store_config;
+mod resource_budget_config;
 mod safety_rules_config;
 mod secure_backend_config;
 mod state_sync_config;
@@ -32,7 +35,9 @@ mod utils;
 // All public usage statements should be declared below
 pub use api_config::*;
 pub use base_config::*;
+pub use config_reload_config::*;
 pub use consensus_config::*;
+pub use consensus_observer_config::*;
 pub use error::*;
 pub use execution_config::*;
 pub use gas_estimation_config::*;
@@ -47,6 +52,7 @@ pub use node_config::*;
 pub use peer_monitoring_config::*;
 pub use persistable_config::*;
 pub use quorum_store_config::*;
+pub use resource_budget_config::*;
 pub use safety_rules_config::*;
 pub use secure_backend_config::*;
 pub use state_sync_config::*;
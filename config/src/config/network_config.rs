@@ -121,6 +121,10 @@ pub struct NetworkConfig {
     pub max_message_size: usize,
     /// The maximum number of parallel message deserialization tasks that can run (per application)
     pub max_parallel_deserialization_tasks: Option<usize>,
+    /// Per-peer outbound bandwidth limits for individual application protocols.
+    /// If empty, no protocol bandwidth limits are enforced. The limits can be
+    /// adjusted at runtime with the network/set_bandwidth_limit admin command.
+    pub outbound_protocol_bandwidth_limits: Vec<ProtocolBandwidthLimitConfig>,
 }
 
 impl Default for NetworkConfig {
@@ -162,6 +166,7 @@ impl NetworkConfig {
             outbound_rx_buffer_size_bytes: Some(OUTBOUND_TCP_RX_BUFFER_SIZE),
             outbound_tx_buffer_size_bytes: Some(OUTBOUND_TCP_TX_BUFFER_SIZE),
             max_parallel_deserialization_tasks: None,
+            outbound_protocol_bandwidth_limits: Vec::new(),
         };
 
         // Configure the number of parallel deserialization tasks
@@ -385,6 +390,20 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Limits the outbound bandwidth of a single application protocol to each peer.
+/// Direct send messages and rpc responses exceeding the limit are dropped.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProtocolBandwidthLimitConfig {
+    /// The name of the limited protocol (e.g., "StorageServiceRpc")
+    pub protocol: String,
+    /// Maximum number of bytes/s sent to a peer for the protocol
+    pub byte_bucket_rate: usize,
+    /// Maximum burst of bytes sent to a peer for the protocol. Messages larger
+    /// than the burst size can never be sent.
+    pub byte_bucket_size: usize,
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
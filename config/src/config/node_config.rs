@@ -4,9 +4,10 @@
 use crate::{
     config::{
         node_config_loader::NodeConfigLoader, persistable_config::PersistableConfig,
-        utils::RootPath, ApiConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig,
-        IndexerConfig, IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
-        NetworkConfig, PeerMonitoringServiceConfig, SafetyRulesTestConfig, StateSyncConfig,
+        utils::RootPath, ApiConfig, BaseConfig, ConfigReloadConfig, ConsensusConfig,
+        ConsensusObserverConfig, Error, ExecutionConfig, IndexerConfig, IndexerGrpcConfig,
+        InspectionServiceConfig, LoggerConfig, MempoolConfig, NetworkConfig,
+        PeerMonitoringServiceConfig, ResourceBudgetConfig, SafetyRulesTestConfig, StateSyncConfig,
         StorageConfig,
     },
     network_id::NetworkId,
@@ -33,8 +34,12 @@ pub struct NodeConfig {
     #[serde(default)]
     pub base: BaseConfig,
     #[serde(default)]
+    pub config_reload: ConfigReloadConfig,
+    #[serde(default)]
     pub consensus: ConsensusConfig,
     #[serde(default)]
+    pub consensus_observer: ConsensusObserverConfig,
+    #[serde(default)]
     pub execution: ExecutionConfig,
     #[serde(default)]
     pub failpoints: Option<HashMap<String, String>>,
@@ -53,6 +58,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub peer_monitoring_service: PeerMonitoringServiceConfig,
     #[serde(default)]
+    pub resource_budget: ResourceBudgetConfig,
+    #[serde(default)]
     pub state_sync: StateSyncConfig,
     #[serde(default)]
     pub storage: StorageConfig,
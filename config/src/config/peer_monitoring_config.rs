@@ -12,6 +12,7 @@ use serde_yaml::Value;
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct PeerMonitoringServiceConfig {
+    pub bandwidth_monitoring: BandwidthMonitoringConfig,
     pub enable_peer_monitoring_client: bool, // Whether or not to spawn the monitoring client
     pub latency_monitoring: LatencyMonitoringConfig,
     pub max_concurrent_requests: u64, // Max num of concurrent server tasks
@@ -23,11 +24,13 @@ pub struct PeerMonitoringServiceConfig {
     pub node_monitoring: NodeMonitoringConfig,
     pub peer_monitor_interval_usec: u64, // The interval (usec) between peer monitor executions
     pub performance_monitoring: PerformanceMonitoringConfig,
+    pub soak_testing: SoakTestConfig,
 }
 
 impl Default for PeerMonitoringServiceConfig {
     fn default() -> Self {
         Self {
+            bandwidth_monitoring: BandwidthMonitoringConfig::default(),
             enable_peer_monitoring_client: true,
             latency_monitoring: LatencyMonitoringConfig::default(),
             max_concurrent_requests: 1000,
@@ -39,6 +42,27 @@ impl Default for PeerMonitoringServiceConfig {
             node_monitoring: NodeMonitoringConfig::default(),
             peer_monitor_interval_usec: 1_000_000, // 1 second
             performance_monitoring: PerformanceMonitoringConfig::default(),
+            soak_testing: SoakTestConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BandwidthMonitoringConfig {
+    pub bandwidth_probe_data_size: u64, // The number of bytes requested by each bandwidth probe
+    pub bandwidth_probe_interval_ms: u64, // The interval (ms) between bandwidth probes for each peer
+    pub bandwidth_probe_timeout_ms: u64,  // The timeout (ms) for each bandwidth probe
+    pub max_num_bandwidth_probes_to_retain: usize, // The max bandwidth probes to retain per peer
+}
+
+impl Default for BandwidthMonitoringConfig {
+    fn default() -> Self {
+        Self {
+            bandwidth_probe_data_size: 64 * 1024, // 64 KB
+            bandwidth_probe_interval_ms: 300_000, // 5 minutes
+            bandwidth_probe_timeout_ms: 20_000,   // 20 seconds
+            max_num_bandwidth_probes_to_retain: 12,
         }
     }
 }
@@ -109,6 +133,31 @@ impl Default for PerformanceMonitoringConfig {
     }
 }
 
+// Note: soak tests are scheduled (with the chosen peers) through the admin commands
+// of the inspection service. These values bound what can be scheduled and
+// determine how each probe is run.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SoakTestConfig {
+    pub max_num_probes_per_peer: u64, // Max num of bandwidth probes sent to each peer per run
+    pub max_num_soak_test_runs_to_retain: usize, // Max num of soak test runs kept in the report
+    pub min_soak_test_interval_ms: u64, // The min interval (ms) between scheduled soak test runs
+    pub num_concurrent_rpcs_per_probe: u64, // The num of bandwidth RPCs kept in flight by each probe
+    pub soak_test_check_interval_ms: u64,   // The interval (ms) between checks for due soak tests
+}
+
+impl Default for SoakTestConfig {
+    fn default() -> Self {
+        Self {
+            max_num_probes_per_peer: 100,
+            max_num_soak_test_runs_to_retain: 24,
+            min_soak_test_interval_ms: 60_000, // 1 minute
+            num_concurrent_rpcs_per_probe: 16,
+            soak_test_check_interval_ms: 1_000, // 1 second
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct NodeMonitoringConfig {
@@ -131,11 +180,42 @@ impl ConfigSanitizer for PeerMonitoringServiceConfig {
         node_type: NodeType,
         chain_id: ChainId,
     ) -> Result<(), Error> {
+        // Sanitize the bandwidth monitoring config
+        BandwidthMonitoringConfig::sanitize(node_config, node_type, chain_id)?;
+
         // Sanitize the performance monitoring config
         PerformanceMonitoringConfig::sanitize(node_config, node_type, chain_id)
     }
 }
 
+impl ConfigSanitizer for BandwidthMonitoringConfig {
+    fn sanitize(
+        node_config: &mut NodeConfig,
+        _node_type: NodeType,
+        _chain_id: ChainId,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let peer_monitoring_config = &node_config.peer_monitoring_service;
+
+        // Verify that the bandwidth probe responses respect the max response size
+        let bandwidth_probe_data_size = peer_monitoring_config
+            .bandwidth_monitoring
+            .bandwidth_probe_data_size;
+        let max_num_response_bytes = peer_monitoring_config.max_num_response_bytes;
+        if bandwidth_probe_data_size >= max_num_response_bytes {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The bandwidth probe data size ({}) must be less than the max response size ({})!",
+                    bandwidth_probe_data_size, max_num_response_bytes
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 impl ConfigSanitizer for PerformanceMonitoringConfig {
     fn sanitize(
         node_config: &mut NodeConfig,
@@ -316,6 +396,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sanitize_bandwidth_probe_data_size() {
+        // Create a node config with a bandwidth probe size larger than the max response size
+        let mut node_config = NodeConfig {
+            peer_monitoring_service: PeerMonitoringServiceConfig {
+                bandwidth_monitoring: BandwidthMonitoringConfig {
+                    bandwidth_probe_data_size: 200 * 1024,
+                    ..Default::default()
+                },
+                max_num_response_bytes: 100 * 1024,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = PeerMonitoringServiceConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     /// Creates a node config with the peer monitoring client disabled
     fn create_config_with_disabled_client() -> NodeConfig {
         NodeConfig {
@@ -38,6 +38,43 @@ impl Default for QuorumStoreBackPressureConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuorumStoreBatchSizeControllerConfig {
+    /// Whether batch sizes and creation intervals adapt to the observed load. If disabled,
+    /// sender_max_batch_txns and batch_generation_min_non_empty_interval_ms are used as is.
+    pub enabled: bool,
+    pub adjustment_interval_ms: u64,
+    /// The batch size shrinks down to this, it grows up to sender_max_batch_txns
+    pub min_batch_txns: usize,
+    /// The interval shrinks down to this, it grows up to batch_generation_max_interval_ms
+    pub min_batch_generation_interval_ms: usize,
+    /// Batches taking longer than this to get a proof of store indicate congestion
+    pub target_batch_to_proof_latency_ms: u64,
+    /// Fraction of batches failing to get a proof of store that indicates congestion
+    pub max_expired_batch_fraction: f64,
+    pub batch_txns_increase_step: usize,
+    pub interval_decrease_step_ms: usize,
+    pub decrease_fraction: f64,
+}
+
+impl Default for QuorumStoreBatchSizeControllerConfig {
+    fn default() -> QuorumStoreBatchSizeControllerConfig {
+        QuorumStoreBatchSizeControllerConfig {
+            enabled: false,
+            adjustment_interval_ms: 1000,
+            min_batch_txns: 25,
+            min_batch_generation_interval_ms: 50,
+            target_batch_to_proof_latency_ms: 500,
+            max_expired_batch_fraction: 0.1,
+            // additive increase under mempool backlog, multiplicative decrease under congestion
+            batch_txns_increase_step: 25,
+            interval_decrease_step_ms: 25,
+            decrease_fraction: 0.5,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct QuorumStoreConfig {
@@ -67,8 +104,12 @@ pub struct QuorumStoreConfig {
     pub batch_quota: usize,
     pub mempool_txn_pull_max_bytes: u64,
     pub back_pressure: QuorumStoreBackPressureConfig,
+    pub batch_size_controller: QuorumStoreBatchSizeControllerConfig,
     pub num_workers_for_remote_batches: usize,
     pub batch_buckets: Vec<u64>,
+    /// Whether to exclude the transactions of certified batches created by other validators
+    /// when pulling transactions from mempool for our own batches.
+    pub enable_remote_batch_dedup: bool,
 }
 
 impl Default for QuorumStoreConfig {
@@ -99,9 +140,11 @@ impl Default for QuorumStoreConfig {
             batch_quota: 300_000,
             mempool_txn_pull_max_bytes: 4 * 1024 * 1024,
             back_pressure: QuorumStoreBackPressureConfig::default(),
+            batch_size_controller: QuorumStoreBatchSizeControllerConfig::default(),
             // number of batch coordinators to handle QS batch messages, should be >= 1
             num_workers_for_remote_batches: 10,
             batch_buckets: DEFAULT_BUCKETS.to_vec(),
+            enable_remote_batch_dedup: false,
         }
     }
 }
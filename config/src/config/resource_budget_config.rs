@@ -0,0 +1,50 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Configures the node-wide resource budget, which tracks the memory and IO used
+/// by the node and its major subsystems (e.g., mempool and the RocksDB block
+/// cache), and asks the subsystems to shed load (e.g., shrink their caches or
+/// pause prefetching) whenever a budget is exceeded.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResourceBudgetConfig {
+    /// Whether to enforce the resource budget
+    pub enabled: bool,
+    /// The interval (ms) between checks of the resource usage
+    pub check_interval_ms: u64,
+    /// The max memory (bytes) used by the node process (i.e., the resident set size)
+    pub max_memory_bytes: Option<u64>,
+    /// The max IO rate (bytes per second) of the node process (reads and writes)
+    pub max_io_bytes_per_sec: Option<u64>,
+    /// The subsystems stop shedding load once their usage (and the usage of the
+    /// node) falls below this percentage of the budgets
+    pub restore_threshold_percent: u64,
+    /// The budgets of the subsystems, by name (e.g., "mempool")
+    pub subsystem_quotas: BTreeMap<String, SubsystemQuota>,
+}
+
+impl Default for ResourceBudgetConfig {
+    fn default() -> ResourceBudgetConfig {
+        ResourceBudgetConfig {
+            enabled: false,
+            check_interval_ms: 5_000,
+            max_memory_bytes: None,
+            max_io_bytes_per_sec: None,
+            restore_threshold_percent: 80,
+            subsystem_quotas: BTreeMap::new(),
+        }
+    }
+}
+
+/// The resource budget of a single subsystem
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubsystemQuota {
+    /// The max memory (bytes) attributed to the subsystem
+    pub max_memory_bytes: Option<u64>,
+    /// The max IO rate (bytes per second) attributed to the subsystem
+    pub max_io_bytes_per_sec: Option<u64>,
+}
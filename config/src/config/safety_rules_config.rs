@@ -19,6 +19,7 @@ use std::{
     net::{SocketAddr, ToSocketAddrs},
     path::PathBuf,
 };
+use url::Url;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -31,6 +32,9 @@ pub struct SafetyRulesConfig {
     pub network_timeout_ms: u64,
     pub enable_cached_safety_data: bool,
     pub initial_safety_rules_config: InitialSafetyRulesConfig,
+    /// Delegates the consensus key operations to a remote signing backend (if set), so that
+    /// the consensus key doesn't have to be kept in the secure backend
+    pub remote_signer: Option<RemoteSignerConfig>,
 }
 
 impl Default for SafetyRulesConfig {
@@ -38,12 +42,19 @@ impl Default for SafetyRulesConfig {
         Self {
             backend: SecureBackend::InMemoryStorage,
             logger: LoggerConfig::default(),
+            // Safety rules run in the node by default, rather than in a separate process with an
+            // authenticated transport: the process needs the `aptos-safety-rules` binary and a key
+            // shared with the node, which existing deployments don't provide, so switching the
+            // default would break them.
+            // TODO: default to an authenticated, managed process once the deployments (e.g., the
+            // docker images and the helm charts) ship the binary and provision the shared key.
             service: SafetyRulesService::Local,
             test: None,
             // Default value of 30 seconds for a timeout
             network_timeout_ms: 30_000,
             enable_cached_safety_data: true,
             initial_safety_rules_config: InitialSafetyRulesConfig::None,
+            remote_signer: None,
         }
     }
 }
@@ -93,11 +104,15 @@ impl ConfigSanitizer for SafetyRulesConfig {
             ));
         }
 
-        // Verify that the safety rules service is set to local for optimal performance
-        if chain_id.is_mainnet() && !safety_rules_config.service.is_local() {
+        // Verify that the safety rules service is set to local for optimal performance, or to an
+        // authenticated process for isolating the consensus key
+        if chain_id.is_mainnet()
+            && !safety_rules_config.service.is_local()
+            && !safety_rules_config.service.is_authenticated_process()
+        {
             return Err(Error::ConfigSanitizerFailed(
                 sanitizer_name,
-                format!("The safety rules service should be set to local in mainnet for optimal performance! Given config: {:?}", &safety_rules_config.service)
+                format!("The safety rules service should be set to local or to an authenticated process in mainnet! Given config: {:?}", &safety_rules_config.service)
             ));
         }
 
@@ -180,12 +195,31 @@ impl SafetyRulesService {
     fn is_local(&self) -> bool {
         matches!(self, SafetyRulesService::Local)
     }
+
+    /// Returns true iff the service is a process with an authenticated transport
+    fn is_authenticated_process(&self) -> bool {
+        matches!(
+            self,
+            SafetyRulesService::Process(RemoteService {
+                authentication_key_path: Some(_),
+                ..
+            })
+        )
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RemoteService {
     pub server_address: NetworkAddress,
+    /// The file containing the key shared by the node and the safety rules process, which
+    /// authenticates their connections (see `aptos_secure_net::StreamAuthConfig`). The file
+    /// should only be readable by both processes. If unset, the connections aren't
+    /// authenticated, which isn't accepted on mainnet.
+    pub authentication_key_path: Option<PathBuf>,
+    /// Starts the safety rules process from the node, and restarts it whenever it exits (if set).
+    /// Otherwise, the process is expected to be managed externally.
+    pub managed_process: Option<ManagedProcessConfig>,
 }
 
 impl RemoteService {
@@ -198,6 +232,56 @@ impl RemoteService {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManagedProcessConfig {
+    /// The `aptos-safety-rules` binary
+    pub binary_path: PathBuf,
+    /// The safety rules config of the process
+    pub config_path: PathBuf,
+    /// The maximum delay between restarts, which doubles after every crash in a row
+    #[serde(default = "ManagedProcessConfig::default_max_restart_delay_ms")]
+    pub max_restart_delay_ms: u64,
+}
+
+impl ManagedProcessConfig {
+    fn default_max_restart_delay_ms() -> u64 {
+        10_000
+    }
+}
+
+/// A remote signing service holding the consensus key (e.g., in front of an HSM or a cloud KMS)
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerConfig {
+    pub url: Url,
+    /// The name of the consensus key in the signing service
+    pub key_name: String,
+    /// The file containing the bearer token of the signing service (if it requires one)
+    pub token_path: Option<PathBuf>,
+    /// The timeout of the operations, after which they fail over to the fallback key
+    #[serde(default = "RemoteSignerConfig::default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// An encrypted copy of the consensus key on disk, to be used when the signing service is
+    /// unavailable
+    pub fallback_key: Option<EncryptedKeyConfig>,
+}
+
+impl RemoteSignerConfig {
+    fn default_timeout_ms() -> u64 {
+        1_000
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedKeyConfig {
+    /// The file containing the encrypted key (see `aptos_secure_storage::EncryptedKey`)
+    pub path: PathBuf,
+    /// The environment variable containing the passphrase of the key
+    pub passphrase_env_var: String,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SafetyRulesTestConfig {
     pub author: PeerId,
@@ -294,6 +378,44 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_authenticated_process_for_mainnet() {
+        let remote_service = RemoteService {
+            server_address: "/ip4/127.0.0.1/tcp/5555".parse().unwrap(),
+            authentication_key_path: None,
+            managed_process: None,
+        };
+        let mut node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                safety_rules: SafetyRulesConfig {
+                    backend: SecureBackend::OnDiskStorage(Default::default()),
+                    service: SafetyRulesService::Process(remote_service.clone()),
+                    initial_safety_rules_config: InitialSafetyRulesConfig::from_file(
+                        PathBuf::new(),
+                        WaypointConfig::None,
+                    ),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails for an unauthenticated process
+        let error =
+            SafetyRulesConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that the config sanitizer passes for an authenticated process
+        node_config.consensus.safety_rules.service = SafetyRulesService::Process(RemoteService {
+            authentication_key_path: Some(PathBuf::from("safety-rules.key")),
+            ..remote_service
+        });
+        SafetyRulesConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap();
+    }
+
     #[test]
     fn test_sanitize_test_config_on_mainnet() {
         // Create a node config with a test config
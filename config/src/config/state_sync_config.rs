@@ -142,6 +142,9 @@ impl Default for StateSyncDriverConfig {
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StorageServiceConfig {
+    /// Whether to deduplicate identical write ops in transaction output responses. This
+    /// should only be enabled once all peers are able to decode deduplicated responses.
+    pub enable_transaction_output_dedup: bool,
     /// Maximum number of concurrent storage server tasks
     pub max_concurrent_requests: u64,
     /// Maximum number of epoch ending ledger infos per chunk
@@ -156,6 +159,10 @@ pub struct StorageServiceConfig {
     pub max_network_chunk_bytes: u64,
     /// Maximum period (ms) of pending optimistic fetch requests
     pub max_optimistic_fetch_period_ms: u64,
+    /// Maximum number of bytes held by the serialized (compressed) response cache
+    /// before eviction. This cache serves popular data directly from memory
+    /// (e.g., during mass syncing events). A value of 0 disables the cache.
+    pub max_serialized_response_cache_bytes: u64,
     /// Maximum number of state keys and values per chunk
     pub max_state_chunk_size: u64,
     /// Maximum number of transactions per chunk
@@ -173,6 +180,7 @@ pub struct StorageServiceConfig {
 impl Default for StorageServiceConfig {
     fn default() -> Self {
         Self {
+            enable_transaction_output_dedup: false,
             max_concurrent_requests: 4000,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
             max_invalid_requests_per_peer: 500,
@@ -180,6 +188,7 @@ impl Default for StorageServiceConfig {
             max_network_channel_size: 4000,
             max_network_chunk_bytes: MAX_MESSAGE_SIZE as u64,
             max_optimistic_fetch_period_ms: 5000, // 5 seconds
+            max_serialized_response_cache_bytes: 0, // Disabled by default
             max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
@@ -193,13 +202,21 @@ impl Default for StorageServiceConfig {
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DataStreamingServiceConfig {
+    /// Whether to auto-tune the prefetch window (i.e., the number of concurrent
+    /// data client requests per stream). If enabled, the window grows while
+    /// notifications are consumed promptly and shrinks on timeouts and
+    /// backpressure. Otherwise, the max number of concurrent requests is used.
+    pub enable_prefetch_window_tuning: bool,
+
     /// The interval (milliseconds) at which to refresh the global data summary.
     pub global_summary_refresh_interval_ms: u64,
 
-    /// Maximum number of concurrent data client requests (per stream).
+    /// Maximum number of concurrent data client requests (per stream). This
+    /// is the upper bound of the prefetch window.
     pub max_concurrent_requests: u64,
 
-    /// Maximum number of concurrent data client requests (per stream) for state keys/values.
+    /// Maximum number of concurrent data client requests (per stream) for state
+    /// keys/values. This is the upper bound of the prefetch window for states.
     pub max_concurrent_state_requests: u64,
 
     /// Maximum channel sizes for each data stream listener. If messages are not
@@ -211,24 +228,39 @@ pub struct DataStreamingServiceConfig {
     /// stream will terminate.
     pub max_request_retry: u64,
 
+    /// Maximum delay (milliseconds) before retrying a failed client request.
+    pub max_request_retry_delay_ms: u64,
+
     /// Maximum number of notification ID to response context mappings held in
     /// memory. Once the number grows beyond this value, garbage collection occurs.
     pub max_notification_id_mappings: u64,
 
+    /// Minimum number of concurrent data client requests (per stream). This is
+    /// the lower bound of the prefetch window (when auto-tuning is enabled).
+    pub min_concurrent_requests: u64,
+
     /// The interval (milliseconds) at which to check the progress of each stream.
     pub progress_check_interval_ms: u64,
+
+    /// Delay (milliseconds) before the first retry of a failed client request.
+    /// The delay grows exponentially (with jitter) for every further retry.
+    pub request_retry_delay_ms: u64,
 }
 
 impl Default for DataStreamingServiceConfig {
     fn default() -> Self {
         Self {
+            enable_prefetch_window_tuning: true,
             global_summary_refresh_interval_ms: 50,
             max_concurrent_requests: MAX_CONCURRENT_REQUESTS,
             max_concurrent_state_requests: MAX_CONCURRENT_STATE_REQUESTS,
             max_data_stream_channel_sizes: 300,
             max_request_retry: 5,
+            max_request_retry_delay_ms: 5_000, // 5 seconds
             max_notification_id_mappings: 300,
+            min_concurrent_requests: 1,
             progress_check_interval_ms: 50,
+            request_retry_delay_ms: 100,
         }
     }
 }
@@ -236,6 +268,11 @@ impl Default for DataStreamingServiceConfig {
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct AptosDataClientConfig {
+    /// Number of versions that the data windows of pruned peers are expected to
+    /// move forward (i.e., be pruned) before requests for the data reach them
+    pub data_window_margin_versions: u64,
+    /// Whether or not to request proofs of the data windows retained by pruned peers
+    pub enable_data_window_proofs: bool,
     /// The interval (milliseconds) at which to refresh the latency monitor
     pub latency_monitor_loop_interval_ms: u64,
     /// Maximum number of epoch ending ledger infos per chunk
@@ -246,6 +283,10 @@ pub struct AptosDataClientConfig {
     pub max_num_in_flight_regular_polls: u64,
     /// Maximum number of output reductions before transactions are returned
     pub max_num_output_reductions: u64,
+    /// Maximum number of sub-chunks that a state value chunk is split into (each
+    /// sub-chunk is fetched from different peers in parallel). 1 disables splitting
+    /// (the default).
+    pub max_num_state_sub_chunks: u64,
     /// Maximum version lag we'll tolerate when sending optimistic fetch requests
     pub max_optimistic_fetch_version_lag: u64,
     /// Maximum timeout (in ms) when waiting for a response (after exponential increases)
@@ -256,10 +297,24 @@ pub struct AptosDataClientConfig {
     pub max_transaction_chunk_size: u64,
     /// Maximum number of transaction outputs per chunk
     pub max_transaction_output_chunk_size: u64,
+    /// Minimum number of state values in each state value sub-chunk
+    pub min_state_sub_chunk_size: u64,
     /// Timeout (in ms) when waiting for an optimistic fetch response
     pub optimistic_fetch_timeout_ms: u64,
+    /// Number of responses after which the weight of a past response (i.e., a
+    /// success, error or timeout) in the reliability of a peer halves
+    pub peer_reliability_half_life: u64,
+    /// Interval (in ms) at which the peer reliabilities are persisted to the
+    /// data directory (so they survive restarts). 0 disables persistence.
+    pub peer_reliability_persist_interval_ms: u64,
     /// First timeout (in ms) when waiting for a response
     pub response_timeout_ms: u64,
+    /// Delay (in ms) after which a state value sub-chunk that hasn't been received
+    /// yet is also requested from the next peer (up to `state_sub_chunk_redundancy`)
+    pub state_sub_chunk_hedge_delay_ms: u64,
+    /// Number of peers that each state value sub-chunk may be requested from (the
+    /// first valid response is used, so a single slow peer won't stall the chunk)
+    pub state_sub_chunk_redundancy: u64,
     /// Interval (in ms) between data summary poll loop executions
     pub summary_poll_loop_interval_ms: u64,
     /// Whether or not to request compression for incoming data
@@ -269,18 +324,26 @@ pub struct AptosDataClientConfig {
 impl Default for AptosDataClientConfig {
     fn default() -> Self {
         Self {
+            data_window_margin_versions: 50_000, // Assumes 5K TPS for 10 seconds
+            enable_data_window_proofs: true,
             latency_monitor_loop_interval_ms: 50, // 50 milliseconds
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
             max_num_in_flight_priority_polls: 10,
             max_num_in_flight_regular_polls: 10,
             max_num_output_reductions: 0,
+            max_num_state_sub_chunks: 1,
             max_optimistic_fetch_version_lag: 50_000, // Assumes 5K TPS for 10 seconds, which should be plenty
             max_response_timeout_ms: 60_000,          // 60 seconds
             max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
+            min_state_sub_chunk_size: 500,
             optimistic_fetch_timeout_ms: 5000, // 5 seconds
-            response_timeout_ms: 10_000,       // 10 seconds
+            peer_reliability_half_life: 50,
+            peer_reliability_persist_interval_ms: 60_000, // 60 seconds
+            response_timeout_ms: 10_000,                  // 10 seconds
+            state_sub_chunk_hedge_delay_ms: 1000,         // 1 second
+            state_sub_chunk_redundancy: 2,
             summary_poll_loop_interval_ms: 200,
             use_compression: true,
         }
@@ -19,6 +19,10 @@ pub const DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD: usize = 1 << 13;
 
 pub const BUFFERED_STATE_TARGET_ITEMS: usize = 100_000;
 
+// The state merkle pruner and the recovery of the buffered state on startup assume the state
+// snapshots are at most this many versions apart.
+pub const MAX_STATE_SNAPSHOT_INTERVAL_IN_VERSIONS: u64 = 20_000;
+
 /// Port selected RocksDB options for tuning underlying rocksdb instance of AptosDB.
 /// see <https://github.com/facebook/rocksdb/blob/master/include/rocksdb/options.h>
 /// for detailed explanations.
@@ -119,6 +123,11 @@ pub struct StorageConfig {
     /// since genesis. To recover operation after data loss, or to bootstrap a node in fast sync
     /// mode, the indexer db needs to be copied in from another node.
     pub enable_indexer: bool,
+    /// Storage usage forecasting configuration
+    pub storage_forecast_config: StorageForecastConfig,
+    /// When to persist a snapshot of the state (in addition to `buffered_state_target_items`).
+    /// Overridden by the on-chain state snapshot config, if set.
+    pub state_snapshot_schedule: StateSnapshotScheduleConfig,
 }
 
 pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
@@ -250,6 +259,42 @@ impl Default for EpochSnapshotPrunerConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageForecastConfig {
+    /// Boolean to enable/disable tracking the growth of the DBs, to forecast when the disk
+    /// will be full and recommend prune windows and backup cadences.
+    pub enable: bool,
+    /// Size of the disk the DBs are stored on. If 0, days until full are not forecasted.
+    pub disk_size_bytes: u64,
+    /// Percentage of the disk to keep free (e.g., for compactions and checkpoints)
+    pub disk_headroom_percent: u64,
+    /// Number of days of history the operator wants to keep (i.e., not prune). If 0, the
+    /// prune window is only recommended based on the disk size.
+    pub target_retention_days: u64,
+    /// Interval between two samples of the DB sizes
+    pub sample_interval_secs: u64,
+    /// Growth rates are computed over the samples taken in this window
+    pub growth_window_secs: u64,
+    /// Interval between two forecast summaries in the logs
+    pub summary_interval_secs: u64,
+}
+
+impl Default for StorageForecastConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            disk_size_bytes: 0,
+            disk_headroom_percent: 20,
+            target_retention_days: 0,
+            sample_interval_secs: 60,
+            // A day smooths out the daily traffic patterns
+            growth_window_secs: 24 * 3600,
+            summary_interval_secs: 3600,
+        }
+    }
+}
+
 impl Default for StorageConfig {
     fn default() -> StorageConfig {
         StorageConfig {
@@ -267,6 +312,36 @@ impl Default for StorageConfig {
             enable_indexer: false,
             buffered_state_target_items: BUFFERED_STATE_TARGET_ITEMS,
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            storage_forecast_config: StorageForecastConfig::default(),
+            state_snapshot_schedule: StateSnapshotScheduleConfig::default(),
+        }
+    }
+}
+
+/// The schedule of the state snapshots. A snapshot is taken at the first state checkpoint
+/// at which any of the intervals has elapsed since the previous snapshot. The intervals are
+/// measured in versions, blocks and block timestamps, but the progress towards them is kept
+/// in memory (and restarts with the node), and snapshots are also taken when too many items
+/// are buffered, so different nodes don't necessarily take the snapshots at the same versions.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StateSnapshotScheduleConfig {
+    /// Max number of versions between two snapshots (at most
+    /// `MAX_STATE_SNAPSHOT_INTERVAL_IN_VERSIONS`)
+    pub interval_versions: u64,
+    /// Max number of blocks between two snapshots. If 0, snapshots aren't taken by blocks.
+    pub interval_blocks: u64,
+    /// Max number of seconds (of block time) between two snapshots. If 0, snapshots aren't
+    /// taken by time.
+    pub interval_secs: u64,
+}
+
+impl Default for StateSnapshotScheduleConfig {
+    fn default() -> Self {
+        Self {
+            interval_versions: MAX_STATE_SNAPSHOT_INTERVAL_IN_VERSIONS,
+            interval_blocks: 0,
+            interval_secs: 0,
         }
     }
 }
@@ -337,6 +412,18 @@ impl ConfigSanitizer for StorageConfig {
                 "user_pruning_window_offset is larger than the ledger prune window, the API will refuse to return any data.".to_string(),
             ));
         }
+        let snapshot_interval_versions = config.state_snapshot_schedule.interval_versions;
+        if snapshot_interval_versions == 0
+            || snapshot_interval_versions > MAX_STATE_SNAPSHOT_INTERVAL_IN_VERSIONS
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "state_snapshot_schedule.interval_versions must be between 1 and {}, found {}.",
+                    MAX_STATE_SNAPSHOT_INTERVAL_IN_VERSIONS, snapshot_interval_versions
+                ),
+            ));
+        }
 
         Ok(())
     }
@@ -4,12 +4,12 @@
 
 use crate::{
     block_data::{BlockData, BlockType},
+    clock,
     common::{Author, Payload, Round},
     quorum_cert::QuorumCert,
 };
 use anyhow::{bail, ensure, format_err};
 use aptos_crypto::{bls12381, hash::CryptoHash, HashValue};
-use aptos_infallible::duration_since_epoch;
 use aptos_types::{
     account_address::AccountAddress,
     block_info::BlockInfo,
@@ -321,7 +321,7 @@ impl Block {
                 "Blocks must have strictly increasing timestamps"
             );
 
-            let current_ts = duration_since_epoch();
+            let current_ts = clock::current_timestamp();
 
             // we can say that too far is 5 minutes in the future
             const TIMEBOUND: u64 = 300_000_000;
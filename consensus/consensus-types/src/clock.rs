@@ -0,0 +1,85 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The clock used by consensus for block timestamps. Local test chains can move it forward
+//! in time (see `advance_clock`), without affecting the clock of the other components.
+
+use aptos_infallible::duration_since_epoch;
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// The offset of the consensus clock from the system time
+static CLOCK_OFFSET: ClockOffset = ClockOffset::new();
+
+/// Gives the duration since the Unix epoch on the consensus clock, i.e., the system time
+/// plus the offset the clock was moved forward by.
+pub fn current_timestamp() -> Duration {
+    CLOCK_OFFSET.apply(duration_since_epoch())
+}
+
+/// Moves the consensus clock forward by the given duration, and returns the new offset from
+/// the system time. The clock can't be moved back.
+pub fn advance_clock(duration: Duration) -> Duration {
+    CLOCK_OFFSET.advance(duration)
+}
+
+/// An offset (in microseconds) from the system time, that can only move forward
+struct ClockOffset {
+    offset_usecs: AtomicU64,
+}
+
+impl ClockOffset {
+    const fn new() -> Self {
+        Self {
+            offset_usecs: AtomicU64::new(0),
+        }
+    }
+
+    fn advance(&self, duration: Duration) -> Duration {
+        let duration_usecs = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        let previous_offset_usecs = self
+            .offset_usecs
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |offset_usecs| {
+                Some(offset_usecs.saturating_add(duration_usecs))
+            })
+            .expect("The clock offset update can't fail");
+        Duration::from_micros(previous_offset_usecs.saturating_add(duration_usecs))
+    }
+
+    fn offset(&self) -> Duration {
+        Duration::from_micros(self.offset_usecs.load(Ordering::SeqCst))
+    }
+
+    fn apply(&self, system_time: Duration) -> Duration {
+        system_time.saturating_add(self.offset())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advance_clock() {
+        // Advance the clock and verify the offset accumulates
+        let clock_offset = ClockOffset::new();
+        assert_eq!(
+            clock_offset.advance(Duration::from_secs(3600)),
+            clock_offset.offset()
+        );
+        assert_eq!(
+            clock_offset.advance(Duration::from_secs(3600)),
+            Duration::from_secs(7200)
+        );
+        assert_eq!(clock_offset.offset(), Duration::from_secs(7200));
+
+        // Verify the clock is moved forward by the offset
+        let system_time = duration_since_epoch();
+        assert_eq!(
+            clock_offset.apply(system_time),
+            system_time + Duration::from_secs(7200)
+        );
+    }
+}
@@ -282,6 +282,10 @@ impl ProofOfStoreMsg {
         Ok(epoch)
     }
 
+    pub fn proofs(&self) -> &[ProofOfStore] {
+        &self.proofs
+    }
+
     pub fn take(self) -> Vec<ProofOfStore> {
         self.proofs
     }
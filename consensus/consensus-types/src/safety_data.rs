@@ -2,7 +2,8 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::vote::Vote;
+use crate::{timeout_2chain::TwoChainTimeout, vote::Vote};
+use aptos_crypto::bls12381;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -17,6 +18,9 @@ pub struct SafetyData {
     #[serde(default)]
     pub one_chain_round: u64,
     pub last_vote: Option<Vote>,
+    // last signed 2-chain timeout, so that it can be re-broadcast identically after a restart
+    #[serde(default)]
+    pub last_timeout: Option<(TwoChainTimeout, bls12381::Signature)>,
 }
 
 impl SafetyData {
@@ -26,6 +30,7 @@ impl SafetyData {
         preferred_round: u64,
         one_chain_round: u64,
         last_vote: Option<Vote>,
+        last_timeout: Option<(TwoChainTimeout, bls12381::Signature)>,
     ) -> Self {
         Self {
             epoch,
@@ -33,6 +38,7 @@ impl SafetyData {
             preferred_round,
             one_chain_round,
             last_vote,
+            last_timeout,
         }
     }
 }
@@ -0,0 +1,88 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{counters, Error};
+use aptos_consensus_types::common::Author;
+use aptos_crypto::{bls12381, hash::CryptoHash, Signature};
+use aptos_secure_storage::BlockingSigner;
+use aptos_types::validator_signer::ValidatorSigner;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// The consensus key held by a remote signing backend, e.g., an HSM (see
+/// `aptos_secure_storage::AsyncSigner`).
+pub struct RemoteConsensusKey {
+    signer: BlockingSigner,
+    key_name: String,
+}
+
+impl RemoteConsensusKey {
+    pub fn new(signer: BlockingSigner, key_name: String) -> Self {
+        Self { signer, key_name }
+    }
+
+    pub fn public_key(&self) -> Result<bls12381::PublicKey, Error> {
+        let _timer = counters::start_timer("remote_signer", "public_key");
+        Ok(self.signer.consensus_public_key(&self.key_name)?)
+    }
+
+    /// Signs the message with the remote key, and verifies the signature against the expected
+    /// public key. The backend may have failed over to a different key (or be faulty), in which
+    /// case its signatures would be rejected by the other validators.
+    fn sign<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &bls12381::PublicKey,
+    ) -> Result<bls12381::Signature, Error> {
+        let _timer = counters::start_timer("remote_signer", "sign");
+        let signature = self.signer.sign_consensus(&self.key_name, message)?;
+        signature.verify(message, public_key).map_err(|error| {
+            Error::InternalError(format!(
+                "The remote signer returned an invalid signature: {}",
+                error
+            ))
+        })?;
+        Ok(signature)
+    }
+}
+
+/// Signs on behalf of the validator, with a consensus key either held in memory or by a remote
+/// signing backend.
+pub enum ConsensusSigner {
+    Local(ValidatorSigner),
+    Remote {
+        author: Author,
+        public_key: bls12381::PublicKey,
+        key: Arc<RemoteConsensusKey>,
+    },
+}
+
+impl ConsensusSigner {
+    pub fn author(&self) -> Author {
+        match self {
+            Self::Local(signer) => signer.author(),
+            Self::Remote { author, .. } => *author,
+        }
+    }
+
+    pub fn public_key(&self) -> bls12381::PublicKey {
+        match self {
+            Self::Local(signer) => signer.public_key(),
+            Self::Remote { public_key, .. } => public_key.clone(),
+        }
+    }
+
+    pub fn sign<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+    ) -> Result<bls12381::Signature, Error> {
+        match self {
+            Self::Local(signer) => signer
+                .sign(message)
+                .map_err(|err| Error::SerializationError(err.to_string())),
+            Self::Remote {
+                public_key, key, ..
+            } => key.sign(message, public_key),
+        }
+    }
+}
@@ -3,8 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_metrics_core::{
-    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, HistogramTimer,
-    HistogramVec, IntCounterVec, IntGaugeVec,
+    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, HistogramTimer, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 
@@ -40,6 +41,43 @@ static STATE_GAUGE: Lazy<IntGaugeVec> = Lazy::new(|| {
     .unwrap()
 });
 
+static REMOTE_SERVICE_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_safety_rules_remote_service_errors",
+        "Failed exchanges between the client and the server of the remote safety rules service",
+        &["side"]
+    )
+    .unwrap()
+});
+
+static PROCESS_RESTARTS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_safety_rules_process_restarts",
+        "Number of times the node restarted the safety rules process"
+    )
+    .unwrap()
+});
+
+static PROCESS_UP: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_safety_rules_process_up",
+        "Whether the safety rules process managed by the node is running"
+    )
+    .unwrap()
+});
+
+pub fn increment_remote_service_error(side: &str) {
+    REMOTE_SERVICE_ERRORS.with_label_values(&[side]).inc();
+}
+
+pub fn increment_process_restart() {
+    PROCESS_RESTARTS.inc();
+}
+
+pub fn set_process_up(is_up: bool) {
+    PROCESS_UP.set(is_up as i64);
+}
+
 pub fn increment_query(method: &str, result: &str) {
     QUERY_COUNTER.with_label_values(&[method, result]).inc();
 }
@@ -4,6 +4,7 @@
 
 #![forbid(unsafe_code)]
 
+mod consensus_signer;
 mod consensus_state;
 mod counters;
 mod error;
@@ -20,9 +21,13 @@ mod t_safety_rules;
 mod thread;
 
 pub use crate::{
-    consensus_state::ConsensusState, error::Error,
-    persistent_safety_storage::PersistentSafetyStorage, process::Process,
-    safety_rules::SafetyRules, safety_rules_manager::SafetyRulesManager,
+    consensus_signer::{ConsensusSigner, RemoteConsensusKey},
+    consensus_state::ConsensusState,
+    error::Error,
+    persistent_safety_storage::PersistentSafetyStorage,
+    process::Process,
+    safety_rules::SafetyRules,
+    safety_rules_manager::SafetyRulesManager,
     t_safety_rules::TSafetyRules,
 };
 
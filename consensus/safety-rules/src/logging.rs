@@ -51,6 +51,7 @@ pub enum LogEntry {
     PreferredRound,
     SignProposal,
     SignTimeoutWithQC,
+    StandbyConsensusKey,
     State,
     Waypoint,
     SignCommitVote,
@@ -69,6 +70,7 @@ impl LogEntry {
             LogEntry::PreferredRound => "preferred_round",
             LogEntry::SignProposal => "sign_proposal",
             LogEntry::SignTimeoutWithQC => "sign_timeout_with_qc",
+            LogEntry::StandbyConsensusKey => "standby_consensus_key",
             LogEntry::State => "state",
             LogEntry::Waypoint => "waypoint",
             LogEntry::SignCommitVote => "sign_commit_vote",
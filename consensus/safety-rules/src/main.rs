@@ -0,0 +1,39 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs safety rules in a separate process, so that the consensus key is isolated from the node.
+//! The node connects to it when its safety rules service is set to `process` (see
+//! `aptos_config::config::RemoteService`), and may start and supervise it.
+
+use aptos_config::config::{PersistableConfig, SafetyRulesConfig};
+use aptos_logger::info;
+use aptos_safety_rules::Process;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Aptos safety rules process
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The safety rules config, with its service set to `process`
+    #[clap(long, value_parser)]
+    config: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+    let config = SafetyRulesConfig::load_config(&args.config).unwrap_or_else(|error| {
+        panic!(
+            "Unable to load the safety rules config {:?}: {}",
+            args.config, error
+        )
+    });
+
+    aptos_logger::Logger::builder()
+        .is_async(config.logger.is_async)
+        .level(config.logger.level)
+        .build();
+    info!("Starting safety rules with config {:?}", config);
+
+    Process::new(config).start();
+}
@@ -3,16 +3,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::{ConsensusSigner, RemoteConsensusKey},
     counters,
     logging::{self, LogEntry, LogEvent},
     Error,
 };
 use aptos_consensus_types::{common::Author, safety_data::SafetyData};
 use aptos_crypto::{bls12381, PrivateKey};
-use aptos_global_constants::{CONSENSUS_KEY, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT};
+use aptos_global_constants::{
+    CONSENSUS_KEY, CONSENSUS_KEY_STANDBY, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT,
+};
 use aptos_logger::prelude::*;
 use aptos_secure_storage::{KVStorage, Storage};
-use aptos_types::waypoint::Waypoint;
+use aptos_types::{validator_signer::ValidatorSigner, waypoint::Waypoint};
+use std::sync::Arc;
 
 /// SafetyRules needs an abstract storage interface to act as a common utility for storing
 /// persistent data to local disk, cloud, secrets managers, or even memory (for tests)
@@ -26,28 +30,63 @@ pub struct PersistentSafetyStorage {
     enable_cached_safety_data: bool,
     cached_safety_data: Option<SafetyData>,
     internal_store: Storage,
+    remote_consensus_key: Option<Arc<RemoteConsensusKey>>,
 }
 
 impl PersistentSafetyStorage {
     /// Use this to instantiate a PersistentStorage for a new data store, one that has no
     /// SafetyRules values set.
     pub fn initialize(
-        mut internal_store: Storage,
+        internal_store: Storage,
         author: Author,
         consensus_private_key: bls12381::PrivateKey,
         waypoint: Waypoint,
         enable_cached_safety_data: bool,
+    ) -> Self {
+        Self::initialize_internal(
+            internal_store,
+            author,
+            Some(consensus_private_key),
+            waypoint,
+            enable_cached_safety_data,
+        )
+    }
+
+    /// Use this to instantiate a PersistentStorage for a new data store, when the consensus
+    /// key is held by a remote signing backend (see `with_remote_consensus_key`).
+    pub fn initialize_without_consensus_key(
+        internal_store: Storage,
+        author: Author,
+        waypoint: Waypoint,
+        enable_cached_safety_data: bool,
+    ) -> Self {
+        Self::initialize_internal(
+            internal_store,
+            author,
+            None,
+            waypoint,
+            enable_cached_safety_data,
+        )
+    }
+
+    fn initialize_internal(
+        mut internal_store: Storage,
+        author: Author,
+        consensus_private_key: Option<bls12381::PrivateKey>,
+        waypoint: Waypoint,
+        enable_cached_safety_data: bool,
     ) -> Self {
         // Initialize the keys and accounts
         Self::initialize_keys_and_accounts(&mut internal_store, author, consensus_private_key)
             .expect("Unable to initialize keys and accounts in storage");
 
         // Create the new persistent safety storage
-        let safety_data = SafetyData::new(1, 0, 0, 0, None);
+        let safety_data = SafetyData::new(1, 0, 0, 0, None, None);
         let mut persisent_safety_storage = Self {
             enable_cached_safety_data,
             cached_safety_data: Some(safety_data.clone()),
             internal_store,
+            remote_consensus_key: None,
         };
 
         // Initialize the safety data and waypoint
@@ -64,9 +103,12 @@ impl PersistentSafetyStorage {
     fn initialize_keys_and_accounts(
         internal_store: &mut Storage,
         author: Author,
-        consensus_private_key: bls12381::PrivateKey,
+        consensus_private_key: Option<bls12381::PrivateKey>,
     ) -> Result<(), Error> {
-        let result = internal_store.set(CONSENSUS_KEY, consensus_private_key);
+        let result = match consensus_private_key {
+            Some(consensus_private_key) => internal_store.set(CONSENSUS_KEY, consensus_private_key),
+            None => Ok(()),
+        };
         // Attempting to re-initialize existing storage. This can happen in environments like
         // forge. Rather than be rigid here, leave it up to the developer to detect
         // inconsistencies or why they did not reset storage between rounds. Do not repeat the
@@ -88,9 +130,17 @@ impl PersistentSafetyStorage {
             enable_cached_safety_data,
             cached_safety_data: None,
             internal_store,
+            remote_consensus_key: None,
         }
     }
 
+    /// Delegates the consensus key operations to a remote signing backend. The keys in the
+    /// internal store are still used for the versions the remote backend doesn't hold.
+    pub fn with_remote_consensus_key(mut self, remote_consensus_key: RemoteConsensusKey) -> Self {
+        self.remote_consensus_key = Some(Arc::new(remote_consensus_key));
+        self
+    }
+
     pub fn author(&self) -> Result<Author, Error> {
         let _timer = counters::start_timer("get", OWNER_ACCOUNT);
         Ok(self.internal_store.get(OWNER_ACCOUNT).map(|v| v.value)?)
@@ -102,13 +152,74 @@ impl PersistentSafetyStorage {
     ) -> Result<bls12381::PrivateKey, Error> {
         let _timer = counters::start_timer("get", CONSENSUS_KEY);
         let key: bls12381::PrivateKey = self.internal_store.get(CONSENSUS_KEY).map(|v| v.value)?;
-        if key.public_key() != version {
-            return Err(Error::SecureStorageMissingDataError(format!(
-                "PrivateKey for {:?} not found",
-                version
-            )));
+        if key.public_key() == version {
+            return Ok(key);
+        }
+
+        // The on-chain key may have been switched to the pre-registered standby key
+        if let Some(standby_key) = self.standby_consensus_key()? {
+            if standby_key.public_key() == version {
+                info!(logging::SafetyLogSchema::new(
+                    LogEntry::StandbyConsensusKey,
+                    LogEvent::Success
+                ));
+                return Ok(standby_key);
+            }
+        }
+
+        Err(Error::SecureStorageMissingDataError(format!(
+            "PrivateKey for {:?} not found",
+            version
+        )))
+    }
+
+    /// Returns the signer of the given consensus key version, which is held either by the remote
+    /// signing backend (if any) or by the internal store.
+    pub fn consensus_signer_for_version(
+        &self,
+        author: Author,
+        version: bls12381::PublicKey,
+    ) -> Result<ConsensusSigner, Error> {
+        if let Some(remote_consensus_key) = &self.remote_consensus_key {
+            if remote_consensus_key.public_key()? == version {
+                return Ok(ConsensusSigner::Remote {
+                    author,
+                    public_key: version,
+                    key: remote_consensus_key.clone(),
+                });
+            }
+        }
+        let consensus_key = self.consensus_key_for_version(version)?;
+        Ok(ConsensusSigner::Local(ValidatorSigner::new(
+            author,
+            consensus_key,
+        )))
+    }
+
+    /// Returns the standby consensus key (if one has been stored)
+    pub fn standby_consensus_key(&self) -> Result<Option<bls12381::PrivateKey>, Error> {
+        let _timer = counters::start_timer("get", CONSENSUS_KEY_STANDBY);
+        match self.internal_store.get(CONSENSUS_KEY_STANDBY) {
+            Ok(response) => Ok(Some(response.value)),
+            Err(aptos_secure_storage::Error::KeyNotSet(_)) => Ok(None),
+            Err(error) => Err(error.into()),
         }
-        Ok(key)
+    }
+
+    /// Stores the standby consensus key. This key is used once the validator's
+    /// on-chain consensus key is switched to the standby key.
+    pub fn set_standby_consensus_key(
+        &mut self,
+        standby_private_key: bls12381::PrivateKey,
+    ) -> Result<(), Error> {
+        let _timer = counters::start_timer("set", CONSENSUS_KEY_STANDBY);
+        self.internal_store
+            .set(CONSENSUS_KEY_STANDBY, standby_private_key)?;
+        info!(logging::SafetyLogSchema::new(
+            LogEntry::StandbyConsensusKey,
+            LogEvent::Update
+        ));
+        Ok(())
     }
 
     pub fn safety_data(&mut self) -> Result<SafetyData, Error> {
@@ -170,8 +281,8 @@ impl PersistentSafetyStorage {
 mod tests {
     use super::*;
     use crate::counters;
-    use aptos_crypto::hash::HashValue;
-    use aptos_secure_storage::InMemoryStorage;
+    use aptos_crypto::{hash::HashValue, test_utils::TestAptosCrypto, Signature};
+    use aptos_secure_storage::{BlockingSigner, InMemoryStorage, LocalSigner};
     use aptos_types::{
         block_info::BlockInfo, epoch_state::EpochState, ledger_info::LedgerInfo,
         transaction::Version, validator_signer::ValidatorSigner, waypoint::Waypoint,
@@ -198,6 +309,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_standby_consensus_key() {
+        let consensus_private_key = ValidatorSigner::from_int(0).private_key().clone();
+        let standby_private_key = ValidatorSigner::from_int(1).private_key().clone();
+        let storage = Storage::from(InMemoryStorage::new());
+        let mut safety_storage = PersistentSafetyStorage::initialize(
+            storage,
+            Author::random(),
+            consensus_private_key.clone(),
+            Waypoint::default(),
+            true,
+        );
+
+        // Without a standby key, only the primary key can be found
+        assert!(safety_storage.standby_consensus_key().unwrap().is_none());
+        assert!(safety_storage
+            .consensus_key_for_version(standby_private_key.public_key())
+            .is_err());
+
+        // Once stored, the standby key is returned for its public key
+        safety_storage
+            .set_standby_consensus_key(standby_private_key.clone())
+            .unwrap();
+        assert_eq!(
+            safety_storage
+                .consensus_key_for_version(standby_private_key.public_key())
+                .unwrap()
+                .public_key(),
+            standby_private_key.public_key()
+        );
+        assert_eq!(
+            safety_storage
+                .consensus_key_for_version(consensus_private_key.public_key())
+                .unwrap()
+                .public_key(),
+            consensus_private_key.public_key()
+        );
+
+        // Unknown keys are still rejected
+        let unknown_private_key = ValidatorSigner::from_int(2).private_key().clone();
+        assert!(safety_storage
+            .consensus_key_for_version(unknown_private_key.public_key())
+            .is_err());
+    }
+
+    #[test]
+    fn test_remote_consensus_key() {
+        let remote_private_key = ValidatorSigner::from_int(0).private_key().clone();
+        let local_private_key = ValidatorSigner::from_int(1).private_key().clone();
+        let author = Author::random();
+        let storage = Storage::from(InMemoryStorage::new());
+        let signer =
+            BlockingSigner::new(Arc::new(LocalSigner::new(remote_private_key.clone()))).unwrap();
+        let safety_storage = PersistentSafetyStorage::initialize_without_consensus_key(
+            storage,
+            author,
+            Waypoint::default(),
+            true,
+        )
+        .with_remote_consensus_key(RemoteConsensusKey::new(signer, "consensus".into()));
+
+        // The remote key is used for its public key
+        let consensus_signer = safety_storage
+            .consensus_signer_for_version(author, remote_private_key.public_key())
+            .unwrap();
+        assert!(matches!(consensus_signer, ConsensusSigner::Remote { .. }));
+        assert_eq!(consensus_signer.author(), author);
+        let message = TestAptosCrypto("Hello, World".to_string());
+        let signature = consensus_signer.sign(&message).unwrap();
+        signature
+            .verify(&message, &remote_private_key.public_key())
+            .unwrap();
+
+        // No key is stored locally
+        assert!(safety_storage
+            .consensus_signer_for_version(author, local_private_key.public_key())
+            .is_err());
+
+        // Signatures that don't match the expected key are rejected
+        let key = match consensus_signer {
+            ConsensusSigner::Remote { key, .. } => key,
+            ConsensusSigner::Local(_) => unreachable!("The remote key is used"),
+        };
+        let consensus_signer = ConsensusSigner::Remote {
+            author,
+            public_key: local_private_key.public_key(),
+            key,
+        };
+        assert!(consensus_signer.sign(&message).is_err());
+    }
+
     fn test_safety_data_counters(safety_storage: &mut PersistentSafetyStorage) {
         let safety_data = safety_storage.safety_data().unwrap();
         assert_eq!(safety_data.epoch, 1);
@@ -208,7 +410,7 @@ mod tests {
         assert_eq!(counters::get_state(counters::PREFERRED_ROUND), 0);
 
         safety_storage
-            .set_safety_data(SafetyData::new(9, 8, 1, 0, None))
+            .set_safety_data(SafetyData::new(9, 8, 1, 0, None, None))
             .unwrap();
 
         let safety_data = safety_storage.safety_data().unwrap();
@@ -3,12 +3,65 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    counters,
     persistent_safety_storage::PersistentSafetyStorage,
     remote_service::{self, RemoteService},
     safety_rules_manager,
 };
-use aptos_config::config::{SafetyRulesConfig, SafetyRulesService};
-use std::net::SocketAddr;
+use aptos_config::config::{
+    ManagedProcessConfig, RemoteService as RemoteServiceConfig, SafetyRulesConfig,
+    SafetyRulesService,
+};
+use aptos_logger::{error, info, warn};
+use aptos_secure_net::StreamAuthConfig;
+use std::{
+    cmp::min,
+    net::SocketAddr,
+    process::{Child, Command},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// The interval between the checks of the managed process
+const PROCESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// The delay before the first restart of the managed process
+const INITIAL_RESTART_DELAY: Duration = Duration::from_millis(100);
+/// A process that ran for this long isn't considered to be crashing in a row
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+
+/// The version of this binary, which must match between the node and the safety rules process
+/// when their connections are authenticated.
+pub fn binary_version() -> String {
+    let build_information = aptos_build_info::get_build_information();
+    let commit_hash = build_information
+        .get(aptos_build_info::BUILD_COMMIT_HASH)
+        .map(String::as_str)
+        .unwrap_or("unknown");
+    format!("{}-{}", env!("CARGO_PKG_VERSION"), commit_hash)
+}
+
+/// Loads the authentication of the connections between the node and the safety rules process
+/// (if any).
+pub fn authentication(service: &RemoteServiceConfig) -> Option<StreamAuthConfig> {
+    if service.authentication_key_path.is_none() {
+        warn!(
+            "The connections between the node and the safety rules process are not authenticated! \
+            Set an authentication key path to authenticate them."
+        );
+    }
+    service.authentication_key_path.as_ref().map(|path| {
+        StreamAuthConfig::from_key_file(path, binary_version()).unwrap_or_else(|error| {
+            panic!(
+                "Unable to read the safety rules authentication key {:?}: {}",
+                path, error
+            )
+        })
+    })
+}
 
 pub struct Process {
     data: Option<ProcessData>,
@@ -29,13 +82,19 @@ impl Process {
                 server_addr,
                 storage,
                 network_timeout: config.network_timeout_ms,
+                authentication: authentication(service),
             }),
         }
     }
 
     pub fn start(&mut self) {
         let data = self.data.take().expect("Unable to retrieve ProcessData");
-        remote_service::execute(data.storage, data.server_addr, data.network_timeout);
+        remote_service::execute(
+            data.storage,
+            data.server_addr,
+            data.network_timeout,
+            data.authentication,
+        );
     }
 }
 
@@ -44,11 +103,14 @@ struct ProcessData {
     storage: PersistentSafetyStorage,
     // Timeout in Seconds for network operations
     network_timeout: u64,
+    authentication: Option<StreamAuthConfig>,
 }
 
 pub struct ProcessService {
     server_addr: SocketAddr,
     network_timeout_ms: u64,
+    authentication: Option<StreamAuthConfig>,
+    _supervisor: Option<ProcessSupervisor>,
 }
 
 impl ProcessService {
@@ -56,6 +118,21 @@ impl ProcessService {
         Self {
             server_addr,
             network_timeout_ms: network_timeout,
+            authentication: None,
+            _supervisor: None,
+        }
+    }
+
+    /// Connects to the safety rules process as configured, and starts the process if it's
+    /// managed by the node.
+    pub fn from_config(service: &RemoteServiceConfig, network_timeout: u64) -> Self {
+        Self {
+            authentication: authentication(service),
+            _supervisor: service
+                .managed_process
+                .clone()
+                .map(ProcessSupervisor::start),
+            ..Self::new(service.server_address(), network_timeout)
         }
     }
 }
@@ -68,4 +145,99 @@ impl RemoteService for ProcessService {
     fn network_timeout_ms(&self) -> u64 {
         self.network_timeout_ms
     }
+
+    fn authentication(&self) -> Option<StreamAuthConfig> {
+        self.authentication.clone()
+    }
+}
+
+/// Runs the safety rules process on behalf of the node, and restarts it (with exponential backoff)
+/// whenever it exits. In the meantime, the requests of the node are retried by the client until
+/// the process is back. The process is killed when the supervisor is dropped.
+pub struct ProcessSupervisor {
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ProcessSupervisor {
+    pub fn start(config: ManagedProcessConfig) -> Self {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread_shutdown = shutdown.clone();
+        let handle = thread::Builder::new()
+            .name("safety-rules-supervisor".into())
+            .spawn(move || supervise(config, thread_shutdown))
+            .expect("Unable to start the safety rules supervisor");
+        Self {
+            shutdown,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for ProcessSupervisor {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn supervise(config: ManagedProcessConfig, shutdown: Arc<AtomicBool>) {
+    let max_restart_delay = Duration::from_millis(config.max_restart_delay_ms);
+    let mut restart_delay = INITIAL_RESTART_DELAY;
+    loop {
+        let started = Instant::now();
+        match Command::new(&config.binary_path)
+            .arg("--config")
+            .arg(&config.config_path)
+            .spawn()
+        {
+            Ok(child) => {
+                info!("Started the safety rules process {}", child.id());
+                counters::set_process_up(true);
+                wait_for_exit(child, &shutdown);
+                counters::set_process_up(false);
+            },
+            Err(error) => error!(
+                "Unable to start the safety rules process {:?}: {}",
+                config.binary_path, error
+            ),
+        }
+        if shutdown.load(Ordering::Relaxed) {
+            return;
+        }
+
+        // Back off while the process keeps crashing
+        if started.elapsed() >= STABLE_UPTIME {
+            restart_delay = INITIAL_RESTART_DELAY;
+        }
+        thread::sleep(restart_delay);
+        restart_delay = min(restart_delay * 2, max_restart_delay);
+        counters::increment_process_restart();
+    }
+}
+
+/// Waits until the process exits, or kills it on shutdown.
+fn wait_for_exit(mut child: Child, shutdown: &AtomicBool) {
+    loop {
+        if shutdown.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return;
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                warn!("The safety rules process exited: {}", status);
+                return;
+            },
+            Ok(None) => thread::sleep(PROCESS_POLL_INTERVAL),
+            Err(error) => {
+                warn!("Unable to check the safety rules process: {}", error);
+                let _ = child.kill();
+                let _ = child.wait();
+                return;
+            },
+        }
+    }
 }
@@ -3,21 +3,30 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    counters,
     persistent_safety_storage::PersistentSafetyStorage,
     serializer::{SafetyRulesInput, SerializerClient, SerializerService, TSerializerClient},
     Error, SafetyRules, TSafetyRules,
 };
 use aptos_logger::warn;
-use aptos_secure_net::{NetworkClient, NetworkServer};
+use aptos_secure_net::{NetworkClient, NetworkServer, StreamAuthConfig};
 use std::net::SocketAddr;
 
 pub trait RemoteService {
     fn client(&self) -> SerializerClient {
-        let network_client = NetworkClient::new(
-            "safety-rules".to_string(),
-            self.server_address(),
-            self.network_timeout_ms(),
-        );
+        let network_client = match self.authentication() {
+            Some(auth) => NetworkClient::new_authenticated(
+                "safety-rules".to_string(),
+                self.server_address(),
+                self.network_timeout_ms(),
+                auth,
+            ),
+            None => NetworkClient::new(
+                "safety-rules".to_string(),
+                self.server_address(),
+                self.network_timeout_ms(),
+            ),
+        };
         let service = Box::new(RemoteClient::new(network_client));
         SerializerClient::new_client(service)
     }
@@ -26,20 +35,36 @@ pub trait RemoteService {
 
     /// Network Timeout in milliseconds.
     fn network_timeout_ms(&self) -> u64;
+
+    /// The authentication of the connections to the service (if any).
+    fn authentication(&self) -> Option<StreamAuthConfig>;
 }
 
-pub fn execute(storage: PersistentSafetyStorage, listen_addr: SocketAddr, network_timeout_ms: u64) {
+pub fn execute(
+    storage: PersistentSafetyStorage,
+    listen_addr: SocketAddr,
+    network_timeout_ms: u64,
+    authentication: Option<StreamAuthConfig>,
+) {
     let mut safety_rules = SafetyRules::new(storage);
     if let Err(e) = safety_rules.consensus_state() {
         warn!("Unable to print consensus state: {}", e);
     }
 
     let mut serializer_service = SerializerService::new(safety_rules);
-    let mut network_server =
-        NetworkServer::new("safety-rules".to_string(), listen_addr, network_timeout_ms);
+    let mut network_server = match authentication {
+        Some(auth) => NetworkServer::new_authenticated(
+            "safety-rules".to_string(),
+            listen_addr,
+            network_timeout_ms,
+            auth,
+        ),
+        None => NetworkServer::new("safety-rules".to_string(), listen_addr, network_timeout_ms),
+    };
 
     loop {
         if let Err(e) = process_one_message(&mut network_server, &mut serializer_service) {
+            counters::increment_remote_service_error("server");
             warn!("Failed to process message: {}", e);
         }
     }
@@ -65,6 +90,7 @@ impl RemoteClient {
     }
 
     fn process_one_message(&mut self, input: &[u8]) -> Result<Vec<u8>, Error> {
+        let _timer = counters::start_timer("remote_client", "request");
         self.network_client.write(input)?;
         self.network_client.read().map_err(|e| e.into())
     }
@@ -75,7 +101,10 @@ impl TSerializerClient for RemoteClient {
         let input_message = serde_json::to_vec(&input)?;
         loop {
             match self.process_one_message(&input_message) {
-                Err(err) => warn!("Failed to communicate with SafetyRules service: {}", err),
+                Err(err) => {
+                    counters::increment_remote_service_error("client");
+                    warn!("Failed to communicate with SafetyRules service: {}", err)
+                },
                 Ok(value) => return Ok(value),
             }
         }
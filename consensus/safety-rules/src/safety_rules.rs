@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::ConsensusSigner,
     consensus_state::ConsensusState,
     counters,
     error::Error,
@@ -26,7 +27,6 @@ use aptos_types::{
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    validator_signer::ValidatorSigner,
     waypoint::Waypoint,
 };
 use serde::Serialize;
@@ -39,7 +39,7 @@ pub(crate) fn next_round(round: Round) -> Result<Round, Error> {
 /// @TODO consider a cache of verified QCs to cut down on verification costs
 pub struct SafetyRules {
     pub(crate) persistent_storage: PersistentSafetyStorage,
-    pub(crate) validator_signer: Option<ValidatorSigner>,
+    pub(crate) validator_signer: Option<ConsensusSigner>,
     pub(crate) epoch_state: Option<EpochState>,
 }
 
@@ -81,13 +81,10 @@ impl SafetyRules {
         &self,
         message: &T,
     ) -> Result<bls12381::Signature, Error> {
-        let signer = self.signer()?;
-        signer
-            .sign(message)
-            .map_err(|err| Error::SerializationError(err.to_string()))
+        self.signer()?.sign(message)
     }
 
-    pub(crate) fn signer(&self) -> Result<&ValidatorSigner, Error> {
+    pub(crate) fn signer(&self) -> Result<&ConsensusSigner, Error> {
         self.validator_signer
             .as_ref()
             .ok_or_else(|| Error::NotInitialized("validator_signer".into()))
@@ -250,6 +247,7 @@ impl SafetyRules {
                     0,
                     0,
                     None,
+                    None,
                 ))?;
 
                 info!(SafetyLogSchema::new(LogEntry::Epoch, LogEvent::Update)
@@ -272,14 +270,14 @@ impl SafetyRules {
                     );
                     Ok(())
                 } else {
-                    // Try to export the consensus key directly from storage.
+                    // Try to export the consensus key directly from storage, unless it's held
+                    // by the remote signing backend.
                     match self
                         .persistent_storage
-                        .consensus_key_for_version(expected_key)
+                        .consensus_signer_for_version(author, expected_key)
                     {
-                        Ok(consensus_key) => {
-                            self.validator_signer =
-                                Some(ValidatorSigner::new(author, consensus_key));
+                        Ok(consensus_signer) => {
+                            self.validator_signer = Some(consensus_signer);
                             Ok(())
                         },
                         Err(Error::SecureStorageMissingDataError(error)) => {
@@ -39,10 +39,11 @@ impl SafetyRules {
         }
         if timeout.round() > safety_data.last_voted_round {
             self.verify_and_update_last_vote_round(timeout.round(), &mut safety_data)?;
-            self.persistent_storage.set_safety_data(safety_data)?;
         }
 
         let signature = self.sign(&timeout.signing_format())?;
+        safety_data.last_timeout = Some((timeout.clone(), signature.clone()));
+        self.persistent_storage.set_safety_data(safety_data)?;
         Ok(signature)
     }
 
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::RemoteConsensusKey,
     local_client::LocalClient,
     persistent_safety_storage::PersistentSafetyStorage,
     process::ProcessService,
@@ -11,10 +12,15 @@ use crate::{
     thread::ThreadService,
     SafetyRules, TSafetyRules,
 };
-use aptos_config::config::{InitialSafetyRulesConfig, SafetyRulesConfig, SafetyRulesService};
+use aptos_config::config::{
+    InitialSafetyRulesConfig, RemoteService as RemoteServiceConfig, RemoteSignerConfig,
+    SafetyRulesConfig, SafetyRulesService,
+};
 use aptos_infallible::RwLock;
-use aptos_secure_storage::{KVStorage, Storage};
-use std::{convert::TryInto, net::SocketAddr, sync::Arc};
+use aptos_secure_storage::{
+    AsyncSigner, BlockingSigner, FailoverSigner, HttpSigner, KVStorage, LocalSigner, Storage,
+};
+use std::{convert::TryInto, sync::Arc, time::Duration};
 
 pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
     let backend = &config.backend;
@@ -43,7 +49,7 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
         let storage =
             PersistentSafetyStorage::new(internal_storage, config.enable_cached_safety_data);
         // If it's initialized, then we can continue
-        if storage.author().is_ok() {
+        let mut storage = if storage.author().is_ok() {
             storage
         } else if !matches!(
             config.initial_safety_rules_config,
@@ -55,25 +61,84 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
             let backend = &config.backend;
             let internal_storage: Storage =
                 backend.try_into().expect("Unable to initialize storage");
-            PersistentSafetyStorage::initialize(
-                internal_storage,
-                identity_blob
-                    .account_address
-                    .expect("AccountAddress needed for safety rules"),
-                identity_blob
-                    .consensus_private_key
-                    .expect("Consensus key needed for safety rules"),
-                waypoint,
-                config.enable_cached_safety_data,
-            )
+            let author = identity_blob
+                .account_address
+                .expect("AccountAddress needed for safety rules");
+            match identity_blob.consensus_private_key {
+                Some(consensus_private_key) => PersistentSafetyStorage::initialize(
+                    internal_storage,
+                    author,
+                    consensus_private_key,
+                    waypoint,
+                    config.enable_cached_safety_data,
+                ),
+                // The consensus key is held by the remote signing backend
+                None if config.remote_signer.is_some() => {
+                    PersistentSafetyStorage::initialize_without_consensus_key(
+                        internal_storage,
+                        author,
+                        waypoint,
+                        config.enable_cached_safety_data,
+                    )
+                },
+                None => panic!("Consensus key needed for safety rules"),
+            }
         } else {
             panic!(
                 "Safety rules storage is not initialized, provide an initial safety rules config"
             )
+        };
+
+        // Store the standby consensus key (if any), so that safety rules can switch to it
+        // once it becomes the on-chain consensus key. This also applies to initialized
+        // storage, to allow adding a standby key to an existing validator.
+        if !matches!(
+            config.initial_safety_rules_config,
+            InitialSafetyRulesConfig::None
+        ) {
+            if let Some(standby_private_key) = config
+                .initial_safety_rules_config
+                .identity_blob()
+                .standby_consensus_private_key
+            {
+                storage
+                    .set_standby_consensus_key(standby_private_key)
+                    .expect("Unable to store the standby consensus key");
+            }
+        }
+
+        match &config.remote_signer {
+            Some(remote_signer_config) => {
+                storage.with_remote_consensus_key(remote_consensus_key(remote_signer_config))
+            },
+            None => storage,
         }
     }
 }
 
+fn remote_consensus_key(config: &RemoteSignerConfig) -> RemoteConsensusKey {
+    let token = config.token_path.as_ref().map(|token_path| {
+        std::fs::read_to_string(token_path)
+            .expect("Unable to read the remote signer token")
+            .trim()
+            .to_string()
+    });
+    let fallback = config.fallback_key.as_ref().map(|fallback_key| {
+        let passphrase = std::env::var(&fallback_key.passphrase_env_var)
+            .expect("Unable to read the passphrase of the fallback consensus key");
+        let signer = LocalSigner::from_encrypted_key(&fallback_key.path, &passphrase)
+            .expect("Unable to load the fallback consensus key");
+        Box::new(signer) as Box<dyn AsyncSigner>
+    });
+    let signer = FailoverSigner::new(
+        Box::new(HttpSigner::new(config.url.clone(), token)),
+        fallback,
+        Duration::from_millis(config.timeout_ms),
+    );
+    let signer = BlockingSigner::new(Arc::new(signer)).expect("Unable to start the remote signer");
+    RemoteConsensusKey::new(signer, config.key_name.clone())
+}
+
 enum SafetyRulesWrapper {
     Local(Arc<RwLock<SafetyRules>>),
     Process(ProcessService),
@@ -88,7 +153,7 @@ pub struct SafetyRulesManager {
 impl SafetyRulesManager {
     pub fn new(config: &SafetyRulesConfig) -> Self {
         if let SafetyRulesService::Process(conf) = &config.service {
-            return Self::new_process(conf.server_address(), config.network_timeout_ms);
+            return Self::new_process(conf, config.network_timeout_ms);
         }
 
         let storage = storage(config);
@@ -107,8 +172,8 @@ impl SafetyRulesManager {
         }
     }
 
-    pub fn new_process(server_addr: SocketAddr, timeout_ms: u64) -> Self {
-        let process_service = ProcessService::new(server_addr, timeout_ms);
+    pub fn new_process(service: &RemoteServiceConfig, timeout_ms: u64) -> Self {
+        let process_service = ProcessService::from_config(service, timeout_ms);
         Self {
             internal_safety_rules: SafetyRulesWrapper::Process(process_service),
         }
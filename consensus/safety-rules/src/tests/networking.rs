@@ -2,8 +2,14 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{test_utils, SafetyRulesManager};
+use crate::{process, remote_service, test_utils, SafetyRulesManager};
+use aptos_config::{config::RemoteService, utils};
 use aptos_types::validator_signer::ValidatorSigner;
+use std::{
+    io::Write,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    thread,
+};
 
 #[test]
 fn test_reconnect() {
@@ -18,3 +24,27 @@ fn test_reconnect() {
     let state1 = safety_rules_manager.client().consensus_state().unwrap();
     assert_eq!(state0, state1);
 }
+
+#[test]
+fn test_authenticated_process() {
+    let signer = ValidatorSigner::from_int(0);
+    let storage = test_utils::test_storage(&signer);
+    let network_timeout = 5_000;
+
+    let mut key_file = tempfile::NamedTempFile::new().unwrap();
+    key_file.write_all(&[7; 32]).unwrap();
+    let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), utils::get_available_port());
+    let service = RemoteService {
+        server_address: server_addr.into(),
+        authentication_key_path: Some(key_file.path().to_path_buf()),
+        managed_process: None,
+    };
+
+    // Run the server side of the process in a thread
+    let auth = process::authentication(&service);
+    thread::spawn(move || remote_service::execute(storage, server_addr, network_timeout, auth));
+
+    let safety_rules_manager = SafetyRulesManager::new_process(&service, network_timeout);
+    let state = safety_rules_manager.client().consensus_state().unwrap();
+    assert_eq!(state.last_voted_round(), 0);
+}
@@ -55,6 +55,7 @@ pub fn run_test_suite(safety_rules: &Callback) {
     test_key_not_in_store(safety_rules);
     test_2chain_rules(safety_rules);
     test_2chain_timeout(safety_rules);
+    test_2chain_timeout_journaled(safety_rules);
     test_sign_commit_vote(safety_rules);
     test_bad_execution_output(safety_rules);
 }
@@ -577,6 +578,38 @@ fn test_2chain_timeout(constructor: &Callback) {
     ));
 }
 
+/// Test that the last signed timeout is persisted, so that it can be re-broadcast after a restart
+fn test_2chain_timeout_journaled(constructor: &Callback) {
+    let (mut safety_rules, signer) = constructor();
+    let (proof, genesis_qc) = test_utils::make_genesis(&signer);
+    safety_rules.initialize(&proof).unwrap();
+    assert_eq!(
+        safety_rules
+            .consensus_state()
+            .unwrap()
+            .safety_data()
+            .last_timeout,
+        None
+    );
+
+    let timeout = TwoChainTimeout::new(1, 1, genesis_qc.clone());
+    let signature = safety_rules.sign_timeout_with_qc(&timeout, None).unwrap();
+    assert_eq!(
+        safety_rules
+            .consensus_state()
+            .unwrap()
+            .safety_data()
+            .last_timeout,
+        Some((timeout.clone(), signature.clone()))
+    );
+
+    // Signing the same timeout again yields the same signature
+    assert_eq!(
+        safety_rules.sign_timeout_with_qc(&timeout, None).unwrap(),
+        signature
+    );
+}
+
 /// Test that we can succesfully sign a valid commit vote
 fn test_sign_commit_vote(constructor: &Callback) {
     // we construct a chain of proposals
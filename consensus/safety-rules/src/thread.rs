@@ -13,6 +13,7 @@ use crate::{
     remote_service::{self, RemoteService},
 };
 use aptos_config::utils;
+use aptos_secure_net::StreamAuthConfig;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     thread::{self, JoinHandle},
@@ -32,7 +33,8 @@ impl ThreadService {
         let listen_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listen_port);
         let server_addr = listen_addr;
 
-        let child = thread::spawn(move || remote_service::execute(storage, listen_addr, timeout));
+        let child =
+            thread::spawn(move || remote_service::execute(storage, listen_addr, timeout, None));
 
         Self {
             _child: child,
@@ -50,4 +52,8 @@ impl RemoteService for ThreadService {
     fn network_timeout_ms(&self) -> u64 {
         self.network_timeout
     }
+
+    fn authentication(&self) -> Option<StreamAuthConfig> {
+        None
+    }
 }
@@ -0,0 +1,110 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters;
+use anyhow::anyhow;
+use aptos_consensus_types::common::Round;
+use futures::{channel::oneshot, SinkExt};
+use std::{fmt, time::Duration};
+
+const ADMIN_COMMAND_CHANNEL_SIZE: usize = 16;
+
+/// A request to propose `num_blocks` empty blocks as soon as this validator is the leader,
+/// without waiting for transactions. Only accepted if time travel is enabled.
+#[derive(Debug)]
+pub struct MineBlocksRequest {
+    /// Number of empty blocks to propose
+    pub num_blocks: u64,
+    /// Receives the last proposed block, or the reason the blocks weren't proposed
+    pub response_tx: oneshot::Sender<anyhow::Result<MinedBlock>>,
+}
+
+/// A request to move the clock of this node forward by `duration`, and to propose an empty
+/// block with the new timestamp. Only accepted if time travel is enabled.
+#[derive(Debug)]
+pub struct AdvanceTimeRequest {
+    /// The duration to move the clock forward by
+    pub duration: Duration,
+    /// Receives the block proposed with the new timestamp, or the reason it wasn't proposed
+    pub response_tx: oneshot::Sender<anyhow::Result<MinedBlock>>,
+}
+
+/// The last block proposed for a mine blocks (or advance time) request
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MinedBlock {
+    pub epoch: u64,
+    pub round: Round,
+    pub timestamp_usecs: u64,
+}
+
+impl fmt::Display for MinedBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[epoch: {}, round: {}, timestamp_usecs: {}]",
+            self.epoch, self.round, self.timestamp_usecs
+        )
+    }
+}
+
+/// Commands that operators can send to a running consensus instance
+#[derive(Debug)]
+pub enum ConsensusAdminCommand {
+    /// Moves the chain forward in time (on local test chains)
+    AdvanceTime(AdvanceTimeRequest),
+    /// Proposes empty blocks on demand (on local test chains)
+    MineBlocks(MineBlocksRequest),
+}
+
+/// Client for sending admin commands to consensus, e.g. from the admin endpoints of the
+/// inspection service.
+#[derive(Clone)]
+pub struct ConsensusAdminClient {
+    command_tx: aptos_channels::Sender<ConsensusAdminCommand>,
+}
+
+impl ConsensusAdminClient {
+    /// Creates a client, and the receiver consensus reads the commands from
+    pub fn new() -> (Self, aptos_channels::Receiver<ConsensusAdminCommand>) {
+        let (command_tx, command_rx) = aptos_channels::new(
+            ADMIN_COMMAND_CHANNEL_SIZE,
+            &counters::PENDING_ADMIN_COMMANDS,
+        );
+        (Self { command_tx }, command_rx)
+    }
+
+    /// Moves the chain forward in time by the given duration, and returns the block proposed
+    /// with the new timestamp
+    pub async fn advance_time(&self, duration: Duration) -> anyhow::Result<MinedBlock> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.send_command(ConsensusAdminCommand::AdvanceTime(AdvanceTimeRequest {
+            duration,
+            response_tx,
+        }))
+        .await?;
+        response_rx
+            .await
+            .map_err(|_| anyhow!("Consensus dropped the request, is this node a validator?"))?
+    }
+
+    /// Proposes the given number of empty blocks, and returns the last proposed block
+    pub async fn mine_blocks(&self, num_blocks: u64) -> anyhow::Result<MinedBlock> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.send_command(ConsensusAdminCommand::MineBlocks(MineBlocksRequest {
+            num_blocks,
+            response_tx,
+        }))
+        .await?;
+        response_rx
+            .await
+            .map_err(|_| anyhow!("Consensus dropped the request, is this node a validator?"))?
+    }
+
+    async fn send_command(&self, command: ConsensusAdminCommand) -> anyhow::Result<()> {
+        self.command_tx
+            .clone()
+            .send(command)
+            .await
+            .map_err(|_| anyhow!("Consensus is not running"))
+    }
+}
@@ -435,6 +435,10 @@ impl BlockTree {
             block_id = block_to_commit.id(),
         );
 
+        if let Err(e) = storage.archive_commit_certificate(commit_proof.ledger_info()) {
+            warn!(error = ?e, "fail to archive commit certificate");
+        }
+
         let id_to_remove = self.find_blocks_to_prune(block_to_commit.id());
         if let Err(e) = storage.prune_tree(id_to_remove.clone().into_iter().collect()) {
             // it's fine to fail here, as long as the commit succeeds, the next restart will clean
@@ -0,0 +1,12 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+/// The stream of consensus observer messages received from the network
+pub mod network_events;
+/// The messages exchanged between the consensus publisher and observers
+pub mod network_message;
+mod observer;
+/// The publisher that pushes ordered blocks to the subscribed observers
+pub mod publisher;
+
+pub(crate) use observer::ConsensusObserver;
@@ -0,0 +1,127 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::consensus_observer::network_message::{
+    ConsensusObserverDirectSend, ConsensusObserverMessage, ConsensusObserverRequest,
+    ConsensusObserverResponse,
+};
+use aptos_config::network_id::{NetworkId, PeerNetworkId};
+use aptos_network::{
+    application::interface::NetworkServiceEvents,
+    protocols::network::{Event, RpcError},
+};
+use bytes::Bytes;
+use futures::{
+    channel::oneshot,
+    future,
+    stream::{select_all, BoxStream, Stream, StreamExt},
+};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A single consensus observer network message, together with the peer that sent it
+pub enum ConsensusObserverNetworkMessage {
+    /// A subscription request (which must be answered using the response sender)
+    Request {
+        /// The peer that sent the request
+        peer_network_id: PeerNetworkId,
+        /// The subscription request
+        request: ConsensusObserverRequest,
+        /// The channel to respond to the request
+        response_sender: ResponseSender,
+    },
+    /// A direct send notification (e.g., an ordered block)
+    DirectSend {
+        /// The peer that sent the notification
+        peer_network_id: PeerNetworkId,
+        /// The notification itself
+        message: ConsensusObserverDirectSend,
+    },
+}
+
+/// A stream of consensus observer messages from the network
+pub struct ConsensusObserverNetworkEvents {
+    network_message_stream: BoxStream<'static, ConsensusObserverNetworkMessage>,
+}
+
+impl ConsensusObserverNetworkEvents {
+    /// Creates a new stream from the given network service events
+    pub fn new(network_service_events: NetworkServiceEvents<ConsensusObserverMessage>) -> Self {
+        // Transform the event streams to also include the network ID
+        let network_events: Vec<_> = network_service_events
+            .into_network_and_events()
+            .into_iter()
+            .map(|(network_id, events)| events.map(move |event| (network_id, event)))
+            .collect();
+        let network_events = select_all(network_events).fuse();
+
+        // Transform each event to a network message
+        let network_message_stream = network_events
+            .filter_map(|(network_id, event)| {
+                future::ready(Self::event_to_message(network_id, event))
+            })
+            .boxed();
+
+        Self {
+            network_message_stream,
+        }
+    }
+
+    /// Filters out everything except subscription requests and direct sends
+    fn event_to_message(
+        network_id: NetworkId,
+        event: Event<ConsensusObserverMessage>,
+    ) -> Option<ConsensusObserverNetworkMessage> {
+        match event {
+            Event::RpcRequest(
+                peer_id,
+                ConsensusObserverMessage::Request(request),
+                _protocol_id,
+                response_tx,
+            ) => Some(ConsensusObserverNetworkMessage::Request {
+                peer_network_id: PeerNetworkId::new(network_id, peer_id),
+                request,
+                response_sender: ResponseSender::new(response_tx),
+            }),
+            Event::Message(peer_id, ConsensusObserverMessage::DirectSend(message)) => {
+                Some(ConsensusObserverNetworkMessage::DirectSend {
+                    peer_network_id: PeerNetworkId::new(network_id, peer_id),
+                    message,
+                })
+            },
+            _ => None, // We don't care about connection events or misrouted messages
+        }
+    }
+}
+
+impl Stream for ConsensusObserverNetworkEvents {
+    type Item = ConsensusObserverNetworkMessage;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.network_message_stream).poll_next(cx)
+    }
+}
+
+/// A channel for fulfilling a pending subscription request.
+/// Provides a more strongly typed interface around the raw RPC response channel.
+pub struct ResponseSender {
+    response_tx: oneshot::Sender<Result<Bytes, RpcError>>,
+}
+
+impl ResponseSender {
+    /// Creates a new response sender from the raw RPC response channel
+    pub fn new(response_tx: oneshot::Sender<Result<Bytes, RpcError>>) -> Self {
+        Self { response_tx }
+    }
+
+    /// Sends the given response to the requesting peer
+    pub fn send(self, response: ConsensusObserverResponse) {
+        let message = ConsensusObserverMessage::Response(response);
+        let result = bcs::to_bytes(&message)
+            .map(Bytes::from)
+            .map_err(RpcError::BcsError);
+        let _ = self.response_tx.send(result);
+    }
+}
@@ -0,0 +1,547 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::types::BatchPayload;
+use anyhow::{bail, ensure};
+use aptos_consensus_types::{
+    block::Block,
+    common::{Payload, Round},
+};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_types::{
+    epoch_change::Verifier, epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures,
+    transaction::SignedTransaction,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Types of messages that can be sent between the consensus publisher and observer
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum ConsensusObserverMessage {
+    /// A subscription request (sent by the observer over RPC)
+    Request(ConsensusObserverRequest),
+    /// A response to a subscription request (sent by the publisher)
+    Response(ConsensusObserverResponse),
+    /// A block notification (sent by the publisher to all subscribers)
+    DirectSend(ConsensusObserverDirectSend),
+}
+
+impl ConsensusObserverMessage {
+    /// Returns a summary label for the message
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ConsensusObserverMessage::Request(request) => request.get_label(),
+            ConsensusObserverMessage::Response(response) => response.get_label(),
+            ConsensusObserverMessage::DirectSend(direct_send) => direct_send.get_label(),
+        }
+    }
+}
+
+/// Subscription requests sent by the observer to the publisher
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ConsensusObserverRequest {
+    /// Subscribes to the ordered blocks, payloads and commit decisions of the publisher
+    Subscribe,
+    /// Terminates an existing subscription
+    Unsubscribe,
+}
+
+impl ConsensusObserverRequest {
+    /// Returns a summary label for the request
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ConsensusObserverRequest::Subscribe => "subscribe",
+            ConsensusObserverRequest::Unsubscribe => "unsubscribe",
+        }
+    }
+}
+
+/// Responses sent by the publisher to subscription requests
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ConsensusObserverResponse {
+    /// The subscription was accepted
+    SubscribeAck,
+    /// The subscription was rejected (e.g., because the publisher is at capacity)
+    SubscribeRejected(String),
+    /// The subscription was terminated
+    UnsubscribeAck,
+}
+
+impl ConsensusObserverResponse {
+    /// Returns a summary label for the response
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ConsensusObserverResponse::SubscribeAck => "subscribe_ack",
+            ConsensusObserverResponse::SubscribeRejected(_) => "subscribe_rejected",
+            ConsensusObserverResponse::UnsubscribeAck => "unsubscribe_ack",
+        }
+    }
+}
+
+/// Notifications pushed by the publisher to its subscribers
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum ConsensusObserverDirectSend {
+    /// A chain of blocks that was ordered by consensus
+    OrderedBlock(OrderedBlock),
+    /// The transactions of a block (quorum store payloads only carry batch proofs)
+    BlockPayload(BlockPayload),
+    /// A commit decision for the previously ordered blocks
+    CommitDecision(CommitDecision),
+}
+
+impl ConsensusObserverDirectSend {
+    /// Returns a summary label for the direct send notification
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ConsensusObserverDirectSend::OrderedBlock(_) => "ordered_block",
+            ConsensusObserverDirectSend::BlockPayload(_) => "block_payload",
+            ConsensusObserverDirectSend::CommitDecision(_) => "commit_decision",
+        }
+    }
+}
+
+impl Display for ConsensusObserverDirectSend {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ConsensusObserverDirectSend::OrderedBlock(ordered_block) => {
+                write!(f, "OrderedBlock: {}", ordered_block.ordered_proof)
+            },
+            ConsensusObserverDirectSend::BlockPayload(block_payload) => write!(
+                f,
+                "BlockPayload: (epoch: {}, round: {}, id: {}), num txns: {}",
+                block_payload.epoch,
+                block_payload.round,
+                block_payload.block_id,
+                block_payload.transactions.len()
+            ),
+            ConsensusObserverDirectSend::CommitDecision(commit_decision) => {
+                write!(f, "CommitDecision: {}", commit_decision.commit_proof)
+            },
+        }
+    }
+}
+
+/// A chain of ordered blocks, together with the proof that orders them
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct OrderedBlock {
+    /// The ordered blocks (in order of ascending round)
+    pub blocks: Vec<Block>,
+    /// The ordering proof, which certifies the last block in the chain
+    pub ordered_proof: LedgerInfoWithSignatures,
+}
+
+impl OrderedBlock {
+    /// Creates a new ordered block notification
+    pub fn new(blocks: Vec<Block>, ordered_proof: LedgerInfoWithSignatures) -> Self {
+        Self {
+            blocks,
+            ordered_proof,
+        }
+    }
+
+    /// Returns the first block in the chain
+    pub fn first_block(&self) -> &Block {
+        self.blocks
+            .first()
+            .expect("The ordered blocks should not be empty!")
+    }
+
+    /// Returns the last block in the chain
+    pub fn last_block(&self) -> &Block {
+        self.blocks
+            .last()
+            .expect("The ordered blocks should not be empty!")
+    }
+
+    /// Verifies that the blocks form a chain that is certified by the ordered proof
+    pub fn verify_ordered_blocks(&self) -> anyhow::Result<()> {
+        ensure!(!self.blocks.is_empty(), "Received empty ordered blocks!");
+
+        // Verify the blocks are chained together
+        for (parent, child) in self.blocks.iter().zip(self.blocks.iter().skip(1)) {
+            ensure!(
+                child.parent_id() == parent.id(),
+                "The ordered blocks are not chained! Block {} has parent {}, expected {}",
+                child.id(),
+                child.parent_id(),
+                parent.id()
+            );
+        }
+
+        // Verify the last block is the one certified by the ordered proof
+        let last_block = self.last_block();
+        let ordered_info = self.ordered_proof.commit_info();
+        ensure!(
+            last_block.id() == ordered_info.id()
+                && last_block.round() == ordered_info.round()
+                && last_block.epoch() == ordered_info.epoch(),
+            "The last ordered block ({}) doesn't match the ordered proof ({})!",
+            last_block,
+            ordered_info
+        );
+
+        Ok(())
+    }
+
+    /// Verifies the signatures of the ordered proof against the given epoch state
+    pub fn verify_ordered_proof(&self, epoch_state: &EpochState) -> anyhow::Result<()> {
+        epoch_state.verify(&self.ordered_proof)
+    }
+}
+
+/// The transactions of an ordered block
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockPayload {
+    /// The epoch of the block
+    pub epoch: u64,
+    /// The round of the block
+    pub round: Round,
+    /// The id of the block
+    pub block_id: HashValue,
+    /// The transactions of the block (before deduplication and shuffling)
+    pub transactions: Vec<SignedTransaction>,
+}
+
+impl BlockPayload {
+    /// Creates a new payload notification for the given block
+    pub fn new(block: &Block, transactions: Vec<SignedTransaction>) -> Self {
+        Self {
+            epoch: block.epoch(),
+            round: block.round(),
+            block_id: block.id(),
+            transactions,
+        }
+    }
+
+    /// Returns the transactions of the given block (if this payload belongs to it)
+    pub fn transactions_for_block(&self, block: &Block) -> anyhow::Result<&[SignedTransaction]> {
+        if self.block_id != block.id() {
+            bail!(
+                "The payload for (epoch: {}, round: {}) belongs to block {}, not block {}!",
+                self.epoch,
+                self.round,
+                self.block_id,
+                block.id()
+            );
+        }
+        Ok(&self.transactions)
+    }
+
+    /// Verifies that the transactions are exactly those of the (unexpired) batches
+    /// certified by the proofs of store in the given block, i.e., that the digest of
+    /// each batch matches its proof.
+    pub fn verify_against_block(&self, block: &Block) -> anyhow::Result<()> {
+        ensure!(
+            self.epoch == block.epoch() && self.round == block.round(),
+            "The payload for (epoch: {}, round: {}) doesn't match the block {}!",
+            self.epoch,
+            self.round,
+            block
+        );
+        let transactions = self.transactions_for_block(block)?;
+        let proof_with_data = match block.payload() {
+            Some(Payload::InQuorumStore(proof_with_data)) => proof_with_data,
+            _ => bail!("Block {} doesn't have a quorum store payload!", block.id()),
+        };
+
+        // Verify the transactions of each batch against the digest of its proof
+        let mut remaining_transactions = transactions;
+        for proof in &proof_with_data.proofs {
+            // Expired batches are skipped by the payload manager, so they aren't published
+            if block.timestamp_usecs() > proof.expiration() {
+                continue;
+            }
+
+            let num_txns = proof.num_txns() as usize;
+            ensure!(
+                remaining_transactions.len() >= num_txns,
+                "The payload of block {} is missing transactions for batch {}!",
+                block.id(),
+                proof.digest()
+            );
+            let (batch_transactions, next_transactions) = remaining_transactions.split_at(num_txns);
+            let batch_digest =
+                BatchPayload::new(proof.author(), batch_transactions.to_vec()).hash();
+            ensure!(
+                batch_digest == *proof.digest(),
+                "The payload of block {} doesn't match batch {}! Found digest: {}",
+                block.id(),
+                proof.digest(),
+                batch_digest
+            );
+            remaining_transactions = next_transactions;
+        }
+        ensure!(
+            remaining_transactions.is_empty(),
+            "The payload of block {} has {} unexpected transactions!",
+            block.id(),
+            remaining_transactions.len()
+        );
+
+        Ok(())
+    }
+}
+
+/// The commit decision for previously ordered blocks
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CommitDecision {
+    /// The proof that commits the blocks (up to and including the certified block)
+    pub commit_proof: LedgerInfoWithSignatures,
+}
+
+impl CommitDecision {
+    /// Creates a new commit decision notification
+    pub fn new(commit_proof: LedgerInfoWithSignatures) -> Self {
+        Self { commit_proof }
+    }
+
+    /// Returns the epoch of the commit decision
+    pub fn epoch(&self) -> u64 {
+        self.commit_proof.ledger_info().epoch()
+    }
+
+    /// Returns the round of the commit decision
+    pub fn round(&self) -> Round {
+        self.commit_proof.ledger_info().round()
+    }
+
+    /// Verifies the signatures of the commit proof against the given epoch state
+    pub fn verify_commit_proof(&self, epoch_state: &EpochState) -> anyhow::Result<()> {
+        epoch_state.verify(&self.commit_proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_consensus_types::{
+        block::block_test_utils::{certificate_for_genesis, placeholder_certificate_for_block},
+        common::ProofWithData,
+        proof_of_store::{BatchId, BatchInfo, ProofOfStore},
+    };
+    use aptos_crypto::{
+        ed25519::Ed25519PrivateKey, hash::ACCUMULATOR_PLACEHOLDER_HASH, PrivateKey, Uniform,
+    };
+    use aptos_types::{
+        account_address::AccountAddress,
+        aggregate_signature::{AggregateSignature, PartialSignatures},
+        block_info::BlockInfo,
+        ledger_info::LedgerInfo,
+        test_helpers::transaction_test_helpers::get_test_signed_txn,
+        validator_signer::ValidatorSigner,
+        validator_verifier::{random_validator_verifier, ValidatorVerifier},
+        PeerId,
+    };
+
+    #[test]
+    fn test_verify_ordered_blocks() {
+        // Create a chain of blocks
+        let (signers, _) = random_validator_verifier(1, None, false);
+        let first_block = Block::new_nil(1, certificate_for_genesis(), vec![]);
+        let second_block = create_child_block(&signers, &first_block);
+
+        // Verify that the chained blocks are accepted
+        let ordered_block = OrderedBlock::new(
+            vec![first_block.clone(), second_block.clone()],
+            create_ordered_proof(&second_block),
+        );
+        ordered_block.verify_ordered_blocks().unwrap();
+
+        // Verify that empty ordered blocks are rejected
+        let empty_ordered_block = OrderedBlock::new(vec![], create_ordered_proof(&second_block));
+        assert!(empty_ordered_block.verify_ordered_blocks().is_err());
+
+        // Verify that unchained blocks are rejected
+        let unchained_block = Block::new_nil(3, certificate_for_genesis(), vec![]);
+        let unchained_ordered_block = OrderedBlock::new(
+            vec![first_block.clone(), unchained_block.clone()],
+            create_ordered_proof(&unchained_block),
+        );
+        assert!(unchained_ordered_block.verify_ordered_blocks().is_err());
+
+        // Verify that blocks that don't match the ordered proof are rejected
+        let mismatched_ordered_block =
+            OrderedBlock::new(vec![first_block], create_ordered_proof(&second_block));
+        assert!(mismatched_ordered_block.verify_ordered_blocks().is_err());
+    }
+
+    #[test]
+    fn test_verify_proofs() {
+        // Create a block and an ordered proof signed by a set of validators
+        let (signers, verifier) = random_validator_verifier(4, None, false);
+        let block = Block::new_nil(1, certificate_for_genesis(), vec![]);
+        let ledger_info = LedgerInfo::new(create_block_info(&block), HashValue::zero());
+        let partial_signatures = signers
+            .iter()
+            .map(|signer| (signer.author(), signer.sign(&ledger_info).unwrap()))
+            .collect();
+        let aggregate_signature = verifier
+            .aggregate_signatures(&PartialSignatures::new(partial_signatures))
+            .unwrap();
+        let ordered_proof = LedgerInfoWithSignatures::new(ledger_info, aggregate_signature);
+
+        // Verify the proofs against the correct epoch state
+        let epoch_state = EpochState {
+            epoch: block.epoch(),
+            verifier,
+        };
+        let ordered_block = OrderedBlock::new(vec![block.clone()], ordered_proof.clone());
+        ordered_block.verify_ordered_proof(&epoch_state).unwrap();
+        CommitDecision::new(ordered_proof)
+            .verify_commit_proof(&epoch_state)
+            .unwrap();
+
+        // Verify unsigned proofs are rejected
+        let unsigned_block = OrderedBlock::new(vec![block.clone()], create_ordered_proof(&block));
+        assert!(unsigned_block.verify_ordered_proof(&epoch_state).is_err());
+
+        // Verify proofs from a different epoch are rejected
+        let other_epoch_state = EpochState {
+            epoch: block.epoch() + 1,
+            verifier: ValidatorVerifier::new(vec![]),
+        };
+        assert!(ordered_block
+            .verify_ordered_proof(&other_epoch_state)
+            .is_err());
+    }
+
+    #[test]
+    fn test_block_payload() {
+        // Create a block payload
+        let block = Block::new_nil(1, certificate_for_genesis(), vec![]);
+        let block_payload = BlockPayload::new(&block, vec![]);
+
+        // Verify the payload is returned for the matching block
+        assert!(block_payload
+            .transactions_for_block(&block)
+            .unwrap()
+            .is_empty());
+
+        // Verify the payload is rejected for other blocks
+        let other_block = Block::new_nil(2, certificate_for_genesis(), vec![]);
+        assert!(block_payload.transactions_for_block(&other_block).is_err());
+    }
+
+    #[test]
+    fn test_verify_block_payload() {
+        // Create a block with two batches (the second of which has expired)
+        let (signers, _) = random_validator_verifier(1, None, false);
+        let signer = &signers[0];
+        let first_batch = create_transactions(2);
+        let expired_batch = create_transactions(1);
+        let block_timestamp = 100;
+        let proofs = vec![
+            create_proof_of_store(signer.author(), &first_batch, block_timestamp),
+            create_proof_of_store(signer.author(), &expired_batch, block_timestamp - 1),
+        ];
+        let block = Block::new_proposal(
+            Payload::InQuorumStore(ProofWithData::new(proofs)),
+            1,
+            block_timestamp,
+            certificate_for_genesis(),
+            signer,
+            vec![],
+        )
+        .unwrap();
+
+        // Verify the payload with the transactions of the unexpired batch is accepted
+        BlockPayload::new(&block, first_batch.clone())
+            .verify_against_block(&block)
+            .unwrap();
+
+        // Verify payloads with missing, extra or modified transactions are rejected
+        let missing_transactions = first_batch[..1].to_vec();
+        let extra_transactions = [first_batch.clone(), expired_batch].concat();
+        let modified_transactions = vec![first_batch[0].clone(), create_transactions(1)[0].clone()];
+        let reordered_transactions = vec![first_batch[1].clone(), first_batch[0].clone()];
+        for transactions in [
+            missing_transactions,
+            extra_transactions,
+            modified_transactions,
+            reordered_transactions,
+        ] {
+            assert!(BlockPayload::new(&block, transactions)
+                .verify_against_block(&block)
+                .is_err());
+        }
+
+        // Verify the payload is rejected for other blocks
+        let nil_block = Block::new_nil(1, certificate_for_genesis(), vec![]);
+        assert!(BlockPayload::new(&block, first_batch.clone())
+            .verify_against_block(&nil_block)
+            .is_err());
+        assert!(BlockPayload::new(&nil_block, first_batch)
+            .verify_against_block(&nil_block)
+            .is_err());
+    }
+
+    /// Creates a nil block that extends the given parent block
+    fn create_child_block(signers: &[ValidatorSigner], parent: &Block) -> Block {
+        let quorum_cert = placeholder_certificate_for_block(
+            signers,
+            parent.id(),
+            parent.round(),
+            parent.parent_id(),
+            parent.round() - 1,
+        );
+        Block::new_nil(parent.round() + 1, quorum_cert, vec![])
+    }
+
+    /// Creates an (unsigned) ordered proof for the given block
+    fn create_ordered_proof(block: &Block) -> LedgerInfoWithSignatures {
+        LedgerInfoWithSignatures::new(
+            LedgerInfo::new(create_block_info(block), HashValue::zero()),
+            AggregateSignature::empty(),
+        )
+    }
+
+    /// Creates a proof of store (without signatures) for the given batch
+    fn create_proof_of_store(
+        author: PeerId,
+        transactions: &[SignedTransaction],
+        expiration: u64,
+    ) -> ProofOfStore {
+        let digest = BatchPayload::new(author, transactions.to_vec()).hash();
+        let batch_info = BatchInfo::new(
+            author,
+            BatchId::new_for_test(expiration),
+            1,
+            expiration,
+            digest,
+            transactions.len() as u64,
+            0,
+            0,
+        );
+        ProofOfStore::new(batch_info, AggregateSignature::empty())
+    }
+
+    /// Creates the given number of transactions for a random account
+    fn create_transactions(num_transactions: u64) -> Vec<SignedTransaction> {
+        let sender = AccountAddress::random();
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        (0..num_transactions)
+            .map(|sequence_number| {
+                get_test_signed_txn(
+                    sender,
+                    sequence_number,
+                    &private_key,
+                    private_key.public_key(),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    /// Creates the block info (with a placeholder execution state) for the given block
+    fn create_block_info(block: &Block) -> BlockInfo {
+        BlockInfo::new(
+            block.epoch(),
+            block.round(),
+            block.id(),
+            *ACCUMULATOR_PLACEHOLDER_HASH,
+            0,
+            block.timestamp_usecs(),
+            None,
+        )
+    }
+}
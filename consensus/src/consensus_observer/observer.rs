@@ -0,0 +1,1174 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::{
+        network_events::{ConsensusObserverNetworkEvents, ConsensusObserverNetworkMessage},
+        network_message::{
+            BlockPayload, CommitDecision, ConsensusObserverDirectSend, ConsensusObserverMessage,
+            ConsensusObserverRequest, ConsensusObserverResponse, OrderedBlock,
+        },
+    },
+    counters,
+    payload_manager::PayloadManager,
+    payload_orderer::create_payload_orderer,
+    state_replication::StateComputer,
+    transaction_deduper::create_transaction_deduper,
+    transaction_shuffler::create_transaction_shuffler,
+};
+use aptos_config::{config::ConsensusObserverConfig, network_id::PeerNetworkId};
+use aptos_consensus_types::{
+    block::Block,
+    common::{Payload, Round},
+    executed_block::ExecutedBlock,
+};
+use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_network::application::interface::{NetworkClient, NetworkClientInterface};
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::{
+    epoch_state::EpochState,
+    on_chain_config::{OnChainConfigPayload, OnChainExecutionConfig, ValidatorSet},
+};
+use futures::{FutureExt, StreamExt};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The consensus observer runs on fullnodes (e.g., VFNs). It subscribes to a
+/// consensus publisher (e.g., its validator), and executes and commits the
+/// ordered blocks it receives, without participating in consensus. If the
+/// observer falls behind (or receives unexpected blocks), it uses state sync
+/// to catch up to the latest commit decision.
+pub struct ConsensusObserver {
+    // The configuration of the consensus observer
+    consensus_observer_config: ConsensusObserverConfig,
+
+    // The network client used to manage the subscription
+    network_client: NetworkClient<ConsensusObserverMessage>,
+
+    // The execution client used to execute, commit and sync the blocks
+    execution_client: Arc<dyn StateComputer>,
+
+    // The reconfiguration listener used to follow epoch changes
+    reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+
+    // The state of the current epoch
+    epoch_state: Option<Arc<EpochState>>,
+
+    // The currently active subscription (if any)
+    active_subscription: Option<ActiveSubscription>,
+
+    // The epoch and round of the latest committed (or synced) block
+    root: (u64, Round),
+
+    // Whether the executor must be synced to a commit decision before it can execute
+    // blocks again (e.g., because the observer missed blocks or execution failed).
+    sync_required: bool,
+
+    // The ordered blocks that are yet to be executed
+    pending_ordered_blocks: PendingOrderedBlocks,
+
+    // The executed blocks that are yet to be committed
+    executed_blocks: Vec<Arc<ExecutedBlock>>,
+
+    // The latest commit decision for blocks that are yet to be executed
+    pending_commit_decision: Option<CommitDecision>,
+
+    // The received block payloads that are yet to be verified against their blocks
+    unverified_block_payloads: BTreeMap<(u64, Round), BlockPayload>,
+
+    // The verified block payloads (shared with the payload manager)
+    block_payloads: Arc<Mutex<BTreeMap<(u64, Round), BlockPayload>>>,
+
+    // The time service
+    time_service: TimeService,
+}
+
+/// A subscription to a consensus publisher
+struct ActiveSubscription {
+    peer_network_id: PeerNetworkId,
+    last_message_time: Instant,
+}
+
+impl ConsensusObserver {
+    /// Creates a new consensus observer
+    pub fn new(
+        consensus_observer_config: ConsensusObserverConfig,
+        network_client: NetworkClient<ConsensusObserverMessage>,
+        execution_client: Arc<dyn StateComputer>,
+        reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+        time_service: TimeService,
+    ) -> Self {
+        let max_num_pending_blocks = consensus_observer_config.max_num_pending_blocks as usize;
+        Self {
+            consensus_observer_config,
+            network_client,
+            execution_client,
+            reconfig_events,
+            epoch_state: None,
+            active_subscription: None,
+            root: (0, 0),
+            sync_required: true,
+            pending_ordered_blocks: PendingOrderedBlocks::new(max_num_pending_blocks),
+            executed_blocks: vec![],
+            pending_commit_decision: None,
+            unverified_block_payloads: BTreeMap::new(),
+            block_payloads: Arc::new(Mutex::new(BTreeMap::new())),
+            time_service,
+        }
+    }
+
+    /// Returns the current epoch state
+    fn epoch_state(&self) -> Arc<EpochState> {
+        self.epoch_state
+            .clone()
+            .expect("The consensus observer has not started an epoch!")
+    }
+
+    /// Starts the observer loop
+    pub async fn start(mut self, mut network_events: ConsensusObserverNetworkEvents) {
+        let mut progress_check_interval = tokio::time::interval(Duration::from_millis(
+            self.consensus_observer_config
+                .observer_progress_check_interval_ms,
+        ));
+
+        // Wait for the initial epoch to start
+        self.wait_for_epoch_start(0).await;
+
+        info!("Started the consensus observer!");
+        loop {
+            tokio::select! {
+                Some(network_message) = network_events.next() => {
+                    self.handle_network_message(network_message).await;
+                },
+                _ = progress_check_interval.tick() => {
+                    self.check_progress().await;
+                },
+            }
+        }
+    }
+
+    /// Handles a message received from the network
+    async fn handle_network_message(&mut self, network_message: ConsensusObserverNetworkMessage) {
+        let (peer_network_id, message) = match network_message {
+            ConsensusObserverNetworkMessage::DirectSend {
+                peer_network_id,
+                message,
+            } => (peer_network_id, message),
+            ConsensusObserverNetworkMessage::Request {
+                peer_network_id,
+                request,
+                response_sender,
+            } => {
+                // Observers don't accept subscriptions
+                warn!(
+                    "The observer received an unexpected {} request from {}",
+                    request.get_label(),
+                    peer_network_id
+                );
+                response_sender.send(ConsensusObserverResponse::SubscribeRejected(
+                    "Consensus observers don't accept subscriptions!".into(),
+                ));
+                return;
+            },
+        };
+
+        // Only process messages from the active subscription
+        match self.active_subscription.as_mut() {
+            Some(subscription) if subscription.peer_network_id == peer_network_id => {
+                subscription.last_message_time = self.time_service.now();
+            },
+            _ => {
+                debug!(
+                    "Ignoring the {} message from {}. It is not the active subscription!",
+                    message.get_label(),
+                    peer_network_id
+                );
+                return;
+            },
+        }
+        counters::CONSENSUS_OBSERVER_MESSAGES
+            .with_label_values(&[message.get_label(), "received"])
+            .inc();
+
+        match message {
+            ConsensusObserverDirectSend::OrderedBlock(ordered_block) => {
+                self.process_ordered_block(ordered_block).await
+            },
+            ConsensusObserverDirectSend::BlockPayload(block_payload) => {
+                self.process_block_payload(block_payload).await
+            },
+            ConsensusObserverDirectSend::CommitDecision(commit_decision) => {
+                self.process_commit_decision(commit_decision).await
+            },
+        }
+    }
+
+    /// Processes an ordered block notification
+    async fn process_ordered_block(&mut self, ordered_block: OrderedBlock) {
+        // Verify the ordered blocks and their proof
+        let epoch_state = self.epoch_state();
+        if ordered_block.ordered_proof.ledger_info().epoch() != epoch_state.epoch {
+            debug!(
+                "Ignoring ordered block for a different epoch: {}",
+                ordered_block.ordered_proof
+            );
+            return;
+        }
+        if let Err(error) = ordered_block
+            .verify_ordered_blocks()
+            .and_then(|_| ordered_block.verify_ordered_proof(&epoch_state))
+        {
+            warn!(
+                "Failed to verify the ordered block {}: {:?}",
+                ordered_block.ordered_proof, error
+            );
+            return;
+        }
+
+        // Ignore the ordered block if it's out of date
+        let last_block = ordered_block.last_block();
+        let last_block_epoch_round = (last_block.epoch(), last_block.round());
+        if last_block_epoch_round <= self.highest_known_epoch_round() {
+            return;
+        }
+
+        // Buffer the ordered block and try to execute it
+        counters::CONSENSUS_OBSERVER_ROUND
+            .with_label_values(&["ordered"])
+            .set(last_block.round() as i64);
+        if let Err(error) = self.pending_ordered_blocks.insert(ordered_block) {
+            warn!("Failed to buffer the ordered block: {:?}", error);
+            return;
+        }
+        self.execute_pending_blocks().await;
+    }
+
+    /// Processes a block payload notification
+    async fn process_block_payload(&mut self, block_payload: BlockPayload) {
+        // Ignore the payload if the block is already committed
+        if (block_payload.epoch, block_payload.round) <= self.root {
+            return;
+        }
+
+        // Store the payload (it's verified once its block is ordered), and
+        // try to execute the pending blocks.
+        self.unverified_block_payloads
+            .insert((block_payload.epoch, block_payload.round), block_payload);
+        self.execute_pending_blocks().await;
+    }
+
+    /// Processes a commit decision notification
+    async fn process_commit_decision(&mut self, commit_decision: CommitDecision) {
+        // Verify the commit decision
+        let epoch_state = self.epoch_state();
+        if commit_decision.epoch() != epoch_state.epoch {
+            debug!(
+                "Ignoring commit decision for a different epoch: {}",
+                commit_decision.commit_proof
+            );
+            return;
+        }
+        if let Err(error) = commit_decision.verify_commit_proof(&epoch_state) {
+            warn!(
+                "Failed to verify the commit decision {}: {:?}",
+                commit_decision.commit_proof, error
+            );
+            return;
+        }
+
+        // Ignore the commit decision if the block is already committed
+        if (commit_decision.epoch(), commit_decision.round()) <= self.root {
+            return;
+        }
+
+        // If the executor needs to be synced, sync to the commit decision
+        if self.sync_required {
+            return self
+                .sync_to_commit_decision(commit_decision, "sync_required")
+                .await;
+        }
+
+        // If the block has been executed, commit it
+        let commit_round = commit_decision.round();
+        if let Some(executed_block) = self
+            .executed_blocks
+            .iter()
+            .find(|executed_block| executed_block.round() == commit_round)
+        {
+            if executed_block.block_info() == *commit_decision.commit_proof.commit_info() {
+                self.commit_executed_blocks(commit_decision).await;
+            } else {
+                error!(
+                    "The executed block {} doesn't match the commit decision {}! Falling back to state sync.",
+                    executed_block.block_info(),
+                    commit_decision.commit_proof
+                );
+                self.sync_to_commit_decision(commit_decision, "execution_mismatch")
+                    .await;
+            }
+            return;
+        }
+
+        // If the block is still pending execution, commit it once it's executed.
+        // Otherwise, we've missed the block, so fall back to state sync.
+        if self
+            .pending_ordered_blocks
+            .contains_block(commit_decision.epoch(), commit_round)
+        {
+            self.pending_commit_decision = Some(commit_decision);
+        } else {
+            self.sync_to_commit_decision(commit_decision, "missing_blocks")
+                .await;
+        }
+    }
+
+    /// Executes all pending blocks whose payloads are available
+    async fn execute_pending_blocks(&mut self) {
+        if self.sync_required {
+            return; // The executor must be synced first
+        }
+
+        while let Some(ordered_block) = self.pending_ordered_blocks.first() {
+            // Check that all payloads are available
+            let blocks_to_execute: Vec<Block> = ordered_block
+                .blocks
+                .iter()
+                .filter(|block| {
+                    (block.epoch(), block.round()) > self.highest_executed_epoch_round()
+                })
+                .cloned()
+                .collect();
+            let mut all_payloads_available = true;
+            for block in &blocks_to_execute {
+                match self.verify_payload(block) {
+                    Ok(payload_available) => all_payloads_available &= payload_available,
+                    Err(error) => {
+                        warn!(
+                            "Failed to verify the payload of block {}: {:?}. Waiting to sync to the next commit decision.",
+                            block, error
+                        );
+                        counters::CONSENSUS_OBSERVER_INVALID_PAYLOADS.inc();
+                        self.reset_execution_state();
+                        return;
+                    },
+                }
+            }
+            if !all_payloads_available {
+                break; // Wait for the missing payloads
+            }
+            self.pending_ordered_blocks.remove_first();
+
+            // Execute the blocks
+            for block in blocks_to_execute {
+                match self
+                    .execution_client
+                    .compute(&block, block.parent_id())
+                    .await
+                {
+                    Ok(compute_result) => {
+                        counters::CONSENSUS_OBSERVER_ROUND
+                            .with_label_values(&["executed"])
+                            .set(block.round() as i64);
+                        self.executed_blocks
+                            .push(Arc::new(ExecutedBlock::new(block, compute_result)));
+                    },
+                    Err(error) => {
+                        warn!(
+                            "Failed to execute block {}: {:?}. Waiting to sync to the next commit decision.",
+                            block, error
+                        );
+                        self.reset_execution_state();
+                        return;
+                    },
+                }
+            }
+        }
+
+        // Commit the executed blocks if the commit decision was waiting for them
+        if let Some(commit_decision) = self.pending_commit_decision.take() {
+            if (commit_decision.epoch(), commit_decision.round())
+                <= self.highest_executed_epoch_round()
+            {
+                self.process_commit_decision(commit_decision).boxed().await;
+            } else {
+                self.pending_commit_decision = Some(commit_decision);
+            }
+        }
+    }
+
+    /// Commits the executed blocks up to (and including) the given commit decision
+    async fn commit_executed_blocks(&mut self, commit_decision: CommitDecision) {
+        let commit_round = commit_decision.round();
+        let split_index = self
+            .executed_blocks
+            .iter()
+            .position(|executed_block| executed_block.round() > commit_round)
+            .unwrap_or(self.executed_blocks.len());
+        let blocks_to_commit: Vec<_> = self.executed_blocks.drain(..split_index).collect();
+
+        // Commit the blocks
+        let commit_proof = commit_decision.commit_proof;
+        if let Err(error) = self
+            .execution_client
+            .commit(&blocks_to_commit, commit_proof.clone(), Box::new(|_, _| {}))
+            .await
+        {
+            error!(
+                "Failed to commit the blocks for {}: {:?}. Waiting to sync to the next commit decision.",
+                commit_proof, error
+            );
+            self.reset_execution_state();
+            return;
+        }
+        counters::CONSENSUS_OBSERVER_ROUND
+            .with_label_values(&["committed"])
+            .set(commit_round as i64);
+        self.update_root(commit_decision.epoch(), commit_round);
+
+        // If the epoch ended, wait for the next epoch to start
+        if commit_proof.ledger_info().ends_epoch() {
+            self.end_epoch().await;
+        }
+    }
+
+    /// Syncs the node to the given commit decision (using state sync)
+    async fn sync_to_commit_decision(&mut self, commit_decision: CommitDecision, reason: &str) {
+        info!(
+            "Syncing to the commit decision {} (reason: {})",
+            commit_decision.commit_proof, reason
+        );
+        counters::CONSENSUS_OBSERVER_STATE_SYNC_FALLBACKS
+            .with_label_values(&[reason])
+            .inc();
+
+        // The executed blocks are discarded when the executor is reset
+        self.executed_blocks.clear();
+        self.pending_commit_decision = None;
+
+        // Sync to the commit decision
+        let commit_proof = commit_decision.commit_proof;
+        if let Err(error) = self.execution_client.sync_to(commit_proof.clone()).await {
+            warn!(
+                "Failed to sync to the commit decision {}: {:?}",
+                commit_proof, error
+            );
+            self.sync_required = true;
+            return;
+        }
+        self.sync_required = false;
+        let commit_round = commit_proof.ledger_info().round();
+        counters::CONSENSUS_OBSERVER_ROUND
+            .with_label_values(&["committed"])
+            .set(commit_round as i64);
+        self.update_root(commit_proof.ledger_info().epoch(), commit_round);
+
+        // If the epoch ended, wait for the next epoch to start. Otherwise,
+        // continue executing any pending blocks.
+        if commit_proof.ledger_info().ends_epoch() {
+            self.end_epoch().await;
+        } else {
+            self.execute_pending_blocks().boxed().await;
+        }
+    }
+
+    /// Updates the root to the given epoch and round, and prunes the old state
+    fn update_root(&mut self, epoch: u64, round: Round) {
+        let root = (epoch, round);
+        self.root = root;
+        self.pending_ordered_blocks.remove_blocks_up_to(root);
+        self.unverified_block_payloads
+            .retain(|epoch_round, _| *epoch_round > root);
+        self.block_payloads
+            .lock()
+            .retain(|epoch_round, _| *epoch_round > root);
+    }
+
+    /// Discards the executed blocks and requires a sync before executing again
+    fn reset_execution_state(&mut self) {
+        self.executed_blocks.clear();
+        self.pending_commit_decision = None;
+        self.sync_required = true;
+    }
+
+    /// Ends the current epoch and waits for the next one to start
+    async fn end_epoch(&mut self) {
+        let next_epoch = self.epoch_state().epoch + 1;
+        self.execution_client.end_epoch();
+        self.executed_blocks.clear();
+        self.pending_commit_decision = None;
+
+        // The blocks of the new epoch start from its genesis block (i.e., round 0)
+        self.update_root(next_epoch, 0);
+        self.wait_for_epoch_start(next_epoch).await;
+    }
+
+    /// Waits for the reconfiguration notification of (at least) the given epoch,
+    /// and starts the new epoch.
+    async fn wait_for_epoch_start(&mut self, min_epoch: u64) {
+        loop {
+            let reconfig_notification = self
+                .reconfig_events
+                .next()
+                .await
+                .expect("Reconfig sender dropped, unable to start new epoch");
+            if reconfig_notification.on_chain_configs.epoch() >= min_epoch {
+                self.start_epoch(reconfig_notification.on_chain_configs);
+                return;
+            }
+        }
+    }
+
+    /// Starts a new epoch using the given on-chain configs
+    fn start_epoch(&mut self, payload: OnChainConfigPayload<DbBackedOnChainConfig>) {
+        let validator_set: ValidatorSet = payload
+            .get()
+            .expect("failed to get ValidatorSet from payload");
+        let epoch_state = Arc::new(EpochState {
+            epoch: payload.epoch(),
+            verifier: (&validator_set).into(),
+        });
+        info!("Consensus observer starting epoch: {}", epoch_state.epoch);
+
+        let onchain_execution_config: OnChainExecutionConfig =
+            payload.get().unwrap_or_else(|error| {
+                error!("Failed to read on-chain execution config {}", error);
+                OnChainExecutionConfig::default_if_missing()
+            });
+        self.execution_client.new_epoch(
+            &epoch_state,
+            Arc::new(PayloadManager::ConsensusObserver(
+                self.block_payloads.clone(),
+            )),
+            create_transaction_shuffler(onchain_execution_config.transaction_shuffler_type()),
+            onchain_execution_config.block_gas_limit(),
+            create_transaction_deduper(onchain_execution_config.transaction_deduper_type()),
+            create_payload_orderer(onchain_execution_config.payload_orderer_type()),
+        );
+        self.epoch_state = Some(epoch_state);
+    }
+
+    /// Checks the health of the subscription (and creates a new one if required)
+    async fn check_progress(&mut self) {
+        // If state sync moved the node into a new epoch, follow it
+        if let Some(Some(reconfig_notification)) = self.reconfig_events.next().now_or_never() {
+            let payload = reconfig_notification.on_chain_configs;
+            if payload.epoch() > self.epoch_state().epoch {
+                self.execution_client.end_epoch();
+                self.reset_execution_state();
+                self.update_root(payload.epoch(), 0);
+                self.start_epoch(payload);
+            }
+        }
+
+        // Terminate the subscription if the publisher is unhealthy
+        if let Some(subscription) = &self.active_subscription {
+            let peer_network_id = subscription.peer_network_id;
+            let max_subscription_timeout =
+                Duration::from_millis(self.consensus_observer_config.max_subscription_timeout_ms);
+            let time_since_last_message = self
+                .time_service
+                .now()
+                .duration_since(subscription.last_message_time);
+            if !self.is_connected(&peer_network_id)
+                || time_since_last_message > max_subscription_timeout
+            {
+                warn!(
+                    "Terminating the subscription to {}. Time since last message: {:?}",
+                    peer_network_id, time_since_last_message
+                );
+                self.send_subscription_request(
+                    peer_network_id,
+                    ConsensusObserverRequest::Unsubscribe,
+                )
+                .await;
+                self.active_subscription = None;
+            }
+        }
+
+        // Create a new subscription if there isn't one
+        if self.active_subscription.is_none() {
+            self.create_subscription().await;
+        }
+    }
+
+    /// Attempts to subscribe to one of the connected publishers
+    async fn create_subscription(&mut self) {
+        let connected_peers = match self.network_client.get_available_peers() {
+            Ok(connected_peers) => connected_peers,
+            Err(error) => {
+                warn!("Failed to fetch the connected peers: {:?}", error);
+                return;
+            },
+        };
+
+        // Only the validator (over the VFN network) can be subscribed to
+        for peer_network_id in connected_peers
+            .into_iter()
+            .filter(|peer_network_id| peer_network_id.network_id().is_vfn_network())
+        {
+            let response = self
+                .send_subscription_request(peer_network_id, ConsensusObserverRequest::Subscribe)
+                .await;
+            if let Some(ConsensusObserverResponse::SubscribeAck) = response {
+                info!("Subscribed to the consensus publisher: {}", peer_network_id);
+
+                // Start from a clean slate (the first commit decision syncs the node)
+                self.active_subscription = Some(ActiveSubscription {
+                    peer_network_id,
+                    last_message_time: self.time_service.now(),
+                });
+                self.pending_ordered_blocks.clear();
+                self.reset_execution_state();
+                return;
+            }
+        }
+    }
+
+    /// Sends a subscription request to the given peer and returns the response (if any)
+    async fn send_subscription_request(
+        &self,
+        peer_network_id: PeerNetworkId,
+        request: ConsensusObserverRequest,
+    ) -> Option<ConsensusObserverResponse> {
+        counters::CONSENSUS_OBSERVER_MESSAGES
+            .with_label_values(&[request.get_label(), "sent"])
+            .inc();
+
+        let request_timeout =
+            Duration::from_millis(self.consensus_observer_config.network_request_timeout_ms);
+        match self
+            .network_client
+            .send_to_peer_rpc(
+                ConsensusObserverMessage::Request(request.clone()),
+                request_timeout,
+                peer_network_id,
+            )
+            .await
+        {
+            Ok(ConsensusObserverMessage::Response(response)) => Some(response),
+            Ok(message) => {
+                warn!(
+                    "Received an unexpected {} response from {}",
+                    message.get_label(),
+                    peer_network_id
+                );
+                None
+            },
+            Err(error) => {
+                warn!(
+                    "Failed to send the {} request to {}: {:?}",
+                    request.get_label(),
+                    peer_network_id,
+                    error
+                );
+                None
+            },
+        }
+    }
+
+    /// Returns true iff the given peer is still connected
+    fn is_connected(&self, peer_network_id: &PeerNetworkId) -> bool {
+        self.network_client
+            .get_available_peers()
+            .map(|connected_peers| connected_peers.contains(peer_network_id))
+            .unwrap_or(false)
+    }
+
+    /// Returns true iff the (verified) transactions of the given block are available.
+    /// Received payloads are verified against the batches certified by the block, and
+    /// an error is returned (and the payload dropped) if they don't match.
+    fn verify_payload(&mut self, block: &Block) -> anyhow::Result<bool> {
+        if !matches!(block.payload(), Some(Payload::InQuorumStore(_))) {
+            return Ok(true); // The transactions are in the block itself
+        }
+
+        let epoch_round = (block.epoch(), block.round());
+        if self.block_payloads.lock().contains_key(&epoch_round) {
+            return Ok(true); // The payload was already verified
+        }
+        match self.unverified_block_payloads.remove(&epoch_round) {
+            Some(block_payload) => {
+                block_payload.verify_against_block(block)?;
+                self.block_payloads
+                    .lock()
+                    .insert(epoch_round, block_payload);
+                Ok(true)
+            },
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the epoch and round of the highest executed (or committed) block
+    fn highest_executed_epoch_round(&self) -> (u64, Round) {
+        self.executed_blocks
+            .last()
+            .map(|executed_block| (executed_block.epoch(), executed_block.round()))
+            .unwrap_or(self.root)
+    }
+
+    /// Returns the epoch and round of the highest known block
+    fn highest_known_epoch_round(&self) -> (u64, Round) {
+        self.pending_ordered_blocks
+            .last_epoch_round()
+            .unwrap_or_else(|| self.highest_executed_epoch_round())
+    }
+}
+
+/// A bounded buffer of ordered blocks that are yet to be executed,
+/// indexed by the epoch and round of their last block.
+struct PendingOrderedBlocks {
+    max_num_pending_blocks: usize,
+    ordered_blocks: BTreeMap<(u64, Round), OrderedBlock>,
+}
+
+impl PendingOrderedBlocks {
+    fn new(max_num_pending_blocks: usize) -> Self {
+        Self {
+            max_num_pending_blocks,
+            ordered_blocks: BTreeMap::new(),
+        }
+    }
+
+    /// Buffers the given ordered block (if there's capacity)
+    fn insert(&mut self, ordered_block: OrderedBlock) -> anyhow::Result<()> {
+        let num_pending_blocks: usize = self
+            .ordered_blocks
+            .values()
+            .map(|ordered_block| ordered_block.blocks.len())
+            .sum();
+        if num_pending_blocks + ordered_block.blocks.len() > self.max_num_pending_blocks {
+            anyhow::bail!(
+                "Too many pending blocks: {}. Dropping ordered block: {}",
+                num_pending_blocks,
+                ordered_block.ordered_proof
+            );
+        }
+
+        let last_block = ordered_block.last_block();
+        self.ordered_blocks
+            .insert((last_block.epoch(), last_block.round()), ordered_block);
+        Ok(())
+    }
+
+    /// Returns true iff a block with the given epoch and round is pending
+    fn contains_block(&self, epoch: u64, round: Round) -> bool {
+        self.ordered_blocks
+            .range((epoch, round)..)
+            .next()
+            .map(|(_, ordered_block)| {
+                ordered_block
+                    .blocks
+                    .iter()
+                    .any(|block| block.epoch() == epoch && block.round() == round)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Returns the first pending ordered block
+    fn first(&self) -> Option<OrderedBlock> {
+        self.ordered_blocks
+            .first_key_value()
+            .map(|(_, ordered_block)| ordered_block.clone())
+    }
+
+    /// Removes the first pending ordered block
+    fn remove_first(&mut self) {
+        self.ordered_blocks.pop_first();
+    }
+
+    /// Returns the epoch and round of the last pending block
+    fn last_epoch_round(&self) -> Option<(u64, Round)> {
+        self.ordered_blocks
+            .last_key_value()
+            .map(|(epoch_round, _)| *epoch_round)
+    }
+
+    /// Removes all ordered blocks whose last block is at (or below) the given epoch and round
+    fn remove_blocks_up_to(&mut self, epoch_round: (u64, Round)) {
+        self.ordered_blocks
+            .retain(|last_epoch_round, _| *last_epoch_round > epoch_round);
+    }
+
+    /// Removes all pending ordered blocks
+    fn clear(&mut self) {
+        self.ordered_blocks.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::StateSyncError, payload_orderer::PayloadOrderer, quorum_store::types::BatchPayload,
+        state_replication::StateComputerCommitCallBackType,
+        transaction_deduper::TransactionDeduper, transaction_shuffler::TransactionShuffler,
+    };
+    use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+    use aptos_config::network_id::NetworkId;
+    use aptos_consensus_types::{
+        block::block_test_utils::{certificate_for_genesis, placeholder_certificate_for_block},
+        common::ProofWithData,
+        proof_of_store::{BatchId, BatchInfo, ProofOfStore},
+    };
+    use aptos_crypto::{
+        ed25519::Ed25519PrivateKey, hash::CryptoHash, HashValue, PrivateKey, Uniform,
+    };
+    use aptos_executor_types::{Error as ExecutionError, StateComputeResult};
+    use aptos_network::application::storage::PeersAndMetadata;
+    use aptos_types::{
+        account_address::AccountAddress,
+        aggregate_signature::{AggregateSignature, PartialSignatures},
+        block_info::BlockInfo,
+        ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+        test_helpers::transaction_test_helpers::get_test_signed_txn,
+        transaction::SignedTransaction,
+        validator_signer::ValidatorSigner,
+        validator_verifier::random_validator_verifier,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_pending_ordered_blocks() {
+        // Create a buffer for 2 blocks and insert 2 ordered blocks
+        let mut pending_ordered_blocks = PendingOrderedBlocks::new(2);
+        pending_ordered_blocks
+            .insert(create_ordered_block(1))
+            .unwrap();
+        pending_ordered_blocks
+            .insert(create_ordered_block(2))
+            .unwrap();
+
+        // Verify the buffer is at capacity
+        assert!(pending_ordered_blocks
+            .insert(create_ordered_block(3))
+            .is_err());
+
+        // Verify the pending blocks (the blocks extend genesis, so they're in epoch 1)
+        assert!(pending_ordered_blocks.contains_block(1, 1));
+        assert!(pending_ordered_blocks.contains_block(1, 2));
+        assert!(!pending_ordered_blocks.contains_block(1, 3));
+        assert_eq!(pending_ordered_blocks.last_epoch_round(), Some((1, 2)));
+        assert_eq!(
+            pending_ordered_blocks.first().unwrap().last_block().round(),
+            1
+        );
+
+        // Remove the first block and verify the remaining one
+        pending_ordered_blocks.remove_first();
+        assert!(!pending_ordered_blocks.contains_block(1, 1));
+        assert_eq!(
+            pending_ordered_blocks.first().unwrap().last_block().round(),
+            2
+        );
+
+        // Insert another block and remove everything up to round 2
+        pending_ordered_blocks
+            .insert(create_ordered_block(3))
+            .unwrap();
+        pending_ordered_blocks.remove_blocks_up_to((1, 2));
+        assert_eq!(
+            pending_ordered_blocks.first().unwrap().last_block().round(),
+            3
+        );
+        assert_eq!(pending_ordered_blocks.last_epoch_round(), Some((1, 3)));
+
+        // Clear the buffer and verify it's empty
+        pending_ordered_blocks.clear();
+        assert!(pending_ordered_blocks.first().is_none());
+        assert!(pending_ordered_blocks.last_epoch_round().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execute_and_commit_blocks() {
+        // Create an observer and two chained blocks (the first with a quorum store payload)
+        let execution_client = Arc::new(MockExecutionClient::default());
+        let (mut observer, signers) = create_observer(execution_client.clone());
+        let transactions = create_transactions(2);
+        let first_block = create_quorum_store_block(&signers, &transactions);
+        let second_block = create_child_block(&signers, &first_block);
+        let ledger_info = create_signed_ledger_info(&observer, &signers, &second_block);
+
+        // Verify the ordered blocks aren't executed until the payload is available
+        observer
+            .process_ordered_block(OrderedBlock::new(
+                vec![first_block.clone(), second_block],
+                ledger_info.clone(),
+            ))
+            .await;
+        assert!(execution_client.executed_rounds.lock().is_empty());
+
+        // Verify both blocks are executed once the payload arrives
+        observer
+            .process_block_payload(BlockPayload::new(&first_block, transactions))
+            .await;
+        assert_eq!(*execution_client.executed_rounds.lock(), vec![1, 2]);
+        assert!(observer.block_payloads.lock().contains_key(&(1, 1)));
+
+        // Verify the blocks are committed by the commit decision
+        observer
+            .process_commit_decision(CommitDecision::new(ledger_info))
+            .await;
+        assert_eq!(*execution_client.committed_rounds.lock(), vec![2]);
+        assert!(execution_client.synced_rounds.lock().is_empty());
+        assert_eq!(observer.root, (1, 2));
+        assert!(observer.executed_blocks.is_empty());
+        assert!(observer.block_payloads.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_block_payload() {
+        // Create an observer and a block with a quorum store payload
+        let execution_client = Arc::new(MockExecutionClient::default());
+        let (mut observer, signers) = create_observer(execution_client.clone());
+        let block = create_quorum_store_block(&signers, &create_transactions(2));
+        let ordered_proof = create_signed_ledger_info(&observer, &signers, &block);
+
+        // Verify a payload that doesn't match the batches is dropped (and not executed)
+        observer
+            .process_ordered_block(OrderedBlock::new(
+                vec![block.clone()],
+                ordered_proof.clone(),
+            ))
+            .await;
+        observer
+            .process_block_payload(BlockPayload::new(&block, create_transactions(2)))
+            .await;
+        assert!(execution_client.executed_rounds.lock().is_empty());
+        assert!(observer.block_payloads.lock().is_empty());
+        assert!(observer.unverified_block_payloads.is_empty());
+        assert!(observer.sync_required);
+
+        // Verify the observer syncs to the next commit decision
+        observer
+            .process_commit_decision(CommitDecision::new(ordered_proof))
+            .await;
+        assert_eq!(*execution_client.synced_rounds.lock(), vec![1]);
+        assert!(execution_client.committed_rounds.lock().is_empty());
+        assert_eq!(observer.root, (1, 1));
+        assert!(!observer.sync_required);
+    }
+
+    #[tokio::test]
+    async fn test_commit_decision_for_missing_blocks() {
+        // Create an observer and a block that the observer never receives
+        let execution_client = Arc::new(MockExecutionClient::default());
+        let (mut observer, signers) = create_observer(execution_client.clone());
+        let block = create_quorum_store_block(&signers, &create_transactions(1));
+        let commit_proof = create_signed_ledger_info(&observer, &signers, &block);
+
+        // Verify the observer falls back to state sync
+        observer
+            .process_commit_decision(CommitDecision::new(commit_proof))
+            .await;
+        assert_eq!(*execution_client.synced_rounds.lock(), vec![1]);
+        assert!(execution_client.executed_rounds.lock().is_empty());
+        assert_eq!(observer.root, (1, 1));
+
+        // Verify unsigned commit decisions are ignored
+        let child_block = create_child_block(&signers, &block);
+        let unsigned_ledger_info = LedgerInfoWithSignatures::new(
+            LedgerInfo::new(create_block_info(&child_block), HashValue::zero()),
+            AggregateSignature::empty(),
+        );
+        observer
+            .process_commit_decision(CommitDecision::new(unsigned_ledger_info))
+            .await;
+        assert_eq!(*execution_client.synced_rounds.lock(), vec![1]);
+    }
+
+    /// A mock execution client that records the executed, committed and synced rounds
+    #[derive(Default)]
+    struct MockExecutionClient {
+        executed_rounds: Mutex<Vec<Round>>,
+        committed_rounds: Mutex<Vec<Round>>,
+        synced_rounds: Mutex<Vec<Round>>,
+    }
+
+    #[async_trait::async_trait]
+    impl StateComputer for MockExecutionClient {
+        async fn compute(
+            &self,
+            block: &Block,
+            _parent_block_id: HashValue,
+        ) -> Result<StateComputeResult, ExecutionError> {
+            self.executed_rounds.lock().push(block.round());
+            Ok(StateComputeResult::new_dummy())
+        }
+
+        async fn commit(
+            &self,
+            _blocks: &[Arc<ExecutedBlock>],
+            finality_proof: LedgerInfoWithSignatures,
+            _callback: StateComputerCommitCallBackType,
+        ) -> Result<(), ExecutionError> {
+            self.committed_rounds
+                .lock()
+                .push(finality_proof.ledger_info().round());
+            Ok(())
+        }
+
+        async fn sync_to(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError> {
+            self.synced_rounds.lock().push(target.ledger_info().round());
+            Ok(())
+        }
+
+        fn new_epoch(
+            &self,
+            _: &EpochState,
+            _: Arc<PayloadManager>,
+            _: Arc<dyn TransactionShuffler>,
+            _: Option<u64>,
+            _: Arc<dyn TransactionDeduper>,
+            _: Arc<dyn PayloadOrderer>,
+        ) {
+        }
+
+        fn end_epoch(&self) {}
+    }
+
+    /// Creates an observer (in epoch 1, with a single validator) that can execute blocks
+    fn create_observer(
+        execution_client: Arc<MockExecutionClient>,
+    ) -> (ConsensusObserver, Vec<ValidatorSigner>) {
+        let network_client = NetworkClient::new(
+            vec![],
+            vec![],
+            HashMap::new(),
+            PeersAndMetadata::new(&[NetworkId::Vfn]),
+        );
+        let (_, notification_receiver) = aptos_channel::new(QueueStyle::LIFO, 1, None);
+        let reconfig_events = ReconfigNotificationListener {
+            notification_receiver,
+        };
+        let mut observer = ConsensusObserver::new(
+            ConsensusObserverConfig::default(),
+            network_client,
+            execution_client,
+            reconfig_events,
+            TimeService::mock(),
+        );
+
+        // Start the epoch and mark the executor as synced to genesis
+        let (signers, verifier) = random_validator_verifier(1, None, false);
+        observer.epoch_state = Some(Arc::new(EpochState { epoch: 1, verifier }));
+        observer.root = (1, 0);
+        observer.sync_required = false;
+
+        (observer, signers)
+    }
+
+    /// Creates a block (extending genesis) with a single quorum store batch
+    fn create_quorum_store_block(
+        signers: &[ValidatorSigner],
+        transactions: &[SignedTransaction],
+    ) -> Block {
+        let author = signers[0].author();
+        let digest = BatchPayload::new(author, transactions.to_vec()).hash();
+        let batch_info = BatchInfo::new(
+            author,
+            BatchId::new_for_test(1),
+            1,
+            u64::MAX,
+            digest,
+            transactions.len() as u64,
+            0,
+            0,
+        );
+        let proof_of_store = ProofOfStore::new(batch_info, AggregateSignature::empty());
+        Block::new_proposal(
+            Payload::InQuorumStore(ProofWithData::new(vec![proof_of_store])),
+            1,
+            1,
+            certificate_for_genesis(),
+            &signers[0],
+            vec![],
+        )
+        .unwrap()
+    }
+
+    /// Creates an empty block that extends the given parent block
+    fn create_child_block(signers: &[ValidatorSigner], parent: &Block) -> Block {
+        let quorum_cert = placeholder_certificate_for_block(
+            signers,
+            parent.id(),
+            parent.round(),
+            parent.parent_id(),
+            parent.round() - 1,
+        );
+        Block::new_proposal(
+            Payload::DirectMempool(vec![]),
+            parent.round() + 1,
+            parent.timestamp_usecs() + 1,
+            quorum_cert,
+            &signers[0],
+            vec![],
+        )
+        .unwrap()
+    }
+
+    /// Creates a ledger info for the (executed) block, signed by all validators
+    fn create_signed_ledger_info(
+        observer: &ConsensusObserver,
+        signers: &[ValidatorSigner],
+        block: &Block,
+    ) -> LedgerInfoWithSignatures {
+        let block_info =
+            ExecutedBlock::new(block.clone(), StateComputeResult::new_dummy()).block_info();
+        let ledger_info = LedgerInfo::new(block_info, HashValue::zero());
+        let partial_signatures = signers
+            .iter()
+            .map(|signer| (signer.author(), signer.sign(&ledger_info).unwrap()))
+            .collect();
+        let aggregate_signature = observer
+            .epoch_state()
+            .verifier
+            .aggregate_signatures(&PartialSignatures::new(partial_signatures))
+            .unwrap();
+        LedgerInfoWithSignatures::new(ledger_info, aggregate_signature)
+    }
+
+    /// Creates the given number of transactions for a random account
+    fn create_transactions(num_transactions: u64) -> Vec<SignedTransaction> {
+        let sender = AccountAddress::random();
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        (0..num_transactions)
+            .map(|sequence_number| {
+                get_test_signed_txn(
+                    sender,
+                    sequence_number,
+                    &private_key,
+                    private_key.public_key(),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    /// Creates the block info (with a placeholder execution state) for the given block
+    fn create_block_info(block: &Block) -> BlockInfo {
+        BlockInfo::new(
+            block.epoch(),
+            block.round(),
+            block.id(),
+            HashValue::zero(),
+            0,
+            block.timestamp_usecs(),
+            None,
+        )
+    }
+
+    /// Creates an ordered block containing a single nil block at the given round
+    fn create_ordered_block(round: Round) -> OrderedBlock {
+        let block = Block::new_nil(round, certificate_for_genesis(), vec![]);
+        let ordered_proof = LedgerInfoWithSignatures::new(
+            LedgerInfo::new(create_block_info(&block), HashValue::zero()),
+            AggregateSignature::empty(),
+        );
+        OrderedBlock::new(vec![block], ordered_proof)
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::{
+        network_events::{ConsensusObserverNetworkEvents, ConsensusObserverNetworkMessage},
+        network_message::{
+            BlockPayload, ConsensusObserverDirectSend, ConsensusObserverMessage,
+            ConsensusObserverRequest, ConsensusObserverResponse, OrderedBlock,
+        },
+    },
+    counters,
+    payload_manager::PayloadManager,
+};
+use aptos_config::{config::ConsensusObserverConfig, network_id::PeerNetworkId};
+use aptos_consensus_types::{block::Block, common::Payload};
+use aptos_infallible::RwLock;
+use aptos_logger::prelude::*;
+use aptos_network::application::interface::{NetworkClient, NetworkClientInterface};
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use futures::StreamExt;
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+/// The consensus publisher runs on validators and pushes the ordered blocks,
+/// block payloads and commit decisions to all subscribed observers.
+pub struct ConsensusPublisher {
+    // The configuration of the consensus observer (and publisher)
+    consensus_observer_config: ConsensusObserverConfig,
+
+    // The network client used to push messages to the subscribers
+    network_client: NetworkClient<ConsensusObserverMessage>,
+
+    // The set of peers currently subscribed to the publisher
+    active_subscribers: Arc<RwLock<HashSet<PeerNetworkId>>>,
+}
+
+impl ConsensusPublisher {
+    /// Creates a new consensus publisher
+    pub fn new(
+        consensus_observer_config: ConsensusObserverConfig,
+        network_client: NetworkClient<ConsensusObserverMessage>,
+    ) -> Self {
+        Self {
+            consensus_observer_config,
+            network_client,
+            active_subscribers: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Returns the set of peers currently subscribed to the publisher
+    pub fn get_active_subscribers(&self) -> HashSet<PeerNetworkId> {
+        self.active_subscribers.read().clone()
+    }
+
+    /// Pushes the given message to all active subscribers
+    pub fn publish_message(&self, message: ConsensusObserverDirectSend) {
+        let active_subscribers: Vec<_> = self.get_active_subscribers().into_iter().collect();
+        if active_subscribers.is_empty() {
+            return; // There's no one to publish to
+        }
+
+        // Send the message to all subscribers
+        let message_label = message.get_label();
+        if let Err(error) = self.network_client.send_to_peers(
+            ConsensusObserverMessage::DirectSend(message),
+            &active_subscribers,
+        ) {
+            warn!(
+                "Failed to publish the {} message to the subscribers: {:?}",
+                message_label, error
+            );
+            return;
+        }
+        counters::CONSENSUS_OBSERVER_MESSAGES
+            .with_label_values(&[message_label, "sent"])
+            .inc_by(active_subscribers.len() as u64);
+    }
+
+    /// Publishes the given ordered blocks, followed by the payloads of the blocks that
+    /// only carry batch proofs. The payloads are fetched in the background (from the
+    /// batch store), so that ordering isn't delayed.
+    pub fn publish_ordered_blocks(
+        self: &Arc<Self>,
+        blocks: Vec<Block>,
+        ordered_proof: LedgerInfoWithSignatures,
+        payload_manager: Arc<PayloadManager>,
+    ) {
+        if self.active_subscribers.read().is_empty() {
+            return; // There's no one to publish to
+        }
+
+        let quorum_store_blocks: Vec<Block> = blocks
+            .iter()
+            .filter(|block| matches!(block.payload(), Some(Payload::InQuorumStore(_))))
+            .cloned()
+            .collect();
+        self.publish_message(ConsensusObserverDirectSend::OrderedBlock(
+            OrderedBlock::new(blocks, ordered_proof),
+        ));
+
+        // Publish the payloads once the batches are available
+        if quorum_store_blocks.is_empty() {
+            return;
+        }
+        let consensus_publisher = self.clone();
+        tokio::spawn(async move {
+            for block in quorum_store_blocks {
+                match payload_manager
+                    .get_transactions_for_publishing(&block)
+                    .await
+                {
+                    Ok(transactions) => consensus_publisher.publish_message(
+                        ConsensusObserverDirectSend::BlockPayload(BlockPayload::new(
+                            &block,
+                            transactions,
+                        )),
+                    ),
+                    Err(error) => {
+                        // The observers will fall back to state sync
+                        warn!(
+                            "Failed to fetch the payload of block {} for publishing: {:?}",
+                            block, error
+                        );
+                        return;
+                    },
+                }
+            }
+        });
+    }
+
+    /// Handles a subscription request from the given peer
+    fn handle_subscription_request(
+        &self,
+        peer_network_id: PeerNetworkId,
+        request: ConsensusObserverRequest,
+    ) -> ConsensusObserverResponse {
+        counters::CONSENSUS_OBSERVER_MESSAGES
+            .with_label_values(&[request.get_label(), "received"])
+            .inc();
+
+        let response = match request {
+            ConsensusObserverRequest::Subscribe => {
+                let mut active_subscribers = self.active_subscribers.write();
+                let max_num_subscribers =
+                    self.consensus_observer_config.max_num_subscribers as usize;
+                if !active_subscribers.contains(&peer_network_id)
+                    && active_subscribers.len() >= max_num_subscribers
+                {
+                    ConsensusObserverResponse::SubscribeRejected(format!(
+                        "The publisher has reached its maximum number of subscribers: {}",
+                        max_num_subscribers
+                    ))
+                } else {
+                    info!(
+                        "New consensus observer subscription from: {}",
+                        peer_network_id
+                    );
+                    active_subscribers.insert(peer_network_id);
+                    ConsensusObserverResponse::SubscribeAck
+                }
+            },
+            ConsensusObserverRequest::Unsubscribe => {
+                info!("Consensus observer unsubscribed: {}", peer_network_id);
+                self.active_subscribers.write().remove(&peer_network_id);
+                ConsensusObserverResponse::UnsubscribeAck
+            },
+        };
+        self.update_subscriber_metrics();
+
+        response
+    }
+
+    /// Removes the subscribers that are no longer connected
+    fn garbage_collect_subscriptions(&self) {
+        let connected_peers: HashSet<_> = match self.network_client.get_available_peers() {
+            Ok(connected_peers) => connected_peers.into_iter().collect(),
+            Err(error) => {
+                warn!("Failed to fetch the connected peers: {:?}", error);
+                return;
+            },
+        };
+
+        self.active_subscribers.write().retain(|peer_network_id| {
+            let connected = connected_peers.contains(peer_network_id);
+            if !connected {
+                info!(
+                    "Removing disconnected consensus observer subscription: {}",
+                    peer_network_id
+                );
+            }
+            connected
+        });
+        self.update_subscriber_metrics();
+    }
+
+    /// Updates the subscriber metrics
+    fn update_subscriber_metrics(&self) {
+        counters::CONSENSUS_PUBLISHER_NUM_SUBSCRIBERS
+            .set(self.active_subscribers.read().len() as i64);
+    }
+
+    /// Starts the publisher loop that handles subscription requests
+    pub async fn start(self: Arc<Self>, mut network_events: ConsensusObserverNetworkEvents) {
+        let mut garbage_collection_interval = tokio::time::interval(Duration::from_millis(
+            self.consensus_observer_config
+                .observer_progress_check_interval_ms,
+        ));
+
+        info!("Started the consensus publisher!");
+        loop {
+            tokio::select! {
+                Some(network_message) = network_events.next() => {
+                    match network_message {
+                        ConsensusObserverNetworkMessage::Request {
+                            peer_network_id,
+                            request,
+                            response_sender,
+                        } => {
+                            let response = self.handle_subscription_request(peer_network_id, request);
+                            response_sender.send(response);
+                        },
+                        ConsensusObserverNetworkMessage::DirectSend { peer_network_id, message } => {
+                            warn!(
+                                "The publisher received an unexpected message from {}: {}",
+                                peer_network_id, message
+                            );
+                        },
+                    }
+                },
+                _ = garbage_collection_interval.tick() => {
+                    self.garbage_collect_subscriptions();
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::network_id::NetworkId;
+    use aptos_network::application::storage::PeersAndMetadata;
+    use aptos_types::PeerId;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_handle_subscription_requests() {
+        // Create a publisher that accepts a single subscriber
+        let consensus_observer_config = ConsensusObserverConfig {
+            publisher_enabled: true,
+            max_num_subscribers: 1,
+            ..Default::default()
+        };
+        let network_client = NetworkClient::new(
+            vec![],
+            vec![],
+            HashMap::new(),
+            PeersAndMetadata::new(&[NetworkId::Vfn]),
+        );
+        let consensus_publisher =
+            ConsensusPublisher::new(consensus_observer_config, network_client);
+
+        // Subscribe the first peer and verify it's accepted
+        let first_peer = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+        let response = consensus_publisher
+            .handle_subscription_request(first_peer, ConsensusObserverRequest::Subscribe);
+        assert_eq!(response, ConsensusObserverResponse::SubscribeAck);
+
+        // Verify that re-subscribing the same peer is accepted
+        let response = consensus_publisher
+            .handle_subscription_request(first_peer, ConsensusObserverRequest::Subscribe);
+        assert_eq!(response, ConsensusObserverResponse::SubscribeAck);
+
+        // Verify that subscribing another peer is rejected (the publisher is at capacity)
+        let second_peer = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+        let response = consensus_publisher
+            .handle_subscription_request(second_peer, ConsensusObserverRequest::Subscribe);
+        assert!(matches!(
+            response,
+            ConsensusObserverResponse::SubscribeRejected(_)
+        ));
+        assert_eq!(
+            consensus_publisher.get_active_subscribers(),
+            HashSet::from([first_peer])
+        );
+
+        // Unsubscribe the first peer and verify the second peer can now subscribe
+        let response = consensus_publisher
+            .handle_subscription_request(first_peer, ConsensusObserverRequest::Unsubscribe);
+        assert_eq!(response, ConsensusObserverResponse::UnsubscribeAck);
+        let response = consensus_publisher
+            .handle_subscription_request(second_peer, ConsensusObserverRequest::Subscribe);
+        assert_eq!(response, ConsensusObserverResponse::SubscribeAck);
+        assert_eq!(
+            consensus_publisher.get_active_subscribers(),
+            HashSet::from([second_peer])
+        );
+    }
+
+    #[test]
+    fn test_garbage_collect_subscriptions() {
+        // Create a publisher with a subscriber
+        let network_client = NetworkClient::new(
+            vec![],
+            vec![],
+            HashMap::new(),
+            PeersAndMetadata::new(&[NetworkId::Vfn]),
+        );
+        let consensus_publisher =
+            ConsensusPublisher::new(ConsensusObserverConfig::default(), network_client);
+        let peer_network_id = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+        consensus_publisher
+            .handle_subscription_request(peer_network_id, ConsensusObserverRequest::Subscribe);
+        assert_eq!(consensus_publisher.get_active_subscribers().len(), 1);
+
+        // Verify the subscriber is removed because it isn't connected
+        consensus_publisher.garbage_collect_subscriptions();
+        assert!(consensus_publisher.get_active_subscribers().is_empty());
+    }
+}
@@ -3,8 +3,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    admin::ConsensusAdminClient,
+    consensus_observer::{
+        network_events::ConsensusObserverNetworkEvents, network_message::ConsensusObserverMessage,
+        publisher::ConsensusPublisher, ConsensusObserver,
+    },
     counters,
     epoch_manager::EpochManager,
+    message_recorder::MessageRecorder,
     network::NetworkTask,
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     persistent_liveness_storage::StorageWriteProxy,
@@ -17,17 +23,19 @@ use aptos_bounded_executor::BoundedExecutor;
 use aptos_config::config::NodeConfig;
 use aptos_consensus_notifications::ConsensusNotificationSender;
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
-use aptos_executor::block_executor::BlockExecutor;
+use aptos_executor::{block_executor::BlockExecutor, warmup::warm_up_with_recent_blocks};
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
 use aptos_storage_interface::DbReaderWriter;
+use aptos_time_service::TimeService;
 use aptos_vm::AptosVM;
 use futures::channel::mpsc;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::runtime::Runtime;
 
-/// Helper function to start consensus based on configuration and return the runtime
+/// Helper function to start consensus based on configuration and return the runtime, and the
+/// client for sending admin commands to it
 pub fn start_consensus(
     node_config: &NodeConfig,
     network_client: NetworkClient<ConsensusMsg>,
@@ -36,7 +44,8 @@ pub fn start_consensus(
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     aptos_db: DbReaderWriter,
     reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
-) -> Runtime {
+    consensus_publisher: Option<Arc<ConsensusPublisher>>,
+) -> (Runtime, ConsensusAdminClient) {
     let runtime = aptos_runtimes::spawn_named_runtime("consensus".into(), None);
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
     let quorum_store_db = Arc::new(QuorumStoreDB::new(node_config.storage.dir()));
@@ -47,10 +56,11 @@ pub fn start_consensus(
     ));
 
     let state_computer = Arc::new(ExecutionProxy::new(
-        Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
+        create_block_executor(node_config, aptos_db),
         txn_notifier,
         state_sync_notifier,
         runtime.handle(),
+        consensus_publisher.clone(),
     ));
 
     let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
@@ -58,6 +68,7 @@ pub fn start_consensus(
     let (timeout_sender, timeout_receiver) =
         aptos_channels::new(1_024, &counters::PENDING_ROUND_TIMEOUTS);
     let (self_sender, self_receiver) = aptos_channels::new(1_024, &counters::PENDING_SELF_MESSAGES);
+    let (admin_client, admin_command_receiver) = ConsensusAdminClient::new();
 
     let consensus_network_client = ConsensusNetworkClient::new(network_client);
     let bounded_executor = BoundedExecutor::new(8, runtime.handle().clone());
@@ -73,13 +84,89 @@ pub fn start_consensus(
         quorum_store_db,
         reconfig_events,
         bounded_executor,
+        consensus_publisher,
     );
 
-    let (network_task, network_receiver) = NetworkTask::new(network_service_events, self_receiver);
+    let (mut network_task, network_receiver) =
+        NetworkTask::new(network_service_events, self_receiver);
+    if let Some(path) = &node_config.consensus.message_recording_path {
+        let message_recorder = Arc::new(MessageRecorder::new());
+        match message_recorder.start(path) {
+            Ok(()) => network_task = network_task.with_message_recorder(message_recorder),
+            Err(e) => error!("Failed to start recording consensus messages: {:?}", e),
+        }
+    }
 
     runtime.spawn(network_task.start());
-    runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
+    runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver, admin_command_receiver));
 
     debug!("Consensus started.");
+    (runtime, admin_client)
+}
+
+/// Helper function to start the consensus observer (on fullnodes) and return the runtime.
+/// The observer executes and commits the blocks ordered by its publisher, instead of
+/// participating in consensus.
+pub fn start_consensus_observer(
+    node_config: &NodeConfig,
+    network_client: NetworkClient<ConsensusObserverMessage>,
+    network_service_events: NetworkServiceEvents<ConsensusObserverMessage>,
+    state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
+    consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
+    aptos_db: DbReaderWriter,
+    reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+) -> Runtime {
+    let runtime = aptos_runtimes::spawn_named_runtime("observer".into(), None);
+
+    let txn_notifier = Arc::new(MempoolNotifier::new(
+        consensus_to_mempool_sender,
+        node_config.consensus.mempool_executed_txn_timeout_ms,
+    ));
+    let execution_client = Arc::new(ExecutionProxy::new(
+        create_block_executor(node_config, aptos_db),
+        txn_notifier,
+        state_sync_notifier,
+        runtime.handle(),
+        None,
+    ));
+
+    let consensus_observer = ConsensusObserver::new(
+        node_config.consensus_observer,
+        network_client,
+        execution_client,
+        reconfig_events,
+        TimeService::real(),
+    );
+    let network_events = ConsensusObserverNetworkEvents::new(network_service_events);
+    runtime.spawn(consensus_observer.start(network_events));
+
+    debug!("Consensus observer started.");
     runtime
 }
+
+/// Creates the block executor, and warms up its caches with the last committed blocks (if
+/// configured), before it executes any block.
+fn create_block_executor(
+    node_config: &NodeConfig,
+    aptos_db: DbReaderWriter,
+) -> Arc<BlockExecutor<AptosVM>> {
+    let executor = BlockExecutor::<AptosVM>::new(aptos_db);
+    let execution_config = &node_config.execution;
+    let num_warmup_blocks = execution_config.num_warmup_blocks;
+    if num_warmup_blocks > 0 {
+        info!(
+            "Warming up the executor with the last {} blocks.",
+            num_warmup_blocks
+        );
+        // The warm-up is best effort, so failures should never prevent the node from starting
+        if let Err(error) = warm_up_with_recent_blocks(
+            &executor,
+            num_warmup_blocks,
+            execution_config.warmup_max_num_keys,
+            Duration::from_millis(execution_config.warmup_time_budget_ms),
+        ) {
+            warn!("Failed to warm up the executor caches! Error: {:?}", error);
+        }
+    }
+    Arc::new(executor)
+}
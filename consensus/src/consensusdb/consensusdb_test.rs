@@ -7,10 +7,13 @@ use crate::dag::{CertifiedNode, Node, Vote};
 use aptos_consensus_types::{
     block::block_test_utils::certificate_for_genesis,
     common::{Author, Payload},
+    timeout_2chain::TwoChainTimeout,
 };
 use aptos_crypto::bls12381::Signature;
 use aptos_temppath::TempPath;
-use aptos_types::aggregate_signature::AggregateSignature;
+use aptos_types::{
+    aggregate_signature::AggregateSignature, block_info::BlockInfo, ledger_info::LedgerInfo,
+};
 use std::{collections::HashMap, hash::Hash};
 
 #[test]
@@ -113,3 +116,42 @@ fn test_dag() {
         &db,
     );
 }
+
+#[test]
+fn test_prune_certificate_archive() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+
+    for epoch in 1..=3 {
+        for round in 1..=2 {
+            let commit_cert = LedgerInfoWithSignatures::new(
+                LedgerInfo::new(
+                    BlockInfo::random_with_epoch(epoch, round),
+                    HashValue::zero(),
+                ),
+                AggregateSignature::empty(),
+            );
+            db.archive_commit_certificate(&commit_cert).unwrap();
+            let timeout = TwoChainTimeout::new(epoch, round, certificate_for_genesis());
+            db.archive_timeout_certificate(&TwoChainTimeoutCertificate::new(timeout))
+                .unwrap();
+        }
+    }
+
+    // Only the certificates of the epochs before the given one are pruned
+    db.prune_certificate_archive(3).unwrap();
+    let commit_cert_keys: Vec<_> = db
+        .get_all_data::<CommitCertArchiveSchema>()
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(commit_cert_keys, vec![(3, 1), (3, 2)]);
+    let timeout_cert_keys: Vec<_> = db
+        .get_all_data::<TimeoutCertArchiveSchema>()
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(timeout_cert_keys, vec![(3, 1), (3, 2)]);
+}
@@ -8,21 +8,27 @@ mod schema;
 
 use crate::error::DbError;
 use anyhow::Result;
-use aptos_consensus_types::{block::Block, quorum_cert::QuorumCert};
+use aptos_consensus_types::{
+    block::Block, quorum_cert::QuorumCert, timeout_2chain::TwoChainTimeoutCertificate,
+};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_schemadb::{
     schema::Schema, Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
 };
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
 pub use schema::{
     block::BlockSchema,
+    certificate_archive,
+    certificate_archive::{CommitCertArchiveSchema, TimeoutCertArchiveSchema},
     dag::{CertifiedNodeSchema, DagVoteSchema, NodeSchema, OrderedAnchorIdSchema},
     quorum_certificate::QCSchema,
 };
 use schema::{
     single_entry::{SingleEntryKey, SingleEntrySchema},
-    BLOCK_CF_NAME, CERTIFIED_NODE_CF_NAME, DAG_VOTE_CF_NAME, NODE_CF_NAME,
-    ORDERED_ANCHOR_ID_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME,
+    BLOCK_CF_NAME, CERTIFIED_NODE_CF_NAME, COMMIT_CERT_ARCHIVE_CF_NAME, DAG_VOTE_CF_NAME,
+    NODE_CF_NAME, ORDERED_ANCHOR_ID_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME,
+    TIMEOUT_CERT_ARCHIVE_CF_NAME,
 };
 use std::{iter::Iterator, path::Path, time::Instant};
 
@@ -60,6 +66,8 @@ impl ConsensusDB {
             CERTIFIED_NODE_CF_NAME,
             DAG_VOTE_CF_NAME,
             ORDERED_ANCHOR_ID_CF_NAME,
+            COMMIT_CERT_ARCHIVE_CF_NAME,
+            TIMEOUT_CERT_ARCHIVE_CF_NAME,
         ];
 
         let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
@@ -153,6 +161,52 @@ impl ConsensusDB {
         self.commit(batch)
     }
 
+    pub fn archive_commit_certificate(
+        &self,
+        commit_cert: &LedgerInfoWithSignatures,
+    ) -> Result<(), DbError> {
+        let ledger_info = commit_cert.ledger_info();
+        self.save_data::<CommitCertArchiveSchema>(
+            &(ledger_info.epoch(), ledger_info.round()),
+            commit_cert,
+        )
+    }
+
+    pub fn archive_timeout_certificate(
+        &self,
+        timeout_cert: &TwoChainTimeoutCertificate,
+    ) -> Result<(), DbError> {
+        self.save_data::<TimeoutCertArchiveSchema>(
+            &(timeout_cert.epoch(), timeout_cert.round()),
+            timeout_cert,
+        )
+    }
+
+    /// Deletes the certificates archived during the epochs before `min_epoch`
+    pub fn prune_certificate_archive(&self, min_epoch: u64) -> Result<(), DbError> {
+        let batch = SchemaBatch::new();
+        self.prune_archived_epochs::<CommitCertArchiveSchema>(&batch, min_epoch)?;
+        self.prune_archived_epochs::<TimeoutCertArchiveSchema>(&batch, min_epoch)?;
+        self.commit(batch)
+    }
+
+    fn prune_archived_epochs<S: Schema<Key = certificate_archive::Key>>(
+        &self,
+        batch: &SchemaBatch,
+        min_epoch: u64,
+    ) -> Result<(), DbError> {
+        let mut iter = self.db.iter::<S>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (key, _) = item?;
+            if key.0 >= min_epoch {
+                break;
+            }
+            batch.delete::<S>(&key)?;
+        }
+        Ok(())
+    }
+
     /// Write the whole schema batch including all data necessary to mutate the ledger
     /// state of some transaction by leveraging rocksdb atomicity support.
     fn commit(&self, batch: SchemaBatch) -> Result<(), DbError> {
@@ -0,0 +1,116 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schemas for the archive of commit certificates and
+//! timeout certificates observed by this node, kept for external audits of finality and
+//! validator participation.
+//!
+//! ```text
+//! |<-----key----->|<------------value------------>|
+//! | epoch | round |  LedgerInfoWithSignatures      |
+//! | epoch | round |  TwoChainTimeoutCertificate    |
+//! ```
+//!
+//! The schemas are exported for the db tool (see `export_certificates`), which reads the archive
+//! without opening the whole ConsensusDB.
+
+use crate::{consensusdb::schema::ensure_slice_len_eq, define_schema};
+use anyhow::Result;
+use aptos_consensus_types::{common::Round, timeout_2chain::TwoChainTimeoutCertificate};
+use aptos_schemadb::{
+    schema::{KeyCodec, SeekKeyCodec, ValueCodec},
+    ColumnFamilyName,
+};
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::mem::size_of;
+
+pub type Epoch = u64;
+pub type Key = (Epoch, Round);
+
+pub const COMMIT_CERT_ARCHIVE_CF_NAME: ColumnFamilyName = "commit_cert_archive";
+
+define_schema!(
+    CommitCertArchiveSchema,
+    Key,
+    LedgerInfoWithSignatures,
+    COMMIT_CERT_ARCHIVE_CF_NAME
+);
+
+impl KeyCodec<CommitCertArchiveSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        encode_key(self)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        decode_key(data)
+    }
+}
+
+impl ValueCodec<CommitCertArchiveSchema> for LedgerInfoWithSignatures {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+impl SeekKeyCodec<CommitCertArchiveSchema> for Epoch {
+    fn encode_seek_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+}
+
+pub const TIMEOUT_CERT_ARCHIVE_CF_NAME: ColumnFamilyName = "timeout_cert_archive";
+
+define_schema!(
+    TimeoutCertArchiveSchema,
+    Key,
+    TwoChainTimeoutCertificate,
+    TIMEOUT_CERT_ARCHIVE_CF_NAME
+);
+
+impl KeyCodec<TimeoutCertArchiveSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        encode_key(self)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        decode_key(data)
+    }
+}
+
+impl ValueCodec<TimeoutCertArchiveSchema> for TwoChainTimeoutCertificate {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+impl SeekKeyCodec<TimeoutCertArchiveSchema> for Epoch {
+    fn encode_seek_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+}
+
+fn encode_key((epoch, round): &Key) -> Result<Vec<u8>> {
+    let mut encoded = Vec::with_capacity(size_of::<Key>());
+    encoded.write_u64::<BigEndian>(*epoch)?;
+    encoded.write_u64::<BigEndian>(*round)?;
+    Ok(encoded)
+}
+
+fn decode_key(mut data: &[u8]) -> Result<Key> {
+    ensure_slice_len_eq(data, size_of::<Key>())?;
+    let epoch = data.read_u64::<BigEndian>()?;
+    let round = data.read_u64::<BigEndian>()?;
+    Ok((epoch, round))
+}
+
+#[cfg(test)]
+mod test;
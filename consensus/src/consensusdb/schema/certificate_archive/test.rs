@@ -0,0 +1,15 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_consensus_types::block::block_test_utils::certificate_for_genesis;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+#[test]
+fn test_encode_decode() {
+    let qc = certificate_for_genesis();
+    assert_encode_decode::<CommitCertArchiveSchema>(&(1, 2), qc.ledger_info());
+}
+
+test_no_panic_decoding!(CommitCertArchiveSchema);
+test_no_panic_decoding!(TimeoutCertArchiveSchema);
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod block;
+pub mod certificate_archive;
 pub(crate) mod dag;
 pub(crate) mod quorum_certificate;
 pub(crate) mod single_entry;
@@ -36,6 +37,7 @@ macro_rules! define_schema {
 }
 
 pub use block::BLOCK_CF_NAME;
+pub use certificate_archive::{COMMIT_CERT_ARCHIVE_CF_NAME, TIMEOUT_CERT_ARCHIVE_CF_NAME};
 pub use dag::{CERTIFIED_NODE_CF_NAME, DAG_VOTE_CF_NAME, NODE_CF_NAME, ORDERED_ANCHOR_ID_CF_NAME};
 pub use quorum_certificate::QC_CF_NAME;
 pub use single_entry::SINGLE_ENTRY_CF_NAME;
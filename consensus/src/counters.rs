@@ -128,6 +128,18 @@ pub static TXN_SHUFFLE_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Transaction ordering call latency
+pub static TXN_ORDER_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_execution_transaction_order_seconds",
+        // metric description
+        "The time spent in seconds in ordering of transactions",
+        exponential_buckets(/*start=*/ 1e-6, /*factor=*/ 2.0, /*count=*/ 30).unwrap(),
+    )
+    .unwrap()
+});
+
 /// Transaction dedup call latency
 pub static TXN_DEDUP_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
@@ -248,6 +260,13 @@ pub static PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED: Lazy<Histogram> = Lazy::
     )
 });
 
+pub static EXECUTION_BACKPRESSURE_ON_PROPOSAL_TRIGGERED: Lazy<Histogram> = Lazy::new(|| {
+    register_avg_counter(
+        "aptos_execution_backpressure_on_proposal_triggered",
+        "Counts when execution backpressure is triggered",
+    )
+});
+
 /// number of rounds pending when creating proposal
 pub static CONSENSUS_PROPOSAL_PENDING_ROUNDS: Lazy<Histogram> = Lazy::new(|| {
     register_avg_counter(
@@ -508,6 +527,28 @@ pub static ROUND_TIMEOUT_MS: Lazy<IntGauge> = Lazy::new(|| {
     .unwrap()
 });
 
+/// The round timeouts (including the backoff and jitter), by the backoff exponent
+/// (i.e., the number of rounds since the last commit, capped at the max exponent).
+pub static ROUND_TIMEOUT_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_consensus_round_timeout_seconds",
+        "The round timeouts (including the backoff and jitter), by the backoff exponent",
+        &["backoff_exponent"],
+        exponential_buckets(/*start=*/ 0.1, /*factor=*/ 1.5, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+/// The random jitter added to the round timeouts
+pub static ROUND_TIMEOUT_JITTER_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_consensus_round_timeout_jitter_seconds",
+        "The random jitter added to the round timeouts",
+        exponential_buckets(/*start=*/ 0.001, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
 ////////////////////////
 // SYNC MANAGER COUNTERS
 ////////////////////////
@@ -621,6 +662,15 @@ pub static PENDING_SELF_MESSAGES: Lazy<IntGauge> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the pending admin commands sent to consensus
+pub static PENDING_ADMIN_COMMANDS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_pending_admin_commands",
+        "Count of the pending admin commands sent to consensus"
+    )
+    .unwrap()
+});
+
 /// Count of the pending outbound round timeouts
 pub static PENDING_ROUND_TIMEOUTS: Lazy<IntGauge> = Lazy::new(|| {
     register_int_gauge!(
@@ -698,6 +748,16 @@ pub static CONSENSUS_RECEIVED_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the inbound consensus messages dropped by the message recorder, because its
+/// writer fell behind
+pub static CONSENSUS_RECORDER_DROPPED_MSGS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_recorder_dropped_msgs_count",
+        "Count of the inbound consensus messages dropped by the message recorder"
+    )
+    .unwrap()
+});
+
 /// Counters for sent consensus messages broken down by type
 pub static CONSENSUS_SENT_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -757,6 +817,25 @@ pub static BUFFER_MANAGER_RETRY_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the speculative executions of proposed blocks, by result (executed, failed, skipped, cancelled)
+pub static SPECULATIVE_EXECUTION_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_speculative_execution_count",
+        "Count of the speculative executions of proposed blocks, by result",
+        &["result"]
+    )
+    .unwrap()
+});
+
+/// Count of the commit certificates gossiped to lagging peers
+pub static COMMIT_CERT_GOSSIP_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_commit_cert_gossip_count",
+        "Count of the commit certificates gossiped to lagging peers"
+    )
+    .unwrap()
+});
+
 const PROPSER_ELECTION_DURATION_BUCKETS: [f64; 17] = [
     0.001, 0.002, 0.003, 0.004, 0.006, 0.008, 0.01, 0.012, 0.014, 0.0175, 0.02, 0.025, 0.05, 0.25,
     0.5, 1.0, 2.0,
@@ -792,3 +871,94 @@ pub static BATCH_WAIT_DURATION: Lazy<DurationHistogram> = Lazy::new(|| {
         .unwrap(),
     )
 });
+
+/// Count of the DAG anchors, by whether they were ordered or skipped
+pub static DAG_ANCHOR_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_dag_anchor_count",
+        "Count of the DAG anchors, by whether they were ordered or skipped",
+        &["outcome"]
+    )
+    .unwrap()
+});
+
+/// Histogram of the number of nodes ordered by each DAG anchor (i.e., its causal history)
+pub static DAG_ANCHOR_CAUSAL_HISTORY_SIZE: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_consensus_dag_anchor_causal_history_size",
+        "Histogram of the number of nodes ordered by each DAG anchor",
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+/// Histogram of the latency of the DAG anchors, by stage (i.e., from creation to being
+/// received as a certified node, and from being received to being ordered)
+pub static DAG_ANCHOR_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_consensus_dag_anchor_latency",
+        "Histogram of the latency of the DAG anchors, by stage",
+        &["stage"],
+        CONSENSUS_WAIT_DURATION_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
+/// Counter of pending network events to the consensus observer (and publisher)
+pub static PENDING_CONSENSUS_OBSERVER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_observer_pending_network_events",
+        "Counters(queued,dequeued,dropped) related to pending network notifications to the consensus observer",
+        &["state"]
+    )
+    .unwrap()
+});
+
+/// Count of the consensus observer messages, by type and direction (i.e., sent or received)
+pub static CONSENSUS_OBSERVER_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_observer_messages",
+        "Count of the consensus observer messages, by type and direction",
+        &["message_type", "direction"]
+    )
+    .unwrap()
+});
+
+/// Number of observers currently subscribed to the consensus publisher
+pub static CONSENSUS_PUBLISHER_NUM_SUBSCRIBERS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_publisher_num_subscribers",
+        "Number of observers currently subscribed to the consensus publisher"
+    )
+    .unwrap()
+});
+
+/// The latest round processed by the consensus observer, by stage (i.e., ordered,
+/// executed or committed)
+pub static CONSENSUS_OBSERVER_ROUND: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_consensus_observer_round",
+        "The latest round processed by the consensus observer, by stage",
+        &["stage"]
+    )
+    .unwrap()
+});
+
+/// Count of the times the consensus observer fell back to state sync, by reason
+pub static CONSENSUS_OBSERVER_STATE_SYNC_FALLBACKS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_observer_state_sync_fallbacks",
+        "Count of the times the consensus observer fell back to state sync, by reason",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+/// Count of the block payloads that failed verification against their blocks
+pub static CONSENSUS_OBSERVER_INVALID_PAYLOADS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_observer_invalid_payloads",
+        "Count of the block payloads that failed verification against their blocks"
+    )
+    .unwrap()
+});
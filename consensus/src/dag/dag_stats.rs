@@ -0,0 +1,167 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters::{DAG_ANCHOR_CAUSAL_HISTORY_SIZE, DAG_ANCHOR_COUNT, DAG_ANCHOR_LATENCY},
+    dag::types::NodeMetadata,
+};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_infallible::Mutex;
+use aptos_reliable_broadcast::BroadcastStats;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Duration,
+};
+
+/// The number of most recent anchor outcomes to keep for debugging
+const MAX_ANCHOR_OUTCOMES: usize = 100;
+
+const ORDERED_LABEL: &str = "ordered";
+const SKIPPED_LABEL: &str = "skipped";
+const CERTIFY_STAGE_LABEL: &str = "certify";
+const ORDER_STAGE_LABEL: &str = "order";
+
+/// The outcome of the anchor election for a single round
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "outcome")]
+pub enum AnchorOutcome {
+    /// The anchor was ordered, along with its causal history
+    Ordered {
+        round: Round,
+        author: Author,
+        causal_history_size: usize,
+        /// The time from the anchor's creation until it was received as a certified node
+        certify_latency_ms: Option<u64>,
+        /// The time from the anchor being received until it was ordered
+        order_latency_ms: Option<u64>,
+    },
+    /// The anchor didn't gather enough votes and was skipped by a later anchor
+    Skipped { round: Round, author: Author },
+}
+
+/// Records the anchor election outcomes and their latency breakdown
+#[derive(Default)]
+pub struct AnchorStats {
+    inner: Mutex<AnchorStatsInner>,
+}
+
+#[derive(Default)]
+struct AnchorStatsInner {
+    // The creation and receipt timestamps (in usecs) of the anchors that are not yet ordered
+    received_anchors: BTreeMap<Round, (u64, u64)>,
+    outcomes: VecDeque<AnchorOutcome>,
+}
+
+impl AnchorStatsInner {
+    fn push_outcome(&mut self, outcome: AnchorOutcome) {
+        if self.outcomes.len() == MAX_ANCHOR_OUTCOMES {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(outcome);
+    }
+}
+
+impl AnchorStats {
+    /// Records that the anchor of its round was received as a certified node
+    pub fn record_anchor_received(&self, anchor: &NodeMetadata, now: Duration) {
+        self.inner
+            .lock()
+            .received_anchors
+            .insert(anchor.round(), (anchor.timestamp(), now.as_micros() as u64));
+    }
+
+    /// Records that the anchor of the given round was skipped
+    pub fn record_anchor_skipped(&self, round: Round, author: Author) {
+        DAG_ANCHOR_COUNT.with_label_values(&[SKIPPED_LABEL]).inc();
+
+        let mut inner = self.inner.lock();
+        inner.received_anchors.remove(&round);
+        inner.push_outcome(AnchorOutcome::Skipped { round, author });
+    }
+
+    /// Records that the anchor was ordered, along with the given number of nodes
+    pub fn record_anchor_ordered(
+        &self,
+        anchor: &NodeMetadata,
+        causal_history_size: usize,
+        now: Duration,
+    ) {
+        DAG_ANCHOR_COUNT.with_label_values(&[ORDERED_LABEL]).inc();
+        DAG_ANCHOR_CAUSAL_HISTORY_SIZE.observe(causal_history_size as f64);
+
+        let mut inner = self.inner.lock();
+        let (certify_latency, order_latency) = match inner.received_anchors.remove(&anchor.round())
+        {
+            Some((created_usecs, received_usecs)) => {
+                let certify_latency =
+                    Duration::from_micros(received_usecs.saturating_sub(created_usecs));
+                let order_latency =
+                    Duration::from_micros((now.as_micros() as u64).saturating_sub(received_usecs));
+                DAG_ANCHOR_LATENCY
+                    .with_label_values(&[CERTIFY_STAGE_LABEL])
+                    .observe(certify_latency.as_secs_f64());
+                DAG_ANCHOR_LATENCY
+                    .with_label_values(&[ORDER_STAGE_LABEL])
+                    .observe(order_latency.as_secs_f64());
+                (Some(certify_latency), Some(order_latency))
+            },
+            None => (None, None),
+        };
+        // Anchors of older rounds can no longer be ordered
+        inner.received_anchors = inner.received_anchors.split_off(&anchor.round());
+        inner.push_outcome(AnchorOutcome::Ordered {
+            round: anchor.round(),
+            author: *anchor.author(),
+            causal_history_size,
+            certify_latency_ms: certify_latency.map(|latency| latency.as_millis() as u64),
+            order_latency_ms: order_latency.map(|latency| latency.as_millis() as u64),
+        });
+    }
+
+    /// Returns the most recent anchor outcomes, from oldest to newest
+    pub fn outcomes(&self) -> Vec<AnchorOutcome> {
+        self.inner.lock().outcomes.iter().cloned().collect()
+    }
+}
+
+/// The reliable broadcast RPC counters for a single peer
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerBroadcastInfo {
+    pub attempts: u64,
+    pub acks: u64,
+    pub failures: u64,
+}
+
+/// A snapshot of the DAG's anchor election outcomes and reliable broadcast stats, used to
+/// diagnose dissemination bottlenecks (e.g., via a debug endpoint).
+#[derive(Clone, Debug, Serialize)]
+pub struct DagDebugInfo {
+    pub anchors: Vec<AnchorOutcome>,
+    pub reliable_broadcast: BTreeMap<Author, PeerBroadcastInfo>,
+}
+
+impl DagDebugInfo {
+    pub fn new(anchor_stats: &AnchorStats, broadcast_stats: &BroadcastStats) -> Self {
+        let reliable_broadcast = broadcast_stats
+            .peer_stats()
+            .into_iter()
+            .map(|(peer, stats)| {
+                (peer, PeerBroadcastInfo {
+                    attempts: stats.attempts,
+                    acks: stats.acks,
+                    failures: stats.failures,
+                })
+            })
+            .collect();
+        Self {
+            anchors: anchor_stats.outcomes(),
+            reliable_broadcast,
+        }
+    }
+
+    /// Returns the debug info as pretty-printed JSON
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
@@ -7,6 +7,7 @@ mod dag_driver;
 mod dag_fetcher;
 mod dag_handler;
 mod dag_network;
+mod dag_stats;
 mod dag_store;
 mod order_rule;
 mod rb_handler;
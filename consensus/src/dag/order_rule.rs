@@ -3,11 +3,12 @@
 
 use super::dag_store::NodeStatus;
 use crate::dag::{
-    anchor_election::AnchorElection, dag_store::Dag, types::NodeMetadata, CertifiedNode,
+    anchor_election::AnchorElection, dag_stats::AnchorStats, dag_store::Dag, types::NodeMetadata,
+    CertifiedNode,
 };
 use aptos_consensus_types::common::Round;
 use aptos_crypto::HashValue;
-use aptos_infallible::RwLock;
+use aptos_infallible::{duration_since_epoch, RwLock};
 use aptos_logger::error;
 use aptos_types::{epoch_state::EpochState, ledger_info::LedgerInfo};
 use futures_channel::mpsc::UnboundedSender;
@@ -20,6 +21,7 @@ pub struct OrderRule {
     dag: Arc<RwLock<Dag>>,
     anchor_election: Box<dyn AnchorElection>,
     ordered_nodes_sender: UnboundedSender<Vec<Arc<CertifiedNode>>>,
+    anchor_stats: Arc<AnchorStats>,
 }
 
 impl OrderRule {
@@ -38,9 +40,15 @@ impl OrderRule {
             dag,
             anchor_election,
             ordered_nodes_sender,
+            anchor_stats: Arc::new(AnchorStats::default()),
         }
     }
 
+    /// Returns the anchor election outcomes recorded by this order rule
+    pub fn anchor_stats(&self) -> Arc<AnchorStats> {
+        self.anchor_stats.clone()
+    }
+
     /// Check if two rounds have the same parity
     fn check_parity(r1: Round, r2: Round) -> bool {
         (r1 ^ r2) & 1 == 0
@@ -48,6 +56,13 @@ impl OrderRule {
 
     pub fn process_new_node(&mut self, node_metadata: &NodeMetadata) {
         let round = node_metadata.round();
+        if round >= self.lowest_unordered_anchor_round
+            && Self::check_parity(round, self.lowest_unordered_anchor_round)
+            && *node_metadata.author() == self.anchor_election.get_anchor(round)
+        {
+            self.anchor_stats
+                .record_anchor_received(node_metadata, duration_since_epoch());
+        }
         // If the node comes from the proposal round in the current instance, it can't trigger any ordering
         if round <= self.lowest_unordered_anchor_round
             || Self::check_parity(round, self.lowest_unordered_anchor_round)
@@ -124,10 +139,14 @@ impl OrderRule {
 
     /// Finalize the ordering with the given anchor node, update anchor election and construct blocks for execution.
     pub fn finalize_order(&mut self, anchor: Arc<CertifiedNode>) {
-        let _failed_anchors: Vec<_> = (self.lowest_unordered_anchor_round..anchor.round())
+        let failed_anchors: Vec<_> = (self.lowest_unordered_anchor_round..anchor.round())
             .step_by(2)
-            .map(|failed_round| self.anchor_election.get_anchor(failed_round))
+            .map(|failed_round| (failed_round, self.anchor_election.get_anchor(failed_round)))
             .collect();
+        for (failed_round, failed_anchor) in failed_anchors {
+            self.anchor_stats
+                .record_anchor_skipped(failed_round, failed_anchor);
+        }
         assert!(Self::check_parity(
             self.lowest_unordered_anchor_round,
             anchor.round(),
@@ -143,6 +162,11 @@ impl OrderRule {
             })
             .collect();
         ordered_nodes.reverse();
+        self.anchor_stats.record_anchor_ordered(
+            anchor.metadata(),
+            ordered_nodes.len(),
+            duration_since_epoch(),
+        );
         if let Err(e) = self.ordered_nodes_sender.unbounded_send(ordered_nodes) {
             error!("Failed to send ordered nodes {:?}", e);
         }
@@ -0,0 +1,79 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    dag_stats::{AnchorOutcome, AnchorStats, DagDebugInfo},
+    types::NodeMetadata,
+};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_crypto::HashValue;
+use aptos_reliable_broadcast::BroadcastStats;
+use std::time::Duration;
+
+fn anchor(round: Round, author: Author, timestamp_ms: u64) -> NodeMetadata {
+    NodeMetadata::new_for_test(1, round, author, timestamp_ms * 1000, HashValue::random())
+}
+
+#[test]
+fn test_anchor_outcomes() {
+    let stats = AnchorStats::default();
+    let author = Author::random();
+
+    // The anchor of round 1 is created at 100ms, received at 150ms and skipped
+    stats.record_anchor_received(&anchor(1, author, 100), Duration::from_millis(150));
+    stats.record_anchor_skipped(1, author);
+
+    // The anchor of round 3 is created at 200ms, received at 300ms and ordered at 450ms
+    let anchor_3 = anchor(3, author, 200);
+    stats.record_anchor_received(&anchor_3, Duration::from_millis(300));
+    stats.record_anchor_ordered(&anchor_3, 7, Duration::from_millis(450));
+
+    // The anchor of round 5 is ordered without having been received (e.g., after a restart)
+    stats.record_anchor_ordered(&anchor(5, author, 500), 4, Duration::from_millis(600));
+
+    assert_eq!(stats.outcomes(), vec![
+        AnchorOutcome::Skipped { round: 1, author },
+        AnchorOutcome::Ordered {
+            round: 3,
+            author,
+            causal_history_size: 7,
+            certify_latency_ms: Some(100),
+            order_latency_ms: Some(150),
+        },
+        AnchorOutcome::Ordered {
+            round: 5,
+            author,
+            causal_history_size: 4,
+            certify_latency_ms: None,
+            order_latency_ms: None,
+        },
+    ]);
+}
+
+#[test]
+fn test_anchor_outcomes_are_bounded() {
+    let stats = AnchorStats::default();
+    let author = Author::random();
+    for round in 0..200 {
+        stats.record_anchor_skipped(round, author);
+    }
+
+    let outcomes = stats.outcomes();
+    assert_eq!(outcomes.len(), 100);
+    assert_eq!(outcomes[0], AnchorOutcome::Skipped { round: 100, author });
+}
+
+#[test]
+fn test_debug_info_json() {
+    let stats = AnchorStats::default();
+    let author = Author::random();
+    stats.record_anchor_skipped(1, author);
+
+    let json = DagDebugInfo::new(&stats, &BroadcastStats::default())
+        .to_json()
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["anchors"][0]["outcome"], "skipped");
+    assert_eq!(value["anchors"][0]["round"], 1);
+    assert!(value["reliable_broadcast"].as_object().unwrap().is_empty());
+}
@@ -115,6 +115,10 @@ impl NodeMetadata {
     pub fn epoch(&self) -> u64 {
         self.epoch
     }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
 }
 
 impl Deref for NodeMetadata {
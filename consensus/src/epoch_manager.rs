@@ -3,16 +3,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    admin::{ConsensusAdminCommand, MineBlocksRequest},
     block_storage::{
         tracing::{observe_block, BlockStage},
         BlockStore,
     },
+    consensus_observer::publisher::ConsensusPublisher,
     counters,
     error::{error_kind, DbError},
     experimental::{
         buffer_manager::{OrderedBlocks, ResetRequest},
         decoupled_execution_utils::prepare_phases_and_buffer_manager,
         ordering_state_computer::OrderingStateComputer,
+        speculative_execution::SpeculativeExecutor,
     },
     liveness::{
         cached_proposer_election::CachedProposerElection,
@@ -20,6 +23,7 @@ use crate::{
             extract_epoch_to_proposers, AptosDBBackend, LeaderReputation,
             ProposerAndVoterHeuristic, ReputationHeuristic,
         },
+        maintenance_proposer_election::{MaintenanceAwareProposerElection, MaintenanceSchedule},
         proposal_generator::{
             ChainHealthBackoffConfig, PipelineBackpressureConfig, ProposalGenerator,
         },
@@ -37,6 +41,8 @@ use crate::{
     },
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     payload_client::QuorumStoreClient,
+    payload_manager::PayloadManager,
+    payload_orderer::create_payload_orderer,
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
     quorum_store::{
         quorum_store_builder::{DirectMempoolInnerBuilder, InnerBuilder, QuorumStoreBuilder},
@@ -70,7 +76,7 @@ use aptos_types::{
     epoch_state::EpochState,
     on_chain_config::{
         LeaderReputationType, OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig,
-        OnChainExecutionConfig, ProposerElectionType, ValidatorSet,
+        OnChainExecutionConfig, OnChainMaintenanceSchedule, ProposerElectionType, ValidatorSet,
     },
     validator_verifier::ValidatorVerifier,
 };
@@ -140,6 +146,8 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     bounded_executor: BoundedExecutor,
     // recovery_mode is set to true when the recovery manager is spawned
     recovery_mode: bool,
+    // publishes the ordered blocks to consensus observers (if enabled)
+    consensus_publisher: Option<Arc<ConsensusPublisher>>,
 }
 
 impl<P: OnChainConfigProvider> EpochManager<P> {
@@ -155,6 +163,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         quorum_store_storage: Arc<dyn QuorumStoreStorage>,
         reconfig_events: ReconfigNotificationListener<P>,
         bounded_executor: BoundedExecutor,
+        consensus_publisher: Option<Arc<ConsensusPublisher>>,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
@@ -186,6 +195,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             batch_retrieval_tx: None,
             bounded_executor,
             recovery_mode: false,
+            consensus_publisher,
         }
     }
 
@@ -203,12 +213,48 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         &self,
         time_service: Arc<dyn TimeService>,
         timeout_sender: aptos_channels::Sender<Round>,
+        onchain_config: &OnChainConsensusConfig,
     ) -> RoundState {
-        let time_interval = Box::new(ExponentialTimeInterval::new(
-            Duration::from_millis(self.config.round_initial_timeout_ms),
-            self.config.round_timeout_backoff_exponent_base,
-            self.config.round_timeout_backoff_max_exponent,
-        ));
+        let mut initial_timeout_ms = self.config.round_initial_timeout_ms;
+        let mut backoff_exponent_base = self.config.round_timeout_backoff_exponent_base;
+        let mut backoff_max_exponent = self.config.round_timeout_backoff_max_exponent;
+        let mut jitter_percent = self.config.round_timeout_jitter_percent;
+
+        // The on-chain round timeout (if set) applies, unless the node overrides it
+        match onchain_config.round_timeout() {
+            Some(_) if self.config.override_onchain_round_timeout => {
+                info!("Overriding the on-chain round timeout with the local config");
+            },
+            Some(round_timeout) if round_timeout.is_valid() => {
+                initial_timeout_ms = round_timeout.initial_timeout_ms;
+                backoff_exponent_base = round_timeout.backoff_exponent_base();
+                backoff_max_exponent = round_timeout.backoff_max_exponent as usize;
+                jitter_percent = round_timeout.jitter_percent;
+            },
+            Some(round_timeout) => {
+                error!(
+                    "Invalid on-chain round timeout {:?}, using the local config instead",
+                    round_timeout
+                );
+            },
+            None => {},
+        }
+        info!(
+            initial_timeout_ms = initial_timeout_ms,
+            backoff_exponent_base = backoff_exponent_base,
+            backoff_max_exponent = backoff_max_exponent,
+            jitter_percent = jitter_percent,
+            "Using round timeout"
+        );
+
+        let time_interval = Box::new(
+            ExponentialTimeInterval::new(
+                Duration::from_millis(initial_timeout_ms),
+                backoff_exponent_base,
+                backoff_max_exponent,
+            )
+            .with_jitter(jitter_percent),
+        );
         RoundState::new(time_interval, time_service, timeout_sender)
     }
 
@@ -238,10 +284,15 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                     weight_by_voting_power,
                     use_history_from_previous_epoch_max_count,
                 ) = match &leader_reputation_type {
-                    LeaderReputationType::ProposerAndVoter(proposer_and_voter_config)
-                    | LeaderReputationType::ProposerAndVoterV2(proposer_and_voter_config) => {
+                    LeaderReputationType::ProposerAndVoter(_)
+                    | LeaderReputationType::ProposerAndVoterV2(_)
+                    | LeaderReputationType::ProposerAndVoterV3(_) => {
+                        let proposer_and_voter_config =
+                            leader_reputation_type.proposer_and_voter_config();
                         let proposer_window_size = proposers.len()
                             * proposer_and_voter_config.proposer_window_num_validators_multiplier;
+                        let failure_window_size = proposers.len()
+                            * proposer_and_voter_config.failure_window_num_validators_multiplier;
                         let voter_window_size = proposers.len()
                             * proposer_and_voter_config.voter_window_num_validators_multiplier;
                         let heuristic: Box<dyn ReputationHeuristic> =
@@ -253,11 +304,14 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                                 proposer_and_voter_config.failure_threshold_percent,
                                 voter_window_size,
                                 proposer_window_size,
+                                failure_window_size,
                                 leader_reputation_type.use_reputation_window_from_stale_end(),
                             ));
                         (
                             heuristic,
-                            std::cmp::max(proposer_window_size, voter_window_size),
+                            proposer_window_size
+                                .max(failure_window_size)
+                                .max(voter_window_size),
                             proposer_and_voter_config.weight_by_voting_power,
                             proposer_and_voter_config.use_history_from_previous_epoch_max_count,
                         )
@@ -485,6 +539,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         &mut self,
         safety_rules_container: Arc<Mutex<MetricsSafetyRules>>,
         verifier: ValidatorVerifier,
+        payload_manager: Arc<PayloadManager>,
     ) -> OrderingStateComputer {
         let network_sender = NetworkSender::new(
             self.author,
@@ -505,10 +560,16 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         self.buffer_manager_msg_tx = Some(commit_msg_tx);
         self.buffer_manager_reset_tx = Some(reset_tx.clone());
 
+        let speculative_executor = self
+            .config
+            .enable_optimistic_execution
+            .then(|| Arc::new(SpeculativeExecutor::new(self.commit_state_computer.clone())));
+
         let (execution_phase, signing_phase, persisting_phase, buffer_manager) =
             prepare_phases_and_buffer_manager(
                 self.author,
                 self.commit_state_computer.clone(),
+                speculative_executor.clone(),
                 safety_rules_container,
                 network_sender,
                 commit_msg_rx,
@@ -523,7 +584,14 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         tokio::spawn(persisting_phase.start());
         tokio::spawn(buffer_manager.start());
 
-        OrderingStateComputer::new(block_tx, self.commit_state_computer.clone(), reset_tx)
+        OrderingStateComputer::new(
+            block_tx,
+            self.commit_state_computer.clone(),
+            reset_tx,
+            speculative_executor,
+            self.consensus_publisher.clone(),
+            payload_manager,
+        )
     }
 
     async fn shutdown_current_processor(&mut self) {
@@ -605,6 +673,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         epoch_state: EpochState,
         onchain_consensus_config: OnChainConsensusConfig,
         onchain_execution_config: OnChainExecutionConfig,
+        onchain_maintenance_schedule: OnChainMaintenanceSchedule,
     ) {
         let epoch = epoch_state.epoch;
         counters::EPOCH.set(epoch_state.epoch as i64);
@@ -630,12 +699,21 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         }
 
         info!(epoch = epoch, "Create RoundState");
-        let round_state =
-            self.create_round_state(self.time_service.clone(), self.timeout_sender.clone());
+        let round_state = self.create_round_state(
+            self.time_service.clone(),
+            self.timeout_sender.clone(),
+            &onchain_consensus_config,
+        );
 
         info!(epoch = epoch, "Create ProposerElection");
-        let proposer_election =
-            self.create_proposer_election(&epoch_state, &onchain_consensus_config);
+        let proposer_election = Box::new(MaintenanceAwareProposerElection::new(
+            self.create_proposer_election(&epoch_state, &onchain_consensus_config),
+            epoch_state
+                .verifier
+                .get_ordered_account_addresses_iter()
+                .collect(),
+            MaintenanceSchedule::new(epoch, &onchain_maintenance_schedule),
+        ));
         let network_sender = NetworkSender::new(
             self.author,
             self.network_sender.clone(),
@@ -684,6 +762,8 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         let block_gas_limit = onchain_execution_config.block_gas_limit();
         let transaction_deduper =
             create_transaction_deduper(onchain_execution_config.transaction_deduper_type());
+        let payload_orderer =
+            create_payload_orderer(onchain_execution_config.payload_orderer_type());
         self.quorum_store_msg_tx = quorum_store_msg_tx;
 
         let payload_client = QuorumStoreClient::new(
@@ -698,11 +778,13 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             transaction_shuffler,
             block_gas_limit,
             transaction_deduper,
+            payload_orderer,
         );
         let state_computer = if onchain_consensus_config.decoupled_execution() {
             Arc::new(self.spawn_decoupled_execution(
                 safety_rules_container.clone(),
                 epoch_state.verifier.clone(),
+                payload_manager.clone(),
             ))
         } else {
             self.commit_state_computer.clone()
@@ -743,7 +825,8 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             pipeline_backpressure_config,
             chain_health_backoff_config,
             self.quorum_store_enabled,
-        );
+        )
+        .with_execution_backpressure(self.config.execution_backpressure.clone());
 
         let (round_manager_tx, round_manager_rx) = aptos_channel::new(
             QueueStyle::LIFO,
@@ -781,6 +864,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             onchain_consensus_config,
             round_manager_tx,
             self.config.clone(),
+            Some(self.quorum_store_to_mempool_sender.clone()),
         );
 
         round_manager.init(last_vote).await;
@@ -803,6 +887,9 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
 
         let onchain_consensus_config: anyhow::Result<OnChainConsensusConfig> = payload.get();
         let onchain_execution_config: anyhow::Result<OnChainExecutionConfig> = payload.get();
+        // The schedule is missing until the first maintenance window is announced
+        let onchain_maintenance_schedule: anyhow::Result<OnChainMaintenanceSchedule> =
+            payload.get();
         if let Err(error) = &onchain_consensus_config {
             error!("Failed to read on-chain consensus config {}", error);
         }
@@ -811,6 +898,10 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             error!("Failed to read on-chain execution config {}", error);
         }
 
+        if let Err(error) = &onchain_maintenance_schedule {
+            info!("No on-chain maintenance schedule {}", error);
+        }
+
         self.epoch_state = Some(Arc::new(epoch_state.clone()));
 
         match self.storage.start() {
@@ -825,6 +916,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                     epoch_state,
                     consensus_config,
                     execution_config,
+                    onchain_maintenance_schedule.unwrap_or_default(),
                 )
                 .await
             },
@@ -1094,6 +1186,46 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         }
     }
 
+    fn process_admin_command(&mut self, command: ConsensusAdminCommand) {
+        match command {
+            ConsensusAdminCommand::AdvanceTime(request) => {
+                if !self.config.enable_time_travel {
+                    let _ = request.response_tx.send(Err(anyhow::anyhow!(
+                        "Time travel is not enabled on this node!"
+                    )));
+                    return;
+                }
+
+                // Move the clock forward, and propose a block with the new timestamp
+                let clock_offset = aptos_consensus_types::clock::advance_clock(request.duration);
+                info!(
+                    "Advanced the clock by {:?}, the clock is now {:?} ahead of the system time",
+                    request.duration, clock_offset
+                );
+                self.process_admin_command(ConsensusAdminCommand::MineBlocks(MineBlocksRequest {
+                    num_blocks: 1,
+                    response_tx: request.response_tx,
+                }));
+            },
+            ConsensusAdminCommand::MineBlocks(request) => {
+                if !self.config.enable_time_travel {
+                    let _ = request.response_tx.send(Err(anyhow::anyhow!(
+                        "Time travel is not enabled on this node!"
+                    )));
+                    return;
+                }
+
+                let peer_id = self.author;
+                let event = VerifiedEvent::LocalMineBlocksRequest(request);
+                if let Some(sender) = self.round_manager_tx.as_mut() {
+                    if let Err(e) = sender.push((peer_id, discriminant(&event)), (peer_id, event)) {
+                        error!("Failed to send event to round manager {:?}", e);
+                    }
+                }
+            },
+        }
+    }
+
     async fn await_reconfig_notification(&mut self) {
         let reconfig_notification = self
             .reconfig_events
@@ -1108,6 +1240,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         mut self,
         mut round_timeout_sender_rx: aptos_channels::Receiver<Round>,
         mut network_receivers: NetworkReceivers,
+        mut admin_command_rx: aptos_channels::Receiver<ConsensusAdminCommand>,
     ) {
         // initial start of the processor
         self.await_reconfig_notification().await;
@@ -1141,6 +1274,10 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                     monitor!("epoch_manager_process_round_timeout",
                     self.process_local_timeout(round));
                 },
+                command = admin_command_rx.select_next_some() => {
+                    monitor!("epoch_manager_process_admin_command",
+                    self.process_admin_command(command));
+                },
             }
             // Continually capture the time of consensus process to ensure that clock skew between
             // validators is reasonable and to find any unusual (possibly byzantine) clock behavior.
@@ -33,9 +33,25 @@ fn verify_signatures(
     commit_ledger_info: &LedgerInfo,
 ) -> PartialSignatures {
     // Returns a valid partial signature from a set of unverified signatures.
-    // TODO: Validating individual signatures in expensive. Replace this with optimistic signature
-    // verification for BLS. Here, we can implement a tree-based batch verification technique that
-    // filters out invalid signature shares much faster when there are only a few of them
+    // The signatures are all expected to be valid, so they're batch-verified first, which is much
+    // cheaper than validating them individually.
+    let signatures = unverified_signatures.signatures();
+    let public_keys: Option<Vec<_>> = signatures
+        .keys()
+        .map(|author| validator.get_public_key(author))
+        .collect();
+    if let Some(public_keys) = public_keys {
+        let messages = vec![commit_ledger_info; signatures.len()];
+        let public_keys_refs = public_keys.iter().collect::<Vec<_>>();
+        let signatures_refs = signatures.values().collect::<Vec<_>>();
+        if bls12381::Signature::batch_verify(&messages, &public_keys_refs, &signatures_refs).is_ok()
+        {
+            return unverified_signatures;
+        }
+    }
+    // TODO: The batch failed, so the invalid signatures are filtered out individually. Here, we
+    // can implement a tree-based batch verification technique that filters out invalid signature
+    // shares much faster when there are only a few of them
     // (e.g., [LM07]: Finding Invalid Signatures in Pairing-Based Batches,
     // by Law, Laurie and Matt, Brian J., in Cryptography and Coding, 2007).
     PartialSignatures::new(
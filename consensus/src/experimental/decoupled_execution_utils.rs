@@ -9,6 +9,7 @@ use crate::{
         persisting_phase::{PersistingPhase, PersistingRequest},
         pipeline_phase::{CountedRequest, PipelinePhase},
         signing_phase::{SigningPhase, SigningRequest, SigningResponse},
+        speculative_execution::SpeculativeExecutor,
     },
     metrics_safety_rules::MetricsSafetyRules,
     network::NetworkSender,
@@ -26,6 +27,7 @@ use std::sync::{atomic::AtomicU64, Arc};
 pub fn prepare_phases_and_buffer_manager(
     author: Author,
     execution_proxy: Arc<dyn StateComputer>,
+    speculative_executor: Option<Arc<SpeculativeExecutor>>,
     safety_rules: Arc<Mutex<MetricsSafetyRules>>,
     commit_msg_tx: NetworkSender,
     commit_msg_rx: Receiver<AccountAddress, VerifiedEvent>,
@@ -47,7 +49,7 @@ pub fn prepare_phases_and_buffer_manager(
 
     let ongoing_tasks = Arc::new(AtomicU64::new(0));
 
-    let execution_phase_processor = ExecutionPhase::new(execution_proxy);
+    let execution_phase_processor = ExecutionPhase::new(execution_proxy, speculative_executor);
     let execution_phase = PipelinePhase::new(
         execution_phase_request_rx,
         Some(execution_phase_response_tx),
@@ -2,7 +2,10 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{experimental::pipeline_phase::StatelessPipeline, state_replication::StateComputer};
+use crate::{
+    experimental::{pipeline_phase::StatelessPipeline, speculative_execution::SpeculativeExecutor},
+    state_replication::StateComputer,
+};
 use anyhow::Result;
 use aptos_consensus_types::executed_block::ExecutedBlock;
 use aptos_crypto::HashValue;
@@ -42,11 +45,18 @@ pub struct ExecutionResponse {
 
 pub struct ExecutionPhase {
     execution_proxy: Arc<dyn StateComputer>,
+    speculative_executor: Option<Arc<SpeculativeExecutor>>,
 }
 
 impl ExecutionPhase {
-    pub fn new(execution_proxy: Arc<dyn StateComputer>) -> Self {
-        Self { execution_proxy }
+    pub fn new(
+        execution_proxy: Arc<dyn StateComputer>,
+        speculative_executor: Option<Arc<SpeculativeExecutor>>,
+    ) -> Self {
+        Self {
+            execution_proxy,
+            speculative_executor,
+        }
     }
 }
 
@@ -67,10 +77,21 @@ impl StatelessPipeline for ExecutionPhase {
         }
 
         let block_id = ordered_blocks.last().unwrap().id();
+        let ordered_round = ordered_blocks.last().unwrap().round();
         let mut result = vec![];
 
         for b in ordered_blocks {
-            match self.execution_proxy.compute(b.block(), b.parent_id()).await {
+            // If the block was executed speculatively, reuse the result instead of
+            // executing it again.
+            let speculative_result = match &self.speculative_executor {
+                Some(speculative_executor) => speculative_executor.wait_for(b.id()).await,
+                None => None,
+            };
+            let compute_result = match speculative_result {
+                Some(compute_result) => Ok(compute_result),
+                None => self.execution_proxy.compute(b.block(), b.parent_id()).await,
+            };
+            match compute_result {
                 Ok(compute_result) => {
                     result.push(ExecutedBlock::new(b.block().clone(), compute_result));
                 },
@@ -83,6 +104,10 @@ impl StatelessPipeline for ExecutionPhase {
             }
         }
 
+        if let Some(speculative_executor) = &self.speculative_executor {
+            speculative_executor.prune(ordered_round);
+        }
+
         ExecutionResponse {
             block_id,
             inner: Ok(result),
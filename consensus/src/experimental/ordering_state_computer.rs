@@ -3,12 +3,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_observer::publisher::ConsensusPublisher,
     error::StateSyncError,
     experimental::{
         buffer_manager::{OrderedBlocks, ResetAck, ResetRequest},
         errors::Error,
+        speculative_execution::SpeculativeExecutor,
     },
     payload_manager::PayloadManager,
+    payload_orderer::PayloadOrderer,
     state_replication::{StateComputer, StateComputerCommitCallBackType},
     transaction_deduper::TransactionDeduper,
     transaction_shuffler::TransactionShuffler,
@@ -35,6 +38,12 @@ pub struct OrderingStateComputer {
     executor_channel: UnboundedSender<OrderedBlocks>,
     state_computer_for_sync: Arc<dyn StateComputer>,
     reset_event_channel_tx: UnboundedSender<ResetRequest>,
+    // Executes proposed blocks before they're ordered, if optimistic execution is enabled.
+    speculative_executor: Option<Arc<SpeculativeExecutor>>,
+    // Publishes the ordered blocks to consensus observers (if enabled)
+    consensus_publisher: Option<Arc<ConsensusPublisher>>,
+    // Fetches the payloads of the ordered blocks for the consensus publisher
+    payload_manager: Arc<PayloadManager>,
 }
 
 impl OrderingStateComputer {
@@ -42,11 +51,17 @@ impl OrderingStateComputer {
         executor_channel: UnboundedSender<OrderedBlocks>,
         state_computer_for_sync: Arc<dyn StateComputer>,
         reset_event_channel_tx: UnboundedSender<ResetRequest>,
+        speculative_executor: Option<Arc<SpeculativeExecutor>>,
+        consensus_publisher: Option<Arc<ConsensusPublisher>>,
+        payload_manager: Arc<PayloadManager>,
     ) -> Self {
         Self {
             executor_channel,
             state_computer_for_sync,
             reset_event_channel_tx,
+            speculative_executor,
+            consensus_publisher,
+            payload_manager,
         }
     }
 }
@@ -56,10 +71,16 @@ impl StateComputer for OrderingStateComputer {
     async fn compute(
         &self,
         // The block to be executed.
-        _block: &Block,
+        block: &Block,
         // The parent block id.
         _parent_block_id: HashValue,
     ) -> Result<StateComputeResult, ExecutionError> {
+        // Start executing the block while it's being voted on, the execution phase
+        // reuses the result once the block is ordered.
+        if let Some(speculative_executor) = &self.speculative_executor {
+            speculative_executor.speculate(block);
+        }
+
         // Return dummy block and bypass the execution phase.
         // This will break the e2e smoke test (for now because
         // no one is actually handling the next phase) if the
@@ -77,6 +98,16 @@ impl StateComputer for OrderingStateComputer {
     ) -> Result<(), ExecutionError> {
         assert!(!blocks.is_empty());
 
+        // Publish the ordered blocks (and their payloads) so that consensus
+        // observers can start executing them.
+        if let Some(consensus_publisher) = &self.consensus_publisher {
+            consensus_publisher.publish_ordered_blocks(
+                blocks.iter().map(|block| block.block().clone()).collect(),
+                finality_proof.clone(),
+                self.payload_manager.clone(),
+            );
+        }
+
         if self
             .executor_channel
             .clone()
@@ -103,6 +134,11 @@ impl StateComputer for OrderingStateComputer {
             Err(anyhow::anyhow!("Injected error in sync_to").into())
         });
 
+        // the executor is about to be reset, so the speculative executions are useless
+        if let Some(speculative_executor) = &self.speculative_executor {
+            speculative_executor.cancel_all();
+        }
+
         // reset execution phase and commit phase
         let (tx, rx) = oneshot::channel::<ResetAck>();
         self.reset_event_channel_tx
@@ -128,6 +164,7 @@ impl StateComputer for OrderingStateComputer {
         _: Arc<dyn TransactionShuffler>,
         _: Option<u64>,
         _: Arc<dyn TransactionDeduper>,
+        _: Arc<dyn PayloadOrderer>,
     ) {
     }
 
@@ -0,0 +1,140 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{counters, state_replication::StateComputer};
+use aptos_consensus_types::{block::Block, common::Round};
+use aptos_crypto::HashValue;
+use aptos_executor_types::StateComputeResult;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use futures::future::{AbortHandle, Abortable, BoxFuture, FutureExt, Shared};
+use std::{collections::HashMap, sync::Arc};
+
+struct InFlightExecution {
+    round: Round,
+    abort_handle: AbortHandle,
+    // Resolves to the result of the execution, or none if it failed, was cancelled or skipped
+    execution: Shared<BoxFuture<'static, Option<StateComputeResult>>>,
+}
+
+/// [ This class is used when consensus.decoupled = true ]
+/// SpeculativeExecutor starts executing proposed blocks as soon as they are
+/// received, while the validators are still voting on them, so that the
+/// execution phase can reuse the result once the block is ordered instead of
+/// executing the block again.
+///
+/// Nothing is published for a speculatively executed block: its payload is only
+/// published to consensus observers (and it is only committed) once it's ordered.
+/// Blocks that are never ordered are pruned from the executor's block tree when
+/// a conflicting block gets committed.
+pub struct SpeculativeExecutor {
+    execution_proxy: Arc<dyn StateComputer>,
+    in_flight: Mutex<HashMap<HashValue, InFlightExecution>>,
+}
+
+impl SpeculativeExecutor {
+    pub fn new(execution_proxy: Arc<dyn StateComputer>) -> Self {
+        Self {
+            execution_proxy,
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Starts executing the block in the background. If its parent is being
+    /// speculatively executed as well, the execution starts once the parent's is done,
+    /// and is skipped if the parent's failed (as the parent is missing from the executor).
+    /// Failures are ignored: the execution phase executes the block again if needed.
+    pub fn speculate(&self, block: &Block) {
+        let mut in_flight = self.in_flight.lock();
+        if in_flight.contains_key(&block.id()) {
+            return;
+        }
+
+        let parent_execution = in_flight
+            .get(&block.parent_id())
+            .map(|parent| parent.execution.clone());
+        let execution_proxy = self.execution_proxy.clone();
+        let block = block.clone();
+        let (block_id, round) = (block.id(), block.round());
+        let execution = async move {
+            if let Some(parent_execution) = parent_execution {
+                if parent_execution.await.is_none() {
+                    counters::SPECULATIVE_EXECUTION_COUNT
+                        .with_label_values(&["skipped"])
+                        .inc();
+                    return None;
+                }
+            }
+            match execution_proxy.compute(&block, block.parent_id()).await {
+                Ok(compute_result) => {
+                    counters::SPECULATIVE_EXECUTION_COUNT
+                        .with_label_values(&["executed"])
+                        .inc();
+                    Some(compute_result)
+                },
+                Err(e) => {
+                    debug!(
+                        block_id = block.id(),
+                        "Speculative execution failed: {:?}", e
+                    );
+                    counters::SPECULATIVE_EXECUTION_COUNT
+                        .with_label_values(&["failed"])
+                        .inc();
+                    None
+                },
+            }
+        };
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let execution = Abortable::new(execution, abort_registration)
+            .map(|result| result.ok().flatten())
+            .boxed()
+            .shared();
+        tokio::spawn(execution.clone());
+
+        in_flight.insert(block_id, InFlightExecution {
+            round,
+            abort_handle,
+            execution,
+        });
+    }
+
+    /// Waits for the speculative execution of the block, if there is one in flight, and
+    /// returns its result. Returns none if the block must be executed (again) by the caller.
+    pub async fn wait_for(&self, block_id: HashValue) -> Option<StateComputeResult> {
+        let execution = self
+            .in_flight
+            .lock()
+            .get(&block_id)
+            .map(|in_flight| in_flight.execution.clone())?;
+        execution.await
+    }
+
+    /// Stops tracking the blocks up to the given (ordered) round. Blocks at these
+    /// rounds were either ordered and handled by the execution phase, or are on
+    /// a fork that can no longer be certified, so their executions are cancelled.
+    pub fn prune(&self, round: Round) {
+        self.in_flight.lock().retain(|_, in_flight| {
+            if in_flight.round > round {
+                return true;
+            }
+            if in_flight.execution.peek().is_none() {
+                in_flight.abort_handle.abort();
+                counters::SPECULATIVE_EXECUTION_COUNT
+                    .with_label_values(&["cancelled"])
+                    .inc();
+            }
+            false
+        });
+    }
+
+    /// Cancels all in-flight executions, e.g. before state sync resets the executor.
+    pub fn cancel_all(&self) {
+        self.prune(Round::MAX);
+    }
+}
+
+impl Drop for SpeculativeExecutor {
+    fn drop(&mut self) {
+        self.cancel_all();
+    }
+}
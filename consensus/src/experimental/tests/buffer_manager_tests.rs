@@ -18,6 +18,7 @@ use crate::{
     metrics_safety_rules::MetricsSafetyRules,
     network::NetworkSender,
     network_interface::{ConsensusMsg, ConsensusNetworkClient, DIRECT_SEND, RPC},
+    payload_manager::PayloadManager,
     round_manager::{UnverifiedEvent, VerifiedEvent},
     test_utils::{
         consensus_runtime, timed_block_on, EmptyStateComputer, MockStorage,
@@ -126,6 +127,9 @@ pub fn prepare_buffer_manager() -> (
         result_tx,
         Arc::new(EmptyStateComputer),
         reset_tx,
+        None,
+        None,
+        Arc::new(PayloadManager::DirectMempool),
     ));
 
     let (block_tx, block_rx) = create_channel::<OrderedBlocks>();
@@ -142,6 +146,7 @@ pub fn prepare_buffer_manager() -> (
     ) = prepare_phases_and_buffer_manager(
         author,
         mocked_execution_proxy,
+        None,
         Arc::new(Mutex::new(safety_rules)),
         network,
         msg_rx,
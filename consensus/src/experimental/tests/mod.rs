@@ -8,4 +8,5 @@ mod integration_tests;
 mod ordering_state_computer_tests;
 mod phase_tester;
 mod signing_phase_tests;
+mod speculative_execution_tests;
 mod test_utils;
@@ -0,0 +1,218 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::StateSyncError,
+    experimental::{
+        execution_phase::{ExecutionPhase, ExecutionRequest},
+        pipeline_phase::StatelessPipeline,
+        speculative_execution::SpeculativeExecutor,
+    },
+    payload_manager::PayloadManager,
+    payload_orderer::PayloadOrderer,
+    state_replication::{StateComputer, StateComputerCommitCallBackType},
+    transaction_deduper::TransactionDeduper,
+    transaction_shuffler::TransactionShuffler,
+};
+use aptos_consensus_types::{
+    block::{
+        block_test_utils::{certificate_for_genesis, placeholder_certificate_for_block},
+        Block,
+    },
+    common::Payload,
+    executed_block::ExecutedBlock,
+};
+use aptos_crypto::HashValue;
+use aptos_executor_types::{Error, StateComputeResult};
+use aptos_infallible::Mutex;
+use aptos_types::{
+    epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures,
+    validator_signer::ValidatorSigner,
+};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::Semaphore;
+
+/// Executes a block only once the test releases it (by adding a permit), and records
+/// the blocks it executed, in the order they finished executing
+struct GatedStateComputer {
+    permits: Semaphore,
+    failing_blocks: HashSet<HashValue>,
+    executed_blocks: Mutex<Vec<HashValue>>,
+}
+
+impl GatedStateComputer {
+    fn new(failing_blocks: HashSet<HashValue>) -> Self {
+        Self {
+            permits: Semaphore::new(0),
+            failing_blocks,
+            executed_blocks: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn release(&self, num_blocks: usize) {
+        self.permits.add_permits(num_blocks);
+    }
+
+    fn executed_blocks(&self) -> Vec<HashValue> {
+        self.executed_blocks.lock().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl StateComputer for GatedStateComputer {
+    async fn compute(
+        &self,
+        block: &Block,
+        _parent_block_id: HashValue,
+    ) -> Result<StateComputeResult, Error> {
+        self.permits.acquire().await.unwrap().forget();
+        self.executed_blocks.lock().push(block.id());
+        if self.failing_blocks.contains(&block.id()) {
+            return Err(Error::BlockNotFound(block.parent_id()));
+        }
+        Ok(StateComputeResult::new_dummy())
+    }
+
+    async fn commit(
+        &self,
+        _blocks: &[Arc<ExecutedBlock>],
+        _commit: LedgerInfoWithSignatures,
+        _call_back: StateComputerCommitCallBackType,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn sync_to(&self, _commit: LedgerInfoWithSignatures) -> Result<(), StateSyncError> {
+        Ok(())
+    }
+
+    fn new_epoch(
+        &self,
+        _: &EpochState,
+        _: Arc<PayloadManager>,
+        _: Arc<dyn TransactionShuffler>,
+        _: Option<u64>,
+        _: Arc<dyn TransactionDeduper>,
+        _: Arc<dyn PayloadOrderer>,
+    ) {
+    }
+
+    fn end_epoch(&self) {}
+}
+
+fn create_blocks(signer: &ValidatorSigner) -> (Block, Block) {
+    let parent = Block::new_proposal(
+        Payload::empty(false),
+        1,
+        1,
+        certificate_for_genesis(),
+        signer,
+        Vec::new(),
+    )
+    .unwrap();
+    let parent_qc = placeholder_certificate_for_block(
+        &[signer.clone()],
+        parent.id(),
+        parent.round(),
+        parent.parent_id(),
+        0,
+    );
+    let child =
+        Block::new_proposal(Payload::empty(false), 2, 2, parent_qc, signer, Vec::new()).unwrap();
+    (parent, child)
+}
+
+#[tokio::test]
+async fn test_speculative_execution_follows_parent() {
+    let signer = ValidatorSigner::random(None);
+    let (parent, child) = create_blocks(&signer);
+    let state_computer = Arc::new(GatedStateComputer::new(HashSet::new()));
+    let speculative_executor = SpeculativeExecutor::new(state_computer.clone());
+
+    speculative_executor.speculate(&parent);
+    speculative_executor.speculate(&child);
+    // Speculating on the same block twice doesn't execute it again
+    speculative_executor.speculate(&child);
+
+    // Release both executions: the child only starts once the parent is done
+    state_computer.release(2);
+    assert!(speculative_executor.wait_for(child.id()).await.is_some());
+    assert!(speculative_executor.wait_for(parent.id()).await.is_some());
+    assert_eq!(state_computer.executed_blocks(), vec![
+        parent.id(),
+        child.id()
+    ]);
+
+    // Waiting for a block that isn't being executed returns right away
+    assert!(speculative_executor
+        .wait_for(HashValue::random())
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_speculative_execution_skips_children_of_failed_blocks() {
+    let signer = ValidatorSigner::random(None);
+    let (parent, child) = create_blocks(&signer);
+    let state_computer = Arc::new(GatedStateComputer::new(HashSet::from([parent.id()])));
+    let speculative_executor = SpeculativeExecutor::new(state_computer.clone());
+
+    speculative_executor.speculate(&parent);
+    speculative_executor.speculate(&child);
+    state_computer.release(2);
+
+    // The parent failed, so the child is skipped instead of failing with a missing parent
+    assert!(speculative_executor.wait_for(child.id()).await.is_none());
+    assert!(speculative_executor.wait_for(parent.id()).await.is_none());
+    assert_eq!(state_computer.executed_blocks(), vec![parent.id()]);
+}
+
+#[tokio::test]
+async fn test_speculative_execution_prune() {
+    let signer = ValidatorSigner::random(None);
+    let (parent, child) = create_blocks(&signer);
+    let state_computer = Arc::new(GatedStateComputer::new(HashSet::new()));
+    let speculative_executor = SpeculativeExecutor::new(state_computer.clone());
+
+    speculative_executor.speculate(&parent);
+    speculative_executor.speculate(&child);
+
+    // The parent was never ordered, so pruning its round cancels its execution
+    // (which never got released), and its child is skipped
+    speculative_executor.prune(parent.round());
+    assert!(speculative_executor.wait_for(parent.id()).await.is_none());
+    assert!(speculative_executor.wait_for(child.id()).await.is_none());
+    assert!(state_computer.executed_blocks().is_empty());
+}
+
+#[tokio::test]
+async fn test_execution_phase_reuses_speculative_result() {
+    let signer = ValidatorSigner::random(None);
+    let (parent, child) = create_blocks(&signer);
+    let state_computer = Arc::new(GatedStateComputer::new(HashSet::new()));
+    let speculative_executor = Arc::new(SpeculativeExecutor::new(state_computer.clone()));
+    let execution_phase =
+        ExecutionPhase::new(state_computer.clone(), Some(speculative_executor.clone()));
+
+    // Only the parent is executed speculatively
+    speculative_executor.speculate(&parent);
+    state_computer.release(2);
+
+    let response = execution_phase
+        .process(ExecutionRequest {
+            ordered_blocks: vec![
+                ExecutedBlock::new(parent.clone(), StateComputeResult::new_dummy()),
+                ExecutedBlock::new(child.clone(), StateComputeResult::new_dummy()),
+            ],
+        })
+        .await;
+    assert_eq!(response.block_id, child.id());
+    assert_eq!(response.inner.unwrap().len(), 2);
+
+    // Each block was executed exactly once, and the ordered blocks are no longer tracked
+    assert_eq!(state_computer.executed_blocks(), vec![
+        parent.id(),
+        child.id()
+    ]);
+    assert!(speculative_executor.wait_for(parent.id()).await.is_none());
+}
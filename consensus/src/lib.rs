@@ -42,22 +42,33 @@ mod twins;
 mod txn_notifier;
 mod util;
 
+/// Commands operators can send to a running consensus instance
+pub mod admin;
+/// Consensus observer (and publisher), which lets fullnodes follow the ordered blocks
+pub mod consensus_observer;
 /// AptosBFT implementation
 pub mod consensus_provider;
 /// Required by the telemetry service
 pub mod counters;
+/// Recording of inbound messages, to replay production scenarios in tests
+pub mod message_recorder;
 /// AptosNet interface.
 pub mod network_interface;
 mod payload_manager;
+mod payload_orderer;
 mod sender_aware_shuffler;
 mod transaction_deduper;
 mod transaction_shuffler;
 mod txn_hash_and_authenticator_deduper;
 
 use aptos_metrics_core::IntGauge;
+/// Required by the db tool to export the certificate archive
+pub use consensusdb::certificate_archive;
 pub use consensusdb::create_checkpoint;
 /// Required by the smoke tests
 pub use consensusdb::CONSENSUS_DB_NAME;
+/// Required by the node to track and shed the memory of the batch cache
+pub use quorum_store::batch_store::{batch_memory_bytes, set_batch_memory_caching};
 pub use quorum_store::quorum_store_db::QUORUM_STORE_DB_NAME;
 #[cfg(feature = "fuzzing")]
 pub use round_manager::round_manager_fuzzing;
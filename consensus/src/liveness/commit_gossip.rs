@@ -0,0 +1,53 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_consensus_types::common::{Author, Round};
+use std::{collections::HashMap, time::Duration};
+use tokio::time::Instant;
+
+/// Decides when to gossip our latest commit certificate to a peer that lags behind.
+///
+/// A lagging validator keeps sending messages for rounds we're long done with, which we drop
+/// as stale. Instead of leaving it to catch up through a cascade of timeouts, we send it our
+/// SyncInfo, whose commit certificate (ledger info with signatures) lets it fast-forward via
+/// state sync right away. Each peer is sent at most one certificate per interval, so that the
+/// gossip stays cheap while the peer is syncing.
+pub struct CommitCertGossip {
+    round_lag: u64,
+    interval: Duration,
+    last_sent: HashMap<Author, Instant>,
+}
+
+impl CommitCertGossip {
+    /// A `round_lag` of 0 disables the gossip.
+    pub fn new(round_lag: u64, interval: Duration) -> Self {
+        Self {
+            round_lag,
+            interval,
+            last_sent: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the peer's highest commit round lags behind ours enough for the commit
+    /// certificate to be gossiped to it now, and records the gossip if so.
+    pub fn should_gossip(
+        &mut self,
+        peer: Author,
+        peer_commit_round: Round,
+        local_commit_round: Round,
+        now: Instant,
+    ) -> bool {
+        if self.round_lag == 0
+            || peer_commit_round.saturating_add(self.round_lag) > local_commit_round
+        {
+            return false;
+        }
+        if let Some(last_sent) = self.last_sent.get(&peer) {
+            if now.saturating_duration_since(*last_sent) < self.interval {
+                return false;
+            }
+        }
+        self.last_sent.insert(peer, now);
+        true
+    }
+}
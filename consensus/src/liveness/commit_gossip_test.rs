@@ -0,0 +1,30 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::liveness::commit_gossip::CommitCertGossip;
+use aptos_types::account_address::AccountAddress;
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[test]
+fn test_commit_cert_gossip() {
+    let interval = Duration::from_secs(1);
+    let mut gossip = CommitCertGossip::new(10, interval);
+    let (peer_1, peer_2) = (AccountAddress::random(), AccountAddress::random());
+    let now = Instant::now();
+
+    // Peers that don't lag enough are not sent anything
+    assert!(!gossip.should_gossip(peer_1, 91, 100, now));
+    assert!(gossip.should_gossip(peer_1, 90, 100, now));
+
+    // At most one gossip per interval to each peer
+    assert!(!gossip.should_gossip(peer_1, 90, 100, now + interval / 2));
+    assert!(gossip.should_gossip(peer_2, 50, 100, now + interval / 2));
+    assert!(gossip.should_gossip(peer_1, 90, 100, now + interval));
+}
+
+#[test]
+fn test_commit_cert_gossip_disabled() {
+    let mut gossip = CommitCertGossip::new(0, Duration::ZERO);
+    assert!(!gossip.should_gossip(AccountAddress::random(), 0, 100, Instant::now()));
+}
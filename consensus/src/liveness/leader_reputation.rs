@@ -223,6 +223,9 @@ pub struct NewBlockEventAggregation {
     // dependig on how many failures we have.
     voter_window_size: usize,
     proposer_window_size: usize,
+    // Failed proposals are counted over their own window, so that how fast
+    // failures are forgotten can be tuned independently of successes.
+    failure_window_size: usize,
     reputation_window_from_stale_end: bool,
 }
 
@@ -230,11 +233,13 @@ impl NewBlockEventAggregation {
     pub fn new(
         voter_window_size: usize,
         proposer_window_size: usize,
+        failure_window_size: usize,
         reputation_window_from_stale_end: bool,
     ) -> Self {
         Self {
             voter_window_size,
             proposer_window_size,
+            failure_window_size,
             reputation_window_from_stale_end,
         }
     }
@@ -431,7 +436,7 @@ impl NewBlockEventAggregation {
         Self::history_iter(
             history,
             epoch_to_candidates,
-            self.proposer_window_size,
+            self.failure_window_size,
             self.reputation_window_from_stale_end,
         )
         .fold(HashMap::new(), |mut map, meta| {
@@ -466,7 +471,8 @@ impl NewBlockEventAggregation {
 /// but we also, in combinatoin with staking rewards logic, need to be reasonably fair.
 ///
 /// Logic is:
-///  * if proposer round failure rate within the proposer window is strictly above threshold, use failed_weight (default 1).
+///  * if proposer round failure rate (failures within the failure window, compared to successes within
+///    the proposer window) is strictly above threshold, use failed_weight (default 1).
 ///  * otherwise, if node had no proposal rounds and no successful votes, use inactive_weight (default 10).
 ///  * otherwise, use the default active_weight (default 100).
 ///
@@ -499,6 +505,7 @@ impl ProposerAndVoterHeuristic {
         failure_threshold_percent: u32,
         voter_window_size: usize,
         proposer_window_size: usize,
+        failure_window_size: usize,
         reputation_window_from_stale_end: bool,
     ) -> Self {
         Self {
@@ -510,6 +517,7 @@ impl ProposerAndVoterHeuristic {
             aggregation: NewBlockEventAggregation::new(
                 voter_window_size,
                 proposer_window_size,
+                failure_window_size,
                 reputation_window_from_stale_end,
             ),
         }
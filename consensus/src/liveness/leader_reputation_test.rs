@@ -147,7 +147,7 @@ fn test_aggregation_counting() {
     let mut example1 = Example1::new(5);
     let validators0 = example1.validators0.clone();
     let epoch_to_validators = HashMap::from([(0u64, validators0.clone())]);
-    let aggregation = NewBlockEventAggregation::new(2, 5, false);
+    let aggregation = NewBlockEventAggregation::new(2, 5, 5, false);
 
     example1.step1();
 
@@ -238,6 +238,33 @@ fn test_aggregation_counting() {
     );
 }
 
+#[test]
+fn test_aggregation_counting_separate_failure_window() {
+    let mut example1 = Example1::new(5);
+    let validators0 = example1.validators0.clone();
+    let epoch_to_validators = HashMap::from([(0u64, validators0.clone())]);
+    let aggregation = NewBlockEventAggregation::new(2, 5, 2, false);
+
+    example1.step1();
+    example1.step2();
+
+    // Successes are still counted over the whole proposer window
+    assert_eq!(
+        aggregation.count_proposals(&epoch_to_validators, &example1.history()),
+        HashMap::from([
+            (validators0[0], 1),
+            (validators0[1], 1),
+            (validators0[2], 1),
+            (validators0[3], 2),
+        ])
+    );
+    // Only the failures within the (smaller) failure window are counted
+    assert_eq!(
+        aggregation.count_failed_proposals(&epoch_to_validators, &example1.history()),
+        HashMap::from([(validators0[1], 2),])
+    );
+}
+
 /// ####
 
 #[test]
@@ -246,7 +273,7 @@ fn test_proposer_and_voter_heuristic() {
     let validators0 = example1.validators0.clone();
     let epoch_to_validators0 = HashMap::from([(0u64, validators0.clone())]);
     let heuristic =
-        ProposerAndVoterHeuristic::new(example1.validators0[0], 100, 10, 1, 49, 2, 5, false);
+        ProposerAndVoterHeuristic::new(example1.validators0[0], 100, 10, 1, 49, 2, 5, 5, false);
 
     example1.step1();
     assert_eq!(
@@ -336,6 +363,7 @@ fn test_api(use_root_hash: bool) {
                 10,
                 proposers.len(),
                 proposers.len(),
+                proposers.len(),
                 false,
             )),
             4,
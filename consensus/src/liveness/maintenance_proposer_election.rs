@@ -0,0 +1,81 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::proposer_election::ProposerElection;
+use aptos_consensus_types::common::{Author, Round};
+use aptos_types::on_chain_config::{MaintenanceWindow, OnChainMaintenanceSchedule};
+use std::collections::HashMap;
+
+/// The maintenance windows of the validators in an epoch, at most one per validator. They are
+/// read from the on-chain schedule at the start of the epoch, and windows can only be announced
+/// for the next epoch, so all validators agree on them (even after a restart).
+#[derive(Default)]
+pub struct MaintenanceSchedule {
+    windows: HashMap<Author, MaintenanceWindow>,
+}
+
+impl MaintenanceSchedule {
+    pub fn new(epoch: u64, on_chain_schedule: &OnChainMaintenanceSchedule) -> Self {
+        Self {
+            windows: on_chain_schedule
+                .windows_in_epoch(epoch)
+                .map(|window| (window.validator, window.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn is_in_maintenance(&self, author: &Author, round: Round) -> bool {
+        self.windows
+            .get(author)
+            .map_or(false, |window| window.contains(round))
+    }
+}
+
+// Wrapper around ProposerElection.
+//
+// If the elected proposer is in a maintenance window covering the round, another proposer is
+// chosen deterministically among the ones that are not in maintenance, so that the round
+// doesn't time out waiting for a proposal that will never come. The proposal generator uses
+// the same election to compute the failed authors of the skipped rounds.
+pub struct MaintenanceAwareProposerElection {
+    proposer_election: Box<dyn ProposerElection + Send + Sync>,
+    proposers: Vec<Author>,
+    schedule: MaintenanceSchedule,
+}
+
+impl MaintenanceAwareProposerElection {
+    pub fn new(
+        proposer_election: Box<dyn ProposerElection + Send + Sync>,
+        proposers: Vec<Author>,
+        schedule: MaintenanceSchedule,
+    ) -> Self {
+        Self {
+            proposer_election,
+            proposers,
+            schedule,
+        }
+    }
+}
+
+impl ProposerElection for MaintenanceAwareProposerElection {
+    fn get_valid_proposer(&self, round: Round) -> Author {
+        let proposer = self.proposer_election.get_valid_proposer(round);
+        if !self.schedule.is_in_maintenance(&proposer, round) {
+            return proposer;
+        }
+        let candidates: Vec<_> = self
+            .proposers
+            .iter()
+            .filter(|author| !self.schedule.is_in_maintenance(author, round))
+            .collect();
+        if candidates.is_empty() {
+            return proposer;
+        }
+        *candidates[(round % candidates.len() as u64) as usize]
+    }
+
+    fn get_voting_power_participation_ratio(&self, round: Round) -> f64 {
+        self.proposer_election
+            .get_voting_power_participation_ratio(round)
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::liveness::{
+    maintenance_proposer_election::{MaintenanceAwareProposerElection, MaintenanceSchedule},
+    proposer_election::ProposerElection,
+    rotating_proposer_election::RotatingProposer,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::{MaintenanceWindow, OnChainMaintenanceSchedule},
+};
+
+fn window(
+    validator: AccountAddress,
+    epoch: u64,
+    start_round: u64,
+    num_rounds: u64,
+) -> MaintenanceWindow {
+    MaintenanceWindow {
+        validator,
+        epoch,
+        start_round,
+        num_rounds,
+    }
+}
+
+fn election(
+    proposers: &[AccountAddress],
+    epoch: u64,
+    windows: Vec<MaintenanceWindow>,
+) -> MaintenanceAwareProposerElection {
+    MaintenanceAwareProposerElection::new(
+        Box::new(RotatingProposer::new(proposers.to_vec(), 1)),
+        proposers.to_vec(),
+        MaintenanceSchedule::new(epoch, &OnChainMaintenanceSchedule { windows }),
+    )
+}
+
+#[test]
+fn test_maintenance_aware_proposer() {
+    let proposers: Vec<_> = (0..4).map(|_| AccountAddress::random()).collect();
+
+    // Without windows, the inner election is used
+    let pe = election(&proposers, 1, vec![]);
+    for round in 0..8 {
+        assert_eq!(pe.get_valid_proposer(round), proposers[round as usize % 4]);
+    }
+
+    // proposers[1] is in maintenance for rounds [5, 15) of epoch 1. The window of epoch 2
+    // doesn't apply.
+    let pe = election(&proposers, 1, vec![
+        window(proposers[1], 1, 5, 10),
+        window(proposers[2], 2, 5, 10),
+    ]);
+    for round in 0..20 {
+        let proposer = pe.get_valid_proposer(round);
+        if (5..15).contains(&round) {
+            assert_ne!(proposer, proposers[1]);
+            assert!(!pe.is_valid_proposer(proposers[1], round));
+        } else {
+            assert_eq!(proposer, proposers[round as usize % 4]);
+        }
+    }
+    // The rounds of proposers[1] are handed off to the other proposers, round robin
+    assert_eq!(pe.get_valid_proposer(9), proposers[0]);
+    assert_eq!(pe.get_valid_proposer(13), proposers[2]);
+
+    // If every validator is in maintenance, the inner election is used
+    let pe = election(
+        &proposers,
+        1,
+        proposers
+            .iter()
+            .map(|proposer| window(*proposer, 1, 30, 10))
+            .collect(),
+    );
+    assert_eq!(pe.get_valid_proposer(33), proposers[1]);
+}
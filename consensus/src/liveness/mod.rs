@@ -3,7 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod cached_proposer_election;
+pub(crate) mod commit_gossip;
 pub(crate) mod leader_reputation;
+pub(crate) mod maintenance_proposer_election;
 pub(crate) mod proposal_generator;
 pub(crate) mod proposer_election;
 pub(crate) mod rotating_proposer_election;
@@ -14,8 +16,12 @@ pub(crate) mod unequivocal_proposer_election;
 #[cfg(test)]
 mod cached_proposer_election_test;
 #[cfg(test)]
+mod commit_gossip_test;
+#[cfg(test)]
 mod leader_reputation_test;
 #[cfg(test)]
+mod maintenance_proposer_election_test;
+#[cfg(test)]
 mod rotating_proposer_test;
 #[cfg(test)]
 mod round_proposer_test;
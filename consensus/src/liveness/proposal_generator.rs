@@ -8,19 +8,22 @@ use super::{
 use crate::{
     block_storage::BlockReader,
     counters::{
-        CHAIN_HEALTH_BACKOFF_TRIGGERED, PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED,
-        PROPOSER_DELAY_PROPOSAL, PROPOSER_PENDING_BLOCKS_COUNT,
-        PROPOSER_PENDING_BLOCKS_FILL_FRACTION,
+        CHAIN_HEALTH_BACKOFF_TRIGGERED, EXECUTION_BACKPRESSURE_ON_PROPOSAL_TRIGGERED,
+        PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED, PROPOSER_DELAY_PROPOSAL,
+        PROPOSER_PENDING_BLOCKS_COUNT, PROPOSER_PENDING_BLOCKS_FILL_FRACTION,
     },
     state_replication::PayloadClient,
     util::time_service::TimeService,
 };
 use anyhow::{bail, ensure, format_err, Context};
-use aptos_config::config::{ChainHealthBackoffValues, PipelineBackpressureValues};
+use aptos_config::config::{
+    ChainHealthBackoffValues, ExecutionBackpressureValues, PipelineBackpressureValues,
+};
 use aptos_consensus_types::{
     block::Block,
     block_data::BlockData,
     common::{Author, Payload, PayloadFilter, Round},
+    executed_block::ExecutedBlock,
     quorum_cert::QuorumCert,
 };
 use aptos_logger::{error, sample, sample::SampleRate, warn};
@@ -164,10 +167,14 @@ pub struct ProposalGenerator {
 
     pipeline_backpressure_config: PipelineBackpressureConfig,
     chain_health_backoff_config: ChainHealthBackoffConfig,
+    // Caps the proposals by the execution throughput of the pending blocks (if set)
+    execution_backpressure: Option<ExecutionBackpressureValues>,
 
     // Last round that a proposal was generated
     last_round_generated: Round,
     quorum_store_enabled: bool,
+    // Number of empty blocks to propose without waiting for transactions (see mine_empty_blocks)
+    num_empty_blocks_to_mine: u64,
 }
 
 impl ProposalGenerator {
@@ -195,15 +202,36 @@ impl ProposalGenerator {
             max_failed_authors_to_store,
             pipeline_backpressure_config,
             chain_health_backoff_config,
+            execution_backpressure: None,
             last_round_generated: 0,
             quorum_store_enabled,
+            num_empty_blocks_to_mine: 0,
         }
     }
 
+    pub fn with_execution_backpressure(
+        mut self,
+        execution_backpressure: Option<ExecutionBackpressureValues>,
+    ) -> Self {
+        self.execution_backpressure = execution_backpressure;
+        self
+    }
+
     pub fn author(&self) -> Author {
         self.author
     }
 
+    /// Makes the next `num_blocks` proposals empty, so that they are proposed right away
+    /// instead of waiting for transactions. Used by local test chains to mine blocks on demand.
+    pub fn mine_empty_blocks(&mut self, num_blocks: u64) {
+        self.num_empty_blocks_to_mine = self.num_empty_blocks_to_mine.saturating_add(num_blocks);
+    }
+
+    /// Returns the number of empty blocks that are still to be proposed
+    pub fn num_empty_blocks_to_mine(&self) -> u64 {
+        self.num_empty_blocks_to_mine
+    }
+
     /// Creates a NIL block proposal extending the highest certified block from the block store.
     pub fn generate_nil_block(
         &self,
@@ -252,6 +280,13 @@ impl ProposalGenerator {
                 Payload::empty(self.quorum_store_enabled),
                 hqc.certified_block().timestamp_usecs(),
             )
+        } else if self.num_empty_blocks_to_mine > 0 {
+            // Mine an empty block right away, with the current (possibly advanced) timestamp
+            self.num_empty_blocks_to_mine -= 1;
+            (
+                Payload::empty(self.quorum_store_enabled),
+                self.time_service.get_current_timestamp().as_micros() as u64,
+            )
         } else {
             // One needs to hold the blocks with the references to the payloads while get_block is
             // being executed: pending blocks vector keeps all the pending ancestors of the extended branch.
@@ -286,7 +321,7 @@ impl ProposalGenerator {
             let voting_power_ratio = proposer_election.get_voting_power_participation_ratio(round);
 
             let (max_block_txns, max_block_bytes, proposal_delay) = self
-                .calculate_max_block_sizes(voting_power_ratio, timestamp)
+                .calculate_max_block_sizes(voting_power_ratio, timestamp, &pending_blocks)
                 .await;
 
             PROPOSER_DELAY_PROPOSAL.set(proposal_delay.as_secs_f64());
@@ -349,6 +384,7 @@ impl ProposalGenerator {
         &mut self,
         voting_power_ratio: f64,
         timestamp: Duration,
+        pending_blocks: &[Arc<ExecutedBlock>],
     ) -> (u64, u64, Duration) {
         let mut values_max_block_txns = vec![self.max_block_txns];
         let mut values_max_block_bytes = vec![self.max_block_bytes];
@@ -378,23 +414,63 @@ impl ProposalGenerator {
             PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED.observe(0.0);
         };
 
+        let execution_backpressure = self.execution_backpressure_max_block_txns(pending_blocks);
+        if let Some(max_block_txns) = execution_backpressure {
+            values_max_block_txns.push(max_block_txns);
+            EXECUTION_BACKPRESSURE_ON_PROPOSAL_TRIGGERED.observe(1.0);
+        } else {
+            EXECUTION_BACKPRESSURE_ON_PROPOSAL_TRIGGERED.observe(0.0);
+        }
+
         let max_block_txns = values_max_block_txns.into_iter().min().unwrap();
         let max_block_bytes = values_max_block_bytes.into_iter().min().unwrap();
         let proposal_delay = values_proposal_delay.into_iter().max().unwrap();
 
-        if pipeline_backpressure.is_some() || chain_health_backoff.is_some() {
+        if pipeline_backpressure.is_some()
+            || chain_health_backoff.is_some()
+            || execution_backpressure.is_some()
+        {
             warn!(
-                "Generating proposal: reducing limits to {} txns and {} bytes, due to pipeline_backpressure: {}, chain health backoff: {}. Delaying sending proposal by {}ms",
+                "Generating proposal: reducing limits to {} txns and {} bytes, due to pipeline_backpressure: {}, chain health backoff: {}, execution backpressure: {}. Delaying sending proposal by {}ms",
                 max_block_txns,
                 max_block_bytes,
                 pipeline_backpressure.is_some(),
                 chain_health_backoff.is_some(),
+                execution_backpressure.is_some(),
                 proposal_delay.as_millis(),
             );
         }
         (max_block_txns, max_block_bytes, proposal_delay)
     }
 
+    /// Returns the max number of txns that can be executed in the target time, at the
+    /// throughput the pending blocks were executed with. Returns None if execution
+    /// backpressure is disabled, or none of the pending blocks executed any txns.
+    pub(crate) fn execution_backpressure_max_block_txns(
+        &self,
+        pending_blocks: &[Arc<ExecutedBlock>],
+    ) -> Option<u64> {
+        let execution_backpressure = self.execution_backpressure.as_ref()?;
+        let (num_txns, execution_duration) = pending_blocks
+            .iter()
+            .map(|block| block.compute_result().execution_profile())
+            .fold((0, Duration::ZERO), |(num_txns, duration), profile| {
+                (
+                    num_txns + profile.num_transactions,
+                    duration + profile.total_duration(),
+                )
+            });
+        if num_txns == 0 || execution_duration.is_zero() {
+            return None;
+        }
+
+        let throughput = num_txns as f64 / execution_duration.as_secs_f64();
+        let max_block_txns = (throughput
+            * Duration::from_millis(execution_backpressure.target_block_execution_time_ms)
+                .as_secs_f64()) as u64;
+        Some(max_block_txns.max(execution_backpressure.min_sending_block_txns))
+    }
+
     fn ensure_highest_quorum_cert(&self, round: Round) -> anyhow::Result<Arc<QuorumCert>> {
         let hqc = self.block_store.highest_quorum_cert();
         ensure!(
@@ -5,6 +5,7 @@
 use crate::{
     block_storage::BlockReader,
     liveness::{
+        maintenance_proposer_election::{MaintenanceAwareProposerElection, MaintenanceSchedule},
         proposal_generator::{
             ChainHealthBackoffConfig, PipelineBackpressureConfig, ProposalGenerator,
         },
@@ -14,11 +15,17 @@ use crate::{
     test_utils::{build_empty_tree, MockPayloadManager, TreeInserter},
     util::mock_time_service::SimulatedTimeService,
 };
+use aptos_config::config::ExecutionBackpressureValues;
 use aptos_consensus_types::{
     block::{block_test_utils::certificate_for_genesis, Block},
     common::Author,
+    executed_block::ExecutedBlock,
+};
+use aptos_executor_types::{ExecutionProfile, StateComputeResult};
+use aptos_types::{
+    on_chain_config::{MaintenanceWindow, OnChainMaintenanceSchedule},
+    validator_signer::ValidatorSigner,
 };
-use aptos_types::validator_signer::ValidatorSigner;
 use futures::{future::BoxFuture, FutureExt};
 use std::{sync::Arc, time::Duration};
 
@@ -213,3 +220,158 @@ async fn test_correct_failed_authors() {
     assert_eq!(result.failed_authors().unwrap()[3], (4, peer1));
     assert_eq!(result.failed_authors().unwrap()[4], (5, peer2));
 }
+
+#[tokio::test]
+async fn test_failed_authors_with_maintenance() {
+    let inserter = TreeInserter::default();
+    let author = inserter.signer().author();
+    let peer1 = Author::random();
+    let peer2 = Author::random();
+    let block_store = inserter.block_store();
+    let mut proposal_generator = ProposalGenerator::new(
+        author,
+        block_store.clone(),
+        Arc::new(MockPayloadManager::new(None)),
+        Arc::new(SimulatedTimeService::new()),
+        Duration::ZERO,
+        1,
+        1000,
+        10,
+        PipelineBackpressureConfig::new_no_backoff(),
+        ChainHealthBackoffConfig::new_no_backoff(),
+        false,
+    );
+    // peer1 is in maintenance in round 4, which is handed off to author
+    let proposers = vec![author, peer1, peer2];
+    let schedule = OnChainMaintenanceSchedule {
+        windows: vec![MaintenanceWindow {
+            validator: peer1,
+            epoch: 1,
+            start_round: 4,
+            num_rounds: 1,
+        }],
+    };
+    let mut proposer_election =
+        UnequivocalProposerElection::new(Box::new(MaintenanceAwareProposerElection::new(
+            Box::new(RotatingProposer::new(proposers.clone(), 1)),
+            proposers,
+            MaintenanceSchedule::new(1, &schedule),
+        )));
+
+    let result = proposal_generator
+        .generate_proposal(6, &mut proposer_election, empty_callback())
+        .await
+        .unwrap();
+    // The failed authors of the skipped rounds follow the same rotation as the election
+    assert_eq!(result.failed_authors().unwrap(), &vec![
+        (1, peer1),
+        (2, peer2),
+        (3, author),
+        (4, author),
+        (5, peer2),
+    ]);
+}
+
+#[tokio::test]
+async fn test_proposal_generation_mine_empty_blocks() {
+    let signer = ValidatorSigner::random(None);
+    let block_store = build_empty_tree();
+    let mut proposal_generator = ProposalGenerator::new(
+        signer.author(),
+        block_store.clone(),
+        Arc::new(MockPayloadManager::new(None)),
+        Arc::new(SimulatedTimeService::new()),
+        Duration::ZERO,
+        1,
+        10,
+        10,
+        PipelineBackpressureConfig::new_no_backoff(),
+        ChainHealthBackoffConfig::new_no_backoff(),
+        false,
+    );
+    let mut proposer_election =
+        UnequivocalProposerElection::new(Box::new(RotatingProposer::new(vec![signer.author()], 1)));
+
+    // Request two empty blocks, and verify they are proposed without any transactions
+    proposal_generator.mine_empty_blocks(2);
+    for round in 1..=2 {
+        let proposal_data = proposal_generator
+            .generate_proposal(round, &mut proposer_election, empty_callback())
+            .await
+            .unwrap();
+        assert!(proposal_data.payload().unwrap().is_empty());
+    }
+    assert_eq!(proposal_generator.num_empty_blocks_to_mine(), 0);
+
+    // Verify the next proposal pulls transactions again
+    let proposal_data = proposal_generator
+        .generate_proposal(3, &mut proposer_election, empty_callback())
+        .await
+        .unwrap();
+    assert!(!proposal_data.payload().unwrap().is_empty());
+}
+
+#[test]
+fn test_execution_backpressure_max_block_txns() {
+    let signer = ValidatorSigner::random(None);
+    let proposal_generator = ProposalGenerator::new(
+        signer.author(),
+        build_empty_tree(),
+        Arc::new(MockPayloadManager::new(None)),
+        Arc::new(SimulatedTimeService::new()),
+        Duration::ZERO,
+        1000,
+        10,
+        10,
+        PipelineBackpressureConfig::new_no_backoff(),
+        ChainHealthBackoffConfig::new_no_backoff(),
+        false,
+    );
+    let executed_block = |num_transactions, vm_execution_ms, apply_to_ledger_ms| {
+        let execution_profile = ExecutionProfile {
+            num_transactions,
+            vm_execution_duration: Duration::from_millis(vm_execution_ms),
+            apply_to_ledger_duration: Duration::from_millis(apply_to_ledger_ms),
+            ..ExecutionProfile::default()
+        };
+        Arc::new(ExecutedBlock::new(
+            Block::make_genesis_block(),
+            StateComputeResult::new_dummy().with_execution_profile(execution_profile),
+        ))
+    };
+    // 300 txns in 1.5s, i.e., 200 txns per second
+    let pending_blocks = vec![executed_block(100, 400, 100), executed_block(200, 900, 100)];
+
+    // Disabled by default
+    assert_eq!(
+        proposal_generator.execution_backpressure_max_block_txns(&pending_blocks),
+        None
+    );
+
+    let proposal_generator =
+        proposal_generator.with_execution_backpressure(Some(ExecutionBackpressureValues {
+            target_block_execution_time_ms: 500,
+            min_sending_block_txns: 50,
+        }));
+    assert_eq!(
+        proposal_generator.execution_backpressure_max_block_txns(&pending_blocks),
+        Some(100)
+    );
+
+    // Not capped below the min number of txns
+    let slow_blocks = vec![executed_block(10, 1000, 0)];
+    assert_eq!(
+        proposal_generator.execution_backpressure_max_block_txns(&slow_blocks),
+        Some(50)
+    );
+
+    // No pending blocks (or only empty ones), so nothing to estimate the throughput with
+    assert_eq!(
+        proposal_generator.execution_backpressure_max_block_txns(&[]),
+        None
+    );
+    assert_eq!(
+        proposal_generator.execution_backpressure_max_block_txns(&[executed_block(0, 0, 0)]),
+        None
+    );
+}
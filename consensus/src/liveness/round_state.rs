@@ -17,6 +17,7 @@ use aptos_types::{
     ledger_info::LedgerInfoWithPartialSignatures, validator_verifier::ValidatorVerifier,
 };
 use futures::future::AbortHandle;
+use rand::Rng;
 use serde::Serialize;
 use std::{fmt, sync::Arc, time::Duration};
 
@@ -75,7 +76,7 @@ pub trait RoundTimeInterval: Send + Sync + 'static {
 }
 
 /// Round durations increase exponentially
-/// Basically time interval is base * mul^power
+/// Basically time interval is base * mul^power (plus a random jitter)
 /// Where power=max(rounds_since_qc, max_exponent)
 #[derive(Clone)]
 pub struct ExponentialTimeInterval {
@@ -89,6 +90,9 @@ pub struct ExponentialTimeInterval {
     // max_interval.  Alternatively, we can consider using max_interval to meet partial synchrony
     // assumptions where while delta is unknown, it is <= max_interval.
     max_exponent: usize,
+    // Max random jitter added to the interval, as a percentage of the interval. Spreads out
+    // the timeouts of the validators, so they don't all time out at the same time.
+    jitter_percent: u64,
 }
 
 impl ExponentialTimeInterval {
@@ -110,8 +114,18 @@ impl ExponentialTimeInterval {
             base_ms: base.as_millis() as u64, // any reasonable ms timeout fits u64 perfectly
             exponent_base,
             max_exponent,
+            jitter_percent: 0,
         }
     }
+
+    pub fn with_jitter(mut self, jitter_percent: u64) -> Self {
+        assert!(
+            jitter_percent <= 100,
+            "jitter_percent for RoundStateTimeInterval should be <=100"
+        );
+        self.jitter_percent = jitter_percent;
+        self
+    }
 }
 
 impl RoundTimeInterval for ExponentialTimeInterval {
@@ -119,7 +133,19 @@ impl RoundTimeInterval for ExponentialTimeInterval {
         let pow = round_index_after_committed_qc.min(self.max_exponent) as u32;
         let base_multiplier = self.exponent_base.powf(f64::from(pow));
         let duration_ms = ((self.base_ms as f64) * base_multiplier).ceil() as u64;
-        Duration::from_millis(duration_ms)
+        let max_jitter_ms = duration_ms * self.jitter_percent / 100;
+        let jitter_ms = if max_jitter_ms > 0 {
+            rand::thread_rng().gen_range(0, max_jitter_ms + 1)
+        } else {
+            0
+        };
+        let duration = Duration::from_millis(duration_ms + jitter_ms);
+
+        counters::ROUND_TIMEOUT_JITTER_SECONDS.observe(jitter_ms as f64 / 1000.0);
+        counters::ROUND_TIMEOUT_SECONDS
+            .with_label_values(&[&pow.to_string()])
+            .observe(duration.as_secs_f64());
+        duration
     }
 }
 
@@ -37,6 +37,18 @@ fn test_round_time_interval() {
     assert_eq!(6750, interval.get_round_duration(1000).as_millis());
 }
 
+#[test]
+fn test_exponential_time_interval_with_jitter() {
+    let interval =
+        ExponentialTimeInterval::new(Duration::from_millis(3000), 1.5, 2).with_jitter(10);
+    for _ in 0..100 {
+        let duration = interval.get_round_duration(0).as_millis();
+        assert!((3000..=3300).contains(&duration));
+        let duration = interval.get_round_duration(2).as_millis();
+        assert!((6750..=7425).contains(&duration));
+    }
+}
+
 #[tokio::test]
 /// Verify that RoundState properly outputs local timeout events upon timeout
 async fn test_basic_timeout() {
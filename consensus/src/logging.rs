@@ -19,6 +19,8 @@ pub struct LogSchema {
 pub enum LogEvent {
     CommitViaBlock,
     CommitViaSync,
+    GossipCommitCert,
+    MineBlocks,
     NewEpoch,
     NewRound,
     Propose,
@@ -0,0 +1,379 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recording of the inbound consensus messages, so that production scenarios (e.g. specific
+//! orderings of messages, or messages from Byzantine peers) can be replayed into a consensus
+//! instance with a mock network and a simulated clock, and turned into regression tests.
+//!
+//! A recording is a sequence of length-prefixed (u32, little endian) BCS encoded
+//! [`RecordedMessage`]s.
+
+use crate::{counters, network_interface::ConsensusMsg};
+use anyhow::{ensure, Context};
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_network::protocols::network::Event;
+use aptos_types::account_address::AccountAddress;
+use futures::SinkExt;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    path::Path,
+    sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+/// Max size of a single recorded message, anything larger is considered a corrupted recording
+const MAX_RECORDED_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Max number of messages waiting to be written, messages are dropped once it's reached
+const MAX_PENDING_RECORDED_MESSAGES: usize = 10_000;
+
+/// An inbound message, with the time it was received at and the peer it was received from.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RecordedMessage {
+    /// The time the message was received at
+    pub timestamp_usecs: u64,
+    /// The peer the message was received from
+    pub peer_id: AccountAddress,
+    /// The received message
+    pub msg: ConsensusMsg,
+}
+
+/// A recording in progress: the messages are written to the file by a dedicated thread, so
+/// recording never blocks the network task on file I/O.
+struct Recording {
+    sender: SyncSender<RecordedMessage>,
+    writer_thread: JoinHandle<anyhow::Result<()>>,
+}
+
+impl Recording {
+    /// Stops the writer thread once it has written the pending messages
+    fn finish(self) -> anyhow::Result<()> {
+        drop(self.sender);
+        self.writer_thread
+            .join()
+            .map_err(|_| anyhow::anyhow!("The recording writer thread panicked"))?
+    }
+}
+
+/// Records the inbound direct-send consensus messages to a file, while recording is started.
+/// RPC requests are not recorded, as replaying them requires the responses of the peer.
+/// If the file can't keep up with the inbound messages, the messages are dropped (see
+/// `CONSENSUS_RECORDER_DROPPED_MSGS`) instead of slowing consensus down.
+#[derive(Default)]
+pub struct MessageRecorder {
+    recording: Mutex<Option<Recording>>,
+}
+
+impl MessageRecorder {
+    /// Creates a recorder that doesn't record until it is started
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts recording to the given file, replacing its contents. If a recording was already
+    /// in progress, it is stopped first.
+    pub fn start(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create recording file {:?}", path))?;
+        let (sender, receiver) = sync_channel(MAX_PENDING_RECORDED_MESSAGES);
+        let writer_thread = std::thread::Builder::new()
+            .name("consensus-recorder".to_string())
+            .spawn(move || Self::write_all(BufWriter::new(file), receiver))?;
+        let previous = self.recording.lock().replace(Recording {
+            sender,
+            writer_thread,
+        });
+        if let Some(previous) = previous {
+            previous.finish()?;
+        }
+        info!("Started recording consensus messages to {:?}", path);
+        Ok(())
+    }
+
+    /// Stops recording and waits for the recorded messages to be written to the file
+    pub fn stop(&self) -> anyhow::Result<()> {
+        let recording = self.recording.lock().take();
+        if let Some(recording) = recording {
+            recording.finish()?;
+            info!("Stopped recording consensus messages");
+        }
+        Ok(())
+    }
+
+    /// Returns true if the inbound messages are being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recording.lock().is_some()
+    }
+
+    /// Hands the message to the writer thread if recording is started
+    pub fn record(&self, peer_id: AccountAddress, msg: &ConsensusMsg) {
+        if let Some(recording) = self.recording.lock().as_ref() {
+            let recorded_message = RecordedMessage {
+                timestamp_usecs: aptos_infallible::duration_since_epoch().as_micros() as u64,
+                peer_id,
+                msg: msg.clone(),
+            };
+            match recording.sender.try_send(recorded_message) {
+                Ok(()) => {},
+                Err(TrySendError::Full(_)) => counters::CONSENSUS_RECORDER_DROPPED_MSGS.inc(),
+                // The writer thread failed, and already logged the error
+                Err(TrySendError::Disconnected(_)) => {},
+            }
+        }
+    }
+
+    /// Writes the messages until the recording is stopped. Writing is stopped on failures, as a
+    /// partially written message would corrupt the rest of the recording.
+    fn write_all(
+        mut file: BufWriter<File>,
+        receiver: Receiver<RecordedMessage>,
+    ) -> anyhow::Result<()> {
+        let result = receiver
+            .iter()
+            .try_for_each(|recorded_message| Self::write(&mut file, &recorded_message))
+            .and_then(|()| Ok(file.flush()?));
+        if let Err(e) = &result {
+            error!(
+                error = ?e,
+                "Failed to record consensus messages, stopping the recording",
+            );
+        }
+        result
+    }
+
+    fn write(file: &mut BufWriter<File>, recorded_message: &RecordedMessage) -> anyhow::Result<()> {
+        let bytes = bcs::to_bytes(recorded_message)?;
+        file.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        file.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+/// Reads all the messages of a recording, in the order they were received.
+pub fn read_recording(path: &Path) -> anyhow::Result<Vec<RecordedMessage>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open recording file {:?}", path))?;
+    let mut reader = BufReader::new(file);
+    let mut messages = vec![];
+    loop {
+        let mut len_bytes = [0u8; 4];
+        match reader.read_exact(&mut len_bytes) {
+            Ok(()) => {},
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        }
+        let len = u32::from_le_bytes(len_bytes) as usize;
+        ensure!(
+            len <= MAX_RECORDED_MESSAGE_BYTES,
+            "Recorded message {} is too large: {} bytes",
+            messages.len(),
+            len
+        );
+        let mut bytes = vec![0u8; len];
+        reader
+            .read_exact(&mut bytes)
+            .with_context(|| format!("Recorded message {} is truncated", messages.len()))?;
+        messages.push(bcs::from_bytes(&bytes)?);
+    }
+    Ok(messages)
+}
+
+/// Feeds recorded messages into a consensus instance, through the channel its `NetworkTask`
+/// reads self messages from, so production scenarios can be turned into regression tests.
+pub struct MessageReplayer {
+    messages: Vec<RecordedMessage>,
+}
+
+impl MessageReplayer {
+    /// Creates a replayer for the given messages
+    pub fn new(messages: Vec<RecordedMessage>) -> Self {
+        Self { messages }
+    }
+
+    /// Creates a replayer for the messages of the given recording
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        Ok(Self::new(read_recording(path)?))
+    }
+
+    /// Sends the messages in order. Before each message, `advance_clock` is called with the
+    /// time elapsed since the previous message, so a simulated clock (e.g. the
+    /// `SimulatedTimeService` of the consensus tests) can fire the timeouts as they did when
+    /// recording.
+    pub async fn replay(
+        self,
+        sender: &mut aptos_channels::Sender<Event<ConsensusMsg>>,
+        mut advance_clock: impl FnMut(Duration),
+    ) -> anyhow::Result<()> {
+        let mut last_timestamp_usecs = self.messages.first().map(|m| m.timestamp_usecs);
+        for recorded_message in self.messages {
+            if let Some(last_timestamp_usecs) = last_timestamp_usecs {
+                advance_clock(Duration::from_micros(
+                    recorded_message
+                        .timestamp_usecs
+                        .saturating_sub(last_timestamp_usecs),
+                ));
+            }
+            last_timestamp_usecs = Some(recorded_message.timestamp_usecs);
+            sender
+                .send(Event::Message(
+                    recorded_message.peer_id,
+                    recorded_message.msg,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        network::{NetworkReceivers, NetworkTask},
+        test_utils::consensus_runtime,
+        util::mock_time_service::SimulatedTimeService,
+    };
+    use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+    use aptos_config::network_id::NetworkId;
+    use aptos_consensus_types::epoch_retrieval::EpochRetrievalRequest;
+    use aptos_network::{
+        application::interface::NetworkServiceEvents,
+        protocols::network::{NetworkEvents, NewNetworkEvents},
+    };
+    use aptos_temppath::TempPath;
+    use futures::StreamExt;
+    use maplit::hashmap;
+    use std::sync::Arc;
+
+    fn epoch_retrieval_msg(start_epoch: u64) -> ConsensusMsg {
+        ConsensusMsg::EpochRetrievalRequest(Box::new(EpochRetrievalRequest {
+            start_epoch,
+            end_epoch: start_epoch + 1,
+        }))
+    }
+
+    fn start_epoch(msg: &ConsensusMsg) -> u64 {
+        match msg {
+            ConsensusMsg::EpochRetrievalRequest(request) => request.start_epoch,
+            msg => panic!("Unexpected message {:?}", msg),
+        }
+    }
+
+    #[test]
+    fn test_record_and_read() {
+        let path = TempPath::new();
+        let recorder = MessageRecorder::new();
+        let peers = [AccountAddress::random(), AccountAddress::random()];
+
+        // Messages are only recorded while recording is started
+        recorder.record(peers[0], &epoch_retrieval_msg(0));
+        recorder.start(path.path()).unwrap();
+        assert!(recorder.is_recording());
+        recorder.record(peers[0], &epoch_retrieval_msg(1));
+        recorder.record(peers[1], &epoch_retrieval_msg(2));
+        recorder.stop().unwrap();
+        assert!(!recorder.is_recording());
+        recorder.record(peers[1], &epoch_retrieval_msg(3));
+
+        let messages = read_recording(path.path()).unwrap();
+        assert_eq!(
+            messages
+                .iter()
+                .map(|m| (m.peer_id, start_epoch(&m.msg)))
+                .collect::<Vec<_>>(),
+            vec![(peers[0], 1), (peers[1], 2)]
+        );
+        assert!(messages[0].timestamp_usecs <= messages[1].timestamp_usecs);
+
+        // A truncated recording is rejected
+        let bytes = std::fs::read(path.path()).unwrap();
+        std::fs::write(path.path(), &bytes[..bytes.len() - 1]).unwrap();
+        assert!(read_recording(path.path()).is_err());
+    }
+
+    fn create_network_task() -> (
+        NetworkTask,
+        NetworkReceivers,
+        aptos_channels::Sender<Event<ConsensusMsg>>,
+    ) {
+        let (_peer_mgr_notifs_tx, peer_mgr_notifs_rx) =
+            aptos_channel::new(QueueStyle::FIFO, 8, None);
+        let (_connection_notifs_tx, connection_notifs_rx) =
+            aptos_channel::new(QueueStyle::FIFO, 8, None);
+        let network_events = NetworkEvents::new(peer_mgr_notifs_rx, connection_notifs_rx, None);
+        let network_service_events =
+            NetworkServiceEvents::new(hashmap! {NetworkId::Validator => network_events});
+        let (self_sender, self_receiver) = aptos_channels::new_test(8);
+        let (network_task, network_receivers) =
+            NetworkTask::new(network_service_events, self_receiver);
+        (network_task, network_receivers, self_sender)
+    }
+
+    async fn assert_received(
+        network_receivers: &mut NetworkReceivers,
+        expected: &[(AccountAddress, u64)],
+    ) {
+        for (expected_peer_id, expected_start_epoch) in expected {
+            let (peer_id, msg) = network_receivers.consensus_messages.next().await.unwrap();
+            assert_eq!(peer_id, *expected_peer_id);
+            assert_eq!(start_epoch(&msg), *expected_start_epoch);
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let runtime = consensus_runtime();
+        let _entered_runtime = runtime.enter();
+
+        // Record everything the first network task receives
+        let path = TempPath::new();
+        let recorder = Arc::new(MessageRecorder::new());
+        recorder.start(path.path()).unwrap();
+        let (network_task, mut network_receivers, mut self_sender) = create_network_task();
+        runtime.spawn(network_task.with_message_recorder(recorder.clone()).start());
+
+        let peers = [AccountAddress::random(), AccountAddress::random()];
+        let messages: Vec<_> = (0..4)
+            .map(|i| RecordedMessage {
+                timestamp_usecs: i * 1000,
+                peer_id: peers[i as usize % 2],
+                msg: epoch_retrieval_msg(i),
+            })
+            .collect();
+        let expected: Vec<_> = messages
+            .iter()
+            .map(|m| (m.peer_id, start_epoch(&m.msg)))
+            .collect();
+
+        runtime.block_on(async {
+            let mut time_service = SimulatedTimeService::new();
+            MessageReplayer::new(messages)
+                .replay(&mut self_sender, |elapsed| {
+                    time_service.update_auto_advance_limit(elapsed)
+                })
+                .await
+                .unwrap();
+            assert_received(&mut network_receivers, &expected).await;
+        });
+        recorder.stop().unwrap();
+
+        // Replaying the recording into another network task delivers the same messages
+        let (network_task, mut network_receivers, mut self_sender) = create_network_task();
+        runtime.spawn(network_task.start());
+        runtime.block_on(async {
+            let mut time_service = SimulatedTimeService::new();
+            MessageReplayer::from_file(path.path())
+                .unwrap()
+                .replay(&mut self_sender, |elapsed| {
+                    time_service.update_auto_advance_limit(elapsed)
+                })
+                .await
+                .unwrap();
+            assert_received(&mut network_receivers, &expected).await;
+        });
+    }
+}
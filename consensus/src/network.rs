@@ -7,6 +7,7 @@ use crate::{
     counters,
     dag::DAGNetworkMessage,
     logging::LogEvent,
+    message_recorder::MessageRecorder,
     monitor,
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     quorum_store::types::{Batch, BatchMsg, BatchRequest},
@@ -43,6 +44,7 @@ use futures::{
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     mem::{discriminant, Discriminant},
+    sync::Arc,
     time::Duration,
 };
 
@@ -275,6 +277,12 @@ impl NetworkSender {
         self.broadcast(msg).await
     }
 
+    pub async fn send_sync_info(&self, sync_info: SyncInfo, recipient: Author) {
+        fail_point!("consensus::send::sync_info", |_| ());
+        let msg = ConsensusMsg::SyncInfo(Box::new(sync_info));
+        self.send(msg, vec![recipient]).await
+    }
+
     pub async fn broadcast_timeout_vote(&mut self, timeout_vote_msg: VoteMsg) {
         fail_point!("consensus::send::broadcast_timeout_vote", |_| ());
         let msg = ConsensusMsg::VoteMsg(Box::new(timeout_vote_msg));
@@ -339,6 +347,11 @@ impl NetworkSender {
         self.author
     }
 
+    /// Reports verified equivocation evidence for the given author
+    pub fn report_equivocation(&self, author: Author) {
+        self.consensus_network_client.report_equivocation(author);
+    }
+
     pub async fn broadcast_commit_proof(&mut self, ledger_info: LedgerInfoWithSignatures) {
         fail_point!("consensus::send::broadcast_commit_proof", |_| ());
         let msg = ConsensusMsg::CommitDecisionMsg(Box::new(CommitDecision::new(ledger_info)));
@@ -419,6 +432,7 @@ pub struct NetworkTask {
     >,
     rpc_tx: aptos_channel::Sender<AccountAddress, (AccountAddress, IncomingRpcRequest)>,
     all_events: Box<dyn Stream<Item = Event<ConsensusMsg>> + Send + Unpin>,
+    message_recorder: Option<Arc<MessageRecorder>>,
 }
 
 impl NetworkTask {
@@ -466,6 +480,7 @@ impl NetworkTask {
                 quorum_store_messages_tx,
                 rpc_tx,
                 all_events,
+                message_recorder: None,
             },
             NetworkReceivers {
                 consensus_messages,
@@ -476,6 +491,12 @@ impl NetworkTask {
         )
     }
 
+    /// Records the inbound direct-send messages with the given recorder, while it is recording.
+    pub fn with_message_recorder(mut self, message_recorder: Arc<MessageRecorder>) -> Self {
+        self.message_recorder = Some(message_recorder);
+        self
+    }
+
     fn push_msg(
         peer_id: AccountAddress,
         msg: ConsensusMsg,
@@ -499,6 +520,9 @@ impl NetworkTask {
                     counters::CONSENSUS_RECEIVED_MSGS
                         .with_label_values(&[msg.name()])
                         .inc();
+                    if let Some(message_recorder) = &self.message_recorder {
+                        message_recorder.record(peer_id, &msg);
+                    }
                     match msg {
                         ConsensusMsg::BatchRequestMsg(_) | ConsensusMsg::BatchResponse(_) => {
                             warn!("unexpected rpc msg");
@@ -19,7 +19,7 @@ use aptos_consensus_types::{
     vote_msg::VoteMsg,
 };
 use aptos_network::{
-    application::{error::Error, interface::NetworkClientInterface},
+    application::{error::Error, interface::NetworkClientInterface, reputation::PeerMisbehavior},
     ProtocolId,
 };
 use aptos_types::{epoch_change::EpochChangeProof, PeerId};
@@ -108,6 +108,7 @@ pub struct ConsensusNetworkClient<NetworkClient> {
 
 /// Supported protocols in preferred order (from highest priority to lowest).
 pub const RPC: &[ProtocolId] = &[
+    ProtocolId::ConsensusRpcZstd,
     ProtocolId::ConsensusRpcCompressed,
     ProtocolId::ConsensusRpcBcs,
     ProtocolId::ConsensusRpcJson,
@@ -115,6 +116,7 @@ pub const RPC: &[ProtocolId] = &[
 
 /// Supported protocols in preferred order (from highest priority to lowest).
 pub const DIRECT_SEND: &[ProtocolId] = &[
+    ProtocolId::ConsensusDirectSendZstd,
     ProtocolId::ConsensusDirectSendCompressed,
     ProtocolId::ConsensusDirectSendBcs,
     ProtocolId::ConsensusDirectSendJson,
@@ -158,6 +160,15 @@ impl<NetworkClient: NetworkClientInterface<ConsensusMsg>> ConsensusNetworkClient
             .await
     }
 
+    /// Reports verified equivocation evidence for the given peer. This will
+    /// result in the peer being temporarily banned by the network.
+    pub fn report_equivocation(&self, peer: PeerId) {
+        let peer_network_id = self.get_peer_network_id_for_peer(peer);
+        self.network_client
+            .get_peers_and_metadata()
+            .report_peer_misbehavior(peer_network_id, PeerMisbehavior::Equivocation);
+    }
+
     // TODO: we shouldn't need to expose this. Migrate the code to handle
     // peer and network ids.
     fn get_peer_network_id_for_peer(&self, peer: PeerId) -> PeerNetworkId {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_observer::network_message::BlockPayload,
     counters,
     network::NetworkSender,
     quorum_store::{
@@ -11,22 +12,26 @@ use crate::{
 };
 use aptos_consensus_types::{
     block::Block,
-    common::{DataStatus, Payload},
+    common::{DataStatus, Payload, Round},
     proof_of_store::ProofOfStore,
 };
 use aptos_crypto::HashValue;
 use aptos_executor_types::{Error::DataNotFound, *};
+use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_types::transaction::SignedTransaction;
 use futures::{channel::mpsc::Sender, SinkExt};
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use tokio::sync::oneshot;
 
 /// Responsible to extract the transactions out of the payload and notify QuorumStore about commits.
 /// If QuorumStore is enabled, has to ask BatchReader for the transaction behind the proofs of availability in the payload.
+/// If the node is a consensus observer, the transactions are published alongside the ordered
+/// blocks, so they are read from the block payloads (indexed by epoch and round).
 pub enum PayloadManager {
     DirectMempool,
     InQuorumStore(Arc<BatchStore<NetworkSender>>, Sender<CoordinatorCommand>),
+    ConsensusObserver(Arc<Mutex<BTreeMap<(u64, Round), BlockPayload>>>),
 }
 
 impl PayloadManager {
@@ -58,7 +63,7 @@ impl PayloadManager {
     ///Pass commit information to BatchReader and QuorumStore wrapper for their internal cleanups.
     pub async fn notify_commit(&self, block_timestamp: u64, payloads: Vec<Payload>) {
         match self {
-            PayloadManager::DirectMempool => {},
+            PayloadManager::DirectMempool | PayloadManager::ConsensusObserver(_) => {},
             PayloadManager::InQuorumStore(batch_store, coordinator_tx) => {
                 // TODO: move this to somewhere in quorum store, so this can be a batch reader
                 batch_store
@@ -101,7 +106,7 @@ impl PayloadManager {
             None => return,
         };
         match self {
-            PayloadManager::DirectMempool => {},
+            PayloadManager::DirectMempool | PayloadManager::ConsensusObserver(_) => {},
             PayloadManager::InQuorumStore(batch_store, _) => match payload {
                 Payload::InQuorumStore(proof_with_status) => {
                     if proof_with_status.status.lock().is_none() {
@@ -124,6 +129,34 @@ impl PayloadManager {
         }
     }
 
+    /// Fetches the transactions of the given block, so that they can be published to
+    /// consensus observers. Unlike `get_transactions`, this doesn't touch the (cached)
+    /// data status of the payload, so it's safe to call while the block is executed.
+    pub async fn get_transactions_for_publishing(
+        &self,
+        block: &Block,
+    ) -> Result<Vec<SignedTransaction>, Error> {
+        match (self, block.payload()) {
+            (
+                PayloadManager::InQuorumStore(batch_store, _),
+                Some(Payload::InQuorumStore(proof_with_data)),
+            ) => {
+                let receivers = PayloadManager::request_transactions(
+                    proof_with_data.proofs.clone(),
+                    block.timestamp_usecs(),
+                    batch_store,
+                )
+                .await;
+                let mut transactions = Vec::new();
+                for (digest, rx) in receivers {
+                    transactions.extend(rx.await.map_err(|_| DataNotFound(digest))??);
+                }
+                Ok(transactions)
+            },
+            _ => self.get_transactions(block).await,
+        }
+    }
+
     /// Extract transaction from a given block
     /// Assumes it is never called for the same block concurrently. Otherwise status can be None.
     pub async fn get_transactions(&self, block: &Block) -> Result<Vec<SignedTransaction>, Error> {
@@ -133,7 +166,21 @@ impl PayloadManager {
         };
 
         match (self, payload) {
-            (PayloadManager::DirectMempool, Payload::DirectMempool(txns)) => Ok(txns.clone()),
+            (PayloadManager::DirectMempool, Payload::DirectMempool(txns))
+            | (PayloadManager::ConsensusObserver(_), Payload::DirectMempool(txns)) => {
+                Ok(txns.clone())
+            },
+            (PayloadManager::ConsensusObserver(block_payloads), Payload::InQuorumStore(_)) => {
+                match block_payloads.lock().get(&(block.epoch(), block.round())) {
+                    Some(block_payload) => block_payload
+                        .transactions_for_block(block)
+                        .map(|transactions| transactions.to_vec())
+                        .map_err(|error| Error::InternalError {
+                            error: error.to_string(),
+                        }),
+                    None => Err(DataNotFound(block.id())),
+                }
+            },
             (
                 PayloadManager::InQuorumStore(batch_store, _),
                 Payload::InQuorumStore(proof_with_data),
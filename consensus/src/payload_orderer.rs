@@ -0,0 +1,222 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters::TXN_ORDER_SECONDS;
+use aptos_crypto::HashValue;
+use aptos_logger::info;
+use aptos_types::{on_chain_config::PayloadOrdererType, transaction::SignedTransaction};
+use move_core_types::account_address::AccountAddress;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    sync::Arc,
+};
+
+/// Interface to order the transactions of a block, after they are pulled from quorum store
+/// (and deduped). The shuffler is only enabled with the pulled order, as it would undo any
+/// other ordering. Every validator orders the transactions of a block independently, so the
+/// ordering must be deterministic.
+pub trait PayloadOrderer: Send + Sync {
+    fn order(&self, block_id: HashValue, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction>;
+}
+
+/// Keeps the order in which the transactions were pulled, to maintain backward compatibility
+struct FairFifoOrderer {}
+
+impl PayloadOrderer for FairFifoOrderer {
+    fn order(&self, _block_id: HashValue, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction> {
+        txns
+    }
+}
+
+/// Orders the transactions by gas unit price (descending). The transactions of each sender are
+/// kept in sequence number order, so a sender's transaction can only be placed once all of the
+/// sender's transactions with lower sequence numbers are. Ties are broken by the pulled order.
+struct GasPriceDescendingOrderer {}
+
+impl PayloadOrderer for GasPriceDescendingOrderer {
+    fn order(&self, _block_id: HashValue, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction> {
+        let _timer = TXN_ORDER_SECONDS.start_timer();
+
+        let num_txns = txns.len();
+        let mut txns_by_sender: HashMap<AccountAddress, Vec<(usize, SignedTransaction)>> =
+            HashMap::new();
+        for (index, txn) in txns.into_iter().enumerate() {
+            txns_by_sender
+                .entry(txn.sender())
+                .or_default()
+                .push((index, txn));
+        }
+
+        // The next transaction of each sender competes on gas unit price, then on pulled order
+        let mut heap = BinaryHeap::new();
+        let mut queues = HashMap::new();
+        for (sender, mut sender_txns) in txns_by_sender {
+            sender_txns.sort_by_key(|(_, txn)| txn.sequence_number());
+            let mut queue = VecDeque::from(sender_txns);
+            if let Some((index, txn)) = queue.front() {
+                heap.push((txn.gas_unit_price(), Reverse(*index), sender));
+            }
+            queues.insert(sender, queue);
+        }
+
+        let mut ordered_txns = Vec::with_capacity(num_txns);
+        while let Some((_, _, sender)) = heap.pop() {
+            let queue = queues.get_mut(&sender).expect("sender must have a queue");
+            let (_, txn) = queue.pop_front().expect("queue must not be empty");
+            ordered_txns.push(txn);
+            if let Some((index, txn)) = queue.front() {
+                heap.push((txn.gas_unit_price(), Reverse(*index), sender));
+            }
+        }
+        ordered_txns
+    }
+}
+
+/// Orders the senders pseudo-randomly, using the block id as the seed, so that the proposer
+/// cannot choose the position of a sender's transactions in the block. The transactions of
+/// each sender are kept together, in sequence number order.
+struct SenderEntropyShuffleOrderer {}
+
+impl PayloadOrderer for SenderEntropyShuffleOrderer {
+    fn order(
+        &self,
+        block_id: HashValue,
+        mut txns: Vec<SignedTransaction>,
+    ) -> Vec<SignedTransaction> {
+        let _timer = TXN_ORDER_SECONDS.start_timer();
+
+        let mut sender_keys = HashMap::new();
+        for txn in &txns {
+            sender_keys.entry(txn.sender()).or_insert_with(|| {
+                HashValue::sha3_256_of(&[block_id.as_ref(), txn.sender().as_ref()].concat())
+            });
+        }
+        // The sort is stable, so transactions with the same sequence number keep their order
+        txns.sort_by_key(|txn| (sender_keys[&txn.sender()], txn.sequence_number()));
+        txns
+    }
+}
+
+pub fn create_payload_orderer(orderer_type: PayloadOrdererType) -> Arc<dyn PayloadOrderer> {
+    match orderer_type {
+        PayloadOrdererType::FairFifo => Arc::new(FairFifoOrderer {}),
+        PayloadOrdererType::GasPriceDescending => {
+            info!("Using gas price descending payload ordering");
+            Arc::new(GasPriceDescendingOrderer {})
+        },
+        PayloadOrdererType::SenderEntropyShuffle => {
+            info!("Using sender entropy shuffle payload ordering");
+            Arc::new(SenderEntropyShuffleOrderer {})
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::payload_orderer::create_payload_orderer;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, SigningKey, Uniform};
+    use aptos_types::{
+        chain_id::ChainId,
+        on_chain_config::PayloadOrdererType,
+        transaction::{RawTransaction, Script, SignedTransaction, TransactionPayload},
+    };
+    use move_core_types::account_address::AccountAddress;
+    use std::collections::HashMap;
+
+    fn create_signed_transaction(
+        sender: AccountAddress,
+        sequence_number: u64,
+        gas_unit_price: u64,
+    ) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_transaction = RawTransaction::new(
+            sender,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            gas_unit_price,
+            0,
+            ChainId::new(10),
+        );
+        SignedTransaction::new(
+            raw_transaction.clone(),
+            private_key.public_key(),
+            private_key.sign(&raw_transaction).unwrap(),
+        )
+    }
+
+    fn summaries(txns: &[SignedTransaction]) -> Vec<(AccountAddress, u64)> {
+        txns.iter()
+            .map(|txn| (txn.sender(), txn.sequence_number()))
+            .collect()
+    }
+
+    fn assert_sender_order(txns: &[SignedTransaction]) {
+        let mut last_sequence_numbers = HashMap::new();
+        for txn in txns {
+            if let Some(last) = last_sequence_numbers.insert(txn.sender(), txn.sequence_number()) {
+                assert!(last < txn.sequence_number());
+            }
+        }
+    }
+
+    #[test]
+    fn test_fair_fifo() {
+        let txns: Vec<_> = (0..10)
+            .map(|i| create_signed_transaction(AccountAddress::random(), 0, i))
+            .collect();
+        let orderer = create_payload_orderer(PayloadOrdererType::FairFifo);
+        assert_eq!(orderer.order(HashValue::random(), txns.clone()), txns);
+    }
+
+    #[test]
+    fn test_gas_price_descending() {
+        let (alice, bob, carol) = (
+            AccountAddress::random(),
+            AccountAddress::random(),
+            AccountAddress::random(),
+        );
+        let txns = vec![
+            create_signed_transaction(alice, 0, 1),
+            create_signed_transaction(bob, 0, 5),
+            // Pays the most, but can't be placed before alice's sequence number 0
+            create_signed_transaction(alice, 1, 10),
+            create_signed_transaction(carol, 0, 5),
+            create_signed_transaction(bob, 1, 3),
+        ];
+        let orderer = create_payload_orderer(PayloadOrdererType::GasPriceDescending);
+        let ordered_txns = orderer.order(HashValue::random(), txns);
+        assert_eq!(summaries(&ordered_txns), vec![
+            (bob, 0),
+            (carol, 0),
+            (bob, 1),
+            (alice, 0),
+            (alice, 1),
+        ]);
+    }
+
+    #[test]
+    fn test_sender_entropy_shuffle() {
+        let senders: Vec<_> = (0..20).map(|_| AccountAddress::random()).collect();
+        let mut txns = vec![];
+        for sequence_number in 0..5 {
+            for sender in &senders {
+                txns.push(create_signed_transaction(*sender, sequence_number, 1));
+            }
+        }
+
+        let orderer = create_payload_orderer(PayloadOrdererType::SenderEntropyShuffle);
+        let block_id = HashValue::random();
+        let ordered_txns = orderer.order(block_id, txns.clone());
+        assert_eq!(ordered_txns.len(), txns.len());
+        assert_sender_order(&ordered_txns);
+
+        // The ordering is deterministic for a block, and differs across blocks
+        assert_eq!(orderer.order(block_id, txns.clone()), ordered_txns);
+        assert_ne!(
+            summaries(&orderer.order(HashValue::random(), txns)),
+            summaries(&ordered_txns)
+        );
+    }
+}
@@ -51,6 +51,12 @@ pub trait PersistentLivenessStorage: Send + Sync {
 
     /// Returns a handle of the aptosdb.
     fn aptos_db(&self) -> Arc<dyn DbReader>;
+
+    /// Archive the commit certificate of a committed block for external audits. This is a
+    /// no-op unless the certificate archive is enabled.
+    fn archive_commit_certificate(&self, _commit_cert: &LedgerInfoWithSignatures) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -309,12 +315,21 @@ impl RecoveryData {
 pub struct StorageWriteProxy {
     db: Arc<ConsensusDB>,
     aptos_db: Arc<dyn DbReader>,
+    enable_certificate_archive: bool,
+    certificate_archive_retention_epochs: u64,
 }
 
 impl StorageWriteProxy {
     pub fn new(config: &NodeConfig, aptos_db: Arc<dyn DbReader>) -> Self {
         let db = Arc::new(ConsensusDB::new(config.storage.dir()));
-        StorageWriteProxy { db, aptos_db }
+        StorageWriteProxy {
+            db,
+            aptos_db,
+            enable_certificate_archive: config.consensus.enable_certificate_archive,
+            certificate_archive_retention_epochs: config
+                .consensus
+                .certificate_archive_retention_epochs,
+        }
     }
 }
 
@@ -427,6 +442,9 @@ impl PersistentLivenessStorage for StorageWriteProxy {
         &self,
         highest_timeout_cert: &TwoChainTimeoutCertificate,
     ) -> Result<()> {
+        if self.enable_certificate_archive {
+            self.db.archive_timeout_certificate(highest_timeout_cert)?;
+        }
         Ok(self
             .db
             .save_highest_2chain_timeout_certificate(bcs::to_bytes(highest_timeout_cert)?)?)
@@ -444,4 +462,20 @@ impl PersistentLivenessStorage for StorageWriteProxy {
     fn aptos_db(&self) -> Arc<dyn DbReader> {
         self.aptos_db.clone()
     }
+
+    fn archive_commit_certificate(&self, commit_cert: &LedgerInfoWithSignatures) -> Result<()> {
+        if !self.enable_certificate_archive {
+            return Ok(());
+        }
+        self.db.archive_commit_certificate(commit_cert)?;
+
+        // Once an epoch ends, the epochs that fell out of the retention window are pruned
+        let ledger_info = commit_cert.ledger_info();
+        if ledger_info.ends_epoch() && self.certificate_archive_retention_epochs > 0 {
+            let min_epoch =
+                (ledger_info.epoch() + 1).saturating_sub(self.certificate_archive_retention_epochs);
+            self.db.prune_certificate_archive(min_epoch)?;
+        }
+        Ok(())
+    }
 }
@@ -4,16 +4,21 @@
 use crate::{
     network::{NetworkSender, QuorumStoreSender},
     quorum_store::{
+        batch_generator::{BatchGeneratorCommand, RemoteBatch},
         batch_store::BatchStore,
         counters,
         types::{Batch, PersistedValue},
     },
 };
 use anyhow::ensure;
+use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_logger::prelude::*;
 use aptos_types::PeerId;
-use std::sync::Arc;
-use tokio::sync::{mpsc::Receiver, oneshot};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::{
+    mpsc::{Receiver, Sender},
+    oneshot,
+};
 
 #[derive(Debug)]
 pub enum BatchCoordinatorCommand {
@@ -29,6 +34,8 @@ pub struct BatchCoordinator {
     max_batch_bytes: u64,
     max_total_txns: u64,
     max_total_bytes: u64,
+    // Notified of the stored remote batches (if remote batch dedup is enabled)
+    batch_generator_cmd_tx: Option<Sender<BatchGeneratorCommand>>,
 }
 
 impl BatchCoordinator {
@@ -40,6 +47,7 @@ impl BatchCoordinator {
         max_batch_bytes: u64,
         max_total_txns: u64,
         max_total_bytes: u64,
+        batch_generator_cmd_tx: Option<Sender<BatchGeneratorCommand>>,
     ) -> Self {
         Self {
             my_peer_id,
@@ -49,6 +57,7 @@ impl BatchCoordinator {
             max_batch_bytes,
             max_total_txns,
             max_total_bytes,
+            batch_generator_cmd_tx,
         }
     }
 
@@ -82,13 +91,18 @@ impl BatchCoordinator {
         Some(batch.into())
     }
 
-    fn persist_and_send_digests(&self, persist_requests: Vec<PersistedValue>) {
+    fn persist_and_send_digests(
+        &self,
+        persist_requests: Vec<PersistedValue>,
+        remote_batches: Vec<RemoteBatch>,
+    ) {
         if persist_requests.is_empty() {
             return;
         }
 
         let batch_store = self.batch_store.clone();
         let network_sender = self.network_sender.clone();
+        let batch_generator_cmd_tx = self.batch_generator_cmd_tx.clone();
         let my_peer_id = self.my_peer_id;
         tokio::spawn(async move {
             let peer_id = persist_requests[0].author();
@@ -97,6 +111,25 @@ impl BatchCoordinator {
                 if my_peer_id != peer_id {
                     counters::RECEIVED_REMOTE_BATCHES_COUNT.inc_by(signed_batch_infos.len() as u64);
                 }
+
+                // Let the batch generator know about the remote batches we stored, so it can
+                // exclude their transactions once they are certified
+                if let Some(batch_generator_cmd_tx) = batch_generator_cmd_tx {
+                    let stored_digests: HashSet<_> = signed_batch_infos
+                        .iter()
+                        .map(|signed_batch_info| *signed_batch_info.digest())
+                        .collect();
+                    let remote_batches: Vec<_> = remote_batches
+                        .into_iter()
+                        .filter(|remote_batch| stored_digests.contains(&remote_batch.digest))
+                        .collect();
+                    if !remote_batches.is_empty() {
+                        let _ = batch_generator_cmd_tx
+                            .send(BatchGeneratorCommand::RemoteBatches(remote_batches))
+                            .await;
+                    }
+                }
+
                 network_sender
                     .send_signed_batch_info_msg(signed_batch_infos, vec![peer_id])
                     .await;
@@ -104,6 +137,30 @@ impl BatchCoordinator {
         });
     }
 
+    /// Returns the transactions of the given batch (if it was created by another validator
+    /// and remote batch dedup is enabled)
+    fn to_remote_batch(&self, batch: &Batch) -> Option<RemoteBatch> {
+        if self.batch_generator_cmd_tx.is_none() || batch.author() == self.my_peer_id {
+            return None;
+        }
+        let txns = batch
+            .txns()
+            .iter()
+            .map(|txn| TransactionInProgress {
+                summary: TransactionSummary {
+                    sender: txn.sender(),
+                    sequence_number: txn.sequence_number(),
+                },
+                gas_unit_price: txn.gas_unit_price(),
+            })
+            .collect();
+        Some(RemoteBatch {
+            digest: *batch.digest(),
+            expiration: batch.expiration(),
+            txns,
+        })
+    }
+
     fn ensure_max_limits(&self, batches: &[Batch]) -> anyhow::Result<()> {
         let mut total_txns = 0;
         let mut total_bytes = 0;
@@ -147,12 +204,15 @@ impl BatchCoordinator {
         }
 
         let mut persist_requests = vec![];
+        let mut remote_batches = vec![];
         for batch in batches.into_iter() {
+            let remote_batch = self.to_remote_batch(&batch);
             if let Some(persist_request) = self.handle_batch(batch).await {
                 persist_requests.push(persist_request);
+                remote_batches.extend(remote_batch);
             }
         }
-        self.persist_and_send_digests(persist_requests);
+        self.persist_and_send_digests(persist_requests, remote_batches);
     }
 
     pub(crate) async fn start(mut self, mut command_rx: Receiver<BatchCoordinatorCommand>) {
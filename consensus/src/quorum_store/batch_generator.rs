@@ -4,6 +4,7 @@ use crate::{
     monitor,
     network::{NetworkSender, QuorumStoreSender},
     quorum_store::{
+        batch_size_controller::BatchSizeController,
         counters,
         quorum_store_db::QuorumStoreStorage,
         types::Batch,
@@ -15,6 +16,7 @@ use aptos_consensus_types::{
     common::{TransactionInProgress, TransactionSummary},
     proof_of_store::BatchId,
 };
+use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_types::{transaction::SignedTransaction, PeerId};
@@ -30,9 +32,31 @@ use tokio::time::Interval;
 pub enum BatchGeneratorCommand {
     CommitNotification(u64),
     ProofExpiration(Vec<BatchId>),
+    // Time it took the batches to get a proof of store, since the last notification
+    ProofCompletion(Vec<Duration>),
+    // Batches created by other validators, which we stored and signed
+    RemoteBatches(Vec<RemoteBatch>),
+    // Digests of the batches that received a proof of store
+    RemoteProofs(Vec<HashValue>),
     Shutdown(tokio::sync::oneshot::Sender<()>),
 }
 
+/// The transactions of a batch created by another validator. Once the batch is certified,
+/// its transactions are excluded from our own batches (until the batch expires), as they
+/// will be committed through the remote batch anyway.
+#[derive(Debug)]
+pub struct RemoteBatch {
+    pub digest: HashValue,
+    pub expiration: u64,
+    pub txns: Vec<TransactionInProgress>,
+}
+
+/// The transactions of a remote batch, and whether the batch has been certified
+struct RemoteBatchTxns {
+    txns: Vec<TransactionInProgress>,
+    certified: bool,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct BackPressure {
     pub txn_count: bool,
@@ -48,10 +72,13 @@ pub struct BatchGenerator {
     mempool_proxy: MempoolProxy,
     batches_in_progress: HashMap<BatchId, Vec<TransactionInProgress>>,
     batch_expirations: TimeExpirations<BatchId>,
+    remote_batches: HashMap<HashValue, RemoteBatchTxns>,
+    remote_batch_expirations: TimeExpirations<HashValue>,
     latest_block_timestamp: u64,
     last_end_batch_time: Instant,
     // quorum store back pressure, get updated from proof manager
     back_pressure: BackPressure,
+    batch_size_controller: BatchSizeController,
 }
 
 impl BatchGenerator {
@@ -84,16 +111,19 @@ impl BatchGenerator {
             my_peer_id,
             batch_id,
             db,
-            config,
             mempool_proxy: MempoolProxy::new(mempool_tx, mempool_txn_pull_timeout_ms),
             batches_in_progress: HashMap::new(),
             batch_expirations: TimeExpirations::new(),
+            remote_batches: HashMap::new(),
+            remote_batch_expirations: TimeExpirations::new(),
             latest_block_timestamp: 0,
             last_end_batch_time: Instant::now(),
             back_pressure: BackPressure {
                 txn_count: false,
                 proof_count: false,
             },
+            batch_size_controller: BatchSizeController::new(&config),
+            config,
         }
     }
 
@@ -150,7 +180,8 @@ impl BatchGenerator {
             if batches.len() == self.config.sender_max_num_batches {
                 return false;
             }
-            let num_batch_txns = std::cmp::min(self.config.sender_max_batch_txns, remaining_txns);
+            let num_batch_txns =
+                std::cmp::min(self.batch_size_controller.batch_txns(), remaining_txns);
             let batch_txns: Vec<_> = txns.drain(0..num_batch_txns).collect();
             let batch = self.create_new_batch(batch_txns, expiry_time, bucket_start);
             batches.push(batch);
@@ -217,14 +248,53 @@ impl BatchGenerator {
         batches
     }
 
+    /// Records the transactions of batches created by other validators, until they expire
+    pub(crate) fn handle_remote_batches(&mut self, remote_batches: Vec<RemoteBatch>) {
+        for remote_batch in remote_batches {
+            if remote_batch.expiration <= self.latest_block_timestamp
+                || self.remote_batches.contains_key(&remote_batch.digest)
+            {
+                continue;
+            }
+            self.remote_batch_expirations
+                .add_item(remote_batch.digest, remote_batch.expiration);
+            self.remote_batches
+                .insert(remote_batch.digest, RemoteBatchTxns {
+                    txns: remote_batch.txns,
+                    certified: false,
+                });
+        }
+    }
+
+    /// Marks the given remote batches as certified, so that their transactions are excluded
+    /// from our own batches. Proofs of batches we don't know about are ignored.
+    pub(crate) fn handle_remote_proofs(&mut self, digests: Vec<HashValue>) {
+        for digest in digests {
+            if let Some(remote_batch) = self.remote_batches.get_mut(&digest) {
+                remote_batch.certified = true;
+            }
+        }
+    }
+
     pub(crate) async fn handle_scheduled_pull(&mut self, max_count: u64) -> Vec<Batch> {
-        let exclude_txns: Vec<_> = self
+        let mut exclude_txns: Vec<_> = self
             .batches_in_progress
             .values()
             .flatten()
             .cloned()
             .collect();
 
+        // Exclude the transactions already covered by certified remote batches
+        let num_own_exclude_txns = exclude_txns.len();
+        exclude_txns.extend(
+            self.remote_batches
+                .values()
+                .filter(|remote_batch| remote_batch.certified)
+                .flat_map(|remote_batch| remote_batch.txns.iter().cloned()),
+        );
+        counters::REMOTE_BATCH_DEDUP_EXCLUDED_TXNS
+            .observe((exclude_txns.len() - num_own_exclude_txns) as f64);
+
         trace!("QS: excluding txs len: {:?}", exclude_txns.len());
         let mut pulled_txns = self
             .mempool_proxy
@@ -237,6 +307,8 @@ impl BatchGenerator {
             .unwrap_or_default();
 
         trace!("QS: pulled_txns len: {:?}", pulled_txns.len());
+        self.batch_size_controller
+            .observe_pull(pulled_txns.len() as u64, max_count);
 
         if pulled_txns.is_empty() {
             counters::PULLED_EMPTY_TXNS_COUNT.inc();
@@ -254,7 +326,7 @@ impl BatchGenerator {
         counters::BATCH_CREATION_DURATION.observe_duration(self.last_end_batch_time.elapsed());
 
         let bucket_compute_start = Instant::now();
-        let expiry_time = aptos_infallible::duration_since_epoch().as_micros() as u64
+        let expiry_time = aptos_consensus_types::clock::current_timestamp().as_micros() as u64
             + self.config.batch_expiry_gap_when_init_usecs;
         let batches = self.bucket_into_batches(&mut pulled_txns, expiry_time);
         counters::BATCH_CREATION_COMPUTE_LATENCY.observe_duration(bucket_compute_start.elapsed());
@@ -294,6 +366,7 @@ impl BatchGenerator {
                 _ = interval.tick() => monitor!("batch_generator_handle_tick", {
 
                     let now = Instant::now();
+                    self.batch_size_controller.maybe_adjust(now);
                     // TODO: refactor back_pressure logic into its own function
                     if self.back_pressure.txn_count {
                         // multiplicative decrease, every second
@@ -330,7 +403,7 @@ impl BatchGenerator {
                         self.config.batch_generation_max_interval_ms as u128
                     ) as usize;
                     if (!self.back_pressure.proof_count
-                        && since_last_non_empty_pull_ms >= self.config.batch_generation_min_non_empty_interval_ms)
+                        && since_last_non_empty_pull_ms >= self.batch_size_controller.min_non_empty_interval_ms())
                         || since_last_non_empty_pull_ms == self.config.batch_generation_max_interval_ms {
 
                         let dynamic_pull_max_txn = std::cmp::max(
@@ -365,8 +438,12 @@ impl BatchGenerator {
                                     );
                                 }
                             }
+                            for digest in self.remote_batch_expirations.expire(block_timestamp) {
+                                self.remote_batches.remove(&digest);
+                            }
                         },
                         BatchGeneratorCommand::ProofExpiration(batch_ids) => {
+                            self.batch_size_controller.observe_expired_batches(batch_ids.len());
                             for batch_id in batch_ids {
                                 debug!(
                                     "QS: received timeout for proof of store, batch id = {}",
@@ -375,7 +452,16 @@ impl BatchGenerator {
                                 // Not able to gather the proof, allow transactions to be polled again.
                                 self.batches_in_progress.remove(&batch_id);
                             }
-                        }
+                        },
+                        BatchGeneratorCommand::ProofCompletion(latencies) => {
+                            self.batch_size_controller.observe_proof_latencies(latencies);
+                        },
+                        BatchGeneratorCommand::RemoteBatches(remote_batches) => {
+                            self.handle_remote_batches(remote_batches);
+                        },
+                        BatchGeneratorCommand::RemoteProofs(digests) => {
+                            self.handle_remote_proofs(digests);
+                        },
                         BatchGeneratorCommand::Shutdown(ack_tx) => {
                             ack_tx
                                 .send(())
@@ -0,0 +1,169 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::counters;
+use aptos_config::config::{QuorumStoreBatchSizeControllerConfig, QuorumStoreConfig};
+use aptos_logger::prelude::*;
+use std::{
+    cmp::{max, min},
+    time::{Duration, Instant},
+};
+
+/// Adapts the size of the batches created by the batch generator, and the interval between
+/// them, to the observed load:
+/// - if mempool has more transactions than we pull (backlog) and batches get their proofs of
+///   store in time, batches grow additively and are created more often.
+/// - if batches take too long to get a proof of store, or too many of them don't get enough
+///   signatures before expiring (congestion), batches shrink multiplicatively and are created
+///   less often, to put less pressure on the network and the other validators.
+///
+/// Without the controller (or while it is disabled), the static limits from the config are used.
+pub(crate) struct BatchSizeController {
+    config: QuorumStoreBatchSizeControllerConfig,
+    max_batch_txns: usize,
+    max_interval_ms: usize,
+    batch_txns: usize,
+    interval_ms: usize,
+    // Signals observed since the last adjustment
+    pulls_with_backlog: usize,
+    proof_latencies: Vec<Duration>,
+    expired_batches: usize,
+    last_adjustment: Instant,
+}
+
+impl BatchSizeController {
+    pub fn new(config: &QuorumStoreConfig) -> Self {
+        let controller_config = config.batch_size_controller;
+        let max_interval_ms = config.batch_generation_max_interval_ms;
+        let controller = Self {
+            config: controller_config,
+            max_batch_txns: config.sender_max_batch_txns,
+            max_interval_ms,
+            batch_txns: config.sender_max_batch_txns,
+            interval_ms: min(
+                config.batch_generation_min_non_empty_interval_ms,
+                max_interval_ms,
+            ),
+            pulls_with_backlog: 0,
+            proof_latencies: vec![],
+            expired_batches: 0,
+            last_adjustment: Instant::now(),
+        };
+        controller.update_metrics(false, false);
+        controller
+    }
+
+    /// Max number of transactions in a single batch
+    pub fn batch_txns(&self) -> usize {
+        self.batch_txns
+    }
+
+    /// Min interval between two non-empty batch pulls from mempool
+    pub fn min_non_empty_interval_ms(&self) -> usize {
+        self.interval_ms
+    }
+
+    /// Records a pull from mempool. Getting as many transactions as requested means mempool
+    /// has a backlog.
+    pub fn observe_pull(&mut self, num_pulled_txns: u64, max_txns: u64) {
+        if num_pulled_txns >= max_txns {
+            self.pulls_with_backlog += 1;
+        }
+    }
+
+    /// Records the time it took our batches to get a proof of store
+    pub fn observe_proof_latencies(&mut self, latencies: Vec<Duration>) {
+        self.proof_latencies.extend(latencies);
+    }
+
+    /// Records batches that expired before getting enough signatures
+    pub fn observe_expired_batches(&mut self, num_batches: usize) {
+        self.expired_batches += num_batches;
+    }
+
+    /// Adjusts the batch size and interval, if the adjustment interval has passed since the last
+    /// adjustment.
+    pub fn maybe_adjust(&mut self, now: Instant) {
+        if !self.config.enabled
+            || now.duration_since(self.last_adjustment)
+                < Duration::from_millis(self.config.adjustment_interval_ms)
+        {
+            return;
+        }
+        self.last_adjustment = now;
+
+        let congested = self.is_congested();
+        let backlog = self.pulls_with_backlog > 0;
+        if congested {
+            self.batch_txns = max(
+                (self.batch_txns as f64 * self.config.decrease_fraction) as usize,
+                self.config.min_batch_txns,
+            );
+            self.interval_ms = min(
+                (self.interval_ms as f64 / self.config.decrease_fraction) as usize,
+                self.max_interval_ms,
+            );
+        } else if backlog {
+            self.batch_txns = min(
+                self.batch_txns + self.config.batch_txns_increase_step,
+                self.max_batch_txns,
+            );
+            self.interval_ms = max(
+                self.interval_ms
+                    .saturating_sub(self.config.interval_decrease_step_ms),
+                self.config.min_batch_generation_interval_ms,
+            );
+        }
+        trace!(
+            "QS: batch size controller, congested: {}, backlog: {}, batch_txns: {}, interval_ms: {}",
+            congested,
+            backlog,
+            self.batch_txns,
+            self.interval_ms
+        );
+        self.update_metrics(congested, backlog);
+
+        self.pulls_with_backlog = 0;
+        self.proof_latencies.clear();
+        self.expired_batches = 0;
+    }
+
+    fn is_congested(&self) -> bool {
+        let num_completed = self.proof_latencies.len();
+        let num_batches = num_completed + self.expired_batches;
+        if num_batches == 0 {
+            return false;
+        }
+        if self.expired_batches as f64 / num_batches as f64 > self.config.max_expired_batch_fraction
+        {
+            return true;
+        }
+        num_completed > 0
+            && self.average_proof_latency()
+                > Duration::from_millis(self.config.target_batch_to_proof_latency_ms)
+    }
+
+    fn average_proof_latency(&self) -> Duration {
+        if self.proof_latencies.is_empty() {
+            return Duration::ZERO;
+        }
+        self.proof_latencies.iter().sum::<Duration>() / self.proof_latencies.len() as u32
+    }
+
+    fn update_metrics(&self, congested: bool, backlog: bool) {
+        counters::BATCH_SIZE_CONTROLLER_BATCH_TXNS.set(self.batch_txns as i64);
+        counters::BATCH_SIZE_CONTROLLER_INTERVAL_MS.set(self.interval_ms as i64);
+        counters::BATCH_SIZE_CONTROLLER_SIGNALS
+            .with_label_values(&["congested"])
+            .set(congested as i64);
+        counters::BATCH_SIZE_CONTROLLER_SIGNALS
+            .with_label_values(&["mempool_backlog"])
+            .set(backlog as i64);
+        counters::BATCH_SIZE_CONTROLLER_SIGNALS
+            .with_label_values(&["expired_batches"])
+            .set(self.expired_batches as i64);
+        counters::BATCH_SIZE_CONTROLLER_SIGNALS
+            .with_label_values(&["avg_proof_latency_ms"])
+            .set(self.average_proof_latency().as_millis() as i64);
+    }
+}
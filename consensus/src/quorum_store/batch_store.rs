@@ -28,13 +28,38 @@ use fail::fail_point;
 use once_cell::sync::OnceCell;
 use std::{
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc, Mutex,
     },
     time::Duration,
 };
 use tokio::sync::oneshot;
 
+// Whether batch payloads may be cached in memory. Caching is paused (i.e., new
+// batches are only persisted) while the node sheds memory.
+static BATCH_MEMORY_CACHING_ENABLED: AtomicBool = AtomicBool::new(true);
+
+// The bytes of the batch payloads cached in memory (across all quota managers)
+static BATCH_MEMORY_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Pauses (or resumes) the caching of batch payloads in memory. While caching
+/// is paused, new batches are only persisted, and their payloads are read from
+/// the db on demand. Batches that are already cached stay in memory.
+pub fn set_batch_memory_caching(enabled: bool) {
+    BATCH_MEMORY_CACHING_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns the bytes of the batch payloads that are cached in memory
+pub fn batch_memory_bytes() -> usize {
+    BATCH_MEMORY_BYTES.load(Ordering::Relaxed)
+}
+
+fn release_batch_memory(num_bytes: usize) {
+    let _ = BATCH_MEMORY_BYTES.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bytes| {
+        Some(bytes.saturating_sub(num_bytes))
+    });
+}
+
 // Pub(crate) for testing only.
 pub(crate) struct QuotaManager {
     memory_balance: usize,
@@ -69,8 +94,11 @@ impl QuotaManager {
             self.batch_balance -= 1;
             self.db_balance -= num_bytes;
 
-            if self.memory_balance >= num_bytes {
+            if self.memory_balance >= num_bytes
+                && BATCH_MEMORY_CACHING_ENABLED.load(Ordering::Relaxed)
+            {
                 self.memory_balance -= num_bytes;
+                BATCH_MEMORY_BYTES.fetch_add(num_bytes, Ordering::Relaxed);
                 Ok(StorageMode::MemoryAndPersisted)
             } else {
                 Ok(StorageMode::PersistedOnly)
@@ -102,10 +130,18 @@ impl QuotaManager {
         if matches!(storage_mode, StorageMode::MemoryAndPersisted) {
             Self::assert_quota(self.memory_balance, num_bytes, self.memory_quota, "Memory");
             self.memory_balance += num_bytes;
+            release_batch_memory(num_bytes);
         }
     }
 }
 
+impl Drop for QuotaManager {
+    fn drop(&mut self) {
+        // The cached payloads are dropped alongside the batch store (e.g., on epoch change)
+        release_batch_memory(self.memory_quota - self.memory_balance);
+    }
+}
+
 /// Provides in memory representation of stored batches (strong cache), and allows
 /// efficient concurrent readers.
 pub struct BatchStore<T> {
@@ -3,8 +3,9 @@
 
 use aptos_metrics_core::{
     exponential_buckets, op_counters::DurationHistogram, register_avg_counter, register_histogram,
-    register_histogram_vec, register_int_counter, register_int_counter_vec, Histogram,
-    HistogramVec, IntCounter, IntCounterVec,
+    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 use std::time::Duration;
@@ -283,6 +284,16 @@ pub static PULLED_TXNS_NUM: Lazy<Histogram> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Histogram for the number of txns excluded from a pull because they are in certified remote batches.
+pub static REMOTE_BATCH_DEDUP_EXCLUDED_TXNS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "quorum_store_remote_batch_dedup_excluded_txns",
+        "Histogram for the number of txns excluded from a pull because they are in certified remote batches.",
+        TRANSACTION_COUNT_BUCKETS.clone()
+    )
+    .unwrap()
+});
+
 /// Count of the pulled empty txns.
 pub static PULLED_EMPTY_TXNS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -503,6 +514,34 @@ pub static QS_BACKPRESSURE_DYNAMIC_MAX: Lazy<Histogram> = Lazy::new(|| {
     )
 });
 
+/// Max number of txns in a batch, as set by the batch size controller.
+pub static BATCH_SIZE_CONTROLLER_BATCH_TXNS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "quorum_store_batch_size_controller_batch_txns",
+        "Max number of txns in a batch, as set by the batch size controller"
+    )
+    .unwrap()
+});
+
+/// Min interval between non-empty batch pulls, as set by the batch size controller.
+pub static BATCH_SIZE_CONTROLLER_INTERVAL_MS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "quorum_store_batch_size_controller_interval_ms",
+        "Min interval between non-empty batch pulls, as set by the batch size controller"
+    )
+    .unwrap()
+});
+
+/// Signals the batch size controller based its last adjustment on.
+pub static BATCH_SIZE_CONTROLLER_SIGNALS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "quorum_store_batch_size_controller_signals",
+        "Signals the batch size controller based its last adjustment on",
+        &["signal"]
+    )
+    .unwrap()
+});
+
 /// Latencies
 
 /// Histogram of the time durations for batch creation.
@@ -8,6 +8,7 @@ pub mod direct_mempool_quorum_store;
 pub(crate) mod batch_coordinator;
 pub(crate) mod batch_generator;
 pub(crate) mod batch_requester;
+pub(crate) mod batch_size_controller;
 pub(crate) mod batch_store;
 pub(crate) mod network_listener;
 pub(crate) mod proof_coordinator;
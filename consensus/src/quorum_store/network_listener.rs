@@ -4,8 +4,8 @@
 use crate::{
     monitor,
     quorum_store::{
-        batch_coordinator::BatchCoordinatorCommand, counters,
-        proof_coordinator::ProofCoordinatorCommand, proof_manager::ProofManagerCommand,
+        batch_coordinator::BatchCoordinatorCommand, batch_generator::BatchGeneratorCommand,
+        counters, proof_coordinator::ProofCoordinatorCommand, proof_manager::ProofManagerCommand,
     },
     round_manager::VerifiedEvent,
 };
@@ -20,6 +20,8 @@ pub(crate) struct NetworkListener {
     proof_coordinator_tx: Sender<ProofCoordinatorCommand>,
     remote_batch_coordinator_tx: Vec<Sender<BatchCoordinatorCommand>>,
     proof_manager_tx: Sender<ProofManagerCommand>,
+    // Notified of the received proofs (if remote batch dedup is enabled)
+    batch_generator_tx: Option<Sender<BatchGeneratorCommand>>,
 }
 
 impl NetworkListener {
@@ -28,12 +30,14 @@ impl NetworkListener {
         proof_coordinator_tx: Sender<ProofCoordinatorCommand>,
         remote_batch_coordinator_tx: Vec<Sender<BatchCoordinatorCommand>>,
         proof_manager_tx: Sender<ProofManagerCommand>,
+        batch_generator_tx: Option<Sender<BatchGeneratorCommand>>,
     ) -> Self {
         Self {
             network_msg_rx,
             proof_coordinator_tx,
             remote_batch_coordinator_tx,
             proof_manager_tx,
+            batch_generator_tx,
         }
     }
 
@@ -76,6 +80,17 @@ impl NetworkListener {
                             .expect("Could not send remote batch");
                     },
                     VerifiedEvent::ProofOfStoreMsg(proofs) => {
+                        if let Some(batch_generator_tx) = &self.batch_generator_tx {
+                            let digests = proofs
+                                .proofs()
+                                .iter()
+                                .map(|proof| *proof.digest())
+                                .collect();
+                            batch_generator_tx
+                                .send(BatchGeneratorCommand::RemoteProofs(digests))
+                                .await
+                                .expect("Could not send remote proofs to batch_generator");
+                        }
                         let cmd = ProofManagerCommand::ReceiveProofs(*proofs);
                         self.proof_manager_tx
                             .send(cmd)
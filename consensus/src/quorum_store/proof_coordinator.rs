@@ -135,6 +135,8 @@ pub(crate) struct ProofCoordinator {
     timeouts: Timeouts<BatchInfo>,
     batch_reader: Arc<dyn BatchReader>,
     batch_generator_cmd_tx: tokio::sync::mpsc::Sender<BatchGeneratorCommand>,
+    // time it took to get the proofs completed since the last expiration check
+    proof_latencies: Vec<Duration>,
 }
 
 //PoQS builder object - gather signed digest to form PoQS
@@ -153,6 +155,7 @@ impl ProofCoordinator {
             timeouts: Timeouts::new(),
             batch_reader,
             batch_generator_cmd_tx,
+            proof_latencies: vec![],
         }
     }
 
@@ -209,6 +212,7 @@ impl ProofCoordinator {
                         .remove(&digest)
                         .expect("Batch created without recording the time!");
                 counters::BATCH_TO_POS_DURATION.observe_duration(Duration::from_micros(duration));
+                self.proof_latencies.push(Duration::from_micros(duration));
                 return Ok(Some(proof));
             }
         }
@@ -247,6 +251,17 @@ impl ProofCoordinator {
         {
             warn!("Failed to send proof expiration to batch generator");
         }
+        if !self.proof_latencies.is_empty()
+            && self
+                .batch_generator_cmd_tx
+                .send(BatchGeneratorCommand::ProofCompletion(std::mem::take(
+                    &mut self.proof_latencies,
+                )))
+                .await
+                .is_err()
+        {
+            warn!("Failed to send proof completion to batch generator");
+        }
     }
 
     pub async fn start(
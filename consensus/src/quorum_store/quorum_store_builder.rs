@@ -24,7 +24,8 @@ use crate::{
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::config::{QuorumStoreConfig, SecureBackend};
 use aptos_consensus_types::{common::Author, request_response::GetPayloadCommand};
-use aptos_global_constants::CONSENSUS_KEY;
+use aptos_crypto::{bls12381, PrivateKey};
+use aptos_global_constants::{CONSENSUS_KEY, CONSENSUS_KEY_STANDBY};
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_secure_storage::{KVStorage, Storage};
@@ -215,10 +216,20 @@ impl InnerBuilder {
         if let Err(error) = storage.available() {
             panic!("Storage is not available: {:?}", error);
         }
-        let private_key = storage
+        let private_key: bls12381::PrivateKey = storage
             .get(CONSENSUS_KEY)
             .map(|v| v.value)
             .expect("Unable to get private key");
+        // Use the standby key instead if it has been activated on-chain
+        let private_key = match storage.get::<bls12381::PrivateKey>(CONSENSUS_KEY_STANDBY) {
+            Ok(standby_key)
+                if self.verifier.get_public_key(&self.author)
+                    == Some(standby_key.value.public_key()) =>
+            {
+                standby_key.value
+            },
+            _ => private_key,
+        };
         let signer = ValidatorSigner::new(self.author, private_key);
 
         let latest_ledger_info_with_sigs = self
@@ -308,6 +319,9 @@ impl InnerBuilder {
                 self.config.receiver_max_batch_bytes as u64,
                 self.config.receiver_max_total_txns as u64,
                 self.config.receiver_max_total_bytes as u64,
+                self.config
+                    .enable_remote_batch_dedup
+                    .then(|| self.batch_generator_cmd_tx.clone()),
             );
             #[allow(unused_variables)]
             let name = format!("batch_coordinator-{}", i);
@@ -357,6 +371,9 @@ impl InnerBuilder {
             self.proof_coordinator_cmd_tx.clone(),
             self.remote_batch_coordinator_cmd_tx.clone(),
             self.proof_manager_cmd_tx.clone(),
+            self.config
+                .enable_remote_batch_dedup
+                .then(|| self.batch_generator_cmd_tx.clone()),
         );
         spawn_named!("network_listener", net.start());
 
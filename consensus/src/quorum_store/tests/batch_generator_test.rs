@@ -3,7 +3,7 @@
 
 use crate::quorum_store::{
     batch_coordinator::BatchCoordinatorCommand,
-    batch_generator::BatchGenerator,
+    batch_generator::{BatchGenerator, RemoteBatch},
     quorum_store_db::MockQuorumStoreDB,
     tests::utils::{
         create_signed_transaction, create_vec_signed_transactions,
@@ -11,7 +11,11 @@ use crate::quorum_store::{
     },
 };
 use aptos_config::config::QuorumStoreConfig;
-use aptos_consensus_types::{common::TransactionInProgress, proof_of_store::BatchId};
+use aptos_consensus_types::{
+    common::{TransactionInProgress, TransactionSummary},
+    proof_of_store::BatchId,
+};
+use aptos_crypto::HashValue;
 use aptos_mempool::{QuorumStoreRequest, QuorumStoreResponse};
 use aptos_types::transaction::SignedTransaction;
 use futures::{
@@ -539,3 +543,67 @@ async fn test_sender_max_num_batches_multi_buckets() {
         .unwrap()
         .unwrap();
 }
+
+fn to_txns_in_progress(signed_txns: &[SignedTransaction]) -> Vec<TransactionInProgress> {
+    signed_txns
+        .iter()
+        .map(|txn| TransactionInProgress {
+            summary: TransactionSummary {
+                sender: txn.sender(),
+                sequence_number: txn.sequence_number(),
+            },
+            gas_unit_price: txn.gas_unit_price(),
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_remote_batch_dedup() {
+    let (quorum_store_to_mempool_tx, mut quorum_store_to_mempool_rx) = channel(1_024);
+
+    let config = QuorumStoreConfig::default();
+    let max_batch_bytes = config.sender_max_batch_bytes;
+
+    let mut batch_generator = BatchGenerator::new(
+        0,
+        AccountAddress::random(),
+        config,
+        Arc::new(MockQuorumStoreDB::new()),
+        quorum_store_to_mempool_tx,
+        1000,
+    );
+
+    let signed_txns = create_vec_signed_transactions(5);
+    let certified_txns = to_txns_in_progress(&signed_txns[0..3]);
+    let uncertified_txns = to_txns_in_progress(&signed_txns[3..]);
+    let certified_digest = HashValue::random();
+    batch_generator.handle_remote_batches(vec![
+        RemoteBatch {
+            digest: certified_digest,
+            expiration: u64::MAX,
+            txns: certified_txns.clone(),
+        },
+        RemoteBatch {
+            digest: HashValue::random(),
+            expiration: u64::MAX,
+            txns: uncertified_txns,
+        },
+    ]);
+    // Only the certified batch should be excluded, unknown proofs are ignored
+    batch_generator.handle_remote_proofs(vec![certified_digest, HashValue::random()]);
+
+    let join_handle = tokio::spawn(async move {
+        let exclude_txns =
+            queue_mempool_batch_response(vec![], max_batch_bytes, &mut quorum_store_to_mempool_rx)
+                .await;
+        assert_eq!(exclude_txns, certified_txns);
+    });
+
+    let result = batch_generator.handle_scheduled_pull(300).await;
+    assert!(result.is_empty());
+
+    timeout(Duration::from_millis(10_000), join_handle)
+        .await
+        .unwrap()
+        .unwrap();
+}
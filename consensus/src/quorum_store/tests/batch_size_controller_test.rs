@@ -0,0 +1,116 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::batch_size_controller::BatchSizeController;
+use aptos_config::config::{QuorumStoreBatchSizeControllerConfig, QuorumStoreConfig};
+use std::time::{Duration, Instant};
+
+fn create_controller(enabled: bool) -> (QuorumStoreConfig, BatchSizeController) {
+    let config = QuorumStoreConfig {
+        batch_size_controller: QuorumStoreBatchSizeControllerConfig {
+            enabled,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let controller = BatchSizeController::new(&config);
+    (config, controller)
+}
+
+fn next_adjustment(now: &mut Instant, config: &QuorumStoreConfig) -> Instant {
+    *now += Duration::from_millis(config.batch_size_controller.adjustment_interval_ms);
+    *now
+}
+
+#[test]
+fn test_disabled_controller_uses_static_config() {
+    let (config, mut controller) = create_controller(false);
+    let mut now = Instant::now();
+
+    controller.observe_expired_batches(10);
+    controller.maybe_adjust(next_adjustment(&mut now, &config));
+    assert_eq!(controller.batch_txns(), config.sender_max_batch_txns);
+    assert_eq!(
+        controller.min_non_empty_interval_ms(),
+        config.batch_generation_min_non_empty_interval_ms
+    );
+}
+
+#[test]
+fn test_congestion_shrinks_batches() {
+    let (config, mut controller) = create_controller(true);
+    let controller_config = config.batch_size_controller;
+    let mut now = Instant::now();
+
+    // Slow proofs of store
+    controller.observe_proof_latencies(vec![
+        Duration::from_millis(
+            controller_config.target_batch_to_proof_latency_ms * 2
+        );
+        10
+    ]);
+    controller.maybe_adjust(next_adjustment(&mut now, &config));
+    assert_eq!(
+        controller.batch_txns(),
+        (config.sender_max_batch_txns as f64 * controller_config.decrease_fraction) as usize
+    );
+    assert_eq!(
+        controller.min_non_empty_interval_ms(),
+        config.batch_generation_max_interval_ms
+    );
+
+    // Too many expired batches, even with a mempool backlog
+    for _ in 0..10 {
+        controller.observe_expired_batches(1);
+        controller.observe_pull(100, 100);
+        controller.maybe_adjust(next_adjustment(&mut now, &config));
+    }
+    assert_eq!(controller.batch_txns(), controller_config.min_batch_txns);
+    assert_eq!(
+        controller.min_non_empty_interval_ms(),
+        config.batch_generation_max_interval_ms
+    );
+}
+
+#[test]
+fn test_backlog_grows_batches() {
+    let (config, mut controller) = create_controller(true);
+    let controller_config = config.batch_size_controller;
+    let mut now = Instant::now();
+
+    controller.observe_expired_batches(1);
+    controller.maybe_adjust(next_adjustment(&mut now, &config));
+    let shrunk_batch_txns = controller.batch_txns();
+    assert!(shrunk_batch_txns < config.sender_max_batch_txns);
+
+    // Adjustments only happen once per interval
+    controller.observe_pull(100, 100);
+    controller.maybe_adjust(now);
+    assert_eq!(controller.batch_txns(), shrunk_batch_txns);
+
+    // Fast proofs of store with a backlog
+    controller.observe_proof_latencies(vec![Duration::from_millis(1); 10]);
+    controller.maybe_adjust(next_adjustment(&mut now, &config));
+    assert_eq!(
+        controller.batch_txns(),
+        shrunk_batch_txns + controller_config.batch_txns_increase_step
+    );
+
+    // No backlog, nothing changes
+    controller.observe_pull(10, 100);
+    controller.maybe_adjust(next_adjustment(&mut now, &config));
+    assert_eq!(
+        controller.batch_txns(),
+        shrunk_batch_txns + controller_config.batch_txns_increase_step
+    );
+
+    for _ in 0..100 {
+        controller.observe_pull(100, 100);
+        controller.maybe_adjust(next_adjustment(&mut now, &config));
+    }
+    assert_eq!(controller.batch_txns(), config.sender_max_batch_txns);
+    assert_eq!(
+        controller.min_non_empty_interval_ms(),
+        controller_config.min_batch_generation_interval_ms
+    );
+}
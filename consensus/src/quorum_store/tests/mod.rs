@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod batch_generator_test;
+mod batch_size_controller_test;
 mod batch_store_test;
 mod direct_mempool_quorum_store_test;
 mod proof_coordinator_test;
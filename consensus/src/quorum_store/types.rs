@@ -184,6 +184,10 @@ impl Batch {
         self.payload.txns
     }
 
+    pub fn txns(&self) -> &[SignedTransaction] {
+        &self.payload.txns
+    }
+
     pub fn batch_info(&self) -> &BatchInfo {
         &self.batch_info
     }
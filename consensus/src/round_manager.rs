@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    admin::{MineBlocksRequest, MinedBlock},
     block_storage::{
         tracing::{observe_block, BlockStage},
         BlockReader, BlockRetriever, BlockStore,
@@ -10,6 +11,7 @@ use crate::{
     counters,
     error::{error_kind, VerifyError},
     liveness::{
+        commit_gossip::CommitCertGossip,
         proposal_generator::ProposalGenerator,
         proposer_election::ProposerElection,
         round_state::{NewRoundEvent, NewRoundReason, RoundState, RoundStateLogSchema},
@@ -41,6 +43,7 @@ use aptos_consensus_types::{
 };
 use aptos_infallible::{checked, Mutex};
 use aptos_logger::prelude::*;
+use aptos_mempool::QuorumStoreRequest;
 #[cfg(test)]
 use aptos_safety_rules::ConsensusState;
 use aptos_safety_rules::TSafetyRules;
@@ -49,7 +52,10 @@ use aptos_types::{
     validator_verifier::ValidatorVerifier, PeerId,
 };
 use fail::fail_point;
-use futures::{channel::oneshot, FutureExt, StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    FutureExt, StreamExt,
+};
 use serde::Serialize;
 use std::{
     mem::{discriminant, Discriminant},
@@ -177,6 +183,7 @@ pub enum VerifiedEvent {
     ProofOfStoreMsg(Box<ProofOfStoreMsg>),
     // local messages
     LocalTimeout(Round),
+    LocalMineBlocksRequest(MineBlocksRequest),
     // Shutdown the NetworkListener
     Shutdown(TokioOneshot::Sender<()>),
 }
@@ -199,7 +206,10 @@ pub struct RoundManager {
     block_store: Arc<BlockStore>,
     round_state: RoundState,
     proposer_election: UnequivocalProposerElection,
+    commit_cert_gossip: CommitCertGossip,
     proposal_generator: ProposalGenerator,
+    // The mine blocks request whose blocks are being proposed (if any)
+    pending_mine_blocks_request: Option<MineBlocksRequest>,
     safety_rules: Arc<Mutex<MetricsSafetyRules>>,
     network: NetworkSender,
     storage: Arc<dyn PersistentLivenessStorage>,
@@ -207,6 +217,10 @@ pub struct RoundManager {
     round_manager_tx:
         aptos_channel::Sender<(Author, Discriminant<VerifiedEvent>), (Author, VerifiedEvent)>,
     local_config: ConsensusConfig,
+    // Used to notify mempool of the upcoming proposers (if any)
+    consensus_to_mempool_sender: Option<mpsc::Sender<QuorumStoreRequest>>,
+    // The upcoming proposers that mempool was last notified of
+    notified_upcoming_proposers: Vec<Author>,
 }
 
 impl RoundManager {
@@ -225,6 +239,7 @@ impl RoundManager {
             (Author, VerifiedEvent),
         >,
         local_config: ConsensusConfig,
+        consensus_to_mempool_sender: Option<mpsc::Sender<QuorumStoreRequest>>,
     ) -> Self {
         // when decoupled execution is false,
         // the counter is still static.
@@ -234,18 +249,26 @@ impl RoundManager {
         counters::OP_COUNTERS
             .gauge("decoupled_execution")
             .set(onchain_config.decoupled_execution() as i64);
+        let commit_cert_gossip = CommitCertGossip::new(
+            local_config.commit_gossip_round_lag,
+            Duration::from_millis(local_config.commit_gossip_interval_ms),
+        );
         Self {
             epoch_state,
             block_store,
             round_state,
             proposer_election: UnequivocalProposerElection::new(proposer_election),
+            commit_cert_gossip,
             proposal_generator,
+            pending_mine_blocks_request: None,
             safety_rules,
             network,
             storage,
             onchain_config,
             round_manager_tx,
             local_config,
+            consensus_to_mempool_sender,
+            notified_upcoming_proposers: vec![],
         }
     }
 
@@ -295,6 +318,7 @@ impl RoundManager {
             self.new_log(LogEvent::NewRound),
             reason = new_round_event.reason
         );
+        self.notify_upcoming_proposers(new_round_event.round);
 
         if self
             .proposer_election
@@ -317,6 +341,39 @@ impl RoundManager {
         Ok(())
     }
 
+    /// Notifies mempool of the proposers of the current and upcoming rounds, so that it
+    /// prioritizes its broadcasts to them. This is best effort, so the notification is dropped
+    /// if mempool is busy. Under quorum store, validators share transactions through batches
+    /// instead of mempool broadcasts, so there is nothing to prioritize.
+    fn notify_upcoming_proposers(&mut self, round: Round) {
+        let lookahead_rounds = self.local_config.mempool_proposer_lookahead_rounds;
+        if lookahead_rounds == 0 || self.onchain_config.quorum_store_enabled() {
+            return;
+        }
+        let sender = match self.consensus_to_mempool_sender.as_mut() {
+            Some(sender) => sender,
+            None => return,
+        };
+        let author = self.proposal_generator.author();
+        let mut upcoming_proposers: Vec<_> = (round..round.saturating_add(lookahead_rounds))
+            .map(|round| self.proposer_election.get_valid_proposer(round))
+            .filter(|proposer| *proposer != author)
+            .collect();
+        upcoming_proposers.dedup();
+        if upcoming_proposers == self.notified_upcoming_proposers {
+            return;
+        }
+        let request = QuorumStoreRequest::UpcomingProposersNotification(upcoming_proposers.clone());
+        if let Err(e) = sender.try_send(request) {
+            debug!(
+                round = round,
+                "Failed to notify mempool of the upcoming proposers: {:?}", e
+            );
+            return;
+        }
+        self.notified_upcoming_proposers = upcoming_proposers;
+    }
+
     fn log_collected_vote_stats(&self, new_round_event: &NewRoundEvent) {
         let prev_round_votes_for_li = new_round_event
             .prev_round_votes
@@ -398,6 +455,7 @@ impl RoundManager {
         }
         .boxed();
 
+        let num_empty_blocks_to_mine = self.proposal_generator.num_empty_blocks_to_mine();
         let proposal = self
             .proposal_generator
             .generate_proposal(new_round_event.round, &mut self.proposer_election, callback)
@@ -407,6 +465,18 @@ impl RoundManager {
             Block::new_proposal_from_block_data_and_signature(proposal, signature);
         observe_block(signed_proposal.timestamp_usecs(), BlockStage::SIGNED);
         info!(self.new_log(LogEvent::Propose), "{}", signed_proposal);
+
+        // Respond to the mine blocks request once its last block is proposed
+        if num_empty_blocks_to_mine == 1 && self.proposal_generator.num_empty_blocks_to_mine() == 0
+        {
+            if let Some(request) = self.pending_mine_blocks_request.take() {
+                let _ = request.response_tx.send(Ok(MinedBlock {
+                    epoch: signed_proposal.epoch(),
+                    round: signed_proposal.round(),
+                    timestamp_usecs: signed_proposal.timestamp_usecs(),
+                }));
+            }
+        }
         Ok(ProposalMsg::new(
             signed_proposal,
             self.block_store.sync_info(),
@@ -510,6 +580,7 @@ impl RoundManager {
         author: Author,
     ) -> anyhow::Result<bool> {
         if message_round < self.round_state.current_round() {
+            self.maybe_gossip_commit_cert(sync_info, author).await;
             return Ok(false);
         }
         self.sync_up(sync_info, author).await?;
@@ -522,6 +593,29 @@ impl RoundManager {
         Ok(true)
     }
 
+    /// Sends our SyncInfo to a peer whose stale message shows that it lags behind, so that it
+    /// fast-forwards to our commit certificate via state sync instead of timing out round
+    /// after round.
+    async fn maybe_gossip_commit_cert(&mut self, sync_info: &SyncInfo, author: Author) {
+        let local_sync_info = self.block_store.sync_info();
+        if !self.commit_cert_gossip.should_gossip(
+            author,
+            sync_info.highest_commit_round(),
+            local_sync_info.highest_commit_round(),
+            Instant::now(),
+        ) {
+            return;
+        }
+        info!(
+            self.new_log(LogEvent::GossipCommitCert).remote_peer(author),
+            "Local commit round {}, remote commit round {}",
+            local_sync_info.highest_commit_round(),
+            sync_info.highest_commit_round(),
+        );
+        counters::COMMIT_CERT_GOSSIP_COUNT.inc();
+        self.network.send_sync_info(local_sync_info, author).await;
+    }
+
     /// Process the SyncInfo sent by peers to catch up to latest state.
     pub async fn process_sync_info_msg(
         &mut self,
@@ -902,6 +996,11 @@ impl RoundManager {
             VoteReceptionResult::VoteAdded(_)
             | VoteReceptionResult::EchoTimeout(_)
             | VoteReceptionResult::DuplicateVote => Ok(()),
+            VoteReceptionResult::EquivocateVote => {
+                // The vote signature has been verified, so this is evidence of equivocation
+                self.network.report_equivocation(vote.author());
+                Err(anyhow::anyhow!("{:?}", VoteReceptionResult::EquivocateVote))
+            },
             e => Err(anyhow::anyhow!("{:?}", e)),
         }
     }
@@ -938,12 +1037,89 @@ impl RoundManager {
             .round_state
             .process_certificates(self.block_store.sync_info())
             .expect("Can not jump start a round_state from existing certificates.");
-        if let Some(vote) = last_vote_sent {
-            self.round_state.record_vote(vote);
+        let last_vote_sent = last_vote_sent.map(|vote| self.recover_2chain_timeout(vote));
+        if let Some(vote) = &last_vote_sent {
+            self.round_state.record_vote(vote.clone());
         }
         if let Err(e) = self.process_new_round_event(new_round_event).await {
             warn!(error = ?e, "[RoundManager] Error during start");
         }
+
+        // If we restarted in the middle of the round we voted in, re-broadcast the identical
+        // vote (or timeout), instead of staying silent until the next round.
+        if let Some(vote) = last_vote_sent {
+            if vote.vote_data().proposed().round() == self.round_state.current_round() {
+                self.rebroadcast_vote(vote).await;
+            }
+        }
+    }
+
+    /// Attaches the 2-chain timeout journaled by safety rules to the last vote, if the vote
+    /// was sent before the timeout was signed for the same round.
+    fn recover_2chain_timeout(&self, mut vote: Vote) -> Vote {
+        if vote.is_timeout() {
+            return vote;
+        }
+        let last_timeout = match self.safety_rules.lock().consensus_state() {
+            Ok(mut consensus_state) => consensus_state.safety_data().last_timeout,
+            Err(e) => {
+                warn!(error = ?e, "[RoundManager] Failed to read the last signed timeout");
+                None
+            },
+        };
+        if let Some((timeout, signature)) = last_timeout {
+            if timeout.round() == vote.vote_data().proposed().round() {
+                vote.add_2chain_timeout(timeout, signature);
+            }
+        }
+        vote
+    }
+
+    async fn rebroadcast_vote(&mut self, vote: Vote) {
+        let round = vote.vote_data().proposed().round();
+        let is_timeout = vote.is_timeout();
+        let vote_msg = VoteMsg::new(vote, self.block_store.sync_info());
+        if is_timeout {
+            info!(
+                self.new_log(LogEvent::Timeout),
+                "Re-broadcasting the timeout of round {} after restart", round
+            );
+            self.network.broadcast_timeout_vote(vote_msg).await;
+        } else {
+            let recipient = self.proposer_election.get_valid_proposer(round + 1);
+            info!(
+                self.new_log(LogEvent::Vote).remote_peer(recipient),
+                "Re-sending the vote of round {} after restart", round
+            );
+            self.network.send_vote(vote_msg, vec![recipient]).await;
+        }
+    }
+
+    /// Proposes the requested number of empty blocks as soon as this validator is the leader,
+    /// without waiting for transactions. Only one request is served at a time.
+    fn process_local_mine_blocks_request(
+        &mut self,
+        request: MineBlocksRequest,
+    ) -> anyhow::Result<()> {
+        if request.num_blocks == 0 {
+            let _ = request
+                .response_tx
+                .send(Err(anyhow::anyhow!("At least one block must be mined")));
+        } else if self.pending_mine_blocks_request.is_some() {
+            let _ = request.response_tx.send(Err(anyhow::anyhow!(
+                "Still mining the blocks of a previous request, {} blocks to go",
+                self.proposal_generator.num_empty_blocks_to_mine()
+            )));
+        } else {
+            info!(
+                self.new_log(LogEvent::MineBlocks),
+                "Mining {} empty blocks", request.num_blocks
+            );
+            self.proposal_generator
+                .mine_empty_blocks(request.num_blocks);
+            self.pending_mine_blocks_request = Some(request);
+        }
+        Ok(())
     }
 
     /// Inspect the current consensus state.
@@ -1011,6 +1187,10 @@ impl RoundManager {
                             "process_local_timeout",
                             self.process_local_timeout(round).await
                         ),
+                        VerifiedEvent::LocalMineBlocksRequest(request) => monitor!(
+                            "process_local_mine_blocks_request",
+                            self.process_local_mine_blocks_request(request)
+                        ),
                         unexpected_event => unreachable!("Unexpected event: {:?}", unexpected_event),
                     }
                     .with_context(|| format!("from peer {}", peer_id));
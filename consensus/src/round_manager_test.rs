@@ -47,6 +47,7 @@ use aptos_consensus_types::{
 use aptos_crypto::HashValue;
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::info;
+use aptos_mempool::QuorumStoreRequest;
 use aptos_network::{
     application::interface::NetworkClient,
     peer_manager::{conn_notifs_channel, ConnectionRequestSender, PeerManagerRequestSender},
@@ -63,7 +64,7 @@ use aptos_secure_storage::Storage;
 use aptos_types::{
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
-    on_chain_config::OnChainConsensusConfig,
+    on_chain_config::{ConsensusConfigV1, OnChainConsensusConfig},
     transaction::SignedTransaction,
     validator_signer::ValidatorSigner,
     validator_verifier::{generate_validator_verifier, random_validator_verifier},
@@ -274,6 +275,7 @@ impl NodeSetup {
             OnChainConsensusConfig::default(),
             round_manager_tx,
             ConsensusConfig::default(),
+            None,
         );
         block_on(round_manager.init(last_vote_sent));
         Self {
@@ -617,6 +619,51 @@ fn new_round_on_quorum_cert() {
     });
 }
 
+#[test]
+/// The proposers of the upcoming rounds are sent to mempool, unless quorum store is enabled
+fn notify_mempool_of_upcoming_proposers() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = NodeSetup::create_nodes(
+        &mut playground,
+        runtime.handle().clone(),
+        3,
+        Some(vec![0, 1, 2]),
+    );
+    let node = &mut nodes[0];
+    let proposers = node.proposers.clone();
+    let (mempool_tx, mut mempool_rx) = mpsc::channel(8);
+    node.round_manager.consensus_to_mempool_sender = Some(mempool_tx);
+    node.round_manager
+        .local_config
+        .mempool_proposer_lookahead_rounds = 2;
+    node.round_manager.onchain_config = OnChainConsensusConfig::V1(ConsensusConfigV1::default());
+
+    let mut next_notification = || match mempool_rx.try_next() {
+        Ok(Some(QuorumStoreRequest::UpcomingProposersNotification(upcoming))) => Some(upcoming),
+        Ok(Some(request)) => panic!("Unexpected request to mempool: {}", request),
+        Ok(None) => panic!("The mempool channel is closed!"),
+        Err(_) => None,
+    };
+
+    // The proposers of rounds 1 and 2 are notified
+    node.round_manager.notify_upcoming_proposers(1);
+    assert_eq!(next_notification(), Some(vec![proposers[1], proposers[2]]));
+
+    // Unchanged proposers aren't notified again
+    node.round_manager.notify_upcoming_proposers(1);
+    assert_eq!(next_notification(), None);
+
+    // The local node is never notified (it proposes round 3)
+    node.round_manager.notify_upcoming_proposers(2);
+    assert_eq!(next_notification(), Some(vec![proposers[2]]));
+
+    // Nothing is notified under quorum store
+    node.round_manager.onchain_config = OnChainConsensusConfig::default();
+    node.round_manager.notify_upcoming_proposers(4);
+    assert_eq!(next_notification(), None);
+}
+
 #[test]
 /// If the proposal is valid, a vote should be sent
 fn vote_on_successful_proposal() {
@@ -1305,6 +1352,36 @@ fn vote_resent_on_timeout() {
     });
 }
 
+#[test]
+/// If the node restarts in the round it timed out in, the identical timeout vote is re-sent.
+fn timeout_resent_on_restart() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut node = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 1, None)
+        .pop()
+        .unwrap();
+    let timeout_vote = timed_block_on(&runtime, async {
+        let proposal_msg = node.next_proposal().await;
+        node.round_manager
+            .process_proposal_msg(proposal_msg)
+            .await
+            .unwrap();
+        node.next_vote().await;
+        node.round_manager
+            .process_local_timeout(1)
+            .await
+            .unwrap_err();
+        node.next_vote().await.vote().clone()
+    });
+    assert!(timeout_vote.is_timeout());
+
+    node = node.restart(&mut playground, runtime.handle().clone());
+    timed_block_on(&runtime, async {
+        let resent_vote_msg = node.next_vote().await;
+        assert_eq!(resent_vote_msg.vote(), &timeout_vote);
+    });
+}
+
 #[test]
 #[ignore] // TODO: this test needs to be fixed!
 fn sync_on_partial_newer_sync_info() {
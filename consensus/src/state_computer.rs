@@ -4,10 +4,15 @@
 
 use crate::{
     block_storage::tracing::{observe_block, BlockStage},
+    consensus_observer::{
+        network_message::{CommitDecision, ConsensusObserverDirectSend},
+        publisher::ConsensusPublisher,
+    },
     counters,
     error::StateSyncError,
     monitor,
     payload_manager::PayloadManager,
+    payload_orderer::PayloadOrderer,
     state_replication::{StateComputer, StateComputerCommitCallBackType},
     transaction_deduper::TransactionDeduper,
     transaction_shuffler::TransactionShuffler,
@@ -60,6 +65,9 @@ pub struct ExecutionProxy {
     transaction_shuffler: Mutex<Option<Arc<dyn TransactionShuffler>>>,
     maybe_block_gas_limit: Mutex<Option<u64>>,
     transaction_deduper: Mutex<Option<Arc<dyn TransactionDeduper>>>,
+    payload_orderer: Mutex<Option<Arc<dyn PayloadOrderer>>>,
+    // Publishes the block payloads and commit decisions to consensus observers (if enabled)
+    consensus_publisher: Option<Arc<ConsensusPublisher>>,
 }
 
 impl ExecutionProxy {
@@ -68,6 +76,7 @@ impl ExecutionProxy {
         txn_notifier: Arc<dyn TxnNotifier>,
         state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
         handle: &tokio::runtime::Handle,
+        consensus_publisher: Option<Arc<ConsensusPublisher>>,
     ) -> Self {
         let (tx, mut rx) =
             aptos_channels::new::<NotificationType>(10, &counters::PENDING_STATE_SYNC_NOTIFICATION);
@@ -95,6 +104,8 @@ impl ExecutionProxy {
             transaction_shuffler: Mutex::new(None),
             maybe_block_gas_limit: Mutex::new(None),
             transaction_deduper: Mutex::new(None),
+            payload_orderer: Mutex::new(None),
+            consensus_publisher,
         }
     }
 }
@@ -123,11 +134,13 @@ impl StateComputer for ExecutionProxy {
 
         let payload_manager = self.payload_manager.lock().as_ref().unwrap().clone();
         let txn_deduper = self.transaction_deduper.lock().as_ref().unwrap().clone();
+        let payload_orderer = self.payload_orderer.lock().as_ref().unwrap().clone();
         let txn_shuffler = self.transaction_shuffler.lock().as_ref().unwrap().clone();
         let txns = payload_manager.get_transactions(block).await?;
 
         let deduped_txns = txn_deduper.dedup(txns);
-        let shuffled_txns = txn_shuffler.shuffle(deduped_txns);
+        let ordered_txns = payload_orderer.order(block_id, deduped_txns);
+        let shuffled_txns = txn_shuffler.shuffle(ordered_txns);
 
         let block_gas_limit = *self.maybe_block_gas_limit.lock();
 
@@ -189,6 +202,7 @@ impl StateComputer for ExecutionProxy {
 
         let payload_manager = self.payload_manager.lock().as_ref().unwrap().clone();
         let txn_deduper = self.transaction_deduper.lock().as_ref().unwrap().clone();
+        let payload_orderer = self.payload_orderer.lock().as_ref().unwrap().clone();
         let txn_shuffler = self.transaction_shuffler.lock().as_ref().unwrap().clone();
 
         let block_gas_limit = *self.maybe_block_gas_limit.lock();
@@ -202,7 +216,8 @@ impl StateComputer for ExecutionProxy {
 
             let signed_txns = payload_manager.get_transactions(block.block()).await?;
             let deduped_txns = txn_deduper.dedup(signed_txns);
-            let shuffled_txns = txn_shuffler.shuffle(deduped_txns);
+            let ordered_txns = payload_orderer.order(block.id(), deduped_txns);
+            let shuffled_txns = txn_shuffler.shuffle(ordered_txns);
 
             txns.extend(block.transactions_to_commit(
                 &self.validators.lock(),
@@ -212,6 +227,13 @@ impl StateComputer for ExecutionProxy {
             reconfig_events.extend(block.reconfig_event());
         }
 
+        // Publish the commit decision so that consensus observers can commit the blocks
+        if let Some(consensus_publisher) = &self.consensus_publisher {
+            consensus_publisher.publish_message(ConsensusObserverDirectSend::CommitDecision(
+                CommitDecision::new(finality_proof.clone()),
+            ));
+        }
+
         let executor = self.executor.clone();
         let proof = finality_proof.clone();
         monitor!(
@@ -303,6 +325,7 @@ impl StateComputer for ExecutionProxy {
         transaction_shuffler: Arc<dyn TransactionShuffler>,
         block_gas_limit: Option<u64>,
         transaction_deduper: Arc<dyn TransactionDeduper>,
+        payload_orderer: Arc<dyn PayloadOrderer>,
     ) {
         *self.validators.lock() = epoch_state
             .verifier
@@ -314,6 +337,7 @@ impl StateComputer for ExecutionProxy {
             .replace(transaction_shuffler);
         *self.maybe_block_gas_limit.lock() = block_gas_limit;
         self.transaction_deduper.lock().replace(transaction_deduper);
+        self.payload_orderer.lock().replace(payload_orderer);
     }
 
     // Clears the epoch-specific state. Only a sync_to call is expected before calling new_epoch
@@ -327,7 +351,8 @@ impl StateComputer for ExecutionProxy {
 #[tokio::test]
 async fn test_commit_sync_race() {
     use crate::{
-        error::MempoolError, transaction_deduper::create_transaction_deduper,
+        error::MempoolError, payload_orderer::create_payload_orderer,
+        transaction_deduper::create_transaction_deduper,
         transaction_shuffler::create_transaction_shuffler,
     };
     use aptos_consensus_notifications::Error;
@@ -336,7 +361,7 @@ async fn test_commit_sync_race() {
         block_executor::partitioner::ExecutableBlock,
         block_info::BlockInfo,
         ledger_info::LedgerInfo,
-        on_chain_config::{TransactionDeduperType, TransactionShufflerType},
+        on_chain_config::{PayloadOrdererType, TransactionDeduperType, TransactionShufflerType},
         transaction::SignedTransaction,
     };
 
@@ -433,6 +458,7 @@ async fn test_commit_sync_race() {
         recorded_commit.clone(),
         recorded_commit.clone(),
         &tokio::runtime::Handle::current(),
+        None,
     );
     executor.new_epoch(
         &EpochState::empty(),
@@ -440,6 +466,7 @@ async fn test_commit_sync_race() {
         create_transaction_shuffler(TransactionShufflerType::NoShuffling),
         None,
         create_transaction_deduper(TransactionDeduperType::NoDedup),
+        create_payload_orderer(PayloadOrdererType::FairFifo),
     );
     executor
         .commit(&[], generate_li(1, 1), callback.clone())
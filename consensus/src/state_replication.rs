@@ -5,6 +5,7 @@
 use crate::{
     error::{QuorumStoreError, StateSyncError},
     payload_manager::PayloadManager,
+    payload_orderer::PayloadOrderer,
     transaction_deduper::TransactionDeduper,
     transaction_shuffler::TransactionShuffler,
 };
@@ -80,6 +81,7 @@ pub trait StateComputer: Send + Sync {
         transaction_shuffler: Arc<dyn TransactionShuffler>,
         block_gas_limit: Option<u64>,
         transaction_deduper: Arc<dyn TransactionDeduper>,
+        payload_orderer: Arc<dyn PayloadOrderer>,
     );
 
     // Reconfigure to clear epoch state at end of epoch.
@@ -6,6 +6,7 @@ use crate::{
     error::StateSyncError,
     experimental::buffer_manager::OrderedBlocks,
     payload_manager::PayloadManager,
+    payload_orderer::PayloadOrderer,
     state_replication::{StateComputer, StateComputerCommitCallBackType},
     test_utils::mock_storage::MockStorage,
     transaction_deduper::TransactionDeduper,
@@ -141,6 +142,7 @@ impl StateComputer for MockStateComputer {
         _: Arc<dyn TransactionShuffler>,
         _: Option<u64>,
         _: Arc<dyn TransactionDeduper>,
+        _: Arc<dyn PayloadOrderer>,
     ) {
     }
 
@@ -179,6 +181,7 @@ impl StateComputer for EmptyStateComputer {
         _: Arc<dyn TransactionShuffler>,
         _: Option<u64>,
         _: Arc<dyn TransactionDeduper>,
+        _: Arc<dyn PayloadOrderer>,
     ) {
     }
 
@@ -241,6 +244,7 @@ impl StateComputer for RandomComputeResultStateComputer {
         _: Arc<dyn TransactionShuffler>,
         _: Option<u64>,
         _: Arc<dyn TransactionDeduper>,
+        _: Arc<dyn PayloadOrderer>,
     ) {
     }
 
@@ -13,7 +13,7 @@ use aptos_types::on_chain_config::ProposerElectionType::{
     FixedProposer, RotatingProposer, RoundProposer,
 };
 use futures::StreamExt;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[test]
 /// This test checks that the first proposal has its parent and
@@ -313,3 +313,58 @@ fn twins_commit_test() {
         }
     });
 }
+
+#[test]
+/// This test checks that a node in a maintenance window (from the
+/// on-chain config of the epoch) doesn't propose during the window,
+/// while the others keep proposing.
+///
+/// Setup:
+///
+/// 4 honest nodes (n0, n1, n2, n3), and 0 twins.
+/// n0 is in maintenance for rounds [3, 11).
+///
+/// Test:
+///
+/// Run consensus until the end of the window, and check that all
+/// the proposals in the window are from n1, n2 or n3.
+///
+/// Run the test:
+/// cargo xtest -p consensus maintenance_handoff_test -- --nocapture
+fn maintenance_handoff_test() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let num_nodes = 4;
+    let num_twins = 0;
+    let window = 3..11;
+    let nodes = SMRNode::start_num_nodes_with_twins_and_maintenance(
+        num_nodes,
+        num_twins,
+        &mut playground,
+        RotatingProposer(1),
+        None,
+        vec![(0, window.start, window.end - window.start)],
+    );
+    let n0_author = nodes[0].id.author;
+
+    timed_block_on(&runtime, async {
+        let mut proposers_in_window = HashSet::new();
+        loop {
+            let msg = playground
+                .wait_for_messages(1, NetworkPlayground::proposals_only)
+                .await;
+            let round = match &msg[0].1 {
+                ConsensusMsg::ProposalMsg(proposal) => proposal.proposal().round(),
+                _ => panic!("Unexpected message found"),
+            };
+            if round >= window.end {
+                break;
+            }
+            if window.contains(&round) {
+                proposers_in_window.insert(msg[0].0);
+            }
+        }
+        assert!(!proposers_in_window.is_empty());
+        assert!(!proposers_in_window.contains(&n0_author));
+    });
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    admin::ConsensusAdminClient,
     counters,
     epoch_manager::EpochManager,
     experimental::buffer_manager::OrderedBlocks,
@@ -38,8 +39,8 @@ use aptos_network::{
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     on_chain_config::{
-        ConsensusConfigV1, InMemoryOnChainConfig, OnChainConfig, OnChainConfigPayload,
-        OnChainConsensusConfig,
+        ConsensusConfigV1, InMemoryOnChainConfig, MaintenanceWindow, OnChainConfig,
+        OnChainConfigPayload, OnChainConsensusConfig, OnChainMaintenanceSchedule,
         ProposerElectionType::{self, RoundProposer},
         ValidatorSet,
     },
@@ -57,6 +58,7 @@ pub struct SMRNode {
     pub id: TwinId,
     pub storage: Arc<MockStorage>,
     pub commit_cb_receiver: mpsc::UnboundedReceiver<LedgerInfoWithSignatures>,
+    pub admin_client: ConsensusAdminClient,
     _runtime: Runtime,
     _shared_mempool: MockSharedMempool,
     _state_sync: mpsc::UnboundedReceiver<Vec<SignedTransaction>>,
@@ -71,6 +73,7 @@ impl SMRNode {
         playground: &mut NetworkPlayground,
         config: NodeConfig,
         consensus_config: OnChainConsensusConfig,
+        maintenance_schedule: &OnChainMaintenanceSchedule,
         storage: Arc<MockStorage>,
         twin_id: TwinId,
     ) -> Self {
@@ -126,6 +129,10 @@ impl SMRNode {
             // Requires double serialization, check deserialize_into_config for more details
             bcs::to_bytes(&bcs::to_bytes(&consensus_config).unwrap()).unwrap(),
         );
+        configs.insert(
+            OnChainMaintenanceSchedule::CONFIG_ID,
+            bcs::to_bytes(maintenance_schedule).unwrap(),
+        );
         let payload = OnChainConfigPayload::new(1, InMemoryOnChainConfig::new(configs));
 
         reconfig_sender
@@ -141,6 +148,7 @@ impl SMRNode {
             aptos_channels::new(1_024, &counters::PENDING_ROUND_TIMEOUTS);
         let (self_sender, self_receiver) =
             aptos_channels::new(1_024, &counters::PENDING_SELF_MESSAGES);
+        let (admin_client, admin_command_receiver) = ConsensusAdminClient::new();
 
         let quorum_store_storage = Arc::new(MockQuorumStoreDB::new());
         let bounded_executor = BoundedExecutor::new(2, playground.handle());
@@ -157,12 +165,13 @@ impl SMRNode {
             quorum_store_storage,
             reconfig_listener,
             bounded_executor,
+            None,
         );
         let (network_task, network_receiver) =
             NetworkTask::new(network_service_events, self_receiver);
 
         runtime.spawn(network_task.start());
-        runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
+        runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver, admin_command_receiver));
 
         let (commit_cb_sender, commit_cb_receiver) = mpsc::unbounded::<LedgerInfoWithSignatures>();
         runtime.spawn(async move {
@@ -182,6 +191,7 @@ impl SMRNode {
             id: twin_id,
             _runtime: runtime,
             commit_cb_receiver,
+            admin_client,
             storage,
             _shared_mempool: shared_mempool,
             _state_sync: state_sync,
@@ -195,6 +205,26 @@ impl SMRNode {
         playground: &mut NetworkPlayground,
         proposer_type: ProposerElectionType,
         round_proposers_idx: Option<HashMap<Round, usize>>,
+    ) -> Vec<Self> {
+        Self::start_num_nodes_with_twins_and_maintenance(
+            num_nodes,
+            num_twins,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+            vec![],
+        )
+    }
+
+    /// Starts a given number of nodes and their twins, with the given maintenance windows
+    /// (node index, start round and number of rounds) in the on-chain config of the epoch
+    pub fn start_num_nodes_with_twins_and_maintenance(
+        num_nodes: usize,
+        num_twins: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ProposerElectionType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+        maintenance_windows: Vec<(usize, Round, u64)>,
     ) -> Vec<Self> {
         assert!(num_nodes >= num_twins);
         let ValidatorSwarm {
@@ -247,6 +277,18 @@ impl SMRNode {
             _ => proposer_type,
         };
 
+        let maintenance_schedule = OnChainMaintenanceSchedule {
+            windows: maintenance_windows
+                .into_iter()
+                .map(|(idx, start_round, num_rounds)| MaintenanceWindow {
+                    validator: author_from_config(&node_configs[idx]),
+                    epoch: 1,
+                    start_round,
+                    num_rounds,
+                })
+                .collect(),
+        };
+
         // We don't add twins to ValidatorSet or round_proposers above
         // because a node with twins should be treated the same at the
         // consensus level
@@ -286,6 +328,7 @@ impl SMRNode {
                 playground,
                 config,
                 consensus_config,
+                &maintenance_schedule,
                 storage,
                 twin_id,
             ));
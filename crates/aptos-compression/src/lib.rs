@@ -2,9 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::metrics::{
-    increment_compression_byte_count, increment_compression_error,
+    increment_compression_byte_count, increment_compression_error, observe_compression_ratio,
     start_compression_operation_timer, CompressionClient, COMPRESS, COMPRESSED_BYTES, DECOMPRESS,
-    RAW_BYTES,
+    LZ4, RAW_BYTES, ZSTD, ZSTD_COMPRESS, ZSTD_DECOMPRESS,
 };
 use aptos_logger::prelude::*;
 use lz4::block::CompressionMode;
@@ -16,6 +16,8 @@ use thiserror::Error;
 /// sent across the network (e.g., by state sync and consensus).
 /// Internally, it uses LZ4 in fast mode to compress the data.
 /// See <https://github.com/10xGenomics/lz4-rs> for more information.
+/// For clients that prefer a better compression ratio over CPU cost,
+/// zstd is also offered (see `zstd_compress` and `zstd_decompress`).
 ///
 /// Note: the crate also exposes some basic compression metrics
 /// that can be used to track the cumulative compression ratio
@@ -28,6 +30,10 @@ mod tests;
 /// This was determined anecdotally.
 const ACCELERATION_PARAMETER: i32 = 1;
 
+/// The zstd compression level. Low levels are fast, and still compress
+/// the messages with many transactions (e.g., blocks and batches) well.
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
 /// A useful wrapper for representing compressed data
 pub type CompressedData = Vec<u8>;
 
@@ -79,7 +85,12 @@ pub fn compress(
     // Stop the timer and update the metrics
     let compression_duration = timer.stop_and_record();
     increment_compression_byte_count(RAW_BYTES, client.clone(), raw_data.len() as u64);
-    increment_compression_byte_count(COMPRESSED_BYTES, client, compressed_data.len() as u64);
+    increment_compression_byte_count(
+        COMPRESSED_BYTES,
+        client.clone(),
+        compressed_data.len() as u64,
+    );
+    observe_compression_ratio(LZ4, client, raw_data.len(), compressed_data.len());
 
     // Log the relative data compression statistics
     let relative_data_size = calculate_relative_size(&raw_data, &compressed_data);
@@ -94,6 +105,66 @@ pub fn compress(
     Ok(compressed_data)
 }
 
+/// Compresses the raw data stream using zstd
+pub fn zstd_compress(
+    raw_data: Vec<u8>,
+    client: CompressionClient,
+    max_bytes: usize,
+) -> Result<CompressedData, CompressionError> {
+    if raw_data.len() > max_bytes {
+        return Err(CompressionError(format!(
+            "Uncompressed size greater than max. size: {}, max: {}",
+            raw_data.len(),
+            max_bytes
+        )));
+    }
+    // Start the compression timer
+    let timer = start_compression_operation_timer(ZSTD_COMPRESS, client.clone());
+
+    // Compress the data
+    let compressed_data = match zstd::bulk::compress(&raw_data, ZSTD_COMPRESSION_LEVEL) {
+        Ok(compressed_data) => compressed_data,
+        Err(error) => {
+            increment_compression_error(ZSTD_COMPRESS, client);
+            return Err(CompressionError(format!(
+                "Failed to compress the data: {}",
+                error
+            )));
+        },
+    };
+
+    // Ensure that the compressed data size is not greater than the max bytes limit
+    if compressed_data.len() > max_bytes {
+        return Err(CompressionError(format!(
+            "Compressed size greater than max. size: {}, max: {}",
+            compressed_data.len(),
+            max_bytes
+        )));
+    }
+
+    // Stop the timer and update the metrics
+    let compression_duration = timer.stop_and_record();
+    increment_compression_byte_count(RAW_BYTES, client.clone(), raw_data.len() as u64);
+    increment_compression_byte_count(
+        COMPRESSED_BYTES,
+        client.clone(),
+        compressed_data.len() as u64,
+    );
+    observe_compression_ratio(ZSTD, client, raw_data.len(), compressed_data.len());
+
+    // Log the relative data compression statistics
+    let relative_data_size = calculate_relative_size(&raw_data, &compressed_data);
+    trace!(
+        "Compressed (zstd) {} bytes to {} bytes ({} %) in {} seconds.",
+        raw_data.len(),
+        compressed_data.len(),
+        relative_data_size,
+        compression_duration
+    );
+
+    Ok(compressed_data)
+}
+
 /// Decompresses the compressed data stream
 pub fn decompress(
     compressed_data: &CompressedData,
@@ -139,6 +210,41 @@ pub fn decompress(
     Ok(raw_data)
 }
 
+/// Decompresses the zstd compressed data stream
+pub fn zstd_decompress(
+    compressed_data: &[u8],
+    client: CompressionClient,
+    max_size: usize,
+) -> Result<Vec<u8>, CompressionError> {
+    // Start the decompression timer
+    let timer = start_compression_operation_timer(ZSTD_DECOMPRESS, client.clone());
+
+    // Decompress the data (this fails if the decompressed data exceeds the max size)
+    let raw_data = match zstd::bulk::decompress(compressed_data, max_size) {
+        Ok(raw_data) => raw_data,
+        Err(error) => {
+            increment_compression_error(ZSTD_DECOMPRESS, client);
+            return Err(CompressionError(format!(
+                "Failed to decompress the data: {}",
+                error
+            )));
+        },
+    };
+
+    // Stop the timer and log the relative data compression statistics
+    let decompression_duration = timer.stop_and_record();
+    let relative_data_size = calculate_relative_size(compressed_data, &raw_data);
+    trace!(
+        "Decompressed (zstd) {} bytes to {} bytes ({} %) in {} seconds.",
+        compressed_data.len(),
+        raw_data.len(),
+        relative_data_size,
+        decompression_duration
+    );
+
+    Ok(raw_data)
+}
+
 /// Derived from lz4-rs crate, which starts the compressed payload with the original data size as i32
 /// see: https://github.com/10XGenomics/lz4-rs/blob/0abc0a52af1f6010f9a57640b1dc8eb8d2d697aa/src/block/mod.rs#L162
 fn get_decompressed_size(src: &CompressedData, max_size: usize) -> std::io::Result<usize> {
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_metrics_core::{
-    register_histogram_vec, register_int_counter_vec, HistogramTimer, HistogramVec, IntCounterVec,
+    exponential_buckets, register_histogram_vec, register_int_counter_vec, HistogramTimer,
+    HistogramVec, IntCounterVec,
 };
 use once_cell::sync::Lazy;
 
@@ -11,6 +12,12 @@ pub const COMPRESS: &str = "compress";
 pub const DECOMPRESS: &str = "decompress";
 pub const COMPRESSED_BYTES: &str = "compressed_bytes";
 pub const RAW_BYTES: &str = "raw_bytes";
+pub const ZSTD_COMPRESS: &str = "zstd_compress";
+pub const ZSTD_DECOMPRESS: &str = "zstd_decompress";
+
+/// Useful metric constants for the compression algorithms
+pub const LZ4: &str = "lz4";
+pub const ZSTD: &str = "zstd";
 
 /// A simple enum for identifying clients of the compression crate. This
 /// allows us to provide a runtime breakdown of compression metrics for
@@ -63,6 +70,17 @@ pub static OPERATION_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// The compression ratio of each compressed message (i.e., raw bytes / compressed bytes)
+pub static COMPRESSION_RATIO: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_compression_ratio",
+        "The compression ratio of each compressed message",
+        &["algorithm", "client"],
+        exponential_buckets(/*start=*/ 0.5, /*factor=*/ 1.25, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
 /// Increments the compression byte count based on the given data type
 pub fn increment_compression_byte_count(
     data_type: &str,
@@ -74,6 +92,20 @@ pub fn increment_compression_byte_count(
         .inc_by(byte_count)
 }
 
+/// Observes the compression ratio of a single message for the given algorithm
+pub fn observe_compression_ratio(
+    algorithm: &str,
+    client: CompressionClient,
+    raw_bytes: usize,
+    compressed_bytes: usize,
+) {
+    if compressed_bytes > 0 {
+        COMPRESSION_RATIO
+            .with_label_values(&[algorithm, client.get_label()])
+            .observe(raw_bytes as f64 / compressed_bytes as f64)
+    }
+}
+
 /// Increments the compression error count based on the given operation
 pub fn increment_compression_error(operation: &str, client: CompressionClient) {
     ERROR_COUNTS
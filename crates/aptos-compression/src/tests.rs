@@ -66,6 +66,42 @@ fn test_compression_limits() {
     assert!(maybe_decompressed_bytes.is_err());
 }
 
+#[test]
+fn test_zstd_compression() {
+    let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
+    let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
+
+    // Test that the data round trips
+    let compressed_bytes = crate::zstd_compress(
+        bcs_encoded_bytes.clone(),
+        CompressionClient::Consensus,
+        MAX_COMPRESSION_SIZE,
+    )
+    .unwrap();
+    let decompressed_bytes = crate::zstd_decompress(
+        &compressed_bytes,
+        CompressionClient::Consensus,
+        MAX_COMPRESSION_SIZE,
+    )
+    .unwrap();
+    assert_eq!(decompressed_bytes, bcs_encoded_bytes);
+
+    // Test the compression and decompression limits
+    let too_small_bytes = 1;
+    assert!(crate::zstd_compress(
+        bcs_encoded_bytes.clone(),
+        CompressionClient::Consensus,
+        too_small_bytes
+    )
+    .is_err());
+    assert!(crate::zstd_decompress(
+        &compressed_bytes,
+        CompressionClient::Consensus,
+        bcs_encoded_bytes.len() - 1
+    )
+    .is_err());
+}
+
 /// Ensures that the given object can be compressed and decompressed successfully
 /// when BCS encoded.
 fn test_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(object: T) {
@@ -65,6 +65,7 @@ fn bench_group(c: &mut Criterion) {
 
         verify_multisig(&mut group, size);
         verify_aggsig(&mut group, size);
+        batch_verify(&mut group, size);
         size *= 2;
     }
 
@@ -415,6 +416,48 @@ fn verify_aggsig<M: Measurement>(g: &mut BenchmarkGroup<M>, n: usize) {
     });
 }
 
+/// Benchmarks the time to batch-verify `n` signatures on different messages, which can be compared
+/// to `n` times the time to verify a signature share
+fn batch_verify<M: Measurement>(g: &mut BenchmarkGroup<M>, n: usize) {
+    let mut rng = thread_rng();
+
+    // pick `n` random keypairs
+    let mut key_pairs = vec![];
+
+    for _ in 0..n {
+        key_pairs.push(KeyPair::<bls12381::PrivateKey, bls12381::PublicKey>::generate(&mut rng));
+    }
+
+    g.throughput(Throughput::Elements(n as u64));
+    g.bench_with_input(BenchmarkId::new("batch_verify", n), &n, |b, &_n| {
+        b.iter_batched(
+            || {
+                // each of the signers computes a signature share on a random message
+                let mut sigshares = vec![];
+                let mut pks = vec![];
+                let mut msgs = vec![];
+
+                for kp in key_pairs.iter() {
+                    msgs.push(random_message(&mut rng));
+                    sigshares.push(kp.private_key.sign(msgs.last().unwrap()).unwrap());
+                    pks.push(&kp.public_key)
+                }
+
+                (msgs, pks, sigshares)
+            },
+            |(msgs, pks, sigshares)| {
+                let msgs_refs = msgs.iter().collect::<Vec<&TestAptosCrypto>>();
+                let sigs_refs = sigshares.iter().collect::<Vec<&bls12381::Signature>>();
+
+                let result = bls12381::Signature::batch_verify(&msgs_refs, &pks, &sigs_refs);
+
+                assert!(result.is_ok());
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
 criterion_group!(
     name = bls12381_benches;
     config = Criterion::default(); //.measurement_time(Duration::from_secs(100));
@@ -95,6 +95,17 @@ impl PrivateKey {
     pub fn to_bytes(&self) -> [u8; Self::LENGTH] {
         self.privkey.to_bytes()
     }
+
+    /// Signs a message that was already serialized by `crate::signing_message` (e.g., by a remote
+    /// client of a signing service). Otherwise, `SigningKey::sign` should be used instead, as
+    /// signing arbitrary bytes bypasses the domain separation of the signed structs.
+    pub fn sign_signing_message(&self, signing_message: &[u8]) -> bls12381::Signature {
+        bls12381::Signature {
+            sig: self
+                .privkey
+                .sign(signing_message, DST_BLS_SIG_IN_G2_WITH_POP, &[]),
+        }
+    }
 }
 
 ///////////////////////
@@ -12,7 +12,8 @@
 //!  3. aggregate signature on different messages from many signers
 //!
 //! The signature verification APIs in `Signature::verify`, `Signature::verify_arbitrary_msg`,
-//! `Signature::verify_aggregate` and `Signature::verify_aggregate_arbitrary_msg` do NOT
+//! `Signature::verify_aggregate`, `Signature::verify_aggregate_arbitrary_msg`,
+//! `Signature::batch_verify` and `Signature::batch_verify_arbitrary_msg` do NOT
 //! assume the signature to be a valid group element and will implicitly "subgroup-check" it. This
 //! makes the caller's job easier and, more importantly, makes the library safer to use.
 
@@ -28,6 +29,7 @@ use crate::{
 use anyhow::{anyhow, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey};
 use blst::BLST_ERROR;
+use rand::Rng;
 use serde::Serialize;
 use std::{convert::TryFrom, fmt};
 
@@ -118,6 +120,96 @@ impl Signature {
         self.verify_aggregate_arbitrary_msg(&msgs_refs, pks)
     }
 
+    /// Verifies many signatures at once, on the messages in `msgs` under the public keys in `pks`.
+    /// Specifically, verifies that each `sigs[i]` is a signature (or multisignature) on `msgs[i]`
+    /// under `pks[i]` (or the aggregate of the public keys of the signers, see
+    /// `PublicKey::aggregate`).
+    ///
+    /// Unlike `Signature::verify_aggregate_arbitrary_msg`, the signatures are not summed up as is,
+    /// but randomized with 64-bit scalars first, so one of them cannot cancel out another one. This
+    /// takes n + 1 pairings instead of the 2n pairings of verifying each signature separately.
+    /// When this fails, the invalid signatures can only be found by verifying them one by one.
+    ///
+    /// WARNING: This function assumes that the public keys have been subgroup-checked by the caller
+    /// implicitly when verifying their proof-of-possession (PoP) in `ProofOfPossession::verify`.
+    pub fn batch_verify_arbitrary_msg(
+        msgs: &[&[u8]],
+        pks: &[&PublicKey],
+        sigs: &[&Signature],
+    ) -> Result<()> {
+        if msgs.len() != pks.len() || msgs.len() != sigs.len() {
+            return Err(anyhow!(
+                "Mismatched lengths: {} messages, {} public keys, {} signatures",
+                msgs.len(),
+                pks.len(),
+                sigs.len()
+            ));
+        }
+        match sigs.len() {
+            0 => return Ok(()),
+            1 => return traits::Signature::verify_arbitrary_msg(sigs[0], msgs[0], pks[0]),
+            _ => {},
+        }
+
+        let pks = pks
+            .iter()
+            .map(|&pk| &pk.pubkey)
+            .collect::<Vec<&blst::min_pk::PublicKey>>();
+        let sigs = sigs
+            .iter()
+            .map(|&sig| &sig.sig)
+            .collect::<Vec<&blst::min_pk::Signature>>();
+        let mut rng = rand::thread_rng();
+        let rands = (0..sigs.len())
+            .map(|_| {
+                // A zero scalar would drop its signature from the check
+                let mut rand = 0u64;
+                while rand == 0 {
+                    rand = rng.gen();
+                }
+                let mut scalar = blst::blst_scalar::default();
+                scalar.b[..8].copy_from_slice(&rand.to_le_bytes());
+                scalar
+            })
+            .collect::<Vec<blst::blst_scalar>>();
+
+        let result = blst::min_pk::Signature::verify_multiple_aggregate_signatures(
+            msgs,
+            DST_BLS_SIG_IN_G2_WITH_POP,
+            &pks,
+            false,
+            &sigs,
+            true,
+            &rands,
+            64,
+        );
+
+        if result == BLST_ERROR::BLST_SUCCESS {
+            Ok(())
+        } else {
+            Err(anyhow!("{:?}", result))
+        }
+    }
+
+    /// Serializes the messages of type `T` to bytes and calls `Signature::batch_verify_arbitrary_msg`.
+    pub fn batch_verify<T: CryptoHash + Serialize>(
+        msgs: &[&T],
+        pks: &[&PublicKey],
+        sigs: &[&Signature],
+    ) -> Result<()> {
+        let mut messages: Vec<Vec<u8>> = vec![];
+        for message in msgs {
+            messages.push(signing_message(*message)?);
+        }
+
+        let msgs_refs = messages
+            .iter()
+            .map(|m| m.as_slice())
+            .collect::<Vec<&[u8]>>();
+
+        Self::batch_verify_arbitrary_msg(&msgs_refs, pks, sigs)
+    }
+
     /// Return a dummy signature for testing.
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn dummy_signature() -> Self {
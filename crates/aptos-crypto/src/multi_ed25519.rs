@@ -402,6 +402,14 @@ impl MultiEd25519Signature {
         &self.bitmap
     }
 
+    /// Returns the indices of the keys that signed (in the same order as the signatures).
+    pub fn signer_indices(&self) -> Vec<u8> {
+        (0..MAX_NUM_OF_KEYS)
+            .filter(|index| bitmap_get_bit(self.bitmap, *index))
+            .map(|index| index as u8)
+            .collect()
+    }
+
     /// Serialize a MultiEd25519Signature in the form of sig0||sig1||..sigN||bitmap.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes: Vec<u8> = self
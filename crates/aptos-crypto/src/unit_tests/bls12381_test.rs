@@ -221,6 +221,72 @@ fn bls12381_aggsig_zero_messages_or_pks_does_not_verify() {
     assert!(aggsig.verify_aggregate(&msgs_refs, &pubkeys).is_err());
 }
 
+/// Tests that a batch of signatures and multisignatures on `n` different messages verifies correctly,
+/// and that it does NOT verify if any of the signatures is invalid, even when the signatures would
+/// still sum up to a valid aggregate signature (i.e., when two of them are swapped).
+#[test]
+fn bls12381_batch_verify() {
+    let mut rng = OsRng;
+    let num_messages = 10;
+    let num_signers = 4;
+
+    let messages = random_messages_for_signing(&mut rng, num_messages);
+    let key_pairs = bls12381_keygen(num_signers, &mut rng);
+
+    // Even messages are signed by one signer, odd ones are multisigned by all the signers
+    let mut signatures = vec![];
+    let mut pubkeys = vec![];
+    for (i, message) in messages.iter().enumerate() {
+        let signers = if i % 2 == 0 {
+            &key_pairs[i % num_signers..i % num_signers + 1]
+        } else {
+            &key_pairs[..]
+        };
+        signatures.push(
+            bls12381::Signature::aggregate(
+                signers
+                    .iter()
+                    .map(|keys| keys.private_key.sign(message).unwrap())
+                    .collect(),
+            )
+            .unwrap(),
+        );
+        pubkeys.push(
+            PublicKey::aggregate(signers.iter().map(|keys| &keys.public_key).collect()).unwrap(),
+        );
+    }
+    let msgs_refs = messages.iter().collect::<Vec<&TestAptosCrypto>>();
+    let pubkeys_refs = pubkeys.iter().collect::<Vec<&PublicKey>>();
+    let sigs_refs = signatures.iter().collect::<Vec<&bls12381::Signature>>();
+
+    // The batch should verify on the correct messages under the correct PKs
+    assert!(bls12381::Signature::batch_verify(&msgs_refs, &pubkeys_refs, &sigs_refs).is_ok());
+
+    // ...including a batch of zero or one signatures
+    assert!(bls12381::Signature::batch_verify::<TestAptosCrypto>(&[], &[], &[]).is_ok());
+    assert!(bls12381::Signature::batch_verify(
+        &msgs_refs[..1],
+        &pubkeys_refs[..1],
+        &sigs_refs[..1]
+    )
+    .is_ok());
+
+    // The batch should NOT verify with swapped signatures
+    let mut sigs_swapped = sigs_refs.clone();
+    sigs_swapped.swap(0, 2);
+    assert!(bls12381::Signature::batch_verify(&msgs_refs, &pubkeys_refs, &sigs_swapped).is_err());
+
+    // The batch should NOT verify with an invalid signature
+    let message_wrong = random_message_for_signing(&mut rng);
+    let signature_wrong = key_pairs[0].private_key.sign(&message_wrong).unwrap();
+    let mut sigs_wrong = sigs_refs.clone();
+    sigs_wrong[0] = &signature_wrong;
+    assert!(bls12381::Signature::batch_verify(&msgs_refs, &pubkeys_refs, &sigs_wrong).is_err());
+
+    // The batch should NOT verify with mismatched lengths
+    assert!(bls12381::Signature::batch_verify(&msgs_refs, &pubkeys_refs[1..], &sigs_refs).is_err());
+}
+
 /// Tests that a multisignature incorrectly aggregated from signature shares on different messages does
 /// NOT verify.
 #[test]
@@ -6,6 +6,7 @@ mod google_captcha;
 mod ip_blocklist;
 mod magic_header;
 mod memory_ratelimit;
+mod quota;
 mod redis_ratelimit;
 mod referer_blocklist;
 mod tap_captcha;
@@ -17,6 +18,7 @@ use self::{
     ip_blocklist::IpBlocklistChecker,
     magic_header::{MagicHeaderChecker, MagicHeaderCheckerConfig},
     memory_ratelimit::{MemoryRatelimitChecker, MemoryRatelimitCheckerConfig},
+    quota::{QuotaChecker, QuotaCheckerConfig},
     redis_ratelimit::{RedisRatelimitChecker, RedisRatelimitCheckerConfig},
     referer_blocklist::RefererBlocklistChecker,
     tap_captcha::{TapCaptchaChecker, TapCaptchaCheckerConfig},
@@ -96,6 +98,10 @@ pub enum CheckerConfig {
     /// Basic in memory ratelimiter that allows a single successful request per IP.
     MemoryRatelimit(MemoryRatelimitCheckerConfig),
 
+    /// Daily quotas per IP, per account and in total, with the counters kept
+    /// in Redis or sled, and optional captcha escalation.
+    Quota(QuotaCheckerConfig),
+
     /// Ratelimiter that uses Redis.
     RedisRatelimit(RedisRatelimitCheckerConfig),
 
@@ -118,6 +124,9 @@ impl CheckerConfig {
             CheckerConfig::MemoryRatelimit(config) => {
                 Checker::from(MemoryRatelimitChecker::new(config))
             },
+            CheckerConfig::Quota(config) => {
+                Checker::from(QuotaChecker::new(config, captcha_manager).await?)
+            },
             CheckerConfig::RedisRatelimit(config) => {
                 Checker::from(RedisRatelimitChecker::new(config).await?)
             },
@@ -139,6 +148,7 @@ pub enum Checker {
     IpBlocklistChecker,
     MagicHeaderChecker,
     MemoryRatelimitChecker,
+    QuotaChecker,
     RedisRatelimitChecker,
     RefererBlocklistChecker,
     TapCaptchaChecker,
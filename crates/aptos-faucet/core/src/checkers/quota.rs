@@ -0,0 +1,339 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    tap_captcha::check_tap_captcha, CaptchaManager, CheckerData, CheckerTrait, CompleteData,
+};
+use crate::{
+    common::{QuotaStore, QuotaStoreConfig, QuotaStoreTrait},
+    endpoints::{AptosTapError, AptosTapErrorCode, RejectionReason, RejectionReasonCode},
+    helpers::{days_since_tap_epoch, seconds_until_next_day},
+};
+use anyhow::Result;
+use aptos_logger::warn;
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tokio::task::JoinSet;
+
+/// How often we remove expired counters from the store.
+const REMOVE_EXPIRED_INTERVAL_SECS: u64 = 60 * 60;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct QuotaCheckerConfig {
+    /// Where to store the counters of requests.
+    pub store_config: QuotaStoreConfig,
+
+    /// Max number of requests per IP per day.
+    pub max_requests_per_ip_per_day: Option<u32>,
+
+    /// Max number of requests per receiver account per day.
+    pub max_requests_per_account_per_day: Option<u32>,
+
+    /// Max number of requests per day in total, across all IPs and accounts.
+    pub max_requests_per_day: Option<u32>,
+
+    /// Once an IP has made this many requests in a day, its further requests
+    /// that day must include a solved captcha (see TapCaptchaChecker). This
+    /// lets us escalate to a captcha for heavy users before rejecting them
+    /// outright. This should not be used alongside the TapCaptchaChecker,
+    /// since a captcha can only be checked once.
+    pub captcha_after_requests_per_ip_per_day: Option<u32>,
+}
+
+/// A daily limit on the number of requests of some scope, e.g. an IP.
+struct Quota {
+    /// The key of the counter of requests in the store.
+    key: String,
+    /// A description of the scope for rejection reasons, e.g. "IP 1.2.3.4".
+    scope: String,
+    /// If None, the requests are only counted, e.g. for captcha escalation.
+    max_requests_per_day: Option<u32>,
+    rejection_reason_code: RejectionReasonCode,
+}
+
+impl Quota {
+    fn is_exceeded_by(&self, requests_today: u64) -> bool {
+        self.max_requests_per_day
+            .map_or(false, |max_requests| requests_today > max_requests as u64)
+    }
+
+    fn rejection_reason(&self, seconds_until_next_day: u64) -> RejectionReason {
+        RejectionReason::new(
+            format!(
+                "{} has reached the maximum allowed number of requests per day: {}",
+                self.scope,
+                self.max_requests_per_day.unwrap_or_default()
+            ),
+            self.rejection_reason_code,
+        )
+        .retry_after(seconds_until_next_day)
+    }
+}
+
+/// The QuotaChecker enforces daily limits on the number of requests per IP,
+/// per receiver account and in total. The counters are kept in a persistent
+/// store (Redis or sled), see QuotaStoreConfig. Like the RedisRatelimitChecker,
+/// requests that result in a 500 are not counted, because they are not the
+/// user's fault.
+///
+/// To avoid incrementing counters unnecessarily, we first read them and only
+/// increment them if no quota has been exhausted. Since incrementing is atomic,
+/// we check the incremented values again, which ensures concurrent requests
+/// can't exceed the quotas.
+pub struct QuotaChecker {
+    config: QuotaCheckerConfig,
+    store: Arc<QuotaStore>,
+
+    /// Reference to the one captcha manager, used for captcha escalation.
+    captcha_manager: Arc<Mutex<CaptchaManager>>,
+}
+
+impl QuotaChecker {
+    pub async fn new(
+        config: QuotaCheckerConfig,
+        captcha_manager: Arc<Mutex<CaptchaManager>>,
+    ) -> Result<Self> {
+        let store = Arc::new(config.store_config.clone().build().await?);
+        Ok(Self {
+            config,
+            store,
+            captcha_manager,
+        })
+    }
+
+    /// Returns the quotas that apply to the request, for the day it was
+    /// received. The IP quota is always first, if there is one.
+    fn get_quotas(&self, data: &CheckerData) -> Vec<Quota> {
+        let day = days_since_tap_epoch(data.time_request_received_secs);
+        let mut quotas = vec![];
+        if self.config.max_requests_per_ip_per_day.is_some()
+            || self.config.captcha_after_requests_per_ip_per_day.is_some()
+        {
+            quotas.push(Quota {
+                key: format!("quota:ip:{}:{}", data.source_ip, day),
+                scope: format!("IP {}", data.source_ip),
+                max_requests_per_day: self.config.max_requests_per_ip_per_day,
+                rejection_reason_code: RejectionReasonCode::IpUsageLimitExhausted,
+            });
+        }
+        if let Some(max_requests) = self.config.max_requests_per_account_per_day {
+            quotas.push(Quota {
+                key: format!("quota:account:{}:{}", data.receiver, day),
+                scope: format!("Account {}", data.receiver),
+                max_requests_per_day: Some(max_requests),
+                rejection_reason_code: RejectionReasonCode::AccountUsageLimitExhausted,
+            });
+        }
+        if let Some(max_requests) = self.config.max_requests_per_day {
+            quotas.push(Quota {
+                key: format!("quota:global:{}", day),
+                scope: "The faucet".to_string(),
+                max_requests_per_day: Some(max_requests),
+                rejection_reason_code: RejectionReasonCode::UsageLimitExhausted,
+            });
+        }
+        quotas
+    }
+
+    /// Decrements the counters of the given quotas, i.e. undoes counting a
+    /// request against them.
+    async fn uncount_request(&self, quotas: &[Quota]) -> Result<(), AptosTapError> {
+        for quota in quotas {
+            self.store
+                .decrement(&quota.key)
+                .await
+                .map_err(storage_error)?;
+        }
+        Ok(())
+    }
+}
+
+fn storage_error(e: anyhow::Error) -> AptosTapError {
+    AptosTapError::new_with_error_code(e, AptosTapErrorCode::StorageError)
+}
+
+#[async_trait]
+impl CheckerTrait for QuotaChecker {
+    async fn check(
+        &self,
+        data: CheckerData,
+        dry_run: bool,
+    ) -> Result<Vec<RejectionReason>, AptosTapError> {
+        let quotas = self.get_quotas(&data);
+        let seconds_until_next_day = seconds_until_next_day(data.time_request_received_secs);
+
+        // Check whether this request would exceed any of the quotas.
+        let mut requests_today = vec![];
+        for quota in &quotas {
+            requests_today.push(self.store.get(&quota.key).await.map_err(storage_error)?);
+        }
+        let rejection_reasons: Vec<_> = quotas
+            .iter()
+            .zip(&requests_today)
+            .filter(|(quota, requests_today)| quota.is_exceeded_by(**requests_today + 1))
+            .map(|(quota, _)| quota.rejection_reason(seconds_until_next_day))
+            .collect();
+        if !rejection_reasons.is_empty() {
+            return Ok(rejection_reasons);
+        }
+
+        // Require a captcha if the IP has already made enough requests today.
+        if let Some(captcha_after_requests) = self.config.captcha_after_requests_per_ip_per_day {
+            let ip_requests_today = requests_today.first().copied().unwrap_or_default();
+            if ip_requests_today >= captcha_after_requests as u64 {
+                let rejection_reasons =
+                    check_tap_captcha(&self.captcha_manager, &data.headers).await?;
+                if !rejection_reasons.is_empty() {
+                    return Ok(rejection_reasons);
+                }
+            }
+        }
+
+        if dry_run {
+            return Ok(vec![]);
+        }
+
+        // Count the request, and check the quotas again, in case concurrent
+        // requests were counted since we read the counters.
+        let mut rejection_reasons = vec![];
+        for quota in &quotas {
+            let requests_today = self
+                .store
+                .increment(&quota.key, seconds_until_next_day)
+                .await
+                .map_err(storage_error)?;
+            if quota.is_exceeded_by(requests_today) {
+                rejection_reasons.push(quota.rejection_reason(seconds_until_next_day));
+            }
+        }
+
+        // A rejected request must not use up any quota, e.g. the IP quota
+        // shouldn't be consumed when the faucet as a whole is out of quota.
+        if !rejection_reasons.is_empty() {
+            self.uncount_request(&quotas).await?;
+        }
+        Ok(rejection_reasons)
+    }
+
+    /// All we have to do here is decrement the counters if the request was a
+    /// failure due to something wrong on our end.
+    async fn complete(&self, data: CompleteData) -> Result<(), AptosTapError> {
+        if !data.response_is_500 {
+            return Ok(());
+        }
+        self.uncount_request(&self.get_quotas(&data.checker_data))
+            .await
+    }
+
+    fn cost(&self) -> u8 {
+        50
+    }
+
+    fn spawn_periodic_tasks(&self, join_set: &mut JoinSet<anyhow::Result<()>>) {
+        let store = self.store.clone();
+        join_set.spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(REMOVE_EXPIRED_INTERVAL_SECS)).await;
+                // Failing to clean up isn't fatal, so we just try again later.
+                if let Err(e) = store.remove_expired().await {
+                    warn!("Failed to remove expired quota counters: {:#}", e);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::SledQuotaStoreConfig;
+    use aptos_sdk::types::account_address::AccountAddress;
+    use aptos_temppath::TempPath;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    async fn new_quota_checker(db_dir: &TempPath) -> QuotaChecker {
+        let config = QuotaCheckerConfig {
+            store_config: QuotaStoreConfig::Sled(SledQuotaStoreConfig {
+                path: db_dir.path().to_path_buf(),
+            }),
+            max_requests_per_ip_per_day: Some(2),
+            max_requests_per_account_per_day: None,
+            max_requests_per_day: Some(2),
+            captcha_after_requests_per_ip_per_day: None,
+        };
+        QuotaChecker::new(config, Arc::new(Mutex::new(CaptchaManager::new())))
+            .await
+            .unwrap()
+    }
+
+    fn checker_data(ip: u8) -> CheckerData {
+        CheckerData {
+            time_request_received_secs: crate::helpers::get_current_time_secs(),
+            receiver: AccountAddress::random(),
+            source_ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, ip)),
+            headers: Arc::new(Default::default()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_requests_use_no_quota() {
+        let db_dir = TempPath::new();
+        let checker = new_quota_checker(&db_dir).await;
+
+        // Many concurrent requests from different IPs race for the global
+        // quota. Whether they're rejected before or after being counted, the
+        // rejected ones must not use up any IP quota.
+        let data: Vec<_> = (0..10).map(checker_data).collect();
+        let results =
+            futures::future::join_all(data.iter().map(|data| checker.check(data.clone(), false)))
+                .await;
+        let mut num_accepted = 0;
+        let mut num_ip_requests = 0;
+        for (data, rejection_reasons) in data.iter().zip(results) {
+            if rejection_reasons.unwrap().is_empty() {
+                num_accepted += 1;
+            }
+            let quotas = checker.get_quotas(data);
+            num_ip_requests += checker.store.get(&quotas[0].key).await.unwrap();
+        }
+        assert_eq!(num_accepted, 2);
+        assert_eq!(num_ip_requests, 2);
+        let quotas = checker.get_quotas(&data[0]);
+        assert_eq!(checker.store.get(&quotas[1].key).await.unwrap(), 2);
+
+        // Requests that resulted in a 500 don't use any quota either.
+        checker
+            .complete(CompleteData {
+                checker_data: data[0].clone(),
+                txn_hashes: vec![],
+                response_is_500: true,
+            })
+            .await
+            .unwrap();
+        assert_eq!(checker.store.get(&quotas[1].key).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_remove_expired() {
+        let db_dir = TempPath::new();
+        let store = QuotaStoreConfig::Sled(SledQuotaStoreConfig {
+            path: db_dir.path().to_path_buf(),
+        })
+        .build()
+        .await
+        .unwrap();
+
+        // Counters that expire now are removed, but live ones are kept.
+        assert_eq!(store.increment("expired", 0).await.unwrap(), 1);
+        assert_eq!(store.increment("live", 60).await.unwrap(), 1);
+        store.remove_expired().await.unwrap();
+        assert_eq!(store.get("expired").await.unwrap(), 0);
+        assert_eq!(store.get("live").await.unwrap(), 1);
+
+        // Decrementing a missing counter doesn't create it.
+        store.decrement("missing").await.unwrap();
+        assert_eq!(store.increment("missing", 60).await.unwrap(), 1);
+    }
+}
@@ -17,6 +17,7 @@ use captcha::{
     Captcha,
 };
 use futures::lock::Mutex;
+use poem::http::HeaderMap;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
@@ -52,70 +53,77 @@ impl CheckerTrait for TapCaptchaChecker {
         data: CheckerData,
         _dry_run: bool,
     ) -> Result<Vec<RejectionReason>, AptosTapError> {
-        let captcha_key = match data.headers.get(CAPTCHA_KEY) {
-            Some(header_value) => match header_value
-                .to_str()
-                .map_err(|e| {
-                    AptosTapError::new_with_error_code(e, AptosTapErrorCode::InvalidRequest)
-                })?
-                .parse::<u32>()
-            {
-                Ok(value) => value,
-                Err(e) => {
-                    return Ok(vec![RejectionReason::new(
-                        format!("Captcha value not a number: {:#}", e),
-                        RejectionReasonCode::CaptchaInvalid,
-                    )])
-                },
-            },
-            None => {
-                return Ok(vec![RejectionReason::new(
-                    format!("Captcha header {} not found", CAPTCHA_KEY),
-                    RejectionReasonCode::CaptchaInvalid,
-                )])
-            },
-        };
+        check_tap_captcha(&self.captcha_manager, &data.headers).await
+    }
 
-        let captcha_value = match data.headers.get(CAPTCHA_VALUE) {
-            Some(header_value) => header_value.to_str().map_err(|e| {
-                AptosTapError::new_with_error_code(e, AptosTapErrorCode::InvalidRequest)
-            })?,
-            None => {
-                return Ok(vec![RejectionReason::new(
-                    format!("Captcha header {} not found", CAPTCHA_VALUE),
-                    RejectionReasonCode::CaptchaInvalid,
-                )])
-            },
-        };
+    fn cost(&self) -> u8 {
+        3
+    }
+}
 
-        let captcha_correct = match self
-            .captcha_manager
-            .lock()
-            .await
-            .check_challenge(captcha_key, captcha_value)
+/// Checks that the headers contain the key of a captcha challenge and its
+/// correct value. This is also used by other Checkers that require a captcha
+/// only in some cases, e.g. the QuotaChecker.
+pub async fn check_tap_captcha(
+    captcha_manager: &Mutex<CaptchaManager>,
+    headers: &HeaderMap,
+) -> Result<Vec<RejectionReason>, AptosTapError> {
+    let captcha_key = match headers.get(CAPTCHA_KEY) {
+        Some(header_value) => match header_value
+            .to_str()
+            .map_err(|e| AptosTapError::new_with_error_code(e, AptosTapErrorCode::InvalidRequest))?
+            .parse::<u32>()
         {
-            Ok(correct) => correct,
+            Ok(value) => value,
             Err(e) => {
                 return Ok(vec![RejectionReason::new(
-                    format!("Captcha key unknown: {}", e),
+                    format!("Captcha value not a number: {:#}", e),
                     RejectionReasonCode::CaptchaInvalid,
                 )])
             },
-        };
-
-        if !captcha_correct {
+        },
+        None => {
             return Ok(vec![RejectionReason::new(
-                format!("Captcha value {} incorrect", captcha_value),
+                format!("Captcha header {} not found", CAPTCHA_KEY),
                 RejectionReasonCode::CaptchaInvalid,
-            )]);
-        }
-
-        Ok(vec![])
+            )])
+        },
+    };
+
+    let captcha_value = match headers.get(CAPTCHA_VALUE) {
+        Some(header_value) => header_value.to_str().map_err(|e| {
+            AptosTapError::new_with_error_code(e, AptosTapErrorCode::InvalidRequest)
+        })?,
+        None => {
+            return Ok(vec![RejectionReason::new(
+                format!("Captcha header {} not found", CAPTCHA_VALUE),
+                RejectionReasonCode::CaptchaInvalid,
+            )])
+        },
+    };
+
+    let captcha_correct = match captcha_manager
+        .lock()
+        .await
+        .check_challenge(captcha_key, captcha_value)
+    {
+        Ok(correct) => correct,
+        Err(e) => {
+            return Ok(vec![RejectionReason::new(
+                format!("Captcha key unknown: {}", e),
+                RejectionReasonCode::CaptchaInvalid,
+            )])
+        },
+    };
+
+    if !captcha_correct {
+        return Ok(vec![RejectionReason::new(
+            format!("Captcha value {} incorrect", captcha_value),
+            RejectionReasonCode::CaptchaInvalid,
+        )]);
     }
 
-    fn cost(&self) -> u8 {
-        3
-    }
+    Ok(vec![])
 }
 
 /// CaptchaManager is responsible for creating captcha challenges and later
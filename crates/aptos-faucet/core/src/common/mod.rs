@@ -3,6 +3,10 @@
 
 mod ip_range_manager;
 mod list_manager;
+mod quota_store;
+mod redis_connection;
 
 pub use ip_range_manager::{IpRangeManager, IpRangeManagerConfig};
 pub use list_manager::{ListManager, ListManagerConfig};
+pub use quota_store::{QuotaStore, QuotaStoreConfig, QuotaStoreTrait, SledQuotaStoreConfig};
+pub use redis_connection::RedisConnectionConfig;
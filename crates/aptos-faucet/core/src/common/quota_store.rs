@@ -0,0 +1,335 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::RedisConnectionConfig;
+use crate::helpers::get_current_time_secs;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use deadpool_redis::{
+    redis::{self, AsyncCommands},
+    Connection, Pool,
+};
+use enum_dispatch::enum_dispatch;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The name of the sled tree that indexes the counters by their expiration.
+const SLED_EXPIRATIONS_TREE: &str = "expirations";
+
+/// Decrements the counter, unless it doesn't exist (or is already 0). A plain
+/// DECR would create a counter of -1 without a TTL for a missing key.
+const REDIS_DECREMENT_SCRIPT: &str = r"
+local value = redis.call('GET', KEYS[1])
+if value and tonumber(value) > 0 then
+    return redis.call('DECR', KEYS[1])
+end
+return 0
+";
+
+/// A store of counters that expire, e.g. the number of requests an IP has
+/// made today. Implementations must increment and decrement counters
+/// atomically, since many requests can be handled concurrently.
+#[async_trait]
+#[enum_dispatch]
+pub trait QuotaStoreTrait: Sync + Send + 'static {
+    /// Returns the value of the counter, which is 0 if it doesn't exist or
+    /// has expired.
+    async fn get(&self, key: &str) -> Result<u64>;
+
+    /// Increments the counter and returns its new value. If the counter
+    /// doesn't exist, it is created and expires after the given number of
+    /// seconds.
+    async fn increment(&self, key: &str, expire_after_secs: u64) -> Result<u64>;
+
+    /// Decrements the counter, if it exists.
+    async fn decrement(&self, key: &str) -> Result<()>;
+
+    /// Removes the expired counters. This only needs to be implemented if
+    /// the store doesn't remove them itself.
+    async fn remove_expired(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum QuotaStoreConfig {
+    /// Counters are stored in Redis, so they can be shared by many instances
+    /// of the faucet.
+    Redis(RedisConnectionConfig),
+
+    /// Counters are stored in a local sled database, so they persist across
+    /// restarts of a single instance of the faucet.
+    Sled(SledQuotaStoreConfig),
+}
+
+impl QuotaStoreConfig {
+    pub async fn build(self) -> Result<QuotaStore> {
+        Ok(match self {
+            QuotaStoreConfig::Redis(config) => {
+                QuotaStore::from(RedisQuotaStore::new(config).await?)
+            },
+            QuotaStoreConfig::Sled(config) => QuotaStore::from(SledQuotaStore::new(config)?),
+        })
+    }
+}
+
+/// This enum has as its variants all possible implementations of QuotaStoreTrait.
+#[enum_dispatch(QuotaStoreTrait)]
+pub enum QuotaStore {
+    RedisQuotaStore,
+    SledQuotaStore,
+}
+
+/// Stores counters in Redis, where they expire using TTLs.
+pub struct RedisQuotaStore {
+    db_pool: Pool,
+}
+
+impl RedisQuotaStore {
+    pub async fn new(config: RedisConnectionConfig) -> Result<Self> {
+        let db_pool = config.build_db_pool()?;
+
+        // Ensure we can connect.
+        db_pool
+            .get()
+            .await
+            .context("Failed to connect to redis on startup")?;
+
+        Ok(Self { db_pool })
+    }
+
+    async fn get_redis_connection(&self) -> Result<Connection> {
+        self.db_pool
+            .get()
+            .await
+            .context("Failed to connect to redis storage")
+    }
+}
+
+#[async_trait]
+impl QuotaStoreTrait for RedisQuotaStore {
+    async fn get(&self, key: &str) -> Result<u64> {
+        let mut conn = self.get_redis_connection().await?;
+        let value: Option<i64> = conn
+            .get(key)
+            .await
+            .with_context(|| format!("Failed to get value for redis key {}", key))?;
+        Ok(value.unwrap_or(0).max(0) as u64)
+    }
+
+    async fn increment(&self, key: &str, expire_after_secs: u64) -> Result<u64> {
+        let mut conn = self.get_redis_connection().await?;
+        // Counters are only created when they're incremented, so we set the
+        // expiration in the same transaction. Only setting it if there isn't
+        // one already (NX) requires Redis 7, so we instead set it every time,
+        // which is fine because callers use the same expiration for a key.
+        let (value,): (i64,) = redis::pipe()
+            .atomic()
+            .incr(key, 1)
+            .expire(key, expire_after_secs as usize)
+            .ignore()
+            .query_async(&mut *conn)
+            .await
+            .with_context(|| format!("Failed to increment value for redis key {}", key))?;
+        Ok(value.max(0) as u64)
+    }
+
+    async fn decrement(&self, key: &str) -> Result<()> {
+        let mut conn = self.get_redis_connection().await?;
+        let _: i64 = redis::Script::new(REDIS_DECREMENT_SCRIPT)
+            .key(key)
+            .invoke_async(&mut *conn)
+            .await
+            .with_context(|| format!("Failed to decrement value for redis key {}", key))?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SledQuotaStoreConfig {
+    /// Path to the directory of the database. It is created if it doesn't
+    /// exist.
+    pub path: PathBuf,
+}
+
+/// Stores counters in a local sled database. Each value holds the counter
+/// and the unixtime (in seconds) at which it expires. The counters are also
+/// indexed by their expiration, so expired counters can be removed without
+/// scanning all of them. Since sled is blocking, it is only accessed from
+/// blocking tasks.
+pub struct SledQuotaStore {
+    db: sled::Db,
+    /// Keys are the expiration (big endian, so they're ordered) followed by
+    /// the key of the counter. Values are empty.
+    expirations: sled::Tree,
+}
+
+/// A counter as stored in sled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct SledCounter {
+    value: u64,
+    expires_at_secs: u64,
+}
+
+impl SledCounter {
+    fn to_bytes(self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&self.value.to_be_bytes());
+        bytes[8..].copy_from_slice(&self.expires_at_secs.to_be_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 16 {
+            return None;
+        }
+        Some(Self {
+            value: u64::from_be_bytes(bytes[..8].try_into().ok()?),
+            expires_at_secs: u64::from_be_bytes(bytes[8..].try_into().ok()?),
+        })
+    }
+
+    /// Returns the counter, unless it has expired (or is malformed).
+    fn from_live_bytes(bytes: Option<&[u8]>, now_secs: u64) -> Option<Self> {
+        bytes
+            .and_then(Self::from_bytes)
+            .filter(|counter| counter.expires_at_secs > now_secs)
+    }
+}
+
+impl SledQuotaStore {
+    pub fn new(config: SledQuotaStoreConfig) -> Result<Self> {
+        let db = sled::open(&config.path).with_context(|| {
+            format!(
+                "Failed to open sled database at {}",
+                config.path.to_string_lossy()
+            )
+        })?;
+        let expirations = db
+            .open_tree(SLED_EXPIRATIONS_TREE)
+            .context("Failed to open the sled expirations tree")?;
+        Ok(Self { db, expirations })
+    }
+
+    /// Runs the given function on a blocking task, so the executor isn't
+    /// blocked by sled.
+    async fn run_blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(sled::Db, sled::Tree) -> Result<T> + Send + 'static,
+    {
+        let db = self.db.clone();
+        let expirations = self.expirations.clone();
+        tokio::task::spawn_blocking(move || f(db, expirations))
+            .await
+            .context("Failed to run sled task")?
+    }
+}
+
+fn expiration_key(expires_at_secs: u64, key: &[u8]) -> Vec<u8> {
+    [&expires_at_secs.to_be_bytes()[..], key].concat()
+}
+
+#[async_trait]
+impl QuotaStoreTrait for SledQuotaStore {
+    async fn get(&self, key: &str) -> Result<u64> {
+        let key = key.to_string();
+        self.run_blocking(move |db, _| {
+            let bytes = db
+                .get(&key)
+                .with_context(|| format!("Failed to get value for sled key {}", key))?;
+            Ok(
+                SledCounter::from_live_bytes(bytes.as_deref(), get_current_time_secs())
+                    .map_or(0, |counter| counter.value),
+            )
+        })
+        .await
+    }
+
+    async fn increment(&self, key: &str, expire_after_secs: u64) -> Result<u64> {
+        let key = key.to_string();
+        self.run_blocking(move |db, expirations| {
+            let now_secs = get_current_time_secs();
+            let bytes = db
+                .update_and_fetch(&key, |bytes| {
+                    let counter = match SledCounter::from_live_bytes(bytes, now_secs) {
+                        Some(counter) => SledCounter {
+                            value: counter.value + 1,
+                            ..counter
+                        },
+                        None => SledCounter {
+                            value: 1,
+                            expires_at_secs: now_secs + expire_after_secs,
+                        },
+                    };
+                    Some(counter.to_bytes().to_vec())
+                })
+                .with_context(|| format!("Failed to increment value for sled key {}", key))?;
+            let counter = match bytes.as_deref().and_then(SledCounter::from_bytes) {
+                Some(counter) => counter,
+                None => return Ok(0),
+            };
+
+            // Index the expiration of new counters (re-indexing is harmless).
+            if counter.value == 1 {
+                expirations
+                    .insert(
+                        expiration_key(counter.expires_at_secs, key.as_bytes()),
+                        &[] as &[u8],
+                    )
+                    .context("Failed to index the expiration of a sled counter")?;
+            }
+            Ok(counter.value)
+        })
+        .await
+    }
+
+    async fn decrement(&self, key: &str) -> Result<()> {
+        let key = key.to_string();
+        self.run_blocking(move |db, _| {
+            let now_secs = get_current_time_secs();
+            db.update_and_fetch(&key, |bytes| {
+                // Expired counters are left as they are, to be removed later.
+                let bytes = bytes?;
+                let counter = match SledCounter::from_live_bytes(Some(bytes), now_secs) {
+                    Some(counter) => SledCounter {
+                        value: counter.value.saturating_sub(1),
+                        ..counter
+                    },
+                    None => return Some(bytes.to_vec()),
+                };
+                Some(counter.to_bytes().to_vec())
+            })
+            .with_context(|| format!("Failed to decrement value for sled key {}", key))?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn remove_expired(&self) -> Result<()> {
+        self.run_blocking(|db, expirations| {
+            let now_secs = get_current_time_secs();
+            // Counters expire once the current time reaches their expiration.
+            let end = (now_secs + 1).to_be_bytes();
+            for entry in expirations.range(..&end[..]) {
+                let (expiration_key, _) = entry.context("Failed to read from sled")?;
+                let key = &expiration_key[8..];
+                if let Some(bytes) = db.get(key).context("Failed to read from sled")? {
+                    if SledCounter::from_live_bytes(Some(&bytes), now_secs).is_none() {
+                        // Only remove the counter if it wasn't recreated in the meantime.
+                        // If the swap fails, the counter is live (and indexed again).
+                        let _ = db
+                            .compare_and_swap(key, Some(&bytes), None as Option<&[u8]>)
+                            .context("Failed to remove expired value from sled")?;
+                    }
+                }
+                expirations
+                    .remove(&expiration_key)
+                    .context("Failed to remove expiration from sled")?;
+            }
+            Ok(())
+        })
+        .await
+    }
+}
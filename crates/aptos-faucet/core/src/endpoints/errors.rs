@@ -64,7 +64,7 @@ impl AptosTapError {
     pub fn status_and_retry_after(&self) -> (StatusCode, Option<u64>) {
         let (mut status_code, mut retry_after) = (self.error_code.status(), None);
         for rejection_reason in &self.rejection_reasons {
-            if rejection_reason.code == RejectionReasonCode::IpUsageLimitExhausted {
+            if rejection_reason.code.is_usage_limit() {
                 status_code = StatusCode::TOO_MANY_REQUESTS;
                 retry_after = rejection_reason.retry_after;
                 break;
@@ -256,4 +256,23 @@ pub enum RejectionReasonCode {
 
     /// Referer was in the blocklist.
     RefererBlocklisted = 108,
+
+    /// Account has exhausted its usage limit.
+    AccountUsageLimitExhausted = 109,
+
+    /// The faucet has exhausted its usage limit.
+    UsageLimitExhausted = 110,
+}
+
+impl RejectionReasonCode {
+    /// Whether the client was rejected because it exhausted a usage limit,
+    /// meaning it can try again later.
+    pub fn is_usage_limit(&self) -> bool {
+        matches!(
+            self,
+            RejectionReasonCode::IpUsageLimitExhausted
+                | RejectionReasonCode::AccountUsageLimitExhausted
+                | RejectionReasonCode::UsageLimitExhausted
+        )
+    }
 }
@@ -4,8 +4,8 @@
 
 use super::{FunderHealthMessage, FunderTrait};
 use crate::endpoints::{AptosTapError, AptosTapErrorCode};
-use anyhow::{Context, Result};
-use aptos_logger::info;
+use anyhow::{bail, Context, Result};
+use aptos_logger::{info, warn};
 use aptos_sdk::{
     crypto::ed25519::Ed25519PublicKey,
     rest_client::Client,
@@ -22,7 +22,8 @@ use aptos_sdk::{
 use async_trait::async_trait;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::{Mutex, RwLock};
 
 static MINTER_SCRIPT: &[u8] = include_bytes!(
     "../../../../../aptos-move/move-examples/scripts/minter/build/Minter/bytecode_scripts/main.mv"
@@ -50,10 +51,21 @@ pub struct MintFunderConfig {
     /// Just use the account given in funder args, don't make a new one and
     /// delegate the mint capability to it.
     pub do_not_delegate: bool,
+
+    /// If given, every time this many requests have been funded, we make a new
+    /// account, delegate the mint capability to it and use it from then on. This
+    /// limits how many transactions are sent from a single minter account (and
+    /// how long its key is in use). This requires delegation.
+    #[serde(default)]
+    pub rotate_delegated_account_after_requests: Option<u64>,
 }
 
 impl MintFunderConfig {
     pub async fn build_funder(self) -> Result<MintFunder> {
+        if self.do_not_delegate && self.rotate_delegated_account_after_requests.is_some() {
+            bail!("rotate_delegated_account_after_requests requires delegation, but do_not_delegate is set");
+        }
+
         let key = self.api_connection_config.get_key()?;
 
         let faucet_account = LocalAccount::new(
@@ -77,6 +89,8 @@ impl MintFunderConfig {
                 .await
                 .context("Failed to make MintFunder use delegated account")?;
         }
+        minter.rotate_delegated_account_after_requests =
+            self.rotate_delegated_account_after_requests;
 
         Ok(minter)
     }
@@ -90,6 +104,18 @@ pub struct MintFunder {
 
     faucet_account: RwLock<LocalAccount>,
 
+    /// The account that delegated the mint capability to the faucet account,
+    /// if we use a delegated account. We keep it to rotate the delegated account.
+    delegator_account: Option<RwLock<LocalAccount>>,
+
+    /// See `MintFunderConfig`.
+    rotate_delegated_account_after_requests: Option<u64>,
+
+    /// How many requests have been funded since the delegated account was last
+    /// rotated, and a lock to ensure only one rotation happens at a time.
+    requests_since_rotation: AtomicU64,
+    rotation_lock: Mutex<()>,
+
     transaction_factory: TransactionFactory,
 
     gas_unit_price_manager: GasUnitPriceManager,
@@ -115,6 +141,10 @@ impl MintFunder {
             node_url,
             txn_config,
             faucet_account: RwLock::new(faucet_account),
+            delegator_account: None,
+            rotate_delegated_account_after_requests: None,
+            requests_since_rotation: AtomicU64::new(0),
+            rotation_lock: Mutex::new(()),
             transaction_factory,
             gas_unit_price_manager,
             outstanding_requests: RwLock::new(vec![]),
@@ -141,8 +171,34 @@ impl MintFunder {
             .with_gas_unit_price(self.get_gas_unit_price().await?))
     }
 
-    /// todo explain / rename
+    /// Make a new account, delegate the mint capability of the current faucet
+    /// account to it, and use the new account from then on.
     pub async fn use_delegated_account(&mut self) -> Result<()> {
+        let delegated_account = self.create_delegated_account(&self.faucet_account).await?;
+        let delegator_account =
+            std::mem::replace(&mut self.faucet_account, RwLock::new(delegated_account));
+        self.delegator_account = Some(delegator_account);
+        Ok(())
+    }
+
+    /// Replace the delegated account with a new one, which the mint capability
+    /// is delegated to by the same delegator account.
+    pub async fn rotate_delegated_account(&self) -> Result<()> {
+        let delegator_account = match &self.delegator_account {
+            Some(delegator_account) => delegator_account,
+            None => bail!("Cannot rotate the account of a MintFunder that doesn't delegate"),
+        };
+        let delegated_account = self.create_delegated_account(delegator_account).await?;
+        *self.faucet_account.write().await = delegated_account;
+        Ok(())
+    }
+
+    /// Create a new random account (funded by the current faucet account), have
+    /// the delegator account delegate the mint capability to it, and claim it.
+    async fn create_delegated_account(
+        &self,
+        delegator_account: &RwLock<LocalAccount>,
+    ) -> Result<LocalAccount> {
         // Build a client.
         let client = self.get_api_client();
 
@@ -170,9 +226,20 @@ impl MintFunder {
 
         // Delegate minting to the account
         {
-            let mut faucet_account = self.faucet_account.write().await;
+            let mut delegator_account = delegator_account.write().await;
+
+            // The delegator might not have sent a transaction in a while (or
+            // ever, if it's the faucet account), so we get its sequence number.
+            let sequence_number = client
+                .get_account(delegator_account.address())
+                .await
+                .context("Failed to get the sequence number of the delegator account")?
+                .into_inner()
+                .sequence_number;
+            *delegator_account.sequence_number_mut() = sequence_number;
+
             client
-                .submit_and_wait(&faucet_account.sign_with_transaction_builder(
+                .submit_and_wait(&delegator_account.sign_with_transaction_builder(
                     transaction_factory.payload(aptos_stdlib::aptos_coin_delegate_mint_capability(
                         delegated_account.address(),
                     )),
@@ -194,9 +261,32 @@ impl MintFunder {
             delegated_account.address().to_hex_literal()
         );
 
-        self.faucet_account = RwLock::new(delegated_account);
+        Ok(delegated_account)
+    }
 
-        Ok(())
+    /// Rotate the delegated account if enough requests have been funded since
+    /// the last rotation. If a rotation is already in progress, or it fails, we
+    /// just keep using the current account.
+    async fn maybe_rotate_delegated_account(&self) {
+        let rotate_after_requests = match self.rotate_delegated_account_after_requests {
+            Some(rotate_after_requests) => rotate_after_requests,
+            None => return,
+        };
+        if self.requests_since_rotation.fetch_add(1, Ordering::Relaxed) + 1 < rotate_after_requests
+        {
+            return;
+        }
+        let _rotation_guard = match self.rotation_lock.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return,
+        };
+        match self.rotate_delegated_account().await {
+            Ok(()) => self.requests_since_rotation.store(0, Ordering::Relaxed),
+            Err(e) => warn!(
+                "Failed to rotate the delegated account of the MintFunder: {:#}",
+                e
+            ),
+        }
     }
 
     /// Within a single request we should just call this once and use this client
@@ -274,14 +364,19 @@ impl FunderTrait for MintFunder {
     ) -> Result<Vec<SignedTransaction>, AptosTapError> {
         let client = self.get_api_client();
         let amount = self.get_amount(amount, did_bypass_checkers);
-        self.process(
-            &client,
-            amount,
-            receiver_address,
-            check_only,
-            self.txn_config.wait_for_transactions,
-        )
-        .await
+        let txns = self
+            .process(
+                &client,
+                amount,
+                receiver_address,
+                check_only,
+                self.txn_config.wait_for_transactions,
+            )
+            .await?;
+        if !check_only {
+            self.maybe_rotate_delegated_account().await;
+        }
+        Ok(txns)
     }
 
     fn get_amount(&self, amount: Option<u64>, did_bypass_checkers: bool) -> u64 {
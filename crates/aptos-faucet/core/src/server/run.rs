@@ -277,6 +277,7 @@ impl RunConfig {
                 ),
                 mint_account_address: Some(aptos_test_root_address()),
                 do_not_delegate,
+                rotate_delegated_account_after_requests: None,
             }),
             handler_config: HandlerConfig {
                 use_helpful_errors: true,
@@ -366,6 +367,7 @@ impl RunSimple {
 mod test {
     use super::*;
     use crate::{
+        common::QuotaStoreConfig,
         endpoints::{
             AptosTapError, AptosTapErrorCode, FundRequest, FundResponse, RejectionReasonCode,
         },
@@ -376,6 +378,7 @@ mod test {
         crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, Uniform},
         types::{account_address::AccountAddress, transaction::authenticator::AuthenticationKey},
     };
+    use aptos_temppath::TempPath;
     use once_cell::sync::OnceCell;
     use poem::http::header::{AUTHORIZATION, CONTENT_TYPE, REFERER};
     use poem_openapi::types::{ParseFromJSON, ToJSON};
@@ -413,10 +416,16 @@ mod test {
     }
 
     async fn start_server(config_content: &'static str) -> Result<(u16, JoinHandle<Result<()>>)> {
-        // Load config.
-        let run_config: RunConfig =
-            serde_yaml::from_str(config_content).context("Failed to parse config content")?;
+        start_server_with_config(load_config(config_content)?).await
+    }
+
+    fn load_config(config_content: &'static str) -> Result<RunConfig> {
+        serde_yaml::from_str(config_content).context("Failed to parse config content")
+    }
 
+    async fn start_server_with_config(
+        run_config: RunConfig,
+    ) -> Result<(u16, JoinHandle<Result<()>>)> {
         // Spawn server.
         let runtime_handle = tokio::runtime::Handle::current();
         let port = aptos_config::utils::get_available_port();
@@ -679,6 +688,65 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_quota_checker() -> Result<()> {
+        init();
+        // Start with no counters, by storing them in a new directory.
+        let db_dir = TempPath::new();
+        let mut run_config = load_config(include_str!("../../../configs/testing_quota.yaml"))?;
+        for checker_config in &mut run_config.checker_configs {
+            if let CheckerConfig::Quota(config) = checker_config {
+                if let QuotaStoreConfig::Sled(config) = &mut config.store_config {
+                    config.path = db_dir.path().to_path_buf();
+                }
+            }
+        }
+        let (port, _handle) = start_server_with_config(run_config).await?;
+
+        async fn fund(port: u16, address: AccountAddress) -> Result<reqwest::Response> {
+            let fund_request = FundRequest {
+                amount: Some(10),
+                address: Some(address.to_string()),
+                ..Default::default()
+            };
+            Ok(reqwest::Client::new()
+                .post(get_fund_endpoint(port))
+                .body(fund_request.to_json_string())
+                .header(CONTENT_TYPE, "application/json")
+                .send()
+                .await?)
+        }
+
+        async fn get_rejection_reason_codes(
+            response: reqwest::Response,
+        ) -> Result<HashSet<RejectionReasonCode>> {
+            assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+            let aptos_error = AptosTapError::parse_from_json_string(&response.text().await?)
+                .expect("Failed to read response as AptosError");
+            Ok(aptos_error
+                .rejection_reasons
+                .into_iter()
+                .map(|r| r.get_code())
+                .collect())
+        }
+
+        // Assert that an account can only be funded once per day.
+        let address = AccountAddress::random();
+        unwrap_reqwest_result(Ok(fund(port, address).await?)).await?;
+        let rejection_reason_codes = get_rejection_reason_codes(fund(port, address).await?).await?;
+        assert!(rejection_reason_codes.contains(&RejectionReasonCode::AccountUsageLimitExhausted));
+
+        // Assert that the rejected request wasn't counted against the IP, so
+        // it can make 2 more requests, but not a third.
+        unwrap_reqwest_result(Ok(fund(port, AccountAddress::random()).await?)).await?;
+        unwrap_reqwest_result(Ok(fund(port, AccountAddress::random()).await?)).await?;
+        let rejection_reason_codes =
+            get_rejection_reason_codes(fund(port, AccountAddress::random()).await?).await?;
+        assert!(rejection_reason_codes.contains(&RejectionReasonCode::IpUsageLimitExhausted));
+
+        Ok(())
+    }
+
     // We skip this for now since we have no current need to use the TransferFunder.
     #[ignore]
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
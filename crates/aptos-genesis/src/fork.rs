@@ -0,0 +1,438 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates the genesis of a new chain that is forked from the state of an existing chain
+//! (e.g., mainnet) at a given version. The new chain starts with a copy of the existing state,
+//! but with the validator set replaced by the validators of the new chain, which makes it
+//! possible to run realistic staging networks and replay incidents.
+//!
+//! The state of the source chain is restored into a new database, like a state snapshot is
+//! restored by fast sync. The genesis of the new chain is then applied on top of it (at the next
+//! version), and only writes the state that differs from the source chain. This starts a new
+//! epoch with the validators of the new chain.
+
+use crate::GenesisInfo;
+use anyhow::{bail, ensure, format_err};
+use aptos_config::config::{
+    RocksdbConfigs, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_crypto::HashValue;
+use aptos_db::AptosDB;
+use aptos_logger::info;
+use aptos_storage_interface::{DbReader, DbReaderWriter, DbWriter};
+use aptos_types::{
+    access_path::{AccessPath, Path},
+    account_address::AccountAddress,
+    account_config::{aptos_test_root_address, NewEpochEvent, CORE_CODE_ADDRESS},
+    contract_event::ContractEvent,
+    on_chain_config::ConfigurationResource,
+    state_store::state_key::{StateKey, StateKeyInner},
+    timestamp::TimestampResource,
+    transaction::{ChangeSet, Transaction, Version, WriteSetPayload},
+    waypoint::Waypoint,
+    write_set::{WriteOp, WriteSetMut},
+};
+use aptos_vm::AptosVM;
+use move_core_types::{
+    language_storage::TypeTag,
+    move_resource::{MoveResource, MoveStructType},
+};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::BTreeSet,
+    path::{Path as FilePath, PathBuf},
+};
+
+/// The number of state values restored from the source database at a time
+const STATE_VALUE_CHUNK_SIZE: usize = 10_000;
+
+/// The framework resources (module and struct names) taken from the genesis of the new chain.
+/// These define the chain and its validator set, all other framework state is forked.
+const NEW_CHAIN_FRAMEWORK_RESOURCES: &[(&str, &str)] = &[
+    ("chain_id", "ChainId"),
+    ("stake", "ValidatorPerformance"),
+    ("stake", "ValidatorSet"),
+];
+
+/// Holder object for all pieces needed to generate the genesis of a forked chain
+#[derive(Clone)]
+pub struct ForkGenesisInfo {
+    /// The database of the chain to fork
+    source_db_path: PathBuf,
+    /// The version of the source chain at which the state is forked
+    source_version: Version,
+    /// The genesis of the new chain, i.e., the chain id and the validators that replace
+    /// the validator set of the source chain
+    new_chain_genesis: GenesisInfo,
+    /// The database of the new chain, which the source state is restored into
+    db_path: PathBuf,
+    /// The genesis transaction, once it's been generated
+    genesis: Option<Transaction>,
+}
+
+impl ForkGenesisInfo {
+    pub fn new(
+        source_db_path: PathBuf,
+        source_version: Version,
+        new_chain_genesis: GenesisInfo,
+        db_path: PathBuf,
+    ) -> Self {
+        ForkGenesisInfo {
+            source_db_path,
+            source_version,
+            new_chain_genesis,
+            db_path,
+            genesis: None,
+        }
+    }
+
+    /// Restores the state of the source chain into the database of the new chain. This has to
+    /// be done before generating the genesis, which is applied on top of the restored state.
+    pub fn restore_source_state(&self) -> anyhow::Result<()> {
+        restore_state(&self.source_db_path, self.source_version, &self.db_path)
+    }
+
+    pub fn get_genesis(&mut self) -> anyhow::Result<&Transaction> {
+        if self.genesis.is_none() {
+            self.genesis = Some(self.generate_genesis_txn()?);
+        }
+        Ok(self.genesis.as_ref().unwrap())
+    }
+
+    fn generate_genesis_txn(&mut self) -> anyhow::Result<Transaction> {
+        let new_chain_accounts = self.new_chain_accounts();
+        let new_chain_change_set = match self.new_chain_genesis.get_genesis() {
+            Transaction::GenesisTransaction(WriteSetPayload::Direct(change_set)) => {
+                change_set.clone()
+            },
+            transaction => bail!("Unexpected genesis transaction: {:?}", transaction),
+        };
+        let db = open_db(&self.db_path, true)?;
+        fork_genesis_txn(&db, new_chain_change_set, &new_chain_accounts)
+    }
+
+    /// Returns the accounts created by the genesis of the new chain, i.e., the root
+    /// account and the accounts of the validators.
+    fn new_chain_accounts(&self) -> BTreeSet<AccountAddress> {
+        let mut accounts = BTreeSet::from([aptos_test_root_address()]);
+        for validator in &self.new_chain_genesis.validators {
+            accounts.insert(validator.owner_address);
+            accounts.insert(validator.operator_address);
+            accounts.insert(validator.voter_address);
+        }
+        accounts
+    }
+
+    /// Generates the waypoint of the genesis, which is applied on top of the restored state
+    pub fn generate_waypoint(&mut self) -> anyhow::Result<Waypoint> {
+        let genesis = self.get_genesis()?.clone();
+        let db_rw = DbReaderWriter::new(open_db(&self.db_path, false)?);
+        aptos_executor::db_bootstrapper::generate_waypoint::<AptosVM>(&db_rw, &genesis)
+    }
+}
+
+fn open_db(db_path: &FilePath, readonly: bool) -> anyhow::Result<AptosDB> {
+    AptosDB::open(
+        db_path,
+        readonly,
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        RocksdbConfigs::default(),
+        false,
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+}
+
+/// Restores the state of the source database at the given version into the (empty) database at
+/// `db_path`, along with the transaction at that version and the ledger info that started its
+/// epoch. This leaves the database as fast sync does, so a new genesis can be applied on top.
+pub fn restore_state(
+    source_db_path: &FilePath,
+    version: Version,
+    db_path: &FilePath,
+) -> anyhow::Result<()> {
+    let source_db = open_db(source_db_path, true)?;
+    let root_hash = get_state_root_hash(&source_db, version)?;
+    let db = open_db(db_path, false)?;
+    ensure!(
+        db.get_latest_ledger_info_option()?.is_none(),
+        "The database to restore the state into ({}) must be empty",
+        db_path.display()
+    );
+
+    // Restore the state values (and the state Merkle tree)
+    let num_state_values = source_db.get_state_leaf_count(version)?;
+    info!(
+        "Restoring {} state values at version {} from the source database",
+        num_state_values, version
+    );
+    let mut receiver = db.get_state_snapshot_receiver(version, root_hash)?;
+    let mut num_restored = 0;
+    while num_restored < num_state_values {
+        let chunk = source_db.get_state_value_chunk_with_proof(
+            version,
+            num_restored,
+            STATE_VALUE_CHUNK_SIZE,
+        )?;
+        ensure!(
+            !chunk.raw_values.is_empty(),
+            "The source database returned no state values at index {} (version {})",
+            num_restored,
+            version
+        );
+        num_restored += chunk.raw_values.len();
+        receiver.add_chunk(chunk.raw_values, chunk.proof)?;
+    }
+    receiver.finish_box()?;
+
+    // Restore the transaction at the version (and the transaction accumulator up to it), and the
+    // ledger info that started its epoch
+    let ledger_version = source_db.get_latest_ledger_info()?.ledger_info().version();
+    let output_with_proof = source_db.get_transaction_outputs(version, 1, ledger_version)?;
+    let epoch = read_resource::<ConfigurationResource>(&source_db, version)?.epoch();
+    let epoch_change_proof = source_db.get_epoch_ending_ledger_infos(epoch - 1, epoch)?;
+    db.finalize_state_snapshot(
+        version,
+        output_with_proof,
+        &epoch_change_proof.ledger_info_with_sigs,
+    )?;
+    info!(
+        "Restored the state of the source database at version {}",
+        version
+    );
+    Ok(())
+}
+
+/// Verifies that the state of the source database can be forked at the version, i.e., that it
+/// has a state snapshot (taken at the end of each block) at the version that isn't pruned.
+/// Returns the root hash of the state.
+fn get_state_root_hash(source_db: &AptosDB, version: Version) -> anyhow::Result<HashValue> {
+    let latest_version = source_db.get_latest_version()?;
+    ensure!(
+        version <= latest_version,
+        "The version to fork at ({}) is ahead of the latest version of the source database ({})",
+        version,
+        latest_version
+    );
+    let first_viable_version = source_db.get_first_viable_txn_version()?;
+    ensure!(
+        version >= first_viable_version,
+        "The version to fork at ({}) is pruned from the source database, the first available \
+         version is {}",
+        version,
+        first_viable_version
+    );
+
+    match source_db.get_state_snapshot_before(version + 1)? {
+        Some((snapshot_version, root_hash)) if snapshot_version == version => Ok(root_hash),
+        Some((snapshot_version, _)) => bail!(
+            "The source database has no state snapshot at version {} (i.e., it's not the end \
+             of a block), the closest earlier one is at version {}",
+            version,
+            snapshot_version
+        ),
+        None => bail!(
+            "The source database has no state snapshot at or before version {}",
+            version
+        ),
+    }
+}
+
+/// Generates the genesis transaction of the forked chain, on top of the latest state of the
+/// restored database. The genesis overrides the state that defines the new chain (see
+/// `is_new_chain_state`) with that written by the genesis of the new chain, and starts a new
+/// epoch with the new validator set.
+pub fn fork_genesis_txn(
+    db: &dyn DbReader,
+    new_chain_change_set: ChangeSet,
+    new_chain_accounts: &BTreeSet<AccountAddress>,
+) -> anyhow::Result<Transaction> {
+    let version = db.get_latest_version()?;
+    let (new_chain_write_set, _) = new_chain_change_set.into_inner();
+    let mut write_set = vec![];
+    for (state_key, write_op) in new_chain_write_set {
+        if is_new_chain_state(&state_key, new_chain_accounts) {
+            let exists = db
+                .get_state_value_by_version(&state_key, version)?
+                .is_some();
+            write_set.push((state_key, into_override(write_op, exists)));
+        }
+    }
+    info!(
+        "Overriding {} state values of the source chain with those of the new chain",
+        write_set.len()
+    );
+
+    // Start the next epoch of the source chain, so that the new validator set takes effect
+    let configuration = read_resource::<ConfigurationResource>(db, version)?;
+    let timestamp = read_resource::<TimestampResource>(db, version)?;
+    let new_configuration = configuration.bump_epoch(timestamp.timestamp.microseconds);
+    let new_epoch_event = ContractEvent::new(
+        *configuration.events().key(),
+        configuration.events().count(),
+        TypeTag::Struct(Box::new(NewEpochEvent::struct_tag())),
+        bcs::to_bytes(&NewEpochEvent::new(new_configuration.epoch()))?,
+    );
+    write_set.push((
+        resource_state_key::<ConfigurationResource>(),
+        WriteOp::Modification(bcs::to_bytes(&new_configuration)?),
+    ));
+
+    let change_set = ChangeSet::new(WriteSetMut::new(write_set).freeze()?, vec![new_epoch_event]);
+    Ok(Transaction::GenesisTransaction(WriteSetPayload::Direct(
+        change_set,
+    )))
+}
+
+fn resource_state_key<T: MoveResource>() -> StateKey {
+    StateKey::access_path(AccessPath::new(CORE_CODE_ADDRESS, T::resource_path()))
+}
+
+/// Reads the framework resource from the state of the database at the version
+fn read_resource<T: MoveResource + DeserializeOwned>(
+    db: &dyn DbReader,
+    version: Version,
+) -> anyhow::Result<T> {
+    let state_value = db
+        .get_state_value_by_version(&resource_state_key::<T>(), version)?
+        .ok_or_else(|| format_err!("{} is missing at version {}", T::struct_tag(), version))?;
+    Ok(bcs::from_bytes(state_value.bytes())?)
+}
+
+/// Converts the write of the new chain's genesis (which creates the state) into a write on top of
+/// the source state, i.e., a modification if the state exists in the source chain.
+fn into_override(write_op: WriteOp, exists: bool) -> WriteOp {
+    match write_op {
+        WriteOp::Creation(data) if exists => WriteOp::Modification(data),
+        WriteOp::CreationWithMetadata { data, metadata } if exists => {
+            WriteOp::ModificationWithMetadata { data, metadata }
+        },
+        write_op => write_op,
+    }
+}
+
+/// Returns true iff the state written by the genesis of the new chain should override that of
+/// the source chain, i.e., it's a resource of the new chain's accounts, or a framework resource
+/// that defines the chain. Code and table items are always forked from the source chain.
+fn is_new_chain_state(state_key: &StateKey, new_chain_accounts: &BTreeSet<AccountAddress>) -> bool {
+    let access_path = match state_key.inner() {
+        StateKeyInner::AccessPath(access_path) => access_path,
+        _ => return false,
+    };
+    let struct_tag = match access_path.get_path() {
+        Path::Resource(struct_tag) | Path::ResourceGroup(struct_tag) => struct_tag,
+        Path::Code(_) => return false,
+    };
+
+    if new_chain_accounts.contains(&access_path.address) {
+        return true;
+    }
+    access_path.address == CORE_CODE_ADDRESS
+        && struct_tag.address == CORE_CODE_ADDRESS
+        && NEW_CHAIN_FRAMEWORK_RESOURCES.iter().any(|(module, name)| {
+            struct_tag.module.as_str() == *module && struct_tag.name.as_str() == *name
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_executor::db_bootstrapper::{generate_waypoint, maybe_bootstrap};
+    use aptos_temppath::TempPath;
+    use aptos_types::on_chain_config::{OnChainConfig, ValidatorSet};
+    use move_core_types::{identifier::Identifier, language_storage::StructTag};
+
+    fn resource_key(address: AccountAddress, module: &str, name: &str) -> StateKey {
+        let struct_tag = StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        };
+        StateKey::access_path(AccessPath::resource_access_path(address, struct_tag).unwrap())
+    }
+
+    #[test]
+    fn test_is_new_chain_state() {
+        let validator = AccountAddress::from_hex_literal("0x1234").unwrap();
+        let other_account = AccountAddress::from_hex_literal("0x5678").unwrap();
+        let new_chain_accounts = BTreeSet::from([validator]);
+
+        // The validator set and the validator accounts are taken from the new chain
+        for state_key in [
+            resource_key(CORE_CODE_ADDRESS, "stake", "ValidatorSet"),
+            resource_key(CORE_CODE_ADDRESS, "chain_id", "ChainId"),
+            resource_key(validator, "stake", "StakePool"),
+            resource_key(validator, "account", "Account"),
+        ] {
+            assert!(is_new_chain_state(&state_key, &new_chain_accounts));
+        }
+
+        // Everything else is forked from the source chain
+        for state_key in [
+            resource_key(CORE_CODE_ADDRESS, "stake", "StakePool"),
+            resource_key(CORE_CODE_ADDRESS, "features", "Features"),
+            resource_key(CORE_CODE_ADDRESS, "timestamp", "CurrentTimeMicroseconds"),
+            resource_key(other_account, "account", "Account"),
+            resource_key(other_account, "stake", "ValidatorSet"),
+        ] {
+            assert!(!is_new_chain_state(&state_key, &new_chain_accounts));
+        }
+    }
+
+    fn read_validator_set(db: &dyn DbReader, version: Version) -> ValidatorSet {
+        let state_key = StateKey::access_path(ValidatorSet::access_path().unwrap());
+        let state_value = db
+            .get_state_value_by_version(&state_key, version)
+            .unwrap()
+            .unwrap();
+        bcs::from_bytes(state_value.bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_fork() {
+        // Create the source chain with a single validator
+        let source_db_path = TempPath::new();
+        let (source_change_set, _) =
+            aptos_vm_genesis::test_genesis_change_set_and_validators(Some(1));
+        let source_genesis =
+            Transaction::GenesisTransaction(WriteSetPayload::Direct(source_change_set));
+        let source_db = DbReaderWriter::new(open_db(source_db_path.path(), false).unwrap());
+        let waypoint = generate_waypoint::<AptosVM>(&source_db, &source_genesis).unwrap();
+        assert!(maybe_bootstrap::<AptosVM>(&source_db, &source_genesis, waypoint).unwrap());
+        drop(source_db);
+
+        // The version must exist in the source database
+        let db_path = TempPath::new();
+        let error = restore_state(source_db_path.path(), 1, db_path.path()).unwrap_err();
+        assert!(error.to_string().contains("is ahead of the latest version"));
+
+        // Fork the source chain with two validators
+        let db_path = TempPath::new();
+        restore_state(source_db_path.path(), 0, db_path.path()).unwrap();
+        let (new_chain_change_set, new_chain_validators) =
+            aptos_vm_genesis::test_genesis_change_set_and_validators(Some(2));
+        let mut new_chain_accounts = BTreeSet::from([aptos_test_root_address()]);
+        for validator in &new_chain_validators {
+            new_chain_accounts.insert(validator.data.owner_address);
+            new_chain_accounts.insert(validator.data.operator_address);
+            new_chain_accounts.insert(validator.data.voter_address);
+        }
+        let db = DbReaderWriter::new(open_db(db_path.path(), false).unwrap());
+        let genesis =
+            fork_genesis_txn(&*db.reader, new_chain_change_set, &new_chain_accounts).unwrap();
+        let waypoint = generate_waypoint::<AptosVM>(&db, &genesis).unwrap();
+        assert_eq!(waypoint.version(), 1);
+        assert!(maybe_bootstrap::<AptosVM>(&db, &genesis, waypoint).unwrap());
+
+        // The new chain starts a new epoch with its validator set
+        let ledger_info = db.reader.get_latest_ledger_info().unwrap();
+        assert_eq!(ledger_info.ledger_info().version(), 1);
+        let next_epoch_state = ledger_info.ledger_info().next_epoch_state().unwrap();
+        assert_eq!(next_epoch_state.epoch, 2);
+        assert_eq!(next_epoch_state.verifier.len(), 2);
+        assert_eq!(read_validator_set(&*db.reader, 1).num_validators(), 2);
+        assert_eq!(read_validator_set(&*db.reader, 0).num_validators(), 1);
+    }
+}
@@ -48,12 +48,14 @@ pub fn generate_key_objects(
         account_address: Some(account_address),
         account_private_key: Some(account_key.private_key()),
         consensus_private_key: Some(consensus_key.private_key()),
+        standby_consensus_private_key: None,
         network_private_key: validator_network_key.private_key(),
     };
     let vfn_blob = IdentityBlob {
         account_address: Some(account_address),
         account_private_key: None,
         consensus_private_key: None,
+        standby_consensus_private_key: None,
         network_private_key: full_node_network_key.private_key(),
     };
 
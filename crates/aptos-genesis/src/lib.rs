@@ -5,6 +5,7 @@
 
 pub mod builder;
 pub mod config;
+pub mod fork;
 pub mod keys;
 pub mod mainnet;
 
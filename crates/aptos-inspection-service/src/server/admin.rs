@@ -0,0 +1,99 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{server::utils::CONTENT_TYPE_TEXT, ADMIN_PATH_PREFIX, INVALID_ENDPOINT_MESSAGE};
+use aptos_config::config::NodeConfig;
+use aptos_infallible::RwLock;
+use futures::future::BoxFuture;
+use hyper::{Body, Method, StatusCode};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+    sync::Arc,
+};
+
+// The message to display when the admin endpoints are disabled
+pub const ADMIN_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_admin_commands: true";
+
+// The message to display when an admin command is not sent from the node's host
+pub const ADMIN_NOT_LOCAL_MESSAGE: &str = "Admin commands are only accepted from localhost!";
+
+// The message to display when an admin command is not sent with a POST request
+pub const ADMIN_INVALID_METHOD_MESSAGE: &str = "Admin commands must be sent as POST requests!";
+
+/// The response of an admin command, or the reason it failed
+pub type AdminCommandResult = Result<String, String>;
+
+/// Runs an admin command, given the query parameters of the request
+pub type AdminCommandHandler =
+    Arc<dyn Fn(HashMap<String, String>) -> BoxFuture<'static, AdminCommandResult> + Send + Sync>;
+
+/// The admin commands registered by the node components (e.g., consensus), served at
+/// /admin/<name>. Unlike the other endpoints, admin commands change the state of the node,
+/// so they are only served for POST requests from localhost, and only if they are enabled.
+#[derive(Clone, Default)]
+pub struct AdminCommands {
+    handlers: Arc<RwLock<BTreeMap<String, AdminCommandHandler>>>,
+}
+
+impl AdminCommands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the handler of the given command, replacing any existing one
+    pub fn register(&self, name: &str, handler: AdminCommandHandler) {
+        self.handlers.write().insert(name.into(), handler);
+    }
+
+    /// Returns the names of all registered commands
+    pub fn names(&self) -> Vec<String> {
+        self.handlers.read().keys().cloned().collect()
+    }
+
+    fn get(&self, name: &str) -> Option<AdminCommandHandler> {
+        self.handlers.read().get(name).cloned()
+    }
+}
+
+/// Handles a new admin command request
+pub async fn handle_admin_request(
+    node_config: &NodeConfig,
+    admin_commands: &AdminCommands,
+    remote_address: SocketAddr,
+    method: &Method,
+    path: &str,
+    query: Option<&str>,
+) -> (StatusCode, Body, String) {
+    let (status_code, body) = if !node_config.inspection_service.expose_admin_commands {
+        (StatusCode::FORBIDDEN, Body::from(ADMIN_DISABLED_MESSAGE))
+    } else if !remote_address.ip().is_loopback() {
+        (StatusCode::FORBIDDEN, Body::from(ADMIN_NOT_LOCAL_MESSAGE))
+    } else if method != Method::POST {
+        (
+            StatusCode::METHOD_NOT_ALLOWED,
+            Body::from(ADMIN_INVALID_METHOD_MESSAGE),
+        )
+    } else {
+        let handler = path
+            .strip_prefix(ADMIN_PATH_PREFIX)
+            .and_then(|name| admin_commands.get(name));
+        match handler {
+            Some(handler) => match handler(parse_query(query)).await {
+                Ok(response) => (StatusCode::OK, Body::from(response)),
+                Err(error) => (StatusCode::BAD_REQUEST, Body::from(error)),
+            },
+            None => (StatusCode::NOT_FOUND, Body::from(INVALID_ENDPOINT_MESSAGE)),
+        }
+    };
+
+    (status_code, body, CONTENT_TYPE_TEXT.into())
+}
+
+/// Parses and URL decodes the query parameters (e.g., "key1=value1&key2=value2")
+pub(crate) fn parse_query(query: Option<&str>) -> HashMap<String, String> {
+    url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+        .into_owned()
+        .collect()
+}
@@ -0,0 +1,116 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::CONTENT_TYPE_JSON;
+use aptos_infallible::RwLock;
+use futures::future::{join_all, BoxFuture};
+use hyper::{Body, StatusCode};
+use serde_json::json;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+// The maximum time a health check may take before it is considered unhealthy
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The outcome of a health check, with a message describing it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthCheckResult {
+    pub healthy: bool,
+    pub message: String,
+}
+
+impl HealthCheckResult {
+    pub fn healthy(message: impl Into<String>) -> Self {
+        Self {
+            healthy: true,
+            message: message.into(),
+        }
+    }
+
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Self {
+            healthy: false,
+            message: message.into(),
+        }
+    }
+}
+
+/// Checks the health of a subsystem of the node
+pub type HealthCheck = Arc<dyn Fn() -> BoxFuture<'static, HealthCheckResult> + Send + Sync>;
+
+/// The health checks registered by the node subsystems (e.g., consensus), which are
+/// aggregated by the readiness endpoint. The node is ready iff all checks are healthy.
+#[derive(Clone, Default)]
+pub struct HealthChecks {
+    checks: Arc<RwLock<BTreeMap<String, HealthCheck>>>,
+}
+
+impl HealthChecks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the given check, replacing any existing one with the same name
+    pub fn register(&self, name: &str, check: HealthCheck) {
+        self.checks.write().insert(name.into(), check);
+    }
+
+    /// Returns the names of all registered checks
+    pub fn names(&self) -> Vec<String> {
+        self.checks.read().keys().cloned().collect()
+    }
+
+    /// Runs all checks concurrently, and returns their results by name
+    pub async fn run(&self) -> BTreeMap<String, HealthCheckResult> {
+        let checks: Vec<_> = self
+            .checks
+            .read()
+            .iter()
+            .map(|(name, check)| (name.clone(), check.clone()))
+            .collect();
+        let results = join_all(checks.into_iter().map(|(name, check)| async move {
+            let result = tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check())
+                .await
+                .unwrap_or_else(|_| {
+                    HealthCheckResult::unhealthy(format!(
+                        "The check timed out after {:?}",
+                        HEALTH_CHECK_TIMEOUT
+                    ))
+                });
+            (name, result)
+        }))
+        .await;
+        results.into_iter().collect()
+    }
+}
+
+/// Handles a new readiness request. Responds with a 200 if all checks are
+/// healthy, and a 503 otherwise (e.g., for load balancers and k8s probes).
+pub async fn handle_readiness_request(health_checks: &HealthChecks) -> (StatusCode, Body, String) {
+    let results = health_checks.run().await;
+    let ready = results.values().all(|result| result.healthy);
+    let checks: serde_json::Map<_, _> = results
+        .into_iter()
+        .map(|(name, result)| {
+            let result = json!({
+                "healthy": result.healthy,
+                "message": result.message,
+            });
+            (name, result)
+        })
+        .collect();
+    let response = json!({
+        "ready": ready,
+        "checks": checks,
+    });
+
+    let status_code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status_code,
+        Body::from(response.to_string()),
+        CONTENT_TYPE_JSON.into(),
+    )
+}
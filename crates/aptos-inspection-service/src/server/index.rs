@@ -2,23 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    server::utils::CONTENT_TYPE_TEXT, CONFIGURATION_PATH, FORGE_METRICS_PATH, JSON_METRICS_PATH,
-    METRICS_PATH, PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    server::{admin::AdminCommands, utils::CONTENT_TYPE_TEXT},
+    ADMIN_PATH_PREFIX, CONFIGURATION_PATH, CPU_PROFILE_PATH, FORGE_METRICS_PATH, HEAP_PROFILE_PATH,
+    HEAP_STATS_PATH, JSON_METRICS_PATH, METRICS_PATH, PEER_INFORMATION_PATH, READINESS_PATH,
+    SYSTEM_INFORMATION_PATH,
 };
 use hyper::{Body, StatusCode};
 
 /// Handles a new index request
-pub fn handle_index_request() -> (StatusCode, Body, String) {
+pub fn handle_index_request(admin_commands: &AdminCommands) -> (StatusCode, Body, String) {
     (
         StatusCode::OK,
-        Body::from(get_index_response()),
+        Body::from(get_index_response(admin_commands)),
         CONTENT_TYPE_TEXT.into(),
     )
 }
 
 /// Returns the response for the index page. The response
 /// simply lists a welcome message and all available endpoints.
-fn get_index_response() -> String {
+fn get_index_response(admin_commands: &AdminCommands) -> String {
     let mut index_response: Vec<String> = Vec::new();
 
     // Add the list of available endpoints
@@ -29,7 +31,16 @@ fn get_index_response() -> String {
     index_response.push(format!("\t- {}", JSON_METRICS_PATH));
     index_response.push(format!("\t- {}", METRICS_PATH));
     index_response.push(format!("\t- {}", PEER_INFORMATION_PATH));
+    if cfg!(unix) {
+        index_response.push(format!("\t- {}", CPU_PROFILE_PATH));
+        index_response.push(format!("\t- {}", HEAP_PROFILE_PATH));
+        index_response.push(format!("\t- {}", HEAP_STATS_PATH));
+    }
+    index_response.push(format!("\t- {}", READINESS_PATH));
     index_response.push(format!("\t- {}", SYSTEM_INFORMATION_PATH));
+    for name in admin_commands.names() {
+        index_response.push(format!("\t- {}{} (POST)", ADMIN_PATH_PREFIX, name));
+    }
 
     index_response.join("\n") // Separate each entry with a newline
 }
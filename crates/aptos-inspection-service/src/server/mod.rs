@@ -1,11 +1,12 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::server::utils::CONTENT_TYPE_TEXT;
+use crate::server::{admin::AdminCommands, health::HealthChecks, utils::CONTENT_TYPE_TEXT};
 use aptos_config::config::NodeConfig;
 use aptos_logger::debug;
 use aptos_network::application::storage::PeersAndMetadata;
 use hyper::{
+    server::conn::AddrStream,
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
 };
@@ -16,11 +17,15 @@ use std::{
     thread,
 };
 
+pub mod admin;
 mod configuration;
+pub mod health;
 mod index;
 mod json_encoder;
 mod metrics;
 mod peer_information;
+#[cfg(unix)]
+mod profiling;
 mod system_information;
 pub mod utils;
 
@@ -28,12 +33,18 @@ pub mod utils;
 mod tests;
 
 // The list of endpoints offered by the inspection service
+pub const ADMIN_PATH_PREFIX: &str = "/admin/";
 pub const CONFIGURATION_PATH: &str = "/configuration";
+pub const CPU_PROFILE_PATH: &str = "/profiling/cpu";
 pub const FORGE_METRICS_PATH: &str = "/forge_metrics";
+pub const HEAP_PROFILE_PATH: &str = "/profiling/heap";
+pub const HEAP_STATS_PATH: &str = "/profiling/heap_stats";
 pub const INDEX_PATH: &str = "/";
 pub const JSON_METRICS_PATH: &str = "/json_metrics";
 pub const METRICS_PATH: &str = "/metrics";
 pub const PEER_INFORMATION_PATH: &str = "/peer_information";
+pub const PROFILING_PATH_PREFIX: &str = "/profiling/";
+pub const READINESS_PATH: &str = "/health/ready";
 pub const SYSTEM_INFORMATION_PATH: &str = "/system_information";
 
 // Useful string constants
@@ -42,10 +53,13 @@ pub const INVALID_ENDPOINT_MESSAGE: &str = "The requested endpoint is invalid!";
 pub const UNEXPECTED_ERROR_MESSAGE: &str = "An unexpected error was encountered!";
 
 /// Starts the inspection service that listens on the configured
-/// address and handles various endpoint requests.
+/// address and handles various endpoint requests. The admin commands
+/// and health checks can be registered after the service is started.
 pub fn start_inspection_service(
     node_config: NodeConfig,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    admin_commands: AdminCommands,
+    health_checks: HealthChecks,
 ) {
     // Fetch the service port and address
     let service_port = node_config.inspection_service.port;
@@ -69,12 +83,22 @@ pub fn start_inspection_service(
     // Spawn the inspection service
     thread::spawn(move || {
         // Create the service function that handles the endpoint requests
-        let make_service = make_service_fn(move |_conn| {
+        let make_service = make_service_fn(move |conn: &AddrStream| {
             let node_config = node_config.clone();
             let peers_and_metadata = peers_and_metadata.clone();
+            let admin_commands = admin_commands.clone();
+            let health_checks = health_checks.clone();
+            let remote_address = conn.remote_addr();
             async move {
                 Ok::<_, Infallible>(service_fn(move |request| {
-                    serve_requests(request, node_config.clone(), peers_and_metadata.clone())
+                    serve_requests(
+                        request,
+                        node_config.clone(),
+                        peers_and_metadata.clone(),
+                        admin_commands.clone(),
+                        health_checks.clone(),
+                        remote_address,
+                    )
                 }))
             }
         });
@@ -94,9 +118,41 @@ async fn serve_requests(
     req: Request<Body>,
     node_config: NodeConfig,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    admin_commands: AdminCommands,
+    health_checks: HealthChecks,
+    remote_address: SocketAddr,
 ) -> Result<Response<Body>, hyper::Error> {
     // Process the request and get the response components
+    let is_admin_request = req.uri().path().starts_with(ADMIN_PATH_PREFIX);
+    #[cfg(unix)]
+    let is_profiling_request = req.uri().path().starts_with(PROFILING_PATH_PREFIX);
     let (status_code, body, content_type) = match req.uri().path() {
+        path if is_admin_request => {
+            // /admin/<command>
+            // Runs the admin command (e.g., mining blocks on a local test chain)
+            admin::handle_admin_request(
+                &node_config,
+                &admin_commands,
+                remote_address,
+                req.method(),
+                path,
+                req.uri().query(),
+            )
+            .await
+        },
+        #[cfg(unix)]
+        path if is_profiling_request => {
+            // /profiling/<profile>
+            // Captures a CPU profile or a heap snapshot on demand
+            profiling::handle_profiling_request(
+                &node_config,
+                remote_address,
+                req.method(),
+                path,
+                req.uri().query(),
+            )
+            .await
+        },
         CONFIGURATION_PATH => {
             // /configuration
             // Exposes the node configuration
@@ -110,7 +166,7 @@ async fn serve_requests(
         INDEX_PATH => {
             // /
             // Exposes the index and list of available endpoints
-            index::handle_index_request()
+            index::handle_index_request(&admin_commands)
         },
         JSON_METRICS_PATH => {
             // /json_metrics
@@ -127,6 +183,11 @@ async fn serve_requests(
             // Exposes the peer information
             peer_information::handle_peer_information_request(&node_config, peers_and_metadata)
         },
+        READINESS_PATH => {
+            // /health/ready
+            // Exposes the readiness of the node (aggregated over the health checks)
+            health::handle_readiness_request(&health_checks).await
+        },
         SYSTEM_INFORMATION_PATH => {
             // /system_information
             // Exposes the system and build information
@@ -151,6 +212,7 @@ async fn serve_requests(
     let response = match *req.method() {
         Method::HEAD => response_builder.body(Body::empty()), // Return only the headers
         Method::GET => response_builder.body(body),           // Include the response body
+        Method::POST if is_admin_request => response_builder.body(body), // Admin command response
         _ => {
             // Invalid method found
             Response::builder()
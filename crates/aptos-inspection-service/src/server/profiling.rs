@@ -0,0 +1,172 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    server::{
+        admin::parse_query,
+        utils::{CONTENT_TYPE_OCTET_STREAM, CONTENT_TYPE_TEXT},
+    },
+    CPU_PROFILE_PATH, HEAP_PROFILE_PATH, HEAP_STATS_PATH, INVALID_ENDPOINT_MESSAGE,
+};
+use aptos_config::config::NodeConfig;
+use aptos_logger::info;
+use aptos_profiler::on_demand;
+use hyper::{Body, Method, StatusCode};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+// The message to display when the profiling endpoints are disabled
+pub const PROFILING_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_profiling: true";
+
+// The message to display when a profile is not requested from the node's host
+pub const PROFILING_NOT_LOCAL_MESSAGE: &str = "Profiles are only served to localhost!";
+
+// The message to display when a profile is not requested with a GET request
+pub const PROFILING_INVALID_METHOD_MESSAGE: &str = "Profiles must be requested with GET requests!";
+
+// The message to display when a CPU profile is requested while another one is captured
+pub const CPU_PROFILE_IN_PROGRESS_MESSAGE: &str = "A CPU profile is already being captured!";
+
+// The defaults and limits of the CPU profile parameters
+const DEFAULT_CPU_PROFILE_SECONDS: u64 = 30;
+const MAX_CPU_PROFILE_SECONDS: u64 = 300;
+const DEFAULT_CPU_PROFILE_FREQUENCY: u64 = 99;
+const MAX_CPU_PROFILE_FREQUENCY: u64 = 1_000;
+
+// Whether a CPU profile is being captured (only one can be captured at a time)
+static CPU_PROFILE_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Handles a new profiling request, e.g.:
+///   - GET /profiling/cpu?seconds=30&frequency=99 (pprof format, e.g., for `go tool pprof`)
+///   - GET /profiling/heap (jemalloc heap profile, e.g., for `jeprof`)
+///   - GET /profiling/heap_stats (jemalloc statistics, as text)
+pub async fn handle_profiling_request(
+    node_config: &NodeConfig,
+    remote_address: SocketAddr,
+    method: &Method,
+    path: &str,
+    query: Option<&str>,
+) -> (StatusCode, Body, String) {
+    let result = if !node_config.inspection_service.expose_profiling {
+        Err((StatusCode::FORBIDDEN, PROFILING_DISABLED_MESSAGE.into()))
+    } else if !remote_address.ip().is_loopback() {
+        Err((StatusCode::FORBIDDEN, PROFILING_NOT_LOCAL_MESSAGE.into()))
+    } else if method != Method::GET {
+        Err((
+            StatusCode::METHOD_NOT_ALLOWED,
+            PROFILING_INVALID_METHOD_MESSAGE.into(),
+        ))
+    } else {
+        match path {
+            CPU_PROFILE_PATH => handle_cpu_profile_request(parse_query(query)).await,
+            HEAP_PROFILE_PATH => handle_heap_profile_request().await,
+            HEAP_STATS_PATH => Ok((
+                Body::from(on_demand::heap_stats()),
+                CONTENT_TYPE_TEXT.into(),
+            )),
+            _ => Err((StatusCode::NOT_FOUND, INVALID_ENDPOINT_MESSAGE.into())),
+        }
+    };
+
+    match result {
+        Ok((body, content_type)) => (StatusCode::OK, body, content_type),
+        Err((status_code, message)) => (status_code, Body::from(message), CONTENT_TYPE_TEXT.into()),
+    }
+}
+
+/// Captures a CPU profile with the requested duration and frequency
+async fn handle_cpu_profile_request(
+    params: HashMap<String, String>,
+) -> Result<(Body, String), (StatusCode, String)> {
+    let seconds = parse_bounded_param(
+        &params,
+        "seconds",
+        DEFAULT_CPU_PROFILE_SECONDS,
+        MAX_CPU_PROFILE_SECONDS,
+    )?;
+    let frequency = parse_bounded_param(
+        &params,
+        "frequency",
+        DEFAULT_CPU_PROFILE_FREQUENCY,
+        MAX_CPU_PROFILE_FREQUENCY,
+    )?;
+
+    let cpu_profile_guard = CpuProfileGuard::try_acquire()
+        .ok_or_else(|| (StatusCode::CONFLICT, CPU_PROFILE_IN_PROGRESS_MESSAGE.into()))?;
+    info!(
+        "Capturing a CPU profile for {} seconds at {} Hz",
+        seconds, frequency
+    );
+
+    // The guard is moved into the blocking task, so that the profile is marked
+    // as done only once the capture completes (even if the client disconnects).
+    let result = tokio::task::spawn_blocking(move || {
+        let _cpu_profile_guard = cpu_profile_guard;
+        on_demand::cpu_profile(Duration::from_secs(seconds), frequency as i32)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(profile)) => Ok((Body::from(profile), CONTENT_TYPE_OCTET_STREAM.into())),
+        Ok(Err(error)) => Err((StatusCode::INTERNAL_SERVER_ERROR, error.to_string())),
+        Err(error) => Err((StatusCode::INTERNAL_SERVER_ERROR, error.to_string())),
+    }
+}
+
+/// A guard that marks a CPU profile as being captured, until the guard is dropped
+struct CpuProfileGuard;
+
+impl CpuProfileGuard {
+    /// Returns a new guard, or None if a CPU profile is already being captured
+    fn try_acquire() -> Option<Self> {
+        CPU_PROFILE_IN_PROGRESS
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for CpuProfileGuard {
+    fn drop(&mut self) {
+        CPU_PROFILE_IN_PROGRESS.store(false, Ordering::Release);
+    }
+}
+
+/// Dumps a snapshot of the sampled heap allocations
+async fn handle_heap_profile_request() -> Result<(Body, String), (StatusCode, String)> {
+    match tokio::task::spawn_blocking(on_demand::heap_profile).await {
+        Ok(Ok(profile)) => Ok((Body::from(profile), CONTENT_TYPE_OCTET_STREAM.into())),
+        Ok(Err(error)) => Err((StatusCode::INTERNAL_SERVER_ERROR, error.to_string())),
+        Err(error) => Err((StatusCode::INTERNAL_SERVER_ERROR, error.to_string())),
+    }
+}
+
+/// Parses the given parameter, which must be in [1, max] (if it is specified)
+fn parse_bounded_param(
+    params: &HashMap<String, String>,
+    name: &str,
+    default: u64,
+    max: u64,
+) -> Result<u64, (StatusCode, String)> {
+    let value = match params.get(name) {
+        Some(value) => value.parse::<u64>().map_err(|error| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid {}: {:?}", name, error),
+            )
+        })?,
+        None => default,
+    };
+    if value == 0 || value > max {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("The {} must be between 1 and {}", name, max),
+        ));
+    }
+    Ok(value)
+}
@@ -3,22 +3,36 @@
 
 use crate::{
     server::{
+        admin::{
+            AdminCommands, ADMIN_DISABLED_MESSAGE, ADMIN_INVALID_METHOD_MESSAGE,
+            ADMIN_NOT_LOCAL_MESSAGE,
+        },
         configuration::CONFIGURATION_DISABLED_MESSAGE,
-        peer_information::PEER_INFO_DISABLED_MESSAGE, serve_requests,
-        system_information::SYS_INFO_DISABLED_MESSAGE, utils::get_all_metrics,
+        health::{HealthCheckResult, HealthChecks},
+        peer_information::PEER_INFO_DISABLED_MESSAGE,
+        serve_requests,
+        system_information::SYS_INFO_DISABLED_MESSAGE,
+        utils::get_all_metrics,
     },
-    CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, METRICS_PATH,
-    PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, INVALID_ENDPOINT_MESSAGE,
+    JSON_METRICS_PATH, METRICS_PATH, PEER_INFORMATION_PATH, READINESS_PATH,
+    SYSTEM_INFORMATION_PATH,
 };
 use aptos_config::config::NodeConfig;
 use aptos_network::application::storage::PeersAndMetadata;
 use assert_approx_eq::assert_approx_eq;
-use futures::executor::block_on;
+use futures::{executor::block_on, FutureExt};
 use hyper::{body, Body, Method, Request, Response, StatusCode};
 use once_cell::sync::Lazy;
 use prometheus::{proto::MetricFamily, register_int_counter, Counter, IntCounter, Opts, Registry};
 use rusty_fork::rusty_fork_test;
-use std::{io::read_to_string, string::String};
+use std::{
+    collections::HashMap,
+    io::read_to_string,
+    net::{Ipv4Addr, SocketAddr},
+    string::String,
+    sync::Arc,
+};
 
 // This metrics counter only exists in this test context; the rest of the
 // system's metrics counters don't exist, so we need to add this for tests.
@@ -26,6 +40,247 @@ const INT_COUNTER_NAME: &str = "INT_COUNTER";
 static INT_COUNTER: Lazy<IntCounter> =
     Lazy::new(|| register_int_counter!(INT_COUNTER_NAME, "An integer counter").unwrap());
 
+#[tokio::test]
+async fn test_admin_commands() {
+    // Create a validator config and register an admin command
+    let mut config = NodeConfig::get_default_validator_config();
+    let admin_commands = AdminCommands::new();
+    admin_commands.register(
+        "echo",
+        Arc::new(|params: HashMap<String, String>| {
+            async move {
+                params
+                    .get("message")
+                    .cloned()
+                    .ok_or_else(|| "Missing message!".to_string())
+            }
+            .boxed()
+        }),
+    );
+    let local_address = SocketAddr::from((Ipv4Addr::LOCALHOST, 1234));
+    let remote_address = SocketAddr::from((Ipv4Addr::new(1, 2, 3, 4), 1234));
+    let echo_path = "/admin/echo?message=hello";
+
+    // Disable the admin commands and send one
+    config.inspection_service.expose_admin_commands = false;
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::POST,
+        echo_path,
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body, ADMIN_DISABLED_MESSAGE);
+
+    // Enable the admin commands and verify they are only accepted from localhost
+    config.inspection_service.expose_admin_commands = true;
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::POST,
+        echo_path,
+        remote_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body, ADMIN_NOT_LOCAL_MESSAGE);
+
+    // Verify that admin commands are only accepted as POST requests
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::GET,
+        echo_path,
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(body, ADMIN_INVALID_METHOD_MESSAGE);
+
+    // Run the command and verify the response
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::POST,
+        echo_path,
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "hello");
+
+    // Verify that the parameters are URL decoded
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::POST,
+        "/admin/echo?message=hello%20world%26more+words",
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "hello world&more words");
+
+    // Run the command with missing parameters and verify the error
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::POST,
+        "/admin/echo",
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body, "Missing message!");
+
+    // Run an unknown command and verify the error
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::POST,
+        "/admin/unknown",
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body, INVALID_ENDPOINT_MESSAGE);
+
+    // Verify that the index lists the command
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::GET,
+        INDEX_PATH,
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("/admin/echo"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_profiling() {
+    use crate::{
+        server::profiling::{PROFILING_DISABLED_MESSAGE, PROFILING_NOT_LOCAL_MESSAGE},
+        CPU_PROFILE_PATH, HEAP_STATS_PATH,
+    };
+
+    let mut config = NodeConfig::get_default_validator_config();
+    let admin_commands = AdminCommands::new();
+    let local_address = SocketAddr::from((Ipv4Addr::LOCALHOST, 1234));
+    let remote_address = SocketAddr::from((Ipv4Addr::new(1, 2, 3, 4), 1234));
+
+    // Verify the profiling endpoints are disabled by default
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::GET,
+        HEAP_STATS_PATH,
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body, PROFILING_DISABLED_MESSAGE);
+
+    // Enable the profiling endpoints and verify they are only served to localhost
+    config.inspection_service.expose_profiling = true;
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::GET,
+        HEAP_STATS_PATH,
+        remote_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body, PROFILING_NOT_LOCAL_MESSAGE);
+
+    // Verify that profiles are only served for GET requests
+    let (status, _) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::HEAD,
+        CPU_PROFILE_PATH,
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+
+    // Verify the heap statistics are returned
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::GET,
+        HEAP_STATS_PATH,
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("jemalloc"));
+
+    // Verify invalid CPU profile parameters are rejected
+    for invalid_query in ["seconds=0", "seconds=301", "frequency=abc"] {
+        let (status, _) = send_request_to_path(
+            &config,
+            &admin_commands,
+            Method::GET,
+            &format!("{}?{}", CPU_PROFILE_PATH, invalid_query),
+            local_address,
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    // Verify unknown profiles are not found
+    let (status, body) = send_request_to_path(
+        &config,
+        &admin_commands,
+        Method::GET,
+        "/profiling/unknown",
+        local_address,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body, INVALID_ENDPOINT_MESSAGE);
+}
+
+#[tokio::test]
+async fn test_readiness() {
+    let config = NodeConfig::get_default_validator_config();
+    let health_checks = HealthChecks::new();
+
+    // Verify the node is ready if no checks are registered
+    let (status, body) = send_readiness_request(&config, &health_checks).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["ready"], true);
+
+    // Register a healthy check and verify the node is ready
+    health_checks.register(
+        "consensus",
+        Arc::new(|| async { HealthCheckResult::healthy("Committed recently") }.boxed()),
+    );
+    let (status, body) = send_readiness_request(&config, &health_checks).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["ready"], true);
+    assert_eq!(body["checks"]["consensus"]["healthy"], true);
+    assert_eq!(body["checks"]["consensus"]["message"], "Committed recently");
+
+    // Register an unhealthy check and verify the node is no longer ready
+    health_checks.register(
+        "mempool",
+        Arc::new(|| async { HealthCheckResult::unhealthy("Mempool is full") }.boxed()),
+    );
+    let (status, body) = send_readiness_request(&config, &health_checks).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["ready"], false);
+    assert_eq!(body["checks"]["consensus"]["healthy"], true);
+    assert_eq!(body["checks"]["mempool"]["healthy"], false);
+    assert_eq!(body["checks"]["mempool"]["message"], "Mempool is full");
+}
+
 #[tokio::test]
 async fn test_inspect_configuration() {
     // Create a validator config
@@ -255,7 +510,72 @@ async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Respon
             .unwrap(),
         config.clone(),
         PeersAndMetadata::new(&[]),
+        AdminCommands::new(),
+        HealthChecks::new(),
+        SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
     )
     .await
     .unwrap()
 }
+
+// Exercise the serve_requests() handler with a readiness request from a
+// remote address, and return the response status and JSON body.
+async fn send_readiness_request(
+    config: &NodeConfig,
+    health_checks: &HealthChecks,
+) -> (StatusCode, serde_json::Value) {
+    let uri = format!("http://127.0.0.1:9201{}", READINESS_PATH);
+    let mut response = serve_requests(
+        Request::builder()
+            .uri(uri)
+            .method(Method::GET)
+            .body(Body::from(""))
+            .unwrap(),
+        config.clone(),
+        PeersAndMetadata::new(&[]),
+        AdminCommands::new(),
+        health_checks.clone(),
+        SocketAddr::from((Ipv4Addr::new(1, 2, 3, 4), 1234)),
+    )
+    .await
+    .unwrap();
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    (
+        response.status(),
+        serde_json::from_slice(&response_body).unwrap(),
+    )
+}
+
+// Exercise the serve_requests() handler with a request to the given path,
+// and return the response status and body.
+async fn send_request_to_path(
+    config: &NodeConfig,
+    admin_commands: &AdminCommands,
+    method: Method,
+    endpoint: &str,
+    remote_address: SocketAddr,
+) -> (StatusCode, String) {
+    // Build the URI
+    let uri = format!("http://127.0.0.1:9201{}", endpoint);
+
+    // Serve the request
+    let mut response = serve_requests(
+        Request::builder()
+            .uri(uri)
+            .method(method)
+            .body(Body::from(""))
+            .unwrap(),
+        config.clone(),
+        PeersAndMetadata::new(&[]),
+        admin_commands.clone(),
+        HealthChecks::new(),
+        remote_address,
+    )
+    .await
+    .unwrap();
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    (
+        response.status(),
+        read_to_string(response_body.as_ref()).unwrap(),
+    )
+}
@@ -12,6 +12,7 @@ use std::collections::HashMap;
 
 // Useful string constants
 pub const CONTENT_TYPE_JSON: &str = "application/json";
+pub const CONTENT_TYPE_OCTET_STREAM: &str = "application/octet-stream";
 pub const CONTENT_TYPE_TEXT: &str = "text/plain";
 
 /// Counter for the number of metrics in various states
@@ -0,0 +1,218 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the Aptos ledger for light clients, e.g., bridges and mobile SDKs.
+//!
+//! A [`LightClient`] starts from a trusted epoch waypoint, and ratchets its trusted state
+//! forward with [`StateProof`]s, i.e., chains of epoch change ledger infos and the latest
+//! ledger info. Once it trusts the validator set of an epoch, it verifies the signatures
+//! of ledger infos in that epoch, and the proofs served by the proof endpoints of the REST
+//! API (e.g., `/transactions/by_version/:version/proof`) against them.
+//!
+//! The verification only depends on `aptos-types` (and `aptos-crypto`), without any IO or
+//! async runtime, but it isn't `no_std`: `aptos-types` and `anyhow` require `std`.
+
+mod proof;
+
+pub use crate::proof::{
+    IndexedTableItemsWithProof, StateValueWithProof, TransactionWithLedgerInfo,
+};
+use anyhow::{format_err, Result};
+use aptos_types::{
+    epoch_change::Verifier,
+    ledger_info::LedgerInfoWithSignatures,
+    state_proof::StateProof,
+    transaction::Version,
+    trusted_state::{TrustedState, TrustedStateChange},
+    waypoint::Waypoint,
+};
+use serde::de::DeserializeOwned;
+
+/// A proof of some data against a ledger info, which is verified by [`LightClient::verify`].
+pub trait VerifiableProof {
+    /// The ledger info the proof is against
+    fn ledger_info_with_signatures(&self) -> &LedgerInfoWithSignatures;
+
+    /// Verifies the proof against its ledger info, without verifying the signatures of the
+    /// ledger info.
+    fn verify_against_ledger_info(&self) -> Result<()>;
+}
+
+impl VerifiableProof for StateValueWithProof {
+    fn ledger_info_with_signatures(&self) -> &LedgerInfoWithSignatures {
+        &self.ledger_info_with_signatures
+    }
+
+    fn verify_against_ledger_info(&self) -> Result<()> {
+        self.verify()
+    }
+}
+
+impl VerifiableProof for IndexedTableItemsWithProof {
+    fn ledger_info_with_signatures(&self) -> &LedgerInfoWithSignatures {
+        &self.ledger_info_with_signatures
+    }
+
+    fn verify_against_ledger_info(&self) -> Result<()> {
+        self.verify()
+    }
+}
+
+impl VerifiableProof for TransactionWithLedgerInfo {
+    fn ledger_info_with_signatures(&self) -> &LedgerInfoWithSignatures {
+        &self.ledger_info_with_signatures
+    }
+
+    fn verify_against_ledger_info(&self) -> Result<()> {
+        self.verify()
+    }
+}
+
+/// `LightClient` keeps the trusted state of a light client, and verifies ledger infos and
+/// proofs against it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LightClient {
+    trusted_state: TrustedState,
+}
+
+impl LightClient {
+    /// Creates a light client that trusts the given epoch waypoint. Note that it can only
+    /// verify the epoch change ledger info of the waypoint, until it's ratcheted forward.
+    pub fn new(epoch_waypoint: Waypoint) -> Self {
+        Self::from_trusted_state(TrustedState::from_epoch_waypoint(epoch_waypoint))
+    }
+
+    /// Creates a light client from a previously trusted state, e.g., one that was persisted.
+    pub fn from_trusted_state(trusted_state: TrustedState) -> Self {
+        Self { trusted_state }
+    }
+
+    pub fn trusted_state(&self) -> &TrustedState {
+        &self.trusted_state
+    }
+
+    pub fn trusted_version(&self) -> Version {
+        self.trusted_state.version()
+    }
+
+    /// Verifies the state proof (see [`TrustedState::verify_and_ratchet`]) and ratchets the
+    /// trusted state forward to its latest ledger info (or its last epoch change ledger info,
+    /// if the proof doesn't reach the epoch of the latest ledger info). Returns the new
+    /// trusted version.
+    pub fn ratchet(&mut self, state_proof: &StateProof) -> Result<Version> {
+        match self.trusted_state.verify_and_ratchet(state_proof)? {
+            TrustedStateChange::Version { new_state }
+            | TrustedStateChange::Epoch { new_state, .. } => self.trusted_state = new_state,
+            TrustedStateChange::NoChange => {},
+        }
+        Ok(self.trusted_version())
+    }
+
+    /// Decodes a BCS encoded [`StateProof`] and ratchets the trusted state with it.
+    pub fn ratchet_bcs(&mut self, bytes: &[u8]) -> Result<Version> {
+        let state_proof: StateProof = decode_bcs(bytes)?;
+        self.ratchet(&state_proof)
+    }
+
+    /// Verifies the signatures of the ledger info against the validator set of the trusted
+    /// epoch. The ledger info must be in the trusted epoch; to verify ledger infos of later
+    /// epochs, the trusted state must first be ratcheted into them.
+    pub fn verify_ledger_info(
+        &self,
+        ledger_info_with_signatures: &LedgerInfoWithSignatures,
+    ) -> Result<()> {
+        Verifier::verify(&self.trusted_state, ledger_info_with_signatures)
+    }
+
+    /// Verifies the proof against its ledger info, and the ledger info against the trusted
+    /// state.
+    pub fn verify<P: VerifiableProof>(&self, proof: &P) -> Result<()> {
+        self.verify_ledger_info(proof.ledger_info_with_signatures())?;
+        proof.verify_against_ledger_info()
+    }
+
+    /// Decodes a BCS encoded proof, as served by the proof endpoints, and returns it if it's
+    /// verified (see [`LightClient::verify`]).
+    pub fn verify_bcs<P: VerifiableProof + DeserializeOwned>(&self, bytes: &[u8]) -> Result<P> {
+        let proof = decode_bcs(bytes)?;
+        self.verify(&proof)?;
+        Ok(proof)
+    }
+}
+
+fn decode_bcs<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    bcs::from_bytes(bytes).map_err(|err| format_err!("Failed to decode BCS: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::HashValue;
+    use aptos_types::{
+        block_info::BlockInfo,
+        epoch_change::EpochChangeProof,
+        epoch_state::EpochState,
+        ledger_info::{generate_ledger_info_with_sig, LedgerInfo},
+        validator_signer::ValidatorSigner,
+        validator_verifier::random_validator_verifier,
+    };
+
+    fn ledger_info(
+        signers: &[ValidatorSigner],
+        epoch: u64,
+        version: Version,
+        next_epoch_state: Option<EpochState>,
+    ) -> LedgerInfoWithSignatures {
+        let block_info = BlockInfo::new(
+            epoch,
+            0,
+            HashValue::zero(),
+            HashValue::zero(),
+            version,
+            0,
+            next_epoch_state,
+        );
+        generate_ledger_info_with_sig(signers, LedgerInfo::new(block_info, HashValue::zero()))
+    }
+
+    #[test]
+    fn test_ratchet_and_verify_ledger_info() {
+        let (signers, verifier) = random_validator_verifier(4, None, true);
+        let epoch_change_li = ledger_info(&signers, 0, 0, Some(EpochState { epoch: 1, verifier }));
+        let waypoint = Waypoint::new_epoch_boundary(epoch_change_li.ledger_info()).unwrap();
+        let mut light_client = LightClient::new(waypoint);
+
+        // Ledger infos of the next epoch can't be verified until we ratchet into it
+        let latest_li = ledger_info(&signers, 1, 10, None);
+        assert!(light_client.verify_ledger_info(&latest_li).is_err());
+
+        let state_proof = StateProof::new(
+            latest_li.clone(),
+            EpochChangeProof::new(vec![epoch_change_li], false),
+        );
+        let version = light_client
+            .ratchet_bcs(&bcs::to_bytes(&state_proof).unwrap())
+            .unwrap();
+        assert_eq!(version, 10);
+        light_client.verify_ledger_info(&latest_li).unwrap();
+        light_client
+            .verify_ledger_info(&ledger_info(&signers, 1, 20, None))
+            .unwrap();
+
+        // Ledger infos signed by other validators or of other epochs are rejected
+        let (other_signers, _) = random_validator_verifier(4, None, false);
+        assert!(light_client
+            .verify_ledger_info(&ledger_info(&other_signers, 1, 20, None))
+            .is_err());
+        assert!(light_client
+            .verify_ledger_info(&ledger_info(&signers, 2, 20, None))
+            .is_err());
+
+        // Stale state proofs are rejected
+        let stale_proof = StateProof::new(
+            ledger_info(&signers, 1, 5, None),
+            EpochChangeProof::new(vec![], false),
+        );
+        assert!(light_client.ratchet(&stale_proof).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{SparseMerkleBatchProof, SparseMerkleProof, TransactionInfoWithProof},
+    state_store::{state_key::StateKey, state_value::StateValue, table::TableHandle},
+    transaction::{TransactionWithProof, Version},
+};
+use serde::{Deserialize, Serialize};
+
+/// A state value (e.g., a resource) and the proofs authenticating it against
+/// a signed ledger info. This is served as BCS only, for light clients.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateValueWithProof {
+    /// The state checkpoint version the value was read at
+    pub version: Version,
+    /// The key of the state value
+    pub state_key: StateKey,
+    /// The state value (or None, if the value doesn't exist)
+    pub state_value: Option<StateValue>,
+    /// The proof of the state value against the state checkpoint root hash
+    pub state_proof: SparseMerkleProof,
+    /// The proof of the state checkpoint transaction info against the ledger info
+    pub transaction_info_with_proof: TransactionInfoWithProof,
+    /// The latest ledger info (and signatures) at the time of the request
+    pub ledger_info_with_signatures: LedgerInfoWithSignatures,
+}
+
+impl StateValueWithProof {
+    /// Verifies the state value against the ledger info carried by `self`.
+    ///
+    /// Note: the signatures of the ledger info are not verified here. Clients
+    /// must verify them against a trusted validator set, e.g., with [`crate::LightClient::verify`].
+    pub fn verify(&self) -> Result<()> {
+        let state_checkpoint_hash = verify_state_checkpoint(
+            &self.transaction_info_with_proof,
+            &self.ledger_info_with_signatures,
+            self.version,
+        )?;
+        self.state_proof.verify(
+            state_checkpoint_hash,
+            self.state_key.hash(),
+            self.state_value.as_ref(),
+        )
+    }
+}
+
+/// A contiguous range of items of a table keyed by `u64` indices (e.g., the buckets
+/// of a `SmartTable` or `BigVector`), and the proofs authenticating them against a
+/// signed ledger info. This is served as BCS only, for light clients.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IndexedTableItemsWithProof {
+    /// The state checkpoint version the items were read at
+    pub version: Version,
+    /// The handle of the table
+    pub table_handle: TableHandle,
+    /// The index of the first item
+    pub start: u64,
+    /// The items at the indices `start`, `start + 1`, etc. If the table ends within
+    /// the requested range, the last item is None.
+    pub items: Vec<Option<StateValue>>,
+    /// The proof of all the items (or the absence of the last one) against the state
+    /// checkpoint root hash
+    pub proof: SparseMerkleBatchProof,
+    /// The proof of the state checkpoint transaction info against the ledger info
+    pub transaction_info_with_proof: TransactionInfoWithProof,
+    /// The latest ledger info (and signatures) at the time of the request
+    pub ledger_info_with_signatures: LedgerInfoWithSignatures,
+}
+
+impl IndexedTableItemsWithProof {
+    /// Verifies the table items against the ledger info carried by `self`.
+    ///
+    /// Note: the signatures of the ledger info are not verified here. Clients
+    /// must verify them against a trusted validator set, e.g., with [`crate::LightClient::verify`].
+    pub fn verify(&self) -> Result<()> {
+        let state_checkpoint_hash = verify_state_checkpoint(
+            &self.transaction_info_with_proof,
+            &self.ledger_info_with_signatures,
+            self.version,
+        )?;
+        let mut elements = Vec::with_capacity(self.items.len());
+        for (offset, state_value) in self.items.iter().enumerate() {
+            ensure!(
+                state_value.is_some() || offset + 1 == self.items.len(),
+                "Only the last table item may be missing, but item {} is missing",
+                offset
+            );
+            let state_key = self
+                .table_handle
+                .indexed_item_state_key(self.start + offset as u64);
+            elements.push((state_key.hash(), state_value.as_ref()));
+        }
+        self.proof.verify(state_checkpoint_hash, &elements)
+    }
+}
+
+/// Verifies the state checkpoint transaction info against the ledger info, and
+/// returns the state checkpoint root hash.
+fn verify_state_checkpoint(
+    transaction_info_with_proof: &TransactionInfoWithProof,
+    ledger_info_with_signatures: &LedgerInfoWithSignatures,
+    version: Version,
+) -> Result<HashValue> {
+    transaction_info_with_proof.verify(ledger_info_with_signatures.ledger_info(), version)?;
+    transaction_info_with_proof
+        .transaction_info()
+        .state_checkpoint_hash()
+        .ok_or_else(|| {
+            format_err!(
+                "The transaction at version {} is not a state checkpoint",
+                version
+            )
+        })
+}
+
+/// A transaction and the proof authenticating it against a signed ledger info.
+/// This is served as BCS only, for light clients.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionWithLedgerInfo {
+    /// The transaction and its transaction info proof
+    pub transaction_with_proof: TransactionWithProof,
+    /// The latest ledger info (and signatures) at the time of the request
+    pub ledger_info_with_signatures: LedgerInfoWithSignatures,
+}
+
+impl TransactionWithLedgerInfo {
+    /// Verifies the transaction against the ledger info carried by `self`.
+    ///
+    /// Note: the signatures of the ledger info are not verified here. Clients
+    /// must verify them against a trusted validator set, e.g., with [`crate::LightClient::verify`].
+    pub fn verify(&self) -> Result<()> {
+        let ledger_info = self.ledger_info_with_signatures.ledger_info();
+        let transaction_with_proof = &self.transaction_with_proof;
+        transaction_with_proof
+            .proof
+            .verify(ledger_info, transaction_with_proof.version)?;
+
+        let transaction_hash = transaction_with_proof.transaction.hash();
+        let expected_hash = transaction_with_proof
+            .proof
+            .transaction_info()
+            .transaction_hash();
+        ensure!(
+            transaction_hash == expected_hash,
+            "Transaction hash ({}) doesn't match the transaction info ({})",
+            transaction_hash,
+            expected_hash,
+        );
+        Ok(())
+    }
+}
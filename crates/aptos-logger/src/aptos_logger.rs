@@ -15,7 +15,7 @@ use crate::{
     telemetry_log_writer::{TelemetryLog, TelemetryLogWriter},
     Event, Filter, Key, Level, LevelFilter, Metadata,
 };
-use aptos_infallible::RwLock;
+use aptos_infallible::{Mutex, RwLock};
 use backtrace::Backtrace;
 use chrono::{SecondsFormat, Utc};
 use futures::channel;
@@ -29,7 +29,7 @@ use std::{
     str::FromStr,
     sync::{self, Arc},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use strum_macros::EnumString;
 use tokio::time;
@@ -231,6 +231,23 @@ pub struct AptosDataBuilder {
     is_async: bool,
     enable_telemetry_flush: bool,
     custom_format: Option<fn(&LogEntry) -> Result<String, fmt::Error>>,
+    module_levels: Vec<ModuleLevel>,
+}
+
+/// A level of the local logs of the modules matching a pattern (e.g.,
+/// `aptos_state_sync::*`), which overrides the global level until it expires
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleLevel {
+    pub pattern: String,
+    pub level: LevelFilter,
+    pub expires_at: Option<Instant>,
+}
+
+impl ModuleLevel {
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= Instant::now())
+    }
 }
 
 impl AptosDataBuilder {
@@ -248,6 +265,7 @@ impl AptosDataBuilder {
             is_async: false,
             enable_telemetry_flush: true,
             custom_format: None,
+            module_levels: vec![],
         }
     }
 
@@ -326,6 +344,13 @@ impl AptosDataBuilder {
                 filter_builder.filter_level(self.level.into());
             }
 
+            // The module levels take precedence over the global level (and `RUST_LOG`)
+            for module_level in &self.module_levels {
+                if !module_level.is_expired() {
+                    filter_builder.filter_module(&module_level.pattern, module_level.level);
+                }
+            }
+
             filter_builder.build()
         };
         let telemetry_filter = {
@@ -711,19 +736,86 @@ fn json_format(entry: &LogEntry) -> Result<String, fmt::Error> {
 /// Periodically rebuilds the filter and replaces the current logger filter.
 /// This is useful for dynamically changing log levels at runtime via existing
 /// environment variables such as `RUST_LOG_TELEMETRY`.
+#[derive(Clone)]
 pub struct LoggerFilterUpdater {
     logger: Arc<AptosData>,
-    logger_builder: AptosDataBuilder,
+    logger_builder: Arc<Mutex<AptosDataBuilder>>,
 }
 
 impl LoggerFilterUpdater {
     pub fn new(logger: Arc<AptosData>, logger_builder: AptosDataBuilder) -> Self {
         Self {
             logger,
-            logger_builder,
+            logger_builder: Arc::new(Mutex::new(logger_builder)),
+        }
+    }
+
+    /// Changes the level of the local logs, and applies it immediately.
+    /// Note: `RUST_LOG` (if set) still takes precedence over the level.
+    pub fn set_level(&self, level: Level) {
+        self.logger_builder.lock().level(level);
+        self.update_filter();
+    }
+
+    /// Overrides the level of the local logs of the modules matching the pattern
+    /// (e.g., `aptos_state_sync::*`), and applies it immediately. If a duration
+    /// is given, the override is reverted once it elapses.
+    pub fn set_module_level(&self, pattern: &str, level: LevelFilter, duration: Option<Duration>) {
+        let module_level = ModuleLevel {
+            pattern: pattern.into(),
+            level,
+            expires_at: duration.map(|duration| Instant::now() + duration),
+        };
+        {
+            let mut logger_builder = self.logger_builder.lock();
+            logger_builder
+                .module_levels
+                .retain(|module_level| module_level.pattern != pattern);
+            logger_builder.module_levels.push(module_level);
+        }
+        self.update_filter();
+
+        // Revert the override once it expires
+        if let Some(duration) = duration {
+            let updater = self.clone();
+            thread::Builder::new()
+                .name("log-level-revert".into())
+                .spawn(move || {
+                    thread::sleep(duration);
+                    updater.update_filter();
+                })
+                .expect("Failed to spawn the log level revert thread");
         }
     }
 
+    /// Removes the level override of the given pattern (or all overrides, if no
+    /// pattern is given), and applies it immediately. Returns the removed overrides.
+    pub fn reset_module_levels(&self, pattern: Option<&str>) -> Vec<ModuleLevel> {
+        let removed = {
+            let mut logger_builder = self.logger_builder.lock();
+            let (removed, retained) = std::mem::take(&mut logger_builder.module_levels)
+                .into_iter()
+                .partition(|module_level| {
+                    pattern.map_or(true, |pattern| module_level.pattern == pattern)
+                });
+            logger_builder.module_levels = retained;
+            removed
+        };
+        self.update_filter();
+        removed
+    }
+
+    /// Returns the module level overrides that haven't expired
+    pub fn module_levels(&self) -> Vec<ModuleLevel> {
+        self.logger_builder
+            .lock()
+            .module_levels
+            .iter()
+            .filter(|module_level| !module_level.is_expired())
+            .cloned()
+            .collect()
+    }
+
     pub async fn run(self) {
         let mut interval = time::interval(FILTER_REFRESH_INTERVAL);
         loop {
@@ -735,7 +827,13 @@ impl LoggerFilterUpdater {
 
     fn update_filter(&self) {
         // TODO: check for change to env var before rebuilding filter.
-        let filter = self.logger_builder.build_filter();
+        let filter = {
+            let mut logger_builder = self.logger_builder.lock();
+            logger_builder
+                .module_levels
+                .retain(|module_level| !module_level.is_expired());
+            logger_builder.build_filter()
+        };
         self.logger.set_filter(filter);
     }
 }
@@ -747,8 +845,8 @@ mod tests {
         aptos_logger::{json_format, RUST_LOG_TELEMETRY},
         debug, error, info,
         logger::Logger,
-        trace, warn, AptosDataBuilder, Event, Key, KeyValue, Level, LoggerFilterUpdater, Metadata,
-        Schema, Value, Visitor,
+        trace, warn, AptosDataBuilder, Event, Key, KeyValue, Level, LevelFilter,
+        LoggerFilterUpdater, Metadata, Schema, Value, Visitor,
     };
     use chrono::{DateTime, Utc};
     #[cfg(test)]
@@ -760,6 +858,7 @@ mod tests {
             Arc,
         },
         thread,
+        time::Duration,
     };
 
     #[derive(serde::Serialize)]
@@ -1025,4 +1124,60 @@ mod tests {
                 "source_path"
             )));
     }
+
+    #[test]
+    fn test_logger_filter_updater_set_level() {
+        let (logger_builder, logger) = new_async_logger();
+        let debug_metadata = &Metadata::new(Level::Debug, "target", "module_path", "source_path");
+        let updater = LoggerFilterUpdater::new(logger.clone(), logger_builder);
+
+        updater.set_level(Level::Info);
+        assert!(!logger.filter.read().local_filter.enabled(debug_metadata));
+
+        // Clones of the updater share the same builder
+        updater.clone().set_level(Level::Debug);
+        assert!(logger.filter.read().local_filter.enabled(debug_metadata));
+
+        updater.update_filter();
+        assert!(logger.filter.read().local_filter.enabled(debug_metadata));
+    }
+
+    #[test]
+    fn test_logger_filter_updater_module_levels() {
+        let (logger_builder, logger) = new_async_logger();
+        let module_metadata = |module_path| Metadata::new(Level::Debug, "target", module_path, "");
+        let updater = LoggerFilterUpdater::new(logger.clone(), logger_builder);
+        updater.set_level(Level::Info);
+
+        // Override the level of the matching modules
+        updater.set_module_level("state_sync::*", LevelFilter::Debug, None);
+        let is_enabled = |module_path| {
+            logger
+                .filter
+                .read()
+                .local_filter
+                .enabled(&module_metadata(module_path))
+        };
+        assert!(is_enabled("state_sync::driver"));
+        assert!(!is_enabled("consensus::round_manager"));
+        assert_eq!(updater.module_levels().len(), 1);
+
+        // Verify that an override reverts once it expires
+        updater.set_module_level(
+            "consensus",
+            LevelFilter::Debug,
+            Some(Duration::from_millis(100)),
+        );
+        assert!(is_enabled("consensus::round_manager"));
+        thread::sleep(Duration::from_millis(500));
+        assert!(!is_enabled("consensus::round_manager"));
+        assert!(is_enabled("state_sync::driver"));
+
+        // Verify that the overrides can be reset
+        let removed = updater.reset_module_levels(None);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].pattern, "state_sync::*");
+        assert!(!is_enabled("state_sync::driver"));
+        assert!(updater.module_levels().is_empty());
+    }
 }
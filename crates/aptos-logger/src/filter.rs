@@ -7,6 +7,7 @@
 use crate::{Level, Metadata};
 use std::{env, str::FromStr};
 
+#[derive(Debug)]
 pub struct FilterParseError;
 
 /// A definition of the most verbose `Level` allowed, or completely off.
@@ -73,7 +74,8 @@ impl Builder {
         self
     }
 
-    /// Adds a directive to the filter for a specific module.
+    /// Adds a directive to the filter for a specific module. The module may be a
+    /// pattern with `*` wildcards, e.g., `aptos_state_sync::*` or `aptos_*::network`.
     pub fn filter_module(&mut self, module: &str, level: LevelFilter) -> &mut Self {
         self.filter(Some(module), level)
     }
@@ -138,7 +140,7 @@ impl Filter {
         // Search for the longest match, the vector is assumed to be pre-sorted.
         for directive in self.directives.iter().rev() {
             match &directive.name {
-                Some(name) if !metadata.module_path().starts_with(name) => {},
+                Some(name) if !matches_module(name, metadata.module_path()) => {},
                 Some(..) | None => return LevelFilter::from(metadata.level()) <= directive.level,
             }
         }
@@ -146,6 +148,30 @@ impl Filter {
     }
 }
 
+/// Returns true iff the module path matches the given directive name. Names match
+/// the module paths they prefix, and may contain `*` wildcards that match any
+/// sequence of characters. A trailing `::*` also matches the parent module itself.
+fn matches_module(name: &str, module_path: &str) -> bool {
+    if !name.contains('*') {
+        return module_path.starts_with(name);
+    }
+    if name.strip_suffix("::*") == Some(module_path) {
+        return true;
+    }
+    matches_prefix(name.as_bytes(), module_path.as_bytes())
+}
+
+/// Returns true iff the pattern (with `*` wildcards) matches a prefix of the path
+fn matches_prefix(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => true,
+        Some((b'*', pattern)) => {
+            (0..=path.len()).any(|index| matches_prefix(pattern, &path[index..]))
+        },
+        Some((byte, pattern)) => path.first() == Some(byte) && matches_prefix(pattern, &path[1..]),
+    }
+}
+
 /// A `Filter` directive for which logs to keep based on a module `name` based filter
 #[derive(Debug)]
 struct Directive {
@@ -210,6 +236,21 @@ mod tests {
         assert!(!logger.enabled(&make_metadata(Level::Debug, "crate2")));
     }
 
+    #[test]
+    fn match_wildcard() {
+        let logger = Builder::new()
+            .filter(None, LevelFilter::Info)
+            .filter(Some("crate1::*"), LevelFilter::Debug)
+            .filter(Some("crate*::net"), LevelFilter::Warn)
+            .build();
+        assert!(logger.enabled(&make_metadata(Level::Debug, "crate1")));
+        assert!(logger.enabled(&make_metadata(Level::Debug, "crate1::mod1")));
+        assert!(!logger.enabled(&make_metadata(Level::Debug, "crate10::mod1")));
+        assert!(!logger.enabled(&make_metadata(Level::Info, "crate2::net::mod1")));
+        assert!(logger.enabled(&make_metadata(Level::Warn, "crate2::net::mod1")));
+        assert!(logger.enabled(&make_metadata(Level::Info, "crate2::mod1")));
+    }
+
     #[test]
     fn parse_default() {
         let logger = Builder::new().parse("info,crate1::mod1=warn").build();
@@ -158,7 +158,7 @@ pub mod tracing_adapter;
 mod security;
 
 pub use crate::aptos_logger::{
-    AptosData as Logger, AptosDataBuilder, LoggerFilterUpdater, Writer, CHANNEL_SIZE,
+    AptosData as Logger, AptosDataBuilder, LoggerFilterUpdater, ModuleLevel, Writer, CHANNEL_SIZE,
 };
 pub use aptos_log_derive::Schema;
 pub use event::Event;
@@ -7,6 +7,7 @@ use std::path::PathBuf;
 
 mod cpu_profiler;
 mod memory_profiler;
+pub mod on_demand;
 mod utils;
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,104 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Profiles captured on demand (e.g., by the inspection service) and returned to the
+//! caller, instead of being written to the result files of the profilers.
+
+use anyhow::{anyhow, Result};
+use aptos_temppath::TempPath;
+use pprof::protos::Message;
+use std::{
+    ffi::{c_char, c_void, CStr, CString},
+    fs, thread,
+    time::Duration,
+};
+
+// The libraries that can't be safely unwound from the signal handler of the CPU profiler
+const CPU_PROFILER_BLOCKLIST: &[&str] = &["libc", "libgcc", "pthread", "vdso"];
+
+/// Samples the stacks of all threads at the given frequency (Hz) for the given
+/// duration, and returns the profile in the (protobuf) pprof format. Only one
+/// CPU profile can be captured at a time.
+pub fn cpu_profile(duration: Duration, frequency: i32) -> Result<Vec<u8>> {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(frequency)
+        .blocklist(CPU_PROFILER_BLOCKLIST)
+        .build()
+        .map_err(|error| anyhow!("Failed to start the CPU profiler: {}", error))?;
+    thread::sleep(duration);
+
+    let profile = guard
+        .report()
+        .build()
+        .and_then(|report| report.pprof())
+        .map_err(|error| anyhow!("Failed to build the CPU profile: {}", error))?;
+    Ok(profile.encode_to_vec())
+}
+
+/// Returns whether jemalloc samples the allocations, i.e., whether the process was
+/// started with `MALLOC_CONF=prof:true`.
+pub fn is_heap_profiling_enabled() -> bool {
+    let mut enabled = false;
+    let mut size = std::mem::size_of::<bool>();
+    let result = unsafe {
+        jemalloc_sys::mallctl(
+            b"opt.prof\0".as_ptr() as *const _,
+            &mut enabled as *mut _ as *mut _,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    result == 0 && enabled
+}
+
+/// Dumps a snapshot of the sampled (live) allocations, in the jemalloc heap
+/// profile format (e.g., to be analyzed with `jeprof`).
+pub fn heap_profile() -> Result<Vec<u8>> {
+    if !is_heap_profiling_enabled() {
+        return Err(anyhow!(
+            "Heap profiling is disabled! Start the process with MALLOC_CONF=prof:true"
+        ));
+    }
+
+    // Dump the profile into a new (randomly named) directory, which is
+    // removed (together with the profile) when the temp path is dropped.
+    let temp_dir = TempPath::new();
+    temp_dir.create_as_dir()?;
+    let path = temp_dir.path().join("heap.prof");
+    let c_path = CString::new(path.to_string_lossy().as_bytes())?;
+    let mut c_path_ptr = c_path.as_ptr();
+    let result = unsafe {
+        jemalloc_sys::mallctl(
+            b"prof.dump\0".as_ptr() as *const _,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut c_path_ptr as *mut _ as *mut _,
+            std::mem::size_of::<*const c_char>(),
+        )
+    };
+    if result != 0 {
+        return Err(anyhow!("Failed to dump the heap profile: {}", result));
+    }
+
+    Ok(fs::read(&path)?)
+}
+
+/// Returns the statistics of the allocator (e.g., the allocated, active and
+/// resident bytes), as printed by jemalloc.
+pub fn heap_stats() -> String {
+    unsafe extern "C" fn write_stats(stats: *mut c_void, message: *const c_char) {
+        let stats = &mut *(stats as *mut String);
+        stats.push_str(&CStr::from_ptr(message).to_string_lossy());
+    }
+
+    let mut stats = String::new();
+    unsafe {
+        jemalloc_sys::malloc_stats_print(
+            Some(write_stats),
+            &mut stats as *mut _ as *mut c_void,
+            std::ptr::null(),
+        )
+    };
+    stats
+}
@@ -1,6 +1,168 @@
 // Copyright © Aptos Foundation
 
 // @generated
+impl serde::Serialize for GetMempoolTransactionEventsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let len = 0;
+        let struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.GetMempoolTransactionEventsRequest", len)?;
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetMempoolTransactionEventsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                            Err(serde::de::Error::unknown_field(value, FIELDS))
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetMempoolTransactionEventsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.internal.fullnode.v1.GetMempoolTransactionEventsRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetMempoolTransactionEventsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                while map.next_key::<GeneratedField>()?.is_some() {
+                    let _ = map.next_value::<serde::de::IgnoredAny>()?;
+                }
+                Ok(GetMempoolTransactionEventsRequest {
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.internal.fullnode.v1.GetMempoolTransactionEventsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetTableInfoRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.handle.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.GetTableInfoRequest", len)?;
+        if !self.handle.is_empty() {
+            struct_ser.serialize_field("handle", &self.handle)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetTableInfoRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "handle",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Handle,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "handle" => Ok(GeneratedField::Handle),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetTableInfoRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.internal.fullnode.v1.GetTableInfoRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetTableInfoRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut handle__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Handle => {
+                            if handle__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("handle"));
+                            }
+                            handle__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(GetTableInfoRequest {
+                    handle: handle__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.internal.fullnode.v1.GetTableInfoRequest", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for GetTransactionsFromNodeRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -115,6 +277,290 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsFromNodeRequest {
         deserializer.deserialize_struct("aptos.internal.fullnode.v1.GetTransactionsFromNodeRequest", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for MempoolTransactionEvent {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.r#type != 0 {
+            len += 1;
+        }
+        if !self.sender.is_empty() {
+            len += 1;
+        }
+        if self.sequence_number != 0 {
+            len += 1;
+        }
+        if !self.hash.is_empty() {
+            len += 1;
+        }
+        if self.gas_unit_price != 0 {
+            len += 1;
+        }
+        if self.eviction_reason.is_some() {
+            len += 1;
+        }
+        if self.chain_id != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.MempoolTransactionEvent", len)?;
+        if self.r#type != 0 {
+            let v = mempool_transaction_event::EventType::from_i32(self.r#type)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.r#type)))?;
+            struct_ser.serialize_field("type", &v)?;
+        }
+        if !self.sender.is_empty() {
+            struct_ser.serialize_field("sender", &self.sender)?;
+        }
+        if self.sequence_number != 0 {
+            struct_ser.serialize_field("sequenceNumber", ToString::to_string(&self.sequence_number).as_str())?;
+        }
+        if !self.hash.is_empty() {
+            struct_ser.serialize_field("hash", &self.hash)?;
+        }
+        if self.gas_unit_price != 0 {
+            struct_ser.serialize_field("gasUnitPrice", ToString::to_string(&self.gas_unit_price).as_str())?;
+        }
+        if let Some(v) = self.eviction_reason.as_ref() {
+            struct_ser.serialize_field("evictionReason", v)?;
+        }
+        if self.chain_id != 0 {
+            struct_ser.serialize_field("chainId", &self.chain_id)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for MempoolTransactionEvent {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "type",
+            "sender",
+            "sequence_number",
+            "sequenceNumber",
+            "hash",
+            "gas_unit_price",
+            "gasUnitPrice",
+            "eviction_reason",
+            "evictionReason",
+            "chain_id",
+            "chainId",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Type,
+            Sender,
+            SequenceNumber,
+            Hash,
+            GasUnitPrice,
+            EvictionReason,
+            ChainId,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "type" => Ok(GeneratedField::Type),
+                            "sender" => Ok(GeneratedField::Sender),
+                            "sequenceNumber" | "sequence_number" => Ok(GeneratedField::SequenceNumber),
+                            "hash" => Ok(GeneratedField::Hash),
+                            "gasUnitPrice" | "gas_unit_price" => Ok(GeneratedField::GasUnitPrice),
+                            "evictionReason" | "eviction_reason" => Ok(GeneratedField::EvictionReason),
+                            "chainId" | "chain_id" => Ok(GeneratedField::ChainId),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = MempoolTransactionEvent;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.internal.fullnode.v1.MempoolTransactionEvent")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<MempoolTransactionEvent, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut r#type__ = None;
+                let mut sender__ = None;
+                let mut sequence_number__ = None;
+                let mut hash__ = None;
+                let mut gas_unit_price__ = None;
+                let mut eviction_reason__ = None;
+                let mut chain_id__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Type => {
+                            if r#type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("type"));
+                            }
+                            r#type__ = Some(map.next_value::<mempool_transaction_event::EventType>()? as i32);
+                        }
+                        GeneratedField::Sender => {
+                            if sender__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sender"));
+                            }
+                            sender__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::SequenceNumber => {
+                            if sequence_number__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sequenceNumber"));
+                            }
+                            sequence_number__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Hash => {
+                            if hash__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("hash"));
+                            }
+                            hash__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::GasUnitPrice => {
+                            if gas_unit_price__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("gasUnitPrice"));
+                            }
+                            gas_unit_price__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::EvictionReason => {
+                            if eviction_reason__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("evictionReason"));
+                            }
+                            eviction_reason__ = map.next_value()?;
+                        }
+                        GeneratedField::ChainId => {
+                            if chain_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("chainId"));
+                            }
+                            chain_id__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(MempoolTransactionEvent {
+                    r#type: r#type__.unwrap_or_default(),
+                    sender: sender__.unwrap_or_default(),
+                    sequence_number: sequence_number__.unwrap_or_default(),
+                    hash: hash__.unwrap_or_default(),
+                    gas_unit_price: gas_unit_price__.unwrap_or_default(),
+                    eviction_reason: eviction_reason__,
+                    chain_id: chain_id__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.internal.fullnode.v1.MempoolTransactionEvent", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for mempool_transaction_event::EventType {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "EVENT_TYPE_UNSPECIFIED",
+            Self::Inserted => "EVENT_TYPE_INSERTED",
+            Self::Committed => "EVENT_TYPE_COMMITTED",
+            Self::Evicted => "EVENT_TYPE_EVICTED",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for mempool_transaction_event::EventType {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "EVENT_TYPE_UNSPECIFIED",
+            "EVENT_TYPE_INSERTED",
+            "EVENT_TYPE_COMMITTED",
+            "EVENT_TYPE_EVICTED",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = mempool_transaction_event::EventType;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(mempool_transaction_event::EventType::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(mempool_transaction_event::EventType::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "EVENT_TYPE_UNSPECIFIED" => Ok(mempool_transaction_event::EventType::Unspecified),
+                    "EVENT_TYPE_INSERTED" => Ok(mempool_transaction_event::EventType::Inserted),
+                    "EVENT_TYPE_COMMITTED" => Ok(mempool_transaction_event::EventType::Committed),
+                    "EVENT_TYPE_EVICTED" => Ok(mempool_transaction_event::EventType::Evicted),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
 impl serde::Serialize for StreamStatus {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -324,6 +770,207 @@ impl<'de> serde::Deserialize<'de> for stream_status::StatusType {
         deserializer.deserialize_any(GeneratedVisitor)
     }
 }
+impl serde::Serialize for TableInfoResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.handle.is_empty() {
+            len += 1;
+        }
+        if !self.key_type.is_empty() {
+            len += 1;
+        }
+        if !self.value_type.is_empty() {
+            len += 1;
+        }
+        if self.owner_address.is_some() {
+            len += 1;
+        }
+        if self.owner_resource_type.is_some() {
+            len += 1;
+        }
+        if self.parent_table_handle.is_some() {
+            len += 1;
+        }
+        if self.chain_id != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.TableInfoResponse", len)?;
+        if !self.handle.is_empty() {
+            struct_ser.serialize_field("handle", &self.handle)?;
+        }
+        if !self.key_type.is_empty() {
+            struct_ser.serialize_field("keyType", &self.key_type)?;
+        }
+        if !self.value_type.is_empty() {
+            struct_ser.serialize_field("valueType", &self.value_type)?;
+        }
+        if let Some(v) = self.owner_address.as_ref() {
+            struct_ser.serialize_field("ownerAddress", v)?;
+        }
+        if let Some(v) = self.owner_resource_type.as_ref() {
+            struct_ser.serialize_field("ownerResourceType", v)?;
+        }
+        if let Some(v) = self.parent_table_handle.as_ref() {
+            struct_ser.serialize_field("parentTableHandle", v)?;
+        }
+        if self.chain_id != 0 {
+            struct_ser.serialize_field("chainId", &self.chain_id)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TableInfoResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "handle",
+            "key_type",
+            "keyType",
+            "value_type",
+            "valueType",
+            "owner_address",
+            "ownerAddress",
+            "owner_resource_type",
+            "ownerResourceType",
+            "parent_table_handle",
+            "parentTableHandle",
+            "chain_id",
+            "chainId",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Handle,
+            KeyType,
+            ValueType,
+            OwnerAddress,
+            OwnerResourceType,
+            ParentTableHandle,
+            ChainId,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "handle" => Ok(GeneratedField::Handle),
+                            "keyType" | "key_type" => Ok(GeneratedField::KeyType),
+                            "valueType" | "value_type" => Ok(GeneratedField::ValueType),
+                            "ownerAddress" | "owner_address" => Ok(GeneratedField::OwnerAddress),
+                            "ownerResourceType" | "owner_resource_type" => Ok(GeneratedField::OwnerResourceType),
+                            "parentTableHandle" | "parent_table_handle" => Ok(GeneratedField::ParentTableHandle),
+                            "chainId" | "chain_id" => Ok(GeneratedField::ChainId),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TableInfoResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.internal.fullnode.v1.TableInfoResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<TableInfoResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut handle__ = None;
+                let mut key_type__ = None;
+                let mut value_type__ = None;
+                let mut owner_address__ = None;
+                let mut owner_resource_type__ = None;
+                let mut parent_table_handle__ = None;
+                let mut chain_id__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Handle => {
+                            if handle__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("handle"));
+                            }
+                            handle__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::KeyType => {
+                            if key_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("keyType"));
+                            }
+                            key_type__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::ValueType => {
+                            if value_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("valueType"));
+                            }
+                            value_type__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::OwnerAddress => {
+                            if owner_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ownerAddress"));
+                            }
+                            owner_address__ = map.next_value()?;
+                        }
+                        GeneratedField::OwnerResourceType => {
+                            if owner_resource_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ownerResourceType"));
+                            }
+                            owner_resource_type__ = map.next_value()?;
+                        }
+                        GeneratedField::ParentTableHandle => {
+                            if parent_table_handle__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("parentTableHandle"));
+                            }
+                            parent_table_handle__ = map.next_value()?;
+                        }
+                        GeneratedField::ChainId => {
+                            if chain_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("chainId"));
+                            }
+                            chain_id__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(TableInfoResponse {
+                    handle: handle__.unwrap_or_default(),
+                    key_type: key_type__.unwrap_or_default(),
+                    value_type: value_type__.unwrap_or_default(),
+                    owner_address: owner_address__,
+                    owner_resource_type: owner_resource_type__,
+                    parent_table_handle: parent_table_handle__,
+                    chain_id: chain_id__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.internal.fullnode.v1.TableInfoResponse", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for TransactionsFromNodeResponse {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -96,6 +96,51 @@ pub mod fullnode_data_client {
             );
             self.inner.server_streaming(request.into_request(), path, codec).await
         }
+        ///
+        pub async fn get_mempool_transaction_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetMempoolTransactionEventsRequest>,
+        ) -> Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::MempoolTransactionEvent>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/aptos.internal.fullnode.v1.FullnodeData/GetMempoolTransactionEvents",
+            );
+            self.inner.server_streaming(request.into_request(), path, codec).await
+        }
+        ///
+        pub async fn get_table_info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetTableInfoRequest>,
+        ) -> Result<tonic::Response<super::TableInfoResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/aptos.internal.fullnode.v1.FullnodeData/GetTableInfo",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -116,6 +161,22 @@ pub mod fullnode_data_server {
             &self,
             request: tonic::Request<super::GetTransactionsFromNodeRequest>,
         ) -> Result<tonic::Response<Self::GetTransactionsFromNodeStream>, tonic::Status>;
+        /// Server streaming response type for the GetMempoolTransactionEvents method.
+        type GetMempoolTransactionEventsStream: futures_core::Stream<
+                Item = Result<super::MempoolTransactionEvent, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        ///
+        async fn get_mempool_transaction_events(
+            &self,
+            request: tonic::Request<super::GetMempoolTransactionEventsRequest>,
+        ) -> Result<tonic::Response<Self::GetMempoolTransactionEventsStream>, tonic::Status>;
+        ///
+        async fn get_table_info(
+            &self,
+            request: tonic::Request<super::GetTableInfoRequest>,
+        ) -> Result<tonic::Response<super::TableInfoResponse>, tonic::Status>;
     }
     ///
     #[derive(Debug)]
@@ -221,6 +282,90 @@ pub mod fullnode_data_server {
                     };
                     Box::pin(fut)
                 }
+                "/aptos.internal.fullnode.v1.FullnodeData/GetMempoolTransactionEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetMempoolTransactionEventsSvc<T: FullnodeData>(pub Arc<T>);
+                    impl<
+                        T: FullnodeData,
+                    > tonic::server::ServerStreamingService<
+                        super::GetMempoolTransactionEventsRequest,
+                    > for GetMempoolTransactionEventsSvc<T> {
+                        type Response = super::MempoolTransactionEvent;
+                        type ResponseStream = T::GetMempoolTransactionEventsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::GetMempoolTransactionEventsRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).get_mempool_transaction_events(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetMempoolTransactionEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/aptos.internal.fullnode.v1.FullnodeData/GetTableInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetTableInfoSvc<T: FullnodeData>(pub Arc<T>);
+                    impl<
+                        T: FullnodeData,
+                    > tonic::server::UnaryService<super::GetTableInfoRequest>
+                    for GetTableInfoSvc<T> {
+                        type Response = super::TableInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetTableInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).get_table_info(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetTableInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
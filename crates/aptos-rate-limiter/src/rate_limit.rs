@@ -174,6 +174,15 @@ impl<Key: Eq + Hash + Clone + Debug> TokenBucketRateLimiter<Key> {
         }
         remove
     }
+
+    /// Garbage collects the buckets that aren't in use and have refilled completely, as they
+    /// grant no more than a new bucket would.  Returns the number of buckets collected.
+    pub fn garbage_collect_full_buckets(&self) -> usize {
+        let mut buckets = self.buckets.write();
+        let num_buckets = buckets.len();
+        buckets.retain(|_, bucket| Arc::strong_count(bucket) > 1 || !bucket.lock().is_full());
+        num_buckets - buckets.len()
+    }
 }
 
 /// A token bucket object that keeps track of everything related to a key
@@ -346,6 +355,12 @@ impl Bucket {
         tokens_allowed
     }
 
+    /// Refills the bucket, and tells us if it holds as many tokens as it can
+    pub(crate) fn is_full(&mut self) -> bool {
+        self.refill();
+        self.tokens >= self.size
+    }
+
     /// Tells us when the next refill is
     pub fn time_of_next_refill(&self) -> Instant {
         self.last_refresh_time + ONE_SEC
@@ -549,4 +564,20 @@ mod tests {
         assert!(!rate_limiter.try_garbage_collect_key(&key_to_keep));
         assert_num_keys(&rate_limiter, 1);
     }
+
+    #[test]
+    fn test_garbage_collect_full_buckets() {
+        let rate_limiter = TokenBucketRateLimiter::test(5, 1);
+
+        // Create a full bucket, a drained bucket and a full bucket in use
+        rate_limiter.bucket("full");
+        assert_acquire(&mut rate_limiter.bucket("drained").lock(), 5);
+        let _bucket_arc = rate_limiter.bucket("in use");
+        assert_num_keys(&rate_limiter, 3);
+
+        // Only the full bucket that isn't in use is collected
+        assert_eq!(rate_limiter.garbage_collect_full_buckets(), 1);
+        assert_num_keys(&rate_limiter, 2);
+        assert!(!rate_limiter.try_garbage_collect_key(&"in use"));
+    }
 }
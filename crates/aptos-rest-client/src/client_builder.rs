@@ -96,6 +96,8 @@ impl ClientBuilder {
                 .unwrap(),
             base_url: self.base_url,
             version_path_base,
+            // Leave the node time to respond before the request times out
+            submit_wait_timeout: self.timeout / 2,
         }
     }
 }
@@ -12,12 +12,14 @@ pub mod response;
 pub use response::Response;
 pub mod client_builder;
 pub mod state;
+pub mod stream;
 pub mod types;
 
 pub use crate::client_builder::{AptosBaseUrl, ClientBuilder};
 use crate::{
     aptos::{AptosVersion, Balance},
     error::RestError,
+    stream::{MessageStream, StreamQuery},
 };
 use anyhow::{anyhow, Result};
 pub use aptos_api_types::{
@@ -25,19 +27,21 @@ pub use aptos_api_types::{
 };
 use aptos_api_types::{
     deserialize_from_string,
-    mime_types::{BCS, BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE, JSON},
-    AptosError, BcsBlock, Block, GasEstimation, HexEncodedBytes, IndexResponse, MoveModuleId,
-    TransactionData, TransactionOnChainData, TransactionsBatchSubmissionResult, UserTransaction,
-    VersionedEvent, ViewRequest,
+    mime_types::{BCS, BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE, BCS_VIEW_FUNCTION, JSON},
+    AptosError, BcsBlock, Block, GasEstimation, HexEncodedBytes, IndexResponse,
+    MempoolAccountDiagnostics, MoveModuleId, PinnedLedgerVersion, TransactionData,
+    TransactionOnChainData, TransactionsBatchSubmissionResult, UserTransaction, VersionedEvent,
+    ViewRequest,
 };
 use aptos_crypto::HashValue;
 use aptos_logger::{debug, info, sample, sample::SampleRate};
+use aptos_retrier::{retry_with_policy, RetryDecision, RetryPolicy};
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{AccountResource, CoinStoreResource, NewBlockEvent, CORE_CODE_ADDRESS},
     contract_event::EventWithVersion,
     state_store::state_key::StateKey,
-    transaction::SignedTransaction,
+    transaction::{EntryFunction, SignedTransaction},
 };
 use move_core_types::language_storage::StructTag;
 use reqwest::{
@@ -48,7 +52,6 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
 pub use state::State;
 use std::{collections::BTreeMap, future::Future, time::Duration};
-use tokio::time::Instant;
 pub use types::{deserialize_from_prefixed_hex_string, Account, Resource};
 use url::Url;
 
@@ -58,6 +61,9 @@ const DEFAULT_INTERVAL_MS: u64 = 1000;
 static DEFAULT_MAX_WAIT_DURATION: Duration = Duration::from_millis(DEFAULT_MAX_WAIT_MS);
 static DEFAULT_INTERVAL_DURATION: Duration = Duration::from_millis(DEFAULT_INTERVAL_MS);
 const DEFAULT_MAX_SERVER_LAG_WAIT_DURATION: Duration = Duration::from_secs(60);
+/// How long the node holds a submission until the transaction is committed, when the
+/// request timeout of the client is unknown. This must be below the request timeout.
+const DEFAULT_SUBMIT_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
 const RESOURCES_PER_CALL_PAGINATION: u64 = 9999;
 const MODULES_PER_CALL_PAGINATION: u64 = 1000;
 const X_APTOS_SDK_HEADER_VALUE: &str = concat!("aptos-rust-sdk/", env!("CARGO_PKG_VERSION"));
@@ -69,6 +75,7 @@ pub struct Client {
     inner: ReqwestClient,
     base_url: Url,
     version_path_base: String,
+    submit_wait_timeout: Duration,
 }
 
 impl Client {
@@ -265,6 +272,19 @@ impl Client {
         Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
     }
 
+    /// Pins the given ledger version (or the latest one), so that later reads passing the
+    /// pin (as `ledger_version_pin`) are all served at the same version.
+    pub async fn get_ledger_version_pin(
+        &self,
+        version: Option<u64>,
+    ) -> AptosResult<Response<PinnedLedgerVersion>> {
+        let path = match version {
+            Some(version) => format!("ledger_version_pin?ledger_version={}", version),
+            None => "ledger_version_pin".to_string(),
+        };
+        self.get(self.build_path(&path)?).await
+    }
+
     // TODO: Remove this, just use `get_index`: https://github.com/aptos-labs/aptos-core/issues/5597.
     pub async fn get_ledger_information(&self) -> AptosResult<Response<State>> {
         let response = self.get_index_bcs().await?.map(|r| State {
@@ -307,6 +327,32 @@ impl Client {
         self.json(response).await
     }
 
+    /// Executes a view function, sending the request and receiving its BCS
+    /// encoded return values as BCS
+    pub async fn view_bcs(
+        &self,
+        request: &EntryFunction,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<Vec<u8>>>> {
+        let request = bcs::to_bytes(request)?;
+        let mut url = self.build_path("view")?;
+        if let Some(version) = version {
+            url.set_query(Some(format!("ledger_version={}", version).as_str()));
+        }
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_VIEW_FUNCTION)
+            .header(ACCEPT, BCS)
+            .body(request)
+            .send()
+            .await?;
+
+        let response = self.check_and_parse_bcs_response(response).await?;
+        Ok(response.and_then(|bytes| bcs::from_bytes(&bytes))?)
+    }
+
     pub async fn simulate(
         &self,
         txn: &SignedTransaction,
@@ -470,7 +516,7 @@ impl Client {
         &self,
         txn: &SignedTransaction,
     ) -> AptosResult<Response<Transaction>> {
-        self.submit(txn).await?;
+        self.submit_and_hold_until_committed(txn).await?;
         self.wait_for_signed_transaction(txn).await
     }
 
@@ -478,10 +524,44 @@ impl Client {
         &self,
         txn: &SignedTransaction,
     ) -> AptosResult<Response<TransactionOnChainData>> {
-        self.submit_bcs(txn).await?;
+        self.submit_and_hold_until_committed(txn).await?;
         self.wait_for_signed_transaction_bcs(txn).await
     }
 
+    /// Submits the transaction, and asks the node to hold the request until the transaction
+    /// is committed (or the wait times out, before the request does). Nodes that don't support
+    /// waiting return immediately, in which case the caller falls back to polling.
+    async fn submit_and_hold_until_committed(&self, txn: &SignedTransaction) -> AptosResult<()> {
+        let txn_payload = bcs::to_bytes(txn)?;
+        let url = self.build_path("transactions")?;
+
+        let result = self
+            .inner
+            .post(url)
+            .query(&[
+                ("wait_for", "committed".to_string()),
+                (
+                    "wait_timeout_secs",
+                    self.submit_wait_timeout.as_secs().to_string(),
+                ),
+            ])
+            .header(CONTENT_TYPE, BCS_CONTENT_TYPE)
+            .header(ACCEPT, BCS)
+            .body(txn_payload)
+            .send()
+            .await;
+        let response = match result {
+            Ok(response) => response,
+            // The request can still time out (e.g., if the node is slow to respond), in which
+            // case the caller polls for the transaction
+            Err(err) if err.is_timeout() => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        self.check_and_parse_bcs_response(response).await?;
+        Ok(())
+    }
+
     pub async fn wait_for_transaction(
         &self,
         pending_transaction: &PendingTransaction,
@@ -839,6 +919,35 @@ impl Client {
         Ok(response)
     }
 
+    /// Returns the transactions of the account that are pending in the mempool of the node.
+    /// Note that this is an experimental endpoint, which may be disabled on the node.
+    pub async fn get_mempool_account_diagnostics(
+        &self,
+        address: AccountAddress,
+    ) -> AptosResult<Response<MempoolAccountDiagnostics>> {
+        self.get(self.build_path(&format!(
+            "experimental/mempool/accounts/{}",
+            address.to_hex_literal()
+        ))?)
+        .await
+    }
+
+    /// Subscribes to the stream endpoint, which pushes new transactions, events and block
+    /// headers as they're committed. Note that the endpoint may be disabled on the node.
+    pub async fn stream(&self, query: &StreamQuery) -> AptosResult<MessageStream> {
+        let response = self
+            .inner
+            .get(self.build_path("stream")?)
+            .header(ACCEPT, "text/event-stream")
+            .query(&query.to_query_params())
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(parse_error(response).await);
+        }
+        Ok(MessageStream::new(response))
+    }
+
     async fn get_transaction_by_hash_inner(
         &self,
         hash: HashValue,
@@ -1420,45 +1529,37 @@ impl Client {
         RetryFun: Fn(StatusCode, Option<AptosError>) -> bool,
         Fut: Future<Output = AptosResult<T>>,
     {
-        let total_wait = total_wait.unwrap_or(DEFAULT_MAX_WAIT_DURATION);
-        let mut backoff = initial_interval.unwrap_or(DEFAULT_INTERVAL_DURATION);
-        let mut result = Err(RestError::Unknown(anyhow!("Failed to run function")));
-        let start = Instant::now();
-
-        // TODO: Add jitter
-        while start.elapsed() < total_wait {
-            result = function().await;
-
-            let retry = match &result {
-                Ok(_) => break,
-                Err(err) => match err {
-                    RestError::Api(inner) => {
+        let policy =
+            RetryPolicy::exponential(initial_interval.unwrap_or(DEFAULT_INTERVAL_DURATION))
+                .with_max_elapsed(total_wait.unwrap_or(DEFAULT_MAX_WAIT_DURATION));
+
+        retry_with_policy(
+            &policy,
+            |result| {
+                let retry = match result {
+                    Ok(_) => false,
+                    Err(RestError::Api(inner)) => {
                         should_retry(inner.status_code, Some(inner.error.clone()))
                     },
-                    RestError::Http(status_code, _e) => should_retry(*status_code, None),
-                    RestError::Bcs(_)
-                    | RestError::Json(_)
-                    | RestError::Timeout(_)
-                    | RestError::Unknown(_) => true,
-                    RestError::UrlParse(_) => false,
-                },
-            };
-
-            if !retry {
-                break;
-            }
-
-            info!(
-                "Failed to call API, retrying in {}ms: {:?}",
-                backoff.as_millis(),
-                result.as_ref().err().unwrap()
-            );
-
-            tokio::time::sleep(backoff).await;
-            backoff = backoff.saturating_mul(2);
-        }
-
-        result
+                    Err(RestError::Http(status_code, _e)) => should_retry(*status_code, None),
+                    Err(
+                        RestError::Bcs(_)
+                        | RestError::Json(_)
+                        | RestError::Timeout(_)
+                        | RestError::Unknown(_),
+                    ) => true,
+                    Err(RestError::UrlParse(_)) => false,
+                };
+                if retry {
+                    info!("Failed to call API, retrying: {:?}", result.as_ref().err());
+                    RetryDecision::Retry
+                } else {
+                    RetryDecision::DoNotRetry
+                }
+            },
+            function,
+        )
+        .await
     }
 
     /// This function builds a URL for use in pagination. It handles setting a limit,
@@ -1590,6 +1691,7 @@ impl From<(ReqwestClient, Url)> for Client {
             inner,
             base_url,
             version_path_base: DEFAULT_VERSION_PATH_BASE.to_string(),
+            submit_wait_timeout: DEFAULT_SUBMIT_WAIT_TIMEOUT,
         }
     }
 }
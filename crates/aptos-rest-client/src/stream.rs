@@ -0,0 +1,127 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::RestError;
+use aptos_api_types::{Address, Event, HashValue, Transaction, U64};
+use aptos_types::account_address::AccountAddress;
+use serde::Deserialize;
+
+/// The items to subscribe to on the stream endpoint (see [`crate::Client::stream`]).
+/// At least one of them must be set.
+#[derive(Clone, Debug, Default)]
+pub struct StreamQuery {
+    /// Stream the transactions sent by this account
+    pub account: Option<AccountAddress>,
+    /// Stream the events with this type (e.g., `0x1::coin::DepositEvent`)
+    pub event_type: Option<String>,
+    /// Stream the events of the handle with this account and creation number
+    pub event_handle: Option<(AccountAddress, u64)>,
+    /// Stream the new block headers
+    pub blocks: bool,
+    /// The version to start streaming from. Defaults to the next committed version.
+    pub start_version: Option<u64>,
+}
+
+impl StreamQuery {
+    pub(crate) fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![];
+        if let Some(account) = &self.account {
+            params.push(("account", account.to_hex_literal()));
+        }
+        if let Some(event_type) = &self.event_type {
+            params.push(("event_type", event_type.clone()));
+        }
+        if let Some((account, creation_number)) = &self.event_handle {
+            params.push(("event_account", account.to_hex_literal()));
+            params.push(("event_creation_number", creation_number.to_string()));
+        }
+        if self.blocks {
+            params.push(("blocks", "true".to_string()));
+        }
+        if let Some(start_version) = self.start_version {
+            params.push(("start_version", start_version.to_string()));
+        }
+        params
+    }
+}
+
+/// The header of a newly committed block
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct StreamBlockHeader {
+    pub block_id: HashValue,
+    pub epoch: U64,
+    pub round: U64,
+    pub timestamp: U64,
+    pub proposer: Address,
+}
+
+/// A message pushed by the stream endpoint
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamMessage {
+    Transaction {
+        version: U64,
+        transaction: Box<Transaction>,
+    },
+    Event {
+        version: U64,
+        event: Event,
+    },
+    Block {
+        version: U64,
+        block: StreamBlockHeader,
+    },
+    /// The stream failed, and is about to be closed
+    Error {
+        message: String,
+    },
+}
+
+/// The messages of a stream subscription, received as server-sent events.
+pub struct MessageStream {
+    response: reqwest::Response,
+    buffer: Vec<u8>,
+}
+
+impl MessageStream {
+    pub(crate) fn new(response: reqwest::Response) -> Self {
+        Self {
+            response,
+            buffer: vec![],
+        }
+    }
+
+    /// Returns the next message, or None if the server closed the stream.
+    pub async fn next(&mut self) -> Result<Option<StreamMessage>, RestError> {
+        loop {
+            // Server-sent events are separated by blank lines
+            if let Some(end) = self.buffer.windows(2).position(|bytes| bytes == b"\n\n") {
+                let event: Vec<u8> = self.buffer.drain(..end + 2).collect();
+                match parse_event_data(&String::from_utf8_lossy(&event)) {
+                    Some(data) => return Ok(Some(serde_json::from_str(&data)?)),
+                    None => continue,
+                }
+            }
+            match self.response.chunk().await? {
+                Some(chunk) => self
+                    .buffer
+                    .extend(chunk.iter().filter(|byte| **byte != b'\r')),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Returns the data of a server-sent event, or None if it has none (e.g., keep-alives).
+fn parse_event_data(event: &str) -> Option<String> {
+    let data: Vec<_> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    if data.is_empty() {
+        None
+    } else {
+        Some(data.join("\n"))
+    }
+}
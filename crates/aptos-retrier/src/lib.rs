@@ -4,7 +4,10 @@
 
 #![forbid(unsafe_code)]
 
+mod policy;
+
 use aptos_logger::debug;
+pub use policy::{hedge, retry_with_policy, Backoff, RetryBudget, RetryDecision, RetryPolicy};
 use std::{
     cmp::min,
     future::Future,
@@ -0,0 +1,430 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_logger::debug;
+use futures::stream::{FuturesUnordered, StreamExt};
+use rand::Rng;
+use std::{
+    fmt::Debug,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The (fixed point) cost of a single retry in a `RetryBudget`
+const RETRY_COST: u64 = 1000;
+/// The cap on retry delays, unless configured otherwise
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(600);
+
+/// What to do after an attempt of an operation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RetryDecision {
+    /// Retry after the backoff delay of the policy
+    Retry,
+    /// Retry after the given delay, e.g. when the server told us when to come back
+    RetryAfter(Duration),
+    /// Return the result of the attempt as is
+    DoNotRetry,
+}
+
+/// A policy for how often and how fast an operation is retried.
+///
+/// The delay before the n-th retry is `initial_delay * multiplier^n`, randomized
+/// by +/- `jitter` (a fraction of the delay) to avoid many clients retrying in
+/// lockstep, and capped at `max_delay`. Retries stop once `max_retries` or
+/// `max_elapsed` is reached, or when the (optionally shared) budget is exhausted.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    jitter: f64,
+    max_retries: Option<u32>,
+    max_elapsed: Option<Duration>,
+    budget: Option<Arc<RetryBudget>>,
+}
+
+impl RetryPolicy {
+    /// A policy that doubles the delay after every retry, with 20% jitter
+    pub fn exponential(initial_delay: Duration) -> Self {
+        Self {
+            initial_delay,
+            max_delay: DEFAULT_MAX_DELAY,
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_retries: None,
+            max_elapsed: None,
+            budget: None,
+        }
+    }
+
+    /// A policy that always waits for the same delay, without jitter
+    pub fn fixed(delay: Duration) -> Self {
+        Self {
+            multiplier: 1.0,
+            jitter: 0.0,
+            ..Self::exponential(delay)
+        }
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    pub fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// Shares the given budget between all users of this policy
+    pub fn with_budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Returns the delay before the retry following `num_past_retries` retries
+    pub fn delay_for_retry(&self, num_past_retries: u32) -> Duration {
+        let delay = self.base_delay_for_retry(num_past_retries);
+        if self.jitter == 0.0 {
+            return delay;
+        }
+        let factor = 1.0 - self.jitter + 2.0 * self.jitter * rand::thread_rng().gen::<f64>();
+        Duration::from_secs_f64((delay.as_secs_f64() * factor).min(self.max_delay.as_secs_f64()))
+    }
+
+    /// Returns the delay before the retry following `num_past_retries` retries, without jitter
+    fn base_delay_for_retry(&self, num_past_retries: u32) -> Duration {
+        // Computed in nanoseconds, so that integral multipliers give exact delays
+        let delay_nanos =
+            self.initial_delay.as_nanos() as f64 * self.multiplier.powf(num_past_retries as f64);
+        if delay_nanos >= self.max_delay.as_nanos() as f64 {
+            self.max_delay
+        } else {
+            Duration::from_nanos(delay_nanos.round() as u64)
+        }
+    }
+
+    /// Returns true iff another retry is allowed, and spends the budget for it.
+    /// `elapsed` is the time spent so far, including the delay before the retry.
+    pub fn should_retry(&self, num_past_retries: u32, elapsed: Duration) -> bool {
+        if matches!(self.max_retries, Some(max_retries) if num_past_retries >= max_retries) {
+            return false;
+        }
+        if matches!(self.max_elapsed, Some(max_elapsed) if elapsed > max_elapsed) {
+            return false;
+        }
+        self.budget
+            .as_ref()
+            .map_or(true, |budget| budget.try_withdraw())
+    }
+
+    /// Returns the delays of this policy as an iterator, for use with `retry` and `retry_async`
+    pub fn backoff(&self) -> Backoff {
+        Backoff {
+            policy: self.clone(),
+            num_past_retries: 0,
+            start: Instant::now(),
+        }
+    }
+
+    fn record_request(&self) {
+        if let Some(budget) = &self.budget {
+            budget.deposit();
+        }
+    }
+}
+
+/// An iterator over the retry delays of a `RetryPolicy`
+pub struct Backoff {
+    policy: RetryPolicy,
+    num_past_retries: u32,
+    start: Instant,
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        let delay = self.policy.delay_for_retry(self.num_past_retries);
+        if !self
+            .policy
+            .should_retry(self.num_past_retries, self.start.elapsed() + delay)
+        {
+            return None;
+        }
+        self.num_past_retries += 1;
+        Some(delay)
+    }
+}
+
+/// Limits retries to a fraction of the requests made, so that a struggling
+/// downstream doesn't get overwhelmed by retries on top of the regular load.
+///
+/// Every request deposits `retry_ratio` tokens (up to `max_retries` tokens),
+/// and every retry withdraws a full token. The budget starts full.
+#[derive(Debug)]
+pub struct RetryBudget {
+    balance: AtomicU64,
+    deposit_per_request: u64,
+    max_balance: u64,
+}
+
+impl RetryBudget {
+    pub fn new(retry_ratio: f64, max_retries: u32) -> Self {
+        let max_balance = max_retries as u64 * RETRY_COST;
+        Self {
+            balance: AtomicU64::new(max_balance),
+            deposit_per_request: (retry_ratio.max(0.0) * RETRY_COST as f64) as u64,
+            max_balance,
+        }
+    }
+
+    pub fn deposit(&self) {
+        let _ = self
+            .balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |balance| {
+                Some(
+                    balance
+                        .saturating_add(self.deposit_per_request)
+                        .min(self.max_balance),
+                )
+            });
+    }
+
+    pub fn try_withdraw(&self) -> bool {
+        self.balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |balance| {
+                balance.checked_sub(RETRY_COST)
+            })
+            .is_ok()
+    }
+}
+
+/// Runs the operation until `should_retry` decides the result is final, or the policy
+/// doesn't allow any more retries, and returns the last result.
+pub async fn retry_with_policy<T, E, O, Fut, C>(
+    policy: &RetryPolicy,
+    should_retry: C,
+    mut operation: O,
+) -> Result<T, E>
+where
+    O: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    C: Fn(&Result<T, E>) -> RetryDecision,
+    E: Debug,
+{
+    policy.record_request();
+    let start = Instant::now();
+    let mut num_past_retries = 0;
+    loop {
+        let result = operation().await;
+        let delay = match should_retry(&result) {
+            RetryDecision::DoNotRetry => return result,
+            RetryDecision::Retry => policy.delay_for_retry(num_past_retries),
+            RetryDecision::RetryAfter(delay) => delay,
+        };
+        if !policy.should_retry(num_past_retries, start.elapsed() + delay) {
+            return result;
+        }
+        if let Err(error) = &result {
+            debug!("{:?}. Retrying in {} ms..", error, delay.as_millis());
+        }
+        tokio::time::sleep(delay).await;
+        num_past_retries += 1;
+    }
+}
+
+/// Runs the operation, and starts another (hedged) attempt every `hedge_delay` while
+/// none has succeeded yet, up to `max_attempts` attempts in total. Failed attempts are
+/// replaced right away. Returns the first success, or the last error if all attempts fail.
+///
+/// This trades extra load for lower tail latency, so it should only be used for
+/// idempotent, cheap operations.
+pub async fn hedge<T, E, O, Fut>(
+    hedge_delay: Duration,
+    max_attempts: usize,
+    mut operation: O,
+) -> Result<T, E>
+where
+    O: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut in_flight = FuturesUnordered::new();
+    in_flight.push(operation());
+    let mut num_attempts = 1;
+    loop {
+        tokio::select! {
+            Some(result) = in_flight.next() => match result {
+                Ok(value) => return Ok(value),
+                Err(error) => {
+                    if num_attempts < max_attempts {
+                        in_flight.push(operation());
+                        num_attempts += 1;
+                    } else if in_flight.is_empty() {
+                        return Err(error);
+                    }
+                },
+            },
+            _ = tokio::time::sleep(hedge_delay), if num_attempts < max_attempts => {
+                in_flight.push(operation());
+                num_attempts += 1;
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_exponential_delays() {
+        let policy = RetryPolicy::exponential(Duration::from_millis(100))
+            .with_jitter(0.0)
+            .with_max_delay(Duration::from_millis(500));
+        let delays: Vec<_> = (0..5).map(|n| policy.delay_for_retry(n)).collect();
+        assert_eq!(delays, vec![
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(400),
+            Duration::from_millis(500),
+            Duration::from_millis(500),
+        ]);
+        // Large retry counts must not overflow
+        assert_eq!(policy.delay_for_retry(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_jitter_bounds() {
+        let policy = RetryPolicy::exponential(Duration::from_millis(1000)).with_jitter(0.5);
+        let capped_policy = policy.clone().with_max_delay(Duration::from_millis(1200));
+        for _ in 0..100 {
+            let delay = policy.delay_for_retry(0);
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_millis(1500));
+            assert!(capped_policy.delay_for_retry(0) <= Duration::from_millis(1200));
+        }
+    }
+
+    #[test]
+    fn test_backoff_max_retries() {
+        let policy = RetryPolicy::fixed(Duration::from_millis(10)).with_max_retries(3);
+        assert_eq!(policy.backoff().count(), 3);
+    }
+
+    #[test]
+    fn test_budget() {
+        let budget = RetryBudget::new(0.5, 2);
+        assert!(budget.try_withdraw());
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+
+        // Two requests pay for a single retry
+        budget.deposit();
+        assert!(!budget.try_withdraw());
+        budget.deposit();
+        assert!(budget.try_withdraw());
+
+        // The balance is capped
+        for _ in 0..10 {
+            budget.deposit();
+        }
+        assert!(budget.try_withdraw());
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_policy_decisions() {
+        let policy = RetryPolicy::fixed(Duration::from_millis(1)).with_max_retries(10);
+
+        // Retry until success
+        let attempts = AtomicUsize::new(0);
+        let result: Result<usize, &str> = retry_with_policy(
+            &policy,
+            |result| match result {
+                Ok(_) => RetryDecision::DoNotRetry,
+                Err(_) => RetryDecision::Retry,
+            },
+            || async {
+                match attempts.fetch_add(1, Ordering::Relaxed) {
+                    attempt if attempt < 3 => Err("transient"),
+                    attempt => Ok(attempt),
+                }
+            },
+        )
+        .await;
+        assert_eq!(result, Ok(3));
+
+        // Permanent errors are returned right away
+        let attempts = AtomicUsize::new(0);
+        let result: Result<(), &str> = retry_with_policy(
+            &policy,
+            |_| RetryDecision::DoNotRetry,
+            || async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Err("permanent")
+            },
+        )
+        .await;
+        assert_eq!(result, Err("permanent"));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+
+        // Retries stop once the policy is exhausted
+        let attempts = AtomicUsize::new(0);
+        let result: Result<(), &str> = retry_with_policy(
+            &policy,
+            |_| RetryDecision::Retry,
+            || async {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                Err("transient")
+            },
+        )
+        .await;
+        assert_eq!(result, Err("transient"));
+        assert_eq!(attempts.load(Ordering::Relaxed), 11);
+    }
+
+    #[tokio::test]
+    async fn test_hedge() {
+        // The first attempt hangs, so the hedged attempt wins
+        let attempts = AtomicUsize::new(0);
+        let result: Result<usize, ()> = hedge(Duration::from_millis(10), 2, || async {
+            let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+            if attempt == 0 {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+            Ok(attempt)
+        })
+        .await;
+        assert_eq!(result, Ok(1));
+
+        // All attempts fail
+        let attempts = AtomicUsize::new(0);
+        let result: Result<(), usize> = hedge(Duration::from_millis(10), 3, || async {
+            Err(attempts.fetch_add(1, Ordering::Relaxed))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+    }
+}
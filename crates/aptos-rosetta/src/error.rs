@@ -49,6 +49,7 @@ pub enum ApiError {
     SequenceNumberTooOld(Option<String>),
     VmError(Option<String>),
     MempoolIsFull(Option<String>),
+    MempoolRateLimited(Option<String>),
 }
 
 impl std::fmt::Display for ApiError {
@@ -97,6 +98,7 @@ impl ApiError {
             SequenceNumberTooOld(None),
             VmError(None),
             MempoolIsFull(None),
+            MempoolRateLimited(None),
         ]
     }
 
@@ -137,6 +139,7 @@ impl ApiError {
             MempoolIsFull(_) => 32,
             CoinTypeFailedToBeFetched(_) => 33,
             StateValueNotFound(_) => 34,
+            MempoolRateLimited(_) => 35,
         }
     }
 
@@ -147,6 +150,7 @@ impl ApiError {
             AccountNotFound(_)
                 | BlockNotFound(_)
                 | MempoolIsFull(_)
+                | MempoolRateLimited(_)
                 | GasEstimationFailed(_)
                 | CoinTypeFailedToBeFetched(_)
         )
@@ -193,6 +197,7 @@ impl ApiError {
             ApiError::SequenceNumberTooOld(_) => "Sequence number too old.  Please create a new transaction with an updated sequence number",
             ApiError::VmError(_) => "Transaction submission failed due to VM error",
             ApiError::MempoolIsFull(_) => "Mempool is full all accounts",
+            ApiError::MempoolRateLimited(_) => "Sender exceeded the mempool submission rate limits, please retry later",
             ApiError::GasEstimationFailed(_) => "Gas estimation failed",
         }
     }
@@ -222,6 +227,7 @@ impl ApiError {
             ApiError::SequenceNumberTooOld(inner) => inner,
             ApiError::VmError(inner) => inner,
             ApiError::MempoolIsFull(inner) => inner,
+            ApiError::MempoolRateLimited(inner) => inner,
             ApiError::GasEstimationFailed(inner) => inner,
             ApiError::MaxGasFeeTooLow(inner) => inner,
             _ => None,
@@ -283,7 +289,7 @@ impl From<RestError> for ApiError {
                 AptosErrorCode::VersionPruned => ApiError::VersionPruned(Some(err.error.message)),
                 AptosErrorCode::BlockPruned => ApiError::BlockPruned(Some(err.error.message)),
                 AptosErrorCode::InvalidInput => ApiError::InvalidInput(Some(err.error.message)),
-                AptosErrorCode::InvalidTransactionUpdate => {
+                AptosErrorCode::InvalidTransactionUpdate | AptosErrorCode::TransactionFiltered => {
                     ApiError::InvalidInput(Some(err.error.message))
                 },
                 AptosErrorCode::SequenceNumberTooOld => {
@@ -294,12 +300,17 @@ impl From<RestError> for ApiError {
                     ApiError::InternalError(Some(err.error.message))
                 },
                 AptosErrorCode::MempoolIsFull => ApiError::MempoolIsFull(Some(err.error.message)),
+                AptosErrorCode::MempoolRateLimited => {
+                    ApiError::MempoolRateLimited(Some(err.error.message))
+                },
                 AptosErrorCode::WebFrameworkError => {
                     ApiError::InternalError(Some(err.error.message))
                 },
                 AptosErrorCode::BcsNotSupported => ApiError::InvalidInput(Some(err.error.message)),
                 AptosErrorCode::InternalError => ApiError::InternalError(Some(err.error.message)),
-                AptosErrorCode::ApiDisabled => ApiError::InternalError(Some(err.error.message)),
+                AptosErrorCode::ApiDisabled
+                | AptosErrorCode::ApiKeyInvalid
+                | AptosErrorCode::QuotaExceeded => ApiError::InternalError(Some(err.error.message)),
             },
             RestError::Bcs(_) => ApiError::DeserializationFailed(None),
             RestError::Json(_) => ApiError::DeserializationFailed(None),
@@ -27,6 +27,8 @@ pub const STORE_RESOURCE: &str = "Store";
 pub const STAKING_GROUP_UPDATE_COMMISSION_RESOURCE: &str = "StakingGroupUpdateCommissionEvent";
 pub const VESTING_RESOURCE: &str = "Vesting";
 pub const DELEGATION_POOL_RESOURCE: &str = "DelegationPool";
+pub const ADD_STAKE_EVENT: &str = "AddStakeEvent";
+pub const UNLOCK_STAKE_EVENT: &str = "UnlockStakeEvent";
 pub const WITHDRAW_STAKE_EVENT: &str = "WithdrawStakeEvent";
 
 pub const CREATE_ACCOUNT_FUNCTION: &str = "create_account";
@@ -229,6 +231,7 @@ pub struct AddDelegationEvent {
     pub pool_address: AccountAddress,
     pub delegator_address: AccountAddress,
     pub amount_added: u64,
+    pub add_stake_fee: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
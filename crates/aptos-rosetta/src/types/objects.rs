@@ -1696,8 +1696,12 @@ async fn parse_update_commission(
     Ok(operations)
 }
 
+/// Parses the delegator operations of a delegation pool from its events
+///
+/// Adding stake moves coins into the delegator's active stake, unlocking moves active stake to
+/// pending_inactive, and withdrawing moves inactive stake back to the delegator's coin store.
 async fn parse_delegation_pool_resource_changes(
-    _owner_address: AccountAddress,
+    pool_address: AccountAddress,
     _data: &[u8],
     events: &[ContractEvent],
     mut operation_index: u64,
@@ -1716,6 +1720,57 @@ async fn parse_delegation_pool_resource_changes(
             struct_tag.module.as_str(),
             struct_tag.name.as_str(),
         ) {
+            (AccountAddress::ONE, DELEGATION_POOL_MODULE, ADD_STAKE_EVENT) => {
+                let event: AddDelegationEvent = if let Ok(event) = bcs::from_bytes(e.event_data()) {
+                    event
+                } else {
+                    warn!(
+                        "Failed to parse add delegated stake event! Skipping for {}:{}",
+                        e.key().get_creator_address(),
+                        e.key().get_creation_number()
+                    );
+                    continue;
+                };
+
+                // Events of other pools are handled with their own resource changes
+                if event.pool_address != pool_address {
+                    continue;
+                }
+
+                operations.push(Operation::add_delegated_stake(
+                    operation_index,
+                    Some(OperationStatusType::Success),
+                    event.delegator_address,
+                    AccountIdentifier::base_account(event.pool_address),
+                    Some(event.amount_added),
+                ));
+                operation_index += 1;
+            },
+            (AccountAddress::ONE, DELEGATION_POOL_MODULE, UNLOCK_STAKE_EVENT) => {
+                let event: UndelegationEvent = if let Ok(event) = bcs::from_bytes(e.event_data()) {
+                    event
+                } else {
+                    warn!(
+                        "Failed to parse unlock delegated stake event! Skipping for {}:{}",
+                        e.key().get_creator_address(),
+                        e.key().get_creation_number()
+                    );
+                    continue;
+                };
+
+                if event.pool_address != pool_address {
+                    continue;
+                }
+
+                operations.push(Operation::unlock_delegated_stake(
+                    operation_index,
+                    Some(OperationStatusType::Success),
+                    event.delegator_address,
+                    AccountIdentifier::base_account(event.pool_address),
+                    Some(event.amount_unlocked),
+                ));
+                operation_index += 1;
+            },
             (AccountAddress::ONE, DELEGATION_POOL_MODULE, WITHDRAW_STAKE_EVENT) => {
                 let event: WithdrawUndelegedEvent =
                     if let Ok(event) = bcs::from_bytes(e.event_data()) {
@@ -1729,6 +1784,10 @@ async fn parse_delegation_pool_resource_changes(
                         continue;
                     };
 
+                if event.pool_address != pool_address {
+                    continue;
+                }
+
                 operations.push(Operation::withdraw_undelegated_stake(
                     operation_index,
                     Some(OperationStatusType::Success),
@@ -2424,3 +2483,88 @@ pub struct UnlockDelegatedStake {
     pub pool_address: AccountAddress,
     pub amount: u64,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use move_core_types::{identifier::Identifier, language_storage::StructTag};
+
+    fn delegation_pool_event<T: Serialize>(name: &str, event: &T) -> ContractEvent {
+        ContractEvent::new(
+            EventKey::new(0, AccountAddress::ONE),
+            0,
+            TypeTag::Struct(Box::new(StructTag {
+                address: AccountAddress::ONE,
+                module: Identifier::new(DELEGATION_POOL_MODULE).unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_params: vec![],
+            })),
+            bcs::to_bytes(event).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_parse_delegation_pool_events() {
+        let pool_address = AccountAddress::from_hex_literal("0xabc").unwrap();
+        let other_pool_address = AccountAddress::from_hex_literal("0xdef").unwrap();
+        let delegator_address = AccountAddress::from_hex_literal("0x123").unwrap();
+        let events = vec![
+            delegation_pool_event(ADD_STAKE_EVENT, &AddDelegationEvent {
+                pool_address,
+                delegator_address,
+                amount_added: 300,
+                add_stake_fee: 1,
+            }),
+            delegation_pool_event(UNLOCK_STAKE_EVENT, &UndelegationEvent {
+                pool_address,
+                delegator_address,
+                amount_unlocked: 200,
+            }),
+            delegation_pool_event(WITHDRAW_STAKE_EVENT, &WithdrawUndelegedEvent {
+                pool_address,
+                delegator_address,
+                amount_withdrawn: 100,
+            }),
+            // Events of other pools are skipped
+            delegation_pool_event(ADD_STAKE_EVENT, &AddDelegationEvent {
+                pool_address: other_pool_address,
+                delegator_address,
+                amount_added: 400,
+                add_stake_fee: 1,
+            }),
+        ];
+
+        let operations = parse_delegation_pool_resource_changes(
+            pool_address,
+            &[],
+            &events,
+            5,
+            &WriteSet::default(),
+        )
+        .await
+        .unwrap();
+
+        let expected = [
+            (OperationType::AddDelegatedStake, 300),
+            (OperationType::UnlockDelegatedStake, 200),
+            (OperationType::WithdrawUndelegatedFunds, 100),
+        ];
+        assert_eq!(operations.len(), expected.len());
+        for (i, (operation, (operation_type, amount))) in
+            operations.iter().zip(expected.into_iter()).enumerate()
+        {
+            assert_eq!(operation.operation_identifier.index, 5 + i as u64);
+            assert_eq!(operation.operation_type(), Some(operation_type));
+            assert_eq!(operation.account(), Some(delegator_address));
+            assert_eq!(operation.metadata_amount(), Some(amount));
+            assert_eq!(
+                operation
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.pool_address.as_ref())
+                    .map(|pool| pool.account_address().unwrap()),
+                Some(pool_address)
+            );
+        }
+    }
+}
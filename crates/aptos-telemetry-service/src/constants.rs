@@ -4,6 +4,9 @@
 /// The maximum content length to accept in the http body.
 pub const MAX_CONTENT_LENGTH: u64 = 1024 * 1024;
 
+/// The maximum length to accept for a gzip encoded http body, once decompressed.
+pub const MAX_DECOMPRESSED_CONTENT_LENGTH: u64 = 16 * MAX_CONTENT_LENGTH;
+
 /// GCP Header field for the current request's trace ID.
 pub const GCP_CLOUD_TRACE_CONTEXT_HEADER: &str = "X-Cloud-Trace-Context";
 
@@ -3,6 +3,7 @@
 
 use crate::{
     auth::with_auth,
+    constants::{MAX_CONTENT_LENGTH, MAX_DECOMPRESSED_CONTENT_LENGTH},
     context::Context,
     debug, error,
     errors::{CustomEventIngestError, ServiceError},
@@ -10,15 +11,17 @@ use crate::{
     types::{
         auth::Claims,
         common::{EventIdentity, NodeType},
-        telemetry::{BigQueryRow, TelemetryDump},
+        telemetry::{BigQueryRow, CustomEventBatchResponse, TelemetryDump},
     },
 };
 use anyhow::anyhow;
+use flate2::bufread::GzDecoder;
 use gcp_bigquery_client::model::table_data_insert_all_request::TableDataInsertAllRequest;
+use reqwest::header::CONTENT_ENCODING;
 use serde_json::json;
-use std::time::Duration;
+use std::{io::Read, time::Duration};
 use tokio::time::Instant;
-use warp::{filters::BoxedFilter, hyper::StatusCode, reject, reply, Filter, Rejection, Reply};
+use warp::{filters::BoxedFilter, hyper::StatusCode, reject, reply, Buf, Filter, Rejection, Reply};
 
 pub fn custom_event_ingest(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("ingest" / "custom-event")
@@ -37,27 +40,130 @@ pub fn custom_event_ingest(context: Context) -> BoxedFilter<(impl Reply,)> {
         .boxed()
 }
 
+/// Ingests a batch of telemetry dumps (possibly gzip encoded), e.g., the events spooled by a node
+/// while the service was unreachable.
+pub fn custom_event_batch_ingest(context: Context) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("ingest" / "custom-event" / "batch")
+        .and(warp::post())
+        .and(context.clone().filter())
+        .and(with_auth(context, vec![
+            NodeType::Validator,
+            NodeType::ValidatorFullNode,
+            NodeType::PublicFullNode,
+            NodeType::Unknown,
+            NodeType::UnknownValidator,
+            NodeType::UnknownFullNode,
+        ]))
+        .and(warp::header::optional(CONTENT_ENCODING.as_str()))
+        .and(warp::body::content_length_limit(MAX_CONTENT_LENGTH))
+        .and(warp::body::aggregate())
+        .and_then(handle_custom_event_batch)
+        .boxed()
+}
+
 pub(crate) async fn handle_custom_event(
     context: Context,
     claims: Claims,
     body: TelemetryDump,
 ) -> anyhow::Result<impl Reply, Rejection> {
-    if !body
-        .user_id
-        .eq_ignore_ascii_case(&claims.peer_id.to_string())
-    {
-        return Err(reject::custom(ServiceError::bad_request(
-            CustomEventIngestError::InvalidEvent(body.user_id, claims.peer_id).into(),
-        )));
+    let event_identity = EventIdentity::from(claims);
+    let row = dump_row(&event_identity, body)?;
+
+    let mut insert_request = TableDataInsertAllRequest::new();
+    insert_request.add_row(None, &row).map_err(|e| {
+        error!("unable to create row: {}", e);
+        ServiceError::internal(CustomEventIngestError::from(e).into())
+    })?;
+    insert_rows(&context, insert_request).await?;
+
+    debug!("row inserted succeefully: {:?}", &row);
+
+    Ok(reply::with_status(reply::reply(), StatusCode::CREATED))
+}
+
+pub(crate) async fn handle_custom_event_batch(
+    context: Context,
+    claims: Claims,
+    encoding: Option<String>,
+    body: impl Buf,
+) -> anyhow::Result<impl Reply, Rejection> {
+    // The compressed body is bounded by the content length limit, but the decompressed
+    // one must be bounded as well (so a small body can't decompress into gigabytes).
+    let dumps: Vec<TelemetryDump> = match encoding {
+        Some(encoding) if encoding.eq_ignore_ascii_case("gzip") => serde_json::from_reader(
+            GzDecoder::new(body.reader()).take(MAX_DECOMPRESSED_CONTENT_LENGTH),
+        ),
+        Some(_) => {
+            return Err(reject::custom(ServiceError::bad_request(
+                CustomEventIngestError::UnexpectedContentEncoding.into(),
+            )))
+        },
+        None => serde_json::from_reader(body.reader()),
     }
+    .map_err(|e| {
+        debug!("unable to decode and deserialize body: {}", e);
+        ServiceError::bad_request(CustomEventIngestError::UnexpectedPayloadBody.into())
+    })?;
 
-    if body.events.is_empty() {
+    if dumps.is_empty() {
         return Err(reject::custom(ServiceError::bad_request(
             CustomEventIngestError::EmptyPayload.into(),
         )));
     }
 
+    // Invalid dumps are rejected on their own, so they don't prevent the ingestion
+    // of the rest of the batch
+    let event_identity = EventIdentity::from(claims);
+    let mut response = CustomEventBatchResponse::default();
     let mut insert_request = TableDataInsertAllRequest::new();
+    let mut first_error = None;
+    for (index, dump) in dumps.into_iter().enumerate() {
+        let row = match dump_row(&event_identity, dump) {
+            Ok(row) => row,
+            Err(e) => {
+                debug!("rejecting telemetry dump {} of the batch: {}", index, e);
+                response.rejected_dumps.push((index, e.to_string()));
+                first_error.get_or_insert(e);
+                continue;
+            },
+        };
+        insert_request.add_row(None, &row).map_err(|e| {
+            error!("unable to create row: {}", e);
+            ServiceError::internal(CustomEventIngestError::from(e).into())
+        })?;
+        response.num_ingested += 1;
+    }
+    if response.num_ingested == 0 {
+        return Err(reject::custom(
+            first_error.expect("at least one dump must have been rejected"),
+        ));
+    }
+    insert_rows(&context, insert_request).await?;
+
+    debug!("{} rows inserted succeefully", response.num_ingested);
+
+    Ok(reply::with_status(reply::json(&response), StatusCode::CREATED))
+}
+
+/// Validates the dump and converts its event to a BigQuery row
+fn dump_row(
+    event_identity: &EventIdentity,
+    body: TelemetryDump,
+) -> Result<BigQueryRow, ServiceError> {
+    if !body
+        .user_id
+        .eq_ignore_ascii_case(&event_identity.peer_id.to_string())
+    {
+        return Err(ServiceError::bad_request(
+            CustomEventIngestError::InvalidEvent(body.user_id, event_identity.peer_id).into(),
+        ));
+    }
+
+    if body.events.is_empty() {
+        return Err(ServiceError::bad_request(
+            CustomEventIngestError::EmptyPayload.into(),
+        ));
+    }
 
     let telemetry_event = &body.events[0];
     let event_params: Vec<serde_json::Value> = telemetry_event
@@ -74,22 +180,22 @@ pub(crate) async fn handle_custom_event(
     let duration =
         Duration::from_micros(body.timestamp_micros.as_str().parse::<u64>().map_err(|_| {
             ServiceError::bad_request(
-                CustomEventIngestError::InvalidTimestamp(body.timestamp_micros).into(),
+                CustomEventIngestError::InvalidTimestamp(body.timestamp_micros.clone()).into(),
             )
         })?);
 
-    let row = BigQueryRow {
-        event_identity: EventIdentity::from(claims),
+    Ok(BigQueryRow {
+        event_identity: event_identity.clone(),
         event_name: telemetry_event.name.clone(),
         event_timestamp: duration.as_secs(),
         event_params,
-    };
-
-    insert_request.add_row(None, &row).map_err(|e| {
-        error!("unable to create row: {}", e);
-        ServiceError::internal(CustomEventIngestError::from(e).into())
-    })?;
+    })
+}
 
+async fn insert_rows(
+    context: &Context,
+    insert_request: TableDataInsertAllRequest,
+) -> Result<(), ServiceError> {
     let start_timer = Instant::now();
 
     context
@@ -121,11 +227,7 @@ pub(crate) async fn handle_custom_event(
                 BIG_QUERY_BACKEND_REQUEST_DURATION
                     .with_label_values(&["success"])
                     .observe(start_timer.elapsed().as_secs_f64());
-                Ok(result)
+                Ok(())
             }
-        })?;
-
-    debug!("row inserted succeefully: {:?}", &row);
-
-    Ok(reply::with_status(reply::reply(), StatusCode::CREATED))
+        })
 }
@@ -33,6 +33,7 @@ pub fn routes(
             .or(auth::check_chain_access(context.clone()))
             .or(auth::auth(context.clone()))
             .or(custom_event::custom_event_ingest(context.clone()))
+            .or(custom_event::custom_event_batch_ingest(context.clone()))
             .or(prometheus_push_metrics::metrics_ingest(context.clone()))
             .or(log_ingest::log_ingest(context.clone()))
             .or(remote_config::telemetry_log_env(context)),
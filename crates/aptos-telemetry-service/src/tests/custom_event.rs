@@ -3,6 +3,7 @@
 
 use super::test_context::new_test_context;
 use crate::{
+    constants::{MAX_CONTENT_LENGTH, MAX_DECOMPRESSED_CONTENT_LENGTH},
     jwt_auth::create_jwt_token,
     types::{
         common::NodeType,
@@ -12,8 +13,10 @@ use crate::{
 use aptos_config::config::PeerSet;
 use aptos_types::{chain_id::ChainId, PeerId};
 use chrono::Utc;
+use flate2::{write::GzEncoder, Compression};
+use reqwest::header::{AUTHORIZATION, CONTENT_ENCODING};
 use serde_json::json;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, io::Write};
 use uuid::Uuid;
 
 #[tokio::test]
@@ -57,3 +60,100 @@ async fn test_custom_event() {
         .post("/api/v1/ingest/custom-event", json!(body))
         .await;
 }
+
+#[tokio::test]
+async fn test_custom_event_batch() {
+    let test_context = new_test_context().await;
+    let chain_id = ChainId::new(28);
+    let peer_id = PeerId::random();
+    let node_type = NodeType::Validator;
+    let uuid = Uuid::new_v4();
+    let epoch = 10;
+
+    test_context
+        .inner
+        .peers()
+        .validators()
+        .write()
+        .insert(chain_id, (epoch, PeerSet::default()));
+
+    let jwt_token = create_jwt_token(
+        test_context.inner.jwt_service(),
+        chain_id,
+        peer_id,
+        node_type,
+        epoch,
+        uuid,
+    )
+    .unwrap();
+
+    let dump = |user_id: String| TelemetryDump {
+        client_id: "test-client".into(),
+        user_id,
+        timestamp_micros: Utc::now().timestamp_micros().to_string(),
+        events: vec![TelemetryEvent {
+            name: "sample-event".into(),
+            params: BTreeMap::new(),
+        }],
+    };
+
+    // The batch is valid, but there's no BigQuery client in tests
+    let body = vec![dump(peer_id.to_string()), dump(peer_id.to_string())];
+    test_context
+        .with_bearer_auth(jwt_token.clone())
+        .expect_status_code(500)
+        .post("/api/v1/ingest/custom-event/batch", json!(body))
+        .await;
+
+    // Dumps that don't belong to the peer are rejected on their own, so the rest of
+    // the batch is still ingested
+    let body = vec![
+        dump(peer_id.to_string()),
+        dump(PeerId::random().to_string()),
+    ];
+    test_context
+        .with_bearer_auth(jwt_token.clone())
+        .expect_status_code(500)
+        .post("/api/v1/ingest/custom-event/batch", json!(body))
+        .await;
+
+    // The batch is rejected if none of its dumps is valid
+    let body = vec![dump(PeerId::random().to_string())];
+    test_context
+        .with_bearer_auth(jwt_token.clone())
+        .expect_status_code(400)
+        .post("/api/v1/ingest/custom-event/batch", json!(body))
+        .await;
+
+    // The decompressed body must not exceed the maximum length
+    let mut gzip_encoder = GzEncoder::new(Vec::new(), Compression::best());
+    gzip_encoder
+        .write_all(&vec![b' '; MAX_DECOMPRESSED_CONTENT_LENGTH as usize + 1])
+        .unwrap();
+    gzip_encoder
+        .write_all(&serde_json::to_vec(&body).unwrap())
+        .unwrap();
+    let compressed_body = gzip_encoder.finish().unwrap();
+    assert!(compressed_body.len() < MAX_CONTENT_LENGTH as usize);
+    let resp = test_context
+        .reply(
+            warp::test::request()
+                .method("POST")
+                .path("/api/v1/ingest/custom-event/batch")
+                .header(AUTHORIZATION, format!("Bearer {}", jwt_token))
+                .header(CONTENT_ENCODING, "gzip")
+                .body(compressed_body),
+        )
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    // The batch must not be empty
+    test_context
+        .with_bearer_auth(jwt_token)
+        .expect_status_code(400)
+        .post(
+            "/api/v1/ingest/custom-event/batch",
+            json!(Vec::<TelemetryDump>::new()),
+        )
+        .await;
+}
@@ -21,6 +21,15 @@ pub struct TelemetryDump {
     pub events: Vec<TelemetryEvent>,
 }
 
+/// The response to a batch of telemetry dumps. Invalid dumps are rejected on their own, and
+/// the other dumps of the batch are still ingested.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct CustomEventBatchResponse {
+    pub num_ingested: usize,
+    /// The index (in the batch) and the error of each rejected dump
+    pub rejected_dumps: Vec<(usize, String)>,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub(crate) struct BigQueryRow {
     #[serde(flatten)]
@@ -43,3 +43,10 @@ pub(crate) const NODE_CONFIG_FREQ_SECS: u64 = 60 * 60; // 60 minutes
 pub(crate) const PROMETHEUS_PUSH_METRICS_FREQ_SECS: u64 = 15; // 15 seconds
 pub(crate) const CHAIN_ACCESS_CHECK_FREQ_SECS: u64 = 30 * 60; // 30 minutes
 pub(crate) const LOG_ENV_POLL_FREQ_SECS: u64 = 5 * 60; // 5 minutes
+pub(crate) const TELEMETRY_EVENT_FLUSH_FREQ_SECS: u64 = 60; // 1 minute
+
+// Custom event pipeline (see event_pipeline.rs)
+pub(crate) const TELEMETRY_EVENT_QUEUE_SIZE: usize = 1_000;
+pub(crate) const TELEMETRY_EVENT_BATCH_SIZE: usize = 100;
+pub(crate) const TELEMETRY_EVENT_SPOOL_DIR: &str = "telemetry_spool";
+pub(crate) const TELEMETRY_EVENT_SPOOL_MAX_BYTES: u64 = 64 * 1024 * 1024; // 64 MiB
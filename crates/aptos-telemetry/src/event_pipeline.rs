@@ -0,0 +1,599 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The pipeline of the custom telemetry events sent to the telemetry service.
+//!
+//! Events are queued and uploaded in gzip compressed batches. When the telemetry service is
+//! unreachable, the batches are spooled to disk (up to a maximum size, after which the oldest
+//! batches are dropped) and uploaded in order once the service is back, including after a restart
+//! of the node. Services that don't support batches yet get the events one by one.
+
+use crate::{constants::*, metrics, sender::TelemetrySender};
+use anyhow::Result;
+use aptos_logger::{debug, warn};
+use aptos_telemetry_service::types::telemetry::TelemetryDump;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use reqwest::StatusCode;
+use std::{
+    collections::VecDeque,
+    io::Write,
+    mem,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{fs, sync::mpsc, time};
+
+/// The extension of the spooled batches
+const SPOOL_FILE_EXTENSION: &str = "json.gz";
+
+/// Queues the telemetry events of the node for upload
+#[derive(Clone)]
+pub(crate) struct EventPipeline {
+    sender: mpsc::Sender<TelemetryDump>,
+}
+
+impl EventPipeline {
+    /// Spawns the uploader of the events on the current runtime. Failed batches are spooled in
+    /// the given directory (if any).
+    pub fn spawn(telemetry_sender: TelemetrySender, spool_dir: Option<PathBuf>) -> Self {
+        let (sender, receiver) = mpsc::channel(TELEMETRY_EVENT_QUEUE_SIZE);
+        tokio::spawn(async move {
+            let spool = EventSpool::open(spool_dir, TELEMETRY_EVENT_SPOOL_MAX_BYTES).await;
+            let uploader = EventUploader {
+                telemetry_sender,
+                spool,
+            };
+            uploader
+                .run(
+                    receiver,
+                    Duration::from_secs(TELEMETRY_EVENT_FLUSH_FREQ_SECS),
+                )
+                .await
+        });
+        Self { sender }
+    }
+
+    /// Queues the event, or drops it if the queue is full.
+    pub fn push(&self, telemetry_dump: TelemetryDump) {
+        let num_events = telemetry_dump.events.len();
+        if self.sender.try_send(telemetry_dump).is_err() {
+            metrics::increment_telemetry_events_dropped_by("queue_full", num_events as u64);
+        }
+    }
+}
+
+/// The outcome of an upload
+#[derive(Debug, PartialEq, Eq)]
+enum UploadOutcome {
+    Uploaded,
+    /// The service is unreachable, so the upload should be retried later
+    Failed,
+    /// The service rejected the batch, so it must not be retried
+    Rejected,
+    /// The service doesn't support batches, so the events must be uploaded one by one
+    Unsupported,
+}
+
+impl UploadOutcome {
+    fn from_status(status: StatusCode) -> Self {
+        if status.is_success() {
+            UploadOutcome::Uploaded
+        } else if status == StatusCode::NOT_FOUND {
+            UploadOutcome::Unsupported
+        } else if status.is_client_error()
+            && status != StatusCode::UNAUTHORIZED
+            && status != StatusCode::REQUEST_TIMEOUT
+            && status != StatusCode::TOO_MANY_REQUESTS
+        {
+            UploadOutcome::Rejected
+        } else {
+            UploadOutcome::Failed
+        }
+    }
+}
+
+struct EventUploader {
+    telemetry_sender: TelemetrySender,
+    spool: EventSpool,
+}
+
+impl EventUploader {
+    async fn run(mut self, mut receiver: mpsc::Receiver<TelemetryDump>, flush_interval: Duration) {
+        let mut interval = time::interval(flush_interval);
+        let mut batch = vec![];
+        loop {
+            tokio::select! {
+                telemetry_dump = receiver.recv() => match telemetry_dump {
+                    Some(telemetry_dump) => {
+                        batch.push(telemetry_dump);
+                        if batch.len() >= TELEMETRY_EVENT_BATCH_SIZE {
+                            self.flush(&mut batch).await;
+                        }
+                    },
+                    None => {
+                        self.flush(&mut batch).await;
+                        return;
+                    },
+                },
+                _ = interval.tick() => self.flush(&mut batch).await,
+            }
+        }
+    }
+
+    /// Uploads the spooled batches, oldest first, and then the given batch. The batch is spooled
+    /// if it can't be uploaded, or if older batches are still pending (to preserve the order).
+    async fn flush(&mut self, batch: &mut Vec<TelemetryDump>) {
+        while let Some((compressed_batch, num_events)) = self.spool.front().await {
+            match self.upload(compressed_batch).await {
+                UploadOutcome::Uploaded => {
+                    metrics::increment_telemetry_events_uploaded_by(num_events as u64);
+                    self.spool.pop_front().await;
+                },
+                UploadOutcome::Rejected => {
+                    metrics::increment_telemetry_events_dropped_by("rejected", num_events as u64);
+                    self.spool.pop_front().await;
+                },
+                _ => break,
+            }
+        }
+
+        if batch.is_empty() {
+            return;
+        }
+        let batch = mem::take(batch);
+        let num_events = count_events(&batch);
+        let compressed_batch = match compress_batch(&batch) {
+            Ok(compressed_batch) => compressed_batch,
+            Err(error) => {
+                debug!("Failed to compress the telemetry events: {}", error);
+                metrics::increment_telemetry_events_dropped_by("serialization", num_events as u64);
+                return;
+            },
+        };
+        if !self.spool.is_empty() {
+            self.spool.push_back(compressed_batch, num_events).await;
+            return;
+        }
+        let outcome = self.upload(compressed_batch.clone()).await;
+        for telemetry_dump in &batch {
+            for telemetry_event in &telemetry_dump.events {
+                if outcome == UploadOutcome::Uploaded {
+                    metrics::increment_telemetry_service_successes(&telemetry_event.name);
+                } else {
+                    metrics::increment_telemetry_service_failures(&telemetry_event.name);
+                }
+            }
+        }
+        match outcome {
+            UploadOutcome::Uploaded => {
+                metrics::increment_telemetry_events_uploaded_by(num_events as u64)
+            },
+            UploadOutcome::Rejected => {
+                metrics::increment_telemetry_events_dropped_by("rejected", num_events as u64)
+            },
+            _ => self.spool.push_back(compressed_batch, num_events).await,
+        }
+    }
+
+    /// Uploads the batch. If the service doesn't support batches (i.e., it's older than the
+    /// node), the dumps of the batch are uploaded one by one instead.
+    async fn upload(&self, compressed_batch: Vec<u8>) -> UploadOutcome {
+        let outcome = match self
+            .telemetry_sender
+            .post_custom_event_batch(compressed_batch.clone())
+            .await
+        {
+            Ok(response) => {
+                let status = response.status();
+                let outcome = UploadOutcome::from_status(status);
+                if outcome != UploadOutcome::Uploaded {
+                    debug!("Failed to upload the telemetry events: {}", status);
+                }
+                outcome
+            },
+            Err(error) => {
+                debug!("Failed to upload the telemetry events: {}", error);
+                UploadOutcome::Failed
+            },
+        };
+        let outcome = match outcome {
+            UploadOutcome::Unsupported => self.upload_one_by_one(&compressed_batch).await,
+            outcome => outcome,
+        };
+        metrics::increment_telemetry_event_batches(match outcome {
+            UploadOutcome::Uploaded => "success",
+            UploadOutcome::Rejected => "rejected",
+            _ => "failure",
+        });
+        outcome
+    }
+
+    /// Uploads the dumps of the batch one by one. Dumps rejected by the service are skipped,
+    /// and the batch is uploaded again (including the dumps uploaded so far) if one fails.
+    async fn upload_one_by_one(&self, compressed_batch: &[u8]) -> UploadOutcome {
+        let batch = match decompress_batch(compressed_batch) {
+            Ok(batch) => batch,
+            Err(error) => {
+                debug!("Failed to decompress the telemetry events: {}", error);
+                return UploadOutcome::Rejected;
+            },
+        };
+        for telemetry_dump in &batch {
+            let outcome = match self
+                .telemetry_sender
+                .post_custom_event(telemetry_dump)
+                .await
+            {
+                Ok(response) => UploadOutcome::from_status(response.status()),
+                Err(error) => {
+                    debug!("Failed to upload the telemetry event: {}", error);
+                    UploadOutcome::Failed
+                },
+            };
+            match outcome {
+                UploadOutcome::Uploaded => {},
+                UploadOutcome::Rejected => debug!("The telemetry event was rejected"),
+                _ => return UploadOutcome::Failed,
+            }
+        }
+        UploadOutcome::Uploaded
+    }
+}
+
+/// Returns the number of events of the batch (each dump may hold several events)
+fn count_events(batch: &[TelemetryDump]) -> usize {
+    batch
+        .iter()
+        .map(|telemetry_dump| telemetry_dump.events.len())
+        .sum()
+}
+
+fn compress_batch(batch: &[TelemetryDump]) -> Result<Vec<u8>> {
+    let json = serde_json::to_vec(batch)?;
+    let mut gzip_encoder = GzEncoder::new(Vec::new(), Compression::default());
+    gzip_encoder.write_all(&json)?;
+    Ok(gzip_encoder.finish()?)
+}
+
+fn decompress_batch(compressed_batch: &[u8]) -> Result<Vec<TelemetryDump>> {
+    Ok(serde_json::from_reader(GzDecoder::new(compressed_batch))?)
+}
+
+/// A batch in the spool
+struct SpooledBatch {
+    /// The file of the batch, unless the batch is only kept in memory
+    path: Option<PathBuf>,
+    /// The batch, if it's kept in memory
+    compressed_batch: Option<Vec<u8>>,
+    num_bytes: u64,
+    num_events: usize,
+}
+
+/// The batches that failed to upload, in order. They're persisted as files in the spool directory
+/// (named after their sequence number and number of events), or kept in memory if the node has no
+/// spool directory.
+struct EventSpool {
+    dir: Option<PathBuf>,
+    max_bytes: u64,
+    batches: VecDeque<SpooledBatch>,
+    num_bytes: u64,
+    num_events: usize,
+    next_sequence_number: u64,
+}
+
+impl EventSpool {
+    /// Opens the spool in the given directory, and recovers the batches spooled by previous runs.
+    async fn open(dir: Option<PathBuf>, max_bytes: u64) -> Self {
+        let mut spool = Self {
+            dir: None,
+            max_bytes,
+            batches: VecDeque::new(),
+            num_bytes: 0,
+            num_events: 0,
+            next_sequence_number: 0,
+        };
+        if let Some(dir) = dir {
+            match spool.recover(&dir).await {
+                Ok(()) => spool.dir = Some(dir),
+                Err(error) => warn!(
+                    "Unable to open the telemetry spool {:?}, the events will be kept in memory: {}",
+                    dir, error
+                ),
+            }
+        }
+        spool.update_backlog_metrics();
+        spool
+    }
+
+    async fn recover(&mut self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir).await?;
+        let mut batches = vec![];
+        let mut entries = fs::read_dir(dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            match parse_spool_file_name(&path) {
+                Some((sequence_number, num_events)) => {
+                    let num_bytes = fs::metadata(&path).await?.len();
+                    batches.push((sequence_number, SpooledBatch {
+                        path: Some(path),
+                        compressed_batch: None,
+                        num_bytes,
+                        num_events,
+                    }));
+                },
+                None => debug!(
+                    "Ignoring unexpected file in the telemetry spool: {:?}",
+                    path
+                ),
+            }
+        }
+        batches.sort_by_key(|(sequence_number, _)| *sequence_number);
+
+        if let Some((sequence_number, _)) = batches.last() {
+            self.next_sequence_number = sequence_number + 1;
+        }
+        for (_, batch) in batches {
+            self.num_bytes += batch.num_bytes;
+            self.num_events += batch.num_events;
+            self.batches.push_back(batch);
+        }
+        self.evict().await;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    /// Returns the oldest batch and its number of events. Batches that can't be read are dropped.
+    async fn front(&mut self) -> Option<(Vec<u8>, usize)> {
+        while let Some(batch) = self.batches.front() {
+            let compressed_batch = match (&batch.compressed_batch, &batch.path) {
+                (Some(compressed_batch), _) => Ok(compressed_batch.clone()),
+                (None, Some(path)) => fs::read(path).await,
+                (None, None) => unreachable!("The spooled batch has no contents"),
+            };
+            match compressed_batch {
+                Ok(compressed_batch) => return Some((compressed_batch, batch.num_events)),
+                Err(error) => {
+                    warn!("Unable to read the spooled telemetry events: {}", error);
+                    let num_events = batch.num_events;
+                    self.pop_front().await;
+                    metrics::increment_telemetry_events_dropped_by(
+                        "spool_error",
+                        num_events as u64,
+                    );
+                },
+            }
+        }
+        None
+    }
+
+    /// Removes the oldest batch.
+    async fn pop_front(&mut self) {
+        if let Some(batch) = self.batches.pop_front() {
+            self.remove(batch).await;
+        }
+        self.update_backlog_metrics();
+    }
+
+    /// Spools the batch, and drops the oldest batches if the spool is full.
+    async fn push_back(&mut self, compressed_batch: Vec<u8>, num_events: usize) {
+        let sequence_number = self.next_sequence_number;
+        self.next_sequence_number += 1;
+
+        let num_bytes = compressed_batch.len() as u64;
+        let batch = match &self.dir {
+            Some(dir) => {
+                let path = dir.join(spool_file_name(sequence_number, num_events));
+                match fs::write(&path, &compressed_batch).await {
+                    Ok(()) => SpooledBatch {
+                        path: Some(path),
+                        compressed_batch: None,
+                        num_bytes,
+                        num_events,
+                    },
+                    Err(error) => {
+                        warn!("Unable to spool the telemetry events: {}", error);
+                        metrics::increment_telemetry_events_dropped_by(
+                            "spool_error",
+                            num_events as u64,
+                        );
+                        return;
+                    },
+                }
+            },
+            None => SpooledBatch {
+                path: None,
+                compressed_batch: Some(compressed_batch),
+                num_bytes,
+                num_events,
+            },
+        };
+        self.num_bytes += batch.num_bytes;
+        self.num_events += batch.num_events;
+        self.batches.push_back(batch);
+        self.evict().await;
+        self.update_backlog_metrics();
+    }
+
+    /// Drops the oldest batches until the spool fits in its maximum size.
+    async fn evict(&mut self) {
+        while self.num_bytes > self.max_bytes {
+            match self.batches.pop_front() {
+                Some(batch) => {
+                    metrics::increment_telemetry_events_dropped_by(
+                        "spool_full",
+                        batch.num_events as u64,
+                    );
+                    self.remove(batch).await;
+                },
+                None => break,
+            }
+        }
+    }
+
+    async fn remove(&mut self, batch: SpooledBatch) {
+        self.num_bytes -= batch.num_bytes;
+        self.num_events -= batch.num_events;
+        if let Some(path) = batch.path {
+            if let Err(error) = fs::remove_file(&path).await {
+                warn!("Unable to remove the spooled telemetry events: {}", error);
+            }
+        }
+    }
+
+    fn update_backlog_metrics(&self) {
+        metrics::set_telemetry_event_backlog(self.num_events as i64, self.num_bytes as i64);
+    }
+}
+
+fn spool_file_name(sequence_number: u64, num_events: usize) -> String {
+    format!(
+        "{:020}-{}.{}",
+        sequence_number, num_events, SPOOL_FILE_EXTENSION
+    )
+}
+
+/// Returns the sequence number and the number of events of a spooled batch.
+fn parse_spool_file_name(path: &Path) -> Option<(u64, usize)> {
+    let file_name = path.file_name()?.to_str()?;
+    let stem = file_name.strip_suffix(&format!(".{}", SPOOL_FILE_EXTENSION))?;
+    let (sequence_number, num_events) = stem.split_once('-')?;
+    Some((sequence_number.parse().ok()?, num_events.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::config::NodeConfig;
+    use aptos_telemetry_service::types::telemetry::TelemetryEvent;
+    use aptos_temppath::TempPath;
+    use aptos_types::chain_id::ChainId;
+    use httpmock::MockServer;
+    use reqwest::Url;
+    use std::collections::BTreeMap;
+
+    fn telemetry_dump(name: &str) -> TelemetryDump {
+        TelemetryDump {
+            client_id: "client-1".into(),
+            user_id: "user-1".into(),
+            timestamp_micros: "0".into(),
+            events: vec![TelemetryEvent {
+                name: name.into(),
+                params: BTreeMap::new(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spool_recovery_and_eviction() {
+        let dir = TempPath::new();
+        dir.create_as_dir().unwrap();
+
+        let mut spool = EventSpool::open(Some(dir.path().to_path_buf()), 10).await;
+        spool.push_back(vec![1; 4], 1).await;
+        spool.push_back(vec![2; 4], 2).await;
+        assert_eq!(spool.num_events, 3);
+
+        // The spool is recovered in order after a restart
+        let mut spool = EventSpool::open(Some(dir.path().to_path_buf()), 10).await;
+        assert_eq!(spool.num_events, 3);
+        assert_eq!(spool.front().await, Some((vec![1; 4], 1)));
+
+        // The oldest batch is dropped once the spool is full
+        spool.push_back(vec![3; 4], 3).await;
+        assert_eq!(spool.num_bytes, 8);
+        assert_eq!(spool.front().await, Some((vec![2; 4], 2)));
+        spool.pop_front().await;
+        assert_eq!(spool.front().await, Some((vec![3; 4], 3)));
+        spool.pop_front().await;
+        assert!(spool.is_empty());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    async fn create_uploader(server: &MockServer) -> EventUploader {
+        let telemetry_sender = TelemetrySender::new(
+            Url::parse(&server.base_url()).expect("unable to parse base url"),
+            ChainId::default(),
+            &NodeConfig::default(),
+        );
+        telemetry_sender.set_auth_token("SECRET_JWT_TOKEN");
+        EventUploader {
+            telemetry_sender,
+            spool: EventSpool::open(None, TELEMETRY_EVENT_SPOOL_MAX_BYTES).await,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_spools_and_uploads_in_order() {
+        let server = MockServer::start();
+        let mut uploader = create_uploader(&server).await;
+
+        // The upload fails (the token is rejected and the node can't authenticate), so the
+        // batch is spooled
+        let mut unavailable = server.mock(|when, then| {
+            when.method("POST")
+                .path("/api/v1/ingest/custom-event/batch");
+            then.status(401);
+        });
+        let mut batch = vec![telemetry_dump("event-1"), telemetry_dump("event-2")];
+        let telemetry_event = batch[0].events[0].clone();
+        batch[1].events.push(telemetry_event);
+        uploader.flush(&mut batch).await;
+        assert_eq!(uploader.spool.num_events, 3);
+        unavailable.assert();
+        unavailable.delete();
+        uploader.telemetry_sender.set_auth_token("SECRET_JWT_TOKEN");
+
+        // Once the service is back, the spooled batch is uploaded before the new one
+        let available = server.mock(|when, then| {
+            when.method("POST")
+                .header("Authorization", "Bearer SECRET_JWT_TOKEN")
+                .header("Content-Encoding", "gzip")
+                .path("/api/v1/ingest/custom-event/batch");
+            then.status(201);
+        });
+        uploader.flush(&mut vec![telemetry_dump("event-3")]).await;
+        assert!(uploader.spool.is_empty());
+        available.assert_hits(2);
+    }
+
+    #[tokio::test]
+    async fn test_flush_without_batch_endpoint() {
+        let server = MockServer::start();
+        let mut uploader = create_uploader(&server).await;
+
+        // The service doesn't support batches, so the events are uploaded one by one
+        let batch_endpoint = server.mock(|when, then| {
+            when.method("POST")
+                .path("/api/v1/ingest/custom-event/batch");
+            then.status(404);
+        });
+        let mut event_endpoint = server.mock(|when, then| {
+            when.method("POST")
+                .header("Authorization", "Bearer SECRET_JWT_TOKEN")
+                .path("/api/v1/ingest/custom-event");
+            then.status(201);
+        });
+        uploader
+            .flush(&mut vec![
+                telemetry_dump("event-1"),
+                telemetry_dump("event-2"),
+            ])
+            .await;
+        assert!(uploader.spool.is_empty());
+        batch_endpoint.assert();
+        event_endpoint.assert_hits(2);
+        event_endpoint.delete();
+
+        // The batch is spooled if an event can't be uploaded
+        let event_endpoint = server.mock(|when, then| {
+            when.method("POST").path("/api/v1/ingest/custom-event");
+            then.status(401);
+        });
+        uploader.flush(&mut vec![telemetry_dump("event-3")]).await;
+        assert_eq!(uploader.spool.num_events, 1);
+        event_endpoint.assert();
+    }
+}
@@ -5,6 +5,7 @@
 
 mod constants;
 mod core_metrics;
+mod event_pipeline;
 mod metrics;
 mod network_metrics;
 mod sender;
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_metrics_core::{
-    register_int_counter, register_int_counter_vec, IntCounter, IntCounterVec,
+    register_int_counter, register_int_counter_vec, register_int_gauge_vec, IntCounter,
+    IntCounterVec, IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 
@@ -117,3 +118,71 @@ pub(crate) fn increment_log_ingest_too_large_by(v: u64) {
 pub(crate) fn increment_log_ingest_failures_by(v: u64) {
     APTOS_LOG_INGEST_FAILURE.inc_by(v);
 }
+
+/// Counter for the batches of telemetry events uploaded to Telemetry Service
+pub(crate) static APTOS_TELEMETRY_EVENT_BATCHES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_telemetry_event_batches",
+        "Number of uploads of telemetry event batches to telemetry service",
+        &["result"]
+    )
+    .unwrap()
+});
+
+/// Counter for the telemetry events uploaded to Telemetry Service (including spooled events)
+pub(crate) static APTOS_TELEMETRY_EVENTS_UPLOADED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_telemetry_events_uploaded",
+        "Number of telemetry events uploaded to telemetry service"
+    )
+    .unwrap()
+});
+
+/// Counter for the telemetry events that were dropped before reaching Telemetry Service
+pub(crate) static APTOS_TELEMETRY_EVENTS_DROPPED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_telemetry_events_dropped",
+        "Number of telemetry events dropped before reaching telemetry service",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+/// Gauge for the backlog of telemetry events spooled while Telemetry Service is unreachable
+pub(crate) static APTOS_TELEMETRY_EVENT_BACKLOG: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_telemetry_event_backlog",
+        "Backlog of telemetry events spooled for upload to telemetry service",
+        &["unit"]
+    )
+    .unwrap()
+});
+
+/// Increments the number of telemetry event batch uploads with the given result
+pub(crate) fn increment_telemetry_event_batches(result: &str) {
+    APTOS_TELEMETRY_EVENT_BATCHES
+        .with_label_values(&[result])
+        .inc();
+}
+
+/// Increments the number of telemetry events uploaded to Telemetry Service
+pub(crate) fn increment_telemetry_events_uploaded_by(v: u64) {
+    APTOS_TELEMETRY_EVENTS_UPLOADED.inc_by(v);
+}
+
+/// Increments the number of telemetry events dropped for the given reason
+pub(crate) fn increment_telemetry_events_dropped_by(reason: &str, v: u64) {
+    APTOS_TELEMETRY_EVENTS_DROPPED
+        .with_label_values(&[reason])
+        .inc_by(v);
+}
+
+/// Sets the backlog of spooled telemetry events
+pub(crate) fn set_telemetry_event_backlog(num_events: i64, num_bytes: i64) {
+    APTOS_TELEMETRY_EVENT_BACKLOG
+        .with_label_values(&["events"])
+        .set(num_events);
+    APTOS_TELEMETRY_EVENT_BACKLOG
+        .with_label_values(&["bytes"])
+        .set(num_bytes);
+}
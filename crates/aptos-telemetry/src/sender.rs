@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::metrics::{self, increment_log_ingest_failures_by, increment_log_ingest_successes_by};
+use crate::metrics::{increment_log_ingest_failures_by, increment_log_ingest_successes_by};
 use anyhow::{anyhow, Error, Result};
 use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::{
@@ -10,6 +10,7 @@ use aptos_crypto::{
 };
 use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::debug;
+use aptos_retrier::{retry_with_policy, RetryDecision, RetryPolicy};
 use aptos_telemetry_service::types::{
     auth::{AuthRequest, AuthResponse},
     response::IndexResponse,
@@ -20,7 +21,6 @@ use flate2::{write::GzEncoder, Compression};
 use prometheus::{default_registry, Registry};
 use reqwest::{header::CONTENT_ENCODING, Response, StatusCode, Url};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, RequestBuilder};
-use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
 use std::{io::Write, sync::Arc, time::Duration};
 use uuid::Uuid;
 
@@ -28,6 +28,7 @@ pub const DEFAULT_VERSION_PATH_BASE: &str = "api/v1/";
 
 pub const PROMETHEUS_PUSH_METRICS_TIMEOUT_SECS: u64 = 8;
 pub const TELEMETRY_SERVICE_TOTAL_RETRY_DURATION_SECS: u64 = 10;
+pub const TELEMETRY_SERVICE_INITIAL_RETRY_DELAY_MS: u64 = 1000;
 
 struct AuthContext {
     noise_config: Option<NoiseConfig>,
@@ -53,20 +54,22 @@ pub(crate) struct TelemetrySender {
     peer_id: PeerId,
     role_type: RoleType,
     client: ClientWithMiddleware,
+    retry_policy: RetryPolicy,
     auth_context: Arc<AuthContext>,
     uuid: Uuid,
 }
 
 impl TelemetrySender {
     pub fn new(base_url: Url, chain_id: ChainId, node_config: &NodeConfig) -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_total_retry_duration(
-            Duration::from_secs(TELEMETRY_SERVICE_TOTAL_RETRY_DURATION_SECS),
-        );
+        let retry_policy = RetryPolicy::exponential(Duration::from_millis(
+            TELEMETRY_SERVICE_INITIAL_RETRY_DELAY_MS,
+        ))
+        .with_max_elapsed(Duration::from_secs(
+            TELEMETRY_SERVICE_TOTAL_RETRY_DURATION_SECS,
+        ));
 
         let reqwest_client = reqwest::Client::new();
-        let client = ClientBuilder::new(reqwest_client)
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .build();
+        let client = ClientBuilder::new(reqwest_client).build();
 
         let version_path_base = match base_url.path() {
             "/" => DEFAULT_VERSION_PATH_BASE.to_string(),
@@ -86,6 +89,7 @@ impl TelemetrySender {
             peer_id: node_config.get_peer_id().unwrap_or(PeerId::ZERO),
             role_type: node_config.base.role,
             client,
+            retry_policy,
             auth_context: Arc::new(AuthContext::new(node_config)),
             uuid: uuid::Uuid::new_v4(),
         }
@@ -103,25 +107,42 @@ impl TelemetrySender {
     ) -> Result<Response, anyhow::Error> {
         let token = self.get_auth_token().await?;
 
-        let request = request_builder
-            .try_clone()
-            .expect("Could not clone request_builder")
-            .bearer_auth(token)
-            .build()?;
-
-        let mut response = self.client.execute(request).await?;
+        let mut response = self
+            .send_with_retries(
+                request_builder
+                    .try_clone()
+                    .expect("Could not clone request_builder")
+                    .bearer_auth(token),
+            )
+            .await?;
 
         // do 1 retry if the first attempt failed
         if response.status() == StatusCode::UNAUTHORIZED {
             // looks like request failed due to auth error. Let's get a new a fresh token. If this fails again we'll just return the error.
             self.reset_token();
             let token = self.get_auth_token().await?;
-            let request = request_builder.bearer_auth(token).build()?;
-            response = self.client.execute(request).await?;
+            response = self
+                .send_with_retries(request_builder.bearer_auth(token))
+                .await?;
         }
         Ok(response)
     }
 
+    // sends the request, retrying transient failures according to the retry policy.
+    // Like send_authenticated_request, this does not work with streaming bodies.
+    async fn send_with_retries(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> reqwest_middleware::Result<Response> {
+        retry_with_policy(&self.retry_policy, transient_failure_decision, || {
+            request_builder
+                .try_clone()
+                .expect("Could not clone request_builder")
+                .send()
+        })
+        .await
+    }
+
     pub(crate) async fn push_prometheus_metrics(
         &self,
         registry: &Registry,
@@ -213,33 +234,35 @@ impl TelemetrySender {
         error_for_status_with_body(response).await
     }
 
-    pub async fn try_send_custom_metrics(&self, event_name: String, telemetry_dump: TelemetryDump) {
-        match self.post_custom_metrics(&telemetry_dump.clone()).await {
-            Ok(_) => {
-                metrics::increment_telemetry_service_successes(&event_name);
-                debug!("Custom metrics with name {} sent successfully.", event_name);
-            },
-            Err(e) => {
-                metrics::increment_telemetry_service_failures(&event_name);
-                debug!("Failed to send custom metrics: {}", e);
-            },
-        }
+    /// Posts a gzip compressed JSON array of telemetry dumps (see `EventPipeline`), and returns
+    /// the response as is, so the caller can decide whether to retry the batch.
+    pub(crate) async fn post_custom_event_batch(
+        &self,
+        compressed_batch: Vec<u8>,
+    ) -> Result<Response, anyhow::Error> {
+        debug!("Sending custom events");
+
+        self.send_authenticated_request(
+            self.client
+                .post(self.build_path("ingest/custom-event/batch")?)
+                .header(CONTENT_ENCODING, "gzip")
+                .body(compressed_batch),
+        )
+        .await
     }
 
-    async fn post_custom_metrics(
+    /// Posts a single telemetry dump, for services that don't support batches (see
+    /// `post_custom_event_batch`), and returns the response as is.
+    pub(crate) async fn post_custom_event(
         &self,
         telemetry_dump: &TelemetryDump,
     ) -> Result<Response, anyhow::Error> {
-        // Send the request and wait for a response
-        let response = self
-            .send_authenticated_request(
-                self.client
-                    .post(self.build_path("ingest/custom-event")?)
-                    .json::<TelemetryDump>(telemetry_dump),
-            )
-            .await?;
-
-        error_for_status_with_body(response).await
+        self.send_authenticated_request(
+            self.client
+                .post(self.build_path("ingest/custom-event")?)
+                .json::<TelemetryDump>(telemetry_dump),
+        )
+        .await
     }
 
     async fn get_auth_token(&self) -> Result<String, Error> {
@@ -256,7 +279,9 @@ impl TelemetrySender {
     }
 
     async fn get_public_key_from_server(&self) -> Result<x25519::PublicKey> {
-        let response = self.client.get(self.build_path("")?).send().await?;
+        let response = self
+            .send_with_retries(self.client.get(self.build_path("")?))
+            .await?;
 
         match error_for_status_with_body(response).await {
             Ok(response) => {
@@ -279,6 +304,11 @@ impl TelemetrySender {
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_auth_token(&self, token: &str) {
+        *self.auth_context.token.write() = Some(token.into());
+    }
+
     fn reset_token(&self) {
         *self.auth_context.token.write() = None;
         *self.auth_context.server_public_key.lock() = None;
@@ -326,10 +356,11 @@ impl TelemetrySender {
         };
 
         let response = self
-            .client
-            .post(self.build_path("auth")?)
-            .json::<AuthRequest>(&auth_request)
-            .send()
+            .send_with_retries(
+                self.client
+                    .post(self.build_path("auth")?)
+                    .json::<AuthRequest>(&auth_request),
+            )
             .await?;
 
         let resp = match error_for_status_with_body(response).await {
@@ -371,11 +402,12 @@ impl TelemetrySender {
     }
 
     async fn try_check_chain_access(&self, chain_id: ChainId) -> Result<Response> {
-        self.client
-            .get(self.build_path(&format!("chain-access/{}", chain_id))?)
-            .send()
-            .await
-            .map_err(|e| anyhow!("error sending request {}", e))
+        self.send_with_retries(
+            self.client
+                .get(self.build_path(&format!("chain-access/{}", chain_id))?),
+        )
+        .await
+        .map_err(|e| anyhow!("error sending request {}", e))
     }
 
     pub(crate) async fn get_telemetry_log_env(&self) -> Option<String> {
@@ -408,15 +440,9 @@ impl TelemetrySender {
 mod tests {
 
     use super::*;
-    use crate::metrics::{APTOS_TELEMETRY_SERVICE_FAILURE, APTOS_TELEMETRY_SERVICE_SUCCESS};
     use aptos_crypto::Uniform;
-    use aptos_telemetry_service::types::telemetry::TelemetryEvent;
     use httpmock::MockServer;
     use prometheus::{register_int_counter_vec_with_registry, Registry};
-    use std::{
-        collections::BTreeMap,
-        time::{SystemTime, UNIX_EPOCH},
-    };
 
     #[tokio::test]
     async fn test_server_public_key() {
@@ -459,32 +485,17 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_post_custom_metrics() {
-        let mut telemetry_event = TelemetryEvent {
-            name: "sample-event".into(),
-            params: BTreeMap::new(),
-        };
-        telemetry_event
-            .params
-            .insert("key-1".into(), "value-1".into());
-        let telemetry_dump = TelemetryDump {
-            client_id: "client-1".into(),
-            user_id: "user-1".into(),
-            timestamp_micros: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_micros()
-                .to_string(),
-            events: vec![],
-        };
+    async fn test_post_custom_event_batch() {
+        let compressed_batch = vec![1, 2, 3];
 
         let server = MockServer::start();
         let mock = server.mock(|when, then| {
             when.method("POST")
                 .header("Authorization", "Bearer SECRET_JWT_TOKEN")
-                .path("/api/v1/ingest/custom-event")
-                .json_body_obj(&telemetry_dump);
-            then.status(200);
+                .header("Content-Encoding", "gzip")
+                .path("/api/v1/ingest/custom-event/batch")
+                .body(String::from_utf8_lossy(&compressed_batch));
+            then.status(201);
         });
 
         let node_config = NodeConfig::default();
@@ -493,40 +504,22 @@ mod tests {
             ChainId::default(),
             &node_config,
         );
-        {
-            *client.auth_context.token.write() = Some("SECRET_JWT_TOKEN".into());
-        }
+        client.set_auth_token("SECRET_JWT_TOKEN");
 
-        let result = client.post_custom_metrics(&telemetry_dump).await;
+        let result = client
+            .post_custom_event_batch(compressed_batch.clone())
+            .await;
 
         mock.assert();
-        assert!(result.is_ok());
+        assert!(result.unwrap().status().is_success());
     }
 
     #[tokio::test]
-    async fn test_try_send_metrics_retry_unauthorized() {
-        let event_name = "sample-event";
-        let mut telemetry_event = TelemetryEvent {
-            name: event_name.into(),
-            params: BTreeMap::new(),
-        };
-        telemetry_event
-            .params
-            .insert("key-1".into(), "value-1".into());
-        let telemetry_dump = TelemetryDump {
-            client_id: "client-1".into(),
-            user_id: "user-1".into(),
-            timestamp_micros: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_micros()
-                .to_string(),
-            events: vec![],
-        };
-
+    async fn test_post_custom_event_batch_retry_unauthorized() {
         let server = MockServer::start();
         let mock = server.mock(|when, then| {
-            when.method("POST").path("/api/v1/ingest/custom-event");
+            when.method("POST")
+                .path("/api/v1/ingest/custom-event/batch");
             then.status(401);
         });
 
@@ -536,27 +529,13 @@ mod tests {
             ChainId::default(),
             &node_config,
         );
-        {
-            *client.auth_context.token.write() = Some("SECRET_JWT_TOKEN".into());
-        }
+        client.set_auth_token("SECRET_JWT_TOKEN");
 
-        client
-            .try_send_custom_metrics(event_name.into(), telemetry_dump)
-            .await;
+        // The token is reset, and the node can't authenticate without an identity key
+        let result = client.post_custom_event_batch(vec![1, 2, 3]).await;
 
         mock.assert_hits(1);
-        assert_eq!(
-            APTOS_TELEMETRY_SERVICE_SUCCESS
-                .with_label_values(&[event_name])
-                .get(),
-            0
-        );
-        assert_eq!(
-            APTOS_TELEMETRY_SERVICE_FAILURE
-                .with_label_values(&[event_name])
-                .get(),
-            1
-        );
+        assert!(result.is_err());
     }
 
     #[tokio::test]
@@ -661,6 +640,28 @@ mod tests {
     }
 }
 
+/// Connection errors, timeouts and responses that signal an overloaded or unavailable
+/// server (408, 429 and 5xx) are retried, everything else is returned as is.
+fn transient_failure_decision(result: &reqwest_middleware::Result<Response>) -> RetryDecision {
+    let is_transient = match result {
+        Ok(response) => {
+            let status = response.status();
+            status.is_server_error()
+                || status == StatusCode::REQUEST_TIMEOUT
+                || status == StatusCode::TOO_MANY_REQUESTS
+        },
+        Err(reqwest_middleware::Error::Reqwest(error)) => {
+            error.is_timeout() || error.is_connect() || error.is_request()
+        },
+        Err(reqwest_middleware::Error::Middleware(_)) => false,
+    };
+    if is_transient {
+        RetryDecision::Retry
+    } else {
+        RetryDecision::DoNotRetry
+    }
+}
+
 async fn error_for_status_with_body(response: Response) -> Result<Response, anyhow::Error> {
     if response.status().is_client_error() || response.status().is_server_error() {
         Err(anyhow!(
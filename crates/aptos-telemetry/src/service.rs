@@ -4,8 +4,8 @@
 #![forbid(unsafe_code)]
 
 use crate::{
-    constants::*, core_metrics::create_core_metric_telemetry_event, metrics,
-    network_metrics::create_network_metric_telemetry_event, sender::TelemetrySender,
+    constants::*, core_metrics::create_core_metric_telemetry_event, event_pipeline::EventPipeline,
+    metrics, network_metrics::create_network_metric_telemetry_event, sender::TelemetrySender,
     system_information::create_system_info_telemetry_event,
     telemetry_log_sender::TelemetryLogSender, utils::create_build_info_telemetry_event,
 };
@@ -228,10 +228,13 @@ fn try_spawn_custom_event_sender(
     build_info: BTreeMap<String, String>,
 ) {
     if enable_push_custom_events() {
-        // Spawn the custom event sender
+        // Spawn the custom event sender, with the events spooled in the storage directory when
+        // the telemetry service is unreachable
         let peer_id = fetch_peer_id(&node_config);
+        let spool_dir = node_config.storage.dir().join(TELEMETRY_EVENT_SPOOL_DIR);
+        let event_pipeline = EventPipeline::spawn(telemetry_sender, Some(spool_dir));
         tokio::spawn(custom_event_sender(
-            Some(telemetry_sender),
+            Some(event_pipeline),
             peer_id,
             chain_id,
             node_config,
@@ -288,7 +291,7 @@ where
 
 /// Spawns the dedicated telemetry service that operates periodically
 async fn custom_event_sender(
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: Option<EventPipeline>,
     peer_id: String,
     chain_id: ChainId,
     node_config: NodeConfig,
@@ -301,7 +304,7 @@ async fn custom_event_sender(
                 peer_id.clone(),
                 chain_id.to_string(),
                 build_info.clone(),
-                telemetry_sender.clone(),
+                event_pipeline.clone(),
             )
         }),
         // Periodically send system information
@@ -309,7 +312,7 @@ async fn custom_event_sender(
             send_system_information(
                 peer_id.clone(),
                 chain_id.to_string(),
-                telemetry_sender.clone(),
+                event_pipeline.clone(),
             )
         }),
         // Periodically send node core metrics
@@ -318,7 +321,7 @@ async fn custom_event_sender(
                 peer_id.clone(),
                 chain_id.to_string(),
                 &node_config,
-                telemetry_sender.clone(),
+                event_pipeline.clone(),
             )
         }),
         // Periodically send node network metrics
@@ -326,7 +329,7 @@ async fn custom_event_sender(
             send_node_network_metrics(
                 peer_id.clone(),
                 chain_id.to_string(),
-                telemetry_sender.clone(),
+                event_pipeline.clone(),
             )
         }),
         run_function_periodically(NODE_CONFIG_FREQ_SECS, || {
@@ -334,7 +337,7 @@ async fn custom_event_sender(
                 peer_id.clone(),
                 chain_id.to_string(),
                 &node_config,
-                telemetry_sender.clone(),
+                event_pipeline.clone(),
             )
         }),
     )
@@ -346,10 +349,10 @@ async fn send_build_information(
     peer_id: String,
     chain_id: String,
     build_info: BTreeMap<String, String>,
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: Option<EventPipeline>,
 ) {
     let telemetry_event = create_build_info_telemetry_event(build_info).await;
-    send_telemetry_event_with_ip(peer_id, chain_id, telemetry_sender, telemetry_event).await;
+    send_telemetry_event_with_ip(peer_id, chain_id, event_pipeline, telemetry_event).await;
 }
 
 /// Collects and sends the core node metrics via telemetry
@@ -357,7 +360,7 @@ async fn send_node_config(
     peer_id: String,
     chain_id: String,
     node_config: &NodeConfig,
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: Option<EventPipeline>,
 ) {
     let node_config: BTreeMap<String, String> = serde_json::to_value(node_config)
         .map(|value| {
@@ -376,7 +379,7 @@ async fn send_node_config(
         name: APTOS_NODE_CONFIG_EVENT_NAME.into(),
         params: node_config,
     };
-    send_telemetry_event_with_ip(peer_id, chain_id, telemetry_sender, telemetry_event).await;
+    send_telemetry_event_with_ip(peer_id, chain_id, event_pipeline, telemetry_event).await;
 }
 
 /// Collects and sends the core node metrics via telemetry
@@ -384,30 +387,30 @@ async fn send_node_core_metrics(
     peer_id: String,
     chain_id: String,
     node_config: &NodeConfig,
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: Option<EventPipeline>,
 ) {
     let telemetry_event = create_core_metric_telemetry_event(node_config).await;
-    send_telemetry_event_with_ip(peer_id, chain_id, telemetry_sender, telemetry_event).await;
+    send_telemetry_event_with_ip(peer_id, chain_id, event_pipeline, telemetry_event).await;
 }
 
 /// Collects and sends the node network metrics via telemetry
 async fn send_node_network_metrics(
     peer_id: String,
     chain_id: String,
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: Option<EventPipeline>,
 ) {
     let telemetry_event = create_network_metric_telemetry_event().await;
-    send_telemetry_event_with_ip(peer_id, chain_id, telemetry_sender, telemetry_event).await;
+    send_telemetry_event_with_ip(peer_id, chain_id, event_pipeline, telemetry_event).await;
 }
 
 /// Collects and sends the system information via telemetry
 async fn send_system_information(
     peer_id: String,
     chain_id: String,
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: Option<EventPipeline>,
 ) {
     let telemetry_event = create_system_info_telemetry_event().await;
-    send_telemetry_event_with_ip(peer_id, chain_id, telemetry_sender, telemetry_event).await;
+    send_telemetry_event_with_ip(peer_id, chain_id, event_pipeline, telemetry_event).await;
 }
 
 /// Fetches the IP address and sends the given telemetry event
@@ -416,7 +419,7 @@ async fn send_system_information(
 pub(crate) async fn send_telemetry_event_with_ip(
     peer_id: String,
     chain_id: String,
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: Option<EventPipeline>,
     telemetry_event: TelemetryEvent,
 ) -> JoinHandle<()> {
     // Update the telemetry event with the ip address and random token
@@ -427,7 +430,7 @@ pub(crate) async fn send_telemetry_event_with_ip(
     let telemetry_event = TelemetryEvent { name, params };
 
     // Send the telemetry event
-    send_telemetry_event(peer_id, telemetry_sender, telemetry_event).await
+    send_telemetry_event(peer_id, event_pipeline, telemetry_event).await
 }
 
 /// Gets the IP origin of the machine by pinging a url.
@@ -446,7 +449,7 @@ async fn get_origin_ip() -> String {
 /// Sends the given event and params to the telemetry endpoint
 async fn send_telemetry_event(
     peer_id: String,
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: Option<EventPipeline>,
     telemetry_event: TelemetryEvent,
 ) -> JoinHandle<()> {
     // Parse the Google analytics env variables
@@ -467,32 +470,27 @@ async fn send_telemetry_event(
         timestamp_micros,
         events: vec![telemetry_event],
     };
-    if telemetry_sender.is_none() {
-        // telemetry_sender is None for Aptos CLI.
-        spawn_event_sender_to_google_analytics(
+    match event_pipeline {
+        // Aptos nodes send their metrics to aptos-telemetry-service crate.
+        Some(event_pipeline) => {
+            spawn_event_sender_to_telemetry_service(event_pipeline, telemetry_dump)
+        },
+        // event_pipeline is None for Aptos CLI.
+        None => spawn_event_sender_to_google_analytics(
             api_secret,
             measurement_id,
             event_name,
             telemetry_dump,
-        )
-    } else {
-        // Aptos nodes send their metrics to aptos-telemetry-service crate.
-        spawn_event_sender_to_telemetry_service(event_name, telemetry_sender, telemetry_dump)
+        ),
     }
 }
 
-/// Spawns the telemetry event sender on a new thread to avoid blocking
+/// Queues the telemetry event for the batched upload to the telemetry service
 fn spawn_event_sender_to_telemetry_service(
-    event_name: String,
-    telemetry_sender: Option<TelemetrySender>,
+    event_pipeline: EventPipeline,
     telemetry_dump: TelemetryDump,
 ) -> JoinHandle<()> {
-    tokio::spawn(async move {
-        telemetry_sender
-            .unwrap()
-            .try_send_custom_metrics(event_name, telemetry_dump)
-            .await;
-    })
+    tokio::spawn(async move { event_pipeline.push(telemetry_dump) })
 }
 
 /// Spawns the telemetry event sender on a new thread to avoid blocking
@@ -0,0 +1,779 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{
+            CliCommand, CliConfig, CliError, CliTypedResult, ConfigSearchMode, ProfileOptions,
+            RestOptions,
+        },
+        utils::{chain_id, create_dir_if_not_exist, read_from_file, write_to_file},
+    },
+    config::GlobalConfig,
+};
+use aptos_rest_client::{
+    aptos_api_types::{Event, MoveType, TransactionPayload, UserTransaction},
+    Client, Resource, Transaction,
+};
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::{Parser, ValueEnum};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::PathBuf,
+};
+
+const HISTORY_FOLDER: &str = "history";
+const MAX_PAGE_SIZE: u16 = 100;
+
+/// The indexer query for the versions of the transactions that affected an account
+const ACCOUNT_TRANSACTIONS_QUERY: &str = "
+    query AccountTransactions($address: String, $start: bigint, $limit: Int) {
+        account_transactions(
+            where: {account_address: {_eq: $address}, transaction_version: {_gte: $start}},
+            order_by: {transaction_version: asc},
+            limit: $limit
+        ) {
+            transaction_version
+        }
+    }";
+
+/// The events that are decoded, with the field holding the amount of each
+const DECODED_EVENTS: &[(&str, &str, &str)] = &[
+    ("coin", "DepositEvent", "amount"),
+    ("coin", "WithdrawEvent", "amount"),
+    ("stake", "AddStakeEvent", "amount_added"),
+    ("stake", "UnlockStakeEvent", "amount_unlocked"),
+    ("stake", "WithdrawStakeEvent", "amount_withdrawn"),
+    ("stake", "DistributeRewardsEvent", "rewards_amount"),
+    ("delegation_pool", "AddStakeEvent", "amount_added"),
+    ("delegation_pool", "UnlockStakeEvent", "amount_unlocked"),
+    ("delegation_pool", "WithdrawStakeEvent", "amount_withdrawn"),
+    (
+        "delegation_pool",
+        "ReactivateStakeEvent",
+        "amount_reactivated",
+    ),
+];
+
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HistoryFormat {
+    Csv,
+    Json,
+}
+
+/// Show the history of transactions of an account
+///
+/// The transactions are paged from the node (or an indexer), and cached locally (per chain) in
+/// the CLI config folder, so only new transactions are fetched on later runs. Coin transfer
+/// (with their coin type) and stake events are decoded, and the history can be exported as CSV
+/// or JSON (e.g., for accounting).
+///
+/// Without an indexer, the history contains the transactions sent by the account and those
+/// that deposited coins to it. With an indexer, it contains all user transactions that affected
+/// the account.
+#[derive(Debug, Parser)]
+pub struct AccountHistory {
+    /// Address of the account you want the history of
+    ///
+    /// Defaults to the account of the profile
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// File to export the history to, instead of printing it
+    #[clap(long, value_parser)]
+    pub(crate) output_file: Option<PathBuf>,
+
+    /// Format of the exported history: [csv, json]
+    #[clap(long, value_enum, ignore_case = true, default_value_t = HistoryFormat::Csv)]
+    pub(crate) format: HistoryFormat,
+
+    /// Fetch the whole history from the node, ignoring (and replacing) the local cache
+    #[clap(long)]
+    pub(crate) refresh: bool,
+
+    /// Number of transactions fetched per request (at most 100)
+    #[clap(long, default_value_t = MAX_PAGE_SIZE)]
+    pub(crate) page_size: u16,
+
+    /// URL of the GraphQL endpoint of an indexer to find the transactions of the account with
+    ///
+    /// If not provided, the transactions are found using the node only
+    #[clap(long)]
+    pub(crate) indexer_url: Option<Url>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// A transaction of the account
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HistoryTransaction {
+    pub version: u64,
+    pub sender: AccountAddress,
+    pub sequence_number: u64,
+    pub hash: String,
+    pub timestamp_us: u64,
+    pub function: String,
+    pub success: bool,
+    pub vm_status: String,
+    pub gas_used: u64,
+    pub gas_unit_price: u64,
+    pub events: Vec<HistoryEvent>,
+}
+
+/// A decoded event of a transaction
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HistoryEvent {
+    pub event_type: String,
+    pub account: AccountAddress,
+    pub amount: u64,
+    /// The coin type of coin deposit and withdraw events (unknown if the coin store of the
+    /// account no longer exists)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_type: Option<String>,
+}
+
+/// The locally cached history of an account, with the positions to continue fetching from
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct HistoryCache {
+    /// The transactions, ordered by version
+    transactions: Vec<HistoryTransaction>,
+    /// The next sequence number of the account to fetch
+    next_sequence_number: u64,
+    /// The next deposit event to fetch, per coin type
+    next_deposit_events: BTreeMap<String, u64>,
+    /// The next version to query the indexer from
+    next_indexer_version: u64,
+}
+
+/// A summary of the account history
+#[derive(Debug, Serialize)]
+pub struct AccountHistorySummary {
+    pub account: AccountAddress,
+    pub num_transactions: usize,
+    pub num_new_transactions: usize,
+    pub cache_file: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_file: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub transactions: Vec<HistoryTransaction>,
+}
+
+#[async_trait]
+impl CliCommand<AccountHistorySummary> for AccountHistory {
+    fn command_name(&self) -> &'static str {
+        "AccountHistory"
+    }
+
+    async fn execute(self) -> CliTypedResult<AccountHistorySummary> {
+        if self.page_size == 0 || self.page_size > MAX_PAGE_SIZE {
+            return Err(CliError::CommandArgumentError(format!(
+                "Page size must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+        let account = if let Some(account) = self.account {
+            account
+        } else if let Some(Some(account)) = CliConfig::load_profile(
+            self.profile_options.profile_name(),
+            ConfigSearchMode::CurrentDirAndParents,
+        )?
+        .map(|p| p.account)
+        {
+            account
+        } else {
+            return Err(CliError::CommandArgumentError(
+                "Please provide an account using --account or run aptos init".to_string(),
+            ));
+        };
+
+        // The cache is per chain, as the same account can exist on multiple chains
+        let client = self.rest_options.client(&self.profile_options)?;
+        let chain_id = chain_id(&client).await?;
+        let cache_dir = GlobalConfig::load()?
+            .get_config_location(ConfigSearchMode::CurrentDirAndParents)?
+            .join(HISTORY_FOLDER)
+            .join(chain_id.id().to_string());
+        let cache_file = cache_dir.join(format!("{}.json", account.to_hex_literal()));
+        let mut cache: HistoryCache = if !self.refresh && cache_file.exists() {
+            serde_json::from_slice(&read_from_file(&cache_file)?).map_err(|err| {
+                CliError::UnexpectedError(format!(
+                    "Failed to parse the history cache {}: {}",
+                    cache_file.display(),
+                    err
+                ))
+            })?
+        } else {
+            HistoryCache::default()
+        };
+
+        // Fetch the transactions after the cached ones, until there are no more
+        let mut fetcher = HistoryFetcher {
+            client: &client,
+            account,
+            page_size: self.page_size,
+            known_versions: cache
+                .transactions
+                .iter()
+                .map(|transaction| transaction.version)
+                .collect(),
+            new_transactions: vec![],
+        };
+        if let Some(indexer_url) = &self.indexer_url {
+            fetcher
+                .fetch_indexed_transactions(indexer_url, &mut cache)
+                .await?;
+        } else {
+            fetcher.fetch_sent_transactions(&mut cache).await?;
+            fetcher.fetch_deposit_transactions(&mut cache).await?;
+        }
+
+        let new_transactions = fetcher.new_transactions;
+        let coin_types = fetch_coin_types(&client, &new_transactions).await?;
+        let num_new_transactions = new_transactions.len();
+        cache.transactions.extend(
+            new_transactions
+                .iter()
+                .map(|transaction| to_history(transaction, &coin_types)),
+        );
+        cache
+            .transactions
+            .sort_by_key(|transaction| transaction.version);
+
+        // The cache is always saved, as the positions to continue fetching from may have moved
+        // even without new transactions (e.g., deposits in transactions sent by the account)
+        create_dir_if_not_exist(&cache_dir)?;
+        write_to_file(
+            &cache_file,
+            "History cache",
+            &serde_json::to_vec(&cache).map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to serialize history: {}", err))
+            })?,
+        )?;
+
+        let transactions = cache.transactions;
+        if let Some(output_file) = &self.output_file {
+            let bytes = match self.format {
+                HistoryFormat::Csv => to_csv(&transactions).into_bytes(),
+                HistoryFormat::Json => serde_json::to_vec_pretty(&transactions).map_err(|err| {
+                    CliError::UnexpectedError(format!("Failed to serialize history: {}", err))
+                })?,
+            };
+            write_to_file(output_file, "History", &bytes)?;
+        }
+
+        Ok(AccountHistorySummary {
+            account,
+            num_transactions: transactions.len(),
+            num_new_transactions,
+            cache_file,
+            output_file: self.output_file.clone(),
+            transactions: if self.output_file.is_some() {
+                vec![]
+            } else {
+                transactions
+            },
+        })
+    }
+}
+
+/// Fetches the (user) transactions of an account that aren't known yet
+struct HistoryFetcher<'a> {
+    client: &'a Client,
+    account: AccountAddress,
+    page_size: u16,
+    known_versions: HashSet<u64>,
+    new_transactions: Vec<UserTransaction>,
+}
+
+impl HistoryFetcher<'_> {
+    /// Fetches the transactions sent by the account
+    async fn fetch_sent_transactions(&mut self, cache: &mut HistoryCache) -> CliTypedResult<()> {
+        loop {
+            let page = self
+                .client
+                .get_account_transactions(
+                    self.account,
+                    Some(cache.next_sequence_number),
+                    Some(self.page_size as u64),
+                )
+                .await?
+                .into_inner();
+            let page_len = page.len();
+            for transaction in page {
+                if let Transaction::UserTransaction(transaction) = transaction {
+                    cache.next_sequence_number = transaction.request.sequence_number.0 + 1;
+                    self.add_transaction(*transaction);
+                }
+            }
+            if page_len < self.page_size as usize {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Fetches the transactions that deposited coins to the account (of any coin store)
+    async fn fetch_deposit_transactions(&mut self, cache: &mut HistoryCache) -> CliTypedResult<()> {
+        let resources = self
+            .client
+            .get_account_resources(self.account)
+            .await?
+            .into_inner();
+        for resource in resources {
+            let coin_type = match coin_store_coin_type(&resource) {
+                Some(coin_type) => coin_type,
+                None => continue,
+            };
+            let coin_store = resource.resource_type.to_string();
+            loop {
+                let start = cache
+                    .next_deposit_events
+                    .get(&coin_type)
+                    .copied()
+                    .unwrap_or_default();
+                let events = self
+                    .client
+                    .get_account_events(
+                        self.account,
+                        &coin_store,
+                        "deposit_events",
+                        Some(start),
+                        Some(self.page_size),
+                    )
+                    .await?
+                    .into_inner();
+                for event in &events {
+                    self.fetch_transaction(event.version.0).await?;
+                }
+                cache
+                    .next_deposit_events
+                    .insert(coin_type.clone(), start + events.len() as u64);
+                if events.len() < self.page_size as usize {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetches all transactions that affected the account, as found by the indexer
+    async fn fetch_indexed_transactions(
+        &mut self,
+        indexer_url: &Url,
+        cache: &mut HistoryCache,
+    ) -> CliTypedResult<()> {
+        let http_client = reqwest::Client::new();
+        loop {
+            let response = http_client
+                .post(indexer_url.clone())
+                .json(&json!({
+                    "query": ACCOUNT_TRANSACTIONS_QUERY,
+                    "variables": {
+                        "address": format!("0x{}", self.account.to_canonical_string()),
+                        "start": cache.next_indexer_version,
+                        "limit": self.page_size,
+                    },
+                }))
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|err| CliError::ApiError(format!("Indexer request failed: {}", err)))?
+                .json::<Value>()
+                .await
+                .map_err(|err| CliError::ApiError(format!("Invalid indexer response: {}", err)))?;
+            let versions = parse_indexer_versions(&response)?;
+            for version in &versions {
+                self.fetch_transaction(*version).await?;
+            }
+            if let Some(version) = versions.last() {
+                cache.next_indexer_version = version + 1;
+            }
+            if versions.len() < self.page_size as usize {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Fetches the transaction at the given version (unless it's already known)
+    async fn fetch_transaction(&mut self, version: u64) -> CliTypedResult<()> {
+        if self.known_versions.contains(&version) {
+            return Ok(());
+        }
+        let transaction = self
+            .client
+            .get_transaction_by_version(version)
+            .await?
+            .into_inner();
+        if let Transaction::UserTransaction(transaction) = transaction {
+            self.add_transaction(*transaction);
+        }
+        Ok(())
+    }
+
+    fn add_transaction(&mut self, transaction: UserTransaction) {
+        if self.known_versions.insert(transaction.info.version.0) {
+            self.new_transactions.push(transaction);
+        }
+    }
+}
+
+/// Returns the versions of the transactions in the response of the indexer query
+fn parse_indexer_versions(response: &Value) -> CliTypedResult<Vec<u64>> {
+    if let Some(errors) = response.get("errors") {
+        return Err(CliError::ApiError(format!(
+            "Indexer query failed: {}",
+            errors
+        )));
+    }
+    let invalid_response = || CliError::ApiError(format!("Invalid indexer response: {}", response));
+    response
+        .pointer("/data/account_transactions")
+        .and_then(Value::as_array)
+        .ok_or_else(invalid_response)?
+        .iter()
+        .map(|transaction| {
+            // Big integers may be returned as numbers or strings
+            let version = transaction.get("transaction_version");
+            version
+                .and_then(Value::as_u64)
+                .or_else(|| version?.as_str()?.parse().ok())
+                .ok_or_else(invalid_response)
+        })
+        .collect()
+}
+
+/// Returns the coin type of the given resource, if it's a coin store
+fn coin_store_coin_type(resource: &Resource) -> Option<String> {
+    let struct_tag = &resource.resource_type;
+    if struct_tag.address != AccountAddress::ONE
+        || struct_tag.module.as_str() != "coin"
+        || struct_tag.name.as_str() != "CoinStore"
+    {
+        return None;
+    }
+    struct_tag
+        .type_params
+        .first()
+        .map(|coin_type| coin_type.to_string())
+}
+
+/// Returns the coin type of each (deposit and withdraw) event handle of the coin stores in the
+/// given resources, by the creation number of the handle
+fn coin_store_event_handles(resources: &[Resource]) -> HashMap<u64, String> {
+    let mut coin_types = HashMap::new();
+    for resource in resources {
+        if let Some(coin_type) = coin_store_coin_type(resource) {
+            for handle in ["deposit_events", "withdraw_events"] {
+                let creation_number = resource
+                    .data
+                    .pointer(&format!("/{}/guid/id/creation_num", handle))
+                    .and_then(Value::as_str)
+                    .and_then(|creation_number| creation_number.parse().ok());
+                if let Some(creation_number) = creation_number {
+                    coin_types.insert(creation_number, coin_type.clone());
+                }
+            }
+        }
+    }
+    coin_types
+}
+
+/// Fetches the coin types of the coin events in the given transactions, by the account and
+/// creation number of their event handles. The coin types are read from the coin stores of the
+/// accounts (as coin events don't contain the coin type).
+async fn fetch_coin_types(
+    client: &Client,
+    transactions: &[UserTransaction],
+) -> CliTypedResult<HashMap<(AccountAddress, u64), String>> {
+    let accounts: BTreeSet<AccountAddress> = transactions
+        .iter()
+        .flat_map(|transaction| &transaction.events)
+        .filter(|event| is_coin_event(event))
+        .map(|event| *event.guid.account_address.inner())
+        .collect();
+
+    let mut coin_types = HashMap::new();
+    for account in accounts {
+        let resources = client.get_account_resources(account).await?.into_inner();
+        for (creation_number, coin_type) in coin_store_event_handles(&resources) {
+            coin_types.insert((account, creation_number), coin_type);
+        }
+    }
+    Ok(coin_types)
+}
+
+/// Returns true iff the event is a coin deposit or withdraw event
+fn is_coin_event(event: &Event) -> bool {
+    match &event.typ {
+        MoveType::Struct(struct_tag) => {
+            struct_tag.address.inner() == &AccountAddress::ONE
+                && struct_tag.module.as_str() == "coin"
+        },
+        _ => false,
+    }
+}
+
+/// Converts a user transaction into its history entry
+fn to_history(
+    transaction: &UserTransaction,
+    coin_types: &HashMap<(AccountAddress, u64), String>,
+) -> HistoryTransaction {
+    let function = match &transaction.request.payload {
+        TransactionPayload::EntryFunctionPayload(payload) => payload.function.to_string(),
+        TransactionPayload::ScriptPayload(_) => "script".to_string(),
+        TransactionPayload::ModuleBundlePayload(_) => "module_bundle".to_string(),
+        TransactionPayload::MultisigPayload(_) => "multisig".to_string(),
+    };
+    HistoryTransaction {
+        version: transaction.info.version.0,
+        sender: *transaction.request.sender.inner(),
+        sequence_number: transaction.request.sequence_number.0,
+        hash: transaction.info.hash.to_string(),
+        timestamp_us: transaction.timestamp.0,
+        function,
+        success: transaction.info.success,
+        vm_status: transaction.info.vm_status.clone(),
+        gas_used: transaction.info.gas_used.0,
+        gas_unit_price: transaction.request.gas_unit_price.0,
+        events: transaction
+            .events
+            .iter()
+            .filter_map(|event| decode_event(event, coin_types))
+            .collect(),
+    }
+}
+
+/// Decodes coin transfer and stake events (other events are skipped)
+fn decode_event(
+    event: &Event,
+    coin_types: &HashMap<(AccountAddress, u64), String>,
+) -> Option<HistoryEvent> {
+    let struct_tag = match &event.typ {
+        MoveType::Struct(struct_tag) if struct_tag.address.inner() == &AccountAddress::ONE => {
+            struct_tag
+        },
+        _ => return None,
+    };
+    let (_, _, amount_field) = DECODED_EVENTS.iter().find(|(module, name, _)| {
+        struct_tag.module.as_str() == *module && struct_tag.name.as_str() == *name
+    })?;
+    // Amounts are serialized as strings in JSON
+    let amount = event.data.get(amount_field)?.as_str()?.parse().ok()?;
+    let account = *event.guid.account_address.inner();
+    let coin_type = if is_coin_event(event) {
+        coin_types
+            .get(&(account, event.guid.creation_number.0))
+            .cloned()
+    } else {
+        None
+    };
+    Some(HistoryEvent {
+        event_type: event.typ.to_string(),
+        account,
+        amount,
+        coin_type,
+    })
+}
+
+/// Converts the history to CSV, with a row per decoded event (or per transaction without any)
+fn to_csv(transactions: &[HistoryTransaction]) -> String {
+    let mut csv = "version,sender,sequence_number,hash,timestamp_us,function,success,vm_status,gas_used,gas_unit_price,event_type,event_account,amount,coin_type\n".to_string();
+    for transaction in transactions {
+        let columns = [
+            transaction.version.to_string(),
+            transaction.sender.to_hex_literal(),
+            transaction.sequence_number.to_string(),
+            transaction.hash.clone(),
+            transaction.timestamp_us.to_string(),
+            escape_csv(&transaction.function),
+            transaction.success.to_string(),
+            escape_csv(&transaction.vm_status),
+            transaction.gas_used.to_string(),
+            transaction.gas_unit_price.to_string(),
+        ]
+        .join(",");
+        if transaction.events.is_empty() {
+            csv.push_str(&format!("{},,,,\n", columns));
+        }
+        for event in &transaction.events {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                columns,
+                escape_csv(&event.event_type),
+                event.account.to_hex_literal(),
+                event.amount,
+                escape_csv(event.coin_type.as_deref().unwrap_or_default())
+            ));
+        }
+    }
+    csv
+}
+
+/// Quotes a CSV field if needed
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin_store(coin_type: &str, deposit_creation_number: u64) -> Resource {
+        let event_handle = |creation_number: u64| {
+            json!({
+                "counter": "1",
+                "guid": { "id": { "addr": "0x1", "creation_num": creation_number.to_string() } },
+            })
+        };
+        serde_json::from_value(json!({
+            "type": format!("0x1::coin::CoinStore<{}>", coin_type),
+            "data": {
+                "coin": { "value": "100" },
+                "deposit_events": event_handle(deposit_creation_number),
+                "frozen": false,
+                "withdraw_events": event_handle(deposit_creation_number + 1),
+            },
+        }))
+        .unwrap()
+    }
+
+    fn event(event_type: &str, creation_number: u64, data: Value) -> Event {
+        serde_json::from_value(json!({
+            "guid": { "creation_number": creation_number.to_string(), "account_address": "0x1" },
+            "sequence_number": "0",
+            "type": event_type,
+            "data": data,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_coin_store_event_handles() {
+        let resources = vec![
+            coin_store("0x1::aptos_coin::AptosCoin", 2),
+            coin_store("0xcafe::usd::USD", 4),
+            serde_json::from_value(json!({
+                "type": "0x1::account::Account",
+                "data": { "sequence_number": "0" },
+            }))
+            .unwrap(),
+        ];
+        let coin_types = coin_store_event_handles(&resources);
+        assert_eq!(coin_types.len(), 4);
+        assert_eq!(coin_types[&2], "0x1::aptos_coin::AptosCoin");
+        assert_eq!(coin_types[&3], "0x1::aptos_coin::AptosCoin");
+        assert_eq!(coin_types[&4], "0xcafe::usd::USD");
+        assert_eq!(coin_types[&5], "0xcafe::usd::USD");
+    }
+
+    #[test]
+    fn test_decode_events() {
+        let coin_types = HashMap::from([
+            (
+                (AccountAddress::ONE, 2),
+                "0x1::aptos_coin::AptosCoin".to_string(),
+            ),
+            ((AccountAddress::ONE, 5), "0xcafe::usd::USD".to_string()),
+        ]);
+        let amount = json!({ "amount": "10" });
+
+        // Coin events are decoded with the coin type of their coin store
+        let deposit = decode_event(
+            &event("0x1::coin::DepositEvent", 2, amount.clone()),
+            &coin_types,
+        );
+        assert_eq!(
+            deposit.unwrap().coin_type.unwrap(),
+            "0x1::aptos_coin::AptosCoin"
+        );
+        let withdraw = decode_event(
+            &event("0x1::coin::WithdrawEvent", 5, amount.clone()),
+            &coin_types,
+        );
+        assert_eq!(withdraw.unwrap().coin_type.unwrap(), "0xcafe::usd::USD");
+
+        // The coin type of a coin event of an unknown coin store is unknown
+        let deposit = decode_event(&event("0x1::coin::DepositEvent", 7, amount), &coin_types);
+        assert_eq!(deposit.unwrap().coin_type, None);
+
+        // Stake events have no coin type, and other events aren't decoded
+        let stake = decode_event(
+            &event(
+                "0x1::stake::AddStakeEvent",
+                2,
+                json!({ "amount_added": "5" }),
+            ),
+            &coin_types,
+        )
+        .unwrap();
+        assert_eq!(stake.amount, 5);
+        assert_eq!(stake.coin_type, None);
+        assert!(decode_event(
+            &event("0xcafe::coin::DepositEvent", 2, json!({ "amount": "10" })),
+            &coin_types
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_parse_indexer_versions() {
+        let response = json!({
+            "data": { "account_transactions": [
+                { "transaction_version": 5 },
+                { "transaction_version": "18446744073709551615" },
+            ] },
+        });
+        assert_eq!(
+            parse_indexer_versions(&response).unwrap(),
+            vec![5, u64::MAX]
+        );
+
+        let response = json!({ "errors": [{ "message": "field not found" }] });
+        assert!(parse_indexer_versions(&response).is_err());
+        let response = json!({ "data": { "account_transactions": [{ "version": 5 }] } });
+        assert!(parse_indexer_versions(&response).is_err());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let transaction = HistoryTransaction {
+            version: 10,
+            sender: AccountAddress::ONE,
+            sequence_number: 3,
+            hash: "0xabc".to_string(),
+            timestamp_us: 1000,
+            function: "0x1::aptos_account::transfer".to_string(),
+            success: false,
+            vm_status: "Move abort, code: 1, \"reason\"".to_string(),
+            gas_used: 7,
+            gas_unit_price: 100,
+            events: vec![],
+        };
+        let mut with_event = transaction.clone();
+        with_event.events.push(HistoryEvent {
+            event_type: "0x1::coin::DepositEvent".to_string(),
+            account: AccountAddress::ONE,
+            amount: 10,
+            coin_type: Some("0x1::aptos_coin::AptosCoin".to_string()),
+        });
+
+        let csv = to_csv(&[transaction, with_event]);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "10,0x1,3,0xabc,1000,0x1::aptos_account::transfer,false,\"Move abort, code: 1, \"\"reason\"\"\",7,100,,,,"
+        );
+        assert!(lines[2].ends_with(",0x1::coin::DepositEvent,0x1,10,0x1::aptos_coin::AptosCoin"));
+    }
+}
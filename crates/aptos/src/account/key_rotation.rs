@@ -7,7 +7,7 @@ use crate::common::{
         AuthenticationKeyInputOptions, CliCommand, CliConfig, CliError, CliTypedResult,
         ConfigSearchMode, EncodingOptions, EncodingType, ExtractPublicKey, ParsePrivateKey,
         ProfileConfig, ProfileOptions, PublicKeyInputOptions, RestOptions, RotationProofChallenge,
-        TransactionOptions, TransactionSummary,
+        TransactionOptions, TransactionSigner, TransactionSummary,
     },
     utils::{prompt_yes, prompt_yes_with_override, read_line},
 };
@@ -102,7 +102,18 @@ impl CliCommand<RotateSummary> for RotateKey {
                 )
             })?;
 
-        let (current_private_key, sender_address) = self.txn_options.get_key_and_address()?;
+        let (current_private_key, sender_address) =
+            match self.txn_options.get_signer_and_address()? {
+                (TransactionSigner::PrivateKey(private_key), sender_address) => {
+                    (private_key, sender_address)
+                },
+                // The device only signs transactions, not the rotation proof challenge
+                (TransactionSigner::Ledger { .. }, _) => {
+                    return Err(CliError::CommandArgumentError(
+                        "The key of a Ledger profile can't be rotated".to_string(),
+                    ))
+                },
+            };
 
         if new_private_key == current_private_key {
             return Err(CliError::CommandArgumentError(
@@ -226,6 +237,7 @@ impl CliCommand<RotateSummary> for RotateKey {
             private_key: Some(new_private_key.clone()),
             public_key: Some(new_private_key.public_key()),
             account: Some(sender_address),
+            derivation_path: None,
             ..self.txn_options.profile_options.profile()?
         };
 
@@ -8,6 +8,7 @@ pub mod create;
 pub mod create_resource_account;
 pub mod derive_resource_account;
 pub mod fund;
+pub mod history;
 pub mod key_rotation;
 pub mod list;
 pub mod multisig_account;
@@ -23,6 +24,7 @@ pub enum AccountTool {
     CreateResourceAccount(create_resource_account::CreateResourceAccount),
     DeriveResourceAccountAddress(derive_resource_account::DeriveResourceAccount),
     FundWithFaucet(fund::FundWithFaucet),
+    History(history::AccountHistory),
     List(list::ListAccount),
     LookupAddress(key_rotation::LookupAddress),
     RotateKey(key_rotation::RotateKey),
@@ -36,6 +38,7 @@ impl AccountTool {
             AccountTool::CreateResourceAccount(tool) => tool.execute_serialized().await,
             AccountTool::DeriveResourceAccountAddress(tool) => tool.execute_serialized().await,
             AccountTool::FundWithFaucet(tool) => tool.execute_serialized().await,
+            AccountTool::History(tool) => tool.execute_serialized().await,
             AccountTool::List(tool) => tool.execute_serialized().await,
             AccountTool::LookupAddress(tool) => tool.execute_serialized().await,
             AccountTool::RotateKey(tool) => tool.execute_serialized().await,
@@ -26,6 +26,9 @@ use std::{collections::BTreeMap, str::FromStr};
 /// 1 APT (might not actually get that much, depending on the faucet)
 const NUM_DEFAULT_OCTAS: u64 = 100000000;
 
+/// Derivation path of the first Aptos account on a Ledger device
+const DEFAULT_LEDGER_DERIVATION_PATH: &str = "m/44'/637'/0'/0'/0'";
+
 /// Tool to initialize current directory for the aptos tool
 ///
 /// Configuration will be pushed into .aptos/config.yaml
@@ -49,6 +52,18 @@ pub struct InitTool {
     #[clap(long)]
     pub skip_faucet: bool,
 
+    /// Use a key on a Ledger device to sign transactions, instead of a private key
+    ///
+    /// The device must be unlocked, with the Aptos app open
+    #[clap(long, conflicts_with = "private_key_input")]
+    pub ledger: bool,
+
+    /// Derivation path of the key on the Ledger device
+    ///
+    /// Defaults to the first account, i.e., m/44'/637'/0'/0'/0'
+    #[clap(long, requires = "ledger")]
+    pub derivation_path: Option<String>,
+
     #[clap(flatten)]
     pub rng_args: RngArgs,
     #[clap(flatten)]
@@ -131,33 +146,26 @@ impl CliCommand<()> for InitTool {
             Network::Custom => self.custom_network(&mut profile_config)?,
         }
 
-        // Private key
-        let private_key = if let Some(private_key) = self
-            .private_key_options
-            .extract_private_key_cli(self.encoding_options.encoding)?
-        {
-            eprintln!("Using command line argument for private key");
-            private_key
+        // Private key (or Ledger key)
+        let (private_key, public_key) = if self.ledger {
+            let derivation_path = self
+                .derivation_path
+                .clone()
+                .unwrap_or_else(|| DEFAULT_LEDGER_DERIVATION_PATH.to_string());
+            eprintln!(
+                "Please confirm the public key of {} on the Ledger device",
+                derivation_path
+            );
+            let public_key = aptos_ledger::get_public_key(&derivation_path, true)
+                .map_err(|err| CliError::UnexpectedError(format!("Ledger error: {}", err)))?;
+            profile_config.derivation_path = Some(derivation_path);
+            (None, public_key)
         } else {
-            eprintln!("Enter your private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", profile_config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
-            let input = read_line("Private key")?;
-            let input = input.trim();
-            if input.is_empty() {
-                if let Some(private_key) = profile_config.private_key {
-                    eprintln!("No key given, keeping existing key...");
-                    private_key
-                } else {
-                    eprintln!("No key given, generating key...");
-                    self.rng_args
-                        .key_generator()?
-                        .generate_ed25519_private_key()
-                }
-            } else {
-                Ed25519PrivateKey::from_encoded_string(input)
-                    .map_err(|err| CliError::UnableToParse("Ed25519PrivateKey", err.to_string()))?
-            }
+            let private_key = self.extract_private_key(&mut profile_config)?;
+            let public_key = private_key.public_key();
+            profile_config.derivation_path = None;
+            (Some(private_key), public_key)
         };
-        let public_key = private_key.public_key();
 
         let client = aptos_rest_client::Client::new(
             Url::parse(
@@ -174,7 +182,7 @@ impl CliCommand<()> for InitTool {
         let derived_address = account_address_from_public_key(&public_key);
         let address = lookup_address(&client, derived_address, false).await?;
 
-        profile_config.private_key = Some(private_key);
+        profile_config.private_key = private_key;
         profile_config.public_key = Some(public_key);
         profile_config.account = Some(address);
 
@@ -259,6 +267,41 @@ impl CliCommand<()> for InitTool {
 }
 
 impl InitTool {
+    /// Private key from the command line, or prompted for (or generated)
+    fn extract_private_key(
+        &self,
+        profile_config: &mut ProfileConfig,
+    ) -> CliTypedResult<Ed25519PrivateKey> {
+        Ok(
+            if let Some(private_key) = self
+                .private_key_options
+                .extract_private_key_cli(self.encoding_options.encoding)?
+            {
+                eprintln!("Using command line argument for private key");
+                private_key
+            } else {
+                eprintln!("Enter your private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", profile_config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
+                let input = read_line("Private key")?;
+                let input = input.trim();
+                if input.is_empty() {
+                    if let Some(private_key) = profile_config.private_key.take() {
+                        eprintln!("No key given, keeping existing key...");
+                        private_key
+                    } else {
+                        eprintln!("No key given, generating key...");
+                        self.rng_args
+                            .key_generator()?
+                            .generate_ed25519_private_key()
+                    }
+                } else {
+                    Ed25519PrivateKey::from_encoded_string(input).map_err(|err| {
+                        CliError::UnableToParse("Ed25519PrivateKey", err.to_string())
+                    })?
+                }
+            },
+        )
+    }
+
     /// Custom network created, which requires a REST URL
     fn custom_network(&self, profile_config: &mut ProfileConfig) -> CliTypedResult<()> {
         // Rest Endpoint
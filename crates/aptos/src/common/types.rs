@@ -31,12 +31,13 @@ use aptos_rest_client::{
     error::RestError,
     AptosBaseUrl, Client, Transaction,
 };
-use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+use aptos_sdk::transaction_builder::TransactionFactory;
 use aptos_types::{
     chain_id::ChainId,
     transaction::{
-        authenticator::AuthenticationKey, EntryFunction, MultisigTransactionPayload, Script,
-        SignedTransaction, TransactionArgument, TransactionPayload, TransactionStatus,
+        authenticator::AuthenticationKey, EntryFunction, MultisigTransactionPayload,
+        RawTransaction, Script, SignedTransaction, TransactionArgument, TransactionPayload,
+        TransactionStatus,
     },
 };
 use async_trait::async_trait;
@@ -215,6 +216,10 @@ pub struct ProfileConfig {
     /// Account for commands
     #[serde(skip_serializing_if = "Option::is_none")]
     pub account: Option<AccountAddress>,
+    /// Derivation path of the key on a Ledger device, which signs transactions for commands
+    /// (instead of the private key)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
     /// URL for the Aptos rest endpoint
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rest_url: Option<String>,
@@ -230,6 +235,8 @@ pub struct ProfileSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub public_key: Option<Ed25519PublicKey>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account: Option<AccountAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rest_url: Option<String>,
@@ -242,6 +249,7 @@ impl From<&ProfileConfig> for ProfileSummary {
         ProfileSummary {
             has_private_key: config.private_key.is_some(),
             public_key: config.public_key.clone(),
+            derivation_path: config.derivation_path.clone(),
             account: config.account,
             rest_url: config.rest_url.clone(),
             faucet_url: config.faucet_url.clone(),
@@ -1331,6 +1339,59 @@ impl Default for GasOptions {
     }
 }
 
+/// The signer of transactions, i.e., a private key or a key on a Ledger device
+pub enum TransactionSigner {
+    PrivateKey(Ed25519PrivateKey),
+    /// The key at the derivation path, which is signed with on the device
+    Ledger {
+        derivation_path: String,
+        public_key: Ed25519PublicKey,
+    },
+}
+
+impl TransactionSigner {
+    pub fn public_key(&self) -> Ed25519PublicKey {
+        match self {
+            TransactionSigner::PrivateKey(private_key) => private_key.public_key(),
+            TransactionSigner::Ledger { public_key, .. } => public_key.clone(),
+        }
+    }
+
+    /// Signs the transaction. The Ledger device displays the transaction for approval, but
+    /// it's also printed here, as the device can't decode every payload.
+    pub fn sign(&self, transaction: RawTransaction) -> CliTypedResult<SignedTransaction> {
+        match self {
+            TransactionSigner::PrivateKey(private_key) => Ok(transaction
+                .sign(private_key, private_key.public_key())
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+                .into_inner()),
+            TransactionSigner::Ledger {
+                derivation_path,
+                public_key,
+            } => {
+                eprintln!(
+                    "Please review and approve the transaction on the Ledger device:\n{}",
+                    transaction.format_for_client(|_| "script".to_string())
+                );
+                let signing_message = transaction
+                    .signing_message()
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+                let signature = aptos_ledger::sign_txn(derivation_path, signing_message)
+                    .map_err(|err| CliError::UnexpectedError(format!("Ledger error: {}", err)))?;
+                let signature =
+                    Ed25519Signature::try_from(signature.as_slice()).map_err(|err| {
+                        CliError::UnexpectedError(format!("Invalid Ledger signature: {}", err))
+                    })?;
+                Ok(SignedTransaction::new(
+                    transaction,
+                    public_key.clone(),
+                    signature,
+                ))
+            },
+        }
+    }
+}
+
 /// Common options for interacting with an account for a validator
 #[derive(Debug, Default, Parser)]
 pub struct TransactionOptions {
@@ -1362,7 +1423,7 @@ pub struct TransactionOptions {
 
 impl TransactionOptions {
     /// Builds a rest client
-    fn rest_client(&self) -> CliTypedResult<Client> {
+    pub(crate) fn rest_client(&self) -> CliTypedResult<Client> {
         self.rest_options.client(&self.profile_options)
     }
 
@@ -1376,8 +1437,53 @@ impl TransactionOptions {
         )
     }
 
+    /// Retrieves the signer of transactions and the associated address
+    ///
+    /// This is the Ledger device of the profile, if it has a derivation path (and no private
+    /// key), unless a private key is passed on the command line.
+    pub fn get_signer_and_address(&self) -> CliTypedResult<(TransactionSigner, AccountAddress)> {
+        if self
+            .private_key_options
+            .extract_private_key_cli(self.encoding_options.encoding)?
+            .is_none()
+        {
+            if let Some(ProfileConfig {
+                private_key: None,
+                derivation_path: Some(derivation_path),
+                public_key,
+                account,
+                ..
+            }) = CliConfig::load_profile(
+                self.profile_options.profile_name(),
+                ConfigSearchMode::CurrentDirAndParents,
+            )? {
+                let public_key =
+                    match public_key {
+                        Some(public_key) => public_key,
+                        None => aptos_ledger::get_public_key(&derivation_path, false).map_err(
+                            |err| CliError::UnexpectedError(format!("Ledger error: {}", err)),
+                        )?,
+                    };
+                let address = self
+                    .sender_account
+                    .or(account)
+                    .unwrap_or_else(|| account_address_from_public_key(&public_key));
+                return Ok((
+                    TransactionSigner::Ledger {
+                        derivation_path,
+                        public_key,
+                    },
+                    address,
+                ));
+            }
+        }
+
+        let (private_key, address) = self.get_key_and_address()?;
+        Ok((TransactionSigner::PrivateKey(private_key), address))
+    }
+
     pub fn sender_address(&self) -> CliTypedResult<AccountAddress> {
-        Ok(self.get_key_and_address()?.1)
+        Ok(self.get_signer_and_address()?.1)
     }
 
     /// Gets the auth key by account address. We need to fetch the auth key from Rest API rather than creating an
@@ -1406,7 +1512,7 @@ impl TransactionOptions {
         payload: TransactionPayload,
     ) -> CliTypedResult<Transaction> {
         let client = self.rest_client()?;
-        let (sender_key, sender_address) = self.get_key_and_address()?;
+        let (signer, sender_address) = self.get_signer_and_address()?;
 
         // Ask to confirm price if the gas unit price is estimated above the lowest value when
         // it is automatically estimated
@@ -1462,7 +1568,7 @@ impl TransactionOptions {
 
             let signed_transaction = SignedTransaction::new(
                 unsigned_transaction,
-                sender_key.public_key(),
+                signer.public_key(),
                 Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
             );
 
@@ -1501,9 +1607,12 @@ impl TransactionOptions {
             .with_gas_unit_price(gas_unit_price)
             .with_max_gas_amount(max_gas)
             .with_transaction_expiration_time(self.gas_options.expiration_secs);
-        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
-        let transaction =
-            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+        let unsigned_transaction = transaction_factory
+            .payload(payload)
+            .sender(sender_address)
+            .sequence_number(sequence_number)
+            .build();
+        let transaction = signer.sign(unsigned_transaction)?;
         let response = client
             .submit_and_wait(&transaction)
             .await
@@ -1512,6 +1621,42 @@ impl TransactionOptions {
         Ok(response.into_inner())
     }
 
+    /// Builds an unsigned transaction (e.g., to be signed offline)
+    ///
+    /// The sender doesn't need a private key, as it falls back to the account of the profile.
+    /// Without a max gas, the transaction can't be simulated, so the default max gas is used.
+    pub async fn build_unsigned_transaction(
+        &self,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<RawTransaction> {
+        let client = self.rest_client()?;
+        let sender_address = match self.sender_account {
+            Some(sender_address) => sender_address,
+            None => self
+                .sender_address()
+                .or_else(|_| self.profile_options.account_address())?,
+        };
+
+        let gas_unit_price = if let Some(gas_unit_price) = self.gas_options.gas_unit_price {
+            gas_unit_price
+        } else {
+            client.estimate_gas_price().await?.into_inner().gas_estimate
+        };
+        let (account, state) = get_account_with_state(&client, sender_address).await?;
+
+        let mut transaction_factory = TransactionFactory::new(ChainId::new(state.chain_id))
+            .with_gas_unit_price(gas_unit_price)
+            .with_transaction_expiration_time(self.gas_options.expiration_secs);
+        if let Some(max_gas) = self.gas_options.max_gas {
+            transaction_factory = transaction_factory.with_max_gas_amount(max_gas);
+        }
+        Ok(transaction_factory
+            .payload(payload)
+            .sender(sender_address)
+            .sequence_number(account.sequence_number)
+            .build())
+    }
+
     /// Simulate the transaction locally using the debugger, with the gas profiler enabled.
     pub async fn profile_gas(
         &self,
@@ -1528,7 +1673,7 @@ impl TransactionOptions {
         const DEFAULT_GAS_UNIT_PRICE: u64 = 100;
         const DEFAULT_MAX_GAS: u64 = 2_000_000;
 
-        let (sender_key, sender_address) = self.get_key_and_address()?;
+        let (signer, sender_address) = self.get_signer_and_address()?;
         let gas_unit_price = self
             .gas_options
             .gas_unit_price
@@ -1557,9 +1702,13 @@ impl TransactionOptions {
             .with_gas_unit_price(gas_unit_price)
             .with_max_gas_amount(max_gas)
             .with_transaction_expiration_time(self.gas_options.expiration_secs);
-        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
-        let transaction =
-            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+        let transaction = signer.sign(
+            transaction_factory
+                .payload(payload)
+                .sender(sender_address)
+                .sequence_number(sequence_number)
+                .build(),
+        )?;
         let hash = transaction.clone().committed_hash();
 
         // Execute the transaction using the debugger
@@ -28,6 +28,7 @@ use aptos_genesis::{
         AccountBalanceMap, EmployeePoolMap, HostAndPort, Layout, StringOperatorConfiguration,
         StringOwnerConfiguration, ValidatorConfiguration,
     },
+    fork::ForkGenesisInfo,
     mainnet::MainnetGenesisInfo,
     GenesisInfo,
 };
@@ -35,6 +36,7 @@ use aptos_logger::info;
 use aptos_types::{
     account_address::{AccountAddress, AccountAddressWithChecks},
     on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig},
+    transaction::Version,
 };
 use aptos_vm_genesis::{default_gas_schedule, AccountBalance, EmployeePool};
 use async_trait::async_trait;
@@ -48,6 +50,7 @@ use std::{
 
 const WAYPOINT_FILE: &str = "waypoint.txt";
 const GENESIS_FILE: &str = "genesis.blob";
+const DB_DIR: &str = "db";
 
 /// Tool for setting up an Aptos chain Genesis transaction
 ///
@@ -55,6 +58,7 @@ const GENESIS_FILE: &str = "genesis.blob";
 /// accounts to build a genesis transaction for a new chain.
 #[derive(Parser)]
 pub enum GenesisTool {
+    Fork(ForkGenesis),
     GenerateAdminWriteSet(keys::GenerateAdminWriteSet),
     GenerateGenesis(GenerateGenesis),
     GetPoolAddresses(tools::PoolAddresses),
@@ -67,6 +71,7 @@ pub enum GenesisTool {
 impl GenesisTool {
     pub async fn execute(self) -> CliResult {
         match self {
+            GenesisTool::Fork(tool) => tool.execute_serialized().await,
             GenesisTool::GenerateAdminWriteSet(tool) => tool.execute_serialized_success().await,
             GenesisTool::GenerateGenesis(tool) => tool.execute_serialized().await,
             GenesisTool::GetPoolAddresses(tool) => tool.execute_serialized().await,
@@ -134,6 +139,79 @@ impl CliCommand<Vec<PathBuf>> for GenerateGenesis {
     }
 }
 
+/// Generate genesis for a new chain forked from the state of an existing chain
+///
+/// This will restore the state of the source database at the given version into
+/// a new database (in the db directory of the output directory), and create a
+/// genesis.blob and a waypoint.txt that are applied on top of it. The genesis only
+/// replaces the validator set (and the chain id) by those in the git repository,
+/// which must be set up as for a test genesis, and starts a new epoch. Code, table
+/// items and all other accounts are kept as is, so the framework release in the
+/// repository should match the framework of the source chain.
+///
+/// The version must be the end of a block in the source database (i.e., have a
+/// state snapshot), that hasn't been pruned. The restored database has to be
+/// copied into the data directory of each node of the new chain.
+#[derive(Parser)]
+pub struct ForkGenesis {
+    /// Path to the database of the chain to fork (e.g., a mainnet full node)
+    #[clap(long, value_parser)]
+    source_db: PathBuf,
+    /// The version of the source chain at which to fork the state
+    #[clap(long)]
+    at_version: Version,
+    /// Output directory for Genesis file and waypoint
+    #[clap(long, value_parser)]
+    output_dir: Option<PathBuf>,
+
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+    #[clap(flatten)]
+    git_options: GitOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<PathBuf>> for ForkGenesis {
+    fn command_name(&self) -> &'static str {
+        "ForkGenesis"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<PathBuf>> {
+        let output_dir = dir_default_to_current(self.output_dir.clone())?;
+        let genesis_file = output_dir.join(GENESIS_FILE);
+        let waypoint_file = output_dir.join(WAYPOINT_FILE);
+        check_if_file_exists(genesis_file.as_path(), self.prompt_options)?;
+        check_if_file_exists(waypoint_file.as_path(), self.prompt_options)?;
+        let db_dir = output_dir.join(DB_DIR);
+        check_if_file_exists(db_dir.as_path(), self.prompt_options)?;
+        // The state can only be restored into an empty database
+        if db_dir.exists() {
+            std::fs::remove_dir_all(db_dir.as_path())
+                .map_err(|e| CliError::IO(db_dir.display().to_string(), e))?;
+        }
+
+        // Restore the source state, and generate genesis and waypoint files on top of it
+        let new_chain_genesis = fetch_genesis_info(self.git_options)?;
+        let mut fork_genesis = ForkGenesisInfo::new(
+            self.source_db,
+            self.at_version,
+            new_chain_genesis,
+            db_dir.clone(),
+        );
+        fork_genesis.restore_source_state()?;
+        let genesis_bytes = bcs::to_bytes(fork_genesis.get_genesis()?)
+            .map_err(|e| CliError::BCS(GENESIS_FILE, e))?;
+        let waypoint = fork_genesis.generate_waypoint()?;
+        write_to_file(genesis_file.as_path(), GENESIS_FILE, &genesis_bytes)?;
+        write_to_file(
+            waypoint_file.as_path(),
+            WAYPOINT_FILE,
+            waypoint.to_string().as_bytes(),
+        )?;
+        Ok(vec![genesis_file, waypoint_file, db_dir])
+    }
+}
+
 /// Retrieves all information for mainnet genesis from the Git repository
 pub fn fetch_mainnet_genesis_info(git_options: GitOptions) -> CliTypedResult<MainnetGenesisInfo> {
     let client = git_options.get_client()?;
@@ -21,6 +21,7 @@ use crate::{
 use aptos_api_types::ViewRequest;
 use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::HashValue;
+use aptos_debugger::{AptosDebugger, ResourceChange};
 use aptos_framework::{BuildOptions, BuiltPackage, ReleasePackage};
 use aptos_logger::warn;
 use aptos_rest_client::{
@@ -36,9 +37,18 @@ use aptos_types::{
     state_store::table::TableHandle,
     transaction::{Script, TransactionPayload},
 };
+use aptos_vm::move_vm_ext::{SessionExt, SessionId};
 use async_trait::async_trait;
 use clap::Parser;
-use move_core_types::transaction_argument::TransactionArgument;
+use move_binary_format::errors::{Location, PartialVMError, VMResult};
+use move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    transaction_argument::TransactionArgument,
+    vm_status::StatusCode,
+};
+use move_vm_types::gas::UnmeteredGasMeter;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -62,6 +72,7 @@ pub enum GovernanceTool {
     ListProposals(ListProposals),
     VerifyProposal(VerifyProposal),
     ExecuteProposal(ExecuteProposal),
+    SimulateProposal(SimulateProposal),
     GenerateUpgradeProposal(GenerateUpgradeProposal),
     ApproveExecutionHash(ApproveExecutionHash),
     #[clap(subcommand)]
@@ -75,6 +86,7 @@ impl GovernanceTool {
             Propose(tool) => tool.execute_serialized().await,
             Vote(tool) => tool.execute_serialized().await,
             ExecuteProposal(tool) => tool.execute_serialized().await,
+            SimulateProposal(tool) => tool.execute_serialized().await,
             GenerateUpgradeProposal(tool) => tool.execute_serialized_success().await,
             ShowProposal(tool) => tool.execute_serialized().await,
             ListProposals(tool) => tool.execute_serialized().await,
@@ -878,6 +890,255 @@ impl CliCommand<TransactionSummary> for ExecuteProposal {
     }
 }
 
+/// Resources changed by the simulation itself, rather than by the proposal
+const SIMULATION_RESOURCES: &[&str] = &[
+    "0x1::timestamp::CurrentTimeMicroseconds",
+    "0x1::voting::VotingForum<0x1::governance_proposal::GovernanceProposal>",
+];
+
+/// Simulate the execution of a proposal before submitting it
+///
+/// The proposal script is executed against the state of the chain at the latest version
+/// (which is fetched from the node as needed, like in the debugger), as if a proposal for
+/// it had been created, voted for and resolved. The resources that the proposal changes
+/// (e.g., the on-chain configs) are printed as diffs of their values.
+#[derive(Parser)]
+pub struct SimulateProposal {
+    /// Whether the proposal would be a multi-step proposal
+    #[clap(long)]
+    pub(crate) is_multi_step: bool,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+    #[clap(flatten)]
+    pub(crate) compile_proposal_args: CompileScriptFunction,
+}
+
+/// The result of simulating a proposal
+#[derive(Serialize)]
+pub struct ProposalSimulationSummary {
+    pub version: u64,
+    pub script_hash: HashValue,
+    pub changes: Vec<ResourceChange>,
+}
+
+#[async_trait]
+impl CliCommand<ProposalSimulationSummary> for SimulateProposal {
+    fn command_name(&self) -> &'static str {
+        "SimulateProposal"
+    }
+
+    async fn execute(self) -> CliTypedResult<ProposalSimulationSummary> {
+        let (bytecode, script_hash) = self
+            .compile_proposal_args
+            .compile("SimulateProposal", self.prompt_options)?;
+
+        let client = self.rest_options.client(&self.profile_options)?;
+        let debugger = AptosDebugger::rest_client(client)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        let version = debugger
+            .get_latest_version()
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+
+        // The proposal is resolved by the script, which checks that its hash is the execution
+        // hash of the proposal, so the session must be run as if it executes the script
+        let session_id = SessionId::Txn {
+            sender: CORE_CODE_ADDRESS,
+            sequence_number: 0,
+            script_hash: script_hash.to_vec(),
+        };
+        let is_multi_step = self.is_multi_step;
+        let change_set = debugger
+            .run_session_with_id_at_version(version, session_id, |session| {
+                simulate_proposal(session, bytecode, script_hash, is_multi_step)
+            })
+            .map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to simulate the proposal: {:#}", err))
+            })?;
+        let changes: Vec<_> = debugger
+            .annotate_resource_changes_at_version(version, &change_set)
+            .map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to annotate the changes: {:#}", err))
+            })?
+            .into_iter()
+            .filter(|change| !SIMULATION_RESOURCES.contains(&change.resource_type.as_str()))
+            .collect();
+
+        println!(
+            "Simulated the proposal at version {} with {} changed resources",
+            version,
+            changes.len()
+        );
+        for change in &changes {
+            print_resource_change(change);
+        }
+        Ok(ProposalSimulationSummary {
+            version,
+            script_hash,
+            changes,
+        })
+    }
+}
+
+/// Creates a proposal for the script, votes for it, and moves the time forward so that it can
+/// be resolved. Then executes the script, which resolves the proposal.
+fn simulate_proposal(
+    session: &mut SessionExt,
+    bytecode: Vec<u8>,
+    script_hash: HashValue,
+    is_multi_step: bool,
+) -> VMResult<()> {
+    let proposal_type = TypeTag::Struct(Box::new(StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: ident_str!("governance_proposal").to_owned(),
+        name: ident_str!("GovernanceProposal").to_owned(),
+        type_params: vec![],
+    }));
+    // A `GovernanceProposal` is empty, so it's serialized as its dummy field
+    let proposal = bcs::to_bytes(&false).unwrap();
+    let forum_address = bcs::to_bytes(&CORE_CODE_ADDRESS).unwrap();
+
+    let now_microseconds = call_framework_function(
+        session,
+        ident_str!("timestamp"),
+        ident_str!("now_microseconds"),
+        vec![],
+        vec![],
+    )?;
+    let now_microseconds: u64 = deserialize_return_value(&now_microseconds)?;
+
+    // A single vote resolves the proposal early
+    let proposal_id = call_framework_function(
+        session,
+        ident_str!("voting"),
+        ident_str!("create_proposal_v2"),
+        vec![proposal_type.clone()],
+        vec![
+            forum_address.clone(),
+            forum_address.clone(),
+            proposal.clone(),
+            bcs::to_bytes(&script_hash.to_vec()).unwrap(),
+            bcs::to_bytes(&0u128).unwrap(),
+            bcs::to_bytes(&u64::MAX).unwrap(),
+            bcs::to_bytes(&Some(1u128)).unwrap(),
+            // An empty `SimpleMap` of metadata
+            bcs::to_bytes(&Vec::<u8>::new()).unwrap(),
+            bcs::to_bytes(&is_multi_step).unwrap(),
+        ],
+    )?;
+    let proposal_id: u64 = deserialize_return_value(&proposal_id)?;
+    call_framework_function(
+        session,
+        ident_str!("voting"),
+        ident_str!("vote"),
+        vec![proposal_type],
+        vec![
+            proposal,
+            forum_address.clone(),
+            bcs::to_bytes(&proposal_id).unwrap(),
+            bcs::to_bytes(&1u64).unwrap(),
+            bcs::to_bytes(&true).unwrap(),
+        ],
+    )?;
+
+    // Proposals can't be resolved in the same second as the last vote
+    call_framework_function(
+        session,
+        ident_str!("timestamp"),
+        ident_str!("update_global_time"),
+        vec![],
+        vec![
+            // The VM signer
+            bcs::to_bytes(&AccountAddress::ZERO).unwrap(),
+            forum_address,
+            bcs::to_bytes(&(now_microseconds + 1_000_000)).unwrap(),
+        ],
+    )?;
+
+    session.execute_script(
+        bytecode,
+        vec![],
+        vec![bcs::to_bytes(&proposal_id).unwrap()],
+        &mut UnmeteredGasMeter,
+    )?;
+    Ok(())
+}
+
+/// Calls a function of the framework, and returns its serialized return values
+fn call_framework_function(
+    session: &mut SessionExt,
+    module: &IdentStr,
+    function: &IdentStr,
+    ty_args: Vec<TypeTag>,
+    args: Vec<Vec<u8>>,
+) -> VMResult<Vec<Vec<u8>>> {
+    let return_values = session
+        .execute_function_bypass_visibility(
+            &ModuleId::new(CORE_CODE_ADDRESS, module.to_owned()),
+            function,
+            ty_args,
+            args,
+            &mut UnmeteredGasMeter,
+        )?
+        .return_values;
+    Ok(return_values.into_iter().map(|(bytes, _)| bytes).collect())
+}
+
+/// Deserializes the first return value of a function
+fn deserialize_return_value<T: serde::de::DeserializeOwned>(
+    return_values: &[Vec<u8>],
+) -> VMResult<T> {
+    return_values
+        .first()
+        .and_then(|bytes| bcs::from_bytes(bytes).ok())
+        .ok_or_else(|| {
+            PartialVMError::new(StatusCode::UNEXPECTED_DESERIALIZATION_ERROR)
+                .finish(Location::Undefined)
+        })
+}
+
+/// Prints the lines of the value of the resource that changed
+fn print_resource_change(change: &ResourceChange) {
+    println!("\n{} at {}:", change.resource_type, change.address);
+    for line in resource_change_diff(change) {
+        println!("{}", line);
+    }
+}
+
+/// Returns the diff of the lines of the value of the resource, with removed lines prefixed
+/// by `-` and added lines prefixed by `+`
+fn resource_change_diff(change: &ResourceChange) -> Vec<String> {
+    let old_lines: Vec<_> = change
+        .old_value
+        .iter()
+        .flat_map(|value| value.lines())
+        .collect();
+    let new_lines: Vec<_> = change
+        .new_value
+        .iter()
+        .flat_map(|value| value.lines())
+        .collect();
+    let mut diff = vec![];
+    if old_lines.len() == new_lines.len() {
+        for (old_line, new_line) in old_lines.iter().zip(&new_lines) {
+            if old_line != new_line {
+                diff.push(format!("-{}", old_line));
+                diff.push(format!("+{}", new_line));
+            }
+        }
+    } else {
+        // The layout of the value changed (or it was created or deleted)
+        diff.extend(old_lines.iter().map(|old_line| format!("-{}", old_line)));
+        diff.extend(new_lines.iter().map(|new_line| format!("+{}", new_line)));
+    }
+    diff
+}
+
 /// Compile a specified script.
 #[derive(Parser)]
 pub struct CompileScriptFunction {
@@ -1222,3 +1483,36 @@ struct JsonMetadataPair {
     key: String,
     value: HexEncodedBytes,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource_change(old_value: Option<&str>, new_value: Option<&str>) -> ResourceChange {
+        ResourceChange {
+            address: CORE_CODE_ADDRESS,
+            resource_type: "0x1::features::Features".to_string(),
+            old_value: old_value.map(str::to_string),
+            new_value: new_value.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_resource_change_diff() {
+        // Only the changed lines are shown
+        let change = resource_change(Some("{\n  a: 1,\n  b: 2\n}"), Some("{\n  a: 1,\n  b: 3\n}"));
+        assert_eq!(resource_change_diff(&change), vec!["-  b: 2", "+  b: 3"]);
+
+        // Created and deleted resources show all of their lines
+        let change = resource_change(None, Some("{\n  a: 1\n}"));
+        assert_eq!(resource_change_diff(&change), vec!["+{", "+  a: 1", "+}"]);
+        let change = resource_change(Some("{\n  a: 1\n}"), None);
+        assert_eq!(resource_change_diff(&change), vec!["-{", "-  a: 1", "-}"]);
+
+        // A changed layout shows the old and new values
+        let change = resource_change(Some("{\n  a: []\n}"), Some("{\n  a: [\n    1\n  ]\n}"));
+        assert_eq!(resource_change_diff(&change), vec![
+            "-{", "-  a: []", "-}", "+{", "+  a: [", "+    1", "+  ]", "+}"
+        ]);
+    }
+}
@@ -0,0 +1,407 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{
+            CliCommand, CliError, CliTypedResult, EntryFunctionArguments, TransactionOptions,
+            TransactionSummary,
+        },
+        utils::{profile_or_submit, read_line, write_to_file},
+    },
+    move_tool::{ArgWithType, FunctionArgType, MemberId},
+};
+use aptos_rest_client::aptos_api_types::{HexEncodedBytes, MoveFunction, MoveType};
+use aptos_types::transaction::{EntryFunction, TransactionPayload};
+use async_trait::async_trait;
+use clap::Parser;
+use move_core_types::{account_address::AccountAddress, language_storage::TypeTag};
+use serde::Serialize;
+use std::{io::Write, path::PathBuf, str::FromStr};
+
+/// Call an entry function, optionally building its arguments interactively
+///
+/// With `--interactive`, the ABI of the function is fetched from the chain, and each type
+/// argument and argument is prompted for (and validated against its type). The `signer`
+/// arguments are filled in by the transaction and aren't prompted for.
+///
+/// With `--output-file`, the transaction isn't signed or submitted. Instead, the unsigned
+/// transaction is written to the file as BCS, e.g., to be signed on an air-gapped machine.
+#[derive(Parser)]
+pub struct CallFunction {
+    #[clap(flatten)]
+    pub(crate) entry_function_args: EntryFunctionArguments,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+
+    /// Prompt for the type arguments and arguments of the function based on its ABI
+    #[clap(long, conflicts_with_all = &["args", "json_file"])]
+    pub(crate) interactive: bool,
+
+    /// Write the unsigned transaction as BCS to this file instead of submitting it
+    ///
+    /// Without `--max-gas`, the default max gas is used, as the transaction isn't simulated.
+    #[clap(long, value_parser)]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+/// The result of calling a function
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum CallFunctionResult {
+    /// The transaction was submitted
+    Submitted(TransactionSummary),
+    /// The unsigned transaction was written to a file
+    Unsigned(UnsignedTransactionSummary),
+}
+
+/// A summary of an unsigned transaction written to a file
+#[derive(Serialize)]
+pub struct UnsignedTransactionSummary {
+    pub output_file: PathBuf,
+    pub sender: AccountAddress,
+    /// The message to be signed by the sender
+    pub signing_message: HexEncodedBytes,
+}
+
+#[async_trait]
+impl CliCommand<CallFunctionResult> for CallFunction {
+    fn command_name(&self) -> &'static str {
+        "CallFunction"
+    }
+
+    async fn execute(self) -> CliTypedResult<CallFunctionResult> {
+        let entry_function = if self.interactive {
+            self.prompt_entry_function().await?
+        } else {
+            self.entry_function_args.try_into()?
+        };
+        let payload = TransactionPayload::EntryFunction(entry_function);
+
+        if let Some(output_file) = self.output_file {
+            let transaction = self.txn_options.build_unsigned_transaction(payload).await?;
+            let signing_message = transaction
+                .signing_message()
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+            write_to_file(
+                output_file.as_path(),
+                "Unsigned transaction",
+                &bcs::to_bytes(&transaction)?,
+            )?;
+            Ok(CallFunctionResult::Unsigned(UnsignedTransactionSummary {
+                output_file,
+                sender: transaction.sender(),
+                signing_message: signing_message.into(),
+            }))
+        } else {
+            profile_or_submit(payload, &self.txn_options)
+                .await
+                .map(CallFunctionResult::Submitted)
+        }
+    }
+}
+
+impl CallFunction {
+    /// Builds the entry function by prompting for its type arguments and arguments
+    async fn prompt_entry_function(&self) -> CliTypedResult<EntryFunction> {
+        let function_id: MemberId = (&self.entry_function_args).try_into()?;
+        let function = self.fetch_function_abi(&function_id).await?;
+        if !function.is_entry {
+            return Err(CliError::CommandArgumentError(format!(
+                "Function {}::{} is not an entry function",
+                function_id.module_id, function_id.member_id
+            )));
+        }
+
+        // Type arguments passed on the command line are used as is
+        let mut type_args = self.entry_function_args.type_arg_vec.type_args.clone();
+        if type_args.is_empty() {
+            for index in 0..function.generic_type_params.len() {
+                type_args.push(prompt_until_valid(
+                    &format!("Type argument T{}", index),
+                    |input| {
+                        MoveType::from_str(input.trim()).map_err(|err| {
+                            CliError::UnableToParse("type argument", err.to_string())
+                        })
+                    },
+                )?);
+            }
+        } else if type_args.len() != function.generic_type_params.len() {
+            return Err(CliError::CommandArgumentError(format!(
+                "Expected {} type arguments, but got {}",
+                function.generic_type_params.len(),
+                type_args.len()
+            )));
+        }
+
+        let mut args = vec![];
+        for (index, param) in function.params.iter().enumerate() {
+            if is_signer(param) {
+                continue;
+            }
+            let (arg_type, vector_layers) = get_function_arg_type(param, &type_args)?;
+            let prompt = format!("Argument {} ({})", index, param);
+            let arg = prompt_until_valid(&prompt, |input| {
+                parse_prompted_arg(&arg_type, &vector_layers, input)
+            })?;
+            args.push(arg.arg);
+        }
+
+        let mut type_tags = vec![];
+        for type_arg in type_args {
+            type_tags.push(
+                TypeTag::try_from(type_arg)
+                    .map_err(|err| CliError::UnableToParse("type argument", err.to_string()))?,
+            );
+        }
+        Ok(EntryFunction::new(
+            function_id.module_id,
+            function_id.member_id,
+            type_tags,
+            args,
+        ))
+    }
+
+    /// Fetches the ABI of the function from its module on chain
+    async fn fetch_function_abi(&self, function_id: &MemberId) -> CliTypedResult<MoveFunction> {
+        let client = self.txn_options.rest_client()?;
+        let module = client
+            .get_account_module(
+                *function_id.module_id.address(),
+                function_id.module_id.name().as_str(),
+            )
+            .await?
+            .into_inner()
+            .try_parse_abi()
+            .map_err(|err| CliError::UnexpectedError(format!("Failed to parse ABI {}", err)))?;
+        module
+            .abi
+            .and_then(|abi| {
+                abi.exposed_functions
+                    .into_iter()
+                    .find(|function| function.name.as_str() == function_id.member_id.as_str())
+            })
+            .ok_or_else(|| {
+                CliError::CommandArgumentError(format!(
+                    "Function {}::{} not found",
+                    function_id.module_id, function_id.member_id
+                ))
+            })
+    }
+}
+
+/// Returns true if the parameter is a signer (or a reference to one)
+fn is_signer(param: &MoveType) -> bool {
+    match param {
+        MoveType::Signer => true,
+        MoveType::Reference { to, .. } => is_signer(to),
+        _ => false,
+    }
+}
+
+/// A vector around an argument, as entered by the user
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VectorLayer {
+    Vector,
+    /// An option, entered as a vector of at most one value
+    Option,
+}
+
+/// Returns the argument type and the vectors around it (outermost first) used to parse an
+/// argument of the given type. Byte vectors are entered as hex.
+fn get_function_arg_type(
+    param: &MoveType,
+    type_args: &[MoveType],
+) -> CliTypedResult<(FunctionArgType, Vec<VectorLayer>)> {
+    let arg_type = match param {
+        MoveType::Bool => FunctionArgType::Bool,
+        MoveType::U8 => FunctionArgType::U8,
+        MoveType::U16 => FunctionArgType::U16,
+        MoveType::U32 => FunctionArgType::U32,
+        MoveType::U64 => FunctionArgType::U64,
+        MoveType::U128 => FunctionArgType::U128,
+        MoveType::U256 => FunctionArgType::U256,
+        MoveType::Address => FunctionArgType::Address,
+        MoveType::Vector { items } if matches!(items.as_ref(), MoveType::U8) => {
+            FunctionArgType::Hex
+        },
+        MoveType::Vector { items } => {
+            let (arg_type, mut vector_layers) = get_function_arg_type(items, type_args)?;
+            vector_layers.insert(0, VectorLayer::Vector);
+            return Ok((arg_type, vector_layers));
+        },
+        MoveType::GenericTypeParam { index } => {
+            let type_arg = type_args.get(*index as usize).ok_or_else(|| {
+                CliError::CommandArgumentError(format!("Missing type argument T{}", index))
+            })?;
+            return get_function_arg_type(type_arg, type_args);
+        },
+        MoveType::Struct(struct_tag) if struct_tag.address.inner() == &AccountAddress::ONE => {
+            match (struct_tag.module.as_str(), struct_tag.name.as_str()) {
+                ("string", "String") => FunctionArgType::String,
+                ("object", "Object") => FunctionArgType::Address,
+                ("option", "Option") => {
+                    let (arg_type, mut vector_layers) = get_function_arg_type(
+                        struct_tag.generic_type_params.first().ok_or_else(|| {
+                            CliError::UnexpectedError("Invalid option type".to_string())
+                        })?,
+                        type_args,
+                    )?;
+                    vector_layers.insert(0, VectorLayer::Option);
+                    return Ok((arg_type, vector_layers));
+                },
+                _ => return Err(unsupported_arg_type(param)),
+            }
+        },
+        _ => return Err(unsupported_arg_type(param)),
+    };
+    Ok((arg_type, vec![]))
+}
+
+fn unsupported_arg_type(param: &MoveType) -> CliError {
+    CliError::CommandArgumentError(format!("Unsupported argument type {}", param))
+}
+
+/// Parses a prompted argument. Vectors are entered as JSON arrays.
+fn parse_prompted_arg(
+    arg_type: &FunctionArgType,
+    vector_layers: &[VectorLayer],
+    input: &str,
+) -> CliTypedResult<ArgWithType> {
+    if vector_layers.is_empty() {
+        // Only strings keep their surrounding whitespace
+        let input = match arg_type {
+            FunctionArgType::String => input,
+            _ => input.trim(),
+        };
+        return Ok(ArgWithType {
+            _ty: arg_type.clone(),
+            _vector_depth: 0,
+            arg: arg_type.parse_arg_str(input)?,
+        });
+    }
+
+    let value: serde_json::Value = serde_json::from_str(input).map_err(|err| {
+        CliError::UnableToParse("vector argument (as a JSON array)", err.to_string())
+    })?;
+    check_vector_layers(&value, vector_layers)?;
+    arg_type.parse_arg_json(&value)
+}
+
+/// Checks that the value is nested in exactly the given vectors, and that options have at
+/// most one value. The depth can't be inferred from the value itself, e.g., `[]` is valid
+/// for any vector depth.
+fn check_vector_layers(
+    value: &serde_json::Value,
+    vector_layers: &[VectorLayer],
+) -> CliTypedResult<()> {
+    match (value.as_array(), vector_layers.split_first()) {
+        (None, None) => Ok(()),
+        (Some(values), Some((layer, inner_layers))) => {
+            if *layer == VectorLayer::Option && values.len() > 1 {
+                return Err(CliError::CommandArgumentError(
+                    "An option argument can have at most one value".to_string(),
+                ));
+            }
+            values
+                .iter()
+                .try_for_each(|value| check_vector_layers(value, inner_layers))
+        },
+        _ => Err(CliError::CommandArgumentError(
+            "Invalid vector depth of argument".to_string(),
+        )),
+    }
+}
+
+/// Prompts for a value until it's parsed successfully
+fn prompt_until_valid<T>(
+    prompt: &str,
+    parse: impl Fn(&str) -> CliTypedResult<T>,
+) -> CliTypedResult<T> {
+    loop {
+        print!("{}: ", prompt);
+        std::io::stdout()
+            .flush()
+            .map_err(|err| CliError::IO("stdout".to_string(), err))?;
+        let input = read_line("Argument")?;
+        // An empty read means the input was closed
+        if input.is_empty() {
+            return Err(CliError::AbortedError);
+        }
+        match parse(input.trim_end_matches(&['\r', '\n'][..])) {
+            Ok(value) => return Ok(value),
+            Err(err) => eprintln!("{}, please try again", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse_type(type_str: &str) -> MoveType {
+        MoveType::from_str(type_str).unwrap()
+    }
+
+    #[test]
+    fn test_get_function_arg_type() {
+        let type_args = vec![parse_type("u128")];
+        let assert_arg_type = |param: MoveType, expected: (FunctionArgType, Vec<VectorLayer>)| {
+            assert_eq!(get_function_arg_type(&param, &type_args).unwrap(), expected);
+        };
+
+        assert_arg_type(parse_type("u64"), (FunctionArgType::U64, vec![]));
+        assert_arg_type(parse_type("vector<u8>"), (FunctionArgType::Hex, vec![]));
+        assert_arg_type(
+            parse_type("vector<vector<u8>>"),
+            (FunctionArgType::Hex, vec![VectorLayer::Vector]),
+        );
+        assert_arg_type(
+            parse_type("0x1::string::String"),
+            (FunctionArgType::String, vec![]),
+        );
+        assert_arg_type(
+            parse_type("0x1::object::Object<0x1::fungible_asset::Metadata>"),
+            (FunctionArgType::Address, vec![]),
+        );
+        assert_arg_type(
+            parse_type("vector<0x1::option::Option<address>>"),
+            (
+                FunctionArgType::Address,
+                vec![VectorLayer::Vector, VectorLayer::Option],
+            ),
+        );
+        assert_arg_type(
+            MoveType::GenericTypeParam { index: 0 },
+            (FunctionArgType::U128, vec![]),
+        );
+
+        // Structs other than the supported framework types can't be entered
+        assert!(get_function_arg_type(&parse_type("0x1::coin::Coin<u64>"), &type_args).is_err());
+        assert!(
+            get_function_arg_type(&MoveType::GenericTypeParam { index: 1 }, &type_args).is_err()
+        );
+    }
+
+    #[test]
+    fn test_check_vector_layers() {
+        let vector = [VectorLayer::Vector];
+        let vector_of_options = [VectorLayer::Vector, VectorLayer::Option];
+
+        assert!(check_vector_layers(&json!(1), &[]).is_ok());
+        assert!(check_vector_layers(&json!([1, 2]), &vector).is_ok());
+        assert!(check_vector_layers(&json!([]), &vector_of_options).is_ok());
+        assert!(check_vector_layers(&json!([[], [1]]), &vector_of_options).is_ok());
+
+        // The depth must match the type
+        assert!(check_vector_layers(&json!([1]), &[]).is_err());
+        assert!(check_vector_layers(&json!(1), &vector).is_err());
+        assert!(check_vector_layers(&json!([[1]]), &vector).is_err());
+        assert!(check_vector_layers(&json!([1]), &vector_of_options).is_err());
+
+        // Options can have at most one value
+        assert!(check_vector_layers(&json!([[1, 2]]), &vector_of_options).is_err());
+        assert!(check_vector_layers(&json!([1, 2]), &vector).is_ok());
+    }
+}
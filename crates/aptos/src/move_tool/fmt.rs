@@ -0,0 +1,318 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{CliCommand, CliError, CliTypedResult},
+    utils::{dir_default_to_current, read_from_file, write_to_file},
+};
+use async_trait::async_trait;
+use clap::Parser;
+use move_package::source_package::layout::SourcePackageLayout;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The file in the package directory that configures the formatting style
+const CONFIG_FILE: &str = "movefmt.toml";
+
+/// The formatting style of a package
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FormatConfig {
+    /// Number of spaces per level of indentation
+    pub indent_size: usize,
+    /// Max number of consecutive blank lines
+    pub max_blank_lines: usize,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            indent_size: 4,
+            max_blank_lines: 1,
+        }
+    }
+}
+
+impl FormatConfig {
+    /// Loads the config of the package, or the default config if the package has none
+    pub fn load(package_dir: &Path) -> CliTypedResult<Self> {
+        let path = package_dir.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let bytes = read_from_file(&path)?;
+        let config = String::from_utf8(bytes)
+            .map_err(|err| CliError::UnableToParse(CONFIG_FILE, err.to_string()))?;
+        toml::from_str(&config).map_err(|err| CliError::UnableToParse(CONFIG_FILE, err.to_string()))
+    }
+}
+
+/// Formats the Move source files of a package
+///
+/// Only the layout of lines is changed (i.e., indentation, trailing whitespace, blank lines and
+/// the final newline), so formatting never changes the meaning of the code, and formatting a
+/// formatted file doesn't change it. Lines are indented by their nesting of brackets, and by
+/// one more level if they continue a statement of the previous line.
+///
+/// The style can be configured in a `movefmt.toml` file in the package directory, e.g.,
+/// `indent_size = 4` and `max_blank_lines = 1`.
+#[derive(Parser)]
+pub struct FmtPackage {
+    /// Path to a move package (the folder with a Move.toml file)
+    #[clap(long, value_parser)]
+    pub package_dir: Option<PathBuf>,
+
+    /// Only check that the files are formatted, and fail if they aren't (e.g., in CI)
+    #[clap(long)]
+    pub check: bool,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for FmtPackage {
+    fn command_name(&self) -> &'static str {
+        "FmtPackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        let package_dir = dir_default_to_current(self.package_dir)?;
+        let config = FormatConfig::load(&package_dir)?;
+
+        // Returns the files that are (or, in check mode, would be) changed
+        let mut changed_files = vec![];
+        for path in find_move_files(&package_dir) {
+            let bytes = read_from_file(&path)?;
+            let source = String::from_utf8(bytes).map_err(|err| {
+                CliError::UnableToParse("Move source file", format!("{}: {}", path.display(), err))
+            })?;
+            let formatted = format_move_source(&source, &config);
+            if formatted != source {
+                if !self.check {
+                    write_to_file(&path, "Move source file", formatted.as_bytes())?;
+                }
+                changed_files.push(path.display().to_string());
+            }
+        }
+
+        if self.check && !changed_files.is_empty() {
+            return Err(CliError::UnexpectedError(format!(
+                "Files are not formatted: {:?}",
+                changed_files
+            )));
+        }
+        Ok(changed_files)
+    }
+}
+
+/// Returns the Move files in the source directories of the package, in a stable order
+fn find_move_files(package_dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for layout in [
+        SourcePackageLayout::Sources,
+        SourcePackageLayout::Scripts,
+        SourcePackageLayout::Examples,
+        SourcePackageLayout::Tests,
+    ] {
+        let dir = package_dir.join(layout.path());
+        files.extend(
+            walkdir::WalkDir::new(dir)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| {
+                    entry.file_type().is_file()
+                        && entry.path().extension().map_or(false, |ext| ext == "move")
+                })
+                .map(|entry| entry.into_path()),
+        );
+    }
+    files
+}
+
+/// The state of the scanner at the start of a line
+#[derive(Clone, Copy, Default)]
+struct ScanState {
+    in_block_comment: bool,
+    in_string: bool,
+}
+
+/// The code of a line, ignoring comments and the contents of strings
+#[derive(Default)]
+struct LineCode {
+    brackets: Vec<char>,      // The brackets in the line, in order
+    last_char: Option<char>,  // The last character of code in the line
+    starts_with_closer: bool, // Whether the line starts with a closing bracket
+}
+
+/// Scans the line (which must be trimmed at the start, unless it starts in a
+/// comment or a string), and updates the scanner state for the next line.
+fn scan_line(line: &str, state: &mut ScanState) -> LineCode {
+    let mut code = LineCode {
+        starts_with_closer: !state.in_block_comment
+            && !state.in_string
+            && line.starts_with(['}', ')', ']']),
+        ..LineCode::default()
+    };
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if state.in_block_comment {
+            if c == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                state.in_block_comment = false;
+            }
+        } else if state.in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                },
+                '"' => state.in_string = false,
+                _ => {},
+            }
+        } else {
+            match c {
+                '/' if chars.peek() == Some(&'/') => break,
+                '/' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    state.in_block_comment = true;
+                    continue;
+                },
+                '"' => state.in_string = true,
+                '{' | '(' | '[' | '}' | ')' | ']' => code.brackets.push(c),
+                _ => {},
+            }
+            if !c.is_whitespace() {
+                code.last_char = Some(c);
+            }
+        }
+    }
+    code
+}
+
+/// Formats the layout of a Move source file (see `FmtPackage`)
+pub fn format_move_source(source: &str, config: &FormatConfig) -> String {
+    let mut state = ScanState::default();
+    let mut open_brackets: Vec<usize> = vec![]; // The indentation level of each open bracket
+    let mut previous_last_char: Option<char> = None;
+    let mut lines: Vec<String> = vec![];
+    let mut num_blank_lines = 0;
+
+    for line in source.lines() {
+        // Lines that start within a block comment or string are kept as is
+        if state.in_block_comment || state.in_string {
+            let line = if state.in_string {
+                line
+            } else {
+                line.trim_end()
+            };
+            let code = scan_line(line, &mut state);
+            let indent = open_brackets.last().map_or(0, |indent| indent + 1);
+            update_open_brackets(&mut open_brackets, &code, indent);
+            if code.last_char.is_some() {
+                previous_last_char = code.last_char;
+            }
+            lines.push(line.to_string());
+            num_blank_lines = 0;
+            continue;
+        }
+
+        let line = line.trim_start();
+        if line.trim_end().is_empty() {
+            num_blank_lines += 1;
+            continue;
+        }
+        if !lines.is_empty() {
+            for _ in 0..num_blank_lines.min(config.max_blank_lines) {
+                lines.push(String::new());
+            }
+        }
+        num_blank_lines = 0;
+
+        let code = scan_line(line, &mut state);
+        let mut indent = if code.starts_with_closer {
+            open_brackets.last().copied().unwrap_or(0)
+        } else {
+            open_brackets.last().map_or(0, |indent| indent + 1)
+        };
+        // A line continues the statement of the previous line, unless that ended a
+        // statement, block, list item or parenthesized expression (or opened a block or list)
+        let is_continuation = !code.starts_with_closer
+            && previous_last_char.map_or(false, |c| !";{}(),[]".contains(c));
+        if is_continuation {
+            indent += 1;
+        }
+        update_open_brackets(&mut open_brackets, &code, indent);
+        if code.last_char.is_some() {
+            previous_last_char = code.last_char;
+        }
+
+        // Strings that continue on the next line keep their trailing whitespace
+        let line = if state.in_string {
+            line
+        } else {
+            line.trim_end()
+        };
+        lines.push(format!(
+            "{}{}",
+            " ".repeat(indent * config.indent_size),
+            line
+        ));
+    }
+
+    if lines.is_empty() {
+        return String::new();
+    }
+    let mut formatted = lines.join("\n");
+    formatted.push('\n');
+    formatted
+}
+
+/// Opens and closes the brackets of the line, which has the given indentation level
+fn update_open_brackets(open_brackets: &mut Vec<usize>, code: &LineCode, indent: usize) {
+    for bracket in &code.brackets {
+        match bracket {
+            '{' | '(' | '[' => open_brackets.push(indent),
+            _ => {
+                open_brackets.pop();
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str) -> String {
+        let formatted = format_move_source(source, &FormatConfig::default());
+        // Formatting must be stable
+        assert_eq!(
+            format_move_source(&formatted, &FormatConfig::default()),
+            formatted
+        );
+        formatted
+    }
+
+    #[test]
+    fn test_format_indentation() {
+        let source = "module 0x1::m {\n\t   use std::vector;\n\n\n\n  fun f(v: vector<u64>): u64 {\nvector::for_each(v, |x| {\n        g(x);\n      });\n  let y = 1\n+ 2;\n    if (y > 2)\n abort 1;\n  y   \n}\n}";
+        let expected = "module 0x1::m {\n    use std::vector;\n\n    fun f(v: vector<u64>): u64 {\n        vector::for_each(v, |x| {\n            g(x);\n        });\n        let y = 1\n            + 2;\n        if (y > 2)\n        abort 1;\n        y\n    }\n}\n";
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_format_keeps_comments_and_strings() {
+        let source = "module 0x1::m {\n/* a\n      block { comment\n */\n  // a { comment\nconst S: vector<u8> = b\"a { \\\" string\";\n}\n";
+        let expected = "module 0x1::m {\n    /* a\n      block { comment\n */\n    // a { comment\n    const S: vector<u8> = b\"a { \\\" string\";\n}\n";
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_format_config() {
+        let config = FormatConfig {
+            indent_size: 2,
+            max_blank_lines: 0,
+        };
+        let source = "\n\nscript {\nfun main() {}\n\nfun other() {}\n}\n\n\n";
+        let expected = "script {\n  fun main() {}\n  fun other() {}\n}\n";
+        assert_eq!(format_move_source(source, &config), expected);
+    }
+}
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aptos_debug_natives;
+mod call;
 pub mod coverage;
 mod disassembler;
+mod fmt;
 mod manifest;
 pub mod package_hooks;
 mod show;
@@ -26,8 +28,10 @@ use crate::{
     },
     governance::CompileScriptFunction,
     move_tool::{
+        call::CallFunction,
         coverage::SummaryCoverage,
         disassembler::Disassemble,
+        fmt::FmtPackage,
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
     },
     CliCommand, CliResult,
@@ -79,6 +83,7 @@ use transactional_tests_runner::TransactionalTestOpts;
 #[derive(Subcommand)]
 pub enum MoveTool {
     BuildPublishPayload(BuildPublishPayload),
+    Call(CallFunction),
     Clean(CleanPackage),
     Compile(CompilePackage),
     CompileScript(CompileScript),
@@ -88,6 +93,7 @@ pub enum MoveTool {
     Disassemble(Disassemble),
     Document(DocumentPackage),
     Download(DownloadPackage),
+    Fmt(FmtPackage),
     Init(InitPackage),
     List(ListPackage),
     Prove(ProvePackage),
@@ -106,6 +112,7 @@ impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
             MoveTool::BuildPublishPayload(tool) => tool.execute_serialized().await,
+            MoveTool::Call(tool) => tool.execute_serialized().await,
             MoveTool::Clean(tool) => tool.execute_serialized().await,
             MoveTool::Compile(tool) => tool.execute_serialized().await,
             MoveTool::CompileScript(tool) => tool.execute_serialized().await,
@@ -116,6 +123,7 @@ impl MoveTool {
             MoveTool::Disassemble(tool) => tool.execute_serialized().await,
             MoveTool::Document(tool) => tool.execute_serialized().await,
             MoveTool::Download(tool) => tool.execute_serialized().await,
+            MoveTool::Fmt(tool) => tool.execute_serialized().await,
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
             MoveTool::List(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
@@ -440,6 +448,14 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    /// Write the gas used by each test, broken down by call stack, to this directory
+    ///
+    /// One collapsed stack file (`<address>-<module>-<test>.folded`) is written per test, which
+    /// can be rendered as a flamegraph, e.g., with `inferno-flamegraph`. Each function is charged
+    /// for its own instructions (and natives), in the abstract gas units of the test runner.
+    #[clap(long, value_parser)]
+    pub gas_profile_dir: Option<PathBuf>,
 }
 
 #[async_trait]
@@ -485,6 +501,7 @@ impl CliCommand<&'static str> for TestPackage {
                 report_stacktrace_on_abort: true,
                 report_storage_on_error: self.dump_state,
                 ignore_compile_warnings: self.ignore_compile_warnings,
+                gas_profile_dir: self.gas_profile_dir.clone(),
                 ..UnitTestingConfig::default_with_bound(None)
             },
             // TODO(Gas): we may want to switch to non-zero costs in the future
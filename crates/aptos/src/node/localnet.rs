@@ -0,0 +1,438 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{CliCommand, CliError, CliTypedResult, ConfigSearchMode, PromptOptions},
+        utils::prompt_yes_with_override,
+    },
+    config::GlobalConfig,
+};
+use aptos_config::config::NodeConfig;
+use aptos_faucet_core::server::{FunderKeyEnum, RunConfig};
+use aptos_rest_client::Client;
+use async_trait::async_trait;
+use clap::Parser;
+use futures::{future::BoxFuture, FutureExt};
+use rand::{rngs::StdRng, SeedableRng};
+use reqwest::Url;
+use std::{
+    fs::File,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    process::Stdio,
+    thread,
+    time::Duration,
+};
+use tokio::{
+    process::{Child, Command},
+    sync::oneshot,
+    time::Instant,
+};
+
+const LOCALNET_FOLDER: &str = "localnet";
+const LOGS_FOLDER: &str = "logs";
+const HEALTH_CHECK_INTERVAL_MS: u64 = 500;
+
+/// Run a local multi-validator network
+///
+/// This local network will run its own Genesis for the given number of validators, and run
+/// each validator as a separate process, with its logs in `<test-dir>/<index>/logs`. The
+/// first validator serves on the default ports. Optionally, a faucet and the indexer gRPC
+/// stream (served by the first validator) can be added for testing.
+///
+/// The command waits until every validator serves the REST API and the chain makes progress,
+/// and then keeps running until any of the validators (or the faucet) stops.
+#[derive(Parser)]
+pub struct RunLocalnet {
+    /// Number of validators in the network
+    #[clap(long, default_value = "4")]
+    validators: NonZeroUsize,
+
+    /// The directory to save all files for the network
+    ///
+    /// Defaults to .aptos/localnet
+    #[clap(long, value_parser)]
+    test_dir: Option<PathBuf>,
+
+    /// Random seed for key generation
+    ///
+    /// This allows you to have deterministic keys for testing
+    #[clap(long, value_parser = aptos_node::load_seed)]
+    seed: Option<[u8; 32]>,
+
+    /// Clean the state and start with a new chain at genesis
+    ///
+    /// This will wipe `test-dir` to remove any incompatible changes (e.g., a different
+    /// number of validators), and start the chain fresh.
+    #[clap(long)]
+    force_restart: bool,
+
+    /// Run a faucet alongside the network
+    ///
+    /// The faucet mints through the REST API of the first validator
+    #[clap(long)]
+    with_faucet: bool,
+
+    /// Port to run the faucet on
+    #[clap(long, default_value = "8081")]
+    faucet_port: u16,
+
+    /// Disable the delegation of faucet minting to a dedicated account
+    #[clap(long)]
+    do_not_delegate: bool,
+
+    /// Serve the indexer gRPC stream from the first validator
+    ///
+    /// This is only used when the network is created (i.e., at genesis)
+    #[clap(long)]
+    with_indexer_grpc: bool,
+
+    /// Port to serve the indexer gRPC stream on
+    #[clap(long, default_value = "50051")]
+    indexer_grpc_port: u16,
+
+    /// Max number of seconds to wait for the network to become healthy
+    #[clap(long, default_value = "120")]
+    startup_timeout_secs: u64,
+
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+}
+
+/// A validator process of the local network
+struct LocalnetValidator {
+    index: usize,
+    rest_url: Url,
+    log_dir: PathBuf,
+    process: Child,
+}
+
+#[async_trait]
+impl CliCommand<()> for RunLocalnet {
+    fn command_name(&self) -> &'static str {
+        "RunLocalnet"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let rng = self
+            .seed
+            .map(StdRng::from_seed)
+            .unwrap_or_else(StdRng::from_entropy);
+
+        let global_config = GlobalConfig::load()?;
+        let test_dir = match self.test_dir {
+            Some(test_dir) => test_dir,
+            None => global_config
+                .get_config_location(ConfigSearchMode::CurrentDirAndParents)?
+                .join(LOCALNET_FOLDER),
+        };
+
+        // Remove the current test directory and start with a new network
+        if self.force_restart && test_dir.exists() {
+            prompt_yes_with_override(
+                "Are you sure you want to delete the existing chain?",
+                self.prompt_options,
+            )?;
+            std::fs::remove_dir_all(test_dir.as_path()).map_err(|err| {
+                CliError::IO(format!("Failed to delete {}", test_dir.display()), err)
+            })?;
+        }
+        std::fs::create_dir_all(test_dir.as_path())
+            .map_err(|err| CliError::IO(format!("Failed to create {}", test_dir.display()), err))?;
+        let test_dir = test_dir.canonicalize().map_err(|err| {
+            CliError::IO(format!("Failed to resolve {}", test_dir.display()), err)
+        })?;
+
+        // Build genesis and the validator configs (or load the existing ones)
+        let num_validators = self.validators;
+        let indexer_grpc_port = self.with_indexer_grpc.then_some(self.indexer_grpc_port);
+        let network_dir = test_dir.clone();
+        let config_paths = tokio::task::spawn_blocking(move || {
+            aptos_node::setup_test_network(
+                &network_dir,
+                num_validators,
+                indexer_grpc_port,
+                aptos_cached_packages::head_release_bundle(),
+                rng,
+            )
+        })
+        .await
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to build genesis: {}", err)))?
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to build genesis: {:#}", err)))?;
+
+        // Start each validator in its own process
+        let mut validators = vec![];
+        for (index, config_path) in config_paths.iter().enumerate() {
+            validators.push(start_validator(index, config_path)?);
+        }
+
+        // Wait for the network to become healthy
+        wait_for_healthy_network(&validators, Duration::from_secs(self.startup_timeout_secs))
+            .await?;
+
+        println!("Completed starting the local network:");
+        println!("\tTest dir: {:?}", test_dir);
+        println!("\tAptos root key path: {:?}", test_dir.join("mint.key"));
+        for validator in &validators {
+            println!(
+                "\tValidator {}: REST API endpoint: {}, Log dir: {:?}",
+                validator.index, validator.rest_url, validator.log_dir
+            );
+        }
+        if let Some(indexer_grpc_port) = indexer_grpc_port {
+            println!("\tIndexer gRPC endpoint: 0.0.0.0:{}", indexer_grpc_port);
+        }
+
+        // Collect futures that should never end
+        let mut futures: Vec<BoxFuture<'static, ()>> = vec![];
+
+        // Run faucet if selected
+        if self.with_faucet {
+            let faucet_config = RunConfig::build_for_cli(
+                validators[0].rest_url.clone(),
+                self.faucet_port,
+                FunderKeyEnum::KeyFile(test_dir.join("mint.key")),
+                self.do_not_delegate,
+                None,
+            );
+            println!("\tFaucet endpoint: http://localhost:{}", self.faucet_port);
+            futures.push(
+                faucet_config
+                    .run()
+                    .map(|result| {
+                        eprintln!("Faucet stopped unexpectedly {:#?}", result);
+                    })
+                    .boxed(),
+            );
+        }
+        println!("\nThe local network is running, press ctrl-c to exit\n");
+
+        // Each of these futures waits for a validator process (which is killed on drop)
+        for validator in validators {
+            let LocalnetValidator {
+                index,
+                log_dir,
+                mut process,
+                ..
+            } = validator;
+            futures.push(
+                async move {
+                    let status = process.wait().await;
+                    eprintln!(
+                        "Validator {} stopped unexpectedly {:?}. See the logs in {:?}",
+                        index, status, log_dir
+                    );
+                }
+                .boxed(),
+            );
+        }
+
+        // Wait for all the futures. We should never get past this point unless
+        // something goes wrong or the user signals for the process to end.
+        futures::future::select_all(futures).await;
+
+        Err(CliError::UnexpectedError(
+            "One of the components stopped unexpectedly".to_string(),
+        ))
+    }
+}
+
+/// Starts the validator with the given config in a separate process (i.e., by running
+/// the CLI itself with the hidden `run-localnet-validator` command).
+fn start_validator(index: usize, config_path: &Path) -> CliTypedResult<LocalnetValidator> {
+    let config = NodeConfig::load_from_path(config_path).map_err(|err| {
+        CliError::UnexpectedError(format!(
+            "Failed to load the config of validator {}: {}",
+            index, err
+        ))
+    })?;
+    let rest_url =
+        Url::parse(&format!("http://localhost:{}", config.api.address.port())).map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to parse localhost URL {}", err))
+        })?;
+
+    // Each validator has its own log directory, which also captures its console output
+    let node_dir = config_path
+        .parent()
+        .ok_or_else(|| CliError::UnexpectedError("Invalid validator config path".to_string()))?;
+    let log_dir = node_dir.join(LOGS_FOLDER);
+    std::fs::create_dir_all(&log_dir)
+        .map_err(|err| CliError::IO(format!("Failed to create {}", log_dir.display()), err))?;
+    let console_log_path = log_dir.join("console.log");
+    let console_log = File::create(&console_log_path).map_err(|err| {
+        CliError::IO(
+            format!("Failed to create {}", console_log_path.display()),
+            err,
+        )
+    })?;
+    let console_log_copy = console_log
+        .try_clone()
+        .map_err(|err| CliError::IO("Failed to open the console log".to_string(), err))?;
+
+    let cli_path = std::env::current_exe()
+        .map_err(|err| CliError::IO("Failed to find the CLI executable".to_string(), err))?;
+    let process = Command::new(cli_path)
+        .args(["node", "run-localnet-validator", "--config-path"])
+        .arg(config_path)
+        .arg("--log-dir")
+        .arg(&log_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::from(console_log))
+        .stderr(Stdio::from(console_log_copy))
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| CliError::IO(format!("Failed to start validator {}", index), err))?;
+
+    Ok(LocalnetValidator {
+        index,
+        rest_url,
+        log_dir,
+        process,
+    })
+}
+
+/// Waits until every validator serves the REST API, and the chain makes progress
+/// (i.e., every validator has committed a block after genesis).
+async fn wait_for_healthy_network(
+    validators: &[LocalnetValidator],
+    timeout: Duration,
+) -> CliTypedResult<()> {
+    let start = Instant::now();
+    for validator in validators {
+        wait_for_healthy_validator(
+            validator.index,
+            &validator.rest_url,
+            &validator.log_dir,
+            start,
+            timeout,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Waits until the validator serves the REST API and has committed a block after
+/// genesis, or until the timeout (measured from the given start) has elapsed.
+async fn wait_for_healthy_validator(
+    index: usize,
+    rest_url: &Url,
+    log_dir: &Path,
+    start: Instant,
+    timeout: Duration,
+) -> CliTypedResult<()> {
+    let rest_client = Client::new(rest_url.clone());
+    loop {
+        if let Ok(state) = rest_client.get_ledger_information().await {
+            if state.inner().block_height > 0 {
+                return Ok(());
+            }
+        }
+        if start.elapsed() > timeout {
+            return Err(CliError::UnexpectedError(format!(
+                "Validator {} at {} did not become healthy within {} seconds. See the logs in {:?}",
+                index,
+                rest_url,
+                timeout.as_secs(),
+                log_dir
+            )));
+        }
+        tokio::time::sleep(Duration::from_millis(HEALTH_CHECK_INTERVAL_MS)).await;
+    }
+}
+
+/// Run a single validator of a local network
+///
+/// This is used by `run-localnet` to run each validator in a separate process.
+#[derive(Parser)]
+pub struct RunLocalnetValidator {
+    /// Path to the config of the validator
+    #[clap(long, value_parser)]
+    config_path: PathBuf,
+
+    /// The directory to write the validator's logs to
+    #[clap(long, value_parser)]
+    log_dir: PathBuf,
+}
+
+#[async_trait]
+impl CliCommand<()> for RunLocalnetValidator {
+    fn command_name(&self) -> &'static str {
+        "RunLocalnetValidator"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let config = NodeConfig::load_from_path(&self.config_path).map_err(|err| {
+            CliError::UnexpectedError(format!(
+                "Failed to load the validator config {}: {}",
+                self.config_path.display(),
+                err
+            ))
+        })?;
+        let log_file = self.log_dir.join("validator.log");
+
+        // The node creates its own runtimes, so it must run in a separate thread.
+        // The thread reports the result when the node stops (the sender is dropped
+        // without a result if the node panics).
+        let (result_sender, result_receiver) = oneshot::channel();
+        thread::spawn(move || {
+            let _ = result_sender.send(aptos_node::start(config, Some(log_file), false));
+        });
+        let result = result_receiver
+            .await
+            .map_err(|_| CliError::UnexpectedError("The validator panicked".to_string()))?;
+        result.map_err(|err| {
+            CliError::UnexpectedError(format!("Validator stopped unexpectedly {:#}", err))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{common::types::CliResult, Tool};
+
+    #[tokio::test]
+    async fn test_run_localnet_args() {
+        // Verify that the network needs at least one validator
+        let args = &["aptos", "node", "run-localnet", "--validators", "0"];
+        assert!(Tool::try_parse_from(args).is_err());
+
+        // Verify that a validator with a missing config fails to start
+        let args = &[
+            "aptos",
+            "node",
+            "run-localnet-validator",
+            "--config-path",
+            "/missing/node.yaml",
+            "--log-dir",
+            "/missing/logs",
+        ];
+        let error_message = run_tool_with_args(args).await.unwrap_err();
+        assert!(error_message.contains("Failed to load the validator config"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_healthy_validator_timeout() {
+        // Verify that an unreachable validator times out with a pointer to its logs
+        let rest_url = Url::parse("http://localhost:1").unwrap();
+        let log_dir = PathBuf::from("/localnet/0/logs");
+        let error = wait_for_healthy_validator(
+            0,
+            &rest_url,
+            &log_dir,
+            Instant::now(),
+            Duration::from_millis(HEALTH_CHECK_INTERVAL_MS),
+        )
+        .await
+        .unwrap_err();
+        let error_message = error.to_string();
+        assert!(error_message.contains("Validator 0 at http://localhost:1/ did not become healthy"));
+        assert!(error_message.contains("/localnet/0/logs"));
+    }
+
+    async fn run_tool_with_args(args: &[&str]) -> CliResult {
+        let tool: Tool = Tool::try_parse_from(args).map_err(|msg| msg.to_string())?;
+        tool.execute().await
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod analyze;
+pub mod localnet;
 
 use crate::{
     common::{
@@ -14,9 +15,12 @@ use crate::{
     },
     config::GlobalConfig,
     genesis::git::from_yaml,
-    node::analyze::{
-        analyze_validators::{AnalyzeValidators, ValidatorStats},
-        fetch_metadata::FetchMetadata,
+    node::{
+        analyze::{
+            analyze_validators::{AnalyzeValidators, ValidatorStats},
+            fetch_metadata::FetchMetadata,
+        },
+        localnet::{RunLocalnet, RunLocalnetValidator},
     },
 };
 use aptos_backup_cli::{
@@ -49,7 +53,7 @@ use aptos_types::{
 use async_trait::async_trait;
 use bcs::Result;
 use chrono::{DateTime, NaiveDateTime, Utc};
-use clap::Parser;
+use clap::{ArgGroup, Parser};
 use futures::FutureExt;
 use rand::{rngs::StdRng, SeedableRng};
 use reqwest::Url;
@@ -72,7 +76,9 @@ const SECS_TO_MICROSECS: u64 = 1_000_000;
 /// identify issues with nodes, and show related information.
 #[derive(Parser)]
 pub enum NodeTool {
+    ActivateStandbyConsensusKey(ActivateStandbyConsensusKey),
     AnalyzeValidatorPerformance(AnalyzeValidatorPerformance),
+    AnnounceMaintenance(AnnounceMaintenance),
     BootstrapDb(BootstrapDb),
     CheckNetworkConnectivity(CheckNetworkConnectivity),
     GetPerformance(GetPerformance),
@@ -80,11 +86,16 @@ pub enum NodeTool {
     InitializeValidator(InitializeValidator),
     JoinValidatorSet(JoinValidatorSet),
     LeaveValidatorSet(LeaveValidatorSet),
+    SetStandbyConsensusKey(SetStandbyConsensusKey),
     ShowEpochInfo(ShowEpochInfo),
     ShowValidatorConfig(ShowValidatorConfig),
     ShowValidatorSet(ShowValidatorSet),
     ShowValidatorStake(ShowValidatorStake),
+    RunLocalnet(RunLocalnet),
+    #[clap(hide = true)]
+    RunLocalnetValidator(RunLocalnetValidator),
     RunLocalTestnet(RunLocalTestnet),
+    TimeTravel(TimeTravel),
     UpdateConsensusKey(UpdateConsensusKey),
     UpdateValidatorNetworkAddresses(UpdateValidatorNetworkAddresses),
 }
@@ -93,7 +104,9 @@ impl NodeTool {
     pub async fn execute(self) -> CliResult {
         use NodeTool::*;
         match self {
+            ActivateStandbyConsensusKey(tool) => tool.execute_serialized().await,
             AnalyzeValidatorPerformance(tool) => tool.execute_serialized().await,
+            AnnounceMaintenance(tool) => tool.execute_serialized().await,
             BootstrapDb(tool) => {
                 tool.execute_serialized_with_logging_level(Level::Info)
                     .await
@@ -104,11 +117,15 @@ impl NodeTool {
             InitializeValidator(tool) => tool.execute_serialized().await,
             JoinValidatorSet(tool) => tool.execute_serialized().await,
             LeaveValidatorSet(tool) => tool.execute_serialized().await,
+            SetStandbyConsensusKey(tool) => tool.execute_serialized().await,
             ShowEpochInfo(tool) => tool.execute_serialized().await,
             ShowValidatorSet(tool) => tool.execute_serialized().await,
             ShowValidatorStake(tool) => tool.execute_serialized().await,
             ShowValidatorConfig(tool) => tool.execute_serialized().await,
+            RunLocalnet(tool) => tool.execute_serialized_without_logger().await,
+            RunLocalnetValidator(tool) => tool.execute_serialized_without_logger().await,
             RunLocalTestnet(tool) => tool.execute_serialized_without_logger().await,
+            TimeTravel(tool) => tool.execute_serialized().await,
             UpdateConsensusKey(tool) => tool.execute_serialized().await,
             UpdateValidatorNetworkAddresses(tool) => tool.execute_serialized().await,
         }
@@ -1240,6 +1257,105 @@ impl CliCommand<()> for RunLocalTestnet {
     }
 }
 
+/// Move a local testnet through time
+///
+/// Advances the timestamp of a local testnet (started with `aptos node run-local-testnet`),
+/// forces an epoch change, or mines empty blocks on demand. This allows testing time-locked
+/// and epoch-dependent contracts without waiting in real time. Note that the chain can only
+/// move forward in time, and that transactions must expire relative to the chain's time.
+#[derive(Parser)]
+#[clap(group(
+    ArgGroup::new("action")
+        .required(true)
+        .args(&["advance_secs", "force_epoch_change", "mine_blocks"]),
+))]
+pub struct TimeTravel {
+    /// Number of seconds to move the chain's timestamp forward by
+    #[clap(long)]
+    advance_secs: Option<u64>,
+
+    /// Move the chain's timestamp to the end of the current epoch, starting a new epoch
+    #[clap(long)]
+    force_epoch_change: bool,
+
+    /// Number of empty blocks to mine
+    #[clap(long)]
+    mine_blocks: Option<u64>,
+
+    /// URL of the local testnet's inspection service, which serves the time travel commands
+    #[clap(long, default_value = "http://127.0.0.1:9101")]
+    admin_url: Url,
+
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+}
+
+#[async_trait]
+impl CliCommand<String> for TimeTravel {
+    fn command_name(&self) -> &'static str {
+        "TimeTravel"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        let client = self.rest_options.client(&self.profile_options)?;
+        let ledger_info_before = client.get_ledger_information().await?.into_inner();
+
+        // Send the admin command to the local testnet
+        let command = if let Some(advance_secs) = self.advance_secs {
+            format!("advance_time?seconds={}", advance_secs)
+        } else if self.force_epoch_change {
+            "force_epoch_change".to_string()
+        } else {
+            format!("mine_blocks?num_blocks={}", self.mine_blocks.unwrap_or(1))
+        };
+        let command_url = self
+            .admin_url
+            .join("admin/")
+            .and_then(|url| url.join(&command));
+        let command_url = command_url.map_err(|err| {
+            CliError::CommandArgumentError(format!("Invalid admin URL {}: {}", self.admin_url, err))
+        })?;
+        let response = reqwest::Client::new()
+            .post(command_url)
+            .send()
+            .await
+            .map_err(|err| {
+                CliError::ApiError(format!("Failed to send the time travel command: {}", err))
+            })?;
+        let status = response.status();
+        let response = response.text().await.map_err(|err| {
+            CliError::ApiError(format!("Failed to read the time travel response: {}", err))
+        })?;
+        if !status.is_success() {
+            return Err(CliError::ApiError(format!(
+                "Time travel command failed ({}): {}",
+                status, response
+            )));
+        }
+
+        // Wait until the mined blocks are committed, so that they are visible to the REST API
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(MAX_WAIT_S) {
+            let ledger_info = client.get_ledger_information().await?.into_inner();
+            let committed = if self.force_epoch_change {
+                ledger_info.epoch > ledger_info_before.epoch
+            } else {
+                ledger_info.timestamp_usecs > ledger_info_before.timestamp_usecs
+            };
+            if committed {
+                return Ok(response);
+            }
+            tokio::time::sleep(Duration::from_millis(WAIT_INTERVAL_MS)).await;
+        }
+        Err(CliError::UnexpectedError(format!(
+            "The chain didn't commit the time travel in time: {}",
+            response
+        )))
+    }
+}
+
 /// Update consensus key for the validator node
 ///
 /// This will take effect in the next epoch
@@ -1284,6 +1400,127 @@ impl CliCommand<TransactionSummary> for UpdateConsensusKey {
     }
 }
 
+/// Pre-register a standby consensus key for the validator node
+///
+/// The standby key can later be switched to with `aptos node activate-standby-consensus-key`,
+/// e.g. if the current consensus key is suspected to be compromised. The private key should
+/// already be provided to the node as `standby_consensus_private_key` in its identity file.
+#[derive(Parser)]
+pub struct SetStandbyConsensusKey {
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+    #[clap(flatten)]
+    pub(crate) operator_args: OperatorArgs,
+
+    /// Hex encoded standby consensus public key
+    ///
+    /// The key should be a BLS12-381 public key
+    #[clap(long, value_parser = bls12381::PublicKey::from_encoded_string)]
+    pub(crate) standby_consensus_public_key: bls12381::PublicKey,
+
+    /// Hex encoded standby consensus proof of possession
+    ///
+    /// The key should be a BLS12-381 proof of possession
+    #[clap(long, value_parser = bls12381::ProofOfPossession::from_encoded_string)]
+    pub(crate) standby_proof_of_possession: bls12381::ProofOfPossession,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for SetStandbyConsensusKey {
+    fn command_name(&self) -> &'static str {
+        "SetStandbyConsensusKey"
+    }
+
+    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+        let address = self
+            .operator_args
+            .address_fallback_to_txn(&self.txn_options)?;
+
+        self.txn_options
+            .submit_transaction(aptos_stdlib::stake_set_standby_consensus_key(
+                address,
+                self.standby_consensus_public_key.to_bytes().to_vec(),
+                self.standby_proof_of_possession.to_bytes().to_vec(),
+            ))
+            .await
+            .map(|inner| inner.into())
+    }
+}
+
+/// Switch the validator node to its pre-registered standby consensus key
+///
+/// This will take effect in the next epoch, at which point the node switches to the
+/// standby key without requiring a restart
+#[derive(Parser)]
+pub struct ActivateStandbyConsensusKey {
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+    #[clap(flatten)]
+    pub(crate) operator_args: OperatorArgs,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ActivateStandbyConsensusKey {
+    fn command_name(&self) -> &'static str {
+        "ActivateStandbyConsensusKey"
+    }
+
+    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+        let address = self
+            .operator_args
+            .address_fallback_to_txn(&self.txn_options)?;
+
+        self.txn_options
+            .submit_transaction(aptos_stdlib::stake_activate_standby_consensus_key(address))
+            .await
+            .map(|inner| inner.into())
+    }
+}
+
+/// Announce a maintenance window of the validator in the next epoch
+///
+/// The other validators don't elect the validator as the leader during the window (e.g., while
+/// it restarts for an upgrade), so that its rounds don't time out. The rounds of each epoch start
+/// at 1, and a new window replaces the one previously announced for the same epoch.
+#[derive(Parser)]
+pub struct AnnounceMaintenance {
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+    #[clap(flatten)]
+    pub(crate) operator_args: OperatorArgs,
+    /// First round of the maintenance window in the next epoch
+    #[clap(long)]
+    pub(crate) start_round: u64,
+    /// Number of rounds in the maintenance window
+    #[clap(long)]
+    pub(crate) num_rounds: u64,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for AnnounceMaintenance {
+    fn command_name(&self) -> &'static str {
+        "AnnounceMaintenance"
+    }
+
+    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+        let address = self
+            .operator_args
+            .address_fallback_to_txn(&self.txn_options)?;
+        let client = self.txn_options.rest_client()?;
+        let next_epoch = get_epoch_info(&client).await?.epoch + 1;
+
+        self.txn_options
+            .submit_transaction(aptos_stdlib::maintenance_schedule_announce(
+                address,
+                next_epoch,
+                self.start_round,
+                self.num_rounds,
+            ))
+            .await
+            .map(|inner| inner.into())
+    }
+}
+
 /// Update the current validator's network and fullnode network addresses
 ///
 /// This will take effect in the next epoch
@@ -22,8 +22,9 @@ use crate::{
         utils::write_to_file,
     },
     governance::{
-        CompileScriptFunction, ProposalSubmissionSummary, SubmitProposal, SubmitProposalArgs,
-        SubmitVote, SubmitVoteArgs, VerifyProposal, VerifyProposalResponse,
+        CompileScriptFunction, ProposalSimulationSummary, ProposalSubmissionSummary,
+        SimulateProposal, SubmitProposal, SubmitProposalArgs, SubmitVote, SubmitVoteArgs,
+        VerifyProposal, VerifyProposalResponse,
     },
     move_tool::{
         ArgWithType, CompilePackage, DownloadPackage, FrameworkPackageArgs, IncludedArtifacts,
@@ -547,6 +548,8 @@ impl CliTestFramework {
             prompt_options: PromptOptions::yes(),
             encoding_options: EncodingOptions::default(),
             skip_faucet: false,
+            ledger: false,
+            derivation_path: None,
         }
         .execute()
         .await
@@ -1201,6 +1204,31 @@ impl CliTestFramework {
         .execute()
         .await
     }
+
+    pub async fn simulate_proposal(
+        &self,
+        script_path: &str,
+        is_multi_step: bool,
+    ) -> CliTypedResult<ProposalSimulationSummary> {
+        SimulateProposal {
+            is_multi_step,
+            rest_options: self.rest_options(),
+            profile_options: Default::default(),
+            prompt_options: PromptOptions::yes(),
+            compile_proposal_args: CompileScriptFunction {
+                script_path: Some(script_path.parse().unwrap()),
+                compiled_script_path: None,
+                framework_package_args: FrameworkPackageArgs {
+                    framework_git_rev: None,
+                    framework_local_dir: Some(Self::aptos_framework_dir()),
+                    skip_fetch_latest_git_deps: false,
+                },
+                bytecode_version: None,
+            },
+        }
+        .execute()
+        .await
+    }
 }
 
 // ValidatorConfig/ValidatorSet doesn't match Move ValidatorSet struct,
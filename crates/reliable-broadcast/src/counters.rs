@@ -0,0 +1,22 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_metrics_core::{register_int_counter_vec, IntCounterVec};
+use once_cell::sync::Lazy;
+
+/// The RPC received a valid ack
+pub const ACK_LABEL: &str = "ack";
+/// The RPC response could not be converted into an ack
+pub const INVALID_ACK_LABEL: &str = "invalid_ack";
+/// The RPC failed and will be retried
+pub const ERROR_LABEL: &str = "error";
+
+/// Count of the reliable broadcast RPCs sent, by result
+pub static RB_RPC_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_reliable_broadcast_rpc_count",
+        "Count of the reliable broadcast RPCs sent, by result",
+        &["result"]
+    )
+    .unwrap()
+});
@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_consensus_types::common::Author;
+use aptos_infallible::Mutex;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use async_trait::async_trait;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
+mod counters;
+
 pub trait RBMessage: Send + Sync + Clone {}
 
 #[async_trait]
@@ -27,11 +30,40 @@ pub trait BroadcastStatus<M: RBMessage> {
     fn add(&mut self, peer: Author, ack: Self::Ack) -> anyhow::Result<Option<Self::Aggregated>>;
 }
 
+/// The reliable broadcast RPC counters for a single peer
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PeerBroadcastStats {
+    /// The number of RPCs sent to the peer (including retries)
+    pub attempts: u64,
+    /// The number of valid acks received from the peer
+    pub acks: u64,
+    /// The number of RPCs to the peer that failed and were retried
+    pub failures: u64,
+}
+
+/// Tracks the reliable broadcast RPCs per peer, to help diagnose dissemination bottlenecks
+#[derive(Default)]
+pub struct BroadcastStats {
+    peers: Mutex<HashMap<Author, PeerBroadcastStats>>,
+}
+
+impl BroadcastStats {
+    fn update(&self, peer: Author, update: impl FnOnce(&mut PeerBroadcastStats)) {
+        update(self.peers.lock().entry(peer).or_default());
+    }
+
+    /// Returns a snapshot of the counters of all peers
+    pub fn peer_stats(&self) -> HashMap<Author, PeerBroadcastStats> {
+        self.peers.lock().clone()
+    }
+}
+
 pub struct ReliableBroadcast<M: RBMessage, TBackoff> {
     validators: Vec<Author>,
     network_sender: Arc<dyn RBNetworkSender<M>>,
     backoff_policy: TBackoff,
     time_service: TimeService,
+    stats: Arc<BroadcastStats>,
 }
 
 impl<M, TBackoff> ReliableBroadcast<M, TBackoff>
@@ -50,9 +82,15 @@ where
             network_sender,
             backoff_policy,
             time_service,
+            stats: Arc::new(BroadcastStats::default()),
         }
     }
 
+    /// Returns the per-peer RPC stats of all broadcasts sent by this instance
+    pub fn stats(&self) -> Arc<BroadcastStats> {
+        self.stats.clone()
+    }
+
     pub fn broadcast<S: BroadcastStatus<M>>(
         &self,
         message: S::Message,
@@ -61,6 +99,7 @@ where
         let receivers: Vec<_> = self.validators.clone();
         let network_sender = self.network_sender.clone();
         let time_service = self.time_service.clone();
+        let stats = self.stats.clone();
         let mut backoff_policies: HashMap<Author, TBackoff> = self
             .validators
             .iter()
@@ -72,10 +111,12 @@ where
             let send_message = |receiver, message, sleep_duration: Option<Duration>| {
                 let network_sender = network_sender.clone();
                 let time_service = time_service.clone();
+                let stats = stats.clone();
                 async move {
                     if let Some(duration) = sleep_duration {
                         time_service.sleep(duration).await;
                     }
+                    stats.update(receiver, |peer_stats| peer_stats.attempts += 1);
                     (
                         receiver,
                         network_sender
@@ -92,12 +133,24 @@ where
                 match result {
                     Ok(msg) => {
                         if let Ok(ack) = msg.try_into() {
+                            counters::RB_RPC_COUNT
+                                .with_label_values(&[counters::ACK_LABEL])
+                                .inc();
+                            stats.update(receiver, |peer_stats| peer_stats.acks += 1);
                             if let Ok(Some(aggregated)) = aggregating.add(receiver, ack) {
                                 return aggregated;
                             }
+                        } else {
+                            counters::RB_RPC_COUNT
+                                .with_label_values(&[counters::INVALID_ACK_LABEL])
+                                .inc();
                         }
                     },
                     Err(_) => {
+                        counters::RB_RPC_COUNT
+                            .with_label_values(&[counters::ERROR_LABEL])
+                            .inc();
+                        stats.update(receiver, |peer_stats| peer_stats.failures += 1);
                         let backoff_strategy = backoff_policies
                             .get_mut(&receiver)
                             .expect("should be present");
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BroadcastStatus, RBMessage, RBNetworkSender, ReliableBroadcast};
+use crate::{BroadcastStatus, PeerBroadcastStats, RBMessage, RBNetworkSender, ReliableBroadcast};
 use anyhow::bail;
 use aptos_consensus_types::common::Author;
 use aptos_enum_conversion_derive::EnumConversion;
@@ -131,6 +131,44 @@ async fn test_reliable_broadcast() {
     assert_eq!(fut.await, validators.into_iter().collect());
 }
 
+#[tokio::test]
+async fn test_reliable_broadcast_stats() {
+    let (_, validator_verifier) = random_validator_verifier(5, None, false);
+    let validators = validator_verifier.get_ordered_account_addresses();
+    let failures = HashMap::from([(validators[0], 1), (validators[2], 3)]);
+    let sender = Arc::new(TestRBSender::<TestRBMessage>::new(failures));
+    let rb = ReliableBroadcast::new(
+        validators.clone(),
+        sender,
+        FixedInterval::from_millis(10),
+        TimeService::real(),
+    );
+    let message = TestMessage(vec![42; validators.len()]);
+    let aggregating = TestBroadcastStatus {
+        threshold: validators.len(),
+        received: HashSet::new(),
+    };
+    rb.broadcast(message, aggregating).await;
+
+    let peer_stats = rb.stats().peer_stats();
+    assert_eq!(peer_stats.len(), validators.len());
+    assert_eq!(peer_stats[&validators[0]], PeerBroadcastStats {
+        attempts: 2,
+        acks: 1,
+        failures: 1,
+    });
+    assert_eq!(peer_stats[&validators[1]], PeerBroadcastStats {
+        attempts: 1,
+        acks: 1,
+        failures: 0,
+    });
+    assert_eq!(peer_stats[&validators[2]], PeerBroadcastStats {
+        attempts: 4,
+        acks: 1,
+        failures: 3,
+    });
+}
+
 #[tokio::test]
 async fn test_chaining_reliable_broadcast() {
     let (_, validator_verifier) = random_validator_verifier(5, None, false);
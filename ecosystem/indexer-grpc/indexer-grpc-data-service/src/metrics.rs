@@ -27,6 +27,26 @@ pub static PROCESSED_VERSIONS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Number of transactions served by data service, by where the data was read from.
+pub static PROCESSED_VERSIONS_COUNT_BY_SOURCE: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_grpc_data_service_processed_versions_by_source",
+        "Number of transactions that have been processed by data service, by data source",
+        &["processor_name", "data_source"],
+    )
+    .unwrap()
+});
+
+/// Moving average of transactions per second served to the streams of each processor.
+pub static PROCESSED_TPS: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "indexer_grpc_data_service_processed_tps",
+        "Transactions per second served by data service to the processor",
+        &["processor_name"],
+    )
+    .unwrap()
+});
+
 /// Number of errors that data service has encountered.
 pub static ERROR_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
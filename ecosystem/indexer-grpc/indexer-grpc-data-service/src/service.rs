@@ -3,8 +3,8 @@
 
 use crate::metrics::{
     CONNECTION_COUNT, ERROR_COUNT, LATEST_PROCESSED_VERSION, PROCESSED_BATCH_SIZE,
-    PROCESSED_LATENCY_IN_SECS, PROCESSED_LATENCY_IN_SECS_ALL, PROCESSED_VERSIONS_COUNT,
-    SHORT_CONNECTION_COUNT,
+    PROCESSED_LATENCY_IN_SECS, PROCESSED_LATENCY_IN_SECS_ALL, PROCESSED_TPS,
+    PROCESSED_VERSIONS_COUNT, PROCESSED_VERSIONS_COUNT_BY_SOURCE, SHORT_CONNECTION_COUNT,
 };
 use aptos_indexer_grpc_utils::{
     build_protobuf_encoded_transaction_wrappers,
@@ -58,6 +58,10 @@ const RESPONSE_CHANNEL_SEND_TIMEOUT: Duration = Duration::from_secs(120);
 
 const SHORT_CONNECTION_DURATION_IN_SECS: u64 = 10;
 
+// Number of transactions files fetched concurrently when serving data evicted from the cache, so
+// that streams starting at old versions are not bound by the latency of a single file download.
+const FILE_STORE_CONCURRENT_FETCH_COUNT: u64 = 5;
+
 pub struct RawDataServerWrapper {
     pub redis_client: Arc<redis::Client>,
     pub file_store_config: IndexerGrpcFileStoreConfig,
@@ -81,10 +85,26 @@ impl RawDataServerWrapper {
     }
 }
 
+/// Where the data served to the client is read from.
+#[derive(Clone, Copy, Debug)]
+enum DataSource {
+    Cache,
+    FileStore,
+}
+
+impl DataSource {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DataSource::Cache => "cache",
+            DataSource::FileStore => "file_store",
+        }
+    }
+}
+
 /// Enum to represent the status of the data fetching overall.
 enum TransactionsDataStatus {
     // Data fetching is successful.
-    Success(Vec<EncodedTransactionWithVersion>),
+    Success(Vec<EncodedTransactionWithVersion>, DataSource),
     // Ahead of current head of cache.
     AheadOfCache,
     // Fatal error when gap detected between cache and file store.
@@ -99,7 +119,9 @@ impl RawData for RawDataServerWrapper {
     /// GetTransactionsStream is a streaming GRPC endpoint:
     /// 1. Fetches data from cache and file store.
     ///    1.1. If the data is beyond the current head of cache, retry after a short sleep.
-    ///    1.2. If the data is not in cache, fetch the data from file store.
+    ///    1.2. If the data is not in cache, fetch the data from file store. Several files are
+    ///         fetched at once so that streams starting at old versions catch up quickly, and the
+    ///         stream switches back to the cache once it reaches the cached versions.
     ///    1.3. If the data is not in file store, stream connection will break.
     ///    1.4  If error happens, retry after a short sleep.
     /// 2. Push data into channel to stream to the client.
@@ -220,14 +242,16 @@ impl RawData for RawDataServerWrapper {
 
                 loop {
                     // 1. Fetch data from cache and file store.
-                    let mut transaction_data = match data_fetch(
+                    let (mut transaction_data, data_source) = match data_fetch(
                         current_version,
                         &mut cache_operator,
                         file_store_operator.as_ref(),
                     )
                     .await
                     {
-                        Ok(TransactionsDataStatus::Success(transactions)) => transactions,
+                        Ok(TransactionsDataStatus::Success(transactions, data_source)) => {
+                            (transactions, data_source)
+                        },
                         Ok(TransactionsDataStatus::AheadOfCache) => {
                             ahead_of_cache_data_handling().await;
                             // Retry after a short sleep.
@@ -294,6 +318,12 @@ impl RawData for RawDataServerWrapper {
                                     request_metadata.request_name.as_str(),
                                 ])
                                 .inc_by(current_batch_size as u64);
+                            PROCESSED_VERSIONS_COUNT_BY_SOURCE
+                                .with_label_values(&[
+                                    request_metadata.request_name.as_str(),
+                                    data_source.as_str(),
+                                ])
+                                .inc_by(current_batch_size as u64);
                             if let Some(data_latency_in_secs) = data_latency_in_secs {
                                 // If it's a partial batch, we record the latency because it usually means
                                 // the data is the latest.
@@ -323,11 +353,15 @@ impl RawData for RawDataServerWrapper {
                     }
                     // 3. Update the current version and record current tps.
                     tps_calculator.tick_now(current_batch_size as u64);
+                    PROCESSED_TPS
+                        .with_label_values(&[request_metadata.request_name.as_str()])
+                        .set(tps_calculator.avg() * 1000.0);
                     current_version = end_of_batch_version + 1;
                     info!(
                         current_version = current_version,
                         end_version = end_of_batch_version,
                         batch_size = current_batch_size,
+                        data_source = data_source.as_str(),
                         tps = (tps_calculator.avg() * 1000.0) as u64,
                         "[Indexer Data] Sending batch."
                     );
@@ -388,28 +422,58 @@ async fn data_fetch(
         Ok(CacheBatchGetStatus::NotReady) => Ok(TransactionsDataStatus::AheadOfCache),
         Ok(CacheBatchGetStatus::Ok(transactions)) => Ok(TransactionsDataStatus::Success(
             build_protobuf_encoded_transaction_wrappers(transactions, starting_version),
+            DataSource::Cache,
         )),
         Ok(CacheBatchGetStatus::EvictedFromCache) => {
             // Data is evicted from the cache. Fetch from file store.
-            let file_store_batch_get_result =
-                file_store_operator.get_transactions(starting_version).await;
-            match file_store_batch_get_result {
-                Ok(transactions) => Ok(TransactionsDataStatus::Success(
-                    build_protobuf_encoded_transaction_wrappers(transactions, starting_version),
-                )),
-                Err(e) => {
-                    if e.to_string().contains("Transactions file not found") {
-                        Ok(TransactionsDataStatus::DataGap)
-                    } else {
-                        Err(e)
-                    }
-                },
-            }
+            file_store_data_fetch(starting_version, file_store_operator).await
         },
         Err(e) => Err(e),
     }
 }
 
+/// Fetches up to FILE_STORE_CONCURRENT_FETCH_COUNT consecutive transactions files from the file
+/// store concurrently. Only the file containing the starting version is required; the following
+/// files may not be uploaded yet, in which case the transactions are returned up to the first
+/// missing file. Any other error fails the whole fetch so that it is retried.
+async fn file_store_data_fetch(
+    starting_version: u64,
+    file_store_operator: &dyn FileStoreOperator,
+) -> anyhow::Result<TransactionsDataStatus> {
+    let batch_start_version =
+        starting_version / BLOB_STORAGE_SIZE as u64 * BLOB_STORAGE_SIZE as u64;
+    let versions = std::iter::once(starting_version).chain(
+        (1..FILE_STORE_CONCURRENT_FETCH_COUNT)
+            .map(|i| batch_start_version + i * BLOB_STORAGE_SIZE as u64),
+    );
+    let results = futures::future::join_all(
+        versions.map(|version| file_store_operator.get_transactions(version)),
+    )
+    .await;
+
+    let mut transactions = vec![];
+    for (i, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(file_transactions) => transactions.extend(file_transactions),
+            Err(e) if is_file_not_found(&e) => {
+                if i == 0 {
+                    return Ok(TransactionsDataStatus::DataGap);
+                }
+                break;
+            },
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(TransactionsDataStatus::Success(
+        build_protobuf_encoded_transaction_wrappers(transactions, starting_version),
+        DataSource::FileStore,
+    ))
+}
+
+fn is_file_not_found(e: &anyhow::Error) -> bool {
+    e.to_string().contains("Transactions file not found")
+}
+
 /// Handles the case when the data is not ready in the cache, i.e., beyond the current head.
 async fn ahead_of_cache_data_handling() {
     // TODO: add exponential backoff.
@@ -499,3 +563,149 @@ async fn channel_send_multiple_with_timeout(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_indexer_grpc_utils::file_store_operator::{
+        FileStoreMetadata, TransactionsFile, VerificationMetadata,
+    };
+    use std::collections::HashMap;
+
+    /// File store that serves the given files, keyed by starting version. Missing files are
+    /// reported as not found, and files mapped to None fail with any other error.
+    struct MockFileStoreOperator {
+        files: HashMap<u64, Option<Vec<String>>>,
+    }
+
+    impl MockFileStoreOperator {
+        fn new(files: Vec<(u64, Option<Vec<String>>)>) -> Self {
+            Self {
+                files: files.into_iter().collect(),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FileStoreOperator for MockFileStoreOperator {
+        async fn verify_storage_bucket_existence(&self) {}
+
+        async fn get_transactions(&self, version: u64) -> anyhow::Result<Vec<String>> {
+            let batch_start_version = version / BLOB_STORAGE_SIZE as u64 * BLOB_STORAGE_SIZE as u64;
+            match self.files.get(&batch_start_version) {
+                Some(Some(transactions)) => Ok(transactions
+                    .iter()
+                    .skip((version - batch_start_version) as usize)
+                    .cloned()
+                    .collect()),
+                Some(None) => anyhow::bail!("[Indexer File] Error happens when transaction file."),
+                None => anyhow::bail!("[Indexer File] Transactions file not found."),
+            }
+        }
+
+        async fn get_file_store_metadata(&self) -> Option<FileStoreMetadata> {
+            unimplemented!()
+        }
+
+        async fn create_default_file_store_metadata_if_absent(
+            &mut self,
+            _expected_chain_id: u64,
+        ) -> anyhow::Result<FileStoreMetadata> {
+            unimplemented!()
+        }
+
+        async fn update_file_store_metadata(
+            &mut self,
+            _chain_id: u64,
+            _version: u64,
+        ) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+
+        async fn upload_transactions(
+            &mut self,
+            _chain_id: u64,
+            _transactions: Vec<EncodedTransactionWithVersion>,
+        ) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+
+        async fn get_raw_transactions(&self, _version: u64) -> anyhow::Result<TransactionsFile> {
+            unimplemented!()
+        }
+
+        async fn get_or_create_verification_metadata(
+            &self,
+            _chain_id: u64,
+        ) -> anyhow::Result<VerificationMetadata> {
+            unimplemented!()
+        }
+
+        async fn update_verification_metadata(
+            &mut self,
+            _chain_id: u64,
+            _next_version_to_verify: u64,
+        ) -> anyhow::Result<()> {
+            unimplemented!()
+        }
+    }
+
+    fn file(starting_version: u64) -> (u64, Option<Vec<String>>) {
+        let transactions = (starting_version..starting_version + BLOB_STORAGE_SIZE as u64)
+            .map(|version| version.to_string())
+            .collect();
+        (starting_version, Some(transactions))
+    }
+
+    fn assert_served_versions(status: TransactionsDataStatus, start: u64, end: u64) {
+        match status {
+            TransactionsDataStatus::Success(transactions, DataSource::FileStore) => {
+                assert_eq!(transactions.len() as u64, end - start);
+                for (expected_version, (transaction, version)) in (start..end).zip(transactions) {
+                    assert_eq!(version, expected_version);
+                    assert_eq!(transaction, expected_version.to_string());
+                }
+            },
+            _ => panic!("Expected transactions served from the file store."),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_store_data_fetch_concurrent_files() {
+        let size = BLOB_STORAGE_SIZE as u64;
+        let operator = MockFileStoreOperator::new((0..8).map(|i| file(i * size)).collect());
+        let status = file_store_data_fetch(size + 10, &operator).await.unwrap();
+        assert_served_versions(
+            status,
+            size + 10,
+            (1 + FILE_STORE_CONCURRENT_FETCH_COUNT) * size,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_store_data_fetch_stops_at_missing_file() {
+        let size = BLOB_STORAGE_SIZE as u64;
+        let operator = MockFileStoreOperator::new(vec![file(0), file(size), file(3 * size)]);
+        let status = file_store_data_fetch(5, &operator).await.unwrap();
+        assert_served_versions(status, 5, 2 * size);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_data_fetch_data_gap() {
+        let size = BLOB_STORAGE_SIZE as u64;
+        let operator = MockFileStoreOperator::new(vec![file(size)]);
+        let status = file_store_data_fetch(0, &operator).await.unwrap();
+        assert!(matches!(status, TransactionsDataStatus::DataGap));
+    }
+
+    #[tokio::test]
+    async fn test_file_store_data_fetch_propagates_errors() {
+        let size = BLOB_STORAGE_SIZE as u64;
+        // A failure on a later file must not be mistaken for the end of the file store.
+        let operator = MockFileStoreOperator::new(vec![file(0), (size, None), file(2 * size)]);
+        assert!(file_store_data_fetch(0, &operator).await.is_err());
+
+        let operator = MockFileStoreOperator::new(vec![(0, None), file(size)]);
+        assert!(file_store_data_fetch(0, &operator).await.is_err());
+    }
+}
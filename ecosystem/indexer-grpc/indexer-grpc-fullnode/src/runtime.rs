@@ -5,19 +5,27 @@ use crate::stream_coordinator::IndexerStreamCoordinator;
 use aptos_api::context::Context;
 use aptos_config::config::NodeConfig;
 use aptos_logger::{error, info};
-use aptos_mempool::MempoolClientSender;
+use aptos_mempool::{MempoolClientSender, MempoolTransactionEventKind};
 use aptos_moving_average::MovingAverage;
 use aptos_protos::internal::fullnode::v1::{
     fullnode_data_server::{FullnodeData, FullnodeDataServer},
+    mempool_transaction_event::EventType,
     stream_status::StatusType,
-    transactions_from_node_response, GetTransactionsFromNodeRequest, StreamStatus,
+    transactions_from_node_response, GetMempoolTransactionEventsRequest, GetTableInfoRequest,
+    GetTransactionsFromNodeRequest, MempoolTransactionEvent, StreamStatus, TableInfoResponse,
     TransactionsFromNodeResponse,
 };
 use aptos_storage_interface::DbReader;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{
+    chain_id::ChainId,
+    state_store::table::{TableHandle, TableInfo, TableOwner},
+};
 use futures::Stream;
-use std::{net::ToSocketAddrs, pin::Pin, sync::Arc};
-use tokio::{runtime::Runtime, sync::mpsc};
+use std::{net::ToSocketAddrs, pin::Pin, str::FromStr, sync::Arc};
+use tokio::{
+    runtime::Runtime,
+    sync::{broadcast::error::RecvError, mpsc},
+};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{transport::Server, Request, Response, Status};
 
@@ -26,9 +34,12 @@ pub const DEFAULT_NUM_RETRIES: usize = 3;
 pub const RETRY_TIME_MILLIS: u64 = 100;
 const TRANSACTION_CHANNEL_SIZE: usize = 35;
 const DEFAULT_EMIT_SIZE: usize = 1000;
+const MEMPOOL_EVENT_CHANNEL_SIZE: usize = 1000;
 
 type ResponseStream =
     Pin<Box<dyn Stream<Item = Result<TransactionsFromNodeResponse, Status>> + Send>>;
+type MempoolEventStream =
+    Pin<Box<dyn Stream<Item = Result<MempoolTransactionEvent, Status>> + Send>>;
 
 // The GRPC server
 pub struct FullnodeDataService {
@@ -84,6 +95,7 @@ pub fn bootstrap(
 
 #[tonic::async_trait]
 impl FullnodeData for FullnodeDataService {
+    type GetMempoolTransactionEventsStream = MempoolEventStream;
     type GetTransactionsFromNodeStream = ResponseStream;
 
     /// This function is required by the GRPC tonic server. It basically handles the request.
@@ -186,6 +198,84 @@ impl FullnodeData for FullnodeDataService {
             Box::pin(output_stream) as Self::GetTransactionsFromNodeStream
         ))
     }
+
+    /// Streams the transaction insert, commit and eviction events of mempool as they happen.
+    /// Events are not persisted, so a client only sees the events published after it subscribed,
+    /// and a client that falls too far behind skips the events it missed.
+    async fn get_mempool_transaction_events(
+        &self,
+        _req: Request<GetMempoolTransactionEventsRequest>,
+    ) -> Result<Response<Self::GetMempoolTransactionEventsStream>, Status> {
+        let ledger_chain_id = self.context.chain_id().id();
+        let mut events = self
+            .context
+            .subscribe_mempool_transaction_events()
+            .await
+            .map_err(|e| Status::unavailable(format!("Unable to subscribe to mempool: {}", e)))?;
+
+        let (tx, rx) = mpsc::channel(MEMPOOL_EVENT_CHANNEL_SIZE);
+        tokio::spawn(async move {
+            info!("[indexer-grpc] Init mempool transaction events stream");
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(num_skipped)) => {
+                        aptos_logger::warn!(
+                            num_skipped = num_skipped,
+                            "[indexer-grpc] Mempool transaction events stream lagged"
+                        );
+                        continue;
+                    },
+                    Err(RecvError::Closed) => break,
+                };
+                let event = get_mempool_transaction_event(event, ledger_chain_id);
+                if tx.send(Result::<_, Status>::Ok(event)).await.is_err() {
+                    // The client disconnected
+                    break;
+                }
+            }
+        });
+        let output_stream = ReceiverStream::new(rx);
+        Ok(Response::new(
+            Box::pin(output_stream) as Self::GetMempoolTransactionEventsStream
+        ))
+    }
+
+    /// Looks up the key and value types of a table, and the resource or table item it was first
+    /// seen in, so that processors can decode table items without knowing the table upfront.
+    async fn get_table_info(
+        &self,
+        req: Request<GetTableInfoRequest>,
+    ) -> Result<Response<TableInfoResponse>, Status> {
+        let handle = TableHandle::from_str(&req.into_inner().handle)
+            .map_err(|e| Status::invalid_argument(format!("Invalid table handle: {}", e)))?;
+        let db = &self.context.db;
+        if !db.indexer_enabled() {
+            return Err(Status::failed_precondition(
+                "Table info requires the internal indexer to be enabled",
+            ));
+        }
+        let table_info = db.get_table_info(handle).map_err(|e| {
+            Status::not_found(format!(
+                "Table info not found for handle {:?}: {}",
+                handle, e
+            ))
+        })?;
+        let table_owner = db
+            .get_table_owner(handle)
+            .map_err(|e| Status::internal(format!("Unable to read table owner: {}", e)))?;
+        if table_owner.is_none() && !db.indexer_ready() {
+            return Err(Status::unavailable(
+                "Table owners are still being backfilled by the internal indexer",
+            ));
+        }
+        Ok(Response::new(get_table_info_response(
+            handle,
+            table_info,
+            table_owner,
+            self.context.chain_id().id(),
+        )))
+    }
 }
 
 pub fn get_status(
@@ -205,3 +295,54 @@ pub fn get_status(
         chain_id: ledger_chain_id as u32,
     }
 }
+
+pub fn get_mempool_transaction_event(
+    event: aptos_mempool::MempoolTransactionEvent,
+    ledger_chain_id: u8,
+) -> MempoolTransactionEvent {
+    let (event_type, eviction_reason) = match event.kind {
+        MempoolTransactionEventKind::Inserted => (EventType::Inserted, None),
+        MempoolTransactionEventKind::Committed => (EventType::Committed, None),
+        MempoolTransactionEventKind::Evicted(reason) => {
+            (EventType::Evicted, Some(reason.as_str().to_string()))
+        },
+    };
+    MempoolTransactionEvent {
+        r#type: event_type as i32,
+        sender: event.sender.to_hex_literal(),
+        sequence_number: event.sequence_number,
+        hash: event.hash.to_hex_literal(),
+        gas_unit_price: event.gas_unit_price,
+        eviction_reason,
+        chain_id: ledger_chain_id as u32,
+    }
+}
+
+pub fn get_table_info_response(
+    handle: TableHandle,
+    table_info: TableInfo,
+    table_owner: Option<TableOwner>,
+    ledger_chain_id: u8,
+) -> TableInfoResponse {
+    let (owner_address, owner_resource_type, parent_table_handle) = match table_owner {
+        Some(TableOwner::Resource {
+            address,
+            struct_tag,
+        }) => (
+            Some(address.to_hex_literal()),
+            Some(struct_tag.to_string()),
+            None,
+        ),
+        Some(TableOwner::TableItem { handle }) => (None, None, Some(handle.0.to_hex_literal())),
+        None => (None, None, None),
+    };
+    TableInfoResponse {
+        handle: handle.0.to_hex_literal(),
+        key_type: table_info.key_type.to_string(),
+        value_type: table_info.value_type.to_string(),
+        owner_address,
+        owner_resource_type,
+        parent_table_handle,
+        chain_id: ledger_chain_id as u32,
+    }
+}
@@ -16,6 +16,7 @@ use aptos_protos::{
     },
     transaction::v1::Transaction as TransactionPB,
 };
+use aptos_retrier::{retry_with_policy, RetryDecision, RetryPolicy};
 use aptos_vm::data_cache::AsMoveResolver;
 use std::{
     sync::Arc,
@@ -26,6 +27,8 @@ use tonic::Status;
 
 type EndVersion = u64;
 
+const FETCH_RETRY_DELAY_MILLIS: u64 = 300;
+
 // Basically a handler for a single GRPC stream request
 pub struct IndexerStreamCoordinator {
     pub current_version: u64,
@@ -177,40 +180,48 @@ impl IndexerStreamCoordinator {
         ledger_version: u64,
         batch: TransactionBatchInfo,
     ) -> Vec<TransactionOnChainData> {
-        let mut retries = 0;
-        loop {
-            match context.get_transactions(
-                batch.start_version,
-                batch.num_transactions_to_fetch,
-                ledger_version,
-            ) {
-                Ok(raw_txns) => return raw_txns,
-                Err(err) => {
-                    UNABLE_TO_FETCH_TRANSACTION.inc();
-                    retries += 1;
-
-                    if retries >= DEFAULT_NUM_RETRIES {
-                        error!(
-                            starting_version = batch.start_version,
-                            num_transactions = batch.num_transactions_to_fetch,
-                            error = format!("{:?}", err),
-                            "Could not fetch transactions: retries exhausted",
-                        );
-                        panic!(
-                            "Could not fetch {} transactions after {} retries, starting at {}: {:?}",
-                            batch.num_transactions_to_fetch, retries, batch.start_version, err
-                        );
-                    } else {
+        let retry_policy = RetryPolicy::fixed(Duration::from_millis(FETCH_RETRY_DELAY_MILLIS))
+            .with_max_retries(DEFAULT_NUM_RETRIES as u32 - 1);
+        let result = retry_with_policy(
+            &retry_policy,
+            |result| match result {
+                Ok(_) => RetryDecision::DoNotRetry,
+                Err(_) => RetryDecision::Retry,
+            },
+            || async {
+                context
+                    .get_transactions(
+                        batch.start_version,
+                        batch.num_transactions_to_fetch,
+                        ledger_version,
+                    )
+                    .map_err(|err| {
+                        UNABLE_TO_FETCH_TRANSACTION.inc();
                         error!(
                             starting_version = batch.start_version,
                             num_transactions = batch.num_transactions_to_fetch,
                             error = format!("{:?}", err),
-                            "Could not fetch transactions: will retry",
+                            "Could not fetch transactions",
                         );
-                    }
-                    tokio::time::sleep(Duration::from_millis(300)).await;
-                },
-            }
+                        err
+                    })
+            },
+        )
+        .await;
+        match result {
+            Ok(raw_txns) => raw_txns,
+            Err(err) => {
+                error!(
+                    starting_version = batch.start_version,
+                    num_transactions = batch.num_transactions_to_fetch,
+                    error = format!("{:?}", err),
+                    "Could not fetch transactions: retries exhausted",
+                );
+                panic!(
+                    "Could not fetch {} transactions after {} retries, starting at {}: {:?}",
+                    batch.num_transactions_to_fetch, DEFAULT_NUM_RETRIES, batch.start_version, err
+                );
+            },
         }
     }
 
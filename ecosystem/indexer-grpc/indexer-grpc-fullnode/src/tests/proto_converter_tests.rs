@@ -10,7 +10,7 @@ use aptos_api_test_context::current_function_name;
 use aptos_protos::extractor::v1::{
     transaction::{TransactionType, TxnData},
     transaction_payload::{Payload, Type as PayloadType},
-    write_set_change::Change::WriteTableItem,
+    write_set_change::Change::{WriteResource, WriteTableItem},
     Transaction as TransactionPB,
 };
 
@@ -217,6 +217,53 @@ async fn test_table_item_parsing_works() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_resources_and_events_are_decoded() {
+    let mut test_context = new_test_context(current_function_name!());
+    let account = test_context.gen_account();
+    let txn = test_context.create_user_account(&account);
+    test_context.commit_block(&vec![txn]).await;
+
+    let context = Arc::new(test_context.clone().context);
+    let streamer = FullnodeDataService::new(context, 0, None);
+    let converted = fetch_all_stream(streamer).await;
+    let user_txn = converted
+        .iter()
+        .find(|txn| txn.r#type() == TransactionType::User)
+        .unwrap();
+
+    // Resources are decoded to JSON with the on-chain ABIs
+    let account_resource = user_txn
+        .info
+        .as_ref()
+        .unwrap()
+        .changes
+        .iter()
+        .find_map(|change| match change.change.as_ref().unwrap() {
+            WriteResource(resource)
+                if resource.type_str == "0x1::account::Account"
+                    && resource.address == account.address().to_hex_literal() =>
+            {
+                Some(resource.clone())
+            },
+            _ => None,
+        })
+        .unwrap();
+    let data: Value = serde_json::from_str(&account_resource.data).unwrap();
+    assert_eq!(data["sequence_number"], json!("0"));
+
+    // And so are events
+    if let TxnData::User(txn) = user_txn.txn_data.as_ref().unwrap() {
+        assert!(!txn.events.is_empty());
+        for event in &txn.events {
+            let data: Value = serde_json::from_str(&event.data).unwrap();
+            assert!(data.is_object(), "{}: {}", event.type_str, event.data);
+        }
+    } else {
+        panic!("Expected a user transaction");
+    }
+}
+
 async fn make_test_tables(ctx: &mut TestContext, account: &mut LocalAccount) {
     let module = build_test_module(account.address()).await;
 
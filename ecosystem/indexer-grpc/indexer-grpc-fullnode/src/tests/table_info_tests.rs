@@ -0,0 +1,69 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{runtime::FullnodeDataService, tests::super_new_test_context};
+use aptos_api_test_context::current_function_name;
+use aptos_config::config::NodeConfig;
+use aptos_protos::internal::fullnode::v1::{
+    fullnode_data_server::FullnodeData, GetTableInfoRequest, TableInfoResponse,
+};
+use aptos_types::{chain_id::ChainId, state_store::table::TableHandle};
+use move_core_types::account_address::AccountAddress;
+use std::{str::FromStr, sync::Arc};
+use tonic::{Code, Request};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_table_info() {
+    let test_context =
+        super_new_test_context(current_function_name!(), NodeConfig::default(), true);
+    let service = FullnodeDataService {
+        context: Arc::new(test_context.context.clone()),
+        processor_task_count: 1,
+        processor_batch_size: 1,
+        output_batch_size: 1,
+    };
+
+    // Genesis creates the aggregator factory, which holds a table, under the framework account
+    let resource = test_context
+        .api_get_account_resource(
+            AccountAddress::ONE,
+            "0x1",
+            "aggregator_factory",
+            "AggregatorFactory",
+        )
+        .await;
+    let table = &resource["data"]["phantom_table"];
+    let handle = table["handle"].as_str().unwrap();
+    let response = service
+        .get_table_info(Request::new(GetTableInfoRequest {
+            handle: handle.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response, TableInfoResponse {
+        handle: TableHandle::from_str(handle).unwrap().0.to_hex_literal(),
+        key_type: "address".to_string(),
+        value_type: "u128".to_string(),
+        owner_address: Some("0x1".to_string()),
+        owner_resource_type: Some("0x1::aggregator_factory::AggregatorFactory".to_string()),
+        parent_table_handle: None,
+        chain_id: ChainId::test().id() as u32,
+    });
+
+    // Unknown and malformed handles are rejected
+    let status = service
+        .get_table_info(Request::new(GetTableInfoRequest {
+            handle: AccountAddress::random().to_hex_literal(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+    let status = service
+        .get_table_info(Request::new(GetTableInfoRequest {
+            handle: "not a handle".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
@@ -10,31 +10,50 @@ use aptos_protos::{
     internal::fullnode::v1::fullnode_data_client::FullnodeDataClient, transaction::v1::Transaction,
     util::timestamp::Timestamp,
 };
+use aptos_retrier::{retry_with_policy, RetryDecision, RetryPolicy};
 use prost::Message;
+use std::time::Duration;
 
 pub type GrpcClientType = FullnodeDataClient<tonic::transport::Channel>;
 
+const GRPC_CONNECT_INITIAL_RETRY_DELAY_MS: u64 = 500;
+const GRPC_CONNECT_MAX_RETRY_DELAY_SECS: u64 = 60;
+const GRPC_CONNECT_MAX_ELAPSED_SECS: u64 = 15 * 60;
+
 /// Create a gRPC client with exponential backoff.
 pub async fn create_grpc_client(address: String) -> GrpcClientType {
-    backoff::future::retry(backoff::ExponentialBackoff::default(), || async {
-        match FullnodeDataClient::connect(address.clone()).await {
-            Ok(client) => {
-                tracing::info!(
-                    address = address.clone(),
-                    "[Indexer Cache] Connected to indexer gRPC server."
-                );
-                Ok(client)
-            },
-            Err(e) => {
-                tracing::error!(
-                    address = address.clone(),
-                    "[Indexer Cache] Failed to connect to indexer gRPC server: {}",
-                    e
-                );
-                Err(backoff::Error::transient(e))
-            },
-        }
-    })
+    let retry_policy =
+        RetryPolicy::exponential(Duration::from_millis(GRPC_CONNECT_INITIAL_RETRY_DELAY_MS))
+            .with_multiplier(1.5)
+            .with_jitter(0.5)
+            .with_max_delay(Duration::from_secs(GRPC_CONNECT_MAX_RETRY_DELAY_SECS))
+            .with_max_elapsed(Duration::from_secs(GRPC_CONNECT_MAX_ELAPSED_SECS));
+    retry_with_policy(
+        &retry_policy,
+        |result| match result {
+            Ok(_) => RetryDecision::DoNotRetry,
+            Err(_) => RetryDecision::Retry,
+        },
+        || async {
+            match FullnodeDataClient::connect(address.clone()).await {
+                Ok(client) => {
+                    tracing::info!(
+                        address = address.clone(),
+                        "[Indexer Cache] Connected to indexer gRPC server."
+                    );
+                    Ok(client)
+                },
+                Err(e) => {
+                    tracing::error!(
+                        address = address.clone(),
+                        "[Indexer Cache] Failed to connect to indexer gRPC server: {}",
+                        e
+                    );
+                    Err(e)
+                },
+            }
+        },
+    )
     .await
     .unwrap()
 }
@@ -16,16 +16,23 @@ export enum AptosErrorCode {
     TABLE_ITEM_NOT_FOUND = 'table_item_not_found',
     BLOCK_NOT_FOUND = 'block_not_found',
     STATE_VALUE_NOT_FOUND = 'state_value_not_found',
+    TRANSACTION_EXPIRED = 'transaction_expired',
+    TRANSACTION_EVICTED = 'transaction_evicted',
     VERSION_PRUNED = 'version_pruned',
     BLOCK_PRUNED = 'block_pruned',
+    PINNED_VERSION_PRUNED = 'pinned_version_pruned',
     INVALID_INPUT = 'invalid_input',
     INVALID_TRANSACTION_UPDATE = 'invalid_transaction_update',
     SEQUENCE_NUMBER_TOO_OLD = 'sequence_number_too_old',
     VM_ERROR = 'vm_error',
+    TRANSACTION_FILTERED = 'transaction_filtered',
     HEALTH_CHECK_FAILED = 'health_check_failed',
     MEMPOOL_IS_FULL = 'mempool_is_full',
+    MEMPOOL_RATE_LIMITED = 'mempool_rate_limited',
     INTERNAL_ERROR = 'internal_error',
     WEB_FRAMEWORK_ERROR = 'web_framework_error',
     BCS_NOT_SUPPORTED = 'bcs_not_supported',
     API_DISABLED = 'api_disabled',
+    API_KEY_INVALID = 'api_key_invalid',
+    QUOTA_EXCEEDED = 'quota_exceeded',
 }
@@ -5,10 +5,11 @@ use crate::{
     db_access::{Account, CoinStore, DbAccessUtil},
     metrics::TIMER,
 };
-use anyhow::Result;
+use anyhow::{bail, Result};
 use aptos_executor::{
     block_executor::TransactionBlockExecutor, components::chunk_output::ChunkOutput,
 };
+use aptos_state_view::StateView;
 use aptos_storage_interface::cached_state_view::CachedStateView;
 use aptos_types::{
     account_address::AccountAddress,
@@ -80,10 +81,87 @@ impl NativeExecutor {
         }
     }
 
+    /// Executes the transactions natively (i.e., without the VM) on top of the state view. Only
+    /// the transfers (and account creations) the benchmark generates are supported, and their
+    /// outputs only approximate the ones of the VM (e.g., the events and the gas are fake).
+    pub fn execute_transactions(
+        transactions: &[Transaction],
+        state_view: &(impl StateView + Sync),
+    ) -> Result<Vec<TransactionOutput>> {
+        NATIVE_EXECUTOR_POOL.install(|| {
+            transactions
+                .par_iter()
+                .map(|txn| match txn {
+                    Transaction::StateCheckpoint(_) => Self::handle_state_checkpoint(),
+                    // The on-chain block info isn't tracked, so the block prologue is a no-op
+                    Transaction::BlockMetadata(_) => Self::handle_state_checkpoint(),
+                    Transaction::UserTransaction(user_txn) => match user_txn.payload() {
+                        aptos_types::transaction::TransactionPayload::EntryFunction(f) => {
+                            match (
+                                *f.module().address(),
+                                f.module().name().as_str(),
+                                f.function().as_str(),
+                            ) {
+                                (AccountAddress::ONE, "coin", "transfer") => {
+                                    Self::handle_account_creation_and_transfer(
+                                        user_txn.sender(),
+                                        bcs::from_bytes(&f.args()[0]).unwrap(),
+                                        bcs::from_bytes(&f.args()[1]).unwrap(),
+                                        state_view,
+                                        false,
+                                        true,
+                                    )
+                                },
+                                (AccountAddress::ONE, "aptos_account", "transfer") => {
+                                    Self::handle_account_creation_and_transfer(
+                                        user_txn.sender(),
+                                        bcs::from_bytes(&f.args()[0]).unwrap(),
+                                        bcs::from_bytes(&f.args()[1]).unwrap(),
+                                        state_view,
+                                        false,
+                                        false,
+                                    )
+                                },
+                                (AccountAddress::ONE, "aptos_account", "create_account") => {
+                                    Self::handle_account_creation_and_transfer(
+                                        user_txn.sender(),
+                                        bcs::from_bytes(&f.args()[0]).unwrap(),
+                                        0,
+                                        state_view,
+                                        true,
+                                        false,
+                                    )
+                                },
+                                (AccountAddress::ONE, "aptos_account", "batch_transfer") => {
+                                    Self::handle_batch_account_creation_and_transfer(
+                                        user_txn.sender(),
+                                        bcs::from_bytes(&f.args()[0]).unwrap(),
+                                        bcs::from_bytes(&f.args()[1]).unwrap(),
+                                        state_view,
+                                        false,
+                                        true,
+                                    )
+                                },
+                                _ => bail!(
+                                    "The native executor doesn't support {} {}::{}",
+                                    *f.module().address(),
+                                    f.module().name().as_str(),
+                                    f.function().as_str()
+                                ),
+                            }
+                        },
+                        _ => bail!("The native executor only supports entry functions"),
+                    },
+                    _ => bail!("The native executor doesn't support system transactions"),
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+    }
+
     fn withdraw_from_signer(
         sender_address: AccountAddress,
         transfer_amount: u64,
-        state_view: &CachedStateView,
+        state_view: &impl StateView,
     ) -> Result<Result<IncrementalOutput, TransactionStatus>> {
         let sender_account_key = DbAccessUtil::new_state_key_account(sender_address);
         let mut sender_account = {
@@ -142,7 +220,7 @@ impl NativeExecutor {
     fn deposit(
         recipient_address: AccountAddress,
         transfer_amount: u64,
-        state_view: &CachedStateView,
+        state_view: &impl StateView,
         fail_on_existing: bool,
         fail_on_missing: bool,
     ) -> Result<Result<IncrementalOutput, TransactionStatus>> {
@@ -238,7 +316,7 @@ impl NativeExecutor {
         sender_address: AccountAddress,
         recipient_address: AccountAddress,
         transfer_amount: u64,
-        state_view: &CachedStateView,
+        state_view: &impl StateView,
         fail_on_existing: bool,
         fail_on_missing: bool,
     ) -> Result<TransactionOutput> {
@@ -273,7 +351,7 @@ impl NativeExecutor {
         sender_address: AccountAddress,
         recipient_addresses: Vec<AccountAddress>,
         transfer_amounts: Vec<u64>,
-        state_view: &CachedStateView,
+        state_view: &impl StateView,
         fail_on_existing: bool,
         fail_on_missing: bool,
     ) -> Result<TransactionOutput> {
@@ -345,76 +423,12 @@ impl TransactionBlockExecutor for NativeExecutor {
             ExecutableTransactions::Unsharded(txns) => txns,
             _ => todo!("sharded execution not yet supported"),
         };
-        let transaction_outputs = NATIVE_EXECUTOR_POOL.install(|| {
-            transactions
-                .par_iter()
-                .map(|txn| match &txn {
-                    Transaction::StateCheckpoint(_) => Self::handle_state_checkpoint(),
-                    Transaction::UserTransaction(user_txn) => match user_txn.payload() {
-                        aptos_types::transaction::TransactionPayload::EntryFunction(f) => {
-                            match (
-                                *f.module().address(),
-                                f.module().name().as_str(),
-                                f.function().as_str(),
-                            ) {
-                                (AccountAddress::ONE, "coin", "transfer") => {
-                                    Self::handle_account_creation_and_transfer(
-                                        user_txn.sender(),
-                                        bcs::from_bytes(&f.args()[0]).unwrap(),
-                                        bcs::from_bytes(&f.args()[1]).unwrap(),
-                                        &state_view,
-                                        false,
-                                        true,
-                                    )
-                                },
-                                (AccountAddress::ONE, "aptos_account", "transfer") => {
-                                    Self::handle_account_creation_and_transfer(
-                                        user_txn.sender(),
-                                        bcs::from_bytes(&f.args()[0]).unwrap(),
-                                        bcs::from_bytes(&f.args()[1]).unwrap(),
-                                        &state_view,
-                                        false,
-                                        false,
-                                    )
-                                },
-                                (AccountAddress::ONE, "aptos_account", "create_account") => {
-                                    Self::handle_account_creation_and_transfer(
-                                        user_txn.sender(),
-                                        bcs::from_bytes(&f.args()[0]).unwrap(),
-                                        0,
-                                        &state_view,
-                                        true,
-                                        false,
-                                    )
-                                },
-                                (AccountAddress::ONE, "aptos_account", "batch_transfer") => {
-                                    Self::handle_batch_account_creation_and_transfer(
-                                        user_txn.sender(),
-                                        bcs::from_bytes(&f.args()[0]).unwrap(),
-                                        bcs::from_bytes(&f.args()[1]).unwrap(),
-                                        &state_view,
-                                        false,
-                                        true,
-                                    )
-                                },
-                                _ => unimplemented!(
-                                    "{} {}::{}",
-                                    *f.module().address(),
-                                    f.module().name().as_str(),
-                                    f.function().as_str()
-                                ),
-                            }
-                        },
-                        _ => unimplemented!(),
-                    },
-                    _ => unimplemented!(),
-                })
-                .collect::<Result<Vec<_>>>()
-        })?;
+        let transaction_outputs = Self::execute_transactions(&transactions, &state_view)?;
         Ok(ChunkOutput {
             transactions,
             transaction_outputs,
             state_cache: state_view.into_state_cache(),
+            block_stm_aborts: 0,
         })
     }
 }
@@ -3,7 +3,7 @@
 
 #![forbid(unsafe_code)]
 
-use crate::StateComputeResult;
+use crate::{ExecutionProfile, StateComputeResult};
 use anyhow::{ensure, Result};
 use aptos_crypto::{hash::TransactionAccumulatorHasher, HashValue};
 use aptos_storage_interface::{cached_state_view::ShardedStateCache, ExecutedTrees};
@@ -27,6 +27,7 @@ pub struct ExecutedBlock {
     pub transaction_info_hashes: Vec<HashValue>,
     pub block_state_updates: ShardedStateUpdates,
     pub sharded_state_cache: ShardedStateCache,
+    pub execution_profile: ExecutionProfile,
 }
 
 impl ExecutedBlock {
@@ -87,5 +88,6 @@ impl ExecutedBlock {
             self.transaction_info_hashes.clone(),
             self.reconfig_events.clone(),
         )
+        .with_execution_profile(self.execution_profile.clone())
     }
 }
@@ -4,7 +4,7 @@
 
 #![forbid(unsafe_code)]
 
-use crate::TransactionData;
+use crate::{ExecutionProfile, TransactionData};
 use anyhow::{bail, ensure, Result};
 use aptos_crypto::hash::CryptoHash;
 use aptos_storage_interface::ExecutedTrees;
@@ -24,6 +24,7 @@ pub struct ExecutedChunk {
     /// If set, this is the new epoch info that should be changed to if this is committed.
     pub next_epoch_state: Option<EpochState>,
     pub ledger_info: Option<LedgerInfoWithSignatures>,
+    pub execution_profile: ExecutionProfile,
 }
 
 impl ExecutedChunk {
@@ -0,0 +1,56 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use aptos_types::write_set::WriteSet;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The profile of the execution of a block (or chunk) of transactions. It is reported along
+/// with the execution result (see `StateComputeResult`) and the commit notification (see
+/// `ChunkCommitNotification`), so that consensus can size its proposals by the actual cost of
+/// the execution, and the cost can be reported to telemetry.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExecutionProfile {
+    /// The time spent creating the state view to execute on
+    pub state_view_duration: Duration,
+    /// The time spent executing the transactions in the VM (or converting the outputs, if
+    /// the outputs were applied directly)
+    pub vm_execution_duration: Duration,
+    /// The time spent applying the outputs to the ledger (i.e., the in-memory trees)
+    pub apply_to_ledger_duration: Duration,
+    /// The time spent committing to storage (zero until committed)
+    pub commit_duration: Duration,
+    /// The number of speculative executions aborted (and retried) by Block-STM (not counted for
+    /// sharded execution)
+    pub block_stm_aborts: u64,
+    /// The number of transactions to commit
+    pub num_transactions: usize,
+    /// The gas used by the transactions to commit
+    pub gas_used: u64,
+    /// The number of write ops of the transactions to commit
+    pub num_write_ops: usize,
+    /// The size (in bytes) of the keys and values written by the transactions to commit
+    pub write_set_bytes: usize,
+}
+
+impl ExecutionProfile {
+    /// Accounts for a transaction to commit
+    pub fn add_transaction(&mut self, gas_used: u64, write_set: &WriteSet) {
+        self.num_transactions += 1;
+        self.gas_used += gas_used;
+        for (state_key, write_op) in write_set.iter() {
+            self.num_write_ops += 1;
+            self.write_set_bytes += state_key.size() + write_op.bytes().map_or(0, |b| b.len());
+        }
+    }
+
+    /// Returns the total time spent executing (and committing, if committed)
+    pub fn total_duration(&self) -> Duration {
+        self.state_view_duration
+            + self.vm_execution_duration
+            + self.apply_to_ledger_duration
+            + self.commit_duration
+    }
+}
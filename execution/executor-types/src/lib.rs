@@ -25,6 +25,7 @@ use aptos_types::{
 pub use error::Error;
 pub use executed_block::ExecutedBlock;
 pub use executed_chunk::ExecutedChunk;
+pub use execution_profile::ExecutionProfile;
 pub use parsed_transaction_output::ParsedTransactionOutput;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -40,6 +41,7 @@ use std::{
 mod error;
 mod executed_block;
 mod executed_chunk;
+mod execution_profile;
 pub mod in_memory_state_calculator;
 mod parsed_transaction_output;
 
@@ -217,6 +219,8 @@ pub struct ChunkCommitNotification {
     pub committed_events: Vec<ContractEvent>,
     pub committed_transactions: Vec<Transaction>,
     pub reconfiguration_occurred: bool,
+    /// The profile of the execution and commit of the chunk
+    pub execution_profile: ExecutionProfile,
 }
 
 /// A structure that summarizes the result of the execution needed for consensus to agree on.
@@ -227,7 +231,7 @@ pub struct ChunkCommitNotification {
 /// of success / failure of the transactions.
 /// Note that the specific details of compute_status are opaque to StateMachineReplication,
 /// which is going to simply pass the results between StateComputer and PayloadClient.
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateComputeResult {
     /// transaction accumulator root hash is identified as `state_id` in Consensus.
     root_hash: HashValue,
@@ -256,8 +260,42 @@ pub struct StateComputeResult {
     transaction_info_hashes: Vec<HashValue>,
 
     reconfig_events: Vec<ContractEvent>,
+
+    /// The profile of the execution of the block (not part of the agreed upon result).
+    #[serde(skip)]
+    execution_profile: ExecutionProfile,
 }
 
+/// The execution profile (e.g., the wall-clock timings) differs between the nodes executing the
+/// same block, so it isn't compared.
+impl PartialEq for StateComputeResult {
+    fn eq(&self, other: &Self) -> bool {
+        let Self {
+            root_hash,
+            frozen_subtree_roots,
+            parent_frozen_subtree_roots,
+            num_leaves,
+            parent_num_leaves,
+            epoch_state,
+            compute_status,
+            transaction_info_hashes,
+            reconfig_events,
+            execution_profile: _,
+        } = self;
+        *root_hash == other.root_hash
+            && *frozen_subtree_roots == other.frozen_subtree_roots
+            && *parent_frozen_subtree_roots == other.parent_frozen_subtree_roots
+            && *num_leaves == other.num_leaves
+            && *parent_num_leaves == other.parent_num_leaves
+            && *epoch_state == other.epoch_state
+            && *compute_status == other.compute_status
+            && *transaction_info_hashes == other.transaction_info_hashes
+            && *reconfig_events == other.reconfig_events
+    }
+}
+
+impl Eq for StateComputeResult {}
+
 impl StateComputeResult {
     pub fn new(
         root_hash: HashValue,
@@ -280,9 +318,15 @@ impl StateComputeResult {
             compute_status,
             transaction_info_hashes,
             reconfig_events,
+            execution_profile: ExecutionProfile::default(),
         }
     }
 
+    pub fn with_execution_profile(mut self, execution_profile: ExecutionProfile) -> Self {
+        self.execution_profile = execution_profile;
+        self
+    }
+
     /// generate a new dummy state compute result with a given root hash.
     /// this function is used in RandomComputeResultStateComputer to assert that the compute
     /// function is really called.
@@ -297,6 +341,7 @@ impl StateComputeResult {
             compute_status: vec![],
             transaction_info_hashes: vec![],
             reconfig_events: vec![],
+            execution_profile: ExecutionProfile::default(),
         }
     }
 
@@ -363,6 +408,10 @@ impl StateComputeResult {
     pub fn reconfig_events(&self) -> &[ContractEvent] {
         &self.reconfig_events
     }
+
+    pub fn execution_profile(&self) -> &ExecutionProfile {
+        &self.execution_profile
+    }
 }
 
 pub struct ProofReader {
@@ -21,16 +21,18 @@ use aptos_logger::prelude::*;
 use aptos_scratchpad::SparseMerkleTree;
 use aptos_state_view::StateViewId;
 use aptos_storage_interface::{
-    async_proof_fetcher::AsyncProofFetcher, cached_state_view::CachedStateView, DbReaderWriter,
+    async_proof_fetcher::AsyncProofFetcher,
+    cached_state_view::{CachedStateView, WarmStateCache},
+    DbReaderWriter,
 };
 use aptos_types::{
     block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValue,
+    state_store::{state_key::StateKey, state_value::StateValue},
 };
 use aptos_vm::AptosVM;
 use fail::fail_point;
-use std::{marker::PhantomData, sync::Arc};
+use std::{marker::PhantomData, sync::Arc, time::Instant};
 
 pub trait TransactionBlockExecutor: Send + Sync {
     fn execute_transaction_block(
@@ -84,6 +86,19 @@ where
         }
         Ok(())
     }
+
+    /// Reads the given keys, and the on-chain configs read to create the VM of every block, on
+    /// top of the committed state into the state cache of the committed block, which its
+    /// children consult (see `WarmStateCache`). The cache is dropped if the executor is reset.
+    /// Returns the number of keys in the cache.
+    pub(crate) fn prime_committed_state_cache(&self, state_keys: &[StateKey]) -> Result<usize> {
+        self.maybe_initialize()?;
+        self.inner
+            .read()
+            .as_ref()
+            .expect("BlockExecutor is not reset")
+            .prime_committed_state_cache(state_keys)
+    }
 }
 
 impl<V> BlockExecutorTrait for BlockExecutor<V>
@@ -174,6 +189,33 @@ where
         self.block_tree.root_block().id
     }
 
+    fn prime_committed_state_cache(&self, state_keys: &[StateKey]) -> Result<usize> {
+        let committed_block = self.block_tree.root_block();
+        let state_view = committed_block.output.result_view.verified_state_view(
+            StateViewId::Miscellaneous,
+            Arc::clone(&self.db.reader),
+            Arc::new(AsyncProofFetcher::new(self.db.reader.clone())),
+        )?;
+        state_view.prime_cache_by_state_keys(state_keys)?;
+        // Creating the VM reads the on-chain configs (e.g., the gas schedule and the features)
+        AptosVM::new(&state_view);
+        // This also waits for the proofs to be read
+        let state_cache = state_view.into_state_cache();
+
+        // The values were read on top of the committed state, so they're valid for its children
+        let committed_cache = &committed_block.output.sharded_state_cache;
+        for (shard, committed_shard) in state_cache
+            .sharded_state_cache
+            .into_iter()
+            .zip(committed_cache.iter())
+        {
+            for (state_key, version_and_value) in shard {
+                committed_shard.insert(state_key, version_and_value);
+            }
+        }
+        Ok(committed_cache.iter().map(|shard| shard.len()).sum())
+    }
+
     fn execute_block(
         &self,
         block: ExecutableBlock,
@@ -215,17 +257,32 @@ where
                 LogSchema::new(LogEntry::BlockExecutor).block_id(block_id),
                 "execute_block"
             );
+            let state_view_start_time = Instant::now();
+            let warm_cache = {
+                let _timer = APTOS_EXECUTOR_OTHER_TIMERS_SECONDS
+                    .with_label_values(&["warm_state_cache"])
+                    .start_timer();
+                // The values read by the parent block stay valid, unless the parent wrote them
+                WarmStateCache::new(
+                    &parent_output.sharded_state_cache,
+                    &parent_output.block_state_updates,
+                )
+            };
             let state_view = {
                 let _timer = APTOS_EXECUTOR_OTHER_TIMERS_SECONDS
                     .with_label_values(&["verified_state_view"])
                     .start_timer();
-                parent_view.verified_state_view(
-                    StateViewId::BlockExecution { block_id },
-                    Arc::clone(&self.db.reader),
-                    Arc::new(AsyncProofFetcher::new(self.db.reader.clone())),
-                )?
+                parent_view
+                    .verified_state_view(
+                        StateViewId::BlockExecution { block_id },
+                        Arc::clone(&self.db.reader),
+                        Arc::new(AsyncProofFetcher::new(self.db.reader.clone())),
+                    )?
+                    .with_warm_cache(Arc::new(warm_cache))
             };
+            let state_view_duration = state_view_start_time.elapsed();
 
+            let vm_execution_start_time = Instant::now();
             let chunk_output = {
                 let _timer = APTOS_EXECUTOR_VM_EXECUTE_BLOCK_SECONDS.start_timer();
                 fail_point!("executor::vm_execute_block", |_| {
@@ -235,14 +292,20 @@ where
                 });
                 V::execute_transaction_block(transactions, state_view, maybe_block_gas_limit)?
             };
+            let vm_execution_duration = vm_execution_start_time.elapsed();
             chunk_output.trace_log_transaction_status();
 
             let _timer = APTOS_EXECUTOR_OTHER_TIMERS_SECONDS
                 .with_label_values(&["apply_to_ledger"])
                 .start_timer();
 
-            let (output, _, _) = chunk_output
+            let apply_to_ledger_start_time = Instant::now();
+            let (mut output, _, _) = chunk_output
                 .apply_to_ledger_for_block(parent_view, maybe_block_gas_limit.map(|_| block_id))?;
+            let execution_profile = &mut output.execution_profile;
+            execution_profile.state_view_duration = state_view_duration;
+            execution_profile.vm_execution_duration = vm_execution_duration;
+            execution_profile.apply_to_ledger_duration = apply_to_ledger_start_time.elapsed();
 
             output
         };
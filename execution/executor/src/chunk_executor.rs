@@ -40,7 +40,7 @@ use aptos_types::{
 use aptos_vm::VMExecutor;
 use fail::fail_point;
 use itertools::multizip;
-use std::{iter::once, marker::PhantomData, sync::Arc};
+use std::{iter::once, marker::PhantomData, sync::Arc, time::Instant};
 
 pub struct ChunkExecutor<V> {
     db: DbReaderWriter,
@@ -200,19 +200,28 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         )?;
 
         // Execute transactions.
+        let state_view_start_time = Instant::now();
         let state_view = self.state_view(&latest_view)?;
+        let state_view_duration = state_view_start_time.elapsed();
+        let vm_execution_start_time = Instant::now();
         let chunk_output = {
             let _timer = APTOS_EXECUTOR_VM_EXECUTE_CHUNK_SECONDS.start_timer();
             // State sync executor shouldn't have block gas limit.
             ChunkOutput::by_transaction_execution::<V>(transactions.into(), state_view, None)?
         };
-        let executed_chunk = Self::apply_chunk_output_for_state_sync(
+        let vm_execution_duration = vm_execution_start_time.elapsed();
+        let apply_to_ledger_start_time = Instant::now();
+        let mut executed_chunk = Self::apply_chunk_output_for_state_sync(
             verified_target_li,
             epoch_change_li,
             &latest_view,
             chunk_output,
             &txn_info_list_with_proof.transaction_infos[txns_to_skip..],
         )?;
+        let execution_profile = &mut executed_chunk.execution_profile;
+        execution_profile.state_view_duration = state_view_duration;
+        execution_profile.vm_execution_duration = vm_execution_duration;
+        execution_profile.apply_to_ledger_duration = apply_to_ledger_start_time.elapsed();
 
         // Add result to commit queue.
         self.commit_queue.lock().enqueue(executed_chunk);
@@ -254,15 +263,24 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         txns_and_outputs.drain(..txns_to_skip);
 
         // Apply transaction outputs.
+        let state_view_start_time = Instant::now();
         let state_view = self.state_view(&latest_view)?;
+        let state_view_duration = state_view_start_time.elapsed();
+        let vm_execution_start_time = Instant::now();
         let chunk_output = ChunkOutput::by_transaction_output(txns_and_outputs, state_view)?;
-        let executed_chunk = Self::apply_chunk_output_for_state_sync(
+        let vm_execution_duration = vm_execution_start_time.elapsed();
+        let apply_to_ledger_start_time = Instant::now();
+        let mut executed_chunk = Self::apply_chunk_output_for_state_sync(
             verified_target_li,
             epoch_change_li,
             &latest_view,
             chunk_output,
             &txn_output_list_with_proof.proof.transaction_infos[txns_to_skip..],
         )?;
+        let execution_profile = &mut executed_chunk.execution_profile;
+        execution_profile.state_view_duration = state_view_duration;
+        execution_profile.vm_execution_duration = vm_execution_duration;
+        execution_profile.apply_to_ledger_duration = apply_to_ledger_start_time.elapsed();
 
         // Add result to commit queue.
         self.commit_queue.lock().enqueue(executed_chunk);
@@ -280,11 +298,15 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
 
     fn commit_chunk(&self) -> Result<ChunkCommitNotification> {
         let _timer = APTOS_EXECUTOR_COMMIT_CHUNK_SECONDS.start_timer();
+        let commit_start_time = Instant::now();
         let executed_chunk = self.commit_chunk_impl()?;
+        let mut execution_profile = executed_chunk.execution_profile.clone();
+        execution_profile.commit_duration = commit_start_time.elapsed();
         Ok(ChunkCommitNotification {
             committed_events: executed_chunk.events_to_commit(),
             committed_transactions: executed_chunk.transactions(),
             reconfiguration_occurred: executed_chunk.has_reconfiguration(),
+            execution_profile,
         })
     }
 }
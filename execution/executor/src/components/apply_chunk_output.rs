@@ -17,7 +17,7 @@ use aptos_crypto::{
 };
 use aptos_executor_types::{
     in_memory_state_calculator::InMemoryStateCalculator, ExecutedBlock, ExecutedChunk,
-    ParsedTransactionOutput, TransactionData,
+    ExecutionProfile, ParsedTransactionOutput, TransactionData,
 };
 use aptos_logger::error;
 use aptos_storage_interface::ExecutedTrees;
@@ -50,6 +50,7 @@ impl ApplyChunkOutput {
             state_cache,
             transactions,
             transaction_outputs,
+            block_stm_aborts,
         } = chunk_output;
         let (new_epoch, status, to_keep, to_discard, to_retry) = {
             let _timer = APTOS_EXECUTOR_OTHER_TIMERS_SECONDS
@@ -100,6 +101,13 @@ impl ApplyChunkOutput {
             result_state,
             Arc::new(base_view.txn_accumulator().append(&transaction_info_hashes)),
         );
+        let mut execution_profile = ExecutionProfile {
+            block_stm_aborts,
+            ..ExecutionProfile::default()
+        };
+        for txn in &to_commit {
+            execution_profile.add_transaction(txn.gas_used(), txn.write_set());
+        }
 
         Ok((
             ExecutedBlock {
@@ -111,6 +119,7 @@ impl ApplyChunkOutput {
                 transaction_info_hashes,
                 block_state_updates,
                 sharded_state_cache,
+                execution_profile,
             },
             to_discard,
             to_retry,
@@ -126,6 +135,7 @@ impl ApplyChunkOutput {
             state_cache,
             transactions,
             transaction_outputs,
+            block_stm_aborts,
         } = chunk_output;
         let (new_epoch, status, to_keep, to_discard, to_retry) = {
             let _timer = APTOS_EXECUTOR_OTHER_TIMERS_SECONDS
@@ -164,6 +174,13 @@ impl ApplyChunkOutput {
             result_state,
             Arc::new(base_view.txn_accumulator().append(&transaction_info_hashes)),
         );
+        let mut execution_profile = ExecutionProfile {
+            block_stm_aborts,
+            ..ExecutionProfile::default()
+        };
+        for (_, txn_data) in &to_commit {
+            execution_profile.add_transaction(txn_data.gas_used(), txn_data.write_set());
+        }
 
         Ok((
             ExecutedChunk {
@@ -172,6 +189,7 @@ impl ApplyChunkOutput {
                 result_view,
                 next_epoch_state,
                 ledger_info: None,
+                execution_profile,
             },
             to_discard,
             to_retry,
@@ -47,6 +47,9 @@ pub struct ChunkOutput {
     /// execution result is processed; as well as all the accounts touched during execution, together
     /// with their proofs.
     pub state_cache: StateCache,
+    /// The number of speculative executions aborted (and re-executed) by Block-STM. Not
+    /// counted for sharded execution (nor for outputs that weren't executed).
+    pub block_stm_aborts: u64,
 }
 
 impl ChunkOutput {
@@ -74,7 +77,7 @@ impl ChunkOutput {
         state_view: CachedStateView,
         maybe_block_gas_limit: Option<u64>,
     ) -> Result<Self> {
-        let transaction_outputs =
+        let (transaction_outputs, block_stm_aborts) =
             Self::execute_block::<V>(transactions.clone(), &state_view, maybe_block_gas_limit)?;
 
         // to print txn output for debugging, uncomment:
@@ -86,6 +89,7 @@ impl ChunkOutput {
             transactions,
             transaction_outputs,
             state_cache: state_view.into_state_cache(),
+            block_stm_aborts,
         })
     }
 
@@ -114,6 +118,7 @@ impl ChunkOutput {
                 .collect(),
             transaction_outputs,
             state_cache: state_view.into_state_cache(),
+            block_stm_aborts: 0,
         })
     }
 
@@ -139,6 +144,7 @@ impl ChunkOutput {
             transactions,
             transaction_outputs,
             state_cache: state_view.into_state_cache(),
+            block_stm_aborts: 0,
         })
     }
 
@@ -193,14 +199,14 @@ impl ChunkOutput {
     }
 
     /// Executes the block of [Transaction]s using the [VMExecutor] and returns
-    /// a vector of [TransactionOutput]s.
+    /// a vector of [TransactionOutput]s, along with the number of Block-STM aborts.
     #[cfg(not(feature = "consensus-only-perf-test"))]
     fn execute_block<V: VMExecutor>(
         transactions: Vec<Transaction>,
         state_view: &CachedStateView,
         maybe_block_gas_limit: Option<u64>,
-    ) -> Result<Vec<TransactionOutput>> {
-        Ok(V::execute_block(
+    ) -> Result<(Vec<TransactionOutput>, u64)> {
+        Ok(V::execute_block_with_aborts(
             transactions,
             &state_view,
             maybe_block_gas_limit,
@@ -216,14 +222,18 @@ impl ChunkOutput {
         transactions: Vec<Transaction>,
         state_view: &CachedStateView,
         maybe_block_gas_limit: Option<u64>,
-    ) -> Result<Vec<TransactionOutput>> {
+    ) -> Result<(Vec<TransactionOutput>, u64)> {
         use aptos_state_view::{StateViewId, TStateView};
         use aptos_types::write_set::WriteSet;
 
         let transaction_outputs = match state_view.id() {
             // this state view ID implies a genesis block in non-test cases.
             StateViewId::Miscellaneous => {
-                V::execute_block(transactions, &state_view, maybe_block_gas_limit)?
+                return Ok(V::execute_block_with_aborts(
+                    transactions,
+                    &state_view,
+                    maybe_block_gas_limit,
+                )?);
             },
             _ => transactions
                 .iter()
@@ -237,7 +247,7 @@ impl ChunkOutput {
                 })
                 .collect::<Vec<_>>(),
         };
-        Ok(transaction_outputs)
+        Ok((transaction_outputs, 0))
     }
 }
 
@@ -20,16 +20,19 @@ use aptos_executor_types::{
 };
 use aptos_state_view::StateViewId;
 use aptos_storage_interface::{
-    async_proof_fetcher::AsyncProofFetcher, DbReaderWriter, ExecutedTrees,
+    async_proof_fetcher::AsyncProofFetcher,
+    cached_state_view::{ShardedStateCache, WarmStateCache},
+    DbReaderWriter, ExecutedTrees,
 };
 use aptos_types::{
+    access_path::AccessPath,
     account_address::AccountAddress,
     aggregate_signature::AggregateSignature,
     block_info::BlockInfo,
     chain_id::ChainId,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     proof::definition::LeafCount,
-    state_store::{state_key::StateKey, state_value::StateValue},
+    state_store::{state_key::StateKey, state_value::StateValue, ShardedStateUpdates},
     test_helpers::transaction_test_helpers::{block, BLOCK_GAS_LIMIT},
     transaction::{
         ExecutionStatus, RawTransaction, Script, SignedTransaction, Transaction,
@@ -38,8 +41,10 @@ use aptos_types::{
     },
     write_set::{WriteOp, WriteSet, WriteSetMut},
 };
+use arr_macro::arr;
+use dashmap::DashMap;
 use proptest::prelude::*;
-use std::{iter::once, sync::Arc};
+use std::{collections::HashMap, iter::once, sync::Arc};
 
 mod chunk_executor_tests;
 
@@ -220,6 +225,12 @@ fn test_executor_one_block() {
     assert_eq!(output.version(), version);
     let block_root_hash = output.root_hash();
 
+    // The execution profile accounts for all the transactions to commit
+    let execution_profile = output.execution_profile();
+    assert_eq!(execution_profile.num_transactions as u64, version);
+    assert!(execution_profile.num_write_ops >= num_user_txns as usize);
+    assert!(execution_profile.write_set_bytes > 0);
+
     let ledger_info = gen_ledger_info(version, block_root_hash, block_id, 1);
     executor.commit_blocks(vec![block_id], ledger_info).unwrap();
 }
@@ -581,6 +592,98 @@ fn test_deleted_key_from_state_store() {
     assert_eq!(state_value_from_db1, StateValue::from(dummy_value1));
 }
 
+#[test]
+fn test_warm_state_cache() {
+    let read_key = StateKey::raw(String::from("read_key").into_bytes());
+    let read_value = StateValue::from(10u64.to_le_bytes().to_vec());
+    let written_key = StateKey::raw(String::from("written_key").into_bytes());
+    let deleted_key = StateKey::raw(String::from("deleted_key").into_bytes());
+
+    // The parent block read all the keys, and wrote two of them
+    let parent_cache: ShardedStateCache = arr![DashMap::new(); 16];
+    for key in [&read_key, &written_key, &deleted_key] {
+        parent_cache[key.get_shard_id() as usize]
+            .insert(key.clone(), (Some(1), Some(read_value.clone())));
+    }
+    let mut parent_updates: ShardedStateUpdates = arr![HashMap::new(); 16];
+    parent_updates[written_key.get_shard_id() as usize].insert(
+        written_key.clone(),
+        Some(StateValue::from(20u64.to_le_bytes().to_vec())),
+    );
+    parent_updates[deleted_key.get_shard_id() as usize].insert(deleted_key.clone(), None);
+
+    // Only the value that wasn't written by the parent is carried over
+    let warm_cache = WarmStateCache::new(&parent_cache, &parent_updates);
+    assert_eq!(warm_cache.len(), 1);
+    assert_eq!(warm_cache.get(&read_key), Some((Some(1), Some(read_value))));
+    assert_eq!(warm_cache.get(&written_key), None);
+    assert_eq!(warm_cache.get(&deleted_key), None);
+}
+
+#[test]
+#[cfg_attr(feature = "consensus-only-perf-test", ignore)]
+fn test_child_block_writes_key_only_read_by_parent() {
+    let executor = TestExecutor::new();
+    let genesis_block_id = executor.committed_block_id();
+
+    // Persist the accounts, so their keys are read from the DB
+    let block1_id = gen_block_id(1);
+    let block1_txns = vec![
+        encode_mint_transaction(gen_address(0), 100),
+        encode_mint_transaction(gen_address(1), 100),
+    ];
+    let output1 = executor
+        .execute_block(
+            (block1_id, block(block1_txns, BLOCK_GAS_LIMIT)).into(),
+            genesis_block_id,
+            BLOCK_GAS_LIMIT,
+        )
+        .unwrap();
+    let ledger_info = gen_ledger_info(output1.version(), output1.root_hash(), block1_id, 1);
+    executor
+        .commit_blocks(vec![block1_id], ledger_info)
+        .unwrap();
+
+    // The parent only reads the balances (the transfer is discarded)
+    let block2_id = gen_block_id(2);
+    let block2_txns = vec![encode_transfer_transaction(
+        gen_address(0),
+        gen_address(1),
+        1000,
+    )];
+    executor
+        .execute_block(
+            (block2_id, block(block2_txns, BLOCK_GAS_LIMIT)).into(),
+            block1_id,
+            BLOCK_GAS_LIMIT,
+        )
+        .unwrap();
+
+    // The child writes a balance it reads from the warm cache, which needs the proof of the key
+    let block3_id = gen_block_id(3);
+    let block3_txns = vec![encode_mint_transaction(gen_address(1), 100)];
+    let output3 = executor
+        .execute_block(
+            (block3_id, block(block3_txns, BLOCK_GAS_LIMIT)).into(),
+            block2_id,
+            BLOCK_GAS_LIMIT,
+        )
+        .unwrap();
+    let ledger_info = gen_ledger_info(output3.version(), output3.root_hash(), block3_id, 3);
+    executor
+        .commit_blocks(vec![block2_id, block3_id], ledger_info)
+        .unwrap();
+
+    let balance_key = StateKey::access_path(AccessPath::new(gen_address(1), b"balance".to_vec()));
+    let balance = executor
+        .db
+        .reader
+        .get_state_value_by_version(&balance_key, output3.version())
+        .unwrap()
+        .unwrap();
+    assert_eq!(balance, StateValue::from(200u64.to_le_bytes().to_vec()));
+}
+
 #[test]
 fn test_reconfig_suffix_empty_blocks() {
     let TestExecutor {
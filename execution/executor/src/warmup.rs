@@ -0,0 +1,145 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use crate::{
+    block_executor::{BlockExecutor, TransactionBlockExecutor},
+    metrics::APTOS_EXECUTOR_OTHER_TIMERS_SECONDS,
+};
+use anyhow::Result;
+use aptos_logger::prelude::*;
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    access_path::AccessPath,
+    state_store::state_key::StateKey,
+    transaction::{Transaction, TransactionPayload, Version},
+};
+use move_binary_format::{access::ModuleAccess, CompiledModule};
+use move_core_types::language_storage::ModuleId;
+use std::{
+    collections::{HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Warms up the caches used to execute the next blocks with the last `num_blocks` committed
+/// blocks (i.e., with the state the recent workload is most likely to touch again):
+/// * the state keys written by the blocks,
+/// * the modules of the entry functions called by the blocks, and their dependencies, and
+/// * the on-chain configs read to create the VM of every block.
+///
+/// The keys are read, with their proofs, on top of the latest committed state, exactly as the
+/// executor reads them. This warms the storage caches (e.g., the RocksDB block cache and the
+/// state merkle node cache), and the values are kept in the state cache of the committed block,
+/// so the first blocks executed after a restart find them in their warm state cache instead of
+/// reading the DB. The VM (and its module loader) is created for each block, so it's the module
+/// bytes that are cached, not the loaded modules. Nothing is executed or written to the DB.
+///
+/// The blocks are visited from the latest one backwards, streaming their write sets and
+/// transactions, until `max_num_keys` keys are collected or `time_budget` has elapsed, which
+/// bounds the memory used and the time the warm-up delays the startup by.
+///
+/// Returns the number of state keys in the cache of the committed block.
+pub fn warm_up_with_recent_blocks<V: TransactionBlockExecutor>(
+    executor: &BlockExecutor<V>,
+    num_blocks: u64,
+    max_num_keys: usize,
+    time_budget: Duration,
+) -> Result<usize> {
+    if num_blocks == 0 || max_num_keys == 0 {
+        return Ok(0);
+    }
+    let db = &executor.db.reader;
+    let latest_version = match db.get_latest_ledger_info_option()? {
+        Some(ledger_info) => ledger_info.ledger_info().version(),
+        None => return Ok(0),
+    };
+
+    let _timer = APTOS_EXECUTOR_OTHER_TIMERS_SECONDS
+        .with_label_values(&["warm_up"])
+        .start_timer();
+    let start_time = Instant::now();
+    let is_over_budget = |state_keys: &HashSet<StateKey>| {
+        state_keys.len() >= max_num_keys || start_time.elapsed() >= time_budget
+    };
+
+    let (_, _, latest_block_event) = db.get_block_info_by_version(latest_version)?;
+    let latest_height = latest_block_event.height();
+    let first_height = latest_height.saturating_sub(num_blocks - 1);
+    let mut state_keys = HashSet::new();
+    let mut module_ids = HashSet::new();
+    let mut height = latest_height;
+    'blocks: loop {
+        let (first_version, last_version, _) = db.get_block_info_by_height(height)?;
+        let num_versions = last_version - first_version + 1;
+        for write_set in db.get_write_set_iterator(first_version, num_versions)? {
+            for (state_key, _) in write_set?.iter() {
+                if is_over_budget(&state_keys) {
+                    break 'blocks;
+                }
+                state_keys.insert(state_key.clone());
+            }
+        }
+        for transaction in db.get_transaction_iterator(first_version, num_versions)? {
+            if let Transaction::UserTransaction(transaction) = transaction? {
+                if let TransactionPayload::EntryFunction(entry_function) = transaction.payload() {
+                    module_ids.insert(entry_function.module().clone());
+                }
+            }
+        }
+        if height == first_height {
+            break;
+        }
+        height -= 1;
+    }
+    let num_written_keys = state_keys.len();
+    add_module_dependencies(
+        db.as_ref(),
+        latest_version,
+        module_ids.into_iter().collect(),
+        &mut state_keys,
+        is_over_budget,
+    )?;
+    let num_module_keys = state_keys.len() - num_written_keys;
+
+    let num_keys =
+        executor.prime_committed_state_cache(&state_keys.into_iter().collect::<Vec<_>>())?;
+
+    info!(
+        first_height = height,
+        latest_height = latest_height,
+        num_written_keys = num_written_keys,
+        num_module_keys = num_module_keys,
+        num_keys = num_keys,
+        time_ms = start_time.elapsed().as_millis() as u64,
+        "Executor warm-up finished."
+    );
+    Ok(num_keys)
+}
+
+/// Adds the keys of the given modules, and of their transitive dependencies, to `state_keys`,
+/// until the budget is exhausted. Modules that don't exist (anymore) are skipped.
+fn add_module_dependencies(
+    db: &dyn DbReader,
+    version: Version,
+    mut module_ids: VecDeque<ModuleId>,
+    state_keys: &mut HashSet<StateKey>,
+    is_over_budget: impl Fn(&HashSet<StateKey>) -> bool,
+) -> Result<()> {
+    let mut visited = HashSet::new();
+    while let Some(module_id) = module_ids.pop_front() {
+        if is_over_budget(state_keys) {
+            break;
+        }
+        if !visited.insert(module_id.clone()) {
+            continue;
+        }
+        let state_key = StateKey::access_path(AccessPath::code_access_path(module_id));
+        if let Some(state_value) = db.get_state_value_by_version(&state_key, version)? {
+            let module = CompiledModule::deserialize(state_value.bytes())?;
+            module_ids.extend(module.immediate_dependencies());
+            state_keys.insert(state_key);
+        }
+    }
+    Ok(())
+}
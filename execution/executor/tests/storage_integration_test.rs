@@ -4,15 +4,17 @@
 
 use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::{hash::CryptoHash, PrivateKey};
+use aptos_executor::{block_executor::BlockExecutor, warmup::warm_up_with_recent_blocks};
 use aptos_executor_test_helpers::{
     gen_block_id, gen_ledger_info_with_sigs, get_test_signed_transaction,
     integration_test_impl::{
-        create_db_and_executor, test_execution_with_storage_impl, verify_committed_txn_status,
+        create_db_and_executor, test_execution_with_storage_impl,
+        test_execution_with_storage_impl_inner, verify_committed_txn_status,
     },
 };
 use aptos_executor_types::BlockExecutorTrait;
 use aptos_state_view::account_with_state_view::AsAccountWithStateView;
-use aptos_storage_interface::state_view::DbStateViewAtVersion;
+use aptos_storage_interface::{state_view::DbStateViewAtVersion, DbReaderWriter};
 use aptos_types::{
     access_path::AccessPath,
     account_config::{aptos_test_root_address, AccountResource, CORE_CODE_ADDRESS},
@@ -24,7 +26,9 @@ use aptos_types::{
     trusted_state::TrustedState,
     validator_signer::ValidatorSigner,
 };
+use aptos_vm::AptosVM;
 use move_core_types::move_resource::MoveStructType;
+use std::{collections::HashSet, time::Duration};
 
 #[test]
 fn test_genesis() {
@@ -192,3 +196,55 @@ fn test_reconfiguration() {
 fn test_execution_with_storage() {
     test_execution_with_storage_impl();
 }
+
+#[test]
+#[cfg_attr(feature = "consensus-only-perf-test", ignore)]
+fn test_warm_up_with_recent_blocks() {
+    let path = aptos_temppath::TempPath::new();
+    path.create_as_dir().unwrap();
+    let db = DbReaderWriter::from_arc(test_execution_with_storage_impl_inner(false, path.path()));
+    let time_budget = Duration::from_secs(60);
+    let executor = BlockExecutor::<AptosVM>::new(db.clone());
+    assert_eq!(
+        warm_up_with_recent_blocks(&executor, 0, 100_000, time_budget).unwrap(),
+        0
+    );
+
+    // The warm-up caches (at least) the keys written by the last two blocks
+    let latest_version = db.reader.get_latest_version().unwrap();
+    let (_, _, latest_block_event) = db.reader.get_block_info_by_version(latest_version).unwrap();
+    let (first_version, _, _) = db
+        .reader
+        .get_block_info_by_height(latest_block_event.height() - 1)
+        .unwrap();
+    let written_keys = db
+        .reader
+        .get_write_set_iterator(first_version, latest_version - first_version + 1)
+        .unwrap()
+        .flat_map(|write_set| {
+            write_set
+                .unwrap()
+                .iter()
+                .map(|(state_key, _)| state_key.clone())
+                .collect::<Vec<_>>()
+        })
+        .collect::<HashSet<_>>();
+    assert!(!written_keys.is_empty());
+    let num_keys = warm_up_with_recent_blocks(&executor, 2, 100_000, time_budget).unwrap();
+    assert!(num_keys >= written_keys.len());
+
+    // Warming up with more blocks than committed caches the keys written since genesis
+    let num_all_keys = warm_up_with_recent_blocks(
+        &executor,
+        latest_block_event.height() + 10,
+        100_000,
+        time_budget,
+    )
+    .unwrap();
+    assert!(num_all_keys > num_keys);
+
+    // The number of keys read is capped
+    let executor = BlockExecutor::<AptosVM>::new(db);
+    let num_capped_keys = warm_up_with_recent_blocks(&executor, 2, 1, time_budget).unwrap();
+    assert!(num_capped_keys < num_keys);
+}
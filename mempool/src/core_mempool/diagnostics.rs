@@ -0,0 +1,86 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::core_mempool::{index::AccountTransactions, MempoolTransaction};
+use aptos_config::network_id::PeerNetworkId;
+use aptos_crypto::HashValue;
+use std::time::SystemTime;
+
+/// The mempool's view of the sequence numbers of an account, used to debug stuck transactions
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountSequenceNumberDiagnostics {
+    /// The sequence number of the account, as last known by mempool (if any)
+    pub committed_sequence_number: Option<u64>,
+    pub pending_transactions: Vec<PendingTransactionDiagnostics>,
+    /// The missing sequence numbers that prevent pending transactions from being ready
+    pub gaps: Vec<SequenceNumberGap>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingTransactionDiagnostics {
+    pub sequence_number: u64,
+    pub hash: HashValue,
+    pub gas_unit_price: u64,
+    pub insertion_time: SystemTime,
+    /// True iff the transaction is parked, i.e., can't be included in the next block
+    pub is_parked: bool,
+    /// True iff the transaction was pulled into consensus (as of the last batch request)
+    pub in_consensus: bool,
+    /// The peer the transaction was first received from (None if submitted by a client)
+    pub received_from: Option<PeerNetworkId>,
+}
+
+/// A range of missing sequence numbers (both ends inclusive)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SequenceNumberGap {
+    pub start_sequence_number: u64,
+    pub end_sequence_number: u64,
+    /// The peer that first sent the transaction right after the gap (None if it was submitted
+    /// by a client)
+    pub first_seen_by: Option<PeerNetworkId>,
+}
+
+impl AccountSequenceNumberDiagnostics {
+    pub(crate) fn new(
+        committed_sequence_number: Option<u64>,
+        transactions: Option<&AccountTransactions>,
+        is_parked: impl Fn(&MempoolTransaction) -> bool,
+    ) -> Self {
+        let mut pending_transactions = vec![];
+        let mut gaps = vec![];
+        let mut next_sequence_number = committed_sequence_number;
+        for (sequence_number, txn) in transactions.into_iter().flatten() {
+            let sequence_number = *sequence_number;
+            let expected_sequence_number =
+                next_sequence_number.unwrap_or(txn.sequence_info.account_sequence_number);
+            if sequence_number < expected_sequence_number {
+                // Already committed, waiting to be cleaned up
+                continue;
+            }
+            if sequence_number > expected_sequence_number {
+                gaps.push(SequenceNumberGap {
+                    start_sequence_number: expected_sequence_number,
+                    end_sequence_number: sequence_number - 1,
+                    first_seen_by: txn.insertion_info.received_from,
+                });
+            }
+            next_sequence_number = Some(sequence_number + 1);
+
+            pending_transactions.push(PendingTransactionDiagnostics {
+                sequence_number,
+                hash: txn.get_committed_hash(),
+                gas_unit_price: txn.get_gas_price(),
+                insertion_time: txn.insertion_info.insertion_time,
+                is_parked: is_parked(txn),
+                in_consensus: false,
+                received_from: txn.insertion_info.received_from,
+            });
+        }
+
+        Self {
+            committed_sequence_number,
+            pending_transactions,
+            gaps,
+        }
+    }
+}
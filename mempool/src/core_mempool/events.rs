@@ -0,0 +1,60 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::core_mempool::MempoolTransaction;
+use aptos_crypto::HashValue;
+use aptos_types::account_address::AccountAddress;
+
+/// Why a transaction was removed from mempool without being committed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EvictionReason {
+    /// The transaction expired (by its expiration time or by the system TTL)
+    Expired,
+    /// The transaction was rejected during execution
+    Rejected,
+    /// The transaction was evicted from the parking lot to make room in a full mempool
+    MempoolFull,
+    /// The transaction was replaced by one with the same sequence number and a higher gas price
+    Replaced,
+}
+
+impl EvictionReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EvictionReason::Expired => "expired",
+            EvictionReason::Rejected => "rejected",
+            EvictionReason::MempoolFull => "mempool_full",
+            EvictionReason::Replaced => "replaced",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MempoolTransactionEventKind {
+    Inserted,
+    Committed,
+    Evicted(EvictionReason),
+}
+
+/// A transaction lifecycle event in core mempool, published to the subscribers (e.g., indexers
+/// that want to track pending transactions).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MempoolTransactionEvent {
+    pub sender: AccountAddress,
+    pub sequence_number: u64,
+    pub hash: HashValue,
+    pub gas_unit_price: u64,
+    pub kind: MempoolTransactionEventKind,
+}
+
+impl MempoolTransactionEvent {
+    pub(crate) fn new(txn: &MempoolTransaction, kind: MempoolTransactionEventKind) -> Self {
+        Self {
+            sender: txn.get_sender(),
+            sequence_number: txn.sequence_info.transaction_sequence_number,
+            hash: txn.get_committed_hash(),
+            gas_unit_price: txn.get_gas_price(),
+            kind,
+        }
+    }
+}
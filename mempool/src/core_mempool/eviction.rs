@@ -0,0 +1,131 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scoring of the parking lot transactions for eviction when mempool is full
+use crate::core_mempool::{MempoolTransaction, TxnPointer};
+use aptos_config::config::ParkingLotEvictionConfig;
+use std::{cmp::Ordering, time::SystemTime};
+
+/// A parking lot transaction that can be evicted, i.e., the last transaction of its sender
+#[derive(Clone, Debug)]
+pub(crate) struct EvictionCandidate {
+    pub txn_pointer: TxnPointer,
+    /// The maximum fee the transaction can pay, per byte of the transaction
+    pub fee_per_byte: u64,
+    /// The number of transactions of the sender in mempool
+    pub sender_backlog: usize,
+    pub insertion_time: SystemTime,
+}
+
+impl EvictionCandidate {
+    pub fn new(txn: &MempoolTransaction, sender_backlog: usize) -> Self {
+        let max_fee = txn.get_gas_price().saturating_mul(txn.txn.max_gas_amount());
+        let num_bytes = txn.txn.raw_txn_bytes_len().max(1) as u64;
+        Self {
+            txn_pointer: TxnPointer::from(txn),
+            fee_per_byte: max_fee / num_bytes,
+            sender_backlog,
+            insertion_time: txn.insertion_info.insertion_time,
+        }
+    }
+}
+
+/// Returns the candidate to evict, i.e., the one with the highest eviction score. The score of
+/// a candidate is the weighted sum of its ranks among the candidates by fee per byte (lowest
+/// ranks highest), sender backlog (deepest ranks highest) and age (oldest ranks highest).
+pub(crate) fn select_eviction_candidate(
+    config: &ParkingLotEvictionConfig,
+    candidates: &[EvictionCandidate],
+) -> Option<TxnPointer> {
+    let fee_density_ranks = ranks(candidates, |a, b| b.fee_per_byte.cmp(&a.fee_per_byte));
+    let sender_backlog_ranks = ranks(candidates, |a, b| a.sender_backlog.cmp(&b.sender_backlog));
+    let age_ranks = ranks(candidates, |a, b| b.insertion_time.cmp(&a.insertion_time));
+
+    (0..candidates.len())
+        .max_by_key(|&i| {
+            config.fee_density_weight * fee_density_ranks[i]
+                + config.sender_backlog_weight * sender_backlog_ranks[i]
+                + config.age_weight * age_ranks[i]
+        })
+        .map(|i| candidates[i].txn_pointer)
+}
+
+/// Returns the rank of each candidate under the given order, where the candidates that order
+/// last rank highest. Equal candidates share the same rank.
+fn ranks(
+    candidates: &[EvictionCandidate],
+    compare: impl Fn(&EvictionCandidate, &EvictionCandidate) -> Ordering,
+) -> Vec<u64> {
+    candidates
+        .iter()
+        .map(|candidate| {
+            candidates
+                .iter()
+                .filter(|other| compare(other, candidate) == Ordering::Less)
+                .count() as u64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_eviction_candidate, EvictionCandidate};
+    use crate::core_mempool::TxnPointer;
+    use aptos_config::config::{ParkingLotEvictionConfig, ParkingLotEvictionPolicy};
+    use aptos_types::account_address::AccountAddress;
+    use std::time::{Duration, SystemTime};
+
+    fn create_candidate(
+        fee_per_byte: u64,
+        sender_backlog: usize,
+        age_secs: u64,
+    ) -> EvictionCandidate {
+        EvictionCandidate {
+            txn_pointer: TxnPointer {
+                sender: AccountAddress::random(),
+                sequence_number: 0,
+            },
+            fee_per_byte,
+            sender_backlog,
+            insertion_time: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000 - age_secs),
+        }
+    }
+
+    fn create_config(
+        fee_density_weight: u64,
+        sender_backlog_weight: u64,
+        age_weight: u64,
+    ) -> ParkingLotEvictionConfig {
+        ParkingLotEvictionConfig {
+            policy: ParkingLotEvictionPolicy::Scored,
+            num_candidates: 16,
+            fee_density_weight,
+            sender_backlog_weight,
+            age_weight,
+        }
+    }
+
+    #[test]
+    fn test_select_eviction_candidate() {
+        let candidates = vec![
+            create_candidate(10, 1, 10),
+            create_candidate(1, 2, 20),
+            create_candidate(5, 9, 30),
+            create_candidate(20, 3, 90),
+        ];
+
+        // Each weight on its own selects the worst candidate for that criterion
+        let select = |config| select_eviction_candidate(&config, &candidates).unwrap();
+        assert_eq!(select(create_config(1, 0, 0)), candidates[1].txn_pointer);
+        assert_eq!(select(create_config(0, 1, 0)), candidates[2].txn_pointer);
+        assert_eq!(select(create_config(0, 0, 1)), candidates[3].txn_pointer);
+
+        // Combined, the candidate that ranks badly across the criteria is selected
+        assert_eq!(select(create_config(1, 1, 1)), candidates[2].txn_pointer);
+    }
+
+    #[test]
+    fn test_select_no_candidates() {
+        assert!(select_eviction_candidate(&create_config(1, 1, 1), &[]).is_none());
+    }
+}
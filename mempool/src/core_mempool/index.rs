@@ -475,6 +475,21 @@ impl ParkingLotIndex {
         })
     }
 
+    /// Returns the "non-ready" transactions (with highest sequence number for their account) of
+    /// up to `num_candidates` random accounts.
+    pub(crate) fn get_poppable_candidates(&self, num_candidates: usize) -> Vec<TxnPointer> {
+        let mut rng = rand::thread_rng();
+        self.data
+            .choose_multiple(&mut rng, num_candidates)
+            .filter_map(|(sender, txns)| {
+                txns.iter().next_back().map(|seq_num| TxnPointer {
+                    sender: *sender,
+                    sequence_number: *seq_num,
+                })
+            })
+            .collect()
+    }
+
     pub(crate) fn size(&self) -> usize {
         self.size
     }
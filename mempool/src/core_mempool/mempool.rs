@@ -6,15 +6,22 @@
 //! agreed upon.
 use crate::{
     core_mempool::{
+        diagnostics::AccountSequenceNumberDiagnostics,
         index::TxnPointer,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction::{
+            InsertionInfo, MempoolTransaction, SubmissionOptions, TimelineState, TtlTier,
+        },
         transaction_store::TransactionStore,
+        MempoolTransactionEvent,
     },
     counters,
     logging::{LogEntry, LogSchema, TxnsLog},
     shared_mempool::types::MultiBucketTimelineIndexIds,
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::{
+    config::{MempoolConfig, NodeConfig},
+    network_id::PeerNetworkId,
+};
 use aptos_consensus_types::common::TransactionInProgress;
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
@@ -25,24 +32,33 @@ use aptos_types::{
     vm_status::DiscardedVMStatus,
 };
 use std::{
+    cmp::min,
     collections::{HashMap, HashSet},
     time::{Duration, SystemTime},
 };
+use tokio::sync::broadcast;
 
 pub struct Mempool {
     // Stores the metadata of all transactions in mempool (of all states).
     transactions: TransactionStore,
+    // The transactions that were pulled into consensus (as of the last batch request), and
+    // aren't committed yet.
+    in_consensus: HashSet<TxnPointer>,
 
     pub system_transaction_timeout: Duration,
+    // The TTL of the transactions submitted with the short TTL tier
+    short_ttl_tier: Duration,
 }
 
 impl Mempool {
     pub fn new(config: &NodeConfig) -> Self {
         Mempool {
             transactions: TransactionStore::new(&config.mempool),
+            in_consensus: HashSet::new(),
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
+            short_ttl_tier: Duration::from_secs(config.mempool.short_ttl_tier_secs),
         }
     }
 
@@ -171,6 +187,55 @@ impl Mempool {
         self.transactions.get_by_hash(hash)
     }
 
+    /// Records the peer the transaction was first received from.
+    pub(crate) fn record_received_from(
+        &mut self,
+        sender: &AccountAddress,
+        sequence_number: u64,
+        peer: PeerNetworkId,
+    ) {
+        self.transactions
+            .record_received_from(sender, sequence_number, peer);
+    }
+
+    /// Returns the mempool's view of the sequence numbers of the account (e.g., for debugging
+    /// sequence number gaps).
+    pub(crate) fn get_sequence_number_diagnostics(
+        &self,
+        address: &AccountAddress,
+    ) -> AccountSequenceNumberDiagnostics {
+        let mut diagnostics = self.transactions.get_sequence_number_diagnostics(address);
+        for txn in diagnostics.pending_transactions.iter_mut() {
+            txn.in_consensus = self
+                .in_consensus
+                .contains(&TxnPointer::new(*address, txn.sequence_number));
+        }
+        diagnostics
+    }
+
+    /// Records the transactions that are in consensus, i.e., the transactions consensus
+    /// excluded from its latest batch request (as they're already in progress), and the
+    /// transactions returned to it.
+    pub(crate) fn record_transactions_in_consensus(
+        &mut self,
+        in_progress: impl IntoIterator<Item = TxnPointer>,
+        batch: &[SignedTransaction],
+    ) {
+        self.in_consensus = in_progress
+            .into_iter()
+            .chain(
+                batch
+                    .iter()
+                    .map(|txn| TxnPointer::new(txn.sender(), txn.sequence_number())),
+            )
+            .collect();
+    }
+
+    /// Returns true iff the transaction expired, and its submitter asked to be notified of it.
+    pub(crate) fn is_expiration_notified(&self, hash: &HashValue) -> bool {
+        self.transactions.is_expiration_notified(hash)
+    }
+
     /// Used to add a transaction to the Mempool.
     /// Performs basic validation: checks account's sequence number.
     pub(crate) fn add_txn(
@@ -180,6 +245,28 @@ impl Mempool {
         db_sequence_number: u64,
         timeline_state: TimelineState,
         client_submitted: bool,
+    ) -> MempoolStatus {
+        self.add_txn_with_options(
+            txn,
+            ranking_score,
+            db_sequence_number,
+            timeline_state,
+            client_submitted,
+            SubmissionOptions::default(),
+        )
+    }
+
+    /// Adds a transaction with the client selected submission options. The options only apply
+    /// if the transaction is inserted, so a duplicate submission can't change the TTL or the
+    /// expiration behavior of a transaction that is already in mempool.
+    pub(crate) fn add_txn_with_options(
+        &mut self,
+        txn: SignedTransaction,
+        ranking_score: u64,
+        db_sequence_number: u64,
+        timeline_state: TimelineState,
+        client_submitted: bool,
+        options: SubmissionOptions,
     ) -> MempoolStatus {
         trace!(
             LogSchema::new(LogEntry::AddTxn)
@@ -197,10 +284,14 @@ impl Mempool {
         }
 
         let now = SystemTime::now();
-        let expiration_time =
-            aptos_infallible::duration_since_epoch_at(&now) + self.system_transaction_timeout;
+        let ttl = match options.ttl_tier {
+            TtlTier::Standard => self.system_transaction_timeout,
+            // The short TTL tier can only shorten the system TTL
+            TtlTier::Short => min(self.short_ttl_tier, self.system_transaction_timeout),
+        };
+        let expiration_time = aptos_infallible::duration_since_epoch_at(&now) + ttl;
 
-        let txn_info = MempoolTransaction::new(
+        let mut txn_info = MempoolTransaction::new(
             txn,
             expiration_time,
             ranking_score,
@@ -209,6 +300,7 @@ impl Mempool {
             now,
             client_submitted,
         );
+        txn_info.expiration_behavior = options.expiration_behavior;
 
         let status = self.transactions.insert(txn_info);
         counters::core_mempool_txn_ranking_score(
@@ -403,6 +495,33 @@ impl Mempool {
         self.transactions.timeline_range(start_end_pairs)
     }
 
+    /// Subscribes to the transaction lifecycle events (inserts, commits and evictions)
+    pub(crate) fn subscribe_transaction_events(
+        &self,
+    ) -> broadcast::Receiver<MempoolTransactionEvent> {
+        self.transactions.subscribe_transaction_events()
+    }
+
+    /// Returns the accounts that have pending transactions
+    pub(crate) fn get_pending_accounts(&self) -> Vec<AccountAddress> {
+        self.transactions.get_pending_accounts()
+    }
+
+    /// Returns up to `max_txns` pending transactions of the given accounts (e.g., to persist
+    /// them across restarts).
+    pub(crate) fn get_pending_transactions(
+        &self,
+        accounts: &[AccountAddress],
+        max_txns: usize,
+    ) -> Vec<SignedTransaction> {
+        self.transactions
+            .get_pending_transactions(accounts, max_txns)
+    }
+
+    pub(crate) fn set_capacity(&mut self, config: &MempoolConfig) {
+        self.transactions.set_capacity(config);
+    }
+
     pub fn gen_snapshot(&self) -> TxnsLog {
         self.transactions.gen_snapshot()
     }
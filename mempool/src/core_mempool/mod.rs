@@ -2,14 +2,24 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod diagnostics;
+mod events;
+mod eviction;
 mod index;
 mod mempool;
 mod transaction;
 mod transaction_store;
 
 pub use self::{
+    diagnostics::{
+        AccountSequenceNumberDiagnostics, PendingTransactionDiagnostics, SequenceNumberGap,
+    },
+    events::{EvictionReason, MempoolTransactionEvent, MempoolTransactionEventKind},
     index::TxnPointer,
     mempool::Mempool as CoreMempool,
-    transaction::{MempoolTransaction, SubmittedBy, TimelineState},
+    transaction::{
+        ExpirationBehavior, MempoolTransaction, SubmissionOptions, SubmittedBy, TimelineState,
+        TtlTier,
+    },
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
 };
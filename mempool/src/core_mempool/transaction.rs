@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{core_mempool::TXN_INDEX_ESTIMATED_BYTES, counters};
+use aptos_config::network_id::PeerNetworkId;
 use aptos_crypto::HashValue;
 use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
 use serde::{Deserialize, Serialize};
@@ -24,6 +25,7 @@ pub struct MempoolTransaction {
     pub sequence_info: SequenceInfo,
     pub insertion_info: InsertionInfo,
     pub was_parked: bool,
+    pub expiration_behavior: ExpirationBehavior,
 }
 
 impl MempoolTransaction {
@@ -47,6 +49,7 @@ impl MempoolTransaction {
             timeline_state,
             insertion_info: InsertionInfo::new(insertion_time, client_submitted, timeline_state),
             was_parked: false,
+            expiration_behavior: ExpirationBehavior::Drop,
         }
     }
 
@@ -79,6 +82,34 @@ pub enum TimelineState {
     NonQualified,
 }
 
+/// What happens when a transaction expires in mempool, selected by the submitting client
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum ExpirationBehavior {
+    /// The transaction is silently dropped
+    #[default]
+    Drop,
+    /// The expiration is recorded, so that clients looking up the transaction learn that it
+    /// expired (instead of it not being found)
+    Notify,
+}
+
+/// How long a transaction can stay in mempool, selected by the submitting client
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum TtlTier {
+    /// The system transaction TTL of mempool
+    #[default]
+    Standard,
+    /// A shorter TTL, for transactions that are useless if not committed quickly
+    Short,
+}
+
+/// The options a client can select when submitting a transaction
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SubmissionOptions {
+    pub ttl_tier: TtlTier,
+    pub expiration_behavior: ExpirationBehavior,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct SequenceInfo {
     pub transaction_sequence_number: u64,
@@ -111,6 +142,8 @@ pub enum SubmittedBy {
 pub struct InsertionInfo {
     pub insertion_time: SystemTime,
     pub submitted_by: SubmittedBy,
+    /// The peer the transaction was first received from (None if submitted by a client)
+    pub received_from: Option<PeerNetworkId>,
 }
 
 impl InsertionInfo {
@@ -129,6 +162,7 @@ impl InsertionInfo {
         Self {
             insertion_time,
             submitted_by,
+            received_from: None,
         }
     }
 
@@ -4,20 +4,25 @@
 
 use crate::{
     core_mempool::{
+        diagnostics::AccountSequenceNumberDiagnostics,
+        eviction::{select_eviction_candidate, EvictionCandidate},
         index::{
             AccountTransactions, MultiBucketTimelineIndex, ParkingLotIndex, PriorityIndex,
             PriorityQueueIter, TTLIndex,
         },
         mempool::Mempool,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
-        TxnPointer,
+        transaction::{ExpirationBehavior, InsertionInfo, MempoolTransaction, TimelineState},
+        EvictionReason, MempoolTransactionEvent, MempoolTransactionEventKind, TxnPointer,
     },
     counters,
     counters::{BROADCAST_BATCHED_LABEL, BROADCAST_READY_LABEL, CONSENSUS_READY_LABEL},
     logging::{LogEntry, LogEvent, LogSchema, TxnsLog},
     shared_mempool::types::MultiBucketTimelineIndexIds,
 };
-use aptos_config::config::MempoolConfig;
+use aptos_config::{
+    config::{MempoolConfig, ParkingLotEvictionConfig, ParkingLotEvictionPolicy},
+    network_id::PeerNetworkId,
+};
 use aptos_crypto::HashValue;
 use aptos_logger::{prelude::*, Level};
 use aptos_types::{
@@ -25,6 +30,7 @@ use aptos_types::{
     mempool_status::{MempoolStatus, MempoolStatusCode},
     transaction::SignedTransaction,
 };
+use lru::LruCache;
 use std::{
     cmp::max,
     collections::HashMap,
@@ -32,6 +38,7 @@ use std::{
     ops::Bound,
     time::{Duration, SystemTime},
 };
+use tokio::sync::broadcast;
 
 /// Estimated per-txn overhead of indexes. Needs to be updated if additional indexes are added.
 pub const TXN_INDEX_ESTIMATED_BYTES: usize = size_of::<crate::core_mempool::index::OrderedQueueKey>() // priority_index
@@ -69,12 +76,18 @@ pub struct TransactionStore {
     size_bytes: usize,
     // keeps track of txns that were resubmitted with higher gas
     gas_upgraded_index: HashMap<TxnPointer, u64>,
+    // publishes the transaction lifecycle events to the subscribers
+    event_sender: broadcast::Sender<MempoolTransactionEvent>,
+    // hashes of the expired txns whose submitters asked to be notified of the expiration
+    expired_txn_notifications: LruCache<HashValue, ()>,
 
     // configuration
     capacity: usize,
     capacity_bytes: usize,
     capacity_per_user: usize,
     max_batch_bytes: u64,
+    replace_by_fee_min_bump_percent: u64,
+    parking_lot_eviction: ParkingLotEvictionConfig,
 
     // eager expiration
     eager_expire_threshold: Option<Duration>,
@@ -101,12 +114,18 @@ impl TransactionStore {
             // estimated size in bytes
             size_bytes: 0,
             gas_upgraded_index: HashMap::new(),
+            event_sender: broadcast::channel(config.transaction_events_channel_size.max(1)).0,
+            expired_txn_notifications: LruCache::new(
+                config.expiration_notifications_capacity.max(1),
+            ),
 
             // configuration
             capacity: config.capacity,
             capacity_bytes: config.capacity_bytes,
             capacity_per_user: config.capacity_per_user,
             max_batch_bytes: config.shared_mempool_max_batch_bytes,
+            replace_by_fee_min_bump_percent: config.replace_by_fee_min_bump_percent,
+            parking_lot_eviction: config.parking_lot_eviction.clone(),
 
             // eager expiration
             eager_expire_threshold: config.eager_expire_threshold_ms.map(Duration::from_millis),
@@ -156,6 +175,46 @@ impl TransactionStore {
         }
     }
 
+    /// Records the peer the transaction was received from, unless it was already received from
+    /// another peer or submitted by a client
+    pub(crate) fn record_received_from(
+        &mut self,
+        address: &AccountAddress,
+        sequence_number: u64,
+        peer: PeerNetworkId,
+    ) {
+        if let Some(txn) = self
+            .transactions
+            .get_mut(address)
+            .and_then(|txns| txns.get_mut(&sequence_number))
+        {
+            let insertion_info = &mut txn.insertion_info;
+            if insertion_info.submitted_by != SubmittedBy::Client
+                && insertion_info.received_from.is_none()
+            {
+                insertion_info.received_from = Some(peer);
+            }
+        }
+    }
+
+    /// Returns the mempool's view of the sequence numbers of the account
+    pub(crate) fn get_sequence_number_diagnostics(
+        &self,
+        address: &AccountAddress,
+    ) -> AccountSequenceNumberDiagnostics {
+        AccountSequenceNumberDiagnostics::new(
+            self.sequence_numbers.get(address).copied(),
+            self.transactions.get(address),
+            |txn| !self.priority_index.contains(txn),
+        )
+    }
+
+    /// Returns true iff the transaction expired in mempool, and its submitter asked to be
+    /// notified of the expiration
+    pub(crate) fn is_expiration_notified(&self, hash: &HashValue) -> bool {
+        self.expired_txn_notifications.contains(hash)
+    }
+
     pub(crate) fn get_insertion_info_and_bucket(
         &self,
         address: &AccountAddress,
@@ -200,6 +259,8 @@ impl TransactionStore {
 
         // If the transaction is already in Mempool, we only allow the user to
         // increase the gas unit price to speed up a transaction, but not the max gas.
+        // The increase must be at least the configured minimum bump, so that senders
+        // can't repeatedly re-prioritize their transactions for free.
         //
         // Transactions with all the same inputs (but possibly signed differently) are idempotent
         // since the raw transaction is the same
@@ -222,9 +283,28 @@ impl TransactionStore {
                             .to_string(),
                     );
                 } else if current_version.get_gas_price() < txn.get_gas_price() {
-                    // Update txn if gas unit price is a larger value than before
+                    let min_gas_price = Self::min_replacement_gas_price(
+                        current_version.get_gas_price(),
+                        self.replace_by_fee_min_bump_percent,
+                    );
+                    if txn.get_gas_price() < min_gas_price {
+                        return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
+                            format!(
+                                "Transaction already in mempool with gas unit price {}. Replacing it requires a gas unit price of at least {} (minimum bump of {}%)",
+                                current_version.get_gas_price(),
+                                min_gas_price,
+                                self.replace_by_fee_min_bump_percent,
+                            ),
+                        );
+                    }
+
+                    // Update txn if gas unit price is sufficiently larger than before
                     if let Some(txn) = txns.remove(&txn_seq_num) {
                         self.index_remove(&txn);
+                        self.publish_event(
+                            &txn,
+                            MempoolTransactionEventKind::Evicted(EvictionReason::Replaced),
+                        );
                     };
                     gas_upgraded = true;
                 } else if current_version.get_gas_price() > txn.get_gas_price() {
@@ -277,6 +357,12 @@ impl TransactionStore {
                 self.gas_upgraded_index
                     .insert(TxnPointer::from(&txn), txn.get_gas_price());
             }
+            if self.event_sender.receiver_count() > 0 {
+                let _ = self.event_sender.send(MempoolTransactionEvent::new(
+                    &txn,
+                    MempoolTransactionEventKind::Inserted,
+                ));
+            }
             txns.insert(txn_seq_num, txn);
             self.track_indices();
         }
@@ -284,6 +370,13 @@ impl TransactionStore {
         MempoolStatus::new(MempoolStatusCode::Accepted)
     }
 
+    /// Returns the minimum gas unit price required to replace a transaction with the given gas
+    /// unit price, i.e., the price bumped by `min_bump_percent` (rounded up), and at least by 1.
+    fn min_replacement_gas_price(gas_price: u64, min_bump_percent: u64) -> u64 {
+        let bump = (gas_price as u128 * min_bump_percent as u128 + 99) / 100;
+        gas_price.saturating_add(bump.clamp(1, u64::MAX as u128) as u64)
+    }
+
     fn track_indices(&self) {
         counters::core_mempool_index_size(
             counters::SYSTEM_TTL_INDEX_LABEL,
@@ -323,7 +416,7 @@ impl TransactionStore {
     ) -> bool {
         if self.is_full() && self.check_txn_ready(txn, curr_sequence_number) {
             // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
-            if let Some(txn_pointer) = self.parking_lot_index.get_poppable() {
+            if let Some(txn_pointer) = self.get_parking_lot_eviction_candidate() {
                 if let Some(txn) = self
                     .transactions
                     .get_mut(&txn_pointer.sender)
@@ -335,13 +428,50 @@ impl TransactionStore {
                             txn.sequence_info.transaction_sequence_number
                         ))
                     );
+                    counters::core_mempool_parking_lot_evicted(
+                        self.parking_lot_eviction.policy,
+                        txn.get_gas_price(),
+                        txn.get_gas_price().saturating_mul(txn.txn.max_gas_amount()),
+                    );
                     self.index_remove(&txn);
+                    self.publish_event(
+                        &txn,
+                        MempoolTransactionEventKind::Evicted(EvictionReason::MempoolFull),
+                    );
                 }
             }
         }
         self.is_full()
     }
 
+    /// Returns the parking lot transaction to evict, according to the eviction policy
+    fn get_parking_lot_eviction_candidate(&self) -> Option<TxnPointer> {
+        match self.parking_lot_eviction.policy {
+            ParkingLotEvictionPolicy::Random => self.parking_lot_index.get_poppable(),
+            ParkingLotEvictionPolicy::Scored => {
+                let candidates: Vec<_> = self
+                    .parking_lot_index
+                    .get_poppable_candidates(self.parking_lot_eviction.num_candidates)
+                    .into_iter()
+                    .filter_map(|txn_pointer| {
+                        let txns = self.transactions.get(&txn_pointer.sender)?;
+                        txns.get(&txn_pointer.sequence_number)
+                            .map(|txn| EvictionCandidate::new(txn, txns.len()))
+                    })
+                    .collect();
+                select_eviction_candidate(&self.parking_lot_eviction, &candidates)
+            },
+        }
+    }
+
+    /// Updates the capacity limits. Transactions already in mempool are kept, even if they
+    /// exceed the new limits.
+    pub(crate) fn set_capacity(&mut self, config: &MempoolConfig) {
+        self.capacity = config.capacity;
+        self.capacity_bytes = config.capacity_bytes;
+        self.capacity_per_user = config.capacity_per_user;
+    }
+
     fn is_full(&self) -> bool {
         self.system_ttl_index.size() >= self.capacity || self.size_bytes >= self.capacity_bytes
     }
@@ -495,6 +625,7 @@ impl TransactionStore {
                     transaction.sequence_info.transaction_sequence_number,
                 );
                 self.index_remove(transaction);
+                self.publish_event(transaction, MempoolTransactionEventKind::Committed);
             }
             trace!(
                 LogSchema::new(LogEntry::CleanCommittedTxn).txns(rm_txns),
@@ -533,6 +664,10 @@ impl TransactionStore {
                 txns.remove(&sequence_number);
             }
             self.index_remove(&txn_to_remove);
+            self.publish_event(
+                &txn_to_remove,
+                MempoolTransactionEventKind::Evicted(EvictionReason::Rejected),
+            );
 
             if aptos_logger::enabled!(Level::Trace) {
                 let mut txns_log = TxnsLog::new();
@@ -545,6 +680,23 @@ impl TransactionStore {
         }
     }
 
+    /// Returns a receiver of the transaction lifecycle events (inserts, commits and evictions)
+    pub(crate) fn subscribe_transaction_events(
+        &self,
+    ) -> broadcast::Receiver<MempoolTransactionEvent> {
+        self.event_sender.subscribe()
+    }
+
+    /// Publishes the transaction event, if there are any subscribers
+    fn publish_event(&self, txn: &MempoolTransaction, kind: MempoolTransactionEventKind) {
+        if self.event_sender.receiver_count() > 0 {
+            // Sending only fails if all the subscribers were dropped in the meantime
+            let _ = self
+                .event_sender
+                .send(MempoolTransactionEvent::new(txn, kind));
+        }
+    }
+
     /// Removes transaction from all indexes. Only call after removing from main transactions DS.
     fn index_remove(&mut self, txn: &MempoolTransaction) {
         counters::CORE_MEMPOOL_REMOVED_TXNS.inc();
@@ -743,6 +895,14 @@ impl TransactionStore {
 
                     // remove txn
                     self.index_remove(&txn);
+                    if txn.expiration_behavior == ExpirationBehavior::Notify {
+                        self.expired_txn_notifications
+                            .put(txn.get_committed_hash(), ());
+                    }
+                    self.publish_event(
+                        &txn,
+                        MempoolTransactionEventKind::Evicted(EvictionReason::Expired),
+                    );
                 }
             }
         }
@@ -759,6 +919,26 @@ impl TransactionStore {
         self.priority_index.iter()
     }
 
+    /// Returns the accounts that have pending transactions
+    pub(crate) fn get_pending_accounts(&self) -> Vec<AccountAddress> {
+        self.transactions.keys().copied().collect()
+    }
+
+    /// Returns up to `max_txns` pending transactions of the given accounts, with the
+    /// transactions of each account in sequence number order.
+    pub(crate) fn get_pending_transactions(
+        &self,
+        accounts: &[AccountAddress],
+        max_txns: usize,
+    ) -> Vec<SignedTransaction> {
+        accounts
+            .iter()
+            .filter_map(|account| self.transactions.get(account))
+            .flat_map(|txns| txns.values().map(|txn| txn.txn.clone()))
+            .take(max_txns)
+            .collect()
+    }
+
     pub(crate) fn gen_snapshot(&self) -> TxnsLog {
         let mut txns_log = TxnsLog::new();
         for (account, txns) in self.transactions.iter() {
@@ -2,11 +2,15 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_config::network_id::{NetworkId, PeerNetworkId};
+use aptos_config::{
+    config::ParkingLotEvictionPolicy,
+    network_id::{NetworkId, PeerNetworkId},
+};
 use aptos_metrics_core::{
     exponential_buckets, histogram_opts, op_counters::DurationHistogram, register_histogram,
-    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge_vec,
-    Histogram, HistogramTimer, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
+    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, Histogram, HistogramTimer, HistogramVec, IntCounter, IntCounterVec,
+    IntGauge, IntGaugeVec,
 };
 use aptos_short_hex_str::AsShortHexStr;
 use once_cell::sync::Lazy;
@@ -28,6 +32,10 @@ pub const COMMIT_REJECTED_LABEL: &str = "commit_rejected";
 pub const COMMIT_REJECTED_DUPLICATE_LABEL: &str = "commit_rejected_duplicate";
 pub const COMMIT_IGNORED_LABEL: &str = "commit_ignored";
 pub const CONSENSUS_READY_LABEL: &str = "consensus_ready";
+
+// Submission rate limit labels
+pub const RATE_LIMIT_SENDER_LABEL: &str = "sender";
+pub const RATE_LIMIT_BUCKET_LABEL: &str = "bucket";
 pub const CONSENSUS_PULLED_LABEL: &str = "consensus_pulled";
 pub const BROADCAST_READY_LABEL: &str = "broadcast_ready";
 pub const BROADCAST_BATCHED_LABEL: &str = "broadcast_batched";
@@ -59,6 +67,7 @@ pub const VM_VALIDATION_LABEL: &str = "vm_validation";
 // Txn process result labels
 pub const CLIENT_LABEL: &str = "client";
 pub const SUCCESS_LABEL: &str = "success";
+pub const REJECTED_LABEL: &str = "rejected";
 
 // Bounded executor task labels
 pub const CLIENT_EVENT_LABEL: &str = "client_event";
@@ -78,6 +87,7 @@ pub const ACK_TXNS: &str = "ack_txns";
 pub const EXPIRED_BROADCAST_LABEL: &str = "expired";
 pub const RETRY_BROADCAST_LABEL: &str = "retry";
 pub const BACKPRESSURE_BROADCAST_LABEL: &str = "backpressure";
+pub const UPCOMING_PROPOSER_BROADCAST_LABEL: &str = "upcoming_proposer";
 
 // ACK direction labels
 pub const RECEIVED_LABEL: &str = "received";
@@ -172,6 +182,55 @@ pub static CORE_MEMPOOL_REMOVED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+static CORE_MEMPOOL_PARKING_LOT_EVICTED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_core_mempool_parking_lot_evicted_txns_count",
+        "Number of txns evicted from the parking lot because core mempool is full",
+        &["policy"]
+    )
+    .unwrap()
+});
+
+static CORE_MEMPOOL_PARKING_LOT_EVICTED_MAX_FEE: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_core_mempool_parking_lot_evicted_max_fee",
+        "Sum of the max fees (gas unit price * max gas amount) of the txns evicted from the parking lot",
+        &["policy"]
+    )
+    .unwrap()
+});
+
+static CORE_MEMPOOL_PARKING_LOT_EVICTED_GAS_UNIT_PRICE: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_core_mempool_parking_lot_evicted_gas_unit_price",
+        "Gas unit price of the txns evicted from the parking lot",
+        &["policy"],
+        exponential_buckets(/*start=*/ 100.0, /*factor=*/ 2.0, /*count=*/ 16).unwrap()
+    )
+    .unwrap()
+});
+
+/// Tracks a txn evicted from the parking lot, to analyze the value lost by each eviction policy
+pub fn core_mempool_parking_lot_evicted(
+    policy: ParkingLotEvictionPolicy,
+    gas_unit_price: u64,
+    max_fee: u64,
+) {
+    let policy = match policy {
+        ParkingLotEvictionPolicy::Random => "random",
+        ParkingLotEvictionPolicy::Scored => "scored",
+    };
+    CORE_MEMPOOL_PARKING_LOT_EVICTED_TXNS
+        .with_label_values(&[policy])
+        .inc();
+    CORE_MEMPOOL_PARKING_LOT_EVICTED_MAX_FEE
+        .with_label_values(&[policy])
+        .inc_by(max_fee);
+    CORE_MEMPOOL_PARKING_LOT_EVICTED_GAS_UNIT_PRICE
+        .with_label_values(&[policy])
+        .observe(gas_unit_price as f64);
+}
+
 /// Counter tracking number of txns received that are idempotent duplicates
 pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -580,6 +639,40 @@ pub fn invalid_ack_inc(network_id: NetworkId, label: &'static str) {
         .inc();
 }
 
+/// Counter for client submitted transactions rejected by the submission rate limiter
+static SUBMISSION_RATE_LIMITED_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_mempool_submission_rate_limited_count",
+        "Number of client submitted transactions rejected by the submission rate limiter",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+pub fn submission_rate_limited_inc(reason: &'static str) {
+    SUBMISSION_RATE_LIMITED_COUNT
+        .with_label_values(&[reason])
+        .inc();
+}
+
+/// Counter for number of transactions rejected by the transaction filter policy
+pub static TRANSACTION_FILTER_REJECTED_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_mempool_transaction_filter_rejected_count",
+        "Number of transactions rejected by the transaction filter policy"
+    )
+    .unwrap()
+});
+
+/// Gauge for the version of the active transaction filter policy
+pub static TRANSACTION_FILTER_POLICY_VERSION: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_mempool_transaction_filter_policy_version",
+        "Version of the active transaction filter policy"
+    )
+    .unwrap()
+});
+
 /// Counter for number of times a DB read resulted in error
 pub static DB_ERROR: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -604,6 +697,34 @@ pub fn active_upstream_peers(network_id: &NetworkId) -> IntGauge {
     ACTIVE_UPSTREAM_PEERS_COUNT.with_label_values(&[network_id.as_str()])
 }
 
+/// Number of transactions written to the mempool journal by the last flush
+pub static MEMPOOL_JOURNAL_TXNS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_mempool_journal_txns",
+        "Number of transactions written to the mempool journal by the last flush"
+    )
+    .unwrap()
+});
+
+/// Latency of writing the pending transactions to the mempool journal
+pub static MEMPOOL_JOURNAL_WRITE_LATENCY: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_mempool_journal_write_latency",
+        "Latency of writing the pending transactions to the mempool journal"
+    )
+    .unwrap()
+});
+
+/// Counter for the transactions reloaded from the mempool journal on startup, by status
+pub static MEMPOOL_JOURNAL_RELOADED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_mempool_journal_reloaded_txns",
+        "Number of transactions reloaded from the mempool journal on startup, by status",
+        &["status"]
+    )
+    .unwrap()
+});
+
 /// Duration of each run of the event loop.
 pub static MAIN_LOOP: Lazy<DurationHistogram> = Lazy::new(|| {
     DurationHistogram::new(
@@ -58,13 +58,22 @@
 
 #[cfg(any(test, feature = "fuzzing"))]
 mod tests;
+pub use core_mempool::{
+    AccountSequenceNumberDiagnostics, EvictionReason, ExpirationBehavior, MempoolTransactionEvent,
+    MempoolTransactionEventKind, PendingTransactionDiagnostics, SequenceNumberGap,
+    SubmissionOptions, TtlTier,
+};
 pub use shared_mempool::{
     bootstrap, network,
     network::MempoolSyncMsg,
+    transaction_filter::{
+        SignedTransactionFilterPolicy, TransactionFilterPolicy, TransactionFilterRule,
+    },
     types::{
         MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, QuorumStoreRequest,
         QuorumStoreResponse, SubmissionStatus,
     },
+    MempoolCapacityUpdater, TransactionFilter,
 };
 #[cfg(any(test, feature = "fuzzing"))]
 pub use tests::{fuzzing, mocks};
@@ -5,7 +5,7 @@
 //! Processes that are directly spawned by shared mempool runtime initialization
 use super::types::MempoolClientRequest;
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, SubmissionOptions, TimelineState},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     network::MempoolSyncMsg,
@@ -14,8 +14,9 @@ use crate::{
         tasks::process_committed_transactions,
         types::{notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification},
     },
-    MempoolEventsReceiver, QuorumStoreRequest,
+    MempoolEventsReceiver, QuorumStoreRequest, SubmissionStatus,
 };
+use anyhow::Result;
 use aptos_bounded_executor::BoundedExecutor;
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
 use aptos_consensus_types::common::TransactionSummary;
@@ -30,10 +31,13 @@ use aptos_network::{
     },
     protocols::network::Event,
 };
-use aptos_types::on_chain_config::{OnChainConfigPayload, OnChainConfigProvider};
+use aptos_types::{
+    on_chain_config::{OnChainConfigPayload, OnChainConfigProvider},
+    transaction::SignedTransaction,
+};
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
-    channel::mpsc,
+    channel::{mpsc, oneshot},
     stream::{select_all, FuturesUnordered},
     FutureExt, StreamExt,
 };
@@ -136,24 +140,17 @@ async fn handle_client_request<NetworkClient, TransactionValidator>(
 {
     match request {
         MempoolClientRequest::SubmitTransaction(txn, callback) => {
-            // This timer measures how long it took for the bounded executor to *schedule* the
-            // task.
-            let _timer = counters::task_spawn_latency_timer(
-                counters::CLIENT_EVENT_LABEL,
-                counters::SPAWN_LABEL,
-            );
-            // This timer measures how long it took for the task to go from scheduled to started.
-            let task_start_timer = counters::task_spawn_latency_timer(
-                counters::CLIENT_EVENT_LABEL,
-                counters::START_LABEL,
-            );
-            bounded_executor
-                .spawn(tasks::process_client_transaction_submission(
-                    smp.clone(),
-                    txn,
-                    callback,
-                    task_start_timer,
-                ))
+            spawn_client_transaction_submission(
+                smp,
+                bounded_executor,
+                txn,
+                SubmissionOptions::default(),
+                callback,
+            )
+            .await;
+        },
+        MempoolClientRequest::SubmitTransactionWithOptions(txn, options, callback) => {
+            spawn_client_transaction_submission(smp, bounded_executor, txn, options, callback)
                 .await;
         },
         MempoolClientRequest::GetTransactionByHash(hash, callback) => {
@@ -177,9 +174,56 @@ async fn handle_client_request<NetworkClient, TransactionValidator>(
                 ))
                 .await;
         },
+        MempoolClientRequest::GetSequenceNumberDiagnostics(address, callback) => {
+            let diagnostics = smp.mempool.lock().get_sequence_number_diagnostics(&address);
+            if callback.send(diagnostics).is_err() {
+                counters::CLIENT_CALLBACK_FAIL.inc();
+            }
+        },
+        MempoolClientRequest::IsTransactionExpired(hash, callback) => {
+            let is_expired = smp.mempool.lock().is_expiration_notified(&hash);
+            if callback.send(is_expired).is_err() {
+                counters::CLIENT_CALLBACK_FAIL.inc();
+            }
+        },
+        MempoolClientRequest::SubscribeTransactionEvents(callback) => {
+            let receiver = smp.mempool.lock().subscribe_transaction_events();
+            if callback.send(receiver).is_err() {
+                counters::CLIENT_CALLBACK_FAIL.inc();
+            }
+        },
     }
 }
 
+/// Spawns a task to process the transaction submitted by a client
+async fn spawn_client_transaction_submission<NetworkClient, TransactionValidator>(
+    smp: &mut SharedMempool<NetworkClient, TransactionValidator>,
+    bounded_executor: &BoundedExecutor,
+    txn: SignedTransaction,
+    options: SubmissionOptions,
+    callback: oneshot::Sender<Result<SubmissionStatus>>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg> + 'static,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    // This timer measures how long it took for the bounded executor to *schedule* the
+    // task.
+    let _timer =
+        counters::task_spawn_latency_timer(counters::CLIENT_EVENT_LABEL, counters::SPAWN_LABEL);
+    // This timer measures how long it took for the task to go from scheduled to started.
+    let task_start_timer =
+        counters::task_spawn_latency_timer(counters::CLIENT_EVENT_LABEL, counters::START_LABEL);
+    bounded_executor
+        .spawn(tasks::process_client_transaction_submission(
+            smp.clone(),
+            txn,
+            options,
+            callback,
+            task_start_timer,
+        ))
+        .await;
+}
+
 /// Handle removing committed transactions from local mempool immediately.  This should be done
 /// immediately to ensure broadcasts of committed transactions stop as soon as possible.
 fn handle_commit_notification<NetworkClient, TransactionValidator>(
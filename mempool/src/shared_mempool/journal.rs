@@ -0,0 +1,148 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A write-behind journal of the pending mempool transactions, so that they survive restarts
+use crate::{
+    core_mempool::CoreMempool,
+    counters,
+    logging::{LogEntry, LogEvent, LogSchema},
+};
+use anyhow::{Context, Result};
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use futures::StreamExt;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::time::{interval_at, Instant};
+use tokio_stream::wrappers::IntervalStream;
+
+/// The number of accounts whose transactions are copied per acquisition of the mempool lock
+const SNAPSHOT_BATCH_ACCOUNTS: usize = 100;
+
+/// The journal holds the latest snapshot of the pending transactions. Snapshots are written to a
+/// temporary file first and then renamed, so a crash mid-write never corrupts the journal.
+#[derive(Clone, Debug)]
+pub(crate) struct MempoolJournal {
+    path: PathBuf,
+}
+
+impl MempoolJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Replaces the journal contents with the given transactions
+    pub fn write(&self, txns: &[SignedTransaction]) -> Result<()> {
+        let bytes = bcs::to_bytes(txns).context("Failed to serialize the journal")?;
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)
+            .with_context(|| format!("Failed to write the journal to {:?}", tmp_path))?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("Failed to move the journal to {:?}", self.path))?;
+        Ok(())
+    }
+
+    /// Reads the transactions from the journal. Returns no transactions if there is no journal.
+    pub fn read(&self) -> Result<Vec<SignedTransaction>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let bytes = fs::read(&self.path)
+            .with_context(|| format!("Failed to read the journal from {:?}", self.path))?;
+        bcs::from_bytes(&bytes).context("Failed to deserialize the journal")
+    }
+}
+
+/// Copies up to `max_txns` pending transactions from core mempool. The transactions are copied in
+/// small batches of accounts, so that the mempool lock is never held for long. The snapshot is
+/// thus not atomic, which is fine as the transactions are re-validated when they're reloaded.
+fn snapshot_pending_transactions(
+    mempool: &Mutex<CoreMempool>,
+    max_txns: usize,
+) -> Vec<SignedTransaction> {
+    let accounts: Vec<AccountAddress> = mempool.lock().get_pending_accounts();
+    let mut txns = vec![];
+    for accounts in accounts.chunks(SNAPSHOT_BATCH_ACCOUNTS) {
+        let max_batch_txns = max_txns.saturating_sub(txns.len());
+        if max_batch_txns == 0 {
+            break;
+        }
+        txns.extend(
+            mempool
+                .lock()
+                .get_pending_transactions(accounts, max_batch_txns),
+        );
+    }
+    txns
+}
+
+/// Periodically writes the pending transactions in core mempool to the journal.
+pub(crate) async fn journal_job(
+    mempool: Arc<Mutex<CoreMempool>>,
+    journal: MempoolJournal,
+    flush_interval_ms: u64,
+    max_journal_txns: usize,
+) {
+    info!(LogSchema::event_log(LogEntry::Journal, LogEvent::Start));
+    // The first flush is delayed, so that the journal isn't overwritten before it's reloaded
+    let flush_interval = Duration::from_millis(flush_interval_ms);
+    let mut interval =
+        IntervalStream::new(interval_at(Instant::now() + flush_interval, flush_interval));
+    while let Some(_interval) = interval.next().await {
+        let mempool = mempool.clone();
+        let journal = journal.clone();
+        let _timer = counters::MEMPOOL_JOURNAL_WRITE_LATENCY.start_timer();
+        let result = tokio::task::spawn_blocking(move || {
+            let txns = snapshot_pending_transactions(&mempool, max_journal_txns);
+            journal.write(&txns).map(|()| txns.len())
+        })
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result);
+        match result {
+            Ok(num_txns) => counters::MEMPOOL_JOURNAL_TXNS.set(num_txns as i64),
+            Err(error) => warn!(LogSchema::new(LogEntry::Journal).error(&error)),
+        }
+    }
+    error!(LogSchema::event_log(
+        LogEntry::Journal,
+        LogEvent::Terminated
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MempoolJournal;
+    use crate::tests::common::TestTransaction;
+    use aptos_temppath::TempPath;
+
+    #[test]
+    fn test_journal_round_trip() {
+        let temp_path = TempPath::new();
+        temp_path.create_as_dir().unwrap();
+        let journal = MempoolJournal::new(temp_path.path().join("mempool_journal.bcs"));
+
+        // There are no transactions without a journal
+        assert!(journal.read().unwrap().is_empty());
+
+        // Write the transactions and read them back
+        let txns: Vec<_> = (0..10)
+            .map(|i| TestTransaction::new(i, 0, 1).make_signed_transaction())
+            .collect();
+        journal.write(&txns).unwrap();
+        assert_eq!(journal.read().unwrap(), txns);
+
+        // Later writes replace the journal
+        journal.write(&txns[..3]).unwrap();
+        assert_eq!(journal.read().unwrap(), txns[..3].to_vec());
+    }
+}
@@ -6,8 +6,12 @@ pub mod network;
 pub use network::MempoolSyncMsg;
 mod runtime;
 pub(crate) mod types;
-pub use runtime::bootstrap;
 #[cfg(any(test, feature = "fuzzing"))]
 pub(crate) use runtime::start_shared_mempool;
+pub use runtime::{bootstrap, MempoolCapacityUpdater};
 mod coordinator;
+pub(crate) mod journal;
+pub(crate) mod rate_limiter;
 pub(crate) mod tasks;
+pub mod transaction_filter;
+pub use transaction_filter::TransactionFilter;
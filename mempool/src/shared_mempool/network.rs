@@ -33,7 +33,7 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
-    collections::{hash_map::RandomState, BTreeMap, BTreeSet, HashMap},
+    collections::{hash_map::RandomState, BTreeMap, BTreeSet, HashMap, HashSet},
     hash::{BuildHasher, Hasher},
     ops::Add,
     sync::Arc,
@@ -82,6 +82,8 @@ pub(crate) struct MempoolNetworkInterface<NetworkClient> {
     network_client: NetworkClient,
     sync_states: Arc<RwLock<HashMap<PeerNetworkId, PeerSyncState>>>,
     prioritized_peers: Arc<Mutex<Vec<PeerNetworkId>>>,
+    // The validators expected to propose the current and upcoming rounds (validators only)
+    upcoming_proposers: Arc<RwLock<HashSet<PeerId>>>,
     role: RoleType,
     mempool_config: MempoolConfig,
     prioritized_peers_comparator: PrioritizedPeersComparator,
@@ -97,6 +99,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             network_client,
             sync_states: Arc::new(RwLock::new(HashMap::new())),
             prioritized_peers: Arc::new(Mutex::new(Vec::new())),
+            upcoming_proposers: Arc::new(RwLock::new(HashSet::new())),
             role,
             mempool_config,
             prioritized_peers_comparator: PrioritizedPeersComparator::new(),
@@ -190,6 +193,18 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         let _ = std::mem::replace(&mut *prioritized_peers, peers);
     }
 
+    /// Replaces the validators expected to propose the current and upcoming rounds
+    pub fn update_upcoming_proposers(&self, proposers: Vec<PeerId>) {
+        *self.upcoming_proposers.write() = proposers.into_iter().collect();
+    }
+
+    /// Upcoming proposers are only prioritized on the validator network, as that's the only
+    /// network where the local node broadcasts to them directly
+    pub fn is_upcoming_proposer(&self, peer: &PeerNetworkId) -> bool {
+        peer.network_id().is_validator_network()
+            && self.upcoming_proposers.read().contains(&peer.peer_id())
+    }
+
     pub fn is_validator(&self) -> bool {
         self.role.is_validator()
     }
@@ -502,6 +517,12 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
                 counters::BACKPRESSURE_BROADCAST_LABEL,
             );
         }
+        if self.is_upcoming_proposer(&peer) {
+            counters::shared_mempool_broadcast_type_inc(
+                network_id,
+                counters::UPCOMING_PROPOSER_BROADCAST_LABEL,
+            );
+        }
         Ok(())
     }
 
@@ -567,6 +588,7 @@ impl PrioritizedPeersComparator {
 mod test {
     use super::*;
     use aptos_config::network_id::NetworkId;
+    use aptos_network::application::{interface::NetworkClient, storage::PeersAndMetadata};
     use aptos_types::PeerId;
 
     #[test]
@@ -610,4 +632,32 @@ mod test {
         // Same the only equal case
         assert_eq!(Ordering::Equal, comparator.compare(&val_1, &val_1));
     }
+
+    #[test]
+    fn check_upcoming_proposers() {
+        let network_client: NetworkClient<MempoolSyncMsg> =
+            NetworkClient::new(vec![], vec![], HashMap::new(), PeersAndMetadata::new(&[]));
+        let network_interface = MempoolNetworkInterface::new(
+            network_client,
+            RoleType::Validator,
+            MempoolConfig::default(),
+        );
+
+        let peer_id_1 = PeerId::from_hex_literal("0x1").unwrap();
+        let peer_id_2 = PeerId::from_hex_literal("0x2").unwrap();
+        let val_1 = PeerNetworkId::new(NetworkId::Validator, peer_id_1);
+        let val_2 = PeerNetworkId::new(NetworkId::Validator, peer_id_2);
+        let vfn_1 = PeerNetworkId::new(NetworkId::Vfn, peer_id_1);
+
+        // Only the upcoming proposers on the validator network are prioritized
+        network_interface.update_upcoming_proposers(vec![peer_id_1]);
+        assert!(network_interface.is_upcoming_proposer(&val_1));
+        assert!(!network_interface.is_upcoming_proposer(&val_2));
+        assert!(!network_interface.is_upcoming_proposer(&vfn_1));
+
+        // Updates replace the upcoming proposers
+        network_interface.update_upcoming_proposers(vec![peer_id_2]);
+        assert!(!network_interface.is_upcoming_proposer(&val_1));
+        assert!(network_interface.is_upcoming_proposer(&val_2));
+    }
 }
@@ -0,0 +1,296 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admission control for the transactions submitted directly by clients
+use crate::counters;
+use aptos_config::config::SubmissionRateLimitConfig;
+use aptos_types::{
+    account_address::AccountAddress,
+    mempool_status::{MempoolStatus, MempoolStatusCode},
+    transaction::SignedTransaction,
+};
+use lru::LruCache;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// The recent submissions of a single sender that were admitted by the rate limiter
+struct SenderSubmissions {
+    /// The submission time and gas bucket index (if any) of each submission in the window
+    submissions: VecDeque<(Instant, Option<usize>)>,
+}
+
+impl SenderSubmissions {
+    fn new() -> Self {
+        Self {
+            submissions: VecDeque::new(),
+        }
+    }
+
+    /// Drops the submissions that fell out of the window
+    fn expire(&mut self, window_start: Instant) {
+        while let Some((submitted_at, _)) = self.submissions.front() {
+            if *submitted_at > window_start {
+                break;
+            }
+            self.submissions.pop_front();
+        }
+    }
+
+    fn num_in_bucket(&self, bucket: usize) -> usize {
+        self.submissions
+            .iter()
+            .filter(|(_, submission_bucket)| *submission_bucket == Some(bucket))
+            .count()
+    }
+}
+
+/// Limits the number of transactions each sender can submit over a sliding time window, both
+/// overall and per gas unit price bucket.
+pub(crate) struct SubmissionRateLimiter {
+    config: SubmissionRateLimitConfig,
+    senders: LruCache<AccountAddress, SenderSubmissions>,
+}
+
+impl SubmissionRateLimiter {
+    pub fn new(config: SubmissionRateLimitConfig) -> Self {
+        let senders = LruCache::new(config.max_tracked_senders.max(1));
+        Self { config, senders }
+    }
+
+    /// Returns the index of the bucket policy the gas unit price falls in (if any)
+    fn bucket_index(&self, gas_unit_price: u64) -> Option<usize> {
+        self.config
+            .bucket_policies
+            .iter()
+            .rposition(|policy| policy.min_gas_unit_price <= gas_unit_price)
+    }
+
+    /// Checks whether the transaction can be admitted at time `now`. Otherwise, returns the
+    /// rejection status with the reason.
+    ///
+    /// This doesn't record the submission: the caller records it with [`Self::record`] once
+    /// the transaction is accepted, so that invalid transactions (e.g., with a spoofed sender)
+    /// can't use up the limits of the sender.
+    pub fn check(&mut self, txn: &SignedTransaction, now: Instant) -> Result<(), MempoolStatus> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let bucket = self.bucket_index(txn.gas_unit_price());
+        let window_start = now.checked_sub(Duration::from_millis(self.config.window_ms));
+        // Unknown senders are not tracked until they get a transaction accepted
+        let sender_submissions = match self.senders.get_mut(&txn.sender()) {
+            Some(sender_submissions) => sender_submissions,
+            None => return Ok(()),
+        };
+        if let Some(window_start) = window_start {
+            sender_submissions.expire(window_start);
+        }
+
+        // Verify the overall limit of the sender
+        if sender_submissions.submissions.len() >= self.config.max_txns_per_sender {
+            counters::submission_rate_limited_inc(counters::RATE_LIMIT_SENDER_LABEL);
+            return Err(
+                MempoolStatus::new(MempoolStatusCode::RateLimited).with_message(format!(
+                    "Sender {} exceeded the limit of {} transactions per {}ms",
+                    txn.sender(),
+                    self.config.max_txns_per_sender,
+                    self.config.window_ms
+                )),
+            );
+        }
+
+        // Verify the limit of the gas bucket
+        if let Some(bucket) = bucket {
+            let policy = &self.config.bucket_policies[bucket];
+            if sender_submissions.num_in_bucket(bucket) >= policy.max_txns_per_sender {
+                counters::submission_rate_limited_inc(counters::RATE_LIMIT_BUCKET_LABEL);
+                return Err(
+                    MempoolStatus::new(MempoolStatusCode::RateLimited).with_message(format!(
+                        "Sender {} exceeded the limit of {} transactions per {}ms with gas unit price >= {}",
+                        txn.sender(),
+                        policy.max_txns_per_sender,
+                        self.config.window_ms,
+                        policy.min_gas_unit_price
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the submission of an accepted transaction at time `now`
+    pub fn record(&mut self, txn: &SignedTransaction, now: Instant) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let bucket = self.bucket_index(txn.gas_unit_price());
+        if !self.senders.contains(&txn.sender()) {
+            self.senders.put(txn.sender(), SenderSubmissions::new());
+        }
+        let sender_submissions = self
+            .senders
+            .get_mut(&txn.sender())
+            .expect("The sender submissions must exist!");
+        if let Some(window_start) = now.checked_sub(Duration::from_millis(self.config.window_ms)) {
+            sender_submissions.expire(window_start);
+        }
+        sender_submissions.submissions.push_back((now, bucket));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubmissionRateLimiter;
+    use aptos_config::config::{BucketRateLimitPolicy, SubmissionRateLimitConfig};
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+    use aptos_types::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        mempool_status::{MempoolStatus, MempoolStatusCode},
+        transaction::{RawTransaction, Script, SignedTransaction, TransactionPayload},
+    };
+    use std::time::{Duration, Instant};
+
+    fn create_signed_transaction(
+        sender: AccountAddress,
+        sequence_number: u64,
+        gas_unit_price: u64,
+    ) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_transaction = RawTransaction::new(
+            sender,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            gas_unit_price,
+            0,
+            ChainId::new(10),
+        );
+        SignedTransaction::new(
+            raw_transaction.clone(),
+            private_key.public_key(),
+            private_key.sign(&raw_transaction).unwrap(),
+        )
+    }
+
+    fn create_rate_limiter() -> SubmissionRateLimiter {
+        SubmissionRateLimiter::new(SubmissionRateLimitConfig {
+            enabled: true,
+            window_ms: 1_000,
+            max_txns_per_sender: 5,
+            bucket_policies: vec![
+                BucketRateLimitPolicy {
+                    min_gas_unit_price: 100,
+                    max_txns_per_sender: 2,
+                },
+                BucketRateLimitPolicy {
+                    min_gas_unit_price: 1_000,
+                    max_txns_per_sender: 4,
+                },
+            ],
+            max_tracked_senders: 10,
+        })
+    }
+
+    /// Submits the transaction, recording it if it passes the rate limits
+    fn submit(
+        rate_limiter: &mut SubmissionRateLimiter,
+        txn: &SignedTransaction,
+        now: Instant,
+    ) -> Result<(), MempoolStatus> {
+        rate_limiter.check(txn, now)?;
+        rate_limiter.record(txn, now);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut rate_limiter = SubmissionRateLimiter::new(SubmissionRateLimitConfig::default());
+        let sender = AccountAddress::random();
+        let now = Instant::now();
+        for sequence_number in 0..1_000 {
+            let txn = create_signed_transaction(sender, sequence_number, 100);
+            assert!(submit(&mut rate_limiter, &txn, now).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_sender_limit() {
+        let mut rate_limiter = create_rate_limiter();
+        let (sender, other_sender) = (AccountAddress::random(), AccountAddress::random());
+        let now = Instant::now();
+
+        // Transactions below the first bucket are only subject to the sender limit
+        for sequence_number in 0..5 {
+            let txn = create_signed_transaction(sender, sequence_number, 1);
+            assert!(submit(&mut rate_limiter, &txn, now).is_ok());
+        }
+        let txn = create_signed_transaction(sender, 5, 1);
+        let status = submit(&mut rate_limiter, &txn, now).unwrap_err();
+        assert_eq!(status.code, MempoolStatusCode::RateLimited);
+        assert!(status.message.contains("5 transactions per 1000ms"));
+
+        // Other senders are not affected
+        let txn = create_signed_transaction(other_sender, 0, 1);
+        assert!(submit(&mut rate_limiter, &txn, now).is_ok());
+
+        // The sender can submit again once the window has passed
+        let later = now + Duration::from_millis(1_001);
+        assert!(submit(&mut rate_limiter, &txn, later).is_ok());
+        let txn = create_signed_transaction(sender, 5, 1);
+        assert!(submit(&mut rate_limiter, &txn, later).is_ok());
+    }
+
+    #[test]
+    fn test_bucket_limit() {
+        let mut rate_limiter = create_rate_limiter();
+        let sender = AccountAddress::random();
+        let now = Instant::now();
+
+        // Fill up the first bucket
+        for sequence_number in 0..2 {
+            let txn = create_signed_transaction(sender, sequence_number, 500);
+            assert!(submit(&mut rate_limiter, &txn, now).is_ok());
+        }
+        let txn = create_signed_transaction(sender, 2, 500);
+        let status = submit(&mut rate_limiter, &txn, now).unwrap_err();
+        assert_eq!(status.code, MempoolStatusCode::RateLimited);
+        assert!(status.message.contains("gas unit price >= 100"));
+
+        // Higher gas unit prices fall in the second bucket
+        for sequence_number in 2..5 {
+            let txn = create_signed_transaction(sender, sequence_number, 1_000);
+            assert!(submit(&mut rate_limiter, &txn, now).is_ok());
+        }
+
+        // The second bucket isn't full, but the sender limit is reached
+        let txn = create_signed_transaction(sender, 5, 1_000);
+        let status = submit(&mut rate_limiter, &txn, now).unwrap_err();
+        assert!(status.message.contains("5 transactions per 1000ms"));
+    }
+
+    #[test]
+    fn test_rejected_submissions_not_recorded() {
+        let mut rate_limiter = create_rate_limiter();
+        let sender = AccountAddress::random();
+        let now = Instant::now();
+
+        // Transactions that pass the check but are rejected later (e.g., by the VM validation)
+        // don't use up the limit of the sender
+        for sequence_number in 0..10 {
+            let txn = create_signed_transaction(sender, sequence_number, 1);
+            assert!(rate_limiter.check(&txn, now).is_ok());
+        }
+        for sequence_number in 0..5 {
+            let txn = create_signed_transaction(sender, sequence_number, 1);
+            assert!(submit(&mut rate_limiter, &txn, now).is_ok());
+        }
+        let txn = create_signed_transaction(sender, 5, 1);
+        assert!(rate_limiter.check(&txn, now).is_err());
+    }
+}
@@ -7,14 +7,17 @@ use crate::{
     network::MempoolSyncMsg,
     shared_mempool::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
+        journal::{journal_job, MempoolJournal},
+        tasks,
+        transaction_filter::TransactionFilter,
         types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
     },
     QuorumStoreRequest,
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::config::{MempoolConfig, NodeConfig};
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_infallible::{Mutex, RwLock};
-use aptos_logger::Level;
+use aptos_logger::{prelude::*, Level};
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_network::application::{
     interface::{NetworkClient, NetworkServiceEvents},
@@ -32,6 +35,7 @@ use tokio::runtime::{Handle, Runtime};
 ///   - outbound_sync_task (task that periodically broadcasts transactions to peers).
 ///   - inbound_network_task (task that handles inbound mempool messages and network events).
 ///   - gc_task (task that performs GC of all expired transactions by SystemTTL).
+///   - journal_task (task that periodically persists pending transactions, if enabled).
 pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
     executor: &Handle,
     config: &NodeConfig,
@@ -46,6 +50,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
     validator: Arc<RwLock<TransactionValidator>>,
     subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    transaction_filter: Option<TransactionFilter>,
 ) where
     TransactionValidator: TransactionValidation + 'static,
     ConfigProvider: OnChainConfigProvider,
@@ -59,8 +64,33 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
             validator,
             subscribers,
             config.base.role,
+            transaction_filter,
         );
 
+    // Reload the transactions persisted before the restart, and start persisting them again
+    let persistence_config = &config.mempool.persistence;
+    if persistence_config.enabled {
+        let journal =
+            MempoolJournal::new(config.base.data_dir.join(&persistence_config.journal_path));
+        match journal.read() {
+            Ok(txns) if !txns.is_empty() => {
+                executor.spawn(tasks::process_journal_transactions(smp.clone(), txns));
+            },
+            Ok(_) => {},
+            Err(error) => warn!(
+                "Failed to reload the mempool journal from {:?}: {:?}",
+                journal.path(),
+                error
+            ),
+        }
+        executor.spawn(journal_job(
+            mempool.clone(),
+            journal,
+            persistence_config.flush_interval_ms,
+            persistence_config.max_journal_txns,
+        ));
+    }
+
     executor.spawn(coordinator(
         smp,
         executor.clone(),
@@ -96,9 +126,11 @@ pub fn bootstrap(
     mempool_listener: MempoolNotificationListener,
     mempool_reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
     peers_and_metadata: Arc<PeersAndMetadata>,
-) -> Runtime {
+    transaction_filter: Option<TransactionFilter>,
+) -> (Runtime, MempoolCapacityUpdater) {
     let runtime = aptos_runtimes::spawn_named_runtime("shared-mem".into(), None);
     let mempool = Arc::new(Mutex::new(CoreMempool::new(config)));
+    let capacity_updater = MempoolCapacityUpdater::new(mempool.clone(), &config.mempool);
     let vm_validator = Arc::new(RwLock::new(VMValidator::new(Arc::clone(&db))));
     start_shared_mempool(
         runtime.handle(),
@@ -114,6 +146,64 @@ pub fn bootstrap(
         vm_validator,
         vec![],
         peers_and_metadata,
+        transaction_filter,
     );
-    runtime
+    (runtime, capacity_updater)
+}
+
+/// Updates the capacity limits of a running mempool (e.g., when the node config is reloaded).
+/// The configured capacity (bytes) can be scaled down while the node sheds load, and the two
+/// are composed here so that neither update reverts the other.
+#[derive(Clone)]
+pub struct MempoolCapacityUpdater {
+    mempool: Arc<Mutex<CoreMempool>>,
+    capacity: Arc<Mutex<MempoolCapacity>>,
+}
+
+/// The configured capacity, and the percentage of its bytes that is currently used
+struct MempoolCapacity {
+    config: MempoolConfig,
+    capacity_bytes_percent: u64,
+}
+
+impl MempoolCapacityUpdater {
+    pub(crate) fn new(mempool: Arc<Mutex<CoreMempool>>, config: &MempoolConfig) -> Self {
+        Self {
+            mempool,
+            capacity: Arc::new(Mutex::new(MempoolCapacity {
+                config: config.clone(),
+                capacity_bytes_percent: 100,
+            })),
+        }
+    }
+
+    /// Applies the `capacity`, `capacity_bytes` and `capacity_per_user` of the given config.
+    pub fn set_capacity(&self, config: &MempoolConfig) {
+        let mut capacity = self.capacity.lock();
+        capacity.config = config.clone();
+        self.apply(&capacity);
+    }
+
+    /// Only uses the given percentage of the configured `capacity_bytes` (e.g., while
+    /// the node sheds load), until it's set back to 100.
+    pub fn set_capacity_bytes_percent(&self, capacity_bytes_percent: u64) {
+        let mut capacity = self.capacity.lock();
+        capacity.capacity_bytes_percent = capacity_bytes_percent;
+        self.apply(&capacity);
+    }
+
+    fn apply(&self, capacity: &MempoolCapacity) {
+        let capacity_bytes = ((capacity.config.capacity_bytes as u128)
+            * (capacity.capacity_bytes_percent as u128)
+            / 100) as usize;
+        let config = MempoolConfig {
+            capacity_bytes,
+            ..capacity.config.clone()
+        };
+        self.mempool.lock().set_capacity(&config);
+        info!(
+            "Updated the mempool capacity: {} transactions, {} bytes, {} per user",
+            config.capacity, config.capacity_bytes, config.capacity_per_user
+        );
+    }
 }
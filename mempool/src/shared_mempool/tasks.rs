@@ -4,7 +4,7 @@
 
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, SubmissionOptions, TimelineState},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     network::{BroadcastError, MempoolSyncMsg},
@@ -82,6 +82,10 @@ pub(crate) async fn execute_broadcast<NetworkClient, TransactionValidator>(
 
     let interval_ms = if schedule_backoff {
         smp.config.shared_mempool_backoff_interval_ms
+    } else if smp.broadcast_within_validator_network()
+        && network_interface.is_upcoming_proposer(&peer)
+    {
+        smp.config.upcoming_proposer_tick_interval_ms
     } else {
         smp.config.shared_mempool_tick_interval_ms
     };
@@ -102,6 +106,7 @@ pub(crate) async fn execute_broadcast<NetworkClient, TransactionValidator>(
 pub(crate) async fn process_client_transaction_submission<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
     transaction: SignedTransaction,
+    options: SubmissionOptions,
     callback: oneshot::Sender<Result<SubmissionStatus>>,
     timer: HistogramTimer,
 ) where
@@ -110,16 +115,31 @@ pub(crate) async fn process_client_transaction_submission<NetworkClient, Transac
 {
     timer.stop_and_record();
     let _timer = counters::process_txn_submit_latency_timer_client();
-    let ineligible_for_broadcast =
-        smp.network_interface.is_validator() && !smp.broadcast_within_validator_network();
-    let timeline_state = if ineligible_for_broadcast {
-        TimelineState::NonQualified
-    } else {
-        TimelineState::NotReady
+    let timeline_state = client_timeline_state(&smp);
+
+    // Reject the transaction early if the sender exceeded the submission rate limits
+    let rate_limit_result = smp
+        .submission_rate_limiter
+        .lock()
+        .check(&transaction, Instant::now());
+    let statuses = match rate_limit_result {
+        Ok(()) => {
+            process_incoming_transactions(&smp, vec![transaction], timeline_state, true, options)
+        },
+        Err(mempool_status) => vec![(transaction, (mempool_status, None))],
     };
-    let statuses = process_incoming_transactions(&smp, vec![transaction], timeline_state, true);
     log_txn_process_results(&statuses, None);
 
+    if let Some((transaction, (mempool_status, _))) = statuses.first() {
+        // Only the accepted transactions count towards the rate limits, so that the transactions
+        // failing validation (e.g., with a spoofed sender) can't use up the limits of the sender
+        if mempool_status.code == MempoolStatusCode::Accepted {
+            smp.submission_rate_limiter
+                .lock()
+                .record(transaction, Instant::now());
+        }
+    }
+
     if let Some(status) = statuses.first() {
         if callback.send(Ok(status.1.clone())).is_err() {
             warn!(LogSchema::event_log(
@@ -131,6 +151,59 @@ pub(crate) async fn process_client_transaction_submission<NetworkClient, Transac
     }
 }
 
+/// Reloads the transactions persisted in the mempool journal (e.g., before a restart). The
+/// transactions are re-validated, exactly like client submissions.
+pub(crate) async fn process_journal_transactions<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    transactions: Vec<SignedTransaction>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let num_txns = transactions.len();
+    let timeline_state = client_timeline_state(&smp);
+    let statuses = process_incoming_transactions(
+        &smp,
+        transactions,
+        timeline_state,
+        true,
+        SubmissionOptions::default(),
+    );
+    log_txn_process_results(&statuses, None);
+
+    let num_accepted = statuses
+        .iter()
+        .filter(|(_, (mempool_status, _))| mempool_status.code == MempoolStatusCode::Accepted)
+        .count();
+    counters::MEMPOOL_JOURNAL_RELOADED_TXNS
+        .with_label_values(&[counters::SUCCESS_LABEL])
+        .inc_by(num_accepted as u64);
+    counters::MEMPOOL_JOURNAL_RELOADED_TXNS
+        .with_label_values(&[counters::REJECTED_LABEL])
+        .inc_by((num_txns - num_accepted) as u64);
+    info!(
+        LogSchema::new(LogEntry::Journal),
+        "Reloaded {} of {} transactions from the mempool journal", num_accepted, num_txns
+    );
+}
+
+/// Client submitted transactions are only broadcast if the node broadcasts to its network.
+fn client_timeline_state<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+) -> TimelineState
+where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    let ineligible_for_broadcast =
+        smp.network_interface.is_validator() && !smp.broadcast_within_validator_network();
+    if ineligible_for_broadcast {
+        TimelineState::NonQualified
+    } else {
+        TimelineState::NotReady
+    }
+}
+
 /// Processes get transaction by hash request by client.
 pub(crate) async fn process_client_get_transaction<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
@@ -168,8 +241,15 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
 {
     timer.stop_and_record();
     let _timer = counters::process_txn_submit_latency_timer(peer.network_id());
-    let results = process_incoming_transactions(&smp, transactions, timeline_state, false);
+    let results = process_incoming_transactions(
+        &smp,
+        transactions,
+        timeline_state,
+        false,
+        SubmissionOptions::default(),
+    );
     log_txn_process_results(&results, Some(peer));
+    record_received_from(&smp, &results, peer);
 
     let ack_response = gen_ack_response(request_id, results, &peer);
 
@@ -191,6 +271,25 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
     notify_subscribers(SharedMempoolNotification::ACK, &smp.subscribers);
 }
 
+/// Records the peer the accepted transactions were received from (to help debug sequence
+/// number gaps)
+fn record_received_from<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+    results: &[SubmissionStatusBundle],
+    peer: PeerNetworkId,
+) {
+    let mut mempool = smp.mempool.lock();
+    for (transaction, (mempool_status, _)) in results {
+        if mempool_status.code == MempoolStatusCode::Accepted {
+            mempool.record_received_from(
+                &transaction.sender(),
+                transaction.sequence_number(),
+                peer,
+            );
+        }
+    }
+}
+
 /// If `MempoolIsFull` on any of the transactions, provide backpressure to the downstream peer.
 fn gen_ack_response(
     request_id: MultiBatchId,
@@ -247,6 +346,7 @@ pub(crate) fn process_incoming_transactions<NetworkClient, TransactionValidator>
     transactions: Vec<SignedTransaction>,
     timeline_state: TimelineState,
     client_submitted: bool,
+    options: SubmissionOptions,
 ) -> Vec<SubmissionStatusBundle>
 where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
@@ -254,6 +354,21 @@ where
 {
     let mut statuses = vec![];
 
+    // Reject the transactions matching the transaction filter policy
+    let transactions = match &smp.transaction_filter {
+        Some(transaction_filter) => transactions
+            .into_iter()
+            .filter_map(|t| match transaction_filter.check(&t) {
+                Ok(()) => Some(t),
+                Err(mempool_status) => {
+                    statuses.push((t, (mempool_status, None)));
+                    None
+                },
+            })
+            .collect(),
+        None => transactions,
+    };
+
     let start_storage_read = Instant::now();
     let state_view = smp
         .db
@@ -315,6 +430,7 @@ where
         timeline_state,
         &mut statuses,
         client_submitted,
+        options,
     );
     notify_subscribers(SharedMempoolNotification::NewTransactions, &smp.subscribers);
     statuses
@@ -329,6 +445,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     timeline_state: TimelineState,
     statuses: &mut Vec<(SignedTransaction, (MempoolStatus, Option<StatusCode>))>,
     client_submitted: bool,
+    options: SubmissionOptions,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
@@ -349,12 +466,13 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
                 match validation_result.status() {
                     None => {
                         let ranking_score = validation_result.score();
-                        let mempool_status = mempool.add_txn(
+                        let mempool_status = mempool.add_txn_with_options(
                             transaction.clone(),
                             ranking_score,
                             sequence_info,
                             timeline_state,
                             client_submitted,
+                            options,
                         );
                         statuses.push((transaction, (mempool_status, None)));
                     },
@@ -395,18 +513,20 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     timeline_state: TimelineState,
     statuses: &mut Vec<(SignedTransaction, (MempoolStatus, Option<StatusCode>))>,
     client_submitted: bool,
+    options: SubmissionOptions,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
 {
     let mut mempool = smp.mempool.lock();
     for (transaction, sequence_info) in transactions.into_iter() {
-        let mempool_status = mempool.add_txn(
+        let mempool_status = mempool.add_txn_with_options(
             transaction.clone(),
             0,
             sequence_info,
             timeline_state,
             client_submitted,
+            options,
         );
         statuses.push((transaction, (mempool_status, None)));
     }
@@ -448,8 +568,9 @@ fn log_txn_process_results(results: &[SubmissionStatusBundle], sender: Option<Pe
 // intra-node communication handlers //
 // ================================= //
 
-/// Only applies to Validators. Either provides transactions to consensus [`GetBlockRequest`] or
-/// handles rejecting transactions [`RejectNotification`]
+/// Only applies to Validators. Either provides transactions to consensus [`GetBlockRequest`],
+/// handles rejecting transactions [`RejectNotification`] or updates the upcoming proposers to
+/// prioritize broadcasts to [`UpcomingProposersNotification`]
 pub(crate) fn process_quorum_store_request<NetworkClient, TransactionValidator>(
     smp: &SharedMempool<NetworkClient, TransactionValidator>,
     req: QuorumStoreRequest,
@@ -494,6 +615,8 @@ pub(crate) fn process_quorum_store_request<NetworkClient, TransactionValidator>(
                     counters::GET_BLOCK_GET_BATCH_LABEL,
                     counters::REQUEST_SUCCESS_LABEL,
                 );
+                let in_progress: Vec<_> =
+                    exclude_transactions.iter().map(|txn| txn.summary).collect();
                 txns = mempool.get_batch(
                     max_txns,
                     max_bytes,
@@ -501,6 +624,7 @@ pub(crate) fn process_quorum_store_request<NetworkClient, TransactionValidator>(
                     include_gas_upgraded,
                     exclude_transactions,
                 );
+                mempool.record_transactions_in_consensus(in_progress, &txns);
             }
 
             // mempool_service_transactions is logged inside get_batch
@@ -523,6 +647,11 @@ pub(crate) fn process_quorum_store_request<NetworkClient, TransactionValidator>(
                 counters::COMMIT_CONSENSUS_LABEL,
             )
         },
+        QuorumStoreRequest::UpcomingProposersNotification(proposers) => {
+            // There is no callback, as this is only a hint for broadcasts
+            smp.network_interface.update_upcoming_proposers(proposers);
+            return;
+        },
     };
     // Send back to callback
     let result = if callback.send(Ok(resp)).is_err() {
@@ -0,0 +1,584 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Filters the transactions submitted to the node by the rules of an operator provided policy,
+//! e.g., to comply with jurisdictions that require filtering at the endpoints. The policy is
+//! read from a JSON file that must be signed with the configured key, and is reloaded whenever
+//! the file changes. The version of the last applied policy is persisted, so that an older
+//! policy is never applied, even after a restart. A transaction is rejected if it matches any
+//! rule of the policy.
+use crate::counters;
+use anyhow::{anyhow, bail, ensure, Result};
+use aptos_config::config::TransactionFilterConfig;
+use aptos_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    Signature, SigningKey,
+};
+use aptos_infallible::RwLock;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    account_address::AccountAddress,
+    mempool_status::{MempoolStatus, MempoolStatusCode},
+    transaction::{
+        EntryFunction, MultisigTransactionPayload, SignedTransaction, TransactionPayload,
+    },
+};
+use move_core_types::identifier::Identifier;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// A rule of the policy. A transaction matches the rule if it matches all the fields that are
+/// set, and a rule must set at least one field.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionFilterRule {
+    /// Matches the transactions sent by this account
+    pub sender: Option<AccountAddress>,
+    /// Matches the transactions calling an entry function of a module published at this address
+    pub module_address: Option<AccountAddress>,
+    /// Matches the transactions calling this entry function, e.g., `0x1::coin::transfer`
+    pub entry_function: Option<String>,
+}
+
+/// The rules the transactions are filtered by. The version must increase with every update,
+/// so that an older (signed) policy can't replace a newer one.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionFilterPolicy {
+    pub version: u64,
+    pub rules: Vec<TransactionFilterRule>,
+}
+
+/// The policy, as stored in the policy file. The signature is over the BCS bytes of the policy.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignedTransactionFilterPolicy {
+    pub policy: TransactionFilterPolicy,
+    pub signature: Ed25519Signature,
+}
+
+impl SignedTransactionFilterPolicy {
+    pub fn sign(policy: TransactionFilterPolicy, private_key: &Ed25519PrivateKey) -> Result<Self> {
+        let signature = private_key.sign_arbitrary_message(&bcs::to_bytes(&policy)?);
+        Ok(Self { policy, signature })
+    }
+
+    pub fn verify(&self, public_key: &Ed25519PublicKey) -> Result<()> {
+        self.signature
+            .verify_arbitrary_msg(&bcs::to_bytes(&self.policy)?, public_key)
+            .map_err(|error| anyhow!("Invalid policy signature: {}", error))
+    }
+}
+
+/// A rule of the active policy, with the entry function parsed
+struct ParsedRule {
+    sender: Option<AccountAddress>,
+    module_address: Option<AccountAddress>,
+    entry_function: Option<(AccountAddress, Identifier, Identifier)>,
+}
+
+impl ParsedRule {
+    fn parse(rule: &TransactionFilterRule) -> Result<Self> {
+        ensure!(
+            rule.sender.is_some() || rule.module_address.is_some() || rule.entry_function.is_some(),
+            "Empty rules would match all transactions"
+        );
+        let entry_function = rule
+            .entry_function
+            .as_deref()
+            .map(parse_entry_function)
+            .transpose()?;
+        Ok(Self {
+            sender: rule.sender,
+            module_address: rule.module_address,
+            entry_function,
+        })
+    }
+
+    fn matches(&self, txn: &SignedTransaction) -> bool {
+        if self.sender.map_or(false, |sender| sender != txn.sender()) {
+            return false;
+        }
+        if self.module_address.is_none() && self.entry_function.is_none() {
+            return true;
+        }
+        let entry_function = match entry_function_of(txn) {
+            Some(entry_function) => entry_function,
+            None => return false,
+        };
+        let module = entry_function.module();
+        if self
+            .module_address
+            .map_or(false, |address| &address != module.address())
+        {
+            return false;
+        }
+        match &self.entry_function {
+            Some((address, module_name, function)) => {
+                address == module.address()
+                    && module_name.as_ident_str() == module.name()
+                    && function.as_ident_str() == entry_function.function()
+            },
+            None => true,
+        }
+    }
+}
+
+/// Parses an entry function of the form `<address>::<module>::<function>`
+fn parse_entry_function(entry_function: &str) -> Result<(AccountAddress, Identifier, Identifier)> {
+    match entry_function.split("::").collect::<Vec<_>>().as_slice() {
+        [address, module, function] => Ok((
+            AccountAddress::from_hex_literal(address)?,
+            Identifier::new(*module)?,
+            Identifier::new(*function)?,
+        )),
+        _ => bail!(
+            "Invalid entry function {} (expected <address>::<module>::<function>)",
+            entry_function
+        ),
+    }
+}
+
+/// Returns the entry function called by the transaction (directly, or via a multisig account)
+fn entry_function_of(txn: &SignedTransaction) -> Option<&EntryFunction> {
+    match txn.payload() {
+        TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+        TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+            Some(MultisigTransactionPayload::EntryFunction(entry_function)) => Some(entry_function),
+            None => None,
+        },
+        TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => None,
+    }
+}
+
+/// The policy the transactions are currently filtered by
+struct ActivePolicy {
+    version: u64,
+    rules: Vec<ParsedRule>,
+}
+
+impl ActivePolicy {
+    fn parse(policy: TransactionFilterPolicy) -> Result<Self> {
+        let rules = policy
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                ParsedRule::parse(rule)
+                    .map_err(|error| anyhow!("Invalid rule {}: {}", index, error))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            version: policy.version,
+            rules,
+        })
+    }
+}
+
+/// Filters the transactions by the active policy. The filter is shared by the REST API and
+/// mempool, so that a reloaded policy applies to both.
+#[derive(Clone)]
+pub struct TransactionFilter {
+    policy_path: PathBuf,
+    applied_version_path: PathBuf,
+    public_key: Ed25519PublicKey,
+    active_policy: Arc<RwLock<ActivePolicy>>,
+}
+
+impl TransactionFilter {
+    /// Creates the filter (if enabled) and loads the policy. Fails if the policy can't be
+    /// loaded or is older than the last applied policy, as the node shouldn't accept
+    /// transactions the operator wants filtered.
+    pub fn new(config: &TransactionFilterConfig, data_dir: &Path) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let public_key = config
+            .policy_public_key
+            .clone()
+            .ok_or_else(|| anyhow!("The public key of the policy signer is missing"))?;
+        let policy_path = data_dir.join(&config.policy_path);
+        let applied_version_path = data_dir.join(&config.applied_version_path);
+        let active_policy = read_policy(&policy_path, &public_key)?;
+        let applied_version = read_applied_version(&applied_version_path)?;
+        ensure!(
+            active_policy.version >= applied_version,
+            "The policy version {} is older than the last applied version {}",
+            active_policy.version,
+            applied_version
+        );
+        write_applied_version(&applied_version_path, active_policy.version)?;
+        log_applied_policy(&active_policy);
+        Ok(Some(Self {
+            policy_path,
+            applied_version_path,
+            public_key,
+            active_policy: Arc::new(RwLock::new(active_policy)),
+        }))
+    }
+
+    /// Returns the version of the active policy
+    pub fn policy_version(&self) -> u64 {
+        self.active_policy.read().version
+    }
+
+    /// Reloads the policy file, and applies the policy if it is correctly signed and newer
+    /// than the active policy. Returns true iff the policy was applied.
+    pub fn reload(&self) -> Result<bool> {
+        let policy = read_policy(&self.policy_path, &self.public_key)?;
+        let mut active_policy = self.active_policy.write();
+        if policy.version == active_policy.version {
+            return Ok(false);
+        }
+        ensure!(
+            policy.version > active_policy.version,
+            "The policy version {} is older than the active version {}",
+            policy.version,
+            active_policy.version
+        );
+        write_applied_version(&self.applied_version_path, policy.version)?;
+        log_applied_policy(&policy);
+        *active_policy = policy;
+        Ok(true)
+    }
+
+    /// Checks the transaction against the active policy. Otherwise, returns the rejection
+    /// status with the rule the transaction matched.
+    pub fn check(&self, txn: &SignedTransaction) -> Result<(), MempoolStatus> {
+        let active_policy = self.active_policy.read();
+        match active_policy
+            .rules
+            .iter()
+            .position(|rule| rule.matches(txn))
+        {
+            Some(index) => {
+                counters::TRANSACTION_FILTER_REJECTED_COUNT.inc();
+                Err(
+                    MempoolStatus::new(MempoolStatusCode::Filtered).with_message(format!(
+                        "Transaction from {} rejected by rule {} of the transaction filter policy (version {})",
+                        txn.sender(),
+                        index,
+                        active_policy.version
+                    )),
+                )
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Reloads the policy in the background whenever the modification time of the policy
+    /// file changes. Invalid policies are logged, and the active policy is kept.
+    pub fn watch_policy_file(&self, reload_interval: Duration) {
+        let filter = self.clone();
+        thread::Builder::new()
+            .name("txn-filter-watch".into())
+            .spawn(move || {
+                let mut last_modified = modified_time(&filter.policy_path);
+                loop {
+                    thread::sleep(reload_interval);
+                    let modified = modified_time(&filter.policy_path);
+                    if modified == last_modified {
+                        continue;
+                    }
+                    last_modified = modified;
+                    if let Err(error) = filter.reload() {
+                        warn!(
+                            "Failed to reload the transaction filter policy {:?}: {:?}",
+                            filter.policy_path, error
+                        );
+                    }
+                }
+            })
+            .expect("Failed to start the transaction filter policy watcher");
+    }
+}
+
+/// Reads the policy file, and verifies the signature and the rules of the policy
+fn read_policy(policy_path: &Path, public_key: &Ed25519PublicKey) -> Result<ActivePolicy> {
+    let signed_policy: SignedTransactionFilterPolicy =
+        serde_json::from_slice(&fs::read(policy_path)?)?;
+    signed_policy.verify(public_key)?;
+    ActivePolicy::parse(signed_policy.policy)
+}
+
+/// Reads the version of the last applied policy (0 if no policy was applied yet)
+fn read_applied_version(applied_version_path: &Path) -> Result<u64> {
+    match fs::read_to_string(applied_version_path) {
+        Ok(version) => Ok(version.trim().parse()?),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn write_applied_version(applied_version_path: &Path, version: u64) -> Result<()> {
+    fs::write(applied_version_path, version.to_string()).map_err(|error| {
+        anyhow!(
+            "Failed to persist the applied policy version to {:?}: {}",
+            applied_version_path,
+            error
+        )
+    })
+}
+
+fn log_applied_policy(policy: &ActivePolicy) {
+    info!(
+        "Applied version {} of the transaction filter policy ({} rules)",
+        policy.version,
+        policy.rules.len()
+    );
+    counters::TRANSACTION_FILTER_POLICY_VERSION.set(policy.version as i64);
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{PrivateKey, Uniform};
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        chain_id::ChainId,
+        transaction::{RawTransaction, Script},
+    };
+    use move_core_types::language_storage::ModuleId;
+
+    fn create_transaction(
+        sender: AccountAddress,
+        payload: TransactionPayload,
+    ) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = RawTransaction::new(sender, 0, payload, 1_000, 100, 0, ChainId::test());
+        SignedTransaction::new(
+            raw_txn,
+            private_key.public_key(),
+            Ed25519Signature::dummy_signature(),
+        )
+    }
+
+    fn entry_function_payload(
+        address: AccountAddress,
+        module: &str,
+        function: &str,
+    ) -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            ModuleId::new(address, Identifier::new(module).unwrap()),
+            Identifier::new(function).unwrap(),
+            vec![],
+            vec![],
+        ))
+    }
+
+    fn write_policy(path: &Path, policy: TransactionFilterPolicy, private_key: &Ed25519PrivateKey) {
+        let signed_policy = SignedTransactionFilterPolicy::sign(policy, private_key).unwrap();
+        fs::write(path, serde_json::to_vec(&signed_policy).unwrap()).unwrap();
+    }
+
+    fn filter_config(private_key: &Ed25519PrivateKey) -> TransactionFilterConfig {
+        TransactionFilterConfig {
+            enabled: true,
+            policy_public_key: Some(private_key.public_key()),
+            ..Default::default()
+        }
+    }
+
+    fn create_filter(
+        private_key: &Ed25519PrivateKey,
+        policy: TransactionFilterPolicy,
+    ) -> (TempPath, TransactionFilter) {
+        let data_dir = TempPath::new();
+        data_dir.create_as_dir().unwrap();
+        let config = filter_config(private_key);
+        write_policy(
+            &data_dir.path().join(&config.policy_path),
+            policy,
+            private_key,
+        );
+        let filter = TransactionFilter::new(&config, data_dir.path())
+            .unwrap()
+            .unwrap();
+        (data_dir, filter)
+    }
+
+    #[test]
+    fn test_filter_rules() {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let blocked_sender = AccountAddress::random();
+        let blocked_module_address = AccountAddress::random();
+        let policy = TransactionFilterPolicy {
+            version: 1,
+            rules: vec![
+                TransactionFilterRule {
+                    sender: Some(blocked_sender),
+                    ..Default::default()
+                },
+                TransactionFilterRule {
+                    module_address: Some(blocked_module_address),
+                    ..Default::default()
+                },
+                TransactionFilterRule {
+                    entry_function: Some("0x1::coin::transfer".to_string()),
+                    ..Default::default()
+                },
+            ],
+        };
+        let (_data_dir, filter) = create_filter(&private_key, policy);
+        let sender = AccountAddress::random();
+        let script_payload = TransactionPayload::Script(Script::new(vec![], vec![], vec![]));
+
+        // Verify the transactions matching a rule are rejected
+        let txn = create_transaction(blocked_sender, script_payload.clone());
+        assert_eq!(
+            filter.check(&txn).unwrap_err().code,
+            MempoolStatusCode::Filtered
+        );
+        let payload = entry_function_payload(blocked_module_address, "dex", "swap");
+        let txn = create_transaction(sender, payload);
+        assert_eq!(
+            filter.check(&txn).unwrap_err().code,
+            MempoolStatusCode::Filtered
+        );
+        let payload = entry_function_payload(AccountAddress::ONE, "coin", "transfer");
+        let txn = create_transaction(sender, payload);
+        assert_eq!(
+            filter.check(&txn).unwrap_err().code,
+            MempoolStatusCode::Filtered
+        );
+
+        // Verify the other transactions are accepted
+        let txn = create_transaction(sender, script_payload);
+        assert!(filter.check(&txn).is_ok());
+        let payload = entry_function_payload(AccountAddress::ONE, "coin", "register");
+        let txn = create_transaction(sender, payload);
+        assert!(filter.check(&txn).is_ok());
+    }
+
+    #[test]
+    fn test_reload_policy() {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let sender = AccountAddress::random();
+        let rule = TransactionFilterRule {
+            sender: Some(sender),
+            ..Default::default()
+        };
+        let (data_dir, filter) = create_filter(&private_key, TransactionFilterPolicy {
+            version: 2,
+            rules: vec![],
+        });
+        let policy_path = data_dir.path().join("transaction_filter_policy.json");
+        let txn = create_transaction(
+            sender,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        );
+        assert!(filter.check(&txn).is_ok());
+
+        // Verify a policy signed with another key is rejected
+        let other_private_key = Ed25519PrivateKey::generate(&mut rand::rngs::OsRng);
+        write_policy(
+            &policy_path,
+            TransactionFilterPolicy {
+                version: 3,
+                rules: vec![rule.clone()],
+            },
+            &other_private_key,
+        );
+        assert!(filter.reload().is_err());
+        assert!(filter.check(&txn).is_ok());
+
+        // Verify an older policy is rejected
+        write_policy(
+            &policy_path,
+            TransactionFilterPolicy {
+                version: 1,
+                rules: vec![rule.clone()],
+            },
+            &private_key,
+        );
+        assert!(filter.reload().is_err());
+        assert_eq!(filter.policy_version(), 2);
+
+        // Verify a newer policy is applied
+        write_policy(
+            &policy_path,
+            TransactionFilterPolicy {
+                version: 3,
+                rules: vec![rule],
+            },
+            &private_key,
+        );
+        assert!(filter.reload().unwrap());
+        assert_eq!(filter.policy_version(), 3);
+        assert!(filter.check(&txn).is_err());
+    }
+
+    #[test]
+    fn test_older_policy_rejected_after_restart() {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let (data_dir, filter) = create_filter(&private_key, TransactionFilterPolicy {
+            version: 2,
+            rules: vec![],
+        });
+        let policy_path = data_dir.path().join("transaction_filter_policy.json");
+        write_policy(
+            &policy_path,
+            TransactionFilterPolicy {
+                version: 3,
+                rules: vec![],
+            },
+            &private_key,
+        );
+        assert!(filter.reload().unwrap());
+        drop(filter);
+
+        // Verify an older policy isn't applied by a restarted node
+        write_policy(
+            &policy_path,
+            TransactionFilterPolicy {
+                version: 2,
+                rules: vec![],
+            },
+            &private_key,
+        );
+        let config = filter_config(&private_key);
+        assert!(TransactionFilter::new(&config, data_dir.path()).is_err());
+
+        // Verify the last applied policy is loaded again
+        write_policy(
+            &policy_path,
+            TransactionFilterPolicy {
+                version: 3,
+                rules: vec![],
+            },
+            &private_key,
+        );
+        let filter = TransactionFilter::new(&config, data_dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(filter.policy_version(), 3);
+    }
+
+    #[test]
+    fn test_empty_rule_is_invalid() {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let (data_dir, filter) = create_filter(&private_key, TransactionFilterPolicy::default());
+        write_policy(
+            &data_dir.path().join("transaction_filter_policy.json"),
+            TransactionFilterPolicy {
+                version: 1,
+                rules: vec![TransactionFilterRule::default()],
+            },
+            &private_key,
+        );
+        assert!(filter.reload().is_err());
+        assert_eq!(filter.policy_version(), 0);
+    }
+}
@@ -4,8 +4,11 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::CoreMempool,
+    core_mempool::{
+        AccountSequenceNumberDiagnostics, CoreMempool, MempoolTransactionEvent, SubmissionOptions,
+    },
     network::{MempoolNetworkInterface, MempoolSyncMsg},
+    shared_mempool::{rate_limiter::SubmissionRateLimiter, transaction_filter::TransactionFilter},
 };
 use anyhow::Result;
 use aptos_config::{
@@ -20,7 +23,8 @@ use aptos_network::{
 };
 use aptos_storage_interface::DbReader;
 use aptos_types::{
-    mempool_status::MempoolStatus, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress, mempool_status::MempoolStatus, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus, PeerId,
 };
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
@@ -38,7 +42,7 @@ use std::{
     task::Waker,
     time::{Instant, SystemTime},
 };
-use tokio::runtime::Handle;
+use tokio::{runtime::Handle, sync::broadcast};
 
 /// Struct that owns all dependencies required by shared mempool routines.
 #[derive(Clone)]
@@ -50,6 +54,8 @@ pub(crate) struct SharedMempool<NetworkClient, TransactionValidator> {
     pub validator: Arc<RwLock<TransactionValidator>>,
     pub subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     pub broadcast_within_validator_network: Arc<RwLock<bool>>,
+    pub submission_rate_limiter: Arc<Mutex<SubmissionRateLimiter>>,
+    pub transaction_filter: Option<TransactionFilter>,
 }
 
 impl<
@@ -65,8 +71,12 @@ impl<
         validator: Arc<RwLock<TransactionValidator>>,
         subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
         role: RoleType,
+        transaction_filter: Option<TransactionFilter>,
     ) -> Self {
         let network_interface = MempoolNetworkInterface::new(network_client, role, config.clone());
+        let submission_rate_limiter = Arc::new(Mutex::new(SubmissionRateLimiter::new(
+            config.submission_rate_limit.clone(),
+        )));
         SharedMempool {
             mempool,
             config,
@@ -75,6 +85,8 @@ impl<
             validator,
             subscribers,
             broadcast_within_validator_network: Arc::new(RwLock::new(true)),
+            submission_rate_limiter,
+            transaction_filter,
         }
     }
 
@@ -182,6 +194,9 @@ pub enum QuorumStoreRequest {
         // callback to respond to
         oneshot::Sender<Result<QuorumStoreResponse>>,
     ),
+    /// Notification about the validators expected to propose the current and upcoming rounds,
+    /// so that broadcasts to them are prioritized.
+    UpcomingProposersNotification(Vec<PeerId>),
 }
 
 impl fmt::Display for QuorumStoreRequest {
@@ -210,6 +225,9 @@ impl fmt::Display for QuorumStoreRequest {
                     rejected_txns.len()
                 )
             },
+            QuorumStoreRequest::UpcomingProposersNotification(proposers) => {
+                format!("UpcomingProposersNotification [proposers: {:?}]", proposers)
+            },
         };
         write!(f, "{}", payload)
     }
@@ -229,7 +247,21 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
+    SubmitTransactionWithOptions(
+        SignedTransaction,
+        SubmissionOptions,
+        oneshot::Sender<Result<SubmissionStatus>>,
+    ),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    /// Returns the mempool's view of the sequence numbers of the account
+    GetSequenceNumberDiagnostics(
+        AccountAddress,
+        oneshot::Sender<AccountSequenceNumberDiagnostics>,
+    ),
+    /// Returns true iff the transaction expired in mempool, and was submitted with the
+    /// notify expiration behavior
+    IsTransactionExpired(HashValue, oneshot::Sender<bool>),
+    SubscribeTransactionEvents(oneshot::Sender<broadcast::Receiver<MempoolTransactionEvent>>),
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, TimelineState, TxnPointer},
+    core_mempool::{CoreMempool, SubmissionOptions, TimelineState, TxnPointer},
     network::MempoolSyncMsg,
 };
 use anyhow::{format_err, Result};
@@ -134,13 +134,22 @@ pub(crate) fn add_txn(pool: &mut CoreMempool, transaction: TestTransaction) -> R
 }
 
 pub(crate) fn add_signed_txn(pool: &mut CoreMempool, transaction: SignedTransaction) -> Result<()> {
+    add_signed_txn_with_options(pool, transaction, SubmissionOptions::default())
+}
+
+pub(crate) fn add_signed_txn_with_options(
+    pool: &mut CoreMempool,
+    transaction: SignedTransaction,
+    options: SubmissionOptions,
+) -> Result<()> {
     match pool
-        .add_txn(
+        .add_txn_with_options(
             transaction.clone(),
             transaction.gas_unit_price(),
             0,
             TimelineState::NotReady,
             false,
+            options,
         )
         .code
     {
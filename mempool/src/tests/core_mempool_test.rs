@@ -3,20 +3,32 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, MempoolTransaction, SubmittedBy, TimelineState},
+    core_mempool::{
+        CoreMempool, EvictionReason, ExpirationBehavior, MempoolTransaction,
+        MempoolTransactionEventKind, SequenceNumberGap, SubmissionOptions, SubmittedBy,
+        TimelineState, TtlTier,
+    },
+    shared_mempool::MempoolCapacityUpdater,
     tests::common::{
-        add_signed_txn, add_txn, add_txns_to_mempool, setup_mempool,
+        add_signed_txn, add_signed_txn_with_options, add_txn, add_txns_to_mempool, setup_mempool,
         setup_mempool_with_broadcast_buckets, TestTransaction,
     },
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::{
+    config::{NodeConfig, ParkingLotEvictionConfig, ParkingLotEvictionPolicy},
+    network_id::PeerNetworkId,
+};
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
 use aptos_types::{
     mempool_status::MempoolStatusCode, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
 };
 use itertools::Itertools;
-use std::time::{Duration, SystemTime};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 #[test]
 fn test_transaction_ordering_only_seqnos() {
@@ -149,6 +161,46 @@ fn test_fail_for_same_gas_amount_and_not_same_expiration_time() {
     assert!(ret.is_err())
 }
 
+#[test]
+fn test_replace_by_fee_min_bump() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.replace_by_fee_min_bump_percent = 10;
+    let mut mempool = CoreMempool::new(&config);
+    add_txn(&mut mempool, TestTransaction::new(0, 0, 100)).unwrap();
+
+    // A bump below the minimum percentage is rejected, and the rule is surfaced
+    let txn = TestTransaction::new(0, 0, 109).make_signed_transaction();
+    let status = mempool.add_txn(
+        txn.clone(),
+        txn.gas_unit_price(),
+        0,
+        TimelineState::NotReady,
+        false,
+    );
+    assert_eq!(status.code, MempoolStatusCode::InvalidUpdate);
+    assert!(status
+        .message
+        .contains("at least 110 (minimum bump of 10%)"));
+
+    // A bump of exactly the minimum percentage replaces the transaction
+    add_txn(&mut mempool, TestTransaction::new(0, 0, 110)).unwrap();
+    let txn = mempool
+        .get_transaction_store()
+        .get(&TestTransaction::get_address(0), 0)
+        .unwrap();
+    assert_eq!(txn.gas_unit_price(), 110);
+
+    // Small gas unit prices must still be bumped by at least one
+    add_txn(&mut mempool, TestTransaction::new(1, 0, 1)).unwrap();
+    assert!(add_txn(&mut mempool, TestTransaction::new(1, 0, 2)).is_ok());
+
+    // Without a minimum bump, any increase replaces the transaction
+    config.mempool.replace_by_fee_min_bump_percent = 0;
+    let mut mempool = CoreMempool::new(&config);
+    add_txn(&mut mempool, TestTransaction::new(0, 0, 100)).unwrap();
+    assert!(add_txn(&mut mempool, TestTransaction::new(0, 0, 101)).is_ok());
+}
+
 #[test]
 fn test_update_invalid_transaction_in_mempool() {
     let (mut mempool, mut consensus) = setup_mempool();
@@ -298,6 +350,144 @@ fn test_system_ttl() {
     assert_eq!(vec![transaction.make_signed_transaction()], batch);
 }
 
+#[test]
+fn test_short_ttl_tier_and_expiration_notification() {
+    // Created mempool with a short TTL tier of 0, so that short TTL transactions are evicted
+    // on the next gc run.
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.short_ttl_tier_secs = 0;
+    let mut mempool = CoreMempool::new(&config);
+
+    // Add a short TTL transaction for each expiration behavior, and a standard one
+    let notified_txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let dropped_txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
+    let standard_txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
+    for (txn, expiration_behavior) in [
+        (&notified_txn, ExpirationBehavior::Notify),
+        (&dropped_txn, ExpirationBehavior::Drop),
+    ] {
+        add_signed_txn_with_options(&mut mempool, txn.clone(), SubmissionOptions {
+            ttl_tier: TtlTier::Short,
+            expiration_behavior,
+        })
+        .unwrap();
+    }
+    add_signed_txn(&mut mempool, standard_txn.clone()).unwrap();
+
+    // A duplicate submission doesn't change the options of the transaction in mempool
+    add_signed_txn_with_options(&mut mempool, standard_txn.clone(), SubmissionOptions {
+        ttl_tier: TtlTier::Short,
+        expiration_behavior: ExpirationBehavior::Notify,
+    })
+    .unwrap();
+
+    // GC routine should only keep the standard transaction
+    mempool.gc();
+    let batch = mempool.get_batch(3, 1024, true, false, vec![]);
+    assert_eq!(vec![standard_txn.clone()], batch);
+
+    // Only the expiration of the transaction with the notify behavior is recorded
+    assert!(mempool.is_expiration_notified(&notified_txn.committed_hash()));
+    assert!(!mempool.is_expiration_notified(&dropped_txn.committed_hash()));
+    assert!(!mempool.is_expiration_notified(&standard_txn.committed_hash()));
+}
+
+#[test]
+fn test_sequence_number_diagnostics() {
+    let (mut mempool, _) = setup_mempool();
+
+    // Add transactions with gaps at sequence numbers 2, 4 and 5
+    let txns = add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(1, 0, 1),
+        TestTransaction::new(1, 1, 1),
+        TestTransaction::new(1, 3, 1),
+        TestTransaction::new(1, 6, 1),
+    ]);
+    let sender = txns[0].sender();
+
+    // Record the peer that sent the transaction after the first gap
+    let peer = PeerNetworkId::random();
+    mempool.record_received_from(&sender, 3, peer);
+    mempool.record_received_from(&sender, 3, PeerNetworkId::random());
+
+    // Verify the pending transactions and the detected gaps
+    let diagnostics = mempool.get_sequence_number_diagnostics(&sender);
+    assert_eq!(diagnostics.committed_sequence_number, Some(0));
+    let pending: Vec<_> = diagnostics
+        .pending_transactions
+        .iter()
+        .map(|txn| (txn.sequence_number, txn.is_parked, txn.received_from))
+        .collect();
+    assert_eq!(pending, vec![
+        (0, false, None),
+        (1, false, None),
+        (3, true, Some(peer)),
+        (6, true, None),
+    ]);
+    assert_eq!(diagnostics.gaps, vec![
+        SequenceNumberGap {
+            start_sequence_number: 2,
+            end_sequence_number: 2,
+            first_seen_by: Some(peer),
+        },
+        SequenceNumberGap {
+            start_sequence_number: 4,
+            end_sequence_number: 5,
+            first_seen_by: None,
+        },
+    ]);
+
+    // Verify that committed transactions are no longer reported
+    mempool.commit_transaction(&sender, 0);
+    mempool.commit_transaction(&sender, 1);
+    let diagnostics = mempool.get_sequence_number_diagnostics(&sender);
+    assert_eq!(diagnostics.committed_sequence_number, Some(2));
+    assert_eq!(diagnostics.pending_transactions.len(), 2);
+    assert_eq!(diagnostics.gaps.len(), 2);
+}
+
+#[test]
+fn test_in_consensus_diagnostics() {
+    let (mut mempool, _) = setup_mempool();
+    let txns = add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(1, 0, 1),
+        TestTransaction::new(1, 1, 1),
+        TestTransaction::new(1, 2, 1),
+    ]);
+    let sender = txns[0].sender();
+    let in_consensus = |mempool: &CoreMempool| -> Vec<bool> {
+        mempool
+            .get_sequence_number_diagnostics(&sender)
+            .pending_transactions
+            .iter()
+            .map(|txn| txn.in_consensus)
+            .collect()
+    };
+    assert_eq!(in_consensus(&mempool), vec![false, false, false]);
+
+    // Consensus pulls the first transaction
+    let batch = mempool.get_batch(1, 10240, true, false, vec![]);
+    mempool.record_transactions_in_consensus(vec![], &batch);
+    assert_eq!(in_consensus(&mempool), vec![true, false, false]);
+
+    // Then excludes it from the next batch request, which pulls the second one
+    let in_progress = vec![TransactionSummary::new(sender, 0)];
+    let exclude_transactions = in_progress
+        .iter()
+        .map(|summary| TransactionInProgress {
+            summary: *summary,
+            gas_unit_price: 1,
+        })
+        .collect();
+    let batch = mempool.get_batch(1, 10240, true, false, exclude_transactions);
+    mempool.record_transactions_in_consensus(in_progress, &batch);
+    assert_eq!(in_consensus(&mempool), vec![true, true, false]);
+
+    // Transactions that are no longer in progress aren't reported anymore
+    mempool.record_transactions_in_consensus(vec![], &[]);
+    assert_eq!(in_consensus(&mempool), vec![false, false, false]);
+}
+
 #[test]
 fn test_commit_callback() {
     // Consensus commit callback should unlock txns in parking lot.
@@ -522,6 +712,49 @@ fn test_capacity() {
     assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_ok());
 }
 
+#[test]
+fn test_set_capacity() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 1;
+    let mut pool = CoreMempool::new(&config);
+    add_txn(&mut pool, TestTransaction::new(1, 0, 1)).unwrap();
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_err());
+
+    // Raising the capacity makes room for more transactions
+    config.mempool.capacity = 2;
+    pool.set_capacity(&config.mempool);
+    add_txn(&mut pool, TestTransaction::new(1, 1, 1)).unwrap();
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_err());
+
+    // Lowering it keeps the existing transactions, but rejects new ones
+    config.mempool.capacity = 1;
+    pool.set_capacity(&config.mempool);
+    pool.commit_transaction(&TestTransaction::get_address(1), 0);
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_err());
+}
+
+#[test]
+fn test_capacity_updater() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 10;
+    let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
+    let capacity_updater = MempoolCapacityUpdater::new(mempool.clone(), &config.mempool);
+
+    // Shedding all of the capacity (bytes) rejects new transactions
+    capacity_updater.set_capacity_bytes_percent(0);
+    assert!(add_txn(&mut mempool.lock(), TestTransaction::new(1, 0, 1)).is_err());
+
+    // Updating the capacity (e.g., on a config reload) doesn't stop the shedding
+    config.mempool.capacity = 1;
+    capacity_updater.set_capacity(&config.mempool);
+    assert!(add_txn(&mut mempool.lock(), TestTransaction::new(1, 0, 1)).is_err());
+
+    // Once the shedding stops, the updated capacity is used (not the initial one)
+    capacity_updater.set_capacity_bytes_percent(100);
+    add_txn(&mut mempool.lock(), TestTransaction::new(1, 0, 1)).unwrap();
+    assert!(add_txn(&mut mempool.lock(), TestTransaction::new(1, 1, 1)).is_err());
+}
+
 #[test]
 fn test_capacity_bytes() {
     let capacity_bytes = 2_048;
@@ -623,6 +856,34 @@ fn test_parking_lot_eviction() {
     assert!(add_txn(&mut pool, TestTransaction::new(0, 2, 1)).is_err());
 }
 
+#[test]
+fn test_parking_lot_scored_eviction() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 3;
+    config.mempool.parking_lot_eviction = ParkingLotEvictionConfig {
+        policy: ParkingLotEvictionPolicy::Scored,
+        fee_density_weight: 1,
+        sender_backlog_weight: 0,
+        age_weight: 0,
+        ..Default::default()
+    };
+    let mut pool = CoreMempool::new(&config);
+
+    // Park a cheap and an expensive transaction, and fill up Mempool with a ready one
+    let cheap_txn = TestTransaction::new(1, 5, 1).make_signed_transaction();
+    let expensive_txn = TestTransaction::new(2, 5, 100).make_signed_transaction();
+    add_signed_txn(&mut pool, cheap_txn.clone()).unwrap();
+    add_signed_txn(&mut pool, expensive_txn.clone()).unwrap();
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    assert_eq!(2, pool.get_parking_lot_size());
+
+    // Inserting a ready transaction evicts the parked transaction with the lowest fee per byte
+    add_txn(&mut pool, TestTransaction::new(0, 1, 1)).unwrap();
+    assert_eq!(1, pool.get_parking_lot_size());
+    assert!(pool.get_by_hash(cheap_txn.committed_hash()).is_none());
+    assert!(pool.get_by_hash(expensive_txn.committed_hash()).is_some());
+}
+
 #[test]
 fn test_parking_lot_evict_only_for_ready_txn_insertion() {
     let mut config = NodeConfig::generate_random_config();
@@ -917,3 +1178,56 @@ fn test_include_gas_upgraded() {
     let batch = pool.get_batch(10, 10240, true, true, vec![high_gas_txn, low_gas_txn]);
     assert_eq!(batch.len(), 0);
 }
+
+#[test]
+fn test_transaction_events() {
+    let (mut pool, _) = setup_mempool();
+    let mut events = pool.subscribe_transaction_events();
+
+    // Insert two transactions and replace the first one with a higher gas price
+    let txns = add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(1, 0, 1),
+        TestTransaction::new(0, 0, 100),
+    ]);
+
+    // Commit the replacement and reject the other transaction
+    pool.commit_transaction(&TestTransaction::get_address(0), 0);
+    pool.reject_transaction(
+        &TestTransaction::get_address(1),
+        0,
+        &txns[1].clone().committed_hash(),
+        &DiscardedVMStatus::MALFORMED,
+    );
+
+    let mut received = vec![];
+    while let Ok(event) = events.try_recv() {
+        received.push((event.hash, event.kind));
+    }
+    assert_eq!(received, vec![
+        (
+            txns[0].clone().committed_hash(),
+            MempoolTransactionEventKind::Inserted
+        ),
+        (
+            txns[1].clone().committed_hash(),
+            MempoolTransactionEventKind::Inserted
+        ),
+        (
+            txns[0].clone().committed_hash(),
+            MempoolTransactionEventKind::Evicted(EvictionReason::Replaced)
+        ),
+        (
+            txns[2].clone().committed_hash(),
+            MempoolTransactionEventKind::Inserted
+        ),
+        (
+            txns[2].clone().committed_hash(),
+            MempoolTransactionEventKind::Committed
+        ),
+        (
+            txns[1].clone().committed_hash(),
+            MempoolTransactionEventKind::Evicted(EvictionReason::Rejected)
+        ),
+    ]);
+}
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, SubmissionOptions, TimelineState},
     network::MempoolSyncMsg,
     shared_mempool::{tasks, types::SharedMempool},
 };
@@ -55,9 +55,16 @@ pub fn test_mempool_process_incoming_transactions_impl(
         vm_validator,
         vec![],
         config.base.role,
+        None,
     );
 
-    let _ = tasks::process_incoming_transactions(&smp, txns, timeline_state, false);
+    let _ = tasks::process_incoming_transactions(
+        &smp,
+        txns,
+        timeline_state,
+        false,
+        SubmissionOptions::default(),
+    );
 }
 
 proptest! {
@@ -56,10 +56,16 @@ impl MockSharedMempool {
     /// Returns the runtime on which the shared mempool is running
     /// and the channel through which shared mempool receives client events.
     pub fn new() -> Self {
+        Self::new_with_config(NodeConfig::generate_random_config())
+    }
+
+    /// Creates a mock of a running instance of shared mempool with the given node config.
+    pub fn new_with_config(config: NodeConfig) -> Self {
         let runtime = aptos_runtimes::spawn_named_runtime("shared-mem".into(), None);
         let _entered_runtime = runtime.enter();
-        let (ac_client, mempool, quorum_store_sender, mempool_notifier) = Self::start(
+        let (ac_client, mempool, quorum_store_sender, mempool_notifier) = Self::start_with_config(
             runtime.handle(),
+            config,
             &DbReaderWriter::new(MockDbReaderWriter),
             MockVMValidator,
         );
@@ -102,7 +108,20 @@ impl MockSharedMempool {
         mpsc::Sender<QuorumStoreRequest>,
         MempoolNotifier,
     ) {
-        let mut config = NodeConfig::generate_random_config();
+        Self::start_with_config(handle, NodeConfig::generate_random_config(), db, validator)
+    }
+
+    fn start_with_config<V: TransactionValidation + 'static>(
+        handle: &Handle,
+        mut config: NodeConfig,
+        db: &DbReaderWriter,
+        validator: V,
+    ) -> (
+        MempoolClientSender,
+        Arc<Mutex<CoreMempool>>,
+        mpsc::Sender<QuorumStoreRequest>,
+        MempoolNotifier,
+    ) {
         config.validator_network = Some(NetworkConfig::network_with_id(NetworkId::Validator));
 
         let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
@@ -157,6 +176,7 @@ impl MockSharedMempool {
             Arc::new(RwLock::new(validator)),
             vec![],
             peers_and_metadata,
+            None,
         );
 
         (ac_client, mempool, quorum_store_sender, mempool_notifier)
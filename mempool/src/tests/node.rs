@@ -634,6 +634,7 @@ fn start_node_mempool(
         Arc::new(RwLock::new(MockVMValidator)),
         vec![sender],
         peers_and_metadata,
+        None,
     );
 
     (mempool, runtime, subscriber)
@@ -4,13 +4,17 @@
 
 use crate::{
     mocks::MockSharedMempool,
+    shared_mempool::journal::MempoolJournal,
     tests::common::{batch_add_signed_txn, TestTransaction},
     QuorumStoreRequest,
 };
+use aptos_config::config::NodeConfig;
 use aptos_consensus_types::common::RejectedTransactionSummary;
 use aptos_mempool_notifications::MempoolNotificationSender;
+use aptos_temppath::TempPath;
 use aptos_types::{transaction::Transaction, vm_status::DiscardedVMStatus};
 use futures::{channel::oneshot, executor::block_on, sink::SinkExt};
+use std::{path::PathBuf, thread, time::Duration};
 
 #[test]
 fn test_consensus_events_rejected_txns() {
@@ -96,3 +100,47 @@ fn test_mempool_notify_committed_txns() {
     assert_eq!(timeline.len(), 1);
     assert_eq!(timeline.first().unwrap(), &kept_txn);
 }
+
+#[test]
+fn test_mempool_journal_reload() {
+    // Persist two transactions in the journal of the node
+    let data_dir = TempPath::new();
+    data_dir.create_as_dir().unwrap();
+    let mut config = NodeConfig::generate_random_config();
+    config.base.data_dir = data_dir.path().to_path_buf();
+    config.mempool.persistence.journal_path = PathBuf::from("mempool_journal.bcs");
+    config.mempool.persistence.enabled = true;
+    config.mempool.persistence.flush_interval_ms = 100;
+    let journal = MempoolJournal::new(data_dir.path().join("mempool_journal.bcs"));
+    let journal_txns = vec![
+        TestTransaction::new(0, 0, 1).make_signed_transaction(),
+        TestTransaction::new(1, 0, 1).make_signed_transaction(),
+    ];
+    journal.write(&journal_txns).unwrap();
+
+    // Verify the transactions are reloaded (and re-validated) on startup
+    let smp = MockSharedMempool::new_with_config(config);
+    wait_for(|| smp.get_txns(10).len() == journal_txns.len());
+
+    // Verify new transactions are persisted by the following flushes
+    let new_txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
+    smp.add_txns(vec![new_txn.clone()]).unwrap();
+    wait_for(|| journal.read().unwrap().contains(&new_txn));
+    let mut persisted_txns = journal.read().unwrap();
+    persisted_txns.sort_by_key(|txn| txn.sender());
+    let mut expected_txns = journal_txns;
+    expected_txns.push(new_txn);
+    expected_txns.sort_by_key(|txn| txn.sender());
+    assert_eq!(persisted_txns, expected_txns);
+}
+
+/// Waits (up to 10 seconds) for the condition to hold
+fn wait_for(condition: impl Fn() -> bool) {
+    for _ in 0..1_000 {
+        if condition() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("Timed out waiting for the condition!");
+}
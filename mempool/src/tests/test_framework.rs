@@ -618,6 +618,7 @@ fn setup_mempool(
         vm_validator,
         vec![sender],
         peers_and_metadata,
+        None,
     );
 
     (
@@ -30,6 +30,7 @@ use aptos_network::{
     connectivity_manager::{builder::ConnectivityManagerBuilder, ConnectivityRequest},
     constants::MAX_MESSAGE_SIZE,
     logging::NetworkSchema,
+    peer::bandwidth::ProtocolBandwidthLimits,
     peer_manager::{
         builder::{AuthenticationMode, PeerManagerBuilder},
         ConnectionRequestSender,
@@ -70,6 +71,7 @@ pub struct NetworkBuilder {
     health_checker_builder: Option<HealthCheckerBuilder>,
     peer_manager_builder: PeerManagerBuilder,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    protocol_bandwidth_limits: ProtocolBandwidthLimits,
 }
 
 impl NetworkBuilder {
@@ -119,6 +121,7 @@ impl NetworkBuilder {
             health_checker_builder: None,
             peer_manager_builder,
             peers_and_metadata,
+            protocol_bandwidth_limits: ProtocolBandwidthLimits::default(),
         }
     }
 
@@ -205,6 +208,14 @@ impl NetworkBuilder {
             ),
         );
 
+        network_builder.protocol_bandwidth_limits = ProtocolBandwidthLimits::new(
+            &network_context,
+            &config.outbound_protocol_bandwidth_limits,
+        );
+        network_builder
+            .peer_manager_builder
+            .set_protocol_bandwidth_limits(network_builder.protocol_bandwidth_limits.clone());
+
         network_builder.add_connection_monitoring(
             config.ping_interval_ms,
             config.ping_timeout_ms,
@@ -313,6 +324,12 @@ impl NetworkBuilder {
         self.network_context
     }
 
+    /// Returns the (shared) per-protocol outbound bandwidth limits of the network,
+    /// which can be used to adjust the limits at runtime.
+    pub fn protocol_bandwidth_limits(&self) -> ProtocolBandwidthLimits {
+        self.protocol_bandwidth_limits.clone()
+    }
+
     pub fn conn_mgr_reqs_tx(&self) -> Option<aptos_channels::Sender<ConnectivityRequest>> {
         self.connectivity_manager_builder
             .as_ref()
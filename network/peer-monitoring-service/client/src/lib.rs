@@ -20,6 +20,7 @@ use error::Error;
 use futures::StreamExt;
 use network::PeerMonitoringServiceClient;
 use peer_states::peer_state::PeerState;
+use soak_test::SoakTests;
 use std::{collections::HashMap, sync::Arc, time::Duration};
 use thiserror::Error;
 use tokio::{runtime::Handle, task::JoinHandle};
@@ -29,6 +30,7 @@ mod logging;
 mod metrics;
 mod network;
 pub mod peer_states;
+pub mod soak_test;
 #[cfg(test)]
 mod tests;
 
@@ -55,10 +57,11 @@ impl PeerMonitorState {
 }
 
 /// Runs the peer monitor that continuously monitors
-/// the state of the peers.
+/// the state of the peers (and runs any scheduled soak tests).
 pub async fn start_peer_monitor(
     node_config: NodeConfig,
     network_client: NetworkClient<PeerMonitoringServiceMessage>,
+    soak_tests: SoakTests,
     runtime: Option<Handle>,
 ) {
     // Create a new monitoring client and peer monitor state
@@ -75,6 +78,16 @@ pub async fn start_peer_monitor(
         runtime.clone(),
     );
 
+    // Spawn the soak test runner
+    soak_test::spawn_soak_test_runner(
+        node_config.peer_monitoring_service,
+        soak_tests,
+        peer_monitoring_client.clone(),
+        peer_monitor_state.request_id_generator.clone(),
+        time_service.clone(),
+        runtime.clone(),
+    );
+
     // Start the peer monitor
     start_peer_monitor_with_state(
         node_config,
@@ -40,12 +40,14 @@ impl<'a> LogSchema<'a> {
 #[derive(Clone, Copy, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
+    BandwidthProbe,
     LatencyPing,
     MetadataUpdateLoop,
     NetworkInfoRequest,
     NodeInfoRequest,
     PeerMonitorLoop,
     SendRequest,
+    SoakTest,
 
     #[cfg(feature = "network-perf-test")] // Disabled by default
     PerformanceMonitoringRequest,
@@ -62,6 +64,8 @@ pub enum LogEvent {
     SendRequest,
     StartedMetadataUpdaterLoop,
     StartedPeerMonitorLoop,
+    StartedSoakTestLoop,
+    ThroughputDegraded,
     TooManyPingFailures,
     UnexpectedErrorEncountered,
 }
@@ -27,6 +27,32 @@ pub static AVERAGE_PING_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(histogram_opts, &["network_id"]).unwrap()
 });
 
+// Histogram buckets for tracking the median bandwidth estimates (KB/sec)
+const MEDIAN_BANDWIDTH_ESTIMATE_BUCKETS: &[f64] = &[
+    16.0, 32.0, 64.0, 128.0, 256.0, 512.0, 1024.0, 2048.0, 4096.0, 8192.0, 16384.0, 32768.0,
+    65536.0, 131072.0, // Max is over 100 MB/sec
+];
+
+/// Counter for tracking the median bandwidth estimates
+pub static MEDIAN_BANDWIDTH_ESTIMATES: Lazy<HistogramVec> = Lazy::new(|| {
+    let histogram_opts = histogram_opts!(
+        "peer_monitoring_client_median_bandwidth_estimates",
+        "Counters related to median bandwidth estimates (KB/sec)",
+        MEDIAN_BANDWIDTH_ESTIMATE_BUCKETS.to_vec()
+    );
+    register_histogram_vec!(histogram_opts, &["network_id"]).unwrap()
+});
+
+/// Counter for tracking the p90 ping latencies
+pub static P90_PING_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
+    let histogram_opts = histogram_opts!(
+        "peer_monitoring_client_p90_ping_latencies",
+        "Counters related to p90 ping latencies (secs)",
+        AVERAGE_PING_LATENCY_BUCKETS.to_vec()
+    );
+    register_histogram_vec!(histogram_opts, &["network_id"]).unwrap()
+});
+
 // Histogram buckets for tracking the distance from the validators
 const DISTANCE_FROM_VALIDATORS_BUCKETS: &[f64] = &[
     0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 15.0, 20.0, 30.0, 40.0, 50.0,
@@ -105,6 +131,16 @@ pub static ERROR_RESPONSES: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for tracking the throughput alerts raised by soak tests
+pub static SOAK_TEST_THROUGHPUT_ALERTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "peer_monitoring_client_soak_test_throughput_alerts",
+        "Counters related to throughput alerts raised by soak tests",
+        &["alert_type", "network_id"]
+    )
+    .unwrap()
+});
+
 /// Counter for tracking request latencies
 pub static REQUEST_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
@@ -0,0 +1,467 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    metrics,
+    peer_states::{key_value::StateValueInterface, request_tracker::RequestTracker},
+    Error, LogEntry, LogEvent, LogSchema,
+};
+use aptos_config::{config::BandwidthMonitoringConfig, network_id::PeerNetworkId};
+use aptos_infallible::RwLock;
+use aptos_logger::{error, warn};
+use aptos_network::application::metadata::PeerMetadata;
+use aptos_peer_monitoring_service_types::{
+    request::{BandwidthProbeRequest, PeerMonitoringServiceRequest},
+    response::PeerMonitoringServiceResponse,
+    PercentileSummary,
+};
+use aptos_time_service::TimeService;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+/// A simple container that holds a peer's bandwidth info
+#[derive(Clone, Debug)]
+pub struct BandwidthInfoState {
+    bandwidth_monitoring_config: BandwidthMonitoringConfig, // The config for bandwidth monitoring
+    bandwidth_probe_counter: u64, // The monotonically increasing counter for each probe
+    recorded_bandwidth_estimates_kb_per_sec: BTreeMap<u64, f64>, // Bandwidth estimates by counter (KB/sec)
+    request_tracker: Arc<RwLock<RequestTracker>>, // The request tracker for bandwidth probes
+}
+
+impl BandwidthInfoState {
+    pub fn new(
+        bandwidth_monitoring_config: BandwidthMonitoringConfig,
+        time_service: TimeService,
+    ) -> Self {
+        let request_tracker = RequestTracker::new(
+            bandwidth_monitoring_config.bandwidth_probe_interval_ms,
+            time_service,
+        );
+
+        Self {
+            bandwidth_monitoring_config,
+            bandwidth_probe_counter: 0,
+            recorded_bandwidth_estimates_kb_per_sec: BTreeMap::new(),
+            request_tracker: Arc::new(RwLock::new(request_tracker)),
+        }
+    }
+
+    /// Returns the current bandwidth probe counter and increments it internally
+    pub fn get_and_increment_bandwidth_probe_counter(&mut self) -> u64 {
+        let bandwidth_probe_counter = self.bandwidth_probe_counter;
+        self.bandwidth_probe_counter += 1;
+        bandwidth_probe_counter
+    }
+
+    /// Handles a request failure for the specified peer
+    fn handle_request_failure(&self) {
+        self.request_tracker.write().record_response_failure();
+    }
+
+    /// Records the new bandwidth estimate for the peer
+    pub fn record_new_bandwidth_estimate(
+        &mut self,
+        bandwidth_probe_counter: u64,
+        bandwidth_estimate_kb_per_sec: f64,
+    ) {
+        // Update the request tracker with a successful response
+        self.request_tracker.write().record_response_success();
+
+        // Save the bandwidth estimate
+        self.recorded_bandwidth_estimates_kb_per_sec
+            .insert(bandwidth_probe_counter, bandwidth_estimate_kb_per_sec);
+
+        // Perform garbage collection on the recorded bandwidth estimates
+        let max_num_bandwidth_probes_to_retain = self
+            .bandwidth_monitoring_config
+            .max_num_bandwidth_probes_to_retain;
+        if self.recorded_bandwidth_estimates_kb_per_sec.len() > max_num_bandwidth_probes_to_retain {
+            // We only need to pop a single element because insertion only happens in this method.
+            // Thus, the size can only ever grow to be 1 greater than the max.
+            let _ = self.recorded_bandwidth_estimates_kb_per_sec.pop_first();
+        }
+    }
+
+    /// Returns the percentiles of the recorded bandwidth estimates (KB/sec).
+    /// If no bandwidth estimates have been recorded, None is returned.
+    pub fn get_bandwidth_percentiles_kb_per_sec(&self) -> Option<PercentileSummary> {
+        PercentileSummary::from_measurements(
+            self.recorded_bandwidth_estimates_kb_per_sec
+                .values()
+                .cloned(),
+        )
+    }
+
+    /// Returns a copy of the recorded bandwidth estimates for test purposes
+    #[cfg(test)]
+    pub fn get_recorded_bandwidth_estimates(&self) -> BTreeMap<u64, f64> {
+        self.recorded_bandwidth_estimates_kb_per_sec.clone()
+    }
+}
+
+impl StateValueInterface for BandwidthInfoState {
+    fn create_monitoring_service_request(&mut self) -> PeerMonitoringServiceRequest {
+        let probe_counter = self.get_and_increment_bandwidth_probe_counter();
+        let num_response_bytes = self.bandwidth_monitoring_config.bandwidth_probe_data_size;
+        PeerMonitoringServiceRequest::BandwidthProbe(BandwidthProbeRequest {
+            probe_counter,
+            num_response_bytes,
+        })
+    }
+
+    fn get_request_timeout_ms(&self) -> u64 {
+        self.bandwidth_monitoring_config.bandwidth_probe_timeout_ms
+    }
+
+    fn get_request_tracker(&self) -> Arc<RwLock<RequestTracker>> {
+        self.request_tracker.clone()
+    }
+
+    fn handle_monitoring_service_response(
+        &mut self,
+        peer_network_id: &PeerNetworkId,
+        peer_metadata: PeerMetadata,
+        monitoring_service_request: PeerMonitoringServiceRequest,
+        monitoring_service_response: PeerMonitoringServiceResponse,
+        response_time_secs: f64,
+    ) {
+        // Verify the request type is correctly formed
+        let bandwidth_probe_request = match monitoring_service_request {
+            PeerMonitoringServiceRequest::BandwidthProbe(bandwidth_probe_request) => {
+                bandwidth_probe_request
+            },
+            request => {
+                error!(LogSchema::new(LogEntry::BandwidthProbe)
+                    .event(LogEvent::UnexpectedErrorEncountered)
+                    .peer(peer_network_id)
+                    .request(&request)
+                    .message("An unexpected request was sent instead of a bandwidth probe!"));
+                self.handle_request_failure();
+                return;
+            },
+        };
+
+        // Verify the response type is valid
+        let bandwidth_probe_response = match monitoring_service_response {
+            PeerMonitoringServiceResponse::BandwidthProbe(bandwidth_probe_response) => {
+                bandwidth_probe_response
+            },
+            _ => {
+                warn!(LogSchema::new(LogEntry::BandwidthProbe)
+                    .event(LogEvent::ResponseError)
+                    .peer(peer_network_id)
+                    .message("An unexpected response was received instead of a bandwidth probe!"));
+                self.handle_request_failure();
+                return;
+            },
+        };
+
+        // Verify the response contains the correct counter and amount of data
+        let request_probe_counter = bandwidth_probe_request.probe_counter;
+        let response_probe_counter = bandwidth_probe_response.probe_counter;
+        let num_response_bytes = bandwidth_probe_response.data.len() as u64;
+        if request_probe_counter != response_probe_counter
+            || bandwidth_probe_request.num_response_bytes != num_response_bytes
+        {
+            warn!(LogSchema::new(LogEntry::BandwidthProbe)
+                .event(LogEvent::InvalidResponse)
+                .peer(peer_network_id)
+                .message(&format!(
+                    "Peer responded with an invalid bandwidth probe! Expected counter: {:?} and \
+                    bytes: {:?}, found counter: {:?} and bytes: {:?}",
+                    request_probe_counter,
+                    bandwidth_probe_request.num_response_bytes,
+                    response_probe_counter,
+                    num_response_bytes
+                )));
+            self.handle_request_failure();
+            return;
+        }
+
+        // The response time includes a full round trip to the peer, so subtract
+        // the median ping latency to isolate the time spent transferring the data.
+        // Without a latency estimate, the transfer time can't be determined.
+        let latency_ping_percentiles_secs = peer_metadata
+            .get_peer_monitoring_metadata()
+            .latency_ping_percentiles_secs;
+        let transfer_time_secs = match latency_ping_percentiles_secs {
+            Some(latency_ping_percentiles_secs) => {
+                response_time_secs - latency_ping_percentiles_secs.p50
+            },
+            None => {
+                self.request_tracker.write().record_response_success();
+                return;
+            },
+        };
+
+        // Skip probes where the transfer time is lost in the latency noise
+        // (this also avoids dividing by zero).
+        if transfer_time_secs <= 0.0 {
+            self.request_tracker.write().record_response_success();
+            return;
+        }
+
+        // Store the new bandwidth estimate
+        let bandwidth_estimate_kb_per_sec =
+            (num_response_bytes as f64 / 1024.0) / transfer_time_secs;
+        self.record_new_bandwidth_estimate(request_probe_counter, bandwidth_estimate_kb_per_sec);
+    }
+
+    fn handle_monitoring_service_response_error(
+        &mut self,
+        peer_network_id: &PeerNetworkId,
+        error: Error,
+    ) {
+        // Handle the failure
+        self.handle_request_failure();
+
+        // Log the error
+        warn!(LogSchema::new(LogEntry::BandwidthProbe)
+            .event(LogEvent::ResponseError)
+            .message("Error encountered when probing the bandwidth of the peer!")
+            .peer(peer_network_id)
+            .error(&error));
+    }
+
+    fn update_peer_state_metrics(&self, peer_network_id: &PeerNetworkId) {
+        if let Some(bandwidth_percentiles) = self.get_bandwidth_percentiles_kb_per_sec() {
+            // Update the median bandwidth metric
+            metrics::observe_value(
+                &metrics::MEDIAN_BANDWIDTH_ESTIMATES,
+                peer_network_id,
+                bandwidth_percentiles.p50,
+            );
+        }
+    }
+}
+
+impl Display for BandwidthInfoState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BandwidthInfoState {{ bandwidth_probe_counter: {:?}, recorded_bandwidth_estimates_kb_per_sec: {:?} }}",
+            self.bandwidth_probe_counter, self.recorded_bandwidth_estimates_kb_per_sec,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::peer_states::{bandwidth_info::BandwidthInfoState, key_value::StateValueInterface};
+    use aptos_config::{
+        config::{BandwidthMonitoringConfig, PeerRole},
+        network_id::{NetworkId, PeerNetworkId},
+    };
+    use aptos_netcore::transport::ConnectionOrigin;
+    use aptos_network::{
+        application::metadata::PeerMetadata,
+        protocols::wire::handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
+        transport::{ConnectionId, ConnectionMetadata},
+    };
+    use aptos_peer_monitoring_service_types::{
+        request::{BandwidthProbeRequest, PeerMonitoringServiceRequest},
+        response::{BandwidthProbeResponse, PeerMonitoringServiceResponse},
+        PeerMonitoringMetadata, PercentileSummary,
+    };
+    use aptos_time_service::TimeService;
+    use aptos_types::{network_address::NetworkAddress, PeerId};
+    use std::str::FromStr;
+
+    // Useful test constants
+    const TEST_NETWORK_ADDRESS: &str = "/ip4/127.0.0.1/tcp/8081";
+    const TEST_PING_LATENCY_SECS: f64 = 0.5;
+
+    #[test]
+    fn test_verify_bandwidth_info_state() {
+        // Create the bandwidth info state
+        let bandwidth_monitoring_config = BandwidthMonitoringConfig::default();
+        let time_service = TimeService::mock();
+        let mut bandwidth_info_state =
+            BandwidthInfoState::new(bandwidth_monitoring_config, time_service);
+
+        // Verify the initial bandwidth info state
+        assert_eq!(bandwidth_info_state.bandwidth_probe_counter, 0);
+        assert!(bandwidth_info_state
+            .get_bandwidth_percentiles_kb_per_sec()
+            .is_none());
+
+        // Attempt to handle an invalid response with mismatched probe counters
+        let probe_counter = bandwidth_info_state.get_and_increment_bandwidth_probe_counter();
+        handle_monitoring_service_response(
+            &mut bandwidth_info_state,
+            probe_counter,
+            probe_counter + 1,
+            1024,
+            1024,
+            1.0,
+            Some(TEST_PING_LATENCY_SECS),
+        );
+        assert!(bandwidth_info_state
+            .get_recorded_bandwidth_estimates()
+            .is_empty());
+
+        // Attempt to handle an invalid response with the wrong amount of data
+        let probe_counter = bandwidth_info_state.get_and_increment_bandwidth_probe_counter();
+        handle_monitoring_service_response(
+            &mut bandwidth_info_state,
+            probe_counter,
+            probe_counter,
+            1024,
+            10,
+            1.0,
+            Some(TEST_PING_LATENCY_SECS),
+        );
+        assert!(bandwidth_info_state
+            .get_recorded_bandwidth_estimates()
+            .is_empty());
+
+        // Handle several valid responses (with increasing response times)
+        let max_num_bandwidth_probes_to_retain =
+            bandwidth_monitoring_config.max_num_bandwidth_probes_to_retain as u64;
+        let num_bandwidth_probes = max_num_bandwidth_probes_to_retain * 2;
+        for i in 0..num_bandwidth_probes {
+            let probe_counter = bandwidth_info_state.get_and_increment_bandwidth_probe_counter();
+            handle_monitoring_service_response(
+                &mut bandwidth_info_state,
+                probe_counter,
+                probe_counter,
+                1024 * 1024,
+                1024 * 1024,
+                (i + 1) as f64 + TEST_PING_LATENCY_SECS,
+                Some(TEST_PING_LATENCY_SECS),
+            );
+        }
+
+        // Verify only the latest estimates are retained
+        let recorded_bandwidth_estimates = bandwidth_info_state.get_recorded_bandwidth_estimates();
+        assert_eq!(
+            recorded_bandwidth_estimates.len() as u64,
+            max_num_bandwidth_probes_to_retain
+        );
+
+        // Verify the bandwidth percentiles (1 MB is sent in each probe)
+        let bandwidth_percentiles = bandwidth_info_state
+            .get_bandwidth_percentiles_kb_per_sec()
+            .unwrap();
+        let lowest_estimate = 1024.0 / num_bandwidth_probes as f64;
+        assert_eq!(bandwidth_percentiles.p99, 1024.0 / 13.0);
+        assert!(bandwidth_percentiles.p50 >= lowest_estimate);
+        assert!(bandwidth_percentiles.p50 <= bandwidth_percentiles.p90);
+    }
+
+    #[test]
+    fn test_bandwidth_estimate_excludes_latency() {
+        // Create the bandwidth info state
+        let bandwidth_monitoring_config = BandwidthMonitoringConfig::default();
+        let time_service = TimeService::mock();
+        let mut bandwidth_info_state =
+            BandwidthInfoState::new(bandwidth_monitoring_config, time_service);
+
+        // Handle a response without a latency estimate and verify it is ignored
+        let probe_counter = bandwidth_info_state.get_and_increment_bandwidth_probe_counter();
+        handle_monitoring_service_response(
+            &mut bandwidth_info_state,
+            probe_counter,
+            probe_counter,
+            1024,
+            1024,
+            1.0,
+            None,
+        );
+        assert!(bandwidth_info_state
+            .get_recorded_bandwidth_estimates()
+            .is_empty());
+
+        // Handle a response that is faster than the ping latency and verify it is ignored
+        let probe_counter = bandwidth_info_state.get_and_increment_bandwidth_probe_counter();
+        handle_monitoring_service_response(
+            &mut bandwidth_info_state,
+            probe_counter,
+            probe_counter,
+            1024,
+            1024,
+            TEST_PING_LATENCY_SECS,
+            Some(TEST_PING_LATENCY_SECS),
+        );
+        assert!(bandwidth_info_state
+            .get_recorded_bandwidth_estimates()
+            .is_empty());
+
+        // Handle a valid response and verify the latency is excluded from the estimate
+        let probe_counter = bandwidth_info_state.get_and_increment_bandwidth_probe_counter();
+        handle_monitoring_service_response(
+            &mut bandwidth_info_state,
+            probe_counter,
+            probe_counter,
+            1024 * 1024,
+            1024 * 1024,
+            2.0 + TEST_PING_LATENCY_SECS,
+            Some(TEST_PING_LATENCY_SECS),
+        );
+        let recorded_bandwidth_estimates = bandwidth_info_state.get_recorded_bandwidth_estimates();
+        assert_eq!(recorded_bandwidth_estimates.len(), 1);
+        assert_eq!(recorded_bandwidth_estimates[&probe_counter], 512.0);
+    }
+
+    /// Handles a monitoring service response from a peer
+    fn handle_monitoring_service_response(
+        bandwidth_info_state: &mut BandwidthInfoState,
+        request_probe_counter: u64,
+        response_probe_counter: u64,
+        num_request_bytes: u64,
+        num_response_bytes: u64,
+        response_time_secs: f64,
+        ping_latency_secs: Option<f64>,
+    ) {
+        // Create a new peer metadata entry
+        let peer_network_id = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+        let connection_metadata = ConnectionMetadata::new(
+            peer_network_id.peer_id(),
+            ConnectionId::default(),
+            NetworkAddress::from_str(TEST_NETWORK_ADDRESS).unwrap(),
+            ConnectionOrigin::Outbound,
+            MessagingProtocolVersion::V1,
+            ProtocolIdSet::empty(),
+            PeerRole::Validator,
+        );
+        let peer_monitoring_metadata = PeerMonitoringMetadata {
+            latency_ping_percentiles_secs: ping_latency_secs.map(|ping_latency_secs| {
+                PercentileSummary {
+                    p50: ping_latency_secs,
+                    p90: ping_latency_secs,
+                    p99: ping_latency_secs,
+                }
+            }),
+            ..PeerMonitoringMetadata::default()
+        };
+        let peer_metadata =
+            PeerMetadata::new_for_test(connection_metadata, peer_monitoring_metadata);
+
+        // Create the service request
+        let peer_monitoring_service_request =
+            PeerMonitoringServiceRequest::BandwidthProbe(BandwidthProbeRequest {
+                probe_counter: request_probe_counter,
+                num_response_bytes: num_request_bytes,
+            });
+
+        // Create the service response
+        let peer_monitoring_service_response =
+            PeerMonitoringServiceResponse::BandwidthProbe(BandwidthProbeResponse {
+                probe_counter: response_probe_counter,
+                data: vec![0; num_response_bytes as usize],
+            });
+
+        // Handle the response
+        bandwidth_info_state.handle_monitoring_service_response(
+            &peer_network_id,
+            peer_metadata,
+            peer_monitoring_service_request,
+            peer_monitoring_service_response,
+            response_time_secs,
+        );
+    }
+}
@@ -3,8 +3,8 @@
 
 use crate::{
     peer_states::{
-        latency_info::LatencyInfoState, network_info::NetworkInfoState, node_info::NodeInfoState,
-        request_tracker::RequestTracker,
+        bandwidth_info::BandwidthInfoState, latency_info::LatencyInfoState,
+        network_info::NetworkInfoState, node_info::NodeInfoState, request_tracker::RequestTracker,
     },
     Error,
 };
@@ -12,7 +12,7 @@ use aptos_config::{config::NodeConfig, network_id::PeerNetworkId};
 use aptos_infallible::RwLock;
 use aptos_network::application::metadata::PeerMetadata;
 use aptos_peer_monitoring_service_types::{
-    request::{LatencyPingRequest, PeerMonitoringServiceRequest},
+    request::{BandwidthProbeRequest, LatencyPingRequest, PeerMonitoringServiceRequest},
     response::PeerMonitoringServiceResponse,
 };
 use aptos_time_service::TimeService;
@@ -28,6 +28,7 @@ use {
 /// states held for each peer.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum PeerStateKey {
+    BandwidthInfo,
     LatencyInfo,
     NetworkInfo,
     NodeInfo,
@@ -40,6 +41,7 @@ impl PeerStateKey {
     /// A utility function for getting all peer state keys
     pub fn get_all_keys() -> Vec<PeerStateKey> {
         vec![
+            PeerStateKey::BandwidthInfo,
             PeerStateKey::LatencyInfo,
             PeerStateKey::NetworkInfo,
             PeerStateKey::NodeInfo,
@@ -51,6 +53,7 @@ impl PeerStateKey {
     /// Returns the label for the peer state key
     pub fn get_label(&self) -> &str {
         match self {
+            PeerStateKey::BandwidthInfo => "bandwidth_info",
             PeerStateKey::LatencyInfo => "latency_info",
             PeerStateKey::NetworkInfo => "network_info",
             PeerStateKey::NodeInfo => "node_info",
@@ -64,6 +67,13 @@ impl PeerStateKey {
     /// Returns the metric label for the requests sent by the peer state key
     pub fn get_metrics_request_label(&self) -> &str {
         match self {
+            PeerStateKey::BandwidthInfo => {
+                PeerMonitoringServiceRequest::BandwidthProbe(BandwidthProbeRequest {
+                    probe_counter: 0,
+                    num_response_bytes: 0,
+                })
+                .get_label()
+            },
             PeerStateKey::LatencyInfo => {
                 PeerMonitoringServiceRequest::LatencyPing(LatencyPingRequest { ping_counter: 0 })
                     .get_label()
@@ -125,6 +135,7 @@ pub trait StateValueInterface {
 #[enum_dispatch(StateValueInterface)]
 #[derive(Clone, Debug)]
 pub enum PeerStateValue {
+    BandwidthInfoState,
     LatencyInfoState,
     NetworkInfoState,
     NodeInfoState,
@@ -140,6 +151,11 @@ impl PeerStateValue {
         peer_state_key: &PeerStateKey,
     ) -> Self {
         match peer_state_key {
+            PeerStateKey::BandwidthInfo => {
+                let bandwidth_monitoring_config =
+                    node_config.peer_monitoring_service.bandwidth_monitoring;
+                BandwidthInfoState::new(bandwidth_monitoring_config, time_service).into()
+            },
             PeerStateKey::LatencyInfo => {
                 let latency_monitoring_config =
                     node_config.peer_monitoring_service.latency_monitoring;
@@ -165,6 +181,9 @@ impl PeerStateValue {
 impl Display for PeerStateValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            PeerStateValue::BandwidthInfoState(state) => {
+                write!(f, "BandwidthInfoState: {}", state)
+            },
             PeerStateValue::LatencyInfoState(state) => write!(f, "LatencyInfoState: {}", state),
             PeerStateValue::NetworkInfoState(state) => write!(f, "NetworkInfoState: {}", state),
             PeerStateValue::NodeInfoState(state) => write!(f, "NodeInfoState: {}", state),
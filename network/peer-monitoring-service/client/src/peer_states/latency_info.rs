@@ -13,6 +13,7 @@ use aptos_network::application::metadata::PeerMetadata;
 use aptos_peer_monitoring_service_types::{
     request::{LatencyPingRequest, PeerMonitoringServiceRequest},
     response::PeerMonitoringServiceResponse,
+    PercentileSummary,
 };
 use aptos_time_service::TimeService;
 use std::{
@@ -109,6 +110,14 @@ impl LatencyInfoState {
         }
     }
 
+    /// Returns the percentiles of the recorded latency pings (in seconds).
+    /// If no latency pings have been recorded, None is returned.
+    pub fn get_latency_ping_percentiles_secs(&self) -> Option<PercentileSummary> {
+        PercentileSummary::from_measurements(
+            self.recorded_latency_ping_durations_secs.values().cloned(),
+        )
+    }
+
     /// Returns a copy of the recorded latency pings for test purposes
     #[cfg(test)]
     pub fn get_recorded_latency_pings(&self) -> BTreeMap<u64, f64> {
@@ -211,6 +220,15 @@ impl StateValueInterface for LatencyInfoState {
                 average_latency_ping_secs,
             );
         }
+
+        if let Some(latency_ping_percentiles) = self.get_latency_ping_percentiles_secs() {
+            // Update the p90 ping latency metric
+            metrics::observe_value(
+                &metrics::P90_PING_LATENCIES,
+                peer_network_id,
+                latency_ping_percentiles.p90,
+            );
+        }
     }
 }
 
@@ -259,6 +277,9 @@ mod test {
         // Verify the initial latency info state
         assert_eq!(latency_info_state.latency_ping_counter, 0);
         assert!(latency_info_state.get_average_latency_ping_secs().is_none());
+        assert!(latency_info_state
+            .get_latency_ping_percentiles_secs()
+            .is_none());
         verify_no_recorded_pings(&mut latency_info_state);
 
         // Attempt to handle an invalid ping response with mismatched ping counters
@@ -293,6 +314,21 @@ mod test {
             latency_info_state.get_average_latency_ping_secs().unwrap(),
             recorded_latency_pings.values().sum::<f64>() / recorded_latency_pings.len() as f64,
         );
+
+        // Verify the latency percentiles
+        let mut sorted_latency_pings: Vec<f64> = recorded_latency_pings.values().cloned().collect();
+        sorted_latency_pings.sort_by(|a, b| a.total_cmp(b));
+        let latency_ping_percentiles = latency_info_state
+            .get_latency_ping_percentiles_secs()
+            .unwrap();
+        assert_eq!(
+            latency_ping_percentiles.p50,
+            sorted_latency_pings[(sorted_latency_pings.len() - 1) / 2]
+        );
+        assert_eq!(
+            latency_ping_percentiles.p99,
+            *sorted_latency_pings.last().unwrap()
+        );
     }
 
     #[test]
@@ -14,6 +14,7 @@ use key_value::PeerStateKey;
 use std::{collections::HashMap, time::Duration};
 use tokio::runtime::Handle;
 
+pub mod bandwidth_info;
 pub mod key_value;
 pub mod latency_info;
 pub mod network_info;
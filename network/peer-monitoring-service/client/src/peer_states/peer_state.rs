@@ -4,6 +4,7 @@
 use crate::{
     metrics, network,
     peer_states::{
+        bandwidth_info::BandwidthInfoState,
         key_value::{PeerStateKey, PeerStateValue, StateValueInterface},
         latency_info::LatencyInfoState,
         network_info::NetworkInfoState,
@@ -193,6 +194,17 @@ impl PeerState {
         let average_latency_ping_secs = latency_info_state.get_average_latency_ping_secs();
         peer_monitoring_metadata.average_ping_latency_secs = average_latency_ping_secs;
 
+        // Get and store the latency ping percentiles
+        let latency_ping_percentiles_secs = latency_info_state.get_latency_ping_percentiles_secs();
+        peer_monitoring_metadata.latency_ping_percentiles_secs = latency_ping_percentiles_secs;
+
+        // Get and store the bandwidth estimate percentiles
+        let bandwidth_info_state = self.get_bandwidth_info_state()?;
+        let bandwidth_percentiles_kb_per_sec =
+            bandwidth_info_state.get_bandwidth_percentiles_kb_per_sec();
+        peer_monitoring_metadata.bandwidth_percentiles_kb_per_sec =
+            bandwidth_percentiles_kb_per_sec;
+
         // Get and store the detailed monitoring metadata
         let internal_client_state = self.get_internal_client_state()?;
         peer_monitoring_metadata.internal_client_state = internal_client_state;
@@ -224,6 +236,21 @@ impl PeerState {
         })
     }
 
+    /// Returns a copy of the bandwidth info state
+    pub(crate) fn get_bandwidth_info_state(&self) -> Result<BandwidthInfoState, Error> {
+        let peer_state_value = self
+            .get_peer_state_value(&PeerStateKey::BandwidthInfo)?
+            .read()
+            .clone();
+        match peer_state_value {
+            PeerStateValue::BandwidthInfoState(bandwidth_info_state) => Ok(bandwidth_info_state),
+            peer_state_value => Err(Error::UnexpectedError(format!(
+                "Invalid peer state value found! Expected bandwidth_info_state but got: {:?}",
+                peer_state_value
+            ))),
+        }
+    }
+
     /// Returns a copy of the latency ping state
     pub(crate) fn get_latency_info_state(&self) -> Result<LatencyInfoState, Error> {
         let peer_state_value = self
@@ -0,0 +1,591 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    logging::{LogEntry, LogEvent, LogSchema},
+    metrics, network,
+    network::PeerMonitoringServiceClient,
+    Error,
+};
+use aptos_config::{
+    config::{PeerMonitoringServiceConfig, SoakTestConfig},
+    network_id::PeerNetworkId,
+};
+use aptos_id_generator::{IdGenerator, U64IdGenerator};
+use aptos_infallible::RwLock;
+use aptos_logger::{info, warn};
+use aptos_network::application::interface::NetworkClient;
+use aptos_peer_monitoring_service_types::{
+    request::{BandwidthProbeRequest, PeerMonitoringServiceRequest},
+    response::PeerMonitoringServiceResponse,
+    PeerMonitoringServiceMessage, PercentileSummary,
+};
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+use tokio::{runtime::Handle, task::JoinHandle};
+
+/// A recurring point-to-point throughput test against a set of chosen peers
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SoakTestSchedule {
+    pub peers: Vec<PeerId>, // The peers to test (on every network they're connected)
+    pub interval_ms: u64,   // The interval (ms) between test runs
+    pub num_probes_per_peer: u64, // The number of bandwidth probes sent to each peer per run
+    pub min_throughput_kb_per_sec: u64, // Peers with a lower median throughput raise an alert
+}
+
+/// The result of a single soak test run against a single peer
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PeerSoakTestResult {
+    pub peer_network_id: PeerNetworkId, // The tested peer
+    pub num_successful_probes: u64,     // The number of probes that succeeded
+    pub num_failed_probes: u64,         // The number of probes that failed
+    pub throughput_kb_per_sec: Option<PercentileSummary>, // The throughput of the successful probes
+    pub throughput_degraded: bool, // Whether the median throughput is below the threshold (or unknown)
+}
+
+/// The results of a single soak test run
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SoakTestRun {
+    pub start_time_usecs: u64,      // The (unix) time at which the run started
+    pub duration_ms: u64,           // The duration of the run
+    pub missing_peers: Vec<PeerId>, // The chosen peers that weren't connected
+    pub peer_results: Vec<PeerSoakTestResult>, // The results of each tested peer
+}
+
+/// The soak test report (as served by the admin service)
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SoakTestReport {
+    pub schedule: Option<SoakTestSchedule>, // The current schedule (if any)
+    pub num_completed_runs: u64,            // The number of runs completed since the node started
+    pub num_throughput_alerts: u64,         // The number of degraded peer results seen
+    pub recent_runs: VecDeque<SoakTestRun>, // The most recent runs (oldest first)
+}
+
+/// The internal soak test state, shared between the admin commands and the runner
+#[derive(Debug, Default)]
+struct SoakTestState {
+    next_run_time_usecs: u64, // The (unix) time at which the next run is due
+    report: SoakTestReport,   // The report of the runs so far
+}
+
+/// A handle for scheduling soak tests and fetching their report
+#[derive(Clone, Debug)]
+pub struct SoakTests {
+    soak_test_config: SoakTestConfig,
+    state: Arc<RwLock<SoakTestState>>,
+}
+
+impl SoakTests {
+    pub fn new(soak_test_config: SoakTestConfig) -> Self {
+        Self {
+            soak_test_config,
+            state: Arc::new(RwLock::new(SoakTestState::default())),
+        }
+    }
+
+    /// Sets the soak test schedule (replacing any existing one). The
+    /// first run starts immediately.
+    pub fn schedule(&self, schedule: SoakTestSchedule) -> Result<(), String> {
+        // Verify the schedule respects the config
+        if schedule.peers.is_empty() {
+            return Err("At least one peer must be chosen!".into());
+        }
+        if schedule.interval_ms < self.soak_test_config.min_soak_test_interval_ms {
+            return Err(format!(
+                "The soak test interval is too short: {}ms. Minimum allowed: {}ms",
+                schedule.interval_ms, self.soak_test_config.min_soak_test_interval_ms
+            ));
+        }
+        if schedule.num_probes_per_peer == 0
+            || schedule.num_probes_per_peer > self.soak_test_config.max_num_probes_per_peer
+        {
+            return Err(format!(
+                "The number of probes per peer must be between 1 and {}!",
+                self.soak_test_config.max_num_probes_per_peer
+            ));
+        }
+
+        // Update the schedule
+        let mut state = self.state.write();
+        state.next_run_time_usecs = 0;
+        state.report.schedule = Some(schedule);
+        Ok(())
+    }
+
+    /// Cancels the soak test schedule. Returns true iff a schedule existed.
+    pub fn cancel(&self) -> bool {
+        self.state.write().report.schedule.take().is_some()
+    }
+
+    /// Returns a copy of the soak test report
+    pub fn get_report(&self) -> SoakTestReport {
+        self.state.read().report.clone()
+    }
+
+    /// Returns the schedule if a soak test run is due at the given time
+    fn get_due_schedule(&self, now_usecs: u64) -> Option<SoakTestSchedule> {
+        let mut state = self.state.write();
+        let schedule = state.report.schedule.clone()?;
+        if now_usecs < state.next_run_time_usecs {
+            return None;
+        }
+
+        // Mark the next run time
+        state.next_run_time_usecs =
+            now_usecs.saturating_add(schedule.interval_ms.saturating_mul(1000));
+        Some(schedule)
+    }
+
+    /// Records the given soak test run in the report
+    fn record_run(&self, soak_test_run: SoakTestRun) {
+        let mut state = self.state.write();
+        let report = &mut state.report;
+
+        // Update the counters
+        report.num_completed_runs += 1;
+        report.num_throughput_alerts += soak_test_run
+            .peer_results
+            .iter()
+            .filter(|peer_result| peer_result.throughput_degraded)
+            .count() as u64;
+
+        // Add the run and garbage collect the oldest ones
+        report.recent_runs.push_back(soak_test_run);
+        while report.recent_runs.len() > self.soak_test_config.max_num_soak_test_runs_to_retain {
+            report.recent_runs.pop_front();
+        }
+    }
+}
+
+/// Creates the soak test result for a peer, given the throughput
+/// estimates of the successful probes and the number of failures.
+fn create_peer_result(
+    peer_network_id: PeerNetworkId,
+    throughput_estimates_kb_per_sec: Vec<f64>,
+    num_failed_probes: u64,
+    min_throughput_kb_per_sec: u64,
+) -> PeerSoakTestResult {
+    let num_successful_probes = throughput_estimates_kb_per_sec.len() as u64;
+    let throughput_kb_per_sec =
+        PercentileSummary::from_measurements(throughput_estimates_kb_per_sec);
+    let throughput_degraded = match throughput_kb_per_sec {
+        Some(throughput_kb_per_sec) => throughput_kb_per_sec.p50 < min_throughput_kb_per_sec as f64,
+        None => true, // All probes failed
+    };
+
+    PeerSoakTestResult {
+        peer_network_id,
+        num_successful_probes,
+        num_failed_probes,
+        throughput_kb_per_sec,
+        throughput_degraded,
+    }
+}
+
+/// Spawns a task that runs the scheduled soak tests
+pub(crate) fn spawn_soak_test_runner(
+    peer_monitoring_config: PeerMonitoringServiceConfig,
+    soak_tests: SoakTests,
+    peer_monitoring_client: PeerMonitoringServiceClient<
+        NetworkClient<PeerMonitoringServiceMessage>,
+    >,
+    request_id_generator: Arc<U64IdGenerator>,
+    time_service: TimeService,
+    runtime: Option<Handle>,
+) -> JoinHandle<()> {
+    let soak_test_runner = async move {
+        // Create an interval ticker for the runner loop
+        let soak_test_check_duration = Duration::from_millis(
+            peer_monitoring_config
+                .soak_testing
+                .soak_test_check_interval_ms,
+        );
+        let soak_test_ticker = time_service.interval(soak_test_check_duration);
+        futures::pin_mut!(soak_test_ticker);
+
+        // Start the runner loop
+        info!(LogSchema::new(LogEntry::SoakTest)
+            .event(LogEvent::StartedSoakTestLoop)
+            .message("Starting the soak test runner!"));
+        loop {
+            // Wait for the next round before checking the schedule
+            soak_test_ticker.next().await;
+
+            // Run the soak test (if one is due)
+            let start_time_usecs = time_service.now_unix_time().as_micros() as u64;
+            if let Some(schedule) = soak_tests.get_due_schedule(start_time_usecs) {
+                let soak_test_run = run_soak_test(
+                    &peer_monitoring_config,
+                    &schedule,
+                    &peer_monitoring_client,
+                    &request_id_generator,
+                    &time_service,
+                    start_time_usecs,
+                )
+                .await;
+                soak_tests.record_run(soak_test_run);
+            }
+        }
+    };
+
+    // Spawn the soak test runner task
+    if let Some(runtime) = runtime {
+        runtime.spawn(soak_test_runner)
+    } else {
+        tokio::spawn(soak_test_runner)
+    }
+}
+
+/// Runs a single soak test against the chosen peers (one peer at a time,
+/// so that the probes don't compete for the local bandwidth).
+async fn run_soak_test(
+    peer_monitoring_config: &PeerMonitoringServiceConfig,
+    schedule: &SoakTestSchedule,
+    peer_monitoring_client: &PeerMonitoringServiceClient<
+        NetworkClient<PeerMonitoringServiceMessage>,
+    >,
+    request_id_generator: &Arc<U64IdGenerator>,
+    time_service: &TimeService,
+    start_time_usecs: u64,
+) -> SoakTestRun {
+    let run_start_time = time_service.now();
+
+    // Identify the chosen peers that are connected
+    let connected_peers: Vec<PeerNetworkId> = peer_monitoring_client
+        .get_peers_and_metadata()
+        .get_connected_peers_and_metadata()
+        .map(|connected_peers| connected_peers.into_keys().collect())
+        .unwrap_or_default();
+    let missing_peers = schedule
+        .peers
+        .iter()
+        .filter(|peer_id| {
+            !connected_peers
+                .iter()
+                .any(|peer_network_id| peer_network_id.peer_id() == **peer_id)
+        })
+        .cloned()
+        .collect();
+
+    // Probe each chosen peer
+    let mut peer_results = vec![];
+    for peer_network_id in connected_peers
+        .into_iter()
+        .filter(|peer_network_id| schedule.peers.contains(&peer_network_id.peer_id()))
+    {
+        let mut throughput_estimates_kb_per_sec = vec![];
+        let mut num_failed_probes = 0;
+        for probe_counter in 0..schedule.num_probes_per_peer {
+            match send_bandwidth_probe(
+                peer_monitoring_config,
+                peer_monitoring_client,
+                request_id_generator,
+                time_service,
+                &peer_network_id,
+                probe_counter,
+            )
+            .await
+            {
+                Some(throughput_kb_per_sec) => {
+                    throughput_estimates_kb_per_sec.push(throughput_kb_per_sec)
+                },
+                None => num_failed_probes += 1,
+            }
+        }
+
+        // Create the result and raise an alert if the throughput degraded
+        let peer_result = create_peer_result(
+            peer_network_id,
+            throughput_estimates_kb_per_sec,
+            num_failed_probes,
+            schedule.min_throughput_kb_per_sec,
+        );
+        if peer_result.throughput_degraded {
+            metrics::increment_request_counter(
+                &metrics::SOAK_TEST_THROUGHPUT_ALERTS,
+                "throughput_degraded",
+                &peer_network_id,
+            );
+            warn!(LogSchema::new(LogEntry::SoakTest)
+                .event(LogEvent::ThroughputDegraded)
+                .peer(&peer_network_id)
+                .message(&format!(
+                    "The peer throughput is below the threshold of {} KB/sec! Result: {:?}",
+                    schedule.min_throughput_kb_per_sec, peer_result
+                )));
+        }
+        peer_results.push(peer_result);
+    }
+
+    SoakTestRun {
+        start_time_usecs,
+        duration_ms: run_start_time.elapsed().as_millis() as u64,
+        missing_peers,
+        peer_results,
+    }
+}
+
+/// Sends a single bandwidth probe to the peer and returns the
+/// throughput estimate (KB/sec), or None if the probe failed. Each probe
+/// keeps several RPCs in flight, so that the estimate reflects a sustained
+/// transfer and isn't bound by the round trip time of a single RPC.
+async fn send_bandwidth_probe(
+    peer_monitoring_config: &PeerMonitoringServiceConfig,
+    peer_monitoring_client: &PeerMonitoringServiceClient<
+        NetworkClient<PeerMonitoringServiceMessage>,
+    >,
+    request_id_generator: &Arc<U64IdGenerator>,
+    time_service: &TimeService,
+    peer_network_id: &PeerNetworkId,
+    probe_counter: u64,
+) -> Option<f64> {
+    let bandwidth_monitoring_config = &peer_monitoring_config.bandwidth_monitoring;
+    let num_response_bytes = bandwidth_monitoring_config.bandwidth_probe_data_size;
+    let request = PeerMonitoringServiceRequest::BandwidthProbe(BandwidthProbeRequest {
+        probe_counter,
+        num_response_bytes,
+    });
+
+    // Send the probe RPCs concurrently and time the responses
+    let num_rpcs = peer_monitoring_config
+        .soak_testing
+        .num_concurrent_rpcs_per_probe;
+    let start_time = time_service.now();
+    let responses = futures::future::join_all((0..num_rpcs).map(|_| {
+        network::send_request_to_peer(
+            peer_monitoring_client.clone(),
+            peer_network_id,
+            request_id_generator.next(),
+            request.clone(),
+            bandwidth_monitoring_config.bandwidth_probe_timeout_ms,
+        )
+    }))
+    .await;
+    let response_time_secs = start_time.elapsed().as_secs_f64();
+
+    calculate_probe_throughput(
+        responses,
+        probe_counter,
+        num_response_bytes,
+        response_time_secs,
+    )
+}
+
+/// Verifies the responses of a bandwidth probe and calculates the throughput
+/// (KB/sec). The probe fails (None is returned) if any of the responses is invalid.
+fn calculate_probe_throughput(
+    responses: Vec<Result<PeerMonitoringServiceResponse, Error>>,
+    probe_counter: u64,
+    num_response_bytes: u64,
+    response_time_secs: f64,
+) -> Option<f64> {
+    // Avoid dividing by zero (e.g., if the time service doesn't advance)
+    if responses.is_empty() || response_time_secs <= 0.0 {
+        return None;
+    }
+
+    // Verify each response
+    for response in &responses {
+        match response {
+            Ok(PeerMonitoringServiceResponse::BandwidthProbe(response))
+                if response.probe_counter == probe_counter
+                    && response.data.len() as u64 == num_response_bytes => {},
+            _ => return None,
+        }
+    }
+
+    // Calculate the throughput across all responses
+    let total_response_bytes = num_response_bytes * responses.len() as u64;
+    Some((total_response_bytes as f64 / 1024.0) / response_time_secs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_config::network_id::NetworkId;
+    use aptos_peer_monitoring_service_types::response::BandwidthProbeResponse;
+
+    #[test]
+    fn test_schedule_validation() {
+        // Create the soak tests
+        let soak_test_config = SoakTestConfig::default();
+        let soak_tests = SoakTests::new(soak_test_config);
+
+        // Verify that schedules without peers are rejected
+        let schedule = create_schedule(vec![], soak_test_config.min_soak_test_interval_ms, 1);
+        assert!(soak_tests.schedule(schedule).is_err());
+
+        // Verify that schedules with short intervals are rejected
+        let schedule = create_schedule(
+            vec![PeerId::random()],
+            soak_test_config.min_soak_test_interval_ms - 1,
+            1,
+        );
+        assert!(soak_tests.schedule(schedule).is_err());
+
+        // Verify that schedules with too many probes are rejected
+        let schedule = create_schedule(
+            vec![PeerId::random()],
+            soak_test_config.min_soak_test_interval_ms,
+            soak_test_config.max_num_probes_per_peer + 1,
+        );
+        assert!(soak_tests.schedule(schedule).is_err());
+        assert!(soak_tests.get_report().schedule.is_none());
+
+        // Verify that a valid schedule is accepted and can be cancelled
+        let schedule = create_schedule(
+            vec![PeerId::random()],
+            soak_test_config.min_soak_test_interval_ms,
+            1,
+        );
+        soak_tests.schedule(schedule.clone()).unwrap();
+        assert_eq!(soak_tests.get_report().schedule, Some(schedule));
+        assert!(soak_tests.cancel());
+        assert!(!soak_tests.cancel());
+    }
+
+    #[test]
+    fn test_due_schedule() {
+        // Create the soak tests and schedule a test
+        let soak_test_config = SoakTestConfig::default();
+        let soak_tests = SoakTests::new(soak_test_config);
+        let interval_ms = soak_test_config.min_soak_test_interval_ms;
+        let schedule = create_schedule(vec![PeerId::random()], interval_ms, 1);
+        soak_tests.schedule(schedule.clone()).unwrap();
+
+        // Verify the first run is due immediately
+        let now_usecs = 1_000_000;
+        assert_eq!(
+            soak_tests.get_due_schedule(now_usecs),
+            Some(schedule.clone())
+        );
+
+        // Verify the next run is only due after the interval
+        assert!(soak_tests.get_due_schedule(now_usecs + 1).is_none());
+        let next_run_time_usecs = now_usecs + interval_ms * 1000;
+        assert!(soak_tests
+            .get_due_schedule(next_run_time_usecs - 1)
+            .is_none());
+        assert_eq!(
+            soak_tests.get_due_schedule(next_run_time_usecs),
+            Some(schedule)
+        );
+
+        // Verify no runs are due once the schedule is cancelled
+        soak_tests.cancel();
+        assert!(soak_tests.get_due_schedule(u64::MAX).is_none());
+    }
+
+    #[test]
+    fn test_report_and_alerts() {
+        // Create the soak tests
+        let soak_test_config = SoakTestConfig::default();
+        let soak_tests = SoakTests::new(soak_test_config);
+
+        // Create a healthy peer result and verify no alert is raised
+        let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let healthy_result = create_peer_result(peer_network_id, vec![2048.0, 4096.0], 0, 1024);
+        assert!(!healthy_result.throughput_degraded);
+        assert_eq!(healthy_result.num_successful_probes, 2);
+
+        // Create degraded peer results and verify alerts are raised
+        let slow_result = create_peer_result(peer_network_id, vec![10.0, 20.0, 4096.0], 0, 1024);
+        assert!(slow_result.throughput_degraded);
+        let failed_result = create_peer_result(peer_network_id, vec![], 5, 1024);
+        assert!(failed_result.throughput_degraded);
+        assert!(failed_result.throughput_kb_per_sec.is_none());
+
+        // Record many runs and verify the report
+        let num_runs = soak_test_config.max_num_soak_test_runs_to_retain as u64 * 2;
+        for i in 0..num_runs {
+            soak_tests.record_run(SoakTestRun {
+                start_time_usecs: i,
+                duration_ms: 0,
+                missing_peers: vec![],
+                peer_results: vec![
+                    healthy_result.clone(),
+                    slow_result.clone(),
+                    failed_result.clone(),
+                ],
+            });
+        }
+        let report = soak_tests.get_report();
+        assert_eq!(report.num_completed_runs, num_runs);
+        assert_eq!(report.num_throughput_alerts, num_runs * 2);
+        assert_eq!(
+            report.recent_runs.len(),
+            soak_test_config.max_num_soak_test_runs_to_retain
+        );
+        assert_eq!(
+            report.recent_runs.front().unwrap().start_time_usecs,
+            num_runs - soak_test_config.max_num_soak_test_runs_to_retain as u64
+        );
+    }
+
+    #[test]
+    fn test_probe_throughput() {
+        // Create several valid bandwidth probe responses (64 KB each)
+        let probe_counter = 10;
+        let num_response_bytes = 64 * 1024;
+        let create_response = |probe_counter: u64, num_bytes: u64| {
+            Ok(PeerMonitoringServiceResponse::BandwidthProbe(
+                BandwidthProbeResponse {
+                    probe_counter,
+                    data: vec![0; num_bytes as usize],
+                },
+            ))
+        };
+        let num_rpcs = 16;
+        let responses = || {
+            (0..num_rpcs)
+                .map(|_| create_response(probe_counter, num_response_bytes))
+                .collect::<Vec<_>>()
+        };
+
+        // Verify the throughput covers the data of all responses
+        let throughput_kb_per_sec =
+            calculate_probe_throughput(responses(), probe_counter, num_response_bytes, 2.0)
+                .unwrap();
+        assert_eq!(throughput_kb_per_sec, (num_rpcs * 64) as f64 / 2.0);
+
+        // Verify the probe fails if a single response is invalid
+        for invalid_response in [
+            create_response(probe_counter + 1, num_response_bytes),
+            create_response(probe_counter, num_response_bytes - 1),
+            Err(Error::UnexpectedError("Failed!".into())),
+        ] {
+            let mut responses = responses();
+            responses.push(invalid_response);
+            assert!(
+                calculate_probe_throughput(responses, probe_counter, num_response_bytes, 2.0)
+                    .is_none()
+            );
+        }
+
+        // Verify the probe fails if no time has passed or no responses were received
+        assert!(
+            calculate_probe_throughput(responses(), probe_counter, num_response_bytes, 0.0)
+                .is_none()
+        );
+        assert!(
+            calculate_probe_throughput(vec![], probe_counter, num_response_bytes, 2.0).is_none()
+        );
+    }
+
+    /// Creates a soak test schedule with the given parameters
+    fn create_schedule(
+        peers: Vec<PeerId>,
+        interval_ms: u64,
+        num_probes_per_peer: u64,
+    ) -> SoakTestSchedule {
+        SoakTestSchedule {
+            peers,
+            interval_ms,
+            num_probes_per_peer,
+            min_throughput_kb_per_sec: 1024,
+        }
+    }
+}
@@ -6,10 +6,10 @@ use crate::{
     tests::{
         mock::MockMonitoringServer,
         utils::{
-            disabled_latency_monitoring_config, disabled_network_monitoring_config,
-            disabled_node_monitoring_config, initialize_and_verify_peer_states, spawn_with_timeout,
-            start_peer_monitor, verify_empty_peer_states, wait_for_peer_state_update,
-            wait_for_request_failure,
+            disabled_bandwidth_monitoring_config, disabled_latency_monitoring_config,
+            disabled_network_monitoring_config, disabled_node_monitoring_config,
+            initialize_and_verify_peer_states, spawn_with_timeout, start_peer_monitor,
+            verify_empty_peer_states, wait_for_peer_state_update, wait_for_request_failure,
         },
     },
     PeerMonitorState,
@@ -295,6 +295,7 @@ async fn test_performance_monitoring_request_failures() {
 fn config_with_performance_requests() -> NodeConfig {
     NodeConfig {
         peer_monitoring_service: PeerMonitoringServiceConfig {
+            bandwidth_monitoring: disabled_bandwidth_monitoring_config(),
             latency_monitoring: disabled_latency_monitoring_config(),
             network_monitoring: disabled_network_monitoring_config(),
             node_monitoring: disabled_node_monitoring_config(),
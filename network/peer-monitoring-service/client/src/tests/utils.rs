@@ -9,8 +9,8 @@ use crate::{
 };
 use aptos_config::{
     config::{
-        LatencyMonitoringConfig, NetworkMonitoringConfig, NodeConfig, NodeMonitoringConfig,
-        PeerMonitoringServiceConfig, PeerRole, PerformanceMonitoringConfig,
+        BandwidthMonitoringConfig, LatencyMonitoringConfig, NetworkMonitoringConfig, NodeConfig,
+        NodeMonitoringConfig, PeerMonitoringServiceConfig, PeerRole, PerformanceMonitoringConfig,
     },
     network_id::{NetworkId, PeerNetworkId},
 };
@@ -18,8 +18,9 @@ use aptos_network::application::{interface::NetworkClient, storage::PeersAndMeta
 use aptos_peer_monitoring_service_types::{
     request::{LatencyPingRequest, PeerMonitoringServiceRequest},
     response::{
-        ConnectionMetadata, LatencyPingResponse, NetworkInformationResponse,
-        NodeInformationResponse, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+        BandwidthProbeResponse, ConnectionMetadata, LatencyPingResponse,
+        NetworkInformationResponse, NodeInformationResponse, PeerMonitoringServiceResponse,
+        ServerProtocolVersionResponse,
     },
     PeerMonitoringServiceMessage,
 };
@@ -48,6 +49,7 @@ const UNREALISTIC_INTERVAL_MS: u64 = 1_000_000_000; // Unrealistically high inte
 pub fn config_with_latency_ping_requests() -> NodeConfig {
     NodeConfig {
         peer_monitoring_service: PeerMonitoringServiceConfig {
+            bandwidth_monitoring: disabled_bandwidth_monitoring_config(),
             network_monitoring: disabled_network_monitoring_config(),
             node_monitoring: disabled_node_monitoring_config(),
             performance_monitoring: disabled_performance_monitoring_config(),
@@ -61,6 +63,7 @@ pub fn config_with_latency_ping_requests() -> NodeConfig {
 pub fn config_with_network_info_requests() -> NodeConfig {
     NodeConfig {
         peer_monitoring_service: PeerMonitoringServiceConfig {
+            bandwidth_monitoring: disabled_bandwidth_monitoring_config(),
             latency_monitoring: disabled_latency_monitoring_config(),
             node_monitoring: disabled_node_monitoring_config(),
             performance_monitoring: disabled_performance_monitoring_config(),
@@ -74,6 +77,7 @@ pub fn config_with_network_info_requests() -> NodeConfig {
 pub fn config_with_node_info_requests() -> NodeConfig {
     NodeConfig {
         peer_monitoring_service: PeerMonitoringServiceConfig {
+            bandwidth_monitoring: disabled_bandwidth_monitoring_config(),
             latency_monitoring: disabled_latency_monitoring_config(),
             network_monitoring: disabled_network_monitoring_config(),
             performance_monitoring: disabled_performance_monitoring_config(),
@@ -87,6 +91,7 @@ pub fn config_with_node_info_requests() -> NodeConfig {
 pub fn config_with_only_latency_and_network_requests() -> NodeConfig {
     NodeConfig {
         peer_monitoring_service: PeerMonitoringServiceConfig {
+            bandwidth_monitoring: disabled_bandwidth_monitoring_config(),
             node_monitoring: disabled_node_monitoring_config(),
             performance_monitoring: disabled_performance_monitoring_config(),
             ..Default::default()
@@ -151,6 +156,14 @@ pub fn create_node_info_response(
     }
 }
 
+/// Returns a bandwidth monitoring config where bandwidth probes are disabled
+pub fn disabled_bandwidth_monitoring_config() -> BandwidthMonitoringConfig {
+    BandwidthMonitoringConfig {
+        bandwidth_probe_interval_ms: UNREALISTIC_INTERVAL_MS,
+        ..Default::default()
+    }
+}
+
 /// Returns a latency monitoring config where latency requests are disabled
 pub fn disabled_latency_monitoring_config() -> LatencyMonitoringConfig {
     LatencyMonitoringConfig {
@@ -654,6 +667,12 @@ pub async fn verify_all_requests_and_respond(
                         ping_counter: latency_ping.ping_counter,
                     })
                 },
+                PeerMonitoringServiceRequest::BandwidthProbe(bandwidth_probe) => {
+                    PeerMonitoringServiceResponse::BandwidthProbe(BandwidthProbeResponse {
+                        probe_counter: bandwidth_probe.probe_counter,
+                        data: vec![0; bandwidth_probe.num_response_bytes as usize],
+                    })
+                },
                 #[cfg(feature = "network-perf-test")] // Disabled by default
                 PeerMonitoringServiceRequest::PerformanceMonitoringRequest(request) => {
                     PeerMonitoringServiceResponse::PerformanceMonitoring(
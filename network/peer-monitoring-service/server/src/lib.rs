@@ -11,16 +11,17 @@ use crate::{
 };
 use aptos_bounded_executor::BoundedExecutor;
 use aptos_config::{
-    config::{BaseConfig, NodeConfig},
+    config::{BaseConfig, NodeConfig, PeerMonitoringServiceConfig},
     network_id::NetworkId,
 };
 use aptos_logger::prelude::*;
 use aptos_network::application::storage::PeersAndMetadata;
 use aptos_peer_monitoring_service_types::{
-    request::{LatencyPingRequest, PeerMonitoringServiceRequest},
+    request::{BandwidthProbeRequest, LatencyPingRequest, PeerMonitoringServiceRequest},
     response::{
-        ConnectionMetadata, LatencyPingResponse, NetworkInformationResponse,
-        NodeInformationResponse, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+        BandwidthProbeResponse, ConnectionMetadata, LatencyPingResponse,
+        NetworkInformationResponse, NodeInformationResponse, PeerMonitoringServiceResponse,
+        PeerReputationsResponse, ServerProtocolVersionResponse,
     },
     PeerMonitoringServiceError, Result, MAX_DISTANCE_FROM_VALIDATORS,
 };
@@ -40,12 +41,13 @@ pub mod storage;
 mod tests;
 
 /// Peer monitoring server constants
-pub const PEER_MONITORING_SERVER_VERSION: u64 = 1;
+pub const PEER_MONITORING_SERVER_VERSION: u64 = 3;
 
 /// The server-side actor for the peer monitoring service
 pub struct PeerMonitoringServiceServer<T> {
     base_config: BaseConfig,
     bounded_executor: BoundedExecutor,
+    monitoring_service_config: PeerMonitoringServiceConfig,
     network_requests: PeerMonitoringServiceNetworkEvents,
     peers_and_metadata: Arc<PeersAndMetadata>,
     start_time: Instant,
@@ -67,11 +69,13 @@ impl<T: StorageReaderInterface> PeerMonitoringServiceServer<T> {
             node_config.peer_monitoring_service.max_concurrent_requests as usize,
             executor,
         );
+        let monitoring_service_config = node_config.peer_monitoring_service;
         let start_time = time_service.now();
 
         Self {
             base_config,
             bounded_executor,
+            monitoring_service_config,
             network_requests,
             peers_and_metadata,
             start_time,
@@ -98,6 +102,7 @@ impl<T: StorageReaderInterface> PeerMonitoringServiceServer<T> {
             // All handler methods are currently CPU-bound so we want
             // to spawn on the blocking thread pool.
             let base_config = self.base_config.clone();
+            let monitoring_service_config = self.monitoring_service_config;
             let peers_and_metadata = self.peers_and_metadata.clone();
             let start_time = self.start_time;
             let storage = self.storage.clone();
@@ -106,6 +111,7 @@ impl<T: StorageReaderInterface> PeerMonitoringServiceServer<T> {
                 .spawn_blocking(move || {
                     let response = Handler::new(
                         base_config,
+                        monitoring_service_config,
                         peers_and_metadata,
                         start_time,
                         storage,
@@ -129,6 +135,7 @@ impl<T: StorageReaderInterface> PeerMonitoringServiceServer<T> {
 #[derive(Clone)]
 pub struct Handler<T> {
     base_config: BaseConfig,
+    monitoring_service_config: PeerMonitoringServiceConfig,
     peers_and_metadata: Arc<PeersAndMetadata>,
     start_time: Instant,
     storage: T,
@@ -138,6 +145,7 @@ pub struct Handler<T> {
 impl<T: StorageReaderInterface> Handler<T> {
     pub fn new(
         base_config: BaseConfig,
+        monitoring_service_config: PeerMonitoringServiceConfig,
         peers_and_metadata: Arc<PeersAndMetadata>,
         start_time: Instant,
         storage: T,
@@ -145,6 +153,7 @@ impl<T: StorageReaderInterface> Handler<T> {
     ) -> Self {
         Self {
             base_config,
+            monitoring_service_config,
             peers_and_metadata,
             start_time,
             storage,
@@ -179,6 +188,10 @@ impl<T: StorageReaderInterface> Handler<T> {
             },
             PeerMonitoringServiceRequest::GetNodeInformation => self.get_node_information(),
             PeerMonitoringServiceRequest::LatencyPing(request) => self.handle_latency_ping(request),
+            PeerMonitoringServiceRequest::GetPeerReputations => self.get_peer_reputations(),
+            PeerMonitoringServiceRequest::BandwidthProbe(request) => {
+                self.handle_bandwidth_probe(request)
+            },
 
             #[cfg(feature = "network-perf-test")] // Disabled by default
             PeerMonitoringServiceRequest::PerformanceMonitoringRequest(request) => {
@@ -252,6 +265,17 @@ impl<T: StorageReaderInterface> Handler<T> {
         ))
     }
 
+    fn get_peer_reputations(&self) -> Result<PeerMonitoringServiceResponse, Error> {
+        let peer_reputations = self
+            .peers_and_metadata
+            .get_peer_reputation_scores()
+            .into_iter()
+            .collect();
+        Ok(PeerMonitoringServiceResponse::PeerReputations(
+            PeerReputationsResponse { peer_reputations },
+        ))
+    }
+
     fn get_server_protocol_version(&self) -> Result<PeerMonitoringServiceResponse, Error> {
         let server_protocol_version_response = ServerProtocolVersionResponse {
             version: PEER_MONITORING_SERVER_VERSION,
@@ -285,6 +309,30 @@ impl<T: StorageReaderInterface> Handler<T> {
         ))
     }
 
+    fn handle_bandwidth_probe(
+        &self,
+        bandwidth_probe_request: &BandwidthProbeRequest,
+    ) -> Result<PeerMonitoringServiceResponse, Error> {
+        // Verify the requested data size respects the max response size
+        let num_response_bytes = bandwidth_probe_request.num_response_bytes;
+        let max_num_response_bytes = self.monitoring_service_config.max_num_response_bytes;
+        if num_response_bytes >= max_num_response_bytes {
+            return Err(Error::InvalidRequest(format!(
+                "The requested bandwidth probe size is too large: {:?}. Maximum allowed: {:?}",
+                num_response_bytes, max_num_response_bytes
+            )));
+        }
+
+        // Create and return the response
+        let bandwidth_probe_response = BandwidthProbeResponse {
+            probe_counter: bandwidth_probe_request.probe_counter,
+            data: vec![0; num_response_bytes as usize],
+        };
+        Ok(PeerMonitoringServiceResponse::BandwidthProbe(
+            bandwidth_probe_response,
+        ))
+    }
+
     fn handle_latency_ping(
         &self,
         latency_ping_request: &LatencyPingRequest,
@@ -17,7 +17,8 @@ use aptos_logger::Level;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_network::{
     application::{
-        interface::NetworkServiceEvents, metadata::ConnectionState, storage::PeersAndMetadata,
+        interface::NetworkServiceEvents, metadata::ConnectionState, reputation::PeerMisbehavior,
+        storage::PeersAndMetadata,
     },
     peer_manager::PeerManagerNotification,
     protocols::{
@@ -28,10 +29,10 @@ use aptos_network::{
     transport::{ConnectionId, ConnectionMetadata},
 };
 use aptos_peer_monitoring_service_types::{
-    request::{LatencyPingRequest, PeerMonitoringServiceRequest},
+    request::{BandwidthProbeRequest, LatencyPingRequest, PeerMonitoringServiceRequest},
     response::{
         NetworkInformationResponse, NodeInformationResponse, PeerMonitoringServiceResponse,
-        ServerProtocolVersionResponse,
+        PeerReputationScore, PeerReputationsResponse, ServerProtocolVersionResponse,
     },
     PeerMonitoringMetadata, PeerMonitoringServiceError, PeerMonitoringServiceMessage,
 };
@@ -385,6 +386,76 @@ async fn test_latency_ping_request() {
     }
 }
 
+#[tokio::test]
+async fn test_bandwidth_probe_request() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _, _) = MockClient::new(None, None, None);
+    tokio::spawn(service.start());
+
+    // Process several bandwidth probes and verify the responses
+    for i in 0..10 {
+        let num_response_bytes = i * 1024;
+        let request = PeerMonitoringServiceRequest::BandwidthProbe(BandwidthProbeRequest {
+            probe_counter: i,
+            num_response_bytes,
+        });
+        let response = mock_client.send_request(request).await.unwrap();
+        match response {
+            PeerMonitoringServiceResponse::BandwidthProbe(bandwidth_probe_response) => {
+                assert_eq!(bandwidth_probe_response.probe_counter, i);
+                assert_eq!(
+                    bandwidth_probe_response.data.len() as u64,
+                    num_response_bytes
+                );
+            },
+            _ => panic!("Expected bandwidth probe response but got: {:?}", response),
+        }
+    }
+
+    // Send a bandwidth probe that is too large and verify an error is returned
+    let max_num_response_bytes = PeerMonitoringServiceConfig::default().max_num_response_bytes;
+    let request = PeerMonitoringServiceRequest::BandwidthProbe(BandwidthProbeRequest {
+        probe_counter: 0,
+        num_response_bytes: max_num_response_bytes,
+    });
+    let error = mock_client.send_request(request).await.unwrap_err();
+    assert!(matches!(
+        error,
+        PeerMonitoringServiceError::InvalidRequest(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_get_peer_reputations() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _, peers_and_metadata) = MockClient::new(None, None, None);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the peer reputations and verify an empty response
+    verify_peer_reputations(&mut mock_client, BTreeMap::new()).await;
+
+    // Report misbehavior for a peer
+    let peer_network_id_1 = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    peers_and_metadata.report_peer_misbehavior(peer_network_id_1, PeerMisbehavior::InvalidProof);
+
+    // Verify the peer's reputation is lowered
+    let expected_peer_reputations = btreemap! {
+        peer_network_id_1 => PeerReputationScore { score: 75, is_banned: false },
+    };
+    verify_peer_reputations(&mut mock_client, expected_peer_reputations).await;
+
+    // Report equivocation for another peer
+    let peer_network_id_2 = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    peers_and_metadata.report_peer_misbehavior(peer_network_id_2, PeerMisbehavior::Equivocation);
+
+    // Verify the peer is banned
+    let expected_peer_reputations = btreemap! {
+        peer_network_id_1 => PeerReputationScore { score: 75, is_banned: false },
+        peer_network_id_2 => PeerReputationScore { score: 0, is_banned: true },
+    };
+    verify_peer_reputations(&mut mock_client, expected_peer_reputations).await;
+}
+
 cfg_block! {
     #[cfg(feature = "network-perf-test")] { // Disabled by default
         #[tokio::test]
@@ -431,6 +502,24 @@ fn create_connection_metadata(peer_id: AccountAddress, peer_role: PeerRole) -> C
     )
 }
 
+/// A simple utility function that sends a request for peer reputations using the
+/// given client, and verifies the response is correct.
+async fn verify_peer_reputations(
+    client: &mut MockClient,
+    expected_peer_reputations: BTreeMap<PeerNetworkId, PeerReputationScore>,
+) {
+    // Send a request to fetch the peer reputations
+    let request = PeerMonitoringServiceRequest::GetPeerReputations;
+    let response = client.send_request(request).await.unwrap();
+
+    // Verify the response is correct
+    let expected_response =
+        PeerMonitoringServiceResponse::PeerReputations(PeerReputationsResponse {
+            peer_reputations: expected_peer_reputations,
+        });
+    assert_eq!(response, expected_response);
+}
+
 /// A simple utility function that sends a request for network info using the given
 /// client, and verifies the response is correct.
 async fn verify_network_information(
@@ -44,6 +44,8 @@ pub enum PeerMonitoringServiceMessage {
 #[derive(Clone, Default, Deserialize, PartialEq, Serialize)]
 pub struct PeerMonitoringMetadata {
     pub average_ping_latency_secs: Option<f64>, // The average latency ping for the peer
+    pub latency_ping_percentiles_secs: Option<PercentileSummary>, // Percentiles of the recent latency pings
+    pub bandwidth_percentiles_kb_per_sec: Option<PercentileSummary>, // Percentiles of the recent bandwidth probes
     pub latest_network_info_response: Option<NetworkInformationResponse>, // The latest network info response
     pub latest_node_info_response: Option<NodeInformationResponse>, // The latest node info response
     pub internal_client_state: Option<String>, // A detailed client state string for debugging and logging
@@ -62,6 +64,8 @@ impl PeerMonitoringMetadata {
     ) -> Self {
         PeerMonitoringMetadata {
             average_ping_latency_secs,
+            latency_ping_percentiles_secs: None,
+            bandwidth_percentiles_kb_per_sec: None,
             latest_network_info_response,
             latest_node_info_response,
             internal_client_state,
@@ -74,8 +78,11 @@ impl Display for PeerMonitoringMetadata {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ average_ping_latency_secs: {}, latest_network_info_response: {}, latest_node_info_response: {} }}",
+            "{{ average_ping_latency_secs: {}, latency_ping_percentiles_secs: {}, bandwidth_percentiles_kb_per_sec: {}, \
+            latest_network_info_response: {}, latest_node_info_response: {} }}",
             display_format_option(&self.average_ping_latency_secs),
+            display_format_option(&self.latency_ping_percentiles_secs),
+            display_format_option(&self.bandwidth_percentiles_kb_per_sec),
             display_format_option(&self.latest_network_info_response),
             display_format_option(&self.latest_node_info_response),
         )
@@ -88,14 +95,59 @@ impl Debug for PeerMonitoringMetadata {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ average_ping_latency_secs: {}, latest_network_info_response: {}, latest_node_info_response: {} }}",
+            "{{ average_ping_latency_secs: {}, latency_ping_percentiles_secs: {}, bandwidth_percentiles_kb_per_sec: {}, \
+            latest_network_info_response: {}, latest_node_info_response: {} }}",
             debug_format_option(&self.average_ping_latency_secs),
+            debug_format_option(&self.latency_ping_percentiles_secs),
+            debug_format_option(&self.bandwidth_percentiles_kb_per_sec),
             debug_format_option(&self.latest_network_info_response),
             debug_format_option(&self.latest_node_info_response),
         )
     }
 }
 
+/// A summary of the percentiles for a set of recent measurements
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PercentileSummary {
+    pub p50: f64, // The median measurement
+    pub p90: f64, // The 90th percentile measurement
+    pub p99: f64, // The 99th percentile measurement
+}
+
+impl PercentileSummary {
+    /// Calculates the percentile summary for the given measurements.
+    /// If no measurements are given, None is returned.
+    pub fn from_measurements(measurements: impl IntoIterator<Item = f64>) -> Option<Self> {
+        // Sort the measurements
+        let mut measurements: Vec<f64> = measurements.into_iter().collect();
+        if measurements.is_empty() {
+            return None;
+        }
+        measurements.sort_by(|a, b| a.total_cmp(b));
+
+        // Calculate the percentiles (using the nearest-rank method)
+        let get_percentile = |percentile: f64| {
+            let rank = ((percentile / 100.0) * measurements.len() as f64).ceil() as usize;
+            measurements[rank.saturating_sub(1).min(measurements.len() - 1)]
+        };
+        Some(Self {
+            p50: get_percentile(50.0),
+            p90: get_percentile(90.0),
+            p99: get_percentile(99.0),
+        })
+    }
+}
+
+impl Display for PercentileSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ p50: {:.4}, p90: {:.4}, p99: {:.4} }}",
+            self.p50, self.p90, self.p99
+        )
+    }
+}
+
 /// A simple utility function for debug formatting an optional value
 fn debug_format_option<T: Debug>(option: &Option<T>) -> String {
     option
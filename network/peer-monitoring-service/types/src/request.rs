@@ -11,6 +11,8 @@ pub enum PeerMonitoringServiceRequest {
     GetNodeInformation,       // Returns relevant node information about the peer
     GetServerProtocolVersion, // Fetches the protocol version run by the server
     LatencyPing(LatencyPingRequest), // A simple message used by the client to ensure liveness and measure latency
+    GetPeerReputations, // Returns the reputation scores of the peers known to the server
+    BandwidthProbe(BandwidthProbeRequest), // A request for a fixed amount of data, used to estimate bandwidth
 
     #[cfg(feature = "network-perf-test")] // Disabled by default
     PerformanceMonitoringRequest(PerformanceMonitoringRequest), // A request to monitor network performance
@@ -24,6 +26,8 @@ impl PeerMonitoringServiceRequest {
             Self::GetNodeInformation => "get_node_information",
             Self::GetServerProtocolVersion => "get_server_protocol_version",
             Self::LatencyPing(_) => "latency_ping",
+            Self::GetPeerReputations => "get_peer_reputations",
+            Self::BandwidthProbe(_) => "bandwidth_probe",
 
             #[cfg(feature = "network-perf-test")] // Disabled by default
             Self::PerformanceMonitoringRequest(_) => "performance_monitoring_request",
@@ -37,6 +41,13 @@ pub struct LatencyPingRequest {
     pub ping_counter: u64, // A monotonically increasing counter to verify latency ping responses
 }
 
+/// The bandwidth probe request
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct BandwidthProbeRequest {
+    pub probe_counter: u64, // A monotonically increasing counter to verify bandwidth probe responses
+    pub num_response_bytes: u64, // The number of data bytes the server should respond with
+}
+
 cfg_block! {
     #[cfg(feature = "network-perf-test")] { // Disabled by default
         /// The performance monitoring request
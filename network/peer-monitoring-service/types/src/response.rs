@@ -16,6 +16,8 @@ pub enum PeerMonitoringServiceResponse {
     NetworkInformation(NetworkInformationResponse), // Holds the response for network information
     NodeInformation(NodeInformationResponse), // Holds the response for node information
     ServerProtocolVersion(ServerProtocolVersionResponse), // Returns the current server protocol version
+    PeerReputations(PeerReputationsResponse), // Holds the reputation scores of known peers
+    BandwidthProbe(BandwidthProbeResponse),   // Holds the data requested by a bandwidth probe
 
     #[cfg(feature = "network-perf-test")] // Disabled by default
     PerformanceMonitoring(PerformanceMonitoringResponse), // A response for performance monitoring requests
@@ -29,6 +31,8 @@ impl PeerMonitoringServiceResponse {
             Self::NetworkInformation(_) => "network_information",
             Self::NodeInformation(_) => "node_information",
             Self::ServerProtocolVersion(_) => "server_protocol_version",
+            Self::PeerReputations(_) => "peer_reputations",
+            Self::BandwidthProbe(_) => "bandwidth_probe",
 
             #[cfg(feature = "network-perf-test")] // Disabled by default
             Self::PerformanceMonitoring(_) => "performance_monitoring_response",
@@ -54,6 +58,13 @@ pub struct LatencyPingResponse {
     pub ping_counter: u64, // A monotonically increasing counter to verify latency ping responses
 }
 
+/// A response for the bandwidth probe request
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BandwidthProbeResponse {
+    pub probe_counter: u64, // A monotonically increasing counter to verify bandwidth probe responses
+    pub data: Vec<u8>,      // The probe data (the size is specified by the request)
+}
+
 /// A response for the network information request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct NetworkInformationResponse {
@@ -91,6 +102,19 @@ impl ConnectionMetadata {
     }
 }
 
+/// A response for the peer reputations request
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PeerReputationsResponse {
+    pub peer_reputations: BTreeMap<PeerNetworkId, PeerReputationScore>, // The reputations of known peers
+}
+
+/// The reputation score of a single peer (as derived from application misbehavior reports)
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PeerReputationScore {
+    pub score: u64,      // The current reputation score (higher is better)
+    pub is_banned: bool, // Whether the peer is currently banned
+}
+
 /// A response for the server protocol version request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ServerProtocolVersionResponse {
@@ -142,6 +166,20 @@ impl TryFrom<PeerMonitoringServiceResponse> for LatencyPingResponse {
     }
 }
 
+impl TryFrom<PeerMonitoringServiceResponse> for BandwidthProbeResponse {
+    type Error = UnexpectedResponseError;
+
+    fn try_from(response: PeerMonitoringServiceResponse) -> crate::Result<Self, Self::Error> {
+        match response {
+            PeerMonitoringServiceResponse::BandwidthProbe(inner) => Ok(inner),
+            _ => Err(UnexpectedResponseError(format!(
+                "expected bandwidth_probe_response, found {}",
+                response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<PeerMonitoringServiceResponse> for NetworkInformationResponse {
     type Error = UnexpectedResponseError;
 
@@ -170,6 +208,20 @@ impl TryFrom<PeerMonitoringServiceResponse> for NodeInformationResponse {
     }
 }
 
+impl TryFrom<PeerMonitoringServiceResponse> for PeerReputationsResponse {
+    type Error = UnexpectedResponseError;
+
+    fn try_from(response: PeerMonitoringServiceResponse) -> crate::Result<Self, Self::Error> {
+        match response {
+            PeerMonitoringServiceResponse::PeerReputations(inner) => Ok(inner),
+            _ => Err(UnexpectedResponseError(format!(
+                "expected peer_reputations_response, found {}",
+                response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<PeerMonitoringServiceResponse> for ServerProtocolVersionResponse {
     type Error = UnexpectedResponseError;
 
@@ -0,0 +1,190 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_peer_monitoring_service_types::response::PeerReputationScore;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// The maximum (and starting) reputation score of a peer
+pub const MAX_REPUTATION_SCORE: u64 = 100;
+/// The reputation score regained by a peer for every minute without misbehavior
+const SCORE_RECOVERY_PER_MINUTE: u64 = 1;
+/// Peers are banned once their score reaches this threshold
+const BAN_SCORE_THRESHOLD: u64 = 0;
+/// The duration for which a peer is banned
+const BAN_DURATION: Duration = Duration::from_secs(10 * 60); // 10 minutes
+
+/// Misbehavior reported by an application about a remote peer
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum PeerMisbehavior {
+    /// The peer sent a message that could not be deserialized or was
+    /// otherwise invalid (e.g., an unexpected message type).
+    InvalidMessage,
+    /// The peer sent data with an invalid proof (e.g., state sync responses
+    /// that fail proof verification).
+    InvalidProof,
+    /// The peer provided evidence of equivocation (e.g., conflicting consensus
+    /// votes or proposals for the same round).
+    Equivocation,
+}
+
+impl PeerMisbehavior {
+    /// Returns a summary label for the misbehavior
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Self::InvalidMessage => "invalid_message",
+            Self::InvalidProof => "invalid_proof",
+            Self::Equivocation => "equivocation",
+        }
+    }
+
+    /// Returns the score penalty for the misbehavior
+    fn get_penalty(&self) -> u64 {
+        match self {
+            Self::InvalidMessage => 10,
+            Self::InvalidProof => 25,
+            Self::Equivocation => MAX_REPUTATION_SCORE, // Equivocation results in an immediate ban
+        }
+    }
+}
+
+/// The reputation of a single peer, derived from the misbehavior reports of
+/// all applications.
+#[derive(Clone, Debug)]
+pub struct PeerReputation {
+    score: u64,                    // The score at the time of the last update
+    last_update: Instant,          // The time of the last score update
+    banned_until: Option<Instant>, // The time at which the current ban (if any) expires
+}
+
+impl PeerReputation {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            score: MAX_REPUTATION_SCORE,
+            last_update: now,
+            banned_until: None,
+        }
+    }
+
+    /// Applies the penalty for the given misbehavior to the peer's score and
+    /// bans the peer if the score reaches the ban threshold. Returns true iff
+    /// the peer was newly banned.
+    pub fn apply_misbehavior(&mut self, misbehavior: PeerMisbehavior, now: Instant) -> bool {
+        self.refresh(now);
+        self.score = self.score.saturating_sub(misbehavior.get_penalty());
+
+        if self.score <= BAN_SCORE_THRESHOLD && self.banned_until.is_none() {
+            self.banned_until = Some(now + BAN_DURATION);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns true iff the peer is currently banned
+    pub fn is_banned(&self, now: Instant) -> bool {
+        self.banned_until
+            .map_or(false, |banned_until| now < banned_until)
+    }
+
+    /// Returns the current score of the peer
+    pub fn get_score(&self, now: Instant) -> u64 {
+        let mut reputation = self.clone();
+        reputation.refresh(now);
+        reputation.score
+    }
+
+    /// Returns true iff the peer is not banned and its score has fully
+    /// recovered, i.e., the reputation no longer needs to be tracked.
+    pub fn is_fully_recovered(&self, now: Instant) -> bool {
+        !self.is_banned(now) && self.get_score(now) == MAX_REPUTATION_SCORE
+    }
+
+    /// Returns the reputation score summary exposed to other components
+    pub fn get_reputation_score(&self, now: Instant) -> PeerReputationScore {
+        PeerReputationScore {
+            score: self.get_score(now),
+            is_banned: self.is_banned(now),
+        }
+    }
+
+    /// Lifts any expired ban (resetting the score) and recovers the score
+    /// for the time elapsed since the last update.
+    fn refresh(&mut self, now: Instant) {
+        if let Some(banned_until) = self.banned_until {
+            if now < banned_until {
+                return; // Scores don't recover while the peer is banned
+            }
+
+            self.banned_until = None;
+            self.score = MAX_REPUTATION_SCORE;
+            self.last_update = now;
+            return;
+        }
+
+        let elapsed_minutes = now.saturating_duration_since(self.last_update).as_secs() / 60;
+        if elapsed_minutes > 0 {
+            self.score = self
+                .score
+                .saturating_add(elapsed_minutes.saturating_mul(SCORE_RECOVERY_PER_MINUTE))
+                .min(MAX_REPUTATION_SCORE);
+            self.last_update += Duration::from_secs(elapsed_minutes * 60);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reputation_ban_and_expiry() {
+        // Report invalid proofs until the peer is banned
+        let now = Instant::now();
+        let mut reputation = PeerReputation::new(now);
+        for _ in 0..3 {
+            assert!(!reputation.apply_misbehavior(PeerMisbehavior::InvalidProof, now));
+            assert!(!reputation.is_banned(now));
+        }
+        assert!(reputation.apply_misbehavior(PeerMisbehavior::InvalidProof, now));
+        assert!(reputation.is_banned(now));
+        assert_eq!(reputation.get_score(now), 0);
+
+        // Verify that further reports don't re-ban the peer or recover the score
+        let later = now + Duration::from_secs(5 * 60);
+        assert!(!reputation.apply_misbehavior(PeerMisbehavior::InvalidMessage, later));
+        assert!(reputation.is_banned(later));
+        assert_eq!(reputation.get_score(later), 0);
+
+        assert!(!reputation.is_fully_recovered(later));
+
+        // Verify that the ban expires and the score is reset
+        let after_ban = now + BAN_DURATION;
+        assert!(!reputation.is_banned(after_ban));
+        assert_eq!(reputation.get_score(after_ban), MAX_REPUTATION_SCORE);
+        assert!(reputation.is_fully_recovered(after_ban));
+    }
+
+    #[test]
+    fn test_reputation_recovery() {
+        // Report an invalid message and verify the score
+        let now = Instant::now();
+        let mut reputation = PeerReputation::new(now);
+        assert!(!reputation.apply_misbehavior(PeerMisbehavior::InvalidMessage, now));
+        assert_eq!(reputation.get_score(now), 90);
+
+        // Verify that the score recovers over time (up to the max)
+        assert_eq!(reputation.get_score(now + Duration::from_secs(59)), 90);
+        assert_eq!(reputation.get_score(now + Duration::from_secs(5 * 60)), 95);
+        assert!(!reputation.is_fully_recovered(now + Duration::from_secs(5 * 60)));
+        assert_eq!(
+            reputation.get_score(now + Duration::from_secs(60 * 60)),
+            MAX_REPUTATION_SCORE
+        );
+        assert!(reputation.is_fully_recovered(now + Duration::from_secs(60 * 60)));
+
+        // Verify that equivocation results in an immediate ban
+        assert!(reputation.apply_misbehavior(PeerMisbehavior::Equivocation, now));
+        assert!(reputation.is_banned(now));
+    }
+}
@@ -6,7 +6,9 @@ use crate::{
     application::{
         error::Error,
         metadata::{ConnectionState, PeerMetadata},
+        reputation::{PeerMisbehavior, PeerReputation},
     },
+    counters,
     transport::{ConnectionId, ConnectionMetadata},
     ProtocolId,
 };
@@ -15,11 +17,13 @@ use aptos_config::{
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_infallible::RwLock;
-use aptos_peer_monitoring_service_types::PeerMonitoringMetadata;
+use aptos_logger::prelude::*;
+use aptos_peer_monitoring_service_types::{response::PeerReputationScore, PeerMonitoringMetadata};
 use aptos_types::PeerId;
 use std::{
     collections::{hash_map::Entry, HashMap},
     sync::Arc,
+    time::Instant,
 };
 
 /// A simple container that tracks all peers and peer metadata for the node.
@@ -30,6 +34,7 @@ use std::{
 pub struct PeersAndMetadata {
     peers_and_metadata: HashMap<NetworkId, RwLock<HashMap<PeerId, PeerMetadata>>>,
     trusted_peers: HashMap<NetworkId, Arc<RwLock<PeerSet>>>,
+    peer_reputations: RwLock<HashMap<PeerNetworkId, PeerReputation>>,
 }
 
 impl PeersAndMetadata {
@@ -38,6 +43,7 @@ impl PeersAndMetadata {
         let mut peers_and_metadata = PeersAndMetadata {
             peers_and_metadata: HashMap::new(),
             trusted_peers: HashMap::new(),
+            peer_reputations: RwLock::new(HashMap::new()),
         };
 
         // Initialize each network mapping and trusted peer set
@@ -239,6 +245,68 @@ impl PeersAndMetadata {
         }
     }
 
+    /// Reports misbehavior of the given peer (e.g., an invalid message or
+    /// proof). This lowers the reputation of the peer, and bans the peer
+    /// temporarily if the reputation drops too low. Returns true iff the
+    /// peer was newly banned.
+    ///
+    /// Note: banned peers are disconnected (and won't be dialed) by the
+    /// connectivity manager, and inbound connections from them are rejected.
+    pub fn report_peer_misbehavior(
+        &self,
+        peer_network_id: PeerNetworkId,
+        misbehavior: PeerMisbehavior,
+    ) -> bool {
+        let network_id = peer_network_id.network_id();
+        counters::PEER_MISBEHAVIOR_REPORTS
+            .with_label_values(&[network_id.as_str(), misbehavior.get_label()])
+            .inc();
+
+        // Update the reputation of the peer (and stop tracking the peers
+        // that have fully recovered, as they are no different from new peers)
+        let now = Instant::now();
+        let newly_banned = {
+            let mut peer_reputations = self.peer_reputations.write();
+            peer_reputations.retain(|_, reputation| !reputation.is_fully_recovered(now));
+            peer_reputations
+                .entry(peer_network_id)
+                .or_insert_with(|| PeerReputation::new(now))
+                .apply_misbehavior(misbehavior, now)
+        };
+
+        if newly_banned {
+            counters::PEER_BANS
+                .with_label_values(&[network_id.as_str()])
+                .inc();
+            warn!(
+                "Peer {:?} has been banned! Latest misbehavior: {:?}",
+                peer_network_id, misbehavior
+            );
+        }
+        newly_banned
+    }
+
+    /// Returns true iff the given peer is currently banned
+    pub fn is_peer_banned(&self, peer_network_id: &PeerNetworkId) -> bool {
+        self.peer_reputations
+            .read()
+            .get(peer_network_id)
+            .map_or(false, |reputation| reputation.is_banned(Instant::now()))
+    }
+
+    /// Returns the reputation scores of all peers that have been reported
+    /// for misbehavior (all other peers have the maximum score).
+    pub fn get_peer_reputation_scores(&self) -> HashMap<PeerNetworkId, PeerReputationScore> {
+        let now = Instant::now();
+        self.peer_reputations
+            .read()
+            .iter()
+            .map(|(peer_network_id, reputation)| {
+                (*peer_network_id, reputation.get_reputation_score(now))
+            })
+            .collect()
+    }
+
     /// A helper method that returns the peers and metadata for the specified network
     fn get_peer_metadata_for_network(
         &self,
@@ -36,7 +36,7 @@ use crate::{
 };
 use aptos_config::{
     config::{Peer, PeerRole, PeerSet},
-    network_id::NetworkContext,
+    network_id::{NetworkContext, PeerNetworkId},
 };
 use aptos_crypto::x25519;
 use aptos_infallible::RwLock;
@@ -484,6 +484,46 @@ where
         }
     }
 
+    /// Returns true iff the given peer is currently banned
+    fn is_peer_banned(&self, peer_id: &PeerId) -> bool {
+        let peer_network_id = PeerNetworkId::new(self.network_context.network_id(), *peer_id);
+        self.peers_and_metadata.is_peer_banned(&peer_network_id)
+    }
+
+    /// Disconnect from all peers that are currently banned (e.g., due to
+    /// misbehavior reported by the applications).
+    async fn close_banned_connections(&mut self) {
+        let banned_peers: Vec<PeerId> = self
+            .connected
+            .keys()
+            .filter(|peer_id| self.is_peer_banned(peer_id))
+            .cloned()
+            .collect();
+
+        // Close existing connections to banned peers
+        for banned_peer in banned_peers {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&banned_peer),
+                "{} Closing connection to banned peer {}",
+                self.network_context,
+                banned_peer.short_str()
+            );
+
+            if let Err(disconnect_error) =
+                self.connection_reqs_tx.disconnect_peer(banned_peer).await
+            {
+                info!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&banned_peer),
+                    error = %disconnect_error,
+                    "{} Failed to close connection to banned peer {}, error: {}",
+                    self.network_context,
+                    banned_peer.short_str(),
+                    disconnect_error
+                );
+            }
+        }
+    }
+
     /// Cancel all pending dials to peers that are no longer eligible.
     ///
     /// For instance, a validator might leave the validator set after a
@@ -536,6 +576,7 @@ where
                     && !self.connected.contains_key(peer_id) // The node is not already connected.
                     && !self.dial_queue.contains_key(peer_id) // There is no pending dial to this node.
                     && roles_to_dial.contains(&peer.role) // We can dial this role
+                    && !self.is_peer_banned(peer_id) // The node is not banned
             })
             .collect();
 
@@ -669,6 +710,8 @@ where
         self.cancel_stale_dials().await;
         // Disconnect from connected peers that are no longer eligible.
         self.close_stale_connections().await;
+        // Disconnect from connected peers that are currently banned.
+        self.close_banned_connections().await;
         // Dial peers which are eligible but are neither connected nor queued for dialing in the
         // future.
         self.dial_eligible_peers(pending_dials);
@@ -24,6 +24,7 @@ pub const RECEIVED_LABEL: &str = "received";
 pub const SENT_LABEL: &str = "sent";
 pub const SUCCEEDED_LABEL: &str = "succeeded";
 pub const FAILED_LABEL: &str = "failed";
+pub const THROTTLED_LABEL: &str = "throttled";
 
 // Serialization labels
 pub const SERIALIZATION_LABEL: &str = "serialization";
@@ -562,6 +563,75 @@ pub fn network_application_outbound_traffic(
         .observe(size as f64);
 }
 
+/// Counters for the misbehavior reported by applications about remote peers
+pub static PEER_MISBEHAVIOR_REPORTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_misbehavior_reports",
+        "Counters for the misbehavior reported by applications about remote peers",
+        &["network_id", "misbehavior"]
+    )
+    .unwrap()
+});
+
+/// Counter for the number of peers banned due to a low reputation score
+pub static PEER_BANS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_bans",
+        "Counter for the number of peers banned due to a low reputation score",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
+/// Outbound bytes sent to (or throttled for) each remote peer, by protocol
+pub static NETWORK_PEER_PROTOCOL_OUTBOUND_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_protocol_outbound_bytes",
+        "Outbound bytes sent to (or throttled for) each remote peer, by protocol",
+        &[
+            "role_type",
+            "network_id",
+            "remote_peer_id",
+            "protocol_id",
+            "state"
+        ]
+    )
+    .unwrap()
+});
+
+pub fn peer_protocol_outbound_bytes(
+    network_context: &NetworkContext,
+    remote_peer_id: &PeerId,
+    protocol_id: ProtocolId,
+    state: &str,
+) -> IntCounter {
+    NETWORK_PEER_PROTOCOL_OUTBOUND_BYTES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        remote_peer_id.short_str().as_str(),
+        protocol_id.as_str(),
+        state,
+    ])
+}
+
+/// Removes the per-protocol outbound byte counters of the given remote peer
+/// (e.g., once the peer has disconnected), to keep the metric cardinality bounded.
+pub fn remove_peer_protocol_outbound_bytes(
+    network_context: &NetworkContext,
+    remote_peer_id: &PeerId,
+    protocol_id: ProtocolId,
+) {
+    for state in [SENT_LABEL, THROTTLED_LABEL] {
+        let _ = NETWORK_PEER_PROTOCOL_OUTBOUND_BYTES.remove_label_values(&[
+            network_context.role().as_str(),
+            network_context.network_id().as_str(),
+            remote_peer_id.short_str().as_str(),
+            protocol_id.as_str(),
+            state,
+        ]);
+    }
+}
+
 /// Time it takes to perform message serialization and deserialization
 pub static NETWORK_APPLICATION_SERIALIZATION_METRIC: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
@@ -0,0 +1,272 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-protocol outbound bandwidth accounting and limiting for a single
+//! [`Peer`](crate::peer::Peer) connection.
+//!
+//! Every outbound direct send message and rpc response is accounted against
+//! the protocol it belongs to. If a bandwidth limit is configured for the
+//! protocol, the message is only sent if the protocol's token bucket for the
+//! remote peer holds enough tokens (bytes). Otherwise, the message is dropped.
+
+use crate::{
+    counters::{self, SENT_LABEL, THROTTLED_LABEL},
+    logging::NetworkSchema,
+    ProtocolId,
+};
+use anyhow::{anyhow, ensure};
+use aptos_config::{config::ProtocolBandwidthLimitConfig, network_id::NetworkContext};
+use aptos_infallible::RwLock;
+use aptos_logger::prelude::*;
+use aptos_rate_limiter::rate_limit::Bucket;
+use aptos_types::PeerId;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+const BANDWIDTH_LIMITER_LABEL: &str = "protocol_bandwidth";
+
+/// The outbound bandwidth limits (keyed by protocol) shared by all connections
+/// of a network. The limits can be adjusted at runtime (e.g., by an admin
+/// command), in which case the connections reset their token buckets.
+#[derive(Clone, Debug, Default)]
+pub struct ProtocolBandwidthLimits {
+    limits: Arc<RwLock<HashMap<ProtocolId, ProtocolBandwidthLimitConfig>>>,
+    /// Incremented on every update, so that the connections notice the update
+    version: Arc<AtomicU64>,
+}
+
+impl ProtocolBandwidthLimits {
+    /// Parses the given limit configs. Limits for unknown protocols are ignored.
+    pub fn new(network_context: &NetworkContext, configs: &[ProtocolBandwidthLimitConfig]) -> Self {
+        let limits = Self::default();
+        for config in configs {
+            if let Err(error) = limits.set_limit(config.clone()) {
+                warn!(
+                    NetworkSchema::new(network_context),
+                    "{} Ignoring bandwidth limit for protocol: {}. Error: {}",
+                    network_context,
+                    config.protocol,
+                    error
+                );
+            }
+        }
+        limits
+    }
+
+    /// Sets (or replaces) the bandwidth limit of the protocol in the given config
+    pub fn set_limit(&self, config: ProtocolBandwidthLimitConfig) -> anyhow::Result<()> {
+        let protocol_id = parse_protocol_id(&config.protocol)?;
+        ensure!(
+            config.byte_bucket_rate > 0 && config.byte_bucket_size >= config.byte_bucket_rate,
+            "The bandwidth limit must have a non-zero rate and a bucket size >= rate! Rate: {}, size: {}",
+            config.byte_bucket_rate,
+            config.byte_bucket_size
+        );
+
+        self.limits.write().insert(protocol_id, config);
+        self.version.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Removes the bandwidth limit of the given protocol (if any).
+    /// Returns true iff a limit was removed.
+    pub fn remove_limit(&self, protocol: &str) -> anyhow::Result<bool> {
+        let protocol_id = parse_protocol_id(protocol)?;
+        let removed = self.limits.write().remove(&protocol_id).is_some();
+        self.version.fetch_add(1, Ordering::Relaxed);
+        Ok(removed)
+    }
+
+    /// Returns the current bandwidth limits, ordered by protocol name
+    pub fn get_limits(&self) -> Vec<ProtocolBandwidthLimitConfig> {
+        let mut limits: Vec<_> = self.limits.read().values().cloned().collect();
+        limits.sort_by(|limit, other_limit| limit.protocol.cmp(&other_limit.protocol));
+        limits
+    }
+
+    fn version(&self) -> u64 {
+        self.version.load(Ordering::Relaxed)
+    }
+
+    /// Creates a token bucket for each limited protocol
+    fn create_buckets(&self, remote_peer_id: PeerId) -> HashMap<ProtocolId, Bucket> {
+        self.limits
+            .read()
+            .iter()
+            .map(|(protocol_id, config)| {
+                let bucket = Bucket::new(
+                    BANDWIDTH_LIMITER_LABEL.into(),
+                    protocol_id.as_str().into(),
+                    remote_peer_id.to_string(),
+                    config.byte_bucket_size,
+                    config.byte_bucket_size,
+                    config.byte_bucket_rate,
+                    None,
+                );
+                (*protocol_id, bucket)
+            })
+            .collect()
+    }
+}
+
+/// Returns the protocol with the given name
+fn parse_protocol_id(protocol: &str) -> anyhow::Result<ProtocolId> {
+    ProtocolId::all()
+        .iter()
+        .find(|protocol_id| protocol_id.as_str() == protocol)
+        .copied()
+        .ok_or_else(|| anyhow!("Unknown protocol: {}", protocol))
+}
+
+/// Accounts and limits the outbound bytes sent to a single remote peer
+pub struct ProtocolBandwidthLimiter {
+    network_context: NetworkContext,
+    remote_peer_id: PeerId,
+    /// The (shared) limits of the network
+    limits: ProtocolBandwidthLimits,
+    /// The version of the limits the buckets were created for
+    limits_version: u64,
+    /// The token buckets of the limited protocols
+    buckets: HashMap<ProtocolId, Bucket>,
+    /// The protocols that have been accounted for (used to clean up the metrics)
+    accounted_protocols: HashSet<ProtocolId>,
+}
+
+impl ProtocolBandwidthLimiter {
+    pub fn new(
+        network_context: NetworkContext,
+        remote_peer_id: PeerId,
+        limits: &ProtocolBandwidthLimits,
+    ) -> Self {
+        let limits_version = limits.version();
+        let buckets = limits.create_buckets(remote_peer_id);
+        Self {
+            network_context,
+            remote_peer_id,
+            limits: limits.clone(),
+            limits_version,
+            buckets,
+            accounted_protocols: HashSet::new(),
+        }
+    }
+
+    /// Accounts for an outbound message of `num_bytes` for the given protocol.
+    /// Returns false iff the message exceeds the protocol's bandwidth limit
+    /// and must be dropped.
+    pub fn try_send(&mut self, protocol_id: ProtocolId, num_bytes: usize) -> bool {
+        // Recreate the buckets if the limits were updated
+        let limits_version = self.limits.version();
+        if limits_version != self.limits_version {
+            self.limits_version = limits_version;
+            self.buckets = self.limits.create_buckets(self.remote_peer_id);
+        }
+
+        let allowed = match self.buckets.get_mut(&protocol_id) {
+            Some(bucket) => bucket.acquire_all_tokens(num_bytes).is_ok(),
+            None => true,
+        };
+
+        let state = if allowed { SENT_LABEL } else { THROTTLED_LABEL };
+        counters::peer_protocol_outbound_bytes(
+            &self.network_context,
+            &self.remote_peer_id,
+            protocol_id,
+            state,
+        )
+        .inc_by(num_bytes as u64);
+        self.accounted_protocols.insert(protocol_id);
+
+        allowed
+    }
+}
+
+impl Drop for ProtocolBandwidthLimiter {
+    fn drop(&mut self) {
+        for protocol_id in self.accounted_protocols.drain() {
+            counters::remove_peer_protocol_outbound_bytes(
+                &self.network_context,
+                &self.remote_peer_id,
+                protocol_id,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_bandwidth_limits() {
+        // Create limits for a known and an unknown protocol
+        let network_context = NetworkContext::mock();
+        let limits = ProtocolBandwidthLimits::new(&network_context, &[
+            ProtocolBandwidthLimitConfig {
+                protocol: ProtocolId::StorageServiceRpc.as_str().into(),
+                byte_bucket_rate: 100,
+                byte_bucket_size: 100,
+            },
+            ProtocolBandwidthLimitConfig {
+                protocol: "UnknownProtocol".into(),
+                byte_bucket_rate: 100,
+                byte_bucket_size: 100,
+            },
+        ]);
+        assert_eq!(limits.get_limits().len(), 1);
+
+        // Verify that the limited protocol is throttled once the bucket is empty
+        let mut limiter = ProtocolBandwidthLimiter::new(network_context, PeerId::random(), &limits);
+        assert!(limiter.try_send(ProtocolId::StorageServiceRpc, 60));
+        assert!(!limiter.try_send(ProtocolId::StorageServiceRpc, 60));
+        assert!(limiter.try_send(ProtocolId::StorageServiceRpc, 40));
+        assert!(!limiter.try_send(ProtocolId::StorageServiceRpc, 1));
+
+        // Verify that messages larger than the bucket are always throttled
+        assert!(!limiter.try_send(ProtocolId::StorageServiceRpc, 101));
+
+        // Verify that unlimited protocols are never throttled
+        assert!(limiter.try_send(ProtocolId::MempoolDirectSend, 1_000_000));
+    }
+
+    #[test]
+    fn test_update_protocol_bandwidth_limits() {
+        // Create a limiter without any limits
+        let network_context = NetworkContext::mock();
+        let limits = ProtocolBandwidthLimits::default();
+        let mut limiter = ProtocolBandwidthLimiter::new(network_context, PeerId::random(), &limits);
+        assert!(limiter.try_send(ProtocolId::StorageServiceRpc, 1_000));
+
+        // Verify that invalid limits are rejected
+        let mut config = ProtocolBandwidthLimitConfig {
+            protocol: "UnknownProtocol".into(),
+            byte_bucket_rate: 100,
+            byte_bucket_size: 100,
+        };
+        assert!(limits.set_limit(config.clone()).is_err());
+        config.protocol = ProtocolId::StorageServiceRpc.as_str().into();
+        config.byte_bucket_size = 50;
+        assert!(limits.set_limit(config.clone()).is_err());
+        assert!(limits.get_limits().is_empty());
+
+        // Add a limit and verify the existing limiter enforces it
+        config.byte_bucket_size = 100;
+        limits.set_limit(config.clone()).unwrap();
+        assert_eq!(limits.get_limits(), vec![config]);
+        assert!(limiter.try_send(ProtocolId::StorageServiceRpc, 100));
+        assert!(!limiter.try_send(ProtocolId::StorageServiceRpc, 100));
+
+        // Remove the limit and verify the limiter no longer enforces it
+        assert!(limits
+            .remove_limit(ProtocolId::StorageServiceRpc.as_str())
+            .unwrap());
+        assert!(!limits
+            .remove_limit(ProtocolId::StorageServiceRpc.as_str())
+            .unwrap());
+        assert!(limiter.try_send(ProtocolId::StorageServiceRpc, 1_000));
+    }
+}
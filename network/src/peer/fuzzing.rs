@@ -3,8 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    application::storage::PeersAndMetadata,
     constants,
-    peer::Peer,
+    peer::{bandwidth::ProtocolBandwidthLimits, Peer},
     protocols::wire::{
         handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
         messaging::v1::{MultiplexMessage, MultiplexMessageSink},
@@ -108,6 +109,8 @@ pub fn fuzz(data: &[u8]) {
         constants::MAX_CONCURRENT_OUTBOUND_RPCS,
         constants::MAX_FRAME_SIZE,
         constants::MAX_MESSAGE_SIZE,
+        &ProtocolBandwidthLimits::default(),
+        PeersAndMetadata::new(&[network_context.network_id()]),
     );
     executor.spawn(peer.start());
 
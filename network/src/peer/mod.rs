@@ -16,15 +16,17 @@
 //! [`PeerManager`]: crate::peer_manager::PeerManager
 
 use crate::{
+    application::{reputation::PeerMisbehavior, storage::PeersAndMetadata},
     counters::{
         self, network_application_inbound_traffic, network_application_outbound_traffic,
-        RECEIVED_LABEL, SENT_LABEL,
+        RECEIVED_LABEL, SENT_LABEL, THROTTLED_LABEL,
     },
     logging::NetworkSchema,
+    peer::bandwidth::{ProtocolBandwidthLimiter, ProtocolBandwidthLimits},
     peer_manager::{PeerManagerError, TransportNotification},
     protocols::{
         direct_send::Message,
-        rpc::{InboundRpcRequest, InboundRpcs, OutboundRpcRequest, OutboundRpcs},
+        rpc::{error::RpcError, InboundRpcRequest, InboundRpcs, OutboundRpcRequest, OutboundRpcs},
         stream::{InboundStreamBuffer, OutboundStream, StreamMessage},
         wire::messaging::v1::{
             DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
@@ -35,8 +37,8 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::network_id::NetworkContext;
-use aptos_logger::prelude::*;
+use aptos_config::network_id::{NetworkContext, PeerNetworkId};
+use aptos_logger::{prelude::*, sample, sample::SampleRate};
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::PeerId;
@@ -50,12 +52,13 @@ use futures::{
 };
 use futures_util::stream::select;
 use serde::Serialize;
-use std::{fmt, panic, time::Duration};
+use std::{fmt, panic, sync::Arc, time::Duration};
 use tokio::runtime::Handle;
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
 };
 
+pub mod bandwidth;
 #[cfg(test)]
 mod test;
 
@@ -138,6 +141,10 @@ pub struct Peer<TSocket> {
     max_message_size: usize,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
+    /// Per-protocol accounting and limiting of the outbound bandwidth to the remote peer
+    bandwidth_limiter: ProtocolBandwidthLimiter,
+    /// Peers and metadata, used to report misbehavior of the remote peer.
+    peers_and_metadata: Arc<PeersAndMetadata>,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -158,6 +165,8 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        protocol_bandwidth_limits: &ProtocolBandwidthLimits,
+        peers_and_metadata: Arc<PeersAndMetadata>,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
@@ -191,6 +200,12 @@ where
             max_frame_size,
             max_message_size,
             inbound_stream: InboundStreamBuffer::new(max_fragments),
+            bandwidth_limiter: ProtocolBandwidthLimiter::new(
+                network_context,
+                remote_peer_id,
+                protocol_bandwidth_limits,
+            ),
+            peers_and_metadata,
         }
     }
 
@@ -268,8 +283,17 @@ where
                     }
                 },
                 // Drive the queue of pending inbound rpcs. When one is fulfilled
-                // by an upstream protocol, send the response to the remote peer.
-                maybe_response = self.inbound_rpcs.next_completed_response() => {
+                // by an upstream protocol, send the response to the remote peer
+                // (unless it exceeds the protocol's bandwidth limit).
+                (protocol_id, maybe_response) = self.inbound_rpcs.next_completed_response() => {
+                    let bandwidth_limiter = &mut self.bandwidth_limiter;
+                    let maybe_response = maybe_response.and_then(|response| {
+                        if bandwidth_limiter.try_send(protocol_id, response.raw_response.len()) {
+                            Ok(response)
+                        } else {
+                            Err(RpcError::BandwidthLimitExceeded(protocol_id))
+                        }
+                    });
                     if let Err(err) = self.inbound_rpcs.send_outbound_response(&mut write_reqs_tx, maybe_response).await {
                         warn!(
                             NetworkSchema::new(&self.network_context).connection_metadata(&self.connection_metadata),
@@ -482,7 +506,15 @@ where
                 ReadError::DeserializeError(_, _, ref frame_prefix) => {
                     // DeserializeError's are recoverable so we'll let the other
                     // peer know about the error and log the issue, but we won't
-                    // close the connection.
+                    // close the connection. The misbehavior is reported, so
+                    // repeated invalid messages will result in a ban.
+                    self.peers_and_metadata.report_peer_misbehavior(
+                        PeerNetworkId::new(
+                            self.network_context.network_id(),
+                            self.remote_peer_id(),
+                        ),
+                        PeerMisbehavior::InvalidMessage,
+                    );
                     let message_type = frame_prefix.as_ref().first().unwrap_or(&0);
                     let protocol_id = frame_prefix.as_ref().get(1).unwrap_or(&0);
                     let error_code = ErrorCode::parsing_error(*message_type, *protocol_id);
@@ -560,11 +592,28 @@ where
             PeerRequest::SendDirectSend(message) => {
                 let message_len = message.mdata.len();
                 let protocol_id = message.protocol_id;
+
+                // Drop the message if it exceeds the protocol's bandwidth limit
+                if !self.bandwidth_limiter.try_send(protocol_id, message_len) {
+                    counters::direct_send_messages(&self.network_context, THROTTLED_LABEL).inc();
+                    sample!(
+                        SampleRate::Duration(Duration::from_secs(10)),
+                        warn!(
+                            NetworkSchema::new(&self.network_context)
+                                .connection_metadata(&self.connection_metadata),
+                            "Dropped direct send message for protocol {} to peer: {}. Bandwidth limit exceeded!",
+                            protocol_id,
+                            self.remote_peer_id().short_str(),
+                        )
+                    );
+                    return;
+                }
                 network_application_outbound_traffic(
                     self.network_context,
                     protocol_id,
                     message_len as u64,
                 );
+
                 let message = NetworkMessage::DirectSendMsg(DirectSendMsg {
                     protocol_id,
                     priority: Priority::default(),
@@ -3,11 +3,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    application::{reputation::MAX_REPUTATION_SCORE, storage::PeersAndMetadata},
     constants::{
         INBOUND_RPC_TIMEOUT_MS, MAX_CONCURRENT_INBOUND_RPCS, MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE, MAX_MESSAGE_SIZE, NETWORK_CHANNEL_SIZE,
     },
-    peer::{DisconnectReason, Peer, PeerNotification, PeerRequest},
+    peer::{
+        bandwidth::ProtocolBandwidthLimits, DisconnectReason, Peer, PeerNotification, PeerRequest,
+    },
     peer_manager::TransportNotification,
     protocols::{
         direct_send::Message,
@@ -15,8 +18,8 @@ use crate::{
         wire::{
             handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
             messaging::v1::{
-                DirectSendMsg, MultiplexMessage, MultiplexMessageSink, MultiplexMessageStream,
-                NetworkMessage, RpcRequest, RpcResponse,
+                DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
+                MultiplexMessageStream, NetworkMessage, RpcRequest, RpcResponse,
             },
         },
     },
@@ -24,7 +27,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::PeerRole, network_id::NetworkContext};
+use aptos_config::{
+    config::{PeerRole, ProtocolBandwidthLimitConfig},
+    network_id::{NetworkContext, NetworkId, PeerNetworkId},
+};
 use aptos_memsocket::MemorySocket;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_time_service::{MockTimeService, TimeService};
@@ -37,7 +43,7 @@ use futures::{
     stream::{StreamExt, TryStreamExt},
     SinkExt,
 };
-use std::{collections::HashSet, str::FromStr, time::Duration};
+use std::{collections::HashSet, str::FromStr, sync::Arc, time::Duration};
 use tokio::runtime::{Handle, Runtime};
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
@@ -55,6 +61,28 @@ fn build_test_peer(
     MemorySocket,
     aptos_channels::Receiver<TransportNotification<MemorySocket>>,
     aptos_channel::Receiver<ProtocolId, PeerNotification>,
+) {
+    build_test_peer_with_bandwidth_limits(
+        executor,
+        time_service,
+        origin,
+        &ProtocolBandwidthLimits::default(),
+        PeersAndMetadata::new(&[NetworkId::Validator]),
+    )
+}
+
+fn build_test_peer_with_bandwidth_limits(
+    executor: Handle,
+    time_service: TimeService,
+    origin: ConnectionOrigin,
+    protocol_bandwidth_limits: &ProtocolBandwidthLimits,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+) -> (
+    Peer<MemorySocket>,
+    PeerHandle,
+    MemorySocket,
+    aptos_channels::Receiver<TransportNotification<MemorySocket>>,
+    aptos_channel::Receiver<ProtocolId, PeerNotification>,
 ) {
     let (a, b) = MemorySocket::new_pair();
     let peer_id = PeerId::random();
@@ -90,6 +118,8 @@ fn build_test_peer(
         MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE,
         MAX_MESSAGE_SIZE,
+        protocol_bandwidth_limits,
+        peers_and_metadata,
     );
     let peer_handle = PeerHandle(peer_reqs_tx);
 
@@ -237,6 +267,77 @@ fn peer_send_message() {
     rt.block_on(future::join3(peer.start(), server, client));
 }
 
+// Outbound DirectSends exceeding the protocol's bandwidth limit should be dropped,
+// and updating the limit at runtime should take effect on the existing connection.
+#[test]
+fn peer_send_message_bandwidth_limit() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+
+    // Limit the protocol to two messages (the bucket is practically never refilled)
+    let limit = ProtocolBandwidthLimitConfig {
+        protocol: PROTOCOL.as_str().into(),
+        byte_bucket_rate: 1,
+        byte_bucket_size: 22,
+    };
+    let protocol_bandwidth_limits = ProtocolBandwidthLimits::default();
+    protocol_bandwidth_limits.set_limit(limit.clone()).unwrap();
+    let (peer, mut peer_handle, mut connection, _connection_notifs_rx, _peer_notifs_rx) =
+        build_test_peer_with_bandwidth_limits(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            &protocol_bandwidth_limits,
+            PeersAndMetadata::new(&[NetworkId::Validator]),
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let create_message = |data: &'static str| Message {
+        protocol_id: PROTOCOL,
+        mdata: Bytes::from(data),
+    };
+    let create_wire_message = |data: &'static str| {
+        MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id: PROTOCOL,
+            priority: 0,
+            raw_msg: Vec::from(data),
+        }))
+    };
+    let (received_tx, received_rx) = oneshot::channel();
+
+    let client = async {
+        // Client should receive the first two messages and the empty one (but not the
+        // third message, which exceeds the limit).
+        for data in ["hello world", "hello world", ""] {
+            let msg = client_stream.next().await.unwrap().unwrap();
+            assert_eq!(msg, create_wire_message(data));
+        }
+        received_tx.send(()).unwrap();
+
+        // Client should receive the message sent after the limit is updated
+        let msg = client_stream.next().await.unwrap().unwrap();
+        assert_eq!(msg, create_wire_message("goodbye"));
+
+        // Client then closes the connection.
+        client_sink.close().await.unwrap();
+    };
+
+    let server = async {
+        // Server sends three messages, followed by an empty message (which doesn't
+        // consume any bandwidth).
+        for data in ["hello world", "hello world", "hello world", ""] {
+            peer_handle.send_direct_send(create_message(data));
+        }
+
+        // Once the messages have been handled, the server updates the limit
+        // (which refills the bucket) and sends another message.
+        received_rx.await.unwrap();
+        protocol_bandwidth_limits.set_limit(limit).unwrap();
+        peer_handle.send_direct_send(create_message("goodbye"));
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
 // Reading an inbound DirectSendMsg off the wire should notify the PeerManager of
 // an inbound DirectSend.
 #[test]
@@ -280,6 +381,49 @@ fn peer_recv_message() {
     rt.block_on(future::join3(peer.start(), server, client));
 }
 
+// Reading a frame that fails to deserialize off the wire should notify the remote
+// peer of the error and report the misbehavior of the remote peer.
+#[test]
+fn peer_recv_invalid_message() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let peers_and_metadata = PeersAndMetadata::new(&[NetworkId::Validator]);
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, _peer_notifs_rx) =
+        build_test_peer_with_bandwidth_limits(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            &ProtocolBandwidthLimits::default(),
+            peers_and_metadata.clone(),
+        );
+    let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer.remote_peer_id());
+
+    let client = async move {
+        let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+        // Send a garbage frame and wait for the error response
+        client_sink
+            .send_raw_frame(Bytes::from_static(&[255, 111]))
+            .await
+            .unwrap();
+        let error_message = client_stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            error_message,
+            MultiplexMessage::Message(NetworkMessage::Error(ErrorCode::parsing_error(255, 111)))
+        );
+
+        // Verify the misbehavior was reported
+        let peer_reputation_scores = peers_and_metadata.get_peer_reputation_scores();
+        let peer_reputation_score = peer_reputation_scores.get(&peer_network_id).unwrap();
+        assert!(peer_reputation_score.score < MAX_REPUTATION_SCORE);
+        assert!(!peer_reputation_score.is_banned);
+
+        // Client then closes connection.
+        client_sink.close().await.unwrap();
+    };
+    rt.block_on(future::join(peer.start(), client));
+}
+
 // Two connected Peer actors should be able to send/recv a DirectSend from each
 // other and then shutdown gracefully.
 #[test]
@@ -6,6 +6,7 @@ use crate::{
     application::storage::PeersAndMetadata,
     counters,
     noise::{stream::NoiseStream, HandshakeAuthMode},
+    peer::bandwidth::ProtocolBandwidthLimits,
     peer_manager::{
         conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerManager,
         PeerManagerNotification, PeerManagerRequest, PeerManagerRequestSender,
@@ -79,6 +80,7 @@ struct PeerManagerContext {
     max_message_size: usize,
     inbound_connection_limit: usize,
     tcp_buffer_cfg: TCPBufferCfg,
+    protocol_bandwidth_limits: ProtocolBandwidthLimits,
 }
 
 impl PeerManagerContext {
@@ -119,6 +121,7 @@ impl PeerManagerContext {
             max_message_size,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            protocol_bandwidth_limits: ProtocolBandwidthLimits::default(),
         }
     }
 
@@ -136,6 +139,14 @@ impl PeerManagerContext {
         self.connection_event_handlers.push(tx);
         rx
     }
+
+    fn set_protocol_bandwidth_limits(
+        &mut self,
+        protocol_bandwidth_limits: ProtocolBandwidthLimits,
+    ) -> &mut Self {
+        self.protocol_bandwidth_limits = protocol_bandwidth_limits;
+        self
+    }
 }
 
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
@@ -346,6 +357,7 @@ impl PeerManagerBuilder {
             pm_context.max_frame_size,
             pm_context.max_message_size,
             pm_context.inbound_connection_limit,
+            pm_context.protocol_bandwidth_limits,
         );
 
         // PeerManager constructor appends a public key to the listen_address.
@@ -386,6 +398,16 @@ impl PeerManagerBuilder {
             .add_connection_event_listener()
     }
 
+    /// Set the per-protocol outbound bandwidth limits applied to each peer
+    pub fn set_protocol_bandwidth_limits(
+        &mut self,
+        protocol_bandwidth_limits: ProtocolBandwidthLimits,
+    ) -> &mut Self {
+        self.peer_manager_context()
+            .set_protocol_bandwidth_limits(protocol_bandwidth_limits);
+        self
+    }
+
     pub fn get_tcp_buffers_cfg(&self) -> TCPBufferCfg {
         self.peer_manager_context
             .as_ref()
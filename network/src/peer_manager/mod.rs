@@ -15,7 +15,7 @@ use crate::{
     constants,
     counters::{self},
     logging::*,
-    peer::{Peer, PeerNotification, PeerRequest},
+    peer::{bandwidth::ProtocolBandwidthLimits, Peer, PeerNotification, PeerRequest},
     transport::{
         Connection, ConnectionId, ConnectionMetadata, TSocket as TransportTSocket,
         TRANSPORT_TIMEOUT,
@@ -119,6 +119,8 @@ where
     max_message_size: usize,
     /// Inbound connection limit separate of outbound connections
     inbound_connection_limit: usize,
+    /// Per-protocol outbound bandwidth limits applied to each peer
+    protocol_bandwidth_limits: ProtocolBandwidthLimits,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -147,6 +149,7 @@ where
         max_frame_size: usize,
         max_message_size: usize,
         inbound_connection_limit: usize,
+        protocol_bandwidth_limits: ProtocolBandwidthLimits,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = aptos_channels::new(
             channel_size,
@@ -189,6 +192,7 @@ where
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            protocol_bandwidth_limits,
         }
     }
 
@@ -354,6 +358,22 @@ where
             },
         };
 
+        // Reject connections from (and to) peers that are currently banned
+        let peer_network_id = PeerNetworkId::new(
+            self.network_context.network_id(),
+            conn.metadata.remote_peer_id,
+        );
+        if self.peers_and_metadata.is_peer_banned(&peer_network_id) {
+            info!(
+                NetworkSchema::new(&self.network_context)
+                    .connection_metadata_with_address(&conn.metadata),
+                "{} Connection rejected due to a peer ban: {}", self.network_context, conn.metadata
+            );
+            counters::connections_rejected(&self.network_context, conn.metadata.origin).inc();
+            self.disconnect(conn);
+            return;
+        }
+
         // Verify that we have not reached the max connection limit for unknown inbound peers
         if conn.metadata.origin == ConnectionOrigin::Inbound {
             // Everything below here is meant for unknown peers only. The role comes from
@@ -673,6 +693,8 @@ where
             constants::MAX_CONCURRENT_OUTBOUND_RPCS,
             self.max_frame_size,
             self.max_message_size,
+            &self.protocol_bandwidth_limits,
+            self.peers_and_metadata.clone(),
         );
         self.executor.spawn(peer.start());
 
@@ -5,7 +5,7 @@
 use crate::{
     application::storage::PeersAndMetadata,
     constants,
-    peer::DisconnectReason,
+    peer::{bandwidth::ProtocolBandwidthLimits, DisconnectReason},
     peer_manager::{
         conn_notifs_channel, error::PeerManagerError, ConnectionNotification, ConnectionRequest,
         PeerManager, PeerManagerNotification, PeerManagerRequest, TransportNotification,
@@ -118,6 +118,7 @@ fn build_test_peer_manager(
         constants::MAX_FRAME_SIZE,
         constants::MAX_MESSAGE_SIZE,
         MAX_INBOUND_CONNECTIONS,
+        ProtocolBandwidthLimits::default(),
     );
 
     (
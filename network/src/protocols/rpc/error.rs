@@ -4,7 +4,7 @@
 
 //! Rpc protocol errors
 
-use crate::peer_manager::PeerManagerError;
+use crate::{peer_manager::PeerManagerError, ProtocolId};
 use anyhow::anyhow;
 use aptos_types::PeerId;
 use futures::channel::{mpsc, oneshot};
@@ -42,6 +42,9 @@ pub enum RpcError {
 
     #[error("Rpc timed out")]
     TimedOut,
+
+    #[error("Bandwidth limit exceeded for protocol: {0}")]
+    BandwidthLimitExceeded(ProtocolId),
 }
 
 impl From<PeerManagerError> for RpcError {
@@ -39,6 +39,14 @@ mod test;
 pub const USER_INPUT_RECURSION_LIMIT: usize = 32;
 pub const RECURSION_LIMIT: usize = 64;
 
+/// Messages smaller than this are not worth compressing with zstd (e.g., votes),
+/// so they are sent as plain BCS (see `Encoding::ZstdBcs`).
+pub const ZSTD_COMPRESSION_THRESHOLD_BYTES: usize = 8 * 1024;
+
+/// The prefixes of the messages sent with `Encoding::ZstdBcs`
+const ZSTD_BCS_RAW_PREFIX: u8 = 0;
+const ZSTD_BCS_COMPRESSED_PREFIX: u8 = 1;
+
 /// Unique identifier associated with each application protocol.
 #[repr(u8)]
 #[derive(Clone, Copy, Hash, Eq, PartialEq, Deserialize, Serialize)]
@@ -57,12 +65,20 @@ pub enum ProtocolId {
     PeerMonitoringServiceRpc = 10,
     ConsensusRpcCompressed = 11,
     ConsensusDirectSendCompressed = 12,
+    ConsensusObserver = 13,
+    ConsensusObserverRpc = 14,
+    ConsensusRpcZstd = 15,
+    ConsensusDirectSendZstd = 16,
+    StorageServiceRpcZstd = 17,
 }
 
 /// The encoding types for Protocols
 enum Encoding {
     Bcs(usize),
     CompressedBcs(usize),
+    // Messages above the compression threshold are compressed with zstd. Each
+    // message is prefixed by a byte that specifies if it is compressed.
+    ZstdBcs(usize),
     Json,
 }
 
@@ -83,6 +99,11 @@ impl ProtocolId {
             PeerMonitoringServiceRpc => "PeerMonitoringServiceRpc",
             ConsensusRpcCompressed => "ConsensusRpcCompressed",
             ConsensusDirectSendCompressed => "ConsensusDirectSendCompressed",
+            ConsensusObserver => "ConsensusObserver",
+            ConsensusObserverRpc => "ConsensusObserverRpc",
+            ConsensusRpcZstd => "ConsensusRpcZstd",
+            ConsensusDirectSendZstd => "ConsensusDirectSendZstd",
+            StorageServiceRpcZstd => "StorageServiceRpcZstd",
         }
     }
 
@@ -102,6 +123,11 @@ impl ProtocolId {
             ProtocolId::PeerMonitoringServiceRpc,
             ProtocolId::ConsensusRpcCompressed,
             ProtocolId::ConsensusDirectSendCompressed,
+            ProtocolId::ConsensusObserver,
+            ProtocolId::ConsensusObserverRpc,
+            ProtocolId::ConsensusRpcZstd,
+            ProtocolId::ConsensusDirectSendZstd,
+            ProtocolId::StorageServiceRpcZstd,
         ]
     }
 
@@ -112,6 +138,9 @@ impl ProtocolId {
             ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
                 Encoding::CompressedBcs(RECURSION_LIMIT)
             },
+            ProtocolId::ConsensusDirectSendZstd
+            | ProtocolId::ConsensusRpcZstd
+            | ProtocolId::StorageServiceRpcZstd => Encoding::ZstdBcs(RECURSION_LIMIT),
             ProtocolId::MempoolDirectSend => Encoding::CompressedBcs(USER_INPUT_RECURSION_LIMIT),
             ProtocolId::MempoolRpc => Encoding::Bcs(USER_INPUT_RECURSION_LIMIT),
             _ => Encoding::Bcs(RECURSION_LIMIT),
@@ -121,10 +150,12 @@ impl ProtocolId {
     /// Returns the compression client label based on the current protocol id
     fn get_compression_client(self) -> CompressionClient {
         match self {
-            ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
-                CompressionClient::Consensus
-            },
+            ProtocolId::ConsensusDirectSendCompressed
+            | ProtocolId::ConsensusRpcCompressed
+            | ProtocolId::ConsensusDirectSendZstd
+            | ProtocolId::ConsensusRpcZstd => CompressionClient::Consensus,
             ProtocolId::MempoolDirectSend => CompressionClient::Mempool,
+            ProtocolId::StorageServiceRpcZstd => CompressionClient::StateSync,
             protocol_id => unreachable!(
                 "The given protocol ({:?}) should not be using compression!",
                 protocol_id
@@ -151,6 +182,7 @@ impl ProtocolId {
                 )
                 .map_err(|e| anyhow!("{:?}", e))
             },
+            Encoding::ZstdBcs(limit) => self.zstd_bcs_encode(value, limit),
             Encoding::Json => serde_json::to_vec(value).map_err(|e| anyhow!("{:?}", e)),
         };
 
@@ -181,6 +213,7 @@ impl ProtocolId {
                 .map_err(|e| anyhow! {"{:?}", e})?;
                 self.bcs_decode(&raw_bytes, limit)
             },
+            Encoding::ZstdBcs(limit) => self.zstd_bcs_decode(bytes, limit),
             Encoding::Json => serde_json::from_slice(bytes).map_err(|e| anyhow!("{:?}", e)),
         };
 
@@ -201,6 +234,52 @@ impl ProtocolId {
     fn bcs_decode<T: DeserializeOwned>(&self, bytes: &[u8], limit: usize) -> anyhow::Result<T> {
         bcs::from_bytes_with_limit(bytes, limit).map_err(|e| anyhow!("{:?}", e))
     }
+
+    /// Serializes the value using BCS encoding, and compresses it with zstd
+    /// if it is above the compression threshold
+    fn zstd_bcs_encode<T: Serialize>(&self, value: &T, limit: usize) -> anyhow::Result<Vec<u8>> {
+        let bcs_bytes = self.bcs_encode(value, limit)?;
+        if bcs_bytes.len() < ZSTD_COMPRESSION_THRESHOLD_BYTES {
+            let mut bytes = Vec::with_capacity(bcs_bytes.len() + 1);
+            bytes.push(ZSTD_BCS_RAW_PREFIX);
+            bytes.extend(bcs_bytes);
+            return Ok(bytes);
+        }
+
+        let compressed_bytes = aptos_compression::zstd_compress(
+            bcs_bytes,
+            self.get_compression_client(),
+            MAX_APPLICATION_MESSAGE_SIZE - 1,
+        )
+        .map_err(|e| anyhow!("{:?}", e))?;
+        let mut bytes = Vec::with_capacity(compressed_bytes.len() + 1);
+        bytes.push(ZSTD_BCS_COMPRESSED_PREFIX);
+        bytes.extend(compressed_bytes);
+        Ok(bytes)
+    }
+
+    /// Deserializes the value using BCS encoding, after decompressing it
+    /// (if it was compressed with zstd)
+    fn zstd_bcs_decode<T: DeserializeOwned>(
+        &self,
+        bytes: &[u8],
+        limit: usize,
+    ) -> anyhow::Result<T> {
+        match bytes.split_first() {
+            Some((&ZSTD_BCS_RAW_PREFIX, bcs_bytes)) => self.bcs_decode(bcs_bytes, limit),
+            Some((&ZSTD_BCS_COMPRESSED_PREFIX, compressed_bytes)) => {
+                let raw_bytes = aptos_compression::zstd_decompress(
+                    compressed_bytes,
+                    self.get_compression_client(),
+                    MAX_APPLICATION_MESSAGE_SIZE,
+                )
+                .map_err(|e| anyhow!("{:?}", e))?;
+                self.bcs_decode(&raw_bytes, limit)
+            },
+            Some((prefix, _)) => Err(anyhow!("Unknown zstd encoding prefix: {}", prefix)),
+            None => Err(anyhow!("Empty zstd encoded message")),
+        }
+    }
 }
 
 impl fmt::Debug for ProtocolId {
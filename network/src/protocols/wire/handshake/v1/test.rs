@@ -183,3 +183,34 @@ fn ignore_unknown_protocols() {
         ProtocolIdSet::empty(),
     );
 }
+
+#[test]
+fn test_zstd_encoding() {
+    for protocol in [
+        ProtocolId::ConsensusDirectSendZstd,
+        ProtocolId::StorageServiceRpcZstd,
+    ] {
+        // Small messages are sent uncompressed
+        let small_message = vec![7u8; 100];
+        let bytes = protocol.to_bytes(&small_message).unwrap();
+        assert_eq!(bytes[0], ZSTD_BCS_RAW_PREFIX);
+        assert_eq!(
+            protocol.from_bytes::<Vec<u8>>(&bytes).unwrap(),
+            small_message
+        );
+
+        // Large messages are compressed
+        let large_message = vec![7u8; 10 * ZSTD_COMPRESSION_THRESHOLD_BYTES];
+        let bytes = protocol.to_bytes(&large_message).unwrap();
+        assert_eq!(bytes[0], ZSTD_BCS_COMPRESSED_PREFIX);
+        assert!(bytes.len() < large_message.len());
+        assert_eq!(
+            protocol.from_bytes::<Vec<u8>>(&bytes).unwrap(),
+            large_message
+        );
+
+        // Unknown prefixes and empty messages are rejected
+        assert!(protocol.from_bytes::<Vec<u8>>(&[2, 0]).is_err());
+        assert!(protocol.from_bytes::<Vec<u8>>(&[]).is_err());
+    }
+}
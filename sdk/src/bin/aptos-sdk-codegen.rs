@@ -0,0 +1,105 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use aptos_api_types::MoveModuleId;
+use aptos_sdk::{
+    codegen::{generate_bindings, AbiLock},
+    move_types::language_storage::ModuleId,
+};
+use clap::Parser;
+use std::path::PathBuf;
+use url::Url;
+
+/// Generates typed Rust bindings for Move modules from their on-chain ABIs
+#[derive(Parser)]
+#[clap(name = "aptos-sdk-codegen", author, version)]
+enum Command {
+    Fetch(Fetch),
+    Generate(Generate),
+}
+
+/// Fetches the modules at a ledger version, and pins them in an ABI lock file
+///
+/// If the lock file exists, its node and modules are used by default, so it can be updated
+/// to a newer ledger version with `aptos-sdk-codegen fetch --abi-lock <FILE>`.
+#[derive(Parser)]
+struct Fetch {
+    /// Path to the ABI lock file
+    #[clap(long, value_parser)]
+    abi_lock: PathBuf,
+
+    /// URL of the node to fetch the modules from
+    #[clap(long)]
+    url: Option<Url>,
+
+    /// Modules to fetch, e.g., `0x1::coin`
+    #[clap(long = "module")]
+    modules: Vec<MoveModuleId>,
+
+    /// Ledger version to fetch the modules at. Defaults to the latest version
+    #[clap(long)]
+    ledger_version: Option<u64>,
+}
+
+/// Generates the bindings of the modules in an ABI lock file
+#[derive(Parser)]
+struct Generate {
+    /// Path to the ABI lock file
+    #[clap(long, value_parser)]
+    abi_lock: PathBuf,
+
+    /// Path to the Rust file to generate
+    #[clap(long, value_parser)]
+    output_file: PathBuf,
+
+    /// Only check that the file is up to date, and fail if it isn't (e.g., in CI)
+    #[clap(long)]
+    check: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    match Command::parse() {
+        Command::Fetch(fetch) => {
+            let existing = if fetch.abi_lock.exists() {
+                Some(AbiLock::load(&fetch.abi_lock)?)
+            } else {
+                None
+            };
+            let url = match (fetch.url, &existing) {
+                (Some(url), _) => url,
+                (None, Some(existing)) => Url::parse(&existing.node_url)?,
+                (None, None) => bail!("--url is required to create an ABI lock"),
+            };
+            let module_ids: Vec<ModuleId> = match (fetch.modules.is_empty(), &existing) {
+                (false, _) => fetch.modules.into_iter().map(Into::into).collect(),
+                (true, Some(existing)) => existing.module_ids()?,
+                (true, None) => bail!("--module is required to create an ABI lock"),
+            };
+            let abi_lock = AbiLock::fetch(url, &module_ids, fetch.ledger_version).await?;
+            abi_lock.save(&fetch.abi_lock)?;
+            println!(
+                "Pinned {} modules at ledger version {}",
+                module_ids.len(),
+                abi_lock.ledger_version
+            );
+        },
+        Command::Generate(generate) => {
+            let bindings = generate_bindings(&AbiLock::load(&generate.abi_lock)?)?;
+            if generate.check {
+                let existing = std::fs::read_to_string(&generate.output_file).unwrap_or_default();
+                if existing != bindings {
+                    return Err(format_err!(
+                        "{} is out of date with {}",
+                        generate.output_file.display(),
+                        generate.abi_lock.display()
+                    ));
+                }
+            } else {
+                std::fs::write(&generate.output_file, bindings)?;
+            }
+        },
+    }
+    Ok(())
+}
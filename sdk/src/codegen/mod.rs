@@ -0,0 +1,156 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generation of typed Rust bindings for Move modules deployed on chain.
+//!
+//! The ABIs of the modules are first fetched from a node and pinned at a ledger version in an
+//! [`AbiLock`] file (e.g., with `aptos-sdk-codegen fetch`). The bindings are then generated from
+//! the lock file, so they only change when the lock file is updated, either by a tool (e.g.,
+//! with `aptos-sdk-codegen generate`) or from a build script (see [`build_bindings`]).
+//!
+//! For each Move module, the bindings include a Rust module with:
+//! * a struct per Move struct, which can be deserialized from the BCS of resources and events
+//! * [`MoveStructType`], [`MoveResource`] and [`MoveEvent`] impls for non-generic structs
+//! * a function per entry function, which builds the payload of a transaction calling it
+//!
+//! The generated code depends on `aptos-sdk` and `serde` (with the `derive` feature).
+//!
+//! [`MoveResource`]: move_core_types::move_resource::MoveResource
+
+mod rust;
+
+use anyhow::{format_err, Context, Result};
+use aptos_api_types::{HexEncodedBytes, MoveModule, MoveModuleBytecode};
+use aptos_rest_client::Client;
+use aptos_types::contract_event::ContractEvent;
+use move_core_types::{
+    language_storage::{ModuleId, TypeTag},
+    move_resource::MoveStructType,
+};
+pub use rust::generate_modules;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::Path;
+use url::Url;
+
+/// The bytecode of Move modules, fetched from a node at a pinned ledger version.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AbiLock {
+    /// The URL of the node the modules were fetched from
+    pub node_url: String,
+    /// The chain ID of the node
+    pub chain_id: u8,
+    /// The ledger version the modules were fetched at
+    pub ledger_version: u64,
+    /// The bytecode of the modules
+    pub modules: Vec<HexEncodedBytes>,
+}
+
+impl AbiLock {
+    /// Fetches the modules at the given ledger version (or the latest one).
+    pub async fn fetch(
+        node_url: Url,
+        module_ids: &[ModuleId],
+        ledger_version: Option<u64>,
+    ) -> Result<Self> {
+        let client = Client::new(node_url.clone());
+        let state = client.get_ledger_information().await?.into_inner();
+        let ledger_version = ledger_version.unwrap_or(state.version);
+        let mut modules = vec![];
+        for module_id in module_ids {
+            let bytes = client
+                .get_account_module_bcs_at_version(
+                    *module_id.address(),
+                    module_id.name().as_str(),
+                    ledger_version,
+                )
+                .await
+                .with_context(|| format!("Failed to fetch module {}", module_id))?
+                .into_inner();
+            modules.push(bytes.to_vec().into());
+        }
+        Ok(Self {
+            node_url: node_url.to_string(),
+            chain_id: state.chain_id,
+            ledger_version,
+            modules,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read ABI lock {}", path.display()))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("Failed to parse ABI lock {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write ABI lock {}", path.display()))
+    }
+
+    /// Parses the ABIs of the modules from their bytecode.
+    pub fn abis(&self) -> Result<Vec<MoveModule>> {
+        self.modules
+            .iter()
+            .map(|bytecode| {
+                MoveModuleBytecode::new(bytecode.inner().to_vec())
+                    .try_parse_abi()?
+                    .abi
+                    .ok_or_else(|| format_err!("Failed to parse module bytecode"))
+            })
+            .collect()
+    }
+
+    pub fn module_ids(&self) -> Result<Vec<ModuleId>> {
+        Ok(self
+            .abis()?
+            .into_iter()
+            .map(|abi| ModuleId::new(*abi.address.inner(), abi.name.0))
+            .collect())
+    }
+}
+
+/// Generates the bindings of the modules in the ABI lock, as the contents of a Rust file.
+pub fn generate_bindings(abi_lock: &AbiLock) -> Result<String> {
+    Ok(format!(
+        "// Copyright © Aptos Foundation\n\
+         // SPDX-License-Identifier: Apache-2.0\n\
+         \n\
+         // This file was generated from the ABIs of chain {} at ledger version {}. Do not modify!\n\
+         //\n\
+         // To update this code, update the ABI lock with `aptos-sdk-codegen fetch`.\n\
+         {}",
+        abi_lock.chain_id,
+        abi_lock.ledger_version,
+        generate_modules(&abi_lock.abis()?)?
+    ))
+}
+
+/// Generates the bindings of an ABI lock into `OUT_DIR`, to be called from a build script.
+/// The bindings can then be included with `include!(concat!(env!("OUT_DIR"), "/<file_name>"))`.
+pub fn build_bindings(abi_lock_path: impl AsRef<Path>, file_name: &str) -> Result<()> {
+    let abi_lock_path = abi_lock_path.as_ref();
+    println!("cargo:rerun-if-changed={}", abi_lock_path.display());
+    let out_dir = std::env::var_os("OUT_DIR")
+        .ok_or_else(|| format_err!("OUT_DIR is not set, is this called from a build script?"))?;
+    let bindings = generate_bindings(&AbiLock::load(abi_lock_path)?)?;
+    let path = Path::new(&out_dir).join(file_name);
+    std::fs::write(&path, bindings)
+        .with_context(|| format!("Failed to write bindings {}", path.display()))
+}
+
+/// A Move struct that is emitted as an event. The impls are generated for the structs that
+/// can be events, i.e., non-generic structs with the `store` and `drop` abilities.
+pub trait MoveEvent: MoveStructType + DeserializeOwned {
+    /// Deserializes the event, or returns None if it's of another type.
+    fn try_from_event(event: &ContractEvent) -> Option<Result<Self>> {
+        match event.type_tag() {
+            TypeTag::Struct(struct_tag) if **struct_tag == Self::struct_tag() => {
+                Some(bcs::from_bytes(event.event_data()).map_err(Into::into))
+            },
+            _ => None,
+        }
+    }
+}
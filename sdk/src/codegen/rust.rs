@@ -0,0 +1,486 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use aptos_api_types::{MoveFunction, MoveModule, MoveStruct, MoveStructTag, MoveType};
+use move_core_types::account_address::AccountAddress;
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Keywords that can't be used as Rust identifiers as is
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Keywords that can't be used as raw identifiers either
+const RESERVED_IDENTIFIERS: &[&str] = &["crate", "self", "Self", "super"];
+
+const MODULE_PREAMBLE: &str = "    use aptos_sdk::{
+        bcs,
+        codegen::MoveEvent,
+        move_types::{
+            account_address::AccountAddress,
+            ident_str,
+            identifier::IdentStr,
+            language_storage::{ModuleId, TypeTag},
+            move_resource::{MoveResource, MoveStructType},
+            u256::U256,
+        },
+        types::{
+            event::EventHandle,
+            state_store::table::TableHandle,
+            transaction::{EntryFunction, TransactionPayload},
+        },
+    };
+    use serde::{Deserialize, Serialize};
+";
+
+/// Generates a Rust module per Move module (see the `codegen` module). The modules can
+/// reference each other's structs, so they must be generated together.
+pub fn generate_modules(modules: &[MoveModule]) -> Result<String> {
+    Generator::new(modules)?.generate()
+}
+
+struct Generator<'a> {
+    modules: &'a [MoveModule],
+    /// The structs of all modules, by address, module name and struct name
+    structs: BTreeMap<(AccountAddress, &'a str, &'a str), &'a MoveStruct>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(modules: &'a [MoveModule]) -> Result<Self> {
+        let mut module_names = BTreeMap::new();
+        let mut structs = BTreeMap::new();
+        for module in modules {
+            // The Rust modules are named after the Move modules, so names must be unique
+            if let Some(address) =
+                module_names.insert(module.name.as_str(), *module.address.inner())
+            {
+                bail!(
+                    "Modules {}::{} and {}::{} have the same name",
+                    address,
+                    module.name,
+                    module.address,
+                    module.name
+                );
+            }
+            for move_struct in &module.structs {
+                structs.insert(
+                    (
+                        *module.address.inner(),
+                        module.name.as_str(),
+                        move_struct.name.as_str(),
+                    ),
+                    move_struct,
+                );
+            }
+        }
+        Ok(Self { modules, structs })
+    }
+
+    fn generate(&self) -> Result<String> {
+        let mut out = String::new();
+        for module in self.modules {
+            self.generate_module(&mut out, module)?;
+        }
+        Ok(out)
+    }
+
+    fn generate_module(&self, out: &mut String, module: &MoveModule) -> Result<()> {
+        writeln!(out)?;
+        writeln!(out, "/// Bindings of `{}::{}`", module.address, module.name)?;
+        writeln!(
+            out,
+            "#[allow(dead_code, unused_imports, clippy::too_many_arguments)]"
+        )?;
+        writeln!(out, "pub mod {} {{", rust_ident(module.name.as_str()))?;
+        out.push_str(MODULE_PREAMBLE);
+        writeln!(out)?;
+        writeln!(
+            out,
+            "    pub const ADDRESS: AccountAddress = AccountAddress::new({:?});",
+            module.address.inner().into_bytes()
+        )?;
+        for move_struct in &module.structs {
+            // Native structs have no fields, so their layout is unknown
+            if !move_struct.is_native {
+                self.generate_struct(out, module, move_struct)?;
+            }
+        }
+        for function in &module.exposed_functions {
+            if function.is_entry {
+                self.generate_entry_function(out, module, function)?;
+            }
+        }
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    fn generate_struct(
+        &self,
+        out: &mut String,
+        module: &MoveModule,
+        move_struct: &MoveStruct,
+    ) -> Result<()> {
+        // Phantom type parameters don't appear in the fields, so they're left out
+        let type_params: Vec<_> = move_struct
+            .generic_type_params
+            .iter()
+            .enumerate()
+            .filter(|(_, param)| !param.is_phantom)
+            .map(|(index, _)| format!("T{}", index))
+            .collect();
+        let name = rust_ident(move_struct.name.as_str());
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "    /// `{}::{}::{}`",
+            module.address, module.name, move_struct.name
+        )?;
+        writeln!(
+            out,
+            "    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]"
+        )?;
+        writeln!(out, "    pub struct {}{} {{", name, type_list(&type_params))?;
+        for field in &move_struct.fields {
+            writeln!(
+                out,
+                "        pub {}: {},",
+                rust_ident(field.name.as_str()),
+                self.rust_type(module, &field.typ, true)?
+            )?;
+        }
+        writeln!(out, "    }}")?;
+
+        // The struct tags of generic structs depend on their type arguments
+        if !move_struct.generic_type_params.is_empty() {
+            return Ok(());
+        }
+        let has_ability = |ability: &str| {
+            move_struct
+                .abilities
+                .iter()
+                .any(|a| a.to_string() == ability)
+        };
+        writeln!(out)?;
+        writeln!(out, "    impl MoveStructType for {} {{", name)?;
+        writeln!(out, "        const ADDRESS: AccountAddress = ADDRESS;")?;
+        writeln!(
+            out,
+            "        const MODULE_NAME: &'static IdentStr = ident_str!(\"{}\");",
+            module.name
+        )?;
+        writeln!(
+            out,
+            "        const STRUCT_NAME: &'static IdentStr = ident_str!(\"{}\");",
+            move_struct.name
+        )?;
+        writeln!(out, "    }}")?;
+        if has_ability("key") {
+            writeln!(out)?;
+            writeln!(out, "    impl MoveResource for {} {{}}", name)?;
+        } else if has_ability("store") && has_ability("drop") {
+            writeln!(out)?;
+            writeln!(out, "    impl MoveEvent for {} {{}}", name)?;
+        }
+        Ok(())
+    }
+
+    fn generate_entry_function(
+        &self,
+        out: &mut String,
+        module: &MoveModule,
+        function: &MoveFunction,
+    ) -> Result<()> {
+        let type_args: Vec<_> = (0..function.generic_type_params.len())
+            .map(|index| format!("t{}", index))
+            .collect();
+        let mut params: Vec<_> = type_args
+            .iter()
+            .map(|type_arg| format!("{}: TypeTag", type_arg))
+            .collect();
+        let mut args = vec![];
+        // Signers are provided by the transaction, so they aren't arguments
+        for param in function.params.iter().filter(|param| !is_signer(param)) {
+            let arg = format!("arg{}", args.len());
+            params.push(format!(
+                "{}: {}",
+                arg,
+                self.rust_type(module, param, false)?
+            ));
+            args.push(arg);
+        }
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "    /// Builds the payload of a call to `{}::{}::{}`",
+            module.address, module.name, function.name
+        )?;
+        writeln!(
+            out,
+            "    pub fn {}({}) -> TransactionPayload {{",
+            rust_ident(function.name.as_str()),
+            params.join(", ")
+        )?;
+        writeln!(
+            out,
+            "        TransactionPayload::EntryFunction(EntryFunction::new("
+        )?;
+        writeln!(
+            out,
+            "            ModuleId::new(ADDRESS, ident_str!(\"{}\").to_owned()),",
+            module.name
+        )?;
+        writeln!(
+            out,
+            "            ident_str!(\"{}\").to_owned(),",
+            function.name
+        )?;
+        writeln!(out, "            vec![{}],", type_args.join(", "))?;
+        writeln!(out, "            vec![")?;
+        for arg in &args {
+            writeln!(out, "                bcs::to_bytes(&{}).unwrap(),", arg)?;
+        }
+        writeln!(out, "            ],")?;
+        writeln!(out, "        ))")?;
+        writeln!(out, "    }}")?;
+        Ok(())
+    }
+
+    /// Returns the Rust type with the same BCS layout as the Move type. Type parameters are
+    /// only allowed in the fields of structs, as entry functions aren't generic in Rust.
+    fn rust_type(
+        &self,
+        module: &MoveModule,
+        move_type: &MoveType,
+        allow_type_params: bool,
+    ) -> Result<String> {
+        Ok(match move_type {
+            MoveType::Bool => "bool".to_string(),
+            MoveType::U8 => "u8".to_string(),
+            MoveType::U16 => "u16".to_string(),
+            MoveType::U32 => "u32".to_string(),
+            MoveType::U64 => "u64".to_string(),
+            MoveType::U128 => "u128".to_string(),
+            MoveType::U256 => "U256".to_string(),
+            MoveType::Address => "AccountAddress".to_string(),
+            MoveType::Vector { items } => format!(
+                "::std::vec::Vec<{}>",
+                self.rust_type(module, items, allow_type_params)?
+            ),
+            MoveType::GenericTypeParam { index } if allow_type_params => format!("T{}", index),
+            MoveType::Struct(struct_tag) => {
+                self.rust_struct_type(module, struct_tag, allow_type_params)?
+            },
+            MoveType::GenericTypeParam { .. }
+            | MoveType::Signer
+            | MoveType::Reference { .. }
+            | MoveType::Unparsable(_) => bail!(
+                "Unsupported type {} in module {}::{}",
+                move_type,
+                module.address,
+                module.name
+            ),
+        })
+    }
+
+    fn rust_struct_type(
+        &self,
+        module: &MoveModule,
+        struct_tag: &MoveStructTag,
+        allow_type_params: bool,
+    ) -> Result<String> {
+        let address = *struct_tag.address.inner();
+        let module_name = struct_tag.module.as_str();
+        let struct_name = struct_tag.name.as_str();
+
+        // Framework structs with a Rust equivalent
+        if address == AccountAddress::ONE {
+            match (module_name, struct_name) {
+                ("string", "String") => return Ok("::std::string::String".to_string()),
+                ("option", "Option") => {
+                    // An option is a vector of at most one value, which has the same layout
+                    if let Some(value_type) = struct_tag.generic_type_params.first() {
+                        return Ok(format!(
+                            "::std::option::Option<{}>",
+                            self.rust_type(module, value_type, allow_type_params)?
+                        ));
+                    }
+                },
+                ("object", "Object") => return Ok("AccountAddress".to_string()),
+                ("event", "EventHandle") => return Ok("EventHandle".to_string()),
+                ("table", "Table") => return Ok("TableHandle".to_string()),
+                _ => {},
+            }
+        }
+
+        let move_struct = match self.structs.get(&(address, module_name, struct_name)) {
+            Some(move_struct) => move_struct,
+            None => bail!(
+                "Struct {} is used by module {}::{}, so its module must be included",
+                struct_tag,
+                module.address,
+                module.name
+            ),
+        };
+        let mut type_args = vec![];
+        for (param, type_arg) in move_struct
+            .generic_type_params
+            .iter()
+            .zip(&struct_tag.generic_type_params)
+        {
+            if !param.is_phantom {
+                type_args.push(self.rust_type(module, type_arg, allow_type_params)?);
+            }
+        }
+        let path = if address == *module.address.inner() && module_name == module.name.as_str() {
+            rust_ident(struct_name)
+        } else {
+            format!(
+                "super::{}::{}",
+                rust_ident(module_name),
+                rust_ident(struct_name)
+            )
+        };
+        Ok(format!("{}{}", path, type_list(&type_args)))
+    }
+}
+
+fn is_signer(move_type: &MoveType) -> bool {
+    match move_type {
+        MoveType::Signer => true,
+        MoveType::Reference { to, .. } => is_signer(to),
+        _ => false,
+    }
+}
+
+/// Returns the list of types as the type arguments (or parameters) of a Rust type
+fn type_list(types: &[String]) -> String {
+    if types.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", types.join(", "))
+    }
+}
+
+/// Returns the Move identifier as a valid Rust identifier
+fn rust_ident(name: &str) -> String {
+    if RESERVED_IDENTIFIERS.contains(&name) {
+        format!("{}_", name)
+    } else if RUST_KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_api_types::{
+        MoveAbility, MoveFunctionVisibility, MoveStructField, MoveStructGenericTypeParam,
+    };
+    use move_core_types::{identifier::Identifier, language_storage::CORE_CODE_ADDRESS};
+    use std::str::FromStr;
+
+    fn move_struct(
+        name: &str,
+        abilities: &[&str],
+        phantom_params: &[bool],
+        fields: Vec<(&str, MoveType)>,
+    ) -> MoveStruct {
+        MoveStruct {
+            name: Identifier::new(name).unwrap().into(),
+            is_native: false,
+            abilities: abilities
+                .iter()
+                .map(|ability| MoveAbility::from_str(ability).unwrap())
+                .collect(),
+            generic_type_params: phantom_params
+                .iter()
+                .map(|is_phantom| MoveStructGenericTypeParam {
+                    constraints: vec![],
+                    is_phantom: *is_phantom,
+                })
+                .collect(),
+            fields: fields
+                .into_iter()
+                .map(|(name, typ)| MoveStructField {
+                    name: Identifier::new(name).unwrap().into(),
+                    typ,
+                })
+                .collect(),
+        }
+    }
+
+    fn move_type(move_type: &str) -> MoveType {
+        MoveType::from_str(move_type).unwrap()
+    }
+
+    fn module() -> MoveModule {
+        let balance_type = MoveType::Struct(MoveStructTag {
+            address: CORE_CODE_ADDRESS.into(),
+            module: Identifier::new("vault").unwrap().into(),
+            name: Identifier::new("Balance").unwrap().into(),
+            generic_type_params: vec![MoveType::GenericTypeParam { index: 0 }],
+        });
+        MoveModule {
+            address: CORE_CODE_ADDRESS.into(),
+            name: Identifier::new("vault").unwrap().into(),
+            friends: vec![],
+            exposed_functions: vec![MoveFunction {
+                name: Identifier::new("deposit").unwrap().into(),
+                visibility: MoveFunctionVisibility::Public,
+                is_entry: true,
+                is_view: false,
+                generic_type_params: vec![],
+                params: vec![
+                    move_type("&signer"),
+                    move_type("u64"),
+                    move_type("0x1::option::Option<0x1::string::String>"),
+                ],
+                return_: vec![],
+            }],
+            structs: vec![
+                move_struct("Vault", &["key"], &[true], vec![
+                    ("balance", balance_type),
+                    ("type", move_type("vector<address>")),
+                ]),
+                move_struct("Balance", &["store"], &[true], vec![(
+                    "value",
+                    move_type("u64"),
+                )]),
+                move_struct("DepositEvent", &["drop", "store"], &[], vec![(
+                    "amount",
+                    move_type("u64"),
+                )]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_generate_modules() {
+        let code = generate_modules(&[module()]).unwrap();
+        // Phantom type parameters are left out
+        assert!(code.contains("    pub struct Vault {\n        pub balance: Balance,\n        pub r#type: ::std::vec::Vec<AccountAddress>,\n    }"));
+        assert!(code.contains("    pub struct Balance {\n        pub value: u64,\n    }"));
+        // Only non-generic structs have struct tags
+        assert!(!code.contains("impl MoveResource for Vault"));
+        assert!(code.contains("impl MoveStructType for DepositEvent"));
+        assert!(code.contains("impl MoveEvent for DepositEvent {}"));
+        assert!(code.contains("    pub fn deposit(arg0: u64, arg1: ::std::option::Option<::std::string::String>) -> TransactionPayload {"));
+    }
+
+    #[test]
+    fn test_generate_modules_with_missing_struct() {
+        let mut module = module();
+        module.structs[1] =
+            move_struct("Other", &["store"], &[], vec![("value", move_type("u64"))]);
+        assert!(generate_modules(&[module]).is_err());
+    }
+}
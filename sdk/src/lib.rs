@@ -8,10 +8,12 @@
 //!
 //! This SDK provides all the necessary components for building on top of the Aptos Blockchain. Some of the important modules are:
 //!
+//! * `codegen` - Generates typed Rust bindings for Move modules from their on-chain ABIs
 //! * `crypto` - Types used for signing and verifying
 //! * `move_types` - Includes types used when interacting with the Move VM
 //! * `rest_client` - The Aptos API Client, used for sending requests to the Aptos Blockchain.
 //! * `transaction_builder` - Includes helpers for constructing transactions
+//! * `transaction_waiter` - Tracks submitted transactions until they're committed or expire (`transaction-waiter` feature, enabled by default)
 //! * `types` - Includes types for Aptos on-chain data structures
 //!
 //! ## Example
@@ -24,6 +26,8 @@ pub use bcs;
 
 pub mod coin_client;
 
+pub mod codegen;
+
 pub mod crypto {
     pub use aptos_crypto::*;
 }
@@ -38,4 +42,7 @@ pub mod rest_client {
 
 pub mod transaction_builder;
 
+#[cfg(feature = "transaction-waiter")]
+pub mod transaction_waiter;
+
 pub mod types;
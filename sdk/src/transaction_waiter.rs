@@ -0,0 +1,589 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    crypto::HashValue,
+    rest_client::{
+        error::RestError,
+        stream::{StreamMessage, StreamQuery},
+        Client, Transaction,
+    },
+    types::{account_address::AccountAddress, transaction::SignedTransaction},
+};
+use anyhow::{bail, Result};
+use std::{
+    mem::discriminant,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The status of a transaction tracked by a [`TransactionWaiter`]
+#[derive(Clone, Debug)]
+pub enum TransactionStatus {
+    /// The transaction was submitted, but hasn't been seen in mempool yet
+    Submitted,
+    /// The transaction is in mempool, and is ready to be ordered in a block
+    Pending,
+    /// The transaction is in mempool, but can't be ordered until the transactions of the
+    /// sender with lower sequence numbers arrive
+    Parked,
+    /// The transaction was pulled from mempool into a batch of a block proposal. This doesn't
+    /// mean it's ordered yet: it goes back to `Pending` if the batch isn't committed (and the
+    /// transaction isn't pulled again). This is only known on validators, so transactions
+    /// tracked on fullnodes go from `Pending` straight to `Committed`.
+    PulledIntoBatch,
+    /// The transaction was committed (successfully or not, see `Transaction::success`)
+    Committed(Box<Transaction>),
+    /// The transaction expired, so it is guaranteed to never be committed
+    Expired,
+}
+
+impl TransactionStatus {
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Committed(_) | Self::Expired)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TransactionWaiterConfig {
+    /// Whether to wait on the stream endpoint of the node, if it's enabled, before falling
+    /// back to polling
+    pub use_stream: bool,
+    /// The stream is considered stalled if no message is received for this long (the stream
+    /// includes block headers, so messages are expected for every block)
+    pub stream_idle_timeout: Duration,
+    /// The interval between the first polls, which doubles after every poll
+    pub initial_poll_interval: Duration,
+    pub max_poll_interval: Duration,
+    /// Whether to check if pending transactions are parked or pulled into a batch in mempool.
+    /// This requires the experimental mempool endpoint of the node, and is skipped if it's
+    /// disabled.
+    pub check_mempool: bool,
+    /// Fullnodes lag behind the chain, so we wait this long after the expiration of the
+    /// transaction for the node to catch up, before giving up
+    pub max_server_lag_wait: Duration,
+}
+
+impl Default for TransactionWaiterConfig {
+    fn default() -> Self {
+        Self {
+            use_stream: true,
+            stream_idle_timeout: Duration::from_secs(10),
+            initial_poll_interval: Duration::from_millis(200),
+            max_poll_interval: Duration::from_secs(5),
+            check_mempool: true,
+            max_server_lag_wait: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Tracks a submitted transaction until it's committed or expires, and reports the status
+/// transitions along the way (see [`TransactionStatus`]).
+///
+/// The transaction is tracked on the stream endpoint of the node when it's enabled, which
+/// pushes the transactions of the sender as soon as they're committed. Otherwise (or if the
+/// stream fails), the transaction is polled by hash with exponential backoff.
+#[derive(Clone, Debug)]
+pub struct TransactionWaiter {
+    client: Client,
+    config: TransactionWaiterConfig,
+}
+
+/// The transaction being tracked
+struct TrackedTransaction {
+    hash: HashValue,
+    sender: AccountAddress,
+    expiration_timestamp_secs: u64,
+}
+
+/// The result of polling the transaction
+struct Poll {
+    /// The status, if the transaction is known to the node
+    status: Option<TransactionStatus>,
+    ledger_version: u64,
+}
+
+/// Reports the status transitions to the caller
+struct StatusUpdates<F> {
+    status: TransactionStatus,
+    on_update: F,
+}
+
+impl<F: FnMut(&TransactionStatus)> StatusUpdates<F> {
+    fn update(&mut self, status: TransactionStatus) {
+        if discriminant(&status) != discriminant(&self.status) {
+            (self.on_update)(&status);
+            self.status = status;
+        }
+    }
+}
+
+impl TransactionWaiter {
+    pub fn new(client: Client) -> Self {
+        Self::with_config(client, TransactionWaiterConfig::default())
+    }
+
+    pub fn with_config(client: Client, config: TransactionWaiterConfig) -> Self {
+        Self { client, config }
+    }
+
+    /// Waits until the transaction is committed or expires, and returns its final status.
+    pub async fn wait(&self, transaction: &SignedTransaction) -> Result<TransactionStatus> {
+        self.wait_with_updates(transaction, |_| {}).await
+    }
+
+    /// Waits until the transaction is committed or expires, and returns its final status.
+    /// `on_update` is called on every status transition, including the final one.
+    pub async fn wait_with_updates(
+        &self,
+        transaction: &SignedTransaction,
+        on_update: impl FnMut(&TransactionStatus),
+    ) -> Result<TransactionStatus> {
+        let transaction = TrackedTransaction {
+            hash: transaction.clone().committed_hash(),
+            sender: transaction.sender(),
+            expiration_timestamp_secs: transaction.expiration_timestamp_secs(),
+        };
+        let mut updates = StatusUpdates {
+            status: TransactionStatus::Submitted,
+            on_update,
+        };
+
+        // Poll first, as the transaction may already be committed. This also gives us the
+        // version to start streaming from, so no commit can be missed.
+        let mut ledger_version = None;
+        if let Ok(poll) = self.poll(&transaction).await {
+            if let Some(status) = poll.status {
+                updates.update(status);
+                if updates.status.is_final() {
+                    return Ok(updates.status);
+                }
+            }
+            ledger_version = Some(poll.ledger_version);
+        }
+
+        if let (true, Some(ledger_version)) = (self.config.use_stream, ledger_version) {
+            // If the stream is disabled or fails, we fall back to polling
+            if let Ok(status) = self.wait_on_stream(&transaction, ledger_version + 1).await {
+                updates.update(status);
+                return Ok(updates.status);
+            }
+        }
+        self.wait_by_polling(&transaction, &mut updates).await
+    }
+
+    async fn wait_on_stream(
+        &self,
+        transaction: &TrackedTransaction,
+        start_version: u64,
+    ) -> Result<TransactionStatus> {
+        let query = StreamQuery {
+            account: Some(transaction.sender),
+            blocks: true,
+            start_version: Some(start_version),
+            ..StreamQuery::default()
+        };
+        let mut stream = self.client.stream(&query).await?;
+        loop {
+            let message =
+                match tokio::time::timeout(self.config.stream_idle_timeout, stream.next()).await {
+                    Ok(message) => message?,
+                    Err(_) => bail!("The stream stalled"),
+                };
+            match message {
+                Some(StreamMessage::Transaction {
+                    transaction: txn, ..
+                }) if txn.transaction_info()?.hash.0 == transaction.hash => {
+                    return Ok(TransactionStatus::Committed(txn));
+                },
+                Some(StreamMessage::Block { block, .. })
+                    if block.timestamp.0 / 1_000_000 >= transaction.expiration_timestamp_secs =>
+                {
+                    // Every version before the block was streamed, so the transaction expired
+                    return Ok(TransactionStatus::Expired);
+                },
+                Some(StreamMessage::Error { message }) => bail!("The stream failed: {}", message),
+                Some(_) => {},
+                None => bail!("The stream was closed"),
+            }
+        }
+    }
+
+    async fn wait_by_polling<F: FnMut(&TransactionStatus)>(
+        &self,
+        transaction: &TrackedTransaction,
+        updates: &mut StatusUpdates<F>,
+    ) -> Result<TransactionStatus> {
+        let mut poll_interval = self.config.initial_poll_interval;
+        loop {
+            // Errors may be transient, so we keep polling until the transaction expires
+            if let Ok(Poll {
+                status: Some(status),
+                ..
+            }) = self.poll(transaction).await
+            {
+                updates.update(status);
+                if updates.status.is_final() {
+                    return Ok(updates.status.clone());
+                }
+            }
+
+            let now_secs = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            if now_secs
+                > transaction.expiration_timestamp_secs + self.config.max_server_lag_wait.as_secs()
+            {
+                bail!(
+                    "Ledger on endpoint ({}) is more than {}s behind current time, timing out waiting for the transaction. Warning, transaction ({}) might still succeed.",
+                    self.client.path_prefix_string(),
+                    self.config.max_server_lag_wait.as_secs(),
+                    transaction.hash,
+                );
+            }
+
+            tokio::time::sleep(poll_interval).await;
+            poll_interval = std::cmp::min(poll_interval * 2, self.config.max_poll_interval);
+        }
+    }
+
+    async fn poll(&self, transaction: &TrackedTransaction) -> Result<Poll> {
+        let (status, state) = match self.client.get_transaction_by_hash(transaction.hash).await {
+            Ok(response) => {
+                let (txn, state) = response.into_parts();
+                let status = match txn {
+                    Transaction::PendingTransaction(_) => self.pending_status(transaction).await,
+                    txn => TransactionStatus::Committed(Box::new(txn)),
+                };
+                (Some(status), state)
+            },
+            Err(RestError::Api(error)) if error.status_code == 404 => match error.state {
+                Some(state) => (None, state),
+                None => bail!("Missing ledger state in the response"),
+            },
+            Err(error) => return Err(error.into()),
+        };
+
+        // The transaction can't be committed once the ledger passed its expiration
+        let is_expired = state.timestamp_usecs / 1_000_000 >= transaction.expiration_timestamp_secs;
+        let status = match status {
+            Some(TransactionStatus::Committed(txn)) => Some(TransactionStatus::Committed(txn)),
+            _ if is_expired => Some(TransactionStatus::Expired),
+            status => status,
+        };
+        Ok(Poll {
+            status,
+            ledger_version: state.version,
+        })
+    }
+
+    /// Returns whether the pending transaction is parked or pulled into a batch, if that can be
+    /// checked.
+    async fn pending_status(&self, transaction: &TrackedTransaction) -> TransactionStatus {
+        if self.config.check_mempool {
+            if let Ok(response) = self
+                .client
+                .get_mempool_account_diagnostics(transaction.sender)
+                .await
+            {
+                let pending_txn = response
+                    .inner()
+                    .pending_transactions
+                    .iter()
+                    .find(|txn| txn.hash.0 == transaction.hash);
+                match pending_txn {
+                    Some(txn) if txn.in_consensus => return TransactionStatus::PulledIntoBatch,
+                    Some(txn) if txn.is_parked => return TransactionStatus::Parked,
+                    _ => {},
+                }
+            }
+        }
+        TransactionStatus::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        transaction_builder::TransactionFactory,
+        types::{chain_id::ChainId, LocalAccount},
+    };
+    use aptos_api_types::{
+        transaction::StateCheckpointTransaction, AptosError, AptosErrorCode,
+        MempoolAccountDiagnostics, MempoolPendingTransaction, TransactionInfo,
+        X_APTOS_BLOCK_HEIGHT, X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_OLDEST_VERSION,
+        X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION, X_APTOS_OLDEST_BLOCK_HEIGHT,
+    };
+    use httpmock::{Mock, MockServer, Then};
+    use serde_json::{json, Value};
+    use tokio::sync::mpsc;
+
+    const LEDGER_VERSION: u64 = 10;
+
+    fn now_secs() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn create_waiter(server: &MockServer, use_stream: bool) -> TransactionWaiter {
+        TransactionWaiter::with_config(
+            Client::new(server.base_url().parse().unwrap()),
+            TransactionWaiterConfig {
+                use_stream,
+                initial_poll_interval: Duration::from_millis(10),
+                max_poll_interval: Duration::from_millis(10),
+                ..TransactionWaiterConfig::default()
+            },
+        )
+    }
+
+    fn create_transaction(expiration_timestamp_secs: u64) -> SignedTransaction {
+        let mut account = LocalAccount::generate(&mut rand::rngs::OsRng);
+        account.sign_with_transaction_builder(
+            TransactionFactory::new(ChainId::test())
+                .transfer(AccountAddress::ONE, 1)
+                .expiration_timestamp_secs(expiration_timestamp_secs),
+        )
+    }
+
+    /// Adds the ledger state headers of the node to the response
+    fn with_ledger_state(then: Then, timestamp_secs: u64) -> Then {
+        then.header(X_APTOS_CHAIN_ID, ChainId::test().id().to_string().as_str())
+            .header(X_APTOS_EPOCH, "1")
+            .header(X_APTOS_LEDGER_VERSION, LEDGER_VERSION.to_string().as_str())
+            .header(X_APTOS_LEDGER_OLDEST_VERSION, "0")
+            .header(
+                X_APTOS_LEDGER_TIMESTAMP,
+                (timestamp_secs * 1_000_000).to_string().as_str(),
+            )
+            .header(X_APTOS_BLOCK_HEIGHT, "5")
+            .header(X_APTOS_OLDEST_BLOCK_HEIGHT, "0")
+    }
+
+    fn committed_transaction(hash: HashValue) -> Transaction {
+        Transaction::StateCheckpointTransaction(StateCheckpointTransaction {
+            info: TransactionInfo {
+                version: (LEDGER_VERSION + 1).into(),
+                hash: hash.into(),
+                state_change_hash: HashValue::zero().into(),
+                event_root_hash: HashValue::zero().into(),
+                state_checkpoint_hash: None,
+                gas_used: 0.into(),
+                success: true,
+                vm_status: "Executed successfully".to_string(),
+                accumulator_root_hash: HashValue::zero().into(),
+                changes: vec![],
+                block_height: None,
+                epoch: None,
+            },
+            timestamp: 0.into(),
+        })
+    }
+
+    fn pending_transaction(txn: &SignedTransaction) -> Value {
+        json!({
+            "type": "pending_transaction",
+            "hash": txn.clone().committed_hash().to_hex_literal(),
+            "sender": txn.sender().to_hex_literal(),
+            "sequence_number": txn.sequence_number().to_string(),
+            "max_gas_amount": txn.max_gas_amount().to_string(),
+            "gas_unit_price": txn.gas_unit_price().to_string(),
+            "expiration_timestamp_secs": txn.expiration_timestamp_secs().to_string(),
+            "payload": {
+                "type": "entry_function_payload",
+                "function": "0x1::coin::transfer",
+                "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+                "arguments": ["0x1", "1"],
+            },
+        })
+    }
+
+    fn block_message(timestamp_secs: u64) -> Value {
+        json!({
+            "type": "block",
+            "version": (LEDGER_VERSION + 1).to_string(),
+            "block": {
+                "block_id": HashValue::zero().to_hex_literal(),
+                "epoch": "1",
+                "round": "1",
+                "timestamp": (timestamp_secs * 1_000_000).to_string(),
+                "proposer": "0x1",
+            },
+        })
+    }
+
+    /// Returns the body of a stream response with the given messages
+    fn stream_body(messages: &[Value]) -> String {
+        messages
+            .iter()
+            .map(|message| format!("data: {}\n\n", message))
+            .collect()
+    }
+
+    fn mock_not_found<'a>(
+        server: &'a MockServer,
+        txn: &SignedTransaction,
+        timestamp_secs: u64,
+    ) -> Mock<'a> {
+        let path = transaction_path(txn);
+        server.mock(|when, then| {
+            when.method("GET").path(path);
+            with_ledger_state(then.status(404), timestamp_secs).json_body_obj(
+                &AptosError::new_with_error_code(
+                    "Transaction not found",
+                    AptosErrorCode::TransactionNotFound,
+                ),
+            );
+        })
+    }
+
+    fn mock_transaction<'a>(
+        server: &'a MockServer,
+        txn: &SignedTransaction,
+        body: Value,
+    ) -> Mock<'a> {
+        let path = transaction_path(txn);
+        server.mock(|when, then| {
+            when.method("GET").path(path);
+            with_ledger_state(then.status(200), now_secs()).json_body(body);
+        })
+    }
+
+    fn mock_mempool<'a>(
+        server: &'a MockServer,
+        txn: &SignedTransaction,
+        in_consensus: bool,
+    ) -> Mock<'a> {
+        let diagnostics = MempoolAccountDiagnostics {
+            committed_sequence_number: Some(txn.sequence_number().into()),
+            pending_transactions: vec![MempoolPendingTransaction {
+                sequence_number: txn.sequence_number().into(),
+                hash: txn.clone().committed_hash().into(),
+                gas_unit_price: txn.gas_unit_price().into(),
+                insertion_timestamp_usecs: 0.into(),
+                is_parked: false,
+                in_consensus,
+                received_from: None,
+            }],
+            gaps: vec![],
+        };
+        let path = format!(
+            "/v1/experimental/mempool/accounts/{}",
+            txn.sender().to_hex_literal()
+        );
+        server.mock(|when, then| {
+            when.method("GET").path(path);
+            with_ledger_state(then.status(200), now_secs()).json_body_obj(&diagnostics);
+        })
+    }
+
+    fn transaction_path(txn: &SignedTransaction) -> String {
+        format!(
+            "/v1/transactions/by_hash/{}",
+            txn.clone().committed_hash().to_hex_literal()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_wait_on_stream_until_committed() {
+        let server = MockServer::start();
+        let txn = create_transaction(now_secs() + 60);
+        mock_not_found(&server, &txn, now_secs());
+
+        // The stream starts right after the polled ledger version
+        let committed_txn = committed_transaction(txn.clone().committed_hash());
+        let stream = server.mock(|when, then| {
+            when.method("GET")
+                .path("/v1/stream")
+                .query_param("account", txn.sender().to_hex_literal().as_str())
+                .query_param("blocks", "true")
+                .query_param("start_version", (LEDGER_VERSION + 1).to_string().as_str());
+            then.status(200).body(stream_body(&[
+                block_message(now_secs()),
+                json!({
+                    "type": "transaction",
+                    "version": (LEDGER_VERSION + 1).to_string(),
+                    "transaction": committed_txn,
+                }),
+            ]));
+        });
+
+        let status = create_waiter(&server, true).wait(&txn).await.unwrap();
+        assert!(matches!(status, TransactionStatus::Committed(txn) if *txn == committed_txn));
+        stream.assert();
+    }
+
+    #[tokio::test]
+    async fn test_wait_on_stream_until_expired() {
+        let server = MockServer::start();
+        let expiration_timestamp_secs = now_secs() + 60;
+        let txn = create_transaction(expiration_timestamp_secs);
+        mock_not_found(&server, &txn, now_secs());
+
+        // A block past the expiration is streamed, before the transaction was committed
+        let stream = server.mock(|when, then| {
+            when.method("GET").path("/v1/stream");
+            then.status(200).body(stream_body(&[
+                block_message(now_secs()),
+                block_message(expiration_timestamp_secs),
+            ]));
+        });
+
+        let status = create_waiter(&server, true).wait(&txn).await.unwrap();
+        assert!(matches!(status, TransactionStatus::Expired));
+        stream.assert();
+    }
+
+    #[tokio::test]
+    async fn test_wait_by_polling_until_committed() {
+        let server = MockServer::start();
+        let txn = create_transaction(now_secs() + 60);
+        let mut pending = mock_transaction(&server, &txn, pending_transaction(&txn));
+        let mut mempool = mock_mempool(&server, &txn, false);
+
+        // Report the status transitions while waiting
+        let waiter = create_waiter(&server, false);
+        let (update_sender, mut update_receiver) = mpsc::unbounded_channel();
+        let wait_txn = txn.clone();
+        let wait = tokio::spawn(async move {
+            waiter
+                .wait_with_updates(&wait_txn, |status| {
+                    update_sender.send(status.clone()).unwrap();
+                })
+                .await
+        });
+        let update = update_receiver.recv().await.unwrap();
+        assert!(matches!(update, TransactionStatus::Pending));
+
+        // Consensus pulls the transaction
+        mempool.delete();
+        mock_mempool(&server, &txn, true);
+        let update = update_receiver.recv().await.unwrap();
+        assert!(matches!(update, TransactionStatus::PulledIntoBatch));
+
+        // And commits it
+        pending.delete();
+        let committed_txn = committed_transaction(txn.clone().committed_hash());
+        mock_transaction(&server, &txn, serde_json::to_value(&committed_txn).unwrap());
+        let update = update_receiver.recv().await.unwrap();
+        assert!(matches!(update, TransactionStatus::Committed(_)));
+        let status = wait.await.unwrap().unwrap();
+        assert!(matches!(status, TransactionStatus::Committed(txn) if *txn == committed_txn));
+        assert!(update_receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_wait_by_polling_until_expired() {
+        let server = MockServer::start();
+        let expiration_timestamp_secs = now_secs() + 60;
+        let txn = create_transaction(expiration_timestamp_secs);
+
+        // The ledger passed the expiration, so the transaction can't be committed anymore
+        mock_not_found(&server, &txn, expiration_timestamp_secs);
+        let status = create_waiter(&server, false).wait(&txn).await.unwrap();
+        assert!(matches!(status, TransactionStatus::Expired));
+    }
+}
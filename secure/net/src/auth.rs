@@ -0,0 +1,165 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Authentication of the streams between a client and a server sharing a secret key (e.g., a node
+//! and its safety rules process on the same host).
+//!
+//! Upon connection, both ends exchange a random nonce and the version of their binary, and then
+//! prove the knowledge of the key by sending an HMAC of the handshake. Connections from peers that
+//! don't know the key or run another version are rejected. Afterwards, every message is followed
+//! by an HMAC (under a key derived for the session) of its direction, its sequence number and its
+//! contents, so messages can't be forged, replayed or reordered.
+
+use crate::{Error, NetworkMode, NetworkStream};
+use ring::{
+    hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path};
+
+const NONCE_LENGTH: usize = 32;
+
+/// The shared key of a client and a server, and the version of the local binary that is attested
+/// to the other end.
+#[derive(Clone)]
+pub struct StreamAuthConfig {
+    key: hmac::Key,
+    version: String,
+}
+
+impl StreamAuthConfig {
+    pub fn new(key: &[u8], version: String) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, key),
+            version,
+        }
+    }
+
+    /// Reads the shared key from a file, which should only be readable by the client and the
+    /// server.
+    pub fn from_key_file(path: &Path, version: String) -> io::Result<Self> {
+        let key = fs::read(path)?;
+        if key.len() < NONCE_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The key must be at least {} bytes", NONCE_LENGTH),
+            ));
+        }
+        Ok(Self::new(&key, version))
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct Hello {
+    nonce: [u8; NONCE_LENGTH],
+    version: String,
+}
+
+/// The authentication of the messages of an established session
+pub(crate) struct SessionAuth {
+    key: hmac::Key,
+    mode: NetworkMode,
+    sent: u64,
+    received: u64,
+}
+
+impl SessionAuth {
+    /// Appends the tag of the next outbound message to it.
+    pub(crate) fn seal(&mut self, data: &[u8]) -> Vec<u8> {
+        let tag = message_tag(&self.key, self.mode, self.sent, data);
+        self.sent += 1;
+        let mut sealed = data.to_vec();
+        sealed.extend_from_slice(tag.as_ref());
+        sealed
+    }
+
+    /// Verifies and strips the tag of the next inbound message.
+    pub(crate) fn open(&mut self, mut sealed: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let tag_length = hmac::HMAC_SHA256.digest_algorithm().output_len;
+        let data_length = sealed
+            .len()
+            .checked_sub(tag_length)
+            .ok_or_else(|| Error::AuthenticationFailed("The message has no tag".into()))?;
+        let tag = sealed.split_off(data_length);
+        let expected_tag = message_tag(&self.key, self.mode.other(), self.received, &sealed);
+        if ring::constant_time::verify_slices_are_equal(expected_tag.as_ref(), &tag).is_err() {
+            return Err(Error::AuthenticationFailed(
+                "The message has an invalid tag".into(),
+            ));
+        }
+        self.received += 1;
+        Ok(sealed)
+    }
+}
+
+fn message_tag(
+    key: &hmac::Key,
+    sender: NetworkMode,
+    sequence_number: u64,
+    data: &[u8],
+) -> hmac::Tag {
+    let mut context = hmac::Context::with_key(key);
+    context.update(sender.as_str().as_bytes());
+    context.update(&sequence_number.to_le_bytes());
+    context.update(data);
+    context.sign()
+}
+
+/// Authenticates the other end of a new stream, and returns the authentication of the session.
+pub(crate) fn handshake(
+    stream: &mut NetworkStream,
+    mode: NetworkMode,
+    config: &StreamAuthConfig,
+) -> Result<SessionAuth, Error> {
+    let mut nonce = [0; NONCE_LENGTH];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| Error::AuthenticationFailed("Unable to generate a nonce".into()))?;
+    let local = Hello {
+        nonce,
+        version: config.version.clone(),
+    };
+
+    stream.write(&bcs::to_bytes(&local)?)?;
+    let remote: Hello = bcs::from_bytes(&stream.read()?)?;
+    if remote.version != local.version {
+        return Err(Error::VersionMismatch {
+            local: local.version,
+            remote: remote.version,
+        });
+    }
+
+    // The transcript is the same for both ends: the client's hello followed by the server's
+    let (client, server) = match mode {
+        NetworkMode::Client => (&local, &remote),
+        NetworkMode::Server => (&remote, &local),
+    };
+    let transcript_tag = |label: &str| {
+        let mut context = hmac::Context::with_key(&config.key);
+        context.update(label.as_bytes());
+        context.update(&client.nonce);
+        context.update(&server.nonce);
+        context.update(client.version.as_bytes());
+        context.sign()
+    };
+
+    // Each end proves the knowledge of the key, and verifies the proof of the other end
+    stream.write(transcript_tag(mode.as_str()).as_ref())?;
+    let remote_proof = stream.read()?;
+    let expected_proof = transcript_tag(mode.other().as_str());
+    if ring::constant_time::verify_slices_are_equal(expected_proof.as_ref(), &remote_proof).is_err()
+    {
+        return Err(Error::AuthenticationFailed(
+            "The peer doesn't know the shared key".into(),
+        ));
+    }
+
+    let session_key = transcript_tag("session");
+    Ok(SessionAuth {
+        key: hmac::Key::new(hmac::HMAC_SHA256, session_key.as_ref()),
+        mode,
+        sent: 0,
+        received: 0,
+    })
+}
@@ -14,12 +14,16 @@
 //! server.
 //!
 //! Internally both the client and server leverage a NetworkStream that communications in blocks
-//! where a block is a length prefixed array of bytes.
+//! where a block is a length prefixed array of bytes. Optionally, the streams can be authenticated
+//! with a key shared by the client and the server (see [`StreamAuthConfig`]).
 
+mod auth;
 pub mod network_controller;
 
+use crate::auth::SessionAuth;
 use aptos_logger::{info, trace, warn, Schema};
 use aptos_metrics_core::{register_int_counter_vec, IntCounterVec};
+pub use auth::StreamAuthConfig;
 use once_cell::sync::Lazy;
 use serde::Serialize;
 use std::{
@@ -60,6 +64,7 @@ enum LogEvent {
     ConnectionFailed,
     DisconnectedPeerOnRead,
     DisconnectedPeerOnWrite,
+    AuthenticationFailed,
     Shutdown,
 }
 
@@ -77,6 +82,13 @@ impl NetworkMode {
             NetworkMode::Server => "server",
         }
     }
+
+    fn other(&self) -> Self {
+        match self {
+            NetworkMode::Client => NetworkMode::Server,
+            NetworkMode::Server => NetworkMode::Client,
+        }
+    }
 }
 
 static EVENT_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -91,6 +103,7 @@ static EVENT_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
 #[derive(Clone, Copy, Serialize)]
 #[serde(rename_all = "snake_case")]
 enum Method {
+    Authenticate,
     Connect,
     Read,
     Write,
@@ -99,6 +112,7 @@ enum Method {
 impl Method {
     fn as_str(&self) -> &'static str {
         match self {
+            Method::Authenticate => "authenticate",
             Method::Connect => "connect",
             Method::Read => "read",
             Method::Write => "write",
@@ -134,6 +148,8 @@ fn increment_counter(service: &str, mode: NetworkMode, method: Method, result: M
 pub enum Error {
     #[error("Already called shutdown")]
     AlreadyShutdown,
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
     #[error("Found data that is too large to decode: {0}")]
     DataTooLarge(usize),
     #[error("Internal network error:")]
@@ -144,6 +160,10 @@ pub enum Error {
     OverflowError(String),
     #[error("Remote stream cleanly closed")]
     RemoteStreamClosed,
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] bcs::Error),
+    #[error("Version mismatch, local: {local}, remote: {remote}")]
+    VersionMismatch { local: String, remote: String },
 }
 
 pub struct NetworkClient {
@@ -152,6 +172,7 @@ pub struct NetworkClient {
     stream: Option<NetworkStream>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    auth: Option<StreamAuthConfig>,
 }
 
 impl NetworkClient {
@@ -161,6 +182,20 @@ impl NetworkClient {
             server,
             stream: None,
             timeout_ms,
+            auth: None,
+        }
+    }
+
+    /// Creates a client that authenticates the server upon connection (see [`StreamAuthConfig`]).
+    pub fn new_authenticated(
+        service: String,
+        server: SocketAddr,
+        timeout_ms: u64,
+        auth: StreamAuthConfig,
+    ) -> Self {
+        Self {
+            auth: Some(auth),
+            ..Self::new(service, server, timeout_ms)
         }
     }
 
@@ -255,7 +290,14 @@ impl NetworkClient {
 
             let stream = stream?;
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, self.server, self.timeout_ms));
+            let mut stream = NetworkStream::new(stream, self.server, self.timeout_ms);
+            authenticate(
+                &mut stream,
+                &self.service,
+                NetworkMode::Client,
+                self.auth.as_ref(),
+            )?;
+            self.stream = Some(stream);
             self.increment_counter(Method::Connect, MethodResult::Success);
             info!(SecureNetLogSchema::new(
                 &self.service,
@@ -275,6 +317,7 @@ pub struct NetworkServer {
     stream: Option<NetworkStream>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    auth: Option<StreamAuthConfig>,
 }
 
 impl NetworkServer {
@@ -285,6 +328,21 @@ impl NetworkServer {
             listener: Some(listener.unwrap()),
             stream: None,
             timeout_ms,
+            auth: None,
+        }
+    }
+
+    /// Creates a server that only accepts the clients that authenticate upon connection (see
+    /// [`StreamAuthConfig`]).
+    pub fn new_authenticated(
+        service: String,
+        listen: SocketAddr,
+        timeout_ms: u64,
+        auth: StreamAuthConfig,
+    ) -> Self {
+        Self {
+            auth: Some(auth),
+            ..Self::new(service, listen, timeout_ms)
         }
     }
 
@@ -397,18 +455,56 @@ impl NetworkServer {
             .remote_peer(&stream_addr));
 
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, stream_addr, self.timeout_ms));
+            let mut stream = NetworkStream::new(stream, stream_addr, self.timeout_ms);
+            authenticate(
+                &mut stream,
+                &self.service,
+                NetworkMode::Server,
+                self.auth.as_ref(),
+            )?;
+            self.stream = Some(stream);
         }
 
         self.stream.as_mut().ok_or(Error::NoActiveStream)
     }
 }
 
+/// Runs the authentication handshake on a new stream (if authentication is enabled).
+fn authenticate(
+    stream: &mut NetworkStream,
+    service: &str,
+    mode: NetworkMode,
+    auth: Option<&StreamAuthConfig>,
+) -> Result<(), Error> {
+    let auth = match auth {
+        Some(auth) => auth,
+        None => return Ok(()),
+    };
+    increment_counter(service, mode, Method::Authenticate, MethodResult::Query);
+    match auth::handshake(stream, mode, auth) {
+        Ok(session_auth) => {
+            increment_counter(service, mode, Method::Authenticate, MethodResult::Success);
+            stream.auth = Some(session_auth);
+            Ok(())
+        },
+        Err(err) => {
+            increment_counter(service, mode, Method::Authenticate, MethodResult::Failure);
+            warn!(
+                SecureNetLogSchema::new(service, mode, LogEvent::AuthenticationFailed)
+                    .error(&err)
+                    .remote_peer(&stream.remote)
+            );
+            Err(err)
+        },
+    }
+}
+
 struct NetworkStream {
     stream: TcpStream,
     remote: SocketAddr,
     buffer: Vec<u8>,
     temp_buffer: [u8; 1024],
+    auth: Option<SessionAuth>,
 }
 
 impl NetworkStream {
@@ -423,11 +519,31 @@ impl NetworkStream {
             remote,
             buffer: Vec::new(),
             temp_buffer: [0; 1024],
+            auth: None,
         }
     }
 
-    /// Blocking read until able to successfully read an entire message
+    /// Blocking read until able to successfully read an entire (authenticated) message
     pub fn read(&mut self) -> Result<Vec<u8>, Error> {
+        let message = self.read_message()?;
+        match &mut self.auth {
+            Some(auth) => auth.open(message),
+            None => Ok(message),
+        }
+    }
+
+    /// Blocking write until able to successfully send an entire (authenticated) message
+    pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        match &mut self.auth {
+            Some(auth) => {
+                let sealed = auth.seal(data);
+                self.write_message(&sealed)
+            },
+            None => self.write_message(data),
+        }
+    }
+
+    fn read_message(&mut self) -> Result<Vec<u8>, Error> {
         let result = self.read_buffer();
         if !result.is_empty() {
             return Ok(result);
@@ -455,8 +571,7 @@ impl NetworkStream {
         Ok(self.stream.shutdown(Shutdown::Both)?)
     }
 
-    /// Blocking write until able to successfully send an entire message
-    pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+    fn write_message(&mut self, data: &[u8]) -> Result<(), Error> {
         let u32_max = u32::max_value() as usize;
         if u32_max <= data.len() {
             return Err(Error::DataTooLarge(data.len()));
@@ -539,6 +654,88 @@ mod test {
         assert_eq!(data, result);
     }
 
+    fn auth_config(key: &[u8; 32], version: &str) -> StreamAuthConfig {
+        StreamAuthConfig::new(key, version.to_string())
+    }
+
+    #[test]
+    fn test_authenticated_ping() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let mut server = NetworkServer::new_authenticated(
+            "test".to_string(),
+            server_addr,
+            TIMEOUT,
+            auth_config(&[0; 32], "1.0"),
+        );
+        let mut client = NetworkClient::new_authenticated(
+            "test".to_string(),
+            server_addr,
+            TIMEOUT,
+            auth_config(&[0; 32], "1.0"),
+        );
+
+        // The handshake requires both ends to be running
+        let server_thread = thread::spawn(move || {
+            let result = server.read().unwrap();
+            server.write(&result).unwrap();
+            let result = server.read().unwrap();
+            server.write(&result).unwrap();
+        });
+        for data in [vec![0, 1, 2, 3], vec![]] {
+            client.write(&data).unwrap();
+            let result = client.read().unwrap();
+            assert_eq!(data, result);
+        }
+        server_thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_authentication_failures() {
+        for (client_key, client_version) in [([1; 32], "1.0"), ([0; 32], "2.0")] {
+            let server_port = utils::get_available_port();
+            let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+            let mut server = NetworkServer::new_authenticated(
+                "test".to_string(),
+                server_addr,
+                TIMEOUT,
+                auth_config(&[0; 32], "1.0"),
+            );
+            let mut client = NetworkClient::new_authenticated(
+                "test".to_string(),
+                server_addr,
+                TIMEOUT,
+                auth_config(&client_key, client_version),
+            );
+
+            let server_thread = thread::spawn(move || server.read().unwrap_err());
+            client.write(&[0, 1, 2, 3]).unwrap_err();
+            let error = server_thread.join().unwrap();
+            if client_version == "1.0" {
+                assert!(matches!(error, Error::AuthenticationFailed(_)));
+            } else {
+                assert!(matches!(error, Error::VersionMismatch { .. }));
+            }
+        }
+    }
+
+    #[test]
+    fn test_unauthenticated_client_rejected() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let mut server = NetworkServer::new_authenticated(
+            "test".to_string(),
+            server_addr,
+            TIMEOUT,
+            auth_config(&[0; 32], "1.0"),
+        );
+        let mut client = NetworkClient::new("test".to_string(), server_addr, TIMEOUT);
+
+        // The message is taken for a hello from the client, which fails to deserialize
+        client.write(&[0, 1, 2, 3]).unwrap();
+        server.read().unwrap_err();
+    }
+
     #[test]
     fn test_client_shutdown() {
         let server_port = utils::get_available_port();
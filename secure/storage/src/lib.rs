@@ -12,6 +12,7 @@ mod kv_storage;
 mod namespaced;
 mod on_disk;
 mod policy;
+mod remote_signer;
 mod storage;
 mod vault;
 
@@ -24,6 +25,9 @@ pub use crate::{
     namespaced::Namespaced,
     on_disk::OnDiskStorage,
     policy::{Capability, Identity, Permission, Policy},
+    remote_signer::{
+        AsyncSigner, BlockingSigner, EncryptedKey, FailoverSigner, HttpSigner, LocalSigner,
+    },
     storage::Storage,
     vault::VaultStorage,
 };
@@ -0,0 +1,429 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{from_base64, to_base64, Error};
+use aptos_crypto::{bls12381, hash::CryptoHash, signing_message};
+use aptos_logger::prelude::*;
+use aptos_metrics_core::{
+    register_histogram_vec, register_int_counter_vec, HistogramVec, IntCounterVec,
+};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use rand::{rngs::OsRng, RngCore};
+use ring::{aead, pbkdf2};
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::TryFrom,
+    future::Future,
+    num::NonZeroU32,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::runtime::Runtime;
+use url::Url;
+
+static SIGNER_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_secure_storage_signer_latency",
+        "Time to perform an operation on a signing backend",
+        &["backend", "operation", "result"]
+    )
+    .unwrap()
+});
+
+static SIGNER_FAILOVERS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_secure_storage_signer_failovers",
+        "Number of operations that failed over from the primary to the fallback signing backend",
+        &["operation"]
+    )
+    .unwrap()
+});
+
+/// A signing backend that holds the consensus keys of a validator, so that they never have to be
+/// loaded into the memory of the node (e.g., an HSM or a cloud KMS). The keys are identified by
+/// name, and the messages are the signing messages of the signed structs (see
+/// `aptos_crypto::signing_message`).
+#[async_trait]
+pub trait AsyncSigner: Send + Sync {
+    /// The name of the backend, used in logs and metrics
+    fn backend(&self) -> &'static str;
+
+    /// Returns the BLS12-381 public key of the key at 'name'.
+    async fn consensus_public_key(&self, name: &str) -> Result<bls12381::PublicKey, Error>;
+
+    /// Signs the message with the BLS12-381 private key at 'name'.
+    async fn sign_consensus(
+        &self,
+        name: &str,
+        message: &[u8],
+    ) -> Result<bls12381::Signature, Error>;
+}
+
+#[derive(Deserialize, Serialize)]
+struct PublicKeyResponse {
+    public_key: bls12381::PublicKey,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SignRequest {
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    message: Vec<u8>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SignResponse {
+    signature: bls12381::Signature,
+}
+
+/// A client of a remote signing service. As most HSMs and cloud KMSs don't support BLS12-381
+/// natively, the keys are expected to be held by a service in front of them (e.g., a PKCS#11
+/// bridge running next to the HSM), which implements the following API:
+/// * `GET /v1/keys/<name>` returns `{"public_key": "0x..."}`
+/// * `POST /v1/keys/<name>/sign` with `{"message": "<base64>"}` returns `{"signature": "0x..."}`
+pub struct HttpSigner {
+    client: reqwest::Client,
+    url: Url,
+    token: Option<String>,
+}
+
+impl HttpSigner {
+    pub fn new(url: Url, token: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            token,
+        }
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, Error> {
+        let url = self
+            .url
+            .join(path)
+            .map_err(|error| Error::InternalError(error.to_string()))?;
+        let request = self.client.request(method, url);
+        Ok(match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        })
+    }
+
+    async fn send<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+        name: &str,
+    ) -> Result<T, Error> {
+        let response = request.send().await.map_err(internal_error)?;
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => Err(Error::KeyNotSet(name.into())),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                Err(Error::PermissionDenied)
+            },
+            status if !status.is_success() => Err(Error::InternalError(format!(
+                "Signing service returned {}: {}",
+                status,
+                response.text().await.unwrap_or_default()
+            ))),
+            _ => response
+                .json()
+                .await
+                .map_err(|error| Error::SerializationError(error.to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncSigner for HttpSigner {
+    fn backend(&self) -> &'static str {
+        "http"
+    }
+
+    async fn consensus_public_key(&self, name: &str) -> Result<bls12381::PublicKey, Error> {
+        let request = self.request(reqwest::Method::GET, &format!("v1/keys/{}", name))?;
+        let response: PublicKeyResponse = self.send(request, name).await?;
+        Ok(response.public_key)
+    }
+
+    async fn sign_consensus(
+        &self,
+        name: &str,
+        message: &[u8],
+    ) -> Result<bls12381::Signature, Error> {
+        let request = self
+            .request(reqwest::Method::POST, &format!("v1/keys/{}/sign", name))?
+            .json(&SignRequest {
+                message: message.to_vec(),
+            });
+        let response: SignResponse = self.send(request, name).await?;
+        Ok(response.signature)
+    }
+}
+
+fn internal_error(error: impl std::fmt::Display) -> Error {
+    Error::InternalError(error.to_string())
+}
+
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// A BLS12-381 private key encrypted with a passphrase (AES-256-GCM, with a key derived by
+/// PBKDF2-HMAC-SHA256), so it can be kept on disk as a fallback for a remote signing backend.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct EncryptedKey {
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    salt: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    nonce: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedKey {
+    pub fn encrypt(private_key: &bls12381::PrivateKey, passphrase: &str) -> Result<Self, Error> {
+        let mut salt = vec![0; 16];
+        let mut nonce = [0; aead::NONCE_LEN];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let mut ciphertext = private_key.to_bytes().to_vec();
+        Self::key(&salt, passphrase)?
+            .seal_in_place_append_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::empty(),
+                &mut ciphertext,
+            )
+            .map_err(|_| Error::InternalError("Failed to encrypt the key".into()))?;
+        Ok(Self {
+            salt,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> Result<bls12381::PrivateKey, Error> {
+        let nonce = aead::Nonce::try_assume_unique_for_key(&self.nonce)
+            .map_err(|_| Error::SerializationError("Invalid nonce".into()))?;
+        let mut plaintext = self.ciphertext.clone();
+        let bytes = Self::key(&self.salt, passphrase)?
+            .open_in_place(nonce, aead::Aad::empty(), &mut plaintext)
+            // Either the passphrase is wrong, or the key was tampered with
+            .map_err(|_| Error::PermissionDenied)?;
+        bls12381::PrivateKey::try_from(&bytes[..])
+            .map_err(|error| Error::SerializationError(error.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        Ok(std::fs::write(path, serde_json::to_vec_pretty(self)?)?)
+    }
+
+    fn key(salt: &[u8], passphrase: &str) -> Result<aead::LessSafeKey, Error> {
+        let mut key = [0; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(PBKDF2_ITERATIONS).unwrap(),
+            salt,
+            passphrase.as_bytes(),
+            &mut key,
+        );
+        let key = aead::UnboundKey::new(&aead::AES_256_GCM, &key)
+            .map_err(|_| Error::InternalError("Failed to derive the key".into()))?;
+        Ok(aead::LessSafeKey::new(key))
+    }
+}
+
+/// A signing backend holding a single key in memory, e.g., decrypted from an [`EncryptedKey`].
+/// The key is returned for any name.
+pub struct LocalSigner {
+    private_key: bls12381::PrivateKey,
+}
+
+impl LocalSigner {
+    pub fn new(private_key: bls12381::PrivateKey) -> Self {
+        Self { private_key }
+    }
+
+    pub fn from_encrypted_key(path: &Path, passphrase: &str) -> Result<Self, Error> {
+        Ok(Self::new(EncryptedKey::load(path)?.decrypt(passphrase)?))
+    }
+}
+
+#[async_trait]
+impl AsyncSigner for LocalSigner {
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+
+    async fn consensus_public_key(&self, _name: &str) -> Result<bls12381::PublicKey, Error> {
+        Ok(bls12381::PublicKey::from(&self.private_key))
+    }
+
+    async fn sign_consensus(
+        &self,
+        _name: &str,
+        message: &[u8],
+    ) -> Result<bls12381::Signature, Error> {
+        Ok(self.private_key.sign_signing_message(message))
+    }
+}
+
+/// A signing backend that uses a primary backend (e.g., an [`HttpSigner`]), and fails over to a
+/// fallback one (e.g., a [`LocalSigner`] with an [`EncryptedKey`]) when the primary one fails or
+/// doesn't respond in time. The latency of every operation is recorded per backend.
+pub struct FailoverSigner {
+    primary: Box<dyn AsyncSigner>,
+    fallback: Option<Box<dyn AsyncSigner>>,
+    timeout: Duration,
+}
+
+impl FailoverSigner {
+    pub fn new(
+        primary: Box<dyn AsyncSigner>,
+        fallback: Option<Box<dyn AsyncSigner>>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            primary,
+            fallback,
+            timeout,
+        }
+    }
+
+    async fn run<'a, T, F, Fut>(&'a self, operation: &'static str, f: F) -> Result<T, Error>
+    where
+        F: Fn(&'a dyn AsyncSigner) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let primary = self.primary.as_ref();
+        let result =
+            match tokio::time::timeout(self.timeout, timed(primary, operation, f(primary))).await {
+                Ok(result) => result,
+                Err(_) => Err(Error::InternalError(format!(
+                    "The {} signing backend timed out",
+                    primary.backend()
+                ))),
+            };
+        match (result, &self.fallback) {
+            (Err(error), Some(fallback)) => {
+                warn!(
+                    "The {} signing backend failed to {}, failing over to the {} one: {}",
+                    primary.backend(),
+                    operation,
+                    fallback.backend(),
+                    error
+                );
+                SIGNER_FAILOVERS.with_label_values(&[operation]).inc();
+                timed(fallback.as_ref(), operation, f(fallback.as_ref())).await
+            },
+            (result, _) => result,
+        }
+    }
+}
+
+async fn timed<T>(
+    signer: &dyn AsyncSigner,
+    operation: &'static str,
+    future: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    let start = Instant::now();
+    let result = future.await;
+    SIGNER_LATENCY
+        .with_label_values(&[
+            signer.backend(),
+            operation,
+            if result.is_ok() { "success" } else { "error" },
+        ])
+        .observe(start.elapsed().as_secs_f64());
+    result
+}
+
+#[async_trait]
+impl AsyncSigner for FailoverSigner {
+    fn backend(&self) -> &'static str {
+        "failover"
+    }
+
+    async fn consensus_public_key(&self, name: &str) -> Result<bls12381::PublicKey, Error> {
+        self.run("consensus_public_key", |signer| {
+            signer.consensus_public_key(name)
+        })
+        .await
+    }
+
+    async fn sign_consensus(
+        &self,
+        name: &str,
+        message: &[u8],
+    ) -> Result<bls12381::Signature, Error> {
+        self.run("sign_consensus", |signer| {
+            signer.sign_consensus(name, message)
+        })
+        .await
+    }
+}
+
+/// A blocking interface to an [`AsyncSigner`], for synchronous callers (e.g., safety rules).
+/// The operations run on a dedicated runtime, so this can be called from any thread, including
+/// the threads of another runtime.
+pub struct BlockingSigner {
+    signer: Arc<dyn AsyncSigner>,
+    // Only taken on drop
+    runtime: Option<Runtime>,
+}
+
+impl BlockingSigner {
+    pub fn new(signer: Arc<dyn AsyncSigner>) -> Result<Self, Error> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("remote-signer")
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            signer,
+            runtime: Some(runtime),
+        })
+    }
+
+    pub fn consensus_public_key(&self, name: &str) -> Result<bls12381::PublicKey, Error> {
+        let (signer, name) = (self.signer.clone(), name.to_string());
+        self.block_on(async move { signer.consensus_public_key(&name).await })
+    }
+
+    pub fn sign_consensus<T: CryptoHash + Serialize>(
+        &self,
+        name: &str,
+        message: &T,
+    ) -> Result<bls12381::Signature, Error> {
+        let message = signing_message(message)
+            .map_err(|error| Error::SerializationError(error.to_string()))?;
+        let (signer, name) = (self.signer.clone(), name.to_string());
+        self.block_on(async move { signer.sign_consensus(&name, &message).await })
+    }
+
+    fn block_on<T: Send + 'static>(
+        &self,
+        future: impl Future<Output = Result<T, Error>> + Send + 'static,
+    ) -> Result<T, Error> {
+        let runtime = self
+            .runtime
+            .as_ref()
+            .expect("The runtime is only taken on drop");
+        futures::executor::block_on(runtime.spawn(future)).map_err(internal_error)?
+    }
+}
+
+impl Drop for BlockingSigner {
+    fn drop(&mut self) {
+        // Unlike a regular drop, this doesn't panic if it's called from another runtime
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
@@ -4,5 +4,6 @@
 
 mod in_memory;
 mod on_disk;
+mod remote_signer;
 mod suite;
 mod vault;
@@ -0,0 +1,103 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{AsyncSigner, BlockingSigner, EncryptedKey, Error, FailoverSigner, LocalSigner};
+use aptos_crypto::{bls12381, test_utils::TestAptosCrypto, Signature, Uniform};
+use async_trait::async_trait;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{sync::Arc, time::Duration};
+
+/// A signing backend that is down
+struct UnavailableSigner;
+
+#[async_trait]
+impl AsyncSigner for UnavailableSigner {
+    fn backend(&self) -> &'static str {
+        "unavailable"
+    }
+
+    async fn consensus_public_key(&self, _name: &str) -> Result<bls12381::PublicKey, Error> {
+        Err(Error::InternalError("Unavailable".into()))
+    }
+
+    async fn sign_consensus(
+        &self,
+        _name: &str,
+        _message: &[u8],
+    ) -> Result<bls12381::Signature, Error> {
+        Err(Error::InternalError("Unavailable".into()))
+    }
+}
+
+fn consensus_key(seed: u8) -> bls12381::PrivateKey {
+    bls12381::PrivateKey::generate(&mut StdRng::from_seed([seed; 32]))
+}
+
+#[test]
+fn encrypted_key() {
+    let private_key = consensus_key(0);
+    let encrypted_key = EncryptedKey::encrypt(&private_key, "passphrase").unwrap();
+    assert_eq!(
+        encrypted_key.decrypt("passphrase").unwrap().to_bytes(),
+        private_key.to_bytes()
+    );
+    assert_eq!(
+        encrypted_key.decrypt("wrong passphrase").unwrap_err(),
+        Error::PermissionDenied
+    );
+}
+
+#[test]
+fn blocking_signer() {
+    let private_key = consensus_key(0);
+    let public_key = bls12381::PublicKey::from(&private_key);
+    let signer = BlockingSigner::new(Arc::new(LocalSigner::new(private_key))).unwrap();
+
+    let message = TestAptosCrypto("Hello, World".to_string());
+    assert_eq!(
+        signer.consensus_public_key("consensus").unwrap(),
+        public_key
+    );
+    let signature = signer.sign_consensus("consensus", &message).unwrap();
+    signature.verify(&message, &public_key).unwrap();
+}
+
+#[test]
+fn failover_signer() {
+    let private_key = consensus_key(0);
+    let public_key = bls12381::PublicKey::from(&private_key);
+    let message = TestAptosCrypto("Hello, World".to_string());
+
+    // Without a fallback, the errors of the primary backend are returned
+    let signer = FailoverSigner::new(Box::new(UnavailableSigner), None, Duration::from_secs(1));
+    let signer = BlockingSigner::new(Arc::new(signer)).unwrap();
+    signer.consensus_public_key("consensus").unwrap_err();
+    signer.sign_consensus("consensus", &message).unwrap_err();
+
+    // Otherwise, the fallback backend is used
+    let signer = FailoverSigner::new(
+        Box::new(UnavailableSigner),
+        Some(Box::new(LocalSigner::new(private_key))),
+        Duration::from_secs(1),
+    );
+    let signer = BlockingSigner::new(Arc::new(signer)).unwrap();
+    assert_eq!(
+        signer.consensus_public_key("consensus").unwrap(),
+        public_key
+    );
+    let signature = signer.sign_consensus("consensus", &message).unwrap();
+    signature.verify(&message, &public_key).unwrap();
+
+    // The primary backend is used when it's available
+    let other_private_key = consensus_key(1);
+    let signer = FailoverSigner::new(
+        Box::new(LocalSigner::new(consensus_key(0))),
+        Some(Box::new(LocalSigner::new(other_private_key))),
+        Duration::from_secs(1),
+    );
+    let signer = BlockingSigner::new(Arc::new(signer)).unwrap();
+    assert_eq!(
+        signer.consensus_public_key("consensus").unwrap(),
+        public_key
+    );
+}
@@ -13,8 +13,11 @@ use crate::{
     metrics::{
         increment_request_counter, set_gauge, start_request_timer, PRIORITIZED_PEER, REGULAR_PEER,
     },
+    multi_peer,
+    multi_peer::MultiPeerResponseCallback,
     peer_states::{ErrorType, PeerStates},
     poller::DataSummaryPoller,
+    reliability,
 };
 use aptos_config::{
     config::{AptosDataClientConfig, BaseConfig},
@@ -23,7 +26,11 @@ use aptos_config::{
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
 use aptos_infallible::RwLock;
 use aptos_logger::{debug, info, sample, sample::SampleRate, trace, warn};
-use aptos_network::{application::interface::NetworkClient, protocols::network::RpcError};
+use aptos_network::{
+    application::{interface::NetworkClient, reputation::PeerMisbehavior},
+    protocols::{network::RpcError, wire::handshake::v1::ProtocolId},
+};
+use aptos_retrier::hedge;
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_types::{
@@ -33,7 +40,10 @@ use aptos_storage_service_types::{
         StateValuesWithProofRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof},
+    responses::{
+        DataWindowProof, StorageServerSummary, StorageServiceResponse,
+        TransactionOrOutputListWithProof,
+    },
     Epoch, StorageServiceMessage,
 };
 use aptos_time_service::TimeService;
@@ -44,6 +54,7 @@ use aptos_types::{
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use async_trait::async_trait;
+use futures::{future, FutureExt};
 use rand::prelude::SliceRandom;
 use std::{fmt, sync::Arc, time::Duration};
 use tokio::runtime::Handle;
@@ -144,6 +155,57 @@ impl AptosDataClient {
         self.peer_states.write().update_summary(peer, summary)
     }
 
+    /// Persists the reliability of each peer (if persistence is enabled)
+    pub fn persist_peer_reliabilities(&self) {
+        let (path, peer_reliabilities) = {
+            let peer_states = self.peer_states.read();
+            match peer_states.peer_reliability_path() {
+                Some(path) => (path, peer_states.get_peer_reliabilities()),
+                None => return,
+            }
+        };
+        if let Err(error) = reliability::save_peer_reliabilities(&path, &peer_reliabilities) {
+            warn!(
+                (LogSchema::new(LogEntry::PeerStates)
+                    .event(LogEvent::PeerReliabilityPersistence)
+                    .message("Unable to persist the peer reliabilities!")
+                    .error(&error))
+            );
+        }
+    }
+
+    /// Returns true iff the data window proof should be requested from the peer
+    pub fn should_request_data_window_proof(&self, peer: &PeerNetworkId) -> bool {
+        self.peer_states
+            .read()
+            .should_request_data_window_proof(peer)
+    }
+
+    /// Marks the data window proof as requested from the peer
+    pub fn mark_data_window_proof_requested(&self, peer: PeerNetworkId) {
+        self.peer_states
+            .write()
+            .mark_data_window_proof_requested(peer)
+    }
+
+    /// Resets the data window proof request for the peer
+    pub fn reset_data_window_proof_requested(&self, peer: PeerNetworkId) {
+        self.peer_states
+            .write()
+            .reset_data_window_proof_requested(peer)
+    }
+
+    /// Update a peer's (verified) data window proof
+    pub fn update_data_window_proof(
+        &self,
+        peer: PeerNetworkId,
+        data_window_proof: DataWindowProof,
+    ) {
+        self.peer_states
+            .write()
+            .update_data_window_proof(peer, data_window_proof)
+    }
+
     /// Recompute and update the global data summary cache
     pub fn update_global_summary_cache(&self) -> crate::error::Result<(), Error> {
         // Before calculating the summary, we should garbage collect
@@ -176,27 +238,37 @@ impl AptosDataClient {
         &self,
         request: &StorageServiceRequest,
     ) -> crate::error::Result<PeerNetworkId, Error> {
-        // All requests should be sent to prioritized peers (if possible).
-        // If none can handle the request, fall back to the regular peers.
-        let (priority_peers, regular_peers) = self.get_priority_and_regular_peers()?;
-        let priority_serviceable = self.identify_serviceable(priority_peers, request);
-        let serviceable_peers = if !priority_serviceable.is_empty() {
-            priority_serviceable
-        } else {
-            self.identify_serviceable(regular_peers, request)
-        };
-
-        // Randomly select a peer to handle the request
+        // Randomly select a peer to handle the request (weighted by the reliability of each peer)
+        let serviceable_peers = self.get_serviceable_peers(request)?;
+        let peer_states = self.peer_states.read();
         serviceable_peers
-            .choose(&mut rand::thread_rng())
+            .choose_weighted(&mut rand::thread_rng(), |peer| {
+                peer_states.get_reliability_weight(peer)
+            })
             .copied()
-            .ok_or_else(|| {
+            .map_err(|_| {
                 Error::DataIsUnavailable(
                     format!("No connected peers are advertising that they can serve this data! Request: {:?}",request),
                 )
             })
     }
 
+    /// Returns the connected peers that can service the given request.
+    /// All requests should be sent to prioritized peers (if possible).
+    /// If none can handle the request, fall back to the regular peers.
+    fn get_serviceable_peers(
+        &self,
+        request: &StorageServiceRequest,
+    ) -> crate::error::Result<Vec<PeerNetworkId>, Error> {
+        let (priority_peers, regular_peers) = self.get_priority_and_regular_peers()?;
+        let priority_serviceable = self.identify_serviceable(priority_peers, request);
+        if !priority_serviceable.is_empty() {
+            Ok(priority_serviceable)
+        } else {
+            Ok(self.identify_serviceable(regular_peers, request))
+        }
+    }
+
     /// Identifies the peers in the given set of prospective peers
     /// that can service the specified request.
     fn identify_serviceable(
@@ -343,6 +415,14 @@ impl AptosDataClient {
         T: TryFrom<StorageServiceResponse, Error = E>,
         E: Into<Error>,
     {
+        // If the peer supports transport-level compression, the response
+        // will already be compressed by the network layer. In this case,
+        // avoid compressing the data twice.
+        let mut request = request;
+        if request.use_compression && self.peer_supports_network_compression(peer) {
+            request.use_compression = false;
+        }
+
         let response = self
             .send_request_to_peer(peer, request.clone(), request_timeout_ms)
             .await?;
@@ -375,6 +455,16 @@ impl AptosDataClient {
         }
     }
 
+    /// Returns true iff the given peer supports the zstd compressed storage
+    /// service protocol (i.e., responses are compressed by the network layer).
+    fn peer_supports_network_compression(&self, peer: PeerNetworkId) -> bool {
+        self.storage_service_client
+            .get_peers_and_metadata()
+            .get_metadata_for_peer(peer)
+            .map(|peer_metadata| peer_metadata.supports_protocol(ProtocolId::StorageServiceRpcZstd))
+            .unwrap_or(false)
+    }
+
     /// Sends a request to a specific peer
     async fn send_request_to_peer(
         &self,
@@ -471,7 +561,12 @@ impl AptosDataClient {
                     peer,
                 );
 
-                self.notify_bad_response(id, peer, &request, ErrorType::NotUseful);
+                let error_type = if matches!(client_error, Error::TimeoutWaitingForResponse(_)) {
+                    ErrorType::Timeout
+                } else {
+                    ErrorType::NotUseful
+                };
+                self.notify_bad_response(id, peer, &request, error_type);
                 Err(client_error)
             },
         }
@@ -485,6 +580,13 @@ impl AptosDataClient {
         _request: &StorageServiceRequest,
         error_type: ErrorType,
     ) {
+        // Report malicious responses (e.g., invalid proofs) to the network
+        if matches!(error_type, ErrorType::Malicious) {
+            self.storage_service_client
+                .get_peers_and_metadata()
+                .report_peer_misbehavior(peer, PeerMisbehavior::InvalidProof);
+        }
+
         self.peer_states
             .write()
             .update_score_error(peer, error_type);
@@ -506,6 +608,143 @@ impl AptosDataClient {
             .await
     }
 
+    /// Fetches the state value chunk by requesting the given sub-chunks from
+    /// multiple peers in parallel (with redundancy), and reassembling the
+    /// responses. Returns None if there aren't enough serviceable peers.
+    async fn get_state_values_from_multiple_peers(
+        &self,
+        version: u64,
+        sub_chunk_ranges: Vec<(u64, u64)>,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Option<Response<StateValueChunkWithProof>>> {
+        // Identify the peers that can service the entire chunk
+        let (start_index, end_index) = match (sub_chunk_ranges.first(), sub_chunk_ranges.last()) {
+            (Some((start_index, _)), Some((_, end_index))) => (*start_index, *end_index),
+            _ => return Ok(None), // There are no sub-chunks to fetch
+        };
+        let request = self.create_state_values_request(version, start_index, end_index);
+        let mut peers = self.get_serviceable_peers(&request)?;
+        if peers.len() < 2 {
+            return Ok(None);
+        }
+        peers.shuffle(&mut rand::thread_rng());
+
+        // Fetch each sub-chunk from a peer, and hedge the request with the
+        // next peers if it's slow or fails (using the first valid response)
+        let hedge_delay =
+            Duration::from_millis(self.data_client_config.state_sub_chunk_hedge_delay_ms);
+        let sub_chunk_end_indices: Vec<u64> = sub_chunk_ranges
+            .iter()
+            .map(|(_, sub_chunk_end_index)| *sub_chunk_end_index)
+            .collect();
+        let sub_chunk_requests = sub_chunk_ranges.into_iter().enumerate().map(
+            |(sub_chunk_index, (sub_chunk_start_index, sub_chunk_end_index))| {
+                let sub_chunk_peers = multi_peer::get_peers_for_sub_chunk(
+                    &peers,
+                    sub_chunk_index,
+                    self.data_client_config.state_sub_chunk_redundancy,
+                );
+                let num_sub_chunk_peers = sub_chunk_peers.len();
+                let mut sub_chunk_peers = sub_chunk_peers.into_iter();
+                hedge(
+                    hedge_delay,
+                    num_sub_chunk_peers,
+                    move || match sub_chunk_peers.next() {
+                        Some(peer) => self
+                            .get_state_value_sub_chunk(
+                                peer,
+                                version,
+                                sub_chunk_start_index,
+                                sub_chunk_end_index,
+                                request_timeout_ms,
+                            )
+                            .boxed(),
+                        None => future::err(Error::UnexpectedErrorEncountered(
+                            "No peers left for the state value sub-chunk!".into(),
+                        ))
+                        .boxed(),
+                    },
+                )
+            },
+        );
+        let sub_chunk_responses = future::try_join_all(sub_chunk_requests).await?;
+
+        // Reassemble the sub-chunks into a single response. If a sub-chunk was
+        // truncated by the server, the chunk ends there (and the following
+        // sub-chunks are dropped).
+        let mut response_callbacks = vec![];
+        let mut sub_chunks = vec![];
+        for (sub_chunk_response, sub_chunk_end_index) in
+            sub_chunk_responses.into_iter().zip(sub_chunk_end_indices)
+        {
+            let (context, sub_chunk) = sub_chunk_response.into_parts();
+            let truncated = sub_chunk.last_index < sub_chunk_end_index;
+            response_callbacks.push(context.response_callback);
+            sub_chunks.push(sub_chunk);
+            if truncated {
+                break;
+            }
+        }
+        let response_callback = MultiPeerResponseCallback::new(response_callbacks);
+        match multi_peer::reassemble_state_value_chunk(sub_chunks) {
+            Ok(state_value_chunk) => {
+                let context = ResponseContext {
+                    id: self.next_response_id(),
+                    response_callback: Box::new(response_callback),
+                };
+                Ok(Some(Response::new(context, state_value_chunk)))
+            },
+            Err(error) => {
+                response_callback.notify_bad_response(ResponseError::InvalidData);
+                Err(error)
+            },
+        }
+    }
+
+    /// Fetches a state value sub-chunk from the given peer and verifies
+    /// that the sub-chunk starts at the requested index (it may be truncated).
+    async fn get_state_value_sub_chunk(
+        &self,
+        peer: PeerNetworkId,
+        version: u64,
+        start_index: u64,
+        end_index: u64,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<StateValueChunkWithProof>> {
+        let request = self.create_state_values_request(version, start_index, end_index);
+        let _timer = start_request_timer(&metrics::REQUEST_LATENCIES, &request.get_label(), peer);
+        let response: Response<StateValueChunkWithProof> = self
+            .send_request_to_peer_and_decode(peer, request, request_timeout_ms)
+            .await?;
+
+        // Verify the sub-chunk and notify the peer if it's invalid
+        if let Err(error) =
+            multi_peer::verify_state_value_sub_chunk(&response.payload, start_index, end_index)
+        {
+            response
+                .context
+                .response_callback
+                .notify_bad_response(ResponseError::InvalidData);
+            return Err(error);
+        }
+        Ok(response)
+    }
+
+    /// Creates a storage service request for the given state values
+    fn create_state_values_request(
+        &self,
+        version: u64,
+        start_index: u64,
+        end_index: u64,
+    ) -> StorageServiceRequest {
+        let data_request = DataRequest::GetStateValuesWithProof(StateValuesWithProofRequest {
+            version,
+            start_index,
+            end_index,
+        });
+        StorageServiceRequest::new(data_request, self.use_compression())
+    }
+
     /// Returns a copy of the peer states for testing
     #[cfg(test)]
     pub(crate) fn get_peer_states(&self) -> PeerStates {
@@ -605,12 +844,33 @@ impl AptosDataClientInterface for AptosDataClient {
         end_index: u64,
         request_timeout_ms: u64,
     ) -> crate::error::Result<Response<StateValueChunkWithProof>> {
-        let data_request = DataRequest::GetStateValuesWithProof(StateValuesWithProofRequest {
-            version,
-            start_index,
-            end_index,
-        });
-        self.create_and_send_storage_request(request_timeout_ms, data_request)
+        // Large chunks are fetched from multiple peers in parallel (if possible)
+        let sub_chunk_ranges =
+            multi_peer::split_state_value_range(&self.data_client_config, start_index, end_index);
+        if sub_chunk_ranges.len() > 1 {
+            match self
+                .get_state_values_from_multiple_peers(version, sub_chunk_ranges, request_timeout_ms)
+                .await
+            {
+                Ok(Some(response)) => return Ok(response),
+                Ok(None) => {}, // There aren't enough peers to split the chunk
+                Err(error) => {
+                    warn!(
+                        (LogSchema::new(LogEntry::StorageServiceRequest)
+                            .event(LogEvent::ResponseError)
+                            .message(
+                                "Failed to fetch the state values from multiple peers! \
+                                Falling back to a single peer."
+                            )
+                            .error(&error))
+                    );
+                },
+            }
+        }
+
+        // Otherwise, fetch the entire chunk from a single peer
+        let request = self.create_state_values_request(version, start_index, end_index);
+        self.send_request_and_decode(request, request_timeout_ms)
             .await
     }
 
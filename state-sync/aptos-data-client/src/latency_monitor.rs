@@ -8,7 +8,10 @@ use crate::{
 };
 use aptos_config::config::AptosDataClientConfig;
 use aptos_logger::{info, sample, sample::SampleRate, warn};
-use aptos_storage_interface::DbReader;
+use aptos_storage_interface::{
+    async_db_reader::{AsyncDbReader, DEFAULT_ASYNC_READ_TIMEOUT},
+    DbReader,
+};
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use futures::StreamExt;
 use std::{
@@ -21,6 +24,7 @@ use std::{
 const LATENCY_MONITOR_LOG_FREQ_SECS: u64 = 5;
 const MAX_NUM_TRACKED_VERSION_ENTRIES: usize = 10_000;
 const MAX_VERSION_LAG_TO_TOLERATE: u64 = 10_000;
+const NUM_STORAGE_READER_THREADS: usize = 1;
 
 /// A simple monitor that tracks the latencies taken to see
 /// and sync new blockchain data (i.e., transactions).
@@ -29,7 +33,7 @@ pub struct LatencyMonitor {
     caught_up_to_latest: bool, // Whether the node has ever caught up to the latest blockchain version
     data_client: Arc<dyn AptosDataClientInterface + Send + Sync>, // The data client through which to see advertised data
     monitor_loop_interval: Duration, // The interval between latency monitor loop executions
-    storage: Arc<dyn DbReader>,      // The reader interface to storage
+    storage: AsyncDbReader,          // The reader interface to storage
     time_service: TimeService,       // The service to monitor elapsed time
 }
 
@@ -48,7 +52,11 @@ impl LatencyMonitor {
             caught_up_to_latest: false,
             data_client,
             monitor_loop_interval,
-            storage,
+            storage: AsyncDbReader::new(
+                storage,
+                NUM_STORAGE_READER_THREADS,
+                DEFAULT_ASYNC_READ_TIMEOUT,
+            ),
             time_service,
         }
     }
@@ -68,7 +76,7 @@ impl LatencyMonitor {
             loop_ticker.next().await;
 
             // Get the highest synced version from storage
-            let highest_synced_version = match self.storage.get_latest_version() {
+            let highest_synced_version = match self.storage.get_latest_version().await {
                 Ok(version) => version,
                 Err(error) => {
                     sample!(
@@ -84,7 +92,7 @@ impl LatencyMonitor {
             };
 
             // Update the latency metrics for all versions that we've now synced
-            self.update_latency_metrics(highest_synced_version);
+            self.update_latency_metrics(highest_synced_version).await;
 
             // Get the highest advertised version from the global data summary
             let advertised_data = &self.data_client.get_global_data_summary().advertised_data;
@@ -112,7 +120,7 @@ impl LatencyMonitor {
     }
 
     /// Updates the latency metrics for all versions that have now been synced
-    fn update_latency_metrics(&mut self, highest_synced_version: u64) {
+    async fn update_latency_metrics(&mut self, highest_synced_version: u64) {
         // Split the advertised versions into synced and unsynced versions
         let unsynced_advertised_versions = self
             .advertised_version_timestamps
@@ -131,7 +139,9 @@ impl LatencyMonitor {
             );
 
             // Update the proposal latencies
-            if let Ok(block_timestamp_usecs) = self.storage.get_block_timestamp(*synced_version) {
+            if let Ok(block_timestamp_usecs) =
+                self.storage.get_block_timestamp(*synced_version).await
+            {
                 // Update the propose to seen latencies
                 if let Some(duration_from_propose_to_seen) =
                     calculate_duration_from_proposal(block_timestamp_usecs, *seen_timestamp_usecs)
@@ -370,7 +380,9 @@ mod tests {
         );
 
         // Update the latency metrics and verify that the tracked version timestamps are empty
-        latency_monitor.update_latency_metrics(num_advertised_versions);
+        latency_monitor
+            .update_latency_metrics(num_advertised_versions)
+            .await;
         verify_advertised_version_timestamps_length(&mut latency_monitor, 0);
 
         // Update the advertised versions many more times than the max (again)
@@ -419,7 +431,9 @@ mod tests {
 
         // Update the latency metrics (we've only synced the first half of the advertised versions)
         let highest_synced_version = 50;
-        latency_monitor.update_latency_metrics(highest_synced_version);
+        latency_monitor
+            .update_latency_metrics(highest_synced_version)
+            .await;
 
         // Verify that we're tracking the correct number of advertised version timestamps
         let expected_num_tracked_versions = 50;
@@ -430,7 +444,9 @@ mod tests {
 
         // Update the latency metrics (we've now almost synced all advertised versions)
         let highest_synced_version = 98;
-        latency_monitor.update_latency_metrics(highest_synced_version);
+        latency_monitor
+            .update_latency_metrics(highest_synced_version)
+            .await;
 
         // Verify that we're tracking the correct number of advertised version timestamps
         let expected_num_tracked_versions = 2;
@@ -441,7 +457,9 @@ mod tests {
 
         // Update the latency metrics (we've now synced all advertised versions)
         let highest_synced_version = 100;
-        latency_monitor.update_latency_metrics(highest_synced_version);
+        latency_monitor
+            .update_latency_metrics(highest_synced_version)
+            .await;
 
         // Verify that we're tracking the correct number of advertised version timestamps
         verify_advertised_version_timestamps_length(&mut latency_monitor, 0);
@@ -10,8 +10,10 @@ pub mod interface;
 mod latency_monitor;
 mod logging;
 mod metrics;
+mod multi_peer;
 mod peer_states;
 mod poller;
+mod reliability;
 
 #[cfg(test)]
 mod tests;
@@ -58,6 +58,7 @@ pub enum LogEvent {
     PeerIgnored,
     PeerNoLongerIgnored,
     PeerPollingError,
+    PeerReliabilityPersistence,
     PeerSelectionError,
     PriorityAndRegularPeers,
     ResponseError,
@@ -0,0 +1,158 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    interface::{ResponseCallback, ResponseError},
+};
+use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
+use aptos_types::state_store::state_value::StateValueChunkWithProof;
+use std::cmp::{max, min};
+
+/// Splits the given state value index range into the sub-chunks that should
+/// be fetched from multiple peers. If the range is too small to be split, a
+/// single sub-chunk (i.e., the entire range) is returned.
+pub fn split_state_value_range(
+    data_client_config: &AptosDataClientConfig,
+    start_index: u64,
+    end_index: u64,
+) -> Vec<(u64, u64)> {
+    // Calculate the number of sub-chunks
+    let num_state_values = match end_index
+        .checked_sub(start_index)
+        .and_then(|range| range.checked_add(1))
+    {
+        Some(num_state_values) => num_state_values,
+        None => return vec![(start_index, end_index)], // The request is invalid or too large
+    };
+    let num_sub_chunks = min(
+        data_client_config.max_num_state_sub_chunks,
+        num_state_values / max(data_client_config.min_state_sub_chunk_size, 1),
+    );
+    if num_sub_chunks <= 1 {
+        return vec![(start_index, end_index)];
+    }
+
+    // Create the sub-chunk ranges (the last sub-chunk may be smaller)
+    let sub_chunk_size = (num_state_values + num_sub_chunks - 1) / num_sub_chunks;
+    let mut sub_chunk_ranges = vec![];
+    let mut sub_chunk_start_index = start_index;
+    loop {
+        let sub_chunk_end_index = min(
+            sub_chunk_start_index.saturating_add(sub_chunk_size - 1),
+            end_index,
+        );
+        sub_chunk_ranges.push((sub_chunk_start_index, sub_chunk_end_index));
+        if sub_chunk_end_index == end_index {
+            return sub_chunk_ranges;
+        }
+        sub_chunk_start_index = sub_chunk_end_index + 1;
+    }
+}
+
+/// Returns the peers that the specified sub-chunk should be requested from.
+/// Sub-chunks are assigned to peers in a round robin fashion, so that the
+/// load is spread across all the given peers.
+pub fn get_peers_for_sub_chunk(
+    peers: &[PeerNetworkId],
+    sub_chunk_index: usize,
+    redundancy: u64,
+) -> Vec<PeerNetworkId> {
+    let num_peers = min(max(redundancy, 1) as usize, peers.len());
+    (0..num_peers)
+        .map(|offset| peers[(sub_chunk_index * num_peers + offset) % peers.len()])
+        .collect()
+}
+
+/// Verifies that the given state value sub-chunk contains the requested
+/// state values. Servers truncate responses that would overflow the network
+/// frame, so the sub-chunk may only contain a prefix of the requested values.
+pub fn verify_state_value_sub_chunk(
+    sub_chunk: &StateValueChunkWithProof,
+    start_index: u64,
+    end_index: u64,
+) -> Result<(), Error> {
+    let num_state_values = sub_chunk
+        .last_index
+        .checked_sub(sub_chunk.first_index)
+        .and_then(|range| range.checked_add(1));
+    if sub_chunk.first_index != start_index
+        || sub_chunk.last_index > end_index
+        || num_state_values != Some(sub_chunk.raw_values.len() as u64)
+    {
+        return Err(Error::InvalidResponse(format!(
+            "The state value sub-chunk is invalid! Requested indices: ({}, {}), \
+            received indices: ({}, {}) with {} values",
+            start_index,
+            end_index,
+            sub_chunk.first_index,
+            sub_chunk.last_index,
+            sub_chunk.raw_values.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Reassembles the given (ordered) state value sub-chunks into a single
+/// chunk. The range proof of the last sub-chunk proves the entire chunk
+/// (as it contains the right siblings of the last state key), so the
+/// reassembled chunk is verified in the same way as any other chunk.
+pub fn reassemble_state_value_chunk(
+    sub_chunks: Vec<StateValueChunkWithProof>,
+) -> Result<StateValueChunkWithProof, Error> {
+    let mut sub_chunks = sub_chunks.into_iter();
+    let mut state_value_chunk = sub_chunks.next().ok_or_else(|| {
+        Error::UnexpectedErrorEncountered("No state value sub-chunks to reassemble!".into())
+    })?;
+
+    for sub_chunk in sub_chunks {
+        // Verify the sub-chunk follows the existing chunk
+        if sub_chunk.root_hash != state_value_chunk.root_hash {
+            return Err(Error::InvalidResponse(format!(
+                "The state value sub-chunks have different root hashes! Expected: {:?}, found: {:?}",
+                state_value_chunk.root_hash, sub_chunk.root_hash
+            )));
+        }
+        if Some(sub_chunk.first_index) != state_value_chunk.last_index.checked_add(1) {
+            return Err(Error::InvalidResponse(format!(
+                "The state value sub-chunks are not contiguous! Last index: {}, next first index: {}",
+                state_value_chunk.last_index, sub_chunk.first_index
+            )));
+        }
+        if sub_chunk.first_key <= state_value_chunk.last_key {
+            return Err(Error::InvalidResponse(format!(
+                "The state value sub-chunks are not ordered! Last key: {:?}, next first key: {:?}",
+                state_value_chunk.last_key, sub_chunk.first_key
+            )));
+        }
+
+        // Append the sub-chunk
+        state_value_chunk.last_index = sub_chunk.last_index;
+        state_value_chunk.last_key = sub_chunk.last_key;
+        state_value_chunk.raw_values.extend(sub_chunk.raw_values);
+        state_value_chunk.proof = sub_chunk.proof;
+    }
+
+    Ok(state_value_chunk)
+}
+
+/// A response callback for a response that was reassembled from the
+/// responses of multiple peers. Bad responses are reported for all peers.
+#[derive(Debug)]
+pub struct MultiPeerResponseCallback {
+    response_callbacks: Vec<Box<dyn ResponseCallback>>,
+}
+
+impl MultiPeerResponseCallback {
+    pub fn new(response_callbacks: Vec<Box<dyn ResponseCallback>>) -> Self {
+        Self { response_callbacks }
+    }
+}
+
+impl ResponseCallback for MultiPeerResponseCallback {
+    fn notify_bad_response(&self, error: ResponseError) {
+        for response_callback in &self.response_callbacks {
+            response_callback.notify_bad_response(error.clone());
+        }
+    }
+}
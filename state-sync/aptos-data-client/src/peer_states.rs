@@ -5,6 +5,8 @@ use crate::{
     global_summary::{AdvertisedData, GlobalDataSummary, OptimalChunkSizes},
     interface::ResponseError,
     logging::{LogEntry, LogEvent, LogSchema},
+    reliability,
+    reliability::{PeerReliability, ResponseOutcome},
 };
 use aptos_config::{
     config::{AptosDataClientConfig, BaseConfig},
@@ -14,12 +16,14 @@ use aptos_logger::prelude::*;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_network::application::storage::PeersAndMetadata;
 use aptos_storage_service_types::{
-    requests::StorageServiceRequest, responses::StorageServerSummary,
+    requests::StorageServiceRequest,
+    responses::{DataWindowProof, StorageServerSummary},
 };
 use itertools::Itertools;
 use std::{
     cmp::min,
     collections::{HashMap, HashSet},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -43,6 +47,9 @@ pub(crate) enum ErrorType {
     /// A response or error that appears to be actively hindering progress or
     /// attempting to deceive us, e.g., invalid proof.
     Malicious,
+    /// A request that timed out (e.g., the peer is overloaded or unreachable).
+    /// This is scored as not useful, but tracked separately for reliability.
+    Timeout,
 }
 
 impl From<ResponseError> for ErrorType {
@@ -63,6 +70,10 @@ pub struct PeerState {
     storage_summary: Option<StorageServerSummary>,
     /// For now, a simplified port of the original state-sync v1 scoring system.
     score: f64,
+    /// The verified proof of the data window retained by the peer (if it is pruned)
+    data_window_proof: Option<DataWindowProof>,
+    /// Whether or not the data window proof has already been requested from the peer
+    data_window_proof_requested: bool,
 }
 
 impl Default for PeerState {
@@ -70,16 +81,43 @@ impl Default for PeerState {
         Self {
             storage_summary: None,
             score: STARTING_SCORE,
+            data_window_proof: None,
+            data_window_proof_requested: false,
         }
     }
 }
 
 impl PeerState {
-    /// Updates the storage summary for the peer
-    fn update_storage_summary(&mut self, storage_summary: StorageServerSummary) {
+    /// Updates the storage summary for the peer. If the data window of the
+    /// peer is known, the advertised data is trimmed to the window.
+    fn update_storage_summary(
+        &mut self,
+        mut storage_summary: StorageServerSummary,
+        data_window_margin_versions: u64,
+    ) {
+        if let Some(data_window_proof) = &self.data_window_proof {
+            storage_summary
+                .data_summary
+                .trim_to_data_window(data_window_proof, data_window_margin_versions);
+        }
         self.storage_summary = Some(storage_summary);
     }
 
+    /// Updates the data window proof for the peer and trims the advertised
+    /// data of the existing storage summary (if any) to the window.
+    fn update_data_window_proof(
+        &mut self,
+        data_window_proof: DataWindowProof,
+        data_window_margin_versions: u64,
+    ) {
+        if let Some(storage_summary) = &mut self.storage_summary {
+            storage_summary
+                .data_summary
+                .trim_to_data_window(&data_window_proof, data_window_margin_versions);
+        }
+        self.data_window_proof = Some(data_window_proof);
+    }
+
     /// Returns the storage summary iff the peer is not below the ignore threshold
     fn storage_summary_if_not_ignored(&self) -> Option<&StorageServerSummary> {
         if self.score <= IGNORE_PEER_THRESHOLD {
@@ -97,7 +135,7 @@ impl PeerState {
     /// Updates the score of the peer according to an error
     fn update_score_error(&mut self, error: ErrorType) {
         let multiplier = match error {
-            ErrorType::NotUseful => NOT_USEFUL_MULTIPLIER,
+            ErrorType::NotUseful | ErrorType::Timeout => NOT_USEFUL_MULTIPLIER,
             ErrorType::Malicious => MALICIOUS_MULTIPLIER,
         };
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
@@ -114,6 +152,10 @@ pub(crate) struct PeerStates {
     in_flight_priority_polls: HashSet<PeerNetworkId>, // The priority peers with in-flight polls
     in_flight_regular_polls: HashSet<PeerNetworkId>,  // The regular peers with in-flight polls
     peers_and_metadata: Arc<PeersAndMetadata>,
+    // The reliability of each peer. This isn't garbage collected with the peer
+    // states, as the history of a peer should survive disconnects (and restarts).
+    peer_to_reliability: HashMap<PeerNetworkId, PeerReliability>,
+    reliability_decay: f64, // The decay of the reliability history (per response)
 }
 
 impl PeerStates {
@@ -122,13 +164,90 @@ impl PeerStates {
         data_client_config: AptosDataClientConfig,
         peers_and_metadata: Arc<PeersAndMetadata>,
     ) -> Self {
-        Self {
+        let mut peer_states = Self {
             base_config,
             data_client_config,
             peer_to_state: HashMap::new(),
             in_flight_priority_polls: HashSet::new(),
             in_flight_regular_polls: HashSet::new(),
             peers_and_metadata,
+            peer_to_reliability: HashMap::new(),
+            reliability_decay: reliability::decay_for_half_life(
+                data_client_config.peer_reliability_half_life,
+            ),
+        };
+
+        // Load the persisted peer reliabilities (if any)
+        if let Some(path) = peer_states.peer_reliability_path() {
+            match reliability::load_peer_reliabilities(&path) {
+                Ok(peer_to_reliability) => peer_states.peer_to_reliability = peer_to_reliability,
+                Err(error) => warn!(
+                    (LogSchema::new(LogEntry::PeerStates)
+                        .event(LogEvent::PeerReliabilityPersistence)
+                        .message("Unable to load the persisted peer reliabilities!")
+                        .error(&error))
+                ),
+            }
+        }
+
+        peer_states
+    }
+
+    /// Returns the path that peer reliabilities are persisted to (or None,
+    /// if persistence is disabled).
+    pub fn peer_reliability_path(&self) -> Option<PathBuf> {
+        if self.data_client_config.peer_reliability_persist_interval_ms == 0 {
+            return None;
+        }
+        Some(
+            self.base_config
+                .data_dir
+                .join(reliability::PEER_RELIABILITY_FILE_NAME),
+        )
+    }
+
+    /// Returns a copy of the reliability of each peer (e.g., for persistence)
+    pub fn get_peer_reliabilities(&self) -> HashMap<PeerNetworkId, PeerReliability> {
+        self.peer_to_reliability.clone()
+    }
+
+    /// Returns the weight of the given peer, according to its reliability history
+    /// (peers without any history are given the weight of the prior).
+    pub fn get_reliability_weight(&self, peer: &PeerNetworkId) -> f64 {
+        self.peer_to_reliability
+            .get(peer)
+            .copied()
+            .unwrap_or_default()
+            .weight()
+    }
+
+    /// Records the outcome of a request in the reliability history of the peer
+    fn record_response_outcome(&mut self, peer: PeerNetworkId, outcome: ResponseOutcome) {
+        // Make room for the peer (if it's new and the reliabilities are full)
+        if !self.peer_to_reliability.contains_key(&peer)
+            && self.peer_to_reliability.len() >= reliability::MAX_NUM_PEER_RELIABILITIES
+        {
+            self.evict_most_reliable_peer();
+        }
+
+        self.peer_to_reliability
+            .entry(peer)
+            .or_default()
+            .record_outcome(outcome, self.reliability_decay);
+    }
+
+    /// Evicts the reliability of the most reliable peer. Unreliable peers are kept,
+    /// as forgetting them would reset their reliability to the (higher) prior.
+    fn evict_most_reliable_peer(&mut self) {
+        let most_reliable_peer = self
+            .peer_to_reliability
+            .iter()
+            .max_by(|(_, reliability), (_, other_reliability)| {
+                reliability.weight().total_cmp(&other_reliability.weight())
+            })
+            .map(|(peer, _)| *peer);
+        if let Some(peer) = most_reliable_peer {
+            self.peer_to_reliability.remove(&peer);
         }
     }
 
@@ -158,6 +277,8 @@ impl PeerStates {
 
     /// Updates the score of the peer according to a successful operation
     pub fn update_score_success(&mut self, peer: PeerNetworkId) {
+        self.record_response_outcome(peer, ResponseOutcome::Success);
+
         let old_score = self.peer_to_state.entry(peer).or_default().score;
         self.peer_to_state
             .entry(peer)
@@ -176,6 +297,12 @@ impl PeerStates {
 
     /// Updates the score of the peer according to an error
     pub fn update_score_error(&mut self, peer: PeerNetworkId, error: ErrorType) {
+        let outcome = match error {
+            ErrorType::Timeout => ResponseOutcome::Timeout,
+            ErrorType::NotUseful | ErrorType::Malicious => ResponseOutcome::Error,
+        };
+        self.record_response_outcome(peer, outcome);
+
         let old_score = self.peer_to_state.entry(peer).or_default().score;
         self.peer_to_state
             .entry(peer)
@@ -294,7 +421,56 @@ impl PeerStates {
         self.peer_to_state
             .entry(peer)
             .or_default()
-            .update_storage_summary(summary);
+            .update_storage_summary(summary, self.data_client_config.data_window_margin_versions);
+    }
+
+    /// Returns true iff the data window proof should be requested from the
+    /// peer, i.e., the peer appears to be pruned and it hasn't been requested.
+    pub fn should_request_data_window_proof(&self, peer: &PeerNetworkId) -> bool {
+        if !self.data_client_config.enable_data_window_proofs {
+            return false;
+        }
+
+        self.peer_to_state.get(peer).map_or(false, |peer_state| {
+            let is_pruned = peer_state
+                .storage_summary
+                .as_ref()
+                .and_then(|summary| summary.data_summary.transactions)
+                .map_or(false, |transactions| transactions.lowest() > 0);
+            is_pruned && !peer_state.data_window_proof_requested
+        })
+    }
+
+    /// Marks the data window proof as requested from the peer (so that
+    /// the request isn't repeated, e.g., if the peer doesn't support it).
+    pub fn mark_data_window_proof_requested(&mut self, peer: PeerNetworkId) {
+        self.peer_to_state
+            .entry(peer)
+            .or_default()
+            .data_window_proof_requested = true;
+    }
+
+    /// Resets the data window proof request for the peer (e.g., because the
+    /// request failed transiently), so that the proof is requested again.
+    pub fn reset_data_window_proof_requested(&mut self, peer: PeerNetworkId) {
+        if let Some(peer_state) = self.peer_to_state.get_mut(&peer) {
+            peer_state.data_window_proof_requested = false;
+        }
+    }
+
+    /// Updates the (verified) data window proof for the given peer
+    pub fn update_data_window_proof(
+        &mut self,
+        peer: PeerNetworkId,
+        data_window_proof: DataWindowProof,
+    ) {
+        self.peer_to_state
+            .entry(peer)
+            .or_default()
+            .update_data_window_proof(
+                data_window_proof,
+                self.data_client_config.data_window_margin_versions,
+            );
     }
 
     /// Garbage collects the peer states to remove data for disconnected peers
@@ -306,11 +482,15 @@ impl PeerStates {
     /// Calculates a global data summary using all known storage summaries
     pub fn calculate_aggregate_summary(&self) -> GlobalDataSummary {
         // Only include likely-not-malicious peers in the data summary aggregation
-        let summaries: Vec<StorageServerSummary> = self
+        // (each summary is weighted by the reliability of the peer).
+        let summaries: Vec<(StorageServerSummary, f64)> = self
             .peer_to_state
-            .values()
-            .filter_map(PeerState::storage_summary_if_not_ignored)
-            .cloned()
+            .iter()
+            .filter_map(|(peer, peer_state)| {
+                peer_state
+                    .storage_summary_if_not_ignored()
+                    .map(|summary| (summary.clone(), self.get_reliability_weight(peer)))
+            })
             .collect();
 
         // If we have no peers, return an empty global summary
@@ -324,7 +504,7 @@ impl PeerStates {
         let mut max_state_chunk_sizes = vec![];
         let mut max_transaction_chunk_sizes = vec![];
         let mut max_transaction_output_chunk_sizes = vec![];
-        for summary in summaries {
+        for (summary, weight) in summaries {
             // Collect aggregate data advertisements
             if let Some(epoch_ending_ledger_infos) = summary.data_summary.epoch_ending_ledger_infos
             {
@@ -349,12 +529,14 @@ impl PeerStates {
                     .push(transaction_outputs);
             }
 
-            // Collect preferred max chunk sizes
-            max_epoch_chunk_sizes.push(summary.protocol_metadata.max_epoch_chunk_size);
-            max_state_chunk_sizes.push(summary.protocol_metadata.max_state_chunk_size);
-            max_transaction_chunk_sizes.push(summary.protocol_metadata.max_transaction_chunk_size);
+            // Collect preferred max chunk sizes (with the weights of the peers)
+            let protocol_metadata = summary.protocol_metadata;
+            max_epoch_chunk_sizes.push((protocol_metadata.max_epoch_chunk_size, weight));
+            max_state_chunk_sizes.push((protocol_metadata.max_state_chunk_size, weight));
+            max_transaction_chunk_sizes
+                .push((protocol_metadata.max_transaction_chunk_size, weight));
             max_transaction_output_chunk_sizes
-                .push(summary.protocol_metadata.max_transaction_output_chunk_size);
+                .push((protocol_metadata.max_transaction_output_chunk_size, weight));
         }
 
         // Calculate optimal chunk sizes based on the advertised data
@@ -379,14 +561,15 @@ impl PeerStates {
 }
 
 /// To calculate the optimal chunk size, we take the median for each
-/// chunk size parameter. This works well when we have an honest
-/// majority that mostly agrees on the same chunk sizes.
+/// chunk size parameter, weighted by the reliability of each peer. This
+/// works well when we have an honest (and reliable) majority that mostly
+/// agrees on the same chunk sizes.
 pub(crate) fn calculate_optimal_chunk_sizes(
     config: &AptosDataClientConfig,
-    max_epoch_chunk_sizes: Vec<u64>,
-    max_state_chunk_sizes: Vec<u64>,
-    max_transaction_chunk_sizes: Vec<u64>,
-    max_transaction_output_chunk_size: Vec<u64>,
+    max_epoch_chunk_sizes: Vec<(u64, f64)>,
+    max_state_chunk_sizes: Vec<(u64, f64)>,
+    max_transaction_chunk_sizes: Vec<(u64, f64)>,
+    max_transaction_output_chunk_size: Vec<(u64, f64)>,
 ) -> OptimalChunkSizes {
     let epoch_chunk_size = median_or_max(max_epoch_chunk_sizes, config.max_epoch_chunk_size);
     let state_chunk_size = median_or_max(max_state_chunk_sizes, config.max_state_chunk_size);
@@ -407,14 +590,19 @@ pub(crate) fn calculate_optimal_chunk_sizes(
     }
 }
 
-/// Calculates the median of the given set of values (if it exists)
-/// and returns the median or the specified max value, whichever is
-/// lower.
-fn median_or_max<T: Ord + Copy>(mut values: Vec<T>, max_value: T) -> T {
-    // Calculate median
-    values.sort_unstable();
-    let idx = values.len() / 2;
-    let median = values.get(idx).copied();
+/// Calculates the weighted median of the given set of values (if it
+/// exists) and returns the median or the specified max value, whichever
+/// is lower. With equal weights, this is the (upper) median.
+fn median_or_max<T: Ord + Copy>(mut values: Vec<(T, f64)>, max_value: T) -> T {
+    // Calculate the weighted median, i.e., the first value at
+    // which the cumulative weight exceeds half the total weight.
+    values.sort_unstable_by_key(|(value, _)| *value);
+    let total_weight: f64 = values.iter().map(|(_, weight)| weight).sum();
+    let mut cumulative_weight = 0.0;
+    let median = values.iter().find_map(|(value, weight)| {
+        cumulative_weight += weight;
+        (cumulative_weight > total_weight / 2.0).then_some(*value)
+    });
 
     // Return median or max
     min(median.unwrap_or(max_value), max_value)
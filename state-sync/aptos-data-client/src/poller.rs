@@ -5,7 +5,7 @@ use crate::{
     client::AptosDataClient,
     error::Error,
     global_summary::GlobalDataSummary,
-    interface::{AptosDataClientInterface, Response},
+    interface::{AptosDataClientInterface, Response, ResponseError},
     latency_monitor::LatencyMonitor,
     logging::{LogEntry, LogEvent, LogSchema},
     metrics,
@@ -16,7 +16,7 @@ use aptos_logger::{debug, info, sample, sample::SampleRate, warn};
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_types::{
     requests::{DataRequest, StorageServiceRequest},
-    responses::StorageServerSummary,
+    responses::{DataWindowProof, StorageServerSummary},
 };
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use futures::StreamExt;
@@ -78,6 +78,11 @@ impl DataSummaryPoller {
         let poll_loop_ticker = self.time_service.interval(self.poll_loop_interval);
         futures::pin_mut!(poll_loop_ticker);
 
+        // Track when the peer reliabilities were last persisted
+        let reliability_persist_interval =
+            Duration::from_millis(self.data_client_config.peer_reliability_persist_interval_ms);
+        let mut last_reliability_persist = self.time_service.now();
+
         loop {
             // Wait for next round before polling
             poll_loop_ticker.next().await;
@@ -95,6 +100,18 @@ impl DataSummaryPoller {
                 );
             }
 
+            // Persist the peer reliabilities (if the interval has elapsed)
+            if !reliability_persist_interval.is_zero()
+                && self
+                    .time_service
+                    .now()
+                    .duration_since(last_reliability_persist)
+                    >= reliability_persist_interval
+            {
+                self.data_client.persist_peer_reliabilities();
+                last_reliability_persist = self.time_service.now();
+            }
+
             // Fetch the prioritized and regular peers to poll (if any)
             let prioritized_peer = self.try_fetch_peer(true);
             let regular_peer = self.fetch_regular_peer(prioritized_peer.is_none());
@@ -117,11 +134,17 @@ impl DataSummaryPoller {
                 poll_peer(
                     self.data_client.clone(),
                     prioritized_peer,
+                    self.storage.clone(),
                     self.runtime.clone(),
                 );
             }
             if let Some(regular_peer) = regular_peer {
-                poll_peer(self.data_client.clone(), regular_peer, self.runtime.clone());
+                poll_peer(
+                    self.data_client.clone(),
+                    regular_peer,
+                    self.storage.clone(),
+                    self.runtime.clone(),
+                );
             }
         }
     }
@@ -170,6 +193,7 @@ fn log_poller_error(error: Error) {
 pub(crate) fn poll_peer(
     data_client: AptosDataClient,
     peer: PeerNetworkId,
+    storage: Arc<dyn DbReader>,
     runtime: Option<Handle>,
 ) -> JoinHandle<()> {
     // Mark the in-flight poll as started. We do this here to prevent
@@ -219,6 +243,11 @@ pub(crate) fn poll_peer(
         // Update the summary for the peer
         data_client.update_summary(peer, storage_summary);
 
+        // If the peer is pruned, fetch the proof of its data window
+        if data_client.should_request_data_window_proof(&peer) {
+            fetch_data_window_proof(&data_client, storage.as_ref(), peer).await;
+        }
+
         // Log the new global data summary and update the metrics
         sample!(
             SampleRate::Duration(Duration::from_secs(GLOBAL_DATA_LOG_FREQ_SECS)),
@@ -246,6 +275,93 @@ pub(crate) fn poll_peer(
     }
 }
 
+/// Fetches and verifies the proof of the data window retained by the given
+/// (pruned) peer. The request is only sent once per peer, as peers running
+/// older versions of the storage service are unable to serve it. However, the
+/// proof is requested again if the request fails transiently, or if the proof
+/// can't be verified against our current epoch (e.g., the peer is ahead of us).
+async fn fetch_data_window_proof(
+    data_client: &AptosDataClient,
+    storage: &dyn DbReader,
+    peer: PeerNetworkId,
+) {
+    // Mark the proof as requested
+    data_client.mark_data_window_proof_requested(peer);
+
+    // Fetch the data window proof from the peer
+    let data_request = DataRequest::GetDataWindowProof;
+    let storage_request = StorageServiceRequest::new(data_request, data_client.use_compression());
+    let request_timeout = data_client.get_response_timeout_ms();
+    let result: crate::error::Result<Response<Option<DataWindowProof>>> = data_client
+        .send_request_to_peer_and_decode(peer, storage_request, request_timeout)
+        .await;
+
+    // Check the data window proof response
+    let (context, data_window_proof) = match result {
+        Ok(response) => response.into_parts(),
+        Err(error) => {
+            if matches!(
+                error,
+                Error::DataIsUnavailable(_) | Error::TimeoutWaitingForResponse(_)
+            ) {
+                data_client.reset_data_window_proof_requested(peer);
+            }
+            debug!(
+                (LogSchema::new(LogEntry::StorageSummaryResponse)
+                    .event(LogEvent::PeerPollingError)
+                    .message("Unable to fetch the data window proof from the peer!")
+                    .error(&error)
+                    .peer(&peer))
+            );
+            return;
+        },
+    };
+    let data_window_proof = match data_window_proof {
+        Some(data_window_proof) => data_window_proof,
+        None => return, // The peer is not pruned
+    };
+
+    // Fetch our latest epoch state to verify the proof against
+    let epoch_state = match storage.get_latest_epoch_state() {
+        Ok(epoch_state) => epoch_state,
+        Err(error) => {
+            data_client.reset_data_window_proof_requested(peer);
+            warn!(
+                (LogSchema::new(LogEntry::StorageSummaryResponse)
+                    .event(LogEvent::StorageReadFailed)
+                    .message(&format!(
+                        "Unable to read the latest epoch state: {:?}",
+                        error
+                    ))
+                    .peer(&peer))
+            );
+            return;
+        },
+    };
+    if data_window_proof.ledger_info.ledger_info().epoch() != epoch_state.epoch {
+        data_client.reset_data_window_proof_requested(peer);
+        return;
+    }
+
+    // Verify the proof and update the data window for the peer
+    if let Err(error) = data_window_proof.verify(&epoch_state) {
+        warn!(
+            (LogSchema::new(LogEntry::StorageSummaryResponse)
+                .event(LogEvent::ResponseError)
+                .message(&format!(
+                    "Received an invalid data window proof: {:?}",
+                    error
+                ))
+                .peer(&peer))
+        );
+        context
+            .response_callback
+            .notify_bad_response(ResponseError::ProofVerificationError);
+        return;
+    }
+    data_client.update_data_window_proof(peer, data_window_proof);
+}
+
 /// Spawns the dedicated latency monitor
 fn start_latency_monitor(
     data_client_config: AptosDataClientConfig,
@@ -0,0 +1,169 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use aptos_config::network_id::PeerNetworkId;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path};
+
+/// The file (in the data directory) that peer reliabilities are persisted to
+pub const PEER_RELIABILITY_FILE_NAME: &str = "data_client_peer_reliability.json";
+
+/// The minimum weight of a peer (so that unreliable peers are rarely, but
+/// still occasionally, selected and can recover their reliability).
+const MIN_RELIABILITY_WEIGHT: f64 = 0.05;
+
+/// The error rate of a peer without any history. Unknown peers start out with a
+/// neutral prior (rather than a clean history), so a misbehaving peer can't escape
+/// its penalties by reconnecting under a new identity.
+const PRIOR_ERROR_RATE: f64 = 0.5;
+
+/// The maximum number of peer reliabilities to keep (the map must be bounded, as
+/// the reliabilities aren't garbage collected when peers disconnect).
+pub const MAX_NUM_PEER_RELIABILITIES: usize = 10_000;
+
+/// The outcome of a request sent to a peer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResponseOutcome {
+    Success,
+    Error,
+    Timeout,
+}
+
+/// The reliability history of a peer, i.e., the exponentially decaying rates
+/// of errors and timeouts across the responses of the peer.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PeerReliability {
+    error_rate: f64,
+    timeout_rate: f64,
+}
+
+impl Default for PeerReliability {
+    /// Returns the reliability of a peer without any history (i.e., the prior)
+    fn default() -> Self {
+        Self {
+            error_rate: PRIOR_ERROR_RATE,
+            timeout_rate: 0.0,
+        }
+    }
+}
+
+impl PeerReliability {
+    /// Records the outcome of a request, where `decay` is the weight
+    /// of the existing history (see `decay_for_half_life()`).
+    pub fn record_outcome(&mut self, outcome: ResponseOutcome, decay: f64) {
+        let (error, timeout) = match outcome {
+            ResponseOutcome::Success => (0.0, 0.0),
+            ResponseOutcome::Error => (1.0, 0.0),
+            ResponseOutcome::Timeout => (0.0, 1.0),
+        };
+        self.error_rate = self.error_rate * decay + error * (1.0 - decay);
+        self.timeout_rate = self.timeout_rate * decay + timeout * (1.0 - decay);
+    }
+
+    #[cfg(test)]
+    /// Returns the rate of errors across the responses of the peer
+    pub fn error_rate(&self) -> f64 {
+        self.error_rate
+    }
+
+    #[cfg(test)]
+    /// Returns the rate of timeouts across the requests to the peer
+    pub fn timeout_rate(&self) -> f64 {
+        self.timeout_rate
+    }
+
+    /// Returns the weight of the peer when aggregating advertised
+    /// data and selecting peers, i.e., its rate of successful responses.
+    pub fn weight(&self) -> f64 {
+        f64::max(
+            1.0 - self.error_rate - self.timeout_rate,
+            MIN_RELIABILITY_WEIGHT,
+        )
+    }
+
+    /// Returns true iff the peer is at least as reliable as a peer without
+    /// any history, and so forgetting its reliability won't reward it.
+    fn is_reliable(&self) -> bool {
+        self.weight() >= PeerReliability::default().weight()
+    }
+}
+
+/// Returns the decay of the reliability history such that the weight of
+/// each outcome halves after the given number of subsequent outcomes.
+pub fn decay_for_half_life(half_life: u64) -> f64 {
+    if half_life == 0 {
+        0.0 // Only the latest outcome is considered
+    } else {
+        0.5_f64.powf(1.0 / half_life as f64)
+    }
+}
+
+/// A single persisted peer reliability (peer network ids can't be JSON map keys)
+#[derive(Deserialize, Serialize)]
+struct PersistedPeerReliability {
+    peer: PeerNetworkId,
+    reliability: PeerReliability,
+}
+
+/// Loads the peer reliabilities from the given file. If the file doesn't exist, no
+/// reliabilities are returned.
+pub fn load_peer_reliabilities(
+    path: &Path,
+) -> Result<HashMap<PeerNetworkId, PeerReliability>, Error> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let bytes = fs::read(path).map_err(|error| {
+        Error::UnexpectedErrorEncountered(format!(
+            "Failed to read the peer reliabilities from {:?}: {:?}",
+            path, error
+        ))
+    })?;
+    let persisted: Vec<PersistedPeerReliability> =
+        serde_json::from_slice(&bytes).map_err(|error| {
+            Error::UnexpectedErrorEncountered(format!(
+                "Failed to parse the peer reliabilities from {:?}: {:?}",
+                path, error
+            ))
+        })?;
+
+    Ok(persisted
+        .into_iter()
+        .map(|persisted| (persisted.peer, persisted.reliability))
+        .collect())
+}
+
+/// Saves the peer reliabilities to the given file (atomically, by writing a temporary file
+/// and moving it). Reliable peers are not saved, as they'd only be reset to the prior.
+pub fn save_peer_reliabilities(
+    path: &Path,
+    peer_reliabilities: &HashMap<PeerNetworkId, PeerReliability>,
+) -> Result<(), Error> {
+    let mut persisted: Vec<PersistedPeerReliability> = peer_reliabilities
+        .iter()
+        .filter(|(_, reliability)| !reliability.is_reliable())
+        .map(|(peer, reliability)| PersistedPeerReliability {
+            peer: *peer,
+            reliability: *reliability,
+        })
+        .collect();
+    persisted.sort_by_key(|persisted| persisted.peer);
+
+    let bytes = serde_json::to_vec_pretty(&persisted).map_err(|error| {
+        Error::UnexpectedErrorEncountered(format!(
+            "Failed to serialize the peer reliabilities: {:?}",
+            error
+        ))
+    })?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, bytes)
+        .and_then(|_| fs::rename(&temp_path, path))
+        .map_err(|error| {
+            Error::UnexpectedErrorEncountered(format!(
+                "Failed to write the peer reliabilities to {:?}: {:?}",
+                path, error
+            ))
+        })
+}
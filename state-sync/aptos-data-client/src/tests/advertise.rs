@@ -117,10 +117,10 @@ async fn optimal_chunk_size_calculations() {
     // Test median calculations
     let optimal_chunk_sizes = calculate_optimal_chunk_sizes(
         &data_client_config,
-        vec![7, 5, 6, 8, 10],
-        vec![100, 200, 300, 100],
-        vec![900, 700, 500],
-        vec![40],
+        with_equal_weights(vec![7, 5, 6, 8, 10]),
+        with_equal_weights(vec![100, 200, 300, 100]),
+        with_equal_weights(vec![900, 700, 500]),
+        with_equal_weights(vec![40]),
     );
     assert_eq!(200, optimal_chunk_sizes.state_chunk_size);
     assert_eq!(7, optimal_chunk_sizes.epoch_chunk_size);
@@ -144,10 +144,10 @@ async fn optimal_chunk_size_calculations() {
     // Verify the config caps the amount of chunks
     let optimal_chunk_sizes = calculate_optimal_chunk_sizes(
         &data_client_config,
-        vec![70, 50, 60, 80, 100],
-        vec![1000, 1000, 2000, 3000],
-        vec![9000, 7000, 5000],
-        vec![400],
+        with_equal_weights(vec![70, 50, 60, 80, 100]),
+        with_equal_weights(vec![1000, 1000, 2000, 3000]),
+        with_equal_weights(vec![9000, 7000, 5000]),
+        with_equal_weights(vec![400]),
     );
     assert_eq!(max_state_chunk_size, optimal_chunk_sizes.state_chunk_size);
     assert_eq!(70, optimal_chunk_sizes.epoch_chunk_size);
@@ -157,3 +157,29 @@ async fn optimal_chunk_size_calculations() {
     );
     assert_eq!(400, optimal_chunk_sizes.transaction_output_chunk_size);
 }
+
+#[tokio::test]
+async fn optimal_chunk_size_calculations_weighted() {
+    let data_client_config = AptosDataClientConfig::default();
+
+    // Verify unreliable peers have less influence on the median
+    let optimal_chunk_sizes = calculate_optimal_chunk_sizes(
+        &data_client_config,
+        vec![(10, 0.05), (20, 0.05), (30, 1.0)],
+        vec![(100, 1.0), (200, 0.5), (300, 0.5), (400, 0.05)],
+        vec![(10, 1.0), (20, 1.0), (30, 0.05), (40, 0.05), (50, 0.05)],
+        vec![(40, 0.05)],
+    );
+    assert_eq!(30, optimal_chunk_sizes.epoch_chunk_size);
+    assert_eq!(200, optimal_chunk_sizes.state_chunk_size);
+    assert_eq!(20, optimal_chunk_sizes.transaction_chunk_size);
+    assert_eq!(40, optimal_chunk_sizes.transaction_output_chunk_size);
+}
+
+/// Returns the given chunk sizes, each with the same weight
+fn with_equal_weights(chunk_sizes: Vec<u64>) -> Vec<(u64, f64)> {
+    chunk_sizes
+        .into_iter()
+        .map(|chunk_size| (chunk_size, 1.0))
+        .collect()
+}
@@ -192,3 +192,61 @@ async fn disable_compression() {
         .unwrap();
     assert_eq!(response.payload, TransactionListWithProof::new_empty());
 }
+
+#[tokio::test]
+async fn network_compression() {
+    ::aptos_logger::Logger::init_for_testing();
+
+    // Create a data client with compression enabled
+    let (mut mock_network, mock_time, client, poller) = MockNetwork::new(None, None, None);
+
+    tokio::spawn(poller.start_poller());
+
+    // Add a connected peer that supports network compression
+    let expected_peer = mock_network.add_peer_with_network_compression(true);
+
+    // Advance time so the poller sends a data summary request
+    tokio::task::yield_now().await;
+    mock_time.advance_async(Duration::from_millis(1_000)).await;
+
+    // Verify the request is sent over the zstd protocol without application compression
+    let network_request = mock_network.next_request().await.unwrap();
+    assert_eq!(network_request.peer_network_id, expected_peer);
+    assert_eq!(
+        network_request.protocol_id,
+        ProtocolId::StorageServiceRpcZstd
+    );
+    assert!(!network_request.storage_service_request.use_compression);
+
+    // Fulfill their request
+    let data_response = DataResponse::StorageServerSummary(utils::create_storage_summary(200));
+    network_request.response_sender.send(Ok(
+        StorageServiceResponse::new(data_response, false).unwrap()
+    ));
+
+    // Let the poller finish processing the response
+    tokio::task::yield_now().await;
+
+    // Handle the client's transactions request
+    tokio::spawn(async move {
+        let network_request = mock_network.next_request().await.unwrap();
+        assert_eq!(
+            network_request.protocol_id,
+            ProtocolId::StorageServiceRpcZstd
+        );
+        assert!(!network_request.storage_service_request.use_compression);
+
+        let data_response =
+            DataResponse::TransactionsWithProof(TransactionListWithProof::new_empty());
+        let storage_response = StorageServiceResponse::new(data_response, false).unwrap();
+        network_request.response_sender.send(Ok(storage_response));
+    });
+
+    // The client's request should succeed (the response is uncompressed)
+    let request_timeout = client.get_response_timeout_ms();
+    let response = client
+        .get_transactions_with_proof(100, 50, 100, false, request_timeout)
+        .await
+        .unwrap();
+    assert_eq!(response.payload, TransactionListWithProof::new_empty());
+}
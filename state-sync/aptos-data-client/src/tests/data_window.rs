@@ -0,0 +1,220 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::AptosDataClient,
+    interface::AptosDataClientInterface,
+    poller::poll_peer,
+    tests::{
+        mock::{create_mock_db_reader, MockNetwork},
+        utils,
+    },
+};
+use aptos_config::config::AptosDataClientConfig;
+use aptos_crypto::{
+    hash::{CryptoHash, TransactionAccumulatorHasher},
+    HashValue,
+};
+use aptos_storage_service_server::network::NetworkRequest;
+use aptos_storage_service_types::{
+    requests::DataRequest,
+    responses::{CompleteDataRange, DataResponse, DataWindowProof, StorageServiceResponse},
+    StorageServiceError,
+};
+use aptos_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{
+        accumulator::InMemoryAccumulator, TransactionAccumulatorProof, TransactionInfoWithProof,
+    },
+    transaction::{ExecutionStatus, Transaction, TransactionInfo, TransactionWithProof},
+};
+use claims::assert_matches;
+
+#[tokio::test]
+async fn data_window_proof_trims_advertised_data() {
+    ::aptos_logger::Logger::init_for_testing();
+
+    // Create a data client with a small data window margin
+    let data_client_config = AptosDataClientConfig {
+        data_window_margin_versions: 10,
+        ..Default::default()
+    };
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add a peer and poll it
+    let peer = mock_network.add_peer(true);
+    let handle = poll_peer(client.clone(), peer, create_mock_db_reader(), None);
+
+    // Respond with the storage summary of a pruned peer
+    let network_request = mock_network.next_request().await.unwrap();
+    respond_with_pruned_summary(network_request, 1, 1000);
+
+    // Respond to the data window proof request with a valid proof
+    let network_request = mock_network.next_request().await.unwrap();
+    assert_matches!(
+        network_request.storage_service_request.data_request,
+        DataRequest::GetDataWindowProof
+    );
+    let data_response = DataResponse::DataWindowProof(Some(create_data_window_proof(100)));
+    network_request
+        .response_sender
+        .send(Ok(StorageServiceResponse::new(data_response, true).unwrap()));
+    handle.await.unwrap();
+
+    // Verify the advertised data is trimmed to the (moved) data window
+    let expected_range = CompleteDataRange::new(911, 1000).unwrap();
+    verify_advertised_transactions(&client, expected_range);
+    assert!(!client.should_request_data_window_proof(&peer));
+}
+
+#[tokio::test]
+async fn data_window_proof_unsupported() {
+    ::aptos_logger::Logger::init_for_testing();
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, None, None);
+
+    // Add a peer and poll it
+    let peer = mock_network.add_peer(true);
+    let handle = poll_peer(client.clone(), peer, create_mock_db_reader(), None);
+
+    // Respond with the storage summary of a pruned peer
+    let network_request = mock_network.next_request().await.unwrap();
+    respond_with_pruned_summary(network_request, 1, 1000);
+
+    // Respond to the data window proof request with an error (e.g., an old server)
+    let network_request = mock_network.next_request().await.unwrap();
+    network_request
+        .response_sender
+        .send(Err(StorageServiceError::InvalidRequest(
+            "Unknown request type!".into(),
+        )));
+    handle.await.unwrap();
+
+    // Verify the advertised data is unchanged and the proof isn't requested again
+    let expected_range = CompleteDataRange::new(1, 1000).unwrap();
+    verify_advertised_transactions(&client, expected_range);
+    assert!(!client.should_request_data_window_proof(&peer));
+}
+
+#[tokio::test]
+async fn data_window_proof_of_another_epoch() {
+    ::aptos_logger::Logger::init_for_testing();
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, None, None);
+
+    // Add a peer and poll it
+    let peer = mock_network.add_peer(true);
+    let handle = poll_peer(client.clone(), peer, create_mock_db_reader(), None);
+
+    // Respond with the storage summary of a pruned peer
+    let network_request = mock_network.next_request().await.unwrap();
+    respond_with_pruned_summary(network_request, 1, 1000);
+
+    // Respond to the data window proof request with a proof of a later epoch
+    // (which can't be verified against our latest epoch state)
+    let mut data_window_proof = create_data_window_proof(100);
+    let ledger_info = data_window_proof.ledger_info.ledger_info().clone();
+    data_window_proof.ledger_info = LedgerInfoWithSignatures::new(
+        LedgerInfo::new(
+            BlockInfo::new(
+                1,
+                0,
+                HashValue::zero(),
+                ledger_info.transaction_accumulator_hash(),
+                ledger_info.version(),
+                0,
+                None,
+            ),
+            HashValue::zero(),
+        ),
+        AggregateSignature::empty(),
+    );
+    let network_request = mock_network.next_request().await.unwrap();
+    let data_response = DataResponse::DataWindowProof(Some(data_window_proof));
+    network_request
+        .response_sender
+        .send(Ok(StorageServiceResponse::new(data_response, true).unwrap()));
+    handle.await.unwrap();
+
+    // Verify the advertised data is unchanged and the proof will be requested again
+    let expected_range = CompleteDataRange::new(1, 1000).unwrap();
+    verify_advertised_transactions(&client, expected_range);
+    assert!(client.should_request_data_window_proof(&peer));
+}
+
+/// Creates a valid data window proof for a peer that retains version 1
+/// onwards (i.e., the ledger is at version 1 and version 0 has been pruned)
+fn create_data_window_proof(window_size: u64) -> DataWindowProof {
+    // Create the lowest retained transaction and its info
+    let transaction = Transaction::StateCheckpoint(HashValue::random());
+    let transaction_info = TransactionInfo::new(
+        transaction.hash(),
+        HashValue::random(),
+        HashValue::random(),
+        None,
+        0,
+        ExecutionStatus::Success,
+    );
+
+    // Create the ledger info with the accumulator root
+    let pruned_leaf = HashValue::random();
+    let root_hash = InMemoryAccumulator::<TransactionAccumulatorHasher>::from_leaves(&[
+        pruned_leaf,
+        transaction_info.hash(),
+    ])
+    .root_hash();
+    let ledger_info = LedgerInfoWithSignatures::new(
+        LedgerInfo::new(
+            BlockInfo::new(0, 0, HashValue::zero(), root_hash, 1, 0, None),
+            HashValue::zero(),
+        ),
+        AggregateSignature::empty(),
+    );
+
+    // Create the data window proof
+    let proof = TransactionInfoWithProof::new(
+        TransactionAccumulatorProof::new(vec![pruned_leaf]),
+        transaction_info,
+    );
+    DataWindowProof {
+        window_size,
+        ledger_info,
+        lowest_transaction: TransactionWithProof::new(1, transaction, None, proof),
+    }
+}
+
+/// Responds to the storage summary request with the summary of a pruned peer
+fn respond_with_pruned_summary(
+    network_request: NetworkRequest,
+    lowest_version: u64,
+    highest_version: u64,
+) {
+    assert_matches!(
+        network_request.storage_service_request.data_request,
+        DataRequest::GetStorageServerSummary
+    );
+    let mut storage_summary = utils::create_storage_summary(highest_version);
+    let data_range = CompleteDataRange::new(lowest_version, highest_version).unwrap();
+    storage_summary.data_summary.transactions = Some(data_range);
+    storage_summary.data_summary.transaction_outputs = Some(data_range);
+    let data_response = DataResponse::StorageServerSummary(storage_summary);
+    network_request
+        .response_sender
+        .send(Ok(StorageServiceResponse::new(data_response, true).unwrap()));
+}
+
+/// Verifies the globally advertised transactions match the expected range
+fn verify_advertised_transactions(
+    client: &AptosDataClient,
+    expected_range: CompleteDataRange<u64>,
+) {
+    client.update_global_summary_cache().unwrap();
+    let global_data_summary = client.get_global_data_summary();
+    assert_eq!(global_data_summary.advertised_data.transactions, vec![
+        expected_range
+    ]);
+    assert_eq!(
+        global_data_summary.advertised_data.transaction_outputs,
+        vec![expected_range]
+    );
+}
@@ -31,6 +31,7 @@ use aptos_storage_service_types::{
 };
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{
+    epoch_state::EpochState,
     ledger_info::LedgerInfoWithSignatures,
     state_store::state_value::StateValueChunkWithProof,
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
@@ -71,7 +72,10 @@ impl MockNetwork {
         let client_network_id = NetworkId::Validator;
         let network_client = NetworkClient::new(
             vec![],
-            vec![ProtocolId::StorageServiceRpc],
+            vec![
+                ProtocolId::StorageServiceRpcZstd,
+                ProtocolId::StorageServiceRpc,
+            ],
             hashmap! {
             client_network_id => network_sender},
             peers_and_metadata.clone(),
@@ -119,6 +123,32 @@ impl MockNetwork {
         &mut self,
         network_id: NetworkId,
         outbound_connection: bool,
+    ) -> PeerNetworkId {
+        self.add_peer_with_protocols(network_id, outbound_connection, &[
+            ProtocolId::StorageServiceRpc,
+        ])
+    }
+
+    /// Add a new peer (that supports network compression) to the network peer DB
+    pub fn add_peer_with_network_compression(&mut self, priority: bool) -> PeerNetworkId {
+        // Get the network id
+        let network_id = if priority {
+            NetworkId::Validator
+        } else {
+            NetworkId::Public
+        };
+        self.add_peer_with_protocols(network_id, false, &[
+            ProtocolId::StorageServiceRpcZstd,
+            ProtocolId::StorageServiceRpc,
+        ])
+    }
+
+    /// Add a new peer (that supports the given protocols) to the network peer DB
+    fn add_peer_with_protocols(
+        &mut self,
+        network_id: NetworkId,
+        outbound_connection: bool,
+        protocol_ids: &[ProtocolId],
     ) -> PeerNetworkId {
         // Create a new peer
         let peer_id = PeerId::random();
@@ -131,9 +161,11 @@ impl MockNetwork {
         } else {
             ConnectionOrigin::Inbound
         };
-        connection_metadata
-            .application_protocols
-            .insert(ProtocolId::StorageServiceRpc);
+        for protocol_id in protocol_ids {
+            connection_metadata
+                .application_protocols
+                .insert(*protocol_id);
+        }
         self.peers_and_metadata
             .insert_connection_metadata(peer_network_id, connection_metadata)
             .unwrap();
@@ -168,7 +200,7 @@ impl MockNetwork {
                 let data = network_request.data;
                 let res_tx = network_request.res_tx;
 
-                let message: StorageServiceMessage = bcs::from_bytes(data.as_ref()).unwrap();
+                let message: StorageServiceMessage = protocol_id.from_bytes(data.as_ref()).unwrap();
                 let storage_service_request = match message {
                     StorageServiceMessage::Request(request) => request,
                     _ => panic!("unexpected: {:?}", message),
@@ -285,4 +317,8 @@ impl DbReader for MockDatabaseReader {
     fn get_block_timestamp(&self, version: Version) -> anyhow::Result<u64> {
         Ok(version * 100_000)
     }
+
+    fn get_latest_epoch_state(&self) -> anyhow::Result<EpochState> {
+        Ok(EpochState::empty())
+    }
 }
@@ -3,8 +3,11 @@
 
 mod advertise;
 mod compression;
+mod data_window;
 pub mod mock;
+mod multi_peer;
 mod peers;
 mod poller;
 mod priority;
+mod reliability;
 mod utils;
@@ -0,0 +1,334 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::AptosDataClient,
+    error::Error,
+    interface::AptosDataClientInterface,
+    multi_peer,
+    tests::{mock::MockNetwork, utils},
+};
+use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
+use aptos_crypto::HashValue;
+use aptos_storage_service_types::{
+    requests::DataRequest,
+    responses::{CompleteDataRange, DataResponse, StorageServiceResponse},
+    StorageServiceError,
+};
+use aptos_types::{
+    proof::SparseMerkleRangeProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
+};
+use claims::assert_matches;
+use std::{
+    cmp::min,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+#[test]
+fn test_split_state_value_range() {
+    // Create a data client config
+    let data_client_config = AptosDataClientConfig {
+        max_num_state_sub_chunks: 4,
+        min_state_sub_chunk_size: 100,
+        ..Default::default()
+    };
+
+    // Verify that small ranges are not split
+    for (start_index, end_index) in [(0, 0), (0, 150), (10, 5), (0, u64::MAX)] {
+        let sub_chunk_ranges =
+            multi_peer::split_state_value_range(&data_client_config, start_index, end_index);
+        assert_eq!(sub_chunk_ranges, vec![(start_index, end_index)]);
+    }
+
+    // Verify that large ranges are split according to the min sub-chunk size
+    let sub_chunk_ranges = multi_peer::split_state_value_range(&data_client_config, 100, 399);
+    assert_eq!(sub_chunk_ranges, vec![(100, 199), (200, 299), (300, 399)]);
+
+    // Verify that large ranges are split according to the max number of sub-chunks
+    let sub_chunk_ranges = multi_peer::split_state_value_range(&data_client_config, 0, 1000);
+    assert_eq!(
+        sub_chunk_ranges,
+        vec![(0, 250), (251, 501), (502, 752), (753, 1000)]
+    );
+
+    // Verify that splitting can be disabled
+    let data_client_config = AptosDataClientConfig {
+        max_num_state_sub_chunks: 1,
+        ..data_client_config
+    };
+    let sub_chunk_ranges = multi_peer::split_state_value_range(&data_client_config, 0, 1000);
+    assert_eq!(sub_chunk_ranges, vec![(0, 1000)]);
+}
+
+#[test]
+fn test_reassemble_state_value_chunk() {
+    // Verify that valid sub-chunks are reassembled
+    let root_hash = HashValue::random();
+    let sub_chunks = vec![
+        create_state_value_chunk(0, 9, root_hash),
+        create_state_value_chunk(10, 19, root_hash),
+        create_state_value_chunk(20, 24, root_hash),
+    ];
+    let state_value_chunk = multi_peer::reassemble_state_value_chunk(sub_chunks).unwrap();
+    assert_eq!(
+        state_value_chunk,
+        create_state_value_chunk(0, 24, root_hash)
+    );
+
+    // Verify that sub-chunks with different root hashes are rejected
+    let sub_chunks = vec![
+        create_state_value_chunk(0, 9, root_hash),
+        create_state_value_chunk(10, 19, HashValue::random()),
+    ];
+    let result = multi_peer::reassemble_state_value_chunk(sub_chunks);
+    assert_matches!(result, Err(Error::InvalidResponse(_)));
+
+    // Verify that non-contiguous sub-chunks are rejected
+    let sub_chunks = vec![
+        create_state_value_chunk(0, 9, root_hash),
+        create_state_value_chunk(11, 19, root_hash),
+    ];
+    let result = multi_peer::reassemble_state_value_chunk(sub_chunks);
+    assert_matches!(result, Err(Error::InvalidResponse(_)));
+}
+
+#[test]
+fn test_verify_state_value_sub_chunk() {
+    // Verify that complete and truncated sub-chunks are accepted
+    let sub_chunk = create_state_value_chunk(0, 9, HashValue::random());
+    assert!(multi_peer::verify_state_value_sub_chunk(&sub_chunk, 0, 9).is_ok());
+    assert!(multi_peer::verify_state_value_sub_chunk(&sub_chunk, 0, 100).is_ok());
+
+    // Verify that sub-chunks with unexpected indices are rejected
+    for (start_index, end_index) in [(1, 9), (0, 8)] {
+        let result = multi_peer::verify_state_value_sub_chunk(&sub_chunk, start_index, end_index);
+        assert_matches!(result, Err(Error::InvalidResponse(_)));
+    }
+
+    // Verify that sub-chunks with missing values are rejected
+    let mut missing_values_sub_chunk = sub_chunk;
+    missing_values_sub_chunk.raw_values.pop();
+    let result = multi_peer::verify_state_value_sub_chunk(&missing_values_sub_chunk, 0, 9);
+    assert_matches!(result, Err(Error::InvalidResponse(_)));
+}
+
+#[tokio::test]
+async fn test_multi_peer_fetch_with_unresponsive_peer() {
+    ::aptos_logger::Logger::init_for_testing();
+
+    // Create a data client with two peers that both advertise the states
+    let (mock_network, client, peers) = create_multi_peer_client(2, 100);
+
+    // Only respond to the requests sent to the first (i.e., responsive) peer
+    let responsive_peer = peers[0];
+    let root_hash = HashValue::random();
+    spawn_state_value_responder(mock_network, move |peer, start_index, end_index| {
+        (peer == responsive_peer)
+            .then(|| Ok(create_state_value_chunk(start_index, end_index, root_hash)))
+    });
+
+    // Fetch the state values and verify the chunk was reassembled
+    let request_timeout = client.get_response_timeout_ms();
+    let response = client
+        .get_state_values_with_proof(200, 0, 99, request_timeout)
+        .await
+        .unwrap();
+    assert_eq!(response.payload, create_state_value_chunk(0, 99, root_hash));
+}
+
+#[tokio::test]
+async fn test_multi_peer_fetch_without_hedging() {
+    ::aptos_logger::Logger::init_for_testing();
+
+    // Create a data client with two peers that both advertise the states
+    let (mock_network, client, _) = create_multi_peer_client(2, 60_000);
+
+    // Respond to all requests, and count them
+    let num_requests = Arc::new(AtomicU64::new(0));
+    let root_hash = HashValue::random();
+    let request_counter = num_requests.clone();
+    spawn_state_value_responder(mock_network, move |_, start_index, end_index| {
+        request_counter.fetch_add(1, Ordering::Relaxed);
+        Some(Ok(create_state_value_chunk(
+            start_index,
+            end_index,
+            root_hash,
+        )))
+    });
+
+    // Verify each sub-chunk was only requested once (the peers respond before
+    // the requests are hedged)
+    let request_timeout = client.get_response_timeout_ms();
+    let response = client
+        .get_state_values_with_proof(200, 0, 99, request_timeout)
+        .await
+        .unwrap();
+    assert_eq!(response.payload, create_state_value_chunk(0, 99, root_hash));
+    assert_eq!(num_requests.load(Ordering::Relaxed), 4);
+}
+
+#[tokio::test]
+async fn test_multi_peer_fetch_with_truncated_sub_chunk() {
+    ::aptos_logger::Logger::init_for_testing();
+
+    // Create a data client with two peers that both advertise the states
+    let (mock_network, client, _) = create_multi_peer_client(2, 100);
+
+    // Respond to all requests, but truncate the responses to 20 values
+    let root_hash = HashValue::random();
+    spawn_state_value_responder(mock_network, move |_, start_index, end_index| {
+        let end_index = min(end_index, start_index + 19);
+        Some(Ok(create_state_value_chunk(
+            start_index,
+            end_index,
+            root_hash,
+        )))
+    });
+
+    // Verify the chunk ends at the truncated sub-chunk
+    let request_timeout = client.get_response_timeout_ms();
+    let response = client
+        .get_state_values_with_proof(200, 0, 99, request_timeout)
+        .await
+        .unwrap();
+    assert_eq!(response.payload, create_state_value_chunk(0, 19, root_hash));
+}
+
+#[tokio::test]
+async fn test_multi_peer_fetch_fallback_to_single_peer() {
+    ::aptos_logger::Logger::init_for_testing();
+
+    // Create a data client with two peers that both advertise the states
+    let (mock_network, client, _) = create_multi_peer_client(2, 100);
+
+    // Fail all sub-chunk requests, but serve requests for the entire chunk
+    let root_hash = HashValue::random();
+    spawn_state_value_responder(mock_network, move |_, start_index, end_index| {
+        if end_index - start_index + 1 < 100 {
+            Some(Err(StorageServiceError::InternalError(
+                "Sub-chunk requests are not supported!".into(),
+            )))
+        } else {
+            Some(Ok(create_state_value_chunk(
+                start_index,
+                end_index,
+                root_hash,
+            )))
+        }
+    });
+
+    // Verify the entire chunk is fetched from a single peer
+    let request_timeout = client.get_response_timeout_ms();
+    let response = client
+        .get_state_values_with_proof(200, 0, 99, request_timeout)
+        .await
+        .unwrap();
+    assert_eq!(response.payload, create_state_value_chunk(0, 99, root_hash));
+}
+
+/// Creates a data client (that splits chunks into sub-chunks of 25 values, and
+/// hedges sub-chunk requests after the given delay) and the given number of
+/// peers that advertise the states
+fn create_multi_peer_client(
+    num_peers: usize,
+    state_sub_chunk_hedge_delay_ms: u64,
+) -> (MockNetwork, AptosDataClient, Vec<PeerNetworkId>) {
+    let data_client_config = AptosDataClientConfig {
+        max_num_state_sub_chunks: 4,
+        min_state_sub_chunk_size: 25,
+        state_sub_chunk_hedge_delay_ms,
+        state_sub_chunk_redundancy: 2,
+        ..Default::default()
+    };
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+    let mut peers = vec![];
+    for _ in 0..num_peers {
+        let peer = mock_network.add_peer(true);
+        let mut storage_summary = utils::create_storage_summary(200);
+        storage_summary.data_summary.states = Some(CompleteDataRange::new(0, 200).unwrap());
+        client.update_summary(peer, storage_summary);
+        peers.push(peer);
+    }
+    client.update_global_summary_cache().unwrap();
+
+    (mock_network, client, peers)
+}
+
+/// Spawns a task that responds to the state value requests sent over the mock
+/// network, using the given handler. The handler is called with the peer and the
+/// requested indices, and requests are left unanswered if it returns None.
+fn spawn_state_value_responder<F>(mut mock_network: MockNetwork, handler: F)
+where
+    F: Fn(PeerNetworkId, u64, u64) -> Option<Result<StateValueChunkWithProof, StorageServiceError>>
+        + Send
+        + 'static,
+{
+    tokio::spawn(async move {
+        let mut unanswered_requests = vec![];
+        while let Some(network_request) = mock_network.next_request().await {
+            let storage_service_request = network_request.storage_service_request;
+            let (start_index, end_index) = match &storage_service_request.data_request {
+                DataRequest::GetStateValuesWithProof(request) => {
+                    (request.start_index, request.end_index)
+                },
+                request => panic!("Unexpected request: {:?}", request),
+            };
+            let response = match handler(network_request.peer_network_id, start_index, end_index) {
+                Some(response) => response,
+                None => {
+                    unanswered_requests.push(network_request.response_sender); // Never respond
+                    continue;
+                },
+            };
+
+            // Respond with the state value chunk (or the error)
+            let response = response.map(|state_value_chunk| {
+                StorageServiceResponse::new(
+                    DataResponse::StateValueChunkWithProof(state_value_chunk),
+                    storage_service_request.use_compression,
+                )
+                .unwrap()
+            });
+            network_request.response_sender.send(response);
+        }
+    });
+}
+
+/// Creates a state value chunk for the given indices (the
+/// hashed state keys are derived from the state indices).
+fn create_state_value_chunk(
+    start_index: u64,
+    end_index: u64,
+    root_hash: HashValue,
+) -> StateValueChunkWithProof {
+    let raw_values = (start_index..=end_index)
+        .map(|index| {
+            let state_key = StateKey::raw(index.to_be_bytes().to_vec());
+            let state_value = StateValue::from(index.to_be_bytes().to_vec());
+            (state_key, state_value)
+        })
+        .collect();
+    StateValueChunkWithProof {
+        first_index: start_index,
+        last_index: end_index,
+        first_key: create_hashed_state_key(start_index),
+        last_key: create_hashed_state_key(end_index),
+        raw_values,
+        proof: SparseMerkleRangeProof::new(vec![]),
+        root_hash,
+    }
+}
+
+/// Creates a hashed state key that is ordered by the given state index
+fn create_hashed_state_key(index: u64) -> HashValue {
+    let mut hashed_state_key = [0; HashValue::LENGTH];
+    hashed_state_key[HashValue::LENGTH - 8..].copy_from_slice(&index.to_be_bytes());
+    HashValue::new(hashed_state_key)
+}
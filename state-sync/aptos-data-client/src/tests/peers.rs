@@ -6,7 +6,10 @@ use crate::{
     error::Error,
     interface::AptosDataClientInterface,
     poller::poll_peer,
-    tests::{mock::MockNetwork, utils},
+    tests::{
+        mock::{create_mock_db_reader, MockNetwork},
+        utils,
+    },
 };
 use aptos_config::network_id::PeerNetworkId;
 use aptos_storage_service_types::{
@@ -315,7 +318,7 @@ async fn poll_peers(
 ) {
     for peer in all_peers {
         // Poll the peer
-        let handle = poll_peer(client.clone(), peer, None);
+        let handle = poll_peer(client.clone(), peer, create_mock_db_reader(), None);
 
         // Respond to the poll request
         let network_request = mock_network.next_request().await.unwrap();
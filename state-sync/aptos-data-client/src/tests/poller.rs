@@ -1,7 +1,12 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{client::AptosDataClient, error::Error, poller::poll_peer, tests::mock::MockNetwork};
+use crate::{
+    client::AptosDataClient,
+    error::Error,
+    poller::poll_peer,
+    tests::mock::{create_mock_db_reader, MockNetwork},
+};
 use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
 use aptos_storage_service_types::StorageServiceError;
 use claims::{assert_matches, assert_none};
@@ -364,7 +369,7 @@ async fn in_flight_error_handling() {
 
     // Poll the peer
     client.in_flight_request_started(&peer);
-    let handle = poll_peer(client.clone(), peer, None);
+    let handle = poll_peer(client.clone(), peer, create_mock_db_reader(), None);
 
     // Respond to the peer poll with an error
     if let Some(network_request) = mock_network.next_request().await {
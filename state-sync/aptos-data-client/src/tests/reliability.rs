@@ -0,0 +1,158 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    peer_states::{ErrorType, PeerStates},
+    reliability::{
+        decay_for_half_life, save_peer_reliabilities, PeerReliability, ResponseOutcome,
+        MAX_NUM_PEER_RELIABILITIES,
+    },
+};
+use aptos_config::{
+    config::{AptosDataClientConfig, BaseConfig},
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_network::application::storage::PeersAndMetadata;
+use aptos_temppath::TempPath;
+use aptos_types::PeerId;
+
+#[test]
+fn reliability_decay() {
+    // Verify the decay halves the weight of an outcome after the half life
+    let half_life = 10;
+    let decay = decay_for_half_life(half_life);
+    let mut peer_reliability = PeerReliability::default();
+    peer_reliability.record_outcome(ResponseOutcome::Error, 0.0);
+    for _ in 0..half_life {
+        peer_reliability.record_outcome(ResponseOutcome::Success, decay);
+    }
+    assert!((peer_reliability.error_rate() - 0.5).abs() < 1e-9);
+
+    // Verify a zero half life only considers the latest outcome
+    let decay = decay_for_half_life(0);
+    peer_reliability.record_outcome(ResponseOutcome::Timeout, decay);
+    assert_eq!(peer_reliability.error_rate(), 0.0);
+    assert_eq!(peer_reliability.timeout_rate(), 1.0);
+}
+
+#[test]
+fn reliability_weight() {
+    let decay = decay_for_half_life(1);
+
+    // Verify a new peer has the weight of the prior
+    let mut peer_reliability = PeerReliability::default();
+    assert_eq!(peer_reliability.weight(), 0.5);
+
+    // Verify successes increase the weight
+    peer_reliability.record_outcome(ResponseOutcome::Success, decay);
+    assert_eq!(peer_reliability.weight(), 0.75);
+
+    // Verify errors and timeouts both reduce the weight
+    peer_reliability.record_outcome(ResponseOutcome::Error, decay);
+    assert_eq!(peer_reliability.weight(), 0.375);
+    peer_reliability.record_outcome(ResponseOutcome::Timeout, decay);
+    assert_eq!(peer_reliability.weight(), 0.1875);
+
+    // Verify the weight never drops to zero
+    for _ in 0..100 {
+        peer_reliability.record_outcome(ResponseOutcome::Timeout, decay);
+    }
+    assert!(peer_reliability.weight() > 0.0);
+}
+
+#[test]
+fn reliability_persisted_across_restarts() {
+    // Create the peer states with a data directory
+    let data_dir = TempPath::new();
+    data_dir.create_as_dir().unwrap();
+    let base_config = BaseConfig {
+        data_dir: data_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let data_client_config = AptosDataClientConfig::default();
+    let peers_and_metadata = PeersAndMetadata::new(&[NetworkId::Validator]);
+    let mut peer_states = PeerStates::new(
+        base_config.clone(),
+        data_client_config,
+        peers_and_metadata.clone(),
+    );
+
+    // Update the reliability of a reliable and an unreliable peer
+    let reliable_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    let unreliable_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    peer_states.update_score_success(reliable_peer);
+    for _ in 0..10 {
+        peer_states.update_score_error(unreliable_peer, ErrorType::Timeout);
+    }
+    let unknown_weight = PeerReliability::default().weight();
+    let unreliable_weight = peer_states.get_reliability_weight(&unreliable_peer);
+    assert!(unreliable_weight < unknown_weight);
+
+    // Persist the reliabilities and restart the peer states
+    let path = peer_states.peer_reliability_path().unwrap();
+    save_peer_reliabilities(&path, &peer_states.get_peer_reliabilities()).unwrap();
+    let peer_states = PeerStates::new(base_config, data_client_config, peers_and_metadata);
+
+    // Verify the reliabilities are restored (and only the unreliable peer is persisted)
+    assert_eq!(
+        peer_states.get_reliability_weight(&reliable_peer),
+        unknown_weight
+    );
+    assert_eq!(
+        peer_states.get_reliability_weight(&unreliable_peer),
+        unreliable_weight
+    );
+    assert_eq!(peer_states.get_peer_reliabilities().len(), 1);
+}
+
+#[test]
+fn unknown_peers_not_preferred() {
+    // Create the peer states
+    let mut peer_states = create_peer_states();
+
+    // Update the reliability of a reliable and an unreliable peer
+    let reliable_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    let unreliable_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    for _ in 0..10 {
+        peer_states.update_score_success(reliable_peer);
+        peer_states.update_score_error(unreliable_peer, ErrorType::Malicious);
+    }
+
+    // Verify an unknown peer is weighted between the reliable and unreliable peer
+    let unknown_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    let unknown_weight = peer_states.get_reliability_weight(&unknown_peer);
+    assert!(unknown_weight < peer_states.get_reliability_weight(&reliable_peer));
+    assert!(unknown_weight > peer_states.get_reliability_weight(&unreliable_peer));
+}
+
+#[test]
+fn reliabilities_bounded() {
+    // Create the peer states
+    let mut peer_states = create_peer_states();
+
+    // Record an error for an unreliable peer, and a success for many reliable peers
+    let unreliable_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    peer_states.update_score_error(unreliable_peer, ErrorType::Malicious);
+    for _ in 0..MAX_NUM_PEER_RELIABILITIES + 10 {
+        let reliable_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+        peer_states.update_score_success(reliable_peer);
+    }
+
+    // Verify the reliabilities are bounded, and the unreliable peer isn't evicted
+    let peer_reliabilities = peer_states.get_peer_reliabilities();
+    assert_eq!(peer_reliabilities.len(), MAX_NUM_PEER_RELIABILITIES);
+    assert!(peer_reliabilities.contains_key(&unreliable_peer));
+}
+
+/// Creates peer states without reliability persistence
+fn create_peer_states() -> PeerStates {
+    let data_client_config = AptosDataClientConfig {
+        peer_reliability_persist_interval_ms: 0,
+        ..Default::default()
+    };
+    PeerStates::new(
+        BaseConfig::default(),
+        data_client_config,
+        PeersAndMetadata::new(&[NetworkId::Validator]),
+    )
+}
@@ -0,0 +1,120 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{DbBackedOnChainConfig, ReconfigNotification, ReconfigNotificationListener};
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_infallible::Mutex;
+use aptos_logger::{info, warn};
+use std::sync::Arc;
+
+// The maximum channel size for each epoch change subscriber. Subscribers only
+// care about the latest epoch, so older notifications are dropped.
+const EPOCH_CHANGE_CHANNEL_SIZE: usize = 1;
+
+/// The bus through which all runtime components (e.g., consensus, mempool, the
+/// network and the API) are notified of epoch changes. The bus is fed by the
+/// event subscription service whenever state sync or consensus commits a new
+/// epoch (or on startup), and guarantees that:
+///   1. All subscribers see the same epoch changes, in the same (version) order,
+///      i.e., stale notifications (for older versions) are never delivered.
+///   2. Subscribers that join late (e.g., after the service has been moved into
+///      state sync) are immediately notified of the latest epoch change.
+#[derive(Clone, Default)]
+pub struct EpochChangeNotifier {
+    epoch_change_bus: Arc<Mutex<EpochChangeBus>>,
+}
+
+impl EpochChangeNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a listener for epoch changes on behalf of the given subscriber (the
+    /// name is used for logging). If an epoch change has already been notified, the
+    /// listener will immediately hold the latest one.
+    pub fn subscribe(
+        &self,
+        subscriber: &str,
+    ) -> ReconfigNotificationListener<DbBackedOnChainConfig> {
+        let (notification_sender, notification_receiver) =
+            aptos_channel::new(QueueStyle::KLAST, EPOCH_CHANGE_CHANNEL_SIZE, None);
+        let epoch_change_subscription = EpochChangeSubscription {
+            subscriber: subscriber.into(),
+            notification_sender,
+        };
+
+        // Notify the subscriber of the latest epoch change (if any)
+        let mut epoch_change_bus = self.epoch_change_bus.lock();
+        if let Some(latest_notification) = &epoch_change_bus.latest_notification {
+            epoch_change_subscription.notify(latest_notification.clone());
+        }
+        epoch_change_bus
+            .subscriptions
+            .push(epoch_change_subscription);
+
+        ReconfigNotificationListener {
+            notification_receiver,
+        }
+    }
+
+    /// Notifies all subscribers (in the order they subscribed) of the given epoch
+    /// change. Notifications older than the latest epoch change are dropped, and
+    /// subscribers that have gone away are removed.
+    pub fn notify(&self, notification: ReconfigNotification<DbBackedOnChainConfig>) {
+        let mut epoch_change_bus = self.epoch_change_bus.lock();
+
+        // Ensure the notification isn't stale
+        if let Some(latest_notification) = &epoch_change_bus.latest_notification {
+            if notification.version < latest_notification.version {
+                warn!(
+                    "Dropping a stale epoch change notification! Version: {}, latest version: {}",
+                    notification.version, latest_notification.version
+                );
+                return;
+            }
+        }
+
+        // Notify the subscribers (and remove any that have gone away)
+        info!(
+            "Notifying {} subscribers of the epoch change. Epoch: {}, version: {}",
+            epoch_change_bus.subscriptions.len(),
+            notification.on_chain_configs.epoch(),
+            notification.version
+        );
+        epoch_change_bus
+            .subscriptions
+            .retain(|subscription| subscription.notify(notification.clone()));
+        epoch_change_bus.latest_notification = Some(notification);
+    }
+}
+
+/// The subscriptions to the epoch changes and the latest epoch change
+#[derive(Default)]
+struct EpochChangeBus {
+    subscriptions: Vec<EpochChangeSubscription>,
+    latest_notification: Option<ReconfigNotification<DbBackedOnChainConfig>>,
+}
+
+/// A single epoch change subscription, holding the subscriber name and the
+/// channel to send the notifications.
+struct EpochChangeSubscription {
+    subscriber: String,
+    notification_sender: aptos_channel::Sender<(), ReconfigNotification<DbBackedOnChainConfig>>,
+}
+
+impl EpochChangeSubscription {
+    /// Notifies the subscriber of the epoch change. Returns false iff the
+    /// subscriber has gone away (i.e., the listener has been dropped).
+    fn notify(&self, notification: ReconfigNotification<DbBackedOnChainConfig>) -> bool {
+        match self.notification_sender.push((), notification) {
+            Ok(()) => true,
+            Err(error) => {
+                warn!(
+                    "Removing the epoch change subscriber {:?}, as it has gone away: {:?}",
+                    self.subscriber, error
+                );
+                false
+            },
+        }
+    }
+}
@@ -32,14 +32,16 @@ use std::{
 };
 use thiserror::Error;
 
+mod epoch_change_notifier;
 #[cfg(test)]
 mod tests;
 
+pub use epoch_change_notifier::EpochChangeNotifier;
+
 // Maximum channel sizes for each notification subscriber. If messages are not
 // consumed, they will be dropped (oldest messages first). The remaining messages
 // will be retrieved using FIFO ordering.
 const EVENT_NOTIFICATION_CHANNEL_SIZE: usize = 100;
-const RECONFIG_NOTIFICATION_CHANNEL_SIZE: usize = 1;
 
 #[derive(Clone, Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
 pub enum Error {
@@ -79,8 +81,8 @@ pub struct EventSubscriptionService {
     event_key_subscriptions: HashMap<EventKey, HashSet<SubscriptionId>>,
     subscription_id_to_event_subscription: HashMap<SubscriptionId, EventSubscription>,
 
-    // Reconfig subscription registry
-    reconfig_subscriptions: HashMap<SubscriptionId, ReconfigSubscription>,
+    // The bus through which reconfiguration (i.e., epoch change) subscribers are notified
+    epoch_change_notifier: EpochChangeNotifier,
 
     // Database to fetch on-chain configuration data
     storage: Arc<RwLock<DbReaderWriter>>,
@@ -94,7 +96,7 @@ impl EventSubscriptionService {
         Self {
             event_key_subscriptions: HashMap::new(),
             subscription_id_to_event_subscription: HashMap::new(),
-            reconfig_subscriptions: HashMap::new(),
+            epoch_change_notifier: EpochChangeNotifier::new(),
             storage,
             subscription_id_generator: U64IdGenerator::new(),
         }
@@ -157,33 +159,20 @@ impl EventSubscriptionService {
     /// begins. Note: if the notification buffer fills up too quickly, older
     /// notifications will be dropped. As such, it is the responsibility of the
     /// subscriber to ensure notifications are processed in a timely manner.
+    ///
+    /// This is equivalent to subscribing via the epoch change notifier (see
+    /// `epoch_change_notifier()`), which should be preferred by named components.
     pub fn subscribe_to_reconfigurations(
         &mut self,
     ) -> Result<ReconfigNotificationListener<DbBackedOnChainConfig>, Error> {
-        let (notification_sender, notification_receiver) =
-            aptos_channel::new(QueueStyle::KLAST, RECONFIG_NOTIFICATION_CHANNEL_SIZE, None);
-
-        // Create a new reconfiguration subscription
-        let subscription_id = self.get_new_subscription_id();
-        let reconfig_subscription = ReconfigSubscription {
-            notification_sender,
-        };
-
-        // Store the new subscription
-        if self
-            .reconfig_subscriptions
-            .insert(subscription_id, reconfig_subscription)
-            .is_some()
-        {
-            return Err(Error::UnexpectedErrorEncountered(format!(
-                "Duplicate reconfiguration subscription found! This should not occur! ID: {}",
-                subscription_id,
-            )));
-        }
+        Ok(self.epoch_change_notifier.subscribe("reconfig_subscriber"))
+    }
 
-        Ok(ReconfigNotificationListener {
-            notification_receiver,
-        })
+    /// Returns the epoch change notifier that all reconfiguration subscribers are
+    /// notified through. Unlike the subscription service (which is owned by state
+    /// sync once it starts), the notifier can be cloned and subscribed to at any time.
+    pub fn epoch_change_notifier(&self) -> EpochChangeNotifier {
+        self.epoch_change_notifier.clone()
     }
 
     fn get_new_subscription_id(&mut self) -> u64 {
@@ -246,14 +235,11 @@ impl EventSubscriptionService {
     /// This notifies all the reconfiguration subscribers of the on-chain
     /// configurations at the specified version.
     fn notify_reconfiguration_subscribers(&mut self, version: Version) -> Result<(), Error> {
-        if self.reconfig_subscriptions.is_empty() {
-            return Ok(()); // No reconfiguration subscribers!
-        }
-
-        let new_configs = self.read_on_chain_configs(version)?;
-        for (_, reconfig_subscription) in self.reconfig_subscriptions.iter_mut() {
-            reconfig_subscription.notify_subscriber_of_configs(version, new_configs.clone())?;
-        }
+        let on_chain_configs = self.read_on_chain_configs(version)?;
+        self.epoch_change_notifier.notify(ReconfigNotification {
+            version,
+            on_chain_configs,
+        });
 
         Ok(())
     }
@@ -352,30 +338,6 @@ impl EventSubscription {
     }
 }
 
-/// A single reconfig subscription, holding the channel to send the
-/// corresponding notifications.
-struct ReconfigSubscription {
-    pub notification_sender:
-        aptos_channels::aptos_channel::Sender<(), ReconfigNotification<DbBackedOnChainConfig>>,
-}
-
-impl ReconfigSubscription {
-    fn notify_subscriber_of_configs(
-        &mut self,
-        version: Version,
-        on_chain_configs: OnChainConfigPayload<DbBackedOnChainConfig>,
-    ) -> Result<(), Error> {
-        let reconfig_notification = ReconfigNotification {
-            version,
-            on_chain_configs,
-        };
-
-        self.notification_sender
-            .push((), reconfig_notification)
-            .map_err(|error| Error::UnexpectedErrorEncountered(format!("{:?}", error)))
-    }
-}
-
 #[derive(Clone)]
 pub struct DbBackedOnChainConfig {
     pub reader: Arc<dyn DbReader>,
@@ -412,7 +374,7 @@ pub struct EventNotification {
 }
 
 /// A notification for reconfigurations.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ReconfigNotification<P: OnChainConfigProvider> {
     pub version: Version,
     pub on_chain_configs: OnChainConfigPayload<P>,
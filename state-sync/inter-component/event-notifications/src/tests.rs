@@ -5,8 +5,9 @@
 #![forbid(unsafe_code)]
 
 use crate::{
-    DbBackedOnChainConfig, Error, EventNotificationListener, EventNotificationSender,
-    EventSubscriptionService, ReconfigNotificationListener,
+    DbBackedOnChainConfig, EpochChangeNotifier, Error, EventNotificationListener,
+    EventNotificationSender, EventSubscriptionService, ReconfigNotification,
+    ReconfigNotificationListener,
 };
 use aptos_db::AptosDB;
 use aptos_executor_test_helpers::bootstrap_genesis;
@@ -17,7 +18,7 @@ use aptos_types::{
     contract_event::ContractEvent,
     event::EventKey,
     on_chain_config,
-    on_chain_config::OnChainConfig,
+    on_chain_config::{OnChainConfig, OnChainConfigPayload},
     transaction::{Transaction, Version, WriteSetPayload},
 };
 use aptos_vm::AptosVM;
@@ -375,6 +376,76 @@ fn test_no_events_no_subscribers() {
     notify_events(&mut event_service, 1, vec![]);
 }
 
+#[test]
+fn test_epoch_change_late_subscribers() {
+    // Create subscription service and mock database
+    let mut event_service = create_event_subscription_service();
+    let epoch_change_notifier = event_service.epoch_change_notifier();
+
+    // Subscribe to epoch changes and notify the initial configs
+    let mut listener_1 = epoch_change_notifier.subscribe("listener_1");
+    notify_initial_configs(&mut event_service, 0);
+    verify_reconfig_notifications_received(vec![&mut listener_1], 0, 1);
+
+    // Verify a late subscriber is immediately notified of the latest epoch change
+    let mut listener_2 = epoch_change_notifier.subscribe("listener_2");
+    verify_reconfig_notifications_received(vec![&mut listener_2], 0, 1);
+    verify_no_reconfig_notifications(vec![&mut listener_1, &mut listener_2]);
+
+    // Verify both subscribers are notified of new epoch changes
+    let reconfig_event = create_test_event(on_chain_config::new_epoch_event_key());
+    notify_events(&mut event_service, 0, vec![reconfig_event]);
+    verify_reconfig_notifications_received(vec![&mut listener_1, &mut listener_2], 0, 1);
+}
+
+#[test]
+fn test_epoch_change_subscriber_after_notification() {
+    // Create subscription service and mock database
+    let mut event_service = create_event_subscription_service();
+
+    // Notify the initial configs before anyone subscribes to epoch changes
+    notify_initial_configs(&mut event_service, 0);
+
+    // Verify the first subscriber is still notified of the latest epoch change
+    let mut listener = event_service.epoch_change_notifier().subscribe("listener");
+    verify_reconfig_notifications_received(vec![&mut listener], 0, 1);
+}
+
+#[test]
+fn test_epoch_change_stale_notifications() {
+    // Create an epoch change notifier and subscriber
+    let epoch_change_notifier = EpochChangeNotifier::new();
+    let mut listener = epoch_change_notifier.subscribe("listener");
+
+    // Notify the subscriber of an epoch change
+    let db_rw = create_database();
+    epoch_change_notifier.notify(create_reconfig_notification(&db_rw, 10, 2));
+    verify_reconfig_notifications_received(vec![&mut listener], 10, 2);
+
+    // Verify an older epoch change is dropped
+    epoch_change_notifier.notify(create_reconfig_notification(&db_rw, 5, 1));
+    verify_no_reconfig_notifications(vec![&mut listener]);
+
+    // Verify a newer epoch change is delivered
+    epoch_change_notifier.notify(create_reconfig_notification(&db_rw, 20, 3));
+    verify_reconfig_notifications_received(vec![&mut listener], 20, 3);
+}
+
+#[test]
+fn test_epoch_change_dropped_subscribers() {
+    // Create subscription service and mock database
+    let mut event_service = create_event_subscription_service();
+
+    // Create two subscribers and drop the first
+    let listener_1 = event_service.subscribe_to_reconfigurations().unwrap();
+    let mut listener_2 = event_service.subscribe_to_reconfigurations().unwrap();
+    drop(listener_1);
+
+    // Verify the remaining subscriber is still notified
+    notify_initial_configs(&mut event_service, 0);
+    verify_reconfig_notifications_received(vec![&mut listener_2], 0, 1);
+}
+
 /// Defines a new on-chain config for test purposes.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct TestOnChainConfig {
@@ -499,6 +570,18 @@ fn create_random_event_key() -> EventKey {
     EventKey::new(0, AccountAddress::random())
 }
 
+fn create_reconfig_notification(
+    db_rw: &Arc<RwLock<DbReaderWriter>>,
+    version: Version,
+    epoch: u64,
+) -> ReconfigNotification<DbBackedOnChainConfig> {
+    let on_chain_config = DbBackedOnChainConfig::new(db_rw.read().reader.clone(), version);
+    ReconfigNotification {
+        version,
+        on_chain_configs: OnChainConfigPayload::new(epoch, on_chain_config),
+    }
+}
+
 fn create_event_subscription_service() -> EventSubscriptionService {
     EventSubscriptionService::new(create_database())
 }
@@ -10,9 +10,11 @@ use serde::{Deserialize, Serialize};
 use std::{
     fmt,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 use thiserror::Error;
+use tokio::sync::watch;
 
 // Note: we limit the queue depth to 1 because it doesn't make sense for the storage service
 // to execute for every notification (because it reads the latest version in the DB). Thus,
@@ -59,21 +61,36 @@ pub fn new_storage_service_notifier_listener_pair(
 #[derive(Clone, Debug)]
 pub struct StorageServiceNotifier {
     notification_sender: aptos_channel::Sender<(), StorageServiceCommitNotification>,
+    // Publishes the highest synced version to the commit subscribers (e.g., the API)
+    highest_synced_version_sender: Arc<watch::Sender<u64>>,
 }
 
 impl StorageServiceNotifier {
     fn new(
         notification_sender: aptos_channel::Sender<(), StorageServiceCommitNotification>,
     ) -> Self {
+        let (highest_synced_version_sender, _) = watch::channel(0);
         Self {
             notification_sender,
+            highest_synced_version_sender: Arc::new(highest_synced_version_sender),
         }
     }
+
+    /// Subscribes to the commit notifications sent to the storage service. The
+    /// receiver always holds the highest synced version, so slow subscribers only
+    /// miss intermediate versions (and never block the driver).
+    pub fn subscribe_to_commits(&self) -> watch::Receiver<u64> {
+        self.highest_synced_version_sender.subscribe()
+    }
 }
 
 #[async_trait]
 impl StorageServiceNotificationSender for StorageServiceNotifier {
     async fn notify_new_commit(&self, highest_synced_version: u64) -> Result<(), Error> {
+        // Notify the commit subscribers
+        self.highest_synced_version_sender
+            .send_replace(highest_synced_version);
+
         // Create a new commit notification
         let commit_notification = StorageServiceCommitNotification {
             highest_synced_version,
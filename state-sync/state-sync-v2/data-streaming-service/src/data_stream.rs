@@ -15,6 +15,8 @@ use crate::{
     logging::{LogEntry, LogEvent, LogSchema},
     metrics,
     metrics::{increment_counter, increment_counter_multiple, start_timer},
+    prefetch_window::PrefetchWindow,
+    stream_checkpoint::StreamCheckpoint,
     stream_engine::{DataStreamEngine, StreamEngine},
     streaming_client::{NotificationFeedback, StreamRequest},
 };
@@ -28,6 +30,7 @@ use aptos_data_client::{
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
+use aptos_retrier::RetryPolicy;
 use futures::{channel::mpsc, stream::FusedStream, SinkExt, Stream};
 use std::{
     cmp::min,
@@ -42,6 +45,10 @@ use tokio::task::JoinHandle;
 // The frequency at which to log sent data request messages
 const SENT_REQUESTS_LOG_FREQ_SECS: u64 = 1;
 
+// The maximum number of unacknowledged stream checkpoints held by a listener.
+// If exceeded, the checkpoints of the oldest notifications are dropped.
+const MAX_PENDING_STREAM_CHECKPOINTS: usize = 1000;
+
 /// A unique ID used to identify each stream.
 pub type DataStreamId = u64;
 
@@ -95,10 +102,23 @@ pub struct DataStream<T> {
     stream_end_notification_id: Option<NotificationId>,
 
     // The current failure count of the request at the head of the request queue.
-    // If this count becomes too large, the stream is evidently blocked (i.e.,
-    // unable to make progress) and will automatically terminate.
+    // If the retry policy doesn't allow another retry, the stream is evidently
+    // blocked (i.e., unable to make progress) and will automatically terminate.
     request_failure_count: u64,
 
+    // The policy for retrying failed data client requests (i.e., the backoff
+    // before resending a request, and the max number of retries).
+    request_retry_policy: RetryPolicy,
+
+    // The prefetch window that determines the number of concurrent data client
+    // requests to send. The window is tuned based on how promptly notifications
+    // are consumed by the listener and on request timeouts.
+    prefetch_window: PrefetchWindow,
+
+    // Whether prefetching is paused (e.g., while the node sheds memory). If so,
+    // only a single data client request may be in flight.
+    prefetching_paused: bool,
+
     // Whether the data stream has encountered an error trying to send a
     // notification to the listener. If so, the stream is dead and it will
     // stop sending notifications. This handles when clients drop the listener.
@@ -118,11 +138,31 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         // Create a new data stream listener
         let (notification_sender, notification_receiver) =
             mpsc::channel(data_stream_config.max_data_stream_channel_sizes as usize);
-        let data_stream_listener = DataStreamListener::new(data_stream_id, notification_receiver);
+        let data_stream_listener = DataStreamListener::new(
+            data_stream_id,
+            stream_request.clone(),
+            notification_receiver,
+        );
 
         // Create a new stream engine
         let stream_engine = StreamEngine::new(stream_request, advertised_data)?;
 
+        // Create a new prefetch window (bounded by the max concurrent requests)
+        let prefetch_window = PrefetchWindow::new(
+            data_stream_config.enable_prefetch_window_tuning,
+            data_stream_config.min_concurrent_requests,
+            get_max_concurrent_requests(&data_stream_config, &stream_engine),
+        );
+
+        // Create the retry policy for failed data client requests
+        let request_retry_policy = RetryPolicy::exponential(Duration::from_millis(
+            data_stream_config.request_retry_delay_ms,
+        ))
+        .with_max_delay(Duration::from_millis(
+            data_stream_config.max_request_retry_delay_ms,
+        ))
+        .with_max_retries(data_stream_config.max_request_retry as u32);
+
         // Create a new data stream
         let data_stream = Self {
             data_client_config,
@@ -137,6 +177,9 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             notification_id_generator,
             stream_end_notification_id: None,
             request_failure_count: 0,
+            request_retry_policy,
+            prefetch_window,
+            prefetching_paused: false,
             send_failure: false,
         };
 
@@ -208,12 +251,19 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
     /// Returns the maximum number of concurrent requests that can be executing
     /// at any given time.
     fn get_max_concurrent_requests(&self) -> u64 {
-        match self.stream_engine {
-            StreamEngine::StateStreamEngine(_) => {
-                self.streaming_service_config.max_concurrent_state_requests
-            },
-            _ => self.streaming_service_config.max_concurrent_requests,
-        }
+        get_max_concurrent_requests(&self.streaming_service_config, &self.stream_engine)
+    }
+
+    /// Returns the current prefetch window size (i.e., the number of
+    /// concurrent requests that should be executing at any given time).
+    pub fn get_prefetch_window_size(&self) -> u64 {
+        self.prefetch_window.get_window_size()
+    }
+
+    /// Pauses (or resumes) prefetching. While paused, the stream only sends a
+    /// single request at a time (i.e., for the data it needs next).
+    pub fn set_prefetching_paused(&mut self, prefetching_paused: bool) {
+        self.prefetching_paused = prefetching_paused;
     }
 
     /// Creates and sends a batch of aptos data client requests to the network
@@ -221,14 +271,15 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         &mut self,
         global_data_summary: &GlobalDataSummary,
     ) -> Result<(), Error> {
-        // Determine how many requests (at most) can be sent to the network
+        // Determine how many requests (at most) can be sent to the network. Note:
+        // the number of sent requests may exceed the window if it has just shrunk.
         let num_sent_requests = self.get_sent_data_requests()?.len() as u64;
-        let max_concurrent_requests = self.get_max_concurrent_requests();
-        let max_num_requests_to_send = max_concurrent_requests
-            .checked_sub(num_sent_requests)
-            .ok_or_else(|| {
-                Error::IntegerOverflow("Max number of requests to send has overflown!".into())
-            })?;
+        let prefetch_window_size = if self.prefetching_paused {
+            1
+        } else {
+            self.prefetch_window.get_window_size()
+        };
+        let max_num_requests_to_send = prefetch_window_size.saturating_sub(num_sent_requests);
 
         // Send the client requests
         if max_num_requests_to_send > 0 {
@@ -259,15 +310,17 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             );
         }
 
-        // Update the counters for the pending response queue
+        // Update the counters for the pending response queue and prefetch window
         metrics::set_pending_data_responses(self.get_sent_data_requests()?.len());
+        metrics::set_prefetch_window_size(prefetch_window_size);
 
         Ok(())
     }
 
     /// Sends a given request to the data client to be forwarded to the network
     /// and returns a pending client response. If `request_retry` is true
-    /// exponential backoff takes affect (i.e., to increase the request timeout).
+    /// exponential backoff takes affect (i.e., the request is resent after the
+    /// backoff delay of the retry policy, with an increased request timeout).
     fn send_client_request(
         &mut self,
         request_retry: bool,
@@ -281,12 +334,16 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             },
         )));
 
-        // Calculate the request timeout to use, based on the
-        // request type and the number of previous failures.
-        let request_timeout_ms = if is_optimistic_fetch_request(&data_client_request) {
-            self.data_client_config.optimistic_fetch_timeout_ms
+        // Calculate the request timeout (and the delay before sending the request)
+        // to use, based on the request type and the number of previous failures.
+        let (request_timeout_ms, retry_delay) = if is_optimistic_fetch_request(&data_client_request)
+        {
+            (
+                self.data_client_config.optimistic_fetch_timeout_ms,
+                Duration::ZERO,
+            )
         } else if !request_retry {
-            self.data_client_config.response_timeout_ms
+            (self.data_client_config.response_timeout_ms, Duration::ZERO)
         } else {
             let response_timeout_ms = self.data_client_config.response_timeout_ms;
             let max_response_timeout_ms = self.data_client_config.max_response_timeout_ms;
@@ -298,6 +355,12 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
                 response_timeout_ms * (u32::pow(2, self.request_failure_count as u32) as u64),
             );
 
+            // Back off before resending the request (the failure count
+            // already includes the failure that caused this retry).
+            let retry_delay = self
+                .request_retry_policy
+                .delay_for_retry(self.request_failure_count.saturating_sub(1) as u32);
+
             // Update the retry counter and log the request
             increment_counter_multiple(
                 &metrics::RETRIED_DATA_REQUESTS,
@@ -308,13 +371,14 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
                 (LogSchema::new(LogEntry::RetryDataRequest)
                     .stream_id(self.data_stream_id)
                     .message(&format!(
-                        "Retrying data request type: {:?}, with new timeout: {:?} (ms)",
+                        "Retrying data request type: {:?}, with new timeout: {:?} (ms), after a delay of: {:?} (ms)",
                         data_client_request.get_label(),
-                        request_timeout_ms.to_string()
+                        request_timeout_ms.to_string(),
+                        retry_delay.as_millis().to_string()
                     )))
             );
 
-            request_timeout_ms
+            (request_timeout_ms, retry_delay)
         };
 
         // Send the request to the network
@@ -323,6 +387,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             self.aptos_data_client.clone(),
             pending_client_response.clone(),
             request_timeout_ms,
+            retry_delay,
         );
         self.spawned_tasks.push(join_handle);
 
@@ -335,6 +400,20 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         &mut self,
         data_notification: DataNotification,
     ) -> Result<(), Error> {
+        // Try to send the notification without blocking. If the channel is full,
+        // the listener isn't keeping up, so we shrink the prefetch window.
+        let data_notification = match self.notification_sender.try_send(data_notification) {
+            Ok(()) => {
+                self.prefetch_window.handle_prompt_consumption();
+                return Ok(());
+            },
+            Err(error) if error.is_full() => {
+                self.prefetch_window.handle_backpressure();
+                error.into_inner()
+            },
+            Err(error) => error.into_inner(), // The send will fail below
+        };
+
         if let Err(error) = self.notification_sender.send(data_notification).await {
             let error = Error::UnexpectedErrorEncountered(error.to_string());
             warn!(
@@ -382,7 +461,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         global_data_summary: GlobalDataSummary,
     ) -> Result<(), Error> {
         if self.stream_engine.is_stream_complete()
-            || self.request_failure_count >= self.streaming_service_config.max_request_retry
+            || !self.request_retry_allowed()
             || self.send_failure
         {
             if !self.send_failure && self.stream_end_notification_id.is_none() {
@@ -416,14 +495,18 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
                     Err(error) => {
                         // If the error was a timeout and the request was an optimistic fetch
                         // we need to notify the stream engine and not retry the request.
-                        if matches!(
+                        // Otherwise, timeouts shrink the prefetch window before the retry.
+                        let is_timeout = matches!(
                             error,
                             aptos_data_client::error::Error::TimeoutWaitingForResponse(_)
-                        ) && is_optimistic_fetch_request(client_request)
-                        {
+                        );
+                        if is_timeout && is_optimistic_fetch_request(client_request) {
                             self.stream_engine
                                 .notify_optimistic_fetch_timeout(client_request)?;
                         } else {
+                            if is_timeout {
+                                self.prefetch_window.handle_request_timeout();
+                            }
                             self.handle_data_client_error(client_request, &error)?;
                         };
                         break;
@@ -487,6 +570,13 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         self.resend_data_client_request(data_client_request)
     }
 
+    /// Returns true iff the retry policy allows the request at the head of the
+    /// queue to be retried again (the policy only bounds the number of retries).
+    fn request_retry_allowed(&self) -> bool {
+        self.request_retry_policy
+            .should_retry(self.request_failure_count as u32, Duration::ZERO)
+    }
+
     /// Resends a failed data client request and pushes the pending notification
     /// to the head of the pending notifications batch.
     fn resend_data_client_request(
@@ -674,6 +764,19 @@ impl<T> Drop for DataStream<T> {
     }
 }
 
+/// Returns the maximum number of concurrent requests for the given stream engine
+fn get_max_concurrent_requests(
+    streaming_service_config: &DataStreamingServiceConfig,
+    stream_engine: &StreamEngine,
+) -> u64 {
+    match stream_engine {
+        StreamEngine::StateStreamEngine(_) => {
+            streaming_service_config.max_concurrent_state_requests
+        },
+        _ => streaming_service_config.max_concurrent_requests,
+    }
+}
+
 /// Allows listening to data streams (i.e., streams of data notifications).
 #[derive(Debug)]
 pub struct DataStreamListener {
@@ -682,26 +785,90 @@ pub struct DataStreamListener {
 
     /// Stores the number of consecutive timeouts encountered when listening to this stream
     pub num_consecutive_timeouts: u64,
+
+    /// Tracks the progress of the notifications acknowledged by the client
+    stream_checkpoint: StreamCheckpoint,
+
+    /// The checkpoints following each received (but unacknowledged) notification
+    pending_stream_checkpoints: VecDeque<(NotificationId, StreamCheckpoint)>,
 }
 
 impl DataStreamListener {
     pub fn new(
         data_stream_id: DataStreamId,
+        stream_request: StreamRequest,
         notification_receiver: mpsc::Receiver<DataNotification>,
     ) -> Self {
         Self {
             data_stream_id,
             notification_receiver,
             num_consecutive_timeouts: 0,
+            stream_checkpoint: StreamCheckpoint::new(stream_request),
+            pending_stream_checkpoints: VecDeque::new(),
         }
     }
+
+    /// Acknowledges that the client has processed the specified notification
+    /// (and all notifications received before it). This advances the stream
+    /// checkpoint past the acknowledged notification. Unknown notification
+    /// IDs are ignored.
+    pub fn acknowledge_notification(&mut self, notification_id: NotificationId) {
+        if !self
+            .pending_stream_checkpoints
+            .iter()
+            .any(|(pending_id, _)| *pending_id == notification_id)
+        {
+            return; // The notification was never received (or is already acknowledged)
+        }
+
+        while let Some((pending_id, stream_checkpoint)) =
+            self.pending_stream_checkpoints.pop_front()
+        {
+            self.stream_checkpoint = stream_checkpoint;
+            if pending_id == notification_id {
+                break;
+            }
+        }
+    }
+
+    /// Returns a checkpoint of the stream's progress (i.e., accounting for
+    /// all notifications acknowledged by the client). The checkpoint can be
+    /// used to recreate an equivalent stream (see `resume_stream()`).
+    pub fn get_stream_checkpoint(&self) -> StreamCheckpoint {
+        self.stream_checkpoint.clone()
+    }
+
+    /// Records the stream checkpoint following the given (received) notification
+    fn add_pending_stream_checkpoint(&mut self, data_notification: &DataNotification) {
+        let mut stream_checkpoint = self
+            .pending_stream_checkpoints
+            .back()
+            .map(|(_, stream_checkpoint)| stream_checkpoint.clone())
+            .unwrap_or_else(|| self.stream_checkpoint.clone());
+        stream_checkpoint.update(&data_notification.data_payload);
+
+        if self.pending_stream_checkpoints.len() >= MAX_PENDING_STREAM_CHECKPOINTS {
+            self.pending_stream_checkpoints.pop_front();
+        }
+        self.pending_stream_checkpoints
+            .push_back((data_notification.notification_id, stream_checkpoint));
+    }
 }
 
 impl Stream for DataStreamListener {
     type Item = DataNotification;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Pin::new(&mut self.get_mut().notification_receiver).poll_next(cx)
+        let data_stream_listener = self.get_mut();
+        let poll_result = Pin::new(&mut data_stream_listener.notification_receiver).poll_next(cx);
+
+        // Track the stream checkpoint following the received notification. The
+        // checkpoint only advances once the client acknowledges the notification.
+        if let Poll::Ready(Some(data_notification)) = &poll_result {
+            data_stream_listener.add_pending_stream_checkpoint(data_notification);
+        }
+
+        poll_result
     }
 }
 
@@ -802,6 +969,7 @@ fn spawn_request_task<T: AptosDataClientInterface + Send + Clone + 'static>(
     aptos_data_client: T,
     pending_response: PendingClientResponse,
     request_timeout_ms: u64,
+    retry_delay: Duration,
 ) -> JoinHandle<()> {
     // Update the requests sent counter
     increment_counter(
@@ -811,6 +979,11 @@ fn spawn_request_task<T: AptosDataClientInterface + Send + Clone + 'static>(
 
     // Spawn the request
     tokio::spawn(async move {
+        // Back off before resending a failed request
+        if !retry_delay.is_zero() {
+            tokio::time::sleep(retry_delay).await;
+        }
+
         // Time the request (the timer will stop when it's dropped)
         let _timer = start_timer(
             &metrics::DATA_REQUEST_PROCESSING_LATENCY,
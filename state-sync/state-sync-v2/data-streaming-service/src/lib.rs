@@ -8,7 +8,9 @@ pub mod data_notification;
 pub mod data_stream;
 pub mod error;
 mod logging;
-mod metrics;
+pub mod metrics;
+mod prefetch_window;
+pub mod stream_checkpoint;
 mod stream_engine;
 pub mod streaming_client;
 pub mod streaming_service;
@@ -102,6 +102,26 @@ pub static PENDING_DATA_RESPONSES: Lazy<IntGauge> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for the current prefetch window size (i.e., the max number of
+/// concurrent data requests) of the most recently active data stream
+pub static PREFETCH_WINDOW_SIZE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_data_streaming_service_prefetch_window_size",
+        "Counters related to the prefetch window size of data streams",
+    )
+    .unwrap()
+});
+
+/// Counter for tracking prefetch window adjustments
+pub static PREFETCH_WINDOW_ADJUSTMENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_data_streaming_service_prefetch_window_adjustments",
+        "Counters related to prefetch window adjustments",
+        &["adjustment_type"]
+    )
+    .unwrap()
+});
+
 /// Counter for tracking received data responses
 pub static RECEIVED_DATA_RESPONSE: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -158,6 +178,11 @@ pub fn set_pending_data_responses(value: usize) {
     PENDING_DATA_RESPONSES.set(value as i64);
 }
 
+/// Sets the current prefetch window size
+pub fn set_prefetch_window_size(value: u64) {
+    PREFETCH_WINDOW_SIZE.set(value as i64);
+}
+
 /// Starts the timer for the provided histogram and label values.
 pub fn start_timer(histogram: &Lazy<HistogramVec>, label: String) -> HistogramTimer {
     histogram.with_label_values(&[&label]).start_timer()
@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics;
+use std::cmp::{max, min};
+
+// Useful labels for the prefetch window adjustments
+const DECREASE_BACKPRESSURE: &str = "decrease_backpressure";
+const DECREASE_TIMEOUT: &str = "decrease_timeout";
+const INCREASE_PROMPT_CONSUMPTION: &str = "increase_prompt_consumption";
+
+/// The prefetch window determines the number of concurrent data client requests
+/// that a data stream may have in flight. The window size is tuned using an
+/// additive increase/multiplicative decrease (AIMD) controller: the window grows
+/// by one each time a notification is promptly consumed by the stream listener,
+/// and is halved on request timeouts or when the listener applies backpressure
+/// (i.e., the notification channel is full).
+#[derive(Clone, Debug)]
+pub struct PrefetchWindow {
+    auto_tuning_enabled: bool, // Whether the window size is tuned (or static)
+    min_window_size: u64,      // The smallest the window may shrink to
+    max_window_size: u64,      // The largest the window may grow to
+    window_size: u64,          // The current window size
+}
+
+impl PrefetchWindow {
+    pub fn new(auto_tuning_enabled: bool, min_window_size: u64, max_window_size: u64) -> Self {
+        // Ensure the window bounds are sane (the window must always allow a request)
+        let max_window_size = max(max_window_size, 1);
+        let min_window_size = min(max(min_window_size, 1), max_window_size);
+
+        // The window starts fully open, so streams don't pay a slow start penalty
+        Self {
+            auto_tuning_enabled,
+            min_window_size,
+            max_window_size,
+            window_size: max_window_size,
+        }
+    }
+
+    /// Returns the current window size
+    pub fn get_window_size(&self) -> u64 {
+        self.window_size
+    }
+
+    /// Grows the window additively, as a notification was promptly
+    /// consumed by the stream listener.
+    pub fn handle_prompt_consumption(&mut self) {
+        if self.auto_tuning_enabled && self.window_size < self.max_window_size {
+            self.window_size += 1;
+            metrics::increment_counter(
+                &metrics::PREFETCH_WINDOW_ADJUSTMENTS,
+                INCREASE_PROMPT_CONSUMPTION,
+            );
+        }
+    }
+
+    /// Shrinks the window multiplicatively, as the stream listener
+    /// is applying backpressure (i.e., it is not keeping up).
+    pub fn handle_backpressure(&mut self) {
+        self.decrease_window_size(DECREASE_BACKPRESSURE);
+    }
+
+    /// Shrinks the window multiplicatively, as a data client request
+    /// timed out (e.g., the network or peers are overloaded).
+    pub fn handle_request_timeout(&mut self) {
+        self.decrease_window_size(DECREASE_TIMEOUT);
+    }
+
+    /// Halves the window size (bounded by the min window size)
+    fn decrease_window_size(&mut self, adjustment_label: &str) {
+        if self.auto_tuning_enabled && self.window_size > self.min_window_size {
+            self.window_size = max(self.window_size / 2, self.min_window_size);
+            metrics::increment_counter(&metrics::PREFETCH_WINDOW_ADJUSTMENTS, adjustment_label);
+        }
+    }
+}
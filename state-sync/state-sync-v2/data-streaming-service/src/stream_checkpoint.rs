@@ -0,0 +1,231 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{data_notification::DataPayload, error::Error, streaming_client::StreamRequest};
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
+use serde::{Deserialize, Serialize};
+use std::{fs, io::ErrorKind, path::Path};
+
+/// A serializable checkpoint (i.e., resume token) of a data stream's progress.
+/// The checkpoint holds the stream request for the remainder of the stream
+/// (i.e., all data after the notifications already acknowledged by the client),
+/// so an equivalent stream can be recreated from it, e.g., after a restart.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StreamCheckpoint {
+    stream_request: StreamRequest, // The request for the remainder of the stream
+    stream_complete: bool,         // Whether the stream has already completed
+}
+
+impl StreamCheckpoint {
+    pub fn new(stream_request: StreamRequest) -> Self {
+        Self {
+            stream_request,
+            stream_complete: false,
+        }
+    }
+
+    /// Returns the request for the remainder of the stream
+    pub fn get_stream_request(&self) -> &StreamRequest {
+        &self.stream_request
+    }
+
+    /// Returns true iff the stream has completed (i.e., there
+    /// is no more data to fetch when resuming the stream).
+    pub fn is_stream_complete(&self) -> bool {
+        self.stream_complete
+    }
+
+    /// Reads a checkpoint previously persisted to the given file (see
+    /// `write_to_file()`). Returns None if the file doesn't exist.
+    pub fn read_from_file(path: &Path) -> Result<Option<Self>, Error> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(Error::UnexpectedErrorEncountered(format!(
+                    "Failed to read the stream checkpoint from {:?}! Error: {:?}",
+                    path, error
+                )))
+            },
+        };
+        bcs::from_bytes(&bytes).map(Some).map_err(|error| {
+            Error::UnexpectedErrorEncountered(format!(
+                "Failed to deserialize the stream checkpoint from {:?}! Error: {:?}",
+                path, error
+            ))
+        })
+    }
+
+    /// Persists the checkpoint to the given file, so the stream can be resumed
+    /// after a restart. The checkpoint is first written to a temporary file
+    /// (which is then renamed), so a crash never leaves a partial checkpoint.
+    pub fn write_to_file(&self, path: &Path) -> Result<(), Error> {
+        let bytes = bcs::to_bytes(self).map_err(|error| {
+            Error::UnexpectedErrorEncountered(format!(
+                "Failed to serialize the stream checkpoint! Error: {:?}",
+                error
+            ))
+        })?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, bytes)
+            .and_then(|()| fs::rename(&temp_path, path))
+            .map_err(|error| {
+                Error::UnexpectedErrorEncountered(format!(
+                    "Failed to write the stream checkpoint to {:?}! Error: {:?}",
+                    path, error
+                ))
+            })
+    }
+
+    /// Updates the checkpoint to account for the given (acknowledged) data payload.
+    /// Payloads that don't match the stream request are ignored.
+    pub fn update(&mut self, data_payload: &DataPayload) {
+        match (&mut self.stream_request, data_payload) {
+            (_, DataPayload::EndOfStream) => {
+                self.stream_complete = true;
+            },
+            (
+                StreamRequest::GetAllEpochEndingLedgerInfos(request),
+                DataPayload::EpochEndingLedgerInfos(ledger_infos),
+            ) => {
+                if let Some(ledger_info) = ledger_infos.last() {
+                    request.start_epoch = ledger_info.ledger_info().epoch().saturating_add(1);
+                }
+            },
+            (StreamRequest::GetAllStates(request), DataPayload::StateValuesWithProof(chunk)) => {
+                request.start_index = chunk.last_index.saturating_add(1);
+                self.stream_complete |= chunk.is_last_chunk();
+            },
+            (
+                StreamRequest::GetAllTransactions(request),
+                DataPayload::TransactionsWithProof(transaction_list),
+            ) => {
+                if let Some(next_version) = get_next_version(
+                    transaction_list.first_transaction_version,
+                    transaction_list.transactions.len(),
+                ) {
+                    request.start_version = next_version;
+                    self.stream_complete |= next_version > request.end_version;
+                }
+            },
+            (
+                StreamRequest::GetAllTransactionOutputs(request),
+                DataPayload::TransactionOutputsWithProof(output_list),
+            ) => {
+                if let Some(next_version) = get_next_version(
+                    output_list.first_transaction_output_version,
+                    output_list.transactions_and_outputs.len(),
+                ) {
+                    request.start_version = next_version;
+                    self.stream_complete |= next_version > request.end_version;
+                }
+            },
+            (StreamRequest::GetAllTransactionsOrOutputs(request), data_payload) => {
+                let next_version = match data_payload {
+                    DataPayload::TransactionsWithProof(transaction_list) => get_next_version(
+                        transaction_list.first_transaction_version,
+                        transaction_list.transactions.len(),
+                    ),
+                    DataPayload::TransactionOutputsWithProof(output_list) => get_next_version(
+                        output_list.first_transaction_output_version,
+                        output_list.transactions_and_outputs.len(),
+                    ),
+                    _ => None,
+                };
+                if let Some(next_version) = next_version {
+                    request.start_version = next_version;
+                    self.stream_complete |= next_version > request.end_version;
+                }
+            },
+            (
+                StreamRequest::ContinuouslyStreamTransactions(request),
+                DataPayload::ContinuousTransactionsWithProof(ledger_info, transaction_list),
+            ) => {
+                if let Some(next_version) = get_next_version(
+                    transaction_list.first_transaction_version,
+                    transaction_list.transactions.len(),
+                ) {
+                    let (known_version, known_epoch) =
+                        get_known_version_and_epoch(next_version, ledger_info);
+                    request.known_version = known_version;
+                    request.known_epoch = known_epoch;
+                    self.stream_complete |= target_reached(known_version, &request.target);
+                }
+            },
+            (
+                StreamRequest::ContinuouslyStreamTransactionOutputs(request),
+                DataPayload::ContinuousTransactionOutputsWithProof(ledger_info, output_list),
+            ) => {
+                if let Some(next_version) = get_next_version(
+                    output_list.first_transaction_output_version,
+                    output_list.transactions_and_outputs.len(),
+                ) {
+                    let (known_version, known_epoch) =
+                        get_known_version_and_epoch(next_version, ledger_info);
+                    request.known_version = known_version;
+                    request.known_epoch = known_epoch;
+                    self.stream_complete |= target_reached(known_version, &request.target);
+                }
+            },
+            (StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request), data_payload) => {
+                let next_version_and_ledger_info = match data_payload {
+                    DataPayload::ContinuousTransactionsWithProof(ledger_info, transaction_list) => {
+                        get_next_version(
+                            transaction_list.first_transaction_version,
+                            transaction_list.transactions.len(),
+                        )
+                        .map(|next_version| (next_version, ledger_info))
+                    },
+                    DataPayload::ContinuousTransactionOutputsWithProof(
+                        ledger_info,
+                        output_list,
+                    ) => get_next_version(
+                        output_list.first_transaction_output_version,
+                        output_list.transactions_and_outputs.len(),
+                    )
+                    .map(|next_version| (next_version, ledger_info)),
+                    _ => None,
+                };
+                if let Some((next_version, ledger_info)) = next_version_and_ledger_info {
+                    let (known_version, known_epoch) =
+                        get_known_version_and_epoch(next_version, ledger_info);
+                    request.known_version = known_version;
+                    request.known_epoch = known_epoch;
+                    self.stream_complete |= target_reached(known_version, &request.target);
+                }
+            },
+            _ => {}, // The payload doesn't match the stream request
+        }
+    }
+}
+
+/// Returns the version following the given (non-empty) list of data
+fn get_next_version(first_version: Option<Version>, num_versions: usize) -> Option<Version> {
+    if num_versions == 0 {
+        return None;
+    }
+    first_version.map(|first_version| first_version.saturating_add(num_versions as u64))
+}
+
+/// Returns the known version and epoch after receiving all data up to (but
+/// excluding) the next version, using the ledger info that proves the data.
+fn get_known_version_and_epoch(
+    next_version: Version,
+    ledger_info: &LedgerInfoWithSignatures,
+) -> (Version, u64) {
+    let known_version = next_version.saturating_sub(1);
+    let ledger_info = ledger_info.ledger_info();
+    if known_version == ledger_info.version() {
+        (known_version, ledger_info.next_block_epoch()) // Accounts for epoch changes
+    } else {
+        (known_version, ledger_info.epoch())
+    }
+}
+
+/// Returns true iff the known version has reached the (optional) stream target
+fn target_reached(known_version: Version, target: &Option<LedgerInfoWithSignatures>) -> bool {
+    target
+        .as_ref()
+        .map(|target| known_version >= target.ledger_info().version())
+        .unwrap_or(false)
+}
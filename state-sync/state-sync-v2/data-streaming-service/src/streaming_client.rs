@@ -6,6 +6,7 @@ use crate::{
     data_notification::NotificationId,
     data_stream::{DataStreamId, DataStreamListener},
     error::Error,
+    stream_checkpoint::StreamCheckpoint,
 };
 use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
 use async_trait::async_trait;
@@ -14,6 +15,7 @@ use futures::{
     stream::FusedStream,
     SinkExt, Stream,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     pin::Pin,
     task::{Context, Poll},
@@ -157,6 +159,19 @@ pub trait DataStreamingClient {
         data_stream_id: DataStreamId,
         notification_and_feedback: Option<NotificationAndFeedback>,
     ) -> Result<(), Error>;
+
+    /// Resumes a stream from the given checkpoint (e.g., one taken from the
+    /// listener of a previous stream using `get_stream_checkpoint()`, or one
+    /// read from a file after a restart). The new stream will only fetch the
+    /// data not yet acknowledged by the client of the previous stream. If the
+    /// previous stream already completed, an error is returned.
+    ///
+    /// Note: resuming a stream does not terminate the previous stream. Clients
+    /// should terminate the previous stream before resuming it.
+    async fn resume_stream(
+        &self,
+        stream_checkpoint: StreamCheckpoint,
+    ) -> Result<DataStreamListener, Error>;
 }
 
 /// Messages used by the data streaming client for communication with the
@@ -169,7 +184,7 @@ pub struct StreamRequestMessage {
 }
 
 /// The data streaming request from the client.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum StreamRequest {
     GetAllEpochEndingLedgerInfos(GetAllEpochEndingLedgerInfosRequest),
     GetAllStates(GetAllStatesRequest),
@@ -204,20 +219,20 @@ impl StreamRequest {
 }
 
 /// A client request for fetching all available epoch ending ledger infos.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct GetAllEpochEndingLedgerInfosRequest {
     pub start_epoch: Epoch,
 }
 
 /// A client request for fetching all states at a specified version.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct GetAllStatesRequest {
     pub version: Version,
     pub start_index: u64,
 }
 
 /// A client request for fetching all transactions with proofs.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct GetAllTransactionsRequest {
     pub start_version: Version,
     pub end_version: Version,
@@ -226,7 +241,7 @@ pub struct GetAllTransactionsRequest {
 }
 
 /// A client request for fetching all transaction outputs with proofs.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct GetAllTransactionOutputsRequest {
     pub start_version: Version,
     pub end_version: Version,
@@ -234,7 +249,7 @@ pub struct GetAllTransactionOutputsRequest {
 }
 
 /// A client request for fetching all transactions or outputs with proofs.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct GetAllTransactionsOrOutputsRequest {
     pub start_version: Version,
     pub end_version: Version,
@@ -243,7 +258,7 @@ pub struct GetAllTransactionsOrOutputsRequest {
 }
 
 /// A client request for continuously streaming transactions with proofs
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ContinuouslyStreamTransactionsRequest {
     pub known_version: Version,
     pub known_epoch: Epoch,
@@ -252,7 +267,7 @@ pub struct ContinuouslyStreamTransactionsRequest {
 }
 
 /// A client request for continuously streaming transaction outputs with proofs
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ContinuouslyStreamTransactionOutputsRequest {
     pub known_version: Version,
     pub known_epoch: Epoch,
@@ -260,7 +275,7 @@ pub struct ContinuouslyStreamTransactionOutputsRequest {
 }
 
 /// A client request for continuously streaming transactions or outputs with proofs
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ContinuouslyStreamTransactionsOrOutputsRequest {
     pub known_version: Version,
     pub known_epoch: Epoch,
@@ -269,14 +284,14 @@ pub struct ContinuouslyStreamTransactionsOrOutputsRequest {
 }
 
 /// A client request for terminating a stream and providing payload feedback.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct TerminateStreamRequest {
     pub data_stream_id: DataStreamId,
     pub notification_and_feedback: Option<NotificationAndFeedback>,
 }
 
 /// The feedback for a given notification.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum NotificationFeedback {
     EmptyPayloadData,
     EndOfStream,
@@ -471,6 +486,25 @@ impl DataStreamingClient for StreamingServiceClient {
         let _receiver = self.send_stream_request(client_request).await?;
         Ok(())
     }
+
+    async fn resume_stream(
+        &self,
+        stream_checkpoint: StreamCheckpoint,
+    ) -> Result<DataStreamListener, Error> {
+        if stream_checkpoint.is_stream_complete() {
+            return Err(Error::NoDataToFetch(
+                "The stream checkpoint is already complete! No data to resume.".into(),
+            ));
+        }
+
+        let client_request = stream_checkpoint.get_stream_request().clone();
+        if let StreamRequest::TerminateStream(_) = client_request {
+            return Err(Error::UnsupportedRequestEncountered(
+                "Unable to resume a stream termination request!".into(),
+            ));
+        }
+        self.send_request_and_await_response(client_request).await
+    }
 }
 
 /// The component that enables listening to requests from streaming service
@@ -502,7 +536,7 @@ impl FusedStream for StreamingServiceListener {
 
 /// A simple container that allows clients to specify feedback
 /// for a notification they received.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct NotificationAndFeedback {
     pub notification_id: NotificationId,
     pub notification_feedback: NotificationFeedback,
@@ -19,7 +19,14 @@ use aptos_data_client::{
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
 use aptos_logger::prelude::*;
 use futures::StreamExt;
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::time::interval;
 use tokio_stream::wrappers::IntervalStream;
 
@@ -37,6 +44,9 @@ pub struct DataStreamingService<T> {
     // The configuration for the streaming service
     streaming_service_config: DataStreamingServiceConfig,
 
+    // The concurrency limits of new data streams (these can be updated at runtime)
+    concurrency_limits: StreamConcurrencyLimits,
+
     // The data client through which to fetch data from the Aptos network
     aptos_data_client: T,
 
@@ -64,6 +74,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         Self {
             data_client_config,
             streaming_service_config,
+            concurrency_limits: StreamConcurrencyLimits::new(&streaming_service_config),
             aptos_data_client,
             global_data_summary: GlobalDataSummary::empty(),
             data_streams: HashMap::new(),
@@ -73,6 +84,12 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         }
     }
 
+    /// Returns a handle to update the concurrency limits of the data streams
+    /// created by the service from now on.
+    pub fn concurrency_limits(&self) -> StreamConcurrencyLimits {
+        self.concurrency_limits.clone()
+    }
+
     /// Starts the dedicated streaming service
     pub async fn start_service(mut self) {
         let mut data_refresh_interval = IntervalStream::new(interval(Duration::from_millis(
@@ -202,6 +219,10 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         // Refresh the cached global data summary
         self.refresh_global_data_summary();
 
+        // Apply the latest concurrency limits to the new stream
+        self.concurrency_limits
+            .apply_to(&mut self.streaming_service_config);
+
         // Create a new data stream
         let stream_id = self.stream_id_generator.next();
         let (data_stream, stream_listener) = DataStream::new(
@@ -303,6 +324,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         data_stream_id: &DataStreamId,
     ) -> Result<(), Error> {
         let global_data_summary = self.global_data_summary.clone();
+        let prefetching_paused = self.concurrency_limits.is_prefetching_paused();
 
         // If there was a send failure, terminate the stream
         let data_stream = self.get_data_stream(data_stream_id)?;
@@ -324,6 +346,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         }
 
         // Drive data stream progress
+        data_stream.set_prefetching_paused(prefetching_paused);
         if !data_stream.data_requests_initialized() {
             // Initialize the request batch by sending out data client requests
             data_stream.initialize_data_requests(global_data_summary)?;
@@ -382,6 +405,61 @@ fn verify_optimal_chunk_sizes(optimal_chunk_sizes: &OptimalChunkSizes) -> Result
     }
 }
 
+/// The maximum number of concurrent requests of the data streams. Updates only
+/// apply to the streams created afterwards (existing streams keep their limits).
+/// Prefetching can also be paused, which applies to all streams immediately.
+#[derive(Clone, Debug)]
+pub struct StreamConcurrencyLimits {
+    max_concurrent_requests: Arc<AtomicU64>,
+    max_concurrent_state_requests: Arc<AtomicU64>,
+    prefetching_paused: Arc<AtomicBool>,
+}
+
+impl StreamConcurrencyLimits {
+    fn new(streaming_service_config: &DataStreamingServiceConfig) -> Self {
+        Self {
+            max_concurrent_requests: Arc::new(AtomicU64::new(
+                streaming_service_config.max_concurrent_requests,
+            )),
+            max_concurrent_state_requests: Arc::new(AtomicU64::new(
+                streaming_service_config.max_concurrent_state_requests,
+            )),
+            prefetching_paused: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Pauses (or resumes) prefetching, i.e., the data streams only send a
+    /// single request at a time while paused (e.g., to shed memory).
+    pub fn pause_prefetching(&self, paused: bool) {
+        self.prefetching_paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Returns true iff prefetching is paused
+    pub fn is_prefetching_paused(&self) -> bool {
+        self.prefetching_paused.load(Ordering::Relaxed)
+    }
+
+    /// Updates the limits to those of the given config
+    pub fn update(&self, streaming_service_config: &DataStreamingServiceConfig) {
+        self.max_concurrent_requests.store(
+            streaming_service_config.max_concurrent_requests,
+            Ordering::Relaxed,
+        );
+        self.max_concurrent_state_requests.store(
+            streaming_service_config.max_concurrent_state_requests,
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Overrides the limits of the given config with the current limits
+    fn apply_to(&self, streaming_service_config: &mut DataStreamingServiceConfig) {
+        streaming_service_config.max_concurrent_requests =
+            self.max_concurrent_requests.load(Ordering::Relaxed);
+        streaming_service_config.max_concurrent_state_requests =
+            self.max_concurrent_state_requests.load(Ordering::Relaxed);
+    }
+}
+
 /// Unit tests for the streaming service. We place these here to inspect
 /// the internal state of the object.
 #[cfg(test)]
@@ -396,6 +474,7 @@ mod streaming_service_tests {
         tests,
         tests::utils::MIN_ADVERTISED_STATES,
     };
+    use aptos_config::config::DataStreamingServiceConfig;
     use futures::{
         channel::{oneshot, oneshot::Receiver},
         FutureExt, StreamExt,
@@ -447,6 +526,37 @@ mod streaming_service_tests {
         panic!("The streaming service failed to drop the data streams!");
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_update_concurrency_limits() {
+        // Create a new streaming service
+        let (_, mut streaming_service) =
+            tests::streaming_service::create_streaming_client_and_server(false, false, true);
+        let original_config = streaming_service.streaming_service_config;
+
+        // Update the concurrency limits
+        let concurrency_limits = streaming_service.concurrency_limits();
+        concurrency_limits.update(&DataStreamingServiceConfig {
+            max_concurrent_requests: original_config.max_concurrent_requests + 1,
+            max_concurrent_state_requests: original_config.max_concurrent_state_requests + 1,
+            ..original_config
+        });
+
+        // Verify the limits are only applied once a new stream is created
+        assert_eq!(streaming_service.streaming_service_config, original_config);
+        let (new_stream_request, response_receiver) = create_new_stream_request();
+        streaming_service.handle_stream_request_message(new_stream_request);
+        response_receiver.now_or_never().unwrap().unwrap().unwrap();
+        let streaming_service_config = streaming_service.streaming_service_config;
+        assert_eq!(
+            streaming_service_config.max_concurrent_requests,
+            original_config.max_concurrent_requests + 1
+        );
+        assert_eq!(
+            streaming_service_config.max_concurrent_state_requests,
+            original_config.max_concurrent_state_requests + 1
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_terminate_data_streams() {
         // Create a new streaming service
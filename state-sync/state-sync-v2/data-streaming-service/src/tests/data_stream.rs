@@ -719,10 +719,11 @@ async fn test_transactions_and_output_stream_timeout() {
         ..Default::default()
     };
 
-    // Create a test streaming service config
+    // Create a test streaming service config (with a static prefetch window)
     let max_concurrent_requests = 3;
     let max_request_retry = 10;
     let streaming_service_config = DataStreamingServiceConfig {
+        enable_prefetch_window_tuning: false,
         max_concurrent_requests,
         max_request_retry,
         ..Default::default()
@@ -821,6 +822,145 @@ async fn test_transactions_and_output_stream_timeout() {
     }
 }
 
+#[tokio::test]
+async fn test_prefetch_window_timeouts() {
+    // Create a transaction stream
+    let max_concurrent_requests = 8;
+    let streaming_service_config = DataStreamingServiceConfig {
+        max_concurrent_requests,
+        min_concurrent_requests: 2,
+        ..Default::default()
+    };
+    let (mut data_stream, mut stream_listener) = create_transaction_stream(
+        AptosDataClientConfig::default(),
+        streaming_service_config,
+        MIN_ADVERTISED_TRANSACTION,
+        MAX_ADVERTISED_TRANSACTION,
+    );
+
+    // Initialize the data stream and verify the window starts fully open
+    let global_data_summary = create_global_data_summary(1);
+    initialize_data_requests(&mut data_stream, &global_data_summary);
+    assert_eq!(
+        data_stream.get_prefetch_window_size(),
+        max_concurrent_requests
+    );
+
+    // Wait for the data client to satisfy all requests
+    for i in 0..max_concurrent_requests as usize {
+        wait_for_data_client_to_respond(&mut data_stream, i).await;
+    }
+
+    // Set a timeout on the first request and verify the window is halved
+    set_timeout_response_in_queue(&mut data_stream, 0);
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    assert_eq!(
+        data_stream.get_prefetch_window_size(),
+        max_concurrent_requests / 2
+    );
+
+    // Verify no new requests were sent (the pending requests exceed the window)
+    let (sent_requests, _) = data_stream.get_sent_requests_and_notifications();
+    assert_eq!(
+        sent_requests.as_ref().unwrap().len(),
+        max_concurrent_requests as usize
+    );
+
+    // Process all responses (which are consumed promptly) and verify the window grows
+    wait_for_data_client_to_respond(&mut data_stream, 0).await;
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    for _ in 0..max_concurrent_requests {
+        let data_notification = get_data_notification(&mut stream_listener).await.unwrap();
+        assert_matches!(
+            data_notification.data_payload,
+            DataPayload::TransactionsWithProof(_)
+        );
+    }
+    assert_eq!(
+        data_stream.get_prefetch_window_size(),
+        max_concurrent_requests
+    );
+}
+
+#[tokio::test]
+async fn test_prefetch_window_backpressure() {
+    // Create a transaction stream with a tiny notification channel
+    let max_concurrent_requests = 3;
+    let streaming_service_config = DataStreamingServiceConfig {
+        max_concurrent_requests,
+        max_data_stream_channel_sizes: 1,
+        min_concurrent_requests: 1,
+        ..Default::default()
+    };
+    let (mut data_stream, mut stream_listener) = create_transaction_stream(
+        AptosDataClientConfig::default(),
+        streaming_service_config,
+        MIN_ADVERTISED_TRANSACTION,
+        MAX_ADVERTISED_TRANSACTION,
+    );
+
+    // Initialize the data stream and wait for the data client to satisfy all requests
+    let global_data_summary = create_global_data_summary(1);
+    initialize_data_requests(&mut data_stream, &global_data_summary);
+    for i in 0..max_concurrent_requests as usize {
+        wait_for_data_client_to_respond(&mut data_stream, i).await;
+    }
+
+    // Process the responses while slowly consuming the notifications. The
+    // channel fills up, so the window should shrink due to the backpressure.
+    let process_responses = process_data_responses(&mut data_stream, &global_data_summary);
+    let consume_notifications = (&mut stream_listener)
+        .take(max_concurrent_requests as usize)
+        .collect::<Vec<_>>();
+    let (_, data_notifications) = futures::join!(process_responses, consume_notifications);
+    assert_eq!(data_notifications.len(), max_concurrent_requests as usize);
+    assert_eq!(data_stream.get_prefetch_window_size(), 1);
+
+    // Verify only a single new request was sent
+    let (sent_requests, _) = data_stream.get_sent_requests_and_notifications();
+    assert_eq!(sent_requests.as_ref().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_prefetching_paused() {
+    // Create a transaction stream
+    let max_concurrent_requests = 4;
+    let streaming_service_config = DataStreamingServiceConfig {
+        max_concurrent_requests,
+        ..Default::default()
+    };
+    let (mut data_stream, mut stream_listener) = create_transaction_stream(
+        AptosDataClientConfig::default(),
+        streaming_service_config,
+        MIN_ADVERTISED_TRANSACTION,
+        MAX_ADVERTISED_TRANSACTION,
+    );
+
+    // Pause prefetching and verify only a single request is sent
+    data_stream.set_prefetching_paused(true);
+    let global_data_summary = create_global_data_summary(1);
+    initialize_data_requests(&mut data_stream, &global_data_summary);
+    let (sent_requests, _) = data_stream.get_sent_requests_and_notifications();
+    assert_eq!(sent_requests.as_ref().unwrap().len(), 1);
+
+    // Process the response and verify the next single request is sent
+    wait_for_data_client_to_respond(&mut data_stream, 0).await;
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    get_data_notification(&mut stream_listener).await.unwrap();
+    let (sent_requests, _) = data_stream.get_sent_requests_and_notifications();
+    assert_eq!(sent_requests.as_ref().unwrap().len(), 1);
+
+    // Resume prefetching and verify the requests fill the window again
+    data_stream.set_prefetching_paused(false);
+    wait_for_data_client_to_respond(&mut data_stream, 0).await;
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    let (sent_requests, _) = data_stream.get_sent_requests_and_notifications();
+    assert_eq!(
+        sent_requests.as_ref().unwrap().len(),
+        max_concurrent_requests as usize
+    );
+}
+
 #[tokio::test]
 async fn test_stream_listener_dropped() {
     // Create an epoch ending data stream
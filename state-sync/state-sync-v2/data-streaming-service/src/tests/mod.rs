@@ -3,6 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod data_stream;
+mod prefetch_window;
+mod stream_checkpoint;
 mod stream_engine;
 mod streaming_client;
 pub mod streaming_service;
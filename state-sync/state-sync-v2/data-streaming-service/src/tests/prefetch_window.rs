@@ -0,0 +1,62 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::prefetch_window::PrefetchWindow;
+
+#[test]
+fn test_prefetch_window_aimd() {
+    // Create a prefetch window and verify it starts fully open
+    let min_window_size = 2;
+    let max_window_size = 10;
+    let mut prefetch_window = PrefetchWindow::new(true, min_window_size, max_window_size);
+    assert_eq!(prefetch_window.get_window_size(), max_window_size);
+
+    // Verify the window doesn't grow beyond the max window size
+    prefetch_window.handle_prompt_consumption();
+    assert_eq!(prefetch_window.get_window_size(), max_window_size);
+
+    // Verify the window is halved on timeouts and backpressure
+    prefetch_window.handle_request_timeout();
+    assert_eq!(prefetch_window.get_window_size(), 5);
+    prefetch_window.handle_backpressure();
+    assert_eq!(prefetch_window.get_window_size(), 2);
+
+    // Verify the window doesn't shrink below the min window size
+    prefetch_window.handle_request_timeout();
+    assert_eq!(prefetch_window.get_window_size(), min_window_size);
+
+    // Verify the window grows additively on prompt consumption
+    for window_size in min_window_size + 1..=max_window_size {
+        prefetch_window.handle_prompt_consumption();
+        assert_eq!(prefetch_window.get_window_size(), window_size);
+    }
+    prefetch_window.handle_prompt_consumption();
+    assert_eq!(prefetch_window.get_window_size(), max_window_size);
+}
+
+#[test]
+fn test_prefetch_window_static() {
+    // Create a prefetch window with auto-tuning disabled
+    let max_window_size = 6;
+    let mut prefetch_window = PrefetchWindow::new(false, 1, max_window_size);
+
+    // Verify the window size never changes
+    prefetch_window.handle_request_timeout();
+    prefetch_window.handle_backpressure();
+    prefetch_window.handle_prompt_consumption();
+    assert_eq!(prefetch_window.get_window_size(), max_window_size);
+}
+
+#[test]
+fn test_prefetch_window_bounds() {
+    // Verify the min window size is bounded by the max window size
+    let mut prefetch_window = PrefetchWindow::new(true, 20, 5);
+    prefetch_window.handle_request_timeout();
+    assert_eq!(prefetch_window.get_window_size(), 5);
+
+    // Verify the window always allows at least a single request
+    let mut prefetch_window = PrefetchWindow::new(true, 0, 0);
+    assert_eq!(prefetch_window.get_window_size(), 1);
+    prefetch_window.handle_backpressure();
+    assert_eq!(prefetch_window.get_window_size(), 1);
+}
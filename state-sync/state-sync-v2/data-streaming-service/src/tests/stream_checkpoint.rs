@@ -0,0 +1,309 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    data_notification::{DataNotification, DataPayload},
+    data_stream::DataStreamListener,
+    stream_checkpoint::StreamCheckpoint,
+    streaming_client::{
+        ContinuouslyStreamTransactionsRequest, GetAllEpochEndingLedgerInfosRequest,
+        GetAllStatesRequest, GetAllTransactionOutputsRequest, GetAllTransactionsRequest,
+        StreamRequest,
+    },
+    tests::utils::{
+        create_ledger_info, create_output_list_with_proof, create_transaction_list_with_proof,
+    },
+};
+use aptos_crypto::HashValue;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    proof::SparseMerkleRangeProof, state_store::state_value::StateValueChunkWithProof,
+};
+use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
+
+#[test]
+fn test_checkpoint_epoch_ending_ledger_infos() {
+    // Create a checkpoint for an epoch ending stream
+    let mut stream_checkpoint = StreamCheckpoint::new(StreamRequest::GetAllEpochEndingLedgerInfos(
+        GetAllEpochEndingLedgerInfosRequest { start_epoch: 5 },
+    ));
+
+    // Update the checkpoint with several epoch ending ledger infos
+    let ledger_infos = (5..10)
+        .map(|epoch| create_ledger_info(epoch * 100, epoch, true))
+        .collect();
+    stream_checkpoint.update(&DataPayload::EpochEndingLedgerInfos(ledger_infos));
+
+    // Verify the checkpoint resumes from the next epoch
+    assert_eq!(
+        stream_checkpoint.get_stream_request(),
+        &StreamRequest::GetAllEpochEndingLedgerInfos(GetAllEpochEndingLedgerInfosRequest {
+            start_epoch: 10
+        })
+    );
+    assert!(!stream_checkpoint.is_stream_complete());
+
+    // Verify the checkpoint completes at the end of the stream
+    stream_checkpoint.update(&DataPayload::EndOfStream);
+    assert!(stream_checkpoint.is_stream_complete());
+}
+
+#[test]
+fn test_checkpoint_states() {
+    // Create a checkpoint for a state value stream
+    let mut stream_checkpoint =
+        StreamCheckpoint::new(StreamRequest::GetAllStates(GetAllStatesRequest {
+            version: 1000,
+            start_index: 0,
+        }));
+
+    // Update the checkpoint with the last state value chunk
+    let state_value_chunk = StateValueChunkWithProof {
+        first_index: 0,
+        last_index: 99,
+        first_key: HashValue::zero(),
+        last_key: HashValue::zero(),
+        raw_values: vec![],
+        proof: SparseMerkleRangeProof::new(vec![]), // No right siblings
+        root_hash: HashValue::zero(),
+    };
+    stream_checkpoint.update(&DataPayload::StateValuesWithProof(state_value_chunk));
+
+    // Verify the checkpoint resumes from the next index and is complete
+    assert_eq!(
+        stream_checkpoint.get_stream_request(),
+        &StreamRequest::GetAllStates(GetAllStatesRequest {
+            version: 1000,
+            start_index: 100,
+        })
+    );
+    assert!(stream_checkpoint.is_stream_complete());
+}
+
+#[test]
+fn test_checkpoint_transactions_and_outputs() {
+    // Create a checkpoint for a transaction stream
+    let mut stream_checkpoint = StreamCheckpoint::new(StreamRequest::GetAllTransactions(
+        GetAllTransactionsRequest {
+            start_version: 0,
+            end_version: 99,
+            proof_version: 100,
+            include_events: true,
+        },
+    ));
+
+    // Update the checkpoint with several transaction lists
+    for (start_version, end_version) in [(0, 9), (10, 49)] {
+        let transaction_list = create_transaction_list_with_proof(start_version, end_version, true);
+        stream_checkpoint.update(&DataPayload::TransactionsWithProof(transaction_list));
+    }
+
+    // Verify the checkpoint resumes from the next version
+    assert_eq!(
+        stream_checkpoint.get_stream_request(),
+        &StreamRequest::GetAllTransactions(GetAllTransactionsRequest {
+            start_version: 50,
+            end_version: 99,
+            proof_version: 100,
+            include_events: true,
+        })
+    );
+    assert!(!stream_checkpoint.is_stream_complete());
+
+    // Verify that mismatched payloads are ignored
+    let output_list = create_output_list_with_proof(50, 59);
+    stream_checkpoint.update(&DataPayload::TransactionOutputsWithProof(output_list));
+    assert!(matches!(
+        stream_checkpoint.get_stream_request(),
+        StreamRequest::GetAllTransactions(request) if request.start_version == 50
+    ));
+
+    // Create a checkpoint for a transaction output stream
+    let mut stream_checkpoint = StreamCheckpoint::new(StreamRequest::GetAllTransactionOutputs(
+        GetAllTransactionOutputsRequest {
+            start_version: 0,
+            end_version: 99,
+            proof_version: 100,
+        },
+    ));
+
+    // Update the checkpoint with all outputs and verify the stream is complete
+    let output_list = create_output_list_with_proof(0, 99);
+    stream_checkpoint.update(&DataPayload::TransactionOutputsWithProof(output_list));
+    assert!(stream_checkpoint.is_stream_complete());
+}
+
+#[test]
+fn test_checkpoint_continuous_transactions() {
+    // Create a checkpoint for a continuous transaction stream with a target
+    let target = create_ledger_info(200, 2, false);
+    let mut stream_checkpoint = StreamCheckpoint::new(
+        StreamRequest::ContinuouslyStreamTransactions(ContinuouslyStreamTransactionsRequest {
+            known_version: 50,
+            known_epoch: 1,
+            include_events: false,
+            target: Some(target.clone()),
+        }),
+    );
+
+    // Update the checkpoint with transactions that end the epoch
+    let transaction_list = create_transaction_list_with_proof(51, 100, false);
+    let epoch_ending_ledger_info = create_ledger_info(100, 1, true);
+    stream_checkpoint.update(&DataPayload::ContinuousTransactionsWithProof(
+        epoch_ending_ledger_info,
+        transaction_list,
+    ));
+
+    // Verify the checkpoint resumes from the next version and epoch
+    assert_eq!(
+        stream_checkpoint.get_stream_request(),
+        &StreamRequest::ContinuouslyStreamTransactions(ContinuouslyStreamTransactionsRequest {
+            known_version: 100,
+            known_epoch: 2,
+            include_events: false,
+            target: Some(target.clone()),
+        })
+    );
+    assert!(!stream_checkpoint.is_stream_complete());
+
+    // Update the checkpoint with transactions that reach the target
+    let transaction_list = create_transaction_list_with_proof(101, 200, false);
+    stream_checkpoint.update(&DataPayload::ContinuousTransactionsWithProof(
+        target,
+        transaction_list,
+    ));
+    assert!(stream_checkpoint.is_stream_complete());
+}
+
+#[test]
+fn test_checkpoint_serialization() {
+    // Create a checkpoint for a continuous transaction stream
+    let mut stream_checkpoint = StreamCheckpoint::new(
+        StreamRequest::ContinuouslyStreamTransactions(ContinuouslyStreamTransactionsRequest {
+            known_version: 0,
+            known_epoch: 0,
+            include_events: true,
+            target: Some(create_ledger_info(1000, 5, false)),
+        }),
+    );
+    let transaction_list = create_transaction_list_with_proof(1, 10, true);
+    stream_checkpoint.update(&DataPayload::ContinuousTransactionsWithProof(
+        create_ledger_info(500, 0, false),
+        transaction_list,
+    ));
+
+    // Serialize and deserialize the checkpoint and verify it is unchanged
+    let serialized_checkpoint = bcs::to_bytes(&stream_checkpoint).unwrap();
+    let deserialized_checkpoint: StreamCheckpoint =
+        bcs::from_bytes(&serialized_checkpoint).unwrap();
+    assert_eq!(deserialized_checkpoint, stream_checkpoint);
+}
+
+#[test]
+fn test_checkpoint_persistence() {
+    // Create a checkpoint for a transaction stream that has received some data
+    let mut stream_checkpoint = StreamCheckpoint::new(StreamRequest::GetAllTransactions(
+        GetAllTransactionsRequest {
+            start_version: 0,
+            end_version: 99,
+            proof_version: 100,
+            include_events: false,
+        },
+    ));
+    let transaction_list = create_transaction_list_with_proof(0, 49, false);
+    stream_checkpoint.update(&DataPayload::TransactionsWithProof(transaction_list));
+
+    // Verify that no checkpoint is read before one is persisted
+    let temp_path = TempPath::new();
+    temp_path.create_as_dir().unwrap();
+    let checkpoint_path = temp_path.path().join("stream_checkpoint");
+    assert_eq!(StreamCheckpoint::read_from_file(&checkpoint_path), Ok(None));
+
+    // Persist the checkpoint and verify it can be read back
+    stream_checkpoint.write_to_file(&checkpoint_path).unwrap();
+    assert_eq!(
+        StreamCheckpoint::read_from_file(&checkpoint_path),
+        Ok(Some(stream_checkpoint.clone()))
+    );
+
+    // Overwrite the checkpoint and verify the latest one is read back
+    stream_checkpoint.update(&DataPayload::EndOfStream);
+    stream_checkpoint.write_to_file(&checkpoint_path).unwrap();
+    assert_eq!(
+        StreamCheckpoint::read_from_file(&checkpoint_path),
+        Ok(Some(stream_checkpoint))
+    );
+}
+
+#[test]
+fn test_listener_checkpoint() {
+    // Create a data stream listener for a transaction stream
+    let stream_request = StreamRequest::GetAllTransactions(GetAllTransactionsRequest {
+        start_version: 0,
+        end_version: 99,
+        proof_version: 100,
+        include_events: false,
+    });
+    let (mut notification_sender, notification_receiver) = mpsc::channel(10);
+    let mut data_stream_listener =
+        DataStreamListener::new(0, stream_request.clone(), notification_receiver);
+
+    // Verify the checkpoint is initially the stream request
+    let stream_checkpoint = data_stream_listener.get_stream_checkpoint();
+    assert_eq!(stream_checkpoint.get_stream_request(), &stream_request);
+
+    // Send several data notifications and verify they're received by the listener
+    for notification_id in 0..3 {
+        let start_version = notification_id * 20;
+        let transaction_list =
+            create_transaction_list_with_proof(start_version, start_version + 19, false);
+        let data_notification = DataNotification {
+            notification_id,
+            data_payload: DataPayload::TransactionsWithProof(transaction_list),
+        };
+        block_on(notification_sender.send(data_notification)).unwrap();
+        block_on(data_stream_listener.next()).unwrap();
+    }
+
+    // Verify the checkpoint is not updated until a notification is acknowledged
+    assert_eq!(
+        data_stream_listener.get_stream_checkpoint(),
+        stream_checkpoint
+    );
+
+    // Acknowledge an unknown notification and verify the checkpoint is unchanged
+    data_stream_listener.acknowledge_notification(10);
+    assert_eq!(
+        data_stream_listener.get_stream_checkpoint(),
+        stream_checkpoint
+    );
+
+    // Acknowledge the second notification and verify the checkpoint
+    // resumes after it (i.e., the first is implicitly acknowledged).
+    data_stream_listener.acknowledge_notification(1);
+    verify_transaction_stream_start(&data_stream_listener, 40);
+
+    // Acknowledge the first notification again and verify the checkpoint doesn't move back
+    data_stream_listener.acknowledge_notification(0);
+    verify_transaction_stream_start(&data_stream_listener, 40);
+
+    // Acknowledge the last notification and verify the checkpoint resumes after it
+    data_stream_listener.acknowledge_notification(2);
+    verify_transaction_stream_start(&data_stream_listener, 60);
+}
+
+/// Verifies that the checkpoint of the given transaction stream
+/// listener resumes the stream from the specified start version.
+fn verify_transaction_stream_start(data_stream_listener: &DataStreamListener, start_version: u64) {
+    assert_eq!(
+        data_stream_listener
+            .get_stream_checkpoint()
+            .get_stream_request(),
+        &StreamRequest::GetAllTransactions(GetAllTransactionsRequest {
+            start_version,
+            end_version: 99,
+            proof_version: 100,
+            include_events: false,
+        })
+    );
+}
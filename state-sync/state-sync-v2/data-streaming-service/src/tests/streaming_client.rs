@@ -3,9 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    data_notification::DataNotification,
+    data_notification::{DataNotification, DataPayload},
     data_stream::DataStreamListener,
     error::Error,
+    stream_checkpoint::StreamCheckpoint,
     streaming_client::{
         new_streaming_service_client_listener_pair, ContinuouslyStreamTransactionOutputsRequest,
         ContinuouslyStreamTransactionsRequest, DataStreamingClient,
@@ -13,9 +14,9 @@ use crate::{
         GetAllTransactionsRequest, NotificationAndFeedback, NotificationFeedback, StreamRequest,
         StreamingServiceListener, TerminateStreamRequest,
     },
-    tests::utils::{create_ledger_info, initialize_logger},
+    tests::utils::{create_ledger_info, create_transaction_list_with_proof, initialize_logger},
 };
-use claims::assert_ok;
+use claims::{assert_matches, assert_ok};
 use futures::{channel::mpsc, executor::block_on, FutureExt, StreamExt};
 use std::thread::JoinHandle;
 
@@ -230,6 +231,45 @@ fn test_terminate_stream() {
     assert_ok!(result);
 }
 
+#[test]
+fn test_resume_stream() {
+    // Create a new streaming service client and listener
+    let (streaming_service_client, streaming_service_listener) =
+        new_streaming_service_client_listener_pair();
+
+    // Create a stream checkpoint that has received some transactions
+    let mut stream_checkpoint = StreamCheckpoint::new(StreamRequest::GetAllTransactions(
+        GetAllTransactionsRequest {
+            start_version: 0,
+            end_version: 100,
+            proof_version: 200,
+            include_events: true,
+        },
+    ));
+    let transaction_list = create_transaction_list_with_proof(0, 49, true);
+    stream_checkpoint.update(&DataPayload::TransactionsWithProof(transaction_list));
+
+    // Note the request we expect to receive on the streaming service side
+    let expected_request = StreamRequest::GetAllTransactions(GetAllTransactionsRequest {
+        start_version: 50,
+        end_version: 100,
+        proof_version: 200,
+        include_events: true,
+    });
+
+    // Spawn a new server thread to handle any stream requests
+    let _handler = spawn_service_and_expect_request(streaming_service_listener, expected_request);
+
+    // Resume the stream and verify we get a data stream listener
+    let response = block_on(streaming_service_client.resume_stream(stream_checkpoint.clone()));
+    assert_ok!(response);
+
+    // Complete the stream and verify it can no longer be resumed
+    stream_checkpoint.update(&DataPayload::EndOfStream);
+    let response = block_on(streaming_service_client.resume_stream(stream_checkpoint));
+    assert_matches!(response, Err(Error::NoDataToFetch(_)));
+}
+
 /// Spawns a new thread that listens to the given streaming service listener and
 /// responds successfully to any requests that match the specified `expected_request`.
 /// Otherwise, an error is returned.
@@ -285,7 +325,11 @@ fn spawn_service_and_respond_with_error(
 /// Creates and returns a new data stream sender and listener pair.
 fn new_data_stream_sender_listener() -> (mpsc::Sender<DataNotification>, DataStreamListener) {
     let (notification_sender, notification_receiver) = mpsc::channel(100);
-    let data_stream_listener = DataStreamListener::new(0, notification_receiver);
+    let stream_request =
+        StreamRequest::GetAllEpochEndingLedgerInfos(GetAllEpochEndingLedgerInfosRequest {
+            start_epoch: 0,
+        });
+    let data_stream_listener = DataStreamListener::new(0, stream_request, notification_receiver);
 
     (notification_sender, data_stream_listener)
 }
@@ -20,7 +20,7 @@ use crate::{
     utils,
     utils::{OutputFallbackHandler, PENDING_DATA_LOG_FREQ_SECS},
 };
-use aptos_config::config::{RoleType, StateSyncDriverConfig};
+use aptos_config::config::{ConsensusObserverConfig, RoleType, StateSyncDriverConfig};
 use aptos_consensus_notifications::{
     ConsensusCommitNotification, ConsensusNotification, ConsensusSyncNotification,
 };
@@ -53,6 +53,9 @@ pub struct DriverConfiguration {
     // The config file of the driver
     pub config: StateSyncDriverConfig,
 
+    // The config for consensus observer
+    pub consensus_observer_config: ConsensusObserverConfig,
+
     // The role of the node
     pub role: RoleType,
 
@@ -61,9 +64,15 @@ pub struct DriverConfiguration {
 }
 
 impl DriverConfiguration {
-    pub fn new(config: StateSyncDriverConfig, role: RoleType, waypoint: Waypoint) -> Self {
+    pub fn new(
+        config: StateSyncDriverConfig,
+        consensus_observer_config: ConsensusObserverConfig,
+        role: RoleType,
+        waypoint: Waypoint,
+    ) -> Self {
         Self {
             config,
+            consensus_observer_config,
             role,
             waypoint,
         }
@@ -103,6 +112,10 @@ pub struct StateSyncDriver<
     // The listener for errors from the storage synchronizer
     error_notification_listener: ErrorNotificationListener,
 
+    // The timestamp of the last notification sent by the consensus observer
+    // (if the observer is enabled and has sent any notifications).
+    last_consensus_observer_notification: Option<Instant>,
+
     // The event subscription service to notify listeners of on-chain events
     event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
 
@@ -188,6 +201,7 @@ impl<
             driver_configuration,
             error_notification_listener,
             event_subscription_service,
+            last_consensus_observer_notification: None,
             mempool_notification_handler,
             start_time: None,
             storage,
@@ -232,9 +246,12 @@ impl<
 
     /// Handles a notification sent by consensus
     async fn handle_consensus_notification(&mut self, notification: ConsensusNotification) {
-        // Verify the notification: full nodes shouldn't receive notifications
-        // and consensus should only send notifications after bootstrapping!
-        let result = if self.driver_configuration.role == RoleType::FullNode {
+        // Verify the notification: full nodes shouldn't receive notifications (unless
+        // they run the consensus observer) and consensus should only send notifications
+        // after bootstrapping!
+        let result = if self.driver_configuration.role == RoleType::FullNode
+            && !self.is_consensus_observer_enabled()
+        {
             Err(Error::FullNodeConsensusNotification(format!(
                 "Received consensus notification: {:?}",
                 notification
@@ -270,6 +287,11 @@ impl<
             return;
         }
 
+        // Track the consensus observer activity (to know when it's executing)
+        if self.is_consensus_observer_enabled() {
+            self.last_consensus_observer_notification = Some(self.time_service.now());
+        }
+
         // Handle the notification
         let result = match notification {
             ConsensusNotification::NotifyCommit(commit_notification) => {
@@ -525,6 +547,11 @@ impl<
         if !self.active_sync_request() {
             self.continuous_syncer.reset_active_stream(None).await?;
             self.storage_synchronizer.finish_chunk_executor(); // Consensus is now in control
+
+            // Give the consensus observer a chance to resume execution
+            if self.is_consensus_observer_enabled() {
+                self.last_consensus_observer_notification = Some(self.time_service.now());
+            }
         }
         Ok(())
     }
@@ -539,9 +566,38 @@ impl<
         self.driver_configuration.role == RoleType::Validator
     }
 
-    /// Returns true iff consensus is currently executing
+    /// Returns true iff the consensus observer is enabled on this node
+    fn is_consensus_observer_enabled(&self) -> bool {
+        self.driver_configuration
+            .consensus_observer_config
+            .observer_enabled
+    }
+
+    /// Returns true iff the consensus observer has recently sent notifications,
+    /// i.e., it is following the blocks of its publisher. Otherwise, state sync
+    /// is responsible for keeping the node up-to-date.
+    fn is_consensus_observer_active(&self) -> bool {
+        if !self.is_consensus_observer_enabled() {
+            return false;
+        }
+
+        let max_inactivity = Duration::from_millis(
+            self.driver_configuration
+                .consensus_observer_config
+                .max_subscription_timeout_ms,
+        );
+        self.last_consensus_observer_notification
+            .map(|last_notification| {
+                self.time_service.now().duration_since(last_notification) < max_inactivity
+            })
+            .unwrap_or(false)
+    }
+
+    /// Returns true iff consensus (or the consensus observer) is currently executing
     fn check_if_consensus_executing(&self) -> bool {
-        self.is_validator() && self.bootstrapper.is_bootstrapped() && !self.active_sync_request()
+        (self.is_validator() || self.is_consensus_observer_active())
+            && self.bootstrapper.is_bootstrapped()
+            && !self.active_sync_request()
     }
 
     /// Checks if the connection deadline has passed. If so, validators with
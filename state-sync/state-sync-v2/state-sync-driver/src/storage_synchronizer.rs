@@ -563,9 +563,11 @@ fn spawn_committer<
                     info!(
                         LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
                             "Committed a new transaction chunk! \
-                                    Transaction total: {:?}, event total: {:?}",
+                                    Transaction total: {:?}, event total: {:?}, \
+                                    execution profile: {:?}",
                             notification.committed_transactions.len(),
-                            notification.committed_events.len()
+                            notification.committed_events.len(),
+                            notification.execution_profile
                         ))
                     );
                     metrics::increment_gauge(
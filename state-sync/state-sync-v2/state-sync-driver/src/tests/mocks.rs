@@ -12,6 +12,7 @@ use aptos_crypto::HashValue;
 use aptos_data_streaming_service::{
     data_notification::NotificationId,
     data_stream::{DataStreamId, DataStreamListener},
+    stream_checkpoint::StreamCheckpoint,
     streaming_client::{DataStreamingClient, Epoch, NotificationAndFeedback},
 };
 use aptos_executor_types::{ChunkCommitNotification, ChunkExecutorTrait};
@@ -420,6 +421,11 @@ mock! {
             data_stream_id: DataStreamId,
             notification_and_feedback: Option<NotificationAndFeedback>,
         ) -> Result<(), aptos_data_streaming_service::error::Error>;
+
+        async fn resume_stream(
+            &self,
+            stream_checkpoint: StreamCheckpoint,
+        ) -> Result<DataStreamListener, aptos_data_streaming_service::error::Error>;
     }
     impl Clone for StreamingClient {
         fn clone(&self) -> Self;
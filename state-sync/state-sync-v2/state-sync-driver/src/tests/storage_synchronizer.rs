@@ -25,7 +25,7 @@ use anyhow::format_err;
 use aptos_config::config::StateSyncDriverConfig;
 use aptos_data_streaming_service::data_notification::NotificationId;
 use aptos_event_notifications::EventSubscriptionService;
-use aptos_executor_types::ChunkCommitNotification;
+use aptos_executor_types::{ChunkCommitNotification, ExecutionProfile};
 use aptos_infallible::{Mutex, RwLock};
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_storage_interface::DbReaderWriter;
@@ -56,6 +56,7 @@ async fn test_apply_transaction_outputs() {
         committed_events: vec![event_to_commit.clone()],
         committed_transactions: vec![transaction_to_commit.clone()],
         reconfiguration_occurred: false,
+        execution_profile: ExecutionProfile::default(),
     });
     chunk_executor
         .expect_commit_chunk()
@@ -189,6 +190,7 @@ async fn test_execute_transactions() {
         committed_events: vec![event_to_commit.clone()],
         committed_transactions: vec![transaction_to_commit.clone()],
         reconfiguration_occurred: false,
+        execution_profile: ExecutionProfile::default(),
     });
     chunk_executor
         .expect_commit_chunk()
@@ -3,14 +3,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::driver::DriverConfiguration;
-use aptos_config::config::{RoleType, StateSyncDriverConfig};
+use aptos_config::config::{ConsensusObserverConfig, RoleType, StateSyncDriverConfig};
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519Signature},
     HashValue, PrivateKey, Uniform,
 };
 use aptos_data_client::global_summary::GlobalDataSummary;
 use aptos_data_streaming_service::{
-    data_notification::DataNotification, data_stream::DataStreamListener, streaming_client::Epoch,
+    data_notification::DataNotification,
+    data_stream::DataStreamListener,
+    streaming_client::{Epoch, GetAllEpochEndingLedgerInfosRequest, StreamRequest},
 };
 use aptos_event_notifications::EventNotificationListener;
 use aptos_mempool_notifications::{CommittedTransaction, MempoolNotificationListener};
@@ -45,7 +47,12 @@ use rand::{rngs::OsRng, Rng};
 /// Creates a new data stream listener and notification sender pair
 pub fn create_data_stream_listener() -> (mpsc::Sender<DataNotification>, DataStreamListener) {
     let (notification_sender, notification_receiver) = mpsc::channel(100);
-    let data_stream_listener = DataStreamListener::new(create_random_u64(), notification_receiver);
+    let stream_request =
+        StreamRequest::GetAllEpochEndingLedgerInfos(GetAllEpochEndingLedgerInfosRequest {
+            start_epoch: 0,
+        });
+    let data_stream_listener =
+        DataStreamListener::new(create_random_u64(), stream_request, notification_receiver);
 
     (notification_sender, data_stream_listener)
 }
@@ -70,6 +77,7 @@ pub fn create_full_node_driver_configuration() -> DriverConfiguration {
 
     DriverConfiguration {
         config,
+        consensus_observer_config: ConsensusObserverConfig::default(),
         role,
         waypoint,
     }
@@ -0,0 +1,111 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use aptos_storage_service_types::{
+    requests::StorageServiceRequest, responses::StorageServiceResponse,
+};
+use dashmap::{mapref::entry::Entry, DashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The response of an in-flight request (which is only populated once
+/// the request completes). The lock is held by the requester fetching
+/// the response, so any other requesters block until it is available.
+///
+/// Note: a std mutex is used so that a panic while fetching the response
+/// (which poisons the lock) doesn't also panic the waiting requesters.
+type InFlightResponse = Arc<Mutex<Option<Result<StorageServiceResponse, Error>>>>;
+
+/// Removes the in-flight request from the coalescer when dropped. This
+/// ensures the request is removed even if fetching the response panics.
+struct InFlightRequestGuard<'a> {
+    in_flight_requests: &'a DashMap<StorageServiceRequest, InFlightResponse>,
+    request: &'a StorageServiceRequest,
+}
+
+impl Drop for InFlightRequestGuard<'_> {
+    fn drop(&mut self) {
+        self.in_flight_requests.remove(self.request);
+    }
+}
+
+/// The request coalescer shares the responses of identical requests that
+/// are being processed concurrently. This avoids fetching and serializing
+/// (and compressing) the same data from storage for each requester.
+#[derive(Default)]
+pub struct RequestCoalescer {
+    in_flight_requests: DashMap<StorageServiceRequest, InFlightResponse>,
+}
+
+impl RequestCoalescer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the response for the given request. If an identical request
+    /// is already in flight, this waits for (and shares) its response.
+    /// Otherwise, the response is fetched using the given function. The
+    /// returned flag indicates if the response was coalesced.
+    pub fn get_or_fetch_response<F>(
+        &self,
+        request: &StorageServiceRequest,
+        fetch_response: F,
+    ) -> (Result<StorageServiceResponse, Error>, bool)
+    where
+        F: FnOnce() -> Result<StorageServiceResponse, Error>,
+    {
+        // Check if an identical request is already in flight
+        let in_flight_response = Arc::new(Mutex::new(None));
+        let mut in_flight_response_guard = lock_in_flight_response(&in_flight_response);
+        let existing_response = match self.in_flight_requests.entry(request.clone()) {
+            Entry::Occupied(entry) => Some(entry.get().clone()),
+            Entry::Vacant(entry) => {
+                entry.insert(in_flight_response.clone());
+                None
+            },
+        };
+
+        // Wait for the in-flight request to complete and share its response
+        if let Some(existing_response) = existing_response {
+            drop(in_flight_response_guard);
+            if let Some(response) = lock_in_flight_response(&existing_response).clone() {
+                return (response, true);
+            }
+            return (fetch_response(), false); // The in-flight request never completed
+        }
+
+        // Otherwise, fetch the response and notify any waiting requesters.
+        // The request is removed once the fetch completes (or panics).
+        let _in_flight_request_guard = InFlightRequestGuard {
+            in_flight_requests: &self.in_flight_requests,
+            request,
+        };
+        let response = fetch_response();
+        *in_flight_response_guard = Some(response.clone());
+
+        (response, false)
+    }
+
+    #[cfg(test)]
+    /// Returns the number of requesters waiting on the in-flight request
+    /// (excluding the requester fetching the response), or None if the
+    /// request is not in flight.
+    pub(crate) fn get_num_waiting_requesters(
+        &self,
+        request: &StorageServiceRequest,
+    ) -> Option<usize> {
+        self.in_flight_requests
+            .get(request)
+            .map(|in_flight_response| Arc::strong_count(in_flight_response.value()) - 2)
+    }
+}
+
+/// Locks the in-flight response, ignoring any poisoning (the response
+/// is simply never populated if fetching it panicked).
+fn lock_in_flight_response(
+    in_flight_response: &InFlightResponse,
+) -> MutexGuard<'_, Option<Result<StorageServiceResponse, Error>>> {
+    in_flight_response
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -2,18 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    coalescer::RequestCoalescer,
     error::Error,
     logging::{LogEntry, LogSchema},
     metrics,
     metrics::{
         increment_counter, start_timer, LRU_CACHE_HIT, LRU_CACHE_PROBE, OPTIMISTIC_FETCH_ADD,
+        SERIALIZED_CACHE_HIT, SERIALIZED_CACHE_PROBE,
     },
     moderator::RequestModerator,
+    network,
     network::ResponseSender,
     optimistic_fetch::OptimisticFetchRequest,
+    serialized_cache::{SerializedResponse, SerializedResponseCache},
     storage::StorageReaderInterface,
 };
-use aptos_config::network_id::PeerNetworkId;
+use aptos_config::{config::StorageServiceConfig, network_id::PeerNetworkId};
 use aptos_infallible::Mutex;
 use aptos_logger::{debug, error, sample, sample::SampleRate, trace, warn};
 use aptos_storage_service_types::{
@@ -45,8 +49,11 @@ const SUMMARY_LOG_FREQUENCY_SECS: u64 = 5; // The frequency to log the storage s
 #[derive(Clone)]
 pub struct Handler<T> {
     cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    config: StorageServiceConfig,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
@@ -55,8 +62,11 @@ pub struct Handler<T> {
 impl<T: StorageReaderInterface> Handler<T> {
     pub fn new(
         cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+        config: StorageServiceConfig,
         optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
         lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+        request_coalescer: Arc<RequestCoalescer>,
+        serialized_response_cache: Arc<SerializedResponseCache>,
         request_moderator: Arc<RequestModerator>,
         storage: T,
         time_service: TimeService,
@@ -64,8 +74,11 @@ impl<T: StorageReaderInterface> Handler<T> {
         Self {
             storage,
             cached_storage_server_summary,
+            config,
             optimistic_fetches,
             lru_response_cache,
+            request_coalescer,
+            serialized_response_cache,
             request_moderator,
             time_service,
         }
@@ -92,11 +105,83 @@ impl<T: StorageReaderInterface> Handler<T> {
             return;
         }
 
+        // Handle any requests that can be served from the serialized response cache
+        if self.serialized_response_cache.is_cachable_request(&request) {
+            self.process_serialized_request_and_respond(peer_network_id, request, response_sender);
+            return;
+        }
+
         // Process the request and return the response to the client
         let response = self.process_request(&peer_network_id, request.clone(), false);
         self.send_response(request, response, response_sender);
     }
 
+    /// Handles the given (cachable) storage service request by responding with
+    /// a serialized response. If the serialized response is not already cached,
+    /// the request is processed and the serialized response is cached.
+    fn process_serialized_request_and_respond(
+        &self,
+        peer_network_id: PeerNetworkId,
+        request: StorageServiceRequest,
+        response_sender: ResponseSender,
+    ) {
+        increment_counter(
+            &metrics::LRU_CACHE_EVENT,
+            peer_network_id.network_id(),
+            SERIALIZED_CACHE_PROBE.into(),
+        );
+
+        // Check if the serialized response is already in the cache
+        if let Some(serialized_response) = self.serialized_response_cache.get(&request) {
+            increment_counter(
+                &metrics::LRU_CACHE_EVENT,
+                peer_network_id.network_id(),
+                SERIALIZED_CACHE_HIT.into(),
+            );
+
+            // The request must still be validated by the moderator
+            match self
+                .request_moderator
+                .validate_request(&peer_network_id, &request)
+            {
+                Ok(()) => {
+                    increment_counter(
+                        &metrics::STORAGE_RESPONSES_SENT,
+                        peer_network_id.network_id(),
+                        serialized_response.get_label(),
+                    );
+                    response_sender.send_serialized(serialized_response.get_serialized_bytes());
+                },
+                Err(error) => {
+                    let response =
+                        Err(self.handle_request_error(&peer_network_id, &request, false, error));
+                    self.send_response(request, response, response_sender);
+                },
+            }
+            return;
+        }
+
+        // Otherwise, process the request and serialize the response
+        let response = match self.process_request(&peer_network_id, request.clone(), false) {
+            Ok(response) => response,
+            Err(error) => return self.send_response(request, Err(error), response_sender),
+        };
+        let response_label = response.get_label();
+        let serialized_bytes = match network::serialize_response(Ok(response)) {
+            Ok(serialized_bytes) => serialized_bytes,
+            Err(error) => {
+                let error = StorageServiceError::InternalError(error.to_string());
+                return self.send_response(request, Err(error), response_sender);
+            },
+        };
+
+        // Cache the serialized response and send it to the client
+        let serialized_response = SerializedResponse::new(response_label, serialized_bytes);
+        self.serialized_response_cache
+            .insert(request, serialized_response.clone());
+        response_sender.send_serialized(serialized_response.get_serialized_bytes());
+    }
+
     /// Processes the given request and returns the response
     pub(crate) fn process_request(
         &self,
@@ -113,34 +198,12 @@ impl<T: StorageReaderInterface> Handler<T> {
 
         // Process the request and handle any errors
         match self.validate_and_handle_request(peer_network_id, &request) {
-            Err(error) => {
-                // Update the error counter
-                increment_counter(
-                    &metrics::STORAGE_ERRORS_ENCOUNTERED,
-                    peer_network_id.network_id(),
-                    error.get_label().into(),
-                );
-
-                // Periodically log the validation failure
-                sample!(
-                        SampleRate::Duration(Duration::from_secs(INVALID_REQUEST_LOG_FREQUENCY_SECS)),
-                        error!(LogSchema::new(LogEntry::StorageServiceError)
-                            .error(&error)
-                            .peer_network_id(peer_network_id)
-                            .request(&request)
-                            .optimistic_fetch_related(optimistic_fetch_related)
-                    );
-                );
-
-                // Return an appropriate response to the client
-                match error {
-                    Error::InvalidRequest(error) => Err(StorageServiceError::InvalidRequest(error)),
-                    Error::TooManyInvalidRequests(error) => {
-                        Err(StorageServiceError::TooManyInvalidRequests(error))
-                    },
-                    error => Err(StorageServiceError::InternalError(error.to_string())),
-                }
-            },
+            Err(error) => Err(self.handle_request_error(
+                peer_network_id,
+                &request,
+                optimistic_fetch_related,
+                error,
+            )),
             Ok(response) => {
                 // Update the successful response counter
                 increment_counter(
@@ -155,6 +218,43 @@ impl<T: StorageReaderInterface> Handler<T> {
         }
     }
 
+    /// Handles an error encountered while processing the given request
+    /// and returns the appropriate error to send to the client.
+    fn handle_request_error(
+        &self,
+        peer_network_id: &PeerNetworkId,
+        request: &StorageServiceRequest,
+        optimistic_fetch_related: bool,
+        error: Error,
+    ) -> StorageServiceError {
+        // Update the error counter
+        increment_counter(
+            &metrics::STORAGE_ERRORS_ENCOUNTERED,
+            peer_network_id.network_id(),
+            error.get_label().into(),
+        );
+
+        // Periodically log the validation failure
+        sample!(
+                SampleRate::Duration(Duration::from_secs(INVALID_REQUEST_LOG_FREQUENCY_SECS)),
+                error!(LogSchema::new(LogEntry::StorageServiceError)
+                    .error(&error)
+                    .peer_network_id(peer_network_id)
+                    .request(request)
+                    .optimistic_fetch_related(optimistic_fetch_related)
+            );
+        );
+
+        // Return an appropriate error to the client
+        match error {
+            Error::InvalidRequest(error) => StorageServiceError::InvalidRequest(error),
+            Error::TooManyInvalidRequests(error) => {
+                StorageServiceError::TooManyInvalidRequests(error)
+            },
+            error => StorageServiceError::InternalError(error.to_string()),
+        }
+    }
+
     /// Validate the request and only handle it if the moderator allows
     fn validate_and_handle_request(
         &self,
@@ -177,6 +277,11 @@ impl<T: StorageReaderInterface> Handler<T> {
                 StorageServiceResponse::new(data_response, request.use_compression)
                     .map_err(|error| error.into())
             },
+            DataRequest::GetDataWindowProof => {
+                let data_response = self.get_data_window_proof()?;
+                StorageServiceResponse::new(data_response, request.use_compression)
+                    .map_err(|error| error.into())
+            },
             _ => self.process_cachable_request(peer_network_id, request),
         }
     }
@@ -255,6 +360,27 @@ impl<T: StorageReaderInterface> Handler<T> {
             return Ok(response.clone());
         }
 
+        // Fetch the response (sharing the response of any identical in-flight request)
+        let (storage_response, coalesced) = self
+            .request_coalescer
+            .get_or_fetch_response(request, || self.fetch_cachable_response(request));
+        if coalesced {
+            increment_counter(
+                &metrics::COALESCED_STORAGE_REQUESTS,
+                peer_network_id.network_id(),
+                request.get_label(),
+            );
+        }
+
+        storage_response
+    }
+
+    /// Fetches the response for a cachable request from storage
+    /// and inserts the response into the cache.
+    fn fetch_cachable_response(
+        &self,
+        request: &StorageServiceRequest,
+    ) -> aptos_storage_service_types::Result<StorageServiceResponse, Error> {
         // Fetch the data response from storage
         let data_response = match &request.data_request {
             DataRequest::GetStateValuesWithProof(request) => {
@@ -280,6 +406,13 @@ impl<T: StorageReaderInterface> Handler<T> {
                 request
             ))),
         }?;
+
+        // Deduplicate the transaction outputs (if enabled)
+        let data_response = if self.config.enable_transaction_output_dedup {
+            data_response.deduplicate_transaction_outputs()
+        } else {
+            data_response
+        };
         let storage_response = StorageServiceResponse::new(data_response, request.use_compression)?;
 
         // Cache the response before returning
@@ -306,6 +439,12 @@ impl<T: StorageReaderInterface> Handler<T> {
         ))
     }
 
+    fn get_data_window_proof(&self) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        let data_window_proof = self.storage.get_data_window_proof()?;
+
+        Ok(DataResponse::DataWindowProof(data_window_proof))
+    }
+
     fn get_epoch_ending_ledger_infos(
         &self,
         request: &EpochEndingLedgerInfoRequest,
@@ -5,8 +5,10 @@
 #![forbid(unsafe_code)]
 
 use crate::{
+    coalescer::RequestCoalescer,
     logging::{LogEntry, LogSchema},
     network::StorageServiceNetworkEvents,
+    serialized_cache::SerializedResponseCache,
 };
 use aptos_bounded_executor::BoundedExecutor;
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
@@ -36,6 +38,7 @@ use storage::StorageReaderInterface;
 use thiserror::Error;
 use tokio::runtime::Handle;
 
+mod coalescer;
 mod error;
 mod handler;
 mod logging;
@@ -43,6 +46,7 @@ pub mod metrics;
 mod moderator;
 pub mod network;
 mod optimistic_fetch;
+mod serialized_cache;
 pub mod storage;
 mod utils;
 
@@ -73,6 +77,13 @@ pub struct StorageServiceServer<T> {
     // responses that have already been serialized and compressed.
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
 
+    // A coalescer that shares the responses of identical in-flight requests
+    request_coalescer: Arc<RequestCoalescer>,
+
+    // An LRU cache of compressed responses that have already been serialized
+    // for the network (i.e., the hottest data is served directly from memory).
+    serialized_response_cache: Arc<SerializedResponseCache>,
+
     // A set of active optimistic fetches for peers waiting for new data
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
 
@@ -108,6 +119,10 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
         let lru_response_cache = Arc::new(Mutex::new(LruCache::new(
             storage_service_config.max_lru_cache_size as usize,
         )));
+        let request_coalescer = Arc::new(RequestCoalescer::new());
+        let serialized_response_cache = Arc::new(SerializedResponseCache::new(
+            storage_service_config.max_serialized_response_cache_bytes,
+        ));
         let request_moderator = Arc::new(RequestModerator::new(
             aptos_data_client_config,
             cached_storage_server_summary.clone(),
@@ -125,6 +140,8 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             time_service,
             cached_storage_server_summary,
             lru_response_cache,
+            request_coalescer,
+            serialized_response_cache,
             optimistic_fetches,
             request_moderator,
             storage_service_listener,
@@ -225,6 +242,8 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
         let config = self.storage_service_config;
         let optimistic_fetches = self.optimistic_fetches.clone();
         let lru_response_cache = self.lru_response_cache.clone();
+        let request_coalescer = self.request_coalescer.clone();
+        let serialized_response_cache = self.serialized_response_cache.clone();
         let request_moderator = self.request_moderator.clone();
         let storage = self.storage.clone();
         let time_service = self.time_service.clone();
@@ -250,6 +269,8 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                                 config,
                                 optimistic_fetches.clone(),
                                 lru_response_cache.clone(),
+                                request_coalescer.clone(),
+                                serialized_response_cache.clone(),
                                 request_moderator.clone(),
                                 storage.clone(),
                                 time_service.clone(),
@@ -267,6 +288,8 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                                 config,
                                 optimistic_fetches.clone(),
                                 lru_response_cache.clone(),
+                                request_coalescer.clone(),
+                                serialized_response_cache.clone(),
                                 request_moderator.clone(),
                                 storage.clone(),
                                 time_service.clone(),
@@ -332,16 +355,22 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             // avoid starving other async tasks on the same runtime.
             let storage = self.storage.clone();
             let cached_storage_server_summary = self.cached_storage_server_summary.clone();
+            let config = self.storage_service_config;
             let optimistic_fetches = self.optimistic_fetches.clone();
             let lru_response_cache = self.lru_response_cache.clone();
+            let request_coalescer = self.request_coalescer.clone();
+            let serialized_response_cache = self.serialized_response_cache.clone();
             let request_moderator = self.request_moderator.clone();
             let time_service = self.time_service.clone();
             self.bounded_executor
                 .spawn_blocking(move || {
                     Handler::new(
                         cached_storage_server_summary,
+                        config,
                         optimistic_fetches,
                         lru_response_cache,
+                        request_coalescer,
+                        serialized_response_cache,
                         request_moderator,
                         storage,
                         time_service,
@@ -379,6 +408,8 @@ async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
     config: StorageServiceConfig,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
@@ -389,6 +420,8 @@ async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
         config,
         optimistic_fetches,
         lru_response_cache,
+        request_coalescer,
+        serialized_response_cache,
         request_moderator,
         storage,
         time_service,
@@ -14,6 +14,18 @@ pub const LRU_CACHE_HIT: &str = "lru_cache_hit";
 pub const LRU_CACHE_PROBE: &str = "lru_cache_probe";
 pub const OPTIMISTIC_FETCH_ADD: &str = "optimistic_fetch_add";
 pub const OPTIMISTIC_FETCH_EXPIRE: &str = "optimistic_fetch_expire";
+pub const SERIALIZED_CACHE_HIT: &str = "serialized_cache_hit";
+pub const SERIALIZED_CACHE_PROBE: &str = "serialized_cache_probe";
+
+/// Counter for requests that shared the response of an identical in-flight request
+pub static COALESCED_STORAGE_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_service_server_coalesced_requests",
+        "Counters for requests that shared the response of an identical in-flight request",
+        &["network_id", "request_type"]
+    )
+    .unwrap()
+});
 
 /// Gauge for tracking the number of actively ignored peers
 pub static IGNORED_PEER_COUNT: Lazy<IntGaugeVec> = Lazy::new(|| {
@@ -86,6 +98,16 @@ pub static PENDING_STORAGE_SERVER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::ne
     .unwrap()
 });
 
+/// Gauge for tracking the size of the serialized response cache
+pub static SERIALIZED_RESPONSE_CACHE_SIZE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_storage_service_server_serialized_response_cache_size",
+        "Gauge for tracking the size of the serialized response cache",
+        &["size_type"]
+    )
+    .unwrap()
+});
+
 /// Counter for storage service errors encountered
 pub static STORAGE_ERRORS_ENCOUNTERED: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -157,6 +179,16 @@ pub fn set_gauge(counter: &Lazy<IntGaugeVec>, label: &str, value: u64) {
     counter.with_label_values(&[label]).set(value as i64);
 }
 
+/// Sets the number of responses and bytes held by the serialized response cache
+pub fn set_serialized_response_cache_size(num_responses: u64, num_bytes: u64) {
+    set_gauge(
+        &SERIALIZED_RESPONSE_CACHE_SIZE,
+        "num_responses",
+        num_responses,
+    );
+    set_gauge(&SERIALIZED_RESPONSE_CACHE_SIZE, "num_bytes", num_bytes);
+}
+
 /// Starts the timer for the provided histogram and label values.
 pub fn start_timer(
     histogram: &Lazy<HistogramVec>,
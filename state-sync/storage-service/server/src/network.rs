@@ -105,10 +105,20 @@ impl ResponseSender {
     }
 
     pub fn send(self, response: Result<StorageServiceResponse>) {
-        let msg = StorageServiceMessage::Response(response);
-        let result = bcs::to_bytes(&msg)
-            .map(Bytes::from)
-            .map_err(RpcError::BcsError);
+        let result = serialize_response(response);
         let _ = self.response_tx.send(result);
     }
+
+    /// Sends a response that has already been serialized (see `serialize_response()`)
+    pub fn send_serialized(self, serialized_response: Bytes) {
+        let _ = self.response_tx.send(Ok(serialized_response));
+    }
+}
+
+/// Serializes the given response into the bytes sent over the network
+pub fn serialize_response(response: Result<StorageServiceResponse>) -> Result<Bytes, RpcError> {
+    let msg = StorageServiceMessage::Response(response);
+    bcs::to_bytes(&msg)
+        .map(Bytes::from)
+        .map_err(RpcError::BcsError)
 }
@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    coalescer::RequestCoalescer,
     error::Error,
     metrics,
     metrics::{increment_counter, OPTIMISTIC_FETCH_EXPIRE},
     moderator::RequestModerator,
     network::ResponseSender,
+    serialized_cache::SerializedResponseCache,
     storage::StorageReaderInterface,
     utils, LogEntry, LogSchema,
 };
@@ -179,6 +181,8 @@ pub(crate) async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
     config: StorageServiceConfig,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
@@ -193,6 +197,8 @@ pub(crate) async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
         cached_storage_server_summary.clone(),
         optimistic_fetches.clone(),
         lru_response_cache.clone(),
+        request_coalescer.clone(),
+        serialized_response_cache.clone(),
         request_moderator.clone(),
         storage.clone(),
         time_service.clone(),
@@ -206,6 +212,8 @@ pub(crate) async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
         config,
         optimistic_fetches,
         lru_response_cache,
+        request_coalescer,
+        serialized_response_cache,
         request_moderator,
         storage,
         time_service,
@@ -224,6 +232,8 @@ async fn handle_ready_optimistic_fetches<T: StorageReaderInterface>(
     config: StorageServiceConfig,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
@@ -239,6 +249,8 @@ async fn handle_ready_optimistic_fetches<T: StorageReaderInterface>(
             let cached_storage_server_summary = cached_storage_server_summary.clone();
             let optimistic_fetches = optimistic_fetches.clone();
             let lru_response_cache = lru_response_cache.clone();
+            let request_coalescer = request_coalescer.clone();
+            let serialized_response_cache = serialized_response_cache.clone();
             let request_moderator = request_moderator.clone();
             let storage = storage.clone();
             let time_service = time_service.clone();
@@ -256,6 +268,8 @@ async fn handle_ready_optimistic_fetches<T: StorageReaderInterface>(
                         config,
                         optimistic_fetches.clone(),
                         lru_response_cache.clone(),
+                        request_coalescer.clone(),
+                        serialized_response_cache.clone(),
                         request_moderator.clone(),
                         storage.clone(),
                         time_service.clone(),
@@ -292,6 +306,8 @@ pub(crate) async fn get_peers_with_ready_optimistic_fetches<T: StorageReaderInte
     cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
@@ -316,6 +332,8 @@ pub(crate) async fn get_peers_with_ready_optimistic_fetches<T: StorageReaderInte
         cached_storage_server_summary,
         optimistic_fetches.clone(),
         lru_response_cache,
+        request_coalescer,
+        serialized_response_cache,
         request_moderator,
         storage,
         time_service,
@@ -346,6 +364,8 @@ async fn identify_expired_invalid_and_ready_fetches<T: StorageReaderInterface>(
     cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
@@ -386,8 +406,11 @@ async fn identify_expired_invalid_and_ready_fetches<T: StorageReaderInterface>(
         identify_ready_and_invalid_optimistic_fetches(
             bounded_executor,
             cached_storage_server_summary,
+            config,
             optimistic_fetches,
             lru_response_cache,
+            request_coalescer,
+            serialized_response_cache,
             request_moderator,
             storage,
             time_service,
@@ -411,8 +434,11 @@ async fn identify_expired_invalid_and_ready_fetches<T: StorageReaderInterface>(
 async fn identify_ready_and_invalid_optimistic_fetches<T: StorageReaderInterface>(
     bounded_executor: BoundedExecutor,
     cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    config: StorageServiceConfig,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
@@ -438,6 +464,8 @@ async fn identify_ready_and_invalid_optimistic_fetches<T: StorageReaderInterface
         let highest_synced_ledger_info = highest_synced_ledger_info.clone();
         let optimistic_fetches = optimistic_fetches.clone();
         let lru_response_cache = lru_response_cache.clone();
+        let request_coalescer = request_coalescer.clone();
+        let serialized_response_cache = serialized_response_cache.clone();
         let request_moderator = request_moderator.clone();
         let storage = storage.clone();
         let time_service = time_service.clone();
@@ -455,9 +483,12 @@ async fn identify_ready_and_invalid_optimistic_fetches<T: StorageReaderInterface
                         // peer needs to sync to their epoch ending ledger info).
                         let epoch_ending_ledger_info = match utils::get_epoch_ending_ledger_info(
                             cached_storage_server_summary.clone(),
+                            config,
                             optimistic_fetches.clone(),
                             highest_known_epoch,
                             lru_response_cache.clone(),
+                            request_coalescer.clone(),
+                            serialized_response_cache.clone(),
                             request_moderator.clone(),
                             &peer_network_id,
                             storage.clone(),
@@ -0,0 +1,131 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics;
+use aptos_infallible::Mutex;
+use aptos_storage_service_types::requests::{DataRequest, StorageServiceRequest};
+use bytes::Bytes;
+use lru::LruCache;
+
+/// A response that has already been serialized (and compressed) for the
+/// network, along with the label of the response (for metrics and logging).
+#[derive(Clone, Debug)]
+pub struct SerializedResponse {
+    response_label: String,
+    serialized_bytes: Bytes,
+}
+
+impl SerializedResponse {
+    pub fn new(response_label: String, serialized_bytes: Bytes) -> Self {
+        Self {
+            response_label,
+            serialized_bytes,
+        }
+    }
+
+    /// Returns the label of the serialized response
+    pub fn get_label(&self) -> String {
+        self.response_label.clone()
+    }
+
+    /// Returns the serialized bytes of the response (the
+    /// bytes are reference counted, so this is cheap).
+    pub fn get_serialized_bytes(&self) -> Bytes {
+        self.serialized_bytes.clone()
+    }
+}
+
+/// The LRU of serialized responses and their total size (in bytes)
+struct CachedResponses {
+    lru_cache: LruCache<StorageServiceRequest, SerializedResponse>,
+    num_cached_bytes: u64,
+}
+
+/// An LRU cache of compressed responses that have already been serialized for
+/// the network. This allows popular data (e.g., during mass syncing events) to
+/// be served directly from memory, without hitting storage, compressing or
+/// serializing the same response for each request. The cache is bounded by the
+/// total number of serialized bytes it holds (a max of 0 disables the cache).
+pub struct SerializedResponseCache {
+    cached_responses: Mutex<CachedResponses>,
+    max_cache_size_bytes: u64,
+}
+
+impl SerializedResponseCache {
+    pub fn new(max_cache_size_bytes: u64) -> Self {
+        let cached_responses = CachedResponses {
+            lru_cache: LruCache::unbounded(),
+            num_cached_bytes: 0,
+        };
+        Self {
+            cached_responses: Mutex::new(cached_responses),
+            max_cache_size_bytes,
+        }
+    }
+
+    /// Returns true iff the response to the given request may be cached. Only
+    /// compressed data requests (excluding summaries, versions, data window proofs
+    /// and optimistic fetches, which change frequently) are cached.
+    pub fn is_cachable_request(&self, request: &StorageServiceRequest) -> bool {
+        self.max_cache_size_bytes > 0
+            && request.use_compression
+            && !request.data_request.is_optimistic_fetch()
+            && !matches!(
+                request.data_request,
+                DataRequest::GetServerProtocolVersion
+                    | DataRequest::GetStorageServerSummary
+                    | DataRequest::GetDataWindowProof
+            )
+    }
+
+    /// Returns the serialized response for the given request (if cached)
+    pub fn get(&self, request: &StorageServiceRequest) -> Option<SerializedResponse> {
+        self.cached_responses.lock().lru_cache.get(request).cloned()
+    }
+
+    /// Inserts the serialized response for the given request into the cache,
+    /// and evicts the least recently used responses if the cache is too large.
+    pub fn insert(&self, request: StorageServiceRequest, serialized_response: SerializedResponse) {
+        // Responses larger than the entire cache are never cached
+        let num_response_bytes = serialized_response.serialized_bytes.len() as u64;
+        if num_response_bytes > self.max_cache_size_bytes {
+            return;
+        }
+
+        // Insert the response (replacing any existing response for the request)
+        let mut cached_responses = self.cached_responses.lock();
+        if let Some(existing_response) =
+            cached_responses.lru_cache.put(request, serialized_response)
+        {
+            cached_responses.num_cached_bytes -= existing_response.serialized_bytes.len() as u64;
+        }
+        cached_responses.num_cached_bytes += num_response_bytes;
+
+        // Evict the least recently used responses until the cache fits
+        while cached_responses.num_cached_bytes > self.max_cache_size_bytes {
+            match cached_responses.lru_cache.pop_lru() {
+                Some((_, evicted_response)) => {
+                    cached_responses.num_cached_bytes -=
+                        evicted_response.serialized_bytes.len() as u64;
+                },
+                None => break, // This should never happen (the cache is empty)
+            }
+        }
+
+        // Update the cache size metrics
+        metrics::set_serialized_response_cache_size(
+            cached_responses.lru_cache.len() as u64,
+            cached_responses.num_cached_bytes,
+        );
+    }
+
+    #[cfg(test)]
+    /// Returns the number of cached responses and their total size (in bytes)
+    pub(crate) fn get_cache_size(&self) -> (usize, u64) {
+        let cached_responses = self.cached_responses.lock();
+        (
+            cached_responses.lru_cache.len(),
+            cached_responses.num_cached_bytes,
+        )
+    }
+}
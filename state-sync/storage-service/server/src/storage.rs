@@ -6,7 +6,7 @@ use aptos_config::config::StorageServiceConfig;
 use aptos_logger::debug;
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_types::responses::{
-    CompleteDataRange, DataResponse, DataSummary, TransactionOrOutputListWithProof,
+    CompleteDataRange, DataResponse, DataSummary, DataWindowProof, TransactionOrOutputListWithProof,
 };
 use aptos_types::{
     epoch_change::EpochChangeProof,
@@ -14,7 +14,10 @@ use aptos_types::{
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use serde::Serialize;
-use std::{cmp::min, sync::Arc};
+use std::{
+    cmp::{max, min},
+    sync::Arc,
+};
 
 /// The interface into local storage (e.g., the Aptos DB) used by the storage
 /// server to handle client requests and responses.
@@ -22,6 +25,12 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
     /// Returns a data summary of the underlying storage state.
     fn get_data_summary(&self) -> aptos_storage_service_types::Result<DataSummary, Error>;
 
+    /// Returns a proof of the data window retained by the underlying storage,
+    /// or `None` if the storage is not pruned (i.e., the ledger pruner is disabled).
+    fn get_data_window_proof(
+        &self,
+    ) -> aptos_storage_service_types::Result<Option<DataWindowProof>, Error>;
+
     /// Returns a list of transactions with a proof relative to the
     /// `proof_version`. The transaction list is expected to start at
     /// `start_version` and end at `end_version` (inclusive). In some cases,
@@ -225,6 +234,55 @@ impl StorageReaderInterface for StorageReader {
         Ok(data_summary)
     }
 
+    fn get_data_window_proof(
+        &self,
+    ) -> aptos_storage_service_types::Result<Option<DataWindowProof>, Error> {
+        let pruner_enabled = self
+            .storage
+            .is_ledger_pruner_enabled()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        if !pruner_enabled {
+            return Ok(None);
+        }
+        let window_size = self
+            .storage
+            .get_ledger_prune_window()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+
+        // The lowest retained version is the lowest version at which
+        // both the transactions and the transaction outputs are held.
+        let first_transaction_version = self
+            .storage
+            .get_first_txn_version()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let first_output_version = self
+            .storage
+            .get_first_write_set_version()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let lowest_version = match (first_transaction_version, first_output_version) {
+            (Some(first_transaction_version), Some(first_output_version)) => {
+                max(first_transaction_version, first_output_version)
+            },
+            _ => return Ok(None), // No data is held yet
+        };
+
+        // Prove the lowest retained transaction relative to the latest ledger info
+        let ledger_info = self
+            .storage
+            .get_latest_ledger_info()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let lowest_transaction = self
+            .storage
+            .get_transaction_by_version(lowest_version, ledger_info.ledger_info().version(), false)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+
+        Ok(Some(DataWindowProof {
+            window_size: window_size as u64,
+            ledger_info,
+            lowest_transaction,
+        }))
+    }
+
     fn get_transactions_with_proof(
         &self,
         proof_version: u64,
@@ -0,0 +1,119 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tests::{mock, mock::MockClient, utils};
+use aptos_crypto::HashValue;
+use aptos_storage_service_types::{
+    requests::DataRequest,
+    responses::{DataResponse, DataWindowProof, StorageServiceResponse},
+};
+use aptos_types::{
+    proof::{TransactionAccumulatorProof, TransactionInfoWithProof},
+    transaction::{ExecutionStatus, Transaction, TransactionInfo, TransactionWithProof},
+};
+use claims::assert_matches;
+use mockall::predicate::eq;
+
+#[tokio::test]
+async fn test_get_data_window_proof() {
+    // Create test data
+    let highest_version = 1000;
+    let lowest_version = 901;
+    let ledger_prune_window = 100;
+    let highest_ledger_info = utils::create_test_ledger_info_with_sigs(1, highest_version);
+    let lowest_transaction = create_transaction_with_proof(lowest_version);
+
+    // Create the mock db reader
+    let mut db_reader = mock::create_mock_db_reader();
+    db_reader
+        .expect_is_ledger_pruner_enabled()
+        .returning(|| Ok(true));
+    db_reader
+        .expect_get_ledger_prune_window()
+        .returning(move || Ok(ledger_prune_window));
+    db_reader
+        .expect_get_first_txn_version()
+        .returning(move || Ok(Some(lowest_version - 1)));
+    db_reader
+        .expect_get_first_write_set_version()
+        .returning(move || Ok(Some(lowest_version)));
+    let highest_ledger_info_clone = highest_ledger_info.clone();
+    db_reader
+        .expect_get_latest_ledger_info()
+        .returning(move || Ok(highest_ledger_info_clone.clone()));
+    let lowest_transaction_clone = lowest_transaction.clone();
+    db_reader
+        .expect_get_transaction_by_version()
+        .times(1)
+        .with(eq(lowest_version), eq(highest_version), eq(false))
+        .returning(move |_, _, _| Ok(lowest_transaction_clone.clone()));
+
+    // Create the storage client and server
+    let (mut mock_client, service, _, _, _) = MockClient::new(Some(db_reader), None);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the data window proof
+    let response = get_data_window_proof(&mut mock_client, false).await;
+
+    // Verify the response is correct
+    let expected_data_window_proof = DataWindowProof {
+        window_size: ledger_prune_window as u64,
+        ledger_info: highest_ledger_info,
+        lowest_transaction,
+    };
+    assert_matches!(response, StorageServiceResponse::RawResponse(_));
+    assert_eq!(
+        response.get_data_response().unwrap(),
+        DataResponse::DataWindowProof(Some(expected_data_window_proof))
+    );
+}
+
+#[tokio::test]
+async fn test_get_data_window_proof_not_pruned() {
+    // Create the mock db reader (with the ledger pruner disabled)
+    let mut db_reader = mock::create_mock_db_reader();
+    db_reader
+        .expect_is_ledger_pruner_enabled()
+        .returning(|| Ok(false));
+
+    // Create the storage client and server
+    let (mut mock_client, service, _, _, _) = MockClient::new(Some(db_reader), None);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the data window proof
+    let response = get_data_window_proof(&mut mock_client, true).await;
+
+    // Verify no proof is returned
+    assert_matches!(response, StorageServiceResponse::CompressedResponse(_, _));
+    assert_eq!(
+        response.get_data_response().unwrap(),
+        DataResponse::DataWindowProof(None)
+    );
+}
+
+/// Creates a transaction with a (dummy) proof at the given version
+fn create_transaction_with_proof(version: u64) -> TransactionWithProof {
+    let transaction = Transaction::StateCheckpoint(HashValue::random());
+    let transaction_info = TransactionInfo::new(
+        HashValue::random(),
+        HashValue::random(),
+        HashValue::random(),
+        None,
+        0,
+        ExecutionStatus::Success,
+    );
+    let proof =
+        TransactionInfoWithProof::new(TransactionAccumulatorProof::new(vec![]), transaction_info);
+    TransactionWithProof::new(version, transaction, None, proof)
+}
+
+/// Sends a data window proof request and processes the response
+async fn get_data_window_proof(
+    mock_client: &mut MockClient,
+    use_compression: bool,
+) -> StorageServiceResponse {
+    let data_request = DataRequest::GetDataWindowProof;
+    utils::send_storage_request(mock_client, use_compression, data_request)
+        .await
+        .unwrap()
+}
@@ -351,6 +351,10 @@ mock! {
         fn get_epoch_snapshot_prune_window(&self) -> Result<usize>;
 
         fn is_state_merkle_pruner_enabled(&self) -> Result<bool>;
+
+        fn is_ledger_pruner_enabled(&self) -> Result<bool>;
+
+        fn get_ledger_prune_window(&self) -> Result<usize>;
     }
 }
 
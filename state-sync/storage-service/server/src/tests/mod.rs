@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod cache;
+mod data_window_proof;
 mod epoch_ending;
 mod mock;
 mod new_transaction_outputs;
@@ -10,7 +11,9 @@ mod new_transactions_or_outputs;
 mod number_of_states;
 mod optimistic_fetch;
 mod protocol_version;
+mod request_coalescer;
 mod request_moderator;
+mod serialized_cache;
 mod state_values;
 mod storage_summary;
 mod transaction_outputs;
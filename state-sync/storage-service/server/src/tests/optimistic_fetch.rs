@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    coalescer::RequestCoalescer,
     moderator::RequestModerator,
     network::ResponseSender,
     optimistic_fetch,
     optimistic_fetch::OptimisticFetchRequest,
+    serialized_cache::SerializedResponseCache,
     storage::StorageReader,
     tests::{mock, utils},
 };
@@ -71,6 +73,10 @@ async fn test_peers_with_ready_optimistic_fetches() {
     let cached_storage_server_summary =
         Arc::new(ArcSwap::from(Arc::new(StorageServerSummary::default())));
     let lru_response_cache = Arc::new(Mutex::new(LruCache::new(0)));
+    let request_coalescer = Arc::new(RequestCoalescer::new());
+    let serialized_response_cache = Arc::new(SerializedResponseCache::new(
+        storage_service_config.max_serialized_response_cache_bytes,
+    ));
     let request_moderator = Arc::new(RequestModerator::new(
         AptosDataClientConfig::default(),
         cached_storage_server_summary.clone(),
@@ -87,6 +93,8 @@ async fn test_peers_with_ready_optimistic_fetches() {
             cached_storage_server_summary.clone(),
             optimistic_fetches.clone(),
             lru_response_cache.clone(),
+            request_coalescer.clone(),
+            serialized_response_cache.clone(),
             request_moderator.clone(),
             storage_reader.clone(),
             time_service.clone(),
@@ -107,6 +115,8 @@ async fn test_peers_with_ready_optimistic_fetches() {
             cached_storage_server_summary.clone(),
             optimistic_fetches.clone(),
             lru_response_cache.clone(),
+            request_coalescer.clone(),
+            serialized_response_cache.clone(),
             request_moderator.clone(),
             storage_reader.clone(),
             time_service.clone(),
@@ -133,6 +143,8 @@ async fn test_peers_with_ready_optimistic_fetches() {
             cached_storage_server_summary,
             optimistic_fetches,
             lru_response_cache,
+            request_coalescer,
+            serialized_response_cache,
             request_moderator,
             storage_reader,
             time_service,
@@ -164,6 +176,10 @@ async fn test_remove_expired_optimistic_fetches() {
     let cached_storage_server_summary =
         Arc::new(ArcSwap::from(Arc::new(StorageServerSummary::default())));
     let lru_response_cache = Arc::new(Mutex::new(LruCache::new(0)));
+    let request_coalescer = Arc::new(RequestCoalescer::new());
+    let serialized_response_cache = Arc::new(SerializedResponseCache::new(
+        storage_service_config.max_serialized_response_cache_bytes,
+    ));
     let request_moderator = Arc::new(RequestModerator::new(
         AptosDataClientConfig::default(),
         cached_storage_server_summary.clone(),
@@ -198,6 +214,8 @@ async fn test_remove_expired_optimistic_fetches() {
             cached_storage_server_summary.clone(),
             optimistic_fetches.clone(),
             lru_response_cache.clone(),
+            request_coalescer.clone(),
+            serialized_response_cache.clone(),
             request_moderator.clone(),
             storage.clone(),
             time_service.clone(),
@@ -231,6 +249,8 @@ async fn test_remove_expired_optimistic_fetches() {
             cached_storage_server_summary.clone(),
             optimistic_fetches.clone(),
             lru_response_cache.clone(),
+            request_coalescer.clone(),
+            serialized_response_cache.clone(),
             request_moderator.clone(),
             storage.clone(),
             time_service.clone(),
@@ -251,6 +271,8 @@ async fn test_remove_expired_optimistic_fetches() {
             cached_storage_server_summary.clone(),
             optimistic_fetches.clone(),
             lru_response_cache,
+            request_coalescer,
+            serialized_response_cache,
             request_moderator,
             storage.clone(),
             time_service.clone(),
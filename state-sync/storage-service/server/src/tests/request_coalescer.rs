@@ -0,0 +1,196 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{coalescer::RequestCoalescer, error::Error};
+use aptos_storage_service_types::{
+    requests::{DataRequest, StorageServiceRequest},
+    responses::{DataResponse, StorageServiceResponse},
+};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn test_identical_requests_are_coalesced() {
+    // Create the request coalescer and a test request
+    let request_coalescer = Arc::new(RequestCoalescer::new());
+    let request = StorageServiceRequest::new(DataRequest::GetNumberOfStatesAtVersion(10), true);
+    let storage_response =
+        StorageServiceResponse::new(DataResponse::NumberOfStatesAtVersion(100), true).unwrap();
+
+    // Spawn a requester that blocks while fetching the response
+    let num_fetches = Arc::new(AtomicU64::new(0));
+    let (fetch_sender, fetch_receiver) = mpsc::channel::<()>();
+    let fetching_requester = spawn_requester(
+        request_coalescer.clone(),
+        request.clone(),
+        num_fetches.clone(),
+        Some(fetch_receiver),
+        Ok(storage_response.clone()),
+    );
+    wait_for_waiting_requesters(&request_coalescer, &request, 0);
+
+    // Spawn several identical requests and wait for them to block
+    let num_waiting_requesters = 10;
+    let waiting_requesters: Vec<_> = (0..num_waiting_requesters)
+        .map(|_| {
+            spawn_requester(
+                request_coalescer.clone(),
+                request.clone(),
+                num_fetches.clone(),
+                None,
+                Ok(storage_response.clone()),
+            )
+        })
+        .collect();
+    wait_for_waiting_requesters(&request_coalescer, &request, num_waiting_requesters);
+
+    // Unblock the fetch and verify the response is only fetched once
+    fetch_sender.send(()).unwrap();
+    let (response, coalesced) = fetching_requester.join().unwrap();
+    assert_eq!(response, Ok(storage_response.clone()));
+    assert!(!coalesced);
+    for waiting_requester in waiting_requesters {
+        let (response, coalesced) = waiting_requester.join().unwrap();
+        assert_eq!(response, Ok(storage_response.clone()));
+        assert!(coalesced);
+    }
+    assert_eq!(num_fetches.load(Ordering::Relaxed), 1);
+
+    // Verify that new requests are no longer coalesced
+    let (response, coalesced) = spawn_requester(
+        request_coalescer,
+        request,
+        num_fetches.clone(),
+        None,
+        Ok(storage_response.clone()),
+    )
+    .join()
+    .unwrap();
+    assert_eq!(response, Ok(storage_response));
+    assert!(!coalesced);
+    assert_eq!(num_fetches.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_errors_are_coalesced() {
+    // Create the request coalescer and a test request
+    let request_coalescer = Arc::new(RequestCoalescer::new());
+    let request = StorageServiceRequest::new(DataRequest::GetNumberOfStatesAtVersion(10), false);
+    let error = Error::StorageErrorEncountered("Failed to fetch the data!".into());
+
+    // Spawn a requester that blocks while fetching the response
+    let num_fetches = Arc::new(AtomicU64::new(0));
+    let (fetch_sender, fetch_receiver) = mpsc::channel::<()>();
+    let fetching_requester = spawn_requester(
+        request_coalescer.clone(),
+        request.clone(),
+        num_fetches.clone(),
+        Some(fetch_receiver),
+        Err(error.clone()),
+    );
+    wait_for_waiting_requesters(&request_coalescer, &request, 0);
+
+    // Spawn an identical request and wait for it to block
+    let waiting_requester = spawn_requester(
+        request_coalescer.clone(),
+        request.clone(),
+        num_fetches.clone(),
+        None,
+        Err(error.clone()),
+    );
+    wait_for_waiting_requesters(&request_coalescer, &request, 1);
+
+    // Unblock the fetch and verify the error is shared
+    fetch_sender.send(()).unwrap();
+    assert_eq!(
+        fetching_requester.join().unwrap(),
+        (Err(error.clone()), false)
+    );
+    assert_eq!(waiting_requester.join().unwrap(), (Err(error), true));
+    assert_eq!(num_fetches.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_panicked_fetch_is_removed() {
+    // Create the request coalescer and a test request
+    let request_coalescer = Arc::new(RequestCoalescer::new());
+    let request = StorageServiceRequest::new(DataRequest::GetNumberOfStatesAtVersion(10), true);
+    let storage_response =
+        StorageServiceResponse::new(DataResponse::NumberOfStatesAtVersion(100), true).unwrap();
+
+    // Spawn a requester that blocks while fetching the response (and then panics)
+    let (fetch_sender, fetch_receiver) = mpsc::channel::<()>();
+    let panicking_requester = {
+        let request_coalescer = request_coalescer.clone();
+        let request = request.clone();
+        thread::spawn(move || {
+            request_coalescer.get_or_fetch_response(&request, || {
+                fetch_receiver.recv().unwrap();
+                panic!("Failed to fetch the response!");
+            })
+        })
+    };
+    wait_for_waiting_requesters(&request_coalescer, &request, 0);
+
+    // Spawn an identical request and wait for it to block
+    let num_fetches = Arc::new(AtomicU64::new(0));
+    let waiting_requester = spawn_requester(
+        request_coalescer.clone(),
+        request.clone(),
+        num_fetches.clone(),
+        None,
+        Ok(storage_response.clone()),
+    );
+    wait_for_waiting_requesters(&request_coalescer, &request, 1);
+
+    // Unblock the fetch and verify the waiting requester fetches the response itself
+    fetch_sender.send(()).unwrap();
+    assert!(panicking_requester.join().is_err());
+    assert_eq!(
+        waiting_requester.join().unwrap(),
+        (Ok(storage_response), false)
+    );
+    assert_eq!(num_fetches.load(Ordering::Relaxed), 1);
+
+    // Verify the request is no longer in flight
+    assert_eq!(request_coalescer.get_num_waiting_requesters(&request), None);
+}
+
+/// Spawns a requester thread that fetches the response via the coalescer.
+/// If a fetch receiver is given, the fetch blocks until it is notified.
+fn spawn_requester(
+    request_coalescer: Arc<RequestCoalescer>,
+    request: StorageServiceRequest,
+    num_fetches: Arc<AtomicU64>,
+    fetch_receiver: Option<mpsc::Receiver<()>>,
+    fetch_result: Result<StorageServiceResponse, Error>,
+) -> thread::JoinHandle<(Result<StorageServiceResponse, Error>, bool)> {
+    thread::spawn(move || {
+        request_coalescer.get_or_fetch_response(&request, || {
+            num_fetches.fetch_add(1, Ordering::Relaxed);
+            if let Some(fetch_receiver) = fetch_receiver {
+                fetch_receiver.recv().unwrap();
+            }
+            fetch_result
+        })
+    })
+}
+
+/// Waits until the request is in flight with the expected number of waiting requesters
+fn wait_for_waiting_requesters(
+    request_coalescer: &RequestCoalescer,
+    request: &StorageServiceRequest,
+    expected_num_waiting_requesters: usize,
+) {
+    while request_coalescer.get_num_waiting_requesters(request)
+        != Some(expected_num_waiting_requesters)
+    {
+        thread::sleep(Duration::from_millis(10));
+    }
+}
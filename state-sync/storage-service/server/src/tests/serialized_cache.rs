@@ -0,0 +1,181 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    serialized_cache::{SerializedResponse, SerializedResponseCache},
+    tests::{mock, mock::MockClient, utils},
+};
+use aptos_config::config::StorageServiceConfig;
+use aptos_crypto::hash::HashValue;
+use aptos_storage_service_types::{
+    requests::{DataRequest, NewTransactionsWithProofRequest, StorageServiceRequest},
+    responses::{DataResponse, StorageServiceResponse},
+};
+use aptos_types::{
+    proof::definition::SparseMerkleRangeProof, state_store::state_value::StateValueChunkWithProof,
+};
+use bytes::Bytes;
+use mockall::predicate::{always, eq};
+
+#[test]
+fn test_cachable_requests() {
+    // Create a serialized response cache
+    let serialized_response_cache = SerializedResponseCache::new(1024);
+
+    // Verify that compressed data requests are cachable
+    let request = StorageServiceRequest::new(DataRequest::GetNumberOfStatesAtVersion(10), true);
+    assert!(serialized_response_cache.is_cachable_request(&request));
+
+    // Verify that uncompressed requests are not cachable
+    let request = StorageServiceRequest::new(DataRequest::GetNumberOfStatesAtVersion(10), false);
+    assert!(!serialized_response_cache.is_cachable_request(&request));
+
+    // Verify that frequently changing requests are not cachable
+    let optimistic_fetch_request =
+        DataRequest::GetNewTransactionsWithProof(NewTransactionsWithProofRequest {
+            known_version: 10,
+            known_epoch: 1,
+            include_events: false,
+        });
+    for data_request in [
+        DataRequest::GetServerProtocolVersion,
+        DataRequest::GetStorageServerSummary,
+        optimistic_fetch_request,
+    ] {
+        let request = StorageServiceRequest::new(data_request, true);
+        assert!(!serialized_response_cache.is_cachable_request(&request));
+    }
+
+    // Verify that no requests are cachable if the cache is disabled
+    let serialized_response_cache = SerializedResponseCache::new(0);
+    let request = StorageServiceRequest::new(DataRequest::GetNumberOfStatesAtVersion(10), true);
+    assert!(!serialized_response_cache.is_cachable_request(&request));
+}
+
+#[test]
+fn test_cache_eviction() {
+    // Create a serialized response cache that can hold 300 bytes
+    let serialized_response_cache = SerializedResponseCache::new(300);
+
+    // Insert several responses and verify they are cached
+    for version in 0..3 {
+        serialized_response_cache.insert(create_request(version), create_response(100));
+    }
+    assert_eq!(serialized_response_cache.get_cache_size(), (3, 300));
+    for version in 0..3 {
+        let serialized_response = serialized_response_cache
+            .get(&create_request(version))
+            .unwrap();
+        assert_eq!(serialized_response.get_serialized_bytes().len(), 100);
+    }
+
+    // Insert another response and verify the least recently used response is evicted
+    serialized_response_cache.insert(create_request(3), create_response(150));
+    assert_eq!(serialized_response_cache.get_cache_size(), (2, 250));
+    for version in 0..2 {
+        assert!(serialized_response_cache
+            .get(&create_request(version))
+            .is_none());
+    }
+
+    // Replace an existing response and verify the cache size is updated
+    serialized_response_cache.insert(create_request(3), create_response(50));
+    assert_eq!(serialized_response_cache.get_cache_size(), (2, 150));
+
+    // Verify that responses larger than the cache are never cached
+    serialized_response_cache.insert(create_request(4), create_response(301));
+    assert_eq!(serialized_response_cache.get_cache_size(), (2, 150));
+    assert!(serialized_response_cache.get(&create_request(4)).is_none());
+}
+
+#[tokio::test]
+async fn test_serialized_responses_outlive_lru_cache() {
+    // Create test data
+    let max_lru_cache_size = StorageServiceConfig::default().max_lru_cache_size;
+    let version = 101;
+    let start_index = 100;
+    let end_index = 199;
+    let state_value_chunk_with_proof = StateValueChunkWithProof {
+        first_index: start_index,
+        last_index: end_index,
+        first_key: HashValue::random(),
+        last_key: HashValue::random(),
+        raw_values: vec![],
+        proof: SparseMerkleRangeProof::new(vec![]),
+        root_hash: HashValue::random(),
+    };
+
+    // Create the mock db reader and expect the state chunk to be fetched exactly once
+    let mut db_reader = mock::create_mock_db_reader();
+    db_reader
+        .expect_get_state_leaf_count()
+        .times(max_lru_cache_size as usize)
+        .with(always())
+        .returning(move |_| Ok(165));
+    let state_value_chunk_with_proof_clone = state_value_chunk_with_proof.clone();
+    db_reader
+        .expect_get_state_value_chunk_with_proof()
+        .times(1)
+        .with(
+            eq(version),
+            eq(start_index as usize),
+            eq((end_index - start_index + 1) as usize),
+        )
+        .return_once(move |_, _, _| Ok(state_value_chunk_with_proof_clone));
+
+    // Create the storage client and server (with the serialized response cache enabled)
+    let storage_config = StorageServiceConfig {
+        max_serialized_response_cache_bytes: 1024 * 1024,
+        ..Default::default()
+    };
+    let (mut mock_client, mut service, _, _, _) =
+        MockClient::new(Some(db_reader), Some(storage_config));
+    utils::update_storage_server_summary(&mut service, version + 10, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch a state chunk. This should serialize and cache the response.
+    let response =
+        utils::get_state_values_with_proof(&mut mock_client, version, start_index, end_index, true)
+            .await
+            .unwrap();
+    verify_state_value_chunk_response(response, &state_value_chunk_with_proof);
+
+    // Process enough requests to evict the response from the LRU cache
+    for version in 0..max_lru_cache_size {
+        let _ = utils::get_number_of_states(&mut mock_client, version, true).await;
+    }
+
+    // Process the state chunk request again and verify the serialized response is served
+    let response =
+        utils::get_state_values_with_proof(&mut mock_client, version, start_index, end_index, true)
+            .await
+            .unwrap();
+    verify_state_value_chunk_response(response, &state_value_chunk_with_proof);
+}
+
+/// Creates a compressed storage request for the number of states at the given version
+fn create_request(version: u64) -> StorageServiceRequest {
+    StorageServiceRequest::new(DataRequest::GetNumberOfStatesAtVersion(version), true)
+}
+
+/// Creates a serialized response with the specified number of bytes
+fn create_response(num_bytes: usize) -> SerializedResponse {
+    SerializedResponse::new("number_of_states".into(), Bytes::from(vec![0; num_bytes]))
+}
+
+/// Verifies that the response is a compressed response with the expected state chunk
+fn verify_state_value_chunk_response(
+    response: StorageServiceResponse,
+    expected_state_value_chunk: &StateValueChunkWithProof,
+) {
+    assert!(response.is_compressed());
+    match response.get_data_response().unwrap() {
+        DataResponse::StateValueChunkWithProof(state_value_chunk) => {
+            assert_eq!(&state_value_chunk, expected_state_value_chunk)
+        },
+        _ => panic!(
+            "Expected state value chunk with proof but got: {:?}",
+            response
+        ),
+    }
+}
@@ -62,6 +62,64 @@ async fn test_get_transaction_outputs_with_proof() {
     }
 }
 
+#[tokio::test]
+async fn test_get_transaction_outputs_with_proof_dedup() {
+    // Create test data
+    let start_version = 0;
+    let end_version = 99;
+    let proof_version = end_version;
+    let output_list_with_proof =
+        utils::create_output_list_with_proof(start_version, end_version, proof_version);
+
+    // Create the mock db reader
+    let mut db_reader = mock::create_mock_db_reader();
+    utils::expect_get_transaction_outputs(
+        &mut db_reader,
+        start_version,
+        end_version - start_version + 1,
+        proof_version,
+        output_list_with_proof.clone(),
+    );
+
+    // Create the storage client and server (with deduplication enabled)
+    let storage_config = StorageServiceConfig {
+        enable_transaction_output_dedup: true,
+        ..Default::default()
+    };
+    let (mut mock_client, mut service, _, _, _) =
+        MockClient::new(Some(db_reader), Some(storage_config));
+    utils::update_storage_server_summary(&mut service, proof_version + 100, 10);
+    tokio::spawn(service.start());
+
+    // Create a request to fetch transactions outputs with a proof
+    let response = get_outputs_with_proof(
+        &mut mock_client,
+        start_version,
+        end_version,
+        end_version,
+        false,
+    )
+    .await
+    .unwrap();
+
+    // Verify the outputs were deduplicated on the wire
+    assert_matches!(
+        response,
+        StorageServiceResponse::RawResponse(DataResponse::DedupedTransactionOutputsWithProof(_))
+    );
+
+    // Verify the response is expanded correctly
+    match response.get_data_response().unwrap() {
+        DataResponse::TransactionOutputsWithProof(outputs_with_proof) => {
+            assert_eq!(outputs_with_proof, output_list_with_proof)
+        },
+        _ => panic!(
+            "Expected transaction outputs with proof but got: {:?}",
+            response
+        ),
+    };
+}
+
 #[tokio::test]
 async fn test_get_transaction_outputs_with_proof_chunk_limit() {
     // Create test data
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    error::Error, handler::Handler, moderator::RequestModerator,
-    optimistic_fetch::OptimisticFetchRequest, storage::StorageReaderInterface,
+    coalescer::RequestCoalescer, error::Error, handler::Handler, moderator::RequestModerator,
+    optimistic_fetch::OptimisticFetchRequest, serialized_cache::SerializedResponseCache,
+    storage::StorageReaderInterface,
 };
 use aptos_config::{config::StorageServiceConfig, network_id::PeerNetworkId};
 use aptos_infallible::Mutex;
@@ -21,9 +22,12 @@ use std::sync::Arc;
 /// Gets the epoch ending ledger info at the given epoch
 pub fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
     cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    config: StorageServiceConfig,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     epoch: u64,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     peer_network_id: &PeerNetworkId,
     storage: T,
@@ -42,8 +46,11 @@ pub fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
     // Process the request
     let handler = Handler::new(
         cached_storage_server_summary,
+        config,
         optimistic_fetches,
         lru_response_cache,
+        request_coalescer,
+        serialized_response_cache,
         request_moderator,
         storage,
         time_service,
@@ -84,6 +91,8 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
     config: StorageServiceConfig,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_coalescer: Arc<RequestCoalescer>,
+    serialized_response_cache: Arc<SerializedResponseCache>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
@@ -97,8 +106,11 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
             let use_compression = storage_request.use_compression;
             let handler = Handler::new(
                 cached_storage_server_summary,
+                config,
                 optimistic_fetches,
                 lru_response_cache,
+                request_coalescer,
+                serialized_response_cache,
                 request_moderator,
                 storage,
                 time_service,
@@ -155,6 +167,11 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
                     )))
                 },
             };
+            let transformed_data_response = if config.enable_transaction_output_dedup {
+                transformed_data_response.deduplicate_transaction_outputs()
+            } else {
+                transformed_data_response
+            };
             let storage_response =
                 match StorageServiceResponse::new(transformed_data_response, use_compression) {
                     Ok(storage_response) => storage_response,
@@ -44,6 +44,7 @@ pub enum DataRequest {
     GetTransactionsWithProof(TransactionsWithProofRequest), // Fetches a list of transactions with a proof
     GetNewTransactionsOrOutputsWithProof(NewTransactionsOrOutputsWithProofRequest), // Optimistically fetches new transactions or outputs
     GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest), // Fetches a list of transactions or outputs with a proof
+    GetDataWindowProof, // Fetches a proof of the data window retained by a pruned server
 }
 
 impl DataRequest {
@@ -63,6 +64,7 @@ impl DataRequest {
                 "get_new_transactions_or_outputs_with_proof"
             },
             Self::GetTransactionsOrOutputsWithProof(_) => "get_transactions_or_outputs_with_proof",
+            Self::GetDataWindowProof => "get_data_window_proof",
         }
     }
 
@@ -3,7 +3,7 @@
 
 use crate::{
     requests::DataRequest::{
-        GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
+        GetDataWindowProof, GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
         GetNewTransactionsOrOutputsWithProof, GetNewTransactionsWithProof,
         GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesWithProof,
         GetStorageServerSummary, GetTransactionOutputsWithProof, GetTransactionsOrOutputsWithProof,
@@ -16,17 +16,25 @@ use aptos_compression::{metrics::CompressionClient, CompressedData, CompressionE
 use aptos_config::config::{
     AptosDataClientConfig, StorageServiceConfig, MAX_APPLICATION_MESSAGE_SIZE,
 };
+use aptos_crypto::hash::CryptoHash;
 use aptos_types::{
-    epoch_change::EpochChangeProof,
+    contract_event::ContractEvent,
+    epoch_change::{EpochChangeProof, Verifier},
+    epoch_state::EpochState,
     ledger_info::LedgerInfoWithSignatures,
     state_store::state_value::StateValueChunkWithProof,
-    transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
+    transaction::{
+        Transaction, TransactionInfoListWithProof, TransactionListWithProof, TransactionOutput,
+        TransactionOutputListWithProof, TransactionStatus, TransactionWithProof, Version,
+    },
+    write_set::DedupedWriteSets,
 };
 use num_traits::{PrimInt, Zero};
 #[cfg(test)]
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use serde::{Deserialize, Serialize};
 use std::{
+    cmp::max,
     convert::TryFrom,
     fmt::{Display, Formatter},
 };
@@ -88,9 +96,11 @@ impl StorageServiceResponse {
                 )?;
                 let data_response = bcs::from_bytes::<DataResponse>(&raw_data)
                     .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
-                Ok(data_response)
+                data_response.expand_transaction_outputs()
+            },
+            StorageServiceResponse::RawResponse(data_response) => {
+                data_response.clone().expand_transaction_outputs()
             },
-            StorageServiceResponse::RawResponse(data_response) => Ok(data_response.clone()),
         }
     }
 
@@ -116,6 +126,93 @@ pub type TransactionOrOutputListWithProof = (
     Option<TransactionOutputListWithProof>,
 );
 
+/// A transaction output list with proof, where the identical write ops of the
+/// outputs are only sent once (see [`DedupedWriteSets`]).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DedupedTransactionOutputListWithProof {
+    pub transactions: Vec<Transaction>,
+    /// The events, gas used and status of each output
+    pub events_gas_and_statuses: Vec<(Vec<ContractEvent>, u64, TransactionStatus)>,
+    pub write_sets: DedupedWriteSets,
+    pub first_transaction_output_version: Option<Version>,
+    pub proof: TransactionInfoListWithProof,
+}
+
+impl From<TransactionOutputListWithProof> for DedupedTransactionOutputListWithProof {
+    fn from(output_list_with_proof: TransactionOutputListWithProof) -> Self {
+        let TransactionOutputListWithProof {
+            transactions_and_outputs,
+            first_transaction_output_version,
+            proof,
+        } = output_list_with_proof;
+
+        let mut transactions = vec![];
+        let mut write_sets = vec![];
+        let mut events_gas_and_statuses = vec![];
+        for (transaction, output) in transactions_and_outputs {
+            let (write_set, events, gas_used, status) = output.unpack();
+            transactions.push(transaction);
+            write_sets.push(write_set);
+            events_gas_and_statuses.push((events, gas_used, status));
+        }
+
+        Self {
+            transactions,
+            events_gas_and_statuses,
+            write_sets: DedupedWriteSets::new(&write_sets),
+            first_transaction_output_version,
+            proof,
+        }
+    }
+}
+
+impl TryFrom<DedupedTransactionOutputListWithProof> for TransactionOutputListWithProof {
+    type Error = crate::responses::Error;
+
+    fn try_from(
+        deduped_output_list_with_proof: DedupedTransactionOutputListWithProof,
+    ) -> crate::Result<Self, Self::Error> {
+        let DedupedTransactionOutputListWithProof {
+            transactions,
+            events_gas_and_statuses,
+            write_sets,
+            first_transaction_output_version,
+            proof,
+        } = deduped_output_list_with_proof;
+
+        let write_sets = write_sets
+            .into_write_sets()
+            .map_err(|error| Error::UnexpectedResponseError(error.to_string()))?;
+        if transactions.len() != write_sets.len()
+            || transactions.len() != events_gas_and_statuses.len()
+        {
+            return Err(Error::UnexpectedResponseError(format!(
+                "Mismatched deduped output list lengths! Transactions: {}, write sets: {}, outputs: {}",
+                transactions.len(),
+                write_sets.len(),
+                events_gas_and_statuses.len()
+            )));
+        }
+
+        let transactions_and_outputs = transactions
+            .into_iter()
+            .zip(write_sets)
+            .zip(events_gas_and_statuses)
+            .map(|((transaction, write_set), (events, gas_used, status))| {
+                (
+                    transaction,
+                    TransactionOutput::new(write_set, events, gas_used, status),
+                )
+            })
+            .collect();
+        Ok(TransactionOutputListWithProof::new(
+            transactions_and_outputs,
+            first_transaction_output_version,
+            proof,
+        ))
+    }
+}
+
 /// A single data response.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[allow(clippy::large_enum_variant)]
@@ -131,6 +228,14 @@ pub enum DataResponse {
     TransactionsWithProof(TransactionListWithProof),
     NewTransactionsOrOutputsWithProof((TransactionOrOutputListWithProof, LedgerInfoWithSignatures)),
     TransactionsOrOutputsWithProof(TransactionOrOutputListWithProof),
+    DedupedTransactionOutputsWithProof(DedupedTransactionOutputListWithProof),
+    NewDedupedTransactionOutputsWithProof(
+        (
+            DedupedTransactionOutputListWithProof,
+            LedgerInfoWithSignatures,
+        ),
+    ),
+    DataWindowProof(Option<DataWindowProof>),
 }
 
 impl DataResponse {
@@ -148,6 +253,46 @@ impl DataResponse {
             Self::TransactionsWithProof(_) => "transactions_with_proof",
             Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
             Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
+            Self::DedupedTransactionOutputsWithProof(_) => "deduped_transaction_outputs_with_proof",
+            Self::NewDedupedTransactionOutputsWithProof(_) => {
+                "new_deduped_transaction_outputs_with_proof"
+            },
+            Self::DataWindowProof(_) => "data_window_proof",
+        }
+    }
+
+    /// Deduplicates the write sets of transaction output responses, to reduce
+    /// their size on the wire. All other responses are returned unchanged.
+    pub fn deduplicate_transaction_outputs(self) -> Self {
+        match self {
+            Self::TransactionOutputsWithProof(output_list_with_proof) => {
+                Self::DedupedTransactionOutputsWithProof(output_list_with_proof.into())
+            },
+            Self::NewTransactionOutputsWithProof((output_list_with_proof, ledger_info)) => {
+                Self::NewDedupedTransactionOutputsWithProof((
+                    output_list_with_proof.into(),
+                    ledger_info,
+                ))
+            },
+            data_response => data_response,
+        }
+    }
+
+    /// Expands deduplicated transaction output responses back into their
+    /// regular form. All other responses are returned unchanged.
+    pub fn expand_transaction_outputs(self) -> Result<Self, Error> {
+        match self {
+            Self::DedupedTransactionOutputsWithProof(deduped_output_list_with_proof) => Ok(
+                Self::TransactionOutputsWithProof(deduped_output_list_with_proof.try_into()?),
+            ),
+            Self::NewDedupedTransactionOutputsWithProof((
+                deduped_output_list_with_proof,
+                ledger_info,
+            )) => Ok(Self::NewTransactionOutputsWithProof((
+                deduped_output_list_with_proof.try_into()?,
+                ledger_info,
+            ))),
+            data_response => Ok(data_response),
         }
     }
 }
@@ -324,6 +469,21 @@ impl TryFrom<StorageServiceResponse>
     }
 }
 
+impl TryFrom<StorageServiceResponse> for Option<DataWindowProof> {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::DataWindowProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected data_window_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<StorageServiceResponse> for TransactionOrOutputListWithProof {
     type Error = crate::responses::Error;
 
@@ -430,7 +590,7 @@ impl DataSummary {
         request: &StorageServiceRequest,
     ) -> bool {
         match &request.data_request {
-            GetServerProtocolVersion | GetStorageServerSummary => true,
+            GetServerProtocolVersion | GetStorageServerSummary | GetDataWindowProof => true,
             GetEpochEndingLedgerInfos(request) => {
                 let desired_range =
                     match CompleteDataRange::new(request.start_epoch, request.expected_end_epoch) {
@@ -557,6 +717,117 @@ impl DataSummary {
             .as_ref()
             .map(|ledger_info| ledger_info.ledger_info().version())
     }
+
+    /// Trims the transaction and transaction output ranges to the versions that a
+    /// pruned server is expected to still hold, given the proof of its data window.
+    /// The margin accounts for the server syncing (and pruning) further before the
+    /// requests for the data reach it.
+    pub fn trim_to_data_window(
+        &mut self,
+        data_window_proof: &DataWindowProof,
+        margin_versions: u64,
+    ) {
+        let synced_version = match self.get_synced_ledger_info_version() {
+            Some(synced_version) => synced_version,
+            None => return,
+        };
+        let lowest_version = data_window_proof
+            .expected_lowest_version(synced_version.saturating_add(margin_versions));
+        self.transactions = trim_data_range(self.transactions, lowest_version);
+        self.transaction_outputs = trim_data_range(self.transaction_outputs, lowest_version);
+    }
+}
+
+/// Trims the lowest versions of the range (if any) below the given version
+fn trim_data_range(
+    range: Option<CompleteDataRange<Version>>,
+    lowest_version: Version,
+) -> Option<CompleteDataRange<Version>> {
+    range.and_then(|range| {
+        if range.lowest() >= lowest_version {
+            Some(range)
+        } else {
+            CompleteDataRange::new(lowest_version, range.highest()).ok()
+        }
+    })
+}
+
+/// A proof of the data window retained by a pruned server, i.e., a server that only
+/// retains the transactions (and outputs) of the latest `window_size` versions. The
+/// server proves that it holds the transaction at the lowest retained version (relative
+/// to the given ledger info). As the window moves forward when the server syncs, clients
+/// use the window size to predict which versions the server will still hold.
+///
+/// Note: the window size itself is reported by the server and can't be proven. It can
+/// only make clients expect the server to retain fewer versions than the proven lowest
+/// version, so a server misreporting it only limits the requests sent to itself.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DataWindowProof {
+    /// The number of versions retained by the server (i.e., the ledger prune window)
+    pub window_size: u64,
+    /// The ledger info the proof is relative to
+    pub ledger_info: LedgerInfoWithSignatures,
+    /// The transaction at the lowest retained version, with a proof
+    pub lowest_transaction: TransactionWithProof,
+}
+
+impl DataWindowProof {
+    /// Returns the lowest version retained by the server (when the proof was created)
+    pub fn lowest_version(&self) -> Version {
+        self.lowest_transaction.version
+    }
+
+    /// Returns the highest version retained by the server (when the proof was created)
+    pub fn highest_version(&self) -> Version {
+        self.ledger_info.ledger_info().version()
+    }
+
+    /// Verifies the ledger info against the given (trusted) epoch state, that the lowest
+    /// retained transaction is in the ledger represented by the ledger info, and that the
+    /// window is consistent with the proof.
+    pub fn verify(&self, epoch_state: &EpochState) -> Result<(), Error> {
+        if self.window_size == 0 {
+            return Err(Error::UnexpectedResponseError(
+                "The data window size must be non-zero!".into(),
+            ));
+        }
+        if self.lowest_version() > self.highest_version() {
+            return Err(Error::UnexpectedResponseError(format!(
+                "The lowest retained version ({}) is higher than the proof version ({})!",
+                self.lowest_version(),
+                self.highest_version()
+            )));
+        }
+        epoch_state
+            .verify(&self.ledger_info)
+            .map_err(|error| Error::UnexpectedResponseError(error.to_string()))?;
+        self.lowest_transaction
+            .proof
+            .verify(self.ledger_info.ledger_info(), self.lowest_version())
+            .map_err(|error| Error::UnexpectedResponseError(error.to_string()))?;
+        let transaction_hash = self.lowest_transaction.transaction.hash();
+        let expected_hash = self
+            .lowest_transaction
+            .proof
+            .transaction_info()
+            .transaction_hash();
+        if transaction_hash != expected_hash {
+            return Err(Error::UnexpectedResponseError(format!(
+                "The lowest retained transaction hash ({}) doesn't match the proof ({})!",
+                transaction_hash, expected_hash
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the lowest version the server is expected to retain once it has
+    /// synced to the given version (i.e., once its window has moved forward).
+    pub fn expected_lowest_version(&self, synced_version: Version) -> Version {
+        let window_start = synced_version
+            .saturating_add(1)
+            .saturating_sub(self.window_size);
+        max(self.lowest_version(), window_start)
+    }
 }
 
 /// A struct representing a contiguous, non-empty data range (lowest to highest,
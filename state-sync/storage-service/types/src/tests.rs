@@ -8,16 +8,31 @@ use crate::{
         StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{CompleteDataRange, DataSummary, ProtocolMetadata},
+    responses::{
+        CompleteDataRange, DataResponse, DataSummary, DataWindowProof, ProtocolMetadata,
+        StorageServiceResponse,
+    },
     Epoch, StorageServiceRequest,
 };
 use aptos_config::config::AptosDataClientConfig;
-use aptos_crypto::hash::HashValue;
+use aptos_crypto::hash::{CryptoHash, HashValue, TransactionAccumulatorHasher};
 use aptos_types::{
     aggregate_signature::AggregateSignature,
     block_info::BlockInfo,
+    epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    transaction::Version,
+    proof::{
+        accumulator::InMemoryAccumulator, TransactionAccumulatorProof,
+        TransactionInfoListWithProof, TransactionInfoWithProof,
+    },
+    state_store::state_key::StateKey,
+    transaction::{
+        ExecutionStatus, Transaction, TransactionInfo, TransactionOutput,
+        TransactionOutputListWithProof, TransactionStatus, TransactionWithProof, Version,
+    },
+    validator_signer::ValidatorSigner,
+    validator_verifier::{ValidatorConsensusInfo, ValidatorVerifier},
+    write_set::{WriteOp, WriteSetMut},
 };
 use claims::{assert_err, assert_ok};
 use proptest::{arbitrary::any, prelude::*};
@@ -411,6 +426,122 @@ fn test_protocol_metadata_service() {
     }
 }
 
+#[test]
+fn test_deduped_transaction_outputs() {
+    // Create an output list where all outputs share most of their write ops
+    let output_list_with_proof = create_output_list_with_shared_writes(50);
+    for compression in [true, false] {
+        for data_response in [
+            DataResponse::TransactionOutputsWithProof(output_list_with_proof.clone()),
+            DataResponse::NewTransactionOutputsWithProof((
+                output_list_with_proof.clone(),
+                create_ledger_info_at_version(49),
+            )),
+        ] {
+            // Verify the deduplicated response is smaller than the original
+            let deduped_data_response = data_response.clone().deduplicate_transaction_outputs();
+            assert_ne!(deduped_data_response, data_response);
+            assert!(
+                bcs::to_bytes(&deduped_data_response).unwrap().len()
+                    < bcs::to_bytes(&data_response).unwrap().len()
+            );
+
+            // Verify the response is transparently expanded by the receiver
+            let storage_response =
+                StorageServiceResponse::new(deduped_data_response, compression).unwrap();
+            assert_eq!(storage_response.get_data_response().unwrap(), data_response);
+        }
+    }
+
+    // Verify other responses are left unchanged
+    let data_response = DataResponse::NumberOfStatesAtVersion(10);
+    assert_eq!(
+        data_response.clone().deduplicate_transaction_outputs(),
+        data_response
+    );
+}
+
+#[test]
+fn test_data_window_proof_verify() {
+    // Verify a valid data window proof
+    let epoch_state = EpochState::empty();
+    let data_window_proof = create_data_window_proof(100);
+    assert_ok!(data_window_proof.verify(&epoch_state));
+
+    // Verify an empty window is rejected
+    let mut invalid_proof = data_window_proof.clone();
+    invalid_proof.window_size = 0;
+    assert_err!(invalid_proof.verify(&epoch_state));
+
+    // Verify a proof for the wrong version is rejected
+    let mut invalid_proof = data_window_proof.clone();
+    invalid_proof.lowest_transaction.version = 0;
+    assert_err!(invalid_proof.verify(&epoch_state));
+
+    // Verify a transaction that doesn't match the proof is rejected
+    let mut invalid_proof = data_window_proof.clone();
+    invalid_proof.lowest_transaction.transaction =
+        Transaction::StateCheckpoint(HashValue::random());
+    assert_err!(invalid_proof.verify(&epoch_state));
+
+    // Verify a ledger info of another epoch is rejected
+    let other_epoch_state = EpochState {
+        epoch: 1,
+        verifier: ValidatorVerifier::new(vec![]),
+    };
+    assert_err!(data_window_proof.verify(&other_epoch_state));
+
+    // Verify a ledger info that isn't signed by the validators is rejected
+    let validator_signer = ValidatorSigner::from_int(0);
+    let validator =
+        ValidatorConsensusInfo::new(validator_signer.author(), validator_signer.public_key(), 1);
+    let validator_epoch_state = EpochState {
+        epoch: 0,
+        verifier: ValidatorVerifier::new(vec![validator]),
+    };
+    assert_err!(data_window_proof.verify(&validator_epoch_state));
+}
+
+#[test]
+fn test_data_summary_trim_to_data_window() {
+    // Create a data window proof with a lowest version of 1 and a window of 100
+    let data_window_proof = create_data_window_proof(100);
+
+    // Verify the ranges are unchanged if the window hasn't moved past them
+    let mut data_summary = create_data_summary(0, 50);
+    data_summary.trim_to_data_window(&data_window_proof, 10);
+    assert_eq!(data_summary.transactions, Some(create_data_range(1, 50)));
+    assert_eq!(
+        data_summary.transaction_outputs,
+        Some(create_data_range(1, 50))
+    );
+
+    // Verify the ranges are trimmed to the moved window (including the margin)
+    let mut data_summary = create_data_summary(0, 1000);
+    data_summary.trim_to_data_window(&data_window_proof, 10);
+    assert_eq!(
+        data_summary.transactions,
+        Some(create_data_range(911, 1000))
+    );
+    assert_eq!(
+        data_summary.transaction_outputs,
+        Some(create_data_range(911, 1000))
+    );
+
+    // Verify trimming is idempotent
+    data_summary.trim_to_data_window(&data_window_proof, 10);
+    assert_eq!(
+        data_summary.transactions,
+        Some(create_data_range(911, 1000))
+    );
+
+    // Verify the ranges are removed if the margin exceeds the window
+    let mut data_summary = create_data_summary(0, 1000);
+    data_summary.trim_to_data_window(&data_window_proof, 200);
+    assert_eq!(data_summary.transactions, None);
+    assert_eq!(data_summary.transaction_outputs, None);
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(1000))]
 
@@ -420,6 +551,90 @@ proptest! {
     }
 }
 
+/// Creates an output list where each output writes the same shared
+/// state value, along with a state value unique to the output.
+fn create_output_list_with_shared_writes(num_outputs: u64) -> TransactionOutputListWithProof {
+    let transactions_and_outputs = (0..num_outputs)
+        .map(|index| {
+            let write_set = WriteSetMut::new(vec![
+                (
+                    StateKey::raw(b"shared".to_vec()),
+                    WriteOp::Modification(vec![7; 100]),
+                ),
+                (
+                    StateKey::raw(index.to_le_bytes().to_vec()),
+                    WriteOp::Creation(vec![1; 10]),
+                ),
+            ])
+            .freeze()
+            .unwrap();
+            let output = TransactionOutput::new(
+                write_set,
+                vec![],
+                index,
+                TransactionStatus::Keep(ExecutionStatus::Success),
+            );
+            (Transaction::StateCheckpoint(HashValue::random()), output)
+        })
+        .collect();
+    TransactionOutputListWithProof::new(
+        transactions_and_outputs,
+        Some(0),
+        TransactionInfoListWithProof::new_empty(),
+    )
+}
+
+/// Creates a data summary with transactions and outputs for the given versions
+fn create_data_summary(lowest_version: Version, highest_version: Version) -> DataSummary {
+    DataSummary {
+        synced_ledger_info: Some(create_ledger_info_at_version(highest_version)),
+        transactions: Some(create_data_range(lowest_version, highest_version)),
+        transaction_outputs: Some(create_data_range(lowest_version, highest_version)),
+        ..Default::default()
+    }
+}
+
+/// Creates a valid data window proof for a server that retains version 1
+/// onwards (i.e., the ledger is at version 1 and version 0 has been pruned)
+fn create_data_window_proof(window_size: u64) -> DataWindowProof {
+    // Create the lowest retained transaction and its info
+    let transaction = Transaction::StateCheckpoint(HashValue::random());
+    let transaction_info = TransactionInfo::new(
+        transaction.hash(),
+        HashValue::random(),
+        HashValue::random(),
+        None,
+        0,
+        ExecutionStatus::Success,
+    );
+
+    // Create the ledger info with the accumulator root
+    let pruned_leaf = HashValue::random();
+    let root_hash = InMemoryAccumulator::<TransactionAccumulatorHasher>::from_leaves(&[
+        pruned_leaf,
+        transaction_info.hash(),
+    ])
+    .root_hash();
+    let ledger_info = LedgerInfoWithSignatures::new(
+        LedgerInfo::new(
+            BlockInfo::new(0, 0, HashValue::zero(), root_hash, 1, 0, None),
+            HashValue::zero(),
+        ),
+        AggregateSignature::empty(),
+    );
+
+    // Create the data window proof
+    let proof = TransactionInfoWithProof::new(
+        TransactionAccumulatorProof::new(vec![pruned_leaf]),
+        transaction_info,
+    );
+    DataWindowProof {
+        window_size,
+        ledger_info,
+        lowest_transaction: TransactionWithProof::new(1, transaction, None, proof),
+    }
+}
+
 /// Creates a new data range using the specified bounds
 fn create_data_range(lowest: u64, highest: u64) -> CompleteDataRange<u64> {
     CompleteDataRange::new(lowest, highest).unwrap()
@@ -0,0 +1,55 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the RocksDB block caches of the databases, so that the node can account
+//! for their memory and shrink them temporarily (e.g., under memory pressure).
+
+use aptos_infallible::Mutex;
+use aptos_schemadb::Cache;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Whether to track the block caches of the databases that are opened. This is
+// disabled by default, as the tracked caches are never released.
+static TRACKING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+// The tracked block caches
+static BLOCK_CACHES: Lazy<Mutex<Vec<TrackedCache>>> = Lazy::new(|| Mutex::new(vec![]));
+
+struct TrackedCache {
+    cache: Cache,
+    configured_capacity: usize,
+}
+
+/// Tracks the block caches of the databases that are opened from now on
+pub fn enable_tracking() {
+    TRACKING_ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Tracks the given block cache (if tracking is enabled)
+pub(crate) fn track(cache: &Cache, configured_capacity: usize) {
+    if TRACKING_ENABLED.load(Ordering::Relaxed) {
+        BLOCK_CACHES.lock().push(TrackedCache {
+            cache: cache.clone(),
+            configured_capacity,
+        });
+    }
+}
+
+/// Returns the memory (bytes) used by the tracked block caches
+pub fn usage() -> usize {
+    BLOCK_CACHES
+        .lock()
+        .iter()
+        .map(|tracked_cache| tracked_cache.cache.get_usage())
+        .sum()
+}
+
+/// Resizes the tracked block caches to the given percentage of their configured
+/// capacity. Shrinking a cache evicts its (unpinned) entries right away.
+pub fn set_capacity_percent(percent: u64) {
+    for tracked_cache in BLOCK_CACHES.lock().iter_mut() {
+        let capacity = tracked_cache.configured_capacity as u128 * percent as u128 / 100;
+        tracked_cache.cache.set_capacity(capacity as usize);
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::schema::*;
+use crate::{block_cache, schema::*};
 use aptos_config::config::RocksdbConfig;
 use aptos_schemadb::{
     BlockBasedOptions, Cache, ColumnFamilyDescriptor, ColumnFamilyName, DBCompressionType, Options,
@@ -121,6 +121,7 @@ where
     table_options.set_block_size(rocksdb_config.block_size as usize);
     let cache = Cache::new_lru_cache(rocksdb_config.block_cache_size as usize);
     table_options.set_block_cache(&cache);
+    block_cache::track(&cache, rocksdb_config.block_cache_size as usize);
     let mut cfds = Vec::with_capacity(cfs.len());
     for cf_name in cfs {
         let mut cf_opts = Options::default();
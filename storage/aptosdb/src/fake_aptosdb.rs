@@ -28,9 +28,9 @@ use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     proof::{
         accumulator::InMemoryAccumulator, position::Position, AccumulatorConsistencyProof,
-        AccumulatorRangeProof, SparseMerkleProofExt, TransactionAccumulatorProof,
-        TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
-        TransactionInfoListWithProof, TransactionInfoWithProof,
+        AccumulatorRangeProof, SparseMerkleBatchProof, SparseMerkleProofExt,
+        TransactionAccumulatorProof, TransactionAccumulatorRangeProof,
+        TransactionAccumulatorSummary, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_proof::StateProof,
     state_store::{
@@ -666,6 +666,14 @@ impl DbReader for FakeAptosDB {
         self.inner.get_state_snapshot_before(next_version)
     }
 
+    fn get_state_snapshot_versions(
+        &self,
+        end_version: Version,
+        limit: usize,
+    ) -> Result<Vec<Version>> {
+        self.inner.get_state_snapshot_versions(end_version, limit)
+    }
+
     fn get_account_transaction(
         &self,
         address: aptos_types::PeerId,
@@ -741,6 +749,17 @@ impl DbReader for FakeAptosDB {
         }
     }
 
+    fn get_state_values_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<Vec<Option<StateValue>>> {
+        state_keys
+            .iter()
+            .map(|state_key| self.get_state_value_by_version(state_key, version))
+            .collect()
+    }
+
     fn get_state_proof_by_version_ext(
         &self,
         state_key: &StateKey,
@@ -759,6 +778,15 @@ impl DbReader for FakeAptosDB {
             .get_state_value_with_proof_by_version_ext(state_key, version)
     }
 
+    fn get_state_batch_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<SparseMerkleBatchProof> {
+        self.inner
+            .get_state_batch_proof_by_version(state_keys, version)
+    }
+
     fn get_latest_executed_trees(&self) -> Result<ExecutedTrees> {
         // If the genesis is not executed yet, we need to get the executed trees from the inner AptosDB
         // This is because when we call save_transactions for the genesis block, we call [AptosDB::save_transactions]
@@ -850,10 +878,18 @@ impl DbReader for FakeAptosDB {
         self.inner.get_table_info(handle)
     }
 
+    fn get_table_owner(&self, handle: table::TableHandle) -> Result<Option<table::TableOwner>> {
+        self.inner.get_table_owner(handle)
+    }
+
     fn indexer_enabled(&self) -> bool {
         self.inner.indexer_enabled()
     }
 
+    fn indexer_ready(&self) -> bool {
+        self.inner.indexer_ready()
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         self.inner.get_state_storage_usage(version)
     }
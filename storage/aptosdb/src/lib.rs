@@ -17,10 +17,12 @@ pub mod fake_aptosdb;
 pub mod test_helper;
 
 pub mod backup;
+pub mod block_cache;
 pub mod errors;
 pub mod metrics;
 pub mod schema;
 pub mod state_restore;
+pub mod state_snapshot_scheduler;
 pub mod utils;
 
 mod db_options;
@@ -32,6 +34,7 @@ mod pruner;
 mod state_kv_db;
 mod state_merkle_db;
 mod state_store;
+pub mod storage_forecaster;
 mod transaction_store;
 mod versioned_node_cache;
 
@@ -59,19 +62,21 @@ use crate::{
     stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
     state_kv_db::StateKvDb,
     state_merkle_db::StateMerkleDb,
+    state_snapshot_scheduler::StateSnapshotScheduler,
     state_store::{buffered_state::BufferedState, StateStore},
+    storage_forecaster::{StorageForecastReporter, StorageForecaster},
     transaction_store::TransactionStore,
 };
 use anyhow::{bail, ensure, Result};
 use aptos_config::config::{
-    PrunerConfig, RocksdbConfig, RocksdbConfigs, NO_OP_STORAGE_PRUNER_CONFIG,
+    PrunerConfig, RocksdbConfig, RocksdbConfigs, StorageForecastConfig, NO_OP_STORAGE_PRUNER_CONFIG,
 };
 #[cfg(any(test, feature = "fuzzing"))]
 use aptos_config::config::{
     BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
 };
 use aptos_crypto::HashValue;
-use aptos_db_indexer::Indexer;
+use aptos_db_indexer::{BackfillProgress, Indexer};
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_schemadb::{SchemaBatch, DB};
@@ -88,8 +93,8 @@ use aptos_types::{
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     proof::{
-        accumulator::InMemoryAccumulator, AccumulatorConsistencyProof, SparseMerkleProofExt,
-        TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
+        accumulator::InMemoryAccumulator, AccumulatorConsistencyProof, SparseMerkleBatchProof,
+        SparseMerkleProofExt, TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
         TransactionInfoListWithProof,
     },
     state_proof::StateProof,
@@ -99,7 +104,7 @@ use aptos_types::{
         state_key_prefix::StateKeyPrefix,
         state_storage_usage::StateStorageUsage,
         state_value::{StateValue, StateValueChunkWithProof},
-        table::{TableHandle, TableInfo},
+        table::{TableHandle, TableInfo, TableOwner},
         ShardedStateUpdates,
     },
     transaction::{
@@ -111,6 +116,7 @@ use aptos_types::{
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use arr_macro::arr;
+use move_core_types::language_storage::StructTag;
 use move_resource_viewer::MoveValueAnnotator;
 use once_cell::sync::Lazy;
 use rayon::prelude::*;
@@ -280,8 +286,9 @@ pub struct AptosDB {
     transaction_store: Arc<TransactionStore>,
     ledger_pruner: LedgerPrunerManager,
     _rocksdb_property_reporter: RocksdbPropertyReporter,
+    _storage_forecast_reporter: Option<StorageForecastReporter>,
     ledger_commit_lock: std::sync::Mutex<()>,
-    indexer: Option<Indexer>,
+    indexer: Option<Arc<Indexer>>,
     skip_index_and_usage: bool,
 }
 
@@ -339,6 +346,7 @@ impl AptosDB {
                 ledger_db.metadata_db_arc(),
                 Arc::clone(&state_merkle_db),
             ),
+            _storage_forecast_reporter: None,
             ledger_commit_lock: std::sync::Mutex::new(()),
             indexer: None,
             skip_index_and_usage,
@@ -450,6 +458,34 @@ impl AptosDB {
         Ok((ledger_db, state_merkle_db, state_kv_db))
     }
 
+    /// Starts sampling the sizes of the DBs in the background, and returns the forecaster that
+    /// forecasts the storage usage from them. Returns None if forecasting is disabled.
+    pub fn start_storage_forecaster(
+        &mut self,
+        config: StorageForecastConfig,
+    ) -> Option<Arc<StorageForecaster>> {
+        if !config.enable {
+            return None;
+        }
+        let ledger_prune_window = self
+            .ledger_pruner
+            .is_pruner_enabled()
+            .then(|| self.ledger_pruner.get_prune_window());
+        let forecaster = Arc::new(StorageForecaster::new(config, ledger_prune_window));
+        self._storage_forecast_reporter = Some(StorageForecastReporter::new(
+            Arc::clone(&forecaster),
+            self.ledger_db.metadata_db_arc(),
+            Arc::clone(&self.state_merkle_db),
+            Arc::clone(&self.ledger_store),
+        ));
+        Some(forecaster)
+    }
+
+    /// Returns the handle through which the schedule of the state snapshots is changed.
+    pub fn state_snapshot_scheduler(&self) -> StateSnapshotScheduler {
+        StateSnapshotScheduler::new(Arc::clone(&self.state_store))
+    }
+
     fn open_indexer(
         &mut self,
         db_root_path: impl AsRef<Path>,
@@ -466,7 +502,7 @@ impl AptosDB {
         if indexer.next_version() < ledger_next_version {
             let state_view = DbStateView {
                 db: self.state_store.clone(),
-                version: Some(ledger_next_version - 1),
+                version: ledger_next_version.checked_sub(1),
             };
             let resolver = state_view.as_move_resolver();
             let annotator = MoveValueAnnotator::new(&resolver);
@@ -487,10 +523,160 @@ impl AptosDB {
         }
         info!("AptosDB Indexer caught up.");
 
+        let indexer = Arc::new(indexer);
+        if !indexer.is_backfilled() {
+            Self::spawn_indexer_backfill(
+                Arc::clone(&indexer),
+                Arc::clone(&self.state_store),
+                Arc::clone(&self.transaction_store),
+            )?;
+        }
         self.indexer = Some(indexer);
         Ok(())
     }
 
+    /// Backfills the indexer in the background, so it doesn't hold up the startup. Until it's
+    /// done, the indexer isn't ready (see `Indexer::is_backfilled()`). An interrupted backfill
+    /// resumes from its persisted progress the next time the DB is opened.
+    fn spawn_indexer_backfill(
+        indexer: Arc<Indexer>,
+        state_store: Arc<StateStore>,
+        transaction_store: Arc<TransactionStore>,
+    ) -> Result<()> {
+        thread::Builder::new()
+            .name("indexer_backfill".into())
+            .spawn(move || {
+                if indexer.account_resource_tag_start_version().is_none() {
+                    if let Err(err) = Self::backfill_account_resource_tags(
+                        &indexer,
+                        &state_store,
+                        &transaction_store,
+                    ) {
+                        error!(
+                            error = ?err,
+                            "Failed to backfill the account resource tags of the AptosDB Indexer.",
+                        );
+                    }
+                }
+                if indexer.table_owner_start_version().is_none() {
+                    if let Err(err) =
+                        Self::backfill_table_owners(&indexer, &state_store, &transaction_store)
+                    {
+                        error!(
+                            error = ?err,
+                            "Failed to backfill the table owners of the AptosDB Indexer.",
+                        );
+                    }
+                }
+            })?;
+        Ok(())
+    }
+
+    /// Returns where to resume a backfill of the indexer from: its persisted progress, or else
+    /// the latest state snapshot before the next version to index (or None, if there's none).
+    fn indexer_backfill_progress(
+        persisted_progress: Option<BackfillProgress>,
+        indexer: &Indexer,
+        state_store: &StateStore,
+    ) -> Result<Option<BackfillProgress>> {
+        if persisted_progress.is_some() {
+            return Ok(persisted_progress);
+        }
+        let next_version = indexer.next_version();
+        Ok(state_store
+            .get_state_snapshot_before(next_version)?
+            .map(|(snapshot_version, _)| BackfillProgress::new(snapshot_version, next_version)))
+    }
+
+    /// Backfills the account resource tags of an indexer created before they were indexed,
+    /// from the latest state snapshot before the next version to index.
+    fn backfill_account_resource_tags(
+        indexer: &Indexer,
+        state_store: &Arc<StateStore>,
+        transaction_store: &TransactionStore,
+    ) -> Result<()> {
+        let progress = match Self::indexer_backfill_progress(
+            indexer.account_resource_tag_backfill_progress()?,
+            indexer,
+            state_store,
+        )? {
+            Some(progress) => progress,
+            None => {
+                warn!(
+                    next_version = indexer.next_version(),
+                    "No state snapshot to backfill the account resource tags of the AptosDB Indexer from.",
+                );
+                return Ok(());
+            },
+        };
+
+        info!(
+            progress = ?progress,
+            "Backfilling the account resource tags of the AptosDB Indexer.",
+        );
+        let state_keys = progress
+            .next_state_key_hash
+            .map(|hash| state_store.get_state_key_and_value_iter(progress.snapshot_version, hash))
+            .transpose()?
+            .into_iter()
+            .flatten()
+            .map(|result| result.map(|(state_key, _)| state_key));
+        let write_sets = transaction_store.get_write_set_iter(
+            progress.next_version,
+            (progress.end_version - progress.next_version) as usize,
+        )?;
+        indexer.backfill_account_resource_tags(progress, state_keys, write_sets)?;
+        info!("Backfilled the account resource tags of the AptosDB Indexer.");
+        Ok(())
+    }
+
+    /// Backfills the owners of the tables indexed before their owners were recorded, from the
+    /// latest state snapshot before the next version to index.
+    fn backfill_table_owners(
+        indexer: &Indexer,
+        state_store: &Arc<StateStore>,
+        transaction_store: &TransactionStore,
+    ) -> Result<()> {
+        let progress = match Self::indexer_backfill_progress(
+            indexer.table_owner_backfill_progress()?,
+            indexer,
+            state_store,
+        )? {
+            Some(progress) => progress,
+            None => {
+                warn!(
+                    next_version = indexer.next_version(),
+                    "No state snapshot to backfill the table owners of the AptosDB Indexer from.",
+                );
+                return Ok(());
+            },
+        };
+
+        info!(
+            progress = ?progress,
+            "Backfilling the table owners of the AptosDB Indexer.",
+        );
+        let state_view = DbStateView {
+            db: state_store.clone(),
+            version: progress.end_version.checked_sub(1),
+        };
+        let resolver = state_view.as_move_resolver();
+        let annotator = MoveValueAnnotator::new(&resolver);
+        let state_values = progress
+            .next_state_key_hash
+            .map(|hash| state_store.get_state_key_and_value_iter(progress.snapshot_version, hash))
+            .transpose()?
+            .into_iter()
+            .flatten();
+        let write_sets = transaction_store.get_write_set_iter(
+            progress.next_version,
+            (progress.end_version - progress.next_version) as usize,
+        )?;
+        indexer.backfill_table_owners(&annotator, progress, state_values, write_sets)?;
+        info!("Backfilled the table owners of the AptosDB Indexer.");
+        Ok(())
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     fn new_without_pruner<P: AsRef<Path> + Clone>(
         db_root_path: P,
@@ -594,6 +780,12 @@ impl AptosDB {
         self.state_store.buffered_state()
     }
 
+    /// This prunes the ledger data before `min_readable_version`, even without the pruner.
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub fn prune_ledger_for_test(&self, min_readable_version: Version) -> Result<()> {
+        self.ledger_pruner.prune_for_test(min_readable_version)
+    }
+
     /// This force the db to update rocksdb properties immediately.
     pub fn update_rocksdb_properties(&self) -> Result<()> {
         update_rocksdb_properties(&self.ledger_db.metadata_db_arc(), &self.state_merkle_db)
@@ -1220,6 +1412,7 @@ impl AptosDB {
         let _timer = OTHER_TIMERS_SECONDS
             .with_label_values(&["buffered_state___update"])
             .start_timer();
+        buffered_state.record_blocks(txns_to_commit.iter().map(|txn| txn.transaction()));
         buffered_state.update(
             updates_until_latest_checkpoint_since_current,
             latest_in_memory_state,
@@ -1729,6 +1922,19 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_values_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<Vec<Option<StateValue>>> {
+        gauged_api("get_state_values_by_version", || {
+            self.error_if_state_kv_pruned("StateValue", version)?;
+
+            self.state_store
+                .get_state_values_by_version(state_keys, version)
+        })
+    }
+
     fn get_state_value_with_version_by_version(
         &self,
         state_key: &StateKey,
@@ -1769,6 +1975,19 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_batch_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<SparseMerkleBatchProof> {
+        gauged_api("get_state_batch_proof_by_version", || {
+            self.error_if_state_merkle_pruned("State merkle", version)?;
+
+            self.state_store
+                .get_state_batch_proof_by_version(state_keys, version)
+        })
+    }
+
     fn get_latest_epoch_state(&self) -> Result<EpochState> {
         gauged_api("get_latest_epoch_state", || {
             let latest_ledger_info = self.ledger_store.get_latest_ledger_info()?;
@@ -1907,6 +2126,17 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_snapshot_versions(
+        &self,
+        end_version: Version,
+        limit: usize,
+    ) -> Result<Vec<Version>> {
+        gauged_api("get_state_snapshot_versions", || {
+            self.state_store
+                .get_state_snapshot_versions(end_version, limit)
+        })
+    }
+
     fn get_accumulator_root_hash(&self, version: Version) -> Result<HashValue> {
         gauged_api("get_accumulator_root_hash", || {
             self.error_if_ledger_pruned("Transaction accumulator", version)?;
@@ -1997,11 +2227,35 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_table_owner(&self, handle: TableHandle) -> Result<Option<TableOwner>> {
+        gauged_api("get_table_owner", || match &self.indexer {
+            Some(indexer) => indexer.get_table_owner(handle),
+            None => bail!("Indexer not enabled."),
+        })
+    }
+
+    fn get_account_resource_tags(
+        &self,
+        address: AccountAddress,
+        version: Version,
+    ) -> Result<Option<Vec<StructTag>>> {
+        gauged_api("get_account_resource_tags", || match &self.indexer {
+            Some(indexer) => indexer.get_account_resource_tags(address, version),
+            None => bail!("Indexer not enabled."),
+        })
+    }
+
     /// Returns whether the indexer DB has been enabled or not
     fn indexer_enabled(&self) -> bool {
         self.indexer.is_some()
     }
 
+    fn indexer_ready(&self) -> bool {
+        self.indexer
+            .as_ref()
+            .map_or(false, |indexer| indexer.is_backfilled())
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         gauged_api("get_state_storage_usage", || {
             if let Some(v) = version {
@@ -2127,6 +2381,8 @@ impl DbWriter for AptosDB {
                     let _timer = OTHER_TIMERS_SECONDS
                         .with_label_values(&["buffered_state___update"])
                         .start_timer();
+                    buffered_state
+                        .record_blocks(txns_to_commit.iter().map(|txn| txn.transaction()));
                     buffered_state.update(
                         Some(block_state_updates),
                         latest_in_memory_state,
@@ -160,4 +160,16 @@ impl LedgerPrunerManager {
             .unwrap()
             .set_target_db_version(min_readable_version);
     }
+
+    /// Prunes the ledger data before `min_readable_version` in the calling thread, whether or not
+    /// the pruner worker is enabled.
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub(crate) fn prune_for_test(&self, min_readable_version: Version) -> Result<()> {
+        use crate::pruner::db_pruner::DBPruner;
+
+        let pruner = LedgerPruner::new(Arc::clone(&self.ledger_db))?;
+        pruner.set_target_version(min_readable_version);
+        pruner.prune(min_readable_version as usize)?;
+        self.save_min_readable_version(min_readable_version)
+    }
 }
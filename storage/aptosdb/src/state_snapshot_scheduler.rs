@@ -0,0 +1,40 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Changes the schedule of the state snapshots while the node is running, e.g., when the
+//! on-chain state snapshot config changes. See `StateSnapshotScheduleConfig`.
+
+use crate::state_store::StateStore;
+use aptos_config::config::StateSnapshotScheduleConfig;
+use aptos_logger::info;
+use std::sync::Arc;
+
+/// The handle through which the schedule of the state snapshots is read and changed
+#[derive(Clone)]
+pub struct StateSnapshotScheduler {
+    state_store: Arc<StateStore>,
+}
+
+impl StateSnapshotScheduler {
+    pub(crate) fn new(state_store: Arc<StateStore>) -> Self {
+        Self { state_store }
+    }
+
+    /// Returns the current schedule of the state snapshots
+    pub fn schedule(&self) -> StateSnapshotScheduleConfig {
+        self.state_store.snapshot_schedule()
+    }
+
+    /// Changes the schedule of the state snapshots, taking effect from the next commit
+    pub fn set_schedule(&self, schedule: StateSnapshotScheduleConfig) {
+        let current_schedule = self.schedule();
+        if current_schedule == schedule {
+            return;
+        }
+        info!(
+            "Changing the state snapshot schedule from {:?} to {:?}",
+            current_schedule, schedule
+        );
+        self.state_store.set_snapshot_schedule(schedule);
+    }
+}
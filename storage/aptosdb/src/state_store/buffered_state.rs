@@ -8,9 +8,13 @@ use crate::{
     state_store::{state_snapshot_committer::StateSnapshotCommitter, StateDb},
 };
 use anyhow::{ensure, Result};
+use aptos_config::config::{StateSnapshotScheduleConfig, MAX_STATE_SNAPSHOT_INTERVAL_IN_VERSIONS};
 use aptos_logger::info;
 use aptos_storage_interface::state_delta::StateDelta;
-use aptos_types::{state_store::ShardedStateUpdates, transaction::Version};
+use aptos_types::{
+    state_store::ShardedStateUpdates,
+    transaction::{Transaction, Version},
+};
 use itertools::zip_eq;
 use std::{
     mem::swap,
@@ -23,7 +27,7 @@ use std::{
 };
 
 pub(crate) const ASYNC_COMMIT_CHANNEL_BUFFER_SIZE: u64 = 1;
-pub(crate) const TARGET_SNAPSHOT_INTERVAL_IN_VERSION: u64 = 20_000;
+pub(crate) const TARGET_SNAPSHOT_INTERVAL_IN_VERSION: u64 = MAX_STATE_SNAPSHOT_INTERVAL_IN_VERSIONS;
 
 /// The in-memory buffered state that consists of two pieces:
 /// `state_until_checkpoint`: The ready-to-commit data in range (last snapshot, latest checkpoint].
@@ -39,6 +43,13 @@ pub struct BufferedState {
     state_after_checkpoint: StateDelta,
     state_commit_sender: SyncSender<CommitMessage<Arc<StateDelta>>>,
     target_items: usize,
+    snapshot_schedule: StateSnapshotScheduleConfig,
+    // blocks committed since the latest snapshot was sent to commit.
+    blocks_since_snapshot: u64,
+    // timestamp of the latest block committed when the latest snapshot was sent to commit.
+    snapshot_block_timestamp_usecs: Option<u64>,
+    // timestamp of the latest block committed.
+    latest_block_timestamp_usecs: Option<u64>,
     join_handle: Option<JoinHandle<()>>,
 }
 
@@ -53,6 +64,7 @@ impl BufferedState {
         state_db: &Arc<StateDb>,
         state_after_checkpoint: StateDelta,
         target_items: usize,
+        snapshot_schedule: StateSnapshotScheduleConfig,
     ) -> Self {
         let (state_commit_sender, state_commit_receiver) =
             mpsc::sync_channel(ASYNC_COMMIT_CHANNEL_BUFFER_SIZE as usize);
@@ -70,6 +82,10 @@ impl BufferedState {
             state_after_checkpoint,
             state_commit_sender,
             target_items,
+            snapshot_schedule,
+            blocks_since_snapshot: 0,
+            snapshot_block_timestamp_usecs: None,
+            latest_block_timestamp_usecs: None,
             // The join handle of the async state commit thread for graceful drop.
             join_handle: Some(join_handle),
         };
@@ -85,7 +101,61 @@ impl BufferedState {
         self.state_after_checkpoint.base_version
     }
 
-    /// This method checks whether a commit is needed based on the target_items value and the number of items in state_until_checkpoint.
+    pub fn snapshot_schedule(&self) -> StateSnapshotScheduleConfig {
+        self.snapshot_schedule
+    }
+
+    /// Changes the schedule of the snapshots, taking effect from the next update.
+    pub fn set_snapshot_schedule(&mut self, snapshot_schedule: StateSnapshotScheduleConfig) {
+        self.snapshot_schedule = snapshot_schedule;
+    }
+
+    /// Accounts for the blocks started by the transactions about to be committed, to schedule
+    /// the snapshots by blocks and block time. Must be called before the corresponding update.
+    pub fn record_blocks<'a>(&mut self, transactions: impl Iterator<Item = &'a Transaction>) {
+        for block_metadata in transactions.filter_map(Transaction::try_as_block_metadata) {
+            self.blocks_since_snapshot += 1;
+            self.latest_block_timestamp_usecs = Some(block_metadata.timestamp_usecs());
+            self.snapshot_block_timestamp_usecs
+                .get_or_insert(block_metadata.timestamp_usecs());
+        }
+    }
+
+    /// Returns true iff the state until the latest checkpoint is due to be committed as a
+    /// snapshot, either because there are too many items buffered, or because an interval of
+    /// the snapshot schedule has elapsed.
+    fn snapshot_due(&self, state_until_checkpoint: &StateDelta) -> bool {
+        let num_items = state_until_checkpoint
+            .updates_since_base
+            .iter()
+            .map(|shard| shard.len())
+            .sum::<usize>();
+        let num_versions = state_until_checkpoint.current_version.map_or(0, |v| v + 1)
+            - state_until_checkpoint.base_version.map_or(0, |v| v + 1);
+        let schedule = &self.snapshot_schedule;
+        // The interval in versions is capped, because recovering the buffered state relies on it.
+        let interval_versions = schedule
+            .interval_versions
+            .clamp(1, TARGET_SNAPSHOT_INTERVAL_IN_VERSION);
+        let elapsed_usecs = self
+            .latest_block_timestamp_usecs
+            .zip(self.snapshot_block_timestamp_usecs)
+            .map_or(0, |(latest, snapshot)| latest.saturating_sub(snapshot));
+
+        num_items >= self.target_items
+            || num_versions >= interval_versions
+            || (schedule.interval_blocks > 0
+                && self.blocks_since_snapshot >= schedule.interval_blocks)
+            || (schedule.interval_secs > 0
+                && elapsed_usecs >= schedule.interval_secs.saturating_mul(1_000_000))
+    }
+
+    fn reset_snapshot_schedule_progress(&mut self) {
+        self.blocks_since_snapshot = 0;
+        self.snapshot_block_timestamp_usecs = self.latest_block_timestamp_usecs;
+    }
+
+    /// This method checks whether a commit is needed based on the target_items value, the number of items in state_until_checkpoint and the snapshot schedule.
     /// If a commit is needed, it sends a CommitMessage::Data message to the StateSnapshotCommitter thread to commit the data.
     /// If sync_commit is true, it also sends a CommitMessage::Sync message to ensure that the commit is completed before returning.
     fn maybe_commit(&mut self, sync_commit: bool) {
@@ -95,25 +165,15 @@ impl BufferedState {
                 self.state_commit_sender
                     .send(CommitMessage::Data(to_commit))
                     .unwrap();
+                self.reset_snapshot_schedule_progress();
             }
             self.state_commit_sender
                 .send(CommitMessage::Sync(commit_sync_sender))
                 .unwrap();
             commit_sync_receiver.recv().unwrap(); // blocks until the to_commit is received.
         } else if self.state_until_checkpoint.is_some() {
-            let take_out_to_commit = {
-                let state_until_checkpoint =
-                    self.state_until_checkpoint.as_ref().expect("Must exist");
-                state_until_checkpoint
-                    .updates_since_base
-                    .iter()
-                    .map(|shard| shard.len())
-                    .sum::<usize>()
-                    >= self.target_items
-                    || state_until_checkpoint.current_version.map_or(0, |v| v + 1)
-                        - state_until_checkpoint.base_version.map_or(0, |v| v + 1)
-                        >= TARGET_SNAPSHOT_INTERVAL_IN_VERSION
-            };
+            let take_out_to_commit =
+                self.snapshot_due(self.state_until_checkpoint.as_ref().expect("Must exist"));
             if take_out_to_commit {
                 let to_commit: Arc<StateDelta> = self
                     .state_until_checkpoint
@@ -128,6 +188,7 @@ impl BufferedState {
                 self.state_commit_sender
                     .send(CommitMessage::Data(to_commit))
                     .unwrap();
+                self.reset_snapshot_schedule_progress();
             }
         }
     }
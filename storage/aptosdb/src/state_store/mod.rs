@@ -28,6 +28,7 @@ use crate::{
     NUM_STATE_SHARDS, OTHER_TIMERS_SECONDS,
 };
 use anyhow::{ensure, format_err, Context, Result};
+use aptos_config::config::StateSnapshotScheduleConfig;
 use aptos_crypto::{
     hash::{CryptoHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
     HashValue,
@@ -45,7 +46,9 @@ use aptos_storage_interface::{
     DbReader, StateSnapshotReceiver,
 };
 use aptos_types::{
-    proof::{definition::LeafCount, SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{
+        definition::LeafCount, SparseMerkleBatchProof, SparseMerkleProofExt, SparseMerkleRangeProof,
+    },
     state_store::{
         create_empty_sharded_state_updates,
         state_key::StateKey,
@@ -105,6 +108,8 @@ pub(crate) struct StateStore {
     // write set stored in ledger_db.
     buffered_state: Mutex<BufferedState>,
     buffered_state_target_items: usize,
+    // Kept to recreate the buffered state with the same schedule on reset.
+    snapshot_schedule: Mutex<StateSnapshotScheduleConfig>,
 }
 
 impl Deref for StateStore {
@@ -144,6 +149,21 @@ impl DbReader for StateDb {
             .map(|(_, value)| value))
     }
 
+    /// Gets the latest state values of the given keys up to the given version, reading the keys
+    /// in parallel.
+    fn get_state_values_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<Vec<Option<StateValue>>> {
+        IO_POOL.install(|| {
+            state_keys
+                .par_iter()
+                .map(|state_key| self.get_state_value_by_version(state_key, version))
+                .collect()
+        })
+    }
+
     /// Gets the latest state value and its corresponding version when it's of the given key up
     /// to the given version.
     fn get_state_value_with_version_by_version(
@@ -195,6 +215,25 @@ impl DbReader for StateDb {
         ))
     }
 
+    /// Returns a single proof of the given state keys and version, reading the proofs of the keys
+    /// in parallel.
+    fn get_state_batch_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<SparseMerkleBatchProof> {
+        let proofs = IO_POOL.install(|| {
+            state_keys
+                .par_iter()
+                .map(|state_key| {
+                    let proof = self.get_state_proof_by_version_ext(state_key, version)?;
+                    Ok((state_key.hash(), proof.into()))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        SparseMerkleBatchProof::new(&proofs)
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         if self.skip_usage {
             return Ok(StateStorageUsage::new_untracked());
@@ -249,6 +288,16 @@ impl DbReader for StateStore {
         self.deref().get_state_value_by_version(state_key, version)
     }
 
+    /// Gets the latest state values of the given keys up to the given version.
+    fn get_state_values_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<Vec<Option<StateValue>>> {
+        self.deref()
+            .get_state_values_by_version(state_keys, version)
+    }
+
     /// Gets the latest state value and the its corresponding version when its of the given key up
     /// to the given version.
     fn get_state_value_with_version_by_version(
@@ -279,6 +328,16 @@ impl DbReader for StateStore {
         self.deref()
             .get_state_value_with_proof_by_version_ext(state_key, version)
     }
+
+    /// Returns a single proof of the given state keys and version.
+    fn get_state_batch_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<SparseMerkleBatchProof> {
+        self.deref()
+            .get_state_batch_proof_by_version(state_keys, version)
+    }
 }
 
 impl StateDb {
@@ -332,17 +391,20 @@ impl StateStore {
                 &state_db,
                 StateDelta::new_empty(),
                 buffered_state_target_items,
+                StateSnapshotScheduleConfig::default(),
             ));
             Self {
                 state_db,
                 buffered_state,
                 buffered_state_target_items,
+                snapshot_schedule: Mutex::new(StateSnapshotScheduleConfig::default()),
             }
         } else {
             let buffered_state = Mutex::new(
                 Self::create_buffered_state_from_latest_snapshot(
                     &state_db,
                     buffered_state_target_items,
+                    StateSnapshotScheduleConfig::default(),
                     hack_for_tests,
                     /*check_max_versions_after_snapshot=*/ true,
                 )
@@ -352,6 +414,7 @@ impl StateStore {
                 state_db,
                 buffered_state,
                 buffered_state_target_items,
+                snapshot_schedule: Mutex::new(StateSnapshotScheduleConfig::default()),
             }
         }
     }
@@ -467,6 +530,7 @@ impl StateStore {
     fn create_buffered_state_from_latest_snapshot(
         state_db: &Arc<StateDb>,
         buffered_state_target_items: usize,
+        snapshot_schedule: StateSnapshotScheduleConfig,
         hack_for_tests: bool,
         check_max_versions_after_snapshot: bool,
     ) -> Result<BufferedState> {
@@ -500,6 +564,7 @@ impl StateStore {
                 latest_snapshot_version,
             ),
             buffered_state_target_items,
+            snapshot_schedule,
         );
 
         // In some backup-restore tests we hope to open the db without consistency check.
@@ -579,12 +644,49 @@ impl StateStore {
         *self.buffered_state.lock() = Self::create_buffered_state_from_latest_snapshot(
             &self.state_db,
             self.buffered_state_target_items,
+            *self.snapshot_schedule.lock(),
             false,
             true,
         )
         .expect("buffered state creation failed.");
     }
 
+    pub fn snapshot_schedule(&self) -> StateSnapshotScheduleConfig {
+        *self.snapshot_schedule.lock()
+    }
+
+    /// Changes the schedule of the state snapshots, taking effect from the next commit.
+    pub fn set_snapshot_schedule(&self, snapshot_schedule: StateSnapshotScheduleConfig) {
+        *self.snapshot_schedule.lock() = snapshot_schedule;
+        self.buffered_state
+            .lock()
+            .set_snapshot_schedule(snapshot_schedule);
+    }
+
+    /// Returns the versions of up to `limit` state snapshots at or before `end_version`, the
+    /// latest first. Snapshots that were pruned are not returned.
+    pub fn get_state_snapshot_versions(
+        &self,
+        end_version: Version,
+        limit: usize,
+    ) -> Result<Vec<Version>> {
+        let mut versions = Vec::with_capacity(limit);
+        let mut next_version = end_version.saturating_add(1);
+        while versions.len() < limit {
+            match self
+                .state_merkle_db
+                .get_state_snapshot_version_before(next_version)?
+            {
+                Some(version) => {
+                    versions.push(version);
+                    next_version = version;
+                },
+                None => break,
+            }
+        }
+        Ok(versions)
+    }
+
     pub fn buffered_state(&self) -> &Mutex<BufferedState> {
         &self.buffered_state
     }
@@ -338,6 +338,76 @@ pub fn test_get_state_snapshot_before() {
     );
 }
 
+#[test]
+pub fn test_get_state_snapshot_versions() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+
+    // Empty store
+    assert_eq!(
+        store.get_state_snapshot_versions(10, 10).unwrap(),
+        Vec::<Version>::new()
+    );
+
+    // put in snapshots at versions 0, 2 and 5
+    let kv = vec![(
+        StateKey::raw(b"key".to_vec()),
+        StateValue::from(b"value".to_vec()),
+    )];
+    put_value_set(store, kv.clone(), 0, None);
+    let usage = store.get_usage(Some(0)).unwrap();
+    for version in [1, 3, 4] {
+        store
+            .ledger_db
+            .metadata_db()
+            .put::<VersionDataSchema>(&version, &usage.into())
+            .unwrap();
+    }
+    put_value_set(store, kv.clone(), 2, Some(0));
+    put_value_set(store, kv, 5, Some(2));
+
+    assert_eq!(store.get_state_snapshot_versions(10, 10).unwrap(), vec![
+        5, 2, 0
+    ]);
+    assert_eq!(store.get_state_snapshot_versions(5, 10).unwrap(), vec![
+        5, 2, 0
+    ]);
+    assert_eq!(store.get_state_snapshot_versions(4, 10).unwrap(), vec![
+        2, 0
+    ]);
+    assert_eq!(store.get_state_snapshot_versions(10, 2).unwrap(), vec![
+        5, 2
+    ]);
+    assert_eq!(
+        store.get_state_snapshot_versions(10, 0).unwrap(),
+        Vec::<Version>::new()
+    );
+}
+
+#[test]
+fn test_state_snapshot_schedule() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    assert_eq!(
+        store.snapshot_schedule(),
+        StateSnapshotScheduleConfig::default()
+    );
+
+    // The schedule is kept when the buffered state is recreated
+    let schedule = StateSnapshotScheduleConfig {
+        interval_versions: 1_000,
+        interval_blocks: 10,
+        interval_secs: 60,
+    };
+    store.set_snapshot_schedule(schedule);
+    assert_eq!(store.buffered_state().lock().snapshot_schedule(), schedule);
+    store.reset();
+    assert_eq!(store.snapshot_schedule(), schedule);
+    assert_eq!(store.buffered_state().lock().snapshot_schedule(), schedule);
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
@@ -0,0 +1,397 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the growth of the column families of the DBs, to forecast when the disk will be full
+//! and to recommend a ledger prune window and a backup cadence that fit the disk and the
+//! retention target of the operator.
+
+use crate::{
+    db_options::{ledger_db_column_families, state_merkle_db_column_families},
+    ledger_db::LEDGER_DB_NAME,
+    ledger_store::LedgerStore,
+    state_merkle_db::{StateMerkleDb, STATE_MERKLE_DB_NAME},
+};
+use anyhow::Result;
+use aptos_config::config::StorageForecastConfig;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_schemadb::DB;
+use aptos_types::transaction::Version;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    sync::{mpsc, Arc},
+    thread,
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+#[cfg(test)]
+mod test;
+
+const SECS_PER_DAY: f64 = 24.0 * 3600.0;
+
+/// Prune windows below this are rejected by the config sanitizer on mainnet and testnet.
+const MIN_RECOMMENDED_LEDGER_PRUNE_WINDOW: Version = 50_000_000;
+
+/// A new prune window is only recommended if it differs from the current one by more than this
+/// ratio, so that the recommendation doesn't flip-flop with small changes of the growth rate.
+const PRUNE_WINDOW_TOLERANCE: f64 = 0.1;
+
+/// Operators are warned if the disk is forecasted to be full within this many days.
+const DAYS_UNTIL_FULL_WARNING: f64 = 30.0;
+
+/// The sizes of the column families of the DBs at some point in time.
+#[derive(Clone, Debug)]
+pub(crate) struct DbSizeSample {
+    pub time: Instant,
+    /// Size on disk of each column family, keyed by (db name, column family name)
+    pub cf_sizes: BTreeMap<(&'static str, &'static str), u64>,
+    /// The latest version committed to the ledger, if any
+    pub latest_version: Option<Version>,
+}
+
+impl DbSizeSample {
+    fn total_bytes(&self) -> u64 {
+        self.cf_sizes.values().sum()
+    }
+
+    fn db_bytes(&self, db_name: &str) -> u64 {
+        self.cf_sizes
+            .iter()
+            .filter(|((db, _), _)| *db == db_name)
+            .map(|(_, bytes)| bytes)
+            .sum()
+    }
+}
+
+/// The size and the growth rate of a column family.
+#[derive(Clone, Debug, Serialize)]
+pub struct ColumnFamilyGrowth {
+    pub db: String,
+    pub column_family: String,
+    pub bytes: u64,
+    pub growth_bytes_per_day: f64,
+}
+
+/// The forecasted storage usage, and the recommended adjustments of the prune window and the
+/// backup cadence.
+#[derive(Clone, Debug, Serialize)]
+pub struct StorageForecast {
+    /// Duration covered by the samples the growth rates are computed from
+    pub observed_secs: u64,
+    pub total_bytes: u64,
+    pub disk_size_bytes: Option<u64>,
+    pub growth_bytes_per_day: f64,
+    pub versions_per_day: Option<f64>,
+    /// None if the disk size is unknown or the DBs are not growing
+    pub days_until_full: Option<f64>,
+    /// None if the ledger pruner is disabled
+    pub ledger_prune_window: Option<Version>,
+    pub recommended_ledger_prune_window: Option<Version>,
+    pub recommended_backup_interval_hours: Option<f64>,
+    /// Column families, the fastest growing first
+    pub column_families: Vec<ColumnFamilyGrowth>,
+    pub recommendations: Vec<String>,
+}
+
+impl fmt::Display for StorageForecast {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "StorageForecast: {:.2} GiB used, growing {:.2} GiB/day over the last {}s",
+            to_gib(self.total_bytes as f64),
+            to_gib(self.growth_bytes_per_day),
+            self.observed_secs,
+        )?;
+        if let Some(disk_size_bytes) = self.disk_size_bytes {
+            write!(f, ", disk size {:.2} GiB", to_gib(disk_size_bytes as f64))?;
+        }
+        if let Some(days_until_full) = self.days_until_full {
+            write!(f, ", full in {:.1} days", days_until_full)?;
+        }
+        if let Some(window) = self.recommended_ledger_prune_window {
+            write!(f, ", recommended ledger prune window {}", window)?;
+        }
+        for cf in self.column_families.iter().take(3) {
+            write!(
+                f,
+                ", {}/{} growing {:.2} GiB/day",
+                cf.db,
+                cf.column_family,
+                to_gib(cf.growth_bytes_per_day)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn to_gib(bytes: f64) -> f64 {
+    bytes / (1u64 << 30) as f64
+}
+
+/// Keeps the samples of the DB sizes taken within the growth window, and forecasts the storage
+/// usage from them.
+pub struct StorageForecaster {
+    config: StorageForecastConfig,
+    /// None if the ledger pruner is disabled
+    ledger_prune_window: Option<Version>,
+    samples: Mutex<VecDeque<DbSizeSample>>,
+}
+
+impl StorageForecaster {
+    pub fn new(config: StorageForecastConfig, ledger_prune_window: Option<Version>) -> Self {
+        Self {
+            config,
+            ledger_prune_window,
+            samples: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn observe(&self, sample: DbSizeSample) {
+        let mut samples = self.samples.lock();
+        let window = Duration::from_secs(self.config.growth_window_secs);
+        while samples.front().map_or(false, |oldest| {
+            sample.time.duration_since(oldest.time) > window
+        }) {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// Returns the forecast based on the samples taken so far, or None if there is none.
+    pub fn forecast(&self) -> Option<StorageForecast> {
+        let samples = self.samples.lock();
+        let (oldest, latest) = (samples.front()?, samples.back()?);
+        let elapsed_days = latest.time.duration_since(oldest.time).as_secs_f64() / SECS_PER_DAY;
+        let per_day = |delta: f64| {
+            if elapsed_days > 0.0 {
+                delta / elapsed_days
+            } else {
+                0.0
+            }
+        };
+
+        let mut column_families: Vec<_> = latest
+            .cf_sizes
+            .iter()
+            .map(|((db, cf), bytes)| {
+                let oldest_bytes = oldest.cf_sizes.get(&(*db, *cf)).copied().unwrap_or(0);
+                ColumnFamilyGrowth {
+                    db: db.to_string(),
+                    column_family: cf.to_string(),
+                    bytes: *bytes,
+                    growth_bytes_per_day: per_day(*bytes as f64 - oldest_bytes as f64),
+                }
+            })
+            .collect();
+        column_families.sort_by(|a, b| b.growth_bytes_per_day.total_cmp(&a.growth_bytes_per_day));
+
+        let total_bytes = latest.total_bytes();
+        let versions_per_day = match (oldest.latest_version, latest.latest_version) {
+            (Some(oldest_version), Some(latest_version)) if elapsed_days > 0.0 => {
+                Some(per_day(latest_version.saturating_sub(oldest_version) as f64))
+            },
+            _ => None,
+        };
+        let mut forecast = StorageForecast {
+            observed_secs: latest.time.duration_since(oldest.time).as_secs(),
+            total_bytes,
+            disk_size_bytes: (self.config.disk_size_bytes > 0)
+                .then_some(self.config.disk_size_bytes),
+            growth_bytes_per_day: per_day(total_bytes as f64 - oldest.total_bytes() as f64),
+            versions_per_day,
+            days_until_full: None,
+            ledger_prune_window: self.ledger_prune_window,
+            recommended_ledger_prune_window: None,
+            recommended_backup_interval_hours: None,
+            column_families,
+            recommendations: vec![],
+        };
+        self.recommend(latest, &mut forecast);
+        Some(forecast)
+    }
+
+    /// The number of bytes the DBs can use without eating into the headroom
+    fn usable_disk_bytes(&self) -> f64 {
+        let headroom_percent = self.config.disk_headroom_percent.min(100);
+        self.config.disk_size_bytes as f64 * (100 - headroom_percent) as f64 / 100.0
+    }
+
+    fn recommend(&self, latest: &DbSizeSample, forecast: &mut StorageForecast) {
+        let usable_bytes = self.usable_disk_bytes();
+        if forecast.disk_size_bytes.is_some() && forecast.growth_bytes_per_day > 0.0 {
+            let free_bytes = (usable_bytes - forecast.total_bytes as f64).max(0.0);
+            forecast.days_until_full = Some(free_bytes / forecast.growth_bytes_per_day);
+        }
+
+        if let Some(versions_per_day) = forecast.versions_per_day.filter(|v| *v > 0.0) {
+            let mut window = (self.config.target_retention_days > 0)
+                .then(|| versions_per_day * self.config.target_retention_days as f64);
+
+            // The ledger DB holds the history that the ledger pruner prunes, so its size is
+            // roughly proportional to the number of retained versions.
+            let ledger_bytes = latest.db_bytes(LEDGER_DB_NAME) as f64;
+            let other_bytes = forecast.total_bytes as f64 - ledger_bytes;
+            let retained_versions = latest.latest_version.map(|version| {
+                let num_versions = version.saturating_add(1);
+                self.ledger_prune_window
+                    .map_or(num_versions, |window| window.min(num_versions))
+            });
+            if let (Some(_), Some(retained_versions)) =
+                (forecast.disk_size_bytes, retained_versions)
+            {
+                let bytes_per_version = ledger_bytes / retained_versions.max(1) as f64;
+                if bytes_per_version > 0.0 {
+                    let max_window = ((usable_bytes - other_bytes) / bytes_per_version).max(0.0);
+                    if window.map_or(true, |window| window > max_window) {
+                        if window.is_some() {
+                            forecast.recommendations.push(format!(
+                                "The disk can only hold {:.1} days of history, less than the target of {} days. Consider a bigger disk.",
+                                max_window / versions_per_day,
+                                self.config.target_retention_days,
+                            ));
+                        }
+                        window = Some(max_window);
+                    }
+                }
+            }
+
+            if let Some(window) = window {
+                let window = window as Version;
+                forecast.recommended_ledger_prune_window = Some(window);
+                let retention_days = window as f64 / versions_per_day;
+                match self.ledger_prune_window {
+                    Some(current) => {
+                        let diff = (window as f64 - current as f64).abs();
+                        if diff > current as f64 * PRUNE_WINDOW_TOLERANCE {
+                            forecast.recommendations.push(format!(
+                                "Change the ledger prune_window from {} to {} (~{:.1} days of history).",
+                                current, window, retention_days,
+                            ));
+                        }
+                    },
+                    None => forecast.recommendations.push(format!(
+                        "Enable the ledger pruner with a prune_window of {} (~{:.1} days of history).",
+                        window, retention_days,
+                    )),
+                }
+                if window < MIN_RECOMMENDED_LEDGER_PRUNE_WINDOW {
+                    forecast.recommendations.push(format!(
+                        "The recommended ledger prune_window is below the minimum of {} versions allowed on mainnet and testnet. Consider a bigger disk.",
+                        MIN_RECOMMENDED_LEDGER_PRUNE_WINDOW,
+                    ));
+                }
+
+                // Back up twice per prune window, so that a failed backup can be retried
+                // before the data it covers is pruned.
+                let backup_interval_hours = retention_days * 24.0 / 2.0;
+                forecast.recommended_backup_interval_hours = Some(backup_interval_hours);
+                forecast.recommendations.push(format!(
+                    "Back up the ledger at least every {:.1} hours, so that it is backed up before being pruned.",
+                    backup_interval_hours,
+                ));
+            }
+        }
+
+        if let Some(days_until_full) = forecast.days_until_full {
+            if days_until_full < DAYS_UNTIL_FULL_WARNING {
+                forecast.recommendations.push(format!(
+                    "The disk is forecasted to be full in {:.1} days.",
+                    days_until_full,
+                ));
+            }
+        }
+    }
+}
+
+fn sample_db_sizes(
+    ledger_rocksdb: &DB,
+    state_merkle_db: &StateMerkleDb,
+    ledger_store: &LedgerStore,
+) -> Result<DbSizeSample> {
+    const SIZE_PROPERTY: &str = "rocksdb.total-sst-files-size";
+
+    let mut cf_sizes = BTreeMap::new();
+    for cf_name in ledger_db_column_families() {
+        cf_sizes.insert(
+            (LEDGER_DB_NAME, cf_name),
+            ledger_rocksdb.get_property(cf_name, SIZE_PROPERTY)?,
+        );
+    }
+    for cf_name in state_merkle_db_column_families() {
+        // TODO(grao): Support sharding here.
+        cf_sizes.insert(
+            (STATE_MERKLE_DB_NAME, cf_name),
+            state_merkle_db
+                .metadata_db()
+                .get_property(cf_name, SIZE_PROPERTY)?,
+        );
+    }
+    Ok(DbSizeSample {
+        time: Instant::now(),
+        cf_sizes,
+        latest_version: ledger_store.get_latest_version().ok(),
+    })
+}
+
+/// Periodically samples the DB sizes into the forecaster, and logs a summary of the forecast.
+#[derive(Debug)]
+pub(crate) struct StorageForecastReporter {
+    sender: Mutex<mpsc::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl StorageForecastReporter {
+    pub fn new(
+        forecaster: Arc<StorageForecaster>,
+        ledger_rocksdb: Arc<DB>,
+        state_merkle_db: Arc<StateMerkleDb>,
+        ledger_store: Arc<LedgerStore>,
+    ) -> Self {
+        let sample_interval = Duration::from_secs(forecaster.config.sample_interval_secs.max(1));
+        let summary_interval = Duration::from_secs(forecaster.config.summary_interval_secs);
+        let (send, recv) = mpsc::channel();
+        let join_handle = Some(thread::spawn(move || {
+            let mut last_summary = Instant::now();
+            loop {
+                match sample_db_sizes(&ledger_rocksdb, &state_merkle_db, &ledger_store) {
+                    Ok(sample) => forecaster.observe(sample),
+                    Err(e) => warn!(error = ?e, "Sampling the DB sizes failed."),
+                }
+                if last_summary.elapsed() >= summary_interval {
+                    last_summary = Instant::now();
+                    if let Some(forecast) = forecaster.forecast() {
+                        info!("{}", forecast);
+                        for recommendation in &forecast.recommendations {
+                            info!(recommendation = recommendation, "Storage recommendation.");
+                        }
+                    }
+                }
+
+                match recv.recv_timeout(sample_interval) {
+                    Ok(_) => break,
+                    Err(mpsc::RecvTimeoutError::Timeout) => (),
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        }));
+        Self {
+            sender: Mutex::new(send),
+            join_handle,
+        }
+    }
+}
+
+impl Drop for StorageForecastReporter {
+    fn drop(&mut self) {
+        // Notify the sampling thread to exit
+        self.sender.lock().send(()).unwrap();
+        self.join_handle
+            .take()
+            .expect("Storage forecast thread must exist.")
+            .join()
+            .expect("Storage forecast thread should join peacefully.");
+    }
+}
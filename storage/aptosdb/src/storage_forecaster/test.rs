@@ -0,0 +1,134 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const DAY: Duration = Duration::from_secs(24 * 3600);
+
+fn sample(time: Instant, ledger_bytes: u64, merkle_bytes: u64, version: Version) -> DbSizeSample {
+    DbSizeSample {
+        time,
+        cf_sizes: [
+            ((LEDGER_DB_NAME, "transaction"), ledger_bytes),
+            (
+                (STATE_MERKLE_DB_NAME, "jellyfish_merkle_node"),
+                merkle_bytes,
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        latest_version: Some(version),
+    }
+}
+
+fn config(target_retention_days: u64) -> StorageForecastConfig {
+    StorageForecastConfig {
+        disk_size_bytes: 10_000,
+        disk_headroom_percent: 20,
+        target_retention_days,
+        ..Default::default()
+    }
+}
+
+// Over a day, the ledger grows from 1024 to 2048 bytes and from 512 to 1024 versions (2 bytes
+// per version), while the state merkle DB stays at 1000 bytes.
+fn observe_a_day(forecaster: &StorageForecaster) {
+    let start = Instant::now();
+    forecaster.observe(sample(start, 1024, 1000, 511));
+    forecaster.observe(sample(start + DAY, 2048, 1000, 1023));
+}
+
+#[test]
+fn test_no_samples() {
+    let forecaster = StorageForecaster::new(config(0), None);
+    assert!(forecaster.forecast().is_none());
+}
+
+#[test]
+fn test_forecast_without_retention_target() {
+    let forecaster = StorageForecaster::new(config(0), None);
+    observe_a_day(&forecaster);
+    let forecast = forecaster.forecast().unwrap();
+
+    assert_eq!(forecast.observed_secs, DAY.as_secs());
+    assert_eq!(forecast.total_bytes, 3048);
+    assert_eq!(forecast.growth_bytes_per_day, 1024.0);
+    assert_eq!(forecast.versions_per_day, Some(512.0));
+    // 8000 usable bytes, 4952 free
+    assert_eq!(forecast.days_until_full, Some(4952.0 / 1024.0));
+    assert_eq!(forecast.column_families[0].column_family, "transaction");
+    assert_eq!(forecast.column_families[1].growth_bytes_per_day, 0.0);
+
+    // The ledger can use 7000 bytes, i.e. 3500 versions
+    assert_eq!(forecast.recommended_ledger_prune_window, Some(3500));
+    assert_eq!(
+        forecast.recommended_backup_interval_hours,
+        Some(3500.0 / 512.0 * 12.0)
+    );
+    let recommendations = forecast.recommendations.join("\n");
+    assert!(recommendations.contains("Enable the ledger pruner"));
+    assert!(recommendations.contains("full in 4.8 days"));
+}
+
+#[test]
+fn test_forecast_with_retention_target() {
+    // The target of 2 days fits the disk
+    let forecaster = StorageForecaster::new(config(2), Some(3000));
+    observe_a_day(&forecaster);
+    let forecast = forecaster.forecast().unwrap();
+    assert_eq!(forecast.recommended_ledger_prune_window, Some(1024));
+    assert!(forecast
+        .recommendations
+        .iter()
+        .any(|r| r.contains("from 3000 to 1024")));
+
+    // The current window is close enough to the recommended one
+    let forecaster = StorageForecaster::new(config(2), Some(1000));
+    observe_a_day(&forecaster);
+    let forecast = forecaster.forecast().unwrap();
+    assert_eq!(forecast.recommended_ledger_prune_window, Some(1024));
+    assert!(!forecast
+        .recommendations
+        .iter()
+        .any(|r| r.contains("prune_window from")));
+
+    // The target of 10 days doesn't fit the disk
+    let forecaster = StorageForecaster::new(config(10), Some(1000));
+    observe_a_day(&forecaster);
+    let forecast = forecaster.forecast().unwrap();
+    assert_eq!(forecast.recommended_ledger_prune_window, Some(3500));
+    assert!(forecast
+        .recommendations
+        .iter()
+        .any(|r| r.contains("less than the target of 10 days")));
+}
+
+#[test]
+fn test_unknown_disk_size() {
+    let forecaster = StorageForecaster::new(
+        StorageForecastConfig {
+            target_retention_days: 2,
+            ..Default::default()
+        },
+        Some(1000),
+    );
+    observe_a_day(&forecaster);
+    let forecast = forecaster.forecast().unwrap();
+    assert_eq!(forecast.disk_size_bytes, None);
+    assert_eq!(forecast.days_until_full, None);
+    // Without a disk size, the window is only based on the retention target
+    assert_eq!(forecast.recommended_ledger_prune_window, Some(1024));
+}
+
+#[test]
+fn test_samples_outside_growth_window_are_dropped() {
+    let forecaster = StorageForecaster::new(config(0), None);
+    let start = Instant::now();
+    forecaster.observe(sample(start, 0, 0, 0));
+    forecaster.observe(sample(start + DAY, 1000, 0, 100));
+    forecaster.observe(sample(start + DAY * 2, 3000, 0, 300));
+    let forecast = forecaster.forecast().unwrap();
+    assert_eq!(forecast.observed_secs, DAY.as_secs());
+    assert_eq!(forecast.growth_bytes_per_day, 2000.0);
+    assert_eq!(forecast.versions_per_day, Some(200.0));
+}
@@ -107,6 +107,7 @@ fn test_end_to_end_impl(d: TestData) {
                 TransactionBackupOpt {
                     start_version: d.txn_start_ver,
                     num_transactions: num_txns_to_backup as usize,
+                    dedup_write_sets: false,
                 },
                 global_backup_opt,
                 Arc::clone(&client),
@@ -13,7 +13,11 @@ use crate::{
 };
 use anyhow::{anyhow, ensure, Result};
 use aptos_logger::prelude::*;
-use aptos_types::transaction::Version;
+use aptos_types::{
+    contract_event::ContractEvent,
+    transaction::{Transaction, TransactionInfo, Version},
+    write_set::{DedupedWriteSets, WriteSet},
+};
 use clap::Parser;
 use once_cell::sync::Lazy;
 use std::{convert::TryInto, str::FromStr, sync::Arc};
@@ -26,11 +30,18 @@ pub struct TransactionBackupOpt {
 
     #[clap(long = "num_transactions", help = "Number of transactions to backup")]
     pub num_transactions: usize,
+
+    #[clap(
+        long,
+        help = "Deduplicate identical write ops within each chunk, to reduce the backup size."
+    )]
+    pub dedup_write_sets: bool,
 }
 
 pub struct TransactionBackupController {
     start_version: u64,
     num_transactions: usize,
+    dedup_write_sets: bool,
     max_chunk_size: usize,
     client: Arc<BackupServiceClient>,
     storage: Arc<dyn BackupStorage>,
@@ -46,6 +57,7 @@ impl TransactionBackupController {
         Self {
             start_version: opt.start_version,
             num_transactions: opt.num_transactions,
+            dedup_write_sets: opt.dedup_write_sets,
             max_chunk_size: global_opt.max_chunk_size,
             client,
             storage,
@@ -139,6 +151,10 @@ impl TransactionBackupController {
         format!("{}-.chunk", first_ver).try_into().unwrap()
     }
 
+    fn chunk_write_op_dictionary_name(first_ver: Version) -> ShellSafeName {
+        format!("{}-.write_ops", first_ver).try_into().unwrap()
+    }
+
     fn chunk_proof_name(first_ver: u64, last_ver: Version) -> ShellSafeName {
         format!("{}-{}.proof", first_ver, last_ver)
             .try_into()
@@ -169,17 +185,36 @@ impl TransactionBackupController {
         .await?;
         proof_file.shutdown().await?;
 
+        let (chunk_bytes, write_op_dictionary) = if self.dedup_write_sets {
+            let (chunk_bytes, write_op_dictionary_bytes) = dedup_chunk(chunk_bytes).await?;
+            let (dictionary_handle, mut dictionary_file) = self
+                .storage
+                .create_for_write(
+                    backup_handle,
+                    &Self::chunk_write_op_dictionary_name(first_version),
+                )
+                .await?;
+            dictionary_file
+                .write_all(&write_op_dictionary_bytes)
+                .await?;
+            dictionary_file.shutdown().await?;
+            (chunk_bytes, Some(dictionary_handle))
+        } else {
+            (chunk_bytes.to_vec(), None)
+        };
+
         let (chunk_handle, mut chunk_file) = self
             .storage
             .create_for_write(backup_handle, &Self::chunk_name(first_version))
             .await?;
-        chunk_file.write_all(chunk_bytes).await?;
+        chunk_file.write_all(&chunk_bytes).await?;
         chunk_file.shutdown().await?;
 
         Ok(TransactionChunk {
             first_version,
             last_version,
             transactions: chunk_handle,
+            write_op_dictionary,
             proof: proof_handle,
         })
     }
@@ -214,3 +249,34 @@ impl TransactionBackupController {
         Ok(manifest_handle)
     }
 }
+
+/// Re-encodes the records of a chunk with their write sets deduplicated, returning the new chunk
+/// bytes and the BCS serialized write op dictionary the records refer to.
+async fn dedup_chunk(mut chunk_bytes: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let mut records = Vec::new();
+    let mut write_sets = Vec::new();
+    while let Some(record_bytes) = chunk_bytes.read_record_bytes().await? {
+        let (txn, txn_info, events, write_set): (
+            Transaction,
+            TransactionInfo,
+            Vec<ContractEvent>,
+            WriteSet,
+        ) = bcs::from_bytes(&record_bytes)?;
+        records.push((txn, txn_info, events));
+        write_sets.push(write_set);
+    }
+
+    let deduped_write_sets = DedupedWriteSets::new(&write_sets);
+    let mut deduped_chunk_bytes = Vec::new();
+    for ((txn, txn_info, events), write_op_indices) in
+        records.into_iter().zip(deduped_write_sets.write_op_indices)
+    {
+        let record_bytes = bcs::to_bytes(&(txn, txn_info, events, write_op_indices))?;
+        deduped_chunk_bytes.extend((record_bytes.len() as u32).to_be_bytes());
+        deduped_chunk_bytes.extend(&record_bytes);
+    }
+    Ok((
+        deduped_chunk_bytes,
+        bcs::to_bytes(&deduped_write_sets.write_ops)?,
+    ))
+}
@@ -14,8 +14,14 @@ pub struct TransactionChunk {
     pub first_version: Version,
     pub last_version: Version,
     /// Repeated `len(record) + record`, where `record` is BCS serialized tuple
-    /// `(Transaction, TransactionInfo)`
+    /// `(Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet)`, or
+    /// `(Transaction, TransactionInfo, Vec<ContractEvent>, Vec<u32>)` if
+    /// `write_op_dictionary` is set.
     pub transactions: FileHandle,
+    /// BCS serialized `Vec<(StateKey, WriteOp)>`, holding the distinct write ops of the chunk.
+    /// If set, the write set of each record is a list of indices into it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_op_dictionary: Option<FileHandle>,
     /// BCS serialized `(TransactionAccumulatorRangeProof, LedgerInfoWithSignatures)`.
     /// The `TransactionAccumulatorRangeProof` links the transactions to the
     /// `LedgerInfoWithSignatures`, and the `LedgerInfoWithSignatures` can be verified by the
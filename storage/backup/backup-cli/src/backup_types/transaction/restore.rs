@@ -34,8 +34,9 @@ use aptos_types::{
     contract_event::ContractEvent,
     ledger_info::LedgerInfoWithSignatures,
     proof::{TransactionAccumulatorRangeProof, TransactionInfoListWithProof},
+    state_store::state_key::StateKey,
     transaction::{Transaction, TransactionInfo, TransactionListWithProof, Version},
-    write_set::WriteSet,
+    write_set::{DedupedWriteSets, WriteOp, WriteSet},
 };
 use aptos_vm::AptosVM;
 use clap::Parser;
@@ -83,8 +84,9 @@ pub struct TransactionRestoreController {
     inner: TransactionRestoreBatchController,
 }
 
-#[allow(dead_code)]
-struct LoadedChunk {
+/// A transaction chunk loaded from the backup storage (and verified against the ledger info
+/// it carries).
+pub struct LoadedChunk {
     pub manifest: TransactionChunk,
     pub txns: Vec<Transaction>,
     pub txn_infos: Vec<TransactionInfo>,
@@ -95,7 +97,7 @@ struct LoadedChunk {
 }
 
 impl LoadedChunk {
-    async fn load(
+    pub async fn load(
         manifest: TransactionChunk,
         storage: &Arc<dyn BackupStorage>,
         epoch_history: Option<&Arc<EpochHistory>>,
@@ -106,13 +108,36 @@ impl LoadedChunk {
         let mut event_vecs = Vec::new();
         let mut write_sets = Vec::new();
 
-        while let Some(record_bytes) = file.read_record_bytes().await? {
-            let (txn, txn_info, events, write_set): (_, _, _, WriteSet) =
-                bcs::from_bytes(&record_bytes)?;
-            txns.push(txn);
-            txn_infos.push(txn_info);
-            event_vecs.push(events);
-            write_sets.push(write_set);
+        match &manifest.write_op_dictionary {
+            Some(write_op_dictionary) => {
+                let write_ops = storage
+                    .load_bcs_file::<Vec<(StateKey, WriteOp)>>(write_op_dictionary)
+                    .await?;
+                let mut write_op_indices = Vec::new();
+                while let Some(record_bytes) = file.read_record_bytes().await? {
+                    let (txn, txn_info, events, indices): (_, _, _, Vec<u32>) =
+                        bcs::from_bytes(&record_bytes)?;
+                    txns.push(txn);
+                    txn_infos.push(txn_info);
+                    event_vecs.push(events);
+                    write_op_indices.push(indices);
+                }
+                write_sets = DedupedWriteSets {
+                    write_ops,
+                    write_op_indices,
+                }
+                .into_write_sets()?;
+            },
+            None => {
+                while let Some(record_bytes) = file.read_record_bytes().await? {
+                    let (txn, txn_info, events, write_set): (_, _, _, WriteSet) =
+                        bcs::from_bytes(&record_bytes)?;
+                    txns.push(txn);
+                    txn_infos.push(txn_info);
+                    event_vecs.push(events);
+                    write_sets.push(write_set);
+                }
+            },
         }
 
         ensure!(
@@ -159,7 +184,7 @@ impl LoadedChunk {
         })
     }
 
-    fn unpack(
+    pub fn unpack(
         self,
     ) -> (
         Vec<Transaction>,
@@ -65,6 +65,7 @@ fn end_to_end() {
                     TransactionBackupOpt {
                         start_version: 0,
                         num_transactions: first_ver_to_backup as usize,
+                        dedup_write_sets: false,
                     },
                     GlobalBackupOpt { max_chunk_size },
                     client.clone(),
@@ -82,6 +83,8 @@ fn end_to_end() {
                 TransactionBackupOpt {
                     start_version: first_ver_to_backup,
                     num_transactions: num_txns_to_backup,
+                    // Restoring both formats of chunks should work
+                    dedup_write_sets: true,
                 },
                 GlobalBackupOpt { max_chunk_size },
                 client,
@@ -69,6 +69,13 @@ pub struct BackupCoordinatorOpt {
         many small files. "
     )]
     pub transaction_batch_size: usize,
+    #[clap(
+        long,
+        help = "Deduplicate identical write ops within each transaction backup chunk. \
+        Significantly reduces the backup size for batch-heavy workloads (e.g., airdrops), \
+        but requires a backup-cli version that supports it to restore."
+    )]
+    pub dedup_write_sets: bool,
     #[clap(flatten)]
     pub concurrent_downloads: ConcurrentDownloadsOpt,
 }
@@ -90,6 +97,7 @@ pub struct BackupCoordinator {
     metadata_cache_opt: MetadataCacheOpt,
     state_snapshot_interval_epochs: usize,
     transaction_batch_size: usize,
+    dedup_write_sets: bool,
     concurrent_downloads: usize,
 }
 
@@ -108,6 +116,7 @@ impl BackupCoordinator {
             metadata_cache_opt: opt.metadata_cache_opt,
             state_snapshot_interval_epochs: opt.state_snapshot_interval_epochs,
             transaction_batch_size: opt.transaction_batch_size,
+            dedup_write_sets: opt.dedup_write_sets,
             concurrent_downloads: opt.concurrent_downloads.get(),
         }
     }
@@ -290,6 +299,7 @@ impl BackupCoordinator {
                 TransactionBackupOpt {
                     start_version: first,
                     num_transactions: (last + 1 - first) as usize,
+                    dedup_write_sets: self.dedup_write_sets,
                 },
                 self.global_opt.clone(),
                 Arc::clone(&self.client),
@@ -0,0 +1,97 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_consensus::{
+    certificate_archive::{
+        CommitCertArchiveSchema, Epoch, Key, TimeoutCertArchiveSchema, COMMIT_CERT_ARCHIVE_CF_NAME,
+        TIMEOUT_CERT_ARCHIVE_CF_NAME,
+    },
+    CONSENSUS_DB_NAME,
+};
+use aptos_consensus_types::timeout_2chain::TwoChainTimeoutCertificate;
+use aptos_schemadb::{
+    schema::{Schema, SeekKeyCodec},
+    Options, ReadOptions, DB, DEFAULT_COLUMN_FAMILY_NAME,
+};
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+/// The commit and timeout certificates archived during a single epoch
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CertificateArchive {
+    /// The epoch of the archived certificates
+    pub epoch: u64,
+    /// The commit certificates, ordered by round
+    pub commit_certificates: Vec<LedgerInfoWithSignatures>,
+    /// The timeout certificates, ordered by round
+    pub timeout_certificates: Vec<TwoChainTimeoutCertificate>,
+}
+
+/// Export the commit and timeout certificates archived by consensus during an epoch
+/// (requires `consensus.enable_certificate_archive` on the node) as a BCS-encoded
+/// `CertificateArchive`, so finality and validator participation can be audited independently.
+#[derive(Parser)]
+pub struct Opt {
+    #[clap(
+        long,
+        value_parser,
+        help = "The node's data directory (containing consensus_db)."
+    )]
+    db_dir: PathBuf,
+    #[clap(long, help = "The epoch to export the certificates of.")]
+    epoch: u64,
+    #[clap(long, value_parser, help = "The file to write the archive to.")]
+    output_file: PathBuf,
+}
+
+impl Opt {
+    pub fn run(self) -> Result<()> {
+        let db = DB::open_cf_readonly(
+            &Options::default(),
+            self.db_dir.join(CONSENSUS_DB_NAME),
+            "consensus_readonly",
+            vec![
+                DEFAULT_COLUMN_FAMILY_NAME,
+                COMMIT_CERT_ARCHIVE_CF_NAME,
+                TIMEOUT_CERT_ARCHIVE_CF_NAME,
+            ],
+        )?;
+        let archive = CertificateArchive {
+            epoch: self.epoch,
+            commit_certificates: read_epoch::<CommitCertArchiveSchema>(&db, self.epoch)?,
+            timeout_certificates: read_epoch::<TimeoutCertArchiveSchema>(&db, self.epoch)?,
+        };
+
+        fs::write(&self.output_file, bcs::to_bytes(&archive)?)?;
+        println!(
+            "Exported {} commit certificates and {} timeout certificates of epoch {} to {:?}.",
+            archive.commit_certificates.len(),
+            archive.timeout_certificates.len(),
+            self.epoch,
+            self.output_file,
+        );
+        Ok(())
+    }
+}
+
+/// Reads the values archived under `epoch`, ordered by round.
+fn read_epoch<S>(db: &DB, epoch: Epoch) -> Result<Vec<S::Value>>
+where
+    S: Schema<Key = Key>,
+    Epoch: SeekKeyCodec<S>,
+{
+    let mut iter = db.iter::<S>(ReadOptions::default())?;
+    iter.seek(&epoch)?;
+    let mut values = vec![];
+    for item in iter {
+        let ((item_epoch, _), value) = item?;
+        if item_epoch != epoch {
+            break;
+        }
+        values.push(value);
+    }
+    Ok(values)
+}
@@ -6,6 +6,7 @@ extern crate core;
 mod backup;
 mod backup_maintenance;
 mod debugger;
+mod export_certificates;
 mod replay_verify;
 pub mod restore;
 #[cfg(test)]
@@ -27,6 +28,7 @@ pub enum DBTool {
     Debug(debugger::Command),
     #[clap(subcommand)]
     BackupMaintenance(backup_maintenance::Command),
+    ExportCertificates(export_certificates::Opt),
 }
 
 impl DBTool {
@@ -37,6 +39,7 @@ impl DBTool {
             DBTool::ReplayVerify(cmd) => cmd.run().await,
             DBTool::BackupMaintenance(cmd) => cmd.run().await,
             DBTool::Debug(cmd) => cmd.run(),
+            DBTool::ExportCertificates(cmd) => cmd.run(),
         }
     }
 }
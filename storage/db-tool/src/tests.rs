@@ -1,7 +1,26 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::DBTool;
+use crate::{export_certificates::CertificateArchive, DBTool};
+use aptos_consensus::{
+    certificate_archive::{
+        CommitCertArchiveSchema, TimeoutCertArchiveSchema, COMMIT_CERT_ARCHIVE_CF_NAME,
+        TIMEOUT_CERT_ARCHIVE_CF_NAME,
+    },
+    CONSENSUS_DB_NAME,
+};
+use aptos_consensus_types::{
+    block::block_test_utils::certificate_for_genesis,
+    timeout_2chain::{TwoChainTimeout, TwoChainTimeoutCertificate},
+};
+use aptos_crypto::HashValue;
+use aptos_schemadb::{Options, DB, DEFAULT_COLUMN_FAMILY_NAME};
+use aptos_temppath::TempPath;
+use aptos_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+};
 use clap::Parser;
 
 #[test]
@@ -70,6 +89,89 @@ fn run_cmd(args: &[&str]) {
     DBTool::try_parse_from(args).expect("command parse unsuccessful");
 }
 
+#[test]
+fn test_export_certificates() {
+    // Archive the certificates of two epochs, as consensus does
+    let db_dir = TempPath::new();
+    db_dir.create_as_dir().unwrap();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open(
+        db_dir.path().join(CONSENSUS_DB_NAME),
+        "consensus",
+        vec![
+            DEFAULT_COLUMN_FAMILY_NAME,
+            COMMIT_CERT_ARCHIVE_CF_NAME,
+            TIMEOUT_CERT_ARCHIVE_CF_NAME,
+        ],
+        &opts,
+    )
+    .unwrap();
+    let mut commit_certificates = vec![];
+    let mut timeout_certificates = vec![];
+    for epoch in 1..=2 {
+        for round in 1..=3 {
+            let commit_cert = LedgerInfoWithSignatures::new(
+                LedgerInfo::new(
+                    BlockInfo::new(
+                        epoch,
+                        round,
+                        HashValue::zero(),
+                        HashValue::zero(),
+                        0,
+                        0,
+                        None,
+                    ),
+                    HashValue::zero(),
+                ),
+                AggregateSignature::empty(),
+            );
+            db.put::<CommitCertArchiveSchema>(&(epoch, round), &commit_cert)
+                .unwrap();
+            let timeout_cert = TwoChainTimeoutCertificate::new(TwoChainTimeout::new(
+                epoch,
+                round,
+                certificate_for_genesis(),
+            ));
+            db.put::<TimeoutCertArchiveSchema>(&(epoch, round), &timeout_cert)
+                .unwrap();
+            if epoch == 2 {
+                commit_certificates.push(commit_cert);
+                timeout_certificates.push(timeout_cert);
+            }
+        }
+    }
+    drop(db);
+
+    // Only the certificates of the requested epoch are exported, ordered by round
+    let output_file = TempPath::new();
+    let db_dir_arg = db_dir.path().to_str().unwrap();
+    let output_file_arg = output_file.path().to_str().unwrap();
+    let cmd = DBTool::try_parse_from([
+        "aptos-db-tool",
+        "export-certificates",
+        "--db-dir",
+        db_dir_arg,
+        "--epoch",
+        "2",
+        "--output-file",
+        output_file_arg,
+    ])
+    .unwrap();
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(cmd.run())
+        .unwrap();
+    let archive: CertificateArchive =
+        bcs::from_bytes(&std::fs::read(output_file.path()).unwrap()).unwrap();
+    assert_eq!(archive, CertificateArchive {
+        epoch: 2,
+        commit_certificates,
+        timeout_certificates,
+    });
+}
+
 #[cfg(test)]
 mod dbtool_tests {
     use crate::DBTool;
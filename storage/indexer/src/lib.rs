@@ -4,26 +4,34 @@
 mod db;
 mod metadata;
 mod schema;
+#[cfg(test)]
+mod tests;
 
+pub use crate::metadata::BackfillProgress;
 use crate::{
     db::INDEX_DB_NAME,
     metadata::{MetadataKey, MetadataValue},
     schema::{
-        column_families, indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
+        account_resource_tag::AccountResourceTagSchema, column_families,
+        indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
+        table_owner::TableOwnerSchema,
     },
 };
 use anyhow::{bail, ensure, Result};
 use aptos_config::config::RocksdbConfig;
+use aptos_crypto::hash::CryptoHash;
+use aptos_infallible::RwLock;
 use aptos_logger::warn;
 use aptos_rocksdb_options::gen_rocksdb_options;
-use aptos_schemadb::{SchemaBatch, DB};
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_storage_interface::{state_view::DbStateView, DbReader};
 use aptos_types::{
     access_path::Path,
     account_address::AccountAddress,
     state_store::{
         state_key::{StateKey, StateKeyInner},
-        table::{TableHandle, TableInfo},
+        state_value::StateValue,
+        table::{TableHandle, TableInfo, TableOwner},
     },
     transaction::{AtomicVersion, Version},
     write_set::{WriteOp, WriteSet},
@@ -44,6 +52,12 @@ use std::{
 pub struct Indexer {
     db: DB,
     next_version: AtomicVersion,
+    // The first version the account resource tags are indexed at (None if they haven't been
+    // backfilled yet, see `backfill_account_resource_tags()`)
+    account_resource_tag_start_version: RwLock<Option<Version>>,
+    // The version from which the owners of all tables are known (None if they haven't been
+    // backfilled yet, see `backfill_table_owners()`)
+    table_owner_start_version: RwLock<Option<Version>>,
 }
 
 impl Indexer {
@@ -64,9 +78,35 @@ impl Indexer {
             .get::<IndexerMetadataSchema>(&MetadataKey::LatestVersion)?
             .map_or(0, |v| v.expect_version());
 
+        let mut account_resource_tag_start_version = db
+            .get::<IndexerMetadataSchema>(&MetadataKey::AccountResourceTagStartVersion)?
+            .map(|v| v.expect_version());
+        if account_resource_tag_start_version.is_none() && next_version == 0 {
+            // A new indexer indexes the account resource tags from genesis
+            db.put::<IndexerMetadataSchema>(
+                &MetadataKey::AccountResourceTagStartVersion,
+                &MetadataValue::Version(0),
+            )?;
+            account_resource_tag_start_version = Some(0);
+        }
+
+        let mut table_owner_start_version = db
+            .get::<IndexerMetadataSchema>(&MetadataKey::TableOwnerStartVersion)?
+            .map(|v| v.expect_version());
+        if table_owner_start_version.is_none() && next_version == 0 {
+            // A new indexer records the owners of all tables from genesis
+            db.put::<IndexerMetadataSchema>(
+                &MetadataKey::TableOwnerStartVersion,
+                &MetadataValue::Version(0),
+            )?;
+            table_owner_start_version = Some(0);
+        }
+
         Ok(Self {
             db,
             next_version: AtomicVersion::new(next_version),
+            account_resource_tag_start_version: RwLock::new(account_resource_tag_start_version),
+            table_owner_start_version: RwLock::new(table_owner_start_version),
         })
     }
 
@@ -118,6 +158,13 @@ impl Indexer {
         }
 
         let mut batch = SchemaBatch::new();
+        for (idx, write_set) in write_sets.iter().enumerate() {
+            Self::index_account_resource_tags(
+                first_version + idx as Version,
+                write_set,
+                &mut batch,
+            )?;
+        }
         match table_info_parser.finish(&mut batch) {
             Ok(_) => {},
             Err(err) => {
@@ -148,13 +195,298 @@ impl Indexer {
     pub fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         self.db.get::<TableInfoSchema>(&handle)
     }
+
+    /// Returns the resource or table item the table was first seen in (or None, if the table
+    /// isn't indexed or its owner still has to be backfilled).
+    pub fn get_table_owner(&self, handle: TableHandle) -> Result<Option<TableOwner>> {
+        self.db.get::<TableOwnerSchema>(&handle)
+    }
+
+    /// Returns the types of all resources (and resource groups) stored under `address` at
+    /// `version`, in ascending order. Returns None if the account resource tags aren't indexed
+    /// at `version` (i.e., it's before they were backfilled).
+    pub fn get_account_resource_tags(
+        &self,
+        address: AccountAddress,
+        version: Version,
+    ) -> Result<Option<Vec<StructTag>>> {
+        ensure!(
+            version < self.next_version(),
+            "Version {} is not indexed yet. Next version to index: {}",
+            version,
+            self.next_version(),
+        );
+        match self.account_resource_tag_start_version() {
+            Some(start_version) if start_version <= version => (),
+            _ => return Ok(None),
+        }
+
+        let mut iter = self
+            .db
+            .iter::<AccountResourceTagSchema>(ReadOptions::default())?;
+        iter.seek(&address)?;
+
+        // Entries of the same type are ordered from the latest version to the oldest, so the
+        // first one at or before the requested version decides whether the resource exists.
+        let mut tags = vec![];
+        let mut last_decided: Option<StructTag> = None;
+        for item in iter {
+            let ((entry_address, struct_tag, entry_version), exists) = item?;
+            if entry_address != address {
+                break;
+            }
+            if entry_version > version || last_decided.as_ref() == Some(&struct_tag) {
+                continue;
+            }
+            if exists {
+                tags.push(struct_tag.clone());
+            }
+            last_decided = Some(struct_tag);
+        }
+        Ok(Some(tags))
+    }
+
+    /// Returns the first version the account resource tags are indexed at (or None, if they
+    /// still have to be backfilled).
+    pub fn account_resource_tag_start_version(&self) -> Option<Version> {
+        *self.account_resource_tag_start_version.read()
+    }
+
+    /// Returns the version from which the owners of all tables are known (or None, if they still
+    /// have to be backfilled).
+    pub fn table_owner_start_version(&self) -> Option<Version> {
+        *self.table_owner_start_version.read()
+    }
+
+    /// Returns whether the indexer is ready, i.e., whether nothing is left to backfill. Until
+    /// then, the account resource tags and the table owners of the versions before the indexer
+    /// tracked them are unknown.
+    pub fn is_backfilled(&self) -> bool {
+        self.account_resource_tag_start_version().is_some()
+            && self.table_owner_start_version().is_some()
+    }
+
+    /// Returns the persisted progress of the backfill of the account resource tags (or None, if
+    /// it hasn't started yet, or is done).
+    pub fn account_resource_tag_backfill_progress(&self) -> Result<Option<BackfillProgress>> {
+        self.get_backfill_progress(MetadataKey::AccountResourceTagBackfillProgress)
+    }
+
+    /// Returns the persisted progress of the backfill of the table owners (or None, if it hasn't
+    /// started yet, or is done).
+    pub fn table_owner_backfill_progress(&self) -> Result<Option<BackfillProgress>> {
+        self.get_backfill_progress(MetadataKey::TableOwnerBackfillProgress)
+    }
+
+    fn get_backfill_progress(&self, key: MetadataKey) -> Result<Option<BackfillProgress>> {
+        Ok(self
+            .db
+            .get::<IndexerMetadataSchema>(&key)?
+            .map(|v| v.expect_backfill_progress()))
+    }
+
+    /// Backfills the account resource tags of an indexer created before they were indexed,
+    /// from the state keys of the snapshot at `progress.snapshot_version` and the write sets of
+    /// the following versions (up to `progress.end_version`, the versions after it being indexed
+    /// as they are committed), resuming at `progress`. The progress is persisted along with
+    /// every batch of backfilled entries. Afterwards, the account resource tags are indexed from
+    /// the snapshot version onwards.
+    ///
+    /// `state_keys` starts at `progress.next_state_key_hash`, and `write_sets` at
+    /// `progress.next_version`.
+    pub fn backfill_account_resource_tags(
+        &self,
+        mut progress: BackfillProgress,
+        mut state_keys: impl Iterator<Item = Result<StateKey>>,
+        mut write_sets: impl Iterator<Item = Result<WriteSet>>,
+    ) -> Result<()> {
+        const BATCH_SIZE: usize = 10000;
+
+        while progress.next_state_key_hash.is_some() {
+            let state_keys = state_keys
+                .by_ref()
+                .take(BATCH_SIZE)
+                .collect::<Result<Vec<_>>>()?;
+            let batch = SchemaBatch::new();
+            for state_key in &state_keys {
+                if let Some((address, struct_tag)) = resource_tag(state_key)? {
+                    batch.put::<AccountResourceTagSchema>(
+                        &(address, struct_tag, progress.snapshot_version),
+                        &true,
+                    )?;
+                }
+            }
+            progress.next_state_key_hash = if state_keys.len() == BATCH_SIZE {
+                state_keys.last().map(CryptoHash::hash)
+            } else {
+                None
+            };
+            self.write_backfill_batch(
+                MetadataKey::AccountResourceTagBackfillProgress,
+                &progress,
+                batch,
+            )?;
+        }
+
+        while progress.next_version < progress.end_version {
+            let write_sets = write_sets
+                .by_ref()
+                .take(BATCH_SIZE)
+                .collect::<Result<Vec<_>>>()?;
+            ensure!(
+                !write_sets.is_empty(),
+                "Account resource tags backfilled up to version {}, but the backfill ends at version {}",
+                progress.next_version,
+                progress.end_version,
+            );
+            let mut batch = SchemaBatch::new();
+            for write_set in &write_sets {
+                Self::index_account_resource_tags(progress.next_version, write_set, &mut batch)?;
+                progress.next_version += 1;
+            }
+            self.write_backfill_batch(
+                MetadataKey::AccountResourceTagBackfillProgress,
+                &progress,
+                batch,
+            )?;
+        }
+
+        let batch = SchemaBatch::new();
+        batch.delete::<IndexerMetadataSchema>(&MetadataKey::AccountResourceTagBackfillProgress)?;
+        batch.put::<IndexerMetadataSchema>(
+            &MetadataKey::AccountResourceTagStartVersion,
+            &MetadataValue::Version(progress.snapshot_version),
+        )?;
+        self.db.write_schemas(batch)?;
+        *self.account_resource_tag_start_version.write() = Some(progress.snapshot_version);
+
+        Ok(())
+    }
+
+    /// Backfills the owners of the tables indexed before their owners were recorded, from the
+    /// state values of the snapshot at `progress.snapshot_version` and the write sets of the
+    /// following versions (up to `progress.end_version`), resuming at `progress`, as
+    /// `backfill_account_resource_tags()` does. A table without an owner gets the resource or
+    /// table item holding it in the snapshot (or, if it was created after the snapshot, the one it
+    /// was first seen in).
+    pub fn backfill_table_owners(
+        &self,
+        annotator: &MoveValueAnnotator<StorageAdapter<DbStateView>>,
+        mut progress: BackfillProgress,
+        mut state_values: impl Iterator<Item = Result<(StateKey, StateValue)>>,
+        mut write_sets: impl Iterator<Item = Result<WriteSet>>,
+    ) -> Result<()> {
+        const BATCH_SIZE: usize = 10000;
+
+        while progress.next_state_key_hash.is_some() {
+            let state_values = state_values
+                .by_ref()
+                .take(BATCH_SIZE)
+                .collect::<Result<Vec<_>>>()?;
+            let mut table_info_parser = TableInfoParser::new_for_owner_backfill(self, annotator);
+            for (state_key, state_value) in &state_values {
+                table_info_parser.parse_state_value(state_key, state_value.bytes())?;
+            }
+            let mut batch = SchemaBatch::new();
+            table_info_parser.finish(&mut batch)?;
+            progress.next_state_key_hash = if state_values.len() == BATCH_SIZE {
+                state_values.last().map(|(state_key, _)| state_key.hash())
+            } else {
+                None
+            };
+            self.write_backfill_batch(MetadataKey::TableOwnerBackfillProgress, &progress, batch)?;
+        }
+
+        while progress.next_version < progress.end_version {
+            let write_sets = write_sets
+                .by_ref()
+                .take(BATCH_SIZE)
+                .collect::<Result<Vec<_>>>()?;
+            ensure!(
+                !write_sets.is_empty(),
+                "Table owners backfilled up to version {}, but the backfill ends at version {}",
+                progress.next_version,
+                progress.end_version,
+            );
+            let mut table_info_parser = TableInfoParser::new_for_owner_backfill(self, annotator);
+            for write_set in &write_sets {
+                for (state_key, write_op) in write_set.iter() {
+                    table_info_parser.parse_write_op(state_key, write_op)?;
+                }
+            }
+            let mut batch = SchemaBatch::new();
+            table_info_parser.finish(&mut batch)?;
+            progress.next_version += write_sets.len() as Version;
+            self.write_backfill_batch(MetadataKey::TableOwnerBackfillProgress, &progress, batch)?;
+        }
+
+        let batch = SchemaBatch::new();
+        batch.delete::<IndexerMetadataSchema>(&MetadataKey::TableOwnerBackfillProgress)?;
+        batch.put::<IndexerMetadataSchema>(
+            &MetadataKey::TableOwnerStartVersion,
+            &MetadataValue::Version(progress.snapshot_version),
+        )?;
+        self.db.write_schemas(batch)?;
+        *self.table_owner_start_version.write() = Some(progress.snapshot_version);
+
+        Ok(())
+    }
+
+    fn write_backfill_batch(
+        &self,
+        progress_key: MetadataKey,
+        progress: &BackfillProgress,
+        batch: SchemaBatch,
+    ) -> Result<()> {
+        batch.put::<IndexerMetadataSchema>(
+            &progress_key,
+            &MetadataValue::BackfillProgress(progress.clone()),
+        )?;
+        self.db.write_schemas(batch)
+    }
+
+    fn index_account_resource_tags(
+        version: Version,
+        write_set: &WriteSet,
+        batch: &mut SchemaBatch,
+    ) -> Result<()> {
+        for (state_key, write_op) in write_set.iter() {
+            // Modifications don't change the set of resources held by an account.
+            if write_op.is_modification() {
+                continue;
+            }
+            if let Some((address, struct_tag)) = resource_tag(state_key)? {
+                batch.put::<AccountResourceTagSchema>(
+                    &(address, struct_tag, version),
+                    &write_op.is_creation(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the account and type of the resource (or resource group) stored under the given
+/// state key (or None, if it doesn't hold a resource).
+fn resource_tag(state_key: &StateKey) -> Result<Option<(AccountAddress, StructTag)>> {
+    if let StateKeyInner::AccessPath(access_path) = state_key.inner() {
+        let path: Path = (&access_path.path).try_into()?;
+        if let Path::Resource(struct_tag) | Path::ResourceGroup(struct_tag) = path {
+            return Ok(Some((access_path.address, struct_tag)));
+        }
+    }
+    Ok(None)
 }
 
 struct TableInfoParser<'a> {
     indexer: &'a Indexer,
     annotator: &'a MoveValueAnnotator<'a, StorageAdapter<'a, DbStateView>>,
     result: HashMap<TableHandle, TableInfo>,
+    owners: HashMap<TableHandle, TableOwner>,
     pending_on: HashMap<TableHandle, Vec<&'a [u8]>>,
+    // Whether to also record the owners of already indexed tables without one (see
+    // `Indexer::backfill_table_owners()`)
+    backfilling_owners: bool,
 }
 
 impl<'a> TableInfoParser<'a> {
@@ -166,41 +498,72 @@ impl<'a> TableInfoParser<'a> {
             indexer,
             annotator,
             result: HashMap::new(),
+            owners: HashMap::new(),
             pending_on: HashMap::new(),
+            backfilling_owners: false,
+        }
+    }
+
+    pub fn new_for_owner_backfill(
+        indexer: &'a Indexer,
+        annotator: &'a MoveValueAnnotator<StorageAdapter<DbStateView>>,
+    ) -> Self {
+        Self {
+            backfilling_owners: true,
+            ..Self::new(indexer, annotator)
         }
     }
 
     pub fn parse_write_op(&mut self, state_key: &'a StateKey, write_op: &'a WriteOp) -> Result<()> {
         if let Some(bytes) = write_op.bytes() {
-            match state_key.inner() {
-                StateKeyInner::AccessPath(access_path) => {
-                    let path: Path = (&access_path.path).try_into()?;
-                    match path {
-                        Path::Code(_) => (),
-                        Path::Resource(struct_tag) => self.parse_struct(struct_tag, bytes)?,
-                        Path::ResourceGroup(_struct_tag) => self.parse_resource_group(bytes)?,
-                    }
-                },
-                StateKeyInner::TableItem { handle, .. } => self.parse_table_item(*handle, bytes)?,
-                StateKeyInner::Raw(_) => (),
-            }
+            self.parse_state_value(state_key, bytes)?;
+        }
+        Ok(())
+    }
+
+    pub fn parse_state_value(&mut self, state_key: &'a StateKey, bytes: &'a [u8]) -> Result<()> {
+        match state_key.inner() {
+            StateKeyInner::AccessPath(access_path) => {
+                let path: Path = (&access_path.path).try_into()?;
+                match path {
+                    Path::Code(_) => (),
+                    Path::Resource(struct_tag) => {
+                        self.parse_resource(access_path.address, struct_tag, bytes)?
+                    },
+                    Path::ResourceGroup(_struct_tag) => {
+                        self.parse_resource_group(access_path.address, bytes)?
+                    },
+                }
+            },
+            StateKeyInner::TableItem { handle, .. } => self.parse_table_item(*handle, bytes)?,
+            StateKeyInner::Raw(_) => (),
         }
         Ok(())
     }
 
-    fn parse_struct(&mut self, struct_tag: StructTag, bytes: &[u8]) -> Result<()> {
+    fn parse_resource(
+        &mut self,
+        address: AccountAddress,
+        struct_tag: StructTag,
+        bytes: &[u8],
+    ) -> Result<()> {
+        let move_value = self
+            .annotator
+            .view_value(&TypeTag::Struct(Box::new(struct_tag.clone())), bytes)?;
         self.parse_move_value(
-            &self
-                .annotator
-                .view_value(&TypeTag::Struct(Box::new(struct_tag)), bytes)?,
+            &TableOwner::Resource {
+                address,
+                struct_tag,
+            },
+            &move_value,
         )
     }
 
-    fn parse_resource_group(&mut self, bytes: &[u8]) -> Result<()> {
+    fn parse_resource_group(&mut self, address: AccountAddress, bytes: &[u8]) -> Result<()> {
         type ResourceGroup = BTreeMap<StructTag, Vec<u8>>;
 
         for (struct_tag, bytes) in bcs::from_bytes::<ResourceGroup>(bytes)? {
-            self.parse_struct(struct_tag, &bytes)?;
+            self.parse_resource(address, struct_tag, &bytes)?;
         }
         Ok(())
     }
@@ -208,7 +571,10 @@ impl<'a> TableInfoParser<'a> {
     fn parse_table_item(&mut self, handle: TableHandle, bytes: &'a [u8]) -> Result<()> {
         match self.get_table_info(handle)? {
             Some(table_info) => {
-                self.parse_move_value(&self.annotator.view_value(&table_info.value_type, bytes)?)?;
+                self.parse_move_value(
+                    &TableOwner::TableItem { handle },
+                    &self.annotator.view_value(&table_info.value_type, bytes)?,
+                )?;
             },
             None => {
                 self.pending_on
@@ -220,11 +586,15 @@ impl<'a> TableInfoParser<'a> {
         Ok(())
     }
 
-    fn parse_move_value(&mut self, move_value: &AnnotatedMoveValue) -> Result<()> {
+    fn parse_move_value(
+        &mut self,
+        owner: &TableOwner,
+        move_value: &AnnotatedMoveValue,
+    ) -> Result<()> {
         match move_value {
             AnnotatedMoveValue::Vector(_type_tag, items) => {
                 for item in items {
-                    self.parse_move_value(item)?;
+                    self.parse_move_value(owner, item)?;
                 }
             },
             AnnotatedMoveValue::Struct(struct_value) => {
@@ -242,10 +612,10 @@ impl<'a> TableInfoParser<'a> {
                         },
                         _ => bail!("Table struct malformed. {:?}", struct_value),
                     };
-                    self.save_table_info(table_handle, table_info)?;
+                    self.save_table_info(table_handle, table_info, owner)?;
                 } else {
                     for (_identifier, field) in &struct_value.value {
-                        self.parse_move_value(field)?;
+                        self.parse_move_value(owner, field)?;
                     }
                 }
             },
@@ -264,14 +634,26 @@ impl<'a> TableInfoParser<'a> {
         Ok(())
     }
 
-    fn save_table_info(&mut self, handle: TableHandle, info: TableInfo) -> Result<()> {
+    fn save_table_info(
+        &mut self,
+        handle: TableHandle,
+        info: TableInfo,
+        owner: &TableOwner,
+    ) -> Result<()> {
         if self.get_table_info(handle)?.is_none() {
+            // Tables can be moved around, only the owner the table is first seen in is recorded.
+            self.owners.insert(handle, owner.clone());
             self.result.insert(handle, info);
             if let Some(pending_items) = self.pending_on.remove(&handle) {
                 for bytes in pending_items {
                     self.parse_table_item(handle, bytes)?;
                 }
             }
+        } else if self.backfilling_owners
+            && !self.owners.contains_key(&handle)
+            && self.indexer.get_table_owner(handle)?.is_none()
+        {
+            self.owners.insert(handle, owner.clone());
         }
         Ok(())
     }
@@ -296,7 +678,7 @@ impl<'a> TableInfoParser<'a> {
             self.pending_on.keys(),
         );
 
-        if self.result.is_empty() {
+        if self.result.is_empty() && self.owners.is_empty() {
             Ok(false)
         } else {
             self.result
@@ -304,6 +686,11 @@ impl<'a> TableInfoParser<'a> {
                 .try_for_each(|(table_handle, table_info)| {
                     batch.put::<TableInfoSchema>(&table_handle, &table_info)
                 })?;
+            self.owners
+                .into_iter()
+                .try_for_each(|(table_handle, table_owner)| {
+                    batch.put::<TableOwnerSchema>(&table_handle, &table_owner)
+                })?;
             Ok(true)
         }
     }
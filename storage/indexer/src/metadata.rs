@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use aptos_crypto::HashValue;
 use aptos_types::transaction::Version;
 use serde::{Deserialize, Serialize};
 
@@ -8,12 +9,49 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
 pub(crate) enum MetadataValue {
     Version(Version),
+    BackfillProgress(BackfillProgress),
 }
 
 impl MetadataValue {
     pub fn expect_version(self) -> Version {
         match self {
             Self::Version(v) => v,
+            _ => panic!("Expected a version, got {:?}", self),
+        }
+    }
+
+    pub fn expect_backfill_progress(self) -> BackfillProgress {
+        match self {
+            Self::BackfillProgress(progress) => progress,
+            _ => panic!("Expected a backfill progress, got {:?}", self),
+        }
+    }
+}
+
+/// The progress of a backfill of the indexer, persisted along with the backfilled entries, so a
+/// backfill interrupted by a restart resumes where it stopped.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub struct BackfillProgress {
+    /// The version of the state snapshot the backfill starts from.
+    pub snapshot_version: Version,
+    /// The next version the indexer was to index when the backfill started. The later versions
+    /// are indexed as they are committed, so the backfill stops there.
+    pub end_version: Version,
+    /// The hash of the state key of the snapshot to resume from (the state keys before it are
+    /// backfilled), or None once all the state keys of the snapshot are.
+    pub next_state_key_hash: Option<HashValue>,
+    /// The version of the next write set to backfill from.
+    pub next_version: Version,
+}
+
+impl BackfillProgress {
+    pub fn new(snapshot_version: Version, end_version: Version) -> Self {
+        Self {
+            snapshot_version,
+            end_version,
+            next_state_key_hash: Some(HashValue::zero()),
+            next_version: snapshot_version + 1,
         }
     }
 }
@@ -22,4 +60,8 @@ impl MetadataValue {
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
 pub(crate) enum MetadataKey {
     LatestVersion,
+    AccountResourceTagStartVersion,
+    TableOwnerStartVersion,
+    AccountResourceTagBackfillProgress,
+    TableOwnerBackfillProgress,
 }
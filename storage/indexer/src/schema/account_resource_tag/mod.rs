@@ -0,0 +1,78 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema recording when a resource (or resource group) type
+//! is created under or deleted from an account, so that the set of resource types held by an
+//! account at any version can be enumerated without scanning the account's state.
+//!
+//! The version is stored inverted so that the latest change of a given type at or before a
+//! version is the first one encountered while iterating forward.
+//!
+//! ```text
+//! |<-------------key------------->|<-value->|
+//! | address | struct_tag | !version | exists |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, ensure_slice_len_gt, ACCOUNT_RESOURCE_TAG_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, SeekKeyCodec, ValueCodec},
+};
+use aptos_types::{account_address::AccountAddress, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use move_core_types::language_storage::StructTag;
+use std::{convert::TryFrom, mem::size_of};
+
+type Key = (AccountAddress, StructTag, Version);
+
+define_schema!(
+    AccountResourceTagSchema,
+    Key,
+    bool,
+    ACCOUNT_RESOURCE_TAG_CF_NAME
+);
+
+impl KeyCodec<AccountResourceTagSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref address, ref struct_tag, version) = *self;
+
+        let mut encoded = address.to_vec();
+        encoded.extend(bcs::to_bytes(struct_tag)?);
+        encoded.write_u64::<BigEndian>(!version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const VERSION_SIZE: usize = size_of::<Version>();
+
+        ensure_slice_len_gt(data, AccountAddress::LENGTH + VERSION_SIZE)?;
+        let struct_tag_end = data.len() - VERSION_SIZE;
+        let address = AccountAddress::try_from(&data[..AccountAddress::LENGTH])?;
+        let struct_tag = bcs::from_bytes(&data[AccountAddress::LENGTH..struct_tag_end])?;
+        let version = !(&data[struct_tag_end..]).read_u64::<BigEndian>()?;
+
+        Ok((address, struct_tag, version))
+    }
+}
+
+impl ValueCodec<AccountResourceTagSchema> for bool {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(vec![u8::from(*self)])
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 1)?;
+        Ok(data[0] != 0)
+    }
+}
+
+impl SeekKeyCodec<AccountResourceTagSchema> for AccountAddress {
+    fn encode_seek_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod test;
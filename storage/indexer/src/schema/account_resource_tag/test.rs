@@ -0,0 +1,29 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use move_core_types::{identifier::Identifier, language_storage::TypeTag};
+use proptest::{collection::vec, prelude::*};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        address in any::<AccountAddress>(),
+        module in any::<Identifier>(),
+        name in any::<Identifier>(),
+        type_params in vec(any::<TypeTag>(), 0..3),
+        version in any::<Version>(),
+        exists in any::<bool>(),
+    ) {
+        let struct_tag = StructTag {
+            address,
+            module,
+            name,
+            type_params,
+        };
+        assert_encode_decode::<AccountResourceTagSchema>(&(address, struct_tag, version), &exists);
+    }
+}
+
+test_no_panic_decoding!(AccountResourceTagSchema);
@@ -6,19 +6,46 @@
 //!
 //! All schemas are `pub(crate)` so not shown in rustdoc, refer to the source code to see details.
 
+pub(crate) mod account_resource_tag;
 pub(crate) mod indexer_metadata;
 pub(crate) mod table_info;
+pub(crate) mod table_owner;
 
+use anyhow::{ensure, Result};
 use aptos_schemadb::ColumnFamilyName;
 
 pub const DEFAULT_COLUMN_FAMILY_NAME: ColumnFamilyName = "default";
+pub const ACCOUNT_RESOURCE_TAG_CF_NAME: ColumnFamilyName = "account_resource_tag";
 pub const INDEXER_METADATA_CF_NAME: ColumnFamilyName = "indexer_metadata";
 pub const TABLE_INFO_CF_NAME: ColumnFamilyName = "table_info";
+pub const TABLE_OWNER_CF_NAME: ColumnFamilyName = "table_owner";
 
 pub fn column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         INDEXER_METADATA_CF_NAME,
         TABLE_INFO_CF_NAME,
+        ACCOUNT_RESOURCE_TAG_CF_NAME,
+        TABLE_OWNER_CF_NAME,
     ]
 }
+
+fn ensure_slice_len_eq(data: &[u8], len: usize) -> Result<()> {
+    ensure!(
+        data.len() == len,
+        "Unexpected data len {}, expected {}.",
+        data.len(),
+        len,
+    );
+    Ok(())
+}
+
+fn ensure_slice_len_gt(data: &[u8], len: usize) -> Result<()> {
+    ensure!(
+        data.len() > len,
+        "Unexpected data len {}, expected to be greater than {}.",
+        data.len(),
+        len,
+    );
+    Ok(())
+}
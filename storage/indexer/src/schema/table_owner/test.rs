@@ -0,0 +1,42 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use aptos_types::account_address::AccountAddress;
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use proptest::{collection::vec, prelude::*};
+
+proptest! {
+    #[test]
+    fn test_encode_decode_resource_owner(
+        table_handle in any::<TableHandle>(),
+        address in any::<AccountAddress>(),
+        module in any::<Identifier>(),
+        name in any::<Identifier>(),
+        type_params in vec(any::<TypeTag>(), 0..3),
+    ) {
+        let struct_tag = StructTag {
+            address,
+            module,
+            name,
+            type_params,
+        };
+        let owner = TableOwner::Resource { address, struct_tag };
+        assert_encode_decode::<TableOwnerSchema>(&table_handle, &owner);
+    }
+
+    #[test]
+    fn test_encode_decode_table_item_owner(
+        table_handle in any::<TableHandle>(),
+        parent_handle in any::<TableHandle>(),
+    ) {
+        let owner = TableOwner::TableItem { handle: parent_handle };
+        assert_encode_decode::<TableOwnerSchema>(&table_handle, &owner);
+    }
+}
+
+test_no_panic_decoding!(TableOwnerSchema);
@@ -0,0 +1,357 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_temppath::TempPath;
+use aptos_types::{access_path::AccessPath, write_set::WriteSetMut};
+use move_core_types::identifier::Identifier;
+
+/// Serves the modules of the framework, which the annotator needs to find tables in values.
+struct FrameworkDbReader {
+    modules: HashMap<StateKey, StateValue>,
+}
+
+impl FrameworkDbReader {
+    fn new() -> Self {
+        let modules = aptos_cached_packages::head_release_bundle()
+            .code_and_compiled_modules()
+            .into_iter()
+            .map(|(code, module)| {
+                let access_path = AccessPath::code_access_path(module.self_id());
+                (StateKey::access_path(access_path), code.to_vec().into())
+            })
+            .collect();
+        Self { modules }
+    }
+}
+
+impl DbReader for FrameworkDbReader {
+    fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        _version: Version,
+    ) -> Result<Option<StateValue>> {
+        Ok(self.modules.get(state_key).cloned())
+    }
+}
+
+fn struct_tag(name: &str) -> StructTag {
+    StructTag {
+        address: AccountAddress::ONE,
+        module: Identifier::new("test").unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn resource_state_key(address: AccountAddress, name: &str) -> StateKey {
+    StateKey::access_path(AccessPath::resource_access_path(address, struct_tag(name)).unwrap())
+}
+
+fn table_tag(key_type: TypeTag, value_type: TypeTag) -> StructTag {
+    StructTag {
+        address: AccountAddress::ONE,
+        module: Identifier::new("table").unwrap(),
+        name: Identifier::new("Table").unwrap(),
+        type_params: vec![key_type, value_type],
+    }
+}
+
+/// A table of tables, held as a resource.
+fn table_of_tables_tag() -> StructTag {
+    let inner_table_tag = table_tag(TypeTag::U64, TypeTag::U64);
+    table_tag(TypeTag::U64, TypeTag::Struct(Box::new(inner_table_tag)))
+}
+
+fn table_of_tables_state_key(address: AccountAddress) -> StateKey {
+    StateKey::access_path(AccessPath::resource_access_path(address, table_of_tables_tag()).unwrap())
+}
+
+#[test]
+fn test_backfill_account_resource_tags() {
+    let tmp_dir = TempPath::new();
+    tmp_dir.create_as_dir().unwrap();
+    let address = AccountAddress::random();
+
+    // Create an indexer that has indexed versions 0 and 1, but not the account resource tags
+    let db = DB::open(
+        tmp_dir.path().join(INDEX_DB_NAME),
+        "index_db",
+        column_families(),
+        &gen_rocksdb_options(&RocksdbConfig::default(), false),
+    )
+    .unwrap();
+    db.put::<IndexerMetadataSchema>(&MetadataKey::LatestVersion, &MetadataValue::Version(2))
+        .unwrap();
+    drop(db);
+    let indexer = Indexer::open(tmp_dir.path(), RocksdbConfig::default()).unwrap();
+    assert_eq!(indexer.account_resource_tag_start_version(), None);
+    assert!(!indexer.is_backfilled());
+    assert_eq!(indexer.get_account_resource_tags(address, 1).unwrap(), None);
+
+    // Backfill the tags from the snapshot at version 0 (holding resources A and B), and
+    // version 1 (deleting A and creating C)
+    let state_keys = vec![
+        resource_state_key(address, "A"),
+        resource_state_key(address, "B"),
+        resource_state_key(AccountAddress::random(), "D"),
+    ];
+    let write_set = WriteSetMut::new(vec![
+        (resource_state_key(address, "A"), WriteOp::Deletion),
+        (resource_state_key(address, "C"), WriteOp::Creation(vec![])),
+    ])
+    .freeze()
+    .unwrap();
+    indexer
+        .backfill_account_resource_tags(
+            BackfillProgress::new(0, 2),
+            state_keys.into_iter().map(Ok),
+            vec![Ok(write_set)].into_iter(),
+        )
+        .unwrap();
+
+    // Verify the tags are indexed from the snapshot onwards (also after a restart)
+    drop(indexer);
+    let indexer = Indexer::open(tmp_dir.path(), RocksdbConfig::default()).unwrap();
+    assert_eq!(indexer.account_resource_tag_start_version(), Some(0));
+    assert_eq!(
+        indexer.account_resource_tag_backfill_progress().unwrap(),
+        None
+    );
+    assert_eq!(
+        indexer.get_account_resource_tags(address, 0).unwrap(),
+        Some(vec![struct_tag("A"), struct_tag("B")])
+    );
+    assert_eq!(
+        indexer.get_account_resource_tags(address, 1).unwrap(),
+        Some(vec![struct_tag("B"), struct_tag("C")])
+    );
+}
+
+#[test]
+fn test_resume_account_resource_tag_backfill() {
+    let tmp_dir = TempPath::new();
+    tmp_dir.create_as_dir().unwrap();
+    let address = AccountAddress::random();
+
+    // Create an indexer that has indexed versions 0 and 1, but not the account resource tags
+    let db = DB::open(
+        tmp_dir.path().join(INDEX_DB_NAME),
+        "index_db",
+        column_families(),
+        &gen_rocksdb_options(&RocksdbConfig::default(), false),
+    )
+    .unwrap();
+    db.put::<IndexerMetadataSchema>(&MetadataKey::LatestVersion, &MetadataValue::Version(2))
+        .unwrap();
+    drop(db);
+    let indexer = Indexer::open(tmp_dir.path(), RocksdbConfig::default()).unwrap();
+
+    // The backfill is interrupted after the snapshot at version 0, before the write sets
+    let state_keys = vec![resource_state_key(address, "A")];
+    indexer
+        .backfill_account_resource_tags(
+            BackfillProgress::new(0, 2),
+            state_keys.into_iter().map(Ok),
+            vec![Err(anyhow::anyhow!("Interrupted"))].into_iter(),
+        )
+        .unwrap_err();
+
+    // After a restart, the backfill resumes at the write set of version 1
+    drop(indexer);
+    let indexer = Indexer::open(tmp_dir.path(), RocksdbConfig::default()).unwrap();
+    assert_eq!(indexer.account_resource_tag_start_version(), None);
+    let progress = indexer
+        .account_resource_tag_backfill_progress()
+        .unwrap()
+        .unwrap();
+    assert_eq!(progress, BackfillProgress {
+        snapshot_version: 0,
+        end_version: 2,
+        next_state_key_hash: None,
+        next_version: 1,
+    });
+    let write_set = WriteSetMut::new(vec![(
+        resource_state_key(address, "B"),
+        WriteOp::Creation(vec![]),
+    )])
+    .freeze()
+    .unwrap();
+    indexer
+        .backfill_account_resource_tags(
+            progress,
+            std::iter::empty(),
+            vec![Ok(write_set)].into_iter(),
+        )
+        .unwrap();
+    assert_eq!(indexer.account_resource_tag_start_version(), Some(0));
+    assert_eq!(
+        indexer.get_account_resource_tags(address, 1).unwrap(),
+        Some(vec![struct_tag("A"), struct_tag("B")])
+    );
+}
+
+#[test]
+fn test_new_indexer_indexes_account_resource_tags_from_genesis() {
+    let tmp_dir = TempPath::new();
+    tmp_dir.create_as_dir().unwrap();
+    let indexer = Indexer::open(tmp_dir.path(), RocksdbConfig::default()).unwrap();
+    assert_eq!(indexer.account_resource_tag_start_version(), Some(0));
+    assert_eq!(indexer.table_owner_start_version(), Some(0));
+    assert!(indexer.is_backfilled());
+}
+
+#[test]
+fn test_index_table_owners() {
+    let tmp_dir = TempPath::new();
+    tmp_dir.create_as_dir().unwrap();
+    let indexer = Indexer::open(tmp_dir.path(), RocksdbConfig::default()).unwrap();
+    let state_view = DbStateView {
+        db: Arc::new(FrameworkDbReader::new()),
+        version: Some(0),
+    };
+    let resolver = state_view.as_move_resolver();
+    let annotator = MoveValueAnnotator::new(&resolver);
+    let address = AccountAddress::random();
+    let outer_handle = TableHandle(AccountAddress::random());
+    let inner_handle = TableHandle(AccountAddress::random());
+
+    // Version 0 creates a resource holding the outer table, and an item of the outer table
+    // holding the inner table
+    let write_set = WriteSetMut::new(vec![
+        (
+            table_of_tables_state_key(address),
+            WriteOp::Creation(bcs::to_bytes(&outer_handle).unwrap()),
+        ),
+        (
+            StateKey::table_item(outer_handle, bcs::to_bytes(&1u64).unwrap()),
+            WriteOp::Creation(bcs::to_bytes(&inner_handle).unwrap()),
+        ),
+    ])
+    .freeze()
+    .unwrap();
+    indexer
+        .index_with_annotator(&annotator, 0, &[&write_set])
+        .unwrap();
+    assert_eq!(
+        indexer.get_table_info(inner_handle).unwrap(),
+        Some(TableInfo {
+            key_type: TypeTag::U64,
+            value_type: TypeTag::U64,
+        })
+    );
+    assert_eq!(
+        indexer.get_table_owner(outer_handle).unwrap(),
+        Some(TableOwner::Resource {
+            address,
+            struct_tag: table_of_tables_tag(),
+        })
+    );
+    assert_eq!(
+        indexer.get_table_owner(inner_handle).unwrap(),
+        Some(TableOwner::TableItem {
+            handle: outer_handle
+        })
+    );
+
+    // Version 1 moves the outer table to another account, which keeps its first owner
+    let write_set = WriteSetMut::new(vec![
+        (table_of_tables_state_key(address), WriteOp::Deletion),
+        (
+            table_of_tables_state_key(AccountAddress::random()),
+            WriteOp::Creation(bcs::to_bytes(&outer_handle).unwrap()),
+        ),
+    ])
+    .freeze()
+    .unwrap();
+    indexer
+        .index_with_annotator(&annotator, 1, &[&write_set])
+        .unwrap();
+    assert_eq!(
+        indexer.get_table_owner(outer_handle).unwrap(),
+        Some(TableOwner::Resource {
+            address,
+            struct_tag: table_of_tables_tag(),
+        })
+    );
+}
+
+#[test]
+fn test_backfill_table_owners() {
+    let tmp_dir = TempPath::new();
+    tmp_dir.create_as_dir().unwrap();
+    let address = AccountAddress::random();
+    let outer_handle = TableHandle(AccountAddress::random());
+    let inner_handle = TableHandle(AccountAddress::random());
+
+    // Create an indexer that has indexed both tables at versions 0 and 1, but not their owners
+    let db = DB::open(
+        tmp_dir.path().join(INDEX_DB_NAME),
+        "index_db",
+        column_families(),
+        &gen_rocksdb_options(&RocksdbConfig::default(), false),
+    )
+    .unwrap();
+    db.put::<IndexerMetadataSchema>(&MetadataKey::LatestVersion, &MetadataValue::Version(2))
+        .unwrap();
+    db.put::<TableInfoSchema>(&outer_handle, &TableInfo {
+        key_type: TypeTag::U64,
+        value_type: TypeTag::Struct(Box::new(table_tag(TypeTag::U64, TypeTag::U64))),
+    })
+    .unwrap();
+    db.put::<TableInfoSchema>(&inner_handle, &TableInfo {
+        key_type: TypeTag::U64,
+        value_type: TypeTag::U64,
+    })
+    .unwrap();
+    drop(db);
+    let indexer = Indexer::open(tmp_dir.path(), RocksdbConfig::default()).unwrap();
+    assert_eq!(indexer.table_owner_start_version(), None);
+    assert_eq!(indexer.get_table_owner(outer_handle).unwrap(), None);
+
+    // Backfill the owners from the snapshot at version 0 (holding the outer table in a
+    // resource), and version 1 (adding the inner table to the outer one)
+    let state_view = DbStateView {
+        db: Arc::new(FrameworkDbReader::new()),
+        version: Some(1),
+    };
+    let resolver = state_view.as_move_resolver();
+    let annotator = MoveValueAnnotator::new(&resolver);
+    let state_values = vec![(
+        table_of_tables_state_key(address),
+        StateValue::from(bcs::to_bytes(&outer_handle).unwrap()),
+    )];
+    let write_set = WriteSetMut::new(vec![(
+        StateKey::table_item(outer_handle, bcs::to_bytes(&1u64).unwrap()),
+        WriteOp::Creation(bcs::to_bytes(&inner_handle).unwrap()),
+    )])
+    .freeze()
+    .unwrap();
+    indexer
+        .backfill_table_owners(
+            &annotator,
+            BackfillProgress::new(0, 2),
+            state_values.into_iter().map(Ok),
+            vec![Ok(write_set)].into_iter(),
+        )
+        .unwrap();
+
+    // Verify the owners are known (also after a restart)
+    drop(indexer);
+    let indexer = Indexer::open(tmp_dir.path(), RocksdbConfig::default()).unwrap();
+    assert_eq!(indexer.table_owner_start_version(), Some(0));
+    assert_eq!(indexer.table_owner_backfill_progress().unwrap(), None);
+    assert_eq!(
+        indexer.get_table_owner(outer_handle).unwrap(),
+        Some(TableOwner::Resource {
+            address,
+            struct_tag: table_of_tables_tag(),
+        })
+    );
+    assert_eq!(
+        indexer.get_table_owner(inner_handle).unwrap(),
+        Some(TableOwner::TableItem {
+            handle: outer_handle
+        })
+    );
+}
@@ -2,11 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    KeyCodec, Schema, SeekKeyCodec, ValueCodec, APTOS_SCHEMADB_ITER_BYTES,
+    KeyCodec, Schema, SeekKeyCodec, ValueCodec, APTOS_SCHEMADB_IO_BYTES, APTOS_SCHEMADB_ITER_BYTES,
     APTOS_SCHEMADB_ITER_LATENCY_SECONDS, APTOS_SCHEMADB_SEEK_LATENCY_SECONDS,
 };
 use anyhow::Result;
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::atomic::Ordering};
 
 pub enum ScanDirection {
     Forward,
@@ -94,6 +94,8 @@ where
         APTOS_SCHEMADB_ITER_BYTES
             .with_label_values(&[S::COLUMN_FAMILY_NAME])
             .observe((raw_key.len() + raw_value.len()) as f64);
+        APTOS_SCHEMADB_IO_BYTES
+            .fetch_add((raw_key.len() + raw_value.len()) as u64, Ordering::Relaxed);
 
         let key = <S::Key as KeyCodec<S>>::decode_key(raw_key)?;
         let value = <S::Value as ValueCodec<S>>::decode_value(raw_value)?;
@@ -23,7 +23,7 @@ use crate::{
     metrics::{
         APTOS_SCHEMADB_BATCH_COMMIT_BYTES, APTOS_SCHEMADB_BATCH_COMMIT_LATENCY_SECONDS,
         APTOS_SCHEMADB_BATCH_PUT_LATENCY_SECONDS, APTOS_SCHEMADB_DELETES, APTOS_SCHEMADB_GET_BYTES,
-        APTOS_SCHEMADB_GET_LATENCY_SECONDS, APTOS_SCHEMADB_ITER_BYTES,
+        APTOS_SCHEMADB_GET_LATENCY_SECONDS, APTOS_SCHEMADB_IO_BYTES, APTOS_SCHEMADB_ITER_BYTES,
         APTOS_SCHEMADB_ITER_LATENCY_SECONDS, APTOS_SCHEMADB_PUT_BYTES,
         APTOS_SCHEMADB_SEEK_LATENCY_SECONDS,
     },
@@ -38,10 +38,16 @@ pub use rocksdb::{
     BlockBasedOptions, Cache, ColumnFamilyDescriptor, DBCompressionType, Options, ReadOptions,
     SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
 };
-use std::{collections::HashMap, iter::Iterator, path::Path};
+use std::{collections::HashMap, iter::Iterator, path::Path, sync::atomic::Ordering};
 
 pub type ColumnFamilyName = &'static str;
 
+/// Returns the total bytes read and written through the schemadb by this process
+/// (i.e., the logical IO, before RocksDB compression and caching)
+pub fn total_io_bytes() -> u64 {
+    APTOS_SCHEMADB_IO_BYTES.load(Ordering::Relaxed)
+}
+
 #[derive(Debug)]
 enum WriteOp {
     Value { key: Vec<u8>, value: Vec<u8> },
@@ -186,6 +192,10 @@ impl DB {
         APTOS_SCHEMADB_GET_BYTES
             .with_label_values(&[S::COLUMN_FAMILY_NAME])
             .observe(result.as_ref().map_or(0.0, |v| v.len() as f64));
+        APTOS_SCHEMADB_IO_BYTES.fetch_add(
+            result.as_ref().map_or(0, |v| v.len() as u64),
+            Ordering::Relaxed,
+        );
 
         result
             .map(|raw_value| <S::Value as ValueCodec<S>>::decode_value(&raw_value))
@@ -261,6 +271,7 @@ impl DB {
         APTOS_SCHEMADB_BATCH_COMMIT_BYTES
             .with_label_values(&[&self.name])
             .observe(serialized_size as f64);
+        APTOS_SCHEMADB_IO_BYTES.fetch_add(serialized_size as u64, Ordering::Relaxed);
 
         Ok(())
     }
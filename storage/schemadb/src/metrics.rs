@@ -7,6 +7,10 @@ use aptos_metrics_core::{
     IntCounterVec,
 };
 use once_cell::sync::Lazy;
+use std::sync::atomic::AtomicU64;
+
+/// The total bytes read and written through the schemadb (across all dbs)
+pub static APTOS_SCHEMADB_IO_BYTES: AtomicU64 = AtomicU64::new(0);
 
 pub static APTOS_SCHEMADB_SEEK_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
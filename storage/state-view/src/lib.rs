@@ -45,6 +45,14 @@ pub trait TStateView {
     /// Gets the state value for a given state key.
     fn get_state_value(&self, state_key: &Self::Key) -> Result<Option<StateValue>>;
 
+    /// Gets the state values for the given state keys, in the same order as the keys.
+    fn get_state_values(&self, state_keys: &[Self::Key]) -> Result<Vec<Option<StateValue>>> {
+        state_keys
+            .iter()
+            .map(|state_key| self.get_state_value(state_key))
+            .collect()
+    }
+
     /// Get state storage usage info at epoch ending.
     fn get_usage(&self) -> Result<StateStorageUsage>;
 
@@ -84,6 +92,10 @@ where
         self.deref().get_state_value(state_key)
     }
 
+    fn get_state_values(&self, state_keys: &[K]) -> Result<Vec<Option<StateValue>>> {
+        self.deref().get_state_values(state_keys)
+    }
+
     fn get_usage(&self) -> Result<StateStorageUsage> {
         self.deref().get_usage()
     }
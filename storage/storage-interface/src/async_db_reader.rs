@@ -0,0 +1,235 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    metrics::{ASYNC_DB_READER_PENDING_CALLS, ASYNC_DB_READER_TIMEOUTS, TIMER},
+    DbReader,
+};
+use anyhow::{anyhow, Result};
+use aptos_metrics_core::IntGauge;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{TransactionListWithProof, Version},
+};
+use futures::channel::oneshot;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Default number of threads dedicated to serving async reads.
+pub const DEFAULT_NUM_ASYNC_READER_THREADS: usize = 16;
+/// Default timeout for a single async read.
+pub const DEFAULT_ASYNC_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A wrapper around a [DbReader] that is safe to use from async contexts. Every call is
+/// offloaded to a dedicated thread pool (so that blocking DB reads never stall the async
+/// executor) and fails if it doesn't complete within the configured timeout.
+///
+/// Note: a call that times out keeps running on the pool until the DB returns, but the result
+/// is discarded.
+#[derive(Clone)]
+pub struct AsyncDbReader {
+    reader: Arc<dyn DbReader>,
+    pool: Arc<rayon::ThreadPool>,
+    call_timeout: Duration,
+    num_pending_calls: Arc<AtomicUsize>,
+}
+
+impl AsyncDbReader {
+    pub fn new(reader: Arc<dyn DbReader>, num_threads: usize, call_timeout: Duration) -> Self {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("async_db_reader_{}", index))
+            .build()
+            .expect("Failed to create the async db reader thread pool!");
+
+        Self {
+            reader,
+            pool: Arc::new(pool),
+            call_timeout,
+            num_pending_calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Returns the underlying (synchronous) reader
+    pub fn reader(&self) -> &Arc<dyn DbReader> {
+        &self.reader
+    }
+
+    /// Returns the number of calls that are queued or running on the pool
+    pub fn num_pending_calls(&self) -> usize {
+        self.num_pending_calls.load(Ordering::Relaxed)
+    }
+
+    /// Runs `read` against the underlying reader on the dedicated pool. `api_name` is
+    /// used to label the metrics.
+    pub async fn call<T, F>(&self, api_name: &'static str, read: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn DbReader) -> Result<T> + Send + 'static,
+    {
+        // The call is pending until the pool is done with it, even if the caller
+        // has stopped waiting for the result (e.g., because it timed out).
+        let pending_call = PendingCallGuard::new(api_name, self.num_pending_calls.clone());
+
+        let (result_sender, result_receiver) = oneshot::channel();
+        let reader = self.reader.clone();
+        self.pool.spawn(move || {
+            let result = {
+                let _pending_call = pending_call;
+                let _timer = TIMER.with_label_values(&[api_name]).start_timer();
+                read(reader.as_ref())
+            };
+            // The receiver is gone if the call has timed out, so there's nothing to do.
+            let _ = result_sender.send(result);
+        });
+
+        match tokio::time::timeout(self.call_timeout, result_receiver).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(anyhow!(
+                "The async db reader dropped the call: {}",
+                api_name
+            )),
+            Err(_) => {
+                ASYNC_DB_READER_TIMEOUTS
+                    .with_label_values(&[api_name])
+                    .inc();
+                Err(anyhow!(
+                    "The async db reader timed out after {:?}: {}",
+                    self.call_timeout,
+                    api_name
+                ))
+            },
+        }
+    }
+
+    pub async fn get_latest_ledger_info(&self) -> Result<LedgerInfoWithSignatures> {
+        self.call("async_get_latest_ledger_info", |reader| {
+            reader.get_latest_ledger_info()
+        })
+        .await
+    }
+
+    pub async fn get_block_timestamp(&self, version: Version) -> Result<u64> {
+        self.call("async_get_block_timestamp", move |reader| {
+            reader.get_block_timestamp(version)
+        })
+        .await
+    }
+
+    pub async fn get_latest_version(&self) -> Result<Version> {
+        self.call("async_get_latest_version", |reader| {
+            reader.get_latest_version()
+        })
+        .await
+    }
+
+    pub async fn get_transactions(
+        &self,
+        start_version: Version,
+        batch_size: u64,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<TransactionListWithProof> {
+        self.call("async_get_transactions", move |reader| {
+            reader.get_transactions(start_version, batch_size, ledger_version, fetch_events)
+        })
+        .await
+    }
+}
+
+/// Tracks a pending call (in the metrics and in the reader), for as long as the guard is alive
+struct PendingCallGuard {
+    pending_calls_gauge: IntGauge,
+    num_pending_calls: Arc<AtomicUsize>,
+}
+
+impl PendingCallGuard {
+    fn new(api_name: &'static str, num_pending_calls: Arc<AtomicUsize>) -> Self {
+        let pending_calls_gauge = ASYNC_DB_READER_PENDING_CALLS.with_label_values(&[api_name]);
+        pending_calls_gauge.inc();
+        num_pending_calls.fetch_add(1, Ordering::Relaxed);
+        Self {
+            pending_calls_gauge,
+            num_pending_calls,
+        }
+    }
+}
+
+impl Drop for PendingCallGuard {
+    fn drop(&mut self) {
+        self.pending_calls_gauge.dec();
+        self.num_pending_calls.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockDbReaderWriter;
+    use std::sync::{mpsc, Mutex};
+
+    /// A reader whose reads block until they're released
+    struct GatedDbReader {
+        release: Mutex<mpsc::Receiver<()>>,
+    }
+
+    impl GatedDbReader {
+        fn new() -> (Self, mpsc::Sender<()>) {
+            let (release_sender, release) = mpsc::channel();
+            let reader = Self {
+                release: Mutex::new(release),
+            };
+            (reader, release_sender)
+        }
+    }
+
+    impl DbReader for GatedDbReader {
+        fn get_latest_version(&self) -> Result<Version> {
+            self.release.lock().unwrap().recv()?;
+            Ok(10)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call() {
+        let reader = AsyncDbReader::new(Arc::new(MockDbReaderWriter), 2, Duration::from_secs(5));
+        let version = reader
+            .call("test_call", |reader| {
+                reader.get_latest_state_checkpoint_version()
+            })
+            .await
+            .unwrap();
+        assert_eq!(version, Some(1));
+        assert_eq!(reader.num_pending_calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_call_timeout() {
+        let (gated_reader, release_sender) = GatedDbReader::new();
+        let reader = AsyncDbReader::new(Arc::new(gated_reader), 1, Duration::from_millis(10));
+        assert!(reader.get_latest_version().await.is_err());
+
+        // The timed out call is pending until the pool is done with it
+        assert_eq!(reader.num_pending_calls(), 1);
+        release_sender.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while reader.num_pending_calls() > 0 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // A call that completes in time returns its result
+        let (gated_reader, release_sender) = GatedDbReader::new();
+        let reader = AsyncDbReader::new(Arc::new(gated_reader), 1, Duration::from_secs(10));
+        release_sender.send(()).unwrap();
+        assert_eq!(reader.get_latest_version().await.unwrap(), 10);
+        assert_eq!(reader.num_pending_calls(), 0);
+    }
+}
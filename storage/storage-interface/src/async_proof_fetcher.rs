@@ -78,6 +78,24 @@ impl AsyncProofFetcher {
         Ok(version_and_value_opt)
     }
 
+    /// Schedules the proof read of a state value that was obtained without reading the DB
+    /// (e.g., carried over from the parent block), so that the proof is still available if the
+    /// key gets updated.
+    pub fn schedule_proof_read_for_value(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+        root_hash: Option<HashValue>,
+        state_value: Option<&StateValue>,
+    ) {
+        self.schedule_proof_read(
+            state_key.clone(),
+            version,
+            root_hash,
+            state_value.map(|value| value.hash()),
+        );
+    }
+
     pub fn get_proof_cache(&self) -> HashMap<HashValue, SparseMerkleProofExt> {
         self.wait()
     }
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    async_proof_fetcher::AsyncProofFetcher, metrics::TIMER, state_view::DbStateView, DbReader,
+    async_proof_fetcher::AsyncProofFetcher,
+    metrics::{TIMER, WARM_STATE_CACHE_LOOKUPS},
+    state_view::DbStateView,
+    DbReader,
 };
 use anyhow::Result;
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -12,6 +15,7 @@ use aptos_types::{
     proof::SparseMerkleProofExt,
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        ShardedStateUpdates,
     },
     transaction::Version,
     write_set::WriteSet,
@@ -41,6 +45,56 @@ static IO_POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
 // we don't know the maximum version).
 pub type ShardedStateCache = [DashMap<StateKey, (Option<Version>, Option<StateValue>)>; 16];
 
+/// The state values read while executing a block that are still valid on top of the block's
+/// result state, i.e., the values of the keys the block didn't write. The child block consults
+/// it instead of reading the persisted state from the DB, so that the hot state (e.g., the 0x1
+/// configs) isn't read cold again by every block. The proofs are not carried over, so the child
+/// still schedules a proof read for every key it finds in the cache. The keys the child reads from it are cached
+/// by the child in turn, so the hot state is carried from block to block.
+#[derive(Default)]
+pub struct WarmStateCache {
+    sharded_state_cache: ShardedStateCache,
+}
+
+impl WarmStateCache {
+    /// Creates the warm cache of a block from the state cache of its parent, invalidating
+    /// the keys written by the parent (i.e., in `parent_updates`)
+    pub fn new(parent_cache: &ShardedStateCache, parent_updates: &ShardedStateUpdates) -> Self {
+        let sharded_state_cache = arr![DashMap::new(); 16];
+        parent_cache
+            .iter()
+            .zip(parent_updates.iter())
+            .zip(sharded_state_cache.iter())
+            .for_each(|((parent_shard, updates_shard), shard)| {
+                for entry in parent_shard.iter() {
+                    if !updates_shard.contains_key(entry.key()) {
+                        shard.insert(entry.key().clone(), entry.value().clone());
+                    }
+                }
+            });
+        Self {
+            sharded_state_cache,
+        }
+    }
+
+    pub fn get(&self, state_key: &StateKey) -> Option<(Option<Version>, Option<StateValue>)> {
+        self.sharded_state_cache[state_key.get_shard_id() as usize]
+            .get(state_key)
+            .map(|entry| entry.value().clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.sharded_state_cache
+            .iter()
+            .map(|shard| shard.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// `CachedStateView` is like a snapshot of the global state comprised of state view at two
 /// levels, persistent storage and memory.
 pub struct CachedStateView {
@@ -96,6 +150,10 @@ pub struct CachedStateView {
     /// in JMT node.
     sharded_state_cache: ShardedStateCache,
     proof_fetcher: Arc<AsyncProofFetcher>,
+
+    /// The values read by the parent block that are still valid, consulted instead of the DB
+    /// (see `WarmStateCache`).
+    warm_cache: Option<Arc<WarmStateCache>>,
 }
 
 impl Debug for CachedStateView {
@@ -127,25 +185,41 @@ impl CachedStateView {
             speculative_state,
             sharded_state_cache: arr![DashMap::new(); 16],
             proof_fetcher,
+            warm_cache: None,
         })
     }
 
+    /// Consults the given warm cache before reading from the DB. The proofs of the keys found
+    /// in it are still read, since the block may update them.
+    /// The cache must hold values that are valid on top of the state of this view.
+    pub fn with_warm_cache(mut self, warm_cache: Arc<WarmStateCache>) -> Self {
+        self.warm_cache = Some(warm_cache);
+        self
+    }
+
     pub fn prime_cache_by_write_set<'a, T: IntoIterator<Item = &'a WriteSet> + Send>(
         &self,
         write_sets: T,
     ) -> Result<()> {
-        IO_POOL.scope(|s| {
+        self.prime_cache_by_state_keys(
             write_sets
                 .into_iter()
                 .flat_map(|write_set| write_set.iter())
                 .map(|(key, _)| key)
-                .collect::<HashSet<_>>()
-                .into_iter()
-                .for_each(|key| {
-                    s.spawn(move |_| {
-                        self.get_state_value_bytes(key).expect("Must succeed.");
-                    })
-                });
+                .collect::<HashSet<_>>(),
+        )
+    }
+
+    pub fn prime_cache_by_state_keys<'a, T: IntoIterator<Item = &'a StateKey>>(
+        &self,
+        state_keys: T,
+    ) -> Result<()> {
+        IO_POOL.scope(|s| {
+            state_keys.into_iter().for_each(|key| {
+                s.spawn(move |_| {
+                    self.get_state_value_bytes(key).expect("Must succeed.");
+                })
+            });
         });
         Ok(())
     }
@@ -158,6 +232,21 @@ impl CachedStateView {
         }
     }
 
+    fn get_from_warm_cache(
+        &self,
+        state_key: &StateKey,
+    ) -> Option<(Option<Version>, Option<StateValue>)> {
+        let warm_cache = self.warm_cache.as_ref()?;
+        let version_and_value_opt = warm_cache.get(state_key);
+        let result = if version_and_value_opt.is_some() {
+            "hit"
+        } else {
+            "miss"
+        };
+        WARM_STATE_CACHE_LOOKUPS.with_label_values(&[result]).inc();
+        version_and_value_opt
+    }
+
     fn get_version_and_state_value_internal(
         &self,
         state_key: &StateKey,
@@ -171,6 +260,17 @@ impl CachedStateView {
             // former case, we don't have the blob data but only its hash.
             StateStoreStatus::ExistsInDB | StateStoreStatus::Unknown => match self.snapshot {
                 Some((version, root_hash)) => {
+                    if let Some(version_and_value_opt) = self.get_from_warm_cache(state_key) {
+                        // The value doesn't need to be read, but the proof is still needed in
+                        // case the key is updated by this block.
+                        self.proof_fetcher.schedule_proof_read_for_value(
+                            state_key,
+                            version,
+                            Some(root_hash),
+                            version_and_value_opt.1.as_ref(),
+                        );
+                        return Ok(version_and_value_opt);
+                    }
                     let version_and_value_opt = self
                         .proof_fetcher
                         .fetch_state_value_with_version_and_schedule_proof_read(
@@ -17,8 +17,9 @@ use aptos_types::{
     move_resource::MoveStorage,
     on_chain_config::{access_path_for_config, ConfigID},
     proof::{
-        AccumulatorConsistencyProof, SparseMerkleProof, SparseMerkleProofExt,
-        SparseMerkleRangeProof, TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
+        AccumulatorConsistencyProof, SparseMerkleBatchProof, SparseMerkleProof,
+        SparseMerkleProofExt, SparseMerkleRangeProof, TransactionAccumulatorRangeProof,
+        TransactionAccumulatorSummary,
     },
     state_proof::StateProof,
     state_store::{
@@ -26,7 +27,7 @@ use aptos_types::{
         state_key_prefix::StateKeyPrefix,
         state_storage_usage::StateStorageUsage,
         state_value::{StateValue, StateValueChunkWithProof},
-        table::{TableHandle, TableInfo},
+        table::{TableHandle, TableInfo, TableOwner},
         ShardedStateUpdates,
     },
     transaction::{
@@ -35,10 +36,12 @@ use aptos_types::{
     },
     write_set::WriteSet,
 };
+use move_core_types::language_storage::StructTag;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
 
+pub mod async_db_reader;
 pub mod async_proof_fetcher;
 pub mod cached_state_view;
 mod executed_trees;
@@ -323,6 +326,16 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the versions of up to `limit` state snapshots at or before `end_version` (the
+    /// latest first), i.e., the checkpoints the state can be read and synced at.
+    fn get_state_snapshot_versions(
+        &self,
+        end_version: Version,
+        limit: usize,
+    ) -> Result<Vec<Version>> {
+        unimplemented!()
+    }
+
     /// Returns the latest version and committed block timestamp
     fn get_latest_commit_metadata(&self) -> Result<(Version, u64)> {
         let ledger_info_with_sig = self.get_latest_ledger_info()?;
@@ -385,6 +398,19 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Gets the state values by state keys at version, in the same order as the keys.
+    /// See [AptosDB::get_state_values_by_version].
+    ///
+    /// [AptosDB::get_state_values_by_version]:
+    /// ../aptosdb/struct.AptosDB.html#method.get_state_values_by_version
+    fn get_state_values_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<Vec<Option<StateValue>>> {
+        unimplemented!()
+    }
+
     /// Get the latest state value and its corresponding version when it's of the given key up
     /// to the given version.
     /// See [AptosDB::get_state_value_with_version_by_version].
@@ -433,6 +459,19 @@ pub trait DbReader: Send + Sync {
             .map(|(value, proof_ext)| (value, proof_ext.into()))
     }
 
+    /// Returns a single proof of the given state keys (or their absence) at the given version.
+    /// See [AptosDB::get_state_batch_proof_by_version].
+    ///
+    /// [AptosDB::get_state_batch_proof_by_version]:
+    /// ../aptosdb/struct.AptosDB.html#method.get_state_batch_proof_by_version
+    fn get_state_batch_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<SparseMerkleBatchProof> {
+        unimplemented!()
+    }
+
     /// Gets the latest ExecutedTrees no matter if db has been bootstrapped.
     /// Used by the Db-bootstrapper.
     fn get_latest_executed_trees(&self) -> Result<ExecutedTrees> {
@@ -523,11 +562,33 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Get the resource or table item a table was first seen in from the internal indexer, if
+    /// known.
+    fn get_table_owner(&self, handle: TableHandle) -> Result<Option<TableOwner>> {
+        unimplemented!()
+    }
+
+    /// Returns the types of the resources held by an account at the given version, as tracked
+    /// by the internal indexer (or None, if the indexer doesn't track them at that version).
+    fn get_account_resource_tags(
+        &self,
+        address: AccountAddress,
+        version: Version,
+    ) -> Result<Option<Vec<StructTag>>> {
+        unimplemented!()
+    }
+
     /// Returns whether the internal indexer DB has been enabled or not
     fn indexer_enabled(&self) -> bool {
         unimplemented!()
     }
 
+    /// Returns whether the internal indexer is ready, i.e., done backfilling the account
+    /// resource tags and table owners of the versions indexed before it tracked them.
+    fn indexer_ready(&self) -> bool {
+        unimplemented!()
+    }
+
     /// Returns state storage usage at the end of an epoch.
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         unimplemented!()
@@ -3,7 +3,10 @@
 
 #![forbid(unsafe_code)]
 
-use aptos_metrics_core::{exponential_buckets, register_histogram_vec, HistogramVec};
+use aptos_metrics_core::{
+    exponential_buckets, register_histogram_vec, register_int_counter_vec, register_int_gauge_vec,
+    HistogramVec, IntCounterVec, IntGaugeVec,
+};
 use once_cell::sync::Lazy;
 
 pub static TIMER: Lazy<HistogramVec> = Lazy::new(|| {
@@ -15,3 +18,33 @@ pub static TIMER: Lazy<HistogramVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Number of async db reader calls that are queued or running, by API
+pub static ASYNC_DB_READER_PENDING_CALLS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_storage_interface_async_db_reader_pending_calls",
+        "Number of async db reader calls that are queued or running.",
+        &["name"]
+    )
+    .unwrap()
+});
+
+/// Number of async db reader calls that timed out, by API
+pub static ASYNC_DB_READER_TIMEOUTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_interface_async_db_reader_timeouts",
+        "Number of async db reader calls that timed out.",
+        &["name"]
+    )
+    .unwrap()
+});
+
+/// Number of lookups in the warm state cache carried over from the parent block, by result
+pub static WARM_STATE_CACHE_LOOKUPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_interface_warm_state_cache_lookups",
+        "Number of lookups in the warm state cache carried over from the parent block.",
+        &["result"]
+    )
+    .unwrap()
+});
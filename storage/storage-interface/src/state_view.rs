@@ -35,6 +35,14 @@ impl TStateView for DbStateView {
         self.get(state_key)
     }
 
+    fn get_state_values(&self, state_keys: &[StateKey]) -> Result<Vec<Option<StateValue>>> {
+        Ok(if let Some(version) = self.version {
+            self.db.get_state_values_by_version(state_keys, version)?
+        } else {
+            vec![None; state_keys.len()]
+        })
+    }
+
     fn get_usage(&self) -> Result<StateStorageUsage> {
         self.db.get_state_storage_usage(self.version)
     }
@@ -0,0 +1,56 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::smoke_test_environment::SwarmBuilder;
+use aptos_forge::Swarm;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    account_config::CORE_CODE_ADDRESS,
+    on_chain_config::{FeatureFlag, Features},
+};
+
+/// A proposal that disables the code dependency check
+const DISABLE_FEATURE_PROPOSAL: &str = r#"
+script {
+    use aptos_framework::aptos_governance;
+    use std::features;
+
+    fun main(proposal_id: u64) {
+        let framework_signer = aptos_governance::resolve(proposal_id, @aptos_framework);
+        features::change_feature_flags(&framework_signer, vector[], vector[1]);
+    }
+}
+"#;
+
+#[tokio::test]
+async fn test_simulate_proposal() {
+    let (swarm, cli, _faucet) = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .build_with_cli(0)
+        .await;
+    let rest_client = swarm.validators().next().unwrap().rest_client();
+
+    let script_dir = TempPath::new();
+    script_dir.create_as_dir().unwrap();
+    let script_path = script_dir.path().join("disable_feature.move");
+    std::fs::write(&script_path, DISABLE_FEATURE_PROPOSAL).unwrap();
+
+    // The simulation only shows the change of the features
+    let summary = cli
+        .simulate_proposal(script_path.to_str().unwrap(), false)
+        .await
+        .unwrap();
+    assert_eq!(summary.changes.len(), 1);
+    let change = &summary.changes[0];
+    assert_eq!(change.address, CORE_CODE_ADDRESS);
+    assert_eq!(change.resource_type, "0x1::features::Features");
+    assert_ne!(change.old_value, change.new_value);
+
+    // The feature is still enabled on chain
+    let features = rest_client
+        .get_account_resource_bcs::<Features>(CORE_CODE_ADDRESS, "0x1::features::Features")
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(features.is_enabled(FeatureFlag::CODE_DEPENDENCY_CHECK));
+}
@@ -198,6 +198,7 @@ async fn test_onchain_config_change() {
             let inner = match genesis_config.consensus_config.clone() {
                 OnChainConsensusConfig::V1(inner) => inner,
                 OnChainConsensusConfig::V2(inner) => inner,
+                OnChainConsensusConfig::V3(inner, _) => inner,
             };
 
             let leader_reputation_type =
@@ -209,7 +210,8 @@ async fn test_onchain_config_change() {
                     panic!()
                 };
             let proposer_and_voter_config = match &leader_reputation_type {
-                LeaderReputationType::ProposerAndVoter(_) => panic!(),
+                LeaderReputationType::ProposerAndVoter(_)
+                | LeaderReputationType::ProposerAndVoterV3(_) => panic!(),
                 LeaderReputationType::ProposerAndVoterV2(proposer_and_voter_config) => {
                     proposer_and_voter_config
                 },
@@ -255,6 +257,7 @@ async fn test_onchain_config_change() {
     let inner = match current_consensus_config {
         OnChainConsensusConfig::V1(inner) => inner,
         OnChainConsensusConfig::V2(inner) => inner,
+        OnChainConsensusConfig::V3(inner, _) => inner,
     };
     let leader_reputation_type =
         if let ProposerElectionType::LeaderReputation(leader_reputation_type) =
@@ -265,7 +268,8 @@ async fn test_onchain_config_change() {
             panic!()
         };
     let proposer_and_voter_config = match &leader_reputation_type {
-        LeaderReputationType::ProposerAndVoterV2(_) => panic!(),
+        LeaderReputationType::ProposerAndVoterV2(_)
+        | LeaderReputationType::ProposerAndVoterV3(_) => panic!(),
         LeaderReputationType::ProposerAndVoter(proposer_and_voter_config) => {
             proposer_and_voter_config
         },
@@ -6,7 +6,7 @@ use crate::{
     errors::{PartialVMError, PartialVMResult},
     file_format::{AbilitySet, StructTypeParameter, Visibility},
     file_format_common::VERSION_5,
-    normalized::Module,
+    normalized::{Function, Module},
 };
 use move_core_types::vm_status::StatusCode;
 use std::collections::BTreeSet;
@@ -139,37 +139,7 @@ impl Compatibility {
                     continue;
                 },
             };
-            let is_vis_compatible = match (old_func.visibility, new_func.visibility) {
-                // public must remain public
-                (Visibility::Public, Visibility::Public) => true,
-                (Visibility::Public, _) => false,
-                // friend can become public or remain friend
-                (Visibility::Friend, Visibility::Public)
-                | (Visibility::Friend, Visibility::Friend) => true,
-                (Visibility::Friend, _) => false,
-                // private can become public or friend, or stay private
-                (Visibility::Private, _) => true,
-            };
-            let is_entry_compatible = if old_module.file_format_version < VERSION_5
-                && new_module.file_format_version < VERSION_5
-            {
-                // if it was public(script), it must remain pubic(script)
-                // if it was not public(script), it _cannot_ become public(script)
-                old_func.is_entry == new_func.is_entry
-            } else {
-                // If it was an entry function, it must remain one.
-                // If it was not an entry function, it is allowed to become one.
-                !old_func.is_entry || new_func.is_entry
-            };
-            if !is_vis_compatible
-                || !is_entry_compatible
-                || old_func.parameters != new_func.parameters
-                || old_func.return_ != new_func.return_
-                || !fun_type_parameters_compatibile(
-                    &old_func.type_parameters,
-                    &new_func.type_parameters,
-                )
-            {
+            if !fun_signature_compatible(old_module, new_module, old_func, new_func) {
                 if matches!(old_func.visibility, Visibility::Friend) {
                     friend_linking = false;
                 } else {
@@ -209,15 +179,51 @@ impl Compatibility {
     }
 }
 
+// When upgrading, an exposed function must keep a compatible visibility, entry modifier
+// and signature (i.e., the same parameters, return types and compatible type parameters).
+pub fn fun_signature_compatible(
+    old_module: &Module,
+    new_module: &Module,
+    old_func: &Function,
+    new_func: &Function,
+) -> bool {
+    let is_vis_compatible = match (old_func.visibility, new_func.visibility) {
+        // public must remain public
+        (Visibility::Public, Visibility::Public) => true,
+        (Visibility::Public, _) => false,
+        // friend can become public or remain friend
+        (Visibility::Friend, Visibility::Public) | (Visibility::Friend, Visibility::Friend) => true,
+        (Visibility::Friend, _) => false,
+        // private can become public or friend, or stay private
+        (Visibility::Private, _) => true,
+    };
+    let is_entry_compatible = if old_module.file_format_version < VERSION_5
+        && new_module.file_format_version < VERSION_5
+    {
+        // if it was public(script), it must remain pubic(script)
+        // if it was not public(script), it _cannot_ become public(script)
+        old_func.is_entry == new_func.is_entry
+    } else {
+        // If it was an entry function, it must remain one.
+        // If it was not an entry function, it is allowed to become one.
+        !old_func.is_entry || new_func.is_entry
+    };
+    is_vis_compatible
+        && is_entry_compatible
+        && old_func.parameters == new_func.parameters
+        && old_func.return_ == new_func.return_
+        && fun_type_parameters_compatibile(&old_func.type_parameters, &new_func.type_parameters)
+}
+
 // When upgrading, the new abilities must be a superset of the old abilities.
 // Adding an ability is fine, but removing an ability could cause existing usages to fail.
-fn struct_abilities_compatibile(old_abilities: AbilitySet, new_abilities: AbilitySet) -> bool {
+pub fn struct_abilities_compatibile(old_abilities: AbilitySet, new_abilities: AbilitySet) -> bool {
     old_abilities.is_subset(new_abilities)
 }
 
 // When upgrading, the new type parameters must be the same length, and the new type parameter
 // constraints must be compatible
-fn fun_type_parameters_compatibile(
+pub fn fun_type_parameters_compatibile(
     old_type_parameters: &[AbilitySet],
     new_type_parameters: &[AbilitySet],
 ) -> bool {
@@ -232,7 +238,7 @@ fn fun_type_parameters_compatibile(
         )
 }
 
-fn struct_type_parameters_compatibile(
+pub fn struct_type_parameters_compatibile(
     old_type_parameters: &[StructTypeParameter],
     new_type_parameters: &[StructTypeParameter],
 ) -> bool {
@@ -207,14 +207,18 @@ impl VMStatus {
                 status_code:
                     StatusCode::EXECUTION_LIMIT_REACHED
                     | StatusCode::IO_LIMIT_REACHED
-                    | StatusCode::STORAGE_LIMIT_REACHED,
+                    | StatusCode::STORAGE_LIMIT_REACHED
+                    | StatusCode::EVENT_COUNT_LIMIT_REACHED
+                    | StatusCode::EVENT_BYTES_LIMIT_REACHED,
                 ..
             }
             | VMStatus::Error {
                 status_code:
                     StatusCode::EXECUTION_LIMIT_REACHED
                     | StatusCode::IO_LIMIT_REACHED
-                    | StatusCode::STORAGE_LIMIT_REACHED,
+                    | StatusCode::STORAGE_LIMIT_REACHED
+                    | StatusCode::EVENT_COUNT_LIMIT_REACHED
+                    | StatusCode::EVENT_BYTES_LIMIT_REACHED,
                 ..
             } => Ok(KeptVMStatus::MiscellaneousError),
 
@@ -793,9 +797,11 @@ pub enum StatusCode {
     IO_LIMIT_REACHED = 4031,
     STORAGE_LIMIT_REACHED = 4032,
     TYPE_TAG_LIMIT_EXCEEDED = 4033,
+    // A transaction emitted more events than allowed
+    EVENT_COUNT_LIMIT_REACHED = 4034,
+    // The events emitted by a transaction are larger than allowed
+    EVENT_BYTES_LIMIT_REACHED = 4035,
     // Reserved error code for future use
-    RESERVED_RUNTIME_ERROR_2 = 4034,
-    RESERVED_RUNTIME_ERROR_3 = 4035,
     RESERVED_RUNTIME_ERROR_4 = 4036,
     RESERVED_RUNTIME_ERROR_5 = 4037,
 
@@ -33,6 +33,7 @@ use move_vm_types::{
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     ops::{Add, Mul},
     u64,
 };
@@ -99,6 +100,55 @@ impl GasCost {
 
 static ZERO_COST_SCHEDULE: Lazy<CostTable> = Lazy::new(zero_cost_schedule);
 
+/// The gas used by each call stack during an execution, i.e., the gas used by each
+/// function excluding its callees. Natives are attributed to their callers.
+#[derive(Clone, Debug, Default)]
+pub struct CallStackProfile {
+    call_stack: Vec<String>,
+    gas_by_call_stack: BTreeMap<Vec<String>, InternalGas>,
+}
+
+impl CallStackProfile {
+    fn new(root_frame: String) -> Self {
+        Self {
+            call_stack: vec![root_frame],
+            gas_by_call_stack: BTreeMap::new(),
+        }
+    }
+
+    fn record_gas(&mut self, amount: InternalGas) {
+        if amount.is_zero() {
+            return;
+        }
+        let gas = self
+            .gas_by_call_stack
+            .entry(self.call_stack.clone())
+            .or_insert_with(InternalGas::zero);
+        *gas += amount;
+    }
+
+    fn push_frame(&mut self, module_id: &ModuleId, func_name: &str) {
+        self.call_stack
+            .push(format!("{}::{}", module_id.short_str_lossless(), func_name));
+    }
+
+    fn pop_frame(&mut self) {
+        // The root frame is never popped
+        if self.call_stack.len() > 1 {
+            self.call_stack.pop();
+        }
+    }
+
+    /// Converts the profile into collapsed (i.e., folded) stack lines, which
+    /// can be used to generate a flamegraph (e.g., with `inferno-flamegraph`).
+    pub fn to_folded_stack_lines(&self) -> Vec<String> {
+        self.gas_by_call_stack
+            .iter()
+            .map(|(call_stack, gas)| format!("{} {}", call_stack.join(";"), gas))
+            .collect()
+    }
+}
+
 /// The Move VM implementation of state for gas metering.
 ///
 /// Initialize with a `CostTable` and the gas provided to the transaction.
@@ -109,6 +159,7 @@ pub struct GasStatus<'a> {
     cost_table: &'a CostTable,
     gas_left: InternalGas,
     charge: bool,
+    call_stack_profile: Option<CallStackProfile>,
 }
 
 impl<'a> GasStatus<'a> {
@@ -121,6 +172,7 @@ impl<'a> GasStatus<'a> {
             gas_left: gas_left.to_unit(),
             cost_table,
             charge: true,
+            call_stack_profile: None,
         }
     }
 
@@ -133,6 +185,7 @@ impl<'a> GasStatus<'a> {
             gas_left: InternalGas::new(0),
             cost_table: &ZERO_COST_SCHEDULE,
             charge: false,
+            call_stack_profile: None,
         }
     }
 
@@ -154,16 +207,24 @@ impl<'a> GasStatus<'a> {
 
         match self.gas_left.checked_sub(amount) {
             Some(gas_left) => {
+                self.record_call_stack_gas(amount);
                 self.gas_left = gas_left;
                 Ok(())
             },
             None => {
+                self.record_call_stack_gas(self.gas_left);
                 self.gas_left = InternalGas::new(0);
                 Err(PartialVMError::new(StatusCode::OUT_OF_GAS))
             },
         }
     }
 
+    fn record_call_stack_gas(&mut self, amount: InternalGas) {
+        if let Some(profile) = &mut self.call_stack_profile {
+            profile.record_gas(amount);
+        }
+    }
+
     fn charge_instr(&mut self, opcode: Opcodes) -> PartialVMResult<()> {
         self.deduct_gas(
             self.cost_table
@@ -193,6 +254,17 @@ impl<'a> GasStatus<'a> {
     pub fn set_metering(&mut self, enabled: bool) {
         self.charge = enabled
     }
+
+    /// Enables recording the gas used by each call stack. The root frame is the
+    /// function (e.g., the unit test) that is executed with this gas status.
+    pub fn enable_call_stack_profile(&mut self, root_frame: String) {
+        self.call_stack_profile = Some(CallStackProfile::new(root_frame));
+    }
+
+    /// Return the gas used by each call stack (if enabled).
+    pub fn call_stack_profile(&self) -> Option<&CallStackProfile> {
+        self.call_stack_profile.as_ref()
+    }
 }
 
 impl<'b> GasMeter for GasStatus<'b> {
@@ -202,7 +274,11 @@ impl<'b> GasMeter for GasStatus<'b> {
 
     /// Charge an instruction and fail if not enough gas units are left.
     fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
-        self.charge_instr(instr.to_opcode())
+        self.charge_instr(instr.to_opcode())?;
+        if let (SimpleInstruction::Ret, Some(profile)) = (instr, &mut self.call_stack_profile) {
+            profile.pop_frame();
+        }
+        Ok(())
     }
 
     fn charge_br_false(&mut self, _target_offset: Option<CodeOffset>) -> PartialVMResult<()> {
@@ -234,23 +310,32 @@ impl<'b> GasMeter for GasStatus<'b> {
         _ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         _args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        // Natives never return through `Ret`, so the frame pushed by the call is popped
+        // here (before the native is charged), and natives are attributed to their callers.
+        if let Some(profile) = &mut self.call_stack_profile {
+            profile.pop_frame();
+        }
         Ok(())
     }
 
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         _num_locals: NumArgs,
     ) -> PartialVMResult<()> {
-        self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())
+        self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())?;
+        if let Some(profile) = &mut self.call_stack_profile {
+            profile.push_frame(module_id, func_name);
+        }
+        Ok(())
     }
 
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         _num_locals: NumArgs,
@@ -258,7 +343,11 @@ impl<'b> GasMeter for GasStatus<'b> {
         self.charge_instr_with_size(
             Opcodes::CALL_GENERIC,
             ((ty_args.len() + args.len() + 1) as u64).into(),
-        )
+        )?;
+        if let Some(profile) = &mut self.call_stack_profile {
+            profile.push_frame(module_id, func_name);
+        }
+        Ok(())
     }
 
     fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
@@ -790,3 +879,100 @@ pub static INITIAL_COST_SCHEDULE: Lazy<CostTable> = Lazy::new(|| {
 
     new_from_instructions(instrs)
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::{identifier::Identifier, language_storage::TypeTag};
+    use move_vm_types::values::Value;
+    use std::iter;
+
+    struct U64Type;
+
+    impl TypeView for U64Type {
+        fn to_type_tag(&self) -> TypeTag {
+            TypeTag::U64
+        }
+    }
+
+    #[test]
+    fn test_call_stack_profile() {
+        let mut gas_status = GasStatus::new(&INITIAL_COST_SCHEDULE, Gas::new(1_000_000));
+        gas_status.enable_call_stack_profile("0x1::test::root".to_string());
+        let module_id = ModuleId::new(AccountAddress::ONE, Identifier::new("test").unwrap());
+
+        // Charge an instruction in the root frame
+        let root_gas = charge(&mut gas_status, |gas_status| {
+            gas_status.charge_simple_instr(SimpleInstruction::LdU64)
+        });
+
+        // Call a function that calls a native (which is attributed to the function)
+        let callee_gas = charge(&mut gas_status, |gas_status| {
+            gas_status.charge_call(&module_id, "callee", iter::empty::<Value>(), 0.into())?;
+            gas_status.charge_simple_instr(SimpleInstruction::LdU64)?;
+            gas_status.charge_call_generic(
+                &module_id,
+                "native",
+                iter::once(U64Type),
+                iter::empty::<Value>(),
+                0.into(),
+            )?;
+            gas_status.charge_native_function_before_execution(
+                iter::once(U64Type),
+                iter::empty::<Value>(),
+            )?;
+            gas_status.charge_native_function(100.into(), Some(iter::empty::<Value>()))?;
+            gas_status.charge_simple_instr(SimpleInstruction::Ret)
+        });
+
+        // Charge another instruction in the root frame (after the function returned)
+        let root_gas = root_gas
+            + charge(&mut gas_status, |gas_status| {
+                gas_status.charge_simple_instr(SimpleInstruction::LdU64)
+            });
+
+        // Verify the gas used by each call stack
+        let profile = gas_status.call_stack_profile().unwrap();
+        assert_eq!(
+            profile.to_folded_stack_lines(),
+            vec![
+                format!("0x1::test::root {}", root_gas),
+                format!("0x1::test::root;0x1::test::callee {}", callee_gas),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_call_stack_profile_root_frame() {
+        let mut gas_status = GasStatus::new(&INITIAL_COST_SCHEDULE, Gas::new(1_000_000));
+        gas_status.enable_call_stack_profile("0x1::test::root".to_string());
+
+        // Verify that returning from the root frame doesn't pop it
+        let root_gas = charge(&mut gas_status, |gas_status| {
+            gas_status.charge_simple_instr(SimpleInstruction::Ret)?;
+            gas_status.charge_simple_instr(SimpleInstruction::LdU64)
+        });
+        let profile = gas_status.call_stack_profile().unwrap();
+        assert_eq!(
+            profile.to_folded_stack_lines(),
+            vec![format!("0x1::test::root {}", root_gas)]
+        );
+
+        // Verify that nothing is recorded if the profile isn't enabled
+        let mut gas_status = GasStatus::new(&INITIAL_COST_SCHEDULE, Gas::new(1_000_000));
+        gas_status
+            .charge_simple_instr(SimpleInstruction::LdU64)
+            .unwrap();
+        assert!(gas_status.call_stack_profile().is_none());
+    }
+
+    /// Runs the given charges and returns the amount of gas they used
+    fn charge(
+        gas_status: &mut GasStatus,
+        charges: impl FnOnce(&mut GasStatus) -> PartialVMResult<()>,
+    ) -> InternalGas {
+        let balance = gas_status.balance_internal();
+        charges(gas_status).unwrap();
+        balance.checked_sub(gas_status.balance_internal()).unwrap()
+    }
+}
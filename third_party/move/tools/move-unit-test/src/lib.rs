@@ -24,6 +24,7 @@ use std::{
     collections::BTreeMap,
     io::{Result, Write},
     marker::Send,
+    path::PathBuf,
     sync::Mutex,
 };
 
@@ -107,6 +108,11 @@ pub struct UnitTestingConfig {
     #[clap(short = 'v', long = "verbose")]
     pub verbose: bool,
 
+    /// Write the gas used by each test, broken down by call stack, to a collapsed stack
+    /// file (consumable by flamegraph tools) in the given directory
+    #[clap(name = "gas_profile_dir", long = "gas_profile_dir")]
+    pub gas_profile_dir: Option<PathBuf>,
+
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
     #[cfg(feature = "evm-backend")]
@@ -137,6 +143,7 @@ impl UnitTestingConfig {
             dep_files: vec![],
             check_stackless_vm: false,
             verbose: false,
+            gas_profile_dir: None,
             list: false,
             named_address_values: vec![],
 
@@ -240,6 +247,7 @@ impl UnitTestingConfig {
             native_function_table,
             cost_table,
             self.verbose,
+            self.gas_profile_dir.clone(),
             #[cfg(feature = "evm-backend")]
             self.evm,
         )
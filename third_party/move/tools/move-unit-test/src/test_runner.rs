@@ -17,6 +17,7 @@ use move_core_types::{
     account_address::AccountAddress,
     effects::{ChangeSet, Op},
     identifier::IdentStr,
+    language_storage::ModuleId,
     value::serialize_values,
     vm_status::StatusCode,
 };
@@ -26,11 +27,17 @@ use move_vm_runtime::{
     native_functions::NativeFunctionTable,
 };
 use move_vm_test_utils::{
-    gas_schedule::{zero_cost_schedule, CostTable, Gas, GasCost, GasStatus},
+    gas_schedule::{zero_cost_schedule, CallStackProfile, CostTable, Gas, GasCost, GasStatus},
     InMemoryStorage,
 };
 use rayon::prelude::*;
-use std::{io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    io::Write,
+    marker::Send,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Instant,
+};
 #[cfg(feature = "evm-backend")]
 use {
     evm::{backend::MemoryVicinity, ExitReason},
@@ -52,6 +59,7 @@ pub struct SharedTestingConfig {
     #[allow(dead_code)] // used by some features
     source_files: Vec<String>,
     record_writeset: bool,
+    gas_profile_dir: Option<PathBuf>,
 
     #[cfg(feature = "evm-backend")]
     evm: bool,
@@ -120,6 +128,26 @@ fn print_resources_and_extensions(
     Ok(buf)
 }
 
+/// Writes the gas used by each call stack of a test to a collapsed stack file
+/// (named after the test) in the given directory.
+fn save_gas_profile(
+    gas_profile_dir: &Path,
+    module_id: &ModuleId,
+    function_name: &str,
+    profile: &CallStackProfile,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(gas_profile_dir)?;
+    let file_name = format!(
+        "0x{}-{}-{}.folded",
+        module_id.address().short_str_lossless(),
+        module_id.name(),
+        function_name
+    );
+    let mut lines = profile.to_folded_stack_lines().join("\n");
+    lines.push('\n');
+    std::fs::write(gas_profile_dir.join(file_name), lines)
+}
+
 impl TestRunner {
     pub fn new(
         execution_bound: u64,
@@ -132,6 +160,7 @@ impl TestRunner {
         native_function_table: Option<NativeFunctionTable>,
         cost_table: Option<CostTable>,
         record_writeset: bool,
+        gas_profile_dir: Option<PathBuf>,
         #[cfg(feature = "evm-backend")] evm: bool,
     ) -> Result<Self> {
         let source_files = tests
@@ -162,6 +191,7 @@ impl TestRunner {
                 cost_table: cost_table.unwrap_or_else(unit_cost_table),
                 source_files,
                 record_writeset,
+                gas_profile_dir,
                 #[cfg(feature = "evm-backend")]
                 evm,
             },
@@ -264,6 +294,13 @@ impl SharedTestingConfig {
         let mut session =
             move_vm.new_session_with_extensions(&self.starting_storage_state, extensions);
         let mut gas_meter = GasStatus::new(&self.cost_table, Gas::new(self.execution_bound));
+        if self.gas_profile_dir.is_some() {
+            gas_meter.enable_call_stack_profile(format!(
+                "{}::{}",
+                format_module_id(&test_plan.module_id),
+                function_name
+            ));
+        }
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
 
         let now = Instant::now();
@@ -295,6 +332,23 @@ impl SharedTestingConfig {
                 .unwrap()
                 .into(),
         );
+        if let (Some(gas_profile_dir), Some(profile)) =
+            (&self.gas_profile_dir, gas_meter.call_stack_profile())
+        {
+            if let Err(err) = save_gas_profile(
+                gas_profile_dir,
+                &test_plan.module_id,
+                function_name,
+                profile,
+            ) {
+                eprintln!(
+                    "Failed to save the gas profile of {}::{}: {}",
+                    format_module_id(&test_plan.module_id),
+                    function_name,
+                    err
+                );
+            }
+        }
         match session.finish_with_extensions() {
             Ok((cs, _, extensions)) => (Ok(cs), Ok(extensions), return_result, test_run_info),
             Err(err) => (Err(err.clone()), Err(err), return_result, test_run_info),
@@ -14,6 +14,10 @@ pub struct NewEpochEvent {
 }
 
 impl NewEpochEvent {
+    pub fn new(epoch: u64) -> Self {
+        Self { epoch }
+    }
+
     pub fn epoch(&self) -> u64 {
         self.epoch
     }
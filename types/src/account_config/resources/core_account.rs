@@ -45,6 +45,14 @@ impl AccountResource {
         }
     }
 
+    /// Returns this resource with its authentication key replaced
+    pub fn with_authentication_key(self, authentication_key: Vec<u8>) -> Self {
+        AccountResource {
+            authentication_key,
+            ..self
+        }
+    }
+
     /// Return the sequence_number field for the given AccountResource
     pub fn sequence_number(&self) -> u64 {
         self.sequence_number
@@ -4,7 +4,11 @@
 
 #![forbid(unsafe_code)]
 
-use crate::ledger_info::{LedgerInfo, LedgerInfoWithSignatures};
+use crate::{
+    epoch_state::EpochState,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_verifier::ValidatorVerifier,
+};
 use anyhow::{ensure, format_err, Result};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest::{collection::vec, prelude::*};
@@ -75,7 +79,8 @@ impl EpochChangeProof {
             "The EpochChangeProof is stale as our verifier is already ahead \
              of the entire EpochChangeProof"
         );
-        let mut verifier_ref = verifier;
+        let mut next_epoch_state: Option<&EpochState> = None;
+        let mut signatures_to_verify = vec![];
 
         for ledger_info_with_sigs in self
             .ledger_info_with_sigs
@@ -105,15 +110,34 @@ impl EpochChangeProof {
             })
         {
             // Try to verify each (epoch -> epoch + 1) jump in the EpochChangeProof.
-            verifier_ref.verify(ledger_info_with_sigs)?;
-            // While the original verification could've been via waypoints,
-            // all the next epoch changes are verified using the (already
-            // trusted) validator sets.
-            verifier_ref = ledger_info_with_sigs
-                .ledger_info()
-                .next_epoch_state()
-                .ok_or_else(|| format_err!("LedgerInfo doesn't carry a ValidatorSet"))?;
+            match next_epoch_state {
+                None => verifier.verify(ledger_info_with_sigs)?,
+                // While the original verification could've been via waypoints,
+                // all the next epoch changes are verified using the (already
+                // trusted) validator sets. Their signatures are verified all at
+                // once below, which is much cheaper for long proofs.
+                Some(epoch_state) => {
+                    ensure!(
+                        epoch_state.epoch == ledger_info_with_sigs.ledger_info().epoch(),
+                        "LedgerInfo has unexpected epoch {}, expected {}",
+                        ledger_info_with_sigs.ledger_info().epoch(),
+                        epoch_state.epoch
+                    );
+                    signatures_to_verify.push((
+                        &epoch_state.verifier,
+                        ledger_info_with_sigs.ledger_info(),
+                        ledger_info_with_sigs.signatures(),
+                    ));
+                },
+            }
+            next_epoch_state = Some(
+                ledger_info_with_sigs
+                    .ledger_info()
+                    .next_epoch_state()
+                    .ok_or_else(|| format_err!("LedgerInfo doesn't carry a ValidatorSet"))?,
+            );
         }
+        ValidatorVerifier::batch_verify_multi_signatures(&signatures_to_verify)?;
 
         Ok(self.ledger_info_with_sigs.last().unwrap())
     }
@@ -64,6 +64,10 @@ pub enum MempoolStatusCode {
     // transaction didn't pass vm_validation
     VmError = 5,
     UnknownStatus = 6,
+    // Sender exceeded the submission rate limit
+    RateLimited = 7,
+    // Transaction matched a rule of the transaction filter policy
+    Filtered = 8,
 }
 
 impl TryFrom<u64> for MempoolStatusCode {
@@ -78,6 +82,8 @@ impl TryFrom<u64> for MempoolStatusCode {
             4 => Ok(MempoolStatusCode::InvalidUpdate),
             5 => Ok(MempoolStatusCode::VmError),
             6 => Ok(MempoolStatusCode::UnknownStatus),
+            7 => Ok(MempoolStatusCode::RateLimited),
+            8 => Ok(MempoolStatusCode::Filtered),
             _ => Err("invalid StatusCode"),
         }
     }
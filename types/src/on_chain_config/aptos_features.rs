@@ -32,6 +32,28 @@ pub enum FeatureFlag {
     GAS_PAYER_ENABLED = 22,
     APTOS_UNIQUE_IDENTIFIERS = 23,
     BULLETPROOFS_NATIVES = 24,
+    RANDOMNESS = 25,
+}
+
+impl FeatureFlag {
+    /// Returns true iff the feature flag is only read by the Move framework (and not by the VM
+    /// when it is created), and so can be changed within an epoch, at the start of any block (see
+    /// `features::schedule_feature_activations`). This must stay aligned with the Move source.
+    ///
+    /// `COLLECT_AND_DISTRIBUTE_GAS_FEES` is excluded, as disabling it mid-epoch would strand the
+    /// fees collected so far in the epoch.
+    pub fn is_hot_activatable(&self) -> bool {
+        matches!(
+            self,
+            FeatureFlag::MULTISIG_ACCOUNTS
+                | FeatureFlag::DELEGATION_POOLS
+                | FeatureFlag::PERIODICAL_REWARD_RATE_DECREASE
+                | FeatureFlag::PARTIAL_GOVERNANCE_VOTING
+                | FeatureFlag::DELEGATION_POOL_PARTIAL_GOVERNANCE_VOTING
+                | FeatureFlag::GAS_PAYER_ENABLED
+                | FeatureFlag::APTOS_UNIQUE_IDENTIFIERS
+        )
+    }
 }
 
 /// Representation of features on chain as a bitset.
@@ -55,6 +77,20 @@ impl OnChainConfig for Features {
 }
 
 impl Features {
+    /// Enables or disables the given feature (by its value)
+    fn set(&mut self, feature: u64, enable: bool) {
+        let byte_index = (feature / 8) as usize;
+        let bit_mask = 1 << (feature % 8);
+        if self.features.len() <= byte_index {
+            self.features.resize(byte_index + 1, 0);
+        }
+        if enable {
+            self.features[byte_index] |= bit_mask;
+        } else {
+            self.features[byte_index] &= !bit_mask;
+        }
+    }
+
     pub fn is_enabled(&self, flag: FeatureFlag) -> bool {
         let val = flag as u64;
         let byte_index = (val / 8) as usize;
@@ -71,5 +107,39 @@ impl Features {
     }
 }
 
+/// A feature flag change that takes effect within the epoch, in the first block whose
+/// timestamp is at or after the activation time.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ScheduledFeatureActivation {
+    pub feature: u64,
+    pub enable: bool,
+    pub activation_time_microseconds: u64,
+}
+
+/// The feature flag changes pending activation, in the order they were scheduled.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ScheduledFeatureActivations {
+    pub activations: Vec<ScheduledFeatureActivation>,
+}
+
+impl OnChainConfig for ScheduledFeatureActivations {
+    const MODULE_IDENTIFIER: &'static str = "features";
+    const TYPE_IDENTIFIER: &'static str = "ScheduledFeatureActivations";
+}
+
+impl ScheduledFeatureActivations {
+    /// Returns the features that will be enabled once all the changes due by the
+    /// given time (in microseconds) have been activated, mirroring the framework.
+    pub fn apply_due(&self, features: &Features, now_microseconds: u64) -> Features {
+        let mut features = features.clone();
+        for activation in &self.activations {
+            if activation.activation_time_microseconds <= now_microseconds {
+                features.set(activation.feature, activation.enable);
+            }
+        }
+        features
+    }
+}
+
 // --------------------------------------------------------------------------------------------
 // Code Publishing
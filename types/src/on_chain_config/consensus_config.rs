@@ -13,17 +13,15 @@ use std::collections::HashMap;
 pub enum OnChainConsensusConfig {
     V1(ConsensusConfigV1),
     V2(ConsensusConfigV1),
+    // Same as V2 (i.e., with quorum store), with the round timeouts set on-chain
+    V3(ConsensusConfigV1, RoundTimeoutConfig),
 }
 
 /// The public interface that exposes all values with safe fallback.
 impl OnChainConsensusConfig {
     /// The number of recent rounds that don't count into reputations.
     pub fn leader_reputation_exclude_round(&self) -> u64 {
-        match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                config.exclude_round
-            },
-        }
+        self.config().exclude_round
     }
 
     /// Decouple execution from consensus or not.
@@ -34,26 +32,35 @@ impl OnChainConsensusConfig {
     // Trim the list of failed authors from immediatelly preceeding rounds
     // to this max size.
     pub fn max_failed_authors_to_store(&self) -> usize {
-        match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                config.max_failed_authors_to_store
-            },
-        }
+        self.config().max_failed_authors_to_store
     }
 
     // Type and configuration used for proposer election.
     pub fn proposer_election_type(&self) -> &ProposerElectionType {
-        match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                &config.proposer_election_type
-            },
-        }
+        &self.config().proposer_election_type
     }
 
     pub fn quorum_store_enabled(&self) -> bool {
         match &self {
             OnChainConsensusConfig::V1(_config) => false,
-            OnChainConsensusConfig::V2(_config) => true,
+            OnChainConsensusConfig::V2(_config) | OnChainConsensusConfig::V3(_config, _) => true,
+        }
+    }
+
+    /// The round timeout parameters, if they are set on-chain. Otherwise, the validators use
+    /// their local config.
+    pub fn round_timeout(&self) -> Option<&RoundTimeoutConfig> {
+        match &self {
+            OnChainConsensusConfig::V1(_config) | OnChainConsensusConfig::V2(_config) => None,
+            OnChainConsensusConfig::V3(_config, round_timeout) => Some(round_timeout),
+        }
+    }
+
+    fn config(&self) -> &ConsensusConfigV1 {
+        match &self {
+            OnChainConsensusConfig::V1(config)
+            | OnChainConsensusConfig::V2(config)
+            | OnChainConsensusConfig::V3(config, _) => config,
         }
     }
 }
@@ -120,6 +127,42 @@ impl Default for ConsensusConfigV1 {
     }
 }
 
+/// The round timeout is `initial_timeout_ms * backoff_exponent_base ^ n`, where `n` is the
+/// number of rounds since the last commit (capped at `backoff_max_exponent`), plus a random
+/// jitter of up to `jitter_percent` of the timeout.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RoundTimeoutConfig {
+    // Timeout of the rounds right after a commit (in milliseconds)
+    pub initial_timeout_ms: u64,
+    // By how much the timeout grows with every round without a commit, in thousandths
+    // (e.g., 1200 is 1.2x). Stored as an integer, as floats are not supported on-chain.
+    pub backoff_exponent_base_per_mille: u64,
+    // Max number of times the timeout grows
+    pub backoff_max_exponent: u64,
+    // Max random jitter added to the timeout, as a percentage of the timeout. Spreads out
+    // the timeouts of the validators, so they don't all time out at once.
+    pub jitter_percent: u64,
+}
+
+impl RoundTimeoutConfig {
+    pub fn backoff_exponent_base(&self) -> f64 {
+        self.backoff_exponent_base_per_mille as f64 / 1000.0
+    }
+
+    /// Whether the timeouts are positive, never shrink, and the max timeout doesn't overflow
+    pub fn is_valid(&self) -> bool {
+        self.initial_timeout_ms > 0
+            && self.backoff_exponent_base_per_mille >= 1000
+            && self.backoff_max_exponent < 32
+            && self
+                .backoff_exponent_base()
+                .powf(self.backoff_max_exponent as f64)
+                .ceil()
+                < f64::from(u32::MAX)
+            && self.jitter_percent <= 100
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum ProposerElectionType {
@@ -150,6 +193,11 @@ pub enum LeaderReputationType {
     // * use reputation window from recent end
     // * unpredictable seed, based on root hash
     ProposerAndVoterV2(ProposerAndVoterConfig),
+    // Version 3:
+    // * same as version 2
+    // * separately configurable window for proposer failures, so that
+    //   failures can decay faster (or slower) than successful proposals
+    ProposerAndVoterV3(ProposerAndVoterV3Config),
 }
 
 impl LeaderReputationType {
@@ -162,6 +210,15 @@ impl LeaderReputationType {
         // all versions after V1 shouldn't use from stale end
         matches!(self, Self::ProposerAndVoter(_))
     }
+
+    /// Returns the config in its most recent form. For versions without a separate
+    /// failure window, proposer failures use the proposer window, as before.
+    pub fn proposer_and_voter_config(&self) -> ProposerAndVoterV3Config {
+        match self {
+            Self::ProposerAndVoter(config) | Self::ProposerAndVoterV2(config) => (*config).into(),
+            Self::ProposerAndVoterV3(config) => *config,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -189,6 +246,54 @@ pub struct ProposerAndVoterConfig {
     pub use_history_from_previous_epoch_max_count: u32,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProposerAndVoterV3Config {
+    // Selection weight for active validators with proposer failures below threshold
+    pub active_weight: u64,
+    // Selection weight for inactive validators with proposer failures below threshold
+    pub inactive_weight: u64,
+    // Selection weight for validators with proposer failures above threshold
+    pub failed_weight: u64,
+    // Thresholed of failures in the rounds validator was selected to be proposer
+    // integer values representing percentages, i.e. 12 is 12%.
+    pub failure_threshold_percent: u32,
+    // Window into history considered for successful proposals, multiplier
+    // on top of number of validators
+    pub proposer_window_num_validators_multiplier: usize,
+    // Window into history considered for failed proposals, multiplier
+    // on top of number of validators. Smaller than the proposer window
+    // means failed validators recover their weight faster.
+    pub failure_window_num_validators_multiplier: usize,
+    // Window into history considered for votre statistics, multiplier
+    // on top of number of validators
+    pub voter_window_num_validators_multiplier: usize,
+    // Flag whether to use voting power as multiplier to the weights
+    pub weight_by_voting_power: bool,
+    // Flag whether to use history from previous epoch (0 if not),
+    // representing a number of historical epochs (beyond the current one)
+    // to consider.
+    pub use_history_from_previous_epoch_max_count: u32,
+}
+
+impl From<ProposerAndVoterConfig> for ProposerAndVoterV3Config {
+    fn from(config: ProposerAndVoterConfig) -> Self {
+        Self {
+            active_weight: config.active_weight,
+            inactive_weight: config.inactive_weight,
+            failed_weight: config.failed_weight,
+            failure_threshold_percent: config.failure_threshold_percent,
+            proposer_window_num_validators_multiplier: config
+                .proposer_window_num_validators_multiplier,
+            failure_window_num_validators_multiplier: config
+                .proposer_window_num_validators_multiplier,
+            voter_window_num_validators_multiplier: config.voter_window_num_validators_multiplier,
+            weight_by_voting_power: config.weight_by_voting_power,
+            use_history_from_previous_epoch_max_count: config
+                .use_history_from_previous_epoch_max_count,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -228,6 +333,79 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_leader_reputation_v3_serialization() {
+        let default_config = match OnChainConsensusConfig::default().proposer_election_type() {
+            ProposerElectionType::LeaderReputation(leader_reputation_type) => {
+                leader_reputation_type.proposer_and_voter_config()
+            },
+            _ => panic!("Default proposer election should use leader reputation"),
+        };
+        // Older versions use the proposer window for failures
+        assert_eq!(
+            default_config.failure_window_num_validators_multiplier,
+            default_config.proposer_window_num_validators_multiplier
+        );
+
+        let leader_reputation_type =
+            LeaderReputationType::ProposerAndVoterV3(ProposerAndVoterV3Config {
+                failure_window_num_validators_multiplier: 2,
+                ..default_config
+            });
+        let config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
+            proposer_election_type: ProposerElectionType::LeaderReputation(
+                leader_reputation_type.clone(),
+            ),
+            ..ConsensusConfigV1::default()
+        });
+
+        let result =
+            bcs::from_bytes::<OnChainConsensusConfig>(&bcs::to_bytes(&config).unwrap()).unwrap();
+        assert_eq!(
+            result.proposer_election_type(),
+            &ProposerElectionType::LeaderReputation(leader_reputation_type.clone())
+        );
+        assert!(leader_reputation_type.use_root_hash_for_seed());
+        assert!(!leader_reputation_type.use_reputation_window_from_stale_end());
+        assert_eq!(
+            leader_reputation_type
+                .proposer_and_voter_config()
+                .failure_window_num_validators_multiplier,
+            2
+        );
+    }
+
+    #[test]
+    fn test_round_timeout_serialization() {
+        assert!(OnChainConsensusConfig::default().round_timeout().is_none());
+
+        let round_timeout = RoundTimeoutConfig {
+            initial_timeout_ms: 3000,
+            backoff_exponent_base_per_mille: 1500,
+            backoff_max_exponent: 4,
+            jitter_percent: 10,
+        };
+        let config = OnChainConsensusConfig::V3(ConsensusConfigV1::default(), round_timeout);
+
+        let result =
+            bcs::from_bytes::<OnChainConsensusConfig>(&bcs::to_bytes(&config).unwrap()).unwrap();
+        assert!(result.quorum_store_enabled());
+        assert_eq!(result.round_timeout(), Some(&round_timeout));
+        assert_eq!(round_timeout.backoff_exponent_base(), 1.5);
+        assert!(round_timeout.is_valid());
+        assert!(!RoundTimeoutConfig {
+            backoff_exponent_base_per_mille: 500,
+            ..round_timeout
+        }
+        .is_valid());
+
+        let s = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<OnChainConsensusConfig>(&s).unwrap(),
+            config
+        );
+    }
+
     #[test]
     fn test_config_onchain_payload() {
         let consensus_config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
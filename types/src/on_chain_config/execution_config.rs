@@ -14,7 +14,8 @@ pub enum OnChainExecutionConfig {
     /// To maintain backwards compatibility on replay, we must ensure that any new features resolve
     /// to previous behavior (before OnChainExecutionConfig was registered) in case of Missing.
     Missing,
-    // Reminder: Add V4 and future versions here, after Missing (order matters for enums).
+    V4(ExecutionConfigV4),
+    // Reminder: Add V5 and future versions here, after Missing (order matters for enums).
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -26,6 +27,12 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V1(config) => config.transaction_shuffler_type.clone(),
             OnChainExecutionConfig::V2(config) => config.transaction_shuffler_type.clone(),
             OnChainExecutionConfig::V3(config) => config.transaction_shuffler_type.clone(),
+            // The shuffler would undo the payload ordering, so it only shuffles the pulled order
+            OnChainExecutionConfig::V4(config) => match config.payload_orderer_type {
+                PayloadOrdererType::FairFifo => config.transaction_shuffler_type.clone(),
+                PayloadOrdererType::GasPriceDescending
+                | PayloadOrdererType::SenderEntropyShuffle => TransactionShufflerType::NoShuffling,
+            },
         }
     }
 
@@ -36,6 +43,7 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V1(_config) => None,
             OnChainExecutionConfig::V2(config) => config.block_gas_limit,
             OnChainExecutionConfig::V3(config) => config.block_gas_limit,
+            OnChainExecutionConfig::V4(config) => config.block_gas_limit,
         }
     }
 
@@ -47,6 +55,19 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V1(_config) => TransactionDeduperType::NoDedup,
             OnChainExecutionConfig::V2(_config) => TransactionDeduperType::NoDedup,
             OnChainExecutionConfig::V3(config) => config.transaction_deduper_type.clone(),
+            OnChainExecutionConfig::V4(config) => config.transaction_deduper_type.clone(),
+        }
+    }
+
+    /// The type of the payload orderer being used.
+    pub fn payload_orderer_type(&self) -> PayloadOrdererType {
+        match &self {
+            // Note, previous versions executed the transactions in the order they were pulled.
+            OnChainExecutionConfig::Missing
+            | OnChainExecutionConfig::V1(_)
+            | OnChainExecutionConfig::V2(_)
+            | OnChainExecutionConfig::V3(_) => PayloadOrdererType::FairFifo,
+            OnChainExecutionConfig::V4(config) => config.payload_orderer_type.clone(),
         }
     }
 
@@ -103,6 +124,14 @@ pub struct ExecutionConfigV3 {
     pub transaction_deduper_type: TransactionDeduperType,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ExecutionConfigV4 {
+    pub transaction_shuffler_type: TransactionShufflerType,
+    pub block_gas_limit: Option<u64>,
+    pub transaction_deduper_type: TransactionDeduperType,
+    pub payload_orderer_type: PayloadOrdererType,
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum TransactionShufflerType {
@@ -118,6 +147,20 @@ pub enum TransactionDeduperType {
     TxnHashAndAuthenticatorV1,
 }
 
+/// The order in which the transactions pulled from quorum store are placed in a block. The
+/// transactions are only shuffled for execution if they are kept in the pulled order.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
+pub enum PayloadOrdererType {
+    // Keep the order in which the transactions were pulled (i.e., batch order)
+    FairFifo,
+    // Order by gas unit price (descending), keeping the sequence number order of each sender
+    GasPriceDescending,
+    // Order the senders pseudo-randomly (seeded by the block id), keeping the sequence
+    // number order of each sender
+    SenderEntropyShuffle,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -184,6 +227,51 @@ mod test {
         assert!(matches!(result.block_gas_limit(), None));
     }
 
+    #[test]
+    fn test_payload_orderer_type() {
+        // Older versions keep the pulled order
+        let config = OnChainExecutionConfig::V3(ExecutionConfigV3 {
+            transaction_shuffler_type: TransactionShufflerType::SenderAwareV2(32),
+            block_gas_limit: None,
+            transaction_deduper_type: TransactionDeduperType::NoDedup,
+        });
+        assert_eq!(config.payload_orderer_type(), PayloadOrdererType::FairFifo);
+        assert_eq!(
+            OnChainExecutionConfig::default_if_missing().payload_orderer_type(),
+            PayloadOrdererType::FairFifo
+        );
+
+        let config = OnChainExecutionConfig::V4(ExecutionConfigV4 {
+            transaction_shuffler_type: TransactionShufflerType::SenderAwareV2(32),
+            block_gas_limit: Some(35000),
+            transaction_deduper_type: TransactionDeduperType::TxnHashAndAuthenticatorV1,
+            payload_orderer_type: PayloadOrdererType::SenderEntropyShuffle,
+        });
+        let result =
+            bcs::from_bytes::<OnChainExecutionConfig>(&bcs::to_bytes(&config).unwrap()).unwrap();
+        assert_eq!(
+            result.payload_orderer_type(),
+            PayloadOrdererType::SenderEntropyShuffle
+        );
+        assert_eq!(result.block_gas_limit(), Some(35000));
+
+        // The shuffler only applies to the pulled order, as it would undo any other ordering
+        assert_eq!(
+            result.transaction_shuffler_type(),
+            TransactionShufflerType::NoShuffling
+        );
+        let config = OnChainExecutionConfig::V4(ExecutionConfigV4 {
+            transaction_shuffler_type: TransactionShufflerType::SenderAwareV2(32),
+            block_gas_limit: Some(35000),
+            transaction_deduper_type: TransactionDeduperType::TxnHashAndAuthenticatorV1,
+            payload_orderer_type: PayloadOrdererType::FairFifo,
+        });
+        assert_eq!(
+            config.transaction_shuffler_type(),
+            TransactionShufflerType::SenderAwareV2(32)
+        );
+    }
+
     #[test]
     fn test_config_onchain_payload() {
         let execution_config = OnChainExecutionConfig::V1(ExecutionConfigV1 {
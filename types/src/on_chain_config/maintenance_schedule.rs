@@ -0,0 +1,49 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use move_core_types::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
+
+/// A maintenance window announced by the operator of a validator, during which consensus
+/// doesn't elect the validator as the leader.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct MaintenanceWindow {
+    pub validator: AccountAddress,
+    pub epoch: u64,
+    pub start_round: u64,
+    pub num_rounds: u64,
+}
+
+impl MaintenanceWindow {
+    /// The first round after the window
+    pub fn end_round(&self) -> u64 {
+        self.start_round.saturating_add(self.num_rounds)
+    }
+
+    /// Returns true iff the round is within the window
+    pub fn contains(&self, round: u64) -> bool {
+        round >= self.start_round && round < self.end_round()
+    }
+}
+
+/// The maintenance windows of the current and next epochs. Windows can only be announced for
+/// the next epoch, so the windows of the current epoch don't change during the epoch.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct OnChainMaintenanceSchedule {
+    pub windows: Vec<MaintenanceWindow>,
+}
+
+impl OnChainMaintenanceSchedule {
+    /// Returns the windows of the given epoch
+    pub fn windows_in_epoch(&self, epoch: u64) -> impl Iterator<Item = &MaintenanceWindow> {
+        self.windows
+            .iter()
+            .filter(move |window| window.epoch == epoch)
+    }
+}
+
+impl OnChainConfig for OnChainMaintenanceSchedule {
+    const MODULE_IDENTIFIER: &'static str = "maintenance_schedule";
+    const TYPE_IDENTIFIER: &'static str = "MaintenanceSchedule";
+}
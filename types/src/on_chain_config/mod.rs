@@ -24,6 +24,10 @@ mod chain_id;
 mod consensus_config;
 mod execution_config;
 mod gas_schedule;
+mod maintenance_schedule;
+mod randomness;
+mod state_snapshot_config;
+mod state_storage_usage;
 mod timed_features;
 mod timestamp;
 mod validator_set;
@@ -36,13 +40,18 @@ pub use self::{
     },
     consensus_config::{
         ConsensusConfigV1, LeaderReputationType, OnChainConsensusConfig, ProposerAndVoterConfig,
-        ProposerElectionType,
+        ProposerElectionType, RoundTimeoutConfig,
     },
     execution_config::{
-        ExecutionConfigV1, ExecutionConfigV2, OnChainExecutionConfig, TransactionDeduperType,
+        ExecutionConfigV1, ExecutionConfigV2, ExecutionConfigV3, ExecutionConfigV4,
+        OnChainExecutionConfig, PayloadOrdererType, TransactionDeduperType,
         TransactionShufflerType,
     },
     gas_schedule::{GasSchedule, GasScheduleV2, StorageGasSchedule},
+    maintenance_schedule::{MaintenanceWindow, OnChainMaintenanceSchedule},
+    randomness::PerBlockRandomness,
+    state_snapshot_config::OnChainStateSnapshotConfig,
+    state_storage_usage::{StateStorageUsageInner, StateStorageUsageResource},
     timed_features::{TimedFeatureFlag, TimedFeatureOverride, TimedFeatures},
     timestamp::CurrentTimeMicroseconds,
     validator_set::{ConsensusScheme, ValidatorSet},
@@ -217,6 +226,16 @@ impl ConfigurationResource {
         &self.events
     }
 
+    /// Returns the configuration of the next epoch, which starts at the given time. The new
+    /// epoch event is counted, so it has to be emitted along with the new configuration.
+    pub fn bump_epoch(&self, last_reconfiguration_time: u64) -> Self {
+        Self {
+            epoch: self.epoch + 1,
+            last_reconfiguration_time,
+            events: EventHandle::new(*self.events.key(), self.events.count() + 1),
+        }
+    }
+
     #[cfg(feature = "fuzzing")]
     pub fn bump_epoch_for_test(&self) -> Self {
         let epoch = self.epoch + 1;
@@ -0,0 +1,18 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+
+/// The randomness seed of the current block, as provided by consensus
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct PerBlockRandomness {
+    pub epoch: u64,
+    pub round: u64,
+    pub seed: Option<Vec<u8>>,
+}
+
+impl OnChainConfig for PerBlockRandomness {
+    const MODULE_IDENTIFIER: &'static str = "randomness";
+    const TYPE_IDENTIFIER: &'static str = "PerBlockRandomness";
+}
@@ -0,0 +1,20 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+
+/// The schedule of the state snapshots, as set by governance. If set, it overrides the
+/// schedule in the storage config of the nodes. Intervals of 0 (in blocks or seconds) are
+/// disabled.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct OnChainStateSnapshotConfig {
+    pub interval_versions: u64,
+    pub interval_blocks: u64,
+    pub interval_secs: u64,
+}
+
+impl OnChainConfig for OnChainStateSnapshotConfig {
+    const MODULE_IDENTIFIER: &'static str = "state_snapshot_config";
+    const TYPE_IDENTIFIER: &'static str = "StateSnapshotConfig";
+}
@@ -0,0 +1,23 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+
+/// The state storage usage recorded by `0x1::state_storage` at the beginning of each epoch
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct StateStorageUsageResource {
+    pub epoch: u64,
+    pub usage: StateStorageUsageInner,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct StateStorageUsageInner {
+    pub items: u64,
+    pub bytes: u64,
+}
+
+impl OnChainConfig for StateStorageUsageResource {
+    const MODULE_IDENTIFIER: &'static str = "state_storage";
+    const TYPE_IDENTIFIER: &'static str = "StateStorageUsage";
+}
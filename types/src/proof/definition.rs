@@ -278,14 +278,54 @@ impl SparseMerkleProof {
         element_key: HashValue,
         element_hash: Option<HashValue>,
     ) -> Result<()> {
+        Self::verify_leaf(element_key, element_hash, self.leaf, self.siblings.len())?;
+
+        let current_hash = self
+            .leaf
+            .map_or(*SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash());
+        let actual_root_hash = self
+            .siblings
+            .iter()
+            .zip(
+                element_key
+                    .iter_bits()
+                    .rev()
+                    .skip(HashValue::LENGTH_IN_BITS - self.siblings.len()),
+            )
+            .fold(current_hash, |hash, (sibling_hash, bit)| {
+                if bit {
+                    SparseMerkleInternalNode::new(*sibling_hash, hash).hash()
+                } else {
+                    SparseMerkleInternalNode::new(hash, *sibling_hash).hash()
+                }
+            });
         ensure!(
-            self.siblings.len() <= HashValue::LENGTH_IN_BITS,
+            actual_root_hash == expected_root_hash,
+            "{}: Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
+            type_name::<Self>(),
+            actual_root_hash,
+            expected_root_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Verifies the leaf at the end of the route of `element_key` (at `depth`) proves the element
+    /// exists, if it has an element hash, or that it doesn't exist otherwise.
+    fn verify_leaf(
+        element_key: HashValue,
+        element_hash: Option<HashValue>,
+        leaf: Option<SparseMerkleLeafNode>,
+        depth: usize,
+    ) -> Result<()> {
+        ensure!(
+            depth <= HashValue::LENGTH_IN_BITS,
             "Sparse Merkle Tree proof has more than {} ({}) siblings.",
             HashValue::LENGTH_IN_BITS,
-            self.siblings.len(),
+            depth,
         );
 
-        match (element_hash, self.leaf) {
+        match (element_hash, leaf) {
             (Some(hash), Some(leaf)) => {
                 // This is an inclusion proof, so the key and value hash provided in the proof
                 // should match element_key and element_value_hash. `siblings` should prove the
@@ -327,7 +367,7 @@ impl SparseMerkleProof {
                     leaf.key,
                 );
                 ensure!(
-                    element_key.common_prefix_bits_len(leaf.key) >= self.siblings.len(),
+                    element_key.common_prefix_bits_len(leaf.key) >= depth,
                     "Key would not have ended up in the subtree where the provided key in proof \
                      is the only existing key, if it existed. So this is not a valid \
                      non-inclusion proof. Key: {:x}. Key in proof: {:x}.",
@@ -342,25 +382,133 @@ impl SparseMerkleProof {
             },
         }
 
-        let current_hash = self
-            .leaf
-            .map_or(*SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash());
-        let actual_root_hash = self
-            .siblings
+        Ok(())
+    }
+}
+
+/// A proof that can be used to authenticate multiple elements (or their absence) in a Sparse Merkle
+/// Tree at once, given a trusted root hash. It's equivalent to a `SparseMerkleProof` for each
+/// element, but the siblings shared by the routes of the elements are only included once, and the
+/// ones on the route of another element are left out (they are computed while verifying).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SparseMerkleBatchProof {
+    /// For each element, the leaf at the end of its route (see `SparseMerkleProof::leaf`) and the
+    /// depth of that leaf (i.e., the number of siblings in the `SparseMerkleProof` of the element).
+    leaves: Vec<(Option<SparseMerkleLeafNode>, u16)>,
+
+    /// The siblings that are not on the route of any element, in depth-first order (i.e., from the
+    /// root level to the bottom level, and from left to right).
+    siblings: Vec<HashValue>,
+}
+
+impl SparseMerkleBatchProof {
+    /// Combines the proofs of the elements with the given keys into a single proof. The elements
+    /// have to be given to `verify` in the same order.
+    pub fn new(proofs: &[(HashValue, SparseMerkleProof)]) -> Result<Self> {
+        let sorted_elements = Self::sort_elements(proofs.iter().map(|(key, _)| *key))?;
+        let mut siblings = vec![];
+        if !proofs.is_empty() {
+            Self::collect_siblings(proofs, &sorted_elements, 0, &mut siblings)?;
+        }
+        Ok(Self {
+            leaves: proofs
+                .iter()
+                .map(|(_, proof)| (proof.leaf, proof.siblings.len() as u16))
+                .collect(),
+            siblings,
+        })
+    }
+
+    /// Returns the indices of the elements, sorted by the keys of the elements (i.e., the order in
+    /// which their routes are visited, from left to right).
+    fn sort_elements(keys: impl Iterator<Item = HashValue>) -> Result<Vec<(HashValue, usize)>> {
+        let mut sorted_elements: Vec<_> = keys.enumerate().map(|(i, key)| (key, i)).collect();
+        sorted_elements.sort_unstable();
+        if let Some(pair) = sorted_elements
+            .windows(2)
+            .find(|pair| pair[0].0 == pair[1].0)
+        {
+            bail!(
+                "Duplicate key in Sparse Merkle Tree batch proof: {:x}",
+                pair[0].0
+            );
+        }
+        Ok(sorted_elements)
+    }
+
+    /// Collects the siblings of the subtree at `depth` holding the given (non-empty) group of
+    /// elements.
+    fn collect_siblings(
+        proofs: &[(HashValue, SparseMerkleProof)],
+        group: &[(HashValue, usize)],
+        depth: usize,
+        siblings: &mut Vec<HashValue>,
+    ) -> Result<()> {
+        let num_siblings = |(_, i): &(HashValue, usize)| proofs[*i].1.siblings.len();
+        if group.iter().all(|element| num_siblings(element) == depth) {
+            return Ok(());
+        }
+        ensure!(
+            group.iter().all(|element| num_siblings(element) > depth),
+            "Inconsistent Sparse Merkle Tree proofs: routes of the same subtree end at different \
+             depths.",
+        );
+
+        let num_left = group.iter().take_while(|(key, _)| !key.bit(depth)).count();
+        let (left, right) = group.split_at(num_left);
+        for (subgroup, other_subgroup) in [(left, right), (right, left)] {
+            if subgroup.is_empty() {
+                // The subtree has no element, so its hash is the sibling of the other subtree
+                let proof_siblings = &proofs[other_subgroup[0].1].1.siblings;
+                siblings.push(proof_siblings[proof_siblings.len() - 1 - depth]);
+            } else {
+                Self::collect_siblings(proofs, subgroup, depth + 1, siblings)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn verify<V: CryptoHash>(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(HashValue, Option<&V>)],
+    ) -> Result<()> {
+        let elements: Vec<_> = elements
             .iter()
-            .zip(
-                element_key
-                    .iter_bits()
-                    .rev()
-                    .skip(HashValue::LENGTH_IN_BITS - self.siblings.len()),
-            )
-            .fold(current_hash, |hash, (sibling_hash, bit)| {
-                if bit {
-                    SparseMerkleInternalNode::new(*sibling_hash, hash).hash()
-                } else {
-                    SparseMerkleInternalNode::new(hash, *sibling_hash).hash()
-                }
-            });
+            .map(|(key, value)| (*key, value.map(|v| v.hash())))
+            .collect();
+        self.verify_by_hash(expected_root_hash, &elements)
+    }
+
+    /// Verifies the given elements (in the order the proof was created for) exist in the Sparse
+    /// Merkle Tree, if they have an element hash. Otherwise verifies they don't exist in the tree
+    /// (see `SparseMerkleProof::verify_by_hash`).
+    pub fn verify_by_hash(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(HashValue, Option<HashValue>)],
+    ) -> Result<()> {
+        ensure!(
+            !elements.is_empty(),
+            "Sparse Merkle Tree batch proof must prove at least one element.",
+        );
+        ensure!(
+            elements.len() == self.leaves.len(),
+            "Sparse Merkle Tree batch proof is for {} elements, but {} are given.",
+            self.leaves.len(),
+            elements.len(),
+        );
+        for ((element_key, element_hash), (leaf, depth)) in elements.iter().zip(&self.leaves) {
+            SparseMerkleProof::verify_leaf(*element_key, *element_hash, *leaf, *depth as usize)?;
+        }
+
+        let sorted_elements = Self::sort_elements(elements.iter().map(|(key, _)| *key))?;
+        let mut siblings = self.siblings.iter();
+        let actual_root_hash = self.compute_hash(&sorted_elements, 0, &mut siblings)?;
+        ensure!(
+            siblings.next().is_none(),
+            "Sparse Merkle Tree batch proof has more siblings than needed.",
+        );
         ensure!(
             actual_root_hash == expected_root_hash,
             "{}: Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
@@ -371,6 +519,46 @@ impl SparseMerkleProof {
 
         Ok(())
     }
+
+    /// Computes the hash of the subtree at `depth` holding the given (non-empty) group of
+    /// elements.
+    fn compute_hash(
+        &self,
+        group: &[(HashValue, usize)],
+        depth: usize,
+        siblings: &mut std::slice::Iter<HashValue>,
+    ) -> Result<HashValue> {
+        let leaf_depth = |(_, i): &(HashValue, usize)| self.leaves[*i].1 as usize;
+        if group.iter().all(|element| leaf_depth(element) == depth) {
+            // All the routes end at the same position, so they must end at the same leaf
+            let leaf = self.leaves[group[0].1].0;
+            ensure!(
+                group.iter().all(|(_, i)| self.leaves[*i].0 == leaf),
+                "Sparse Merkle Tree batch proof has different leaves at the same position.",
+            );
+            return Ok(leaf.map_or(*SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash()));
+        }
+        ensure!(
+            group.iter().all(|element| leaf_depth(element) > depth),
+            "Sparse Merkle Tree batch proof has routes of the same subtree ending at different \
+             depths.",
+        );
+
+        let num_left = group.iter().take_while(|(key, _)| !key.bit(depth)).count();
+        let (left, right) = group.split_at(num_left);
+        let mut subtree_hash = |subgroup: &[(HashValue, usize)]| {
+            if subgroup.is_empty() {
+                siblings.next().copied().ok_or_else(|| {
+                    format_err!("Sparse Merkle Tree batch proof has fewer siblings than needed.")
+                })
+            } else {
+                self.compute_hash(subgroup, depth + 1, siblings)
+            }
+        };
+        let left_hash = subtree_hash(left)?;
+        let right_hash = subtree_hash(right)?;
+        Ok(SparseMerkleInternalNode::new(left_hash, right_hash).hash())
+    }
 }
 
 /// An in-memory accumulator for storing a summary of the core transaction info
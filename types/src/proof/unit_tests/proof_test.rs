@@ -11,9 +11,9 @@ use crate::{
     ledger_info::LedgerInfo,
     proof::{
         definition::MAX_ACCUMULATOR_PROOF_DEPTH, AccumulatorExtensionProof, AccumulatorRangeProof,
-        SparseMerkleInternalNode, SparseMerkleLeafNode, TestAccumulatorInternalNode,
-        TestAccumulatorProof, TransactionAccumulatorInternalNode, TransactionAccumulatorProof,
-        TransactionInfoListWithProof, TransactionInfoWithProof,
+        SparseMerkleBatchProof, SparseMerkleInternalNode, SparseMerkleLeafNode,
+        TestAccumulatorInternalNode, TestAccumulatorProof, TransactionAccumulatorInternalNode,
+        TransactionAccumulatorProof, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_store::state_value::StateValue,
     transaction::{
@@ -266,6 +266,146 @@ fn test_verify_three_element_sparse_merkle() {
     }
 }
 
+#[test]
+fn test_verify_sparse_merkle_batch() {
+    // Same tree as in `test_verify_three_element_sparse_merkle`.
+    //            root
+    //           /    \
+    //          a      default
+    //         / \
+    //     key1   b
+    //           / \
+    //       key2   key3
+    let key1 = b"hello".test_only_hash();
+    let key2 = b"world".test_only_hash();
+    let key3 = b"!".test_only_hash();
+    let non_existing_key1 = b"abc".test_only_hash();
+    let non_existing_key2 = b"def".test_only_hash();
+
+    let blob1 = StateValue::from(b"1".to_vec());
+    let blob2 = StateValue::from(b"2".to_vec());
+    let blob3 = StateValue::from(b"3".to_vec());
+
+    let leaf1 = SparseMerkleLeafNode::new(key1, blob1.hash());
+    let leaf2 = SparseMerkleLeafNode::new(key2, blob2.hash());
+    let leaf3 = SparseMerkleLeafNode::new(key3, blob3.hash());
+    let internal_b_hash = SparseMerkleInternalNode::new(leaf2.hash(), leaf3.hash()).hash();
+    let internal_a_hash = SparseMerkleInternalNode::new(leaf1.hash(), internal_b_hash).hash();
+    let root_hash =
+        SparseMerkleInternalNode::new(internal_a_hash, *SPARSE_MERKLE_PLACEHOLDER_HASH).hash();
+
+    let proof1 = SparseMerkleProof::new(Some(leaf1), vec![
+        internal_b_hash,
+        *SPARSE_MERKLE_PLACEHOLDER_HASH,
+    ]);
+    let proof2 = SparseMerkleProof::new(Some(leaf2), vec![
+        leaf3.hash(),
+        leaf1.hash(),
+        *SPARSE_MERKLE_PLACEHOLDER_HASH,
+    ]);
+    let proof3 = SparseMerkleProof::new(Some(leaf3), vec![
+        leaf2.hash(),
+        leaf1.hash(),
+        *SPARSE_MERKLE_PLACEHOLDER_HASH,
+    ]);
+    let non_existing_proof2 = SparseMerkleProof::new(None, vec![internal_a_hash]);
+
+    {
+        // A proof of all the keys needs no sibling.
+        let proof = SparseMerkleBatchProof::new(&[
+            (key3, proof3.clone()),
+            (key1, proof1.clone()),
+            (key2, proof2.clone()),
+        ])
+        .unwrap();
+        assert!(proof
+            .verify(root_hash, &[
+                (key3, Some(&blob3)),
+                (key1, Some(&blob1)),
+                (key2, Some(&blob2)),
+            ])
+            .is_ok());
+        // The elements have to be in the same order as the proofs.
+        assert!(proof
+            .verify(root_hash, &[
+                (key1, Some(&blob1)),
+                (key2, Some(&blob2)),
+                (key3, Some(&blob3)),
+            ])
+            .is_err());
+        // Trying to show that a key has another value.
+        assert!(proof
+            .verify(root_hash, &[
+                (key3, Some(&blob3)),
+                (key1, Some(&blob2)),
+                (key2, Some(&blob2)),
+            ])
+            .is_err());
+        // Trying to show that a key doesn't exist.
+        assert!(proof
+            .verify(root_hash, &[
+                (key3, Some(&blob3)),
+                (key1, Some(&blob1)),
+                (key2, None),
+            ])
+            .is_err());
+        // The proof is for exactly three elements.
+        assert!(proof
+            .verify(root_hash, &[(key3, Some(&blob3)), (key1, Some(&blob1))])
+            .is_err());
+        assert!(proof
+            .verify(HashValue::zero(), &[
+                (key3, Some(&blob3)),
+                (key1, Some(&blob1)),
+                (key2, Some(&blob2)),
+            ])
+            .is_err());
+    }
+
+    {
+        // A proof mixing inclusion and non-inclusion, with elements ending at the same leaf.
+        let proof = SparseMerkleBatchProof::new(&[
+            (key2, proof2.clone()),
+            (non_existing_key2, non_existing_proof2.clone()),
+            (non_existing_key1, proof1.clone()),
+            (key1, proof1.clone()),
+        ])
+        .unwrap();
+        assert!(proof
+            .verify(root_hash, &[
+                (key2, Some(&blob2)),
+                (non_existing_key2, None),
+                (non_existing_key1, None),
+                (key1, Some(&blob1)),
+            ])
+            .is_ok());
+        // The sibling of key2 (i.e., leaf3) is included, so it can't be replaced by another one.
+        let tampered_proof2 = SparseMerkleProof::new(Some(leaf2), vec![
+            leaf1.hash(),
+            leaf1.hash(),
+            *SPARSE_MERKLE_PLACEHOLDER_HASH,
+        ]);
+        let tampered_proof = SparseMerkleBatchProof::new(&[
+            (key2, tampered_proof2),
+            (non_existing_key2, non_existing_proof2.clone()),
+        ])
+        .unwrap();
+        let elements = [(key2, Some(&blob2)), (non_existing_key2, None)];
+        assert!(tampered_proof.verify(root_hash, &elements).is_err());
+    }
+
+    {
+        // The proof of the default node can't be used to show a key starting with 0 doesn't exist.
+        assert!(SparseMerkleBatchProof::new(&[
+            (key1, proof1.clone()),
+            (non_existing_key1, non_existing_proof2),
+        ])
+        .is_err());
+        // Keys can't be proved twice.
+        assert!(SparseMerkleBatchProof::new(&[(key1, proof1.clone()), (key1, proof1)]).is_err());
+    }
+}
+
 #[test]
 fn test_verify_transaction() {
     //            root
@@ -1,9 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::state_store::state_key::StateKey;
 use move_core_types::{
     account_address::{AccountAddress, AccountAddressParseError},
-    language_storage::TypeTag,
+    language_storage::{StructTag, TypeTag},
 };
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -16,6 +17,12 @@ impl TableHandle {
     pub fn size(&self) -> usize {
         std::mem::size_of_val(&self.0)
     }
+
+    /// Returns the state key of the item at the given index, for tables keyed by
+    /// contiguous `u64` indices (e.g., the buckets of a `SmartTable` or `BigVector`).
+    pub fn indexed_item_state_key(&self, index: u64) -> StateKey {
+        StateKey::table_item(*self, bcs::to_bytes(&index).expect("u64 must serialize"))
+    }
 }
 
 impl FromStr for TableHandle {
@@ -39,3 +46,16 @@ pub struct TableInfo {
     pub key_type: TypeTag,
     pub value_type: TypeTag,
 }
+
+/// The value a table was held by when the indexer first saw it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum TableOwner {
+    /// The table is held, possibly nested in other structs or vectors, by a resource stored under
+    /// an account.
+    Resource {
+        address: AccountAddress,
+        struct_tag: StructTag,
+    },
+    /// The table is held by an item of another table.
+    TableItem { handle: TableHandle },
+}
@@ -260,6 +260,117 @@ impl TransactionAuthenticator {
             } => Some(fee_payer_signer.clone()),
         }
     }
+
+    /// Returns the signature status of each signer (i.e., the sender, the secondary signers and
+    /// the fee payer) of the given raw transaction. Unlike `verify`, the signatures of each signer
+    /// (and each key of a multisignature) are checked independently, so partially signed
+    /// transactions can report which signatures are still required.
+    pub fn signature_statuses(&self, raw_txn: &RawTransaction) -> Vec<SignerSignatureStatus> {
+        let sender_address = raw_txn.sender();
+        match self {
+            Self::Ed25519 { .. } | Self::MultiEd25519 { .. } => vec![self
+                .sender()
+                .signature_status(sender_address, SignerRole::Sender, raw_txn)],
+            Self::FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => {
+                let message = RawTransactionWithData::new_fee_payer(
+                    raw_txn.clone(),
+                    secondary_signer_addresses.clone(),
+                    *fee_payer_address,
+                );
+                let mut statuses = get_signature_statuses(
+                    sender_address,
+                    sender,
+                    secondary_signer_addresses,
+                    secondary_signers,
+                    &message,
+                );
+                statuses.push(fee_payer_signer.signature_status(
+                    *fee_payer_address,
+                    SignerRole::FeePayer,
+                    &message,
+                ));
+                statuses
+            },
+            Self::MultiAgent {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+            } => {
+                let message = RawTransactionWithData::new_multi_agent(
+                    raw_txn.clone(),
+                    secondary_signer_addresses.clone(),
+                );
+                get_signature_statuses(
+                    sender_address,
+                    sender,
+                    secondary_signer_addresses,
+                    secondary_signers,
+                    &message,
+                )
+            },
+        }
+    }
+}
+
+/// Returns the signature statuses of the sender and the secondary signers for the given message
+fn get_signature_statuses<T: Serialize + CryptoHash>(
+    sender_address: AccountAddress,
+    sender: &AccountAuthenticator,
+    secondary_signer_addresses: &[AccountAddress],
+    secondary_signers: &[AccountAuthenticator],
+    message: &T,
+) -> Vec<SignerSignatureStatus> {
+    let mut statuses = vec![sender.signature_status(sender_address, SignerRole::Sender, message)];
+    for (address, signer) in secondary_signer_addresses.iter().zip(secondary_signers) {
+        statuses.push(signer.signature_status(*address, SignerRole::SecondarySigner, message));
+    }
+    statuses
+}
+
+/// The role of a signer in a transaction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignerRole {
+    Sender,
+    SecondarySigner,
+    FeePayer,
+}
+
+/// The status of the signature(s) of a single signer of a transaction. For a k-of-n
+/// multisignature, each of the n keys may or may not have signed, and k valid
+/// signatures are required. For a single signature, n and k are both 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerSignatureStatus {
+    pub address: AccountAddress,
+    pub role: SignerRole,
+    pub num_keys: usize,                // The number of keys (n) of the signer
+    pub num_signatures_required: usize, // The number of signatures (k) required
+    pub valid_key_indices: Vec<u8>,     // The indices of the keys with a valid signature
+}
+
+impl SignerSignatureStatus {
+    /// Returns true iff the signer has enough valid signatures
+    pub fn is_fully_signed(&self) -> bool {
+        self.num_missing_signatures() == 0
+    }
+
+    /// Returns the number of valid signatures still required from the signer
+    pub fn num_missing_signatures(&self) -> usize {
+        self.num_signatures_required
+            .saturating_sub(self.valid_key_indices.len())
+    }
+
+    /// Returns the indices of the keys without a valid signature
+    pub fn missing_key_indices(&self) -> Vec<u8> {
+        (0..self.num_keys as u8)
+            .filter(|index| !self.valid_key_indices.contains(index))
+            .collect()
+    }
 }
 
 impl fmt::Display for TransactionAuthenticator {
@@ -469,6 +580,59 @@ impl AccountAuthenticator {
             Self::MultiEd25519 { signature, .. } => signature.signatures().len(),
         }
     }
+
+    /// Returns the signature status of the signer with the given address and role. Unlike
+    /// `verify`, the signature of each key is checked independently.
+    pub fn signature_status<T: Serialize + CryptoHash>(
+        &self,
+        address: AccountAddress,
+        role: SignerRole,
+        message: &T,
+    ) -> SignerSignatureStatus {
+        let (num_keys, num_signatures_required, valid_key_indices) = match self {
+            Self::Ed25519 {
+                public_key,
+                signature,
+            } => {
+                let valid_key_indices = if signature.verify(message, public_key).is_ok() {
+                    vec![0]
+                } else {
+                    vec![]
+                };
+                (1, 1, valid_key_indices)
+            },
+            Self::MultiEd25519 {
+                public_key,
+                signature,
+            } => {
+                let public_keys = public_key.public_keys();
+                let valid_key_indices = signature
+                    .signer_indices()
+                    .into_iter()
+                    .zip(signature.signatures())
+                    .filter(|(index, signature)| {
+                        public_keys
+                            .get(*index as usize)
+                            .map_or(false, |key| signature.verify(message, key).is_ok())
+                    })
+                    .map(|(index, _)| index)
+                    .collect();
+                (
+                    public_keys.len(),
+                    *public_key.threshold() as usize,
+                    valid_key_indices,
+                )
+            },
+        };
+
+        SignerSignatureStatus {
+            address,
+            role,
+            num_keys,
+            num_signatures_required,
+            valid_key_indices,
+        }
+    }
 }
 
 /// A struct that represents an account authentication key. An account's address is the last 32
@@ -12,7 +12,9 @@ use crate::{
         accumulator::InMemoryAccumulator, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_store::ShardedStateUpdates,
-    transaction::authenticator::{AccountAuthenticator, TransactionAuthenticator},
+    transaction::authenticator::{
+        AccountAuthenticator, SignerSignatureStatus, TransactionAuthenticator,
+    },
     vm_status::{DiscardedVMStatus, KeptVMStatus, StatusCode, StatusType, VMStatus},
     write_set::WriteSet,
 };
@@ -42,6 +44,7 @@ mod module;
 mod multisig;
 mod script;
 mod transaction_argument;
+mod verified_signature_cache;
 
 pub use change_set::ChangeSet;
 pub use module::{Module, ModuleBundle};
@@ -54,6 +57,7 @@ pub use script::{
 };
 use std::{collections::BTreeSet, hash::Hash, ops::Deref, sync::atomic::AtomicU64};
 pub use transaction_argument::{parse_transaction_argument, TransactionArgument};
+pub use verified_signature_cache::VerifiedSignatureCache;
 
 pub type Version = u64; // Height - also used for MVCC in StateDB
 pub type AtomicVersion = AtomicU64;
@@ -536,6 +540,13 @@ impl PartialEq for SignedTransaction {
 pub struct SignatureCheckedTransaction(SignedTransaction);
 
 impl SignatureCheckedTransaction {
+    /// Wraps a `SignedTransaction` whose signature is already known to be valid (e.g., it
+    /// was verified before and recorded by its committed hash). No signature check is done,
+    /// so this must only be used for transactions that have already been verified.
+    pub(crate) fn new_already_verified(txn: SignedTransaction) -> Self {
+        Self(txn)
+    }
+
     /// Returns the `SignedTransaction` within.
     pub fn into_inner(self) -> SignedTransaction {
         self.0
@@ -710,6 +721,12 @@ impl SignedTransaction {
         self.authenticator.verify(&self.raw_txn).is_ok()
     }
 
+    /// Returns the signature status of each signer of the transaction, i.e.,
+    /// which signatures are still required for the transaction to be valid.
+    pub fn signature_statuses(&self) -> Vec<SignerSignatureStatus> {
+        self.authenticator.signature_statuses(&self.raw_txn)
+    }
+
     pub fn contains_duplicate_signers(&self) -> bool {
         let mut all_signer_addresses = self.authenticator.secondary_signer_addreses();
         all_signer_addresses.push(self.sender());
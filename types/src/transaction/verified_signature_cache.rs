@@ -0,0 +1,161 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transaction::{SignatureCheckedTransaction, SignedTransaction, Transaction};
+use anyhow::Result;
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_infallible::Mutex;
+use lru::LruCache;
+
+/// The number of shards of the cache. Transactions are spread over the shards by their
+/// committed hash, so parallel signature checks rarely contend on the same lock.
+const NUM_SHARDS: usize = 32;
+
+/// A cache of the (committed) hashes of transactions whose signatures have already been
+/// verified. Mempool verifies the signatures of transactions when they are submitted, so
+/// the signature verification pre-pass of block execution can skip verifying them again.
+/// The committed hash covers the entire signed transaction (including the authenticator),
+/// so a cached hash can only match a transaction with exactly the same signatures.
+pub struct VerifiedSignatureCache {
+    shards: Vec<Mutex<LruCache<HashValue, ()>>>,
+}
+
+impl VerifiedSignatureCache {
+    pub fn new(max_verified_transactions: usize) -> Self {
+        let shard_capacity = (max_verified_transactions / NUM_SHARDS).max(1);
+        Self {
+            shards: (0..NUM_SHARDS)
+                .map(|_| Mutex::new(LruCache::new(shard_capacity)))
+                .collect(),
+        }
+    }
+
+    /// Checks the signature of the given transaction, unless the transaction has already
+    /// been verified. Transactions with valid signatures are added to the cache. Also
+    /// returns whether the transaction was found in the cache.
+    pub fn check_signature(
+        &self,
+        txn: SignedTransaction,
+    ) -> (Result<SignatureCheckedTransaction>, bool) {
+        let (txn, committed_hash) = committed_hash(txn);
+        let shard = self.shard(&committed_hash);
+        if shard.lock().get(&committed_hash).is_some() {
+            return (
+                Ok(SignatureCheckedTransaction::new_already_verified(txn)),
+                true,
+            );
+        }
+
+        let result = txn.check_signature();
+        if result.is_ok() {
+            shard.lock().put(committed_hash, ());
+        }
+        (result, false)
+    }
+
+    fn shard(&self, committed_hash: &HashValue) -> &Mutex<LruCache<HashValue, ()>> {
+        &self.shards[committed_hash[0] as usize % NUM_SHARDS]
+    }
+
+    /// Returns true iff the transaction with the given committed hash has been verified
+    #[cfg(test)]
+    fn contains(&self, committed_hash: &HashValue) -> bool {
+        self.shard(committed_hash).lock().contains(committed_hash)
+    }
+}
+
+/// Returns the committed hash of the transaction, without cloning it
+fn committed_hash(txn: SignedTransaction) -> (SignedTransaction, HashValue) {
+    let txn = Transaction::UserTransaction(txn);
+    let committed_hash = txn.hash();
+    match txn {
+        Transaction::UserTransaction(txn) => (txn, committed_hash),
+        _ => unreachable!("The transaction is a user transaction"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{RawTransaction, Script},
+    };
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+
+    fn create_signed_transaction(sequence_number: u64) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        RawTransaction::new_script(
+            AccountAddress::random(),
+            sequence_number,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        )
+        .sign(&private_key, private_key.public_key())
+        .unwrap()
+        .into_inner()
+    }
+
+    #[test]
+    fn test_verified_transactions_are_cached() {
+        let cache = VerifiedSignatureCache::new(10);
+        let txn = create_signed_transaction(0);
+        let committed_hash = txn.clone().committed_hash();
+        assert!(!cache.contains(&committed_hash));
+
+        // Verify the transaction and check that it's cached
+        let (result, cache_hit) = cache.check_signature(txn.clone());
+        assert_eq!(result.unwrap().into_inner(), txn);
+        assert!(!cache_hit);
+        assert!(cache.contains(&committed_hash));
+
+        // Verify the transaction again (this time from the cache)
+        let (result, cache_hit) = cache.check_signature(txn.clone());
+        assert_eq!(result.unwrap().into_inner(), txn);
+        assert!(cache_hit);
+    }
+
+    #[test]
+    fn test_invalid_transactions_are_not_cached() {
+        let cache = VerifiedSignatureCache::new(10);
+
+        // Replace the signature of a valid transaction with the signature of another
+        let txn = create_signed_transaction(0);
+        let other_txn = create_signed_transaction(1);
+        let invalid_txn = SignedTransaction::new_with_authenticator(
+            txn.clone().into_raw_transaction(),
+            other_txn.authenticator(),
+        );
+
+        // Verify the valid transaction, and check that the invalid one still fails
+        cache.check_signature(txn).0.unwrap();
+        assert!(cache.check_signature(invalid_txn.clone()).0.is_err());
+        assert!(!cache.contains(&invalid_txn.committed_hash()));
+    }
+
+    #[test]
+    fn test_cache_eviction() {
+        // Each shard holds a single transaction
+        let cache = VerifiedSignatureCache::new(NUM_SHARDS);
+        let txns: Vec<_> = (0..(2 * NUM_SHARDS as u64))
+            .map(create_signed_transaction)
+            .collect();
+        for txn in &txns {
+            cache.check_signature(txn.clone()).0.unwrap();
+        }
+
+        // Only the most recently verified transaction of each shard is kept
+        let shard_of =
+            |txn: &SignedTransaction| txn.clone().committed_hash()[0] as usize % NUM_SHARDS;
+        for (i, txn) in txns.iter().enumerate() {
+            let evicted = txns[i + 1..]
+                .iter()
+                .any(|later_txn| shard_of(later_txn) == shard_of(txn));
+            assert_eq!(cache.contains(&txn.clone().committed_hash()), !evicted);
+        }
+    }
+}
@@ -6,16 +6,20 @@ use crate::{
     account_address::AccountAddress,
     chain_id::ChainId,
     transaction::{
-        AccountTransactionsWithProof, RawTransaction, Script, SignedTransaction, Transaction,
-        TransactionInfo, TransactionListWithProof, TransactionPayload, TransactionWithProof,
+        authenticator::{AccountAuthenticator, SignerRole},
+        AccountTransactionsWithProof, RawTransaction, RawTransactionWithData, Script,
+        SignedTransaction, Transaction, TransactionInfo, TransactionListWithProof,
+        TransactionPayload, TransactionWithProof,
     },
 };
 use aptos_crypto::{
     ed25519::{self, Ed25519PrivateKey, Ed25519Signature},
-    PrivateKey, Uniform,
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    PrivateKey, SigningKey, Uniform,
 };
 use bcs::test_helpers::assert_canonical_encode_decode;
 use proptest::prelude::*;
+use rand::rngs::OsRng;
 use std::convert::TryFrom;
 
 #[test]
@@ -36,6 +40,107 @@ fn test_invalid_signature() {
     assert!(!txn.signature_is_valid(), "Signature checking should fail")
 }
 
+fn create_raw_transaction(sender: AccountAddress) -> RawTransaction {
+    RawTransaction::new_script(
+        sender,
+        0,
+        Script::new(vec![], vec![], vec![]),
+        0,
+        0,
+        0,
+        ChainId::test(),
+    )
+}
+
+#[test]
+fn test_signature_statuses_multi_ed25519() {
+    // Create a 2-of-3 multisignature key
+    let private_keys: Vec<_> = (0..3)
+        .map(|_| Ed25519PrivateKey::generate(&mut OsRng))
+        .collect();
+    let public_key =
+        MultiEd25519PublicKey::new(private_keys.iter().map(|key| key.public_key()).collect(), 2)
+            .unwrap();
+    let raw_txn = create_raw_transaction(AccountAddress::random());
+
+    // Only the last key signs the transaction
+    let signature = private_keys[2].sign(&raw_txn).unwrap();
+    let signature = MultiEd25519Signature::new(vec![(signature, 2)]).unwrap();
+    let txn = SignedTransaction::new_multisig(raw_txn.clone(), public_key.clone(), signature);
+    assert!(!txn.signature_is_valid());
+
+    // Verify that one more signature is required (from the first or second key)
+    let statuses = txn.signature_statuses();
+    assert_eq!(statuses.len(), 1);
+    let status = &statuses[0];
+    assert_eq!(status.address, raw_txn.sender());
+    assert_eq!(status.role, SignerRole::Sender);
+    assert_eq!(status.valid_key_indices, vec![2]);
+    assert_eq!(status.missing_key_indices(), vec![0, 1]);
+    assert_eq!(status.num_missing_signatures(), 1);
+    assert!(!status.is_fully_signed());
+
+    // Add an invalid signature for the first key and a valid one for the second key
+    let signature = MultiEd25519Signature::new(vec![
+        (Ed25519Signature::try_from(&[1u8; 64][..]).unwrap(), 0),
+        (private_keys[1].sign(&raw_txn).unwrap(), 1),
+        (private_keys[2].sign(&raw_txn).unwrap(), 2),
+    ])
+    .unwrap();
+    let txn = SignedTransaction::new_multisig(raw_txn, public_key, signature);
+
+    // Verify that the signer is fully signed (even though the first signature is invalid)
+    let status = &txn.signature_statuses()[0];
+    assert_eq!(status.valid_key_indices, vec![1, 2]);
+    assert_eq!(status.missing_key_indices(), vec![0]);
+    assert!(status.is_fully_signed());
+}
+
+#[test]
+fn test_signature_statuses_multi_agent() {
+    let sender_key = Ed25519PrivateKey::generate(&mut OsRng);
+    let secondary_keys: Vec<_> = (0..2)
+        .map(|_| Ed25519PrivateKey::generate(&mut OsRng))
+        .collect();
+    let secondary_signers = vec![AccountAddress::random(), AccountAddress::random()];
+    let raw_txn = create_raw_transaction(AccountAddress::random());
+
+    // Only the sender and the second secondary signer sign the transaction
+    let message =
+        RawTransactionWithData::new_multi_agent(raw_txn.clone(), secondary_signers.clone());
+    let sender =
+        AccountAuthenticator::ed25519(sender_key.public_key(), sender_key.sign(&message).unwrap());
+    let secondary_authenticators = vec![
+        AccountAuthenticator::ed25519(
+            secondary_keys[0].public_key(),
+            Ed25519Signature::try_from(&[1u8; 64][..]).unwrap(),
+        ),
+        AccountAuthenticator::ed25519(
+            secondary_keys[1].public_key(),
+            secondary_keys[1].sign(&message).unwrap(),
+        ),
+    ];
+    let txn = SignedTransaction::new_multi_agent(
+        raw_txn,
+        sender,
+        secondary_signers.clone(),
+        secondary_authenticators,
+    );
+    assert!(!txn.signature_is_valid());
+
+    // Verify that only the first secondary signer is missing a signature
+    let statuses = txn.signature_statuses();
+    assert_eq!(statuses.len(), 3);
+    assert_eq!(statuses[0].role, SignerRole::Sender);
+    assert!(statuses[0].is_fully_signed());
+    assert_eq!(statuses[1].address, secondary_signers[0]);
+    assert_eq!(statuses[1].role, SignerRole::SecondarySigner);
+    assert_eq!(statuses[1].missing_key_indices(), vec![0]);
+    assert!(!statuses[1].is_fully_signed());
+    assert_eq!(statuses[2].address, secondary_signers[1]);
+    assert!(statuses[2].is_fully_signed());
+}
+
 proptest! {
     #[test]
     fn test_sign_raw_transaction(raw_txn in any::<RawTransaction>(), keypair in ed25519::keypair_strategy()) {
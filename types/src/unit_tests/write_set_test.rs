@@ -2,7 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::write_set::WriteSet;
+use crate::write_set::{DedupedWriteSets, WriteSet};
 use bcs::test_helpers::assert_canonical_encode_decode;
 use proptest::prelude::*;
 
@@ -12,3 +12,30 @@ proptest! {
         assert_canonical_encode_decode(write_set);
     }
 }
+
+proptest! {
+    #[test]
+    fn deduped_write_sets_roundtrip(
+        write_sets in proptest::collection::vec(any::<WriteSet>(), 0..10),
+        repeats in 1usize..4,
+    ) {
+        let write_sets: Vec<_> = write_sets
+            .into_iter()
+            .flat_map(|write_set| std::iter::repeat(write_set).take(repeats))
+            .collect();
+        let deduped = DedupedWriteSets::new(&write_sets);
+        prop_assert_eq!(deduped.len(), write_sets.len());
+        let num_write_ops: usize = write_sets.iter().map(|write_set| write_set.iter().count()).sum();
+        prop_assert!(deduped.write_ops.len() * repeats <= num_write_ops);
+        prop_assert_eq!(deduped.into_write_sets().unwrap(), write_sets);
+    }
+}
+
+#[test]
+fn deduped_write_sets_out_of_bounds_index() {
+    let deduped = DedupedWriteSets {
+        write_ops: vec![],
+        write_op_indices: vec![vec![0]],
+    };
+    assert!(deduped.into_write_sets().is_err());
+}
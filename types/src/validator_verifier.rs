@@ -230,6 +230,52 @@ impl ValidatorVerifier {
         message: &T,
         multi_signature: &AggregateSignature,
     ) -> std::result::Result<(), VerifyError> {
+        if let Some((multi_sig, aggregated_key)) = self.prepare_multi_signature(multi_signature)? {
+            multi_sig
+                .verify(message, &aggregated_key)
+                .map_err(|_| VerifyError::InvalidMultiSignature)?;
+        }
+        Ok(())
+    }
+
+    /// Verifies many multi-signatures at once, each on its message under its own validator
+    /// verifier (e.g., the verifiers of consecutive epochs). This is much cheaper than calling
+    /// `verify_multi_signatures` on each of them (see `bls12381::Signature::batch_verify`).
+    ///
+    /// The multi-signatures are verified one by one only if the batch fails, to return the
+    /// error of the first invalid one.
+    pub fn batch_verify_multi_signatures<T: CryptoHash + Serialize>(
+        items: &[(&ValidatorVerifier, &T, &AggregateSignature)],
+    ) -> std::result::Result<(), VerifyError> {
+        let mut messages = vec![];
+        let mut multi_sigs = vec![];
+        let mut aggregated_keys = vec![];
+        for (verifier, message, multi_signature) in items {
+            if let Some((multi_sig, aggregated_key)) =
+                verifier.prepare_multi_signature(multi_signature)?
+            {
+                messages.push(*message);
+                multi_sigs.push(multi_sig);
+                aggregated_keys.push(aggregated_key);
+            }
+        }
+        let aggregated_keys_refs = aggregated_keys.iter().collect::<Vec<_>>();
+        if bls12381::Signature::batch_verify(&messages, &aggregated_keys_refs, &multi_sigs).is_err()
+        {
+            for (verifier, message, multi_signature) in items {
+                verifier.verify_multi_signatures(*message, multi_signature)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the signers and the voting power of a multi-signature, and returns the signature
+    /// along with the aggregated public key of the signers to verify it with (or None if there's
+    /// nothing to verify, in tests).
+    fn prepare_multi_signature<'a>(
+        &self,
+        multi_signature: &'a AggregateSignature,
+    ) -> std::result::Result<Option<(&'a bls12381::Signature, PublicKey)>, VerifyError> {
         // Verify the number of signature is not greater than expected.
         Self::check_num_of_voters(self.len() as u16, multi_signature.get_signers_bitvec())?;
         let mut pub_keys = vec![];
@@ -250,7 +296,7 @@ impl ValidatorVerifier {
                 // This should happen only in case of tests.
                 // TODO(skedia): Clean up the test behaviors to not rely on empty signature
                 // verification
-                return Ok(());
+                return Ok(None);
             }
         }
         // Verify empty multi signature
@@ -258,14 +304,10 @@ impl ValidatorVerifier {
             .sig()
             .as_ref()
             .ok_or(VerifyError::EmptySignature)?;
-        // Verify the optimistically aggregated signature.
+        // The signature is verified optimistically against the aggregated key.
         let aggregated_key =
             PublicKey::aggregate(pub_keys).map_err(|_| VerifyError::FailedToAggregatePubKey)?;
-
-        multi_sig
-            .verify(message, &aggregated_key)
-            .map_err(|_| VerifyError::InvalidMultiSignature)?;
-        Ok(())
+        Ok(Some((multi_sig, aggregated_key)))
     }
 
     pub fn verify_aggregate_signatures<T: CryptoHash + Serialize>(
@@ -639,6 +681,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_batch_verify_multi_signatures() {
+        let validator_signers: Vec<_> = (0..3).map(|i| ValidatorSigner::random([i; 32])).collect();
+        let validators: Vec<_> = validator_signers
+            .iter()
+            .map(|signer| ValidatorVerifier::new_single(signer.author(), signer.public_key()))
+            .collect();
+        let messages: Vec<_> = (0..3)
+            .map(|i| TestAptosCrypto(format!("Hello, World {}", i)))
+            .collect();
+        let multi_sigs: Vec<_> = (0..3)
+            .map(|i| {
+                let mut partial_sig = PartialSignatures::empty();
+                partial_sig.add_signature(
+                    validator_signers[i].author(),
+                    validator_signers[i].sign(&messages[i]).unwrap(),
+                );
+                validators[i].aggregate_signatures(&partial_sig).unwrap()
+            })
+            .collect();
+
+        let items: Vec<_> = (0..3)
+            .map(|i| (&validators[i], &messages[i], &multi_sigs[i]))
+            .collect();
+        assert_eq!(
+            ValidatorVerifier::batch_verify_multi_signatures(&items),
+            Ok(())
+        );
+
+        // A multi-signature on the wrong message fails the batch
+        let mut items_wrong = items.clone();
+        items_wrong[1].1 = &messages[2];
+        assert_eq!(
+            ValidatorVerifier::batch_verify_multi_signatures(&items_wrong),
+            Err(VerifyError::InvalidMultiSignature)
+        );
+
+        // A multi-signature checked under the wrong verifier fails the batch
+        let mut items_wrong = items.clone();
+        items_wrong[2].0 = &validators[0];
+        assert_eq!(
+            ValidatorVerifier::batch_verify_multi_signatures(&items_wrong),
+            Err(VerifyError::InvalidMultiSignature)
+        );
+    }
+
     #[test]
     fn test_verify_empty_signature() {
         let validator_signer = ValidatorSigner::random(TEST_SEED);
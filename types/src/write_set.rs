@@ -13,7 +13,7 @@ use anyhow::{bail, Result};
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{btree_map, BTreeMap},
+    collections::{btree_map, BTreeMap, HashMap},
     ops::Deref,
 };
 
@@ -378,3 +378,70 @@ impl ::std::iter::IntoIterator for WriteSet {
         }
     }
 }
+
+/// A compact encoding of a list of write sets, where each distinct write op is stored once in a
+/// dictionary and each write set is a list of indices into it. This significantly reduces the size
+/// of batch-heavy workloads (e.g., airdrops), where many transactions write the same ops.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DedupedWriteSets {
+    /// The distinct write ops, in order of first appearance
+    pub write_ops: Vec<(StateKey, WriteOp)>,
+    /// For each write set, the indices of its write ops in `write_ops`
+    pub write_op_indices: Vec<Vec<u32>>,
+}
+
+impl DedupedWriteSets {
+    pub fn new<'a>(write_sets: impl IntoIterator<Item = &'a WriteSet>) -> Self {
+        let mut dictionary: HashMap<(&StateKey, &WriteOp), u32> = HashMap::new();
+        let mut write_ops = vec![];
+        let write_op_indices = write_sets
+            .into_iter()
+            .map(|write_set| {
+                write_set
+                    .iter()
+                    .map(|(key, op)| {
+                        *dictionary.entry((key, op)).or_insert_with(|| {
+                            write_ops.push((key.clone(), op.clone()));
+                            (write_ops.len() - 1) as u32
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            write_ops,
+            write_op_indices,
+        }
+    }
+
+    /// Returns the number of write sets
+    pub fn len(&self) -> usize {
+        self.write_op_indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.write_op_indices.is_empty()
+    }
+
+    /// Expands the write sets, failing if an index is out of the bounds of the dictionary.
+    pub fn into_write_sets(self) -> Result<Vec<WriteSet>> {
+        let write_ops = self.write_ops;
+        self.write_op_indices
+            .into_iter()
+            .map(|indices| {
+                indices
+                    .into_iter()
+                    .map(|index| match write_ops.get(index as usize) {
+                        Some(write_op) => Ok(write_op.clone()),
+                        None => bail!(
+                            "Write op index {} is out of bounds, the dictionary has {} write ops.",
+                            index,
+                            write_ops.len()
+                        ),
+                    })
+                    .collect::<Result<WriteSetMut>>()?
+                    .freeze()
+            })
+            .collect()
+    }
+}